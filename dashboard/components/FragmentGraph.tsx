@@ -82,14 +82,27 @@ const actorMarginY = nodeRadius
 const actorDistanceX = nodeRadius * 5
 const actorDistanceY = nodeRadius * 5
 
+/** Color of an edge by the back pressure ratio of its upstream, from green to red. */
+const backPressureColor = d3
+  .scaleLinear<string>()
+  .domain([0, 0.5, 1])
+  .range([
+    theme.colors.green[500],
+    theme.colors.yellow[500],
+    theme.colors.red[500],
+  ])
+  .clamp(true)
+
 export default function FragmentGraph({
   planNodeDependencies,
   fragmentDependency,
   selectedFragmentId,
+  backPressures,
 }: {
   planNodeDependencies: Map<string, d3.HierarchyNode<any>>
   fragmentDependency: ActorBox[]
   selectedFragmentId: string | undefined
+  backPressures?: Map<number, number>
 }) {
   const svgRef = useRef<any>()
 
@@ -199,6 +212,12 @@ export default function FragmentGraph({
 
       const isSelected = (d: any) => d === selectedFragmentId
 
+      // An edge points from a fragment (`source`) to its upstream fragment
+      // (`target`). The back pressure of an edge is that of the upstream, which
+      // is blocked on sending to the downstream.
+      const edgeBackPressure = (d: any) =>
+        backPressures?.get(parseInt(d.target))
+
       const applyActor = (
         gSel: d3.Selection<SVGGElement, PlanNodeDesc, SVGGElement, undefined>
       ) => {
@@ -373,18 +392,38 @@ export default function FragmentGraph({
         .x(({ x }) => x)
         .y(({ y }) => y)
 
-      const applyEdge = (sel: any) =>
+      const applyEdge = (sel: any) => {
         sel
           .attr("d", ({ points }: any) => line(points))
           .attr("fill", "none")
-          .attr("stroke-width", (d: any) =>
-            isSelected(d.source) || isSelected(d.target) ? 2 : 1
-          )
-          .attr("stroke", (d: any) =>
-            isSelected(d.source) || isSelected(d.target)
-              ? theme.colors.teal["500"]
-              : theme.colors.gray["300"]
-          )
+          .attr("stroke-width", (d: any) => {
+            if (isSelected(d.source) || isSelected(d.target)) {
+              return 2
+            }
+            return edgeBackPressure(d) === undefined ? 1 : 3
+          })
+          .attr("stroke", (d: any) => {
+            if (isSelected(d.source) || isSelected(d.target)) {
+              return theme.colors.teal["500"]
+            }
+            const ratio = edgeBackPressure(d)
+            return ratio === undefined
+              ? theme.colors.gray["300"]
+              : backPressureColor(ratio)
+          })
+
+        let title = sel.select("title")
+        if (title.empty()) {
+          title = sel.append("title")
+        }
+        title.text((d: any) => {
+          const ratio = edgeBackPressure(d)
+          return ratio === undefined
+            ? ""
+            : `Back pressure: ${(ratio * 100).toFixed(2)}%`
+        })
+        return sel
+      }
       const createEdge = (sel: any) =>
         sel.append("path").attr("class", "actor-link").call(applyEdge)
 
@@ -392,7 +431,13 @@ export default function FragmentGraph({
       edgeSelection.call(applyEdge)
       edgeSelection.exit().remove()
     }
-  }, [planNodeDependencyDag, links, selectedFragmentId, openPlanNodeDetail])
+  }, [
+    planNodeDependencyDag,
+    links,
+    selectedFragmentId,
+    openPlanNodeDetail,
+    backPressures,
+  ])
 
   return (
     <Fragment>
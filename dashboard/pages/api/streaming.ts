@@ -31,6 +31,25 @@ export async function getFragments(): Promise<TableFragments[]> {
   return fragmentList
 }
 
+export interface FragmentMetrics {
+  /** Fragment id -> ratio of time the fragment is blocked by its downstream. */
+  backPressures: Map<number, number>
+  /** P99 barrier latency of the cluster in seconds, if available. */
+  barrierLatencyP99?: number
+}
+
+export async function getFragmentMetrics(): Promise<FragmentMetrics> {
+  const res = await api.get("/api/metrics/fragments")
+  const backPressures = new Map<number, number>()
+  for (const [fragmentId, ratio] of Object.entries(res.back_pressures ?? {})) {
+    backPressures.set(parseInt(fragmentId), ratio as number)
+  }
+  return {
+    backPressures,
+    barrierLatencyP99: res.barrier_latency_p99 ?? undefined,
+  }
+}
+
 export async function getMaterializedViews(): Promise<Table[]> {
   let mvList: Table[] = (await api.get("/api/materialized_views")).map(
     Table.fromJSON
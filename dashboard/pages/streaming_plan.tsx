@@ -40,7 +40,11 @@ import Title from "../components/Title"
 import { ActorBox } from "../lib/layout"
 import { TableFragments, TableFragments_Fragment } from "../proto/gen/meta"
 import { StreamNode } from "../proto/gen/stream_plan"
-import { getFragments, getMaterializedViews } from "./api/streaming"
+import {
+  getFragmentMetrics,
+  getFragments,
+  getMaterializedViews,
+} from "./api/streaming"
 
 function buildPlanNodeDependency(
   fragment: TableFragments_Fragment
@@ -130,6 +134,7 @@ function useFetch<T>(fetchFn: () => Promise<T>) {
 export default function Streaming() {
   const { response: mvList } = useFetch(getMaterializedViews)
   const { response: fragmentList } = useFetch(getFragments)
+  const { response: fragmentMetrics } = useFetch(getFragmentMetrics)
 
   const [selectedFragmentId, setSelectedFragmentId] = useState<number>()
   const router = useRouter()
@@ -350,11 +355,18 @@ export default function Streaming() {
           overflowY="scroll"
         >
           <Text fontWeight="semibold">Fragment Graph</Text>
+          {fragmentMetrics?.barrierLatencyP99 !== undefined && (
+            <Text>
+              Barrier Latency (p99):{" "}
+              {fragmentMetrics.barrierLatencyP99.toFixed(3)}s
+            </Text>
+          )}
           {planNodeDependencies && fragmentDependency && (
             <FragmentGraph
               selectedFragmentId={selectedFragmentId?.toString()}
               fragmentDependency={fragmentDependency}
               planNodeDependencies={planNodeDependencies}
+              backPressures={fragmentMetrics?.backPressures}
             />
           )}
         </Box>
@@ -1255,6 +1255,44 @@ def section_streaming_actors(outer_panels):
                                       "{{actor_id}}"),
                     ],
                 ),
+                panels.timeseries_count(
+                    "Actor In-Flight Barrier Number",
+                    "The number of barriers issued to the compute node but not yet collected from the actor",
+                    [
+                        panels.target("stream_actor_in_flight_barrier_nums > 0",
+                                      "{{actor_id}}"),
+                    ],
+                ),
+                panels.timeseries_actor_latency(
+                    "Fragment Barrier Queued Latency",
+                    "The max time of the last barrier waiting for the upstreams of the actors in the fragment",
+                    [
+                        panels.target(
+                            "meta_fragment_barrier_queued_duration_seconds",
+                            "{{fragment_id}}",
+                        ),
+                    ],
+                ),
+                panels.timeseries_actor_latency(
+                    "Fragment Barrier Processing Latency",
+                    "The max time of the last barrier being processed by the actors in the fragment",
+                    [
+                        panels.target(
+                            "meta_fragment_barrier_processing_duration_seconds",
+                            "{{fragment_id}}",
+                        ),
+                    ],
+                ),
+                panels.timeseries_actor_latency(
+                    "Slow Actor Barrier Processing Latency",
+                    "The time of the last barrier being processed by the slowest actors, only reported if the barrier is slow to be collected",
+                    [
+                        panels.target(
+                            "meta_slow_actor_barrier_processing_duration_seconds",
+                            "{{actor_id}} @ {{worker_id}}",
+                        ),
+                    ],
+                ),
             ],
         )
     ]
@@ -38,6 +38,9 @@ pub enum BatchError {
     #[error("Failed to send result to channel")]
     SenderError,
 
+    #[error("Recursive query does not reach a fixed point after {0} iterations, consider increasing `rw_batch_max_recursion_depth`")]
+    MaxRecursionDepthExceeded(u32),
+
     #[error(transparent)]
     Internal(#[from] anyhow::Error),
 
@@ -25,6 +25,7 @@ use risingwave_rpc_client::ComputeClient;
 use tonic::Streaming;
 
 use crate::exchange_source::ExchangeSource;
+use crate::executor::ExecutorStatsCollector;
 use crate::task::TaskId;
 
 /// Use grpc client as the source.
@@ -32,6 +33,9 @@ pub struct GrpcExchangeSource {
     stream: Streaming<GetDataResponse>,
 
     task_output_id: TaskOutputId,
+
+    /// Collects the executor stats reported by the remote task, if any.
+    executor_stats: Option<ExecutorStatsCollector>,
 }
 
 impl GrpcExchangeSource {
@@ -39,6 +43,7 @@ impl GrpcExchangeSource {
         client: ComputeClient,
         task_output_id: TaskOutputId,
         local_execute_plan: Option<LocalExecutePlan>,
+        executor_stats: Option<ExecutorStatsCollector>,
     ) -> Result<Self> {
         let task_id = task_output_id.get_task_id()?.clone();
         let stream = match local_execute_plan {
@@ -50,6 +55,7 @@ impl GrpcExchangeSource {
                     task_id: Some(task_id),
                     plan: plan.plan,
                     epoch: plan.epoch,
+                    collect_executor_stats: plan.collect_executor_stats,
                 };
                 client.execute(execute_request).await?
             }
@@ -58,6 +64,7 @@ impl GrpcExchangeSource {
         let source = Self {
             stream,
             task_output_id,
+            executor_stats,
         };
         Ok(source)
     }
@@ -76,11 +83,20 @@ impl ExchangeSource for GrpcExchangeSource {
 
     fn take_data(&mut self) -> Self::TakeDataFuture<'_> {
         async {
-            let res = match self.stream.next().await {
-                None => return Ok(None),
-                Some(r) => r,
+            let task_data = loop {
+                let res = match self.stream.next().await {
+                    None => return Ok(None),
+                    Some(r) => r,
+                };
+                let task_data = res?;
+                if task_data.record_batch.is_some() {
+                    break task_data;
+                }
+                // The response without data carries the executor stats of the remote task.
+                if let Some(collector) = &self.executor_stats {
+                    collector.merge_from_protobuf(&task_data.executor_stats);
+                }
             };
-            let task_data = res?;
             let data = DataChunk::from_protobuf(task_data.get_record_batch()?)?.compact();
             trace!(
                 "Receiver taskOutput = {:?}, data = {:?}",
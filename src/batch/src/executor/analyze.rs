@@ -0,0 +1,107 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Instant;
+
+use futures::stream::StreamExt;
+use futures_async_stream::try_stream;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::Schema;
+use risingwave_common::collection::estimate_size::EstimateSize;
+use risingwave_common::error::RwError;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, Executor, ExecutorStats, ExecutorStatsCollector,
+};
+
+/// If executor stats are collected, e.g. for `EXPLAIN ANALYZE`, we build an [`AnalyzeExecutor`] on
+/// top of the underlying executor to record its output rows and elapsed time.
+pub struct AnalyzeExecutor {
+    child: BoxedExecutor,
+    /// Identity of the plan node, which is the key of the stats.
+    plan_node_identity: String,
+    collector: ExecutorStatsCollector,
+}
+
+impl AnalyzeExecutor {
+    pub fn new(
+        child: BoxedExecutor,
+        plan_node_identity: String,
+        collector: ExecutorStatsCollector,
+    ) -> Self {
+        Self {
+            child,
+            plan_node_identity,
+            collector,
+        }
+    }
+}
+
+impl Executor for AnalyzeExecutor {
+    fn schema(&self) -> &Schema {
+        self.child.schema()
+    }
+
+    fn identity(&self) -> &str {
+        "AnalyzeExecutor"
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl AnalyzeExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        // The stats are reported once the executor ends, including the case of being dropped
+        // early, e.g., by a limit executor. It also shows up in the stats if it outputs nothing.
+        let mut reporter = StatsReporter {
+            identity: self.plan_node_identity,
+            collector: self.collector,
+            stats: ExecutorStats::default(),
+        };
+
+        let mut child_stream = self.child.execute();
+        loop {
+            let start = Instant::now();
+            let chunk = child_stream.next().await;
+            reporter.stats.elapsed += start.elapsed();
+            match chunk {
+                Some(chunk) => {
+                    let chunk = chunk?;
+                    reporter.stats.output_rows += chunk.cardinality() as u64;
+                    reporter.stats.output_chunks += 1;
+                    reporter.stats.output_bytes += chunk.estimated_size() as u64;
+                    yield chunk;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Reports the stats of an executor to the collector on drop.
+struct StatsReporter {
+    identity: String,
+    collector: ExecutorStatsCollector,
+    stats: ExecutorStats,
+}
+
+impl Drop for StatsReporter {
+    fn drop(&mut self) {
+        self.collector
+            .update(&self.identity, |stats| stats.merge(&self.stats));
+    }
+}
@@ -29,6 +29,8 @@ use crate::executor::{
 use crate::task::BatchTaskContext;
 
 /// [`DeleteExecutor`] implements table deletion with values from its child executor.
+///
+/// It outputs the number of deleted rows, or the deleted rows themselves for `RETURNING`.
 // TODO: concurrent `DELETE` may cause problems. A scheduler might be required.
 pub struct DeleteExecutor {
     /// Target table id.
@@ -37,6 +39,7 @@ pub struct DeleteExecutor {
     child: BoxedExecutor,
     schema: Schema,
     identity: String,
+    returning: bool,
 }
 
 impl DeleteExecutor {
@@ -45,16 +48,22 @@ impl DeleteExecutor {
         source_manager: TableSourceManagerRef,
         child: BoxedExecutor,
         identity: String,
+        returning: bool,
     ) -> Self {
+        let schema = if returning {
+            child.schema().clone()
+        } else {
+            Schema {
+                fields: vec![Field::unnamed(DataType::Int64)],
+            }
+        };
         Self {
             table_id,
             source_manager,
             child,
-            // TODO: support `RETURNING`
-            schema: Schema {
-                fields: vec![Field::unnamed(DataType::Int64)],
-            },
+            schema,
             identity,
+            returning,
         }
     }
 }
@@ -80,12 +89,16 @@ impl DeleteExecutor {
         let source = source_desc.source.as_table().expect("not table source");
 
         let mut notifiers = Vec::new();
+        let mut returning_chunks = Vec::new();
 
         #[for_await]
         for data_chunk in self.child.execute() {
             let data_chunk = data_chunk?;
             let len = data_chunk.cardinality();
             assert!(data_chunk.visibility().is_none());
+            if self.returning {
+                returning_chunks.push(data_chunk.clone());
+            }
 
             let chunk = StreamChunk::from_parts(vec![Op::Delete; len], data_chunk);
 
@@ -100,6 +113,13 @@ impl DeleteExecutor {
             .into_iter()
             .sum::<usize>();
 
+        if self.returning {
+            for chunk in returning_chunks {
+                yield chunk;
+            }
+            return Ok(());
+        }
+
         // create ret value
         {
             let mut array_builder = PrimitiveArrayBuilder::<i64>::new(1);
@@ -132,6 +152,7 @@ impl BoxedExecutorBuilder for DeleteExecutor {
             source.context().source_manager(),
             child,
             source.plan_node().get_identity().clone(),
+            delete_node.returning,
         )))
     }
 }
@@ -193,6 +214,7 @@ mod tests {
             source_manager.clone(),
             Box::new(mock_executor),
             "DeleteExecutor".to_string(),
+            false,
         ));
 
         let handle = tokio::spawn(async move {
@@ -240,4 +262,58 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_delete_executor_returning() -> Result<()> {
+        let source_manager: TableSourceManagerRef = Arc::new(TableSourceManager::default());
+
+        let schema = schema_test_utils::ii();
+        let mut mock_executor = MockExecutor::new(schema.clone());
+        let data_chunk = DataChunk::from_pretty(
+            "i  i
+             1  2
+             3  4",
+        );
+        mock_executor.add(data_chunk.clone());
+
+        let table_id = TableId::new(0);
+        let source_builder = create_table_source_desc_builder(
+            &schema,
+            table_id,
+            None,
+            vec![1],
+            source_manager.clone(),
+        );
+        let source_desc = source_builder.build().await.unwrap();
+        let source = source_desc.source.as_table().unwrap();
+        let mut reader = source
+            .stream_reader(vec![0.into(), 1.into()])
+            .await?
+            .into_stream();
+
+        let delete_executor = Box::new(DeleteExecutor::new(
+            table_id,
+            source_manager.clone(),
+            Box::new(mock_executor),
+            "DeleteExecutor".to_string(),
+            true,
+        ));
+
+        let handle = tokio::spawn(async move {
+            // The deleted rows are output instead of the number of them.
+            assert_eq!(delete_executor.schema(), &schema);
+
+            let mut stream = delete_executor.execute();
+            let result = stream.next().await.unwrap().unwrap();
+            assert_eq!(result, data_chunk);
+            assert!(stream.next().await.is_none());
+        });
+
+        let chunk = reader.next().await.unwrap()?.chunk;
+        assert_eq!(chunk.ops().to_vec(), vec![Op::Delete; 2]);
+
+        handle.await.unwrap();
+
+        Ok(())
+    }
 }
@@ -98,6 +98,7 @@ impl CreateSource for DefaultCreateSource {
                     self.client_pool.get_by_addr(peer_addr).await?,
                     task_output_id.clone(),
                     prost_source.local_execute_plan.clone(),
+                    context.executor_stats(),
                 )
                 .await?,
             ))
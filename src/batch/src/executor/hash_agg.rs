@@ -12,13 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::path::PathBuf;
 
 use futures_async_stream::try_stream;
 use itertools::Itertools;
 use risingwave_common::array::DataChunk;
+use risingwave_common::buffer::BitmapBuilder;
 use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::collection::estimate_size::EstimateSize;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::hash::{HashKey, HashKeyDispatcher, PrecomputedBuildHasher};
 use risingwave_common::types::DataType;
@@ -28,8 +32,9 @@ use risingwave_pb::batch_plan::HashAggNode;
 
 use crate::executor::{
     BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+    SpillFileExecutor, SpillPartitioner,
 };
-use crate::task::{BatchTaskContext, TaskId};
+use crate::task::{BatchMemoryTrackerRef, BatchTaskContext, MemoryReservation, TaskId};
 
 type AggHashMap<K> = HashMap<K, Vec<BoxedAggState>, PrecomputedBuildHasher>;
 
@@ -38,7 +43,7 @@ impl HashKeyDispatcher for HashAggExecutorBuilder {
     type Output = BoxedExecutor;
 
     fn dispatch_impl<K: HashKey>(self) -> Self::Output {
-        Box::new(HashAggExecutor::<K>::new(
+        let mut executor = HashAggExecutor::<K>::new(
             self.agg_factories,
             self.group_key_columns,
             self.group_key_types,
@@ -46,7 +51,11 @@ impl HashKeyDispatcher for HashAggExecutorBuilder {
             self.child,
             self.identity,
             self.chunk_size,
-        ))
+        );
+        if let Some(spill) = self.spill {
+            executor = executor.with_spill(spill);
+        }
+        Box::new(executor)
     }
 
     fn data_types(&self) -> &[DataType] {
@@ -63,6 +72,7 @@ pub struct HashAggExecutorBuilder {
     task_id: TaskId,
     identity: String,
     chunk_size: usize,
+    spill: Option<HashAggSpillConfig>,
 }
 
 impl HashAggExecutorBuilder {
@@ -72,6 +82,7 @@ impl HashAggExecutorBuilder {
         task_id: TaskId,
         identity: String,
         chunk_size: usize,
+        spill: Option<HashAggSpillConfig>,
     ) -> Result<BoxedExecutor> {
         let agg_factories: Vec<_> = hash_agg_node
             .get_agg_calls()
@@ -108,6 +119,7 @@ impl HashAggExecutorBuilder {
             task_id,
             identity,
             chunk_size,
+            spill,
         };
 
         Ok(builder.dispatch())
@@ -128,17 +140,30 @@ impl BoxedExecutorBuilder for HashAggExecutorBuilder {
         )?;
 
         let identity = source.plan_node().get_identity().clone();
+        let config = source.context.get_config();
+        let spill = HashAggSpillConfig::new(
+            config.developer.batch_hash_agg_spill_threshold_bytes,
+            config
+                .spill_dir
+                .as_ref()
+                .map_or_else(std::env::temp_dir, PathBuf::from),
+            source.context.memory_tracker(),
+        );
         Self::deserialize(
             hash_agg_node,
             child,
             source.task_id.clone(),
             identity,
-            source.context.get_config().developer.batch_chunk_size,
+            config.developer.batch_chunk_size,
+            Some(spill),
         )
     }
 }
 
 /// `HashAggExecutor` implements the hash aggregate algorithm.
+///
+/// If the groups exceed the memory limit, the rows of the groups not in memory yet are spilled to
+/// disk partitions and aggregated partition by partition. See [`HashAggSpillConfig`].
 pub struct HashAggExecutor<K> {
     /// Factories to construct aggregator for each groups
     agg_factories: Vec<AggStateFactory>,
@@ -151,6 +176,8 @@ pub struct HashAggExecutor<K> {
     child: BoxedExecutor,
     identity: String,
     chunk_size: usize,
+    /// Spilling settings. All groups are always held in memory if not set.
+    spill: Option<HashAggSpillConfig>,
     _phantom: PhantomData<K>,
 }
 
@@ -172,9 +199,17 @@ impl<K> HashAggExecutor<K> {
             child,
             identity,
             chunk_size,
+            spill: None,
             _phantom: PhantomData,
         }
     }
+
+    /// Enables spilling to disk when the groups exceed the memory limit.
+    #[must_use]
+    pub fn with_spill(mut self, spill: HashAggSpillConfig) -> Self {
+        self.spill = Some(spill);
+        self
+    }
 }
 
 impl<K: HashKey + Send + Sync> Executor for HashAggExecutor<K> {
@@ -191,31 +226,160 @@ impl<K: HashKey + Send + Sync> Executor for HashAggExecutor<K> {
     }
 }
 
+/// The number of partitions that the rows of new groups are spilled into at a time.
+const SPILL_PARTITION_COUNT: usize = 16;
+
+/// The maximum number of times the input can be partitioned. After that, all groups of a partition
+/// are held in memory regardless of the memory limit.
+const MAX_SPILL_LEVEL: usize = 3;
+
+/// Settings for [`HashAggExecutor`] to spill to disk when the groups exceed the memory limit.
+///
+/// Once the limit is reached, the groups in memory keep being updated, while the rows of the other
+/// groups are partitioned by the hash of the group keys into [`SpillFile`]s. After the groups in
+/// memory are output, each partition is aggregated by a new [`HashAggExecutor`], which partitions
+/// it again with another hash seed if its groups are still too large.
+///
+/// [`SpillFile`]: crate::executor::SpillFile
+#[derive(Clone)]
+pub struct HashAggSpillConfig {
+    /// The memory the groups can hold before spilling.
+    threshold_bytes: usize,
+    /// The directory of the spill files.
+    spill_dir: PathBuf,
+    /// The node-wide memory tracker. None indicates only `threshold_bytes` is respected.
+    memory_tracker: Option<BatchMemoryTrackerRef>,
+    /// The number of times the input has been partitioned.
+    level: usize,
+}
+
+impl HashAggSpillConfig {
+    pub fn new(
+        threshold_bytes: usize,
+        spill_dir: PathBuf,
+        memory_tracker: Option<BatchMemoryTrackerRef>,
+    ) -> Self {
+        Self {
+            threshold_bytes,
+            spill_dir,
+            memory_tracker,
+            level: 0,
+        }
+    }
+
+    fn new_reservation(&self) -> MemoryReservation {
+        MemoryReservation::new(self.memory_tracker.clone(), self.threshold_bytes)
+    }
+
+    fn can_spill(&self) -> bool {
+        self.level < MAX_SPILL_LEVEL
+    }
+
+    fn next_level(&self) -> Self {
+        Self {
+            level: self.level + 1,
+            ..self.clone()
+        }
+    }
+
+    async fn new_partitioner(&self, group_key_columns: &[usize]) -> Result<SpillPartitioner> {
+        SpillPartitioner::new(
+            &self.spill_dir,
+            SPILL_PARTITION_COUNT,
+            group_key_columns.to_vec(),
+            self.level as u64,
+        )
+        .await
+    }
+}
+
+/// The estimated memory of the aggregation states of a group.
+fn states_size(states: &[BoxedAggState]) -> usize {
+    states.iter().map(|state| state.estimated_size()).sum()
+}
+
 impl<K: HashKey + Send + Sync> HashAggExecutor<K> {
     #[try_stream(boxed, ok = DataChunk, error = RwError)]
     async fn do_execute(self: Box<Self>) {
+        let child_schema = self.child.schema().clone();
         // hash map for each agg groups
         let mut groups = AggHashMap::<K>::default();
+        let mut memory = self.spill.as_ref().map(HashAggSpillConfig::new_reservation);
+        // partitions the rows of new groups once the groups exceed the memory limit
+        let mut partitioner = None;
 
         // consume all chunks to compute the agg result
         #[for_await]
         for chunk in self.child.execute() {
             let chunk = chunk?.compact();
             let keys = K::build(self.group_key_columns.as_slice(), &chunk)?;
+            let mut spilled_rows = None;
             for (row_id, key) in keys.into_iter().enumerate() {
-                let states: &mut Vec<BoxedAggState> = groups.entry(key).or_insert_with(|| {
-                    self.agg_factories
-                        .iter()
-                        .map(AggStateFactory::create_agg_state)
-                        .collect()
-                });
+                // the memory of the key of a new group, and of the states before the update
+                let mut key_size = 0;
+                let mut states_size_before = 0;
+                let states: &mut Vec<BoxedAggState> = match groups.entry(key) {
+                    Entry::Occupied(entry) => {
+                        let states = entry.into_mut();
+                        if memory.is_some() {
+                            states_size_before = states_size(states);
+                        }
+                        states
+                    }
+                    Entry::Vacant(_) if partitioner.is_some() => {
+                        spilled_rows
+                            .get_or_insert_with(|| BitmapBuilder::zeroed(chunk.capacity()))
+                            .set(row_id, true);
+                        continue;
+                    }
+                    Entry::Vacant(entry) => {
+                        key_size = entry.key().estimated_size()
+                            + std::mem::size_of::<Vec<BoxedAggState>>();
+                        entry.insert(
+                            self.agg_factories
+                                .iter()
+                                .map(AggStateFactory::create_agg_state)
+                                .collect(),
+                        )
+                    }
+                };
 
                 // TODO: currently not a vectorized implementation
-                for state in states {
+                for state in states.iter_mut() {
                     state.update_single(&chunk, row_id)?
                 }
+
+                // Besides new groups, the states grow with the rows, e.g., `string_agg`.
+                if let Some(memory) = &mut memory {
+                    let grown_bytes =
+                        key_size + states_size(states).saturating_sub(states_size_before);
+                    if !memory.grow(grown_bytes)
+                        && partitioner.is_none()
+                        && let Some(spill) = &self.spill
+                        && spill.can_spill()
+                    {
+                        tracing::info!(
+                            identity = %self.identity,
+                            level = spill.level,
+                            "groups of hash agg exceed the memory limit, spilling to disk"
+                        );
+                        partitioner = Some(spill.new_partitioner(&self.group_key_columns).await?);
+                    }
+                }
+            }
+
+            if let Some(spilled_rows) = spilled_rows
+                && let Some(partitioner) = &mut partitioner
+            {
+                partitioner
+                    .write(chunk.with_visibility(spilled_rows.finish()))
+                    .await?;
             }
         }
+        let partitions = match partitioner {
+            Some(partitioner) => Some(partitioner.finish().await?),
+            None => None,
+        };
 
         // generate output data chunks
         let mut result = groups.into_iter();
@@ -261,6 +425,37 @@ impl<K: HashKey + Send + Sync> HashAggExecutor<K> {
             let output = DataChunk::new(columns, array_len);
             yield output;
         }
+        drop(memory);
+
+        // aggregate the spilled partitions, whose groups are disjoint with the groups in memory
+        if let Some(partitions) = partitions
+            && let Some(spill) = &self.spill
+        {
+            for partition in partitions {
+                if partition.is_empty() {
+                    continue;
+                }
+                let partition_executor = Box::new(Self {
+                    agg_factories: self.agg_factories.clone(),
+                    group_key_columns: self.group_key_columns.clone(),
+                    group_key_types: self.group_key_types.clone(),
+                    schema: self.schema.clone(),
+                    child: Box::new(SpillFileExecutor::new(
+                        partition,
+                        child_schema.clone(),
+                        format!("{}-Spill", self.identity),
+                    )),
+                    identity: self.identity.clone(),
+                    chunk_size: self.chunk_size,
+                    spill: Some(spill.next_level()),
+                    _phantom: PhantomData,
+                });
+                #[for_await]
+                for chunk in partition_executor.execute() {
+                    yield chunk?;
+                }
+            }
+        }
     }
 }
 
@@ -274,7 +469,7 @@ mod tests {
     use risingwave_pb::expr::{AggCall, InputRefExpr};
 
     use super::*;
-    use crate::executor::test_utils::{diff_executor_output, MockExecutor};
+    use crate::executor::test_utils::{collect_sorted_rows, diff_executor_output, MockExecutor};
 
     const CHUNK_SIZE: usize = 1024;
 
@@ -333,6 +528,7 @@ mod tests {
             TaskId::default(),
             "HashAggExecutor".to_string(),
             CHUNK_SIZE,
+            None,
         )
         .unwrap();
 
@@ -401,6 +597,7 @@ mod tests {
             TaskId::default(),
             "HashAggExecutor".to_string(),
             CHUNK_SIZE,
+            None,
         )
         .unwrap();
         let schema = Schema {
@@ -416,4 +613,68 @@ mod tests {
         );
         diff_executor_output(actual_exec, Box::new(expect_exec)).await;
     }
+
+    #[tokio::test]
+    async fn test_hash_agg_spill() {
+        let t32 = DataType::Int32;
+        let schema = Schema {
+            fields: vec![Field::unnamed(t32.clone()), Field::unnamed(t32)],
+        };
+        // 100 groups spread over 4 chunks
+        let chunks = (0..4)
+            .map(|i| {
+                let rows = (0..64)
+                    .map(|j| format!("{} {}", (i * 64 + j) % 100, j))
+                    .join("\n");
+                DataChunk::from_pretty(&format!("i i\n{}", rows))
+            })
+            .collect_vec();
+
+        let agg_call = AggCall {
+            r#type: Type::Sum as i32,
+            args: vec![Arg {
+                input: Some(InputRefExpr { column_idx: 1 }),
+                r#type: Some(ProstDataType {
+                    type_name: TypeName::Int32 as i32,
+                    ..Default::default()
+                }),
+            }],
+            return_type: Some(ProstDataType {
+                type_name: TypeName::Int64 as i32,
+                ..Default::default()
+            }),
+            distinct: false,
+            order_by_fields: vec![],
+            filter: None,
+        };
+        let agg_prost = HashAggNode {
+            group_key: vec![0],
+            agg_calls: vec![agg_call],
+        };
+
+        let new_executor = |spill| {
+            let mut src_exec = MockExecutor::new(schema.clone());
+            for chunk in &chunks {
+                src_exec.add(chunk.clone());
+            }
+            HashAggExecutorBuilder::deserialize(
+                &agg_prost,
+                Box::new(src_exec),
+                TaskId::default(),
+                "HashAggExecutor".to_string(),
+                CHUNK_SIZE,
+                spill,
+            )
+            .unwrap()
+        };
+
+        let expected = collect_sorted_rows(new_executor(None)).await;
+        assert_eq!(expected.len(), 100);
+
+        // Spill every group but the first one at each level.
+        let spill_dir = tempfile::tempdir().unwrap();
+        let spill = HashAggSpillConfig::new(0, spill_dir.path().to_path_buf(), None);
+        let actual = collect_sorted_rows(new_executor(Some(spill))).await;
+        assert_eq!(actual, expected);
+    }
 }
@@ -30,6 +30,8 @@ use crate::executor::{
 };
 use crate::task::BatchTaskContext;
 /// [`InsertExecutor`] implements table insertion with values from its child executor.
+///
+/// It outputs the number of inserted rows, or the inserted rows themselves for `RETURNING`.
 pub struct InsertExecutor {
     /// Target table id.
     table_id: TableId,
@@ -38,6 +40,7 @@ pub struct InsertExecutor {
     child: BoxedExecutor,
     schema: Schema,
     identity: String,
+    returning: bool,
 }
 
 impl InsertExecutor {
@@ -46,15 +49,22 @@ impl InsertExecutor {
         source_manager: TableSourceManagerRef,
         child: BoxedExecutor,
         identity: String,
+        returning: bool,
     ) -> Self {
+        let schema = if returning {
+            child.schema().clone()
+        } else {
+            Schema {
+                fields: vec![Field::unnamed(DataType::Int64)],
+            }
+        };
         Self {
             table_id,
             source_manager,
             child,
-            schema: Schema {
-                fields: vec![Field::unnamed(DataType::Int64)],
-            },
+            schema,
             identity,
+            returning,
         }
     }
 }
@@ -81,12 +91,16 @@ impl InsertExecutor {
         let row_id_index = source_desc.row_id_index;
 
         let mut notifiers = Vec::new();
+        let mut returning_chunks = Vec::new();
 
         #[for_await]
         for data_chunk in self.child.execute() {
             let data_chunk = data_chunk?;
             let len = data_chunk.cardinality();
             assert!(data_chunk.visibility().is_none());
+            if self.returning {
+                returning_chunks.push(data_chunk.clone());
+            }
 
             let (mut columns, _) = data_chunk.into_parts();
 
@@ -111,6 +125,13 @@ impl InsertExecutor {
             .into_iter()
             .sum::<usize>();
 
+        if self.returning {
+            for chunk in returning_chunks {
+                yield chunk;
+            }
+            return Ok(());
+        }
+
         // create ret value
         {
             let mut array_builder = PrimitiveArrayBuilder::<i64>::new(1);
@@ -144,6 +165,7 @@ impl BoxedExecutorBuilder for InsertExecutor {
             source.context().source_manager(),
             child,
             source.plan_node().get_identity().clone(),
+            insert_node.returning,
         )))
     }
 }
@@ -227,6 +249,7 @@ mod tests {
             source_manager.clone(),
             Box::new(mock_executor),
             "InsertExecutor".to_string(),
+            false,
         ));
         let handle = tokio::spawn(async move {
             let mut stream = insert_executor.execute();
@@ -16,14 +16,17 @@ use std::collections::HashMap;
 use std::iter;
 use std::iter::empty;
 use std::marker::PhantomData;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use fixedbitset::FixedBitSet;
+use futures::StreamExt;
 use futures_async_stream::try_stream;
 use itertools::{repeat_n, Itertools};
 use risingwave_common::array::{Array, DataChunk, RowRef};
 use risingwave_common::buffer::{Bitmap, BitmapBuilder};
 use risingwave_common::catalog::Schema;
+use risingwave_common::collection::estimate_size::EstimateSize;
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::hash::{HashKey, HashKeyDispatcher, PrecomputedBuildHasher};
 use risingwave_common::types::DataType;
@@ -34,8 +37,9 @@ use risingwave_pb::batch_plan::plan_node::NodeBody;
 use super::{ChunkedData, JoinType, RowId};
 use crate::executor::{
     BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+    SpillFile, SpillFileExecutor, SpillPartitioner,
 };
-use crate::task::BatchTaskContext;
+use crate::task::{BatchMemoryTrackerRef, BatchTaskContext, MemoryReservation};
 
 /// Hash Join Executor
 ///
@@ -46,6 +50,9 @@ use crate::task::BatchTaskContext;
 /// 3. Concatenate the matched pair of probe side row and build side row into a single row and push
 /// it into the data chunk builder.
 /// 4. Yield chunks from the builder.
+///
+/// If the build side exceeds the memory limit, both sides are spilled to disk partitions and
+/// joined partition by partition. See [`HashJoinSpillConfig`].
 pub struct HashJoinExecutor<K> {
     /// Join type e.g. inner, left outer, ...
     join_type: JoinType,
@@ -63,13 +70,16 @@ pub struct HashJoinExecutor<K> {
     probe_key_idxs: Vec<usize>,
     /// Column indices of right keys in equi join
     build_key_idxs: Vec<usize>,
-    /// Non-equi join condition (optional)
-    cond: Option<BoxedExpression>,
+    /// Non-equi join condition (optional). Shared with the executors joining the spilled
+    /// partitions.
+    cond: Option<Arc<BoxedExpression>>,
     /// Whether or not to enable 'IS NOT DISTINCT FROM' semantics for a specific probe/build key
     /// column
     null_matched: Vec<bool>,
     identity: String,
     chunk_size: usize,
+    /// Spilling settings. The build side is always held in memory if not set.
+    spill: Option<HashJoinSpillConfig>,
     _phantom: PhantomData<K>,
 }
 
@@ -185,6 +195,105 @@ impl<K> EquiJoinParams<K> {
     }
 }
 
+/// The number of partitions that the inputs are spilled into at a time.
+const SPILL_PARTITION_COUNT: usize = 16;
+
+/// The maximum number of times the inputs can be partitioned. After that, a partition is joined in
+/// memory regardless of the memory limit, e.g., when most rows have the same join key.
+const MAX_SPILL_LEVEL: usize = 3;
+
+/// Settings for [`HashJoinExecutor`] to spill to disk when the build side exceeds the memory limit,
+/// a.k.a. grace hash join.
+///
+/// Both sides are partitioned by the hash of the join keys into [`SpillFile`]s, and each pair of
+/// partitions is joined by a new [`HashJoinExecutor`], which partitions them again with another
+/// hash seed if the build side is still too large.
+#[derive(Clone)]
+pub struct HashJoinSpillConfig {
+    /// The memory the build side can hold before spilling.
+    threshold_bytes: usize,
+    /// The directory of the spill files.
+    spill_dir: PathBuf,
+    /// The node-wide memory tracker. None indicates only `threshold_bytes` is respected.
+    memory_tracker: Option<BatchMemoryTrackerRef>,
+    /// The number of times the inputs have been partitioned.
+    level: usize,
+}
+
+impl HashJoinSpillConfig {
+    pub fn new(
+        threshold_bytes: usize,
+        spill_dir: PathBuf,
+        memory_tracker: Option<BatchMemoryTrackerRef>,
+    ) -> Self {
+        Self {
+            threshold_bytes,
+            spill_dir,
+            memory_tracker,
+            level: 0,
+        }
+    }
+
+    fn new_reservation(&self) -> MemoryReservation {
+        MemoryReservation::new(self.memory_tracker.clone(), self.threshold_bytes)
+    }
+
+    fn can_spill(&self) -> bool {
+        self.level < MAX_SPILL_LEVEL
+    }
+
+    fn next_level(&self) -> Self {
+        Self {
+            level: self.level + 1,
+            ..self.clone()
+        }
+    }
+
+    /// Partitions both sides into spill files. Returns the partitions of the probe side and the
+    /// build side respectively.
+    async fn spill_inputs(
+        &self,
+        probe_side: BoxedExecutor,
+        probe_key_idxs: &[usize],
+        build_side: Vec<DataChunk>,
+        mut build_stream: BoxedDataChunkStream,
+        build_key_idxs: &[usize],
+    ) -> Result<(Vec<SpillFile>, Vec<SpillFile>)> {
+        let seed = self.level as u64;
+
+        let mut build_partitioner = SpillPartitioner::new(
+            &self.spill_dir,
+            SPILL_PARTITION_COUNT,
+            build_key_idxs.to_vec(),
+            seed,
+        )
+        .await?;
+        for build_chunk in build_side {
+            build_partitioner.write(build_chunk).await?;
+        }
+        while let Some(build_chunk) = build_stream.next().await {
+            build_partitioner.write(build_chunk?).await?;
+        }
+
+        let mut probe_partitioner = SpillPartitioner::new(
+            &self.spill_dir,
+            SPILL_PARTITION_COUNT,
+            probe_key_idxs.to_vec(),
+            seed,
+        )
+        .await?;
+        let mut probe_stream = probe_side.execute();
+        while let Some(probe_chunk) = probe_stream.next().await {
+            probe_partitioner.write(probe_chunk?).await?;
+        }
+
+        Ok((
+            probe_partitioner.finish().await?,
+            build_partitioner.finish().await?,
+        ))
+    }
+}
+
 /// State variables used in left outer/semi/anti join and full outer join.
 #[derive(Default)]
 struct LeftNonEquiJoinState {
@@ -210,20 +319,98 @@ struct RightNonEquiJoinState {
 }
 
 impl<K: HashKey> HashJoinExecutor<K> {
+    /// The estimated memory of the hash table for a build row, i.e., an entry of the hash map and
+    /// of `next_build_row_with_same_key`, excluding the heap memory of the key.
+    fn hash_table_row_size() -> usize {
+        std::mem::size_of::<(K, RowId)>() + std::mem::size_of::<Option<RowId>>()
+    }
+
     #[try_stream(boxed, ok = DataChunk, error = RwError)]
     async fn do_execute(self: Box<Self>) {
         let probe_data_types = self.probe_side_source.schema().data_types();
         let build_data_types = self.build_side_source.schema().data_types();
         let full_data_types = [probe_data_types.clone(), build_data_types.clone()].concat();
+        let probe_schema = self.probe_side_source.schema().clone();
+        let build_schema = self.build_side_source.schema().clone();
 
+        let mut memory = self
+            .spill
+            .as_ref()
+            .map(HashJoinSpillConfig::new_reservation);
         let mut build_side = Vec::new();
         let mut build_row_count = 0;
-        #[for_await]
-        for build_chunk in self.build_side_source.execute() {
+        let mut build_stream = self.build_side_source.execute();
+        while let Some(build_chunk) = build_stream.next().await {
             let build_chunk = build_chunk?;
             if build_chunk.cardinality() > 0 {
                 build_row_count += build_chunk.cardinality();
-                build_side.push(build_chunk.compact())
+                let build_chunk = build_chunk.compact();
+                let within_limit = match &mut memory {
+                    Some(memory) => memory.grow(
+                        build_chunk.estimated_size()
+                            + build_chunk.cardinality() * Self::hash_table_row_size(),
+                    ),
+                    None => true,
+                };
+                build_side.push(build_chunk);
+
+                if !within_limit
+                    && let Some(spill) = &self.spill
+                    && spill.can_spill()
+                {
+                    tracing::info!(
+                        identity = %self.identity,
+                        level = spill.level,
+                        "build side of hash join exceeds the memory limit, spilling to disk"
+                    );
+                    let (probe_partitions, build_partitions) = spill
+                        .spill_inputs(
+                            self.probe_side_source,
+                            &self.probe_key_idxs,
+                            build_side,
+                            build_stream,
+                            &self.build_key_idxs,
+                        )
+                        .await?;
+                    drop(memory.take());
+
+                    for (probe_partition, build_partition) in
+                        probe_partitions.into_iter().zip_eq(build_partitions)
+                    {
+                        if probe_partition.is_empty() && build_partition.is_empty() {
+                            continue;
+                        }
+                        let partition_executor = Box::new(Self {
+                            join_type: self.join_type,
+                            original_schema: self.original_schema.clone(),
+                            schema: self.schema.clone(),
+                            output_indices: self.output_indices.clone(),
+                            probe_side_source: Box::new(SpillFileExecutor::new(
+                                probe_partition,
+                                probe_schema.clone(),
+                                format!("{}-ProbeSpill", self.identity),
+                            )),
+                            build_side_source: Box::new(SpillFileExecutor::new(
+                                build_partition,
+                                build_schema.clone(),
+                                format!("{}-BuildSpill", self.identity),
+                            )),
+                            probe_key_idxs: self.probe_key_idxs.clone(),
+                            build_key_idxs: self.build_key_idxs.clone(),
+                            cond: self.cond.clone(),
+                            null_matched: self.null_matched.clone(),
+                            identity: self.identity.clone(),
+                            chunk_size: self.chunk_size,
+                            spill: Some(spill.next_level()),
+                            _phantom: PhantomData,
+                        });
+                        #[for_await]
+                        for chunk in partition_executor.execute() {
+                            yield chunk?;
+                        }
+                    }
+                    return Ok(());
+                }
             }
         }
         let mut hash_map =
@@ -1656,6 +1843,8 @@ impl BoxedExecutorBuilder for HashJoinExecutor<()> {
             .map(|&x| x as usize)
             .collect();
 
+        let config = context.context.get_config();
+
         Ok(HashJoinExecutorArgs {
             join_type,
             output_indices,
@@ -1667,7 +1856,15 @@ impl BoxedExecutorBuilder for HashJoinExecutor<()> {
             cond,
             identity: context.plan_node().get_identity().clone(),
             right_key_types,
-            chunk_size: context.context.get_config().developer.batch_chunk_size,
+            chunk_size: config.developer.batch_chunk_size,
+            spill: HashJoinSpillConfig::new(
+                config.developer.batch_hash_join_spill_threshold_bytes,
+                config
+                    .spill_dir
+                    .as_ref()
+                    .map_or_else(std::env::temp_dir, PathBuf::from),
+                context.context.memory_tracker(),
+            ),
         }
         .dispatch())
     }
@@ -1685,24 +1882,28 @@ struct HashJoinExecutorArgs {
     identity: String,
     right_key_types: Vec<DataType>,
     chunk_size: usize,
+    spill: HashJoinSpillConfig,
 }
 
 impl HashKeyDispatcher for HashJoinExecutorArgs {
     type Output = BoxedExecutor;
 
     fn dispatch_impl<K: HashKey>(self) -> Self::Output {
-        Box::new(HashJoinExecutor::<K>::new(
-            self.join_type,
-            self.output_indices,
-            self.probe_side_source,
-            self.build_side_source,
-            self.probe_key_idxs,
-            self.build_key_idxs,
-            self.null_matched,
-            self.cond,
-            self.identity,
-            self.chunk_size,
-        ))
+        Box::new(
+            HashJoinExecutor::<K>::new(
+                self.join_type,
+                self.output_indices,
+                self.probe_side_source,
+                self.build_side_source,
+                self.probe_key_idxs,
+                self.build_key_idxs,
+                self.null_matched,
+                self.cond,
+                self.identity,
+                self.chunk_size,
+            )
+            .with_spill(self.spill),
+        )
     }
 
     fn data_types(&self) -> &[DataType] {
@@ -1753,12 +1954,20 @@ impl<K> HashJoinExecutor<K> {
             probe_key_idxs,
             build_key_idxs,
             null_matched,
-            cond,
+            cond: cond.map(Arc::new),
             identity,
             chunk_size,
+            spill: None,
             _phantom: PhantomData,
         }
     }
+
+    /// Enables spilling to disk when the build side exceeds the memory limit.
+    #[must_use]
+    pub fn with_spill(mut self, spill: HashJoinSpillConfig) -> Self {
+        self.spill = Some(spill);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -1777,9 +1986,10 @@ mod tests {
     use risingwave_pb::expr::expr_node::Type;
 
     use super::{
-        ChunkedData, HashJoinExecutor, JoinType, LeftNonEquiJoinState, RightNonEquiJoinState, RowId,
+        ChunkedData, HashJoinExecutor, HashJoinSpillConfig, JoinType, LeftNonEquiJoinState,
+        RightNonEquiJoinState, RowId,
     };
-    use crate::executor::test_utils::MockExecutor;
+    use crate::executor::test_utils::{collect_sorted_rows, MockExecutor};
     use crate::executor::BoxedExecutor;
 
     const CHUNK_SIZE: usize = 1024;
@@ -1971,6 +2181,14 @@ mod tests {
         }
 
         fn create_join_executor(&self, has_non_equi_cond: bool, null_safe: bool) -> BoxedExecutor {
+            Box::new(self.new_join_executor(has_non_equi_cond, null_safe))
+        }
+
+        fn new_join_executor(
+            &self,
+            has_non_equi_cond: bool,
+            null_safe: bool,
+        ) -> HashJoinExecutor<Key32> {
             let join_type = self.join_type;
 
             let left_child = self.create_left_executor();
@@ -1989,7 +2207,7 @@ mod tests {
                 None
             };
 
-            Box::new(HashJoinExecutor::<Key32>::new(
+            HashJoinExecutor::<Key32>::new(
                 join_type,
                 output_indices,
                 left_child,
@@ -2000,7 +2218,7 @@ mod tests {
                 cond,
                 "HashJoinExecutor".to_string(),
                 CHUNK_SIZE,
-            ))
+            )
         }
 
         async fn do_test(&self, expected: DataChunk, has_non_equi_cond: bool, null_safe: bool) {
@@ -2320,6 +2538,41 @@ mod tests {
         test_fixture.do_test(expected_chunk, true, false).await;
     }
 
+    #[tokio::test]
+    async fn test_hash_join_spill() {
+        let spill_dir = tempfile::tempdir().unwrap();
+        for join_type in [
+            JoinType::Inner,
+            JoinType::LeftOuter,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightOuter,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+            JoinType::FullOuter,
+        ] {
+            let test_fixture = TestFixture::with_join_type(join_type);
+            for has_non_equi_cond in [false, true] {
+                let expected = collect_sorted_rows(
+                    test_fixture.create_join_executor(has_non_equi_cond, false),
+                )
+                .await;
+
+                // Spill on every build chunk until the maximum level is reached.
+                let spill = HashJoinSpillConfig::new(0, spill_dir.path().to_path_buf(), None);
+                let executor = test_fixture
+                    .new_join_executor(has_non_equi_cond, false)
+                    .with_spill(spill);
+                let actual = collect_sorted_rows(Box::new(executor)).await;
+                assert_eq!(
+                    actual, expected,
+                    "join type: {:?}, has non-equi condition: {}",
+                    join_type, has_non_equi_cond
+                );
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_left_anti_join() {
         let test_fixture = TestFixture::with_join_type(JoinType::LeftAnti);
@@ -110,6 +110,7 @@ impl<C: BatchTaskContext> InnerSideExecutorBuilder<C> {
             column_ids: self.inner_side_column_ids.clone(),
             scan_ranges,
             vnode_bitmap: Some(vnode_bitmap.finish().to_protobuf()),
+            limit: 0,
         });
 
         Ok(row_seq_scan_node)
@@ -134,6 +135,7 @@ impl<C: BatchTaskContext> InnerSideExecutorBuilder<C> {
                 }),
             }),
             epoch: self.epoch,
+            collect_executor_stats: false,
         };
 
         let prost_exchange_source = ProstExchangeSource {
@@ -17,6 +17,7 @@ mod distributed_lookup_join;
 pub mod hash_join;
 pub mod local_lookup_join;
 mod lookup_join_base;
+mod nested_loop_apply;
 pub mod nested_loop_join;
 mod sort_merge_join;
 
@@ -26,6 +27,7 @@ pub use hash_join::*;
 use itertools::Itertools;
 pub use local_lookup_join::*;
 pub use lookup_join_base::*;
+pub use nested_loop_apply::*;
 pub use nested_loop_join::*;
 use risingwave_common::array::{DataChunk, RowRef, Vis};
 use risingwave_common::error::Result;
@@ -0,0 +1,440 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use itertools::repeat_n;
+use risingwave_common::array::{Array, DataChunk, RowRef};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, ToOwnedDatum};
+use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
+use risingwave_common::util::value_encoding::serialize_datum_to_bytes;
+use risingwave_expr::expr::{build_from_prost as expr_build_from_prost, BoxedExpression};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::PlanNode;
+use risingwave_pb::data::Datum as ProstDatum;
+use risingwave_pb::expr::expr_node::{RexNode, Type};
+use risingwave_pb::expr::project_set_select_item::SelectItem;
+use risingwave_pb::expr::ExprNode;
+
+use crate::executor::join::{concatenate, convert_row_to_chunk, JoinType};
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+    WorkTables,
+};
+use crate::task::{BatchTaskContext, TaskId};
+
+/// [`NestedLoopApplyExecutor`] executes a correlated join which can not be decorrelated by the
+/// optimizer.
+///
+/// For each row of the left child, the correlated input refs in the plan of the right child are
+/// replaced by the values of the row, and then the right child is built and executed. Its output
+/// is joined with the row on the join condition.
+pub struct NestedLoopApplyExecutor<C> {
+    join_expr: BoxedExpression,
+    join_type: JoinType,
+    correlated_id: u32,
+    left_child: BoxedExecutor,
+    /// The plan of the right child, which is built for each row of the left child.
+    right_plan: PlanNode,
+    right_data_types: Vec<DataType>,
+
+    task_id: TaskId,
+    context: C,
+    epoch: u64,
+    work_tables: WorkTables,
+
+    schema: Schema,
+    identity: String,
+    chunk_size: usize,
+}
+
+impl<C: BatchTaskContext> Executor for NestedLoopApplyExecutor<C> {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl<C: BatchTaskContext> NestedLoopApplyExecutor<C> {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let Self {
+            join_expr,
+            join_type,
+            correlated_id,
+            left_child,
+            right_plan,
+            right_data_types,
+            task_id,
+            context,
+            epoch,
+            work_tables,
+            schema,
+            chunk_size,
+            ..
+        } = *self;
+        let left_data_types = left_child.schema().data_types();
+        let mut chunk_builder = DataChunkBuilder::new(schema.data_types(), chunk_size);
+
+        #[for_await]
+        for left_chunk in left_child.execute() {
+            let left_chunk = left_chunk?;
+            for left_row in left_chunk.rows() {
+                let mut plan = right_plan.clone();
+                substitute_correlated_input_refs(&mut plan, correlated_id, &left_row);
+                let right = ExecutorBuilder::new(&plan, &task_id, context.clone(), epoch)
+                    .with_work_tables(work_tables.clone())
+                    .build()
+                    .await?;
+
+                let mut matched = false;
+                #[for_await]
+                for right_chunk in right.execute() {
+                    let right_chunk = right_chunk?;
+                    let row_chunk =
+                        convert_row_to_chunk(&left_row, right_chunk.capacity(), &left_data_types)?;
+                    let mut chunk = concatenate(&row_chunk, &right_chunk)?;
+                    chunk.set_visibility(join_expr.eval(&chunk)?.as_bool().iter().collect());
+                    if chunk.cardinality() == 0 {
+                        continue;
+                    }
+                    matched = true;
+                    match join_type {
+                        JoinType::Inner | JoinType::LeftOuter => {
+                            #[for_await]
+                            for spilled in chunk_builder.trunc_data_chunk(chunk) {
+                                yield spilled
+                            }
+                        }
+                        // The output of the row has been decided.
+                        _ => break,
+                    }
+                }
+
+                let output_left_row = match join_type {
+                    JoinType::LeftOuter | JoinType::LeftAnti => !matched,
+                    JoinType::LeftSemi => matched,
+                    _ => false,
+                };
+                if output_left_row {
+                    let null_padding = match join_type {
+                        JoinType::LeftOuter => right_data_types.len(),
+                        _ => 0,
+                    };
+                    let datum_refs = left_row.values().chain(repeat_n(None, null_padding));
+                    if let Some(chunk) = chunk_builder.append_one_row_from_datum_refs(datum_refs) {
+                        yield chunk
+                    }
+                }
+            }
+        }
+
+        if let Some(chunk) = chunk_builder.consume_all() {
+            yield chunk
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for NestedLoopApplyExecutor<()> {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        // Only the left child is built as the input, see `ExecutorBuilder::try_build`.
+        ensure!(
+            inputs.len() == 1,
+            "NestedLoopApplyExecutor should have 1 input!"
+        );
+        let [left_child]: [_; 1] = inputs.try_into().unwrap();
+        let apply_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::NestedLoopApply
+        )?;
+
+        let join_type = JoinType::from_prost(apply_node.get_join_type()?);
+        ensure!(
+            matches!(
+                join_type,
+                JoinType::Inner | JoinType::LeftOuter | JoinType::LeftSemi | JoinType::LeftAnti
+            ),
+            "unsupported join type of nested-loop apply: {:?}",
+            join_type
+        );
+        let join_expr = expr_build_from_prost(apply_node.get_join_cond()?)?;
+        let right_fields = apply_node
+            .right_schema
+            .iter()
+            .map(Field::from)
+            .collect::<Vec<_>>();
+        let schema = match join_type {
+            JoinType::LeftSemi | JoinType::LeftAnti => left_child.schema().clone(),
+            _ => Schema::from_iter(
+                left_child
+                    .schema()
+                    .fields()
+                    .iter()
+                    .chain(right_fields.iter())
+                    .cloned(),
+            ),
+        };
+
+        Ok(Box::new(NestedLoopApplyExecutor {
+            join_expr,
+            join_type,
+            correlated_id: apply_node.correlated_id,
+            left_child,
+            right_plan: source.plan_node().get_children()[1].clone(),
+            right_data_types: right_fields.iter().map(|f| f.data_type()).collect(),
+            task_id: source.task_id.clone(),
+            context: source.context().clone(),
+            epoch: source.epoch(),
+            work_tables: source.work_tables().clone(),
+            schema,
+            identity: source.plan_node().get_identity().clone(),
+            chunk_size: source.context().get_config().developer.batch_chunk_size,
+        }))
+    }
+}
+
+/// Replaces the correlated input refs with `correlated_id` in the expressions of `plan` and all of
+/// its descendants with the values of `row`.
+fn substitute_correlated_input_refs(plan: &mut PlanNode, correlated_id: u32, row: &RowRef<'_>) {
+    let mut substitute = |expr: &mut ExprNode| substitute_in_expr(expr, correlated_id, row);
+    match plan.node_body.as_mut() {
+        Some(NodeBody::Project(node)) => node.select_list.iter_mut().for_each(&mut substitute),
+        Some(NodeBody::Filter(node)) => node.search_condition.iter_mut().for_each(&mut substitute),
+        Some(NodeBody::NestedLoopJoin(node)) => node.join_cond.iter_mut().for_each(&mut substitute),
+        Some(NodeBody::NestedLoopApply(node)) => {
+            node.join_cond.iter_mut().for_each(&mut substitute)
+        }
+        Some(NodeBody::HashJoin(node)) => node.condition.iter_mut().for_each(&mut substitute),
+        Some(NodeBody::LocalLookupJoin(node)) => {
+            node.condition.iter_mut().for_each(&mut substitute)
+        }
+        Some(NodeBody::DistributedLookupJoin(node)) => {
+            node.condition.iter_mut().for_each(&mut substitute)
+        }
+        Some(NodeBody::SortAgg(node)) => {
+            node.group_key.iter_mut().for_each(&mut substitute);
+            node.agg_calls
+                .iter_mut()
+                .flat_map(|agg_call| agg_call.filter.iter_mut())
+                .for_each(&mut substitute);
+        }
+        Some(NodeBody::HashAgg(node)) => node
+            .agg_calls
+            .iter_mut()
+            .flat_map(|agg_call| agg_call.filter.iter_mut())
+            .for_each(&mut substitute),
+        Some(NodeBody::Values(node)) => node
+            .tuples
+            .iter_mut()
+            .flat_map(|tuple| tuple.cells.iter_mut())
+            .for_each(&mut substitute),
+        Some(NodeBody::ProjectSet(node)) => {
+            for item in &mut node.select_list {
+                match item.select_item.as_mut() {
+                    Some(SelectItem::Expr(expr)) => substitute(expr),
+                    Some(SelectItem::TableFunction(tf)) => {
+                        tf.args.iter_mut().for_each(&mut substitute)
+                    }
+                    None => {}
+                }
+            }
+        }
+        Some(NodeBody::TableFunction(node)) => node
+            .table_function
+            .iter_mut()
+            .flat_map(|tf| tf.args.iter_mut())
+            .for_each(&mut substitute),
+        Some(NodeBody::Update(node)) => node.exprs.iter_mut().for_each(&mut substitute),
+        _ => {}
+    }
+    for child in &mut plan.children {
+        substitute_correlated_input_refs(child, correlated_id, row);
+    }
+}
+
+fn substitute_in_expr(expr: &mut ExprNode, correlated_id: u32, row: &RowRef<'_>) {
+    match expr.rex_node.as_mut() {
+        Some(RexNode::CorrelatedInputRef(input_ref))
+            if input_ref.correlated_id == correlated_id =>
+        {
+            let datum = row.value_at(input_ref.column_idx as usize).to_owned_datum();
+            expr.expr_type = Type::ConstantValue as i32;
+            expr.rex_node = datum.map(|scalar| {
+                RexNode::Constant(ProstDatum {
+                    body: serialize_datum_to_bytes(Some(&scalar)),
+                })
+            });
+        }
+        Some(RexNode::FuncCall(func_call)) => func_call
+            .children
+            .iter_mut()
+            .for_each(|child| substitute_in_expr(child, correlated_id, row)),
+        Some(RexNode::Udf(udf)) => udf
+            .children
+            .iter_mut()
+            .for_each(|child| substitute_in_expr(child, correlated_id, row)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::ScalarImpl;
+    use risingwave_expr::expr::{make_i32_literal, make_input_ref};
+    use risingwave_pb::batch_plan::values_node::ExprTuple;
+    use risingwave_pb::batch_plan::{FilterNode, ValuesNode};
+    use risingwave_pb::data::data_type::TypeName;
+    use risingwave_pb::expr::{CorrelatedInputRefExpr, FunctionCall};
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+    use crate::task::ComputeNodeContext;
+
+    const CORRELATED_ID: u32 = 1;
+
+    /// Builds an apply whose right side outputs the values in `[1, 2]` less than the left row, i.e.
+    /// `select * from left, lateral (select * from (values (1), (2)) r(v) where v < left.i)`.
+    fn nested_loop_apply_executor(join_type: JoinType) -> Box<dyn Executor> {
+        let int32 = DataType::Int32.to_protobuf();
+        let left_schema = Schema {
+            fields: vec![Field::unnamed(DataType::Int32)],
+        };
+        let mut left = MockExecutor::new(left_schema.clone());
+        left.add(DataChunk::from_pretty(
+            "i
+             1
+             2
+             3",
+        ));
+
+        let values = PlanNode {
+            node_body: Some(NodeBody::Values(ValuesNode {
+                tuples: vec![
+                    ExprTuple {
+                        cells: vec![make_i32_literal(1)],
+                    },
+                    ExprTuple {
+                        cells: vec![make_i32_literal(2)],
+                    },
+                ],
+                fields: left_schema.to_prost(),
+            })),
+            ..Default::default()
+        };
+        let correlated_input_ref = ExprNode {
+            expr_type: Type::CorrelatedInputRef as i32,
+            return_type: Some(int32),
+            rex_node: Some(RexNode::CorrelatedInputRef(CorrelatedInputRefExpr {
+                correlated_id: CORRELATED_ID,
+                column_idx: 0,
+            })),
+        };
+        let less_than = ExprNode {
+            expr_type: Type::LessThan as i32,
+            return_type: Some(DataType::Boolean.to_protobuf()),
+            rex_node: Some(RexNode::FuncCall(FunctionCall {
+                children: vec![make_input_ref(0, TypeName::Int32), correlated_input_ref],
+            })),
+        };
+        let right_plan = PlanNode {
+            children: vec![values],
+            node_body: Some(NodeBody::Filter(FilterNode {
+                search_condition: Some(less_than),
+            })),
+            ..Default::default()
+        };
+
+        let join_cond = ExprNode {
+            expr_type: Type::ConstantValue as i32,
+            return_type: Some(DataType::Boolean.to_protobuf()),
+            rex_node: Some(RexNode::Constant(ProstDatum {
+                body: serialize_datum_to_bytes(Some(&ScalarImpl::Bool(true))),
+            })),
+        };
+        let schema = match join_type {
+            JoinType::LeftSemi | JoinType::LeftAnti => left_schema,
+            _ => Schema {
+                fields: vec![Field::unnamed(DataType::Int32); 2],
+            },
+        };
+        Box::new(NestedLoopApplyExecutor {
+            join_expr: expr_build_from_prost(&join_cond).unwrap(),
+            join_type,
+            correlated_id: CORRELATED_ID,
+            left_child: Box::new(left),
+            right_plan,
+            right_data_types: vec![DataType::Int32],
+            task_id: TaskId::default(),
+            context: ComputeNodeContext::for_test(),
+            epoch: u64::MAX,
+            work_tables: WorkTables::default(),
+            schema,
+            identity: "NestedLoopApplyExecutor".to_string(),
+            chunk_size: 1024,
+        })
+    }
+
+    async fn do_test(join_type: JoinType, expected: DataChunk) {
+        let mut stream = nested_loop_apply_executor(join_type).execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(chunk, expected);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_inner_apply() {
+        let expected = DataChunk::from_pretty(
+            "i i
+             2 1
+             3 1
+             3 2",
+        );
+        do_test(JoinType::Inner, expected).await;
+    }
+
+    #[tokio::test]
+    async fn test_left_outer_apply() {
+        let expected = DataChunk::from_pretty(
+            "i i
+             1 .
+             2 1
+             3 1
+             3 2",
+        );
+        do_test(JoinType::LeftOuter, expected).await;
+    }
+
+    #[tokio::test]
+    async fn test_left_anti_apply() {
+        let expected = DataChunk::from_pretty(
+            "i
+             1",
+        );
+        do_test(JoinType::LeftAnti, expected).await;
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use anyhow::anyhow;
+mod analyze;
 mod delete;
 mod expand;
 mod filter;
@@ -27,8 +28,12 @@ pub mod monitor;
 mod order_by;
 mod project;
 mod project_set;
+mod recursive_union;
 mod row_seq_scan;
 mod sort_agg;
+mod sort_over_window;
+mod sort_session_window;
+mod spill;
 mod sys_row_seq_scan;
 mod table_function;
 pub mod test_utils;
@@ -38,7 +43,9 @@ mod union;
 mod update;
 mod utils;
 mod values;
+mod work_table;
 
+pub use analyze::*;
 use async_recursion::async_recursion;
 pub use delete::*;
 pub use expand::*;
@@ -56,6 +63,7 @@ pub use monitor::*;
 pub use order_by::*;
 pub use project::*;
 pub use project_set::*;
+pub use recursive_union::*;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::Result;
@@ -63,6 +71,9 @@ use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::PlanNode;
 pub use row_seq_scan::*;
 pub use sort_agg::*;
+pub use sort_over_window::*;
+pub use sort_session_window::*;
+pub use spill::*;
 pub use table_function::*;
 pub use top_n::TopNExecutor;
 pub use trace::*;
@@ -70,6 +81,7 @@ pub use union::*;
 pub use update::*;
 pub use utils::*;
 pub use values::*;
+pub use work_table::*;
 
 use crate::executor::sys_row_seq_scan::SysRowSeqScanExecutorBuilder;
 use crate::task::{BatchTaskContext, TaskId};
@@ -119,6 +131,8 @@ pub struct ExecutorBuilder<'a, C> {
     pub task_id: &'a TaskId,
     context: C,
     epoch: u64,
+    /// The work tables of the recursive CTEs, shared by all the executors built for the task.
+    work_tables: WorkTables,
 }
 
 macro_rules! build_executor {
@@ -140,12 +154,20 @@ impl<'a, C: Clone> ExecutorBuilder<'a, C> {
             task_id,
             context,
             epoch,
+            work_tables: WorkTables::default(),
         }
     }
 
     #[must_use]
     pub fn clone_for_plan(&self, plan_node: &'a PlanNode) -> Self {
         ExecutorBuilder::new(plan_node, self.task_id, self.context.clone(), self.epoch)
+            .with_work_tables(self.work_tables.clone())
+    }
+
+    #[must_use]
+    pub fn with_work_tables(mut self, work_tables: WorkTables) -> Self {
+        self.work_tables = work_tables;
+        self
     }
 
     pub fn plan_node(&self) -> &PlanNode {
@@ -159,6 +181,10 @@ impl<'a, C: Clone> ExecutorBuilder<'a, C> {
     pub fn epoch(&self) -> u64 {
         self.epoch
     }
+
+    pub fn work_tables(&self) -> &WorkTables {
+        &self.work_tables
+    }
 }
 
 impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
@@ -175,8 +201,16 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
 
     #[async_recursion]
     async fn try_build(&self) -> Result<BoxedExecutor> {
-        let mut inputs = Vec::with_capacity(self.plan_node.children.len());
-        for input_node in &self.plan_node.children {
+        let children = match self.plan_node.get_node_body().unwrap() {
+            // The recursive term is built by the executor in each iteration, and the right side of
+            // the nested-loop apply is built for each row of the left side.
+            NodeBody::RecursiveUnion(_) | NodeBody::NestedLoopApply(_) => {
+                &self.plan_node.children[..1]
+            }
+            _ => &self.plan_node.children[..],
+        };
+        let mut inputs = Vec::with_capacity(children.len());
+        for input_node in children {
             let input = self.clone_for_plan(input_node).build().await?;
             inputs.push(input);
         }
@@ -196,6 +230,7 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
             NodeBody::Limit => LimitExecutor,
             NodeBody::Values => ValuesExecutor,
             NodeBody::NestedLoopJoin => NestedLoopJoinExecutor,
+            NodeBody::NestedLoopApply => NestedLoopApplyExecutor<()>,
             NodeBody::HashJoin => HashJoinExecutor<()>,
             NodeBody::SortMergeJoin => SortMergeJoinExecutor,
             NodeBody::HashAgg => HashAggExecutorBuilder,
@@ -208,10 +243,22 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
             NodeBody::DistributedLookupJoin => DistributedLookupJoinExecutorBuilder,
             NodeBody::ProjectSet => ProjectSetExecutor,
             NodeBody::Union => UnionExecutor,
+            NodeBody::SortOverWindow => SortOverWindowExecutor,
+            NodeBody::SortSessionWindow => SortSessionWindowExecutor,
+            NodeBody::RecursiveUnion => RecursiveUnionExecutor<()>,
+            NodeBody::WorkTable => WorkTableExecutor,
         }
         .await?;
         let input_desc = real_executor.identity().to_string();
-        Ok(Box::new(TraceExecutor::new(real_executor, input_desc)) as BoxedExecutor)
+        let executor = Box::new(TraceExecutor::new(real_executor, input_desc)) as BoxedExecutor;
+        match self.context.executor_stats() {
+            Some(collector) => Ok(Box::new(AnalyzeExecutor::new(
+                executor,
+                self.plan_node.get_identity().clone(),
+                collector,
+            )) as BoxedExecutor),
+            None => Ok(executor),
+        }
     }
 }
 
@@ -0,0 +1,164 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use risingwave_pb::task_service::ExecutorStats as ProstExecutorStats;
+use tokio::sync::Notify;
+
+/// Runtime stats of a batch executor.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutorStats {
+    pub output_rows: u64,
+    pub output_chunks: u64,
+    /// Total time spent in the executor and its inputs.
+    pub elapsed: Duration,
+    /// Estimated size of the output chunks in bytes.
+    pub output_bytes: u64,
+}
+
+impl ExecutorStats {
+    /// Merges the stats of the same plan node from another task. The elapsed time is the max one,
+    /// as the tasks run in parallel.
+    pub fn merge(&mut self, other: &ExecutorStats) {
+        self.output_rows += other.output_rows;
+        self.output_chunks += other.output_chunks;
+        self.elapsed = self.elapsed.max(other.elapsed);
+        self.output_bytes += other.output_bytes;
+    }
+}
+
+/// Collects the runtime stats of executors for `EXPLAIN ANALYZE`, keyed by the identity of the
+/// plan node. It's shared by all executors of a query running in the same node.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutorStatsCollector {
+    stats: Arc<Mutex<HashMap<String, ExecutorStats>>>,
+    /// The number of remote tasks whose stats have been merged.
+    reported_tasks: Arc<AtomicUsize>,
+    task_reported: Arc<Notify>,
+}
+
+impl ExecutorStatsCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&self, identity: &str, f: impl FnOnce(&mut ExecutorStats)) {
+        let mut stats = self.stats.lock();
+        f(stats.entry(identity.to_string()).or_default());
+    }
+
+    /// Merges the stats reported by a remote task once it ends.
+    pub fn merge_from_protobuf(&self, prost: &[ProstExecutorStats]) {
+        {
+            let mut stats = self.stats.lock();
+            for p in prost {
+                stats
+                    .entry(p.identity.clone())
+                    .or_default()
+                    .merge(&ExecutorStats {
+                        output_rows: p.output_rows,
+                        output_chunks: p.output_chunks,
+                        elapsed: Duration::from_micros(p.elapsed_us),
+                        output_bytes: p.output_bytes,
+                    });
+            }
+        }
+        self.reported_tasks.fetch_add(1, Ordering::Relaxed);
+        self.task_reported.notify_waiters();
+    }
+
+    /// Waits until the stats of `task_count` remote tasks have been merged. In distributed mode,
+    /// the stats are reported along with the task status, which may arrive after the query output.
+    pub async fn wait_for_tasks(&self, task_count: usize) {
+        loop {
+            let notified = self.task_reported.notified();
+            if self.reported_tasks.load(Ordering::Relaxed) >= task_count {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    pub fn to_protobuf(&self) -> Vec<ProstExecutorStats> {
+        self.stats
+            .lock()
+            .iter()
+            .map(|(identity, s)| ProstExecutorStats {
+                identity: identity.clone(),
+                output_rows: s.output_rows,
+                output_chunks: s.output_chunks,
+                elapsed_us: s.elapsed.as_micros() as u64,
+                output_bytes: s.output_bytes,
+            })
+            .collect()
+    }
+
+    pub fn snapshot(&self) -> HashMap<String, ExecutorStats> {
+        self.stats.lock().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_executor_stats() {
+        let collector = ExecutorStatsCollector::new();
+        collector.update("scan", |s| {
+            s.output_rows += 10;
+            s.output_chunks += 1;
+            s.elapsed += Duration::from_millis(5);
+        });
+
+        let remote = ExecutorStatsCollector::new();
+        remote.update("scan", |s| {
+            s.output_rows += 5;
+            s.output_chunks += 1;
+            s.elapsed += Duration::from_millis(8);
+        });
+        collector.merge_from_protobuf(&remote.to_protobuf());
+
+        let stats = collector.snapshot();
+        assert_eq!(
+            stats["scan"],
+            ExecutorStats {
+                output_rows: 15,
+                output_chunks: 2,
+                elapsed: Duration::from_millis(8),
+                output_bytes: 0,
+            }
+        );
+    }
+    #[tokio::test]
+    async fn test_wait_for_tasks() {
+        let collector = ExecutorStatsCollector::new();
+        let waiter = tokio::spawn({
+            let collector = collector.clone();
+            async move { collector.wait_for_tasks(2).await }
+        });
+
+        let remote = ExecutorStatsCollector::new();
+        remote.update("scan", |s| s.output_rows += 5);
+        collector.merge_from_protobuf(&remote.to_protobuf());
+        collector.merge_from_protobuf(&remote.to_protobuf());
+        waiter.await.unwrap();
+        assert_eq!(collector.snapshot()["scan"].output_rows, 10);
+    }
+}
@@ -12,5 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 //
+pub mod executor_stats;
 pub mod stats;
+pub use executor_stats::*;
 pub use stats::*;
@@ -54,11 +54,10 @@ impl ProjectExecutor {
         for data_chunk in self.child.execute() {
             let data_chunk = data_chunk?;
             // let data_chunk = data_chunk.compact();
-            let arrays: Vec<Column> = self
-                .expr
-                .iter_mut()
-                .map(|expr| expr.eval(&data_chunk).map(Column::new))
-                .try_collect()?;
+            let mut arrays = Vec::with_capacity(self.expr.len());
+            for expr in &self.expr {
+                arrays.push(Column::new(expr.eval_async(&data_chunk).await?));
+            }
             let (_, vis) = data_chunk.into_parts();
             let ret = DataChunk::new(arrays, vis);
             yield ret
@@ -101,12 +100,24 @@ impl BoxedExecutorBuilder for ProjectExecutor {
 
 #[cfg(test)]
 mod tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
     use futures::stream::StreamExt;
     use risingwave_common::array::{Array, I32Array};
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::test_prelude::*;
     use risingwave_common::types::DataType;
-    use risingwave_expr::expr::{InputRefExpression, LiteralExpression};
+    use risingwave_expr::expr::{make_input_ref, InputRefExpression, LiteralExpression};
+    use risingwave_pb::data::data_type::TypeName;
+    use risingwave_pb::data::DataType as ProstDataType;
+    use risingwave_pb::expr::expr_node::{RexNode, Type};
+    use risingwave_pb::expr::{ExprNode, UserDefinedFunction};
+    use risingwave_pb::udf::udf_service_server::{UdfService, UdfServiceServer};
+    use risingwave_pb::udf::{EvalRequest, EvalResponse};
+    use tokio::time::sleep;
+    use tonic::{Request, Response, Status};
 
     use super::*;
     use crate::executor::test_utils::MockExecutor;
@@ -189,4 +200,113 @@ mod tests {
             array_nonnull!(I32Array, [1]).into()
         );
     }
+
+    /// A UDF server which doubles the only argument.
+    struct MockUdfService;
+
+    #[async_trait::async_trait]
+    impl UdfService for MockUdfService {
+        async fn eval(
+            &self,
+            request: Request<EvalRequest>,
+        ) -> std::result::Result<Response<EvalResponse>, Status> {
+            let request = request.into_inner();
+            assert_eq!(request.identifier, "double");
+            let input = DataChunk::from_protobuf(request.input.as_ref().unwrap()).unwrap();
+            let output = input
+                .column_at(0)
+                .array_ref()
+                .as_int32()
+                .iter()
+                .map(|v| v.map(|v| v * 2))
+                .collect::<Vec<_>>();
+            let output = Column::new(Arc::new(I32Array::from_slice(&output).into()));
+            Ok(Response::new(EvalResponse {
+                output: Some(output.to_protobuf()),
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_project_udf() {
+        let addr: SocketAddr = "127.0.0.1:12346".parse().unwrap();
+
+        // Start a UDF server.
+        let (shutdown_send, shutdown_recv) = tokio::sync::oneshot::channel();
+        let join_handle = tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(UdfServiceServer::new(MockUdfService))
+                .serve_with_shutdown(addr, async move {
+                    shutdown_recv.await.unwrap();
+                })
+                .await
+                .unwrap();
+        });
+        sleep(Duration::from_secs(1)).await;
+
+        let udf = build_from_prost(&ExprNode {
+            expr_type: Type::Udf as i32,
+            return_type: Some(ProstDataType {
+                type_name: TypeName::Int32 as i32,
+                ..Default::default()
+            }),
+            rex_node: Some(RexNode::Udf(UserDefinedFunction {
+                children: vec![make_input_ref(0, TypeName::Int32)],
+                name: "double".to_string(),
+                language: "python".to_string(),
+                link: format!("http://{}", addr),
+                identifier: "double".to_string(),
+                ..Default::default()
+            })),
+        })
+        .unwrap();
+
+        let schema = schema_unnamed! { DataType::Int32 };
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "i
+             1
+             2
+             .",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "i
+             3",
+        ));
+
+        // The evaluation doesn't block the thread, otherwise it would panic in the current-thread
+        // runtime.
+        let proj_executor = Box::new(ProjectExecutor {
+            expr: vec![udf],
+            child: Box::new(mock_executor),
+            schema: schema_unnamed!(DataType::Int32),
+            identity: "ProjectExecutor".to_string(),
+        });
+        let mut stream = proj_executor.execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk
+                .column_at(0)
+                .array_ref()
+                .as_int32()
+                .iter()
+                .collect_vec(),
+            vec![Some(2), Some(4), None]
+        );
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk
+                .column_at(0)
+                .array_ref()
+                .as_int32()
+                .iter()
+                .collect_vec(),
+            vec![Some(6)]
+        );
+        assert!(stream.next().await.is_none());
+
+        // Gracefully terminate the server.
+        shutdown_send.send(()).unwrap();
+        join_handle.await.unwrap();
+    }
 }
@@ -0,0 +1,244 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use risingwave_common::array::{DataChunk, Row};
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::PlanNode;
+
+use crate::error::BatchError;
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+    WorkTable, WorkTables,
+};
+use crate::task::{BatchTaskContext, TaskId};
+
+/// [`RecursiveUnionExecutor`] evaluates a recursive CTE iteratively. The rows of the base term are
+/// output and put into the work table first. Then in each iteration, the recursive term is built
+/// and executed against the rows produced by the last iteration in the work table, until it
+/// produces no new rows, i.e., a fixed point is reached.
+///
+/// Without `ALL`, the rows which have been output before are discarded, so the recursion stops
+/// once no new distinct rows are found.
+pub struct RecursiveUnionExecutor<C> {
+    base: BoxedExecutor,
+    /// The plan of the recursive term, which is built in each iteration.
+    recursive_plan: PlanNode,
+    work_table: WorkTable,
+    /// The work tables visible to the recursive term, including `work_table`.
+    work_tables: WorkTables,
+    all: bool,
+    max_depth: u32,
+
+    task_id: TaskId,
+    context: C,
+    epoch: u64,
+
+    schema: Schema,
+    identity: String,
+}
+
+impl<C: BatchTaskContext> Executor for RecursiveUnionExecutor<C> {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl<C: BatchTaskContext> RecursiveUnionExecutor<C> {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let Self {
+            base,
+            recursive_plan,
+            work_table,
+            work_tables,
+            all,
+            max_depth,
+            task_id,
+            context,
+            epoch,
+            ..
+        } = *self;
+
+        // The rows output so far, only used for `UNION` to remove duplicates.
+        let mut seen = HashSet::new();
+        let mut dedup = |chunk: DataChunk| -> Option<DataChunk> {
+            let chunk = if all {
+                chunk
+            } else {
+                let vis = (0..chunk.capacity()).map(|pos| {
+                    let (row, visible) = chunk.row_at(pos);
+                    visible && seen.insert(Row::from(row))
+                });
+                let vis = vis.collect();
+                chunk.with_visibility(vis).compact()
+            };
+            (chunk.cardinality() > 0).then_some(chunk)
+        };
+
+        let mut new_chunks = vec![];
+        #[for_await]
+        for chunk in base.execute() {
+            if let Some(chunk) = dedup(chunk?) {
+                new_chunks.push(chunk.clone());
+                yield chunk;
+            }
+        }
+
+        let mut depth = 0;
+        while !new_chunks.is_empty() {
+            if depth >= max_depth {
+                return Err(BatchError::MaxRecursionDepthExceeded(max_depth).into());
+            }
+            depth += 1;
+            work_table.set(std::mem::take(&mut new_chunks));
+
+            let executor = ExecutorBuilder::new(&recursive_plan, &task_id, context.clone(), epoch)
+                .with_work_tables(work_tables.clone())
+                .build()
+                .await?;
+            let mut stream = executor.execute();
+            while let Some(chunk) = stream.next().await {
+                if let Some(chunk) = dedup(chunk?) {
+                    new_chunks.push(chunk.clone());
+                    yield chunk;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for RecursiveUnionExecutor<()> {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        // Only the base term is built as the input, see `ExecutorBuilder::try_build`.
+        ensure!(
+            inputs.len() == 1,
+            "RecursiveUnionExecutor should have 1 input!"
+        );
+        let [base]: [_; 1] = inputs.try_into().unwrap();
+        let recursive_union_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::RecursiveUnion
+        )?;
+
+        let work_table = WorkTable::default();
+        let work_tables = source
+            .work_tables()
+            .with(recursive_union_node.work_table_id, work_table.clone());
+        Ok(Box::new(RecursiveUnionExecutor {
+            schema: base.schema().clone(),
+            base,
+            recursive_plan: source.plan_node().get_children()[1].clone(),
+            work_table,
+            work_tables,
+            all: recursive_union_node.all,
+            max_depth: recursive_union_node.max_depth,
+            task_id: source.task_id.clone(),
+            context: source.context().clone(),
+            epoch: source.epoch(),
+            identity: source.plan_node().get_identity().clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::Field;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::DataType;
+    use risingwave_pb::batch_plan::WorkTableNode;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+    use crate::task::ComputeNodeContext;
+
+    /// Builds a recursive union whose recursive term outputs the work table as is.
+    fn recursive_union_executor(all: bool, max_depth: u32) -> Box<dyn Executor> {
+        let schema = Schema {
+            fields: vec![Field::unnamed(DataType::Int32)],
+        };
+        let mut base = MockExecutor::new(schema.clone());
+        base.add(DataChunk::from_pretty(
+            "i
+             1
+             1
+             2",
+        ));
+        let recursive_plan = PlanNode {
+            node_body: Some(NodeBody::WorkTable(WorkTableNode {
+                work_table_id: 0,
+                fields: schema.to_prost(),
+            })),
+            ..Default::default()
+        };
+        let work_table = WorkTable::default();
+
+        Box::new(RecursiveUnionExecutor {
+            base: Box::new(base),
+            recursive_plan,
+            work_tables: WorkTables::default().with(0, work_table.clone()),
+            work_table,
+            all,
+            max_depth,
+            task_id: TaskId::default(),
+            context: ComputeNodeContext::for_test(),
+            epoch: u64::MAX,
+            schema,
+            identity: "RecursiveUnionExecutor".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_recursive_union_fixed_point() {
+        let mut stream = recursive_union_executor(false, 10).execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk,
+            DataChunk::from_pretty(
+                "i
+                 1
+                 2"
+            )
+        );
+        // The recursive term only produces the rows which have been output.
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recursive_union_max_depth() {
+        let mut stream = recursive_union_executor(true, 3).execute();
+        for _ in 0..4 {
+            let chunk = stream.next().await.unwrap().unwrap();
+            assert_eq!(chunk.cardinality(), 3);
+        }
+        assert!(stream.next().await.unwrap().is_err());
+    }
+}
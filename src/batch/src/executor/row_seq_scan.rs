@@ -52,6 +52,8 @@ pub struct RowSeqScanExecutor<S: StateStore> {
 
     table: StorageTable<S>,
     scan_ranges: Vec<ScanRange>,
+    /// The maximum number of rows to read from each range scan of each vnode.
+    limit: Option<u64>,
     epoch: u64,
 }
 
@@ -129,6 +131,7 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
     pub fn new(
         table: StorageTable<S>,
         scan_ranges: Vec<ScanRange>,
+        limit: Option<u64>,
         epoch: u64,
         chunk_size: usize,
         identity: String,
@@ -140,6 +143,7 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
             metrics,
             table,
             scan_ranges,
+            limit,
             epoch,
         }
     }
@@ -233,6 +237,9 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             }
         };
 
+        // 0 means no limit.
+        let limit = (seq_scan_node.limit > 0).then_some(seq_scan_node.limit);
+
         let epoch = source.epoch;
         let chunk_size = source.context.get_config().developer.batch_chunk_size;
         let metrics = source.context().task_metrics();
@@ -253,6 +260,7 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             Ok(Box::new(RowSeqScanExecutor::new(
                 table,
                 scan_ranges,
+                limit,
                 epoch,
                 chunk_size,
                 source.plan_node().get_identity().clone(),
@@ -285,6 +293,7 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
             metrics,
             table,
             scan_ranges,
+            limit,
             epoch,
         } = *self;
 
@@ -328,7 +337,7 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
             let table = table.clone();
             let histogram = histogram.clone();
             Box::pin(Self::execute_range(
-                table, range_scan, epoch, chunk_size, histogram,
+                table, range_scan, limit, epoch, chunk_size, histogram,
             ))
         }));
         #[for_await]
@@ -364,6 +373,7 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
     async fn execute_range(
         table: Arc<StorageTable<S>>,
         scan_range: ScanRange,
+        limit: Option<u64>,
         epoch: u64,
         chunk_size: usize,
         histogram: Option<Histogram>,
@@ -380,6 +390,7 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
                 HummockReadEpoch::Committed(epoch),
                 &pk_prefix,
                 next_col_bounds,
+                limit.map(|limit| limit as usize),
             )
             .await?;
 
@@ -488,6 +499,7 @@ mod tests {
         let row_seq_scan_exec = RowSeqScanExecutor::new(
             table.clone(),
             vec![scan_range1, scan_range2],
+            None,
             epoch.curr,
             1024,
             "row_seq_scan_exec".to_string(),
@@ -509,8 +521,9 @@ mod tests {
         );
 
         let full_row_seq_scan_exec = RowSeqScanExecutor::new(
-            table,
+            table.clone(),
             vec![ScanRange::full()],
+            None,
             epoch.curr,
             1024,
             "row_seq_scan_exec".to_string(),
@@ -534,5 +547,31 @@ mod tests {
             chunk.row_at(2).0.to_owned_row(),
             Row(vec![Some(33_i32.into()), Some(333_i32.into())])
         );
+
+        let limited_row_seq_scan_exec = RowSeqScanExecutor::new(
+            table,
+            vec![ScanRange::full()],
+            Some(2),
+            epoch.curr,
+            1024,
+            "row_seq_scan_exec".to_string(),
+            None,
+        );
+
+        let row_seq_scan_exec = Box::new(limited_row_seq_scan_exec);
+
+        let mut stream = row_seq_scan_exec.execute();
+        let chunk = stream.next().await.unwrap().unwrap();
+
+        assert_eq!(chunk.cardinality(), 2);
+        assert_eq!(
+            chunk.row_at(0).0.to_owned_row(),
+            Row(vec![Some(11_i32.into()), Some(111_i32.into())])
+        );
+        assert_eq!(
+            chunk.row_at(1).0.to_owned_row(),
+            Row(vec![Some(22_i32.into()), Some(222_i32.into())])
+        );
+        assert!(stream.next().await.is_none());
     }
 }
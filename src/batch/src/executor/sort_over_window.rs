@@ -0,0 +1,286 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{DataChunk, Row};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::DataType;
+use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
+use risingwave_expr::window_function::{evaluate_partition, WindowFuncCall};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+/// Sort Over Window Executor
+///
+/// Evaluates window functions over an input sorted by the partition key and then the window
+/// order. Rows of one partition are buffered until the partition key changes, and then emitted
+/// with the results of the window functions appended.
+pub struct SortOverWindowExecutor {
+    child: BoxedExecutor,
+    calls: Vec<WindowFuncCall>,
+    partition_key_indices: Vec<usize>,
+    order_key_indices: Vec<usize>,
+    schema: Schema,
+    identity: String,
+    chunk_size: usize,
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for SortOverWindowExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        let [child]: [_; 1] = inputs.try_into().unwrap();
+
+        let over_window_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::SortOverWindow
+        )?;
+
+        let calls: Vec<_> = over_window_node
+            .calls
+            .iter()
+            .map(WindowFuncCall::from_protobuf)
+            .try_collect()?;
+        let partition_key_indices = over_window_node
+            .partition_by
+            .iter()
+            .map(|i| *i as usize)
+            .collect();
+        let order_key_indices = over_window_node
+            .order_by
+            .iter()
+            .map(|o| o.index as usize)
+            .collect();
+
+        Ok(Box::new(Self::new(
+            child,
+            calls,
+            partition_key_indices,
+            order_key_indices,
+            source.plan_node().get_identity().clone(),
+            source.context.get_config().developer.batch_chunk_size,
+        )))
+    }
+}
+
+impl SortOverWindowExecutor {
+    pub fn new(
+        child: BoxedExecutor,
+        calls: Vec<WindowFuncCall>,
+        partition_key_indices: Vec<usize>,
+        order_key_indices: Vec<usize>,
+        identity: String,
+        chunk_size: usize,
+    ) -> Self {
+        let mut schema = child.schema().clone();
+        schema
+            .fields
+            .extend(calls.iter().map(|call| Field::unnamed(call.return_type())));
+        Self {
+            child,
+            calls,
+            partition_key_indices,
+            order_key_indices,
+            schema,
+            identity,
+            chunk_size,
+        }
+    }
+}
+
+impl Executor for SortOverWindowExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl SortOverWindowExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let input_data_types = self.child.schema().data_types();
+        let mut chunk_builder = DataChunkBuilder::new(self.schema.data_types(), self.chunk_size);
+        let mut partition: Vec<Row> = vec![];
+
+        #[for_await]
+        for chunk in self.child.execute() {
+            let chunk = chunk?;
+            for row in chunk.rows() {
+                let row = row.to_owned_row();
+                if let Some(last) = partition.last()
+                    && self
+                        .partition_key_indices
+                        .iter()
+                        .any(|&idx| last.0[idx] != row.0[idx])
+                {
+                    for spilled in self.emit_partition(
+                        std::mem::take(&mut partition),
+                        &input_data_types,
+                        &mut chunk_builder,
+                    )? {
+                        yield spilled;
+                    }
+                }
+                partition.push(row);
+            }
+        }
+        if !partition.is_empty() {
+            for spilled in self.emit_partition(partition, &input_data_types, &mut chunk_builder)? {
+                yield spilled;
+            }
+        }
+        if let Some(spilled) = chunk_builder.consume_all() {
+            yield spilled;
+        }
+    }
+
+    /// Evaluates the window functions over a complete partition and appends the output rows to
+    /// `chunk_builder`, returning the chunks that are full.
+    fn emit_partition(
+        &self,
+        partition: Vec<Row>,
+        input_data_types: &[DataType],
+        chunk_builder: &mut DataChunkBuilder,
+    ) -> Result<Vec<DataChunk>> {
+        let outputs = evaluate_partition(
+            &self.calls,
+            &partition,
+            input_data_types,
+            &self.order_key_indices,
+            0..partition.len(),
+        )?;
+        let mut spilled_chunks = vec![];
+        for (i, row) in partition.into_iter().enumerate() {
+            let row = row.concat(outputs.iter().map(|output| output[i].clone()));
+            if let Some(spilled) = chunk_builder.append_one_row_from_datums(row.values()) {
+                spilled_chunks.push(spilled);
+            }
+        }
+        Ok(spilled_chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_pb::data::data_type::TypeName;
+    use risingwave_pb::data::DataType as ProstDataType;
+    use risingwave_pb::expr::agg_call::{Arg, Type as AggType};
+    use risingwave_pb::expr::window_function::Type;
+    use risingwave_pb::expr::{AggCall, InputRefExpr, WindowFunction};
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    const CHUNK_SIZE: usize = 1024;
+
+    #[tokio::test]
+    async fn test_sort_over_window_executor() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Int32),
+            ],
+        };
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "i i
+             1 1
+             1 2
+             1 2",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "i i
+             1 3
+             2 5
+             2 6",
+        ));
+
+        let int32 = ProstDataType {
+            type_name: TypeName::Int32 as i32,
+            ..Default::default()
+        };
+        let int64 = ProstDataType {
+            type_name: TypeName::Int64 as i32,
+            ..Default::default()
+        };
+        let arg = Arg {
+            input: Some(InputRefExpr { column_idx: 1 }),
+            r#type: Some(int32),
+        };
+        let calls = vec![
+            WindowFunction {
+                r#type: Type::Rank as i32,
+                return_type: Some(int64.clone()),
+                ..Default::default()
+            },
+            WindowFunction {
+                r#type: Type::Aggregate as i32,
+                args: vec![arg.clone()],
+                return_type: Some(int64.clone()),
+                agg_call: Some(AggCall {
+                    r#type: AggType::Sum as i32,
+                    args: vec![arg],
+                    return_type: Some(int64),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ]
+        .iter()
+        .map(|call| WindowFuncCall::from_protobuf(call).unwrap())
+        .collect();
+
+        let executor = Box::new(SortOverWindowExecutor::new(
+            Box::new(mock_executor),
+            calls,
+            vec![0],
+            vec![1],
+            "SortOverWindowExecutor".to_string(),
+            CHUNK_SIZE,
+        ));
+
+        let mut stream = executor.execute();
+        let res = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            res,
+            DataChunk::from_pretty(
+                "i i I I
+                 1 1 1 1
+                 1 2 2 5
+                 1 2 2 5
+                 1 3 4 8
+                 2 5 1 5
+                 2 6 2 11"
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
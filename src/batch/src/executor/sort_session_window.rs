@@ -0,0 +1,227 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use risingwave_common::array::{DataChunk, Row};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, IntervalUnit};
+use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
+use risingwave_expr::window_function::evaluate_sessions;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+/// Sort Session Window Executor
+///
+/// Assigns the rows to session windows over an input sorted by the partition key and then the
+/// time column. Rows of one partition are buffered until the partition key changes, and then
+/// emitted with the `window_start` and `window_end` of their sessions appended.
+pub struct SortSessionWindowExecutor {
+    child: BoxedExecutor,
+    time_col: usize,
+    gap: IntervalUnit,
+    partition_key_indices: Vec<usize>,
+    schema: Schema,
+    identity: String,
+    chunk_size: usize,
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for SortSessionWindowExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        let [child]: [_; 1] = inputs.try_into().unwrap();
+
+        let session_window_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::SortSessionWindow
+        )?;
+
+        let partition_key_indices = session_window_node
+            .partition_by
+            .iter()
+            .map(|i| *i as usize)
+            .collect();
+
+        Ok(Box::new(Self::new(
+            child,
+            session_window_node.time_col as usize,
+            session_window_node.get_gap()?.into(),
+            partition_key_indices,
+            source.plan_node().get_identity().clone(),
+            source.context.get_config().developer.batch_chunk_size,
+        )))
+    }
+}
+
+impl SortSessionWindowExecutor {
+    pub fn new(
+        child: BoxedExecutor,
+        time_col: usize,
+        gap: IntervalUnit,
+        partition_key_indices: Vec<usize>,
+        identity: String,
+        chunk_size: usize,
+    ) -> Self {
+        let output_type = DataType::window_of(&child.schema()[time_col].data_type).unwrap();
+        let mut schema = child.schema().clone();
+        schema.fields.extend([
+            Field::with_name(output_type.clone(), "window_start"),
+            Field::with_name(output_type, "window_end"),
+        ]);
+        Self {
+            child,
+            time_col,
+            gap,
+            partition_key_indices,
+            schema,
+            identity,
+            chunk_size,
+        }
+    }
+}
+
+impl Executor for SortSessionWindowExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl SortSessionWindowExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let mut chunk_builder = DataChunkBuilder::new(self.schema.data_types(), self.chunk_size);
+        let mut partition: Vec<Row> = vec![];
+
+        #[for_await]
+        for chunk in self.child.execute() {
+            let chunk = chunk?;
+            for row in chunk.rows() {
+                let row = row.to_owned_row();
+                if let Some(last) = partition.last()
+                    && self
+                        .partition_key_indices
+                        .iter()
+                        .any(|&idx| last.0[idx] != row.0[idx])
+                {
+                    for spilled in
+                        self.emit_partition(std::mem::take(&mut partition), &mut chunk_builder)?
+                    {
+                        yield spilled;
+                    }
+                }
+                partition.push(row);
+            }
+        }
+        if !partition.is_empty() {
+            for spilled in self.emit_partition(partition, &mut chunk_builder)? {
+                yield spilled;
+            }
+        }
+        if let Some(spilled) = chunk_builder.consume_all() {
+            yield spilled;
+        }
+    }
+
+    /// Assigns the rows of a complete partition to sessions and appends the output rows to
+    /// `chunk_builder`, returning the chunks that are full.
+    fn emit_partition(
+        &self,
+        partition: Vec<Row>,
+        chunk_builder: &mut DataChunkBuilder,
+    ) -> Result<Vec<DataChunk>> {
+        let sessions = evaluate_sessions(&partition, self.time_col, self.gap)?;
+        let mut spilled_chunks = vec![];
+        for (row, (window_start, window_end)) in partition.into_iter().zip(sessions) {
+            let row = row.concat([window_start, window_end]);
+            if let Some(spilled) = chunk_builder.append_one_row_from_datums(row.values()) {
+                spilled_chunks.push(spilled);
+            }
+        }
+        Ok(spilled_chunks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    const CHUNK_SIZE: usize = 1024;
+
+    #[tokio::test]
+    async fn test_sort_session_window_executor() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Timestamp),
+            ],
+        };
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "i TS
+             1 2022-11-07T00:00:00
+             1 2022-11-07T00:00:02
+             1 2022-11-07T00:00:10",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "i TS
+             1 2022-11-07T00:00:14
+             2 2022-11-07T00:00:03
+             2 2022-11-07T00:00:09",
+        ));
+
+        let executor = Box::new(SortSessionWindowExecutor::new(
+            Box::new(mock_executor),
+            1,
+            IntervalUnit::from_millis(5000),
+            vec![0],
+            "SortSessionWindowExecutor".to_string(),
+            CHUNK_SIZE,
+        ));
+
+        let mut stream = executor.execute();
+        let res = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            res,
+            DataChunk::from_pretty(
+                "i TS                  TS                  TS
+                 1 2022-11-07T00:00:00 2022-11-07T00:00:00 2022-11-07T00:00:07
+                 1 2022-11-07T00:00:02 2022-11-07T00:00:00 2022-11-07T00:00:07
+                 1 2022-11-07T00:00:10 2022-11-07T00:00:10 2022-11-07T00:00:19
+                 1 2022-11-07T00:00:14 2022-11-07T00:00:10 2022-11-07T00:00:19
+                 2 2022-11-07T00:00:03 2022-11-07T00:00:03 2022-11-07T00:00:08
+                 2 2022-11-07T00:00:09 2022-11-07T00:00:09 2022-11-07T00:00:14"
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
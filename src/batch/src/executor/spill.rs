@@ -0,0 +1,257 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::hash::BuildHasher;
+use std::io::SeekFrom;
+use std::path::Path;
+
+use futures::future::try_join_all;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use prost::Message;
+use risingwave_common::array::DataChunk;
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::data::DataChunk as ProstDataChunk;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, BufWriter};
+use twox_hash::XxHash64;
+
+use crate::executor::{BoxedDataChunkStream, Executor};
+
+/// A temporary file of the data chunks spilled to disk, which is removed once dropped.
+pub struct SpillFile {
+    file: File,
+    /// The number of chunks in the file.
+    chunk_count: usize,
+}
+
+impl SpillFile {
+    pub fn is_empty(&self) -> bool {
+        self.chunk_count == 0
+    }
+}
+
+/// Writes data chunks into a [`SpillFile`]. Each chunk is encoded in protobuf and prefixed with
+/// the length of the encoded bytes.
+pub struct SpillFileWriter {
+    writer: BufWriter<File>,
+    chunk_count: usize,
+}
+
+impl SpillFileWriter {
+    /// Creates a writer of a new spill file in `dir`.
+    pub async fn new(dir: &Path) -> Result<Self> {
+        // `tempfile` only provides a blocking API to create the file.
+        let dir = dir.to_path_buf();
+        let file = tokio::task::spawn_blocking(move || tempfile::tempfile_in(dir))
+            .await
+            .map_err(anyhow::Error::from)??;
+        Ok(Self {
+            writer: BufWriter::new(File::from_std(file)),
+            chunk_count: 0,
+        })
+    }
+
+    pub async fn write(&mut self, chunk: DataChunk) -> Result<()> {
+        let encoded = chunk.compact().to_protobuf().encode_to_vec();
+        self.writer.write_u32_le(encoded.len() as u32).await?;
+        self.writer.write_all(&encoded).await?;
+        self.chunk_count += 1;
+        Ok(())
+    }
+
+    /// Flushes the written chunks and returns the file to be read from the beginning.
+    pub async fn finish(mut self) -> Result<SpillFile> {
+        self.writer.flush().await?;
+        let mut file = self.writer.into_inner();
+        file.seek(SeekFrom::Start(0)).await?;
+        Ok(SpillFile {
+            file,
+            chunk_count: self.chunk_count,
+        })
+    }
+}
+
+/// Builds the hashers for partitioning with the given seed.
+#[derive(Clone, Copy)]
+struct SpillHasherBuilder(u64);
+
+impl BuildHasher for SpillHasherBuilder {
+    type Hasher = XxHash64;
+
+    fn build_hasher(&self) -> XxHash64 {
+        XxHash64::with_seed(self.0)
+    }
+}
+
+/// Partitions data chunks by the hash of the key columns into [`SpillFile`]s, so that the rows
+/// with the same key are spilled into the same partition.
+pub struct SpillPartitioner {
+    writers: Vec<SpillFileWriter>,
+    key_idxs: Vec<usize>,
+    /// The seed of the hash function. The rows of a partition must be repartitioned with another
+    /// seed, otherwise they will fall into the same partition again.
+    seed: u64,
+}
+
+impl SpillPartitioner {
+    pub async fn new(
+        dir: &Path,
+        partition_count: usize,
+        key_idxs: Vec<usize>,
+        seed: u64,
+    ) -> Result<Self> {
+        let writers = try_join_all((0..partition_count).map(|_| SpillFileWriter::new(dir))).await?;
+        Ok(Self {
+            writers,
+            key_idxs,
+            seed,
+        })
+    }
+
+    pub async fn write(&mut self, chunk: DataChunk) -> Result<()> {
+        let chunk = chunk.compact();
+        let partition_count = self.writers.len();
+        let partitions = chunk
+            .get_hash_values(&self.key_idxs, SpillHasherBuilder(self.seed))
+            .into_iter()
+            .map(|hash_code| hash_code.hash_code() as usize % partition_count)
+            .collect_vec();
+
+        for (partition, writer) in self.writers.iter_mut().enumerate() {
+            let visibility: Bitmap = partitions.iter().map(|&p| p == partition).collect();
+            if visibility.num_high_bits() > 0 {
+                writer.write(chunk.with_visibility(visibility)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn finish(self) -> Result<Vec<SpillFile>> {
+        try_join_all(self.writers.into_iter().map(SpillFileWriter::finish)).await
+    }
+}
+
+/// [`SpillFileExecutor`] outputs the data chunks in a [`SpillFile`].
+pub struct SpillFileExecutor {
+    file: SpillFile,
+    schema: Schema,
+    identity: String,
+}
+
+impl SpillFileExecutor {
+    pub fn new(file: SpillFile, schema: Schema, identity: String) -> Self {
+        Self {
+            file,
+            schema,
+            identity,
+        }
+    }
+}
+
+impl Executor for SpillFileExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl SpillFileExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let SpillFile { file, chunk_count } = self.file;
+        let mut reader = BufReader::new(file);
+        for _ in 0..chunk_count {
+            let len = reader.read_u32_le().await? as usize;
+            let mut encoded = vec![0; len];
+            reader.read_exact(&mut encoded).await?;
+            let proto = ProstDataChunk::decode(encoded.as_slice()).map_err(anyhow::Error::from)?;
+            yield DataChunk::from_protobuf(&proto)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use futures::StreamExt;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spill_partitioner() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut partitioner = SpillPartitioner::new(dir.path(), 4, vec![0], 0)
+            .await
+            .unwrap();
+        partitioner
+            .write(DataChunk::from_pretty(
+                "i i
+                 1 1
+                 2 2
+                 3 3
+                 1 4",
+            ))
+            .await
+            .unwrap();
+        partitioner
+            .write(DataChunk::from_pretty(
+                "i i
+                 2 5
+                 . 6
+                 4 7",
+            ))
+            .await
+            .unwrap();
+
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int32),
+        ]);
+        let mut key_partitions = HashMap::new();
+        let mut row_count = 0;
+        for (partition, file) in partitioner.finish().await.unwrap().into_iter().enumerate() {
+            let executor = Box::new(SpillFileExecutor::new(
+                file,
+                schema.clone(),
+                "SpillFileExecutor".to_string(),
+            ));
+            let mut stream = executor.execute();
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk.unwrap();
+                for row in chunk.rows() {
+                    let key = row.value_at(0).map(|key| key.into_scalar_impl());
+                    // Rows with the same key are spilled into the same partition.
+                    assert_eq!(*key_partitions.entry(key).or_insert(partition), partition);
+                    row_count += 1;
+                }
+            }
+        }
+        assert_eq!(row_count, 7);
+        assert_eq!(key_partitions.len(), 5);
+    }
+}
@@ -224,6 +224,18 @@ pub async fn diff_executor_output(actual: BoxedExecutor, expect: BoxedExecutor)
     is_data_chunk_eq(&expect, &actual)
 }
 
+/// Collects the output rows in their debug format, sorted to be compared regardless of order.
+pub async fn collect_sorted_rows(executor: BoxedExecutor) -> Vec<String> {
+    let mut rows = vec![];
+    #[for_await]
+    for chunk in executor.execute() {
+        let chunk = chunk.unwrap().compact();
+        rows.extend(chunk.rows().map(|row| format!("{:?}", row.to_owned_row())));
+    }
+    rows.sort();
+    rows
+}
+
 fn is_data_chunk_eq(left: &DataChunk, right: &DataChunk) {
     assert!(left.visibility().is_none());
     assert!(right.visibility().is_none());
@@ -32,7 +32,11 @@ use crate::executor::{
 use crate::task::BatchTaskContext;
 /// [`UpdateExecutor`] implements table updation with values from its child executor and given
 /// expressions.
-// TODO: multiple `UPDATE`s in a single epoch may cause problems. Need validation on materialize.
+///
+/// The rows are read from the committed snapshot, so the frontend flushes the previous writes of
+/// the session before `UPDATE` to avoid retracting stale values.
+///
+/// It outputs the number of updated rows, or the rows with the new values for `RETURNING`.
 // TODO: concurrent `UPDATE` may cause problems. A scheduler might be required.
 pub struct UpdateExecutor {
     /// Target table id.
@@ -42,6 +46,7 @@ pub struct UpdateExecutor {
     exprs: Vec<BoxedExpression>,
     schema: Schema,
     identity: String,
+    returning: bool,
 }
 
 impl UpdateExecutor {
@@ -51,6 +56,7 @@ impl UpdateExecutor {
         child: BoxedExecutor,
         exprs: Vec<BoxedExpression>,
         identity: String,
+        returning: bool,
     ) -> Self {
         assert_eq!(
             child.schema().data_types(),
@@ -58,16 +64,21 @@ impl UpdateExecutor {
             "bad update schema"
         );
 
+        let schema = if returning {
+            child.schema().clone()
+        } else {
+            Schema {
+                fields: vec![Field::unnamed(DataType::Int64)],
+            }
+        };
         Self {
             table_id,
             source_manager,
             child,
             exprs,
-            // TODO: support `RETURNING`
-            schema: Schema {
-                fields: vec![Field::unnamed(DataType::Int64)],
-            },
+            schema,
             identity,
+            returning,
         }
     }
 }
@@ -94,6 +105,7 @@ impl UpdateExecutor {
 
         let schema = self.child.schema().clone();
         let mut notifiers = Vec::new();
+        let mut returning_chunks = Vec::new();
 
         #[for_await]
         for data_chunk in self.child.execute() {
@@ -109,6 +121,9 @@ impl UpdateExecutor {
 
                 DataChunk::new(columns, len)
             };
+            if self.returning {
+                returning_chunks.push(updated_data_chunk.clone());
+            }
 
             // Merge two data chunks into (U-, U+) pairs.
             // TODO: split chunks
@@ -146,6 +161,13 @@ impl UpdateExecutor {
             .sum::<usize>()
             / 2;
 
+        if self.returning {
+            for chunk in returning_chunks {
+                yield chunk;
+            }
+            return Ok(());
+        }
+
         // Create ret value
         {
             let mut array_builder = PrimitiveArrayBuilder::<i64>::new(1);
@@ -186,6 +208,7 @@ impl BoxedExecutorBuilder for UpdateExecutor {
             child,
             exprs,
             source.plan_node().get_identity().clone(),
+            update_node.returning,
         )))
     }
 }
@@ -257,6 +280,7 @@ mod tests {
             Box::new(mock_executor),
             exprs,
             "UpdateExecutor".to_string(),
+            false,
         ));
 
         let handle = tokio::spawn(async move {
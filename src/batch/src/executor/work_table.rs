@@ -0,0 +1,141 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use futures_async_stream::try_stream;
+use parking_lot::Mutex;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::error::BatchError;
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+/// The rows produced by the last iteration of a recursive CTE, which are written by the
+/// [`super::RecursiveUnionExecutor`] and read by the [`WorkTableExecutor`]s with the same id.
+#[derive(Clone, Default)]
+pub struct WorkTable(Arc<Mutex<Vec<DataChunk>>>);
+
+impl WorkTable {
+    pub fn set(&self, chunks: Vec<DataChunk>) {
+        *self.0.lock() = chunks;
+    }
+
+    pub fn chunks(&self) -> Vec<DataChunk> {
+        self.0.lock().clone()
+    }
+}
+
+/// The work tables of the recursive CTEs enclosing the executors being built. Each
+/// [`super::RecursiveUnionExecutor`] builds its recursive term with a new scope, so that the
+/// recursive unions duplicated in a plan don't share the work table.
+#[derive(Clone, Default)]
+pub struct WorkTables(HashMap<u32, WorkTable>);
+
+impl WorkTables {
+    pub fn get(&self, id: u32) -> Option<&WorkTable> {
+        self.0.get(&id)
+    }
+
+    /// Returns a new scope with the work table of `id` added.
+    #[must_use]
+    pub fn with(&self, id: u32, work_table: WorkTable) -> Self {
+        let mut work_tables = self.0.clone();
+        work_tables.insert(id, work_table);
+        Self(work_tables)
+    }
+}
+
+/// [`WorkTableExecutor`] outputs the rows in the work table at the time it's executed.
+pub struct WorkTableExecutor {
+    work_table: WorkTable,
+    schema: Schema,
+    identity: String,
+}
+
+impl WorkTableExecutor {
+    pub fn new(work_table: WorkTable, schema: Schema, identity: String) -> Self {
+        Self {
+            work_table,
+            schema,
+            identity,
+        }
+    }
+}
+
+impl Executor for WorkTableExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl WorkTableExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        for chunk in self.work_table.chunks() {
+            yield chunk;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for WorkTableExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<'_, C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        ensure!(inputs.is_empty(), "WorkTableExecutor should have no child!");
+        let work_table_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::WorkTable
+        )?;
+
+        let fields = work_table_node
+            .get_fields()
+            .iter()
+            .map(Field::from)
+            .collect::<Vec<Field>>();
+
+        let work_table = source
+            .work_tables()
+            .get(work_table_node.work_table_id)
+            .ok_or_else(|| {
+                BatchError::Internal(anyhow!(
+                    "work table {} not found",
+                    work_table_node.work_table_id
+                ))
+            })?;
+
+        Ok(Box::new(Self::new(
+            work_table.clone(),
+            Schema { fields },
+            source.plan_node().get_identity().clone(),
+        )))
+    }
+}
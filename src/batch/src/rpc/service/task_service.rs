@@ -24,6 +24,7 @@ use risingwave_pb::task_service::{
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
+use crate::executor::ExecutorStatsCollector;
 use crate::rpc::service::exchange::GrpcExchangeWriter;
 use crate::task::{
     self, BatchEnvironment, BatchManager, BatchTaskExecution, ComputeNodeContext, TaskId,
@@ -57,18 +58,23 @@ impl TaskService for BatchServiceImpl {
             task_id,
             plan,
             epoch,
+            collect_executor_stats,
         } = request.into_inner();
 
+        let mut context = ComputeNodeContext::new(
+            self.env.clone(),
+            TaskId::from(task_id.as_ref().expect("no task id found")),
+        );
+        if collect_executor_stats {
+            context = context.with_executor_stats(ExecutorStatsCollector::new());
+        }
         let res = self
             .mgr
             .fire_task(
                 task_id.as_ref().expect("no task id found"),
                 plan.expect("no plan found").clone(),
                 epoch,
-                ComputeNodeContext::new(
-                    self.env.clone(),
-                    TaskId::from(task_id.as_ref().expect("no task id found")),
-                ),
+                context,
             )
             .await;
         match res {
@@ -108,10 +114,15 @@ impl TaskService for BatchServiceImpl {
             task_id,
             plan,
             epoch,
+            collect_executor_stats,
         } = req.into_inner();
         let task_id = task_id.expect("no task id found");
         let plan = plan.expect("no plan found").clone();
-        let context = ComputeNodeContext::new_for_local(self.env.clone());
+        let executor_stats = collect_executor_stats.then(ExecutorStatsCollector::new);
+        let mut context = ComputeNodeContext::new_for_local(self.env.clone());
+        if let Some(collector) = &executor_stats {
+            context = context.with_executor_stats(collector.clone());
+        }
         trace!(
             "local execute request: plan:{:?} with task id:{:?}",
             plan,
@@ -147,11 +158,23 @@ impl TaskService for BatchServiceImpl {
         let finish = output
             .take_data_with_num(&mut writer, tx.capacity())
             .await?;
-        if !finish {
+        if !finish || executor_stats.is_some() {
             self.mgr.runtime().spawn(async move {
-                match output.take_data(&mut writer).await {
-                    Ok(_) => Ok(()),
-                    Err(e) => tx.send(Err(e.into())).await,
+                if !finish {
+                    if let Err(e) = output.take_data(&mut writer).await {
+                        return tx.send(Err(e.into())).await;
+                    }
+                }
+                // All data has been sent, report the executor stats in the last response.
+                match executor_stats {
+                    Some(collector) => {
+                        tx.send(Ok(GetDataResponse {
+                            executor_stats: collector.to_protobuf(),
+                            ..Default::default()
+                        }))
+                        .await
+                    }
+                    None => Ok(()),
                 }
             });
         }
@@ -21,8 +21,8 @@ use risingwave_source::TableSourceManagerRef;
 use risingwave_storage::StateStoreImpl;
 
 use super::TaskId;
-use crate::executor::BatchTaskMetricsWithTaskLabels;
-use crate::task::{BatchEnvironment, TaskOutput, TaskOutputId};
+use crate::executor::{BatchTaskMetricsWithTaskLabels, ExecutorStatsCollector};
+use crate::task::{BatchEnvironment, BatchMemoryTrackerRef, TaskOutput, TaskOutputId};
 
 /// Context for batch task execution.
 ///
@@ -47,12 +47,20 @@ pub trait BatchTaskContext: Clone + Send + Sync + 'static {
     /// None indicates that not collect task metrics.
     fn task_metrics(&self) -> Option<BatchTaskMetricsWithTaskLabels>;
 
+    /// Get the collector of executor stats.
+    /// None indicates that not collect executor stats, which is only needed by `EXPLAIN ANALYZE`.
+    fn executor_stats(&self) -> Option<ExecutorStatsCollector>;
+
     /// Get compute client pool. This is used in grpc exchange to avoid creating new compute client
     /// for each grpc call.
     fn client_pool(&self) -> ComputeClientPoolRef;
 
     /// Get config for batch environment
     fn get_config(&self) -> &BatchConfig;
+
+    /// Get the tracker of the memory held by the batch executors in the node.
+    /// None indicates that the memory is only limited per executor.
+    fn memory_tracker(&self) -> Option<BatchMemoryTrackerRef>;
 }
 
 /// Batch task context on compute node.
@@ -61,6 +69,7 @@ pub struct ComputeNodeContext {
     env: BatchEnvironment,
     // None: Local mode don't record metrics.
     task_metrics: Option<BatchTaskMetricsWithTaskLabels>,
+    executor_stats: Option<ExecutorStatsCollector>,
 }
 
 impl BatchTaskContext for ComputeNodeContext {
@@ -90,6 +99,10 @@ impl BatchTaskContext for ComputeNodeContext {
         self.task_metrics.clone()
     }
 
+    fn executor_stats(&self) -> Option<ExecutorStatsCollector> {
+        self.executor_stats.clone()
+    }
+
     fn client_pool(&self) -> ComputeClientPoolRef {
         self.env.client_pool()
     }
@@ -97,6 +110,10 @@ impl BatchTaskContext for ComputeNodeContext {
     fn get_config(&self) -> &BatchConfig {
         self.env.config()
     }
+
+    fn memory_tracker(&self) -> Option<BatchMemoryTrackerRef> {
+        Some(self.env.memory_tracker())
+    }
 }
 
 impl ComputeNodeContext {
@@ -105,6 +122,7 @@ impl ComputeNodeContext {
         Self {
             env: BatchEnvironment::for_test(),
             task_metrics: None,
+            executor_stats: None,
         }
     }
 
@@ -113,6 +131,7 @@ impl ComputeNodeContext {
         Self {
             env,
             task_metrics: Some(task_metrics),
+            executor_stats: None,
         }
     }
 
@@ -120,6 +139,13 @@ impl ComputeNodeContext {
         Self {
             env,
             task_metrics: None,
+            executor_stats: None,
         }
     }
+
+    /// Collects the runtime stats of executors in this task.
+    pub fn with_executor_stats(mut self, collector: ExecutorStatsCollector) -> Self {
+        self.executor_stats = Some(collector);
+        self
+    }
 }
@@ -21,7 +21,7 @@ use risingwave_source::{TableSourceManager, TableSourceManagerRef};
 use risingwave_storage::StateStoreImpl;
 
 use crate::executor::BatchTaskMetrics;
-use crate::task::BatchManager;
+use crate::task::{BatchManager, BatchMemoryTracker, BatchMemoryTrackerRef};
 
 pub(crate) type WorkerNodeId = u32;
 
@@ -52,6 +52,9 @@ pub struct BatchEnvironment {
 
     /// Compute client pool for grpc exchange.
     client_pool: ComputeClientPoolRef,
+
+    /// Memory held by the batch executors in this compute node.
+    memory_tracker: BatchMemoryTrackerRef,
 }
 
 impl BatchEnvironment {
@@ -66,6 +69,7 @@ impl BatchEnvironment {
         task_metrics: Arc<BatchTaskMetrics>,
         client_pool: ComputeClientPoolRef,
     ) -> Self {
+        let memory_tracker = Arc::new(BatchMemoryTracker::new(config.memory_limit_bytes));
         BatchEnvironment {
             server_addr,
             task_manager,
@@ -75,6 +79,7 @@ impl BatchEnvironment {
             state_store,
             task_metrics,
             client_pool,
+            memory_tracker,
         }
     }
 
@@ -95,6 +100,9 @@ impl BatchEnvironment {
             )),
             task_metrics: Arc::new(BatchTaskMetrics::for_test()),
             client_pool: Arc::new(ComputeClientPool::default()),
+            memory_tracker: Arc::new(BatchMemoryTracker::new(
+                BatchConfig::default().memory_limit_bytes,
+            )),
         }
     }
 
@@ -133,4 +141,8 @@ impl BatchEnvironment {
     pub fn client_pool(&self) -> ComputeClientPoolRef {
         self.client_pool.clone()
     }
+
+    pub fn memory_tracker(&self) -> BatchMemoryTrackerRef {
+        self.memory_tracker.clone()
+    }
 }
@@ -0,0 +1,119 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Tracks the memory held by the memory-intensive batch executors in the compute node, e.g. the
+/// build sides of the hash joins, so that they spill to disk before the node runs out of memory.
+#[derive(Debug)]
+pub struct BatchMemoryTracker {
+    /// The memory currently held by all executors.
+    used_bytes: AtomicUsize,
+    /// The total memory that can be held by all executors.
+    limit_bytes: usize,
+}
+
+pub type BatchMemoryTrackerRef = Arc<BatchMemoryTracker>;
+
+impl BatchMemoryTracker {
+    pub fn new(limit_bytes: usize) -> Self {
+        Self {
+            used_bytes: AtomicUsize::new(0),
+            limit_bytes,
+        }
+    }
+
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn limit_bytes(&self) -> usize {
+        self.limit_bytes
+    }
+}
+
+/// The memory held by one executor, which is released from the [`BatchMemoryTracker`] on drop.
+#[derive(Debug)]
+pub struct MemoryReservation {
+    tracker: Option<BatchMemoryTrackerRef>,
+    /// The memory held by this executor.
+    bytes: usize,
+    /// The memory that can be held by this executor.
+    limit_bytes: usize,
+}
+
+impl MemoryReservation {
+    /// Creates a reservation limited by `limit_bytes`, and by the node-wide limit if a tracker is
+    /// given.
+    pub fn new(tracker: Option<BatchMemoryTrackerRef>, limit_bytes: usize) -> Self {
+        Self {
+            tracker,
+            bytes: 0,
+            limit_bytes,
+        }
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Accounts `bytes` more memory held by the executor. Returns `false` if the memory exceeds
+    /// the limit of this reservation or the node-wide limit, in which case the executor should
+    /// release some memory, e.g., by spilling to disk.
+    pub fn grow(&mut self, bytes: usize) -> bool {
+        self.bytes += bytes;
+        let within_node_limit = match &self.tracker {
+            Some(tracker) => {
+                let used_bytes = tracker.used_bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+                used_bytes <= tracker.limit_bytes
+            }
+            None => true,
+        };
+        within_node_limit && self.bytes <= self.limit_bytes
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        if let Some(tracker) = &self.tracker {
+            tracker.used_bytes.fetch_sub(self.bytes, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_reservation() {
+        let tracker = Arc::new(BatchMemoryTracker::new(100));
+
+        let mut reservation_1 = MemoryReservation::new(Some(tracker.clone()), 60);
+        assert!(reservation_1.grow(50));
+        assert!(!reservation_1.grow(20));
+
+        let mut reservation_2 = MemoryReservation::new(Some(tracker.clone()), 60);
+        assert!(!reservation_2.grow(40));
+        assert_eq!(tracker.used_bytes(), 110);
+
+        drop(reservation_1);
+        assert_eq!(tracker.used_bytes(), 40);
+        assert!(reservation_2.grow(10));
+
+        drop(reservation_2);
+        assert_eq!(tracker.used_bytes(), 0);
+    }
+}
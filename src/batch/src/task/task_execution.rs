@@ -138,6 +138,7 @@ impl TaskOutput {
                     let resp = GetDataResponse {
                         status: Default::default(),
                         record_batch: Some(pb),
+                        ..Default::default()
                     };
                     writer.write(resp).await?;
                 }
@@ -390,12 +391,18 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
                 .await
                 .map_err(|_| SenderError)
         } else {
+            // Report the executor stats once the task ends.
+            let executor_stats = match (task_status, self.context.executor_stats()) {
+                (TaskStatus::Running, _) | (_, None) => vec![],
+                (_, Some(collector)) => collector.to_protobuf(),
+            };
             // Notify frontend the task status.
             state_tx
                 .send(Ok(TaskInfoResponse {
                     task_info: Some(TaskInfo {
                         task_id: Some(TaskId::default().to_prost()),
                         task_status: task_status.into(),
+                        executor_stats,
                     }),
                     // TODO: Fill the real status.
                     ..Default::default()
@@ -449,6 +456,8 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
             }
             }
         }
+        // Drop the executors, so that their stats are reported before the task status.
+        drop(data_chunk_stream);
 
         *self.state.lock() = state;
         if let Err(e) = sender.send(None).await {
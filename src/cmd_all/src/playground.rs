@@ -148,7 +148,11 @@ pub async fn playground() -> Result<()> {
         Err(e) => {
             tracing::warn!("Failed to load risedev config. All components will be started using the default command line options.\n{}", e);
             vec![
-                RisingWaveService::Meta(vec!["--backend".into(), "mem".into()]),
+                RisingWaveService::Meta(vec![
+                    "--backend".into(),
+                    "mem".into(),
+                    "--disable-telemetry".into(),
+                ]),
                 RisingWaveService::Compute(vec!["--state-store".into(), "hummock+memory".into()]),
                 RisingWaveService::Frontend(vec![]),
             ]
@@ -17,6 +17,7 @@ use risingwave_pb::data::{Array as ProstArray, ArrayType};
 use super::{Array, ArrayBuilder, ArrayIterator, ArrayMeta};
 use crate::array::ArrayBuilderImpl;
 use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::collection::estimate_size::EstimateSize;
 
 #[derive(Debug, Clone)]
 pub struct BoolArray {
@@ -24,6 +25,12 @@ pub struct BoolArray {
     data: Bitmap,
 }
 
+impl EstimateSize for BoolArray {
+    fn estimated_heap_size(&self) -> usize {
+        self.bitmap.estimated_heap_size() + self.data.estimated_heap_size()
+    }
+}
+
 impl BoolArray {
     pub fn new(bitmap: Bitmap, data: Bitmap) -> Self {
         assert_eq!(bitmap.len(), data.len());
@@ -25,6 +25,7 @@ use crate::array::column::Column;
 use crate::array::data_chunk_iter::{Row, RowRef};
 use crate::array::{ArrayBuilderImpl, StructValue};
 use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::collection::estimate_size::EstimateSize;
 use crate::hash::HashCode;
 use crate::types::struct_type::StructType;
 use crate::types::to_text::ToText;
@@ -40,6 +41,22 @@ pub struct DataChunk {
     vis2: Vis,
 }
 
+impl EstimateSize for DataChunk {
+    fn estimated_heap_size(&self) -> usize {
+        let visibility_size = match &self.vis2 {
+            Vis::Bitmap(bitmap) => bitmap.estimated_heap_size(),
+            Vis::Compact(_) => 0,
+        };
+        self.columns.capacity() * std::mem::size_of::<Column>()
+            + self
+                .columns
+                .iter()
+                .map(|column| column.array_ref().estimated_size())
+                .sum::<usize>()
+            + visibility_size
+    }
+}
+
 /// `Vis` is a visibility bitmap of rows. When all rows are visible, it is considered compact and
 /// is represented by a single cardinality number rather than that many of ones.
 #[derive(Clone, PartialEq, Debug)]
@@ -403,9 +403,16 @@ impl RowDeserializer {
     }
 
     /// Deserialize the row from value encoding bytes.
+    ///
+    /// Columns may be appended to a table by `ALTER TABLE`, so the rows written before that end
+    /// early. The missing trailing columns are deserialized as nulls.
     pub fn deserialize(&self, mut data: impl bytes::Buf) -> value_encoding::Result<Row> {
         let mut values = Vec::with_capacity(self.data_types.len());
         for typ in &self.data_types {
+            if !data.has_remaining() {
+                values.push(None);
+                continue;
+            }
             values.push(deserialize_datum(&mut data, typ)?);
         }
         Ok(Row(values))
@@ -453,6 +460,15 @@ mod tests {
         assert_eq!(row, row1);
     }
 
+    #[test]
+    fn row_value_decode_with_appended_columns() {
+        let row = Row(vec![Some(ScalarImpl::Int32(1)), None]);
+        let bytes = row.serialize(&None);
+        let de = RowDeserializer::new(vec![Ty::Int32, Ty::Varchar, Ty::Int64]);
+        let row1 = de.deserialize(bytes.as_ref()).unwrap();
+        assert_eq!(row1, Row(vec![Some(ScalarImpl::Int32(1)), None, None]));
+    }
+
     #[test]
     fn test_hash_row() {
         let hash_builder = Crc32FastBuilder {};
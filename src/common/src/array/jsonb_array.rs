@@ -0,0 +1,192 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_pb::data::{Array as ProstArray, ArrayType};
+use serde_json::Value;
+
+use super::value_reader::Utf8ValueReader;
+use super::{
+    read_string_array, Array, ArrayBuilder, ArrayBuilderImpl, ArrayError, ArrayImpl, ArrayIterator,
+    ArrayMeta, ArrayResult, Utf8ArrayBuilder,
+};
+use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::collection::estimate_size::EstimateSize;
+use crate::types::{JsonbRef, JsonbVal, Scalar};
+
+/// `JsonbArray` is a collection of parsed JSON values.
+#[derive(Debug, Clone)]
+pub struct JsonbArray {
+    bitmap: Bitmap,
+    data: Vec<Value>,
+}
+
+impl EstimateSize for JsonbArray {
+    fn estimated_heap_size(&self) -> usize {
+        // FIXME: the heap size of the json values is not counted.
+        self.bitmap.estimated_heap_size() + self.data.capacity() * std::mem::size_of::<Value>()
+    }
+}
+
+impl Array for JsonbArray {
+    type Builder = JsonbArrayBuilder;
+    type Iter<'a> = ArrayIterator<'a, Self>;
+    type OwnedItem = JsonbVal;
+    type RefItem<'a> = JsonbRef<'a>;
+
+    fn value_at(&self, idx: usize) -> Option<JsonbRef<'_>> {
+        if !self.is_null(idx) {
+            Some(JsonbRef::new(&self.data[idx]))
+        } else {
+            None
+        }
+    }
+
+    unsafe fn value_at_unchecked(&self, idx: usize) -> Option<JsonbRef<'_>> {
+        if !self.is_null_unchecked(idx) {
+            Some(JsonbRef::new(self.data.get_unchecked(idx)))
+        } else {
+            None
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        ArrayIterator::new(self)
+    }
+
+    /// Jsonb values are transferred in their text form, with the same layout as `Utf8Array`.
+    fn to_protobuf(&self) -> ProstArray {
+        let mut builder = Utf8ArrayBuilder::new(self.len());
+        for value in self.iter() {
+            builder.append(value.map(|v| v.to_string()).as_deref());
+        }
+        ProstArray {
+            array_type: ArrayType::Jsonb as i32,
+            ..builder.finish().to_protobuf()
+        }
+    }
+
+    fn null_bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    fn into_null_bitmap(self) -> Bitmap {
+        self.bitmap
+    }
+
+    fn set_bitmap(&mut self, bitmap: Bitmap) {
+        self.bitmap = bitmap;
+    }
+
+    fn create_builder(&self, capacity: usize) -> ArrayBuilderImpl {
+        let array_builder = JsonbArrayBuilder::new(capacity);
+        ArrayBuilderImpl::Jsonb(array_builder)
+    }
+}
+
+impl JsonbArray {
+    pub fn from_protobuf(array: &ProstArray, cardinality: usize) -> ArrayResult<ArrayImpl> {
+        let text_array =
+            read_string_array::<Utf8ArrayBuilder, Utf8ValueReader>(array, cardinality)?.into_utf8();
+        let mut builder = JsonbArrayBuilder::new(cardinality);
+        for text in text_array.iter() {
+            let value = text
+                .map(|s| s.parse::<JsonbVal>())
+                .transpose()
+                .map_err(|e| {
+                    ArrayError::internal(format!("failed to read jsonb from string: {}", e))
+                })?;
+            builder.append(value.as_ref().map(|v| v.as_scalar_ref()));
+        }
+        Ok(builder.finish().into())
+    }
+}
+
+/// `JsonbArrayBuilder` use `JsonbRef` to build a `JsonbArray`.
+#[derive(Debug)]
+pub struct JsonbArrayBuilder {
+    bitmap: BitmapBuilder,
+    data: Vec<Value>,
+}
+
+impl ArrayBuilder for JsonbArrayBuilder {
+    type ArrayType = JsonbArray;
+
+    fn with_meta(capacity: usize, _meta: ArrayMeta) -> Self {
+        Self {
+            bitmap: BitmapBuilder::with_capacity(capacity),
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    fn append(&mut self, value: Option<JsonbRef<'_>>) {
+        match value {
+            Some(x) => {
+                self.bitmap.append(true);
+                self.data.push(x.value().clone());
+            }
+            None => {
+                self.bitmap.append(false);
+                self.data.push(Value::Null);
+            }
+        }
+    }
+
+    fn append_array(&mut self, other: &JsonbArray) {
+        for bit in other.bitmap.iter() {
+            self.bitmap.append(bit);
+        }
+        self.data.extend_from_slice(&other.data);
+    }
+
+    fn pop(&mut self) -> Option<()> {
+        self.data.pop().map(|_| self.bitmap.pop().unwrap())
+    }
+
+    fn finish(self) -> JsonbArray {
+        JsonbArray {
+            bitmap: self.bitmap.finish(),
+            data: self.data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonb_array_protobuf() {
+        let values = [
+            Some(r#"{"a": [1, null]}"#.parse::<JsonbVal>().unwrap()),
+            None,
+            Some("null".parse().unwrap()),
+        ];
+        let mut builder = JsonbArrayBuilder::new(values.len());
+        for v in &values {
+            builder.append(v.as_ref().map(|v| v.as_scalar_ref()));
+        }
+        let array = builder.finish();
+        assert_eq!(array.value_at(1), None);
+        assert!(array.value_at(2).unwrap().is_jsonb_null());
+
+        let decoded = ArrayImpl::from_protobuf(&array.to_protobuf(), values.len())
+            .unwrap()
+            .into_jsonb();
+        assert!(array.iter().eq(decoded.iter()));
+    }
+}
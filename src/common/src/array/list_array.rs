@@ -27,6 +27,7 @@ use super::{
     Array, ArrayBuilder, ArrayBuilderImpl, ArrayImpl, ArrayIterator, ArrayMeta, ArrayResult, RowRef,
 };
 use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::collection::estimate_size::EstimateSize;
 use crate::types::to_text::ToText;
 use crate::types::{
     deserialize_datum_from, hash_datum_ref, serialize_datum_ref_into, to_datum_ref, DataType,
@@ -154,6 +155,14 @@ pub struct ListArray {
     len: usize,
 }
 
+impl EstimateSize for ListArray {
+    fn estimated_heap_size(&self) -> usize {
+        self.bitmap.estimated_heap_size()
+            + self.offsets.capacity() * std::mem::size_of::<usize>()
+            + self.value.estimated_size()
+    }
+}
+
 impl Array for ListArray {
     type Builder = ListArrayBuilder;
     type Iter<'a> = ArrayIterator<'a, Self>;
@@ -23,6 +23,7 @@ use risingwave_pb::data::{Array as ProstArray, ArrayType};
 use super::{Array, ArrayBuilder, ArrayIterator, ArrayResult};
 use crate::array::{ArrayBuilderImpl, ArrayImpl, ArrayMeta};
 use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::collection::estimate_size::EstimateSize;
 use crate::for_all_native_types;
 use crate::types::interval::IntervalUnit;
 use crate::types::{
@@ -130,6 +131,12 @@ pub struct PrimitiveArray<T: PrimitiveArrayItemType> {
     data: Vec<T>,
 }
 
+impl<T: PrimitiveArrayItemType> EstimateSize for PrimitiveArray<T> {
+    fn estimated_heap_size(&self) -> usize {
+        self.bitmap.estimated_heap_size() + self.data.capacity() * size_of::<T>()
+    }
+}
+
 impl<T: PrimitiveArrayItemType> PrimitiveArray<T> {
     pub fn from_slice(data: &[Option<T>]) -> Self {
         let mut builder = <Self as Array>::Builder::new(data.len());
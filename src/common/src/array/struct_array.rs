@@ -27,6 +27,7 @@ use super::{
 };
 use crate::array::ArrayRef;
 use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::collection::estimate_size::EstimateSize;
 use crate::types::to_text::ToText;
 use crate::types::{
     deserialize_datum_from, hash_datum_ref, serialize_datum_ref_into, to_datum_ref, DataType,
@@ -140,6 +141,17 @@ pub struct StructArray {
     len: usize,
 }
 
+impl EstimateSize for StructArray {
+    fn estimated_heap_size(&self) -> usize {
+        self.bitmap.estimated_heap_size()
+            + self
+                .children
+                .iter()
+                .map(|child| child.estimated_size())
+                .sum::<usize>()
+    }
+}
+
 impl StructArrayBuilder {
     pub fn append_array_refs(&mut self, refs: Vec<ArrayRef>, len: usize) {
         for _ in 0..len {
@@ -23,6 +23,7 @@ use risingwave_pb::data::{Array as ProstArray, ArrayType};
 use super::{Array, ArrayBuilder, ArrayIterator, ArrayMeta, ArrayResult};
 use crate::array::ArrayBuilderImpl;
 use crate::buffer::{Bitmap, BitmapBuilder};
+use crate::collection::estimate_size::EstimateSize;
 
 /// `Utf8Array` is a collection of Rust Utf8 `String`s.
 #[derive(Debug, Clone)]
@@ -32,6 +33,14 @@ pub struct Utf8Array {
     data: Vec<u8>,
 }
 
+impl EstimateSize for Utf8Array {
+    fn estimated_heap_size(&self) -> usize {
+        self.offset.capacity() * size_of::<usize>()
+            + self.bitmap.estimated_heap_size()
+            + self.data.capacity()
+    }
+}
+
 impl Array for Utf8Array {
     type Builder = Utf8ArrayBuilder;
     type Iter<'a> = ArrayIterator<'a, Self>;
@@ -40,6 +40,7 @@ use itertools::Itertools;
 use risingwave_pb::common::buffer::CompressionType;
 use risingwave_pb::common::Buffer as ProstBuffer;
 
+use crate::collection::estimate_size::EstimateSize;
 use crate::util::bit_util;
 
 #[derive(Default, Debug)]
@@ -160,6 +161,12 @@ pub struct Bitmap {
     num_high_bits: usize,
 }
 
+impl EstimateSize for Bitmap {
+    fn estimated_heap_size(&self) -> usize {
+        self.bits.len()
+    }
+}
+
 impl std::fmt::Debug for Bitmap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[")?;
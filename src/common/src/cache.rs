@@ -733,6 +733,26 @@ impl<K: LruKey, T: LruValue> LruCache<K, T> {
             .sum()
     }
 
+    /// Changes the capacity of the cache. Unreferenced entries exceeding the new capacity are
+    /// evicted immediately, while referenced entries are evicted after they are released.
+    pub fn set_capacity(&self, capacity: usize) {
+        let per_shard = capacity / self.shards.len();
+        let mut to_delete = vec![];
+        for shard in &self.shards {
+            let mut shard = shard.lock();
+            shard.capacity = per_shard;
+            unsafe {
+                shard.evict_from_lru(0, &mut to_delete);
+            }
+        }
+        // do not deallocate data with holding mutex.
+        if let Some(listener) = &self.listener {
+            for (key, value) in to_delete {
+                listener.on_release(key, value);
+            }
+        }
+    }
+
     fn shard(&self, hash: u64) -> usize {
         hash as usize % self.shards.len()
     }
@@ -1043,6 +1063,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_capacity() {
+        let cache = Arc::new(LruCache::<String, String>::new(0, 10));
+        for key in ["k1", "k2", "k3", "k4", "k5"] {
+            cache.insert(key.to_string(), 0, 2, key.to_string());
+        }
+        assert_eq!(cache.get_memory_usage(), 10);
+
+        let h5 = cache.lookup(0, &"k5".to_string()).unwrap();
+        cache.set_capacity(4);
+        assert_eq!(cache.get_memory_usage(), 4);
+        assert!(cache.lookup(0, &"k4".to_string()).is_some());
+        assert!(cache.lookup(0, &"k3".to_string()).is_none());
+
+        // The referenced entry is evicted after released, since the cache is over-sized.
+        cache.set_capacity(1);
+        assert_eq!(cache.get_memory_usage(), 2);
+        drop(h5);
+        assert_eq!(cache.get_memory_usage(), 0);
+    }
+
     #[test]
     fn test_update_referenced_key() {
         unsafe {
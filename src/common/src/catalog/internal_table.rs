@@ -33,6 +33,15 @@ pub fn generate_internal_table_name_with_type(
     )
 }
 
+/// Extracts the lowercased table type from a name generated by
+/// [`generate_internal_table_name_with_type`], e.g. `hashjoinleft`.
+pub fn internal_table_type(table_name: &str) -> Option<&str> {
+    if valid_table_name(table_name) {
+        return None;
+    }
+    table_name.rsplitn(3, '_').nth(1)
+}
+
 pub fn valid_table_name(table_name: &str) -> bool {
     static INTERNAL_TABLE_NAME: LazyLock<Regex> =
         LazyLock::new(|| Regex::new(r"__internal_.*_\d+").unwrap());
@@ -35,6 +35,8 @@ use crate::error::Result;
 pub const DEFAULT_DATABASE_NAME: &str = "dev";
 pub const DEFAULT_SCHEMA_NAME: &str = "public";
 pub const PG_CATALOG_SCHEMA_NAME: &str = "pg_catalog";
+pub const RW_CATALOG_SCHEMA_NAME: &str = "rw_catalog";
+pub const INFORMATION_SCHEMA_SCHEMA_NAME: &str = "information_schema";
 pub const RESERVED_PG_SCHEMA_PREFIX: &str = "pg_";
 pub const DEFAULT_SUPER_USER: &str = "root";
 pub const DEFAULT_SUPER_USER_ID: u32 = 1;
@@ -45,6 +47,17 @@ pub const DEFAULT_SUPER_USER_FOR_PG_ID: u32 = 2;
 pub const NON_RESERVED_USER_ID: i32 = 11;
 pub const NON_RESERVED_PG_CATALOG_TABLE_ID: i32 = 1001;
 
+/// The number of columns prepended to the input columns in the log store of a decoupled sink,
+/// i.e. the epoch, the sequence number in the epoch and the op of each row.
+pub const LOG_STORE_PREFIX_LEN: usize = 3;
+
+/// Whether the schema holds system catalogs, which are read-only and can not be dropped.
+pub fn is_system_schema(schema_name: &str) -> bool {
+    schema_name == PG_CATALOG_SCHEMA_NAME
+        || schema_name == RW_CATALOG_SCHEMA_NAME
+        || schema_name == INFORMATION_SCHEMA_SCHEMA_NAME
+}
+
 /// The local system catalog reader in the frontend node.
 #[async_trait]
 pub trait SysCatalogReader: Sync + Send + 'static {
@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 
+use fixedbitset::FixedBitSet;
 use risingwave_pb::plan_common::{ColumnOrder, StorageTableDesc};
 
 use super::{ColumnDesc, ColumnId, TableId};
@@ -43,6 +44,9 @@ pub struct TableDesc {
     pub retention_seconds: u32,
 
     pub value_indices: Vec<usize>,
+
+    /// The column indices which have watermarks.
+    pub watermark_columns: FixedBitSet,
 }
 
 impl TableDesc {
@@ -58,6 +58,40 @@ impl Default for ServerConfig {
     }
 }
 
+/// The TLS configuration of the pgwire server in the frontend node.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SslConfig {
+    #[serde(default)]
+    pub ssl_mode: SslMode,
+
+    /// The path to the certificate chain in PEM format.
+    #[serde(default = "default::ssl_cert")]
+    pub ssl_cert: String,
+
+    /// The path to the private key in PEM format.
+    #[serde(default = "default::ssl_key")]
+    pub ssl_key: String,
+}
+
+impl Default for SslConfig {
+    fn default() -> Self {
+        toml::from_str("").unwrap()
+    }
+}
+
+/// Whether the client connections are encrypted by TLS.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SslMode {
+    /// Refuse the TLS requests of the clients.
+    Disable,
+    /// Encrypt the connection if the client requests TLS, e.g. with `sslmode=prefer`.
+    #[default]
+    Prefer,
+    /// Reject the connections without TLS.
+    Require,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BatchConfig {
     /// The thread number of the batch task runtime in the compute node. The default value is
@@ -65,6 +99,16 @@ pub struct BatchConfig {
     #[serde(default)]
     pub worker_threads_num: Option<usize>,
 
+    /// The total memory that can be held by the memory-intensive batch executors in the compute
+    /// node, e.g. the build sides of the hash joins. Executors spill to disk when it's exceeded.
+    #[serde(default = "default::batch_memory_limit_bytes")]
+    pub memory_limit_bytes: usize,
+
+    /// The directory for the spill files of batch executors. The temporary directory of the
+    /// system is used if not specified.
+    #[serde(default)]
+    pub spill_dir: Option<String>,
+
     #[serde(default)]
     pub developer: DeveloperConfig,
 }
@@ -251,6 +295,16 @@ pub struct DeveloperConfig {
     #[serde(default = "default::developer::batch_chunk_size")]
     pub batch_chunk_size: usize,
 
+    /// The memory that the build side of a hash join can hold before the join spills its inputs
+    /// to disk partitions.
+    #[serde(default = "default::developer::batch_hash_join_spill_threshold_bytes")]
+    pub batch_hash_join_spill_threshold_bytes: usize,
+
+    /// The memory that the groups of a hash aggregation can hold before the aggregation spills the
+    /// rows of new groups to disk partitions.
+    #[serde(default = "default::developer::batch_hash_agg_spill_threshold_bytes")]
+    pub batch_hash_agg_spill_threshold_bytes: usize,
+
     /// Set to true to enable per-executor row count metrics. This will produce a lot of timeseries
     /// and might affect the prometheus performance. If you only need actor input and output
     /// rows data, see `stream_actor_in_record_cnt` and `stream_actor_out_record_cnt` instead.
@@ -274,6 +328,19 @@ pub struct DeveloperConfig {
     #[serde(default = "default::developer::unsafe_stream_extreme_cache_size")]
     pub unsafe_stream_extreme_cache_size: usize,
 
+    /// Limit number of the cached entries (one per group key) in a group top-n executor.
+    #[serde(default = "default::developer::unsafe_stream_group_top_n_cache_size")]
+    pub unsafe_stream_group_top_n_cache_size: usize,
+
+    /// Limit number of the cached partitions (one per partition key) in an over window executor.
+    #[serde(default = "default::developer::unsafe_stream_over_window_cache_size")]
+    pub unsafe_stream_over_window_cache_size: usize,
+
+    /// Limit number of the rows of a partition to be cached in an over window executor. Larger
+    /// partitions are read from the state table on every change.
+    #[serde(default = "default::developer::unsafe_stream_over_window_cached_partition_rows")]
+    pub unsafe_stream_over_window_cached_partition_rows: usize,
+
     /// The maximum size of the chunk produced by executor at a time.
     #[serde(default = "default::developer::stream_chunk_size")]
     pub stream_chunk_size: usize,
@@ -296,6 +363,14 @@ mod default {
         16
     }
 
+    pub fn ssl_cert() -> String {
+        "src/utils/pgwire/tests/ssl/demo.crt".to_string()
+    }
+
+    pub fn ssl_key() -> String {
+        "src/utils/pgwire/tests/ssl/demo.key".to_string()
+    }
+
     pub fn sst_size_mb() -> u32 {
         256
     }
@@ -374,6 +449,10 @@ mod default {
         sys.total_memory() as usize
     }
 
+    pub fn batch_memory_limit_bytes() -> usize {
+        total_memory_available_bytes() / 4
+    }
+
     pub fn compactor_memory_limit_mb() -> usize {
         512
     }
@@ -427,6 +506,14 @@ mod default {
             1024
         }
 
+        pub fn batch_hash_join_spill_threshold_bytes() -> usize {
+            256 << 20
+        }
+
+        pub fn batch_hash_agg_spill_threshold_bytes() -> usize {
+            256 << 20
+        }
+
         pub fn stream_enable_executor_row_count() -> bool {
             false
         }
@@ -447,6 +534,18 @@ mod default {
             1 << 10
         }
 
+        pub fn unsafe_stream_group_top_n_cache_size() -> usize {
+            1 << 16
+        }
+
+        pub fn unsafe_stream_over_window_cache_size() -> usize {
+            1 << 10
+        }
+
+        pub fn unsafe_stream_over_window_cached_partition_rows() -> usize {
+            1 << 14
+        }
+
         pub fn stream_chunk_size() -> usize {
             1024
         }
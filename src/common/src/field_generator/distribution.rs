@@ -0,0 +1,227 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use rand::Rng;
+
+/// The options of a non-uniform distribution that a random numeric field samples from, such as
+/// `'fields.v1.distribution'='zipf'` and `'fields.v1.exponent'='1.2'`.
+#[derive(Clone, Debug, Default)]
+pub struct DistributionOptions {
+    /// `uniform`, `zipf` or `normal`.
+    pub kind: String,
+    /// The exponent of the zipfian distribution, default 1.0.
+    pub exponent: Option<String>,
+    /// The mean of the normal distribution, default the midpoint of `[min, max]`.
+    pub mean: Option<String>,
+    /// The standard deviation of the normal distribution, default a sixth of `max - min`.
+    pub stddev: Option<String>,
+}
+
+/// The distribution that a random numeric field samples from. Samples always fall in the range
+/// `[min, max]` of the field.
+pub enum Distribution {
+    Uniform,
+    /// Ranks `1..=n` are drawn with a probability proportional to `1 / rank^exponent`, and rank
+    /// `k` is mapped to the value `min + k - 1`, so that small values are the hot ones.
+    Zipf(ZipfSampler),
+    /// Samples out of `[min, max]` are clamped to the bounds.
+    Normal(NormalSampler),
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Distribution::Uniform
+    }
+}
+
+impl Distribution {
+    pub fn new(options: &DistributionOptions, min: f64, max: f64) -> Result<Self> {
+        match options.kind.to_lowercase().as_str() {
+            "uniform" => Ok(Distribution::Uniform),
+            "zipf" => {
+                let exponent = match &options.exponent {
+                    Some(exponent) => exponent.parse::<f64>()?,
+                    None => 1.0,
+                };
+                let n = (max - min).floor() as u64 + 1;
+                Ok(Distribution::Zipf(ZipfSampler::new(n, exponent)?))
+            }
+            "normal" => {
+                let mean = match &options.mean {
+                    Some(mean) => mean.parse::<f64>()?,
+                    None => (min + max) / 2.0,
+                };
+                let stddev = match &options.stddev {
+                    Some(stddev) => stddev.parse::<f64>()?,
+                    None => (max - min) / 6.0,
+                };
+                Ok(Distribution::Normal(NormalSampler::new(mean, stddev)?))
+            }
+            kind => Err(anyhow!("unknown distribution {}", kind)),
+        }
+    }
+}
+
+/// Samples from a bounded zipfian distribution with the rejection-inversion method described in
+/// "Rejection-inversion to generate variates from monotone discrete distributions" by Hörmann and
+/// Derflinger, which takes constant time regardless of the number of ranks.
+pub struct ZipfSampler {
+    n: f64,
+    exponent: f64,
+    h_integral_x1: f64,
+    h_integral_n: f64,
+    s: f64,
+}
+
+impl ZipfSampler {
+    pub fn new(n: u64, exponent: f64) -> Result<Self> {
+        if n == 0 {
+            return Err(anyhow!("zipf distribution requires at least one element"));
+        }
+        if exponent <= 0.0 {
+            return Err(anyhow!(
+                "zipf exponent must be positive, but got {}",
+                exponent
+            ));
+        }
+        let n = n as f64;
+        let mut sampler = Self {
+            n,
+            exponent,
+            h_integral_x1: 0.0,
+            h_integral_n: 0.0,
+            s: 0.0,
+        };
+        sampler.h_integral_x1 = sampler.h_integral(1.5) - 1.0;
+        sampler.h_integral_n = sampler.h_integral(n + 0.5);
+        sampler.s = 2.0 - sampler.h_integral_inverse(sampler.h_integral(2.5) - sampler.h(2.0));
+        Ok(sampler)
+    }
+
+    /// Returns a rank in `1..=n`.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> u64 {
+        loop {
+            let u = self.h_integral_n + rng.gen::<f64>() * (self.h_integral_x1 - self.h_integral_n);
+            let x = self.h_integral_inverse(u);
+            let k = (x + 0.5).floor().clamp(1.0, self.n);
+            if k - x <= self.s || u >= self.h_integral(k + 0.5) - self.h(k) {
+                return k as u64;
+            }
+        }
+    }
+
+    fn h(&self, x: f64) -> f64 {
+        (-self.exponent * x.ln()).exp()
+    }
+
+    fn h_integral(&self, x: f64) -> f64 {
+        let log_x = x.ln();
+        helper2((1.0 - self.exponent) * log_x) * log_x
+    }
+
+    fn h_integral_inverse(&self, x: f64) -> f64 {
+        let t = (x * (1.0 - self.exponent)).max(-1.0);
+        (helper1(t) * x).exp()
+    }
+}
+
+/// `ln(1 + x) / x`, with the limit 1 at 0.
+fn helper1(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.ln_1p() / x
+    } else {
+        1.0 - x * (0.5 - x * (1.0 / 3.0 - 0.25 * x))
+    }
+}
+
+/// `(exp(x) - 1) / x`, with the limit 1 at 0.
+fn helper2(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.exp_m1() / x
+    } else {
+        1.0 + x * 0.5 * (1.0 + x / 3.0 * (1.0 + 0.25 * x))
+    }
+}
+
+/// Samples from a normal distribution with the Box-Muller transform.
+pub struct NormalSampler {
+    mean: f64,
+    stddev: f64,
+}
+
+impl NormalSampler {
+    pub fn new(mean: f64, stddev: f64) -> Result<Self> {
+        if stddev.is_nan() || stddev < 0.0 {
+            return Err(anyhow!(
+                "standard deviation must not be negative, but got {}",
+                stddev
+            ));
+        }
+        Ok(Self { mean, stddev })
+    }
+
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        // `gen` returns a value in [0, 1), so `u1` is in (0, 1] and its logarithm is finite.
+        let u1 = 1.0 - rng.gen::<f64>();
+        let u2 = rng.gen::<f64>();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        self.mean + self.stddev * z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_zipf_sampler() {
+        let sampler = ZipfSampler::new(100, 1.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(1234);
+        let mut counts = vec![0; 101];
+        for _ in 0..10000 {
+            let rank = sampler.sample(&mut rng);
+            assert!((1..=100).contains(&rank));
+            counts[rank as usize] += 1;
+        }
+        // rank 1 is drawn about twice as often as rank 2, and far more often than rank 100
+        assert!(counts[1] > counts[2]);
+        assert!(counts[2] > counts[10]);
+        assert!(counts[1] > 10 * counts[100]);
+
+        let sampler = ZipfSampler::new(1, 2.0).unwrap();
+        assert_eq!(sampler.sample(&mut rng), 1);
+
+        assert!(ZipfSampler::new(10, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_normal_sampler() {
+        let sampler = NormalSampler::new(100.0, 10.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(1234);
+        let samples = (0..10000)
+            .map(|_| sampler.sample(&mut rng))
+            .collect::<Vec<_>>();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!((mean - 100.0).abs() < 1.0);
+        let within_one_stddev = samples
+            .iter()
+            .filter(|x| (**x - 100.0).abs() < 10.0)
+            .count();
+        assert!((6500..7100).contains(&within_one_stddev));
+    }
+}
@@ -12,13 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod distribution;
 mod numeric;
 mod timestamp;
 mod varchar;
 
 use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+pub use distribution::*;
 pub use numeric::*;
 use serde_json::Value;
 pub use timestamp::*;
@@ -35,6 +37,9 @@ pub const DEFAULT_END: i16 = i16::MAX;
 /// default max past for `TimestampField` = 1 day
 pub const DEFAULT_MAX_PAST: Duration = Duration::from_secs(60 * 60 * 24);
 
+/// default step for `TimestampSequenceField` = 1 second
+pub const DEFAULT_STEP: Duration = Duration::from_secs(1);
+
 /// default length for `VarcharField` = 10
 pub const DEFAULT_LENGTH: usize = 10;
 
@@ -83,8 +88,10 @@ pub enum FieldGeneratorImpl {
     I64Random(I64RandomField),
     F32Random(F32RandomField),
     F64Random(F64RandomField),
+    Reference(ReferenceField),
     Varchar(VarcharField),
     Timestamp(TimestampField),
+    TimestampSequence(TimestampSequenceField),
     Struct(Vec<(String, FieldGeneratorImpl)>),
 }
 
@@ -165,6 +172,81 @@ impl FieldGeneratorImpl {
         }
     }
 
+    pub fn with_distribution(
+        data_type: DataType,
+        min: Option<String>,
+        max: Option<String>,
+        distribution: &DistributionOptions,
+        seed: u64,
+    ) -> Result<Self> {
+        match data_type {
+            DataType::Int16 => Ok(FieldGeneratorImpl::I16Random(
+                I16RandomField::new(min, max, seed)?.with_distribution(distribution)?,
+            )),
+            DataType::Int32 => Ok(FieldGeneratorImpl::I32Random(
+                I32RandomField::new(min, max, seed)?.with_distribution(distribution)?,
+            )),
+            DataType::Int64 => Ok(FieldGeneratorImpl::I64Random(
+                I64RandomField::new(min, max, seed)?.with_distribution(distribution)?,
+            )),
+            DataType::Float32 => Ok(FieldGeneratorImpl::F32Random(
+                F32RandomField::new(min, max, seed)?.with_distribution(distribution)?,
+            )),
+            DataType::Float64 => Ok(FieldGeneratorImpl::F64Random(
+                F64RandomField::new(min, max, seed)?.with_distribution(distribution)?,
+            )),
+            _ => Err(anyhow!(
+                "distribution is not supported on the column of type {}",
+                data_type
+            )),
+        }
+    }
+
+    pub fn with_reference(
+        data_type: DataType,
+        start: Option<String>,
+        end: Option<String>,
+        split_index: u64,
+        split_num: u64,
+        seed: u64,
+    ) -> Result<Self> {
+        match data_type {
+            DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+                Ok(FieldGeneratorImpl::Reference(ReferenceField::new(
+                    data_type,
+                    start,
+                    end,
+                    split_index,
+                    split_num,
+                    seed,
+                )?))
+            }
+            _ => Err(anyhow!(
+                "reference is only supported on the columns of integer types, but got {}",
+                data_type
+            )),
+        }
+    }
+
+    pub fn with_pattern(pattern: String, seed: u64) -> Result<Self> {
+        Ok(FieldGeneratorImpl::Varchar(VarcharField::with_pattern(
+            pattern, seed,
+        )?))
+    }
+
+    pub fn with_timestamp_sequence(
+        start: Option<String>,
+        step: Option<String>,
+        jitter: Option<String>,
+        split_index: u64,
+        split_num: u64,
+        seed: u64,
+    ) -> Result<Self> {
+        Ok(FieldGeneratorImpl::TimestampSequence(
+            TimestampSequenceField::new(start, step, jitter, split_index, split_num, seed)?,
+        ))
+    }
+
     pub fn with_struct_fields(fields: Vec<(String, FieldGeneratorImpl)>) -> Result<Self> {
         Ok(FieldGeneratorImpl::Struct(fields))
     }
@@ -181,8 +263,10 @@ impl FieldGeneratorImpl {
             FieldGeneratorImpl::I64Random(f) => f.generate(offset),
             FieldGeneratorImpl::F32Random(f) => f.generate(offset),
             FieldGeneratorImpl::F64Random(f) => f.generate(offset),
+            FieldGeneratorImpl::Reference(f) => f.generate(offset),
             FieldGeneratorImpl::Varchar(f) => f.generate(offset),
             FieldGeneratorImpl::Timestamp(f) => f.generate(offset),
+            FieldGeneratorImpl::TimestampSequence(f) => f.generate(offset),
             FieldGeneratorImpl::Struct(fields) => {
                 let map = fields
                     .iter_mut()
@@ -205,8 +289,10 @@ impl FieldGeneratorImpl {
             FieldGeneratorImpl::I64Random(f) => f.generate_datum(offset),
             FieldGeneratorImpl::F32Random(f) => f.generate_datum(offset),
             FieldGeneratorImpl::F64Random(f) => f.generate_datum(offset),
+            FieldGeneratorImpl::Reference(f) => f.generate_datum(offset),
             FieldGeneratorImpl::Varchar(f) => f.generate_datum(offset),
             FieldGeneratorImpl::Timestamp(f) => f.generate_datum(offset),
+            FieldGeneratorImpl::TimestampSequence(f) => f.generate_datum(offset),
             FieldGeneratorImpl::Struct(fields) => {
                 let data = fields
                     .iter_mut()
@@ -287,4 +373,23 @@ mod tests {
             assert_eq!(datum2_new, datum2);
         }
     }
+
+    #[test]
+    fn test_unsupported_data_type() {
+        let distribution = DistributionOptions {
+            kind: "zipf".to_string(),
+            ..Default::default()
+        };
+        assert!(FieldGeneratorImpl::with_distribution(
+            DataType::Varchar,
+            None,
+            None,
+            &distribution,
+            1234
+        )
+        .is_err());
+        assert!(
+            FieldGeneratorImpl::with_reference(DataType::Float64, None, None, 0, 1, 1234).is_err()
+        );
+    }
 }
@@ -19,10 +19,12 @@ use anyhow::Result;
 use rand::distributions::uniform::SampleUniform;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use serde_json::json;
+use serde_json::{json, Value};
 
-use crate::field_generator::{NumericFieldRandomGenerator, NumericFieldSequenceGenerator};
-use crate::types::{Datum, OrderedF32, OrderedF64, Scalar};
+use crate::field_generator::{
+    Distribution, DistributionOptions, NumericFieldRandomGenerator, NumericFieldSequenceGenerator,
+};
+use crate::types::{DataType, Datum, OrderedF32, OrderedF64, Scalar, ScalarImpl};
 
 trait NumericType
 where
@@ -51,6 +53,7 @@ pub struct NumericFieldRandomConcrete<T> {
     min: T,
     max: T,
     seed: u64,
+    distribution: Distribution,
 }
 
 #[derive(Default)]
@@ -82,21 +85,117 @@ where
         }
         assert!(min < max);
 
-        Ok(Self { min, max, seed })
+        Ok(Self {
+            min,
+            max,
+            seed,
+            distribution: Distribution::Uniform,
+        })
     }
 
     fn generate(&mut self, offset: u64) -> serde_json::Value {
         let mut rng = StdRng::seed_from_u64(offset ^ self.seed);
-        let result = rng.gen_range(self.min..=self.max);
+        let result = self.sample(&mut rng);
         json!(result)
     }
 
     fn generate_datum(&mut self, offset: u64) -> Datum {
         let mut rng = StdRng::seed_from_u64(offset ^ self.seed);
-        let result = rng.gen_range(self.min..=self.max);
+        let result = self.sample(&mut rng);
         Some(result.to_scalar_value())
     }
 }
+
+impl<T> NumericFieldRandomConcrete<T>
+where
+    T: NumericType + Scalar,
+{
+    /// Samples from the given distribution instead of the uniform one.
+    pub fn with_distribution(mut self, options: &DistributionOptions) -> Result<Self> {
+        let (min, max) = (self.min.to_f64().unwrap(), self.max.to_f64().unwrap());
+        self.distribution = Distribution::new(options, min, max)?;
+        Ok(self)
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> T {
+        match &self.distribution {
+            Distribution::Uniform => rng.gen_range(self.min..=self.max),
+            Distribution::Zipf(zipf) => self.min + T::from(zipf.sample(rng) - 1).unwrap(),
+            Distribution::Normal(normal) => {
+                let (min, max) = (self.min.to_f64().unwrap(), self.max.to_f64().unwrap());
+                T::from(normal.sample(rng).clamp(min, max)).unwrap()
+            }
+        }
+    }
+}
+
+/// Generates foreign-key-like values of an integer field, which are picked from the values that a
+/// sequence field of the same split has produced up to the current offset, such as
+/// `'fields.v2.kind'='reference'` and `'fields.v2.reference'='v1'` where `v1` is a sequence field.
+pub struct ReferenceField {
+    data_type: DataType,
+    start: i64,
+    end: i64,
+    split_index: u64,
+    split_num: u64,
+    seed: u64,
+}
+
+impl ReferenceField {
+    /// `start` and `end` are the options of the referenced sequence field.
+    pub fn new(
+        data_type: DataType,
+        start: Option<String>,
+        end: Option<String>,
+        split_index: u64,
+        split_num: u64,
+        seed: u64,
+    ) -> Result<Self> {
+        let mut start_value = 0;
+        let mut end_value = i16::MAX as i64;
+        if let Some(start) = start {
+            start_value = start.parse::<i64>()?;
+        }
+        if let Some(end) = end {
+            end_value = end.parse::<i64>()?;
+        }
+        assert!(start_value < end_value);
+        Ok(Self {
+            data_type,
+            start: start_value,
+            end: end_value,
+            split_index,
+            split_num,
+            seed,
+        })
+    }
+
+    fn generate_data(&mut self, offset: u64) -> Option<i64> {
+        // The referenced sequence field produces `start + split_index + split_num * k` at the
+        // offset `k`, until it exceeds `end`.
+        let first = self.start + self.split_index as i64;
+        if first > self.end {
+            return None;
+        }
+        let max_k = ((self.end - first) as u64 / self.split_num).min(offset);
+        let mut rng = StdRng::seed_from_u64(offset ^ self.seed);
+        let k = rng.gen_range(0..=max_k);
+        Some(first + (self.split_num * k) as i64)
+    }
+
+    pub fn generate(&mut self, offset: u64) -> Value {
+        json!(self.generate_data(offset))
+    }
+
+    pub fn generate_datum(&mut self, offset: u64) -> Datum {
+        let value = self.generate_data(offset)?;
+        match self.data_type {
+            DataType::Int16 => Some(ScalarImpl::Int16(value as i16)),
+            DataType::Int32 => Some(ScalarImpl::Int32(value as i32)),
+            _ => Some(ScalarImpl::Int64(value)),
+        }
+    }
+}
 impl<T> NumericFieldSequenceGenerator for NumericFieldSequenceConcrete<T>
 where
     T: NumericType + Scalar,
@@ -244,6 +343,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_random_field_generator_with_distribution() {
+        let mut i32_field =
+            I32RandomField::new(Some("10".to_string()), Some("1000".to_string()), 114)
+                .unwrap()
+                .with_distribution(&DistributionOptions {
+                    kind: "zipf".to_string(),
+                    exponent: Some("1.5".to_string()),
+                    ..Default::default()
+                })
+                .unwrap();
+        let values = (0..1000)
+            .map(|i| i32_field.generate(i).as_i64().unwrap())
+            .collect::<Vec<_>>();
+        assert!(values.iter().all(|v| (10..=1000).contains(v)));
+        // the minimum is the most frequent value
+        let hot = values.iter().filter(|v| **v == 10).count();
+        assert!(hot > values.iter().filter(|v| **v == 11).count());
+        assert!(hot > 200);
+
+        let mut f64_field =
+            F64RandomField::new(Some("0".to_string()), Some("100".to_string()), 114)
+                .unwrap()
+                .with_distribution(&DistributionOptions {
+                    kind: "normal".to_string(),
+                    mean: Some("50".to_string()),
+                    stddev: Some("1".to_string()),
+                    ..Default::default()
+                })
+                .unwrap();
+        for i in 0..100 {
+            let res = f64_field.generate(i).as_f64().unwrap();
+            assert!((40. ..60.).contains(&res));
+        }
+
+        assert!(I32RandomField::new(None, None, 114)
+            .unwrap()
+            .with_distribution(&DistributionOptions {
+                kind: "poisson".to_string(),
+                ..Default::default()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_reference_field_generator() {
+        let (split_index, split_num) = (1, 3);
+        let mut sequence = I64SequenceField::new(
+            Some("1".to_string()),
+            Some("20".to_string()),
+            split_index,
+            split_num,
+        )
+        .unwrap();
+        let mut reference = ReferenceField::new(
+            DataType::Int64,
+            Some("1".to_string()),
+            Some("20".to_string()),
+            split_index,
+            split_num,
+            114,
+        )
+        .unwrap();
+        let mut produced = vec![];
+        for offset in 0..10 {
+            let value = sequence.generate();
+            if !value.is_null() {
+                produced.push(value);
+            }
+            let referenced = reference.generate(offset);
+            assert!(produced.contains(&referenced), "{:?}", referenced);
+        }
+    }
+
     #[test]
     fn test_sequence_field_generator_float() {
         let mut f64_field =
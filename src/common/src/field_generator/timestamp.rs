@@ -20,7 +20,7 @@ use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use serde_json::{json, Value};
 
-use super::DEFAULT_MAX_PAST;
+use super::{DEFAULT_MAX_PAST, DEFAULT_STEP};
 use crate::types::{Datum, NaiveDateTimeWrapper, Scalar};
 
 pub struct TimestampField {
@@ -63,3 +63,108 @@ impl TimestampField {
         Some(NaiveDateTimeWrapper::new(self.generate_data(offset)).to_scalar_value())
     }
 }
+
+/// Generates timestamps increasing by `step` from `start`, each randomly moved by at most `jitter`
+/// forward or backward to simulate out-of-order events. Rows of the same split are `step *
+/// split_num` apart so that the splits together advance by `step` per row.
+pub struct TimestampSequenceField {
+    start: NaiveDateTime,
+    step: Duration,
+    jitter: Duration,
+    split_index: u64,
+    split_num: u64,
+    seed: u64,
+}
+
+impl TimestampSequenceField {
+    pub fn new(
+        start_option: Option<String>,
+        step_option: Option<String>,
+        jitter_option: Option<String>,
+        split_index: u64,
+        split_num: u64,
+        seed: u64,
+    ) -> Result<Self> {
+        let start = match start_option {
+            Some(start) => NaiveDateTime::parse_from_str(&start, "%Y-%m-%d %H:%M:%S%.f")?,
+            None => Local::now()
+                .naive_local()
+                .duration_round(Duration::microseconds(1))?,
+        };
+        let step = match step_option {
+            Some(step) => parse_duration(&step)?,
+            None => DEFAULT_STEP,
+        };
+        let jitter = match jitter_option {
+            Some(jitter) => parse_duration(&jitter)?,
+            None => std::time::Duration::ZERO,
+        };
+        Ok(Self {
+            start,
+            step: Duration::from_std(step)?,
+            jitter: Duration::from_std(jitter)?,
+            split_index,
+            split_num,
+            seed,
+        })
+    }
+
+    fn generate_data(&mut self, offset: u64) -> NaiveDateTime {
+        let index = (offset * self.split_num + self.split_index) as i64;
+        let step = self.step.num_microseconds().unwrap_or(i64::MAX);
+        let jitter = self.jitter.num_milliseconds();
+        let jitter = if jitter > 0 {
+            let mut rng = StdRng::seed_from_u64(offset ^ self.seed);
+            rng.gen_range(-jitter..=jitter)
+        } else {
+            0
+        };
+        self.start
+            + Duration::microseconds(step.saturating_mul(index))
+            + Duration::milliseconds(jitter)
+    }
+
+    pub fn generate(&mut self, offset: u64) -> Value {
+        json!(self.generate_data(offset).to_string())
+    }
+
+    pub fn generate_datum(&mut self, offset: u64) -> Datum {
+        Some(NaiveDateTimeWrapper::new(self.generate_data(offset)).to_scalar_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_sequence() {
+        let mut field = TimestampSequenceField::new(
+            Some("2022-01-01 00:00:00".to_string()),
+            Some("1m".to_string()),
+            None,
+            1,
+            2,
+            1234,
+        )
+        .unwrap();
+        assert_eq!(field.generate(0), json!("2022-01-01 00:01:00"));
+        assert_eq!(field.generate(1), json!("2022-01-01 00:03:00"));
+
+        let mut field = TimestampSequenceField::new(
+            Some("2022-01-01 00:00:00".to_string()),
+            Some("1m".to_string()),
+            Some("10s".to_string()),
+            0,
+            1,
+            1234,
+        )
+        .unwrap();
+        let start = NaiveDate::from_ymd(2022, 1, 1).and_hms(0, 0, 0);
+        for offset in 0..100 {
+            let expected = start + Duration::minutes(offset as i64);
+            let actual = field.generate_data(offset);
+            assert!((actual - expected).num_milliseconds().abs() <= 10_000);
+        }
+    }
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use rand::distributions::Alphanumeric;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
@@ -21,8 +21,21 @@ use serde_json::{json, Value};
 use super::DEFAULT_LENGTH;
 use crate::types::{Datum, Scalar};
 
+/// A character of the pattern of `VarcharField`.
+enum PatternChar {
+    /// `#` generates a random digit.
+    Digit,
+    /// `?` generates a random lowercase letter.
+    Letter,
+    /// `*` generates a random alphanumeric character.
+    Alphanumeric,
+    /// Other characters, or the ones escaped by `\`, are copied as is.
+    Literal(char),
+}
+
 pub struct VarcharField {
     length: usize,
+    pattern: Option<Vec<PatternChar>>,
     seed: u64,
 }
 
@@ -33,24 +46,87 @@ impl VarcharField {
         } else {
             DEFAULT_LENGTH
         };
-        Ok(Self { length, seed })
+        Ok(Self {
+            length,
+            pattern: None,
+            seed,
+        })
+    }
+
+    /// Generates strings following the pattern, such as `user_####` for `user_0042`. See
+    /// `PatternChar` for the syntax.
+    pub fn with_pattern(pattern: String, seed: u64) -> Result<Self> {
+        let mut pattern_chars = vec![];
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            pattern_chars.push(match c {
+                '#' => PatternChar::Digit,
+                '?' => PatternChar::Letter,
+                '*' => PatternChar::Alphanumeric,
+                '\\' => PatternChar::Literal(
+                    chars
+                        .next()
+                        .ok_or_else(|| anyhow!("pattern {:?} ends with an escape", pattern))?,
+                ),
+                c => PatternChar::Literal(c),
+            });
+        }
+        Ok(Self {
+            length: pattern_chars.len(),
+            pattern: Some(pattern_chars),
+            seed,
+        })
+    }
+
+    fn generate_data(&mut self, offset: u64) -> String {
+        let mut rng = StdRng::seed_from_u64(offset ^ self.seed);
+        match &self.pattern {
+            Some(pattern) => pattern
+                .iter()
+                .map(|c| match c {
+                    PatternChar::Digit => char::from(rng.gen_range(b'0'..=b'9')),
+                    PatternChar::Letter => char::from(rng.gen_range(b'a'..=b'z')),
+                    PatternChar::Alphanumeric => char::from(rng.sample(Alphanumeric)),
+                    PatternChar::Literal(c) => *c,
+                })
+                .collect(),
+            None => rng
+                .sample_iter(&Alphanumeric)
+                .take(self.length)
+                .map(char::from)
+                .collect(),
+        }
     }
 
     pub fn generate(&mut self, offset: u64) -> Value {
-        let s: String = StdRng::seed_from_u64(offset ^ self.seed)
-            .sample_iter(&Alphanumeric)
-            .take(self.length)
-            .map(char::from)
-            .collect();
-        json!(s)
+        json!(self.generate_data(offset))
     }
 
     pub fn generate_datum(&mut self, offset: u64) -> Datum {
-        let s: String = StdRng::seed_from_u64(offset ^ self.seed)
-            .sample_iter(&Alphanumeric)
-            .take(self.length)
-            .map(char::from)
-            .collect();
-        Some(s.to_scalar_value())
+        Some(self.generate_data(offset).to_scalar_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varchar_pattern() {
+        let mut field = VarcharField::with_pattern("user_##-?*\\#".to_string(), 1234).unwrap();
+        for offset in 0..100 {
+            let value = field.generate(offset);
+            let s = value.as_str().unwrap().as_bytes();
+            assert_eq!(s.len(), 11);
+            assert_eq!(&s[..5], b"user_");
+            assert!(s[5].is_ascii_digit() && s[6].is_ascii_digit());
+            assert_eq!(s[7], b'-');
+            assert!(s[8].is_ascii_lowercase());
+            assert!(s[9].is_ascii_alphanumeric());
+            assert_eq!(s[10], b'#');
+        }
+        assert_eq!(field.generate(7), field.generate(7));
+
+        assert!(VarcharField::with_pattern("id_\\".to_string(), 1234).is_err());
     }
 }
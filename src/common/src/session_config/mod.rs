@@ -14,20 +14,23 @@
 
 mod query_mode;
 mod search_path;
+mod timezone;
 mod transaction_isolation_level;
 
 use std::ops::Deref;
+use std::time::Duration;
 
 use itertools::Itertools;
 pub use query_mode::QueryMode;
 pub use search_path::{SearchPath, USER_NAME_WILD_CARD};
+pub use timezone::Timezone;
 
 use crate::error::{ErrorCode, RwError};
 use crate::session_config::transaction_isolation_level::IsolationLevel;
 
 // This is a hack, &'static str is not allowed as a const generics argument.
 // TODO: refine this using the adt_const_params feature.
-const CONFIG_KEYS: [&str; 10] = [
+const CONFIG_KEYS: [&str; 21] = [
     "RW_IMPLICIT_FLUSH",
     "CREATE_COMPACTION_GROUP_FOR_MV",
     "QUERY_MODE",
@@ -38,6 +41,17 @@ const CONFIG_KEYS: [&str; 10] = [
     "MAX_SPLIT_RANGE_GAP",
     "SEARCH_PATH",
     "TRANSACTION ISOLATION LEVEL",
+    "RW_BATCH_MAX_RECURSION_DEPTH",
+    "STATEMENT_TIMEOUT",
+    "TIMEZONE",
+    "RW_BATCH_ENABLE_BUSHY_JOIN",
+    "RW_BATCH_JOIN_REORDER_DP_LIMIT",
+    "RW_STREAMING_ENABLE_TWO_PHASE_AGG",
+    "RW_STREAMING_ENABLE_DISTINCT_DEDUP",
+    "RW_STREAMING_BACKFILL_RATE_LIMIT",
+    "RW_STREAMING_PROPAGATE_SCHEMA_CHANGE",
+    "RW_BATCH_ENABLE_SORT_AGG",
+    "RW_STREAMING_ENABLE_LOOKUP_JOIN",
 ];
 
 // MUST HAVE 1v1 relationship to CONFIG_KEYS. e.g. CONFIG_KEYS[IMPLICIT_FLUSH] =
@@ -52,6 +66,17 @@ const BATCH_ENABLE_LOOKUP_JOIN: usize = 6;
 const MAX_SPLIT_RANGE_GAP: usize = 7;
 const SEARCH_PATH: usize = 8;
 const TRANSACTION_ISOLATION_LEVEL: usize = 9;
+const BATCH_MAX_RECURSION_DEPTH: usize = 10;
+const STATEMENT_TIMEOUT: usize = 11;
+const TIMEZONE: usize = 12;
+const BATCH_ENABLE_BUSHY_JOIN: usize = 13;
+const BATCH_JOIN_REORDER_DP_LIMIT: usize = 14;
+const STREAMING_ENABLE_TWO_PHASE_AGG: usize = 15;
+const STREAMING_ENABLE_DISTINCT_DEDUP: usize = 16;
+const STREAMING_BACKFILL_RATE_LIMIT: usize = 17;
+const STREAMING_PROPAGATE_SCHEMA_CHANGE: usize = 18;
+const BATCH_ENABLE_SORT_AGG: usize = 19;
+const STREAMING_ENABLE_LOOKUP_JOIN: usize = 20;
 
 trait ConfigEntry: Default + for<'a> TryFrom<&'a [&'a str], Error = RwError> {
     fn entry_name() -> &'static str;
@@ -198,6 +223,16 @@ type ExtraFloatDigit = ConfigI32<EXTRA_FLOAT_DIGITS, 1>;
 type DateStyle = ConfigString<DATE_STYLE>;
 type BatchEnableLookupJoin = ConfigBool<BATCH_ENABLE_LOOKUP_JOIN, false>;
 type MaxSplitRangeGap = ConfigI32<MAX_SPLIT_RANGE_GAP, 8>;
+type BatchMaxRecursionDepth = ConfigI32<BATCH_MAX_RECURSION_DEPTH, 1024>;
+type StatementTimeout = ConfigI32<STATEMENT_TIMEOUT, 0>;
+type BatchEnableBushyJoin = ConfigBool<BATCH_ENABLE_BUSHY_JOIN, false>;
+type BatchJoinReorderDpLimit = ConfigI32<BATCH_JOIN_REORDER_DP_LIMIT, 10>;
+type StreamingEnableTwoPhaseAgg = ConfigBool<STREAMING_ENABLE_TWO_PHASE_AGG, false>;
+type StreamingEnableDistinctDedup = ConfigBool<STREAMING_ENABLE_DISTINCT_DEDUP, false>;
+type StreamingBackfillRateLimit = ConfigI32<STREAMING_BACKFILL_RATE_LIMIT, 0>;
+type StreamingPropagateSchemaChange = ConfigBool<STREAMING_PROPAGATE_SCHEMA_CHANGE, false>;
+type BatchEnableSortAgg = ConfigBool<BATCH_ENABLE_SORT_AGG, true>;
+type StreamingEnableLookupJoin = ConfigBool<STREAMING_ENABLE_LOOKUP_JOIN, false>;
 
 #[derive(Default)]
 pub struct ConfigMap {
@@ -210,8 +245,8 @@ pub struct ConfigMap {
     /// MV creation.
     create_compaction_group_for_mv: CreateCompactionGroupForMv,
 
-    /// A temporary config variable to force query running in either local or distributed mode.
-    /// It will be removed in the future.
+    /// Force query running in either local or distributed mode. By default (`auto`), the mode is
+    /// decided by the optimizer, and simple point queries are executed in local mode.
     query_mode: QueryMode,
 
     /// see <https://www.postgresql.org/docs/current/runtime-config-client.html#:~:text=for%20more%20information.-,extra_float_digits,-(integer)>
@@ -234,6 +269,59 @@ pub struct ConfigMap {
 
     /// see <https://www.postgresql.org/docs/current/transaction-iso.html>
     transaction_isolation_level: IsolationLevel,
+
+    /// The maximum number of iterations of the recursive term of a recursive CTE in batch
+    /// execution.
+    batch_max_recursion_depth: BatchMaxRecursionDepth,
+
+    /// Abort any batch query that takes more than the specified number of milliseconds. Zero
+    /// turns off the limit.
+    /// see <https://www.postgresql.org/docs/current/runtime-config-client.html#GUC-STATEMENT-TIMEOUT>
+    statement_timeout: StatementTimeout,
+
+    /// see <https://www.postgresql.org/docs/current/runtime-config-client.html#GUC-TIMEZONE>
+    timezone: Timezone,
+
+    /// If `RW_BATCH_ENABLE_BUSHY_JOIN` is on, the join reordering of batch queries also considers
+    /// bushy join trees besides the left-deep ones.
+    batch_enable_bushy_join: BatchEnableBushyJoin,
+
+    /// The maximum number of relations in a multi-way join to be reordered by dynamic programming
+    /// in batch queries. Larger joins are reordered greedily.
+    batch_join_reorder_dp_limit: BatchJoinReorderDpLimit,
+
+    /// If `RW_STREAMING_ENABLE_TWO_PHASE_AGG` is on, the streaming aggregations with group keys
+    /// are split into a stateless local phase and a global phase when possible, which shuffles the
+    /// partial results instead of the input rows.
+    streaming_enable_two_phase_agg: StreamingEnableTwoPhaseAgg,
+
+    /// If `RW_STREAMING_ENABLE_DISTINCT_DEDUP` is on, the distinct aggregations of streaming
+    /// queries are computed by the agg executor with dedup tables shared by the distinct
+    /// aggregations on the same column, instead of being rewritten with `Expand`.
+    streaming_enable_distinct_dedup: StreamingEnableDistinctDedup,
+
+    /// The maximum number of rows per second read from the snapshots of the upstream tables by
+    /// each backfilling actor of newly created streaming jobs, so the total rate scales with the
+    /// parallelism. Zero turns off the limit. The snapshots read by the index scans are not
+    /// throttled, as they block the upstream. The limit of a materialized view can be changed by
+    /// `ALTER MATERIALIZED VIEW ... SET backfill_rate_limit` afterwards.
+    streaming_backfill_rate_limit: StreamingBackfillRateLimit,
+
+    /// If `RW_STREAMING_PROPAGATE_SCHEMA_CHANGE` is on, the columns added to a table by `ALTER
+    /// TABLE` are also added to the materialized views defined with `SELECT *` on the table, by
+    /// updating their running fragments in place. Otherwise, these views keep the columns at their
+    /// creation and are flagged as stale.
+    streaming_propagate_schema_change: StreamingPropagateSchemaChange,
+
+    /// If `RW_BATCH_ENABLE_SORT_AGG` is on, the aggregations of batch queries are computed by
+    /// sort aggregation instead of hash aggregation when the input is already sorted on the group
+    /// keys, which holds only one group in memory at a time.
+    batch_enable_sort_agg: BatchEnableSortAgg,
+
+    /// If `RW_STREAMING_ENABLE_LOOKUP_JOIN` is on, the streaming joins of an append-only stream
+    /// with a table look up the rows of the table in its storage, instead of keeping them in the
+    /// join state.
+    streaming_enable_lookup_join: StreamingEnableLookupJoin,
 }
 
 impl ConfigMap {
@@ -257,6 +345,28 @@ impl ConfigMap {
             self.max_split_range_gap = val.as_slice().try_into()?;
         } else if key.eq_ignore_ascii_case(SearchPath::entry_name()) {
             self.search_path = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(BatchMaxRecursionDepth::entry_name()) {
+            self.batch_max_recursion_depth = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(StatementTimeout::entry_name()) {
+            self.statement_timeout = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(Timezone::entry_name()) {
+            self.timezone = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(BatchEnableBushyJoin::entry_name()) {
+            self.batch_enable_bushy_join = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(BatchJoinReorderDpLimit::entry_name()) {
+            self.batch_join_reorder_dp_limit = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(StreamingEnableTwoPhaseAgg::entry_name()) {
+            self.streaming_enable_two_phase_agg = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(StreamingEnableDistinctDedup::entry_name()) {
+            self.streaming_enable_distinct_dedup = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(StreamingBackfillRateLimit::entry_name()) {
+            self.streaming_backfill_rate_limit = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(StreamingPropagateSchemaChange::entry_name()) {
+            self.streaming_propagate_schema_change = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(BatchEnableSortAgg::entry_name()) {
+            self.batch_enable_sort_agg = val.as_slice().try_into()?;
+        } else if key.eq_ignore_ascii_case(StreamingEnableLookupJoin::entry_name()) {
+            self.streaming_enable_lookup_join = val.as_slice().try_into()?;
         } else {
             return Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into());
         }
@@ -285,6 +395,28 @@ impl ConfigMap {
             Ok(self.search_path.to_string())
         } else if key.eq_ignore_ascii_case(IsolationLevel::entry_name()) {
             Ok(self.transaction_isolation_level.to_string())
+        } else if key.eq_ignore_ascii_case(BatchMaxRecursionDepth::entry_name()) {
+            Ok(self.batch_max_recursion_depth.to_string())
+        } else if key.eq_ignore_ascii_case(StatementTimeout::entry_name()) {
+            Ok(self.statement_timeout.to_string())
+        } else if key.eq_ignore_ascii_case(Timezone::entry_name()) {
+            Ok(self.timezone.to_string())
+        } else if key.eq_ignore_ascii_case(BatchEnableBushyJoin::entry_name()) {
+            Ok(self.batch_enable_bushy_join.to_string())
+        } else if key.eq_ignore_ascii_case(BatchJoinReorderDpLimit::entry_name()) {
+            Ok(self.batch_join_reorder_dp_limit.to_string())
+        } else if key.eq_ignore_ascii_case(StreamingEnableTwoPhaseAgg::entry_name()) {
+            Ok(self.streaming_enable_two_phase_agg.to_string())
+        } else if key.eq_ignore_ascii_case(StreamingEnableDistinctDedup::entry_name()) {
+            Ok(self.streaming_enable_distinct_dedup.to_string())
+        } else if key.eq_ignore_ascii_case(StreamingBackfillRateLimit::entry_name()) {
+            Ok(self.streaming_backfill_rate_limit.to_string())
+        } else if key.eq_ignore_ascii_case(StreamingPropagateSchemaChange::entry_name()) {
+            Ok(self.streaming_propagate_schema_change.to_string())
+        } else if key.eq_ignore_ascii_case(BatchEnableSortAgg::entry_name()) {
+            Ok(self.batch_enable_sort_agg.to_string())
+        } else if key.eq_ignore_ascii_case(StreamingEnableLookupJoin::entry_name()) {
+            Ok(self.streaming_enable_lookup_join.to_string())
         } else {
             Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into())
         }
@@ -305,7 +437,7 @@ impl ConfigMap {
             VariableInfo{
                 name : QueryMode::entry_name().to_lowercase(),
                 setting : self.query_mode.to_string(),
-                description : String::from("A temporary config variable to force query running in either local or distributed mode.")
+                description : String::from("Force query running in either local or distributed mode. If `auto`, simple point queries run in local mode and others run in distributed mode.")
             },
             VariableInfo{
                 name : ExtraFloatDigit::entry_name().to_lowercase(),
@@ -336,6 +468,61 @@ impl ConfigMap {
                 name: SearchPath::entry_name().to_lowercase(),
                 setting : self.search_path.to_string(),
                 description : String::from("Sets the order in which schemas are searched when an object (table, data type, function, etc.) is referenced by a simple name with no schema specified")
+            },
+            VariableInfo {
+                name: BatchMaxRecursionDepth::entry_name().to_lowercase(),
+                setting : self.batch_max_recursion_depth.to_string(),
+                description : String::from("The maximum number of iterations of the recursive term of a recursive CTE in batch queries.")
+            },
+            VariableInfo {
+                name: StatementTimeout::entry_name().to_lowercase(),
+                setting : self.statement_timeout.to_string(),
+                description : String::from("Sets the maximum allowed duration of any batch query in milliseconds, 0 turns off the limit.")
+            },
+            VariableInfo {
+                name: Timezone::entry_name().to_lowercase(),
+                setting : self.timezone.to_string(),
+                description : String::from("Sets the time zone for displaying and interpreting time stamps.")
+            },
+            VariableInfo {
+                name: BatchEnableBushyJoin::entry_name().to_lowercase(),
+                setting : self.batch_enable_bushy_join.to_string(),
+                description : String::from("To consider bushy join trees besides left-deep ones when reordering the joins of batch queries.")
+            },
+            VariableInfo {
+                name: BatchJoinReorderDpLimit::entry_name().to_lowercase(),
+                setting : self.batch_join_reorder_dp_limit.to_string(),
+                description : String::from("The maximum number of relations in a join of batch queries to be reordered by dynamic programming.")
+            },
+            VariableInfo {
+                name: StreamingEnableTwoPhaseAgg::entry_name().to_lowercase(),
+                setting : self.streaming_enable_two_phase_agg.to_string(),
+                description : String::from("To split the aggregations with group keys of streaming queries into a stateless local phase and a global phase when possible.")
+            },
+            VariableInfo {
+                name: StreamingEnableDistinctDedup::entry_name().to_lowercase(),
+                setting : self.streaming_enable_distinct_dedup.to_string(),
+                description : String::from("To compute the distinct aggregations of streaming queries with shared dedup tables instead of rewriting them with expand.")
+            },
+            VariableInfo {
+                name: StreamingBackfillRateLimit::entry_name().to_lowercase(),
+                setting : self.streaming_backfill_rate_limit.to_string(),
+                description : String::from("The maximum number of rows per second read from the upstream snapshots by each actor when creating streaming jobs, 0 turns off the limit.")
+            },
+            VariableInfo {
+                name: StreamingPropagateSchemaChange::entry_name().to_lowercase(),
+                setting : self.streaming_propagate_schema_change.to_string(),
+                description : String::from("To add the columns added to a table to the materialized views defined with `SELECT *` on it, instead of flagging them as stale.")
+            },
+            VariableInfo {
+                name: BatchEnableSortAgg::entry_name().to_lowercase(),
+                setting : self.batch_enable_sort_agg.to_string(),
+                description : String::from("To use sort aggregation instead of hash aggregation in batch queries when the input is already sorted on the group keys.")
+            },
+            VariableInfo {
+                name: StreamingEnableLookupJoin::entry_name().to_lowercase(),
+                setting : self.streaming_enable_lookup_join.to_string(),
+                description : String::from("To look up the rows of a table in its storage when joining an append-only stream with it in streaming queries, instead of keeping them in the join state.")
             }
         ]
     }
@@ -379,4 +566,66 @@ impl ConfigMap {
     pub fn get_search_path(&self) -> SearchPath {
         self.search_path.clone()
     }
+
+    pub fn get_batch_max_recursion_depth(&self) -> u32 {
+        if *self.batch_max_recursion_depth < 0 {
+            0
+        } else {
+            *self.batch_max_recursion_depth as u32
+        }
+    }
+
+    /// Returns `None` if the statement timeout is turned off.
+    pub fn get_statement_timeout(&self) -> Option<Duration> {
+        if *self.statement_timeout <= 0 {
+            None
+        } else {
+            Some(Duration::from_millis(*self.statement_timeout as u64))
+        }
+    }
+
+    pub fn get_timezone(&self) -> &str {
+        self.timezone.as_str()
+    }
+
+    pub fn get_batch_enable_bushy_join(&self) -> bool {
+        *self.batch_enable_bushy_join
+    }
+
+    pub fn get_batch_join_reorder_dp_limit(&self) -> usize {
+        if *self.batch_join_reorder_dp_limit < 0 {
+            0
+        } else {
+            *self.batch_join_reorder_dp_limit as usize
+        }
+    }
+
+    pub fn get_streaming_enable_two_phase_agg(&self) -> bool {
+        *self.streaming_enable_two_phase_agg
+    }
+
+    pub fn get_streaming_enable_distinct_dedup(&self) -> bool {
+        *self.streaming_enable_distinct_dedup
+    }
+
+    /// Returns zero if the backfill rate limit is turned off.
+    pub fn get_streaming_backfill_rate_limit(&self) -> u64 {
+        if *self.streaming_backfill_rate_limit < 0 {
+            0
+        } else {
+            *self.streaming_backfill_rate_limit as u64
+        }
+    }
+
+    pub fn get_streaming_propagate_schema_change(&self) -> bool {
+        *self.streaming_propagate_schema_change
+    }
+
+    pub fn get_batch_enable_sort_agg(&self) -> bool {
+        *self.batch_enable_sort_agg
+    }
+
+    pub fn get_streaming_enable_lookup_join(&self) -> bool {
+        *self.streaming_enable_lookup_join
+    }
 }
@@ -0,0 +1,74 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ConfigEntry, CONFIG_KEYS, TIMEZONE};
+use crate::error::{ErrorCode, RwError};
+
+const DEFAULT_TIMEZONE: &str = "UTC";
+
+/// The time zone used to interpret and display `timestamp with time zone` values, and to convert
+/// between `timestamp` and `timestamp with time zone`.
+///
+/// Only the names in the IANA time zone database are accepted, which are validated by the
+/// frontend when the variable is set.
+///
+/// see <https://www.postgresql.org/docs/current/runtime-config-client.html#GUC-TIMEZONE>
+#[derive(Clone)]
+pub struct Timezone(String);
+
+impl Timezone {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Self(DEFAULT_TIMEZONE.to_string())
+    }
+}
+
+impl ConfigEntry for Timezone {
+    fn entry_name() -> &'static str {
+        CONFIG_KEYS[TIMEZONE]
+    }
+}
+
+impl TryFrom<&[&str]> for Timezone {
+    type Error = RwError;
+
+    fn try_from(value: &[&str]) -> Result<Self, Self::Error> {
+        if value.len() != 1 {
+            return Err(ErrorCode::InternalError(format!(
+                "SET {} takes only one argument",
+                Self::entry_name()
+            ))
+            .into());
+        }
+
+        let time_zone = value[0].trim().trim_matches('\'');
+        // `SET TIME ZONE LOCAL` and `SET TIME ZONE DEFAULT` both reset to the default.
+        if time_zone.eq_ignore_ascii_case("LOCAL") || time_zone.eq_ignore_ascii_case("DEFAULT") {
+            return Ok(Self::default());
+        }
+
+        Ok(Self(time_zone.to_string()))
+    }
+}
+
+impl std::fmt::Display for Timezone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
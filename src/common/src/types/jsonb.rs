@@ -0,0 +1,380 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::{fmt, io};
+
+use bytes::{Buf, BufMut};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::Scalar;
+
+/// An owned `jsonb` value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonbVal(Box<Value>);
+
+/// A reference to a `jsonb` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonbRef<'a>(&'a Value);
+
+impl JsonbVal {
+    pub fn new(value: Value) -> Self {
+        Self(Box::new(value))
+    }
+
+    pub fn null() -> Self {
+        Self::new(Value::Null)
+    }
+
+    pub fn value(&self) -> &Value {
+        &self.0
+    }
+
+    pub fn into_value(self) -> Value {
+        *self.0
+    }
+
+    /// Decodes a value written by [`JsonbRef::value_serialize`].
+    pub fn value_deserialize(buf: &[u8]) -> Option<Self> {
+        serde_json::from_slice(buf).ok().map(Self::new)
+    }
+
+    /// Decodes a value written by [`JsonbRef::memcmp_serialize`].
+    pub fn memcmp_deserialize(
+        deserializer: &mut memcomparable::Deserializer<impl Buf>,
+    ) -> memcomparable::Result<Self> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|e: serde_json::Error| memcomparable::Error::Message(e.to_string()))
+    }
+}
+
+impl<'a> JsonbRef<'a> {
+    pub fn new(value: &'a Value) -> Self {
+        Self(value)
+    }
+
+    pub fn value(&self) -> &'a Value {
+        self.0
+    }
+
+    pub fn is_jsonb_null(&self) -> bool {
+        self.0.is_null()
+    }
+
+    /// Name of the top-level JSON type, as reported by `jsonb_typeof`.
+    pub fn type_name(&self) -> &'static str {
+        match self.0 {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    /// Returns the field `key` if this is an object, i.e. `jsonb -> text`.
+    pub fn access_object_field(&self, key: &str) -> Option<JsonbRef<'a>> {
+        self.0.as_object()?.get(key).map(Self)
+    }
+
+    /// Returns the 0-based element `idx` if this is an array, i.e. `jsonb -> int`. Negative
+    /// indexes count from the end of the array.
+    pub fn access_array_element(&self, idx: i32) -> Option<JsonbRef<'a>> {
+        let array = self.0.as_array()?;
+        let idx = if idx < 0 {
+            array.len().checked_sub(idx.unsigned_abs() as usize)?
+        } else {
+            idx as usize
+        };
+        array.get(idx).map(Self)
+    }
+
+    /// Follows `path` from this value, i.e. `jsonb #> text[]`. Each path element is a field name
+    /// for objects and an integer index for arrays.
+    pub fn access_path<'b>(&self, path: impl IntoIterator<Item = &'b str>) -> Option<JsonbRef<'a>> {
+        let mut current = *self;
+        for key in path {
+            current = match current.0 {
+                Value::Object(_) => current.access_object_field(key)?,
+                Value::Array(_) => current.access_array_element(key.parse().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Iterates over the elements if this is an array, i.e. `jsonb_array_elements`.
+    pub fn array_elements(&self) -> Option<impl Iterator<Item = JsonbRef<'a>>> {
+        Some(self.0.as_array()?.iter().map(Self))
+    }
+
+    /// Converts to the text returned by the `->>` family: strings are unquoted and a JSON `null`
+    /// becomes SQL `NULL`.
+    pub fn as_text(&self) -> Option<String> {
+        match self.0 {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            _ => Some(self.to_string()),
+        }
+    }
+
+    /// Encodes the value in its compact JSON form for the value encoding.
+    pub fn value_serialize(&self) -> Vec<u8> {
+        serde_json::to_vec(self.0).unwrap()
+    }
+
+    /// Encodes the value as its compact JSON text. Note that the encoding is only consistent with
+    /// `Eq` and does not follow the order of `Ord`.
+    pub fn memcmp_serialize(
+        &self,
+        serializer: &mut memcomparable::Serializer<impl BufMut>,
+    ) -> memcomparable::Result<()> {
+        serde_json::to_string(self.0).unwrap().serialize(serializer)
+    }
+}
+
+impl FromStr for JsonbVal {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map(Self::new)
+    }
+}
+
+/// Formats JSON the way Postgres prints `jsonb`, with a space after `,` and `:`.
+struct PgJsonbFormatter;
+
+impl serde_json::ser::Formatter for PgJsonbFormatter {
+    fn begin_array_value<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b", ")
+        }
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b": ")
+    }
+}
+
+impl fmt::Display for JsonbRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = Vec::new();
+        let mut ser = serde_json::Serializer::with_formatter(&mut buf, PgJsonbFormatter);
+        self.0.serialize(&mut ser).map_err(|_| fmt::Error)?;
+        f.write_str(std::str::from_utf8(&buf).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Display for JsonbVal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_scalar_ref().fmt(f)
+    }
+}
+
+/// Ranks the JSON types the same way as Postgres:
+/// `Object > Array > Boolean > Number > String > Null`.
+fn type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::String(_) => 1,
+        Value::Number(_) => 2,
+        Value::Bool(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+fn cmp_value(lhs: &Value, rhs: &Value) -> Ordering {
+    match (lhs, rhs) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Bool(l), Value::Bool(r)) => l.cmp(r),
+        (Value::Number(l), Value::Number(r)) => {
+            let (lf, rf) = (l.as_f64().unwrap(), r.as_f64().unwrap());
+            // Fall back to the textual form so that the order is consistent with `Eq`, which
+            // distinguishes e.g. `1` and `1.0`.
+            lf.total_cmp(&rf)
+                .then_with(|| l.to_string().cmp(&r.to_string()))
+        }
+        (Value::String(l), Value::String(r)) => l.cmp(r),
+        // Longer arrays and objects are greater, then compare the elements in order.
+        (Value::Array(l), Value::Array(r)) => l.len().cmp(&r.len()).then_with(|| {
+            l.iter()
+                .zip(r.iter())
+                .map(|(l, r)| cmp_value(l, r))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        }),
+        (Value::Object(l), Value::Object(r)) => l.len().cmp(&r.len()).then_with(|| {
+            l.iter()
+                .zip(r.iter())
+                .map(|((lk, lv), (rk, rv))| lk.cmp(rk).then_with(|| cmp_value(lv, rv)))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        }),
+        (l, r) => type_rank(l).cmp(&type_rank(r)),
+    }
+}
+
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    type_rank(value).hash(state);
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => b.hash(state),
+        Value::Number(n) => n.to_string().hash(state),
+        Value::String(s) => s.hash(state),
+        Value::Array(array) => {
+            array.len().hash(state);
+            array.iter().for_each(|v| hash_value(v, state));
+        }
+        Value::Object(object) => {
+            object.len().hash(state);
+            object.iter().for_each(|(k, v)| {
+                k.hash(state);
+                hash_value(v, state);
+            });
+        }
+    }
+}
+
+impl PartialOrd for JsonbRef<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbRef<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_value(self.0, other.0)
+    }
+}
+
+impl PartialOrd for JsonbVal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonbVal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_scalar_ref().cmp(&other.as_scalar_ref())
+    }
+}
+
+impl Hash for JsonbRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(self.0, state)
+    }
+}
+
+impl Hash for JsonbVal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_scalar_ref().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jsonb(s: &str) -> JsonbVal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_jsonb_display() {
+        assert_eq!(
+            jsonb(r#"{"b":[1,2,{"c":null}],"a":"x"}"#).to_string(),
+            r#"{"a": "x", "b": [1, 2, {"c": null}]}"#
+        );
+        assert_eq!(jsonb("true").to_string(), "true");
+        assert!("{".parse::<JsonbVal>().is_err());
+    }
+
+    #[test]
+    fn test_jsonb_access() {
+        let v = jsonb(r#"{"a": {"b": [10, 20, "x"]}, "n": null}"#);
+        let r = v.as_scalar_ref();
+        let arr = r.access_object_field("a").unwrap().access_object_field("b");
+        assert_eq!(
+            arr.unwrap().access_array_element(1).unwrap().to_string(),
+            "20"
+        );
+        assert_eq!(
+            arr.unwrap()
+                .access_array_element(-1)
+                .unwrap()
+                .as_text()
+                .unwrap(),
+            "x"
+        );
+        assert!(arr.unwrap().access_array_element(3).is_none());
+        assert!(arr.unwrap().access_array_element(-4).is_none());
+        assert!(r.access_array_element(0).is_none());
+        assert_eq!(r.access_path(["a", "b", "0"]).unwrap().to_string(), "10");
+        assert!(r.access_path(["a", "c"]).is_none());
+        assert!(r.access_object_field("n").unwrap().as_text().is_none());
+        assert_eq!(arr.unwrap().array_elements().unwrap().count(), 3);
+    }
+
+    #[test]
+    fn test_jsonb_cmp() {
+        let ordered = [
+            "null",
+            r#""b""#,
+            "1",
+            "2.5",
+            "false",
+            "true",
+            "[]",
+            "[3]",
+            "[1, 2]",
+            "{}",
+            r#"{"a": 2}"#,
+            r#"{"b": 1}"#,
+        ];
+        for (l, r) in ordered.iter().zip(ordered.iter().skip(1)) {
+            assert!(jsonb(l) < jsonb(r), "{} < {}", l, r);
+        }
+    }
+
+    #[test]
+    fn test_jsonb_value_serialize() {
+        let v = jsonb(r#"{"a": [1, "x", null]}"#);
+        let buf = v.as_scalar_ref().value_serialize();
+        assert_eq!(JsonbVal::value_deserialize(&buf).unwrap(), v);
+    }
+}
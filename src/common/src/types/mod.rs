@@ -37,6 +37,7 @@ pub use scalar_impl::*;
 pub mod chrono_wrapper;
 pub mod decimal;
 pub mod interval;
+pub mod jsonb;
 pub mod struct_type;
 pub mod to_text;
 
@@ -49,6 +50,7 @@ pub use chrono_wrapper::{
 pub use decimal::Decimal;
 pub use interval::*;
 use itertools::Itertools;
+pub use jsonb::{JsonbRef, JsonbVal};
 pub use ops::{CheckedAdd, IsNegative};
 pub use ordered_float::IntoOrdered;
 use paste::paste;
@@ -109,6 +111,8 @@ pub enum DataType {
     Timestampz,
     #[display("interval")]
     Interval,
+    #[display("jsonb")]
+    Jsonb,
     #[display("{0}")]
     Struct(Arc<StructType>),
     #[display("{datatype}[]")]
@@ -130,7 +134,8 @@ impl DataTypeName {
             | DataTypeName::Timestamp
             | DataTypeName::Timestampz
             | DataTypeName::Time
-            | DataTypeName::Interval => true,
+            | DataTypeName::Interval
+            | DataTypeName::Jsonb => true,
 
             DataTypeName::Struct | DataTypeName::List => false,
         }
@@ -151,6 +156,7 @@ impl DataTypeName {
             DataTypeName::Timestampz => DataType::Timestampz,
             DataTypeName::Time => DataType::Time,
             DataTypeName::Interval => DataType::Interval,
+            DataTypeName::Jsonb => DataType::Jsonb,
             DataTypeName::Struct | DataTypeName::List => {
                 return None;
             }
@@ -188,6 +194,7 @@ impl From<&ProstDataType> for DataType {
             TypeName::Timestampz => DataType::Timestampz,
             TypeName::Decimal => DataType::Decimal,
             TypeName::Interval => DataType::Interval,
+            TypeName::Jsonb => DataType::Jsonb,
             TypeName::Struct => {
                 let fields: Vec<DataType> = proto.field_type.iter().map(|f| f.into()).collect_vec();
                 let field_names: Vec<String> = proto.field_names.iter().cloned().collect_vec();
@@ -219,6 +226,7 @@ impl DataType {
             DataType::Timestamp => NaiveDateTimeArrayBuilder::new(capacity).into(),
             DataType::Timestampz => PrimitiveArrayBuilder::<i64>::new(capacity).into(),
             DataType::Interval => IntervalArrayBuilder::new(capacity).into(),
+            DataType::Jsonb => JsonbArrayBuilder::new(capacity).into(),
             DataType::Struct(t) => {
                 StructArrayBuilder::with_meta(capacity, t.to_array_meta()).into()
             }
@@ -247,6 +255,7 @@ impl DataType {
             DataType::Timestampz => TypeName::Timestampz,
             DataType::Decimal => TypeName::Decimal,
             DataType::Interval => TypeName::Interval,
+            DataType::Jsonb => TypeName::Jsonb,
             DataType::Struct { .. } => TypeName::Struct,
             DataType::List { .. } => TypeName::List,
         }
@@ -306,7 +315,7 @@ impl DataType {
         match self {
             Boolean | Int16 | Int32 | Int64 => true,
             Float32 | Float64 | Decimal | Date | Varchar | Time | Timestamp | Timestampz
-            | Interval => false,
+            | Interval | Jsonb => false,
             Struct(t) => t.fields.iter().all(|dt| dt.mem_cmp_eq_value_enc()),
             List { datatype } => datatype.mem_cmp_eq_value_enc(),
         }
@@ -403,6 +412,7 @@ macro_rules! for_all_scalar_variants {
             { Bool, bool, bool, bool },
             { Decimal, decimal, Decimal, Decimal  },
             { Interval, interval, IntervalUnit, IntervalUnit },
+            { Jsonb, jsonb, JsonbVal, JsonbRef<'scalar> },
             { NaiveDate, naivedate, NaiveDateWrapper, NaiveDateWrapper },
             { NaiveDateTime, naivedatetime, NaiveDateTimeWrapper, NaiveDateTimeWrapper },
             { NaiveTime, naivetime, NaiveTimeWrapper, NaiveTimeWrapper },
@@ -775,6 +785,12 @@ impl ScalarRefImpl<'_> {
             Self::Struct(_) => todo!("Don't support struct serialization yet"),
             Self::List(_) => todo!("Don't support list serialization yet"),
             Self::Interval(v) => v.to_sql(ty, &mut output).unwrap(),
+            Self::Jsonb(v) => {
+                // The binary format of jsonb is a version number followed by the text form.
+                output.put_u8(1);
+                output.put_slice(v.to_string().as_bytes());
+                IsNull::No
+            }
         };
         output.freeze()
     }
@@ -801,6 +817,7 @@ impl ScalarRefImpl<'_> {
                 ser.serialize_decimal(mantissa, scale)?;
             }
             Self::Interval(v) => v.serialize(ser)?,
+            Self::Jsonb(v) => v.memcmp_serialize(ser)?,
             Self::NaiveDate(v) => ser.serialize_naivedate(v.0.num_days_from_ce())?,
             Self::NaiveDateTime(v) => {
                 ser.serialize_naivedatetime(v.0.timestamp(), v.0.timestamp_subsec_nanos())?
@@ -848,6 +865,7 @@ impl ScalarImpl {
                 }
             }),
             Ty::Interval => Self::Interval(IntervalUnit::deserialize(de)?),
+            Ty::Jsonb => Self::Jsonb(JsonbVal::memcmp_deserialize(de)?),
             Ty::Time => Self::NaiveTime({
                 let (secs, nano) = de.deserialize_naivetime()?;
                 NaiveTimeWrapper::with_secs_nano(secs, nano)?
@@ -903,7 +921,7 @@ impl ScalarImpl {
                         .iter()
                         .map(|field| Self::encoding_data_size(field, deserializer))
                         .try_fold(0, |a, b| b.map(|b| a + b))?,
-                    DataType::Varchar => deserializer.read_bytes_len()?,
+                    DataType::Varchar | DataType::Jsonb => deserializer.read_bytes_len()?,
                 };
 
                 // consume offset of fixed_type
@@ -937,6 +955,7 @@ pub fn literal_type_match(data_type: &DataType, literal: Option<&ScalarImpl>) ->
                     | (DataType::Timestampz, ScalarImpl::Int64(_))
                     | (DataType::Decimal, ScalarImpl::Decimal(_))
                     | (DataType::Interval, ScalarImpl::Interval(_))
+                    | (DataType::Jsonb, ScalarImpl::Jsonb(_))
                     | (DataType::Struct { .. }, ScalarImpl::Struct(_))
                     | (DataType::List { .. }, ScalarImpl::List(_))
             )
@@ -1118,6 +1137,10 @@ mod tests {
                     ScalarImpl::Interval(IntervalUnit::new(2, 3, 3333)),
                     DataType::Interval,
                 ),
+                DataTypeName::Jsonb => (
+                    ScalarImpl::Jsonb(r#"{"a": [2, 3.3]}"#.parse().unwrap()),
+                    DataType::Jsonb,
+                ),
                 DataTypeName::Struct => (
                     ScalarImpl::Struct(StructValue::new(vec![
                         ScalarImpl::Int64(233).into(),
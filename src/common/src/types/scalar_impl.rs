@@ -215,6 +215,32 @@ impl<'a> ScalarRef<'a> for IntervalUnit {
     }
 }
 
+/// Implement `Scalar` for `JsonbVal`.
+impl Scalar for JsonbVal {
+    type ScalarRefType<'a> = JsonbRef<'a>;
+
+    fn as_scalar_ref(&self) -> JsonbRef<'_> {
+        JsonbRef::new(self.value())
+    }
+
+    fn to_scalar_value(self) -> ScalarImpl {
+        ScalarImpl::Jsonb(self)
+    }
+}
+
+/// Implement `ScalarRef` for `JsonbRef`.
+impl<'a> ScalarRef<'a> for JsonbRef<'a> {
+    type ScalarType = JsonbVal;
+
+    fn to_owned_scalar(&self) -> JsonbVal {
+        JsonbVal::new(self.value().clone())
+    }
+
+    fn hash_scalar<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hash(state)
+    }
+}
+
 /// Implement `Scalar` for `NaiveDateWrapper`.
 impl Scalar for NaiveDateWrapper {
     type ScalarRefType<'a> = NaiveDateWrapper;
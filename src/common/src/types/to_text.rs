@@ -38,7 +38,8 @@ implement_using_to_string! {
     { String },
     { &str },
     { crate::types::OrderedF32 },
-    { crate::types::OrderedF64 }
+    { crate::types::OrderedF64 },
+    { crate::types::JsonbRef<'_> }
 }
 
 impl ToText for bool {
@@ -62,6 +63,7 @@ impl ToText for ScalarRefImpl<'_> {
             ScalarRefImpl::Float64(f) => f.to_text(),
             ScalarRefImpl::Decimal(d) => d.to_text(),
             ScalarRefImpl::Interval(i) => i.to_text(),
+            ScalarRefImpl::Jsonb(j) => j.to_text(),
             ScalarRefImpl::NaiveDate(d) => d.to_text(),
             ScalarRefImpl::NaiveTime(t) => t.to_text(),
             ScalarRefImpl::NaiveDateTime(dt) => dt.to_text(),
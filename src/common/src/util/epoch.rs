@@ -77,6 +77,15 @@ impl Epoch {
         *UNIX_SINGULARITY_DATE_EPOCH + Duration::from_millis(self.physical_time())
     }
 
+    /// Returns the physical time of the epoch as the milliseconds since the UNIX epoch.
+    pub fn as_unix_millis(&self) -> u64 {
+        UNIX_SINGULARITY_DATE_EPOCH
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+            + self.physical_time()
+    }
+
     /// Returns the epoch subtract `relative_time_ms`, which used for ttl to get epoch corresponding
     /// to the lowerbound timepoint (`src/storage/src/hummock/iterator/forward_user.rs`)
     pub fn subtract_ms(&self, relative_time_ms: u64) -> Self {
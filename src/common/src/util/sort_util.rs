@@ -43,6 +43,14 @@ impl OrderType {
             OrderType::Descending => ProstOrderType::Descending,
         }
     }
+
+    /// Returns the opposite order type.
+    pub fn reverse(self) -> OrderType {
+        match self {
+            OrderType::Ascending => OrderType::Descending,
+            OrderType::Descending => OrderType::Ascending,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -172,6 +180,7 @@ pub fn compare_rows(lhs: &Row, rhs: &Row, order_pairs: &[OrderPair]) -> Result<O
                 Bool,
                 Decimal,
                 Interval,
+                Jsonb,
                 NaiveDate,
                 NaiveDateTime,
                 NaiveTime,
@@ -237,6 +246,7 @@ pub fn compare_rows_in_chunk(
                 Bool,
                 Decimal,
                 Interval,
+                Jsonb,
                 NaiveDate,
                 NaiveDateTime,
                 NaiveTime,
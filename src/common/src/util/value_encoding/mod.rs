@@ -22,7 +22,7 @@ use itertools::Itertools;
 use crate::array::{ListRef, ListValue, StructRef, StructValue};
 use crate::types::struct_type::StructType;
 use crate::types::{
-    to_datum_ref, DataType, Datum, DatumRef, Decimal, IntervalUnit, NaiveDateTimeWrapper,
+    to_datum_ref, DataType, Datum, DatumRef, Decimal, IntervalUnit, JsonbVal, NaiveDateTimeWrapper,
     NaiveDateWrapper, NaiveTimeWrapper, OrderedF32, OrderedF64, ScalarImpl, ScalarRefImpl,
 };
 
@@ -80,6 +80,7 @@ fn serialize_value(value: ScalarRefImpl<'_>, buf: &mut impl BufMut) {
         ScalarRefImpl::Bool(v) => buf.put_u8(v as u8),
         ScalarRefImpl::Decimal(v) => serialize_decimal(&v, buf),
         ScalarRefImpl::Interval(v) => serialize_interval(&v, buf),
+        ScalarRefImpl::Jsonb(v) => serialize_str(&v.value_serialize(), buf),
         ScalarRefImpl::NaiveDate(v) => serialize_naivedate(v.0.num_days_from_ce(), buf),
         ScalarRefImpl::NaiveDateTime(v) => {
             serialize_naivedatetime(v.0.timestamp(), v.0.timestamp_subsec_nanos(), buf)
@@ -154,6 +155,7 @@ fn deserialize_value(ty: &DataType, data: &mut impl Buf) -> Result<Datum> {
         DataType::Boolean => ScalarImpl::Bool(deserialize_bool(data)?),
         DataType::Decimal => ScalarImpl::Decimal(deserialize_decimal(data)?),
         DataType::Interval => ScalarImpl::Interval(deserialize_interval(data)?),
+        DataType::Jsonb => ScalarImpl::Jsonb(deserialize_jsonb(data)?),
         DataType::Time => ScalarImpl::NaiveTime(deserialize_naivetime(data)?),
         DataType::Timestamp => ScalarImpl::NaiveDateTime(deserialize_naivedatetime(data)?),
         DataType::Timestampz => ScalarImpl::Int64(data.get_i64_le()),
@@ -191,6 +193,13 @@ fn deserialize_str(data: &mut impl Buf) -> Result<String> {
     String::from_utf8(bytes).map_err(ValueEncodingError::InvalidUtf8)
 }
 
+fn deserialize_jsonb(data: &mut impl Buf) -> Result<JsonbVal> {
+    let len = data.get_u32_le();
+    let mut bytes = vec![0; len as usize];
+    data.copy_to_slice(&mut bytes);
+    JsonbVal::value_deserialize(&bytes).ok_or(ValueEncodingError::InvalidJsonbEncoding)
+}
+
 fn deserialize_bool(data: &mut impl Buf) -> Result<bool> {
     match data.get_u8() {
         1 => Ok(true),
@@ -22,6 +22,7 @@
 #[macro_use]
 extern crate tracing;
 
+pub mod memory_management;
 pub mod rpc;
 pub mod server;
 
@@ -79,8 +80,9 @@ pub struct ComputeNodeOpts {
     #[clap(long, default_value = "")]
     pub file_cache_dir: String,
 
-    /// Enable managed lru cache, or use local lru cache.
-    #[clap(long)]
+    /// Deprecated and has no effect. The executor caches are always managed by the global memory
+    /// manager now, and the option is only kept for the existing command lines.
+    #[clap(long, hide = true)]
     pub enable_managed_cache: bool,
 }
 
@@ -0,0 +1,223 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use risingwave_common::util::epoch::Epoch;
+use risingwave_stream::executor::monitor::StreamingMetrics;
+use tikv_jemalloc_ctl::{epoch as jemalloc_epoch, stats as jemalloc_stats};
+
+/// The compute-node-wide memory manager. It monitors the memory usage of the process and advances
+/// the watermark epoch under memory pressure. All managed caches of the streaming executors (join,
+/// agg, top-n, lookup, etc.) share the watermark and evict the entries accessed before it.
+pub struct GlobalMemoryManager {
+    /// All cached data before the watermark should be evicted.
+    watermark_epoch: Arc<AtomicU64>,
+    /// Total memory can be allocated by the process.
+    total_memory_available_bytes: usize,
+    /// Barrier interval.
+    barrier_interval_ms: u32,
+    metrics: Arc<StreamingMetrics>,
+}
+
+pub type GlobalMemoryManagerRef = Arc<GlobalMemoryManager>;
+
+impl GlobalMemoryManager {
+    const EVICTION_THRESHOLD_AGGRESSIVE: f64 = 0.9;
+    const EVICTION_THRESHOLD_GRACEFUL: f64 = 0.7;
+
+    pub fn new(
+        total_memory_available_bytes: usize,
+        barrier_interval_ms: u32,
+        metrics: Arc<StreamingMetrics>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            watermark_epoch: Arc::new(0.into()),
+            total_memory_available_bytes,
+            barrier_interval_ms,
+            metrics,
+        })
+    }
+
+    /// Returns the watermark epoch to be shared with the managed caches.
+    pub fn get_watermark_epoch(&self) -> Arc<AtomicU64> {
+        self.watermark_epoch.clone()
+    }
+
+    fn set_watermark_time_ms(&self, time_ms: u64) {
+        let epoch = Epoch::from_physical_time(time_ms).0;
+        let watermark_epoch = self.watermark_epoch.as_ref();
+        watermark_epoch.store(epoch, Ordering::Relaxed);
+    }
+
+    /// Returns the eviction step, i.e. the number of barrier intervals to move the watermark
+    /// forward, given the last step and the memory usage in the last and the current check.
+    fn next_step(
+        &self,
+        step: u64,
+        last_total_bytes_used: usize,
+        cur_total_bytes_used: usize,
+    ) -> u64 {
+        let mem_threshold_graceful =
+            (self.total_memory_available_bytes as f64 * Self::EVICTION_THRESHOLD_GRACEFUL) as usize;
+        let mem_threshold_aggressive = (self.total_memory_available_bytes as f64
+            * Self::EVICTION_THRESHOLD_AGGRESSIVE) as usize;
+
+        // The strategy works as follow:
+        //
+        // 1. When the memory usage is below the graceful threshold, we do not evict any caches
+        // and reset the step to 0.
+        //
+        // 2. When the memory usage is between the graceful and aggressive threshold:
+        //   - If the last eviction memory usage decrease after last eviction, we set the eviction
+        //     step to 1
+        //   - or else we set the step to last_step + 1
+        //
+        // 3. When the memory usage exceeds aggressive threshold:
+        //   - If the memory usage decrease after last eviction, we set the eviction step to
+        //     last_step * 2, or 2 if there was no eviction
+        //   - or else we keep the step as last_step
+
+        if cur_total_bytes_used < mem_threshold_graceful {
+            // Do not evict if the memory usage is lower than `mem_threshold_graceful`
+            0
+        } else if cur_total_bytes_used < mem_threshold_aggressive {
+            // Gracefully evict
+            if last_total_bytes_used > cur_total_bytes_used {
+                1
+            } else {
+                step + 1
+            }
+        } else if last_total_bytes_used > cur_total_bytes_used {
+            // Aggressively evict
+            if step == 0 {
+                2
+            } else {
+                step * 2
+            }
+        } else {
+            step
+        }
+    }
+
+    /// Returns the watermark time moved forward by `step` barrier intervals, which never goes
+    /// beyond the current time, otherwise the entries accessed in the future epochs would be
+    /// evicted at once.
+    fn next_watermark_time_ms(
+        &self,
+        watermark_time_ms: u64,
+        step: u64,
+        physical_now_ms: u64,
+    ) -> u64 {
+        (watermark_time_ms + self.barrier_interval_ms as u64 * step).min(physical_now_ms)
+    }
+
+    /// Runs the memory control loop, which checks the memory usage on every barrier interval and
+    /// moves the watermark forward if necessary.
+    pub async fn run(self: Arc<Self>) {
+        let mut watermark_time_ms = Epoch::physical_now();
+        let mut last_total_bytes_used = 0;
+        let mut step = 0;
+
+        let jemalloc_epoch_mib = jemalloc_epoch::mib().unwrap();
+        let jemalloc_allocated_mib = jemalloc_stats::allocated::mib().unwrap();
+
+        let mut tick_interval =
+            tokio::time::interval(Duration::from_millis(self.barrier_interval_ms as u64));
+
+        loop {
+            // Wait for a while to check if need eviction.
+            tick_interval.tick().await;
+
+            if let Err(e) = jemalloc_epoch_mib.advance() {
+                tracing::warn!("Jemalloc epoch advance failed! {:?}", e);
+            }
+
+            let cur_total_bytes_used = jemalloc_allocated_mib.read().unwrap_or_else(|e| {
+                tracing::warn!("Jemalloc read allocated failed! {:?}", e);
+                last_total_bytes_used
+            });
+
+            step = self.next_step(step, last_total_bytes_used, cur_total_bytes_used);
+            last_total_bytes_used = cur_total_bytes_used;
+
+            let physical_now_ms = Epoch::physical_now();
+            watermark_time_ms =
+                self.next_watermark_time_ms(watermark_time_ms, step, physical_now_ms);
+
+            self.set_watermark_time_ms(watermark_time_ms);
+            self.metrics
+                .jemalloc_allocated_bytes
+                .set(cur_total_bytes_used as i64);
+            self.metrics
+                .lru_current_watermark_time_ms
+                .set(watermark_time_ms as i64);
+            self.metrics.lru_physical_now_ms.set(physical_now_ms as i64);
+            self.metrics.lru_runtime_loop_count.inc();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> GlobalMemoryManagerRef {
+        GlobalMemoryManager::new(1000, 100, Arc::new(StreamingMetrics::unused()))
+    }
+
+    #[test]
+    fn test_next_step() {
+        let manager = manager();
+
+        // No eviction below the graceful threshold.
+        assert_eq!(manager.next_step(0, 0, 600), 0);
+        assert_eq!(manager.next_step(4, 950, 699), 0);
+
+        // Graceful eviction, which restarts from 1 once the memory usage decreases.
+        assert_eq!(manager.next_step(0, 600, 750), 1);
+        assert_eq!(manager.next_step(1, 750, 800), 2);
+        assert_eq!(manager.next_step(2, 800, 780), 1);
+
+        // Aggressive eviction.
+        assert_eq!(manager.next_step(0, 950, 920), 2);
+        assert_eq!(manager.next_step(2, 950, 920), 4);
+        assert_eq!(manager.next_step(4, 920, 950), 4);
+    }
+
+    #[test]
+    fn test_next_watermark_time_ms() {
+        let manager = manager();
+        assert_eq!(manager.next_watermark_time_ms(1000, 0, 5000), 1000);
+        assert_eq!(manager.next_watermark_time_ms(1000, 3, 5000), 1300);
+        // The watermark never goes beyond the current time.
+        assert_eq!(manager.next_watermark_time_ms(1000, 50, 5000), 5000);
+    }
+
+    #[test]
+    fn test_watermark_epoch() {
+        let manager = manager();
+        let watermark_epoch = manager.get_watermark_epoch();
+        assert_eq!(watermark_epoch.load(Ordering::Relaxed), 0);
+
+        let time_ms = Epoch::physical_now();
+        manager.set_watermark_time_ms(time_ms);
+        assert_eq!(
+            watermark_epoch.load(Ordering::Relaxed),
+            Epoch::from_physical_time(time_ms).0
+        );
+    }
+}
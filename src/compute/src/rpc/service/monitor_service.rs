@@ -19,22 +19,26 @@ use risingwave_pb::monitor_service::monitor_service_server::MonitorService;
 use risingwave_pb::monitor_service::{
     ProfilingRequest, ProfilingResponse, StackTraceRequest, StackTraceResponse,
 };
+use risingwave_storage::StateStoreImpl;
 use risingwave_stream::task::LocalStreamManager;
 use tonic::{Request, Response, Status};
 
 #[derive(Clone)]
 pub struct MonitorServiceImpl {
     stream_mgr: Arc<LocalStreamManager>,
+    state_store: StateStoreImpl,
     grpc_stack_trace_mgr: GrpcStackTraceManagerRef,
 }
 
 impl MonitorServiceImpl {
     pub fn new(
         stream_mgr: Arc<LocalStreamManager>,
+        state_store: StateStoreImpl,
         grpc_stack_trace_mgr: GrpcStackTraceManagerRef,
     ) -> Self {
         Self {
             stream_mgr,
+            state_store,
             grpc_stack_trace_mgr,
         }
     }
@@ -64,9 +68,19 @@ impl MonitorService for MonitorServiceImpl {
             .map(|(k, v)| (k.to_string(), v.to_string()))
             .collect();
 
+        let upload_traces = match &self.state_store {
+            StateStoreImpl::HummockStateStore(store) => store.inner().get_upload_task_traces(),
+            StateStoreImpl::HummockStateStoreV1(store) => store.inner().get_upload_task_traces(),
+            _ => Default::default(),
+        }
+        .into_iter()
+        .map(|(k, v)| (k, v.to_string()))
+        .collect();
+
         Ok(Response::new(StackTraceResponse {
             actor_traces,
             rpc_traces,
+            upload_traces,
         }))
     }
 
@@ -168,6 +168,7 @@ impl StreamService for StreamServiceImpl {
             request_id: req.request_id,
             status: None,
             create_mview_progress: collect_result.create_mview_progress,
+            actor_barrier_latency: collect_result.actor_barrier_latency,
             synced_sstables: synced_sstables
                 .into_iter()
                 .map(|(compaction_group_id, sst)| GroupedSstableInfo {
@@ -47,6 +47,7 @@ use risingwave_stream::task::{LocalStreamManager, StreamEnvironment};
 use tokio::sync::oneshot::Sender;
 use tokio::task::JoinHandle;
 
+use crate::memory_management::GlobalMemoryManager;
 use crate::rpc::service::exchange_metrics::ExchangeServiceMetrics;
 use crate::rpc::service::exchange_service::ExchangeServiceImpl;
 use crate::rpc::service::monitor_service::{
@@ -68,6 +69,11 @@ pub async fn compute_node_serve(
         config,
         if cfg!(debug_assertions) { "on" } else { "off" }
     );
+    if opts.enable_managed_cache {
+        tracing::warn!(
+            "--enable-managed-cache is deprecated and has no effect, caches are always managed"
+        );
+    }
     // Initialize all the configs
     let storage_config = Arc::new(config.storage.clone());
     let stream_config = Arc::new(config.streaming.clone());
@@ -148,6 +154,7 @@ pub async fn compute_node_serve(
                 read_memory_limiter,
                 sstable_id_manager: storage.sstable_id_manager(),
                 task_progress_manager: Default::default(),
+                compaction_params: storage.inner().compaction_params().clone(),
             });
             // TODO: use normal sstable store for single-process mode.
             let compactor_sstable_store = CompactorSstableStore::new(
@@ -186,6 +193,14 @@ pub async fn compute_node_serve(
         }),
     };
 
+    // Run a background memory monitor
+    let memory_mgr = GlobalMemoryManager::new(
+        config.streaming.total_memory_available_bytes,
+        config.streaming.barrier_interval_ms,
+        streaming_metrics.clone(),
+    );
+    tokio::spawn(memory_mgr.clone().run());
+
     // Initialize the managers.
     let batch_mgr = Arc::new(BatchManager::new(config.batch.worker_threads_num));
     let stream_mgr = Arc::new(LocalStreamManager::new(
@@ -194,7 +209,7 @@ pub async fn compute_node_serve(
         streaming_metrics.clone(),
         config.streaming.clone(),
         async_stack_trace_config.clone(),
-        opts.enable_managed_cache,
+        memory_mgr.get_watermark_epoch(),
     ));
     let source_mgr = Arc::new(TableSourceManager::new(
         source_metrics,
@@ -221,7 +236,8 @@ pub async fn compute_node_serve(
         client_addr.clone(),
         stream_config,
         worker_id,
-        state_store,
+        state_store.clone(),
+        meta_client.clone(),
     );
 
     // Generally, one may use `risedev ctl trace` to manually get the trace reports. However, if
@@ -238,7 +254,8 @@ pub async fn compute_node_serve(
     let exchange_srv =
         ExchangeServiceImpl::new(batch_mgr, stream_mgr.clone(), exchange_srv_metrics);
     let stream_srv = StreamServiceImpl::new(stream_mgr.clone(), stream_env.clone());
-    let monitor_srv = MonitorServiceImpl::new(stream_mgr, grpc_stack_trace_mgr.clone());
+    let monitor_srv =
+        MonitorServiceImpl::new(stream_mgr, state_store, grpc_stack_trace_mgr.clone());
 
     let (shutdown_send, mut shutdown_recv) = tokio::sync::oneshot::channel::<()>();
     let join_handle = tokio::spawn(async move {
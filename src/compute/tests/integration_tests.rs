@@ -34,6 +34,7 @@ use risingwave_common::test_prelude::DataChunkTestExt;
 use risingwave_common::types::{DataType, IntoOrdered};
 use risingwave_common::util::epoch::EpochPair;
 use risingwave_common::util::sort_util::{OrderPair, OrderType};
+use risingwave_connector::source::SourceRateLimit;
 use risingwave_source::table_test_utils::create_table_source_desc_builder;
 use risingwave_source::{TableSourceManager, TableSourceManagerRef};
 use risingwave_storage::memory::MemoryStateStore;
@@ -147,6 +148,9 @@ async fn test_table_materialize() -> StreamResult<()> {
         "SourceExecutor".to_string(),
         Arc::new(StreamingMetrics::unused()),
         u64::MAX,
+        vec![],
+        SourceRateLimit::default(),
+        false,
     )?;
 
     // Create a `Materialize` to write the changes to storage
@@ -178,6 +182,7 @@ async fn test_table_materialize() -> StreamResult<()> {
         source_manager.clone(),
         insert_inner,
         "InsertExecutor".to_string(),
+        false,
     ));
 
     tokio::spawn(async move {
@@ -210,6 +215,7 @@ async fn test_table_materialize() -> StreamResult<()> {
     let scan = Box::new(RowSeqScanExecutor::new(
         table.clone(),
         vec![ScanRange::full()],
+        None,
         u64::MAX,
         1024,
         "RowSeqExecutor2".to_string(),
@@ -238,9 +244,7 @@ async fn test_table_materialize() -> StreamResult<()> {
     let message = materialize.next().await.unwrap()?;
     let mut col_row_ids = vec![];
     match message {
-        Message::Watermark(_) => {
-            todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-        }
+        Message::Watermark(_) => unreachable!(),
         Message::Chunk(c) => {
             let col_row_id = c.columns()[0].array_ref().as_int64();
             col_row_ids.push(col_row_id.value_at(0).unwrap());
@@ -272,6 +276,7 @@ async fn test_table_materialize() -> StreamResult<()> {
     let scan = Box::new(RowSeqScanExecutor::new(
         table.clone(),
         vec![ScanRange::full()],
+        None,
         u64::MAX,
         1024,
         "RowSeqScanExecutor2".to_string(),
@@ -302,6 +307,7 @@ async fn test_table_materialize() -> StreamResult<()> {
         source_manager.clone(),
         delete_inner,
         "DeleteExecutor".to_string(),
+        false,
     ));
 
     tokio::spawn(async move {
@@ -313,9 +319,7 @@ async fn test_table_materialize() -> StreamResult<()> {
     // Poll `Materialize`, should output the same deletion stream chunk
     let message = materialize.next().await.unwrap()?;
     match message {
-        Message::Watermark(_) => {
-            todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-        }
+        Message::Watermark(_) => unreachable!(),
         Message::Chunk(c) => {
             let col_row_id = c.columns()[0].array_ref().as_int64();
             assert_eq!(col_row_id.value_at(0).unwrap(), col_row_ids[0]);
@@ -344,6 +348,7 @@ async fn test_table_materialize() -> StreamResult<()> {
     let scan = Box::new(RowSeqScanExecutor::new(
         table,
         vec![ScanRange::full()],
+        None,
         u64::MAX,
         1024,
         "RowSeqScanExecutor2".to_string(),
@@ -410,6 +415,7 @@ async fn test_row_seq_scan() -> Result<()> {
     let executor = Box::new(RowSeqScanExecutor::new(
         table,
         vec![ScanRange::full()],
+        None,
         u64::MAX,
         1,
         "RowSeqScanExecutor2".to_string(),
@@ -28,5 +28,6 @@
 pub mod aws_utils;
 pub mod error;
 mod macros;
+pub mod schema;
 pub mod sink;
 pub mod source;
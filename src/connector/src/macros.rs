@@ -83,6 +83,18 @@ macro_rules! impl_split {
             fn restore_from_bytes(bytes: &[u8]) -> Result<Self> {
                 SplitImpl::try_from(&ConnectorSplit::decode(bytes)?)
             }
+
+            fn parent_ids(&self) -> Vec<SplitId> {
+                match self {
+                    $( Self::$variant_name(inner) => inner.parent_ids(), )*
+                }
+            }
+
+            fn is_finished(&self) -> bool {
+                match self {
+                    $( Self::$variant_name(inner) => inner.is_finished(), )*
+                }
+            }
         }
 
         impl SplitImpl {
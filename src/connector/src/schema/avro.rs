@@ -0,0 +1,143 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Maps the avro schemas to the columns of a source.
+
+use apache_avro::Schema;
+use itertools::Itertools;
+use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::DataType;
+use risingwave_pb::plan_common::ColumnDesc;
+
+/// Maps the avro schema, which must be a record at top level, to relational schema.
+pub fn avro_schema_to_columns(schema: &Schema) -> Result<Vec<ColumnDesc>> {
+    // there must be a record at top level
+    if let Schema::Record { fields, .. } = schema {
+        let mut index = 0;
+        let fields = fields
+            .iter()
+            .map(|field| avro_field_to_column_desc(&field.name, &field.schema, &mut index))
+            .collect::<Result<Vec<_>>>()?;
+        tracing::info!("fields is {:?}", fields);
+        Ok(fields)
+    } else {
+        Err(RwError::from(InternalError(
+            "schema invalid, record required".into(),
+        )))
+    }
+}
+
+/// Returns whether the top-level field can be absent in the messages written by older schemas,
+/// i.e. it is nullable or has a default value.
+pub fn is_nullable_avro_field(schema: &Schema, name: &str) -> bool {
+    let Schema::Record { fields, .. } = schema else {
+        return false;
+    };
+    fields.iter().find(|f| f.name == name).map_or(false, |f| {
+        let nullable = match &f.schema {
+            Schema::Union(union) => union.variants().contains(&Schema::Null),
+            _ => false,
+        };
+        nullable || f.default.is_some()
+    })
+}
+
+fn avro_field_to_column_desc(name: &str, schema: &Schema, index: &mut i32) -> Result<ColumnDesc> {
+    if let Schema::Union(union) = schema
+        && let [Schema::Null, schema] | [schema, Schema::Null] = union.variants()
+    {
+        return avro_field_to_column_desc(name, schema, index);
+    }
+    let data_type = avro_type_mapping(schema)?;
+    match schema {
+        Schema::Record {
+            name: schema_name,
+            fields,
+            ..
+        } => {
+            let vec_column = fields
+                .iter()
+                .map(|f| avro_field_to_column_desc(&f.name, &f.schema, index))
+                .collect::<Result<Vec<_>>>()?;
+            *index += 1;
+            Ok(ColumnDesc {
+                column_type: Some(data_type.to_protobuf()),
+                column_id: *index,
+                name: name.to_owned(),
+                field_descs: vec_column,
+                type_name: schema_name.to_string(),
+            })
+        }
+        _ => {
+            *index += 1;
+            Ok(ColumnDesc {
+                column_type: Some(data_type.to_protobuf()),
+                column_id: *index,
+                name: name.to_owned(),
+                ..Default::default()
+            })
+        }
+    }
+}
+
+/// Maps avro type to RW type.
+pub fn avro_type_mapping(schema: &Schema) -> Result<DataType> {
+    let data_type = match schema {
+        Schema::String => DataType::Varchar,
+        Schema::Int => DataType::Int32,
+        Schema::Long => DataType::Int64,
+        Schema::Boolean => DataType::Boolean,
+        Schema::Float => DataType::Float32,
+        Schema::Double => DataType::Float64,
+        Schema::Date => DataType::Date,
+        Schema::TimestampMillis => DataType::Timestamp,
+        Schema::TimestampMicros => DataType::Timestamp,
+        Schema::Duration => DataType::Interval,
+        Schema::Decimal { .. } => DataType::Decimal,
+        Schema::Enum { .. } => DataType::Varchar,
+        Schema::Record { fields, .. } => {
+            let struct_fields = fields
+                .iter()
+                .map(|f| avro_type_mapping(&f.schema))
+                .collect::<Result<Vec<_>>>()?;
+            let struct_names = fields.iter().map(|f| f.name.clone()).collect_vec();
+            DataType::new_struct(struct_fields, struct_names)
+        }
+        Schema::Array(item_schema) => {
+            let item_type = avro_type_mapping(item_schema.as_ref())?;
+            DataType::List {
+                datatype: Box::new(item_type),
+            }
+        }
+        Schema::Union(union) => match union.variants() {
+            // A nullable field is a union of null and the type.
+            [Schema::Null, schema] | [schema, Schema::Null] => avro_type_mapping(schema)?,
+            _ => {
+                return Err(RwError::from(InternalError(format!(
+                    "unsupported union in Avro, only nullable types are supported: {:?}",
+                    schema
+                ))));
+            }
+        },
+        _ => {
+            return Err(RwError::from(InternalError(format!(
+                "unsupported type in Avro: {:?}",
+                schema
+            ))));
+        }
+    };
+
+    Ok(data_type)
+}
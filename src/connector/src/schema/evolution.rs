@@ -0,0 +1,293 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Schema evolution of the sources whose schemas are fetched from a schema registry. With
+//! [`SchemaEvolutionPolicy::Backward`], the nullable columns added to the upstream schema are
+//! detected by meta and proposed to the frontends, which add them to the source with the
+//! `SELECT *` materialized views on it replanned.
+
+use std::collections::HashMap;
+
+use risingwave_common::error::ErrorCode::{InvalidConfigValue, ProtocolError};
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::catalog::StreamSourceInfo;
+use risingwave_pb::plan_common::{ColumnCatalog, ColumnDesc, RowFormatType};
+
+use super::avro::{avro_schema_to_columns, is_nullable_avro_field};
+use super::protobuf::{
+    compile_proto, get_message_descriptor, is_nullable_pb_field, pb_schema_to_columns,
+};
+use super::schema_registry::ConfluentSchemaResolver;
+
+pub const SCHEMA_EVOLUTION_POLICY_KEY: &str = "schema.evolution.policy";
+const PROTOBUF_MESSAGE_KEY: &str = "proto.message";
+const KAFKA_TOPIC_KEY: &str = "kafka.topic";
+
+/// How a source follows the changes of its upstream schema.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchemaEvolutionPolicy {
+    /// The columns of the source are fixed once it is created.
+    #[default]
+    None,
+    /// The nullable columns added to the upstream schema are added to the source, which is
+    /// compatible with both the old and the new messages. Other changes are rejected.
+    Backward,
+}
+
+impl SchemaEvolutionPolicy {
+    /// Parses the policy of a source from its properties. Only the sources in avro or protobuf
+    /// with a schema registry can evolve.
+    pub fn from_properties(
+        properties: &HashMap<String, String>,
+        info: &StreamSourceInfo,
+    ) -> Result<Self> {
+        let policy = match properties
+            .get(SCHEMA_EVOLUTION_POLICY_KEY)
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            None | Some("none") => Self::None,
+            Some("backward") => Self::Backward,
+            Some(policy) => {
+                return Err(RwError::from(InvalidConfigValue {
+                    config_entry: SCHEMA_EVOLUTION_POLICY_KEY.to_string(),
+                    config_value: policy.to_string(),
+                }))
+            }
+        };
+
+        let row_format = RowFormatType::from_i32(info.row_format);
+        if policy == Self::Backward
+            && !(info.use_schema_registry
+                && matches!(
+                    row_format,
+                    Some(RowFormatType::Avro | RowFormatType::Protobuf)
+                ))
+        {
+            return Err(RwError::from(ProtocolError(format!(
+                "{} can only be set on the sources in avro or protobuf with a schema registry",
+                SCHEMA_EVOLUTION_POLICY_KEY
+            ))));
+        }
+        Ok(policy)
+    }
+}
+
+/// Fetches the latest schema of the source from the schema registry, and returns the columns
+/// added to it compared with `columns`, whose ids follow the existing ones.
+pub async fn detect_added_columns(
+    info: &StreamSourceInfo,
+    properties: &HashMap<String, String>,
+    columns: &[ColumnCatalog],
+) -> Result<Vec<ColumnCatalog>> {
+    let required = |key: &str| {
+        properties.get(key).ok_or_else(|| {
+            RwError::from(ProtocolError(format!(
+                "Must specify '{}' in WITH clause",
+                key
+            )))
+        })
+    };
+    let resolver = ConfluentSchemaResolver::new(&info.row_schema_location);
+    let subject = format!("{}-value", required(KAFKA_TOPIC_KEY)?);
+    let latest = match RowFormatType::from_i32(info.row_format) {
+        Some(RowFormatType::Avro) => {
+            let (_, schema) = resolver.get_by_subject_latest(&subject).await?;
+            avro_schema_to_columns(&schema)?
+                .into_iter()
+                .map(|desc| {
+                    let nullable = is_nullable_avro_field(&schema, &desc.name);
+                    (desc, nullable)
+                })
+                .collect()
+        }
+        Some(RowFormatType::Protobuf) => {
+            let (_, proto) = resolver.get_raw_by_subject_latest(&subject).await?;
+            let message_descriptor = get_message_descriptor(
+                &compile_proto(proto)?,
+                &info.row_schema_location,
+                required(PROTOBUF_MESSAGE_KEY)?,
+            )?;
+            pb_schema_to_columns(&message_descriptor)?
+                .into_iter()
+                .map(|desc| {
+                    let nullable = is_nullable_pb_field(&message_descriptor, &desc.name);
+                    (desc, nullable)
+                })
+                .collect()
+        }
+        _ => {
+            return Err(RwError::from(ProtocolError(
+                "only the schemas of avro and protobuf can evolve".to_string(),
+            )))
+        }
+    };
+    diff_columns(columns, &info.meta_columns, latest)
+}
+
+/// Compares the columns of the source with the columns mapped from the latest schema, each of
+/// which comes with whether it is nullable. The columns parsed from the payload must remain with
+/// the same types, and the new columns must be nullable, so that the old messages can still be
+/// parsed.
+fn diff_columns(
+    columns: &[ColumnCatalog],
+    meta_columns: &HashMap<i32, i32>,
+    latest: Vec<(ColumnDesc, bool)>,
+) -> Result<Vec<ColumnCatalog>> {
+    let incompatible = |reason: String| {
+        RwError::from(ProtocolError(format!(
+            "incompatible change of the upstream schema: {}",
+            reason
+        )))
+    };
+
+    let descs = columns
+        .iter()
+        .map(|c| c.column_desc.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    // The hidden row id column and the columns of `INCLUDE` are not parsed from the payload.
+    let is_payload_column = |c: &ColumnCatalog| {
+        !c.is_hidden && !meta_columns.contains_key(&c.column_desc.as_ref().unwrap().column_id)
+    };
+
+    for desc in columns
+        .iter()
+        .filter(|c| is_payload_column(c))
+        .map(|c| c.column_desc.as_ref().unwrap())
+    {
+        match latest.iter().find(|(latest, _)| latest.name == desc.name) {
+            None => return Err(incompatible(format!("column {} is removed", desc.name))),
+            Some((latest, _)) if latest.column_type != desc.column_type => {
+                return Err(incompatible(format!(
+                    "type of column {} is changed",
+                    desc.name
+                )))
+            }
+            Some(_) => {}
+        }
+    }
+
+    let mut next_column_id = descs.iter().map(|d| d.column_id).max().unwrap_or(0) + 1;
+    let mut added = vec![];
+    for (mut desc, nullable) in latest {
+        if let Some(existing) = columns
+            .iter()
+            .find(|c| c.column_desc.as_ref().unwrap().name == desc.name)
+        {
+            if is_payload_column(existing) {
+                continue;
+            }
+            return Err(incompatible(format!(
+                "column {} conflicts with an existing column",
+                desc.name
+            )));
+        }
+        if !nullable {
+            return Err(incompatible(format!(
+                "column {} is added but not nullable",
+                desc.name
+            )));
+        }
+        assign_column_ids(&mut desc, &mut next_column_id);
+        added.push(ColumnCatalog {
+            column_desc: Some(desc),
+            is_hidden: false,
+        });
+    }
+    Ok(added)
+}
+
+/// Assigns the ids to the column and its nested fields in the same order as the parsers, i.e. the
+/// fields before the column.
+fn assign_column_ids(desc: &mut ColumnDesc, next_column_id: &mut i32) {
+    for field in &mut desc.field_descs {
+        assign_column_ids(field, next_column_id);
+    }
+    desc.column_id = *next_column_id;
+    *next_column_id += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::DataType;
+
+    use super::*;
+
+    fn column(id: i32, name: &str, data_type: DataType, is_hidden: bool) -> ColumnCatalog {
+        ColumnCatalog {
+            column_desc: Some(ColumnDesc {
+                column_type: Some(data_type.to_protobuf()),
+                column_id: id,
+                name: name.to_string(),
+                ..Default::default()
+            }),
+            is_hidden,
+        }
+    }
+
+    #[test]
+    fn test_diff_columns() {
+        let columns = vec![
+            column(0, "_row_id", DataType::Int64, true),
+            column(1, "id", DataType::Int32, false),
+            column(2, "name", DataType::Varchar, false),
+            column(3, "offset", DataType::Varchar, false),
+        ];
+        let meta_columns = HashMap::from([(3, 3)]);
+        let latest = |extra: Vec<(ColumnDesc, bool)>| {
+            let mut latest = vec![
+                (
+                    column(1, "id", DataType::Int32, false).column_desc.unwrap(),
+                    false,
+                ),
+                (
+                    column(2, "name", DataType::Varchar, false)
+                        .column_desc
+                        .unwrap(),
+                    true,
+                ),
+            ];
+            latest.extend(extra);
+            latest
+        };
+
+        let added = diff_columns(&columns, &meta_columns, latest(vec![])).unwrap();
+        assert!(added.is_empty());
+
+        let score = column(3, "score", DataType::Float64, false)
+            .column_desc
+            .unwrap();
+        let added =
+            diff_columns(&columns, &meta_columns, latest(vec![(score.clone(), true)])).unwrap();
+        assert_eq!(added, vec![column(4, "score", DataType::Float64, false)]);
+
+        // The added column must be nullable.
+        assert!(diff_columns(&columns, &meta_columns, latest(vec![(score, false)])).is_err());
+
+        // The added column conflicts with the column of `INCLUDE`.
+        let offset = column(3, "offset", DataType::Int64, false)
+            .column_desc
+            .unwrap();
+        assert!(diff_columns(&columns, &meta_columns, latest(vec![(offset, true)])).is_err());
+
+        // The column is removed.
+        let removed = latest(vec![]).into_iter().take(1).collect();
+        assert!(diff_columns(&columns, &meta_columns, removed).is_err());
+
+        // The type of the column is changed.
+        let mut changed = latest(vec![]);
+        changed[1].0.column_type = Some(DataType::Int64.to_protobuf());
+        assert!(diff_columns(&columns, &meta_columns, changed).is_err());
+    }
+}
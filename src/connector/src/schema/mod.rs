@@ -0,0 +1,23 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The schemas of the sources in avro or protobuf, which are mapped to the columns of the sources
+//! by both the parsers and meta, which follows the upstream schemas fetched from a schema registry.
+
+pub mod avro;
+mod evolution;
+pub mod protobuf;
+pub mod schema_registry;
+
+pub use evolution::*;
@@ -0,0 +1,172 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Maps the protobuf schemas to the columns of a source.
+
+use itertools::Itertools;
+use prost::Message;
+use prost_reflect::{Cardinality, DescriptorPool, FieldDescriptor, Kind, MessageDescriptor};
+use protox::file::{File, FileResolver, GoogleFileResolver};
+use risingwave_common::error::ErrorCode::{NotImplemented, ProtocolError};
+use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_pb::plan_common::ColumnDesc;
+
+pub const PB_TIMESTAMP: &str = "google.protobuf.Timestamp";
+pub const PB_STRUCT: &str = "google.protobuf.Struct";
+
+/// The name under which the `.proto` file fetched from the schema registry is compiled.
+const SCHEMA_REGISTRY_PROTO_FILE: &str = "schema_registry.proto";
+
+/// Whether the message is a well-known type mapped to a RW type other than struct.
+pub fn is_well_known_type(message_descriptor: &MessageDescriptor) -> bool {
+    matches!(message_descriptor.full_name(), PB_TIMESTAMP | PB_STRUCT)
+}
+
+/// Finds the message in an encoded `FileDescriptorSet`.
+pub fn get_message_descriptor(
+    schema_bytes: &[u8],
+    location: &str,
+    message_name: &str,
+) -> Result<MessageDescriptor> {
+    let pool = DescriptorPool::decode(schema_bytes).map_err(|e| {
+        ProtocolError(format!(
+            "cannot build descriptor pool from schema: {}, error: {}",
+            location, e
+        ))
+    })?;
+    let message_descriptor = pool.get_message_by_name(message_name).ok_or_else(|| {
+        ProtocolError(format!(
+            "cannot find message {} in schema: {}.\n poll is {:?}",
+            message_name, location, pool
+        ))
+    })?;
+    Ok(message_descriptor)
+}
+
+/// Resolves the `.proto` file fetched from the schema registry, whose imports can only be the
+/// well-known types.
+struct SchemaRegistryFileResolver {
+    proto: String,
+}
+
+impl FileResolver for SchemaRegistryFileResolver {
+    fn open_file(&self, name: &str) -> std::result::Result<File, protox::Error> {
+        if name == SCHEMA_REGISTRY_PROTO_FILE {
+            File::from_source(name, &self.proto)
+        } else {
+            GoogleFileResolver::new().open_file(name)
+        }
+    }
+}
+
+/// Compiles the text of a `.proto` file into an encoded `FileDescriptorSet`.
+pub fn compile_proto(proto: String) -> Result<Vec<u8>> {
+    let mut compiler = protox::Compiler::with_file_resolver(SchemaRegistryFileResolver { proto });
+    compiler.include_imports(true);
+    compiler
+        .open_file(SCHEMA_REGISTRY_PROTO_FILE)
+        .map_err(|e| ProtocolError(format!("failed to compile protobuf schema: {}", e)))?;
+    Ok(compiler.file_descriptor_set().encode_to_vec())
+}
+
+/// Maps the protobuf schema to relational schema.
+pub fn pb_schema_to_columns(message_descriptor: &MessageDescriptor) -> Result<Vec<ColumnDesc>> {
+    let mut columns = Vec::with_capacity(message_descriptor.fields().len());
+    let mut index = 0;
+    for field in message_descriptor.fields() {
+        columns.push(pb_field_to_col_desc(&field, &mut index)?);
+    }
+
+    Ok(columns)
+}
+
+/// Returns whether the top-level field can be absent in the messages, i.e. it is not `required`.
+pub fn is_nullable_pb_field(message_descriptor: &MessageDescriptor, name: &str) -> bool {
+    message_descriptor
+        .get_field_by_name(name)
+        .map_or(false, |f| f.cardinality() != Cardinality::Required)
+}
+
+/// Maps a protobuf field to a RW column.
+fn pb_field_to_col_desc(field_descriptor: &FieldDescriptor, index: &mut i32) -> Result<ColumnDesc> {
+    let field_type = protobuf_type_mapping(field_descriptor)?;
+    if let Some(m) = field_descriptor
+        .kind()
+        .as_message()
+        .filter(|m| !is_well_known_type(m))
+    {
+        let field_descs = if let DataType::List { .. } = field_type {
+            vec![]
+        } else {
+            m.fields()
+                .map(|f| pb_field_to_col_desc(&f, index))
+                .collect::<Result<Vec<_>>>()?
+        };
+        *index += 1;
+        Ok(ColumnDesc {
+            column_id: *index,
+            name: field_descriptor.name().to_string(),
+            column_type: Some(field_type.to_protobuf()),
+            field_descs,
+            type_name: m.full_name().to_string(),
+        })
+    } else {
+        *index += 1;
+        Ok(ColumnDesc {
+            column_id: *index,
+            name: field_descriptor.name().to_string(),
+            column_type: Some(field_type.to_protobuf()),
+            ..Default::default()
+        })
+    }
+}
+
+/// Maps protobuf type to RW type.
+pub fn protobuf_type_mapping(field_descriptor: &FieldDescriptor) -> Result<DataType> {
+    let field_type = field_descriptor.kind();
+    let mut t = match field_type {
+        Kind::Bool => DataType::Boolean,
+        Kind::Double => DataType::Float64,
+        Kind::Float => DataType::Float32,
+        Kind::Int32 | Kind::Sfixed32 | Kind::Fixed32 => DataType::Int32,
+        Kind::Int64 | Kind::Sfixed64 | Kind::Fixed64 | Kind::Uint32 => DataType::Int64,
+        Kind::Uint64 => DataType::Decimal,
+        Kind::String => DataType::Varchar,
+        Kind::Message(m) if m.full_name() == PB_TIMESTAMP => DataType::Timestampz,
+        Kind::Message(m) if m.full_name() == PB_STRUCT => DataType::Jsonb,
+        Kind::Message(m) => {
+            let fields = m
+                .fields()
+                .map(|f| protobuf_type_mapping(&f))
+                .collect::<Result<Vec<_>>>()?;
+            let field_names = m.fields().map(|f| f.name().to_string()).collect_vec();
+            DataType::new_struct(fields, field_names)
+        }
+        Kind::Enum(_) => DataType::Varchar,
+        actual_type => {
+            return Err(NotImplemented(
+                format!("unsupported field type: {:?}", actual_type),
+                None.into(),
+            )
+            .into());
+        }
+    };
+    if field_descriptor.cardinality() == Cardinality::Repeated {
+        t = DataType::List {
+            datatype: Box::new(t),
+        }
+    }
+    Ok(t)
+}
@@ -0,0 +1,159 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A client of the Confluent Schema Registry, which resolves the avro schemas by the ids in the
+//! Confluent wire format, and fetches the protobuf schemas by subject.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use apache_avro::Schema;
+use hyper::client::HttpConnector;
+use hyper::http::uri::InvalidUri;
+use hyper::{Body, Client, Uri};
+use hyper_tls::HttpsConnector;
+use parking_lot::RwLock;
+use risingwave_common::error::ErrorCode::{InternalError, InvalidParameterValue, ProtocolError};
+use risingwave_common::error::{Result, RwError};
+use serde_json::Value;
+
+/// The magic byte at the beginning of a message in the Confluent wire format.
+const MAGIC_BYTE: u8 = 0;
+
+/// Splits a message in the Confluent wire format into the schema id and the avro datum.
+pub fn extract_schema_id(payload: &[u8]) -> Result<(i32, &[u8])> {
+    match payload {
+        [MAGIC_BYTE, id @ ..] if id.len() >= 4 => {
+            let (id, datum) = id.split_at(4);
+            Ok((i32::from_be_bytes(id.try_into().unwrap()), datum))
+        }
+        _ => Err(RwError::from(ProtocolError(
+            "not a message in the Confluent wire format".to_string(),
+        ))),
+    }
+}
+
+/// [`ConfluentSchemaResolver`] fetches the schemas from the schema registry and caches them by id.
+#[derive(Debug)]
+pub struct ConfluentSchemaResolver {
+    url: String,
+    client: Client<HttpsConnector<HttpConnector>>,
+    cache: RwLock<HashMap<i32, Arc<Schema>>>,
+}
+
+impl ConfluentSchemaResolver {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            client: Client::builder().build::<_, Body>(HttpsConnector::new()),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Fetches the latest schema registered under the subject.
+    pub async fn get_by_subject_latest(&self, subject: &str) -> Result<(i32, Arc<Schema>)> {
+        let (id, schema) = self.get_raw_by_subject_latest(subject).await?;
+        let schema = Self::parse_schema(&schema)?;
+        self.cache.write().insert(id, schema.clone());
+        Ok((id, schema))
+    }
+
+    /// Fetches the definition of the latest schema registered under the subject without parsing
+    /// it, which is the text of a `.proto` file for the protobuf schemas.
+    pub async fn get_raw_by_subject_latest(&self, subject: &str) -> Result<(i32, String)> {
+        let res = self
+            .get_json(&format!(
+                "{}/subjects/{}/versions/latest",
+                self.url, subject
+            ))
+            .await?;
+        let id = res.get("id").and_then(Value::as_i64).ok_or_else(|| {
+            RwError::from(ProtocolError(format!("no schema id in response: {}", res)))
+        })? as i32;
+        Ok((id, Self::schema_str(&res)?.to_string()))
+    }
+
+    /// Returns the schema of the id, which is fetched from the schema registry if not cached.
+    pub async fn get_by_id(&self, id: i32) -> Result<Arc<Schema>> {
+        if let Some(schema) = self.get_cached(id) {
+            return Ok(schema);
+        }
+        let res = self
+            .get_json(&format!("{}/schemas/ids/{}", self.url, id))
+            .await?;
+        let schema = Self::parse_schema(Self::schema_str(&res)?)?;
+        self.cache.write().insert(id, schema.clone());
+        Ok(schema)
+    }
+
+    /// Returns the schema of the id if it has been fetched.
+    pub fn get_cached(&self, id: i32) -> Option<Arc<Schema>> {
+        self.cache.read().get(&id).cloned()
+    }
+
+    fn schema_str(res: &Value) -> Result<&str> {
+        res.get("schema")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RwError::from(ProtocolError(format!("no schema in response: {}", res))))
+    }
+
+    fn parse_schema(schema: &str) -> Result<Arc<Schema>> {
+        let schema = Schema::parse_str(schema)
+            .map_err(|e| RwError::from(InternalError(format!("Avro schema parse error {}", e))))?;
+        Ok(Arc::new(schema))
+    }
+
+    async fn get_json(&self, url: &str) -> Result<Value> {
+        let uri: Uri = url
+            .parse()
+            .map_err(|e: InvalidUri| InvalidParameterValue(e.to_string()))?;
+        let res = self.client.get(uri).await.map_err(|e| {
+            InvalidParameterValue(format!("failed to read from URL {}: {}", url, e))
+        })?;
+        let status = res.status();
+        let buf = hyper::body::to_bytes(res)
+            .await
+            .map_err(|e| InvalidParameterValue(format!("failed to read HTTP body: {}", e)))?;
+        if !status.is_success() {
+            return Err(RwError::from(ProtocolError(format!(
+                "failed to read from URL {}: {} {}",
+                url,
+                status,
+                String::from_utf8_lossy(&buf)
+            ))));
+        }
+        serde_json::from_slice(&buf).map_err(|e| {
+            RwError::from(InternalError(format!(
+                "failed to parse response of {}: {}",
+                url, e
+            )))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_schema_id() {
+        let payload = [0, 0, 0, 1, 2, 42, 43];
+        let (id, datum) = extract_schema_id(&payload).unwrap();
+        assert_eq!(id, 258);
+        assert_eq!(datum, &[42, 43]);
+
+        assert!(extract_schema_id(&[1, 0, 0, 0, 1]).is_err());
+        assert!(extract_schema_id(&[0, 0, 0]).is_err());
+    }
+}
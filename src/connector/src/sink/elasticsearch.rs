@@ -0,0 +1,367 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hyper::client::HttpConnector;
+use hyper::http::uri::InvalidUri;
+use hyper::{Body, Method, Request, StatusCode};
+use hyper_tls::HttpsConnector;
+use itertools::Itertools;
+use risingwave_common::array::{Op, RowRef, StreamChunk};
+use risingwave_common::catalog::Schema;
+use serde_json::{json, Value};
+
+use super::kafka::{datum_to_json_object, record_to_json};
+use crate::sink::{Result, Sink, SinkError};
+
+pub const ELASTICSEARCH_SINK: &str = "elasticsearch";
+pub const OPENSEARCH_SINK: &str = "opensearch";
+
+#[derive(Debug, Clone)]
+pub struct ElasticsearchConfig {
+    /// The url of the cluster, e.g. `http://localhost:9200`.
+    pub url: String,
+
+    /// The index to write to, unless it is routed by `index_column`.
+    pub index: String,
+
+    /// The column whose value is the index to write a row to. The rows with a null value are
+    /// written to `index`.
+    pub index_column: Option<String>,
+
+    /// The delimiter joining the primary key columns into the document id.
+    pub delimiter: String,
+
+    pub username: Option<String>,
+    pub password: Option<String>,
+
+    /// The max number of actions in a `_bulk` request.
+    pub batch_size: usize,
+
+    pub max_retry_num: u32,
+    /// The initial backoff of retrying, which is doubled on each retry.
+    pub retry_interval: Duration,
+}
+
+impl ElasticsearchConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let get = |key: &str| {
+            values
+                .get(key)
+                .cloned()
+                .ok_or_else(|| SinkError::Config(format!("missing config: {}", key)))
+        };
+        let parse = |key: &str, default: usize| {
+            values.get(key).map_or(Ok(default), |v| {
+                v.parse::<usize>()
+                    .map_err(|e| SinkError::Config(format!("invalid {}: {}", key, e)))
+            })
+        };
+
+        Ok(ElasticsearchConfig {
+            url: get("url")?.trim_end_matches('/').to_string(),
+            index: get("index")?,
+            index_column: values.get("index.column").cloned(),
+            delimiter: values
+                .get("delimiter")
+                .cloned()
+                .unwrap_or_else(|| "_".to_string()),
+            username: values.get("username").cloned(),
+            password: values.get("password").cloned(),
+            batch_size: parse("batch.size", 1000)?.max(1),
+            max_retry_num: parse("max.retries", 5)? as u32,
+            retry_interval: Duration::from_millis(parse("retry.interval.ms", 100)? as u64),
+        })
+    }
+}
+
+/// A document to index or delete, identified by its index and id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DocKey {
+    index: String,
+    id: String,
+}
+
+/// [`ElasticsearchSink`] writes the changes to Elasticsearch or OpenSearch with the `_bulk` API.
+/// A row is indexed as a document whose id is its primary key, and a deleted row deletes the
+/// document. The changes are compacted by the document and sent on commit, so that the documents
+/// are consistent with the checkpoints. Throttled requests and actions are retried with backoff.
+pub struct ElasticsearchSink {
+    config: ElasticsearchConfig,
+    pk_indices: Vec<usize>,
+    client: hyper::Client<HttpsConnector<HttpConnector>>,
+
+    /// The latest change of each document. `None` means the document is deleted.
+    buffer: HashMap<DocKey, Option<Value>>,
+}
+
+impl std::fmt::Debug for ElasticsearchSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ElasticsearchSink")
+            .field("config", &self.config)
+            .field("pk_indices", &self.pk_indices)
+            .finish()
+    }
+}
+
+impl ElasticsearchSink {
+    pub fn new(config: ElasticsearchConfig, pk_indices: Vec<usize>) -> Result<Self> {
+        if pk_indices.is_empty() {
+            return Err(SinkError::Config(
+                "elasticsearch sink requires a primary key".to_string(),
+            ));
+        }
+        Ok(Self {
+            config,
+            pk_indices,
+            client: hyper::Client::builder().build::<_, Body>(HttpsConnector::new()),
+            buffer: HashMap::new(),
+        })
+    }
+
+    fn doc_key(&self, row: &RowRef<'_>, schema: &Schema) -> Result<DocKey> {
+        let to_string = |idx: usize| -> Result<Option<String>> {
+            let value = datum_to_json_object(&schema.fields[idx], row.value_at(idx))
+                .map_err(|e| SinkError::JsonParse(e.to_string()))?;
+            Ok(match value {
+                Value::Null => None,
+                Value::String(s) => Some(s),
+                v => Some(v.to_string()),
+            })
+        };
+
+        let id = self
+            .pk_indices
+            .iter()
+            .map(|&idx| Ok(to_string(idx)?.unwrap_or_default()))
+            .collect::<Result<Vec<_>>>()?
+            .join(&self.config.delimiter);
+        let index = match &self.config.index_column {
+            Some(column) => {
+                let idx = schema
+                    .fields
+                    .iter()
+                    .position(|f| &f.name == column)
+                    .ok_or_else(|| {
+                        SinkError::Config(format!("index.column {} does not exist", column))
+                    })?;
+                to_string(idx)?.unwrap_or_else(|| self.config.index.clone())
+            }
+            None => self.config.index.clone(),
+        };
+        Ok(DocKey { index, id })
+    }
+
+    /// Encodes the actions of `docs` as the NDJSON body of a `_bulk` request.
+    fn bulk_body(docs: &[(&DocKey, &Option<Value>)]) -> String {
+        let mut body = String::new();
+        for (key, doc) in docs {
+            let meta = json!({ "_index": key.index, "_id": key.id });
+            match doc {
+                Some(doc) => {
+                    body.push_str(&json!({ "index": meta }).to_string());
+                    body.push('\n');
+                    body.push_str(&doc.to_string());
+                }
+                None => body.push_str(&json!({ "delete": meta }).to_string()),
+            }
+            body.push('\n');
+        }
+        body
+    }
+
+    /// Sends a `_bulk` request, and returns the actions to retry, i.e. those throttled.
+    async fn send_bulk<'a>(
+        &self,
+        docs: Vec<(&'a DocKey, &'a Option<Value>)>,
+    ) -> Result<Vec<(&'a DocKey, &'a Option<Value>)>> {
+        let uri = format!("{}/_bulk", self.config.url);
+        let mut request = Request::builder()
+            .method(Method::POST)
+            .uri(
+                uri.parse::<hyper::Uri>()
+                    .map_err(|e: InvalidUri| SinkError::Config(e.to_string()))?,
+            )
+            .header("Content-Type", "application/x-ndjson");
+        if let Some(username) = &self.config.username {
+            let credential = format!(
+                "{}:{}",
+                username,
+                self.config.password.as_deref().unwrap_or_default()
+            );
+            request = request.header(
+                "Authorization",
+                format!("Basic {}", base64::encode(credential)),
+            );
+        }
+        let request = request
+            .body(Body::from(Self::bulk_body(&docs)))
+            .map_err(|e| SinkError::Elasticsearch(e.to_string()))?;
+
+        let res =
+            self.client.request(request).await.map_err(|e| {
+                SinkError::Elasticsearch(format!("failed to request {}: {}", uri, e))
+            })?;
+        let status = res.status();
+        let buf = hyper::body::to_bytes(res)
+            .await
+            .map_err(|e| SinkError::Elasticsearch(format!("failed to read HTTP body: {}", e)))?;
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Ok(docs);
+        }
+        if !status.is_success() {
+            return Err(SinkError::Elasticsearch(format!(
+                "bulk request failed: {} {}",
+                status,
+                String::from_utf8_lossy(&buf)
+            )));
+        }
+
+        let res: Value =
+            serde_json::from_slice(&buf).map_err(|e| SinkError::JsonParse(e.to_string()))?;
+        if !res.get("errors").and_then(Value::as_bool).unwrap_or(false) {
+            return Ok(vec![]);
+        }
+        let items = res
+            .get("items")
+            .and_then(Value::as_array)
+            .ok_or_else(|| SinkError::JsonParse(format!("no items in response: {}", res)))?;
+        let mut retries = vec![];
+        for (item, doc) in items.iter().zip_eq(docs) {
+            let Some(result) = item.as_object().and_then(|item| item.values().next()) else {
+                return Err(SinkError::JsonParse(format!("invalid bulk item: {}", item)));
+            };
+            match result.get("status").and_then(Value::as_u64) {
+                Some(status) if status < 300 => {}
+                // Deleting a missing document is fine.
+                Some(404) if doc.1.is_none() => {}
+                Some(429) => retries.push(doc),
+                _ => {
+                    return Err(SinkError::Elasticsearch(format!(
+                        "failed to write document {} to index {}: {}",
+                        doc.0.id,
+                        doc.0.index,
+                        result.get("error").unwrap_or(&Value::Null)
+                    )))
+                }
+            }
+        }
+        Ok(retries)
+    }
+}
+
+#[async_trait]
+impl Sink for ElasticsearchSink {
+    async fn write_batch(&mut self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+        for (op, row) in chunk.rows() {
+            let key = self.doc_key(&row, schema)?;
+            match op {
+                Op::Insert | Op::UpdateInsert => {
+                    let doc = Value::Object(record_to_json(row, schema.fields.clone())?);
+                    self.buffer.insert(key, Some(doc));
+                }
+                Op::Delete | Op::UpdateDelete => {
+                    self.buffer.insert(key, None);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn begin_epoch(&mut self, _epoch: u64) -> Result<()> {
+        Ok(())
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        let actions = self.buffer.iter().collect_vec();
+        for batch in actions.chunks(self.config.batch_size) {
+            let mut docs = batch.to_vec();
+            let mut backoff = self.config.retry_interval;
+            let mut retry_num = 0;
+            loop {
+                docs = self.send_bulk(docs).await?;
+                if docs.is_empty() {
+                    break;
+                }
+                if retry_num >= self.config.max_retry_num {
+                    return Err(SinkError::Elasticsearch(format!(
+                        "{} actions are still throttled after {} retries",
+                        docs.len(),
+                        retry_num
+                    )));
+                }
+                retry_num += 1;
+                tracing::warn!(
+                    "{} actions are throttled, retry {}/{} in {:?}",
+                    docs.len(),
+                    retry_num,
+                    self.config.max_retry_num,
+                    backoff
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+        self.buffer.clear();
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+
+    use super::*;
+
+    #[test]
+    fn test_bulk_body() {
+        let config = ElasticsearchConfig::from_hashmap(hashmap! {
+            "url".to_string() => "http://localhost:9200/".to_string(),
+            "index".to_string() => "test".to_string(),
+        })
+        .unwrap();
+        assert_eq!(config.url, "http://localhost:9200");
+        assert_eq!(config.delimiter, "_");
+
+        let insert = DocKey {
+            index: "test".to_string(),
+            id: "1".to_string(),
+        };
+        let delete = DocKey {
+            index: "test".to_string(),
+            id: "2".to_string(),
+        };
+        let doc = Some(json!({ "id": 1, "v": "a" }));
+        let body = ElasticsearchSink::bulk_body(&[(&insert, &doc), (&delete, &None)]);
+        assert_eq!(
+            body,
+            concat!(
+                r#"{"index":{"_id":"1","_index":"test"}}"#,
+                "\n",
+                r#"{"id":1,"v":"a"}"#,
+                "\n",
+                r#"{"delete":{"_id":"2","_index":"test"}}"#,
+                "\n",
+            )
+        );
+    }
+}
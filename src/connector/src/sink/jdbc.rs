@@ -0,0 +1,791 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use deadpool_postgres::{Manager, ManagerConfig, RecyclingMethod};
+use itertools::Itertools;
+use mysql_async::prelude::Queryable;
+use mysql_async::{Params, TxOpts, Value as MySqlValue};
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{DataType, Datum, Decimal, ScalarImpl};
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{GenericClient, NoTls};
+
+use crate::sink::{Result, Sink, SinkError};
+
+pub const JDBC_SINK: &str = "jdbc";
+
+/// The database that the sink writes to, decided by the scheme of `jdbc.url`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JdbcDialect {
+    Postgres,
+    MySql,
+}
+
+impl JdbcDialect {
+    fn from_url(url: &str) -> Result<Self> {
+        let url = url.strip_prefix("jdbc:").unwrap_or(url);
+        if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            Ok(JdbcDialect::Postgres)
+        } else if url.starts_with("mysql://") {
+            Ok(JdbcDialect::MySql)
+        } else {
+            Err(SinkError::Config(format!(
+                "unsupported jdbc.url: {}, expect a postgres:// or mysql:// url",
+                url
+            )))
+        }
+    }
+
+    fn quote(&self, ident: &str) -> String {
+        match self {
+            JdbcDialect::Postgres => format!("\"{}\"", ident.replace('"', "\"\"")),
+            JdbcDialect::MySql => format!("`{}`", ident.replace('`', "``")),
+        }
+    }
+
+    /// Quotes a table name, which may be qualified by the schema or the database.
+    fn quote_table(&self, table: &str) -> String {
+        table.split('.').map(|part| self.quote(part)).join(".")
+    }
+
+    /// The placeholder of the `idx`-th parameter, counting from 0.
+    fn placeholder(&self, idx: usize) -> String {
+        match self {
+            JdbcDialect::Postgres => format!("${}", idx + 1),
+            JdbcDialect::MySql => "?".to_string(),
+        }
+    }
+
+    /// Builds the statement inserting `n_rows` rows. If `pk_indices` is given, the rows replace the
+    /// existing ones with the same primary key, by `INSERT ... ON CONFLICT` on Postgres and
+    /// `REPLACE INTO` on MySQL.
+    pub fn insert_sql(
+        &self,
+        table: &str,
+        columns: &[String],
+        pk_indices: Option<&[usize]>,
+        n_rows: usize,
+    ) -> String {
+        let values = (0..n_rows)
+            .map(|row| {
+                let placeholders = (0..columns.len())
+                    .map(|col| self.placeholder(row * columns.len() + col))
+                    .join(", ");
+                format!("({})", placeholders)
+            })
+            .join(", ");
+        let column_list = columns.iter().map(|c| self.quote(c)).join(", ");
+        let table = self.quote_table(table);
+        match (self, pk_indices) {
+            (_, None) => format!("INSERT INTO {} ({}) VALUES {}", table, column_list, values),
+            (JdbcDialect::Postgres, Some(pk_indices)) => {
+                let conflict_target = pk_indices
+                    .iter()
+                    .map(|&i| self.quote(&columns[i]))
+                    .join(", ");
+                let updates = columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !pk_indices.contains(i))
+                    .map(|(_, c)| format!("{0} = EXCLUDED.{0}", self.quote(c)))
+                    .join(", ");
+                let action = if updates.is_empty() {
+                    "DO NOTHING".to_string()
+                } else {
+                    format!("DO UPDATE SET {}", updates)
+                };
+                format!(
+                    "INSERT INTO {} ({}) VALUES {} ON CONFLICT ({}) {}",
+                    table, column_list, values, conflict_target, action
+                )
+            }
+            (JdbcDialect::MySql, Some(_)) => {
+                format!("REPLACE INTO {} ({}) VALUES {}", table, column_list, values)
+            }
+        }
+    }
+
+    /// Builds the statement deleting `n_rows` rows by the primary key columns `pk_columns`.
+    pub fn delete_sql(&self, table: &str, pk_columns: &[String], n_rows: usize) -> String {
+        let conditions = (0..n_rows)
+            .map(|row| {
+                let condition = pk_columns
+                    .iter()
+                    .enumerate()
+                    .map(|(col, c)| {
+                        format!(
+                            "{} = {}",
+                            self.quote(c),
+                            self.placeholder(row * pk_columns.len() + col)
+                        )
+                    })
+                    .join(" AND ");
+                format!("({})", condition)
+            })
+            .join(" OR ");
+        format!(
+            "DELETE FROM {} WHERE {}",
+            self.quote_table(table),
+            conditions
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JdbcConfig {
+    pub url: String,
+    pub table: String,
+    pub dialect: JdbcDialect,
+
+    /// Whether the sink only inserts rows. Otherwise, the rows are upserted and deleted by the
+    /// primary key.
+    pub is_append_only: bool,
+
+    /// The max number of rows written by a statement.
+    pub batch_size: usize,
+
+    pub pool_size: usize,
+
+    /// Whether the changes of a checkpoint are written in a transaction, so that they are either
+    /// all visible or not at all. The transaction is committed in two phases, by
+    /// `PREPARE TRANSACTION` on Postgres and `XA` on MySQL.
+    pub transactional: bool,
+
+    /// The prefix of the ids of the prepared transactions, unique among the writers of the sinks.
+    pub identifier: String,
+
+    pub max_retry_num: u32,
+    pub retry_interval: Duration,
+}
+
+impl JdbcConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let get = |key: &str| {
+            values
+                .get(key)
+                .cloned()
+                .ok_or_else(|| SinkError::Config(format!("missing config: {}", key)))
+        };
+        let parse = |key: &str, default: usize| {
+            values.get(key).map_or(Ok(default), |v| {
+                v.parse::<usize>()
+                    .map_err(|e| SinkError::Config(format!("invalid {}: {}", key, e)))
+            })
+        };
+        let url = get("jdbc.url")?;
+        let dialect = JdbcDialect::from_url(&url)?;
+        let url = url.strip_prefix("jdbc:").unwrap_or(&url).to_string();
+        let table = get("table.name")?;
+        let is_append_only = match values.get("type").map(|t| t.to_lowercase()) {
+            None => false,
+            Some(t) if t == "upsert" => false,
+            Some(t) if t == "append-only" => true,
+            Some(_) => {
+                return Err(SinkError::Config(
+                    "type must be set to \"upsert\" or \"append-only\"".to_string(),
+                ))
+            }
+        };
+        let transactional = match values.get("jdbc.transaction") {
+            None => false,
+            Some(v) => v
+                .parse::<bool>()
+                .map_err(|e| SinkError::Config(format!("invalid jdbc.transaction: {}", e)))?,
+        };
+
+        Ok(JdbcConfig {
+            url,
+            table,
+            dialect,
+            is_append_only,
+            batch_size: parse("jdbc.batch.size", 1024)?.max(1),
+            pool_size: parse("jdbc.pool.size", 4)?.max(1),
+            transactional,
+            identifier: values
+                .get("identifier")
+                .cloned()
+                .unwrap_or_else(|| JDBC_SINK.to_string()),
+            max_retry_num: parse("jdbc.max.retries", 3)? as u32,
+            retry_interval: Duration::from_millis(100),
+        })
+    }
+}
+
+enum JdbcPool {
+    Postgres(deadpool_postgres::Pool),
+    MySql(mysql_async::Pool),
+}
+
+/// [`JdbcSink`] writes the changes to a table in Postgres or MySQL. The changes of an epoch are
+/// buffered and compacted by the primary key, and written on commit in batches of parameterized
+/// statements. Failed writes are retried, which is safe as upserts and deletes are idempotent.
+pub struct JdbcSink {
+    config: JdbcConfig,
+    pool: JdbcPool,
+    pk_indices: Vec<usize>,
+    columns: Vec<String>,
+    data_types: Vec<DataType>,
+
+    /// The rows to insert, in the append-only mode.
+    appends: Vec<Row>,
+    /// The latest change of each primary key, in the upsert mode. `None` means the row is deleted.
+    upserts: HashMap<Row, Option<Row>>,
+
+    /// The id of the transaction prepared by `pre_commit`, to be committed or rolled back.
+    prepared: Option<String>,
+}
+
+impl std::fmt::Debug for JdbcSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JdbcSink")
+            .field("config", &self.config)
+            .field("pk_indices", &self.pk_indices)
+            .finish()
+    }
+}
+
+impl JdbcSink {
+    pub async fn new(config: JdbcConfig, pk_indices: Vec<usize>) -> Result<Self> {
+        if !config.is_append_only && pk_indices.is_empty() {
+            return Err(SinkError::Config(
+                "the upsert jdbc sink requires a primary key, use type = 'append-only' instead"
+                    .to_string(),
+            ));
+        }
+        let pool = match config.dialect {
+            JdbcDialect::Postgres => {
+                let pg_config = config
+                    .url
+                    .parse::<tokio_postgres::Config>()
+                    .map_err(|e| SinkError::Config(format!("invalid jdbc.url: {}", e)))?;
+                let manager = Manager::from_config(
+                    pg_config,
+                    NoTls,
+                    ManagerConfig {
+                        recycling_method: RecyclingMethod::Fast,
+                    },
+                );
+                let pool = deadpool_postgres::Pool::builder(manager)
+                    .max_size(config.pool_size)
+                    .build()
+                    .map_err(|e| SinkError::Jdbc(e.to_string()))?;
+                JdbcPool::Postgres(pool)
+            }
+            JdbcDialect::MySql => {
+                let opts = mysql_async::Opts::from_url(&config.url)
+                    .map_err(|e| SinkError::Config(format!("invalid jdbc.url: {}", e)))?;
+                let constraints = mysql_async::PoolConstraints::new(1, config.pool_size)
+                    .ok_or_else(|| SinkError::Config("invalid jdbc.pool.size".to_string()))?;
+                let opts = mysql_async::OptsBuilder::from_opts(opts)
+                    .pool_opts(mysql_async::PoolOpts::default().with_constraints(constraints));
+                JdbcPool::MySql(mysql_async::Pool::new(opts))
+            }
+        };
+
+        Ok(Self {
+            config,
+            pool,
+            pk_indices,
+            columns: vec![],
+            data_types: vec![],
+            appends: vec![],
+            upserts: HashMap::new(),
+            prepared: None,
+        })
+    }
+
+    /// Buffers the changes of the chunk.
+    fn buffer_chunk(&mut self, chunk: &StreamChunk) -> Result<()> {
+        for (op, row) in chunk.rows() {
+            if self.config.is_append_only {
+                if op != Op::Insert {
+                    return Err(SinkError::Jdbc(format!(
+                        "the append-only jdbc sink received a {:?} change",
+                        op
+                    )));
+                }
+                self.appends.push(row.to_owned_row());
+            } else {
+                let pk = row.row_by_indices(&self.pk_indices);
+                match op {
+                    Op::Insert | Op::UpdateInsert => {
+                        self.upserts.insert(pk, Some(row.to_owned_row()));
+                    }
+                    Op::Delete | Op::UpdateDelete => {
+                        self.upserts.insert(pk, None);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The statements to write the buffered changes, each with its parameters and their types.
+    fn statements(&self) -> Vec<(String, Vec<(&Datum, &DataType)>)> {
+        let dialect = self.config.dialect;
+        let table = &self.config.table;
+        let mut statements = vec![];
+
+        let (deletes, upserts): (Vec<_>, Vec<_>) = if self.config.is_append_only {
+            (vec![], self.appends.iter().collect())
+        } else {
+            let (deletes, upserts): (Vec<_>, Vec<_>) =
+                self.upserts.iter().partition(|(_, row)| row.is_none());
+            (
+                deletes.into_iter().map(|(pk, _)| pk).collect(),
+                upserts
+                    .into_iter()
+                    .filter_map(|(_, row)| row.as_ref())
+                    .collect(),
+            )
+        };
+
+        let pk_columns = self
+            .pk_indices
+            .iter()
+            .map(|&i| self.columns[i].clone())
+            .collect_vec();
+        let pk_types = self
+            .pk_indices
+            .iter()
+            .map(|&i| &self.data_types[i])
+            .collect_vec();
+        for batch in deletes.chunks(self.config.batch_size) {
+            statements.push((
+                dialect.delete_sql(table, &pk_columns, batch.len()),
+                batch
+                    .iter()
+                    .flat_map(|pk| pk.0.iter().zip_eq(pk_types.iter().copied()))
+                    .collect(),
+            ));
+        }
+
+        let pk_indices = (!self.config.is_append_only).then_some(self.pk_indices.as_slice());
+        for batch in upserts.chunks(self.config.batch_size) {
+            statements.push((
+                dialect.insert_sql(table, &self.columns, pk_indices, batch.len()),
+                batch
+                    .iter()
+                    .flat_map(|row| row.0.iter().zip_eq(self.data_types.iter()))
+                    .collect(),
+            ));
+        }
+        statements
+    }
+
+    async fn write_postgres(
+        &self,
+        client: &impl GenericClient,
+        statements: &[(String, Vec<(&Datum, &DataType)>)],
+    ) -> Result<()> {
+        for (sql, params) in statements {
+            let params = params
+                .iter()
+                .map(|(datum, data_type)| postgres_value(datum, data_type))
+                .collect::<Result<Vec<_>>>()?;
+            let params = params
+                .iter()
+                .map(|param| param.as_ref() as &(dyn ToSql + Sync))
+                .collect_vec();
+            client.execute(sql.as_str(), &params).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_mysql(
+        &self,
+        conn: &mut impl Queryable,
+        statements: &[(String, Vec<(&Datum, &DataType)>)],
+    ) -> Result<()> {
+        for (sql, params) in statements {
+            let params = params
+                .iter()
+                .map(|(datum, _)| mysql_value(datum))
+                .collect::<Result<Vec<_>>>()?;
+            conn.exec_drop(sql.as_str(), Params::Positional(params))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let statements = self.statements();
+        if statements.is_empty() {
+            return Ok(());
+        }
+        match &self.pool {
+            JdbcPool::Postgres(pool) => {
+                let mut conn = pool
+                    .get()
+                    .await
+                    .map_err(|e| SinkError::Jdbc(e.to_string()))?;
+                let client: &mut tokio_postgres::Client = &mut conn;
+                if self.config.transactional {
+                    let txn = client.transaction().await?;
+                    self.write_postgres(&txn, &statements).await?;
+                    txn.commit().await?;
+                } else {
+                    self.write_postgres(&*client, &statements).await?;
+                }
+            }
+            JdbcPool::MySql(pool) => {
+                let mut conn = pool.get_conn().await?;
+                if self.config.transactional {
+                    let mut txn = conn.start_transaction(TxOpts::default()).await?;
+                    self.write_mysql(&mut txn, &statements).await?;
+                    txn.commit().await?;
+                } else {
+                    self.write_mysql(&mut conn, &statements).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered changes in a transaction, and prepares it as `gid`.
+    async fn prepare_transaction(&self, gid: &str) -> Result<()> {
+        let statements = self.statements();
+        let gid = quote_literal(gid);
+        match &self.pool {
+            JdbcPool::Postgres(pool) => {
+                let mut conn = pool
+                    .get()
+                    .await
+                    .map_err(|e| SinkError::Jdbc(e.to_string()))?;
+                let client: &mut tokio_postgres::Client = &mut conn;
+                client.batch_execute("BEGIN").await?;
+                let result: Result<()> = async {
+                    self.write_postgres(&*client, &statements).await?;
+                    client
+                        .batch_execute(&format!("PREPARE TRANSACTION {}", gid))
+                        .await?;
+                    Ok(())
+                }
+                .await;
+                if result.is_err() {
+                    let _ = client.batch_execute("ROLLBACK").await;
+                }
+                result
+            }
+            JdbcPool::MySql(pool) => {
+                let mut conn = pool.get_conn().await?;
+                conn.query_drop(format!("XA START {}", gid)).await?;
+                let result: Result<()> = async {
+                    self.write_mysql(&mut conn, &statements).await?;
+                    conn.query_drop(format!("XA END {}", gid)).await?;
+                    conn.query_drop(format!("XA PREPARE {}", gid)).await?;
+                    Ok(())
+                }
+                .await;
+                if result.is_err() {
+                    let _ = conn.query_drop(format!("XA END {}", gid)).await;
+                    let _ = conn.query_drop(format!("XA ROLLBACK {}", gid)).await;
+                }
+                result
+            }
+        }
+    }
+
+    /// Commits or rolls back the prepared transaction `gid`, which can be done by any connection.
+    async fn finish_transaction(&self, gid: &str, commit: bool) -> Result<()> {
+        let gid = quote_literal(gid);
+        match &self.pool {
+            JdbcPool::Postgres(pool) => {
+                let conn = pool
+                    .get()
+                    .await
+                    .map_err(|e| SinkError::Jdbc(e.to_string()))?;
+                let action = if commit { "COMMIT" } else { "ROLLBACK" };
+                conn.batch_execute(&format!("{} PREPARED {}", action, gid))
+                    .await?;
+            }
+            JdbcPool::MySql(pool) => {
+                let mut conn = pool.get_conn().await?;
+                let action = if commit { "COMMIT" } else { "ROLLBACK" };
+                conn.query_drop(format!("XA {} {}", action, gid)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the transaction `gid` is prepared and not committed or rolled back yet.
+    async fn is_prepared(&self, gid: &str) -> Result<bool> {
+        let gids = match &self.pool {
+            JdbcPool::Postgres(pool) => {
+                let conn = pool
+                    .get()
+                    .await
+                    .map_err(|e| SinkError::Jdbc(e.to_string()))?;
+                conn.query("SELECT gid FROM pg_prepared_xacts", &[])
+                    .await?
+                    .iter()
+                    .map(|row| row.get::<_, String>(0))
+                    .collect_vec()
+            }
+            JdbcPool::MySql(pool) => {
+                let mut conn = pool.get_conn().await?;
+                // The columns are `formatID`, `gtrid_length`, `bqual_length` and `data`, where
+                // `data` is the gtrid as no bqual is given.
+                conn.query::<mysql_async::Row, _>("XA RECOVER")
+                    .await?
+                    .into_iter()
+                    .filter_map(|row| row.get::<Vec<u8>, _>(3))
+                    .map(|data| String::from_utf8_lossy(&data).into_owned())
+                    .collect_vec()
+            }
+        };
+        Ok(gids.iter().any(|prepared| prepared == gid))
+    }
+
+    async fn with_retry<F, Fut>(&self, f: F) -> Result<()>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let mut retry_num = 0;
+        while let Err(e) = f().await {
+            if retry_num >= self.config.max_retry_num {
+                return Err(e);
+            }
+            retry_num += 1;
+            tracing::warn!(
+                "failed to write to {}, retry {}/{}: {}",
+                self.config.table,
+                retry_num,
+                self.config.max_retry_num,
+                e
+            );
+            tokio::time::sleep(self.config.retry_interval).await;
+        }
+        Ok(())
+    }
+
+    pub async fn prepare(&mut self, schema: &Schema) -> Result<()> {
+        self.columns = schema.names();
+        self.data_types = schema.data_types();
+        Ok(())
+    }
+}
+
+/// Converts a datum to a Postgres parameter. A null is typed by `data_type`, as Postgres checks
+/// the types of the parameters even if they are null.
+fn postgres_value(datum: &Datum, data_type: &DataType) -> Result<Box<dyn ToSql + Sync + Send>> {
+    let value: Box<dyn ToSql + Sync + Send> = match (data_type, datum) {
+        (DataType::Int16, v) => Box::new(v.as_ref().map(|v| *v.as_int16())),
+        (DataType::Int32, v) => Box::new(v.as_ref().map(|v| *v.as_int32())),
+        (DataType::Int64, v) => Box::new(v.as_ref().map(|v| *v.as_int64())),
+        (DataType::Float32, v) => Box::new(v.as_ref().map(|v| v.as_float32().0)),
+        (DataType::Float64, v) => Box::new(v.as_ref().map(|v| v.as_float64().0)),
+        (DataType::Boolean, v) => Box::new(v.as_ref().map(|v| *v.as_bool())),
+        (DataType::Varchar, v) => Box::new(v.as_ref().map(|v| v.as_utf8().clone())),
+        (DataType::Decimal, v) => Box::new(
+            v.as_ref()
+                .map(|v| match v.as_decimal() {
+                    Decimal::Normalized(d) => Ok(*d),
+                    d => Err(SinkError::Jdbc(format!(
+                        "{} is not supported by jdbc sink",
+                        d
+                    ))),
+                })
+                .transpose()?,
+        ),
+        (DataType::Date, v) => Box::new(v.as_ref().map(|v| v.as_naivedate().0)),
+        (DataType::Time, v) => Box::new(v.as_ref().map(|v| v.as_naivetime().0)),
+        (DataType::Timestamp, v) => Box::new(v.as_ref().map(|v| v.as_naivedatetime().0)),
+        (DataType::Timestampz, v) => Box::new(v.as_ref().map(|v| {
+            let micros = *v.as_int64();
+            DateTime::<Utc>::from_utc(
+                NaiveDateTime::from_timestamp(
+                    micros.div_euclid(1_000_000),
+                    (micros.rem_euclid(1_000_000) * 1000) as u32,
+                ),
+                Utc,
+            )
+        })),
+        (DataType::Jsonb, v) => Box::new(v.as_ref().map(|v| v.as_jsonb().value().clone())),
+        (data_type, _) => {
+            return Err(SinkError::Jdbc(format!(
+                "{:?} is not supported by jdbc sink",
+                data_type
+            )))
+        }
+    };
+    Ok(value)
+}
+
+fn mysql_value(datum: &Datum) -> Result<MySqlValue> {
+    let Some(scalar) = datum else {
+        return Ok(MySqlValue::NULL);
+    };
+    let value = match scalar {
+        ScalarImpl::Int16(v) => (*v).into(),
+        ScalarImpl::Int32(v) => (*v).into(),
+        ScalarImpl::Int64(v) => (*v).into(),
+        ScalarImpl::Float32(v) => v.0.into(),
+        ScalarImpl::Float64(v) => v.0.into(),
+        ScalarImpl::Bool(v) => (*v).into(),
+        ScalarImpl::Decimal(Decimal::Normalized(v)) => (*v).into(),
+        ScalarImpl::Utf8(v) => v.clone().into(),
+        ScalarImpl::NaiveDate(v) => v.to_string().into(),
+        ScalarImpl::NaiveTime(v) => v.to_string().into(),
+        ScalarImpl::NaiveDateTime(v) => v.to_string().into(),
+        ScalarImpl::Jsonb(v) => v.to_string().into(),
+        v => {
+            return Err(SinkError::Jdbc(format!(
+                "{:?} is not supported by jdbc sink",
+                v
+            )))
+        }
+    };
+    Ok(value)
+}
+
+#[async_trait]
+impl Sink for JdbcSink {
+    async fn write_batch(&mut self, chunk: StreamChunk, _schema: &Schema) -> Result<()> {
+        self.buffer_chunk(&chunk)
+    }
+
+    async fn begin_epoch(&mut self, _epoch: u64) -> Result<()> {
+        Ok(())
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        if let Some(gid) = self.prepared.take() {
+            self.with_retry(|| self.finish_transaction(&gid, true))
+                .await?;
+            return Ok(());
+        }
+        self.with_retry(|| self.flush()).await?;
+        self.appends.clear();
+        self.upserts.clear();
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        if let Some(gid) = self.prepared.take() {
+            self.with_retry(|| self.finish_transaction(&gid, false))
+                .await?;
+        }
+        self.appends.clear();
+        self.upserts.clear();
+        Ok(())
+    }
+
+    fn is_two_phase(&self) -> bool {
+        self.config.transactional
+    }
+
+    async fn pre_commit(&mut self, epoch: u64) -> Result<()> {
+        if self.appends.is_empty() && self.upserts.is_empty() {
+            return Ok(());
+        }
+        let gid = self.transaction_id(epoch);
+        self.with_retry(|| self.prepare_transaction(&gid)).await?;
+        self.prepared = Some(gid);
+        self.appends.clear();
+        self.upserts.clear();
+        Ok(())
+    }
+
+    fn transaction_id(&self, epoch: u64) -> String {
+        format!("{}-{}", self.config.identifier, epoch)
+    }
+
+    async fn resolve_prepared(&mut self, transaction_id: &str, commit: bool) -> Result<()> {
+        // The transaction is reported to meta before it's prepared, so it may not exist.
+        if !self.is_prepared(transaction_id).await? {
+            return Ok(());
+        }
+        tracing::info!(
+            "{} prepared transaction {} on recovery",
+            if commit { "commit" } else { "roll back" },
+            transaction_id
+        );
+        self.with_retry(|| self.finish_transaction(transaction_id, commit))
+            .await
+    }
+}
+
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+
+    use super::*;
+
+    #[test]
+    fn test_jdbc_config() {
+        let config = JdbcConfig::from_hashmap(hashmap! {
+            "connector".to_string() => "jdbc".to_string(),
+            "jdbc.url".to_string() => "jdbc:postgres://root@localhost:5432/dev".to_string(),
+            "table.name".to_string() => "public.t".to_string(),
+            "jdbc.transaction".to_string() => "true".to_string(),
+        })
+        .unwrap();
+        assert_eq!(config.dialect, JdbcDialect::Postgres);
+        assert_eq!(config.url, "postgres://root@localhost:5432/dev");
+        assert!(!config.is_append_only);
+        assert!(config.transactional);
+        assert_eq!(config.batch_size, 1024);
+
+        assert!(JdbcConfig::from_hashmap(hashmap! {
+            "jdbc.url".to_string() => "sqlite://t.db".to_string(),
+            "table.name".to_string() => "t".to_string(),
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_postgres_sql() {
+        let columns = vec!["id".to_string(), "v".to_string()];
+        let dialect = JdbcDialect::Postgres;
+        assert_eq!(
+            dialect.insert_sql("public.t", &columns, Some(&[0]), 2),
+            r#"INSERT INTO "public"."t" ("id", "v") VALUES ($1, $2), ($3, $4) ON CONFLICT ("id") DO UPDATE SET "v" = EXCLUDED."v""#
+        );
+        assert_eq!(
+            dialect.insert_sql("t", &columns, None, 1),
+            r#"INSERT INTO "t" ("id", "v") VALUES ($1, $2)"#
+        );
+        assert_eq!(
+            dialect.delete_sql("t", &columns, 2),
+            r#"DELETE FROM "t" WHERE ("id" = $1 AND "v" = $2) OR ("id" = $3 AND "v" = $4)"#
+        );
+    }
+
+    #[test]
+    fn test_mysql_sql() {
+        let columns = vec!["id".to_string(), "v".to_string()];
+        let dialect = JdbcDialect::MySql;
+        assert_eq!(
+            dialect.insert_sql("t", &columns, Some(&[0]), 2),
+            "REPLACE INTO `t` (`id`, `v`) VALUES (?, ?), (?, ?)"
+        );
+        assert_eq!(
+            dialect.delete_sql("t", &columns[..1], 2),
+            "DELETE FROM `t` WHERE (`id` = ?) OR (`id` = ?)"
+        );
+    }
+}
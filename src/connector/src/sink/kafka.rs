@@ -129,7 +129,10 @@ impl KafkaSink {
         Err(err)
     }
 
-    async fn send<'a, K, P>(&'a self, mut record: BaseRecord<'a, K, P>) -> KafkaResult<()>
+    pub(crate) async fn send<'a, K, P>(
+        &'a self,
+        mut record: BaseRecord<'a, K, P>,
+    ) -> KafkaResult<()>
     where
         K: ToBytes + ?Sized,
         P: ToBytes + ?Sized,
@@ -300,6 +303,39 @@ impl Sink for KafkaSink {
         self.in_transaction_epoch = None;
         Ok(())
     }
+
+    fn is_two_phase(&self) -> bool {
+        true
+    }
+
+    // The transactions of a producer are identified by its transactional id.
+    fn transaction_id(&self, _epoch: u64) -> String {
+        self.config.identifier.clone()
+    }
+
+    async fn pre_commit(&mut self, _epoch: u64) -> Result<()> {
+        self.do_with_retry(|conductor| conductor.flush()).await?;
+        Ok(())
+    }
+
+    // A pending transaction is aborted once a new producer with the same transactional id is
+    // initialized, which is done by the producer of the sink itself on creation, or by a
+    // temporary producer for the transactions of the other producers. Kafka doesn't allow a new
+    // producer to commit the pending transaction though, so a transaction decided to commit but
+    // not committed by its producer before the failure can't be recovered.
+    async fn resolve_prepared(&mut self, transaction_id: &str, commit: bool) -> Result<()> {
+        if commit {
+            tracing::error!(
+                "transaction {} of kafka sink is decided to commit but can't be committed by a \
+                 new producer, its messages are discarded",
+                transaction_id
+            );
+        }
+        if transaction_id != self.config.identifier {
+            KafkaTransactionConductor::fence(&self.config, transaction_id).await?;
+        }
+        Ok(())
+    }
 }
 
 impl Debug for KafkaSink {
@@ -308,7 +344,7 @@ impl Debug for KafkaSink {
     }
 }
 
-fn datum_to_json_object(field: &Field, datum: DatumRef<'_>) -> ArrayResult<Value> {
+pub(crate) fn datum_to_json_object(field: &Field, datum: DatumRef<'_>) -> ArrayResult<Value> {
     let scalar_ref = match datum {
         None => return Ok(Value::Null),
         Some(datum) => datum,
@@ -345,6 +381,7 @@ fn datum_to_json_object(field: &Field, datum: DatumRef<'_>) -> ArrayResult<Value
         (DataType::Time, ScalarRefImpl::NaiveTime(_v)) => {
             unimplemented!()
         }
+        (DataType::Jsonb, ScalarRefImpl::Jsonb(v)) => v.value().clone(),
         (DataType::List { .. }, ScalarRefImpl::List(list_ref)) => {
             let mut vec = Vec::with_capacity(field.sub_fields.len());
             for (sub_datum_ref, sub_field) in list_ref
@@ -375,7 +412,7 @@ fn datum_to_json_object(field: &Field, datum: DatumRef<'_>) -> ArrayResult<Value
     Ok(value)
 }
 
-fn record_to_json(row: RowRef<'_>, schema: Vec<Field>) -> Result<Map<String, Value>> {
+pub(crate) fn record_to_json(row: RowRef<'_>, schema: Vec<Field>) -> Result<Map<String, Value>> {
     let mut mappings = Map::with_capacity(schema.len());
     for (field, datum_ref) in schema.iter().zip_eq(row.values()) {
         let key = field.name.clone();
@@ -453,6 +490,18 @@ impl KafkaTransactionConductor {
         })
     }
 
+    /// Aborts the pending transaction of the producer with `transactional_id`, by initializing a
+    /// new producer with the same id.
+    async fn fence(config: &KafkaConfig, transactional_id: &str) -> Result<()> {
+        let producer: ThreadedProducer<DefaultProducerContext> = ClientConfig::new()
+            .set("bootstrap.servers", &config.brokers)
+            .set("transactional.id", transactional_id)
+            .create()
+            .await?;
+        producer.init_transactions(config.timeout).await?;
+        Ok(())
+    }
+
     #[expect(clippy::unused_async)]
     async fn start_transaction(&self) -> KafkaResult<()> {
         self.inner.begin_transaction()
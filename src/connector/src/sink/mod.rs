@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod elasticsearch;
+pub mod jdbc;
 pub mod kafka;
 pub mod mysql;
 pub mod redis;
+pub mod upsert_kafka;
 
 use std::collections::HashMap;
 
@@ -27,9 +30,14 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 pub use tracing;
 
+use crate::sink::elasticsearch::{
+    ElasticsearchConfig, ElasticsearchSink, ELASTICSEARCH_SINK, OPENSEARCH_SINK,
+};
+use crate::sink::jdbc::{JdbcConfig, JdbcSink, JDBC_SINK};
 use crate::sink::kafka::{KafkaConfig, KafkaSink, KAFKA_SINK};
 pub use crate::sink::mysql::{MySqlConfig, MySqlSink, MYSQL_SINK};
-use crate::sink::redis::{RedisConfig, RedisSink};
+use crate::sink::redis::{RedisConfig, RedisSink, REDIS_SINK};
+use crate::sink::upsert_kafka::{UpsertKafkaConfig, UpsertKafkaSink, UPSERT_KAFKA_SINK};
 
 #[async_trait]
 pub trait Sink {
@@ -45,6 +53,33 @@ pub trait Sink {
     // aborts the current transaction because some error happens. we should rollback to the last
     // commit point.
     async fn abort(&mut self) -> Result<()>;
+
+    // the following interface is for the two-phase commit coordinated by meta. a two-phase sink
+    // pre-commits the transaction at a checkpoint, and commits or aborts it by the decision of
+    // meta, once the checkpoint is durable or abandoned by a recovery. so the data of a
+    // checkpoint failed to persist is never visible, and is not duplicated after recovery.
+    fn is_two_phase(&self) -> bool {
+        false
+    }
+
+    // the id of the transaction that `pre_commit` prepares for the checkpoint `epoch` in the
+    // external system, which is reported to meta before preparing it.
+    fn transaction_id(&self, _epoch: u64) -> String {
+        String::new()
+    }
+
+    // prepares the current transaction of the checkpoint `epoch` to commit, e.g. flushes all the
+    // messages. after that, the transaction is decided by `commit` or `abort`.
+    async fn pre_commit(&mut self, _epoch: u64) -> Result<()> {
+        Ok(())
+    }
+
+    // commits or aborts the transaction `transaction_id` pre-committed before a failure, possibly
+    // by another writer of the sink, as decided by meta. the transaction may not exist, if it
+    // failed to prepare or has been resolved already.
+    async fn resolve_prepared(&mut self, _transaction_id: &str, _commit: bool) -> Result<()> {
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, EnumAsInner)]
@@ -52,6 +87,9 @@ pub enum SinkConfig {
     Mysql(MySqlConfig),
     Redis(RedisConfig),
     Kafka(KafkaConfig),
+    UpsertKafka(UpsertKafkaConfig),
+    Jdbc(JdbcConfig),
+    Elasticsearch(ElasticsearchConfig),
 }
 
 #[derive(Clone, Debug, EnumAsInner, Serialize, Deserialize)]
@@ -59,6 +97,9 @@ pub enum SinkState {
     Kafka,
     Mysql,
     Redis,
+    UpsertKafka,
+    Jdbc,
+    Elasticsearch,
 }
 
 impl SinkConfig {
@@ -70,6 +111,14 @@ impl SinkConfig {
         match sink_type.to_lowercase().as_str() {
             KAFKA_SINK => Ok(SinkConfig::Kafka(KafkaConfig::from_hashmap(properties)?)),
             MYSQL_SINK => Ok(SinkConfig::Mysql(MySqlConfig::from_hashmap(properties)?)),
+            UPSERT_KAFKA_SINK => Ok(SinkConfig::UpsertKafka(UpsertKafkaConfig::from_hashmap(
+                properties,
+            )?)),
+            REDIS_SINK => Ok(SinkConfig::Redis(RedisConfig::from_hashmap(properties)?)),
+            JDBC_SINK => Ok(SinkConfig::Jdbc(JdbcConfig::from_hashmap(properties)?)),
+            ELASTICSEARCH_SINK | OPENSEARCH_SINK => Ok(SinkConfig::Elasticsearch(
+                ElasticsearchConfig::from_hashmap(properties)?,
+            )),
             _ => unimplemented!(),
         }
     }
@@ -78,7 +127,10 @@ impl SinkConfig {
         match self {
             SinkConfig::Mysql(_) => "mysql",
             SinkConfig::Kafka(_) => "kafka",
-            SinkConfig::Redis(_) => "redis",
+            SinkConfig::Redis(_) => REDIS_SINK,
+            SinkConfig::UpsertKafka(_) => UPSERT_KAFKA_SINK,
+            SinkConfig::Jdbc(_) => JDBC_SINK,
+            SinkConfig::Elasticsearch(_) => ELASTICSEARCH_SINK,
         }
     }
 }
@@ -88,28 +140,50 @@ pub enum SinkImpl {
     MySql(Box<MySqlSink>),
     Redis(Box<RedisSink>),
     Kafka(Box<KafkaSink>),
+    UpsertKafka(Box<UpsertKafkaSink>),
+    Jdbc(Box<JdbcSink>),
+    Elasticsearch(Box<ElasticsearchSink>),
 }
 
 impl SinkImpl {
-    pub async fn new(cfg: SinkConfig) -> Result<Self> {
+    /// Creates the sink. `pk_indices` is the primary key of the sinked rows, which is used by the
+    /// sinks that upsert by key.
+    pub async fn new(cfg: SinkConfig, pk_indices: Vec<usize>) -> Result<Self> {
         Ok(match cfg {
             SinkConfig::Mysql(cfg) => SinkImpl::MySql(Box::new(MySqlSink::new(cfg).await?)),
-            SinkConfig::Redis(cfg) => SinkImpl::Redis(Box::new(RedisSink::new(cfg)?)),
+            SinkConfig::Redis(cfg) => {
+                SinkImpl::Redis(Box::new(RedisSink::new(cfg, pk_indices).await?))
+            }
             SinkConfig::Kafka(cfg) => SinkImpl::Kafka(Box::new(KafkaSink::new(cfg).await?)),
+            SinkConfig::UpsertKafka(cfg) => {
+                SinkImpl::UpsertKafka(Box::new(UpsertKafkaSink::new(cfg, pk_indices).await?))
+            }
+            SinkConfig::Jdbc(cfg) => {
+                SinkImpl::Jdbc(Box::new(JdbcSink::new(cfg, pk_indices).await?))
+            }
+            SinkConfig::Elasticsearch(cfg) => {
+                SinkImpl::Elasticsearch(Box::new(ElasticsearchSink::new(cfg, pk_indices)?))
+            }
         })
     }
 
     pub fn needs_preparation(&self) -> bool {
         match self {
             SinkImpl::MySql(_) => true,
-            SinkImpl::Redis(_) => false,
+            SinkImpl::Redis(_) => true,
             SinkImpl::Kafka(_) => false,
+            SinkImpl::UpsertKafka(_) => true,
+            SinkImpl::Jdbc(_) => true,
+            SinkImpl::Elasticsearch(_) => false,
         }
     }
 
     pub async fn prepare(&mut self, schema: &Schema) -> Result<()> {
         match self {
             SinkImpl::MySql(sink) => sink.prepare(schema).await,
+            SinkImpl::UpsertKafka(sink) => sink.prepare(schema).await,
+            SinkImpl::Jdbc(sink) => sink.prepare(schema).await,
+            SinkImpl::Redis(sink) => sink.prepare(schema).await,
             _ => unreachable!(),
         }
     }
@@ -122,6 +196,9 @@ impl Sink for SinkImpl {
             SinkImpl::MySql(sink) => sink.write_batch(chunk, schema).await,
             SinkImpl::Redis(sink) => sink.write_batch(chunk, schema).await,
             SinkImpl::Kafka(sink) => sink.write_batch(chunk, schema).await,
+            SinkImpl::UpsertKafka(sink) => sink.write_batch(chunk, schema).await,
+            SinkImpl::Jdbc(sink) => sink.write_batch(chunk, schema).await,
+            SinkImpl::Elasticsearch(sink) => sink.write_batch(chunk, schema).await,
         }
     }
 
@@ -130,6 +207,9 @@ impl Sink for SinkImpl {
             SinkImpl::MySql(sink) => sink.begin_epoch(epoch).await,
             SinkImpl::Redis(sink) => sink.begin_epoch(epoch).await,
             SinkImpl::Kafka(sink) => sink.begin_epoch(epoch).await,
+            SinkImpl::UpsertKafka(sink) => sink.begin_epoch(epoch).await,
+            SinkImpl::Jdbc(sink) => sink.begin_epoch(epoch).await,
+            SinkImpl::Elasticsearch(sink) => sink.begin_epoch(epoch).await,
         }
     }
 
@@ -138,6 +218,9 @@ impl Sink for SinkImpl {
             SinkImpl::MySql(sink) => sink.commit().await,
             SinkImpl::Redis(sink) => sink.commit().await,
             SinkImpl::Kafka(sink) => sink.commit().await,
+            SinkImpl::UpsertKafka(sink) => sink.commit().await,
+            SinkImpl::Jdbc(sink) => sink.commit().await,
+            SinkImpl::Elasticsearch(sink) => sink.commit().await,
         }
     }
 
@@ -146,6 +229,53 @@ impl Sink for SinkImpl {
             SinkImpl::MySql(sink) => sink.abort().await,
             SinkImpl::Redis(sink) => sink.abort().await,
             SinkImpl::Kafka(sink) => sink.abort().await,
+            SinkImpl::UpsertKafka(sink) => sink.abort().await,
+            SinkImpl::Jdbc(sink) => sink.abort().await,
+            SinkImpl::Elasticsearch(sink) => sink.abort().await,
+        }
+    }
+
+    fn is_two_phase(&self) -> bool {
+        match self {
+            SinkImpl::MySql(sink) => sink.is_two_phase(),
+            SinkImpl::Redis(sink) => sink.is_two_phase(),
+            SinkImpl::Kafka(sink) => sink.is_two_phase(),
+            SinkImpl::UpsertKafka(sink) => sink.is_two_phase(),
+            SinkImpl::Jdbc(sink) => sink.is_two_phase(),
+            SinkImpl::Elasticsearch(sink) => sink.is_two_phase(),
+        }
+    }
+
+    async fn pre_commit(&mut self, epoch: u64) -> Result<()> {
+        match self {
+            SinkImpl::MySql(sink) => sink.pre_commit(epoch).await,
+            SinkImpl::Redis(sink) => sink.pre_commit(epoch).await,
+            SinkImpl::Kafka(sink) => sink.pre_commit(epoch).await,
+            SinkImpl::UpsertKafka(sink) => sink.pre_commit(epoch).await,
+            SinkImpl::Jdbc(sink) => sink.pre_commit(epoch).await,
+            SinkImpl::Elasticsearch(sink) => sink.pre_commit(epoch).await,
+        }
+    }
+
+    fn transaction_id(&self, epoch: u64) -> String {
+        match self {
+            SinkImpl::MySql(sink) => sink.transaction_id(epoch),
+            SinkImpl::Redis(sink) => sink.transaction_id(epoch),
+            SinkImpl::Kafka(sink) => sink.transaction_id(epoch),
+            SinkImpl::UpsertKafka(sink) => sink.transaction_id(epoch),
+            SinkImpl::Jdbc(sink) => sink.transaction_id(epoch),
+            SinkImpl::Elasticsearch(sink) => sink.transaction_id(epoch),
+        }
+    }
+
+    async fn resolve_prepared(&mut self, transaction_id: &str, commit: bool) -> Result<()> {
+        match self {
+            SinkImpl::MySql(sink) => sink.resolve_prepared(transaction_id, commit).await,
+            SinkImpl::Redis(sink) => sink.resolve_prepared(transaction_id, commit).await,
+            SinkImpl::Kafka(sink) => sink.resolve_prepared(transaction_id, commit).await,
+            SinkImpl::UpsertKafka(sink) => sink.resolve_prepared(transaction_id, commit).await,
+            SinkImpl::Jdbc(sink) => sink.resolve_prepared(transaction_id, commit).await,
+            SinkImpl::Elasticsearch(sink) => sink.resolve_prepared(transaction_id, commit).await,
         }
     }
 }
@@ -158,6 +288,14 @@ pub enum SinkError {
     MySql(String),
     #[error("MySql inner error: {0}")]
     MySqlInner(#[from] mysql_async::Error),
+    #[error("Jdbc error: {0}")]
+    Jdbc(String),
+    #[error("Redis error: {0}")]
+    Redis(String),
+    #[error("Elasticsearch error: {0}")]
+    Elasticsearch(String),
+    #[error("Postgres error: {0}")]
+    Postgres(#[from] tokio_postgres::Error),
     #[error("Kafka error: {0}")]
     Kafka(#[from] rdkafka::error::KafkaError),
     #[error("Json parse error: {0}")]
@@ -12,39 +12,413 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
+use ::redis::aio::MultiplexedConnection;
+use ::redis::cluster::ClusterClient;
+use ::redis::cluster_async::ClusterConnection;
+use ::redis::{Client, Cmd, RedisError};
 use async_trait::async_trait;
-use risingwave_common::array::StreamChunk;
+use futures::future::try_join_all;
+use itertools::Itertools;
+use risingwave_common::array::{Op, RowRef, StreamChunk};
 use risingwave_common::catalog::Schema;
+use serde_json::Value;
+
+use super::kafka::{datum_to_json_object, record_to_json};
+use crate::sink::{Result, Sink, SinkError};
 
-use crate::sink::{Result, Sink};
+pub const REDIS_SINK: &str = "redis";
+
+/// How a row is stored as the value of its key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedisValueType {
+    /// A string written by `SET`, which is the rendered `value.template`, or the row encoded in
+    /// json if there's no template.
+    String,
+    /// A hash written by `HSET`, whose fields are the columns.
+    Hash,
+}
 
 #[derive(Clone, Debug)]
-pub struct RedisConfig;
+pub struct RedisConfig {
+    /// The urls of the nodes, e.g. `redis://127.0.0.1:6379`. In the cluster mode, they are the
+    /// seed nodes to discover the topology from.
+    pub urls: Vec<String>,
+    pub cluster: bool,
+
+    /// The template of the key, e.g. `user:{id}`, where `{id}` is replaced by the value of the
+    /// column `id`. The primary key columns joined by `:` are used if it's not set.
+    pub key_template: Option<String>,
+    pub value_template: Option<String>,
+    pub value_type: RedisValueType,
+
+    /// The time to live of the keys in seconds.
+    pub ttl: Option<usize>,
+}
+
+impl RedisConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let urls = values
+            .get("redis.url")
+            .ok_or_else(|| SinkError::Config("missing config: redis.url".to_string()))?
+            .split(',')
+            .map(|url| url.trim().to_string())
+            .collect_vec();
+        let cluster = match values.get("redis.cluster") {
+            None => false,
+            Some(v) => v
+                .parse::<bool>()
+                .map_err(|e| SinkError::Config(format!("invalid redis.cluster: {}", e)))?,
+        };
+        let value_type = match values.get("value.type").map(|t| t.to_lowercase()) {
+            None => RedisValueType::String,
+            Some(t) if t == "string" => RedisValueType::String,
+            Some(t) if t == "hash" => RedisValueType::Hash,
+            Some(_) => {
+                return Err(SinkError::Config(
+                    "value.type must be set to \"string\" or \"hash\"".to_string(),
+                ))
+            }
+        };
+        let value_template = values.get("value.template").cloned();
+        if value_type == RedisValueType::Hash && value_template.is_some() {
+            return Err(SinkError::Config(
+                "value.template is not supported by the hash value type".to_string(),
+            ));
+        }
+        let ttl = values
+            .get("ttl")
+            .map(|ttl| {
+                ttl.parse::<usize>()
+                    .map_err(|e| SinkError::Config(format!("invalid ttl: {}", e)))
+            })
+            .transpose()?;
+
+        Ok(RedisConfig {
+            urls,
+            cluster,
+            key_template: values.get("key.template").cloned(),
+            value_template,
+            value_type,
+            ttl,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Literal(String),
+    /// The index of the column whose value is filled in.
+    Column(usize),
+}
 
-#[derive(Debug)]
-pub struct RedisSink;
+/// A template like `user:{id}:{name}`, resolved against the schema. `{{` and `}}` are escaped
+/// braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Template(Vec<TemplatePart>);
+
+impl Template {
+    fn parse(template: &str, schema: &Schema) -> Result<Self> {
+        let invalid = || SinkError::Config(format!("invalid template: {}", template));
+        let mut parts = vec![];
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    let idx = schema
+                        .fields
+                        .iter()
+                        .position(|f| f.name == name)
+                        .ok_or_else(|| {
+                            SinkError::Config(format!(
+                                "column {} in template {} does not exist",
+                                name, template
+                            ))
+                        })?;
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(TemplatePart::Column(idx));
+                }
+                '}' => return Err(invalid()),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+        Ok(Self(parts))
+    }
+
+    /// The template joining the primary key columns by `:`.
+    fn from_pk(pk_indices: &[usize]) -> Self {
+        let mut parts = vec![];
+        for (i, &idx) in pk_indices.iter().enumerate() {
+            if i > 0 {
+                parts.push(TemplatePart::Literal(":".to_string()));
+            }
+            parts.push(TemplatePart::Column(idx));
+        }
+        Self(parts)
+    }
+
+    fn render(&self, row: &RowRef<'_>, schema: &Schema) -> Result<String> {
+        let mut rendered = String::new();
+        for part in &self.0 {
+            match part {
+                TemplatePart::Literal(literal) => rendered.push_str(literal),
+                TemplatePart::Column(idx) => {
+                    if let Some(value) = datum_to_string(row, schema, *idx)? {
+                        rendered.push_str(&value);
+                    }
+                }
+            }
+        }
+        Ok(rendered)
+    }
+}
+
+/// Formats a datum as a string, where strings are not quoted.
+fn datum_to_string(row: &RowRef<'_>, schema: &Schema, idx: usize) -> Result<Option<String>> {
+    let value = datum_to_json_object(&schema.fields[idx], row.value_at(idx))
+        .map_err(|e| SinkError::JsonParse(e.to_string()))?;
+    Ok(match value {
+        Value::Null => None,
+        Value::String(s) => Some(s),
+        v => Some(v.to_string()),
+    })
+}
+
+/// The value of a key to write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RedisValue {
+    String(String),
+    /// The fields to set, and the fields to delete as the columns are null.
+    Hash(Vec<(String, String)>, Vec<String>),
+}
+
+enum RedisConnection {
+    Single(MultiplexedConnection),
+    Cluster(ClusterConnection),
+}
+
+/// [`RedisSink`] writes each row to a key rendered from the key template. Inserts are written by
+/// `SET` or `HSET` and deletes by `DEL`. The changes are compacted by the key and written on
+/// commit, pipelined on a single node, or sent concurrently to the nodes in the cluster mode.
+pub struct RedisSink {
+    config: RedisConfig,
+    pk_indices: Vec<usize>,
+    conn: RedisConnection,
+
+    key_template: Template,
+    value_template: Option<Template>,
+
+    /// The latest value of each key. `None` means the key is deleted.
+    buffer: HashMap<String, Option<RedisValue>>,
+}
+
+impl std::fmt::Debug for RedisSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisSink")
+            .field("config", &self.config)
+            .field("pk_indices", &self.pk_indices)
+            .finish()
+    }
+}
 
 impl RedisSink {
-    pub fn new(_cfg: RedisConfig) -> Result<Self> {
-        todo!()
+    pub async fn new(config: RedisConfig, pk_indices: Vec<usize>) -> Result<Self> {
+        if pk_indices.is_empty() && config.key_template.is_none() {
+            return Err(SinkError::Config(
+                "redis sink requires a primary key or key.template".to_string(),
+            ));
+        }
+        let conn = if config.cluster {
+            // The client discovers the slots of the cluster from the seed nodes, and follows the
+            // topology changes on redirections.
+            let client = ClusterClient::new(config.urls.clone())?;
+            RedisConnection::Cluster(client.get_async_connection().await?)
+        } else {
+            let url = config.urls.iter().exactly_one().map_err(|_| {
+                SinkError::Config("redis.url must be a single url without cluster".to_string())
+            })?;
+            let client = Client::open(url.as_str())?;
+            RedisConnection::Single(client.get_multiplexed_tokio_connection().await?)
+        };
+
+        Ok(Self {
+            key_template: Template::from_pk(&pk_indices),
+            value_template: None,
+            config,
+            pk_indices,
+            conn,
+            buffer: HashMap::new(),
+        })
+    }
+
+    pub async fn prepare(&mut self, schema: &Schema) -> Result<()> {
+        if let Some(template) = &self.config.key_template {
+            self.key_template = Template::parse(template, schema)?;
+        }
+        self.value_template = self
+            .config
+            .value_template
+            .as_ref()
+            .map(|template| Template::parse(template, schema))
+            .transpose()?;
+        Ok(())
+    }
+
+    fn encode_value(&self, row: RowRef<'_>, schema: &Schema) -> Result<RedisValue> {
+        match self.config.value_type {
+            RedisValueType::String => match &self.value_template {
+                Some(template) => Ok(RedisValue::String(template.render(&row, schema)?)),
+                None => Ok(RedisValue::String(
+                    Value::Object(record_to_json(row, schema.fields.clone())?).to_string(),
+                )),
+            },
+            RedisValueType::Hash => {
+                let mut fields = vec![];
+                let mut null_fields = vec![];
+                for (idx, field) in schema.fields.iter().enumerate() {
+                    match datum_to_string(&row, schema, idx)? {
+                        Some(value) => fields.push((field.name.clone(), value)),
+                        None => null_fields.push(field.name.clone()),
+                    }
+                }
+                Ok(RedisValue::Hash(fields, null_fields))
+            }
+        }
+    }
+
+    /// The commands to write the buffered changes.
+    fn commands(&self) -> Vec<Cmd> {
+        let mut cmds = vec![];
+        for (key, value) in &self.buffer {
+            match value {
+                None => cmds.push(::redis::cmd("DEL").arg(key).clone()),
+                Some(RedisValue::String(value)) => {
+                    let mut cmd = ::redis::cmd("SET");
+                    cmd.arg(key).arg(value);
+                    if let Some(ttl) = self.config.ttl {
+                        cmd.arg("EX").arg(ttl);
+                    }
+                    cmds.push(cmd);
+                }
+                Some(RedisValue::Hash(fields, null_fields)) => {
+                    if !null_fields.is_empty() {
+                        cmds.push(::redis::cmd("HDEL").arg(key).arg(null_fields).clone());
+                    }
+                    if !fields.is_empty() {
+                        cmds.push(::redis::cmd("HSET").arg(key).arg(fields).clone());
+                    }
+                    if let Some(ttl) = self.config.ttl {
+                        cmds.push(::redis::cmd("EXPIRE").arg(key).arg(ttl).clone());
+                    }
+                }
+            }
+        }
+        cmds
     }
 }
 
 #[async_trait]
 impl Sink for RedisSink {
-    async fn write_batch(&mut self, _chunk: StreamChunk, _schema: &Schema) -> Result<()> {
-        todo!();
+    async fn write_batch(&mut self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+        for (op, row) in chunk.rows() {
+            let key = self.key_template.render(&row, schema)?;
+            match op {
+                Op::Insert | Op::UpdateInsert => {
+                    let value = self.encode_value(row, schema)?;
+                    self.buffer.insert(key, Some(value));
+                }
+                Op::Delete | Op::UpdateDelete => {
+                    self.buffer.insert(key, None);
+                }
+            }
+        }
+        Ok(())
     }
 
     async fn begin_epoch(&mut self, _epoch: u64) -> Result<()> {
-        todo!()
+        Ok(())
     }
 
     async fn commit(&mut self) -> Result<()> {
-        todo!()
+        let cmds = self.commands();
+        if !cmds.is_empty() {
+            match &mut self.conn {
+                RedisConnection::Single(conn) => {
+                    let mut pipe = ::redis::pipe();
+                    for cmd in cmds {
+                        pipe.add_command(cmd).ignore();
+                    }
+                    pipe.query_async::<_, ()>(conn).await?;
+                }
+                RedisConnection::Cluster(conn) => {
+                    // The keys of a pipeline may belong to different nodes, so the commands are
+                    // sent concurrently over the multiplexed connection instead.
+                    try_join_all(cmds.iter().map(|cmd| {
+                        let mut conn = conn.clone();
+                        async move { cmd.query_async::<_, ()>(&mut conn).await }
+                    }))
+                    .await?;
+                }
+            }
+        }
+        self.buffer.clear();
+        Ok(())
     }
 
     async fn abort(&mut self) -> Result<()> {
-        todo!()
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl From<RedisError> for SinkError {
+    fn from(e: RedisError) -> Self {
+        SinkError::Redis(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::StreamChunkTestExt;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+
+    #[test]
+    fn test_template() {
+        let schema = Schema::new(vec![
+            Field::with_name(DataType::Int32, "id"),
+            Field::with_name(DataType::Varchar, "name"),
+        ]);
+        let chunk = StreamChunk::from_pretty(
+            " i T
+            + 1 foo",
+        );
+        let (_, row) = chunk.rows().next().unwrap();
+
+        let template = Template::parse("user:{id}:{{{name}}}", &schema).unwrap();
+        assert_eq!(template.render(&row, &schema).unwrap(), "user:1:{foo}");
+        assert_eq!(
+            Template::from_pk(&[1, 0]).render(&row, &schema).unwrap(),
+            "foo:1"
+        );
+        assert!(Template::parse("user:{age}", &schema).is_err());
     }
 }
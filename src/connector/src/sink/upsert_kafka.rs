@@ -0,0 +1,471 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
+
+use apache_avro::types::Value as AvroValue;
+use apache_avro::Schema as AvroSchema;
+use chrono::Datelike;
+use hyper::http::uri::InvalidUri;
+use hyper::{Body, Method, Request};
+use hyper_tls::HttpsConnector;
+use itertools::Itertools;
+use rdkafka::producer::BaseRecord;
+use risingwave_common::array::{Op, RowRef, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::{DataType, DatumRef, ScalarRefImpl, UNIX_EPOCH_DAYS};
+use serde_json::{json, Map, Value};
+
+use super::kafka::{datum_to_json_object, record_to_json, KafkaConfig, KafkaSink};
+use super::{Sink, SinkError};
+use crate::sink::Result;
+
+pub const UPSERT_KAFKA_SINK: &str = "upsert-kafka";
+
+/// The encoding of the keys and the payloads of the messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertKafkaEncode {
+    Json,
+    /// Encoded in the Confluent wire format, with the schemas registered in the schema registry.
+    Avro,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpsertKafkaConfig {
+    pub kafka: KafkaConfig,
+
+    pub encode: UpsertKafkaEncode,
+
+    /// The url of the schema registry, required by the avro encoding.
+    pub schema_registry_url: Option<String>,
+}
+
+impl UpsertKafkaConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let get = |key: &str| {
+            values
+                .get(key)
+                .cloned()
+                .ok_or_else(|| SinkError::Config(format!("missing config: {}", key)))
+        };
+        let brokers = get("kafka.brokers")?;
+        let topic = get("kafka.topic")?;
+        let identifier = get("identifier")?;
+        let encode = match values.get("format").map(|format| format.to_lowercase()) {
+            None => UpsertKafkaEncode::Json,
+            Some(format) if format == "json" => UpsertKafkaEncode::Json,
+            Some(format) if format == "avro" => UpsertKafkaEncode::Avro,
+            Some(_) => {
+                return Err(SinkError::Config(
+                    "format must be set to \"json\" or \"avro\"".to_string(),
+                ))
+            }
+        };
+        let schema_registry_url = values.get("schema.registry").cloned();
+        if encode == UpsertKafkaEncode::Avro && schema_registry_url.is_none() {
+            return Err(SinkError::Config(
+                "schema.registry must be set for the avro format".to_string(),
+            ));
+        }
+
+        Ok(UpsertKafkaConfig {
+            kafka: KafkaConfig {
+                brokers,
+                topic,
+                identifier,
+                partition: None,
+                format: "upsert".to_string(),
+                timeout: Duration::from_secs(5), // default timeout is 5 seconds
+                max_retry_num: 3,                // default max retry num is 3
+                retry_interval: Duration::from_millis(100), // default retry interval is 100ms
+            },
+            encode,
+            schema_registry_url,
+        })
+    }
+}
+
+/// The avro schemas of the keys and the payloads, and their ids in the schema registry.
+struct AvroEncoder {
+    key_schema: AvroSchema,
+    key_schema_id: i32,
+    value_schema: AvroSchema,
+    value_schema_id: i32,
+}
+
+/// [`UpsertKafkaSink`] writes the changes of a materialized view to a compacted Kafka topic. The
+/// primary key of a row is encoded as the message key, so that the latest message of a key is the
+/// latest value of the row. A delete is written as a tombstone, i.e. a message without payload.
+pub struct UpsertKafkaSink {
+    pub config: UpsertKafkaConfig,
+    inner: KafkaSink,
+    pk_indices: Vec<usize>,
+    avro_encoder: Option<AvroEncoder>,
+}
+
+impl UpsertKafkaSink {
+    pub async fn new(config: UpsertKafkaConfig, pk_indices: Vec<usize>) -> Result<Self> {
+        Ok(UpsertKafkaSink {
+            inner: KafkaSink::new(config.kafka.clone()).await?,
+            config,
+            pk_indices,
+            avro_encoder: None,
+        })
+    }
+
+    /// Checks the primary key, and registers the schemas of the avro encoding.
+    pub async fn prepare(&mut self, schema: &Schema) -> Result<()> {
+        if self.pk_indices.is_empty() {
+            return Err(SinkError::Config(
+                "upsert-kafka sink requires a primary key".to_string(),
+            ));
+        }
+        if self.config.encode == UpsertKafkaEncode::Avro {
+            let topic = &self.config.kafka.topic;
+            let url = self.config.schema_registry_url.as_ref().unwrap();
+            let key_fields = self
+                .pk_indices
+                .iter()
+                .map(|&idx| schema.fields[idx].clone())
+                .collect_vec();
+            let key_schema = avro_schema(&format!("{}_key", topic), &key_fields)?;
+            let value_schema = avro_schema(&format!("{}_value", topic), &schema.fields)?;
+            let key_schema_id =
+                register_schema(url, &format!("{}-key", topic), &key_schema).await?;
+            let value_schema_id =
+                register_schema(url, &format!("{}-value", topic), &value_schema).await?;
+            self.avro_encoder = Some(AvroEncoder {
+                key_schema,
+                key_schema_id,
+                value_schema,
+                value_schema_id,
+            });
+        }
+        Ok(())
+    }
+
+    fn encode_key(&self, row: &RowRef<'_>, schema: &Schema) -> Result<Vec<u8>> {
+        match &self.avro_encoder {
+            None => {
+                let mut mappings = Map::with_capacity(self.pk_indices.len());
+                for &idx in &self.pk_indices {
+                    let field = &schema.fields[idx];
+                    let value = datum_to_json_object(field, row.value_at(idx))
+                        .map_err(|e| SinkError::JsonParse(e.to_string()))?;
+                    mappings.insert(field.name.clone(), value);
+                }
+                Ok(Value::Object(mappings).to_string().into_bytes())
+            }
+            Some(encoder) => {
+                let record = self
+                    .pk_indices
+                    .iter()
+                    .map(|&idx| {
+                        let field = &schema.fields[idx];
+                        datum_to_avro_value(row.value_at(idx)).map(|v| (field.name.clone(), v))
+                    })
+                    .try_collect()?;
+                encode_confluent_avro(
+                    &encoder.key_schema,
+                    encoder.key_schema_id,
+                    AvroValue::Record(record),
+                )
+            }
+        }
+    }
+
+    fn encode_value(&self, row: RowRef<'_>, schema: &Schema) -> Result<Vec<u8>> {
+        match &self.avro_encoder {
+            None => Ok(Value::Object(record_to_json(row, schema.fields.clone())?)
+                .to_string()
+                .into_bytes()),
+            Some(encoder) => {
+                let record = schema
+                    .fields
+                    .iter()
+                    .zip_eq(row.values())
+                    .map(|(field, datum)| {
+                        datum_to_avro_value(datum).map(|v| (field.name.clone(), v))
+                    })
+                    .try_collect()?;
+                encode_confluent_avro(
+                    &encoder.value_schema,
+                    encoder.value_schema_id,
+                    AvroValue::Record(record),
+                )
+            }
+        }
+    }
+
+    async fn send_tombstone(&self, key: &[u8]) -> Result<()> {
+        self.inner
+            .send(BaseRecord::<[u8], [u8]>::to(self.config.kafka.topic.as_str()).key(key))
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Sink for UpsertKafkaSink {
+    async fn write_batch(&mut self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+        // The key of the last `UpdateDelete`, which is deleted if the following `UpdateInsert`
+        // changes the primary key.
+        let mut update_cache: Option<Vec<u8>> = None;
+        for (op, row) in chunk.rows() {
+            let key = self.encode_key(&row, schema)?;
+            match op {
+                Op::Insert | Op::UpdateInsert => {
+                    if let Some(old_key) = update_cache.take() && old_key != key {
+                        self.send_tombstone(&old_key).await?;
+                    }
+                    let payload = self.encode_value(row, schema)?;
+                    self.inner
+                        .send(
+                            BaseRecord::to(self.config.kafka.topic.as_str())
+                                .key(key.as_slice())
+                                .payload(payload.as_slice()),
+                        )
+                        .await?;
+                }
+                Op::Delete => self.send_tombstone(&key).await?,
+                Op::UpdateDelete => update_cache = Some(key),
+            }
+        }
+        if let Some(old_key) = update_cache {
+            self.send_tombstone(&old_key).await?;
+        }
+        Ok(())
+    }
+
+    async fn begin_epoch(&mut self, epoch: u64) -> Result<()> {
+        self.inner.begin_epoch(epoch).await
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        self.inner.commit().await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.inner.abort().await
+    }
+
+    fn is_two_phase(&self) -> bool {
+        self.inner.is_two_phase()
+    }
+
+    async fn pre_commit(&mut self, epoch: u64) -> Result<()> {
+        self.inner.pre_commit(epoch).await
+    }
+
+    fn transaction_id(&self, epoch: u64) -> String {
+        self.inner.transaction_id(epoch)
+    }
+
+    async fn resolve_prepared(&mut self, transaction_id: &str, commit: bool) -> Result<()> {
+        self.inner.resolve_prepared(transaction_id, commit).await
+    }
+}
+
+impl Debug for UpsertKafkaSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UpsertKafkaSink")
+            .field("config", &self.config)
+            .field("pk_indices", &self.pk_indices)
+            .finish()
+    }
+}
+
+fn data_type_to_avro(data_type: &DataType) -> Result<Value> {
+    let avro_type = match data_type {
+        DataType::Boolean => json!("boolean"),
+        DataType::Int16 | DataType::Int32 => json!("int"),
+        DataType::Int64 => json!("long"),
+        DataType::Float32 => json!("float"),
+        DataType::Float64 => json!("double"),
+        // Decimals are encoded as strings, the same as the json encoding.
+        DataType::Varchar | DataType::Decimal => json!("string"),
+        DataType::Date => json!({"type": "int", "logicalType": "date"}),
+        DataType::Timestamp | DataType::Timestampz => {
+            json!({"type": "long", "logicalType": "timestamp-micros"})
+        }
+        _ => {
+            return Err(SinkError::Config(format!(
+                "unsupported data type of upsert-kafka sink with avro format: {:?}",
+                data_type
+            )))
+        }
+    };
+    Ok(avro_type)
+}
+
+/// Generates a record schema with a nullable field for each column.
+fn avro_schema(name: &str, fields: &[Field]) -> Result<AvroSchema> {
+    // Avro names only consist of letters, digits and underscores.
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let fields: Vec<Value> = fields
+        .iter()
+        .map(|field| {
+            data_type_to_avro(&field.data_type).map(|avro_type| {
+                json!({
+                    "name": field.name,
+                    "type": ["null", avro_type],
+                    "default": null,
+                })
+            })
+        })
+        .try_collect()?;
+    let schema = json!({
+        "type": "record",
+        "name": name,
+        "fields": fields,
+    });
+    AvroSchema::parse(&schema).map_err(|e| SinkError::Config(format!("invalid avro schema: {}", e)))
+}
+
+fn datum_to_avro_value(datum: DatumRef<'_>) -> Result<AvroValue> {
+    let Some(scalar) = datum else {
+        return Ok(AvroValue::Union(0, Box::new(AvroValue::Null)));
+    };
+    let value = match scalar {
+        ScalarRefImpl::Bool(v) => AvroValue::Boolean(v),
+        ScalarRefImpl::Int16(v) => AvroValue::Int(v as i32),
+        ScalarRefImpl::Int32(v) => AvroValue::Int(v),
+        // Both `bigint` and `timestamp with time zone` are stored as `i64`.
+        ScalarRefImpl::Int64(v) => AvroValue::Long(v),
+        ScalarRefImpl::Float32(v) => AvroValue::Float(f32::from(v)),
+        ScalarRefImpl::Float64(v) => AvroValue::Double(f64::from(v)),
+        ScalarRefImpl::Utf8(v) => AvroValue::String(v.to_string()),
+        ScalarRefImpl::Decimal(v) => AvroValue::String(v.to_string()),
+        ScalarRefImpl::NaiveDate(v) => AvroValue::Date(v.0.num_days_from_ce() - UNIX_EPOCH_DAYS),
+        ScalarRefImpl::NaiveDateTime(v) => AvroValue::TimestampMicros(
+            v.0.timestamp() * 1_000_000 + v.0.timestamp_subsec_micros() as i64,
+        ),
+        _ => {
+            return Err(SinkError::Config(format!(
+                "unsupported value of upsert-kafka sink with avro format: {:?}",
+                scalar
+            )))
+        }
+    };
+    Ok(AvroValue::Union(1, Box::new(value)))
+}
+
+/// Encodes the value in the Confluent wire format: a zero magic byte, the 4-byte big-endian schema
+/// id, and then the avro binary encoding.
+fn encode_confluent_avro(schema: &AvroSchema, schema_id: i32, value: AvroValue) -> Result<Vec<u8>> {
+    let datum = apache_avro::to_avro_datum(schema, value)
+        .map_err(|e| SinkError::Config(format!("failed to encode avro value: {}", e)))?;
+    let mut buf = Vec::with_capacity(5 + datum.len());
+    buf.push(0);
+    buf.extend_from_slice(&schema_id.to_be_bytes());
+    buf.extend(datum);
+    Ok(buf)
+}
+
+/// Registers the schema under the subject in the schema registry, and returns its id.
+async fn register_schema(url: &str, subject: &str, schema: &AvroSchema) -> Result<i32> {
+    let uri = format!(
+        "{}/subjects/{}/versions",
+        url.trim_end_matches('/'),
+        subject
+    );
+    let body = json!({ "schema": schema.canonical_form() }).to_string();
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(
+            uri.parse::<hyper::Uri>()
+                .map_err(|e: InvalidUri| SinkError::Config(e.to_string()))?,
+        )
+        .header("Content-Type", "application/vnd.schemaregistry.v1+json")
+        .body(Body::from(body))
+        .map_err(|e| SinkError::Config(e.to_string()))?;
+
+    let client = hyper::Client::builder().build::<_, Body>(HttpsConnector::new());
+    let res = client
+        .request(request)
+        .await
+        .map_err(|e| SinkError::Config(format!("failed to register schema to {}: {}", uri, e)))?;
+    let status = res.status();
+    let buf = hyper::body::to_bytes(res)
+        .await
+        .map_err(|e| SinkError::Config(format!("failed to read HTTP body: {}", e)))?;
+    if !status.is_success() {
+        return Err(SinkError::Config(format!(
+            "failed to register schema to {}: {} {}",
+            uri,
+            status,
+            String::from_utf8_lossy(&buf)
+        )));
+    }
+    let res: Value =
+        serde_json::from_slice(&buf).map_err(|e| SinkError::JsonParse(e.to_string()))?;
+    res.get("id")
+        .and_then(Value::as_i64)
+        .map(|id| id as i32)
+        .ok_or_else(|| SinkError::JsonParse(format!("no schema id in response: {}", res)))
+}
+
+#[cfg(test)]
+mod test {
+    use maplit::hashmap;
+
+    use super::*;
+
+    #[test]
+    fn test_upsert_kafka_config() {
+        let properties = hashmap! {
+            "kafka.brokers".to_string() => "localhost:29092".to_string(),
+            "identifier".to_string() => "test_sink_1".to_string(),
+            "kafka.topic".to_string() => "test_topic".to_string(),
+        };
+        let config = UpsertKafkaConfig::from_hashmap(properties.clone()).unwrap();
+        assert_eq!(config.encode, UpsertKafkaEncode::Json);
+
+        // The avro format requires the schema registry.
+        let mut avro_properties = properties;
+        avro_properties.insert("format".to_string(), "avro".to_string());
+        assert!(UpsertKafkaConfig::from_hashmap(avro_properties.clone()).is_err());
+        avro_properties.insert(
+            "schema.registry".to_string(),
+            "http://localhost:8081".to_string(),
+        );
+        let config = UpsertKafkaConfig::from_hashmap(avro_properties).unwrap();
+        assert_eq!(config.encode, UpsertKafkaEncode::Avro);
+    }
+
+    #[test]
+    fn test_encode_confluent_avro() {
+        let fields = vec![
+            Field::with_name(DataType::Int32, "id"),
+            Field::with_name(DataType::Varchar, "name"),
+        ];
+        let schema = avro_schema("test-topic_value", &fields).unwrap();
+        let record = AvroValue::Record(vec![
+            (
+                "id".to_string(),
+                datum_to_avro_value(Some(ScalarRefImpl::Int32(1))).unwrap(),
+            ),
+            ("name".to_string(), datum_to_avro_value(None).unwrap()),
+        ]);
+        let encoded = encode_confluent_avro(&schema, 42, record.clone()).unwrap();
+        assert_eq!(&encoded[..5], &[0, 0, 0, 0, 42]);
+        let decoded = apache_avro::from_avro_datum(&schema, &mut &encoded[5..], None).unwrap();
+        assert_eq!(decoded, record);
+    }
+}
@@ -33,6 +33,9 @@ use crate::source::datagen::{
     DatagenProperties, DatagenSplit, DatagenSplitEnumerator, DatagenSplitReader, DATAGEN_CONNECTOR,
 };
 use crate::source::dummy_connector::DummySplitReader;
+use crate::source::filesystem::s3::enumerator::S3SplitEnumerator;
+use crate::source::filesystem::s3::source::S3FileReader;
+use crate::source::filesystem::s3::split::S3FileSplit;
 use crate::source::filesystem::s3::{S3Properties, S3_CONNECTOR};
 use crate::source::kafka::enumerator::KafkaSplitEnumerator;
 use crate::source::kafka::source::KafkaSplitReader;
@@ -41,10 +44,18 @@ use crate::source::kinesis::enumerator::client::KinesisSplitEnumerator;
 use crate::source::kinesis::source::reader::KinesisSplitReader;
 use crate::source::kinesis::split::KinesisSplit;
 use crate::source::kinesis::{KinesisProperties, KINESIS_CONNECTOR};
+use crate::source::mysql_cdc::source::MySqlCdcSplitReader;
+use crate::source::mysql_cdc::{
+    MySqlCdcProperties, MySqlCdcSplit, MySqlCdcSplitEnumerator, MYSQL_CDC_CONNECTOR,
+};
 use crate::source::nexmark::source::reader::NexmarkSplitReader;
 use crate::source::nexmark::{
     NexmarkProperties, NexmarkSplit, NexmarkSplitEnumerator, NEXMARK_CONNECTOR,
 };
+use crate::source::postgres_cdc::source::PostgresCdcSplitReader;
+use crate::source::postgres_cdc::{
+    PostgresCdcProperties, PostgresCdcSplit, PostgresCdcSplitEnumerator, POSTGRES_CDC_CONNECTOR,
+};
 use crate::source::pulsar::source::reader::PulsarSplitReader;
 use crate::source::pulsar::{
     PulsarProperties, PulsarSplit, PulsarSplitEnumerator, PULSAR_CONNECTOR,
@@ -84,6 +95,41 @@ pub type BoxSourceStream = BoxStream<'static, Result<Vec<SourceMessage>>>;
 /// The max size of a chunk yielded by source stream.
 pub const MAX_CHUNK_SIZE: usize = 1024;
 
+/// The property of the max rows per second read by each actor of a source.
+pub const RATE_LIMIT_KEY: &str = "rate_limit";
+/// The property of the max bytes per second read by each actor of a source.
+pub const BYTE_RATE_LIMIT_KEY: &str = "byte_rate_limit";
+
+/// The rate limits of a source, set by the `WITH` options or `ALTER SOURCE ... SET`. A limit of
+/// zero means unlimited. Each actor of the source is throttled independently, so the total rate of
+/// the source is the limit times its parallelism.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceRateLimit {
+    pub rows_per_second: u64,
+    pub bytes_per_second: u64,
+}
+
+impl SourceRateLimit {
+    pub fn from_properties(properties: &HashMap<String, String>) -> Result<Self> {
+        let parse = |key: &str| -> Result<u64> {
+            properties.get(key).map_or(Ok(0), |value| {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|e| anyhow!("invalid {} \"{}\": {}", key, value, e))
+            })
+        };
+        Ok(Self {
+            rows_per_second: parse(RATE_LIMIT_KEY)?,
+            bytes_per_second: parse(BYTE_RATE_LIMIT_KEY)?,
+        })
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.rows_per_second == 0 && self.bytes_per_second == 0
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, EnumAsInner, PartialEq, Hash)]
 pub enum SplitImpl {
     Kafka(KafkaSplit),
@@ -91,6 +137,9 @@ pub enum SplitImpl {
     Kinesis(KinesisSplit),
     Nexmark(NexmarkSplit),
     Datagen(DatagenSplit),
+    S3(S3FileSplit),
+    MySqlCdc(MySqlCdcSplit),
+    PostgresCdc(PostgresCdcSplit),
 }
 
 pub enum SplitReaderImpl {
@@ -100,6 +149,9 @@ pub enum SplitReaderImpl {
     Nexmark(Box<NexmarkSplitReader>),
     Pulsar(Box<PulsarSplitReader>),
     Datagen(Box<DatagenSplitReader>),
+    S3(Box<S3FileReader>),
+    MySqlCdc(Box<MySqlCdcSplitReader>),
+    PostgresCdc(Box<PostgresCdcSplitReader>),
 }
 
 pub enum SplitEnumeratorImpl {
@@ -108,6 +160,9 @@ pub enum SplitEnumeratorImpl {
     Kinesis(KinesisSplitEnumerator),
     Nexmark(NexmarkSplitEnumerator),
     Datagen(DatagenSplitEnumerator),
+    S3(S3SplitEnumerator),
+    MySqlCdc(MySqlCdcSplitEnumerator),
+    PostgresCdc(PostgresCdcSplitEnumerator),
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -118,6 +173,8 @@ pub enum ConnectorProperties {
     Nexmark(Box<NexmarkProperties>),
     Datagen(Box<DatagenProperties>),
     S3(Box<S3Properties>),
+    MySqlCdc(Box<MySqlCdcProperties>),
+    PostgresCdc(Box<PostgresCdcProperties>),
     Dummy(Box<()>),
 }
 
@@ -127,7 +184,76 @@ impl_connector_properties! {
     { Kinesis, KINESIS_CONNECTOR },
     { Nexmark, NEXMARK_CONNECTOR },
     { Datagen, DATAGEN_CONNECTOR },
-    { S3, S3_CONNECTOR }
+    { S3, S3_CONNECTOR },
+    { MySqlCdc, MYSQL_CDC_CONNECTOR },
+    { PostgresCdc, POSTGRES_CDC_CONNECTOR }
+}
+
+/// The position to rewind or fast-forward the offsets of a source to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceOffsetTarget {
+    Earliest,
+    Latest,
+    /// The first message whose timestamp is not earlier than the given milliseconds.
+    Timestamp(i64),
+}
+
+impl ConnectorProperties {
+    /// Overrides the startup options of the source, so that the splits listed by the enumerator
+    /// built from the properties start from `target` instead.
+    pub fn set_startup_offset(&mut self, target: SourceOffsetTarget) -> Result<()> {
+        let (mode, timestamp) = match target {
+            SourceOffsetTarget::Earliest => (Some("earliest".to_string()), None),
+            SourceOffsetTarget::Latest => (Some("latest".to_string()), None),
+            SourceOffsetTarget::Timestamp(millis) => (None, Some(millis.to_string())),
+        };
+        match self {
+            ConnectorProperties::Kafka(props) => {
+                props.scan_startup_mode = mode;
+                props.time_offset = timestamp;
+            }
+            ConnectorProperties::Pulsar(props) => {
+                props.scan_startup_mode = mode;
+                props.time_offset = timestamp;
+            }
+            ConnectorProperties::Kinesis(props) => {
+                props.scan_startup_mode = mode;
+                props.timestamp_offset = timestamp;
+            }
+            _ => return Err(anyhow!("the offsets of the connector can not be reset")),
+        }
+        Ok(())
+    }
+
+    /// Whether the offsets need to be committed to the upstream once checkpointed, so that the
+    /// upstream can discard the data before them.
+    pub fn need_commit_offsets(&self) -> bool {
+        matches!(self, ConnectorProperties::PostgresCdc(_))
+    }
+
+    /// Commits the offsets of the splits, which have been durably checkpointed, to the upstream.
+    pub async fn commit_offsets(&self, splits: &[SplitImpl]) -> Result<()> {
+        if let ConnectorProperties::PostgresCdc(props) = self {
+            for split in splits {
+                let split = split
+                    .as_postgres_cdc()
+                    .ok_or_else(|| anyhow!("unexpected split {:?}", split))?;
+                match &split.offset {
+                    Some(offset) if offset.lsn > 0 => props.advance_slot(offset.lsn).await?,
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the resources created in the upstream for the source, e.g. the replication slots.
+    pub async fn drop_upstream_resources(&self) -> Result<()> {
+        if let ConnectorProperties::PostgresCdc(props) = self {
+            props.drop_replication().await?;
+        }
+        Ok(())
+    }
 }
 
 impl_split_enumerator! {
@@ -135,7 +261,10 @@ impl_split_enumerator! {
     { Pulsar, PulsarSplitEnumerator },
     { Kinesis, KinesisSplitEnumerator },
     { Nexmark, NexmarkSplitEnumerator },
-    { Datagen, DatagenSplitEnumerator }
+    { Datagen, DatagenSplitEnumerator },
+    { S3, S3SplitEnumerator },
+    { MySqlCdc, MySqlCdcSplitEnumerator },
+    { PostgresCdc, PostgresCdcSplitEnumerator }
 }
 
 impl_split! {
@@ -143,7 +272,10 @@ impl_split! {
     { Pulsar, PULSAR_CONNECTOR, PulsarSplit },
     { Kinesis, KINESIS_CONNECTOR, KinesisSplit },
     { Nexmark, NEXMARK_CONNECTOR, NexmarkSplit },
-    { Datagen, DATAGEN_CONNECTOR, DatagenSplit }
+    { Datagen, DATAGEN_CONNECTOR, DatagenSplit },
+    { S3, S3_CONNECTOR, S3FileSplit },
+    { MySqlCdc, MYSQL_CDC_CONNECTOR, MySqlCdcSplit },
+    { PostgresCdc, POSTGRES_CDC_CONNECTOR, PostgresCdcSplit }
 }
 
 impl_split_reader! {
@@ -152,6 +284,9 @@ impl_split_reader! {
     { Kinesis, KinesisSplitReader },
     { Nexmark, NexmarkSplitReader },
     { Datagen, DatagenSplitReader },
+    { S3, S3FileReader },
+    { MySqlCdc, MySqlCdcSplitReader },
+    { PostgresCdc, PostgresCdcSplitReader },
     { Dummy, DummySplitReader }
 }
 
@@ -173,6 +308,19 @@ pub struct SourceMessage {
     pub payload: Option<Bytes>,
     pub offset: String,
     pub split_id: SplitId,
+    pub meta: SourceMeta,
+}
+
+/// The metadata of a message provided by the message brokers, which can be included as columns of
+/// the source besides the split id and the offset.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct SourceMeta {
+    /// The timestamp of the message in milliseconds since the epoch.
+    pub timestamp: Option<i64>,
+    pub key: Option<Bytes>,
+    /// The header line of the file that the message is read from, e.g. the column names of a CSV
+    /// file, which is only provided by the file sources reading files with headers.
+    pub header: Option<Bytes>,
 }
 
 /// The metadata of a split.
@@ -180,6 +328,18 @@ pub trait SplitMetaData: Sized {
     fn id(&self) -> SplitId;
     fn encode_to_bytes(&self) -> Bytes;
     fn restore_from_bytes(bytes: &[u8]) -> Result<Self>;
+
+    /// The splits to be read to the end before this one, e.g. the parent shards of a Kinesis shard
+    /// created by resharding, so that the records of a key are read in order.
+    fn parent_ids(&self) -> Vec<SplitId> {
+        vec![]
+    }
+
+    /// Whether all the records of the split are read, which is only possible for the splits
+    /// closed by the upstream.
+    fn is_finished(&self) -> bool {
+        false
+    }
 }
 
 /// [`ConnectorState`] maintains the consuming splits' info. In specific split readers,
@@ -251,4 +411,60 @@ mod tests {
             panic!("extract nexmark config failed");
         }
     }
+
+    #[test]
+    fn test_source_rate_limit_from_properties() {
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "connector" => "kafka",
+            "rate_limit" => "1000",
+        ));
+        let rate_limit = SourceRateLimit::from_properties(&props).unwrap();
+        assert_eq!(rate_limit.rows_per_second, 1000);
+        assert_eq!(rate_limit.bytes_per_second, 0);
+        assert!(!rate_limit.is_unlimited());
+
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "byte_rate_limit" => "-1",
+        ));
+        assert!(SourceRateLimit::from_properties(&props).is_err());
+    }
+
+    #[test]
+    fn test_set_startup_offset() {
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "connector" => "kafka",
+            "properties.bootstrap.server" => "localhost:9092",
+            "topic" => "test",
+            "scan.startup.mode" => "earliest",
+        ));
+        let mut props = ConnectorProperties::extract(props).unwrap();
+
+        props
+            .set_startup_offset(SourceOffsetTarget::Timestamp(1000))
+            .unwrap();
+        let ConnectorProperties::Kafka(kafka_props) = &props else {
+            panic!("extract kafka config failed");
+        };
+        assert_eq!(kafka_props.scan_startup_mode, None);
+        assert_eq!(kafka_props.time_offset.as_deref(), Some("1000"));
+
+        props
+            .set_startup_offset(SourceOffsetTarget::Latest)
+            .unwrap();
+        let ConnectorProperties::Kafka(kafka_props) = &props else {
+            panic!("extract kafka config failed");
+        };
+        assert_eq!(kafka_props.scan_startup_mode.as_deref(), Some("latest"));
+        assert_eq!(kafka_props.time_offset, None);
+
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "connector" => "nexmark",
+            "nexmark.table.type" => "Person",
+            "nexmark.split.num" => "1",
+        ));
+        let mut props = ConnectorProperties::extract(props).unwrap();
+        assert!(props
+            .set_startup_offset(SourceOffsetTarget::Earliest)
+            .is_err());
+    }
 }
@@ -43,6 +43,19 @@ pub struct DatagenProperties {
     #[serde_as(as = "DisplayFromStr")]
     pub rows_per_second: u64,
 
+    /// The ratios of updates and deletes among the generated events, both default 0. When either
+    /// is positive, the source emits a change stream in the Debezium JSON format: besides inserts,
+    /// it updates and deletes the rows it has inserted, where the fields of the `sequence` kind
+    /// identify a row and keep their values across updates.
+    /// for example: 'datagen.update.ratio'='0.2', 'datagen.delete.ratio'='0.1'
+    #[serde(rename = "datagen.update.ratio", default)]
+    #[serde_as(as = "DisplayFromStr")]
+    pub update_ratio: f64,
+
+    #[serde(rename = "datagen.delete.ratio", default)]
+    #[serde_as(as = "DisplayFromStr")]
+    pub delete_ratio: f64,
+
     /// Some connector options of the datagen source's fields
     /// for example: create datagen source with column v1 int, v2 float
     /// 'fields.v1.kind'='sequence',
@@ -51,6 +64,12 @@ pub struct DatagenProperties {
     /// 'fields.v2.kind'='random',
     /// datagen will create v1 by self-incrementing from 1 to 1000
     /// datagen will create v2 by randomly generating from default_min to default_max
+    ///
+    /// Random numeric fields accept a `distribution` of `uniform`, `zipf` (with `exponent`) or
+    /// `normal` (with `mean` and `stddev`), varchar fields accept a `pattern` like `user_####`,
+    /// timestamp fields of the `sequence` kind accept `start`, `step` and `jitter`, and integer
+    /// fields of the `reference` kind pick values produced by the sequence field named by
+    /// `reference`.
     #[serde(flatten)]
     fields: HashMap<String, String>,
 }
@@ -17,16 +17,79 @@ use std::time::Duration;
 use anyhow::Result;
 use bytes::Bytes;
 use futures_async_stream::try_stream;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use risingwave_common::field_generator::FieldGeneratorImpl;
-use serde_json::Value;
+use serde_json::{Map, Value};
 
-use crate::source::{SourceMessage, SplitId};
+use crate::source::cdc::{
+    debezium_event, DEBEZIUM_CREATE_OP, DEBEZIUM_DELETE_OP, DEBEZIUM_UPDATE_OP,
+};
+use crate::source::{SourceMessage, SourceMeta, SplitId};
+
+/// Mixed into the seed of the change stream, so that the choice of the operation does not
+/// correlate with the random fields, which are seeded by the split index by default.
+const CHANGE_STREAM_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Turns the generated rows into a change stream in the Debezium JSON format, which updates and
+/// deletes the rows inserted earlier. The inserted rows are kept in memory and are not recovered
+/// after failover, so that only the rows inserted since the reader starts will be changed.
+pub struct ChangeStream {
+    update_ratio: f64,
+    delete_ratio: f64,
+    /// The fields that identify a row, which keep their values across updates.
+    key_fields: Vec<String>,
+    live_rows: Vec<Map<String, Value>>,
+    seed: u64,
+}
+
+impl ChangeStream {
+    pub fn new(update_ratio: f64, delete_ratio: f64, key_fields: Vec<String>, seed: u64) -> Self {
+        Self {
+            update_ratio,
+            delete_ratio,
+            key_fields,
+            live_rows: vec![],
+            seed: seed ^ CHANGE_STREAM_SEED,
+        }
+    }
+
+    /// Encodes the row generated at `offset` as an insert, or uses it as the new value of a random
+    /// live row for an update, or deletes a random live row and drops it.
+    fn next_event(&mut self, offset: u64, mut row: Map<String, Value>) -> Bytes {
+        let mut rng = StdRng::seed_from_u64(offset ^ self.seed);
+        let p = rng.gen::<f64>();
+        if self.live_rows.is_empty() || p >= self.update_ratio + self.delete_ratio {
+            self.live_rows.push(row.clone());
+            return debezium_event(DEBEZIUM_CREATE_OP, Value::Null, Value::Object(row));
+        }
+        let index = rng.gen_range(0..self.live_rows.len());
+        if p < self.update_ratio {
+            let live_row = &mut self.live_rows[index];
+            for key in &self.key_fields {
+                if let Some(value) = live_row.get(key) {
+                    row.insert(key.clone(), value.clone());
+                }
+            }
+            let before = std::mem::replace(live_row, row.clone());
+            debezium_event(
+                DEBEZIUM_UPDATE_OP,
+                Value::Object(before),
+                Value::Object(row),
+            )
+        } else {
+            let before = self.live_rows.swap_remove(index);
+            debezium_event(DEBEZIUM_DELETE_OP, Value::Object(before), Value::Null)
+        }
+    }
+}
 
 pub struct DatagenEventGenerator {
     fields_map: HashMap<String, FieldGeneratorImpl>,
     offset: u64,
     split_id: SplitId,
     partition_rows_per_second: u64,
+    change_stream: Option<ChangeStream>,
 }
 
 impl DatagenEventGenerator {
@@ -37,6 +100,7 @@ impl DatagenEventGenerator {
         split_id: SplitId,
         split_num: u64,
         split_index: u64,
+        change_stream: Option<ChangeStream>,
     ) -> Result<Self> {
         let partition_rows_per_second = if rows_per_second % split_num > split_index {
             rows_per_second / split_num + 1
@@ -48,6 +112,7 @@ impl DatagenEventGenerator {
             offset,
             split_id,
             partition_rows_per_second,
+            change_stream,
         })
     }
 
@@ -59,18 +124,22 @@ impl DatagenEventGenerator {
             interval.tick().await;
             let mut msgs = vec![];
             for _ in 0..self.partition_rows_per_second {
-                let value = Value::Object(
-                    self.fields_map
-                        .iter_mut()
-                        .map(|(name, field_generator)| {
-                            (name.to_string(), field_generator.generate(self.offset))
-                        })
-                        .collect(),
-                );
+                let row: Map<String, Value> = self
+                    .fields_map
+                    .iter_mut()
+                    .map(|(name, field_generator)| {
+                        (name.to_string(), field_generator.generate(self.offset))
+                    })
+                    .collect();
+                let payload = match &mut self.change_stream {
+                    Some(change_stream) => change_stream.next_event(self.offset, row),
+                    None => Bytes::from(Value::Object(row).to_string()),
+                };
                 msgs.push(SourceMessage {
-                    payload: Some(Bytes::from(value.to_string())),
+                    payload: Some(payload),
                     offset: self.offset.to_string(),
                     split_id: self.split_id.clone(),
+                    meta: SourceMeta::default(),
                 });
                 self.offset += 1;
             }
@@ -82,6 +151,7 @@ impl DatagenEventGenerator {
 #[cfg(test)]
 mod tests {
     use futures::stream::StreamExt;
+    use serde_json::json;
 
     use super::*;
 
@@ -124,6 +194,7 @@ mod tests {
             split_id,
             split_num,
             split_index,
+            None,
         )
         .unwrap();
 
@@ -154,4 +225,47 @@ mod tests {
         check_sequence_partition_result(3, 1, 10, 3).await;
         check_sequence_partition_result(3, 2, 10, 3).await;
     }
+
+    #[test]
+    fn test_change_stream() {
+        let mut change_stream = ChangeStream::new(0.3, 0.2, vec!["id".to_string()], 0);
+        // the current value of every live row by its id
+        let mut live_rows = HashMap::new();
+        let (mut updates, mut deletes) = (0, 0);
+        for offset in 0..1000 {
+            let mut row = Map::new();
+            row.insert("id".to_string(), json!(offset));
+            row.insert("v".to_string(), json!(offset * 2));
+            let event: Value =
+                serde_json::from_slice(&change_stream.next_event(offset, row)).unwrap();
+            let payload = &event["payload"];
+            match payload["op"].as_str().unwrap() {
+                DEBEZIUM_CREATE_OP => {
+                    assert!(payload["before"].is_null());
+                    let after = payload["after"].clone();
+                    assert_eq!(after["id"], json!(offset));
+                    live_rows.insert(offset, after);
+                }
+                DEBEZIUM_UPDATE_OP => {
+                    updates += 1;
+                    let id = payload["before"]["id"].as_u64().unwrap();
+                    assert_eq!(live_rows[&id], payload["before"]);
+                    // the key is kept while the other fields are regenerated
+                    assert_eq!(payload["after"]["id"], json!(id));
+                    assert_eq!(payload["after"]["v"], json!(offset * 2));
+                    live_rows.insert(id, payload["after"].clone());
+                }
+                DEBEZIUM_DELETE_OP => {
+                    deletes += 1;
+                    assert!(payload["after"].is_null());
+                    let id = payload["before"]["id"].as_u64().unwrap();
+                    assert_eq!(live_rows.remove(&id).unwrap(), payload["before"]);
+                }
+                op => unreachable!("unexpected op {}", op),
+            }
+        }
+        assert_eq!(live_rows.len(), change_stream.live_rows.len());
+        assert!((250..350).contains(&updates), "{}", updates);
+        assert!((150..250).contains(&deletes), "{}", deletes);
+    }
 }
@@ -18,3 +18,4 @@ mod reader;
 pub use reader::*;
 
 const SEQUENCE_FIELD_KIND: &str = "sequence";
+const REFERENCE_FIELD_KIND: &str = "reference";
@@ -14,14 +14,14 @@
 
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{anyhow, ensure, Result};
 use async_trait::async_trait;
 use futures::StreamExt;
 use itertools::zip_eq;
-use risingwave_common::field_generator::FieldGeneratorImpl;
+use risingwave_common::field_generator::{DistributionOptions, FieldGeneratorImpl};
 
-use super::generator::DatagenEventGenerator;
-use crate::source::datagen::source::SEQUENCE_FIELD_KIND;
+use super::generator::{ChangeStream, DatagenEventGenerator};
+use crate::source::datagen::source::{REFERENCE_FIELD_KIND, SEQUENCE_FIELD_KIND};
 use crate::source::datagen::{DatagenProperties, DatagenSplit};
 use crate::source::{
     spawn_data_generation_stream, BoxSourceStream, Column, ConnectorState, DataType, SplitId,
@@ -66,6 +66,7 @@ impl SplitReader for DatagenSplitReader {
         let split_num = assigned_split.split_num as u64;
 
         let rows_per_second = properties.rows_per_second;
+        let (update_ratio, delete_ratio) = (properties.update_ratio, properties.delete_ratio);
         let fields_option_map = properties.fields;
         let mut fields_map = HashMap::<String, FieldGeneratorImpl>::new();
 
@@ -91,6 +92,36 @@ impl SplitReader for DatagenSplitReader {
         // 'fields.f_random_str.length'='10'
         // )
 
+        // the fields of the sequence kind identify a row in the change stream
+        let key_fields = columns
+            .iter()
+            .filter(|column| {
+                fields_option_map
+                    .get(&format!("fields.{}.kind", column.name))
+                    .map(|kind| kind.as_str())
+                    == Some(SEQUENCE_FIELD_KIND)
+            })
+            .map(|column| column.name.clone())
+            .collect::<Vec<_>>();
+        let change_stream = if update_ratio > 0.0 || delete_ratio > 0.0 {
+            ensure!(
+                update_ratio >= 0.0 && delete_ratio >= 0.0 && update_ratio + delete_ratio < 1.0,
+                "the update and delete ratios must be non-negative and sum to less than 1"
+            );
+            ensure!(
+                !key_fields.is_empty(),
+                "a field of the sequence kind is required to identify the rows to update or delete"
+            );
+            Some(ChangeStream::new(
+                update_ratio,
+                delete_ratio,
+                key_fields,
+                split_index,
+            ))
+        } else {
+            None
+        };
+
         for column in columns {
             let name = column.name.clone();
             let gen = generator_from_data_type(
@@ -110,6 +141,7 @@ impl SplitReader for DatagenSplitReader {
             split_id,
             split_num,
             split_index,
+            change_stream,
         )?;
 
         Ok(DatagenSplitReader {
@@ -155,23 +187,54 @@ fn generator_from_data_type(
         }
         None => split_index,
     };
+    let kind_key = format!("fields.{}.kind", name);
+    let kind = fields_option_map.get(&kind_key).map(|s| s.as_str());
     match data_type {
         DataType::Timestamp => {
-            let max_past_key = format!("fields.{}.max_past", name);
-            let max_past_value = fields_option_map.get(&max_past_key).map(|s| s.to_string());
-            FieldGeneratorImpl::with_random(
-                data_type,
-                None,
-                None,
-                max_past_value,
-                None,
-                random_seed,
-            )
+            if kind == Some(SEQUENCE_FIELD_KIND) {
+                let start_key = format!("fields.{}.start", name);
+                let step_key = format!("fields.{}.step", name);
+                let jitter_key = format!("fields.{}.jitter", name);
+                let start_value = fields_option_map.get(&start_key).map(|s| s.to_string());
+                let step_value = fields_option_map.get(&step_key).map(|s| s.to_string());
+                let jitter_value = fields_option_map.get(&jitter_key).map(|s| s.to_string());
+                FieldGeneratorImpl::with_timestamp_sequence(
+                    start_value,
+                    step_value,
+                    jitter_value,
+                    split_index,
+                    split_num,
+                    random_seed,
+                )
+            } else {
+                let max_past_key = format!("fields.{}.max_past", name);
+                let max_past_value = fields_option_map.get(&max_past_key).map(|s| s.to_string());
+                FieldGeneratorImpl::with_random(
+                    data_type,
+                    None,
+                    None,
+                    max_past_value,
+                    None,
+                    random_seed,
+                )
+            }
         }
         DataType::Varchar => {
-            let length_key = format!("fields.{}.length", name);
-            let length_value = fields_option_map.get(&length_key).map(|s| s.to_string());
-            FieldGeneratorImpl::with_random(data_type, None, None, None, length_value, random_seed)
+            let pattern_key = format!("fields.{}.pattern", name);
+            if let Some(pattern) = fields_option_map.get(&pattern_key) {
+                FieldGeneratorImpl::with_pattern(pattern.to_string(), random_seed)
+            } else {
+                let length_key = format!("fields.{}.length", name);
+                let length_value = fields_option_map.get(&length_key).map(|s| s.to_string());
+                FieldGeneratorImpl::with_random(
+                    data_type,
+                    None,
+                    None,
+                    None,
+                    length_value,
+                    random_seed,
+                )
+            }
         }
         DataType::Struct(struct_type) => {
             let struct_fields = zip_eq(struct_type.field_names.clone(), struct_type.fields.clone())
@@ -189,33 +252,77 @@ fn generator_from_data_type(
             FieldGeneratorImpl::with_struct_fields(struct_fields)
         }
         _ => {
-            let kind_key = format!("fields.{}.kind", name);
-            if let Some(kind) = fields_option_map.get(&kind_key) && kind.as_str() == SEQUENCE_FIELD_KIND {
+            if kind == Some(SEQUENCE_FIELD_KIND) {
                 let start_key = format!("fields.{}.start", name);
                 let end_key = format!("fields.{}.end", name);
-                let start_value =
-                    fields_option_map.get(&start_key).map(|s| s.to_string());
+                let start_value = fields_option_map.get(&start_key).map(|s| s.to_string());
                 let end_value = fields_option_map.get(&end_key).map(|s| s.to_string());
                 FieldGeneratorImpl::with_sequence(
                     data_type,
                     start_value,
                     end_value,
                     split_index,
-                    split_num
+                    split_num,
+                )
+            } else if kind == Some(REFERENCE_FIELD_KIND) {
+                let reference_key = format!("fields.{}.reference", name);
+                let reference = fields_option_map
+                    .get(&reference_key)
+                    .ok_or_else(|| anyhow!("{} is required by reference field", reference_key))?;
+                ensure!(
+                    fields_option_map
+                        .get(&format!("fields.{}.kind", reference))
+                        .map(|s| s.as_str())
+                        == Some(SEQUENCE_FIELD_KIND),
+                    "field {} referenced by {} must be of the sequence kind",
+                    reference,
+                    name
+                );
+                let start_key = format!("fields.{}.start", reference);
+                let end_key = format!("fields.{}.end", reference);
+                let start_value = fields_option_map.get(&start_key).map(|s| s.to_string());
+                let end_value = fields_option_map.get(&end_key).map(|s| s.to_string());
+                FieldGeneratorImpl::with_reference(
+                    data_type,
+                    start_value,
+                    end_value,
+                    split_index,
+                    split_num,
+                    random_seed,
                 )
             } else {
                 let min_key = format!("fields.{}.min", name);
                 let max_key = format!("fields.{}.max", name);
                 let min_value = fields_option_map.get(&min_key).map(|s| s.to_string());
                 let max_value = fields_option_map.get(&max_key).map(|s| s.to_string());
-                FieldGeneratorImpl::with_random(
-                    data_type,
-                    min_value,
-                    max_value,
-                    None,
-                    None,
-                    random_seed
-                )
+                let distribution_key = format!("fields.{}.distribution", name);
+                if let Some(distribution) = fields_option_map.get(&distribution_key) {
+                    let exponent_key = format!("fields.{}.exponent", name);
+                    let mean_key = format!("fields.{}.mean", name);
+                    let stddev_key = format!("fields.{}.stddev", name);
+                    let distribution = DistributionOptions {
+                        kind: distribution.to_string(),
+                        exponent: fields_option_map.get(&exponent_key).map(|s| s.to_string()),
+                        mean: fields_option_map.get(&mean_key).map(|s| s.to_string()),
+                        stddev: fields_option_map.get(&stddev_key).map(|s| s.to_string()),
+                    };
+                    FieldGeneratorImpl::with_distribution(
+                        data_type,
+                        min_value,
+                        max_value,
+                        &distribution,
+                        random_seed,
+                    )
+                } else {
+                    FieldGeneratorImpl::with_random(
+                        data_type,
+                        min_value,
+                        max_value,
+                        None,
+                        None,
+                        random_seed,
+                    )
+                }
             }
         }
     }
@@ -261,6 +368,8 @@ mod tests {
         let properties = DatagenProperties {
             split_num: None,
             rows_per_second: 10,
+            update_ratio: 0.0,
+            delete_ratio: 0.0,
             fields: convert_args!(hashmap!(
                 "fields.random_int.min" => "1",
                 "fields.random_int.max" => "1000",
@@ -313,6 +422,8 @@ mod tests {
         let properties = DatagenProperties {
             split_num: None,
             rows_per_second: 10,
+            update_ratio: 0.0,
+            delete_ratio: 0.0,
             fields: HashMap::new(),
         };
         let stream = DatagenSplitReader::new(properties.clone(), state, Some(mock_datum.clone()))
@@ -333,4 +444,89 @@ mod tests {
         assert_eq!(v1, v2);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_change_stream() -> Result<()> {
+        let mock_datum = vec![
+            Column {
+                name: "id".to_string(),
+                data_type: DataType::Int64,
+            },
+            Column {
+                name: "parent_id".to_string(),
+                data_type: DataType::Int64,
+            },
+            Column {
+                name: "name".to_string(),
+                data_type: DataType::Varchar,
+            },
+        ];
+        let state = Some(vec![SplitImpl::Datagen(DatagenSplit {
+            split_index: 0,
+            split_num: 1,
+            start_offset: None,
+        })]);
+        let properties = DatagenProperties {
+            split_num: None,
+            rows_per_second: 100,
+            update_ratio: 0.3,
+            delete_ratio: 0.1,
+            fields: convert_args!(hashmap!(
+                "fields.id.kind" => "sequence",
+                "fields.id.start" => "1",
+                "fields.id.end" => "10000",
+
+                "fields.parent_id.kind" => "reference",
+                "fields.parent_id.reference" => "id",
+
+                "fields.name.pattern" => "user_####",
+            )),
+        };
+
+        let mut reader = DatagenSplitReader::new(properties, state, Some(mock_datum))
+            .await?
+            .into_stream();
+        let msgs = reader.next().await.unwrap()?;
+        let mut ops = HashMap::new();
+        for msg in msgs {
+            let event: serde_json::Value =
+                serde_json::from_slice(msg.payload.as_ref().unwrap()).unwrap();
+            let payload = &event["payload"];
+            let op = payload["op"].as_str().unwrap().to_string();
+            let row = if op == "d" {
+                &payload["before"]
+            } else {
+                &payload["after"]
+            };
+            // `id` is `offset + 1` at every offset, so references never go beyond it
+            let offset = msg.offset.parse::<i64>().unwrap();
+            assert!(row["parent_id"].as_i64().unwrap() <= offset + 1);
+            assert!(row["name"].as_str().unwrap().starts_with("user_"));
+            *ops.entry(op).or_insert(0) += 1;
+        }
+        assert_eq!(ops.len(), 3, "{:?}", ops);
+
+        let properties = DatagenProperties {
+            split_num: None,
+            rows_per_second: 10,
+            update_ratio: 0.5,
+            delete_ratio: 0.0,
+            fields: HashMap::new(),
+        };
+        let state = Some(vec![SplitImpl::Datagen(DatagenSplit {
+            split_index: 0,
+            split_num: 1,
+            start_offset: None,
+        })]);
+        let columns = vec![Column {
+            name: "v".to_string(),
+            data_type: DataType::Int32,
+        }];
+        // there is no field to identify a row
+        assert!(DatagenSplitReader::new(properties, state, Some(columns))
+            .await
+            .is_err());
+
+        Ok(())
+    }
 }
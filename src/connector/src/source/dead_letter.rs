@@ -0,0 +1,214 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use rdkafka::error::KafkaError;
+use rdkafka::producer::{BaseRecord, DefaultProducerContext, ThreadedProducer};
+use rdkafka::types::RDKafkaErrorCode;
+use rdkafka::ClientConfig;
+use serde_json::json;
+
+/// The property of the policy on the messages failing to be parsed.
+pub const PARSE_ERROR_POLICY_KEY: &str = "parse.error.policy";
+/// The property of the Kafka topic which the dead-letter records are sent to.
+pub const DEAD_LETTER_TOPIC_KEY: &str = "parse.dead_letter.topic";
+/// The property of the Kafka brokers of the dead-letter topic, which defaults to the brokers of
+/// the source if it's a Kafka source.
+pub const DEAD_LETTER_BROKERS_KEY: &str = "parse.dead_letter.brokers";
+
+const KAFKA_BROKERS_KEYS: [&str; 2] = ["properties.bootstrap.server", "kafka.brokers"];
+
+/// What to do with the messages failing to be parsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseErrorPolicy {
+    /// Skip the message.
+    #[default]
+    Skip,
+    /// Skip the message and send it to the dead-letter topic with the error reason.
+    DeadLetter,
+    /// Fail the source, so that the message is retried after recovery.
+    Fail,
+}
+
+impl ParseErrorPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParseErrorPolicy::Skip => "skip",
+            ParseErrorPolicy::DeadLetter => "dead_letter",
+            ParseErrorPolicy::Fail => "fail",
+        }
+    }
+}
+
+/// The destination of the dead-letter records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetterConfig {
+    pub brokers: String,
+    pub topic: String,
+}
+
+/// The error tolerance of parsing the messages of a source, set by the `WITH` options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseErrorConfig {
+    pub policy: ParseErrorPolicy,
+    /// Only set if the policy is [`ParseErrorPolicy::DeadLetter`].
+    pub dead_letter: Option<DeadLetterConfig>,
+}
+
+impl ParseErrorConfig {
+    pub fn from_properties(properties: &HashMap<String, String>) -> Result<Self> {
+        let policy = match properties
+            .get(PARSE_ERROR_POLICY_KEY)
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            None | Some("skip") => ParseErrorPolicy::Skip,
+            Some("dead_letter") => ParseErrorPolicy::DeadLetter,
+            Some("fail") => ParseErrorPolicy::Fail,
+            Some(policy) => {
+                return Err(anyhow!(
+                    "invalid {} \"{}\", expected one of skip, dead_letter and fail",
+                    PARSE_ERROR_POLICY_KEY,
+                    policy
+                ))
+            }
+        };
+
+        let dead_letter = if policy == ParseErrorPolicy::DeadLetter {
+            let topic = properties
+                .get(DEAD_LETTER_TOPIC_KEY)
+                .ok_or_else(|| anyhow!("{} must be set for dead_letter", DEAD_LETTER_TOPIC_KEY))?;
+            let brokers = properties
+                .get(DEAD_LETTER_BROKERS_KEY)
+                .or_else(|| KAFKA_BROKERS_KEYS.iter().find_map(|k| properties.get(*k)))
+                .ok_or_else(|| {
+                    anyhow!("{} must be set for dead_letter", DEAD_LETTER_BROKERS_KEY)
+                })?;
+            Some(DeadLetterConfig {
+                brokers: brokers.clone(),
+                topic: topic.clone(),
+            })
+        } else {
+            None
+        };
+
+        Ok(Self {
+            policy,
+            dead_letter,
+        })
+    }
+}
+
+/// Sends the messages failing to be parsed to a Kafka topic. Each dead-letter record is a JSON
+/// object with the raw payload in base64 and the error reason, keyed by the split and offset of
+/// the message.
+pub struct DeadLetterQueue {
+    topic: String,
+    producer: ThreadedProducer<DefaultProducerContext>,
+}
+
+impl DeadLetterQueue {
+    const MAX_RETRY_NUM: usize = 10;
+    const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+    pub async fn new(config: &DeadLetterConfig) -> Result<Self> {
+        let producer: ThreadedProducer<DefaultProducerContext> = ClientConfig::new()
+            .set("bootstrap.servers", &config.brokers)
+            .create()
+            .await?;
+        Ok(Self {
+            topic: config.topic.clone(),
+            producer,
+        })
+    }
+
+    pub async fn send(
+        &self,
+        source_id: u32,
+        split_id: &str,
+        offset: &str,
+        payload: &[u8],
+        error: &str,
+    ) -> Result<()> {
+        let key = format!("{}-{}-{}", source_id, split_id, offset);
+        let value = json!({
+            "source_id": source_id,
+            "split_id": split_id,
+            "offset": offset,
+            "payload": base64::encode(payload),
+            "error": error,
+        })
+        .to_string();
+
+        let mut record = BaseRecord::to(&self.topic)
+            .key(key.as_bytes())
+            .payload(value.as_bytes());
+        for _ in 0..Self::MAX_RETRY_NUM {
+            match self.producer.send(record) {
+                Ok(()) => return Ok(()),
+                // Wait for the queued records to be delivered.
+                Err((KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull), rec)) => {
+                    record = rec;
+                    tokio::time::sleep(Self::RETRY_INTERVAL).await;
+                }
+                Err((e, _)) => return Err(e.into()),
+            }
+        }
+        Err(anyhow!(
+            "failed to send dead-letter record to {}: queue is full",
+            self.topic
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::*;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_error_config_from_properties() {
+        let config = ParseErrorConfig::from_properties(&HashMap::new()).unwrap();
+        assert_eq!(config, ParseErrorConfig::default());
+
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "properties.bootstrap.server" => "localhost:9092",
+            "parse.error.policy" => "DEAD_LETTER",
+            "parse.dead_letter.topic" => "dlq",
+        ));
+        let config = ParseErrorConfig::from_properties(&props).unwrap();
+        assert_eq!(config.policy, ParseErrorPolicy::DeadLetter);
+        assert_eq!(
+            config.dead_letter,
+            Some(DeadLetterConfig {
+                brokers: "localhost:9092".to_string(),
+                topic: "dlq".to_string(),
+            })
+        );
+
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "parse.error.policy" => "dead_letter",
+        ));
+        assert!(ParseErrorConfig::from_properties(&props).is_err());
+
+        let props: HashMap<String, String> = convert_args!(hashmap!(
+            "parse.error.policy" => "ignore",
+        ));
+        assert!(ParseErrorConfig::from_properties(&props).is_err());
+    }
+}
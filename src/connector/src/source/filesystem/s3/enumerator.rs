@@ -0,0 +1,104 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use aws_sdk_s3::client::Client;
+use globset::{Glob, GlobMatcher};
+
+use crate::source::filesystem::s3::s3_dir::find_prefix;
+use crate::source::filesystem::s3::split::S3FileSplit;
+use crate::source::filesystem::s3::S3Properties;
+use crate::source::SplitEnumerator;
+
+/// [`S3SplitEnumerator`] lists the objects of the bucket whose keys match `match_pattern`, and
+/// assigns each of them as a split. As the source manager lists the splits periodically, newly
+/// arrived files are discovered and assigned, while the files already assigned keep their offsets.
+pub struct S3SplitEnumerator {
+    bucket_name: String,
+    /// The literal prefix of `match_pattern`, used to narrow down the listing.
+    prefix: Option<String>,
+    matcher: Option<GlobMatcher>,
+    client: Client,
+}
+
+#[async_trait]
+impl SplitEnumerator for S3SplitEnumerator {
+    type Properties = S3Properties;
+    type Split = S3FileSplit;
+
+    async fn new(properties: Self::Properties) -> Result<Self> {
+        let client = properties.build_client().await;
+        let (prefix, matcher) = if let Some(pattern) = properties.match_pattern.as_ref() {
+            let glob = Glob::new(pattern)
+                .map_err(|e| anyhow!("invalid match_pattern {:?}: {}", pattern, e))?;
+            let prefix = find_prefix(glob.glob());
+            (
+                (!prefix.is_empty()).then_some(prefix),
+                Some(glob.compile_matcher()),
+            )
+        } else {
+            (None, None)
+        };
+        Ok(Self {
+            bucket_name: properties.bucket_name,
+            prefix,
+            matcher,
+            client,
+        })
+    }
+
+    async fn list_splits(&mut self) -> Result<Vec<Self::Split>> {
+        let mut splits = vec![];
+        let mut continuation_token = None;
+        loop {
+            let resp = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket_name)
+                .set_prefix(self.prefix.clone())
+                .set_continuation_token(continuation_token.take())
+                .send()
+                .await
+                .map_err(|e| anyhow!("failed to list objects of {}: {}", self.bucket_name, e))?;
+
+            for object in resp.contents().unwrap_or_default() {
+                let Some(key) = object.key() else {
+                    continue;
+                };
+                // Skip the "directories".
+                if key.ends_with('/') {
+                    continue;
+                }
+                if let Some(matcher) = &self.matcher && !matcher.is_match(key) {
+                    continue;
+                }
+                splits.push(S3FileSplit::new(
+                    key.to_string(),
+                    0,
+                    object.size().max(0) as u64,
+                ));
+            }
+
+            if !resp.is_truncated() {
+                break;
+            }
+            continuation_token = resp.next_continuation_token().map(str::to_string);
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(splits)
+    }
+}
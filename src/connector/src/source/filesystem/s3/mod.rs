@@ -11,21 +11,27 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+pub mod enumerator;
 mod s3_dir;
 mod s3_notification_event;
-mod source;
+pub mod source;
+pub mod split;
 
 use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::aws_utils::{default_conn_config, s3_client, AwsConfigV2, AwsCredentialV2};
 
 pub const S3_CONNECTOR: &str = "s3";
 
+#[serde_as]
 #[derive(Clone, Debug, Deserialize)]
 pub struct S3Properties {
     #[serde(rename = "s3.region_name")]
     pub region_name: String,
     #[serde(rename = "s3.bucket_name")]
     pub bucket_name: String,
-    #[serde(rename = "sqs_queue_name")]
+    #[serde(rename = "sqs_queue_name", default)]
     pub sqs_queue_name: String,
     #[serde(rename = "match_pattern", default)]
     pub match_pattern: Option<String>,
@@ -33,4 +39,33 @@ pub struct S3Properties {
     pub access: String,
     #[serde(rename = "s3.credentials.secret", default)]
     pub secret: String,
+    /// Whether the files begin with a header line, which is attached to the messages of the
+    /// following lines rather than parsed as a record.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(rename = "csv.header", default)]
+    pub csv_header: bool,
+}
+
+impl S3Properties {
+    /// Builds the S3 client shared by the enumerator and the reader. Static credentials are used
+    /// if both the access key and the secret are given, otherwise the default credential chain.
+    pub(crate) async fn build_client(&self) -> aws_sdk_s3::Client {
+        let credential = if self.access.is_empty() || self.secret.is_empty() {
+            AwsCredentialV2::default()
+        } else {
+            AwsCredentialV2::Static {
+                access_key: self.access.clone(),
+                secret_access: self.secret.clone(),
+                session_token: None,
+            }
+        };
+        let aws_config = AwsConfigV2 {
+            region: Some(self.region_name.clone()),
+            arn: None,
+            credential,
+            endpoint: None,
+        };
+        let shared_config = aws_config.load_config(None).await;
+        s3_client(&shared_config, Some(default_conn_config()))
+    }
 }
@@ -0,0 +1,217 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::pin::Pin;
+
+use anyhow::{anyhow, Result};
+use async_compression::tokio::bufread::GzipDecoder;
+use async_trait::async_trait;
+use aws_sdk_s3::client::Client;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use futures_async_stream::try_stream;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio_util::io::StreamReader;
+
+use crate::source::base::{SourceMessage, SourceMeta, SplitReader, MAX_CHUNK_SIZE};
+use crate::source::filesystem::s3::split::S3FileSplit;
+use crate::source::filesystem::s3::S3Properties;
+use crate::source::{BoxSourceStream, Column, ConnectorState, SplitId, SplitImpl};
+
+/// [`S3FileReader`] reads the assigned files line by line, so each line of an NDJSON or CSV file
+/// becomes a message. The offset of a message is the number of bytes consumed after the line, from
+/// which the reading is resumed on recovery. Files with the `.gz` extension are decompressed, and
+/// their offsets count the decompressed bytes.
+///
+/// If `csv.header` is set, the first non-blank line of each file is taken as the header. It only
+/// advances the offset, and is attached to the metadata of the following messages of the file.
+pub struct S3FileReader {
+    bucket_name: String,
+    client: Client,
+    splits: Vec<S3FileSplit>,
+    csv_header: bool,
+}
+
+#[async_trait]
+impl SplitReader for S3FileReader {
+    type Properties = S3Properties;
+
+    async fn new(
+        props: S3Properties,
+        state: ConnectorState,
+        _columns: Option<Vec<Column>>,
+    ) -> Result<Self> {
+        let splits = state
+            .unwrap_or_default()
+            .into_iter()
+            .map(|split| match split {
+                SplitImpl::S3(split) => Ok(split),
+                other => Err(anyhow!("expect S3FileSplit, got {:?}", other)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let client = props.build_client().await;
+        Ok(Self {
+            bucket_name: props.bucket_name,
+            client,
+            splits,
+            csv_header: props.csv_header,
+        })
+    }
+
+    fn into_stream(self) -> BoxSourceStream {
+        self.into_stream()
+    }
+}
+
+impl S3FileReader {
+    /// Opens the file from `split.offset`. Uncompressed files are read with a range request, while
+    /// compressed ones have to be decompressed from the beginning, skipping the consumed bytes.
+    async fn open(&self, split: &S3FileSplit) -> Result<Pin<Box<dyn AsyncBufRead + Send>>> {
+        let mut request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(&split.name);
+        if !split.is_gzip() && split.offset > 0 {
+            request = request.range(format!("bytes={}-", split.offset));
+        }
+        let object = request.send().await.map_err(|e| {
+            anyhow!(
+                "failed to get object {} of {}: {}",
+                split.name,
+                self.bucket_name,
+                e
+            )
+        })?;
+        let reader = StreamReader::new(
+            object
+                .body
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)),
+        );
+
+        if split.is_gzip() {
+            let mut reader = Box::pin(BufReader::new(GzipDecoder::new(reader)));
+            let skipped = tokio::io::copy(
+                &mut (&mut reader).take(split.offset),
+                &mut tokio::io::sink(),
+            )
+            .await?;
+            if skipped < split.offset {
+                return Err(anyhow!(
+                    "offset {} exceeds the decompressed size {} of {}",
+                    split.offset,
+                    skipped,
+                    split.name
+                ));
+            }
+            Ok(reader)
+        } else {
+            Ok(Box::pin(reader))
+        }
+    }
+
+    /// Reads the first non-blank line of the file without the line terminator, or `None` if the
+    /// file is blank. Returns the number of bytes consumed as well.
+    async fn read_first_line(
+        reader: &mut Pin<Box<dyn AsyncBufRead + Send>>,
+    ) -> Result<(Option<Bytes>, u64)> {
+        let mut consumed = 0;
+        loop {
+            let mut line = vec![];
+            let n_read = reader.read_until(b'\n', &mut line).await?;
+            if n_read == 0 {
+                return Ok((None, consumed));
+            }
+            consumed += n_read as u64;
+            while matches!(line.last(), Some(b'\n' | b'\r')) {
+                line.pop();
+            }
+            if !line.is_empty() {
+                return Ok((Some(line.into()), consumed));
+            }
+        }
+    }
+
+    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+    async fn into_stream(self) {
+        for split in &self.splits {
+            // The file has been fully consumed.
+            if !split.is_gzip() && split.offset >= split.size {
+                continue;
+            }
+            let split_id: SplitId = split.name.as_str().into();
+            let mut msgs = Vec::with_capacity(MAX_CHUNK_SIZE);
+            // The header has been consumed before the recovery, so read it again from the
+            // beginning.
+            let mut header = None;
+            if self.csv_header && split.offset > 0 {
+                let from_start = S3FileSplit {
+                    offset: 0,
+                    ..split.clone()
+                };
+                header = Self::read_first_line(&mut self.open(&from_start).await?)
+                    .await?
+                    .0;
+            }
+            let mut reader = self.open(split).await?;
+            let mut offset = split.offset;
+            if self.csv_header && header.is_none() {
+                let (line, n_read) = Self::read_first_line(&mut reader).await?;
+                offset += n_read;
+                header = line;
+                if header.is_some() {
+                    msgs.push(SourceMessage {
+                        payload: None,
+                        offset: offset.to_string(),
+                        split_id: split_id.clone(),
+                        meta: SourceMeta::default(),
+                    });
+                }
+            }
+            loop {
+                let mut line = vec![];
+                let n_read = reader.read_until(b'\n', &mut line).await?;
+                if n_read == 0 {
+                    break;
+                }
+                offset += n_read as u64;
+                // Strip the line terminator and skip the blank lines.
+                while matches!(line.last(), Some(b'\n' | b'\r')) {
+                    line.pop();
+                }
+                if line.is_empty() {
+                    continue;
+                }
+                msgs.push(SourceMessage {
+                    payload: Some(line.into()),
+                    offset: offset.to_string(),
+                    split_id: split_id.clone(),
+                    meta: SourceMeta {
+                        header: header.clone(),
+                        ..Default::default()
+                    },
+                });
+                if msgs.len() >= MAX_CHUNK_SIZE {
+                    yield std::mem::replace(&mut msgs, Vec::with_capacity(MAX_CHUNK_SIZE));
+                }
+            }
+            if !msgs.is_empty() {
+                yield msgs;
+            }
+        }
+        // All assigned files are consumed. Newly arrived files will be assigned by the meta, so
+        // keep the stream open instead of ending the source.
+        futures::future::pending::<()>().await;
+    }
+}
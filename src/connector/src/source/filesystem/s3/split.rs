@@ -0,0 +1,75 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::source::{SplitId, SplitMetaData};
+
+/// A file in the bucket, identified by its object key. The `offset` is the number of bytes of the
+/// (decompressed) file that have been consumed, so that the reader can resume from it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Hash)]
+pub struct S3FileSplit {
+    pub(crate) name: String,
+    pub(crate) offset: u64,
+    pub(crate) size: u64,
+}
+
+impl SplitMetaData for S3FileSplit {
+    fn id(&self) -> SplitId {
+        self.name.as_str().into()
+    }
+
+    fn encode_to_bytes(&self) -> Bytes {
+        Bytes::from(serde_json::to_string(self).unwrap())
+    }
+
+    fn restore_from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
+    }
+}
+
+impl S3FileSplit {
+    pub fn new(name: String, offset: u64, size: u64) -> Self {
+        Self { name, offset, size }
+    }
+
+    pub fn copy_with_offset(&self, start_offset: String) -> Self {
+        let offset = start_offset.parse().unwrap_or_default();
+        Self::new(self.name.clone(), offset, self.size)
+    }
+
+    /// Whether the file is gzip compressed, judged by its extension.
+    pub fn is_gzip(&self) -> bool {
+        self.name.ends_with(".gz")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SplitImpl;
+
+    #[test]
+    fn test_s3_split_with_offset() {
+        let split = S3FileSplit::new("data/2022-11-01.json.gz".to_string(), 0, 1024);
+        assert!(split.is_gzip());
+
+        let split = SplitImpl::S3(split).update("512".to_string());
+        let restored = SplitImpl::restore_from_bytes(&split.encode_to_bytes()).unwrap();
+        assert_eq!(restored.id().as_ref(), "data/2022-11-01.json.gz");
+        assert_eq!(restored.into_s3().unwrap().offset, 512);
+    }
+}
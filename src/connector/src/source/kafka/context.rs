@@ -0,0 +1,350 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context, Result};
+use aws_sigv4::http_request::{
+    sign, SignableRequest, SignatureLocation, SigningParams, SigningSettings,
+};
+use aws_types::credentials::ProvideCredentials;
+use hyper::{Body, Method, Request};
+use hyper_tls::HttpsConnector;
+use rdkafka::client::OAuthToken;
+use rdkafka::consumer::ConsumerContext;
+use rdkafka::ClientContext;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::runtime::Handle;
+
+use crate::source::kinesis::config::AwsConfigInfo;
+
+/// The value of `properties.sasl.mechanism` to authenticate with the IAM access control of Amazon
+/// MSK. It's carried over SASL/OAUTHBEARER, with the signed request as the token.
+pub const AWS_MSK_IAM_MECHANISM: &str = "AWS_MSK_IAM";
+
+const MSK_IAM_SERVICE_NAME: &str = "kafka-cluster";
+const MSK_IAM_USER_AGENT: &str = "risingwave";
+const MSK_IAM_TOKEN_LIFETIME: Duration = Duration::from_secs(900);
+
+/// The token lifetime if the OAuth provider doesn't tell.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(3600);
+
+/// Where to get the tokens for SASL/OAUTHBEARER.
+#[derive(Clone, Debug)]
+pub enum OAuthTokenProvider {
+    /// Builds unsigned JWTs from `properties.sasl.oauthbearer.config`, which is only suitable for
+    /// testing.
+    Unsecured {
+        principal: String,
+        lifetime: Duration,
+    },
+    /// Fetches tokens from an OAuth 2.0 token endpoint with the client credentials grant.
+    ClientCredentials {
+        token_endpoint_url: String,
+        client_id: String,
+        client_secret: String,
+        scope: Option<String>,
+    },
+    /// Signs IAM authentication tokens of Amazon MSK with the AWS credentials.
+    MskIam(AwsConfigInfo),
+}
+
+impl OAuthTokenProvider {
+    /// Parses the unsecured JWT settings in the format of librdkafka's `sasl.oauthbearer.config`,
+    /// e.g. `principal=admin lifetimeSeconds=3600`.
+    pub fn unsecured(config: &str) -> Result<Self> {
+        let mut principal = None;
+        let mut lifetime = DEFAULT_TOKEN_LIFETIME;
+        for pair in config.split_whitespace() {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow!("invalid SASL/OAUTHBEARER config: {}", pair))?;
+            match key {
+                "principal" => principal = Some(value.to_string()),
+                "lifetimeSeconds" => {
+                    lifetime = Duration::from_secs(
+                        value
+                            .parse()
+                            .with_context(|| format!("invalid lifetimeSeconds: {}", value))?,
+                    )
+                }
+                _ => {}
+            }
+        }
+        let principal =
+            principal.ok_or_else(|| anyhow!("principal is required in SASL/OAUTHBEARER config"))?;
+        Ok(Self::Unsecured {
+            principal,
+            lifetime,
+        })
+    }
+
+    pub async fn fetch_token(&self) -> Result<OAuthToken> {
+        match self {
+            Self::Unsecured {
+                principal,
+                lifetime,
+            } => Ok(unsecured_token(principal, *lifetime)),
+            Self::ClientCredentials {
+                token_endpoint_url,
+                client_id,
+                client_secret,
+                scope,
+            } => {
+                fetch_client_credentials_token(
+                    token_endpoint_url,
+                    client_id,
+                    client_secret,
+                    scope.as_deref(),
+                )
+                .await
+            }
+            Self::MskIam(config) => sign_msk_iam_token(config).await,
+        }
+    }
+}
+
+/// The client context of Kafka consumers. It refreshes the SASL/OAUTHBEARER tokens with the
+/// configured provider whenever librdkafka asks for a new one.
+pub struct KafkaContext {
+    token_provider: Option<OAuthTokenProvider>,
+    runtime: Handle,
+}
+
+impl KafkaContext {
+    /// Creates the context. Must be called within a tokio runtime, which is used to fetch the
+    /// tokens.
+    pub fn new(token_provider: Option<OAuthTokenProvider>) -> Self {
+        Self {
+            token_provider,
+            runtime: Handle::current(),
+        }
+    }
+}
+
+impl ClientContext for KafkaContext {
+    const ENABLE_REFRESH_OAUTH_TOKEN: bool = true;
+
+    fn generate_oauth_token(
+        &self,
+        _oauthbearer_config: Option<&str>,
+    ) -> Result<OAuthToken, Box<dyn Error>> {
+        let provider = self
+            .token_provider
+            .as_ref()
+            .ok_or("no token provider for SASL/OAUTHBEARER")?;
+        // The callback may be invoked on a runtime worker when the consumer is polled, so fetch the
+        // token on a separate thread instead of blocking the runtime in place.
+        let token = std::thread::scope(|s| {
+            s.spawn(|| self.runtime.block_on(provider.fetch_token()))
+                .join()
+        })
+        .map_err(|_| "panicked when fetching the SASL/OAUTHBEARER token")?
+        .map_err(|e| {
+            tracing::warn!("failed to fetch the SASL/OAUTHBEARER token: {:?}", e);
+            e.to_string()
+        })?;
+        Ok(token)
+    }
+}
+
+impl ConsumerContext for KafkaContext {}
+
+fn millis_since_epoch(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).unwrap().as_millis() as i64
+}
+
+fn unsecured_token(principal: &str, lifetime: Duration) -> OAuthToken {
+    let now = SystemTime::now();
+    let expiration = now + lifetime;
+    let header = json!({ "alg": "none" });
+    let claims = json!({
+        "sub": principal,
+        "iat": now.duration_since(UNIX_EPOCH).unwrap().as_secs(),
+        "exp": expiration.duration_since(UNIX_EPOCH).unwrap().as_secs(),
+    });
+    let token = format!(
+        "{}.{}.",
+        base64::encode_config(header.to_string(), base64::URL_SAFE_NO_PAD),
+        base64::encode_config(claims.to_string(), base64::URL_SAFE_NO_PAD)
+    );
+    OAuthToken {
+        token,
+        principal_name: principal.to_string(),
+        lifetime_ms: millis_since_epoch(expiration),
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+async fn fetch_client_credentials_token(
+    token_endpoint_url: &str,
+    client_id: &str,
+    client_secret: &str,
+    scope: Option<&str>,
+) -> Result<OAuthToken> {
+    let mut body = format!(
+        "grant_type=client_credentials&client_id={}&client_secret={}",
+        urlencoding::encode(client_id),
+        urlencoding::encode(client_secret)
+    );
+    if let Some(scope) = scope {
+        body.push_str(&format!("&scope={}", urlencoding::encode(scope)));
+    }
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(token_endpoint_url)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(body))?;
+
+    let now = SystemTime::now();
+    let client = hyper::Client::builder().build::<_, Body>(HttpsConnector::new());
+    let res = client
+        .request(request)
+        .await
+        .with_context(|| format!("failed to request token from {}", token_endpoint_url))?;
+    let status = res.status();
+    let buf = hyper::body::to_bytes(res).await?;
+    if !status.is_success() {
+        return Err(anyhow!(
+            "failed to request token from {}: {} {}",
+            token_endpoint_url,
+            status,
+            String::from_utf8_lossy(&buf)
+        ));
+    }
+    let res: TokenResponse =
+        serde_json::from_slice(&buf).context("failed to parse the token response")?;
+    let lifetime = res
+        .expires_in
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_TOKEN_LIFETIME);
+    Ok(OAuthToken {
+        token: res.access_token,
+        principal_name: client_id.to_string(),
+        lifetime_ms: millis_since_epoch(now + lifetime),
+    })
+}
+
+/// Signs a presigned `kafka-cluster:Connect` request with SigV4, whose URL encoded in base64 is
+/// the token accepted by the IAM access control of Amazon MSK.
+async fn sign_msk_iam_token(config: &AwsConfigInfo) -> Result<OAuthToken> {
+    let region = config
+        .region
+        .as_ref()
+        .ok_or_else(|| anyhow!("region should be provided"))?;
+    let sdk_config = config.load().await?;
+    let credentials = sdk_config
+        .credentials_provider()
+        .ok_or_else(|| anyhow!("no AWS credentials provider"))?
+        .provide_credentials()
+        .await
+        .context("failed to load AWS credentials")?;
+
+    let now = SystemTime::now();
+    let mut settings = SigningSettings::default();
+    settings.signature_location = SignatureLocation::QueryParams;
+    settings.expires_in = Some(MSK_IAM_TOKEN_LIFETIME);
+    let mut builder = SigningParams::builder()
+        .access_key(credentials.access_key_id())
+        .secret_key(credentials.secret_access_key())
+        .region(region)
+        .service_name(MSK_IAM_SERVICE_NAME)
+        .time(now)
+        .settings(settings);
+    builder.set_security_token(credentials.session_token());
+    let params = builder.build()?;
+
+    let mut request = http::Request::builder()
+        .method(Method::GET)
+        .uri(format!(
+            "https://kafka.{}.amazonaws.com/?Action={}",
+            region,
+            urlencoding::encode("kafka-cluster:Connect")
+        ))
+        .body("")?;
+    let (instructions, _) = sign(SignableRequest::from(&request), &params)
+        .map_err(|e| anyhow!(e))?
+        .into_parts();
+    instructions.apply_to_request(&mut request);
+
+    let url = format!("{}&User-Agent={}", request.uri(), MSK_IAM_USER_AGENT);
+    Ok(OAuthToken {
+        token: base64::encode_config(url, base64::URL_SAFE_NO_PAD),
+        principal_name: String::new(),
+        lifetime_ms: millis_since_epoch(now + MSK_IAM_TOKEN_LIFETIME),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::kinesis::config::AwsCredentials;
+
+    #[test]
+    fn test_unsecured_token() {
+        let provider = OAuthTokenProvider::unsecured("principal=admin lifetimeSeconds=60").unwrap();
+        let token = match provider {
+            OAuthTokenProvider::Unsecured {
+                principal,
+                lifetime,
+            } => {
+                assert_eq!(lifetime, Duration::from_secs(60));
+                unsecured_token(&principal, lifetime)
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(token.principal_name, "admin");
+        let parts = token.token.split('.').collect::<Vec<_>>();
+        assert_eq!(parts.len(), 3);
+        assert!(parts[2].is_empty());
+        let claims: serde_json::Value = serde_json::from_slice(
+            &base64::decode_config(parts[1], base64::URL_SAFE_NO_PAD).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(claims["sub"], "admin");
+
+        assert!(OAuthTokenProvider::unsecured("lifetimeSeconds=60").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_msk_iam_token() {
+        let config = AwsConfigInfo {
+            stream_name: "topic".to_string(),
+            region: Some("us-east-1".to_string()),
+            endpoint: None,
+            credentials: Some(AwsCredentials {
+                access_key_id: "access_key".to_string(),
+                secret_access_key: "secret_key".to_string(),
+                session_token: None,
+            }),
+            assume_role: None,
+        };
+        let token = sign_msk_iam_token(&config).await.unwrap();
+        let url = String::from_utf8(
+            base64::decode_config(&token.token, base64::URL_SAFE_NO_PAD).unwrap(),
+        )
+        .unwrap();
+        assert!(url.starts_with("https://kafka.us-east-1.amazonaws.com/?"));
+        assert!(url.contains("Action=kafka-cluster%3AConnect"));
+        assert!(url.contains("X-Amz-Signature="));
+        assert!(url.ends_with("&User-Agent=risingwave"));
+    }
+}
@@ -16,13 +16,13 @@ use std::collections::HashMap;
 
 use anyhow::{anyhow, Context};
 use async_trait::async_trait;
-use rdkafka::consumer::{BaseConsumer, Consumer, DefaultConsumerContext};
+use rdkafka::consumer::{BaseConsumer, Consumer};
 use rdkafka::error::KafkaResult;
 use rdkafka::{Offset, TopicPartitionList};
 
 use crate::source::base::SplitEnumerator;
 use crate::source::kafka::split::KafkaSplit;
-use crate::source::kafka::{KafkaProperties, KAFKA_SYNC_CALL_TIMEOUT};
+use crate::source::kafka::{KafkaContext, KafkaProperties, KAFKA_SYNC_CALL_TIMEOUT};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum KafkaEnumeratorOffset {
@@ -35,7 +35,7 @@ pub enum KafkaEnumeratorOffset {
 pub struct KafkaSplitEnumerator {
     broker_address: String,
     topic: String,
-    client: BaseConsumer,
+    client: BaseConsumer<KafkaContext>,
     start_offset: KafkaEnumeratorOffset,
 
     // maybe used in the future for batch processing
@@ -77,7 +77,9 @@ impl SplitEnumerator for KafkaSplitEnumerator {
         let mut config = rdkafka::ClientConfig::new();
         config.set("bootstrap.servers", &broker_address);
         properties.set_security_properties(&mut config);
-        let client: BaseConsumer = config.create_with_context(DefaultConsumerContext).await?;
+        let client: BaseConsumer<KafkaContext> = config
+            .create_with_context(properties.create_context()?)
+            .await?;
 
         Ok(Self {
             broker_address,
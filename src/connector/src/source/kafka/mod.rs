@@ -14,13 +14,18 @@
 
 use std::time::Duration;
 
+use anyhow::{anyhow, Result};
 use rdkafka::ClientConfig;
 use serde::Deserialize;
 
+use crate::source::kinesis::config::{AwsAssumeRole, AwsConfigInfo, AwsCredentials};
+
+pub mod context;
 pub mod enumerator;
 pub mod source;
 pub mod split;
 
+pub use context::*;
 pub use enumerator::*;
 pub use source::*;
 pub use split::*;
@@ -44,6 +49,11 @@ pub struct KafkaProperties {
     #[serde(rename = "properties.group.id", alias = "kafka.consumer.group")]
     pub consumer_group: Option<String>,
 
+    /// The lower bound of the message timestamps in milliseconds, which is derived from the
+    /// predicates on the source by the optimizer. The reader skips the messages before it.
+    #[serde(rename = "scan.pushdown.timestamp_millis")]
+    pub pushdown_timestamp_millis: Option<String>,
+
     /// Security protocol used for RisingWave to communicate with Kafka brokers. Could be
     /// PLAINTEXT, SSL, SASL_PLAINTEXT or SASL_SSL.
     #[serde(rename = "properties.security.protocol")]
@@ -66,7 +76,8 @@ pub struct KafkaProperties {
     #[serde(rename = "properties.ssl.key.password")]
     ssl_key_password: Option<String>,
 
-    /// SASL mechanism if SASL is enabled. Currently support PLAIN, SCRAM and GSSAPI.
+    /// SASL mechanism if SASL is enabled. Currently support PLAIN, SCRAM, GSSAPI, OAUTHBEARER
+    /// and AWS_MSK_IAM.
     #[serde(rename = "properties.sasl.mechanism")]
     sasl_mechanism: Option<String>,
 
@@ -101,6 +112,46 @@ pub struct KafkaProperties {
     /// Configurations for SASL/OAUTHBEARER.
     #[serde(rename = "properties.sasl.oauthbearer.config")]
     sasl_oathbearer_config: Option<String>,
+
+    /// Token endpoint of the OAuth 2.0 provider under SASL/OAUTHBEARER. If set, tokens are
+    /// fetched with the client credentials grant and refreshed before they expire. Otherwise,
+    /// unsecured tokens are built from `properties.sasl.oauthbearer.config`.
+    #[serde(rename = "properties.sasl.oauthbearer.token.endpoint.url")]
+    sasl_oauthbearer_token_endpoint_url: Option<String>,
+
+    /// Client id for the client credentials grant under SASL/OAUTHBEARER.
+    #[serde(rename = "properties.sasl.oauthbearer.client.id")]
+    sasl_oauthbearer_client_id: Option<String>,
+
+    /// Client secret for the client credentials grant under SASL/OAUTHBEARER.
+    #[serde(rename = "properties.sasl.oauthbearer.client.secret")]
+    sasl_oauthbearer_client_secret: Option<String>,
+
+    /// Scope of the requested tokens under SASL/OAUTHBEARER.
+    #[serde(rename = "properties.sasl.oauthbearer.scope")]
+    sasl_oauthbearer_scope: Option<String>,
+
+    /// Region of the Amazon MSK cluster under SASL/AWS_MSK_IAM.
+    #[serde(rename = "aws.region")]
+    aws_region: Option<String>,
+
+    /// AWS credentials under SASL/AWS_MSK_IAM. If not set, the default credentials chain is
+    /// used.
+    #[serde(rename = "aws.credentials.access_key_id")]
+    aws_access_key_id: Option<String>,
+
+    #[serde(rename = "aws.credentials.secret_access_key")]
+    aws_secret_access_key: Option<String>,
+
+    #[serde(rename = "aws.credentials.session_token")]
+    aws_session_token: Option<String>,
+
+    /// IAM role to assume under SASL/AWS_MSK_IAM.
+    #[serde(rename = "aws.credentials.role.arn")]
+    aws_assume_role_arn: Option<String>,
+
+    #[serde(rename = "aws.credentials.role.external_id")]
+    aws_assume_role_external_id: Option<String>,
 }
 
 impl KafkaProperties {
@@ -126,7 +177,12 @@ impl KafkaProperties {
 
         // SASL mechanism
         if let Some(sasl_mechanism) = self.sasl_mechanism.as_ref() {
-            config.set("sasl.mechanism", sasl_mechanism);
+            if sasl_mechanism.eq_ignore_ascii_case(AWS_MSK_IAM_MECHANISM) {
+                // The signed token of MSK IAM is carried over SASL/OAUTHBEARER.
+                config.set("sasl.mechanism", "OAUTHBEARER");
+            } else {
+                config.set("sasl.mechanism", sasl_mechanism);
+            }
         }
 
         // SASL/PLAIN & SASL/SCRAM
@@ -163,8 +219,79 @@ impl KafkaProperties {
         if let Some(sasl_oathbearer_config) = self.sasl_oathbearer_config.as_ref() {
             config.set("sasl.oauthbearer.config", sasl_oathbearer_config);
         }
-        // Currently, we only support unsecured OAUTH.
-        config.set("enable.sasl.oauthbearer.unsecure.jwt", "true");
+        // The tokens are always provided by the token refresh callback of `KafkaContext`, which
+        // is mutually exclusive with librdkafka's builtin unsecured JWT.
+    }
+
+    /// Creates the client context, which provides the tokens if the SASL mechanism is OAUTHBEARER
+    /// or AWS_MSK_IAM.
+    fn create_context(&self) -> Result<KafkaContext> {
+        Ok(KafkaContext::new(self.token_provider()?))
+    }
+
+    fn token_provider(&self) -> Result<Option<OAuthTokenProvider>> {
+        let mechanism = match self.sasl_mechanism.as_ref() {
+            Some(mechanism) => mechanism.to_uppercase(),
+            None => return Ok(None),
+        };
+        let provider = match mechanism.as_str() {
+            "OAUTHBEARER" => match &self.sasl_oauthbearer_token_endpoint_url {
+                Some(token_endpoint_url) => {
+                    let (client_id, client_secret) = match (
+                        &self.sasl_oauthbearer_client_id,
+                        &self.sasl_oauthbearer_client_secret,
+                    ) {
+                        (Some(id), Some(secret)) => (id.clone(), secret.clone()),
+                        _ => {
+                            return Err(anyhow!(
+                                "both `properties.sasl.oauthbearer.client.id` and `properties.sasl.oauthbearer.client.secret` are required with the token endpoint"
+                            ))
+                        }
+                    };
+                    OAuthTokenProvider::ClientCredentials {
+                        token_endpoint_url: token_endpoint_url.clone(),
+                        client_id,
+                        client_secret,
+                        scope: self.sasl_oauthbearer_scope.clone(),
+                    }
+                }
+                None => OAuthTokenProvider::unsecured(
+                    self.sasl_oathbearer_config.as_deref().unwrap_or_default(),
+                )?,
+            },
+            AWS_MSK_IAM_MECHANISM => {
+                let region = self
+                    .aws_region
+                    .clone()
+                    .ok_or_else(|| anyhow!("`aws.region` is required for AWS_MSK_IAM"))?;
+                let credentials = match (&self.aws_access_key_id, &self.aws_secret_access_key) {
+                    (Some(access_key_id), Some(secret_access_key)) => Some(AwsCredentials {
+                        access_key_id: access_key_id.clone(),
+                        secret_access_key: secret_access_key.clone(),
+                        session_token: self.aws_session_token.clone(),
+                    }),
+                    (None, None) => None,
+                    _ => {
+                        return Err(anyhow!(
+                            "both `aws.credentials.access_key_id` and `aws.credentials.secret_access_key` should be provided or not provided at the same time"
+                        ))
+                    }
+                };
+                let assume_role = self.aws_assume_role_arn.as_ref().map(|arn| AwsAssumeRole {
+                    arn: arn.clone(),
+                    external_id: self.aws_assume_role_external_id.clone(),
+                });
+                OAuthTokenProvider::MskIam(AwsConfigInfo {
+                    stream_name: self.topic.clone(),
+                    region: Some(region),
+                    endpoint: None,
+                    credentials,
+                    assume_role,
+                })
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(provider))
     }
 }
 
@@ -16,7 +16,7 @@ use bytes::Bytes;
 use rdkafka::message::BorrowedMessage;
 use rdkafka::Message;
 
-use crate::source::base::SourceMessage;
+use crate::source::base::{SourceMessage, SourceMeta};
 
 impl<'a> From<BorrowedMessage<'a>> for SourceMessage {
     fn from(message: BorrowedMessage<'a>) -> Self {
@@ -25,6 +25,11 @@ impl<'a> From<BorrowedMessage<'a>> for SourceMessage {
             payload: message.payload().map(Bytes::copy_from_slice),
             offset: message.offset().to_string(),
             split_id: message.partition().to_string().into(),
+            meta: SourceMeta {
+                timestamp: message.timestamp().to_millis(),
+                key: message.key().map(Bytes::copy_from_slice),
+                ..Default::default()
+            },
         }
     }
 }
@@ -19,17 +19,18 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use futures::StreamExt;
 use futures_async_stream::try_stream;
+use itertools::Itertools;
 use rdkafka::config::RDKafkaLogLevel;
-use rdkafka::consumer::{Consumer, DefaultConsumerContext, StreamConsumer};
+use rdkafka::consumer::{Consumer, StreamConsumer};
 use rdkafka::{ClientConfig, Offset, TopicPartitionList};
 
 use crate::source::base::{SourceMessage, SplitReader, MAX_CHUNK_SIZE};
 use crate::source::kafka::split::KafkaSplit;
-use crate::source::kafka::KafkaProperties;
+use crate::source::kafka::{KafkaContext, KafkaProperties, KAFKA_SYNC_CALL_TIMEOUT};
 use crate::source::{BoxSourceStream, Column, ConnectorState, SplitImpl};
 
 pub struct KafkaSplitReader {
-    consumer: StreamConsumer<DefaultConsumerContext>,
+    consumer: StreamConsumer<KafkaContext>,
     assigned_splits: HashMap<String, Vec<KafkaSplit>>,
 }
 
@@ -67,26 +68,47 @@ impl SplitReader for KafkaSplitReader {
             );
         }
 
-        let consumer: StreamConsumer = config
+        let consumer: StreamConsumer<KafkaContext> = config
             .set_log_level(RDKafkaLogLevel::Info)
-            .create_with_context(DefaultConsumerContext)
+            .create_with_context(properties.create_context()?)
             .await
             .context("failed to create kafka consumer")?;
 
         if let Some(splits) = state {
-            let mut tpl = TopicPartitionList::with_capacity(splits.len());
-
-            for split in &splits {
-                if let SplitImpl::Kafka(k) = split {
-                    if let Some(offset) = k.start_offset {
-                        tpl.add_partition_offset(
-                            k.topic.as_str(),
-                            k.partition,
-                            Offset::Offset(offset + 1),
-                        )?;
-                    } else {
-                        tpl.add_partition(k.topic.as_str(), k.partition);
+            let kafka_splits = splits
+                .iter()
+                .filter_map(|split| split.as_kafka())
+                .collect_vec();
+            let timestamp_offsets = match &properties.pushdown_timestamp_millis {
+                Some(millis) => {
+                    let millis = millis
+                        .parse()
+                        .context("failed to parse the pushed down timestamp")?;
+                    Self::fetch_offsets_for_time(&consumer, &kafka_splits, millis).await?
+                }
+                None => HashMap::new(),
+            };
+
+            let mut tpl = TopicPartitionList::with_capacity(kafka_splits.len());
+
+            for k in kafka_splits {
+                // Start from the later one of the split offset and the offset of the pushed down
+                // timestamp, as the messages before the latter can't satisfy the predicates.
+                let offset = match (k.start_offset, timestamp_offsets.get(&k.partition)) {
+                    (_, Some(Offset::End)) => Some(Offset::End),
+                    (Some(offset), Some(Offset::Offset(timestamp_offset))) => {
+                        Some(Offset::Offset((offset + 1).max(*timestamp_offset)))
+                    }
+                    (None, Some(Offset::Offset(timestamp_offset))) => {
+                        Some(Offset::Offset(*timestamp_offset))
                     }
+                    (Some(offset), _) => Some(Offset::Offset(offset + 1)),
+                    (None, _) => None,
+                };
+                if let Some(offset) = offset {
+                    tpl.add_partition_offset(k.topic.as_str(), k.partition, offset)?;
+                } else {
+                    tpl.add_partition(k.topic.as_str(), k.partition);
                 }
             }
 
@@ -105,6 +127,35 @@ impl SplitReader for KafkaSplitReader {
 }
 
 impl KafkaSplitReader {
+    /// Fetches the offset of the earliest message whose timestamp is not earlier than the given
+    /// one in each partition, or [`Offset::End`] if there is no such message yet.
+    async fn fetch_offsets_for_time(
+        consumer: &StreamConsumer<KafkaContext>,
+        splits: &[&KafkaSplit],
+        millis: i64,
+    ) -> Result<HashMap<i32, Offset>> {
+        let mut tpl = TopicPartitionList::with_capacity(splits.len());
+        for split in splits {
+            tpl.add_partition_offset(
+                split.topic.as_str(),
+                split.partition,
+                Offset::Offset(millis),
+            )?;
+        }
+        let offsets = consumer
+            .offsets_for_times(tpl, KAFKA_SYNC_CALL_TIMEOUT)
+            .await
+            .context("failed to fetch the offsets for the pushed down timestamp")?;
+        Ok(offsets
+            .elements()
+            .into_iter()
+            .filter_map(|elem| match elem.offset() {
+                offset @ (Offset::Offset(_) | Offset::End) => Some((elem.partition(), offset)),
+                _ => None,
+            })
+            .collect())
+    }
+
     #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
     pub async fn into_stream(self) {
         #[for_await]
@@ -12,22 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::Result;
+use std::collections::HashSet;
+
+use anyhow::{anyhow, bail, Result};
 use async_trait::async_trait;
 use aws_sdk_kinesis::model::Shard;
 use aws_sdk_kinesis::Client as kinesis_client;
+use itertools::Itertools;
 
 use crate::source::kinesis::split::{KinesisOffset, KinesisSplit};
 use crate::source::kinesis::*;
-use crate::source::SplitEnumerator;
+use crate::source::{SplitEnumerator, SplitId};
 
 pub struct KinesisSplitEnumerator {
     stream_name: String,
     client: kinesis_client,
+    start_position: KinesisOffset,
 }
 
-impl KinesisSplitEnumerator {}
-
 #[async_trait]
 impl SplitEnumerator for KinesisSplitEnumerator {
     type Properties = KinesisProperties;
@@ -36,9 +38,29 @@ impl SplitEnumerator for KinesisSplitEnumerator {
     async fn new(properties: KinesisProperties) -> Result<Self> {
         let client = build_client(properties.clone()).await?;
         let stream_name = properties.stream_name.clone();
+
+        let mut start_position = match properties
+            .scan_startup_mode
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            Some("earliest") | None => KinesisOffset::Earliest,
+            Some("latest") => KinesisOffset::Latest,
+            _ => {
+                bail!(
+                    "properties `scan.startup.mode` only support earliest and latest or leave it empty"
+                );
+            }
+        };
+        if let Some(s) = properties.timestamp_offset {
+            let timestamp_offset = s.parse::<i64>().map_err(|e| anyhow!(e))?;
+            start_position = KinesisOffset::Timestamp(timestamp_offset);
+        }
+
         Ok(Self {
             stream_name,
             client,
+            start_position,
         })
     }
 
@@ -69,17 +91,46 @@ impl SplitEnumerator for KinesisSplitEnumerator {
                 None => break,
             }
         }
-        Ok(shard_collect
-            .into_iter()
-            .map(|x| KinesisSplit {
-                shard_id: x.shard_id().unwrap_or_default().to_string().into(),
-                start_position: KinesisOffset::None,
-                end_position: KinesisOffset::None,
-            })
-            .collect())
+        Ok(shards_to_splits(&shard_collect, &self.start_position))
     }
 }
 
+/// Builds the splits of the listed shards, including the closed ones whose records are read to
+/// the end. The lineage is derived from the shards on every listing, so it is kept after meta
+/// restarts.
+///
+/// A shard whose parents are listed is created by resharding. It is read after its parents are
+/// read to the end, from the beginning to not miss any record, or from the timestamp if given.
+/// The other shards are read from `start_position`. With `Latest`, the children of the shards
+/// closed before the source is created are read from the beginning as well, which is preferred to
+/// missing the records of the shards created while meta is down.
+fn shards_to_splits(shards: &[Shard], start_position: &KinesisOffset) -> Vec<KinesisSplit> {
+    let shard_ids: HashSet<&str> = shards.iter().filter_map(|s| s.shard_id()).collect();
+    shards
+        .iter()
+        .filter_map(|shard| {
+            let shard_id = shard.shard_id()?;
+            let parent_shard_ids = [shard.parent_shard_id(), shard.adjacent_parent_shard_id()]
+                .into_iter()
+                .flatten()
+                .filter(|parent| shard_ids.contains(parent))
+                .map(SplitId::from)
+                .collect_vec();
+            let start_position = if parent_shard_ids.is_empty()
+                || matches!(start_position, KinesisOffset::Timestamp(_))
+            {
+                start_position.clone()
+            } else {
+                KinesisOffset::Earliest
+            };
+            Some(
+                KinesisSplit::new(shard_id.into(), start_position, KinesisOffset::None)
+                    .with_parent_shard_ids(parent_shard_ids),
+            )
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use aws_sdk_kinesis::Region;
@@ -98,10 +149,79 @@ mod tests {
         let mut enumerator = KinesisSplitEnumerator {
             stream_name,
             client,
+            start_position: KinesisOffset::Earliest,
         };
         let list_splits_resp = enumerator.list_splits().await?;
         println!("{:#?}", list_splits_resp);
         assert_eq!(list_splits_resp.len(), 4);
         Ok(())
     }
+
+    fn shard(shard_id: &str, parents: &[&str]) -> Shard {
+        Shard::builder()
+            .shard_id(shard_id)
+            .set_parent_shard_id(parents.first().map(|s| s.to_string()))
+            .set_adjacent_parent_shard_id(parents.get(1).map(|s| s.to_string()))
+            .build()
+    }
+
+    #[test]
+    fn test_shards_to_splits() {
+        // Shard 0 is split into 2 and 3, which are then merged into 4. Shard 1 is the child of a
+        // shard expired by retention.
+        let shards = vec![
+            shard("shardId-0", &[]),
+            shard("shardId-1", &["shardId-expired"]),
+            shard("shardId-2", &["shardId-0"]),
+            shard("shardId-3", &["shardId-0"]),
+            shard("shardId-4", &["shardId-2", "shardId-3"]),
+        ];
+        let lineage = |splits: &[KinesisSplit]| {
+            splits
+                .iter()
+                .map(|s| {
+                    (
+                        s.shard_id.to_string(),
+                        s.start_position.clone(),
+                        s.parent_shard_ids
+                            .iter()
+                            .map(|p| p.to_string())
+                            .collect_vec(),
+                    )
+                })
+                .collect_vec()
+        };
+
+        // The lineage only depends on the listed shards, so the children found by the first
+        // listing after a restart are read from the beginning.
+        let splits = shards_to_splits(&shards, &KinesisOffset::Latest);
+        assert_eq!(
+            lineage(&splits),
+            vec![
+                ("shardId-0".to_string(), KinesisOffset::Latest, vec![]),
+                ("shardId-1".to_string(), KinesisOffset::Latest, vec![]),
+                (
+                    "shardId-2".to_string(),
+                    KinesisOffset::Earliest,
+                    vec!["shardId-0".to_string()]
+                ),
+                (
+                    "shardId-3".to_string(),
+                    KinesisOffset::Earliest,
+                    vec!["shardId-0".to_string()]
+                ),
+                (
+                    "shardId-4".to_string(),
+                    KinesisOffset::Earliest,
+                    vec!["shardId-2".to_string(), "shardId-3".to_string()]
+                ),
+            ]
+        );
+
+        let splits = shards_to_splits(&shards, &KinesisOffset::Timestamp(1000));
+        assert!(splits
+            .iter()
+            .all(|s| s.start_position == KinesisOffset::Timestamp(1000)));
+        assert_eq!(splits[4].parent_shard_ids.len(), 2);
+    }
 }
@@ -52,4 +52,21 @@ pub struct KinesisProperties {
         alias = "kinesis.assumerole.external_id"
     )]
     pub assume_role_external_id: Option<String>,
+
+    #[serde(rename = "scan.startup.mode", alias = "kinesis.scan.startup.mode")]
+    pub scan_startup_mode: Option<String>,
+
+    #[serde(
+        rename = "scan.startup.timestamp_millis",
+        alias = "kinesis.scan.startup.timestamp_millis"
+    )]
+    pub timestamp_offset: Option<String>,
+
+    /// The ARN of a registered stream consumer, with which the shards are read by enhanced
+    /// fan-out instead of polling.
+    #[serde(
+        rename = "enhanced_fanout.consumer.arn",
+        alias = "kinesis.enhanced_fanout.consumer.arn"
+    )]
+    pub consumer_arn: Option<String>,
 }
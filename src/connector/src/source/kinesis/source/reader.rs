@@ -14,20 +14,23 @@
 
 use std::time::Duration;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use async_trait::async_trait;
 use aws_sdk_kinesis::error::GetRecordsError;
-use aws_sdk_kinesis::model::ShardIteratorType;
+use aws_sdk_kinesis::model::{ShardIteratorType, StartingPosition, SubscribeToShardEventStream};
 use aws_sdk_kinesis::output::GetRecordsOutput;
 use aws_sdk_kinesis::types::SdkError;
 use aws_sdk_kinesis::Client as KinesisClient;
+use aws_smithy_types::DateTime;
+use futures::StreamExt;
 use futures_async_stream::try_stream;
 
 use crate::source::kinesis::source::message::KinesisMessage;
-use crate::source::kinesis::split::KinesisOffset;
+use crate::source::kinesis::split::{KinesisOffset, KINESIS_SHARD_END_OFFSET};
 use crate::source::kinesis::{build_client, KinesisProperties};
 use crate::source::{
-    BoxSourceStream, Column, ConnectorState, SourceMessage, SplitId, SplitImpl, SplitReader,
+    BoxSourceStream, Column, ConnectorState, SourceMessage, SourceMeta, SplitId, SplitImpl,
+    SplitReader,
 };
 
 #[derive(Debug, Clone)]
@@ -39,6 +42,7 @@ pub struct KinesisSplitReader {
     shard_iter: Option<String>,
     start_position: KinesisOffset,
     end_position: KinesisOffset,
+    consumer_arn: Option<String>,
 }
 
 #[async_trait]
@@ -55,6 +59,7 @@ impl SplitReader for KinesisSplitReader {
             split => return Err(anyhow!("expect KinesisSplit, got {:?}", split)),
         };
         let stream_name = properties.stream_name.clone();
+        let consumer_arn = properties.consumer_arn.clone();
         let client = build_client(properties).await?;
         Ok(Self {
             client,
@@ -64,6 +69,7 @@ impl SplitReader for KinesisSplitReader {
             latest_offset: None,
             start_position: split.start_position,
             end_position: split.end_position,
+            consumer_arn,
         })
     }
 
@@ -73,8 +79,19 @@ impl SplitReader for KinesisSplitReader {
 }
 
 impl KinesisSplitReader {
+    pub fn into_stream(self) -> BoxSourceStream {
+        if self.start_position == KinesisOffset::ShardEnd {
+            tracing::info!("kinesis shard {} is already read to the end", self.shard_id);
+            futures::stream::empty().boxed()
+        } else if self.consumer_arn.is_some() {
+            self.into_fanout_stream()
+        } else {
+            self.into_polling_stream()
+        }
+    }
+
     #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
-    pub async fn into_stream(mut self) {
+    async fn into_polling_stream(mut self) {
         self.new_shard_iter().await?;
         loop {
             match self.get_records().await {
@@ -88,12 +105,19 @@ impl KinesisSplitReader {
                             ))
                         })
                         .collect::<Vec<SourceMessage>>();
-                    if chunk.is_empty() {
+                    if let Some(last) = chunk.last() {
+                        self.latest_offset = Some(last.offset.clone());
+                        yield chunk;
+                    }
+                    if self.shard_iter.is_none() {
+                        // The shard is closed by resharding and all its records are read.
+                        tracing::info!("kinesis shard {} is closed", self.shard_id);
+                        yield vec![self.shard_end_message()];
+                        return Ok(());
+                    }
+                    if resp.records().map_or(true, |records| records.is_empty()) {
                         tokio::time::sleep(Duration::from_millis(200)).await;
-                        continue;
                     }
-                    self.latest_offset = Some(chunk.last().unwrap().offset.clone());
-                    yield chunk;
                 }
                 Err(SdkError::ServiceError { err, .. }) if err.is_expired_iterator_exception() => {
                     self.new_shard_iter().await?;
@@ -105,29 +129,99 @@ impl KinesisSplitReader {
         }
     }
 
-    async fn new_shard_iter(&mut self) -> Result<()> {
-        let (starting_seq_num, iter_type) = if self.latest_offset.is_some() {
-            (
-                self.latest_offset.take(),
-                ShardIteratorType::AfterSequenceNumber,
-            )
+    /// Reads the shard by enhanced fan-out, which pushes the records through subscriptions that
+    /// expire every 5 minutes.
+    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+    async fn into_fanout_stream(mut self) {
+        let consumer_arn = self.consumer_arn.clone().unwrap();
+        loop {
+            let output = self
+                .client
+                .subscribe_to_shard()
+                .consumer_arn(&consumer_arn)
+                .shard_id(self.shard_id.as_ref())
+                .starting_position(self.starting_position()?)
+                .send()
+                .await
+                .map_err(|e| anyhow!(e))?;
+            let mut event_stream = output.event_stream;
+            while let Some(event) = event_stream.recv().await.map_err(|e| anyhow!(e))? {
+                let SubscribeToShardEventStream::SubscribeToShardEvent(event) = event else {
+                    continue;
+                };
+                let chunk = (event.records().unwrap_or_default().iter())
+                    .map(|r| {
+                        SourceMessage::from(KinesisMessage::new(self.shard_id.clone(), r.clone()))
+                    })
+                    .collect::<Vec<SourceMessage>>();
+                // The subscription is renewed from the continuation, which is the sequence number
+                // of the last record of the event.
+                let continuation = event.continuation_sequence_number().map(String::from);
+                if let Some(continuation) = &continuation {
+                    self.latest_offset = Some(continuation.clone());
+                }
+                if !chunk.is_empty() {
+                    yield chunk;
+                }
+                if continuation.is_none() {
+                    // The shard is closed by resharding and all its records are read.
+                    tracing::info!("kinesis shard {} is closed", self.shard_id);
+                    yield vec![self.shard_end_message()];
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// The message without payload telling that the shard is read to the end, which is recorded in
+    /// the state of the split to start reading its children shards.
+    fn shard_end_message(&self) -> SourceMessage {
+        SourceMessage {
+            payload: None,
+            offset: KINESIS_SHARD_END_OFFSET.to_string(),
+            split_id: self.shard_id.clone(),
+            meta: SourceMeta::default(),
+        }
+    }
+
+    /// Returns where to continue reading the shard, which is after the last read record if any.
+    fn starting_position(&self) -> Result<StartingPosition> {
+        let builder = StartingPosition::builder();
+        let builder = if let Some(seq) = &self.latest_offset {
+            builder
+                .r#type(ShardIteratorType::AfterSequenceNumber)
+                .sequence_number(seq)
         } else {
             match &self.start_position {
-                KinesisOffset::Earliest => (None, ShardIteratorType::TrimHorizon),
-                KinesisOffset::SequenceNumber(seq) => {
-                    (Some(seq.clone()), ShardIteratorType::AfterSequenceNumber)
+                KinesisOffset::Earliest => builder.r#type(ShardIteratorType::TrimHorizon),
+                KinesisOffset::Latest => builder.r#type(ShardIteratorType::Latest),
+                KinesisOffset::SequenceNumber(seq) => builder
+                    .r#type(ShardIteratorType::AfterSequenceNumber)
+                    .sequence_number(seq),
+                KinesisOffset::Timestamp(ts) => builder
+                    .r#type(ShardIteratorType::AtTimestamp)
+                    .timestamp(DateTime::from_millis(*ts)),
+                KinesisOffset::None => {
+                    bail!("no start position of kinesis shard {}", self.shard_id)
+                }
+                KinesisOffset::ShardEnd => {
+                    bail!("kinesis shard {} is already read to the end", self.shard_id)
                 }
-                _ => unreachable!(),
             }
         };
+        Ok(builder.build())
+    }
 
+    async fn new_shard_iter(&mut self) -> Result<()> {
+        let position = self.starting_position()?;
         let resp = self
             .client
             .get_shard_iterator()
             .stream_name(self.stream_name.clone())
             .shard_id(self.shard_id.as_ref())
-            .shard_iterator_type(iter_type)
-            .set_starting_sequence_number(starting_seq_num)
+            .set_shard_iterator_type(position.r#type)
+            .set_starting_sequence_number(position.sequence_number)
+            .set_timestamp(position.timestamp)
             .send()
             .await?;
 
@@ -149,8 +243,6 @@ impl KinesisSplitReader {
 
 #[cfg(test)]
 mod tests {
-    use futures::StreamExt;
-
     use super::*;
     use crate::source::kinesis::split::KinesisSplit;
 
@@ -166,6 +258,9 @@ mod tests {
             endpoint: None,
             session_token: None,
             assume_role_external_id: None,
+            scan_startup_mode: None,
+            timestamp_offset: None,
+            consumer_arn: None,
         };
 
         let mut trim_horizen_reader = KinesisSplitReader::new(
@@ -174,6 +269,7 @@ mod tests {
                 shard_id: "shardId-000000000001".to_string().into(),
                 start_position: KinesisOffset::Earliest,
                 end_position: KinesisOffset::None,
+                parent_shard_ids: vec![],
             })]),
             None,
         )
@@ -189,6 +285,7 @@ mod tests {
                     "49629139817504901062972448413535783695568426186596941842".to_string(),
                 ),
                 end_position: KinesisOffset::None,
+                parent_shard_ids: vec![],
             })]),
             None,
         )
@@ -25,13 +25,22 @@ pub enum KinesisOffset {
     SequenceNumber(String),
     Timestamp(i64),
     None,
+    /// All the records of the shard closed by resharding are read.
+    ShardEnd,
 }
 
+/// The offset of the message sent by the reader after reading a closed shard to the end, which
+/// never conflicts with the sequence numbers of the records.
+pub const KINESIS_SHARD_END_OFFSET: &str = "SHARD_END";
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Hash)]
 pub struct KinesisSplit {
     pub(crate) shard_id: SplitId,
     pub(crate) start_position: KinesisOffset,
     pub(crate) end_position: KinesisOffset,
+    /// The parent shards listed along with the shard, which are read to the end before it.
+    #[serde(default)]
+    pub(crate) parent_shard_ids: Vec<SplitId>,
 }
 
 impl SplitMetaData for KinesisSplit {
@@ -46,6 +55,14 @@ impl SplitMetaData for KinesisSplit {
     fn restore_from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
         serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
     }
+
+    fn parent_ids(&self) -> Vec<SplitId> {
+        self.parent_shard_ids.clone()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.start_position == KinesisOffset::ShardEnd
+    }
 }
 
 impl KinesisSplit {
@@ -58,12 +75,20 @@ impl KinesisSplit {
             shard_id,
             start_position,
             end_position,
+            parent_shard_ids: vec![],
         }
     }
 
+    pub fn with_parent_shard_ids(mut self, parent_shard_ids: Vec<SplitId>) -> Self {
+        self.parent_shard_ids = parent_shard_ids;
+        self
+    }
+
     pub fn copy_with_offset(&self, start_offset: String) -> Self {
         let start_offset = if start_offset.is_empty() {
             KinesisOffset::Earliest
+        } else if start_offset == KINESIS_SHARD_END_OFFSET {
+            KinesisOffset::ShardEnd
         } else {
             KinesisOffset::SequenceNumber(start_offset)
         };
@@ -72,5 +97,44 @@ impl KinesisSplit {
             start_offset,
             self.end_position.clone(),
         )
+        .with_parent_shard_ids(self.parent_shard_ids.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shard_end() {
+        let parent_shard_id: SplitId = "shardId-000000000000".into();
+        let split = KinesisSplit::new(
+            "shardId-000000000002".into(),
+            KinesisOffset::Earliest,
+            KinesisOffset::None,
+        )
+        .with_parent_shard_ids(vec![parent_shard_id.clone()]);
+        assert!(!split.is_finished());
+
+        let split = split.copy_with_offset(
+            "49629139817504901062972448413535783695568426186596941842".to_string(),
+        );
+        assert!(!split.is_finished());
+        assert_eq!(split.parent_ids(), vec![parent_shard_id.clone()]);
+
+        let split = split.copy_with_offset(KINESIS_SHARD_END_OFFSET.to_string());
+        assert!(split.is_finished());
+        assert_eq!(split.parent_ids(), vec![parent_shard_id.clone()]);
+
+        // The splits persisted before the lineage is recorded have no parents.
+        let restored = KinesisSplit::restore_from_bytes(
+            br#"{"shard_id":"shard-1","start_position":"Earliest","end_position":"None"}"#,
+        )
+        .unwrap();
+        assert!(restored.parent_ids().is_empty());
+        assert_eq!(
+            KinesisSplit::restore_from_bytes(&split.encode_to_bytes()).unwrap(),
+            split
+        );
     }
 }
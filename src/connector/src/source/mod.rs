@@ -13,16 +13,22 @@
 // limitations under the License.
 
 pub mod base;
+pub mod cdc;
 pub mod datagen;
+pub mod dead_letter;
 pub mod dummy_connector;
 pub mod filesystem;
 pub mod kafka;
 pub mod kinesis;
+pub mod mysql_cdc;
 pub mod nexmark;
+pub mod postgres_cdc;
 pub mod pulsar;
 pub use base::*;
 pub use kafka::KAFKA_CONNECTOR;
 pub use kinesis::KINESIS_CONNECTOR;
+pub use mysql_cdc::MYSQL_CDC_CONNECTOR;
 pub use nexmark::NEXMARK_CONNECTOR;
+pub use postgres_cdc::{fill_postgres_cdc_source_id, POSTGRES_CDC_CONNECTOR};
 
 pub use crate::source::pulsar::PULSAR_CONNECTOR;
@@ -0,0 +1,70 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+
+use crate::source::mysql_cdc::{
+    fetch_binlog_position, validate_binlog_settings, MySqlCdcOffset, MySqlCdcProperties,
+    MySqlCdcSplit, MySqlTableSchema,
+};
+use crate::source::SplitEnumerator;
+
+pub struct MySqlCdcSplitEnumerator {
+    properties: MySqlCdcProperties,
+    split: Option<MySqlCdcSplit>,
+}
+
+#[async_trait]
+impl SplitEnumerator for MySqlCdcSplitEnumerator {
+    type Properties = MySqlCdcProperties;
+    type Split = MySqlCdcSplit;
+
+    async fn new(properties: MySqlCdcProperties) -> anyhow::Result<MySqlCdcSplitEnumerator> {
+        properties.skip_snapshot()?;
+        let mut conn = properties.connect().await?;
+        validate_binlog_settings(&mut conn).await?;
+        MySqlTableSchema::fetch(&mut conn, &properties).await?;
+        conn.disconnect().await?;
+        Ok(Self {
+            properties,
+            split: None,
+        })
+    }
+
+    async fn list_splits(&mut self) -> anyhow::Result<Vec<MySqlCdcSplit>> {
+        if let Some(split) = &self.split {
+            return Ok(vec![split.clone()]);
+        }
+        // Without a snapshot, the binlog is read from the position when the split is first listed,
+        // which is persisted with the split once assigned.
+        let offset = if self.properties.skip_snapshot()? {
+            let mut conn = self.properties.connect().await?;
+            let binlog = fetch_binlog_position(&mut conn).await?;
+            conn.disconnect().await?;
+            Some(MySqlCdcOffset {
+                binlog,
+                snapshot: None,
+            })
+        } else {
+            None
+        };
+        let split = MySqlCdcSplit {
+            database_name: self.properties.database_name.clone(),
+            table_name: self.properties.table_name.clone(),
+            offset,
+        };
+        self.split = Some(split.clone());
+        Ok(vec![split])
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod enumerator;
+pub mod source;
+pub mod split;
+
+use anyhow::{anyhow, Result};
+pub use enumerator::*;
+use itertools::Itertools;
+use mysql_async::prelude::Queryable;
+use mysql_async::{Conn, OptsBuilder};
+use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr};
+pub use split::*;
+
+pub const MYSQL_CDC_CONNECTOR: &str = "mysql-cdc";
+
+const fn default_port() -> u16 {
+    3306
+}
+
+const fn default_snapshot_chunk_size() -> usize {
+    1024
+}
+
+/// The properties of the native MySQL CDC connector, which captures the changes of a single
+/// table. The changes are emitted in the Debezium JSON format, so the source must be created
+/// with `ROW FORMAT DEBEZIUM_JSON` and the primary key of the upstream table.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize)]
+pub struct MySqlCdcProperties {
+    #[serde(rename = "hostname")]
+    pub hostname: String,
+
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(rename = "port", default = "default_port")]
+    pub port: u16,
+
+    #[serde(rename = "username")]
+    pub username: String,
+
+    #[serde(rename = "password", default)]
+    pub password: String,
+
+    #[serde(rename = "database.name")]
+    pub database_name: String,
+
+    #[serde(rename = "table.name")]
+    pub table_name: String,
+
+    /// The server id used to register as a replica for reading the binlog, which must be unique
+    /// among all the replicas of the MySQL server. Defaults to a random one.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(rename = "server.id", default)]
+    pub server_id: Option<u32>,
+
+    /// `initial` takes a snapshot of the table before reading the binlog, while `latest` only
+    /// reads the changes made after the source is created. Defaults to `initial`.
+    #[serde(rename = "scan.startup.mode")]
+    pub scan_startup_mode: Option<String>,
+
+    /// The number of rows read in each query of the snapshot.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(
+        rename = "snapshot.chunk.size",
+        default = "default_snapshot_chunk_size"
+    )]
+    pub snapshot_chunk_size: usize,
+}
+
+impl MySqlCdcProperties {
+    pub async fn connect(&self) -> Result<Conn> {
+        let opts = OptsBuilder::default()
+            .ip_or_hostname(self.hostname.clone())
+            .tcp_port(self.port)
+            .user(Some(self.username.clone()))
+            .pass(Some(self.password.clone()))
+            .db_name(Some(self.database_name.clone()));
+        Conn::new(opts).await.map_err(|e| {
+            anyhow!(
+                "failed to connect to mysql {}:{}: {}",
+                self.hostname,
+                self.port,
+                e
+            )
+        })
+    }
+
+    /// Whether to skip the snapshot and only read the new changes.
+    pub fn skip_snapshot(&self) -> Result<bool> {
+        match self
+            .scan_startup_mode
+            .as_ref()
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            Some("initial") | None => Ok(false),
+            Some("latest") => Ok(true),
+            _ => Err(anyhow!(
+                "properties `scan.startup.mode` only support initial and latest or leave it empty"
+            )),
+        }
+    }
+
+    /// The quoted name of the captured table.
+    pub fn quoted_table_name(&self) -> String {
+        format!(
+            "{}.{}",
+            quote_identifier(&self.database_name),
+            quote_identifier(&self.table_name)
+        )
+    }
+}
+
+pub(crate) fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// The schema of the captured table in the upstream.
+#[derive(Clone, Debug)]
+pub struct MySqlTableSchema {
+    /// All the columns in the order of their ordinal positions, which is also the order of the
+    /// values in the binlog rows.
+    pub columns: Vec<String>,
+    /// The indices of the primary key columns in `columns`.
+    pub pk_indices: Vec<usize>,
+}
+
+impl MySqlTableSchema {
+    pub async fn fetch(conn: &mut Conn, properties: &MySqlCdcProperties) -> Result<Self> {
+        let columns: Vec<String> = conn
+            .exec(
+                "SELECT COLUMN_NAME FROM information_schema.COLUMNS \
+                 WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION",
+                (&properties.database_name, &properties.table_name),
+            )
+            .await?;
+        if columns.is_empty() {
+            return Err(anyhow!(
+                "table {} does not exist in mysql",
+                properties.quoted_table_name()
+            ));
+        }
+        let pk_columns: Vec<String> = conn
+            .exec(
+                "SELECT COLUMN_NAME FROM information_schema.KEY_COLUMN_USAGE \
+                 WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND CONSTRAINT_NAME = 'PRIMARY' \
+                 ORDER BY ORDINAL_POSITION",
+                (&properties.database_name, &properties.table_name),
+            )
+            .await?;
+        if pk_columns.is_empty() {
+            return Err(anyhow!(
+                "table {} must have a primary key to be captured",
+                properties.quoted_table_name()
+            ));
+        }
+        let pk_indices = pk_columns
+            .iter()
+            .map(|pk| columns.iter().position(|c| c == pk).unwrap())
+            .collect_vec();
+        Ok(Self {
+            columns,
+            pk_indices,
+        })
+    }
+}
+
+/// Checks that the binlog of the server is enabled and contains the full rows, which are
+/// required to emit the changes with both the before and after images.
+pub async fn validate_binlog_settings(conn: &mut Conn) -> Result<()> {
+    let settings: Option<(String, String, String)> = conn
+        .query_first("SELECT @@log_bin, @@binlog_format, @@binlog_row_image")
+        .await?;
+    let (log_bin, format, row_image) =
+        settings.ok_or_else(|| anyhow!("failed to query the binlog settings"))?;
+    if log_bin != "1" && !log_bin.eq_ignore_ascii_case("ON") {
+        return Err(anyhow!("the binlog of mysql is not enabled"));
+    }
+    if !format.eq_ignore_ascii_case("ROW") {
+        return Err(anyhow!(
+            "`binlog_format` of mysql must be ROW, but got {}",
+            format
+        ));
+    }
+    if !row_image.eq_ignore_ascii_case("FULL") {
+        return Err(anyhow!(
+            "`binlog_row_image` of mysql must be FULL, but got {}",
+            row_image
+        ));
+    }
+    Ok(())
+}
+
+/// Fetches the current position of the binlog.
+pub async fn fetch_binlog_position(conn: &mut Conn) -> Result<BinlogPosition> {
+    let status: Option<mysql_async::Row> = conn.query_first("SHOW MASTER STATUS").await?;
+    let mut status = status.ok_or_else(|| anyhow!("the binlog of mysql is not enabled"))?;
+    let filename: String = status
+        .take("File")
+        .ok_or_else(|| anyhow!("no binlog file in master status"))?;
+    let position: u64 = status
+        .take("Position")
+        .ok_or_else(|| anyhow!("no binlog position in master status"))?;
+    Ok(BinlogPosition { filename, position })
+}
@@ -0,0 +1,321 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDate, NaiveDateTime};
+use mysql_async::binlog::value::BinlogValue;
+use mysql_async::Value;
+use serde_json::{Map, Number, Value as JsonValue};
+
+use crate::source::cdc::{
+    debezium_event, DEBEZIUM_CREATE_OP, DEBEZIUM_DELETE_OP, DEBEZIUM_READ_OP, DEBEZIUM_UPDATE_OP,
+};
+use crate::source::mysql_cdc::MySqlTableSchema;
+use crate::source::{DataType, SourceMessage, SourceMeta, SplitId};
+
+/// Builds the change events of the captured table in the Debezium JSON format.
+pub struct ChangeEventBuilder {
+    split_id: SplitId,
+    schema: MySqlTableSchema,
+    /// The types of the columns in RisingWave, by which the values are converted. The values of
+    /// the columns not in the source are converted by their types in MySQL.
+    column_types: HashMap<String, DataType>,
+}
+
+impl ChangeEventBuilder {
+    pub fn new(
+        split_id: SplitId,
+        schema: MySqlTableSchema,
+        column_types: HashMap<String, DataType>,
+    ) -> Self {
+        Self {
+            split_id,
+            schema,
+            column_types,
+        }
+    }
+
+    pub fn schema(&self) -> &MySqlTableSchema {
+        &self.schema
+    }
+
+    /// Converts the values of a row, in the order of the columns in MySQL, to a JSON object.
+    pub fn row_to_json(&self, values: Vec<Value>) -> Result<JsonValue> {
+        if values.len() != self.schema.columns.len() {
+            return Err(anyhow!(
+                "expect {} columns in the row, but got {}. The schema of the table may have \
+                 changed, which is not supported yet",
+                self.schema.columns.len(),
+                values.len()
+            ));
+        }
+        let mut row = Map::with_capacity(values.len());
+        for (name, value) in self.schema.columns.iter().zip(values) {
+            let value = mysql_value_to_json(value, self.column_types.get(name))?;
+            row.insert(name.clone(), value);
+        }
+        Ok(JsonValue::Object(row))
+    }
+
+    /// Builds the event of a row read by the snapshot.
+    pub fn build_read(&self, values: Vec<Value>, offset: String) -> Result<SourceMessage> {
+        let after = self.row_to_json(values)?;
+        Ok(self.build(DEBEZIUM_READ_OP, JsonValue::Null, after, offset))
+    }
+
+    /// Builds the event of a row changed in the binlog, where an insert has no before image and a
+    /// delete has no after image.
+    pub fn build_change(
+        &self,
+        before: Option<Vec<Value>>,
+        after: Option<Vec<Value>>,
+        offset: String,
+    ) -> Result<SourceMessage> {
+        let op = match (&before, &after) {
+            (None, Some(_)) => DEBEZIUM_CREATE_OP,
+            (Some(_), Some(_)) => DEBEZIUM_UPDATE_OP,
+            (Some(_), None) => DEBEZIUM_DELETE_OP,
+            (None, None) => return Err(anyhow!("no row image in the rows event")),
+        };
+        let before = before.map_or(Ok(JsonValue::Null), |v| self.row_to_json(v))?;
+        let after = after.map_or(Ok(JsonValue::Null), |v| self.row_to_json(v))?;
+        Ok(self.build(op, before, after, offset))
+    }
+
+    fn build(
+        &self,
+        op: &str,
+        before: JsonValue,
+        after: JsonValue,
+        offset: String,
+    ) -> SourceMessage {
+        SourceMessage {
+            payload: Some(debezium_event(op, before, after)),
+            offset,
+            split_id: self.split_id.clone(),
+            meta: SourceMeta::default(),
+        }
+    }
+}
+
+/// Converts a value in the binlog rows to a plain value. JSON values are converted to their
+/// textual form.
+pub fn binlog_value_to_value(value: BinlogValue<'static>) -> Result<Value> {
+    match value {
+        BinlogValue::Value(value) => Ok(value),
+        BinlogValue::Jsonb(value) => {
+            let json = JsonValue::try_from(value)?;
+            Ok(Value::Bytes(json.to_string().into_bytes()))
+        }
+        BinlogValue::JsonDiff(_) => Err(anyhow!(
+            "partial JSON updates are not supported, please set `binlog_row_value_options` to \
+             empty"
+        )),
+    }
+}
+
+/// Converts a value of MySQL to the JSON value accepted by the Debezium JSON parser for the given
+/// type.
+pub fn mysql_value_to_json(value: Value, data_type: Option<&DataType>) -> Result<JsonValue> {
+    let json = match value {
+        Value::NULL => JsonValue::Null,
+        Value::Int(v) => match data_type {
+            Some(DataType::Boolean) => JsonValue::Bool(v != 0),
+            _ => v.into(),
+        },
+        Value::UInt(v) => match data_type {
+            Some(DataType::Boolean) => JsonValue::Bool(v != 0),
+            _ => v.into(),
+        },
+        Value::Float(v) => float_to_json(v as f64),
+        Value::Double(v) => float_to_json(v),
+        Value::Date(year, month, day, hour, minute, second, micros) => {
+            // The zero dates of MySQL can't be represented, so they are converted to null.
+            match NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32).and_then(|date| {
+                date.and_hms_micro_opt(hour as u32, minute as u32, second as u32, micros)
+            }) {
+                Some(datetime) => datetime_to_json(datetime, data_type),
+                None => JsonValue::Null,
+            }
+        }
+        Value::Time(negative, days, hours, minutes, seconds, micros) => JsonValue::String(format!(
+            "{}{:02}:{:02}:{:02}.{:06}",
+            if negative { "-" } else { "" },
+            days * 24 + hours as u32,
+            minutes,
+            seconds,
+            micros
+        )),
+        Value::Bytes(bytes) => bytes_to_json(bytes, data_type)?,
+    };
+    Ok(json)
+}
+
+fn float_to_json(v: f64) -> JsonValue {
+    Number::from_f64(v).map_or(JsonValue::Null, JsonValue::Number)
+}
+
+fn datetime_to_json(datetime: NaiveDateTime, data_type: Option<&DataType>) -> JsonValue {
+    let s = match data_type {
+        Some(DataType::Date) => datetime.format("%Y-%m-%d").to_string(),
+        // The timestamps are read in UTC.
+        Some(DataType::Timestampz) => datetime.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string(),
+        _ => datetime.format("%Y-%m-%d %H:%M:%S%.6f").to_string(),
+    };
+    JsonValue::String(s)
+}
+
+fn bytes_to_json(bytes: Vec<u8>, data_type: Option<&DataType>) -> Result<JsonValue> {
+    let s = String::from_utf8_lossy(&bytes);
+    let parse_number = || -> Result<JsonValue> {
+        if let Ok(v) = s.parse::<i64>() {
+            Ok(v.into())
+        } else {
+            let v = s
+                .parse::<f64>()
+                .map_err(|e| anyhow!("failed to parse number \"{}\": {}", s, e))?;
+            Ok(float_to_json(v))
+        }
+    };
+    let json = match data_type {
+        Some(
+            DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal,
+        ) => parse_number()?,
+        Some(DataType::Boolean) => JsonValue::Bool(
+            s.parse::<i64>()
+                .map_err(|e| anyhow!("failed to parse boolean \"{}\": {}", s, e))?
+                != 0,
+        ),
+        Some(DataType::Jsonb) => serde_json::from_slice(&bytes)?,
+        // The timestamps in the binlog are the seconds since the epoch.
+        Some(DataType::Timestamp | DataType::Timestampz) if !s.contains('-') => {
+            let secs = s
+                .parse::<f64>()
+                .map_err(|e| anyhow!("failed to parse timestamp \"{}\": {}", s, e))?;
+            let datetime = NaiveDateTime::from_timestamp_opt(
+                secs.trunc() as i64,
+                (secs.fract() * 1e9).round() as u32,
+            )
+            .ok_or_else(|| anyhow!("timestamp out of range: {}", s))?;
+            datetime_to_json(datetime, data_type)
+        }
+        _ => JsonValue::String(s.into_owned()),
+    };
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_mysql_value_to_json() {
+        assert_eq!(
+            mysql_value_to_json(Value::Int(1), Some(&DataType::Boolean)).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            mysql_value_to_json(Value::Int(-3), Some(&DataType::Int32)).unwrap(),
+            json!(-3)
+        );
+        assert_eq!(
+            mysql_value_to_json(Value::Bytes(b"12.5".to_vec()), Some(&DataType::Decimal)).unwrap(),
+            json!(12.5)
+        );
+        assert_eq!(
+            mysql_value_to_json(Value::Bytes(b"abc".to_vec()), Some(&DataType::Varchar)).unwrap(),
+            json!("abc")
+        );
+        assert_eq!(
+            mysql_value_to_json(Value::Date(2022, 10, 1, 0, 0, 0, 0), Some(&DataType::Date))
+                .unwrap(),
+            json!("2022-10-01")
+        );
+        assert_eq!(
+            mysql_value_to_json(
+                Value::Date(2022, 10, 1, 12, 30, 15, 500),
+                Some(&DataType::Timestamp)
+            )
+            .unwrap(),
+            json!("2022-10-01 12:30:15.000500")
+        );
+        assert_eq!(
+            mysql_value_to_json(
+                Value::Bytes(b"1664627415".to_vec()),
+                Some(&DataType::Timestampz)
+            )
+            .unwrap(),
+            json!("2022-10-01T12:30:15.000000Z")
+        );
+        assert_eq!(
+            mysql_value_to_json(Value::Date(0, 0, 0, 0, 0, 0, 0), Some(&DataType::Date)).unwrap(),
+            JsonValue::Null
+        );
+        assert_eq!(
+            mysql_value_to_json(Value::Time(false, 1, 2, 3, 4, 0), Some(&DataType::Time)).unwrap(),
+            json!("26:03:04.000000")
+        );
+        assert_eq!(
+            mysql_value_to_json(Value::Bytes(br#"{"a":1}"#.to_vec()), Some(&DataType::Jsonb))
+                .unwrap(),
+            json!({"a": 1})
+        );
+    }
+
+    #[test]
+    fn test_build_change() {
+        let schema = MySqlTableSchema {
+            columns: vec!["id".to_string(), "name".to_string()],
+            pk_indices: vec![0],
+        };
+        let column_types = HashMap::from([
+            ("id".to_string(), DataType::Int32),
+            ("name".to_string(), DataType::Varchar),
+        ]);
+        let builder = ChangeEventBuilder::new("db.t".into(), schema, column_types);
+
+        let msg = builder
+            .build_change(
+                Some(vec![Value::Int(1), Value::Bytes(b"a".to_vec())]),
+                Some(vec![Value::Int(1), Value::Bytes(b"b".to_vec())]),
+                "offset".to_string(),
+            )
+            .unwrap();
+        let event: JsonValue = serde_json::from_slice(msg.payload.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            event,
+            json!({
+                "payload": {
+                    "before": {"id": 1, "name": "a"},
+                    "after": {"id": 1, "name": "b"},
+                    "op": "u",
+                }
+            })
+        );
+        assert_eq!(msg.offset, "offset");
+
+        assert!(builder
+            .build_change(Some(vec![Value::Int(1)]), None, "offset".to_string())
+            .is_err());
+    }
+}
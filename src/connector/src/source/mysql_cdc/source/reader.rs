@@ -0,0 +1,295 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, ensure, Result};
+use async_trait::async_trait;
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use mysql_async::binlog::events::EventData;
+use mysql_async::prelude::Queryable;
+use mysql_async::{BinlogRequest, Conn, Row, Value};
+use rand::Rng;
+use risingwave_common::try_match_expand;
+
+use crate::source::mysql_cdc::source::message::{binlog_value_to_value, ChangeEventBuilder};
+use crate::source::mysql_cdc::{
+    fetch_binlog_position, quote_identifier, BinlogPosition, MySqlCdcOffset, MySqlCdcProperties,
+    MySqlCdcSplit, MySqlTableSchema, SnapshotProgress,
+};
+use crate::source::{
+    BoxSourceStream, Column, ConnectorState, SourceMessage, SplitImpl, SplitMetaData, SplitReader,
+    MAX_CHUNK_SIZE,
+};
+
+/// Reads the changes of a MySQL table. Unless skipped, a snapshot of the table is read first,
+/// which is taken in a transaction started while the writes to the table are blocked, so that it
+/// is consistent with the binlog position at that moment. Then the binlog is read from the
+/// position.
+///
+/// The progress is carried by the offsets of the messages. If the reader is recovered in the
+/// middle of a snapshot, the rest rows are read in a new snapshot, while the binlog is still read
+/// from the position of the first one, so the changes in between may be applied more than once.
+pub struct MySqlCdcSplitReader {
+    properties: MySqlCdcProperties,
+    split: MySqlCdcSplit,
+    builder: ChangeEventBuilder,
+}
+
+#[async_trait]
+impl SplitReader for MySqlCdcSplitReader {
+    type Properties = MySqlCdcProperties;
+
+    async fn new(
+        properties: MySqlCdcProperties,
+        state: ConnectorState,
+        columns: Option<Vec<Column>>,
+    ) -> Result<Self> {
+        let splits = state.ok_or_else(|| anyhow!("no default state for reader"))?;
+        ensure!(splits.len() == 1, "only support single split");
+        let split = try_match_expand!(splits.into_iter().next().unwrap(), SplitImpl::MySqlCdc)?;
+
+        let mut conn = properties.connect().await?;
+        let schema = MySqlTableSchema::fetch(&mut conn, &properties).await?;
+        conn.disconnect().await?;
+
+        let column_types = columns
+            .unwrap_or_default()
+            .into_iter()
+            .map(|c| (c.name, c.data_type))
+            .collect();
+        let builder = ChangeEventBuilder::new(split.id(), schema, column_types);
+
+        Ok(Self {
+            properties,
+            split,
+            builder,
+        })
+    }
+
+    fn into_stream(self) -> BoxSourceStream {
+        self.into_stream()
+    }
+}
+
+impl MySqlCdcSplitReader {
+    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+    pub async fn into_stream(self) {
+        let binlog = match self.split.offset.clone() {
+            Some(MySqlCdcOffset {
+                binlog,
+                snapshot: None,
+            }) => binlog,
+            offset => {
+                let (mut conn, position) = self.begin_snapshot().await?;
+                // Resume from the position of the first snapshot, so that no change is missed.
+                let (binlog, mut last_pk) = match offset {
+                    Some(MySqlCdcOffset {
+                        binlog,
+                        snapshot: Some(SnapshotProgress { last_pk }),
+                    }) => (binlog, last_pk),
+                    _ => (position, None),
+                };
+                tracing::info!(
+                    "reading snapshot of mysql table {}, binlog position {:?}",
+                    self.properties.quoted_table_name(),
+                    binlog
+                );
+
+                let mut chunk = self.read_snapshot_chunk(&mut conn, &last_pk).await?;
+                while !chunk.is_empty() {
+                    last_pk = Some(self.pk_literals(chunk.last().unwrap()));
+                    let next_chunk = self.read_snapshot_chunk(&mut conn, &last_pk).await?;
+                    let mut offset = MySqlCdcOffset {
+                        binlog: binlog.clone(),
+                        snapshot: Some(SnapshotProgress {
+                            last_pk: last_pk.clone(),
+                        }),
+                    };
+                    let len = chunk.len();
+                    let mut msgs = Vec::with_capacity(len);
+                    for (i, row) in chunk.into_iter().enumerate() {
+                        // The last row of the snapshot finishes it.
+                        if i == len - 1 && next_chunk.is_empty() {
+                            offset.snapshot = None;
+                        }
+                        msgs.push(self.builder.build_read(row.unwrap(), offset.encode())?);
+                    }
+                    yield msgs;
+                    chunk = next_chunk;
+                }
+                conn.query_drop("COMMIT").await?;
+                conn.disconnect().await?;
+                binlog
+            }
+        };
+
+        #[for_await]
+        for msgs in self.read_binlog(binlog) {
+            yield msgs?;
+        }
+    }
+
+    /// Starts a transaction with a consistent snapshot, and returns the binlog position of it.
+    async fn begin_snapshot(&self) -> Result<(Conn, BinlogPosition)> {
+        let mut lock_conn = self.properties.connect().await?;
+        let mut conn = self.properties.connect().await?;
+        conn.query_drop("SET SESSION time_zone = '+00:00'").await?;
+        conn.query_drop("SET SESSION TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .await?;
+
+        // Block the writes to the table, so that no change of it is committed between the
+        // snapshot and the binlog position.
+        lock_conn
+            .query_drop(format!(
+                "FLUSH TABLES {} WITH READ LOCK",
+                self.properties.quoted_table_name()
+            ))
+            .await?;
+        let position = async {
+            conn.query_drop("START TRANSACTION WITH CONSISTENT SNAPSHOT, READ ONLY")
+                .await?;
+            fetch_binlog_position(&mut conn).await
+        }
+        .await;
+        lock_conn.query_drop("UNLOCK TABLES").await?;
+        lock_conn.disconnect().await?;
+
+        Ok((conn, position?))
+    }
+
+    /// Reads the rows after `last_pk` in the order of the primary key.
+    async fn read_snapshot_chunk(
+        &self,
+        conn: &mut Conn,
+        last_pk: &Option<Vec<String>>,
+    ) -> Result<Vec<Row>> {
+        let schema = self.builder.schema();
+        let columns = schema
+            .columns
+            .iter()
+            .map(|c| quote_identifier(c))
+            .join(", ");
+        let pk = schema
+            .pk_indices
+            .iter()
+            .map(|i| quote_identifier(&schema.columns[*i]))
+            .join(", ");
+        let filter = match last_pk {
+            Some(last_pk) => format!("WHERE ({}) > ({})", pk, last_pk.join(", ")),
+            None => "".to_string(),
+        };
+        let query = format!(
+            "SELECT {} FROM {} {} ORDER BY {} LIMIT {}",
+            columns,
+            self.properties.quoted_table_name(),
+            filter,
+            pk,
+            self.properties.snapshot_chunk_size
+        );
+        // Use the binary protocol to get the typed values.
+        let rows: Vec<Row> = conn.exec(query, ()).await?;
+        Ok(rows)
+    }
+
+    fn pk_literals(&self, row: &Row) -> Vec<String> {
+        self.builder
+            .schema()
+            .pk_indices
+            .iter()
+            .map(|i| row.as_ref(*i).unwrap_or(&Value::NULL).as_sql(false))
+            .collect()
+    }
+
+    /// Reads the changes of the table from the binlog. The changes of a transaction are emitted
+    /// together when it's committed, with the position after it as the offset.
+    #[try_stream(ok = Vec<SourceMessage>, error = anyhow::Error)]
+    async fn read_binlog(&self, start: BinlogPosition) {
+        let server_id = self
+            .properties
+            .server_id
+            .unwrap_or_else(|| rand::thread_rng().gen_range(5400..6400));
+        tracing::info!(
+            "reading binlog of mysql table {} from {:?} as server {}",
+            self.properties.quoted_table_name(),
+            start,
+            server_id
+        );
+        let conn = self.properties.connect().await?;
+        let request = BinlogRequest::new(server_id)
+            .with_filename(start.filename.as_bytes())
+            .with_pos(start.position);
+        let mut stream = conn.get_binlog_stream(request).await?;
+
+        let mut filename = start.filename.clone();
+        let mut changes = vec![];
+        while let Some(event) = stream.next().await {
+            let event = event?;
+            let committed = match event.read_data()? {
+                Some(EventData::RotateEvent(e)) => {
+                    filename = e.name().into_owned();
+                    false
+                }
+                Some(EventData::RowsEvent(rows_event)) => {
+                    let tme = stream.get_tme(rows_event.table_id()).ok_or_else(|| {
+                        anyhow!("no table map event for table {}", rows_event.table_id())
+                    })?;
+                    if tme.database_name() != self.properties.database_name
+                        || tme.table_name() != self.properties.table_name
+                    {
+                        continue;
+                    }
+                    for row in rows_event.rows(tme) {
+                        let (before, after) = row?;
+                        let to_values = |row: mysql_async::binlog::row::BinlogRow| {
+                            row.unwrap()
+                                .into_iter()
+                                .map(binlog_value_to_value)
+                                .collect::<Result<Vec<_>>>()
+                        };
+                        changes.push((
+                            before.map(to_values).transpose()?,
+                            after.map(to_values).transpose()?,
+                        ));
+                    }
+                    false
+                }
+                Some(EventData::XidEvent(_)) => true,
+                // The transactions on non-transactional tables end with a `COMMIT` query.
+                Some(EventData::QueryEvent(e)) => e.query_raw() == b"COMMIT",
+                _ => false,
+            };
+
+            if committed && !changes.is_empty() {
+                let offset = MySqlCdcOffset {
+                    binlog: BinlogPosition {
+                        filename: filename.clone(),
+                        position: event.header().log_pos() as u64,
+                    },
+                    snapshot: None,
+                }
+                .encode();
+                let mut msgs = changes
+                    .drain(..)
+                    .map(|(before, after)| self.builder.build_change(before, after, offset.clone()))
+                    .collect::<Result<Vec<_>>>()?;
+                while msgs.len() > MAX_CHUNK_SIZE {
+                    let rest = msgs.split_off(MAX_CHUNK_SIZE);
+                    yield std::mem::replace(&mut msgs, rest);
+                }
+                yield msgs;
+            }
+        }
+    }
+}
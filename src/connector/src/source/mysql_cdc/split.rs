@@ -0,0 +1,117 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::source::{SplitId, SplitMetaData};
+
+/// A position in the binlog of the MySQL server.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct BinlogPosition {
+    pub filename: String,
+    pub position: u64,
+}
+
+/// The progress of capturing a table, which is carried by the offset of every message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MySqlCdcOffset {
+    /// The position from which the binlog is read. While snapshotting, it's the position at which
+    /// the snapshot is taken.
+    pub binlog: BinlogPosition,
+
+    /// The snapshot progress, or `None` once the snapshot is finished.
+    pub snapshot: Option<SnapshotProgress>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SnapshotProgress {
+    /// The primary key of the last row read by the snapshot, as SQL literals.
+    pub last_pk: Option<Vec<String>>,
+}
+
+impl MySqlCdcOffset {
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn decode(offset: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(offset).map_err(|e| anyhow!("illegal mysql cdc offset: {}", e))
+    }
+}
+
+/// The split of a captured table. There is only one split for each table, as the binlog can't be
+/// read in parallel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct MySqlCdcSplit {
+    pub database_name: String,
+    pub table_name: String,
+    /// The progress of capturing, or `None` if nothing has been read.
+    pub offset: Option<MySqlCdcOffset>,
+}
+
+impl MySqlCdcSplit {
+    pub fn copy_with_offset(&self, start_offset: String) -> Self {
+        Self {
+            database_name: self.database_name.clone(),
+            table_name: self.table_name.clone(),
+            offset: Some(MySqlCdcOffset::decode(&start_offset).unwrap()),
+        }
+    }
+}
+
+impl SplitMetaData for MySqlCdcSplit {
+    fn id(&self) -> SplitId {
+        format!("{}.{}", self.database_name, self.table_name).into()
+    }
+
+    fn encode_to_bytes(&self) -> Bytes {
+        Bytes::from(serde_json::to_string(self).unwrap())
+    }
+
+    fn restore_from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SplitImpl;
+
+    #[test]
+    fn test_split_with_offset() {
+        let split = MySqlCdcSplit {
+            database_name: "db".to_string(),
+            table_name: "t".to_string(),
+            offset: None,
+        };
+        assert_eq!(split.id().as_ref(), "db.t");
+
+        let offset = MySqlCdcOffset {
+            binlog: BinlogPosition {
+                filename: "mysql-bin.000003".to_string(),
+                position: 154,
+            },
+            snapshot: Some(SnapshotProgress {
+                last_pk: Some(vec!["42".to_string(), "'a'".to_string()]),
+            }),
+        };
+        let split = SplitImpl::MySqlCdc(split).update(offset.encode());
+        let restored = SplitImpl::restore_from_bytes(&split.encode_to_bytes()).unwrap();
+        assert_eq!(split, restored);
+        assert_eq!(restored.into_my_sql_cdc().unwrap().offset, Some(offset));
+    }
+}
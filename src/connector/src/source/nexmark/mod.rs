@@ -202,6 +202,8 @@ pub struct NexmarkPropertiesInner {
     #[serde(rename = "nexmark.first.event.rate", default = "none")]
     pub first_event_rate: Option<usize>,
 
+    /// The overall rate of Bid + Auction + Person events across all splits, which overrides
+    /// `nexmark.min.event.gap.in.ns` and is the default of `nexmark.first.event.rate`
     #[serde_as(as = "Option<DisplayFromStr>")]
     #[serde(rename = "nexmark.events.per.sec", default = "none")]
     pub events_per_sec: Option<usize>,
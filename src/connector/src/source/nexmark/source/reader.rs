@@ -59,7 +59,13 @@ impl SplitReader for NexmarkSplitReader {
         let use_real_time = properties.use_real_time;
         let mut min_event_gap_in_ns = 0;
         if !use_real_time {
-            min_event_gap_in_ns = properties.min_event_gap_in_ns;
+            min_event_gap_in_ns = match properties.events_per_sec {
+                // Every split goes through all of the events, so the gap between two events of the
+                // whole stream is also the one of each split.
+                Some(0) => return Err(anyhow!("nexmark.events.per.sec must be positive")),
+                Some(events_per_sec) => 1_000_000_000 / events_per_sec as u64,
+                None => properties.min_event_gap_in_ns,
+            };
         }
 
         let max_chunk_size = properties.max_chunk_size;
@@ -146,4 +152,25 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_nexmark_events_per_sec() -> Result<()> {
+        let props = Box::new(NexmarkPropertiesInner {
+            table_type: "Bid".to_string(),
+            events_per_sec: Some(2000),
+            ..Default::default()
+        });
+        let reader = NexmarkSplitReader::new(props, None, None).await?;
+        assert_eq!(reader.generator.min_event_gap_in_ns, 500_000);
+        assert_eq!(reader.generator.config.inter_event_delays, vec![500.0]);
+
+        let props = Box::new(NexmarkPropertiesInner {
+            table_type: "Bid".to_string(),
+            events_per_sec: Some(0),
+            ..Default::default()
+        });
+        assert!(NexmarkSplitReader::new(props, None, None).await.is_err());
+
+        Ok(())
+    }
 }
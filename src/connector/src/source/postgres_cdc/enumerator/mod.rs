@@ -0,0 +1,67 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+
+use crate::source::postgres_cdc::{
+    validate_replication_settings, PostgresCdcOffset, PostgresCdcProperties, PostgresCdcSplit,
+    PostgresTableSchema,
+};
+use crate::source::SplitEnumerator;
+
+pub struct PostgresCdcSplitEnumerator {
+    properties: PostgresCdcProperties,
+    split: Option<PostgresCdcSplit>,
+}
+
+#[async_trait]
+impl SplitEnumerator for PostgresCdcSplitEnumerator {
+    type Properties = PostgresCdcProperties;
+    type Split = PostgresCdcSplit;
+
+    async fn new(properties: PostgresCdcProperties) -> anyhow::Result<PostgresCdcSplitEnumerator> {
+        properties.skip_snapshot()?;
+        let client = properties.connect().await?;
+        validate_replication_settings(&client, &properties).await?;
+        PostgresTableSchema::fetch(&client, &properties).await?;
+        // The slot retains the changes from now on, which is dropped along with the source.
+        properties.create_replication(&client).await?;
+        Ok(Self {
+            properties,
+            split: None,
+        })
+    }
+
+    async fn list_splits(&mut self) -> anyhow::Result<Vec<PostgresCdcSplit>> {
+        if let Some(split) = &self.split {
+            return Ok(vec![split.clone()]);
+        }
+        // Without a snapshot, all the changes retained by the slot are read.
+        let offset = if self.properties.skip_snapshot()? {
+            Some(PostgresCdcOffset {
+                lsn: 0,
+                snapshot: None,
+            })
+        } else {
+            None
+        };
+        let split = PostgresCdcSplit {
+            schema_name: self.properties.schema_name.clone(),
+            table_name: self.properties.table_name.clone(),
+            offset,
+        };
+        self.split = Some(split.clone());
+        Ok(vec![split])
+    }
+}
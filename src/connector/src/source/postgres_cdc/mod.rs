@@ -0,0 +1,495 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod enumerator;
+pub mod source;
+pub mod split;
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+pub use enumerator::*;
+use itertools::Itertools;
+use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr};
+pub use split::*;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::{Client, NoTls};
+
+pub const POSTGRES_CDC_CONNECTOR: &str = "postgres-cdc";
+
+/// The property filled by meta with the id of the source, which is part of the default names of
+/// the replication slot and the publication.
+pub const POSTGRES_CDC_SOURCE_ID_KEY: &str = "source.id";
+
+/// Fills the id of the source into its properties if it's a postgres cdc source, so that the
+/// sources of the same table don't share the replication slot and the publication.
+pub fn fill_postgres_cdc_source_id(properties: &mut HashMap<String, String>, source_id: u32) {
+    let is_postgres_cdc = properties
+        .get("connector")
+        .map_or(false, |c| c.eq_ignore_ascii_case(POSTGRES_CDC_CONNECTOR));
+    if is_postgres_cdc {
+        properties.insert(
+            POSTGRES_CDC_SOURCE_ID_KEY.to_string(),
+            source_id.to_string(),
+        );
+    }
+}
+
+const fn default_port() -> u16 {
+    5432
+}
+
+fn default_schema_name() -> String {
+    "public".to_string()
+}
+
+const fn default_snapshot_chunk_size() -> usize {
+    1024
+}
+
+/// The properties of the native Postgres CDC connector, which captures the changes of a single
+/// table by logical replication. The changes are emitted in the Debezium JSON format, so the source
+/// must be created with `ROW FORMAT DEBEZIUM_JSON` and the primary key of the upstream table.
+#[serde_as]
+#[derive(Clone, Debug, Deserialize)]
+pub struct PostgresCdcProperties {
+    #[serde(rename = "hostname")]
+    pub hostname: String,
+
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(rename = "port", default = "default_port")]
+    pub port: u16,
+
+    #[serde(rename = "username")]
+    pub username: String,
+
+    #[serde(rename = "password", default)]
+    pub password: String,
+
+    #[serde(rename = "database.name")]
+    pub database_name: String,
+
+    #[serde(rename = "schema.name", default = "default_schema_name")]
+    pub schema_name: String,
+
+    #[serde(rename = "table.name")]
+    pub table_name: String,
+
+    /// The logical replication slot to read the changes from. It's created along with the source
+    /// if not exists, and dropped along with the source. Defaults to a name derived from the
+    /// source and the table.
+    #[serde(rename = "slot.name")]
+    pub slot_name: Option<String>,
+
+    /// The publication of the table for decoding the changes with `pgoutput`. If not given, a
+    /// publication is created along with the source and dropped along with the source.
+    #[serde(rename = "publication.name")]
+    pub publication_name: Option<String>,
+
+    /// `initial` takes a snapshot of the table before reading the changes, while `latest` only
+    /// reads the changes made after the source is created. Defaults to `initial`.
+    #[serde(rename = "scan.startup.mode")]
+    pub scan_startup_mode: Option<String>,
+
+    /// The number of rows read in each query of the snapshot.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(
+        rename = "snapshot.chunk.size",
+        default = "default_snapshot_chunk_size"
+    )]
+    pub snapshot_chunk_size: usize,
+
+    /// The id of the source, filled by meta. It's absent for the sources created before it's
+    /// filled, whose default names are derived from the table only.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(rename = "source.id", default)]
+    pub source_id: Option<u32>,
+}
+
+impl PostgresCdcProperties {
+    pub async fn connect(&self) -> Result<Client> {
+        let (client, connection) = tokio_postgres::Config::new()
+            .host(&self.hostname)
+            .port(self.port)
+            .user(&self.username)
+            .password(&self.password)
+            .dbname(&self.database_name)
+            .connect(NoTls)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to connect to postgres {}:{}",
+                    self.hostname, self.port
+                )
+            })?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                tracing::warn!("postgres connection error: {}", e);
+            }
+        });
+        // Decode the changes and read the snapshot in a fixed format.
+        client
+            .batch_execute("SET TimeZone = 'UTC'; SET DateStyle = 'ISO'")
+            .await?;
+        Ok(client)
+    }
+
+    /// Whether to skip the snapshot and only read the new changes.
+    pub fn skip_snapshot(&self) -> Result<bool> {
+        match self
+            .scan_startup_mode
+            .as_ref()
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            Some("initial") | None => Ok(false),
+            Some("latest") => Ok(true),
+            _ => Err(anyhow!(
+                "properties `scan.startup.mode` only support initial and latest or leave it empty"
+            )),
+        }
+    }
+
+    /// The quoted name of the captured table.
+    pub fn quoted_table_name(&self) -> String {
+        format!(
+            "{}.{}",
+            quote_identifier(&self.schema_name),
+            quote_identifier(&self.table_name)
+        )
+    }
+
+    fn default_name(&self) -> String {
+        // The source id goes first, so that it's not truncated.
+        let prefix = match self.source_id {
+            Some(source_id) => format!("risingwave_{}", source_id),
+            None => "risingwave".to_string(),
+        };
+        let name = format!(
+            "{}_{}_{}_{}",
+            prefix, self.database_name, self.schema_name, self.table_name
+        );
+        truncate_identifier(
+            name.to_lowercase()
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect(),
+            MAX_IDENTIFIER_LEN,
+        )
+    }
+
+    pub fn slot_name(&self) -> String {
+        self.slot_name
+            .clone()
+            .unwrap_or_else(|| self.default_name())
+    }
+
+    pub fn publication_name(&self) -> String {
+        self.publication_name
+            .clone()
+            .unwrap_or_else(|| self.default_name())
+    }
+
+    /// Creates the publication and the replication slot for the source if not exist. The changes
+    /// made after that are retained by the slot until the source is dropped.
+    pub async fn create_replication(&self, client: &Client) -> Result<()> {
+        let publication = self.publication_name();
+        let exists = client
+            .query_opt(
+                "SELECT 1 FROM pg_publication WHERE pubname = $1",
+                &[&publication],
+            )
+            .await?
+            .is_some();
+        if !exists {
+            if self.publication_name.is_some() {
+                return Err(anyhow!("publication {} does not exist", publication));
+            }
+            client
+                .batch_execute(&format!(
+                    "CREATE PUBLICATION {} FOR TABLE {}",
+                    quote_identifier(&publication),
+                    self.quoted_table_name()
+                ))
+                .await?;
+        }
+
+        let slot = self.slot_name();
+        let exists = client
+            .query_opt(
+                "SELECT 1 FROM pg_replication_slots WHERE slot_name = $1",
+                &[&slot],
+            )
+            .await?
+            .is_some();
+        if !exists {
+            client
+                .query(
+                    "SELECT pg_create_logical_replication_slot($1, 'pgoutput')",
+                    &[&slot],
+                )
+                .await?;
+            tracing::info!("created replication slot {} for postgres cdc", slot);
+        }
+        Ok(())
+    }
+
+    /// Drops the replication slot of the source, and the publication if it's created for the
+    /// source. The slot retains the WAL until it's dropped, so it must be dropped manually on
+    /// failures, which is told by the error.
+    pub async fn drop_replication(&self) -> Result<()> {
+        let slot = self.slot_name();
+        self.drop_replication_inner().await.with_context(|| {
+            format!(
+                "failed to drop replication slot {} of postgres cdc, please drop it manually \
+                 by `SELECT pg_drop_replication_slot('{}')`",
+                slot, slot
+            )
+        })
+    }
+
+    async fn drop_replication_inner(&self) -> Result<()> {
+        let client = self.connect().await?;
+        let slot = self.slot_name();
+        // The slot may be acquired by the reader or an advance of it meanwhile, which releases it
+        // soon.
+        let mut dropped = false;
+        for _ in 0..SLOT_IN_USE_MAX_RETRIES {
+            match client
+                .query(
+                    "SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots \
+                     WHERE slot_name = $1",
+                    &[&slot],
+                )
+                .await
+            {
+                Err(e) if e.code() == Some(&SqlState::OBJECT_IN_USE) => {
+                    tokio::time::sleep(SLOT_IN_USE_RETRY_INTERVAL).await;
+                }
+                result => {
+                    result?;
+                    dropped = true;
+                    break;
+                }
+            }
+        }
+        if !dropped {
+            return Err(anyhow!("replication slot {} is in use", slot));
+        }
+        if self.publication_name.is_none() {
+            client
+                .batch_execute(&format!(
+                    "DROP PUBLICATION IF EXISTS {}",
+                    quote_identifier(&self.publication_name())
+                ))
+                .await?;
+        }
+        tracing::info!("dropped replication slot {} of postgres cdc", slot);
+        Ok(())
+    }
+
+    /// Confirms that the changes before `lsn` have been durably read, so that the WAL retained
+    /// for them can be recycled.
+    pub async fn advance_slot(&self, lsn: u64) -> Result<()> {
+        let client = self.connect().await?;
+        let slot = self.slot_name();
+        let lsn = format_lsn(lsn);
+        // The slot may be acquired by another advance of it meanwhile.
+        for _ in 0..SLOT_IN_USE_MAX_RETRIES {
+            match client
+                .query(
+                    "SELECT pg_replication_slot_advance($1, $2::text::pg_lsn)",
+                    &[&slot, &lsn],
+                )
+                .await
+            {
+                Err(e) if e.code() == Some(&SqlState::OBJECT_IN_USE) => {
+                    tokio::time::sleep(SLOT_IN_USE_RETRY_INTERVAL).await;
+                }
+                result => {
+                    result?;
+                    return Ok(());
+                }
+            }
+        }
+        Err(anyhow!("replication slot {} is in use", slot))
+    }
+}
+
+/// The interval to retry when the replication slot is acquired by another session.
+const SLOT_IN_USE_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The times to retry when the replication slot is acquired by another session.
+const SLOT_IN_USE_MAX_RETRIES: usize = 50;
+
+/// The identifiers longer than it are truncated by postgres.
+pub(crate) const MAX_IDENTIFIER_LEN: usize = 63;
+
+pub(crate) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Truncates the identifier to `len` bytes at most, on a char boundary.
+pub(crate) fn truncate_identifier(mut name: String, len: usize) -> String {
+    if name.len() > len {
+        let mut end = len;
+        while !name.is_char_boundary(end) {
+            end -= 1;
+        }
+        name.truncate(end);
+    }
+    name
+}
+
+/// Parses an LSN in the textual form of postgres, e.g. `16/B374D848`.
+pub fn parse_lsn(lsn: &str) -> Result<u64> {
+    let (hi, lo) = lsn
+        .split_once('/')
+        .ok_or_else(|| anyhow!("illegal lsn {}", lsn))?;
+    let hi = u64::from_str_radix(hi, 16).map_err(|e| anyhow!("illegal lsn {}: {}", lsn, e))?;
+    let lo = u64::from_str_radix(lo, 16).map_err(|e| anyhow!("illegal lsn {}: {}", lsn, e))?;
+    Ok((hi << 32) | lo)
+}
+
+pub fn format_lsn(lsn: u64) -> String {
+    format!("{:X}/{:X}", lsn >> 32, lsn & 0xFFFF_FFFF)
+}
+
+/// The schema of the captured table in the upstream.
+#[derive(Clone, Debug)]
+pub struct PostgresTableSchema {
+    /// The names and the types of all the columns in the order of their numbers.
+    pub columns: Vec<(String, String)>,
+    /// The indices of the primary key columns in `columns`.
+    pub pk_indices: Vec<usize>,
+}
+
+impl PostgresTableSchema {
+    pub async fn fetch(client: &Client, properties: &PostgresCdcProperties) -> Result<Self> {
+        let table = properties.quoted_table_name();
+        let columns = client
+            .query(
+                "SELECT attname::text, format_type(atttypid, atttypmod) FROM pg_attribute \
+                 WHERE attrelid = $1::text::regclass AND attnum > 0 AND NOT attisdropped \
+                 ORDER BY attnum",
+                &[&table],
+            )
+            .await
+            .with_context(|| format!("failed to fetch the columns of table {}", table))?
+            .into_iter()
+            .map(|row| (row.get::<_, String>(0), row.get::<_, String>(1)))
+            .collect_vec();
+        let pk_columns: Vec<String> = client
+            .query(
+                "SELECT a.attname::text FROM pg_index i JOIN pg_attribute a \
+                 ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey) \
+                 WHERE i.indrelid = $1::text::regclass AND i.indisprimary \
+                 ORDER BY array_position(i.indkey::int2[], a.attnum)",
+                &[&table],
+            )
+            .await?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect();
+        if pk_columns.is_empty() {
+            return Err(anyhow!(
+                "table {} must have a primary key to be captured",
+                table
+            ));
+        }
+        let pk_indices = pk_columns
+            .iter()
+            .map(|pk| columns.iter().position(|(c, _)| c == pk).unwrap())
+            .collect_vec();
+        Ok(Self {
+            columns,
+            pk_indices,
+        })
+    }
+}
+
+/// Checks that the server and the table are configured for capturing the changes.
+pub async fn validate_replication_settings(
+    client: &Client,
+    properties: &PostgresCdcProperties,
+) -> Result<()> {
+    let wal_level: String = client.query_one("SHOW wal_level", &[]).await?.get(0);
+    if !wal_level.eq_ignore_ascii_case("logical") {
+        return Err(anyhow!(
+            "`wal_level` of postgres must be logical, but got {}",
+            wal_level
+        ));
+    }
+    // The full old rows are required to emit the updates and deletes with the before images, and
+    // to fill in the unchanged TOAST values of the new rows.
+    let replica_identity: i8 = client
+        .query_one(
+            "SELECT relreplident FROM pg_class WHERE oid = $1::text::regclass",
+            &[&properties.quoted_table_name()],
+        )
+        .await?
+        .get(0);
+    if replica_identity != b'f' as i8 {
+        return Err(anyhow!(
+            "the replica identity of table {} must be FULL, please run `ALTER TABLE {} REPLICA \
+             IDENTITY FULL`",
+            properties.quoted_table_name(),
+            properties.quoted_table_name()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lsn() {
+        assert_eq!(parse_lsn("16/B374D848").unwrap(), 0x16_B374_D848);
+        assert_eq!(format_lsn(0x16_B374_D848), "16/B374D848");
+        assert_eq!(parse_lsn("0/0").unwrap(), 0);
+        assert!(parse_lsn("16B374D848").is_err());
+    }
+
+    #[test]
+    fn test_default_name() {
+        let mut properties = HashMap::from([
+            ("connector".to_string(), "postgres-cdc".to_string()),
+            ("hostname".to_string(), "localhost".to_string()),
+            ("username".to_string(), "postgres".to_string()),
+            ("database.name".to_string(), "mydb".to_string()),
+            ("table.name".to_string(), "Orders".to_string()),
+        ]);
+        fill_postgres_cdc_source_id(&mut properties, 1001);
+        properties.remove("connector");
+        let mut properties: PostgresCdcProperties =
+            serde_json::from_value(serde_json::to_value(properties).unwrap()).unwrap();
+        assert_eq!(properties.source_id, Some(1001));
+        assert_eq!(properties.slot_name(), "risingwave_1001_mydb_public_orders");
+        assert_eq!(properties.publication_name(), properties.slot_name());
+
+        // The source id is kept when the name is truncated.
+        properties.table_name = "t".repeat(100);
+        let name = properties.slot_name();
+        assert_eq!(name.len(), MAX_IDENTIFIER_LEN);
+        assert!(name.starts_with("risingwave_1001_"));
+
+        properties.source_id = None;
+        properties.table_name = "orders".to_string();
+        assert_eq!(properties.slot_name(), "risingwave_mydb_public_orders");
+    }
+}
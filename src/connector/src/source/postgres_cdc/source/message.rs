@@ -0,0 +1,244 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Number, Value as JsonValue};
+
+use crate::source::cdc::{
+    debezium_event, DEBEZIUM_CREATE_OP, DEBEZIUM_DELETE_OP, DEBEZIUM_READ_OP, DEBEZIUM_UPDATE_OP,
+};
+use crate::source::{DataType, SourceMessage, SourceMeta, SplitId};
+
+/// Builds the change events of the captured table in the Debezium JSON format from the values in
+/// the textual form of postgres.
+pub struct ChangeEventBuilder {
+    split_id: SplitId,
+    /// The types of the columns in RisingWave, by which the values are converted. The values of
+    /// the columns not in the source are kept as strings.
+    column_types: HashMap<String, DataType>,
+}
+
+impl ChangeEventBuilder {
+    pub fn new(split_id: SplitId, column_types: HashMap<String, DataType>) -> Self {
+        Self {
+            split_id,
+            column_types,
+        }
+    }
+
+    /// Converts the values of a row to a JSON object, where `columns` are the names of the
+    /// values.
+    pub fn row_to_json(
+        &self,
+        columns: &[String],
+        values: Vec<Option<String>>,
+    ) -> Result<JsonValue> {
+        if values.len() != columns.len() {
+            return Err(anyhow!(
+                "expect {} columns in the row, but got {}",
+                columns.len(),
+                values.len()
+            ));
+        }
+        let mut row = Map::with_capacity(values.len());
+        for (name, value) in columns.iter().zip(values) {
+            let value = match value {
+                Some(text) => pg_text_to_json(&text, self.column_types.get(name))?,
+                None => JsonValue::Null,
+            };
+            row.insert(name.clone(), value);
+        }
+        Ok(JsonValue::Object(row))
+    }
+
+    /// Builds the event of a row read by the snapshot.
+    pub fn build_read(
+        &self,
+        columns: &[String],
+        values: Vec<Option<String>>,
+        offset: String,
+    ) -> Result<SourceMessage> {
+        let after = self.row_to_json(columns, values)?;
+        Ok(self.build(DEBEZIUM_READ_OP, JsonValue::Null, after, offset))
+    }
+
+    /// Builds the event of a row changed in the replication slot, where an insert has no before
+    /// image and a delete has no after image.
+    pub fn build_change(
+        &self,
+        columns: &[String],
+        before: Option<Vec<Option<String>>>,
+        after: Option<Vec<Option<String>>>,
+        offset: String,
+    ) -> Result<SourceMessage> {
+        let op = match (&before, &after) {
+            (None, Some(_)) => DEBEZIUM_CREATE_OP,
+            (Some(_), Some(_)) => DEBEZIUM_UPDATE_OP,
+            (Some(_), None) => DEBEZIUM_DELETE_OP,
+            (None, None) => return Err(anyhow!("no row image in the change")),
+        };
+        let before = before.map_or(Ok(JsonValue::Null), |v| self.row_to_json(columns, v))?;
+        let after = after.map_or(Ok(JsonValue::Null), |v| self.row_to_json(columns, v))?;
+        Ok(self.build(op, before, after, offset))
+    }
+
+    /// Builds a message without payload, which only advances the offset over the transactions
+    /// not touching the captured table.
+    pub fn build_heartbeat(&self, offset: String) -> SourceMessage {
+        SourceMessage {
+            payload: None,
+            offset,
+            split_id: self.split_id.clone(),
+            meta: SourceMeta::default(),
+        }
+    }
+
+    fn build(
+        &self,
+        op: &str,
+        before: JsonValue,
+        after: JsonValue,
+        offset: String,
+    ) -> SourceMessage {
+        SourceMessage {
+            payload: Some(debezium_event(op, before, after)),
+            offset,
+            split_id: self.split_id.clone(),
+            meta: SourceMeta::default(),
+        }
+    }
+}
+
+/// Converts a value in the textual form of postgres to the JSON value accepted by the Debezium
+/// JSON parser for the given type.
+pub fn pg_text_to_json(text: &str, data_type: Option<&DataType>) -> Result<JsonValue> {
+    let json = match data_type {
+        Some(
+            DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal,
+        ) => {
+            if let Ok(v) = text.parse::<i64>() {
+                v.into()
+            } else {
+                // `NaN` and the infinities can't be represented in JSON, so they are converted to
+                // null.
+                let v = text
+                    .parse::<f64>()
+                    .map_err(|e| anyhow!("failed to parse number \"{}\": {}", text, e))?;
+                Number::from_f64(v).map_or(JsonValue::Null, JsonValue::Number)
+            }
+        }
+        Some(DataType::Boolean) => match text {
+            "t" | "true" => JsonValue::Bool(true),
+            "f" | "false" => JsonValue::Bool(false),
+            _ => return Err(anyhow!("failed to parse boolean \"{}\"", text)),
+        },
+        Some(DataType::Jsonb) => serde_json::from_str(text)?,
+        // The timestamps are read in UTC, e.g. `2022-10-01 12:30:15.5+00`.
+        Some(DataType::Timestampz) => {
+            let datetime = DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z")
+                .map_err(|e| anyhow!("failed to parse timestamptz \"{}\": {}", text, e))?
+                .with_timezone(&Utc);
+            JsonValue::String(datetime.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string())
+        }
+        _ => JsonValue::String(text.to_string()),
+    };
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_pg_text_to_json() {
+        assert_eq!(
+            pg_text_to_json("t", Some(&DataType::Boolean)).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            pg_text_to_json("false", Some(&DataType::Boolean)).unwrap(),
+            json!(false)
+        );
+        assert_eq!(
+            pg_text_to_json("-3", Some(&DataType::Int32)).unwrap(),
+            json!(-3)
+        );
+        assert_eq!(
+            pg_text_to_json("12.5", Some(&DataType::Decimal)).unwrap(),
+            json!(12.5)
+        );
+        assert_eq!(
+            pg_text_to_json("NaN", Some(&DataType::Float64)).unwrap(),
+            JsonValue::Null
+        );
+        assert_eq!(
+            pg_text_to_json("2022-10-01", Some(&DataType::Date)).unwrap(),
+            json!("2022-10-01")
+        );
+        assert_eq!(
+            pg_text_to_json("2022-10-01 12:30:15.5+00", Some(&DataType::Timestampz)).unwrap(),
+            json!("2022-10-01T12:30:15.500000Z")
+        );
+        assert_eq!(
+            pg_text_to_json(r#"{"a": 1}"#, Some(&DataType::Jsonb)).unwrap(),
+            json!({"a": 1})
+        );
+        assert_eq!(pg_text_to_json("abc", None).unwrap(), json!("abc"));
+        assert!(pg_text_to_json("abc", Some(&DataType::Int64)).is_err());
+    }
+
+    #[test]
+    fn test_build_change() {
+        let column_types = HashMap::from([
+            ("id".to_string(), DataType::Int32),
+            ("name".to_string(), DataType::Varchar),
+        ]);
+        let builder = ChangeEventBuilder::new("public.t".into(), column_types);
+        let columns = vec!["id".to_string(), "name".to_string()];
+
+        let msg = builder
+            .build_change(
+                &columns,
+                Some(vec![Some("1".to_string()), Some("a".to_string())]),
+                None,
+                "offset".to_string(),
+            )
+            .unwrap();
+        let event: JsonValue = serde_json::from_slice(msg.payload.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            event,
+            json!({
+                "payload": {
+                    "before": {"id": 1, "name": "a"},
+                    "after": null,
+                    "op": "d",
+                }
+            })
+        );
+
+        let msg = builder.build_heartbeat("offset".to_string());
+        assert!(msg.payload.is_none());
+        assert_eq!(msg.offset, "offset");
+    }
+}
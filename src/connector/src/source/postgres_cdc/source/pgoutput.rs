@@ -0,0 +1,319 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of the messages of `pgoutput`, the built-in logical decoding plugin of postgres. See
+//! <https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html>.
+
+use anyhow::{anyhow, ensure, Result};
+use bytes::Buf;
+
+/// A value of a column in the tuple data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TupleValue {
+    Null,
+    /// An unchanged TOAST value, which is not sent by the upstream.
+    Unchanged,
+    Text(String),
+}
+
+pub type Tuple = Vec<TupleValue>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Relation {
+    pub id: u32,
+    pub namespace: String,
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PgOutputMessage {
+    Begin {
+        final_lsn: u64,
+        xid: u32,
+    },
+    Commit {
+        commit_lsn: u64,
+        end_lsn: u64,
+    },
+    Relation(Relation),
+    Insert {
+        relation_id: u32,
+        new: Tuple,
+    },
+    /// The old tuple is present only if the replica identity is changed or is `FULL`, and only
+    /// contains the key columns unless it's `FULL`.
+    Update {
+        relation_id: u32,
+        old: Option<Tuple>,
+        new: Tuple,
+    },
+    Delete {
+        relation_id: u32,
+        old: Tuple,
+    },
+    /// The messages not concerned, e.g. origins, types and truncates.
+    Other(u8),
+}
+
+impl PgOutputMessage {
+    pub fn decode(mut buf: &[u8]) -> Result<Self> {
+        ensure!(buf.has_remaining(), "empty pgoutput message");
+        let tag = buf.get_u8();
+        let message = match tag {
+            b'B' => {
+                ensure!(buf.remaining() >= 20, "truncated begin message");
+                let final_lsn = buf.get_u64();
+                let _commit_ts = buf.get_i64();
+                let xid = buf.get_u32();
+                Self::Begin { final_lsn, xid }
+            }
+            b'C' => {
+                ensure!(buf.remaining() >= 25, "truncated commit message");
+                let _flags = buf.get_u8();
+                let commit_lsn = buf.get_u64();
+                let end_lsn = buf.get_u64();
+                let _commit_ts = buf.get_i64();
+                Self::Commit {
+                    commit_lsn,
+                    end_lsn,
+                }
+            }
+            b'R' => {
+                ensure!(buf.remaining() >= 4, "truncated relation message");
+                let id = buf.get_u32();
+                let namespace = get_cstr(&mut buf)?;
+                let name = get_cstr(&mut buf)?;
+                ensure!(buf.remaining() >= 3, "truncated relation message");
+                let _replica_identity = buf.get_u8();
+                let num_columns = buf.get_i16();
+                let mut columns = Vec::with_capacity(num_columns.max(0) as usize);
+                for _ in 0..num_columns {
+                    ensure!(buf.has_remaining(), "truncated relation message");
+                    let _flags = buf.get_u8();
+                    columns.push(get_cstr(&mut buf)?);
+                    ensure!(buf.remaining() >= 8, "truncated relation message");
+                    let _type_id = buf.get_u32();
+                    let _type_modifier = buf.get_i32();
+                }
+                Self::Relation(Relation {
+                    id,
+                    namespace,
+                    name,
+                    columns,
+                })
+            }
+            b'I' => {
+                let relation_id = get_relation_id(&mut buf)?;
+                expect_tag(&mut buf, b'N')?;
+                let new = get_tuple(&mut buf)?;
+                Self::Insert { relation_id, new }
+            }
+            b'U' => {
+                let relation_id = get_relation_id(&mut buf)?;
+                ensure!(buf.has_remaining(), "truncated update message");
+                let old = match buf.get_u8() {
+                    b'K' | b'O' => {
+                        let old = get_tuple(&mut buf)?;
+                        expect_tag(&mut buf, b'N')?;
+                        Some(old)
+                    }
+                    b'N' => None,
+                    tag => return Err(anyhow!("unexpected tuple tag {}", tag as char)),
+                };
+                let new = get_tuple(&mut buf)?;
+                Self::Update {
+                    relation_id,
+                    old,
+                    new,
+                }
+            }
+            b'D' => {
+                let relation_id = get_relation_id(&mut buf)?;
+                ensure!(buf.has_remaining(), "truncated delete message");
+                match buf.get_u8() {
+                    b'K' | b'O' => {}
+                    tag => return Err(anyhow!("unexpected tuple tag {}", tag as char)),
+                }
+                let old = get_tuple(&mut buf)?;
+                Self::Delete { relation_id, old }
+            }
+            tag => Self::Other(tag),
+        };
+        Ok(message)
+    }
+}
+
+fn get_cstr(buf: &mut &[u8]) -> Result<String> {
+    let end = buf
+        .iter()
+        .position(|b| *b == 0)
+        .ok_or_else(|| anyhow!("unterminated string in pgoutput message"))?;
+    let s = String::from_utf8(buf[..end].to_vec())?;
+    buf.advance(end + 1);
+    Ok(s)
+}
+
+fn get_relation_id(buf: &mut &[u8]) -> Result<u32> {
+    ensure!(buf.remaining() >= 4, "truncated pgoutput message");
+    Ok(buf.get_u32())
+}
+
+fn expect_tag(buf: &mut &[u8], expected: u8) -> Result<()> {
+    ensure!(buf.has_remaining(), "truncated pgoutput message");
+    let tag = buf.get_u8();
+    ensure!(
+        tag == expected,
+        "expect tuple tag {}, but got {}",
+        expected as char,
+        tag as char
+    );
+    Ok(())
+}
+
+fn get_tuple(buf: &mut &[u8]) -> Result<Tuple> {
+    ensure!(buf.remaining() >= 2, "truncated tuple data");
+    let num_columns = buf.get_i16();
+    let mut tuple = Vec::with_capacity(num_columns.max(0) as usize);
+    for _ in 0..num_columns {
+        ensure!(buf.has_remaining(), "truncated tuple data");
+        let value = match buf.get_u8() {
+            b'n' => TupleValue::Null,
+            b'u' => TupleValue::Unchanged,
+            b't' => {
+                ensure!(buf.remaining() >= 4, "truncated tuple data");
+                let len = buf.get_i32() as usize;
+                ensure!(buf.remaining() >= len, "truncated tuple data");
+                let text = String::from_utf8(buf[..len].to_vec())?;
+                buf.advance(len);
+                TupleValue::Text(text)
+            }
+            tag => return Err(anyhow!("unexpected column tag {}", tag as char)),
+        };
+        tuple.push(value);
+    }
+    Ok(tuple)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BufMut;
+
+    use super::*;
+
+    fn put_tuple(buf: &mut Vec<u8>, values: &[Option<&str>]) {
+        buf.put_i16(values.len() as i16);
+        for value in values {
+            match value {
+                Some(text) => {
+                    buf.put_u8(b't');
+                    buf.put_i32(text.len() as i32);
+                    buf.put_slice(text.as_bytes());
+                }
+                None => buf.put_u8(b'u'),
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_transaction() {
+        let mut buf = vec![b'B'];
+        buf.put_u64(0x100);
+        buf.put_i64(0);
+        buf.put_u32(42);
+        assert_eq!(
+            PgOutputMessage::decode(&buf).unwrap(),
+            PgOutputMessage::Begin {
+                final_lsn: 0x100,
+                xid: 42
+            }
+        );
+
+        let mut buf = vec![b'C', 0];
+        buf.put_u64(0x100);
+        buf.put_u64(0x130);
+        buf.put_i64(0);
+        assert_eq!(
+            PgOutputMessage::decode(&buf).unwrap(),
+            PgOutputMessage::Commit {
+                commit_lsn: 0x100,
+                end_lsn: 0x130
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_changes() {
+        let mut buf = vec![b'R'];
+        buf.put_u32(16385);
+        buf.put_slice(b"public\0t\0f");
+        buf.put_i16(2);
+        buf.put_u8(1);
+        buf.put_slice(b"id\0");
+        buf.put_u32(23);
+        buf.put_i32(-1);
+        buf.put_u8(0);
+        buf.put_slice(b"doc\0");
+        buf.put_u32(25);
+        buf.put_i32(-1);
+        assert_eq!(
+            PgOutputMessage::decode(&buf).unwrap(),
+            PgOutputMessage::Relation(Relation {
+                id: 16385,
+                namespace: "public".to_string(),
+                name: "t".to_string(),
+                columns: vec!["id".to_string(), "doc".to_string()],
+            })
+        );
+
+        let mut buf = vec![b'U'];
+        buf.put_u32(16385);
+        buf.put_u8(b'O');
+        put_tuple(&mut buf, &[Some("1"), Some("long text")]);
+        buf.put_u8(b'N');
+        put_tuple(&mut buf, &[Some("2"), None]);
+        assert_eq!(
+            PgOutputMessage::decode(&buf).unwrap(),
+            PgOutputMessage::Update {
+                relation_id: 16385,
+                old: Some(vec![
+                    TupleValue::Text("1".to_string()),
+                    TupleValue::Text("long text".to_string())
+                ]),
+                new: vec![TupleValue::Text("2".to_string()), TupleValue::Unchanged],
+            }
+        );
+
+        let mut buf = vec![b'D'];
+        buf.put_u32(16385);
+        buf.put_u8(b'O');
+        buf.put_i16(1);
+        buf.put_u8(b'n');
+        assert_eq!(
+            PgOutputMessage::decode(&buf).unwrap(),
+            PgOutputMessage::Delete {
+                relation_id: 16385,
+                old: vec![TupleValue::Null],
+            }
+        );
+
+        let mut buf = vec![b'I'];
+        buf.put_u32(16385);
+        buf.put_u8(b'N');
+        buf.put_i16(1);
+        buf.put_u8(b't');
+        buf.put_i32(10);
+        assert!(PgOutputMessage::decode(&buf).is_err());
+    }
+}
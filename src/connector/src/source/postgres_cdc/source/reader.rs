@@ -0,0 +1,480 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use async_trait::async_trait;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::try_match_expand;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Client, Row};
+
+use crate::source::postgres_cdc::source::message::ChangeEventBuilder;
+use crate::source::postgres_cdc::source::pgoutput::{PgOutputMessage, Tuple, TupleValue};
+use crate::source::postgres_cdc::{
+    parse_lsn, quote_identifier, truncate_identifier, PostgresCdcOffset, PostgresCdcProperties,
+    PostgresCdcSplit, PostgresTableSchema, SnapshotInfo, MAX_IDENTIFIER_LEN,
+};
+use crate::source::{
+    BoxSourceStream, Column, ConnectorState, SourceMessage, SplitImpl, SplitMetaData, SplitReader,
+    MAX_CHUNK_SIZE,
+};
+
+/// The interval to poll the replication slot when there are no new changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The number of changes to decode from the replication slot in one poll at least.
+const CHANGES_PER_POLL: i32 = 4096;
+
+/// A change of a row, with the old and the new values.
+type RowChange = (Option<Vec<Option<String>>>, Option<Vec<Option<String>>>);
+
+/// Reads the changes of a Postgres table. Unless skipped, a snapshot of the table is read first.
+/// Then the changes retained by the replication slot are read, skipping the transactions already
+/// included in the snapshot.
+///
+/// The slot of the source is only advanced after the offsets are durably checkpointed, so that the
+/// changes are not lost on recovery. The reader consumes the changes from a temporary copy of the
+/// slot instead, so that every change is decoded once, and the copy is dropped along with the
+/// connection of the reader. If the reader is recovered in the middle of a snapshot, the rest rows
+/// are read in a new snapshot, so the changes committed in between may be applied more than once.
+pub struct PostgresCdcSplitReader {
+    properties: PostgresCdcProperties,
+    split: PostgresCdcSplit,
+    schema: PostgresTableSchema,
+    builder: ChangeEventBuilder,
+}
+
+#[async_trait]
+impl SplitReader for PostgresCdcSplitReader {
+    type Properties = PostgresCdcProperties;
+
+    async fn new(
+        properties: PostgresCdcProperties,
+        state: ConnectorState,
+        columns: Option<Vec<Column>>,
+    ) -> Result<Self> {
+        let splits = state.ok_or_else(|| anyhow!("no default state for reader"))?;
+        ensure!(splits.len() == 1, "only support single split");
+        let split = try_match_expand!(splits.into_iter().next().unwrap(), SplitImpl::PostgresCdc)?;
+
+        let client = properties.connect().await?;
+        let schema = PostgresTableSchema::fetch(&client, &properties).await?;
+
+        let column_types = columns
+            .unwrap_or_default()
+            .into_iter()
+            .map(|c| (c.name, c.data_type))
+            .collect();
+        let builder = ChangeEventBuilder::new(split.id(), column_types);
+
+        Ok(Self {
+            properties,
+            split,
+            schema,
+            builder,
+        })
+    }
+
+    fn into_stream(self) -> BoxSourceStream {
+        self.into_stream()
+    }
+}
+
+impl PostgresCdcSplitReader {
+    #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
+    pub async fn into_stream(self) {
+        let offset = match self.split.offset.clone() {
+            Some(
+                offset @ PostgresCdcOffset {
+                    snapshot: None | Some(SnapshotInfo { finished: true, .. }),
+                    ..
+                },
+            ) => offset,
+            offset => {
+                let client = self.properties.connect().await?;
+                let new_snapshot = self.begin_snapshot(&client).await?;
+                // The changes are filtered by the first snapshot, so that no change is missed.
+                let mut snapshot = match offset {
+                    Some(PostgresCdcOffset {
+                        snapshot: Some(snapshot),
+                        ..
+                    }) => snapshot,
+                    _ => new_snapshot,
+                };
+                tracing::info!(
+                    "reading snapshot of postgres table {}, snapshot {:?}",
+                    self.properties.quoted_table_name(),
+                    snapshot
+                );
+
+                let columns = self
+                    .schema
+                    .columns
+                    .iter()
+                    .map(|(name, _)| name.clone())
+                    .collect_vec();
+                let mut chunk = self.read_snapshot_chunk(&client, &snapshot.last_pk).await?;
+                while !chunk.is_empty() {
+                    snapshot.last_pk = Some(self.pk_values(chunk.last().unwrap())?);
+                    let next_chunk = self.read_snapshot_chunk(&client, &snapshot.last_pk).await?;
+                    let len = chunk.len();
+                    let mut msgs = Vec::with_capacity(len);
+                    for (i, row) in chunk.into_iter().enumerate() {
+                        // The last row of the snapshot finishes it.
+                        if i == len - 1 && next_chunk.is_empty() {
+                            snapshot.finished = true;
+                        }
+                        let offset = PostgresCdcOffset {
+                            lsn: 0,
+                            snapshot: Some(snapshot.clone()),
+                        };
+                        let values = (0..columns.len()).map(|i| row.get(i)).collect();
+                        msgs.push(self.builder.build_read(&columns, values, offset.encode())?);
+                    }
+                    yield msgs;
+                    chunk = next_chunk;
+                }
+                client.batch_execute("COMMIT").await?;
+
+                snapshot.finished = true;
+                PostgresCdcOffset {
+                    lsn: 0,
+                    snapshot: Some(snapshot),
+                }
+            }
+        };
+
+        #[for_await]
+        for msgs in self.read_changes(offset) {
+            yield msgs?;
+        }
+    }
+
+    /// Starts a transaction of `REPEATABLE READ` to read the snapshot.
+    async fn begin_snapshot(&self, client: &Client) -> Result<SnapshotInfo> {
+        client
+            .batch_execute("BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .await?;
+        // The snapshot of the transaction is taken by its first query.
+        let row = client
+            .query_one(
+                "SELECT txid_current_snapshot()::text, pg_current_wal_insert_lsn()::text",
+                &[],
+            )
+            .await?;
+        let txid_snapshot: String = row.get(0);
+        let wal_lsn: String = row.get(1);
+
+        // The snapshot is in the form of `xmin:xmax:xip_list`.
+        let parts = txid_snapshot.split(':').collect_vec();
+        ensure!(parts.len() == 3, "illegal txid snapshot {}", txid_snapshot);
+        let xmin = parts[0].parse()?;
+        let xmax = parts[1].parse()?;
+        let xip = parts[2]
+            .split(',')
+            .filter(|xid| !xid.is_empty())
+            .map(|xid| xid.parse())
+            .try_collect()?;
+        Ok(SnapshotInfo {
+            xmin,
+            xmax,
+            xip,
+            wal_lsn: parse_lsn(&wal_lsn)?,
+            last_pk: None,
+            finished: false,
+        })
+    }
+
+    /// Reads the rows after `last_pk` in the order of the primary key. All the values are read in
+    /// the textual form, the same as in the replication messages.
+    async fn read_snapshot_chunk(
+        &self,
+        client: &Client,
+        last_pk: &Option<Vec<String>>,
+    ) -> Result<Vec<Row>> {
+        let schema = &self.schema;
+        let columns = schema
+            .columns
+            .iter()
+            .map(|(name, _)| format!("{}::text", quote_identifier(name)))
+            .join(", ");
+        let pk = schema
+            .pk_indices
+            .iter()
+            .map(|i| quote_identifier(&schema.columns[*i].0))
+            .join(", ");
+        let filter = match last_pk {
+            Some(_) => format!(
+                "WHERE ({}) > ({})",
+                pk,
+                schema
+                    .pk_indices
+                    .iter()
+                    .enumerate()
+                    .map(|(i, col)| format!("${}::text::{}", i + 1, schema.columns[*col].1))
+                    .join(", ")
+            ),
+            None => "".to_string(),
+        };
+        let query = format!(
+            "SELECT {} FROM {} {} ORDER BY {} LIMIT {}",
+            columns,
+            self.properties.quoted_table_name(),
+            filter,
+            pk,
+            self.properties.snapshot_chunk_size
+        );
+        let params = last_pk
+            .iter()
+            .flatten()
+            .map(|v| v as &(dyn ToSql + Sync))
+            .collect_vec();
+        Ok(client.query(&query, &params).await?)
+    }
+
+    fn pk_values(&self, row: &Row) -> Result<Vec<String>> {
+        self.schema
+            .pk_indices
+            .iter()
+            .map(|i| {
+                row.get::<_, Option<String>>(*i)
+                    .ok_or_else(|| anyhow!("null primary key in table"))
+            })
+            .collect()
+    }
+
+    /// Copies the replication slot of the source to a temporary slot of the session of `client`,
+    /// which starts from the position confirmed by the last checkpoint. Returns its name.
+    async fn copy_slot(&self, client: &Client) -> Result<String> {
+        let slot = self.properties.slot_name();
+        // The copy of the last reader may not be dropped yet, so the names are made unique.
+        let suffix = format!("_{:08x}", rand::random::<u32>());
+        let copy = truncate_identifier(slot.clone(), MAX_IDENTIFIER_LEN - suffix.len()) + &suffix;
+        client
+            .query(
+                "SELECT pg_copy_logical_replication_slot($1, $2, true)",
+                &[&slot, &copy],
+            )
+            .await
+            .with_context(|| format!("failed to copy replication slot {}", slot))?;
+        Ok(copy)
+    }
+
+    /// Consumes the changes retained by the temporary slot. Returns the LSNs and the data of the
+    /// messages of the complete transactions, up to about `limit` changes.
+    async fn get_changes(
+        &self,
+        client: &Client,
+        slot: &str,
+        limit: i32,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let publication = self.properties.publication_name();
+        Ok(client
+            .query(
+                "SELECT lsn::text, data FROM pg_logical_slot_get_binary_changes($1, NULL, $2, \
+                 'proto_version', '1', 'publication_names', $3)",
+                &[&slot, &limit, &publication],
+            )
+            .await?
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect())
+    }
+
+    /// Reads the changes of the table from the replication slot. The changes of a transaction are
+    /// emitted together when it's committed, with the LSN after it as the offset.
+    #[try_stream(ok = Vec<SourceMessage>, error = anyhow::Error)]
+    async fn read_changes(&self, offset: PostgresCdcOffset) {
+        tracing::info!(
+            "reading changes of postgres table {} from slot {}, offset {:?}",
+            self.properties.quoted_table_name(),
+            self.properties.slot_name(),
+            offset
+        );
+        let client = self.properties.connect().await?;
+        let slot = self.copy_slot(&client).await?;
+        let PostgresCdcOffset {
+            mut lsn,
+            mut snapshot,
+        } = offset;
+
+        // The relations in the messages, with the names of their columns if it's the captured
+        // table.
+        let mut relations: HashMap<u32, Option<Arc<Vec<String>>>> = HashMap::new();
+        loop {
+            let rows = self.get_changes(&client, &slot, CHANGES_PER_POLL).await?;
+            let num_rows = rows.len();
+
+            let mut msgs = vec![];
+            let mut xid = 0;
+            let mut changes: Vec<(Arc<Vec<String>>, RowChange)> = vec![];
+            let mut heartbeat = None;
+            for (_, data) in rows {
+                match PgOutputMessage::decode(&data)? {
+                    PgOutputMessage::Begin { xid: id, .. } => {
+                        xid = id;
+                        changes.clear();
+                    }
+                    PgOutputMessage::Relation(relation) => {
+                        let captured = relation.namespace == self.properties.schema_name
+                            && relation.name == self.properties.table_name;
+                        relations.insert(relation.id, captured.then(|| Arc::new(relation.columns)));
+                    }
+                    PgOutputMessage::Insert { relation_id, new } => {
+                        if let Some(columns) = self.captured_columns(&relations, relation_id)? {
+                            changes.push((columns, (None, Some(tuple_values(new, None)?))));
+                        }
+                    }
+                    PgOutputMessage::Update {
+                        relation_id,
+                        old,
+                        new,
+                    } => {
+                        if let Some(columns) = self.captured_columns(&relations, relation_id)? {
+                            // The unchanged TOAST values are filled from the old row.
+                            let old = old.ok_or_else(|| {
+                                anyhow!(
+                                    "no old row in the update, the replica identity must be FULL"
+                                )
+                            })?;
+                            let new = tuple_values(new, Some(&old))?;
+                            let old = tuple_values(old, None)?;
+                            changes.push((columns, (Some(old), Some(new))));
+                        }
+                    }
+                    PgOutputMessage::Delete { relation_id, old } => {
+                        if let Some(columns) = self.captured_columns(&relations, relation_id)? {
+                            changes.push((columns, (Some(tuple_values(old, None)?), None)));
+                        }
+                    }
+                    PgOutputMessage::Commit {
+                        commit_lsn,
+                        end_lsn,
+                    } => {
+                        // Skip the transactions already read but not checkpointed when the slot is
+                        // copied.
+                        if end_lsn <= lsn {
+                            changes.clear();
+                            continue;
+                        }
+                        let in_snapshot = match &snapshot {
+                            Some(s) if commit_lsn < s.wal_lsn => s.is_visible(xid),
+                            _ => false,
+                        };
+                        lsn = end_lsn;
+                        // All the transactions committed after the snapshot are read.
+                        if matches!(&snapshot, Some(s) if commit_lsn >= s.wal_lsn) {
+                            snapshot = None;
+                        }
+                        let offset = PostgresCdcOffset {
+                            lsn,
+                            snapshot: snapshot.clone(),
+                        }
+                        .encode();
+                        if in_snapshot || changes.is_empty() {
+                            changes.clear();
+                            heartbeat = Some(offset);
+                            continue;
+                        }
+                        heartbeat = None;
+                        for (columns, (before, after)) in changes.drain(..) {
+                            msgs.push(self.builder.build_change(
+                                &columns,
+                                before,
+                                after,
+                                offset.clone(),
+                            )?);
+                        }
+                    }
+                    PgOutputMessage::Other(_) => {}
+                }
+            }
+            // Advance the offset over the transactions without changes of the table, so that the
+            // slot doesn't retain them.
+            msgs.extend(heartbeat.map(|offset| self.builder.build_heartbeat(offset)));
+
+            if !msgs.is_empty() {
+                while msgs.len() > MAX_CHUNK_SIZE {
+                    let rest = msgs.split_off(MAX_CHUNK_SIZE);
+                    yield std::mem::replace(&mut msgs, rest);
+                }
+                yield msgs;
+            } else if num_rows == 0 {
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    /// Returns the columns of the relation if it's the captured table.
+    fn captured_columns(
+        &self,
+        relations: &HashMap<u32, Option<Arc<Vec<String>>>>,
+        relation_id: u32,
+    ) -> Result<Option<Arc<Vec<String>>>> {
+        relations
+            .get(&relation_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("no relation message for relation {}", relation_id))
+    }
+}
+
+/// Converts the tuple to the values of the columns, where the unchanged TOAST values are taken
+/// from `old`.
+fn tuple_values(tuple: Tuple, old: Option<&Tuple>) -> Result<Vec<Option<String>>> {
+    tuple
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| match value {
+            TupleValue::Null => Ok(None),
+            TupleValue::Text(text) => Ok(Some(text)),
+            TupleValue::Unchanged => match old.and_then(|old| old.get(i)) {
+                Some(TupleValue::Text(text)) => Ok(Some(text.clone())),
+                Some(TupleValue::Null) => Ok(None),
+                _ => Err(anyhow!(
+                    "unchanged TOAST value without the old value, the replica identity must be \
+                     FULL"
+                )),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tuple_values() {
+        let old = vec![
+            TupleValue::Text("1".to_string()),
+            TupleValue::Text("long text".to_string()),
+            TupleValue::Null,
+        ];
+        let new = vec![
+            TupleValue::Text("2".to_string()),
+            TupleValue::Unchanged,
+            TupleValue::Null,
+        ];
+        assert_eq!(
+            tuple_values(new.clone(), Some(&old)).unwrap(),
+            vec![Some("2".to_string()), Some("long text".to_string()), None]
+        );
+        assert!(tuple_values(new, None).is_err());
+    }
+}
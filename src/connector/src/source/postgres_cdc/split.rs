@@ -0,0 +1,174 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::source::{SplitId, SplitMetaData};
+
+/// The snapshot of the table read before the changes, taken in a transaction of `REPEATABLE READ`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct SnapshotInfo {
+    /// The transaction snapshot in the form of `txid_current_snapshot()`, by which the changes
+    /// already included in the snapshot are skipped when reading the replication slot.
+    pub xmin: u64,
+    pub xmax: u64,
+    pub xip: Vec<u64>,
+
+    /// The WAL insert position right after the snapshot is taken. All the transactions committed
+    /// after it are not included in the snapshot.
+    pub wal_lsn: u64,
+
+    /// The primary key of the last row read by the snapshot, in the textual form.
+    pub last_pk: Option<Vec<String>>,
+
+    /// Whether all the rows of the snapshot have been read.
+    pub finished: bool,
+}
+
+impl SnapshotInfo {
+    /// Whether the changes of a transaction are included in the snapshot. The 32-bit transaction
+    /// ids of the replication messages are extended to the epoch of `xmax`.
+    pub fn is_visible(&self, xid: u32) -> bool {
+        let mut xid = (self.xmax & !0xFFFF_FFFF) | xid as u64;
+        let diff = xid as i64 - self.xmax as i64;
+        if diff > i32::MAX as i64 {
+            xid = xid.wrapping_sub(1 << 32);
+        } else if diff < i32::MIN as i64 {
+            xid += 1 << 32;
+        }
+        xid < self.xmin || (xid < self.xmax && !self.xip.contains(&xid))
+    }
+}
+
+/// The progress of capturing a table, which is carried by the offset of every message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PostgresCdcOffset {
+    /// The end LSN of the last transaction read from the replication slot, or 0 if none.
+    pub lsn: u64,
+
+    /// The snapshot, which is kept until the transactions committed before it have all been
+    /// read from the replication slot.
+    pub snapshot: Option<SnapshotInfo>,
+}
+
+impl PostgresCdcOffset {
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn decode(offset: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(offset).map_err(|e| anyhow!("illegal postgres cdc offset: {}", e))
+    }
+}
+
+/// The split of a captured table. There is only one split for each table, as a replication slot
+/// can't be read in parallel.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PostgresCdcSplit {
+    pub schema_name: String,
+    pub table_name: String,
+    /// The progress of capturing, or `None` if nothing has been read.
+    pub offset: Option<PostgresCdcOffset>,
+}
+
+impl PostgresCdcSplit {
+    pub fn copy_with_offset(&self, start_offset: String) -> Self {
+        Self {
+            schema_name: self.schema_name.clone(),
+            table_name: self.table_name.clone(),
+            offset: Some(PostgresCdcOffset::decode(&start_offset).unwrap()),
+        }
+    }
+}
+
+impl SplitMetaData for PostgresCdcSplit {
+    fn id(&self) -> SplitId {
+        format!("{}.{}", self.schema_name, self.table_name).into()
+    }
+
+    fn encode_to_bytes(&self) -> Bytes {
+        Bytes::from(serde_json::to_string(self).unwrap())
+    }
+
+    fn restore_from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::SplitImpl;
+
+    #[test]
+    fn test_split_with_offset() {
+        let split = PostgresCdcSplit {
+            schema_name: "public".to_string(),
+            table_name: "t".to_string(),
+            offset: None,
+        };
+        assert_eq!(split.id().as_ref(), "public.t");
+
+        let offset = PostgresCdcOffset {
+            lsn: 0x16_B374_D848,
+            snapshot: Some(SnapshotInfo {
+                xmin: 100,
+                xmax: 105,
+                xip: vec![100, 103],
+                wal_lsn: 0x16_B374_D000,
+                last_pk: Some(vec!["42".to_string(), "a".to_string()]),
+                finished: false,
+            }),
+        };
+        let split = SplitImpl::PostgresCdc(split).update(offset.encode());
+        let restored = SplitImpl::restore_from_bytes(&split.encode_to_bytes()).unwrap();
+        assert_eq!(split, restored);
+        assert_eq!(restored.into_postgres_cdc().unwrap().offset, Some(offset));
+    }
+
+    #[test]
+    fn test_snapshot_visibility() {
+        let snapshot = SnapshotInfo {
+            xmin: 100,
+            xmax: 105,
+            xip: vec![100, 103],
+            wal_lsn: 0,
+            last_pk: None,
+            finished: true,
+        };
+        assert!(snapshot.is_visible(99));
+        assert!(!snapshot.is_visible(100));
+        assert!(snapshot.is_visible(101));
+        assert!(!snapshot.is_visible(103));
+        assert!(!snapshot.is_visible(105));
+        assert!(!snapshot.is_visible(200));
+
+        // The transaction ids wrap around in the replication messages.
+        let epoch = 1 << 32;
+        let snapshot = SnapshotInfo {
+            xmin: epoch - 10,
+            xmax: epoch + 5,
+            xip: vec![epoch - 3, epoch + 2],
+            ..snapshot
+        };
+        assert!(snapshot.is_visible(u32::MAX - 20));
+        assert!(snapshot.is_visible(u32::MAX - 5));
+        assert!(!snapshot.is_visible(u32::MAX - 2));
+        assert!(snapshot.is_visible(1));
+        assert!(!snapshot.is_visible(2));
+        assert!(!snapshot.is_visible(6));
+    }
+}
@@ -12,25 +12,69 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt;
+
 use anyhow::{anyhow, bail, Result};
-use http::{Response, StatusCode};
+use http::header::AUTHORIZATION;
+use http::{Request, Response, StatusCode};
 use hyper::body::Buf;
 use hyper::{Body, Client, Uri};
 use serde_derive::{Deserialize, Serialize};
+use tokio::sync::Mutex;
 
 use crate::source::pulsar::topic::Topic;
 
+/// The authentication of the admin REST API, which is a bearer token.
+pub enum PulsarAdminAuth {
+    Token(String),
+    /// The token is acquired from the OAuth 2.0 provider, which refreshes it on expiry.
+    OAuth(Mutex<Box<dyn pulsar::authentication::Authentication>>),
+}
+
+impl fmt::Debug for PulsarAdminAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Token(_) => write!(f, "Token"),
+            Self::OAuth(_) => write!(f, "OAuth"),
+        }
+    }
+}
+
+impl PulsarAdminAuth {
+    async fn bearer_token(&self) -> Result<String> {
+        match self {
+            Self::Token(token) => Ok(token.clone()),
+            Self::OAuth(authentication) => {
+                let token = authentication
+                    .lock()
+                    .await
+                    .auth_data()
+                    .await
+                    .map_err(|e| anyhow!("failed to get pulsar oauth token: {:?}", e))?;
+                String::from_utf8(token).map_err(|e| anyhow!(e))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct PulsarAdminClient {
     pub(crate) base_path: String,
+    auth: Option<PulsarAdminAuth>,
 }
 
 impl PulsarAdminClient {
     pub fn new(base_path: String) -> Self {
         Self {
             base_path: base_path.trim_end_matches('/').to_string(),
+            auth: None,
         }
     }
+
+    pub fn with_auth(mut self, auth: Option<PulsarAdminAuth>) -> Self {
+        self.auth = auth;
+        self
+    }
 }
 
 impl PulsarAdminClient {
@@ -64,7 +108,17 @@ impl PulsarAdminClient {
         );
 
         let url: Uri = url.parse()?;
-        client.get(url).await.map_err(|e| anyhow!(e))
+        let mut request = Request::get(url);
+        if let Some(auth) = &self.auth {
+            request = request.header(
+                AUTHORIZATION,
+                format!("Bearer {}", auth.bearer_token().await?),
+            );
+        }
+        client
+            .request(request.body(Body::empty())?)
+            .await
+            .map_err(|e| anyhow!(e))
     }
 
     pub async fn get<T>(&self, topic: &Topic, api: &str) -> Result<T>
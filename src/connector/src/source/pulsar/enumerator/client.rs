@@ -43,9 +43,8 @@ impl SplitEnumerator for PulsarSplitEnumerator {
     type Split = PulsarSplit;
 
     async fn new(properties: PulsarProperties) -> Result<PulsarSplitEnumerator> {
-        let topic = properties.topic;
-        let admin_url = properties.admin_url;
-        let parsed_topic = parse_topic(&topic)?;
+        let admin_client = properties.build_admin_client().await?;
+        let parsed_topic = parse_topic(&properties.topic)?;
 
         let mut scan_start_offset = match properties
             .scan_startup_mode
@@ -68,7 +67,7 @@ impl SplitEnumerator for PulsarSplitEnumerator {
         }
 
         Ok(PulsarSplitEnumerator {
-            admin_client: PulsarAdminClient::new(admin_url),
+            admin_client,
             topic: parsed_topic,
             start_offset: scan_start_offset,
         })
@@ -147,6 +146,8 @@ mod test {
             service_url: "pulsar://localhost:6650".to_string(),
             scan_startup_mode: Some("earliest".to_string()),
             time_offset: None,
+            auth_token: None,
+            oauth: None,
         };
         let mut enumerator = PulsarSplitEnumerator::new(prop).await.unwrap();
         assert!(enumerator.list_splits().await.is_err());
@@ -163,6 +164,8 @@ mod test {
             service_url: "pulsar://localhost:6650".to_string(),
             scan_startup_mode: Some("earliest".to_string()),
             time_offset: None,
+            auth_token: None,
+            oauth: None,
         };
         let mut enumerator = PulsarSplitEnumerator::new(prop).await.unwrap();
         assert!(enumerator.list_splits().await.is_err());
@@ -183,6 +186,8 @@ mod test {
             service_url: "pulsar://localhost:6650".to_string(),
             scan_startup_mode: Some("earliest".to_string()),
             time_offset: None,
+            auth_token: None,
+            oauth: None,
         };
         let mut enumerator = PulsarSplitEnumerator::new(prop).await.unwrap();
 
@@ -210,6 +215,8 @@ mod test {
             service_url: "pulsar://localhost:6650".to_string(),
             scan_startup_mode: Some("earliest".to_string()),
             time_offset: None,
+            auth_token: None,
+            oauth: None,
         };
         let mut enumerator = PulsarSplitEnumerator::new(prop).await.unwrap();
 
@@ -18,10 +18,15 @@ pub mod source;
 pub mod split;
 pub mod topic;
 
+use anyhow::{anyhow, Result};
 pub use enumerator::*;
+use pulsar::authentication::oauth2::{OAuth2Authentication, OAuth2Params};
+use pulsar::{Authentication, Pulsar, TokioExecutor};
 use serde::Deserialize;
 pub use split::*;
 
+use crate::source::pulsar::admin::{PulsarAdminAuth, PulsarAdminClient};
+
 pub const PULSAR_CONNECTOR: &str = "pulsar";
 
 #[derive(Clone, Debug, Deserialize)]
@@ -40,4 +45,72 @@ pub struct PulsarProperties {
 
     #[serde(rename = "scan.startup.timestamp_millis", alias = "pulsar.time.offset")]
     pub time_offset: Option<String>,
+
+    #[serde(rename = "auth.token", alias = "pulsar.auth.token")]
+    pub auth_token: Option<String>,
+
+    #[serde(flatten)]
+    pub oauth: Option<PulsarOauth>,
+}
+
+/// The OAuth 2.0 client credentials to authenticate with, which take precedence over
+/// `auth.token`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PulsarOauth {
+    #[serde(rename = "oauth.issuer.url")]
+    pub issuer_url: String,
+
+    /// The url of the json credentials file, e.g. `file:///path/to/credentials.json`.
+    #[serde(rename = "oauth.credentials.url")]
+    pub credentials_url: String,
+
+    #[serde(rename = "oauth.audience")]
+    pub audience: String,
+
+    #[serde(rename = "oauth.scope")]
+    pub scope: Option<String>,
+}
+
+impl PulsarOauth {
+    fn authentication(&self) -> Box<dyn pulsar::authentication::Authentication> {
+        OAuth2Authentication::client_credentials(OAuth2Params {
+            issuer_url: self.issuer_url.clone(),
+            credentials_url: self.credentials_url.clone(),
+            audience: Some(self.audience.clone()),
+            scope: self.scope.clone(),
+        })
+    }
+}
+
+impl PulsarProperties {
+    /// Connects to the pulsar brokers with the configured authentication.
+    pub async fn build_pulsar_client(&self) -> Result<Pulsar<TokioExecutor>> {
+        let mut builder = Pulsar::builder(&self.service_url, TokioExecutor);
+        if let Some(oauth) = &self.oauth {
+            builder = builder.with_auth_provider(oauth.authentication());
+        } else if let Some(token) = &self.auth_token {
+            builder = builder.with_auth(Authentication {
+                name: "token".to_string(),
+                data: token.clone().into_bytes(),
+            });
+        }
+        builder.build().await.map_err(|e| anyhow!(e))
+    }
+
+    /// Creates the client of the admin REST API with the configured authentication.
+    pub async fn build_admin_client(&self) -> Result<PulsarAdminClient> {
+        let auth = if let Some(oauth) = &self.oauth {
+            let mut authentication = oauth.authentication();
+            authentication
+                .initialize()
+                .await
+                .map_err(|e| anyhow!("failed to initialize pulsar oauth: {:?}", e))?;
+            Some(PulsarAdminAuth::OAuth(tokio::sync::Mutex::new(
+                authentication,
+            )))
+        } else {
+            self.auth_token.clone().map(PulsarAdminAuth::Token)
+        };
+        Ok(PulsarAdminClient::new(self.admin_url.clone()).with_auth(auth))
+    }
 }
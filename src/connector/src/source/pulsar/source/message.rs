@@ -14,11 +14,16 @@
 
 use pulsar::consumer::Message;
 
-use crate::source::SourceMessage;
+use crate::source::{SourceMessage, SourceMeta};
 
 impl From<Message<Vec<u8>>> for SourceMessage {
     fn from(msg: Message<Vec<u8>>) -> Self {
         let message_id = msg.message_id.id;
+        let meta = SourceMeta {
+            timestamp: Some(msg.payload.metadata.publish_time as i64),
+            key: msg.payload.metadata.partition_key.clone().map(Into::into),
+            ..Default::default()
+        };
 
         SourceMessage {
             payload: Some(msg.payload.data.into()),
@@ -30,6 +35,7 @@ impl From<Message<Vec<u8>>> for SourceMessage {
                 message_id.batch_index.unwrap_or(-1)
             ),
             split_id: msg.topic.into(),
+            meta,
         }
     }
 }
@@ -36,6 +36,15 @@ pub struct PulsarSplitReader {
     split: PulsarSplit,
 }
 
+/// Parses the part of a message id, where the negative values written by [`SourceMessage`] stand
+/// for the absence.
+fn parse_optional_id(id: &str, name: &str) -> Result<Option<i32>> {
+    let id = id
+        .parse::<i32>()
+        .map_err(|e| anyhow!("illegal {} {}", name, e))?;
+    Ok((id >= 0).then_some(id))
+}
+
 // {ledger_id}:{entry_id}:{partition}:{batch_index}
 fn parse_message_id(id: &str) -> Result<MessageIdData> {
     let splits = id.split(':').collect_vec();
@@ -62,17 +71,11 @@ fn parse_message_id(id: &str) -> Result<MessageIdData> {
     };
 
     if splits.len() > 2 {
-        let partition = splits[2]
-            .parse::<i32>()
-            .map_err(|e| anyhow!("illegal partition {}", e))?;
-        message_id.partition = Some(partition);
+        message_id.partition = parse_optional_id(splits[2], "partition")?;
     }
 
     if splits.len() == 4 {
-        let batch_index = splits[3]
-            .parse::<i32>()
-            .map_err(|e| anyhow!("illegal batch index {}", e))?;
-        message_id.batch_index = Some(batch_index);
+        message_id.batch_index = parse_optional_id(splits[3], "batch index")?;
     }
 
     Ok(message_id)
@@ -91,15 +94,11 @@ impl SplitReader for PulsarSplitReader {
         ensure!(splits.len() == 1, "only support single split");
         let split = try_match_expand!(splits.into_iter().next().unwrap(), SplitImpl::Pulsar)?;
 
-        let service_url = &props.service_url;
         let topic = split.topic.to_string();
 
         tracing::debug!("creating consumer for pulsar split topic {}", topic,);
 
-        let pulsar: Pulsar<_> = Pulsar::builder(service_url, TokioExecutor)
-            .build()
-            .await
-            .map_err(|e| anyhow!(e))?;
+        let pulsar = props.build_pulsar_client().await?;
 
         let builder: ConsumerBuilder<TokioExecutor> = pulsar
             .consumer()
@@ -122,20 +121,17 @@ impl SplitReader for PulsarSplitReader {
             ),
             PulsarEnumeratorOffset::MessageId(m) => builder.with_options(pulsar::ConsumerOptions {
                 durable: Some(false),
-                start_message_id: parse_message_id(m.as_str()).ok(),
+                start_message_id: Some(parse_message_id(m.as_str())?),
                 ..Default::default()
             }),
-
-            PulsarEnumeratorOffset::Timestamp(_) => builder,
+            // The consumer can't seek by time yet, so the messages published before the timestamp
+            // are skipped by the reader.
+            PulsarEnumeratorOffset::Timestamp(_) => builder.with_options(
+                ConsumerOptions::default().with_initial_position(InitialPosition::Earliest),
+            ),
         };
 
         let consumer: Consumer<Vec<u8>, _> = builder.build().await?;
-        if let PulsarEnumeratorOffset::Timestamp(_ts) = split.start_offset {
-            // FIXME: Here we need pulsar-rs to support the send + sync consumer
-            // consumer
-            //     .seek(None, None, Some(ts as u64), pulsar.clone())
-            //     .await?;
-        }
 
         Ok(Self {
             pulsar,
@@ -152,13 +148,47 @@ impl SplitReader for PulsarSplitReader {
 impl PulsarSplitReader {
     #[try_stream(boxed, ok = Vec<SourceMessage>, error = anyhow::Error)]
     pub async fn into_stream(self) {
+        let mut start_time = match self.split.start_offset {
+            PulsarEnumeratorOffset::Timestamp(ts) => Some(ts as u64),
+            _ => None,
+        };
         #[for_await]
         for msgs in self.consumer.ready_chunks(MAX_CHUNK_SIZE) {
             let mut res = Vec::with_capacity(msgs.len());
             for msg in msgs {
-                res.push(SourceMessage::from(msg?));
+                let msg = msg?;
+                if let Some(ts) = start_time {
+                    // Once a message is read, the later ones are read whenever they are published.
+                    if msg.payload.metadata.publish_time < ts {
+                        continue;
+                    }
+                    start_time = None;
+                }
+                res.push(SourceMessage::from(msg));
+            }
+            if !res.is_empty() {
+                yield res;
             }
-            yield res;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message_id() {
+        let message_id = parse_message_id("12:34:1:-1").unwrap();
+        assert_eq!(message_id.ledger_id, 12);
+        assert_eq!(message_id.entry_id, 34);
+        assert_eq!(message_id.partition, Some(1));
+        assert_eq!(message_id.batch_index, None);
+
+        let message_id = parse_message_id("12:34:-1:-1").unwrap();
+        assert_eq!(message_id.partition, None);
+
+        assert!(parse_message_id("12").is_err());
+        assert!(parse_message_id("12:x").is_err());
+    }
+}
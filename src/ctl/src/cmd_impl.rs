@@ -13,8 +13,11 @@
 // limitations under the License.
 
 pub mod bench;
+pub mod debug;
 pub mod hummock;
 pub mod meta;
 pub mod profile;
+pub mod scale;
 pub mod table;
+pub mod telemetry;
 pub mod trace;
@@ -0,0 +1,37 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use risingwave_pb::common::WorkerType;
+
+use crate::cmd_impl::trace::{collect_traces, print_traces};
+use crate::common::MetaServiceOpts;
+
+/// Dump the await trees of the actors, the RPCs and the storage upload tasks running on the
+/// given compute node, which helps to find out where the streaming graph gets stuck.
+pub async fn dump_await_tree(worker_id: u32) -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+
+    let workers = meta_client.get_cluster_info().await?.worker_nodes;
+    let compute_node = workers
+        .into_iter()
+        .find(|w| w.id == worker_id && w.r#type() == WorkerType::ComputeNode)
+        .ok_or_else(|| anyhow!("compute node {} not found", worker_id))?;
+
+    let traces = collect_traces([compute_node]).await?;
+    print_traces(traces);
+
+    Ok(())
+}
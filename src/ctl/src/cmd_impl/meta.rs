@@ -13,9 +13,11 @@
 // limitations under the License.
 
 mod cluster_info;
+mod cordon;
 mod pause_resume;
 mod reschedule;
 
 pub use cluster_info::*;
+pub use cordon::*;
 pub use pause_resume::*;
 pub use reschedule::*;
@@ -0,0 +1,75 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::Result;
+use risingwave_pb::meta::GetClusterInfoResponse;
+
+use crate::cmd_impl::scale::scale;
+use crate::common::MetaServiceOpts;
+
+pub async fn cordon(worker_ids: Vec<u32>) -> Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    meta_client.update_schedulability(&worker_ids, true).await?;
+    println!("Cordoned compute nodes {:?}", worker_ids);
+    Ok(())
+}
+
+pub async fn uncordon(worker_ids: Vec<u32>) -> Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    meta_client
+        .update_schedulability(&worker_ids, false)
+        .await?;
+    println!("Uncordoned compute nodes {:?}", worker_ids);
+    Ok(())
+}
+
+/// Cordons the compute node and migrates all its actors to other compute nodes. The compute node
+/// is safe to stop once no actor is left on it.
+pub async fn drain(worker_id: u32, dry_run: bool) -> Result<()> {
+    if !dry_run {
+        cordon(vec![worker_id]).await?;
+    }
+    scale(vec![], vec![worker_id], vec![], dry_run).await?;
+    if dry_run {
+        return Ok(());
+    }
+
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    let GetClusterInfoResponse {
+        table_fragments, ..
+    } = meta_client.get_cluster_info().await?;
+    let remaining_actors = table_fragments
+        .iter()
+        .flat_map(|table_fragment| table_fragment.actor_status.iter())
+        .filter(|(_, status)| {
+            status
+                .parallel_unit
+                .as_ref()
+                .map_or(false, |pu| pu.worker_node_id == worker_id)
+        })
+        .map(|(actor_id, _)| *actor_id)
+        .collect::<Vec<_>>();
+    if remaining_actors.is_empty() {
+        println!("Compute node {} is drained and safe to stop", worker_id);
+    } else {
+        println!(
+            "Compute node {} is cordoned, but actors {:?} can not be migrated away",
+            worker_id, remaining_actors
+        );
+    }
+    Ok(())
+}
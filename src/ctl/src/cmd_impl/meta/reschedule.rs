@@ -17,6 +17,7 @@ use std::collections::HashMap;
 use anyhow::{anyhow, Error, Result};
 use regex::{Match, Regex};
 use risingwave_pb::meta::reschedule_request::Reschedule;
+use risingwave_rpc_client::MetaClient;
 
 use crate::common::MetaServiceOpts;
 
@@ -100,6 +101,15 @@ pub async fn reschedule(mut plan: String, dry_run: bool) -> Result<()> {
         }
     }
 
+    apply_reschedules(&meta_client, reschedules, dry_run).await
+}
+
+/// Prints the reschedule plan and sends it to meta unless `dry_run` is set.
+pub(crate) async fn apply_reschedules(
+    meta_client: &MetaClient,
+    reschedules: HashMap<u32, Reschedule>,
+    dry_run: bool,
+) -> Result<()> {
     for (fragment_id, reschedule) in &reschedules {
         println!("For fragment #{}", fragment_id);
         if !reschedule.removed_parallel_units.is_empty() {
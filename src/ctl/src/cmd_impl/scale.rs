@@ -0,0 +1,387 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+use risingwave_pb::common::WorkerNode;
+use risingwave_pb::meta::reschedule_request::Reschedule;
+use risingwave_pb::meta::table_fragments::fragment::FragmentDistributionType;
+use risingwave_pb::meta::table_fragments::{Fragment, State};
+use risingwave_pb::meta::{GetClusterInfoResponse, TableFragments};
+use risingwave_pb::stream_plan::stream_node::NodeBody;
+use risingwave_pb::stream_plan::{FragmentType, StreamNode};
+
+use crate::cmd_impl::meta::apply_reschedules;
+use crate::common::MetaServiceOpts;
+
+/// Scales the streaming jobs in or out by workers: actors are added on all parallel units of
+/// `include_workers`, and removed from all parallel units of `exclude_workers`. If
+/// `fragment_ids` is empty, all fragments that can be rescheduled are affected.
+pub async fn scale(
+    include_workers: Vec<u32>,
+    exclude_workers: Vec<u32>,
+    fragment_ids: Vec<u32>,
+    dry_run: bool,
+) -> Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+
+    let GetClusterInfoResponse {
+        worker_nodes,
+        table_fragments,
+        ..
+    } = meta_client.get_cluster_info().await?;
+
+    let reschedules = plan_reschedules(
+        &worker_nodes,
+        &table_fragments,
+        &include_workers,
+        &exclude_workers,
+        &fragment_ids,
+    )?;
+    if reschedules.is_empty() {
+        println!("No fragment needs to be rescheduled");
+        return Ok(());
+    }
+
+    apply_reschedules(&meta_client, reschedules, dry_run).await
+}
+
+/// Computes the reschedule plan of each affected fragment from the cluster info.
+fn plan_reschedules(
+    worker_nodes: &[WorkerNode],
+    table_fragments: &[TableFragments],
+    include_workers: &[u32],
+    exclude_workers: &[u32],
+    fragment_ids: &[u32],
+) -> Result<HashMap<u32, Reschedule>> {
+    let worker_ids: HashSet<_> = worker_nodes.iter().map(|worker| worker.id).collect();
+    for worker_id in include_workers.iter().chain(exclude_workers.iter()) {
+        if !worker_ids.contains(worker_id) {
+            bail!("compute node {} not found", worker_id);
+        }
+    }
+    if let Some(worker_id) = include_workers
+        .iter()
+        .find(|worker_id| exclude_workers.contains(worker_id))
+    {
+        bail!("compute node {} is both included and excluded", worker_id);
+    }
+
+    if let Some(worker) = worker_nodes
+        .iter()
+        .find(|worker| worker.unschedulable && include_workers.contains(&worker.id))
+    {
+        bail!("compute node {} is cordoned", worker.id);
+    }
+
+    let parallel_units_of = |workers: &[u32]| -> BTreeSet<u32> {
+        worker_nodes
+            .iter()
+            .filter(|worker| workers.contains(&worker.id))
+            .flat_map(|worker| worker.parallel_units.iter().map(|pu| pu.id))
+            .collect()
+    };
+    let included_parallel_units = parallel_units_of(include_workers);
+    let excluded_parallel_units = parallel_units_of(exclude_workers);
+    let available_parallel_units = parallel_units_of(
+        &worker_nodes
+            .iter()
+            .filter(|worker| !worker.unschedulable && !exclude_workers.contains(&worker.id))
+            .map(|worker| worker.id)
+            .collect_vec(),
+    );
+
+    let mut reschedules = HashMap::new();
+    for table_fragment in table_fragments {
+        if table_fragment.state() != State::Created {
+            continue;
+        }
+        for (fragment_id, fragment) in &table_fragment.fragments {
+            if !fragment_ids.is_empty() && !fragment_ids.contains(fragment_id) {
+                continue;
+            }
+            if !is_reschedulable(fragment) {
+                if !fragment_ids.is_empty() {
+                    bail!("fragment {} can not be rescheduled", fragment_id);
+                }
+                continue;
+            }
+
+            let current_parallel_units: BTreeSet<_> = fragment
+                .actors
+                .iter()
+                .map(|actor| {
+                    table_fragment
+                        .actor_status
+                        .get(&actor.actor_id)
+                        .and_then(|status| status.parallel_unit.as_ref())
+                        .map(|parallel_unit| parallel_unit.id)
+                        .ok_or_else(|| {
+                            anyhow!("parallel unit of actor {} not found", actor.actor_id)
+                        })
+                })
+                .collect::<Result<_>>()?;
+
+            let (added_parallel_units, removed_parallel_units) = match fragment.distribution_type()
+            {
+                FragmentDistributionType::Single => {
+                    // Singleton fragments can only be migrated.
+                    let current = match current_parallel_units.iter().exactly_one() {
+                        Ok(current) => *current,
+                        Err(_) => bail!("singleton fragment {} has multiple actors", fragment_id),
+                    };
+                    if !excluded_parallel_units.contains(&current) {
+                        continue;
+                    }
+                    let target = included_parallel_units
+                        .iter()
+                        .chain(available_parallel_units.iter())
+                        .next();
+                    match target {
+                        Some(target) => (vec![*target], vec![current]),
+                        None => bail!("no parallel unit available for fragment {}", fragment_id),
+                    }
+                }
+                FragmentDistributionType::Hash => {
+                    let added = included_parallel_units
+                        .difference(&current_parallel_units)
+                        .cloned()
+                        .collect_vec();
+                    let removed = current_parallel_units
+                        .intersection(&excluded_parallel_units)
+                        .cloned()
+                        .collect_vec();
+                    if added.is_empty() && removed.len() == current_parallel_units.len() {
+                        bail!("no actor would be left for fragment {}", fragment_id);
+                    }
+                    (added, removed)
+                }
+                FragmentDistributionType::Unspecified => {
+                    bail!(
+                        "distribution type of fragment {} is unspecified",
+                        fragment_id
+                    )
+                }
+            };
+
+            if !(added_parallel_units.is_empty() && removed_parallel_units.is_empty()) {
+                reschedules.insert(
+                    *fragment_id,
+                    Reschedule {
+                        added_parallel_units,
+                        removed_parallel_units,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(reschedules)
+}
+
+/// Fragments with `Chain`, or materialized views with downstream, are not supported by reschedule
+/// yet.
+fn is_reschedulable(fragment: &Fragment) -> bool {
+    fn contains_chain(node: &StreamNode) -> bool {
+        matches!(node.node_body, Some(NodeBody::Chain(_))) || node.input.iter().any(contains_chain)
+    }
+
+    let actor = match fragment.actors.first() {
+        Some(actor) => actor,
+        None => return false,
+    };
+    if actor.nodes.as_ref().map_or(false, contains_chain) {
+        return false;
+    }
+    !(fragment.fragment_type() == FragmentType::Sink
+        && fragment
+            .actors
+            .iter()
+            .any(|actor| !actor.dispatcher.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::common::ParallelUnit;
+    use risingwave_pb::meta::table_fragments::ActorStatus;
+    use risingwave_pb::stream_plan::StreamActor;
+
+    use super::*;
+
+    /// Three workers with parallel units `[1, 2]`, `[3, 4]` and `[5, 6]`.
+    fn worker_nodes() -> Vec<WorkerNode> {
+        (1..=3)
+            .map(|id| WorkerNode {
+                id,
+                parallel_units: (id * 2 - 1..=id * 2)
+                    .map(|pu| ParallelUnit {
+                        id: pu,
+                        worker_node_id: id,
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// A created job with a single fragment 1, whose actor `i` runs on `parallel_units[i]`.
+    fn mock_table_fragments(
+        distribution_type: FragmentDistributionType,
+        parallel_units: &[u32],
+    ) -> Vec<TableFragments> {
+        let actors = (0..parallel_units.len() as u32)
+            .map(|actor_id| StreamActor {
+                actor_id,
+                fragment_id: 1,
+                ..Default::default()
+            })
+            .collect();
+        let actor_status = parallel_units
+            .iter()
+            .enumerate()
+            .map(|(actor_id, &pu)| {
+                let status = ActorStatus {
+                    parallel_unit: Some(ParallelUnit {
+                        id: pu,
+                        worker_node_id: (pu + 1) / 2,
+                    }),
+                    ..Default::default()
+                };
+                (actor_id as u32, status)
+            })
+            .collect();
+        let fragment = Fragment {
+            fragment_id: 1,
+            fragment_type: FragmentType::Others as i32,
+            distribution_type: distribution_type as i32,
+            actors,
+            ..Default::default()
+        };
+        vec![TableFragments {
+            table_id: 1,
+            state: State::Created as i32,
+            fragments: [(1, fragment)].into_iter().collect(),
+            actor_status,
+            ..Default::default()
+        }]
+    }
+
+    #[test]
+    fn test_plan_hash_reschedules() {
+        let table_fragments = mock_table_fragments(FragmentDistributionType::Hash, &[1, 2, 3, 4]);
+
+        let reschedules =
+            plan_reschedules(&worker_nodes(), &table_fragments, &[3], &[2], &[]).unwrap();
+        assert_eq!(
+            reschedules[&1],
+            Reschedule {
+                added_parallel_units: vec![5, 6],
+                removed_parallel_units: vec![3, 4],
+            }
+        );
+
+        // Nothing to do if the workers already hold all or none of the actors.
+        let reschedules =
+            plan_reschedules(&worker_nodes(), &table_fragments, &[1], &[3], &[]).unwrap();
+        assert!(reschedules.is_empty());
+
+        // Excluding all workers would leave no actor.
+        assert!(plan_reschedules(&worker_nodes(), &table_fragments, &[], &[1, 2], &[]).is_err());
+    }
+
+    #[test]
+    fn test_plan_singleton_reschedules() {
+        let table_fragments = mock_table_fragments(FragmentDistributionType::Single, &[3]);
+
+        // Singleton fragments are migrated to an included worker first.
+        let reschedules =
+            plan_reschedules(&worker_nodes(), &table_fragments, &[3], &[2], &[]).unwrap();
+        assert_eq!(
+            reschedules[&1],
+            Reschedule {
+                added_parallel_units: vec![5],
+                removed_parallel_units: vec![3],
+            }
+        );
+
+        // Or to any other available worker.
+        let reschedules =
+            plan_reschedules(&worker_nodes(), &table_fragments, &[], &[2], &[]).unwrap();
+        assert_eq!(
+            reschedules[&1],
+            Reschedule {
+                added_parallel_units: vec![1],
+                removed_parallel_units: vec![3],
+            }
+        );
+
+        // And left alone if its worker is not excluded.
+        let reschedules =
+            plan_reschedules(&worker_nodes(), &table_fragments, &[3], &[], &[]).unwrap();
+        assert!(reschedules.is_empty());
+    }
+
+    #[test]
+    fn test_plan_drain_reschedules() {
+        // Draining worker 2 cordons it, then moves all its actors away.
+        let mut worker_nodes = worker_nodes();
+        worker_nodes[1].unschedulable = true;
+
+        let table_fragments = mock_table_fragments(FragmentDistributionType::Hash, &[1, 3, 4, 5]);
+        let reschedules =
+            plan_reschedules(&worker_nodes, &table_fragments, &[], &[2], &[]).unwrap();
+        assert_eq!(
+            reschedules[&1],
+            Reschedule {
+                added_parallel_units: vec![],
+                removed_parallel_units: vec![3, 4],
+            }
+        );
+
+        // Singleton fragments are never migrated to the cordoned worker.
+        let table_fragments = mock_table_fragments(FragmentDistributionType::Single, &[4]);
+        let reschedules =
+            plan_reschedules(&worker_nodes, &table_fragments, &[], &[2], &[]).unwrap();
+        assert_eq!(
+            reschedules[&1],
+            Reschedule {
+                added_parallel_units: vec![1],
+                removed_parallel_units: vec![4],
+            }
+        );
+
+        // A drained worker has nothing left to move.
+        let table_fragments = mock_table_fragments(FragmentDistributionType::Hash, &[1, 5]);
+        let reschedules =
+            plan_reschedules(&worker_nodes, &table_fragments, &[], &[2], &[]).unwrap();
+        assert!(reschedules.is_empty());
+    }
+
+    #[test]
+    fn test_plan_reschedules_with_invalid_input() {
+        let table_fragments = mock_table_fragments(FragmentDistributionType::Unspecified, &[1]);
+        assert!(plan_reschedules(&worker_nodes(), &table_fragments, &[2], &[], &[]).is_err());
+
+        let table_fragments = mock_table_fragments(FragmentDistributionType::Hash, &[1]);
+        assert!(plan_reschedules(&worker_nodes(), &table_fragments, &[4], &[], &[]).is_err());
+        assert!(plan_reschedules(&worker_nodes(), &table_fragments, &[2], &[2], &[]).is_err());
+
+        let mut cordoned = worker_nodes();
+        cordoned[1].unschedulable = true;
+        assert!(plan_reschedules(&cordoned, &table_fragments, &[2], &[], &[]).is_err());
+    }
+}
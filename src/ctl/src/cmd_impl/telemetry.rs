@@ -0,0 +1,28 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::MetaServiceOpts;
+
+pub async fn show() -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    let resp = meta_client.get_telemetry_report().await?;
+    if resp.enabled {
+        println!("Telemetry is enabled. The following report is sent periodically:");
+    } else {
+        println!("Telemetry is disabled. The following report would be sent if enabled:");
+    }
+    println!("{}", resp.report);
+    Ok(())
+}
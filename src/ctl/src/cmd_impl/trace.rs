@@ -15,7 +15,7 @@
 use std::collections::BTreeMap;
 
 use risingwave_common::util::addr::HostAddr;
-use risingwave_pb::common::WorkerType;
+use risingwave_pb::common::{WorkerNode, WorkerType};
 use risingwave_pb::monitor_service::StackTraceResponse;
 use risingwave_rpc_client::ComputeClientPool;
 
@@ -30,10 +30,25 @@ pub async fn trace() -> anyhow::Result<()> {
         .into_iter()
         .filter(|w| w.r#type() == WorkerType::ComputeNode);
 
-    let clients = ComputeClientPool::default();
+    let traces = collect_traces(compute_nodes).await?;
+    print_traces(traces);
+
+    Ok(())
+}
 
-    let mut all_actor_traces = BTreeMap::new();
-    let mut all_rpc_traces = BTreeMap::new();
+/// The stack traces collected from the compute nodes.
+#[derive(Default)]
+pub(crate) struct Traces {
+    actor_traces: BTreeMap<u32, String>,
+    rpc_traces: BTreeMap<String, String>,
+    upload_traces: BTreeMap<String, String>,
+}
+
+pub(crate) async fn collect_traces(
+    compute_nodes: impl IntoIterator<Item = WorkerNode>,
+) -> anyhow::Result<Traces> {
+    let clients = ComputeClientPool::default();
+    let mut traces = Traces::default();
 
     // FIXME: the compute node may not be accessible directly from risectl, we may let the meta
     // service collect the reports from all compute nodes in the future.
@@ -42,29 +57,47 @@ pub async fn trace() -> anyhow::Result<()> {
         let StackTraceResponse {
             actor_traces,
             rpc_traces,
+            upload_traces,
         } = client.stack_trace().await?;
 
-        all_actor_traces.extend(actor_traces);
-        all_rpc_traces.extend(rpc_traces.into_iter().map(|(k, v)| {
-            (
-                format!("{} ({})", HostAddr::from(cn.get_host().unwrap()), k),
-                v,
-            )
-        }));
+        let host = HostAddr::from(cn.get_host().unwrap());
+        traces.actor_traces.extend(actor_traces);
+        traces.rpc_traces.extend(
+            rpc_traces
+                .into_iter()
+                .map(|(k, v)| (format!("{} ({})", host, k), v)),
+        );
+        traces.upload_traces.extend(
+            upload_traces
+                .into_iter()
+                .map(|(k, v)| (format!("{} ({})", host, k), v)),
+        );
     }
 
-    if all_actor_traces.is_empty() && all_rpc_traces.is_empty() {
+    Ok(traces)
+}
+
+pub(crate) fn print_traces(traces: Traces) {
+    let Traces {
+        actor_traces,
+        rpc_traces,
+        upload_traces,
+    } = traces;
+
+    if actor_traces.is_empty() && rpc_traces.is_empty() && upload_traces.is_empty() {
         println!("No traces found. No actors are running, or `--async-stack-trace` not set?");
     } else {
         println!("--- Actor Traces ---");
-        for (key, trace) in all_actor_traces {
+        for (key, trace) in actor_traces {
             println!(">> Actor {key}\n{trace}");
         }
         println!("--- RPC Traces ---");
-        for (key, trace) in all_rpc_traces {
+        for (key, trace) in rpc_traces {
             println!(">> RPC {key}\n{trace}");
         }
+        println!("--- Upload Task Traces ---");
+        for (key, trace) in upload_traces {
+            println!(">> Upload Task {key}\n{trace}");
+        }
     }
-
-    Ok(())
 }
@@ -52,8 +52,30 @@ enum Commands {
     /// Commands for Benchmarks
     #[clap(subcommand)]
     Bench(BenchCommands),
+    /// Commands for Telemetry
+    #[clap(subcommand)]
+    Telemetry(TelemetryCommands),
+    /// Scale the streaming jobs in or out by adding actors on or removing actors from the given
+    /// compute nodes
+    Scale {
+        /// Compute nodes to place actors on, separated by `,`
+        #[clap(long, value_delimiter = ',')]
+        include_workers: Vec<u32>,
+        /// Compute nodes to remove actors from, separated by `,`
+        #[clap(long, value_delimiter = ',')]
+        exclude_workers: Vec<u32>,
+        /// Fragments to reschedule, separated by `,`. All reschedulable fragments if not given.
+        #[clap(long, value_delimiter = ',')]
+        fragments: Vec<u32>,
+        /// Show the plan only, no actual operation
+        #[clap(long)]
+        dry_run: bool,
+    },
     /// Commands for tracing the compute nodes
     Trace,
+    /// Commands for debugging the compute nodes
+    #[clap(subcommand)]
+    Debug(DebugCommands),
     // TODO(yuhao): profile other nodes
     /// Commands for profilng the compute nodes
     Profile {
@@ -132,6 +154,12 @@ enum HummockCommands {
         #[clap(long)]
         max_sub_compaction: Option<u32>,
     },
+    /// Cancel an assigned compaction task, so that its input SSTs can be compacted by another
+    /// compactor.
+    CancelCompactTask {
+        #[clap(short, long)]
+        task_id: u64,
+    },
 }
 
 #[derive(Subcommand)]
@@ -150,6 +178,21 @@ enum TableCommands {
     List,
 }
 
+#[derive(Subcommand)]
+enum DebugCommands {
+    /// dump the await trees of the actors, RPCs and storage upload tasks on a compute node
+    DumpAwaitTree {
+        /// The id of the compute node
+        worker_id: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum TelemetryCommands {
+    /// print the anonymous telemetry report, exactly as it is sent
+    Show,
+}
+
 #[derive(Subcommand)]
 enum MetaCommands {
     /// pause the stream graph
@@ -181,6 +224,28 @@ enum MetaCommands {
         #[clap(long)]
         dry_run: bool,
     },
+    /// Stop scheduling new actors and batch tasks to the compute nodes
+    Cordon {
+        /// Compute nodes to cordon, separated by `,`
+        #[clap(long, value_delimiter = ',')]
+        workers: Vec<u32>,
+    },
+    /// Resume scheduling new actors and batch tasks to the compute nodes
+    Uncordon {
+        /// Compute nodes to uncordon, separated by `,`
+        #[clap(long, value_delimiter = ',')]
+        workers: Vec<u32>,
+    },
+    /// Cordon the compute node and migrate its actors away, so that it can be safely stopped for
+    /// maintenance
+    Drain {
+        /// Compute node to drain
+        #[clap(long)]
+        worker: u32,
+        /// Show the plan only, no actual operation
+        #[clap(long)]
+        dry_run: bool,
+    },
 }
 
 pub async fn start(opts: CliOpts) -> Result<()> {
@@ -247,6 +312,9 @@ pub async fn start(opts: CliOpts) -> Result<()> {
             )
             .await?
         }
+        Commands::Hummock(HummockCommands::CancelCompactTask { task_id }) => {
+            cmd_impl::hummock::cancel_compact_task(task_id).await?
+        }
         Commands::Table(TableCommands::Scan { mv_name }) => cmd_impl::table::scan(mv_name).await?,
         Commands::Table(TableCommands::ScanById { table_id }) => {
             cmd_impl::table::scan_id(table_id).await?
@@ -259,7 +327,24 @@ pub async fn start(opts: CliOpts) -> Result<()> {
         Commands::Meta(MetaCommands::Reschedule { plan, dry_run }) => {
             cmd_impl::meta::reschedule(plan, dry_run).await?
         }
+        Commands::Scale {
+            include_workers,
+            exclude_workers,
+            fragments,
+            dry_run,
+        } => cmd_impl::scale::scale(include_workers, exclude_workers, fragments, dry_run).await?,
+        Commands::Meta(MetaCommands::Cordon { workers }) => cmd_impl::meta::cordon(workers).await?,
+        Commands::Meta(MetaCommands::Uncordon { workers }) => {
+            cmd_impl::meta::uncordon(workers).await?
+        }
+        Commands::Meta(MetaCommands::Drain { worker, dry_run }) => {
+            cmd_impl::meta::drain(worker, dry_run).await?
+        }
+        Commands::Telemetry(TelemetryCommands::Show) => cmd_impl::telemetry::show().await?,
         Commands::Trace => cmd_impl::trace::trace().await?,
+        Commands::Debug(DebugCommands::DumpAwaitTree { worker_id }) => {
+            cmd_impl::debug::dump_await_tree(worker_id).await?
+        }
         Commands::Profile { sleep } => cmd_impl::profile::profile(sleep).await?,
     }
     Ok(())
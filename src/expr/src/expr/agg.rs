@@ -34,6 +34,11 @@ pub enum AggKind {
     ApproxCountDistinct,
     ArrayAgg,
     FirstValue,
+    LastValue,
+    /// Merges the sketches of a partial `approx_count_distinct` in two-phase aggregation.
+    ApproxCountDistinctMerge,
+    PercentileCont,
+    PercentileDisc,
 }
 
 impl TryFrom<Type> for AggKind {
@@ -51,6 +56,10 @@ impl TryFrom<Type> for AggKind {
             Type::ApproxCountDistinct => Ok(AggKind::ApproxCountDistinct),
             Type::ArrayAgg => Ok(AggKind::ArrayAgg),
             Type::FirstValue => Ok(AggKind::FirstValue),
+            Type::LastValue => Ok(AggKind::LastValue),
+            Type::ApproxCountDistinctMerge => Ok(AggKind::ApproxCountDistinctMerge),
+            Type::PercentileCont => Ok(AggKind::PercentileCont),
+            Type::PercentileDisc => Ok(AggKind::PercentileDisc),
             Type::Unspecified => bail!("Unrecognized agg."),
         }
     }
@@ -69,6 +78,10 @@ impl AggKind {
             Self::ApproxCountDistinct => Type::ApproxCountDistinct,
             Self::ArrayAgg => Type::ArrayAgg,
             Self::FirstValue => Type::FirstValue,
+            Self::LastValue => Type::LastValue,
+            Self::ApproxCountDistinctMerge => Type::ApproxCountDistinctMerge,
+            Self::PercentileCont => Type::PercentileCont,
+            Self::PercentileDisc => Type::PercentileDisc,
         }
     }
 }
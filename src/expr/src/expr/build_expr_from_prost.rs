@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use risingwave_common::array::{I64Array, NaiveDateTimeArray, Utf8Array};
 use risingwave_common::types::DataType;
 use risingwave_common::util::value_encoding::deserialize_datum;
 use risingwave_pb::expr::expr_node::RexNode;
@@ -32,7 +33,9 @@ use crate::expr::expr_to_char_const_tmpl::{ExprToCharConstTmpl, ExprToCharConstT
 use crate::expr::expr_unary::{
     new_length_default, new_ltrim_expr, new_rtrim_expr, new_trim_expr, new_unary_expr,
 };
+use crate::expr::template::{BinaryExpression, TernaryExpression};
 use crate::expr::{build_from_prost as expr_build_from_prost, BoxedExpression, Expression};
+use crate::vector_op::date_trunc::{date_trunc_timestamp, date_trunc_timestampz};
 use crate::vector_op::to_char::compile_pattern_to_chrono;
 use crate::{bail, ensure, Result};
 
@@ -120,6 +123,38 @@ pub fn build_substr_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     }
 }
 
+pub fn build_date_trunc_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let (children, ret_type) = get_children_and_return_type(prost)?;
+    ensure!(children.len() == 2 || children.len() == 3);
+    let field = expr_build_from_prost(&children[0])?;
+    let source = expr_build_from_prost(&children[1])?;
+    if children.len() == 2 {
+        Ok(
+            BinaryExpression::<Utf8Array, NaiveDateTimeArray, NaiveDateTimeArray, _>::new(
+                field,
+                source,
+                ret_type,
+                date_trunc_timestamp,
+            )
+            .boxed(),
+        )
+    } else {
+        // The time zone is always specified for `timestamp with time zone`, which is the session
+        // TimeZone when omitted by the user.
+        let time_zone = expr_build_from_prost(&children[2])?;
+        Ok(
+            TernaryExpression::<Utf8Array, I64Array, Utf8Array, I64Array, _>::new(
+                field,
+                source,
+                time_zone,
+                ret_type,
+                date_trunc_timestampz,
+            )
+            .boxed(),
+        )
+    }
+}
+
 pub fn build_trim_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     let (children, ret_type) = get_children_and_return_type(prost)?;
     ensure!(!children.is_empty() && children.len() <= 2);
@@ -31,7 +31,10 @@ use crate::vector_op::extract::{
 use crate::vector_op::like::like_default;
 use crate::vector_op::position::position;
 use crate::vector_op::round::round_digits;
-use crate::vector_op::timestampz::{timestamp_at_time_zone, timestampz_at_time_zone};
+use crate::vector_op::timestampz::{
+    str_to_timestampz_in_time_zone, timestamp_at_time_zone, timestampz_at_time_zone,
+    timestampz_to_string_in_time_zone,
+};
 use crate::vector_op::tumble::{
     tumble_start_date, tumble_start_date_time, tumble_start_timestampz,
 };
@@ -383,6 +386,38 @@ fn build_at_time_zone_expr(
     Ok(expr)
 }
 
+fn build_cast_with_time_zone_expr(
+    ret: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match (l.return_type(), &ret) {
+        (DataType::Varchar, DataType::Timestampz) => {
+            Box::new(BinaryExpression::<Utf8Array, Utf8Array, I64Array, _>::new(
+                l,
+                r,
+                ret,
+                str_to_timestampz_in_time_zone,
+            ))
+        }
+        (DataType::Timestampz, DataType::Varchar) => {
+            Box::new(BinaryExpression::<I64Array, Utf8Array, Utf8Array, _>::new(
+                l,
+                r,
+                ret,
+                timestampz_to_string_in_time_zone,
+            ))
+        }
+        (input, ret) => {
+            return Err(ExprError::UnsupportedFunction(format!(
+                "cast {:?} to {:?} with time zone is not supported yet!",
+                input, ret
+            )))
+        }
+    };
+    Ok(expr)
+}
+
 pub fn new_binary_expr(
     expr_type: Type,
     ret: DataType,
@@ -545,6 +580,7 @@ pub fn new_binary_expr(
         }
         Type::Extract => build_extract_expr(ret, l, r)?,
         Type::AtTimeZone => build_at_time_zone_expr(ret, l, r)?,
+        Type::CastWithTimeZone => build_cast_with_time_zone_expr(ret, l, r)?,
         Type::RoundDigit => Box::new(
             BinaryExpression::<DecimalArray, I32Array, DecimalArray, _>::new(
                 l,
@@ -22,10 +22,15 @@ use super::BoxedExpression;
 use crate::expr::template::BinaryNullableExpression;
 use crate::vector_op::array_access::array_access;
 use crate::vector_op::cmp::{
-    general_is_distinct_from, general_is_not_distinct_from, str_is_distinct_from,
-    str_is_not_distinct_from,
+    general_is_distinct_from, general_is_not_distinct_from, list_is_distinct_from,
+    list_is_not_distinct_from, str_is_distinct_from, str_is_not_distinct_from,
+    struct_is_distinct_from, struct_is_not_distinct_from,
 };
 use crate::vector_op::conjunction::{and, or};
+use crate::vector_op::jsonb_access::{
+    jsonb_array_element, jsonb_array_element_text, jsonb_extract_path, jsonb_object_field,
+    jsonb_object_field_text,
+};
 use crate::{for_all_cmp_variants, ExprError, Result};
 
 macro_rules! gen_nullable_cmp_impl {
@@ -78,6 +83,15 @@ pub fn new_nullable_binary_expr(
         ),
         Type::IsDistinctFrom => new_distinct_from_expr(l, r, ret)?,
         Type::IsNotDistinctFrom => new_not_distinct_from_expr(l, r, ret)?,
+        Type::JsonbAccessInner | Type::JsonbAccessStr => {
+            build_jsonb_access_expr(expr_type, ret, l, r)?
+        }
+        Type::JsonbAccessPath => Box::new(BinaryNullableExpression::<
+            JsonbArray,
+            ListArray,
+            JsonbArray,
+            _,
+        >::new(l, r, ret, jsonb_extract_path)),
         tp => {
             return Err(ExprError::UnsupportedFunction(format!(
                 "{:?}({:?}, {:?})",
@@ -122,11 +136,63 @@ fn build_array_access_expr(
         DataType::Timestamp => array_access_expression!(NaiveDateTimeArray),
         DataType::Timestampz => array_access_expression!(PrimitiveArray::<i64>),
         DataType::Interval => array_access_expression!(IntervalArray),
+        DataType::Jsonb => array_access_expression!(JsonbArray),
         DataType::Struct { .. } => array_access_expression!(StructArray),
         DataType::List { .. } => array_access_expression!(ListArray),
     }
 }
 
+fn build_jsonb_access_expr(
+    expr_type: Type,
+    ret: DataType,
+    l: BoxedExpression,
+    r: BoxedExpression,
+) -> Result<BoxedExpression> {
+    let expr: BoxedExpression = match (expr_type, r.return_type()) {
+        (Type::JsonbAccessInner, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_object_field))
+        }
+        (Type::JsonbAccessInner, DataType::Int32) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                I32Array,
+                JsonbArray,
+                _,
+            >::new(l, r, ret, jsonb_array_element))
+        }
+        (Type::JsonbAccessStr, DataType::Varchar) => {
+            Box::new(BinaryNullableExpression::<
+                JsonbArray,
+                Utf8Array,
+                Utf8Array,
+                _,
+            >::new(l, r, ret, jsonb_object_field_text))
+        }
+        (Type::JsonbAccessStr, DataType::Int32) => Box::new(BinaryNullableExpression::<
+            JsonbArray,
+            I32Array,
+            Utf8Array,
+            _,
+        >::new(
+            l, r, ret, jsonb_array_element_text
+        )),
+        (tp, r_type) => {
+            return Err(ExprError::UnsupportedFunction(format!(
+                "{:?}({:?}, {:?})",
+                tp,
+                l.return_type(),
+                r_type,
+            )));
+        }
+    };
+    Ok(expr)
+}
+
 pub fn new_distinct_from_expr(
     l: BoxedExpression,
     r: BoxedExpression,
@@ -143,6 +209,24 @@ pub fn new_distinct_from_expr(
         >::new(
             l, r, ret, str_is_distinct_from
         )),
+        (DataType::Struct { .. }, DataType::Struct { .. }) => {
+            Box::new(BinaryNullableExpression::<
+                StructArray,
+                StructArray,
+                BoolArray,
+                _,
+            >::new(l, r, ret, struct_is_distinct_from))
+        }
+        (DataType::List { .. }, DataType::List { .. }) => {
+            Box::new(
+                BinaryNullableExpression::<ListArray, ListArray, BoolArray, _>::new(
+                    l,
+                    r,
+                    ret,
+                    list_is_distinct_from,
+                ),
+            )
+        }
         _ => {
             for_all_cmp_variants! {gen_nullable_cmp_impl, l, r, ret, general_is_distinct_from}
         }
@@ -166,6 +250,24 @@ pub fn new_not_distinct_from_expr(
         >::new(
             l, r, ret, str_is_not_distinct_from
         )),
+        (DataType::Struct { .. }, DataType::Struct { .. }) => {
+            Box::new(BinaryNullableExpression::<
+                StructArray,
+                StructArray,
+                BoolArray,
+                _,
+            >::new(l, r, ret, struct_is_not_distinct_from))
+        }
+        (DataType::List { .. }, DataType::List { .. }) => {
+            Box::new(
+                BinaryNullableExpression::<ListArray, ListArray, BoolArray, _>::new(
+                    l,
+                    r,
+                    ret,
+                    list_is_not_distinct_from,
+                ),
+            )
+        }
         _ => {
             for_all_cmp_variants! {gen_nullable_cmp_impl, l, r, ret, general_is_not_distinct_from}
         }
@@ -0,0 +1,145 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{ArrayRef, DataChunk, Row};
+use risingwave_common::types::{DataType, Datum};
+use risingwave_pb::expr::expr_node::{RexNode, Type};
+use risingwave_pb::expr::ExprNode;
+use risingwave_rpc_client::UdfClient;
+
+use crate::expr::expr_wasm_udf::WasmUdfExpression;
+use crate::expr::{build_from_prost, BoxedExpression, Expression};
+use crate::{bail, ensure, ExprError, Result};
+
+/// A call to a user-defined function, evaluated by an external UDF server.
+///
+/// The arguments of all rows in a chunk are sent to the server in a single request. Executors
+/// should call [`Expression::eval_async`], as [`Expression::eval`] blocks the current thread until
+/// the server responds.
+pub struct UdfExpression {
+    name: String,
+    identifier: String,
+    children: Vec<BoxedExpression>,
+    return_type: DataType,
+    client: Arc<UdfClient>,
+}
+
+impl std::fmt::Debug for UdfExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UdfExpression")
+            .field("name", &self.name)
+            .field("identifier", &self.identifier)
+            .field("children", &self.children)
+            .field("return_type", &self.return_type)
+            .finish()
+    }
+}
+
+#[async_trait::async_trait]
+impl Expression for UdfExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let args = self.eval_args(input)?;
+        self.call_blocking(&args)
+    }
+
+    async fn eval_async(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let args = self.eval_args(input)?;
+        self.call(&args).await
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let datums = self
+            .children
+            .iter()
+            .map(|c| c.eval_row(input))
+            .collect::<Result<Vec<_>>>()?;
+        let data_types: Vec<_> = self.children.iter().map(|c| c.return_type()).collect();
+        let args = DataChunk::from_rows(&[Row::new(datums)], &data_types);
+        let output = self.call_blocking(&args)?;
+        Ok(output.datum_at(0))
+    }
+}
+
+impl UdfExpression {
+    /// Evaluates the arguments, one column for each.
+    fn eval_args(&self, input: &DataChunk) -> Result<DataChunk> {
+        let columns = self
+            .children
+            .iter()
+            .map(|c| c.eval_checked(input).map(Column::new))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(DataChunk::new(columns, input.capacity()))
+    }
+
+    /// Calls the UDF server with the arguments of a batch of rows.
+    async fn call(&self, args: &DataChunk) -> Result<ArrayRef> {
+        let output = self
+            .client
+            .eval(&self.identifier, args)
+            .await
+            .map_err(|e| anyhow::anyhow!("failed to call UDF {}: {}", self.name, e))?;
+        ensure!(output.len() == args.capacity());
+        Ok(output)
+    }
+
+    /// Calls the UDF server, blocking the current thread until the results are returned. Only used
+    /// by the synchronous evaluation, e.g. when the UDF is nested in another expression.
+    fn call_blocking(&self, args: &DataChunk) -> Result<ArrayRef> {
+        tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(self.call(args)))
+    }
+}
+
+impl<'a> TryFrom<&'a ExprNode> for UdfExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type().unwrap() == Type::Udf);
+        let return_type = DataType::from(prost.get_return_type().unwrap());
+        let RexNode::Udf(udf) = prost.get_rex_node().unwrap() else {
+            bail!("Expected RexNode::Udf");
+        };
+
+        let children = udf
+            .children
+            .iter()
+            .map(build_from_prost)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            name: udf.name.clone(),
+            identifier: udf.identifier.clone(),
+            children,
+            return_type,
+            client: Arc::new(UdfClient::new(&udf.link)),
+        })
+    }
+}
+
+/// Builds a call to a user-defined function, dispatching on the language of the function.
+pub fn build_udf_expr(prost: &ExprNode) -> Result<BoxedExpression> {
+    let RexNode::Udf(udf) = prost.get_rex_node()? else {
+        bail!("Expected RexNode::Udf");
+    };
+    match udf.language.as_str() {
+        "wasm" => WasmUdfExpression::try_from(prost).map(Expression::boxed),
+        _ => UdfExpression::try_from(prost).map(Expression::boxed),
+    }
+}
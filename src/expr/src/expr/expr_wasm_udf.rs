@@ -0,0 +1,334 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use risingwave_common::array::{ArrayRef, DataChunk, Row};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_pb::expr::expr_node::{RexNode, Type};
+use risingwave_pb::expr::ExprNode;
+use wasmtime::{
+    Config, Engine, ExternType, Func, Instance, Module, Store, StoreLimits, StoreLimitsBuilder,
+    Val, ValType,
+};
+
+use crate::expr::{build_from_prost, BoxedExpression, Expression};
+use crate::{bail, ensure, ExprError, Result};
+
+/// Fuel given to each call of a WASM UDF, which roughly bounds the number of executed
+/// instructions.
+const WASM_UDF_FUEL_PER_CALL: u64 = 10_000_000;
+
+/// The maximum size the linear memory of a WASM UDF instance can grow to.
+const WASM_UDF_MEMORY_LIMIT: usize = 64 << 20;
+
+static WASM_ENGINE: LazyLock<Engine> = LazyLock::new(|| {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    Engine::new(&config).expect("failed to create WASM engine")
+});
+
+/// Checks that `binary` is a self-contained WASM module which exports a function named
+/// `identifier` whose signature matches the given SQL types.
+pub fn validate_wasm_udf(
+    binary: &[u8],
+    identifier: &str,
+    arg_types: &[DataType],
+    return_type: &DataType,
+) -> Result<()> {
+    compile(binary, identifier, arg_types, return_type).map(|_| ())
+}
+
+fn compile(
+    binary: &[u8],
+    identifier: &str,
+    arg_types: &[DataType],
+    return_type: &DataType,
+) -> Result<Module> {
+    let module = Module::new(&WASM_ENGINE, binary)
+        .map_err(|e| anyhow::anyhow!("invalid WASM module: {}", e))?;
+    // Without imports, the module has no access to anything outside its own memory.
+    if let Some(import) = module.imports().next() {
+        bail!(
+            "WASM UDF must not import anything, found {}::{}",
+            import.module(),
+            import.name()
+        );
+    }
+    let Some(ExternType::Func(func_type)) = module.get_export(identifier) else {
+        bail!("function {} is not exported by the WASM module", identifier);
+    };
+    let params = arg_types
+        .iter()
+        .map(wasm_type)
+        .collect::<Result<Vec<_>>>()?;
+    let results = vec![wasm_type(return_type)?];
+    if !func_type.params().eq(params) || !func_type.results().eq(results) {
+        bail!(
+            "signature of function {} in the WASM module does not match the declaration",
+            identifier
+        );
+    }
+    Ok(module)
+}
+
+/// Returns the WASM value type that values of `data_type` are passed as.
+fn wasm_type(data_type: &DataType) -> Result<ValType> {
+    Ok(match data_type {
+        DataType::Boolean | DataType::Int16 | DataType::Int32 => ValType::I32,
+        DataType::Int64 => ValType::I64,
+        DataType::Float32 => ValType::F32,
+        DataType::Float64 => ValType::F64,
+        _ => bail!("type {} is not supported in WASM UDF", data_type),
+    })
+}
+
+fn to_wasm_val(scalar: ScalarImpl) -> Val {
+    match scalar {
+        ScalarImpl::Bool(v) => Val::I32(v as i32),
+        ScalarImpl::Int16(v) => Val::I32(v as i32),
+        ScalarImpl::Int32(v) => Val::I32(v),
+        ScalarImpl::Int64(v) => Val::I64(v),
+        ScalarImpl::Float32(v) => Val::F32(v.0.to_bits()),
+        ScalarImpl::Float64(v) => Val::F64(v.0.to_bits()),
+        _ => unreachable!("unsupported types are rejected on compilation"),
+    }
+}
+
+fn from_wasm_val(val: &Val, data_type: &DataType) -> Result<ScalarImpl> {
+    Ok(match (data_type, val) {
+        (DataType::Boolean, Val::I32(v)) => ScalarImpl::Bool(*v != 0),
+        (DataType::Int16, Val::I32(v)) => {
+            ScalarImpl::Int16(i16::try_from(*v).map_err(|_| ExprError::NumericOutOfRange)?)
+        }
+        (DataType::Int32, Val::I32(v)) => ScalarImpl::Int32(*v),
+        (DataType::Int64, Val::I64(v)) => ScalarImpl::Int64(*v),
+        (DataType::Float32, Val::F32(v)) => ScalarImpl::Float32(f32::from_bits(*v).into()),
+        (DataType::Float64, Val::F64(v)) => ScalarImpl::Float64(f64::from_bits(*v).into()),
+        _ => bail!("unexpected WASM value {:?} for type {}", val, data_type),
+    })
+}
+
+/// An instance of the WASM module, with its own memory.
+struct WasmInstance {
+    store: Store<StoreLimits>,
+    func: Func,
+}
+
+impl WasmInstance {
+    fn new(module: &Module, identifier: &str) -> Result<Self> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(WASM_UDF_MEMORY_LIMIT)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&WASM_ENGINE, limits);
+        store.limiter(|limits| limits);
+        let instance = Instance::new(&mut store, module, &[])?;
+        let Some(func) = instance.get_func(&mut store, identifier) else {
+            bail!("function {} is not exported by the WASM module", identifier);
+        };
+        Ok(Self { store, func })
+    }
+
+    /// Calls the function on a single row. Returns null if any argument is null.
+    fn call(&mut self, args: &[Datum], return_type: &DataType) -> anyhow::Result<Datum> {
+        let Some(params) = args
+            .iter()
+            .map(|arg| arg.clone().map(to_wasm_val))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(None);
+        };
+        // Refill the fuel so that every call runs under the same budget.
+        let remaining = self.store.consume_fuel(0)?;
+        self.store
+            .add_fuel(WASM_UDF_FUEL_PER_CALL.saturating_sub(remaining))?;
+        let mut results = [Val::I32(0)];
+        self.func.call(&mut self.store, &params, &mut results)?;
+        Ok(Some(from_wasm_val(&results[0], return_type)?))
+    }
+}
+
+/// A call to a user-defined function written in WASM, executed in-process.
+///
+/// Each expression owns a sandboxed instance of the module. Calls are limited in the amount of
+/// fuel they consume and the memory the instance can allocate.
+pub struct WasmUdfExpression {
+    name: String,
+    children: Vec<BoxedExpression>,
+    return_type: DataType,
+    instance: Mutex<WasmInstance>,
+}
+
+impl std::fmt::Debug for WasmUdfExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmUdfExpression")
+            .field("name", &self.name)
+            .field("children", &self.children)
+            .field("return_type", &self.return_type)
+            .finish()
+    }
+}
+
+impl Expression for WasmUdfExpression {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn eval(&self, input: &DataChunk) -> Result<ArrayRef> {
+        let columns = self
+            .children
+            .iter()
+            .map(|c| c.eval_checked(input))
+            .collect::<Result<Vec<_>>>()?;
+        let mut builder = self.return_type.create_array_builder(input.capacity());
+        let mut instance = self.instance.lock().unwrap();
+        let mut args = Vec::with_capacity(columns.len());
+        for row_idx in 0..input.capacity() {
+            if !input.vis().is_set(row_idx) {
+                builder.append_null();
+                continue;
+            }
+            args.clear();
+            args.extend(columns.iter().map(|c| c.datum_at(row_idx)));
+            let output = self.call(&mut instance, &args)?;
+            builder.append_datum(&output);
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+
+    fn eval_row(&self, input: &Row) -> Result<Datum> {
+        let args = self
+            .children
+            .iter()
+            .map(|c| c.eval_row(input))
+            .collect::<Result<Vec<_>>>()?;
+        let mut instance = self.instance.lock().unwrap();
+        self.call(&mut instance, &args)
+    }
+}
+
+impl WasmUdfExpression {
+    fn call(&self, instance: &mut WasmInstance, args: &[Datum]) -> Result<Datum> {
+        instance
+            .call(args, &self.return_type)
+            .map_err(|e| anyhow::anyhow!("failed to call WASM UDF {}: {}", self.name, e).into())
+    }
+}
+
+impl<'a> TryFrom<&'a ExprNode> for WasmUdfExpression {
+    type Error = ExprError;
+
+    fn try_from(prost: &'a ExprNode) -> Result<Self> {
+        ensure!(prost.get_expr_type().unwrap() == Type::Udf);
+        let return_type = DataType::from(prost.get_return_type().unwrap());
+        let RexNode::Udf(udf) = prost.get_rex_node().unwrap() else {
+            bail!("Expected RexNode::Udf");
+        };
+
+        let children = udf
+            .children
+            .iter()
+            .map(build_from_prost)
+            .collect::<Result<Vec<_>>>()?;
+        let arg_types: Vec<_> = udf.arg_types.iter().map(DataType::from).collect();
+        let module = compile(&udf.wasm_binary, &udf.identifier, &arg_types, &return_type)?;
+        let instance = WasmInstance::new(&module, &udf.identifier)?;
+        Ok(Self {
+            name: udf.name.clone(),
+            children,
+            return_type,
+            instance: Mutex::new(instance),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::{DataChunk, DataChunkTestExt};
+
+    use super::*;
+
+    const GCD_WAT: &str = r#"
+        (module
+          (func (export "gcd") (param $a i32) (param $b i32) (result i32)
+            (loop $continue
+              (if (local.get $b)
+                (then
+                  (local.get $b)
+                  (local.set $b (i32.rem_u (local.get $a) (local.get $b)))
+                  (local.set $a)
+                  (br $continue))))
+            (local.get $a))
+          (func (export "spin") (param i32) (result i32)
+            (loop $forever (br $forever))
+            (i32.const 0)))
+    "#;
+
+    fn build(identifier: &str) -> Result<WasmUdfExpression> {
+        let binary = wat::parse_str(GCD_WAT).unwrap();
+        let arg_types = [DataType::Int32, DataType::Int32];
+        let module = compile(&binary, identifier, &arg_types, &DataType::Int32)?;
+        Ok(WasmUdfExpression {
+            name: identifier.to_string(),
+            children: vec![
+                crate::expr::InputRefExpression::new(DataType::Int32, 0).boxed(),
+                crate::expr::InputRefExpression::new(DataType::Int32, 1).boxed(),
+            ],
+            return_type: DataType::Int32,
+            instance: Mutex::new(WasmInstance::new(&module, identifier)?),
+        })
+    }
+
+    #[test]
+    fn test_wasm_udf() {
+        let expr = build("gcd").unwrap();
+        let input = DataChunk::from_pretty(
+            "i i
+             12 18
+             7  .
+             25 15",
+        );
+        let output = expr.eval(&input).unwrap();
+        assert_eq!(output.datum_at(0), Some(ScalarImpl::Int32(6)));
+        assert_eq!(output.datum_at(1), None);
+        assert_eq!(output.datum_at(2), Some(ScalarImpl::Int32(5)));
+    }
+
+    #[test]
+    fn test_wasm_udf_validation() {
+        let binary = wat::parse_str(GCD_WAT).unwrap();
+        let int_args = [DataType::Int32, DataType::Int32];
+        assert!(validate_wasm_udf(&binary, "gcd", &int_args, &DataType::Int32).is_ok());
+        // wrong signature
+        assert!(validate_wasm_udf(&binary, "gcd", &int_args, &DataType::Int64).is_err());
+        // missing export
+        assert!(validate_wasm_udf(&binary, "lcm", &int_args, &DataType::Int32).is_err());
+        // unsupported type
+        let varchar_args = [DataType::Varchar, DataType::Int32];
+        assert!(validate_wasm_udf(&binary, "gcd", &varchar_args, &DataType::Int32).is_err());
+    }
+
+    #[test]
+    fn test_wasm_udf_out_of_fuel() {
+        let binary = wat::parse_str(GCD_WAT).unwrap();
+        let module = compile(&binary, "spin", &[DataType::Int32], &DataType::Int32).unwrap();
+        let mut instance = WasmInstance::new(&module, "spin").unwrap();
+        let args = [Some(ScalarImpl::Int32(1))];
+        assert!(instance.call(&args, &DataType::Int32).is_err());
+        // fuel is refilled for the next call
+        assert!(instance.call(&args, &DataType::Int32).is_err());
+    }
+}
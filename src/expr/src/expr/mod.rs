@@ -32,8 +32,10 @@ mod expr_quaternary_bytes;
 mod expr_regexp;
 mod expr_ternary_bytes;
 mod expr_to_char_const_tmpl;
+mod expr_udf;
 pub mod expr_unary;
 mod expr_vnode;
+mod expr_wasm_udf;
 mod template;
 
 use std::convert::TryFrom;
@@ -42,8 +44,10 @@ use std::sync::Arc;
 pub use agg::AggKind;
 pub use expr_input_ref::InputRefExpression;
 pub use expr_literal::*;
+pub use expr_wasm_udf::validate_wasm_udf;
 use risingwave_common::array::{ArrayRef, DataChunk, Row};
-use risingwave_common::types::{DataType, Datum};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_common::util::epoch::Epoch;
 use risingwave_pb::expr::ExprNode;
 
 use super::Result;
@@ -56,12 +60,14 @@ use crate::expr::expr_field::FieldExpression;
 use crate::expr::expr_in::InExpression;
 use crate::expr::expr_nested_construct::NestedConstructExpression;
 use crate::expr::expr_regexp::RegexpMatchExpression;
+use crate::expr::expr_udf::build_udf_expr;
 use crate::expr::expr_vnode::VnodeExpression;
 use crate::ExprError;
 
 pub type ExpressionRef = Arc<dyn Expression>;
 
 /// Instance of an expression
+#[async_trait::async_trait]
 pub trait Expression: std::fmt::Debug + Sync + Send {
     fn return_type(&self) -> DataType;
 
@@ -82,6 +88,12 @@ pub trait Expression: std::fmt::Debug + Sync + Send {
     /// * `input` - input data of the Project Executor
     fn eval(&self, input: &DataChunk) -> Result<ArrayRef>;
 
+    /// Evaluate the expression without blocking the current thread. Only the expressions calling
+    /// external services, e.g. UDFs, need to override it.
+    async fn eval_async(&self, input: &DataChunk) -> Result<ArrayRef> {
+        self.eval(input)
+    }
+
     /// Evaluate the expression in row-based execution.
     fn eval_row(&self, input: &Row) -> Result<Datum>;
 
@@ -106,10 +118,9 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
         Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual | Add
         | Subtract | Multiply | Divide | Modulus | Extract | RoundDigit | TumbleStart
         | Position | BitwiseShiftLeft | BitwiseShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor
-        | ConcatOp | AtTimeZone => build_binary_expr_prost(prost),
-        And | Or | IsDistinctFrom | IsNotDistinctFrom | ArrayAccess => {
-            build_nullable_binary_expr_prost(prost)
-        }
+        | ConcatOp | AtTimeZone | CastWithTimeZone => build_binary_expr_prost(prost),
+        And | Or | IsDistinctFrom | IsNotDistinctFrom | ArrayAccess | JsonbAccessInner
+        | JsonbAccessStr | JsonbAccessPath => build_nullable_binary_expr_prost(prost),
         ToChar => build_to_char_expr(prost),
         Length => build_length_expr(prost),
         Replace => build_replace_expr(prost),
@@ -120,6 +131,7 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
 
         // Variable number of arguments and based on `Unary/Binary/Ternary/...Expression`
         Substr => build_substr_expr(prost),
+        DateTrunc => build_date_trunc_expr(prost),
         Overlay => build_overlay_expr(prost),
         Trim => build_trim_expr(prost),
         Ltrim => build_ltrim_expr(prost),
@@ -143,6 +155,16 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
             ArrayConcatExpression::try_from(prost).map(Expression::boxed)
         }
         Vnode => VnodeExpression::try_from(prost).map(Expression::boxed),
+        // `now()` is evaluated once when the expression is built, i.e. the batch task is started.
+        // Streaming queries never evaluate it, but read the `Now` stream instead.
+        Now => Ok(LiteralExpression::new(
+            DataType::Timestampz,
+            Some(ScalarImpl::Int64(
+                Epoch::now().as_unix_millis() as i64 * 1000,
+            )),
+        )
+        .boxed()),
+        Udf => build_udf_expr(prost),
         _ => Err(ExprError::UnsupportedFunction(format!(
             "{:?}",
             prost.get_expr_type()
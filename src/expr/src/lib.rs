@@ -24,11 +24,13 @@
 #![feature(type_alias_impl_trait)]
 #![feature(generators)]
 #![feature(iterator_try_collect)]
+#![feature(once_cell)]
 
 pub mod error;
 pub mod expr;
 pub mod table_function;
 pub mod vector_op;
+pub mod window_function;
 
 pub use error::ExprError;
 pub use risingwave_common::{bail, ensure};
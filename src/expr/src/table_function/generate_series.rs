@@ -18,7 +18,7 @@ use anyhow::anyhow;
 use itertools::multizip;
 use num_traits::Zero;
 use risingwave_common::array::{
-    Array, ArrayBuilder, ArrayImpl, ArrayRef, DataChunk, I32Array, IntervalArray,
+    Array, ArrayBuilder, ArrayImpl, ArrayRef, DataChunk, I32Array, I64Array, IntervalArray,
     NaiveDateTimeArray,
 };
 use risingwave_common::types::{CheckedAdd, IsNegative, Scalar, ScalarRef};
@@ -26,11 +26,14 @@ use risingwave_common::types::{CheckedAdd, IsNegative, Scalar, ScalarRef};
 use super::*;
 use crate::ExprError;
 
+/// Generates the values from `start` to `stop` by `step`. The `stop` bound is included for
+/// `generate_series` and excluded for `range`.
 #[derive(Debug)]
 pub struct GenerateSeries<T: Array, S: Array> {
     start: BoxedExpression,
     stop: BoxedExpression,
     step: BoxedExpression,
+    stop_inclusive: bool,
     chunk_size: usize,
     _phantom: std::marker::PhantomData<(T, S)>,
 }
@@ -45,12 +48,14 @@ where
         start: BoxedExpression,
         stop: BoxedExpression,
         step: BoxedExpression,
+        stop_inclusive: bool,
         chunk_size: usize,
     ) -> Self {
         Self {
             start,
             stop,
             step,
+            stop_inclusive,
             chunk_size,
             _phantom: Default::default(),
         }
@@ -73,10 +78,11 @@ where
 
         let mut cur: T::OwnedItem = start.to_owned_scalar();
 
-        while if step.is_negative() {
-            cur >= stop
-        } else {
-            cur <= stop
+        while match (step.is_negative(), self.stop_inclusive) {
+            (true, true) => cur >= stop,
+            (true, false) => cur > stop,
+            (false, true) => cur <= stop,
+            (false, false) => cur < stop,
         } {
             builder.append(Some(cur.as_scalar_ref()));
             cur = cur.checked_add(step).ok_or(ExprError::NumericOutOfRange)?;
@@ -147,6 +153,7 @@ where
 pub fn new_generate_series(
     prost: &TableFunctionProst,
     chunk_size: usize,
+    stop_inclusive: bool,
 ) -> Result<BoxedTableFunction> {
     let return_type = DataType::from(prost.get_return_type().unwrap());
     let args: Vec<_> = prost.args.iter().map(expr_build_from_prost).try_collect()?;
@@ -154,12 +161,29 @@ pub fn new_generate_series(
 
     match return_type {
         DataType::Timestamp => Ok(GenerateSeries::<NaiveDateTimeArray, IntervalArray>::new(
-            start, stop, step, chunk_size,
+            start,
+            stop,
+            step,
+            stop_inclusive,
+            chunk_size,
+        )
+        .boxed()),
+        DataType::Int32 => Ok(GenerateSeries::<I32Array, I32Array>::new(
+            start,
+            stop,
+            step,
+            stop_inclusive,
+            chunk_size,
+        )
+        .boxed()),
+        DataType::Int64 => Ok(GenerateSeries::<I64Array, I64Array>::new(
+            start,
+            stop,
+            step,
+            stop_inclusive,
+            chunk_size,
         )
         .boxed()),
-        DataType::Int32 => {
-            Ok(GenerateSeries::<I32Array, I32Array>::new(start, stop, step, chunk_size).boxed())
-        }
         _ => Err(ExprError::Internal(anyhow!(
             "the return type of Generate Series Function is incorrect".to_string(),
         ))),
@@ -184,15 +208,43 @@ mod tests {
         generate_series_test_case(0, (CHUNK_SIZE * 2 + 3) as i32, 1);
     }
 
-    fn generate_series_test_case(start: i32, stop: i32, step: i32) {
-        fn to_lit_expr(v: i32) -> BoxedExpression {
-            LiteralExpression::new(DataType::Int32, Some(v.into())).boxed()
-        }
+    #[test]
+    fn test_i32_range() {
+        range_test_case(2, 4, 1, 2);
+        range_test_case(4, 2, -1, 2);
+        range_test_case(0, 9, 2, 5);
+        range_test_case(0, 10, 2, 5);
+        range_test_case(2, 2, 1, 0);
+        range_test_case(0, (CHUNK_SIZE * 2 + 3) as i32, 1, CHUNK_SIZE * 2 + 3);
+    }
+
+    fn to_i32_lit_expr(v: i32) -> BoxedExpression {
+        LiteralExpression::new(DataType::Int32, Some(v.into())).boxed()
+    }
+
+    fn range_test_case(start: i32, stop: i32, step: i32, expect_cnt: usize) {
+        let function = GenerateSeries::<I32Array, I32Array>::new(
+            to_i32_lit_expr(start),
+            to_i32_lit_expr(stop),
+            to_i32_lit_expr(step),
+            false,
+            CHUNK_SIZE,
+        )
+        .boxed();
 
+        let dummy_chunk = DataChunk::new_dummy(1);
+        let arrays = function.eval(&dummy_chunk).unwrap();
+
+        let cnt: usize = arrays.iter().map(|a| a.len()).sum();
+        assert_eq!(cnt, expect_cnt);
+    }
+
+    fn generate_series_test_case(start: i32, stop: i32, step: i32) {
         let function = GenerateSeries::<I32Array, I32Array> {
-            start: to_lit_expr(start),
-            stop: to_lit_expr(stop),
-            step: to_lit_expr(step),
+            start: to_i32_lit_expr(start),
+            stop: to_i32_lit_expr(stop),
+            step: to_i32_lit_expr(step),
+            stop_inclusive: true,
             chunk_size: CHUNK_SIZE,
             _phantom: Default::default(),
         }
@@ -233,6 +285,7 @@ mod tests {
             start: to_lit_expr(DataType::Timestamp, start.into()),
             stop: to_lit_expr(DataType::Timestamp, stop.into()),
             step: to_lit_expr(DataType::Interval, step.into()),
+            stop_inclusive: true,
             chunk_size: CHUNK_SIZE,
             _phantom: Default::default(),
         };
@@ -32,6 +32,8 @@ mod unnest;
 use unnest::*;
 mod regexp_matches;
 use regexp_matches::*;
+mod jsonb_array_elements;
+use jsonb_array_elements::*;
 
 /// Instance of a table function.
 ///
@@ -59,9 +61,11 @@ pub fn build_from_prost(
     use risingwave_pb::expr::table_function::Type::*;
 
     match prost.get_function_type().unwrap() {
-        Generate => new_generate_series(prost, chunk_size),
+        Generate => new_generate_series(prost, chunk_size, true),
+        Range => new_generate_series(prost, chunk_size, false),
         Unnest => new_unnest(prost, chunk_size),
         RegexpMatches => new_regexp_matches(prost, chunk_size),
+        JsonbArrayElements => new_jsonb_array_elements(prost, chunk_size),
         Unspecified => unreachable!(),
     }
 }
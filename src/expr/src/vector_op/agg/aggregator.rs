@@ -30,6 +30,7 @@ use crate::vector_op::agg::filter::*;
 use crate::vector_op::agg::functions::*;
 use crate::vector_op::agg::general_agg::*;
 use crate::vector_op::agg::general_distinct_agg::*;
+use crate::vector_op::agg::percentile::create_percentile_state;
 use crate::vector_op::agg::string_agg::create_string_agg_state;
 use crate::Result;
 
@@ -51,12 +52,24 @@ pub trait Aggregator: Send + DynClone + 'static {
     /// `output` the aggregator to `ArrayBuilder` with input with type checked at runtime.
     /// After `output` the aggregator is reset to initial state.
     fn output(&mut self, builder: &mut ArrayBuilderImpl) -> Result<()>;
+
+    /// The estimated memory of the aggregator in bytes, including the values it accumulates.
+    fn estimated_size(&self) -> usize;
 }
 
 dyn_clone::clone_trait_object!(Aggregator);
 
+/// The estimated heap memory of a datum accumulated by an aggregator. Only strings are counted.
+pub(super) fn estimated_datum_heap_size(datum: &Datum) -> usize {
+    match datum {
+        Some(ScalarImpl::Utf8(s)) => s.capacity(),
+        _ => 0,
+    }
+}
+
 pub type BoxedAggState = Box<dyn Aggregator>;
 
+#[derive(Clone)]
 pub struct AggStateFactory {
     /// Return type of the agg call.
     return_type: DataType,
@@ -90,6 +103,13 @@ impl AggStateFactory {
                 let input_col_idx = arg.get_input()?.get_column_idx() as usize;
                 Box::new(ApproxCountDistinct::new(return_type.clone(), input_col_idx))
             }
+            (AggKind::ApproxCountDistinctMerge, [arg]) => {
+                let input_col_idx = arg.get_input()?.get_column_idx() as usize;
+                Box::new(ApproxCountDistinct::new_merger(
+                    return_type.clone(),
+                    input_col_idx,
+                ))
+            }
             (AggKind::StringAgg, [agg_arg, delim_arg]) => {
                 assert_eq!(
                     DataType::from(agg_arg.get_type().unwrap()),
@@ -107,6 +127,21 @@ impl AggStateFactory {
                 let agg_col_idx = arg.get_input()?.get_column_idx() as usize;
                 create_array_agg_state(return_type.clone(), agg_col_idx, order_pairs)?
             }
+            (AggKind::PercentileCont | AggKind::PercentileDisc, [agg_arg, fraction_arg]) => {
+                assert_eq!(
+                    DataType::from(fraction_arg.get_type().unwrap()),
+                    DataType::Float64
+                );
+                let agg_col_idx = agg_arg.get_input()?.get_column_idx() as usize;
+                let fraction_col_idx = fraction_arg.get_input()?.get_column_idx() as usize;
+                create_percentile_state(
+                    return_type.clone(),
+                    agg_col_idx,
+                    fraction_col_idx,
+                    order_pairs,
+                    agg_kind == AggKind::PercentileDisc,
+                )?
+            }
             (agg_kind, [arg]) => {
                 // other unary agg call
                 let input_type = DataType::from(arg.get_type()?);
@@ -255,6 +290,20 @@ pub fn create_agg_state_unary(
         (FirstValue, first_struct, struct_type, struct_type, None),
         (FirstValue, first_str, varchar, varchar, None),
         (FirstValue, first_list, list, list, None),
+        (LastValue, last, int16, int16, None),
+        (LastValue, last, int32, int32, None),
+        (LastValue, last, int64, int64, None),
+        (LastValue, last, float32, float32, None),
+        (LastValue, last, float64, float64, None),
+        (LastValue, last, decimal, decimal, None),
+        (LastValue, last, boolean, boolean, None),
+        (LastValue, last, interval, interval, None),
+        (LastValue, last, date, date, None),
+        (LastValue, last, timestamp, timestamp, None),
+        (LastValue, last, time, time, None),
+        (LastValue, last_struct, struct_type, struct_type, None),
+        (LastValue, last_str, varchar, varchar, None),
+        (LastValue, last_list, list, list, None),
         // Global Agg
         (Sum, sum, int64, int64, None),
     ];
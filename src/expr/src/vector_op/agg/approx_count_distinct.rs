@@ -15,6 +15,7 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
+use itertools::Itertools;
 use risingwave_common::array::*;
 use risingwave_common::bail;
 use risingwave_common::types::*;
@@ -33,10 +34,16 @@ const BIAS_CORRECTION: f64 = 0.7213 / (1. + (1.079 / NUM_OF_REGISTERS as f64));
 /// `ApproxCountDistinct` approximates the count of non-null rows using `HyperLogLog`. The
 /// estimation error for `HyperLogLog` is 1.04/sqrt(num of registers). With 2^14 registers this
 /// is ~1/128.
+///
+/// In a two-phase aggregation, the partial phase returns the registers as a sketch of type
+/// `bigint[]` instead of the estimation, and the total phase merges the sketches from its input
+/// by taking the maximum of each register.
 #[derive(Clone)]
 pub struct ApproxCountDistinct {
     return_type: DataType,
     input_col_idx: usize,
+    /// Whether the input column contains sketches to merge rather than values to count.
+    merge_sketches: bool,
     registers: [u8; NUM_OF_REGISTERS],
 }
 
@@ -45,10 +52,29 @@ impl ApproxCountDistinct {
         Self {
             return_type,
             input_col_idx,
+            merge_sketches: false,
             registers: [0; NUM_OF_REGISTERS],
         }
     }
 
+    /// Creates the total phase of a two-phase `approx_count_distinct`, whose input column is the
+    /// sketches returned by the partial phase.
+    pub fn new_merger(return_type: DataType, input_col_idx: usize) -> Self {
+        Self {
+            merge_sketches: true,
+            ..Self::new(return_type, input_col_idx)
+        }
+    }
+
+    fn add(&mut self, datum_ref: DatumRef<'_>) -> Result<()> {
+        if self.merge_sketches {
+            self.merge_sketch(datum_ref)
+        } else {
+            self.add_datum(datum_ref);
+            Ok(())
+        }
+    }
+
     /// Adds the count of the datum's hash into the register, if it is greater than the existing
     /// count at the register
     fn add_datum(&mut self, datum_ref: DatumRef<'_>) {
@@ -67,6 +93,41 @@ impl ApproxCountDistinct {
         }
     }
 
+    /// Merges a sketch produced by [`Self::output_sketch`] into the registers.
+    fn merge_sketch(&mut self, datum_ref: DatumRef<'_>) -> Result<()> {
+        let sketch = match datum_ref {
+            Some(ScalarRefImpl::List(sketch)) => sketch.values_ref(),
+            None => return Ok(()),
+            Some(_) => bail!("Unexpected sketch for approx_count_distinct."),
+        };
+        if sketch.len() * 8 != NUM_OF_REGISTERS {
+            bail!("Unexpected sketch size for approx_count_distinct.");
+        }
+        for (word, registers) in sketch.iter().zip_eq(self.registers.chunks_mut(8)) {
+            let word = match word {
+                Some(ScalarRefImpl::Int64(word)) => *word,
+                _ => bail!("Unexpected sketch for approx_count_distinct."),
+            };
+            for (register, count) in registers.iter_mut().zip_eq(word.to_le_bytes()) {
+                *register = (*register).max(count);
+            }
+        }
+        Ok(())
+    }
+
+    /// Packs the registers into a sketch, with every 8 registers in an `i64`.
+    fn output_sketch(&self) -> ListValue {
+        ListValue::new(
+            self.registers
+                .chunks(8)
+                .map(|registers| {
+                    let word = i64::from_le_bytes(registers.try_into().unwrap());
+                    Some(ScalarImpl::Int64(word))
+                })
+                .collect(),
+        )
+    }
+
     /// Calculate the hash of the `scalar_impl` using Rust's default hasher
     /// Perhaps a different hash like Murmur2 could be used instead for optimization?
     fn get_hash(&self, scalar_impl: ScalarImpl) -> u64 {
@@ -125,8 +186,7 @@ impl Aggregator for ApproxCountDistinct {
 
     fn update_single(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
         let array = input.column_at(self.input_col_idx).array_ref();
-        self.add_datum(array.value_at(row_id));
-        Ok(())
+        self.add(array.value_at(row_id))
     }
 
     fn update_multi(
@@ -137,21 +197,29 @@ impl Aggregator for ApproxCountDistinct {
     ) -> Result<()> {
         let array = input.column_at(self.input_col_idx).array_ref();
         for row_id in start_row_id..end_row_id {
-            self.add_datum(array.value_at(row_id));
+            self.add(array.value_at(row_id))?;
         }
         Ok(())
     }
 
     fn output(&mut self, builder: &mut ArrayBuilderImpl) -> Result<()> {
-        let result = self.calculate_result();
-        self.registers = [0; NUM_OF_REGISTERS];
-        match builder {
+        let res = match builder {
             ArrayBuilderImpl::Int64(b) => {
-                b.append(Some(result));
+                b.append(Some(self.calculate_result()));
                 Ok(())
             }
-            _ => bail!("Unexpected builder for count(*)."),
-        }
+            ArrayBuilderImpl::List(b) => {
+                b.append(Some(self.output_sketch().as_scalar_ref()));
+                Ok(())
+            }
+            _ => bail!("Unexpected builder for approx_count_distinct."),
+        };
+        self.registers = [0; NUM_OF_REGISTERS];
+        res
+    }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
     }
 }
 
@@ -214,4 +282,42 @@ mod tests {
         let array = builder.finish();
         assert_eq!(array.len(), 3);
     }
+
+    #[test]
+    fn test_merge_sketches() {
+        let list_type = DataType::List {
+            datatype: Box::new(DataType::Int64),
+        };
+
+        // Count the distinct values of overlapping chunks in a single phase.
+        let mut agg = ApproxCountDistinct::new(DataType::Int64, 0);
+        let mut builder = ArrayBuilderImpl::Int64(I64ArrayBuilder::new(1));
+        for start in [0, 5000] {
+            let data_chunk = generate_data_chunk(10000, start);
+            agg.update_multi(&data_chunk, 0, data_chunk.cardinality())
+                .unwrap();
+        }
+        agg.output(&mut builder).unwrap();
+        let expected = builder.finish();
+
+        // Count them again in two phases.
+        let mut partial_agg = ApproxCountDistinct::new(list_type.clone(), 0);
+        let mut sketches = list_type.create_array_builder(2);
+        for start in [0, 5000] {
+            let data_chunk = generate_data_chunk(10000, start);
+            partial_agg
+                .update_multi(&data_chunk, 0, data_chunk.cardinality())
+                .unwrap();
+            partial_agg.output(&mut sketches).unwrap();
+        }
+        let sketches = DataChunk::new(vec![sketches.finish().into()], 2);
+
+        let mut total_agg = ApproxCountDistinct::new_merger(DataType::Int64, 0);
+        let mut builder = ArrayBuilderImpl::Int64(I64ArrayBuilder::new(1));
+        total_agg.update_multi(&sketches, 0, 2).unwrap();
+        total_agg.output(&mut builder).unwrap();
+        let actual = builder.finish();
+
+        assert_eq!(actual.value_at(0), expected.value_at(0));
+    }
 }
@@ -15,10 +15,10 @@
 use risingwave_common::array::{ArrayBuilder, ArrayBuilderImpl, DataChunk, ListValue, RowRef};
 use risingwave_common::bail;
 use risingwave_common::types::{DataType, Datum, Scalar};
-use risingwave_common::util::ordered::OrderedRow;
+use risingwave_common::util::ordered::{OrderedDatum, OrderedRow};
 use risingwave_common::util::sort_util::{OrderPair, OrderType};
 
-use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::aggregator::{estimated_datum_heap_size, Aggregator};
 use crate::Result;
 
 #[derive(Clone)]
@@ -26,6 +26,8 @@ struct ArrayAggUnordered {
     return_type: DataType,
     agg_col_idx: usize,
     values: Vec<Datum>,
+    /// The heap memory of the datums in `values`.
+    values_heap_size: usize,
 }
 
 impl ArrayAggUnordered {
@@ -35,14 +37,17 @@ impl ArrayAggUnordered {
             return_type,
             agg_col_idx,
             values: vec![],
+            values_heap_size: 0,
         }
     }
 
     fn push(&mut self, datum: Datum) {
+        self.values_heap_size += estimated_datum_heap_size(&datum);
         self.values.push(datum);
     }
 
     fn get_result_and_reset(&mut self) -> Option<ListValue> {
+        self.values_heap_size = 0;
         if self.values.is_empty() {
             None
         } else {
@@ -87,6 +92,12 @@ impl Aggregator for ArrayAggUnordered {
             bail!("Builder fail to match {}.", stringify!(Utf8))
         }
     }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.values.capacity() * std::mem::size_of::<Datum>()
+            + self.values_heap_size
+    }
 }
 
 #[derive(Clone)]
@@ -96,6 +107,8 @@ struct ArrayAggOrdered {
     order_col_indices: Vec<usize>,
     order_types: Vec<OrderType>,
     unordered_values: Vec<(OrderedRow, Datum)>,
+    /// The heap memory of `unordered_values`.
+    values_heap_size: usize,
 }
 
 impl ArrayAggOrdered {
@@ -111,6 +124,7 @@ impl ArrayAggOrdered {
             order_col_indices,
             order_types,
             unordered_values: vec![],
+            values_heap_size: 0,
         }
     }
 
@@ -120,11 +134,14 @@ impl ArrayAggOrdered {
             &self.order_types,
         );
         let datum = row.value_at(self.agg_col_idx).map(|x| x.into_scalar_impl());
+        self.values_heap_size += self.order_col_indices.len() * std::mem::size_of::<OrderedDatum>()
+            + estimated_datum_heap_size(&datum);
         self.unordered_values.push((key, datum));
     }
 
     fn get_result_and_reset(&mut self) -> ListValue {
         let mut rows = std::mem::take(&mut self.unordered_values);
+        self.values_heap_size = 0;
         rows.sort_unstable_by(|a, b| a.0.cmp(&b.0));
         ListValue::new(rows.into_iter().map(|(_, datum)| datum).collect())
     }
@@ -163,6 +180,12 @@ impl Aggregator for ArrayAggOrdered {
             bail!("Builder fail to match {}.", stringify!(Utf8))
         }
     }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.unordered_values.capacity() * std::mem::size_of::<(OrderedRow, Datum)>()
+            + self.values_heap_size
+    }
 }
 
 pub fn create_array_agg_state(
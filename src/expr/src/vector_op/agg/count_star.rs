@@ -74,4 +74,8 @@ impl Aggregator for CountStar {
             _ => bail!("Unexpected builder for count(*)."),
         }
     }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
 }
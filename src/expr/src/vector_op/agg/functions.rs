@@ -147,6 +147,28 @@ pub fn first_list<'a>(
     first(r, i)
 }
 
+pub fn last<T>(result: Option<T>, input: Option<T>) -> Result<Option<T>> {
+    Ok(input.or(result))
+}
+
+pub fn last_str<'a>(r: Option<&'a str>, i: Option<&'a str>) -> Result<Option<&'a str>> {
+    last(r, i)
+}
+
+pub fn last_struct<'a>(
+    r: Option<StructRef<'a>>,
+    i: Option<StructRef<'a>>,
+) -> Result<Option<StructRef<'a>>> {
+    last(r, i)
+}
+
+pub fn last_list<'a>(
+    r: Option<ListRef<'a>>,
+    i: Option<ListRef<'a>>,
+) -> Result<Option<ListRef<'a>>> {
+    last(r, i)
+}
+
 /// Note the following corner cases:
 ///
 /// ```slt
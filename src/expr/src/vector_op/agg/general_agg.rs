@@ -138,6 +138,10 @@ macro_rules! impl_aggregator {
                     bail!("Builder fail to match {}.", stringify!($result_variant))
                 }
             }
+
+            fn estimated_size(&self) -> usize {
+                std::mem::size_of::<Self>()
+            }
         }
     };
 }
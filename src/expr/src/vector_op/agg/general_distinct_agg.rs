@@ -19,7 +19,7 @@ use risingwave_common::array::*;
 use risingwave_common::bail;
 use risingwave_common::types::*;
 
-use crate::vector_op::agg::aggregator::Aggregator;
+use crate::vector_op::agg::aggregator::{estimated_datum_heap_size, Aggregator};
 use crate::vector_op::agg::functions::RTFn;
 use crate::Result;
 
@@ -41,6 +41,8 @@ where
     result: Option<R::OwnedItem>,
     f: F,
     exists: HashSet<Datum>,
+    /// The heap memory of the datums in `exists`.
+    exists_heap_size: usize,
     _phantom: PhantomData<T>,
 }
 impl<T, F, R> GeneralDistinctAgg<T, F, R>
@@ -56,6 +58,7 @@ where
             result: None,
             f,
             exists: HashSet::new(),
+            exists_heap_size: 0,
             _phantom: PhantomData,
         }
     }
@@ -64,7 +67,9 @@ where
         let value = input
             .value_at(row_id)
             .map(|scalar_ref| scalar_ref.to_owned_scalar().to_scalar_value());
+        let heap_size = estimated_datum_heap_size(&value);
         if self.exists.insert(value) {
+            self.exists_heap_size += heap_size;
             let datum = self
                 .f
                 .eval(
@@ -88,9 +93,14 @@ where
             .skip(start_row_id)
             .take(end_row_id - start_row_id)
             .filter(|scalar_ref| {
-                self.exists.insert(
-                    scalar_ref.map(|scalar_ref| scalar_ref.to_owned_scalar().to_scalar_value()),
-                )
+                let value =
+                    scalar_ref.map(|scalar_ref| scalar_ref.to_owned_scalar().to_scalar_value());
+                let heap_size = estimated_datum_heap_size(&value);
+                let inserted = self.exists.insert(value);
+                if inserted {
+                    self.exists_heap_size += heap_size;
+                }
+                inserted
             });
         let mut cur = self.result.as_ref().map(|x| x.as_scalar_ref());
         for datum in input {
@@ -149,6 +159,12 @@ macro_rules! impl_aggregator {
                     bail!("Builder fail to match {}.", stringify!($result_variant))
                 }
             }
+
+            fn estimated_size(&self) -> usize {
+                std::mem::size_of::<Self>()
+                    + self.exists.capacity() * std::mem::size_of::<Datum>()
+                    + self.exists_heap_size
+            }
         }
     };
 }
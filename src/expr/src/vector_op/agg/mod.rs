@@ -21,6 +21,7 @@ mod functions;
 mod general_agg;
 mod general_distinct_agg;
 mod general_sorted_grouper;
+mod percentile;
 mod string_agg;
 
 pub use aggregator::{AggStateFactory, BoxedAggState};
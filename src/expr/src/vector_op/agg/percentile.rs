@@ -0,0 +1,233 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::{ArrayBuilderImpl, DataChunk};
+use risingwave_common::bail;
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_common::util::sort_util::{OrderPair, OrderType};
+
+use crate::vector_op::agg::aggregator::Aggregator;
+use crate::Result;
+
+/// Computes the ordered-set aggregates `percentile_cont` and `percentile_disc` exactly, by
+/// buffering all the non-null input values and sorting them on output.
+#[derive(Clone)]
+struct Percentile {
+    return_type: DataType,
+    agg_col_idx: usize,
+    fraction_col_idx: usize,
+    descending: bool,
+    /// Whether to return an input value (`percentile_disc`) rather than interpolating between
+    /// the adjacent input values (`percentile_cont`).
+    discrete: bool,
+    fraction: Option<f64>,
+    values: Vec<ScalarImpl>,
+}
+
+impl Percentile {
+    fn new(
+        return_type: DataType,
+        agg_col_idx: usize,
+        fraction_col_idx: usize,
+        descending: bool,
+        discrete: bool,
+    ) -> Self {
+        Self {
+            return_type,
+            agg_col_idx,
+            fraction_col_idx,
+            descending,
+            discrete,
+            fraction: None,
+            values: vec![],
+        }
+    }
+
+    fn get_result_and_reset(&mut self) -> Result<Datum> {
+        let fraction = self.fraction.take();
+        let mut values = std::mem::take(&mut self.values);
+        let Some(fraction) = fraction else {
+            return Ok(None);
+        };
+        if values.is_empty() {
+            return Ok(None);
+        }
+        if self.descending {
+            values.sort_unstable_by(|a, b| b.cmp(a));
+        } else {
+            values.sort_unstable();
+        }
+
+        if self.discrete {
+            // The first value whose position in the ordering equals or exceeds the fraction.
+            let idx = ((fraction * values.len() as f64).ceil() as usize).max(1) - 1;
+            Ok(Some(values.swap_remove(idx)))
+        } else {
+            let rank = fraction * (values.len() - 1) as f64;
+            let lower = values[rank.floor() as usize].clone().into_float64().0;
+            let upper = values[rank.ceil() as usize].clone().into_float64().0;
+            let result = lower + (upper - lower) * (rank - rank.floor());
+            Ok(Some(ScalarImpl::Float64(result.into())))
+        }
+    }
+}
+
+impl Aggregator for Percentile {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    fn update_single(&mut self, input: &DataChunk, row_id: usize) -> Result<()> {
+        if self.fraction.is_none() {
+            let fraction = input
+                .column_at(self.fraction_col_idx)
+                .array_ref()
+                .datum_at(row_id)
+                .map(|fraction| fraction.into_float64().0);
+            match fraction {
+                Some(fraction) if (0.0..=1.0).contains(&fraction) => self.fraction = Some(fraction),
+                Some(fraction) => bail!("percentile value {} is not between 0 and 1", fraction),
+                // A null fraction leads to a null result.
+                None => return Ok(()),
+            }
+        }
+        if let Some(value) = input
+            .column_at(self.agg_col_idx)
+            .array_ref()
+            .datum_at(row_id)
+        {
+            self.values.push(value);
+        }
+        Ok(())
+    }
+
+    fn update_multi(
+        &mut self,
+        input: &DataChunk,
+        start_row_id: usize,
+        end_row_id: usize,
+    ) -> Result<()> {
+        for row_id in start_row_id..end_row_id {
+            self.update_single(input, row_id)?;
+        }
+        Ok(())
+    }
+
+    fn output(&mut self, builder: &mut ArrayBuilderImpl) -> Result<()> {
+        let result = self.get_result_and_reset()?;
+        builder.append_datum(&result);
+        Ok(())
+    }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.values.capacity() * std::mem::size_of::<ScalarImpl>()
+    }
+}
+
+pub fn create_percentile_state(
+    return_type: DataType,
+    agg_col_idx: usize,
+    fraction_col_idx: usize,
+    order_pairs: Vec<OrderPair>,
+    discrete: bool,
+) -> Result<Box<dyn Aggregator>> {
+    let descending = matches!(
+        order_pairs.first(),
+        Some(OrderPair {
+            order_type: OrderType::Descending,
+            ..
+        })
+    );
+    Ok(Box::new(Percentile::new(
+        return_type,
+        agg_col_idx,
+        fraction_col_idx,
+        descending,
+        discrete,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::test_prelude::DataChunkTestExt;
+
+    use super::*;
+
+    fn percentile(
+        chunk: &DataChunk,
+        return_type: DataType,
+        order_type: OrderType,
+        discrete: bool,
+    ) -> Result<Datum> {
+        let order_pairs = vec![OrderPair::new(0, order_type)];
+        let mut agg = create_percentile_state(return_type.clone(), 0, 1, order_pairs, discrete)?;
+        let mut builder = return_type.create_array_builder(0);
+        agg.update_multi(chunk, 0, chunk.cardinality())?;
+        agg.output(&mut builder)?;
+        let output = builder.finish();
+        Ok(output.datum_at(0))
+    }
+
+    #[test]
+    fn test_percentile_cont() -> Result<()> {
+        let chunk = DataChunk::from_pretty(
+            "F F
+             4 0.25
+             . 0.25
+             1 0.25
+             3 0.25
+             2 0.25",
+        );
+        let asc = percentile(&chunk, DataType::Float64, OrderType::Ascending, false)?;
+        assert_eq!(asc, Some(ScalarImpl::Float64(1.75.into())));
+        let desc = percentile(&chunk, DataType::Float64, OrderType::Descending, false)?;
+        assert_eq!(desc, Some(ScalarImpl::Float64(3.25.into())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_percentile_disc() -> Result<()> {
+        let chunk = DataChunk::from_pretty(
+            "i F
+             4 0.5
+             1 0.5
+             3 0.5
+             2 0.5",
+        );
+        let asc = percentile(&chunk, DataType::Int32, OrderType::Ascending, true)?;
+        assert_eq!(asc, Some(ScalarImpl::Int32(2)));
+        let desc = percentile(&chunk, DataType::Int32, OrderType::Descending, true)?;
+        assert_eq!(desc, Some(ScalarImpl::Int32(3)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_percentile_empty() -> Result<()> {
+        let chunk = DataChunk::from_pretty(
+            "F F
+             . 0.5",
+        );
+        assert_eq!(
+            percentile(&chunk, DataType::Float64, OrderType::Ascending, false)?,
+            None
+        );
+
+        let chunk = DataChunk::from_pretty(
+            "F F
+             1 1.5",
+        );
+        assert!(percentile(&chunk, DataType::Float64, OrderType::Ascending, false).is_err());
+        Ok(())
+    }
+}
@@ -18,7 +18,7 @@ use risingwave_common::array::{
 };
 use risingwave_common::bail;
 use risingwave_common::types::{DataType, Scalar};
-use risingwave_common::util::ordered::OrderedRow;
+use risingwave_common::util::ordered::{OrderedDatum, OrderedRow};
 use risingwave_common::util::sort_util::{OrderPair, OrderType};
 
 use crate::vector_op::agg::aggregator::Aggregator;
@@ -109,6 +109,10 @@ impl Aggregator for StringAggUnordered {
             bail!("Builder fail to match {}.", stringify!(Utf8))
         }
     }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.result.as_ref().map_or(0, String::capacity)
+    }
 }
 
 #[derive(Clone)]
@@ -124,6 +128,8 @@ struct StringAggOrdered {
     order_col_indices: Vec<usize>,
     order_types: Vec<OrderType>,
     unordered_values: Vec<(OrderedRow, StringAggData)>,
+    /// The heap memory of `unordered_values`.
+    values_heap_size: usize,
 }
 
 impl StringAggOrdered {
@@ -138,6 +144,7 @@ impl StringAggOrdered {
             order_col_indices,
             order_types,
             unordered_values: vec![],
+            values_heap_size: 0,
         }
     }
 
@@ -146,6 +153,9 @@ impl StringAggOrdered {
             row.row_by_indices(&self.order_col_indices),
             &self.order_types,
         );
+        self.values_heap_size += self.order_col_indices.len() * std::mem::size_of::<OrderedDatum>()
+            + value.len()
+            + delim.len();
         self.unordered_values.push((
             key,
             StringAggData {
@@ -157,6 +167,7 @@ impl StringAggOrdered {
 
     fn get_result_and_reset(&mut self) -> Option<String> {
         let mut rows = std::mem::take(&mut self.unordered_values);
+        self.values_heap_size = 0;
         if rows.is_empty() {
             return None;
         }
@@ -231,6 +242,12 @@ impl Aggregator for StringAggOrdered {
             bail!("Builder fail to match {}.", stringify!(Utf8))
         }
     }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.unordered_values.capacity() * std::mem::size_of::<(OrderedRow, StringAggData)>()
+            + self.values_heap_size
+    }
 }
 
 pub fn create_string_agg_state(
@@ -110,8 +110,8 @@ pub fn str_to_timestampz(elem: &str) -> Result<i64> {
 
 #[inline(always)]
 pub fn timestampz_to_utc_string(elem: i64) -> String {
-    // Just a meaningful representation as placeholder. The real implementation depends on TimeZone
-    // from session. See #3552.
+    // Only used when there's no time zone available. The casts bound by the frontend depend on the
+    // session TimeZone and are done by `CastWithTimeZone` instead.
     let instant = Utc.timestamp_nanos(elem * 1000);
     // PostgreSQL uses a space rather than `T` to separate the date and time.
     // https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-DATETIME-OUTPUT
@@ -119,8 +119,8 @@ pub fn timestampz_to_utc_string(elem: i64) -> String {
 }
 
 pub fn timestampz_to_utc_binary(elem: i64) -> Bytes {
-    // Just a meaningful representation as placeholder. The real implementation depends on TimeZone
-    // from session. See #3552.
+    // The binary format is independent of the session TimeZone, as it's always the microseconds
+    // since the UTC epoch.
     let instant = Utc.timestamp_nanos(elem * 1000);
     let mut out = BytesMut::new();
     // postgres_types::Type::ANY is only used as a placeholder.
@@ -290,6 +290,7 @@ macro_rules! for_all_cast_variants {
             { varchar, float64, str_parse },
             { varchar, decimal, str_parse },
             { varchar, boolean, str_to_bool },
+            { varchar, jsonb, str_parse },
             // `str_to_list` requires `target_elem_type` and is handled elsewhere
 
             { boolean, varchar, bool_to_varchar },
@@ -305,6 +306,7 @@ macro_rules! for_all_cast_variants {
             { timestamp, varchar, general_to_text },
             { timestampz, varchar, |x| Ok(timestampz_to_utc_string(x)) },
             { list, varchar, |x| general_to_text(x) },
+            { jsonb, varchar, |x| general_to_text(x) },
 
             { boolean, int32, general_cast },
             { int32, boolean, int32_to_bool },
@@ -194,6 +194,34 @@ pub fn str_is_not_distinct_from(l: Option<&str>, r: Option<&str>) -> Result<Opti
     }
 }
 
+pub fn struct_is_distinct_from(
+    l: Option<StructRef<'_>>,
+    r: Option<StructRef<'_>>,
+) -> Result<Option<bool>> {
+    Ok(Some(l != r))
+}
+
+pub fn struct_is_not_distinct_from(
+    l: Option<StructRef<'_>>,
+    r: Option<StructRef<'_>>,
+) -> Result<Option<bool>> {
+    Ok(Some(l == r))
+}
+
+pub fn list_is_distinct_from(
+    l: Option<ListRef<'_>>,
+    r: Option<ListRef<'_>>,
+) -> Result<Option<bool>> {
+    Ok(Some(l != r))
+}
+
+pub fn list_is_not_distinct_from(
+    l: Option<ListRef<'_>>,
+    r: Option<ListRef<'_>>,
+) -> Result<Option<bool>> {
+    Ok(Some(l == r))
+}
+
 #[inline(always)]
 pub fn is_true(v: Option<bool>) -> Result<Option<bool>> {
     Ok(Some(v == Some(true)))
@@ -228,7 +256,8 @@ pub fn is_not_unknown(v: Option<bool>) -> Result<Option<bool>> {
 mod tests {
     use std::str::FromStr;
 
-    use risingwave_common::types::Decimal;
+    use risingwave_common::array::ListValue;
+    use risingwave_common::types::{Decimal, ScalarImpl};
 
     use super::*;
 
@@ -236,4 +265,20 @@ mod tests {
     fn test_deci_f() {
         assert!(general_eq::<_, _, Decimal>(Decimal::from_str("1.1").unwrap(), 1.1f32).unwrap())
     }
+
+    #[test]
+    fn test_list_is_distinct_from() {
+        let v1 = ListValue::new(vec![Some(ScalarImpl::Int32(1)), None]);
+        let v2 = ListValue::new(vec![Some(ScalarImpl::Int32(1)), None]);
+        let v3 = ListValue::new(vec![Some(ScalarImpl::Int32(1))]);
+        let l1 = Some(ListRef::ValueRef { val: &v1 });
+        let l2 = Some(ListRef::ValueRef { val: &v2 });
+        let l3 = Some(ListRef::ValueRef { val: &v3 });
+
+        // Null elements are compared as equal, the same as in PostgreSQL.
+        assert_eq!(list_is_distinct_from(l1, l2).unwrap(), Some(false));
+        assert_eq!(list_is_distinct_from(l1, l3).unwrap(), Some(true));
+        assert_eq!(list_is_distinct_from(l1, None).unwrap(), Some(true));
+        assert_eq!(list_is_not_distinct_from(None, None).unwrap(), Some(true));
+    }
 }
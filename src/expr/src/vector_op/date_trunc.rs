@@ -0,0 +1,124 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, Duration, NaiveDate, Timelike};
+use risingwave_common::types::NaiveDateTimeWrapper;
+
+use super::timestampz::{timestamp_at_time_zone, timestampz_at_time_zone};
+use crate::{ExprError, Result};
+
+/// Truncates a timestamp to the given precision, e.g. `date_trunc('hour', timestamp)`.
+///
+/// See <https://www.postgresql.org/docs/current/functions-datetime.html#FUNCTIONS-DATETIME-TRUNC>
+pub fn date_trunc_timestamp(
+    field: &str,
+    input: NaiveDateTimeWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    let ts = input.0;
+    let date = ts.date();
+    let first_day_of_year = |year: i32| NaiveDate::from_ymd(year, 1, 1).and_hms(0, 0, 0);
+    let truncated = match field.to_ascii_lowercase().as_str() {
+        "microseconds" => ts,
+        "milliseconds" => ts
+            .with_nanosecond(ts.nanosecond() / 1_000_000 * 1_000_000)
+            .unwrap(),
+        "second" => ts.with_nanosecond(0).unwrap(),
+        "minute" => date.and_hms(ts.hour(), ts.minute(), 0),
+        "hour" => date.and_hms(ts.hour(), 0, 0),
+        "day" => date.and_hms(0, 0, 0),
+        // Weeks start on Monday, the same as ISO 8601.
+        "week" => {
+            (date - Duration::days(date.weekday().num_days_from_monday() as i64)).and_hms(0, 0, 0)
+        }
+        "month" => NaiveDate::from_ymd(date.year(), date.month(), 1).and_hms(0, 0, 0),
+        "quarter" => {
+            NaiveDate::from_ymd(date.year(), (date.month() - 1) / 3 * 3 + 1, 1).and_hms(0, 0, 0)
+        }
+        "year" => first_day_of_year(date.year()),
+        "decade" => first_day_of_year(date.year().div_euclid(10) * 10),
+        // Centuries and millenniums start from year 1 rather than 0, e.g. the 21st century starts
+        // from 2001.
+        "century" => first_day_of_year((date.year() - 1).div_euclid(100) * 100 + 1),
+        "millennium" => first_day_of_year((date.year() - 1).div_euclid(1000) * 1000 + 1),
+        _ => {
+            return Err(ExprError::InvalidParam {
+                name: "field",
+                reason: format!("timestamp units \"{}\" not recognized", field),
+            })
+        }
+    };
+    Ok(NaiveDateTimeWrapper::new(truncated))
+}
+
+/// Truncates a timestamp with time zone to the given precision, where the truncation is done on
+/// the local time in `time_zone`.
+pub fn date_trunc_timestampz(field: &str, input: i64, time_zone: &str) -> Result<i64> {
+    let local = timestampz_at_time_zone(input, time_zone)?;
+    let truncated = date_trunc_timestamp(field, local)?;
+    timestamp_at_time_zone(truncated, time_zone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector_op::cast::{str_to_timestamp, str_to_timestampz};
+
+    #[test]
+    fn test_date_trunc_timestamp() {
+        let ts = str_to_timestamp("2022-11-23 12:34:56.789012").unwrap();
+        for (field, expected) in [
+            ("microseconds", "2022-11-23 12:34:56.789012"),
+            ("milliseconds", "2022-11-23 12:34:56.789"),
+            ("second", "2022-11-23 12:34:56"),
+            ("minute", "2022-11-23 12:34:00"),
+            ("HOUR", "2022-11-23 12:00:00"),
+            ("day", "2022-11-23 00:00:00"),
+            ("week", "2022-11-21 00:00:00"),
+            ("month", "2022-11-01 00:00:00"),
+            ("quarter", "2022-10-01 00:00:00"),
+            ("year", "2022-01-01 00:00:00"),
+            ("decade", "2020-01-01 00:00:00"),
+            ("century", "2001-01-01 00:00:00"),
+            ("millennium", "2001-01-01 00:00:00"),
+        ] {
+            assert_eq!(
+                date_trunc_timestamp(field, ts).unwrap(),
+                str_to_timestamp(expected).unwrap(),
+                "{}",
+                field
+            );
+        }
+        assert!(date_trunc_timestamp("fortnight", ts).is_err());
+    }
+
+    #[test]
+    fn test_date_trunc_timestampz() {
+        let usecs = str_to_timestampz("2022-11-23 02:34:56Z").unwrap();
+        for (field, time_zone, expected) in [
+            ("hour", "UTC", "2022-11-23 02:00:00Z"),
+            ("day", "UTC", "2022-11-23 00:00:00Z"),
+            ("day", "US/Pacific", "2022-11-22 08:00:00Z"),
+            ("day", "Asia/Singapore", "2022-11-22 16:00:00Z"),
+            ("month", "US/Pacific", "2022-11-01 07:00:00Z"),
+        ] {
+            assert_eq!(
+                date_trunc_timestampz(field, usecs, time_zone).unwrap(),
+                str_to_timestampz(expected).unwrap(),
+                "{} {}",
+                field,
+                time_zone
+            );
+        }
+    }
+}
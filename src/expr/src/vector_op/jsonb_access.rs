@@ -0,0 +1,129 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::ListRef;
+use risingwave_common::types::{JsonbRef, JsonbVal, ScalarRef, ScalarRefImpl};
+
+use crate::Result;
+
+/// `jsonb -> text`, extracts the object field with the given key.
+#[inline(always)]
+pub fn jsonb_object_field(l: Option<JsonbRef<'_>>, r: Option<&str>) -> Result<Option<JsonbVal>> {
+    match (l, r) {
+        (Some(jsonb), Some(key)) => Ok(jsonb.access_object_field(key).map(|v| v.to_owned_scalar())),
+        _ => Ok(None),
+    }
+}
+
+/// `jsonb -> int`, extracts the 0-based array element, counting from the end if negative.
+#[inline(always)]
+pub fn jsonb_array_element(l: Option<JsonbRef<'_>>, r: Option<i32>) -> Result<Option<JsonbVal>> {
+    match (l, r) {
+        (Some(jsonb), Some(idx)) => {
+            Ok(jsonb.access_array_element(idx).map(|v| v.to_owned_scalar()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// `jsonb ->> text`, extracts the object field with the given key as text.
+#[inline(always)]
+pub fn jsonb_object_field_text(l: Option<JsonbRef<'_>>, r: Option<&str>) -> Result<Option<String>> {
+    match (l, r) {
+        (Some(jsonb), Some(key)) => Ok(jsonb.access_object_field(key).and_then(|v| v.as_text())),
+        _ => Ok(None),
+    }
+}
+
+/// `jsonb ->> int`, extracts the array element as text.
+#[inline(always)]
+pub fn jsonb_array_element_text(l: Option<JsonbRef<'_>>, r: Option<i32>) -> Result<Option<String>> {
+    match (l, r) {
+        (Some(jsonb), Some(idx)) => Ok(jsonb.access_array_element(idx).and_then(|v| v.as_text())),
+        _ => Ok(None),
+    }
+}
+
+/// `jsonb #> text[]` and `jsonb_extract_path`, extracts the sub-object at the given path. Returns
+/// `NULL` if any of the path elements is `NULL`.
+#[inline(always)]
+pub fn jsonb_extract_path(
+    l: Option<JsonbRef<'_>>,
+    path: Option<ListRef<'_>>,
+) -> Result<Option<JsonbVal>> {
+    let (Some(jsonb), Some(path)) = (l, path) else {
+        return Ok(None);
+    };
+    let keys: Option<Vec<&str>> = path
+        .values_ref()
+        .into_iter()
+        .map(|key| match key {
+            Some(ScalarRefImpl::Utf8(key)) => Some(key),
+            _ => None,
+        })
+        .collect();
+    Ok(keys
+        .and_then(|keys| jsonb.access_path(keys))
+        .map(|v| v.to_owned_scalar()))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::ListValue;
+    use risingwave_common::types::{Scalar, ScalarImpl};
+
+    use super::*;
+
+    #[test]
+    fn test_jsonb_access() {
+        let v: JsonbVal = r#"{"a": [1, {"b": "x"}], "c": null}"#.parse().unwrap();
+        let v = Some(v.as_scalar_ref());
+
+        let a = jsonb_object_field(v, Some("a")).unwrap().unwrap();
+        assert_eq!(a.to_string(), r#"[1, {"b": "x"}]"#);
+        assert_eq!(jsonb_object_field(v, Some("d")).unwrap(), None);
+        assert_eq!(jsonb_object_field(v, None).unwrap(), None);
+
+        let a = Some(a.as_scalar_ref());
+        assert_eq!(
+            jsonb_array_element(a, Some(0))
+                .unwrap()
+                .unwrap()
+                .to_string(),
+            "1"
+        );
+        assert_eq!(
+            jsonb_array_element_text(a, Some(-1)).unwrap().unwrap(),
+            r#"{"b": "x"}"#
+        );
+        assert_eq!(jsonb_array_element(v, Some(0)).unwrap(), None);
+
+        // Strings are unquoted and JSON `null` becomes SQL `NULL`.
+        assert_eq!(jsonb_object_field_text(v, Some("c")).unwrap(), None);
+
+        let path = ListValue::new(vec![
+            Some(ScalarImpl::Utf8("a".into())),
+            Some(ScalarImpl::Utf8("1".into())),
+            Some(ScalarImpl::Utf8("b".into())),
+        ]);
+        let path = Some(ListRef::ValueRef { val: &path });
+        assert_eq!(
+            jsonb_extract_path(v, path).unwrap().unwrap().to_string(),
+            r#""x""#
+        );
+        let path = ListValue::new(vec![Some(ScalarImpl::Utf8("a".into())), None]);
+        let path = Some(ListRef::ValueRef { val: &path });
+        assert_eq!(jsonb_extract_path(v, path).unwrap(), None);
+    }
+}
@@ -17,11 +17,12 @@ use chrono_tz::Tz;
 use num_traits::ToPrimitive;
 use risingwave_common::types::{NaiveDateTimeWrapper, OrderedF64};
 
+use super::cast::{str_to_timestamp, str_to_timestampz};
 use crate::{ExprError, Result};
 
 /// Just a wrapper to reuse the `map_err` logic.
 #[inline(always)]
-fn lookup_time_zone(time_zone: &str) -> Result<Tz> {
+pub fn lookup_time_zone(time_zone: &str) -> Result<Tz> {
     Tz::from_str_insensitive(time_zone).map_err(|e| ExprError::InvalidParam {
         name: "time_zone",
         reason: e,
@@ -71,6 +72,27 @@ pub fn timestampz_at_time_zone(input: i64, time_zone: &str) -> Result<NaiveDateT
     Ok(NaiveDateTimeWrapper(naive))
 }
 
+/// Formats the instant as the local time in `time_zone` with its UTC offset, e.g.
+/// `2022-10-01 05:00:00-07:00`.
+#[inline(always)]
+pub fn timestampz_to_string_in_time_zone(elem: i64, time_zone: &str) -> Result<String> {
+    let time_zone = lookup_time_zone(time_zone)?;
+    let instant_local = Utc.timestamp_nanos(elem * 1000).with_timezone(&time_zone);
+    // PostgreSQL uses a space rather than `T` to separate the date and time.
+    // https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-DATETIME-OUTPUT
+    Ok(instant_local.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string())
+}
+
+/// Parses a timestamp with time zone. The input without an explicit UTC offset is interpreted as
+/// the local time in `time_zone`.
+#[inline(always)]
+pub fn str_to_timestampz_in_time_zone(elem: &str, time_zone: &str) -> Result<i64> {
+    match str_to_timestampz(elem) {
+        Ok(usecs) => Ok(usecs),
+        Err(_) => timestamp_at_time_zone(str_to_timestamp(elem)?, time_zone),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
@@ -78,7 +100,6 @@ mod tests {
     use itertools::Itertools;
 
     use super::*;
-    use crate::vector_op::cast::{str_to_timestamp, str_to_timestampz};
 
     #[test]
     fn test_time_zone_conversion() {
@@ -116,6 +137,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_timestampz_string_in_time_zone() {
+        #[rustfmt::skip]
+        let test_cases = [
+            ("2022-01-01 00:00:00", "UTC", "2022-01-01 00:00:00+00:00"),
+            ("2022-01-01 00:00:00", "US/Pacific", "2022-01-01 00:00:00-08:00"),
+            ("2022-07-01 00:00:00.5", "US/Pacific", "2022-07-01 00:00:00.500-07:00"),
+            ("2022-01-01 00:00:00+08:00", "US/Pacific", "2021-12-31 08:00:00-08:00"),
+            ("2022-01-01 00:00:00Z", "Asia/Singapore", "2022-01-01 08:00:00+08:00"),
+        ];
+        for (input, zone, output) in test_cases {
+            let usecs = str_to_timestampz_in_time_zone(input, zone).unwrap();
+            assert_eq!(
+                timestampz_to_string_in_time_zone(usecs, zone).unwrap(),
+                output
+            );
+        }
+        assert_matches!(
+            str_to_timestampz_in_time_zone("2022-01-01 00:00:00", "Mars/Olympus"),
+            Err(_)
+        );
+    }
+
     #[test]
     fn test_time_zone_conversion_daylight_forward() {
         for (local, zone) in [
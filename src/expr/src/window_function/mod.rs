@@ -0,0 +1,743 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Evaluation of window functions (`OVER` clause) over a single partition.
+
+use std::ops::Range;
+
+use itertools::Itertools;
+use num_traits::CheckedAdd;
+use risingwave_common::array::{DataChunk, Row};
+use risingwave_common::bail;
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_pb::expr::window_function::{FrameBound, RowsFrame as RowsFrameProst, Type};
+use risingwave_pb::expr::WindowFunction as WindowFunctionProst;
+
+use crate::expr::AggKind;
+use crate::vector_op::agg::{AggStateFactory, BoxedAggState};
+use crate::{ExprError, Result};
+
+mod session;
+pub use session::*;
+
+/// Kind of window function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WindowFuncKind {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag,
+    Lead,
+    Aggregate(AggKind),
+}
+
+/// A `ROWS` window frame, with the bounds as the offsets relative to the current row, negative for
+/// the preceding rows. Unbounded bounds are `None`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RowsFrame {
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+}
+
+impl RowsFrame {
+    pub fn from_protobuf(prost: &RowsFrameProst) -> Self {
+        Self {
+            start: prost.start.as_ref().map(|bound| bound.offset),
+            end: prost.end.as_ref().map(|bound| bound.offset),
+        }
+    }
+
+    pub fn to_protobuf(self) -> RowsFrameProst {
+        RowsFrameProst {
+            start: self.start.map(|offset| FrameBound { offset }),
+            end: self.end.map(|offset| FrameBound { offset }),
+        }
+    }
+
+    /// Returns the range `[start, end)` of the frame of the `i`-th row in a partition of `len`
+    /// rows, which is empty if the frame is out of the partition.
+    fn range_of(&self, i: usize, len: usize) -> (usize, usize) {
+        let bound = |offset: i64| (i as i64).saturating_add(offset).clamp(0, len as i64) as usize;
+        let start = self.start.map_or(0, bound);
+        let end = self
+            .end
+            .map_or(len, |offset| bound(offset.saturating_add(1)));
+        (start, end.max(start))
+    }
+}
+
+impl std::fmt::Display for RowsFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bound =
+            |f: &mut std::fmt::Formatter<'_>, offset: Option<i64>, unbounded: &str| match offset {
+                None => write!(f, "UNBOUNDED {unbounded}"),
+                Some(0) => f.write_str("CURRENT ROW"),
+                Some(n) if n < 0 => write!(f, "{} PRECEDING", n.unsigned_abs()),
+                Some(n) => write!(f, "{n} FOLLOWING"),
+            };
+        f.write_str("ROWS BETWEEN ")?;
+        bound(f, self.start, "PRECEDING")?;
+        f.write_str(" AND ")?;
+        bound(f, self.end, "FOLLOWING")
+    }
+}
+
+/// A window function call, with its arguments referring to the columns of the input.
+pub struct WindowFuncCall {
+    kind: WindowFuncKind,
+    args: Vec<usize>,
+    return_type: DataType,
+    offset: usize,
+    agg_state_factory: Option<AggStateFactory>,
+    /// Whether the aggregate call is `DISTINCT`.
+    distinct: bool,
+    /// Only set for an aggregate call with a `ROWS` frame.
+    rows_frame: Option<RowsFrame>,
+}
+
+impl WindowFuncCall {
+    pub fn from_protobuf(prost: &WindowFunctionProst) -> Result<Self> {
+        let args: Vec<usize> = prost
+            .get_args()
+            .iter()
+            .map(|arg| arg.get_input().map(|input| input.get_column_idx() as usize))
+            .try_collect()?;
+        let return_type = DataType::from(prost.get_return_type()?);
+
+        let (kind, agg_state_factory, distinct) = match prost.get_type()? {
+            Type::RowNumber => (WindowFuncKind::RowNumber, None, false),
+            Type::Rank => (WindowFuncKind::Rank, None, false),
+            Type::DenseRank => (WindowFuncKind::DenseRank, None, false),
+            Type::Lag => (WindowFuncKind::Lag, None, false),
+            Type::Lead => (WindowFuncKind::Lead, None, false),
+            Type::Aggregate => {
+                let agg_call = prost.get_agg_call()?;
+                let agg_kind = AggKind::try_from(agg_call.get_type()?)?;
+                (
+                    WindowFuncKind::Aggregate(agg_kind),
+                    Some(AggStateFactory::new(agg_call)?),
+                    agg_call.distinct,
+                )
+            }
+            Type::Unspecified => bail!("Unrecognized window function."),
+        };
+        let rows_frame = prost.rows_frame.as_ref().map(RowsFrame::from_protobuf);
+
+        Ok(Self {
+            kind,
+            args,
+            return_type,
+            offset: prost.offset as usize,
+            agg_state_factory,
+            distinct,
+            rows_frame,
+        })
+    }
+
+    pub fn kind(&self) -> WindowFuncKind {
+        self.kind
+    }
+
+    pub fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    /// Returns the number of rows following the current row that its result depends on, or `None`
+    /// if the result depends on all the following rows. The peers of the current row are not
+    /// counted, see [`WindowFuncCall::depends_on_peers`].
+    pub fn following_rows(&self) -> Option<usize> {
+        match (self.kind, self.rows_frame) {
+            (WindowFuncKind::Lead, _) => Some(self.offset),
+            (WindowFuncKind::Aggregate(_), Some(RowsFrame { end, .. })) => {
+                end.map(|end| end.max(0) as usize)
+            }
+            _ => Some(0),
+        }
+    }
+
+    /// Returns the number of rows preceding the current row that its result depends on, or `None`
+    /// if the result depends on all the preceding rows.
+    pub fn preceding_rows(&self) -> Option<usize> {
+        match (self.kind, self.rows_frame) {
+            (WindowFuncKind::Lag, _) => Some(self.offset),
+            (WindowFuncKind::Lead, _) => Some(0),
+            (WindowFuncKind::Aggregate(_), Some(RowsFrame { start, .. })) => {
+                start.map(|start| start.min(0).unsigned_abs() as usize)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether the result of the current row depends on all of its peers, which is the case
+    /// for an aggregate call without a `ROWS` frame.
+    pub fn depends_on_peers(&self) -> bool {
+        matches!(self.kind, WindowFuncKind::Aggregate(_)) && self.rows_frame.is_none()
+    }
+
+    /// Returns whether the partial results of the aggregate call over disjoint rows can be merged
+    /// by [`merge_partial`].
+    fn is_mergeable(&self) -> bool {
+        match self.kind {
+            WindowFuncKind::Aggregate(AggKind::Min | AggKind::Max) => true,
+            WindowFuncKind::Aggregate(AggKind::Sum | AggKind::Sum0 | AggKind::Count) => {
+                !self.distinct
+            }
+            _ => false,
+        }
+    }
+
+    fn create_agg_state(&self) -> BoxedAggState {
+        self.agg_state_factory.as_ref().unwrap().create_agg_state()
+    }
+}
+
+/// Merges the partial results of an aggregate call over two disjoint sets of rows.
+fn merge_partial(kind: AggKind, lhs: Datum, rhs: Datum) -> Result<Datum> {
+    // A `NULL` partial result adds nothing to the other one for all the mergeable kinds.
+    let (lhs, rhs) = match (lhs, rhs) {
+        (None, partial) | (partial, None) => return Ok(partial),
+        (Some(lhs), Some(rhs)) => (lhs, rhs),
+    };
+    let merged = match kind {
+        AggKind::Min => {
+            if rhs < lhs {
+                rhs
+            } else {
+                lhs
+            }
+        }
+        AggKind::Max => {
+            if rhs > lhs {
+                rhs
+            } else {
+                lhs
+            }
+        }
+        AggKind::Sum | AggKind::Sum0 | AggKind::Count => match (lhs, rhs) {
+            (ScalarImpl::Int16(l), ScalarImpl::Int16(r)) => l.checked_add(r).map(ScalarImpl::Int16),
+            (ScalarImpl::Int32(l), ScalarImpl::Int32(r)) => l.checked_add(r).map(ScalarImpl::Int32),
+            (ScalarImpl::Int64(l), ScalarImpl::Int64(r)) => l.checked_add(r).map(ScalarImpl::Int64),
+            (ScalarImpl::Float32(l), ScalarImpl::Float32(r)) => {
+                CheckedAdd::checked_add(&l, &r).map(ScalarImpl::Float32)
+            }
+            (ScalarImpl::Float64(l), ScalarImpl::Float64(r)) => {
+                CheckedAdd::checked_add(&l, &r).map(ScalarImpl::Float64)
+            }
+            (ScalarImpl::Decimal(l), ScalarImpl::Decimal(r)) => {
+                CheckedAdd::checked_add(&l, &r).map(ScalarImpl::Decimal)
+            }
+            (ScalarImpl::Interval(l), ScalarImpl::Interval(r)) => {
+                CheckedAdd::checked_add(&l, &r).map(ScalarImpl::Interval)
+            }
+            (lhs, rhs) => bail!("Cannot add the partial sums {:?} and {:?}", lhs, rhs),
+        }
+        .ok_or(ExprError::NumericOutOfRange)?,
+        kind => bail!("Cannot merge the partial results of {:?}", kind),
+    };
+    Ok(Some(merged))
+}
+
+/// Takes the result out of an aggregate state, which is reset afterwards.
+fn take_output(state: &mut BoxedAggState, return_type: &DataType) -> Result<Datum> {
+    let mut builder = return_type.create_array_builder(1);
+    state.output(&mut builder)?;
+    Ok(builder.finish().datum_at(0))
+}
+
+/// Evaluates `calls` over the rows of one partition, which must be sorted by the window order,
+/// and returns the results of the rows in `range`.
+///
+/// Rows with equal values on `order_key_indices` are peers: they share the same `RANK` and see
+/// the same aggregate frame, i.e. `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`. When there
+/// is no order key, all rows of the partition are peers. Aggregate calls with a `ROWS` frame see
+/// the rows within their frame instead.
+///
+/// Only the rows that the results in `range` depend on are read, see
+/// [`WindowFuncCall::preceding_rows`] and [`WindowFuncCall::following_rows`], and each of them is
+/// aggregated a constant number of times. The exception is a `ROWS` frame with a bounded start
+/// on an aggregate whose partial results cannot be merged, e.g. `avg`, where every frame is
+/// aggregated from scratch.
+///
+/// Returns one column of datums for each call, with one datum for each row in `range`.
+pub fn evaluate_partition(
+    calls: &[WindowFuncCall],
+    rows: &[Row],
+    data_types: &[DataType],
+    order_key_indices: &[usize],
+    range: Range<usize>,
+) -> Result<Vec<Vec<Datum>>> {
+    if range.is_empty() {
+        return Ok(calls.iter().map(|_| vec![]).collect());
+    }
+
+    // Split the partition into groups of peers, represented as `[start, end)`, up to the group of
+    // the last row in `range`.
+    let mut peer_groups = vec![];
+    if calls.iter().any(|call| {
+        matches!(call.kind, WindowFuncKind::Rank | WindowFuncKind::DenseRank)
+            || call.depends_on_peers()
+    }) {
+        let mut start = 0;
+        for i in 1..=rows.len() {
+            if i == rows.len()
+                || order_key_indices
+                    .iter()
+                    .any(|&idx| rows[i].0[idx] != rows[start].0[idx])
+            {
+                peer_groups.push((start, i));
+                if i >= range.end {
+                    break;
+                }
+                start = i;
+            }
+        }
+    }
+    let group_of = |i: usize| peer_groups.partition_point(|&(_, end)| end <= i);
+
+    // The rows read by the aggregate calls, which are shared by all of them as a chunk.
+    let agg_rows = calls
+        .iter()
+        .filter(|call| matches!(call.kind, WindowFuncKind::Aggregate(_)))
+        .map(|call| match &call.rows_frame {
+            Some(frame) => {
+                let (start, _) = frame.range_of(range.start, rows.len());
+                let (_, end) = frame.range_of(range.end - 1, rows.len());
+                start..end
+            }
+            None => 0..peer_groups.last().unwrap().1,
+        })
+        .reduce(|a, b| a.start.min(b.start)..a.end.max(b.end));
+    let chunk = agg_rows
+        .as_ref()
+        .map(|agg_rows| DataChunk::from_rows(&rows[agg_rows.clone()], data_types));
+
+    let mut results = Vec::with_capacity(calls.len());
+    for call in calls {
+        let outputs: Vec<Datum> = match call.kind {
+            WindowFuncKind::RowNumber => {
+                range.clone().map(|i| Some((i as i64 + 1).into())).collect()
+            }
+            WindowFuncKind::Rank => range
+                .clone()
+                .map(|i| Some((peer_groups[group_of(i)].0 as i64 + 1).into()))
+                .collect(),
+            WindowFuncKind::DenseRank => range
+                .clone()
+                .map(|i| Some((group_of(i) as i64 + 1).into()))
+                .collect(),
+            WindowFuncKind::Lag | WindowFuncKind::Lead => {
+                let arg = call.args[0];
+                range
+                    .clone()
+                    .map(|i| {
+                        let target = if call.kind == WindowFuncKind::Lag {
+                            i.checked_sub(call.offset)
+                        } else {
+                            Some(i + call.offset).filter(|&j| j < rows.len())
+                        };
+                        target.and_then(|j| rows[j].0[arg].clone())
+                    })
+                    .collect()
+            }
+            WindowFuncKind::Aggregate(_) => {
+                let chunk = chunk.as_ref().unwrap();
+                let base = agg_rows.as_ref().unwrap().start;
+                if let Some(frame) = &call.rows_frame {
+                    let frames = range
+                        .clone()
+                        .map(|i| {
+                            let (start, end) = frame.range_of(i, rows.len());
+                            (start - base, end - base)
+                        })
+                        .collect_vec();
+                    evaluate_rows_frame(call, chunk, &frames)?
+                } else {
+                    let mut outputs = Vec::with_capacity(range.len());
+                    let mut state = call.create_agg_state();
+                    for &(start, end) in &peer_groups {
+                        state.update_multi(chunk, start - base, end - base)?;
+                        if end <= range.start {
+                            continue;
+                        }
+                        // `output` resets the state, so take the result from a snapshot to keep
+                        // accumulating the following peer groups.
+                        let mut snapshot = dyn_clone::clone_box(&*state);
+                        let datum = take_output(&mut snapshot, &call.return_type)?;
+                        outputs.extend(
+                            (start.max(range.start)..end.min(range.end)).map(|_| datum.clone()),
+                        );
+                    }
+                    outputs
+                }
+            }
+        };
+        results.push(outputs);
+    }
+    Ok(results)
+}
+
+/// Evaluates an aggregate call with a `ROWS` frame, given the frame `[start, end)` of each row as
+/// the positions in `chunk`, which are non-decreasing in both bounds.
+fn evaluate_rows_frame(
+    call: &WindowFuncCall,
+    chunk: &DataChunk,
+    frames: &[(usize, usize)],
+) -> Result<Vec<Datum>> {
+    let WindowFuncKind::Aggregate(kind) = call.kind else {
+        unreachable!("not an aggregate call: {:?}", call.kind)
+    };
+    let return_type = &call.return_type;
+    let mut state = call.create_agg_state();
+
+    if call.rows_frame.unwrap().start.is_none() {
+        // All frames start from the first row, so the state keeps accumulating the rows entering
+        // the frames.
+        let mut outputs = Vec::with_capacity(frames.len());
+        let mut accumulated = 0;
+        for &(_, end) in frames {
+            if accumulated < end {
+                state.update_multi(chunk, accumulated, end)?;
+                accumulated = end;
+            }
+            let mut snapshot = dyn_clone::clone_box(&*state);
+            outputs.push(take_output(&mut snapshot, return_type)?);
+        }
+        return Ok(outputs);
+    }
+
+    if !call.is_mergeable() {
+        // Every frame is aggregated from scratch.
+        return frames
+            .iter()
+            .map(|&(start, end)| {
+                if start < end {
+                    state.update_multi(chunk, start, end)?;
+                }
+                take_output(&mut state, return_type)
+            })
+            .collect();
+    }
+
+    // Split the rows into blocks as wide as the widest frame, so that every frame is within one
+    // block, or spans two adjacent ones. The partial results over the prefixes and the suffixes
+    // of the blocks are then merged into the result over any frame.
+    let first = frames[0].0;
+    let len = frames.last().unwrap().1 - first;
+    let width = frames
+        .iter()
+        .map(|&(start, end)| end - start)
+        .max()
+        .unwrap()
+        .max(1);
+    let singles: Vec<Datum> = (first..first + len)
+        .map(|i| {
+            state.update_single(chunk, i)?;
+            take_output(&mut state, return_type)
+        })
+        .try_collect()?;
+    let mut prefixes: Vec<Datum> = Vec::with_capacity(len);
+    for (i, single) in singles.iter().enumerate() {
+        let prefix = if i % width == 0 {
+            single.clone()
+        } else {
+            merge_partial(kind, prefixes[i - 1].clone(), single.clone())?
+        };
+        prefixes.push(prefix);
+    }
+    let mut suffixes: Vec<Datum> = vec![None; len];
+    for (i, single) in singles.iter().enumerate().rev() {
+        suffixes[i] = if i % width == width - 1 || i == len - 1 {
+            single.clone()
+        } else {
+            merge_partial(kind, single.clone(), suffixes[i + 1].clone())?
+        };
+    }
+
+    let empty = take_output(&mut state, return_type)?;
+    frames
+        .iter()
+        .map(|&(start, end)| {
+            if start == end {
+                return Ok(empty.clone());
+            }
+            let (start, last) = (start - first, end - 1 - first);
+            let block_end = ((start / width + 1) * width).min(len);
+            if start / width != last / width {
+                merge_partial(kind, suffixes[start].clone(), prefixes[last].clone())
+            } else if start % width == 0 {
+                Ok(prefixes[last].clone())
+            } else if last + 1 == block_end {
+                Ok(suffixes[start].clone())
+            } else {
+                singles[start..=last]
+                    .iter()
+                    .try_fold(None, |acc, single| merge_partial(kind, acc, single.clone()))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::expr::agg_call::{Arg, Type as AggType};
+    use risingwave_pb::expr::{AggCall, InputRefExpr};
+
+    use super::*;
+
+    fn call(kind: WindowFuncKind, args: Vec<usize>, offset: usize) -> WindowFuncCall {
+        WindowFuncCall {
+            kind,
+            args,
+            return_type: DataType::Int64,
+            offset,
+            agg_state_factory: None,
+            distinct: false,
+            rows_frame: None,
+        }
+    }
+
+    fn agg_call(
+        agg_type: AggType,
+        return_type: DataType,
+        rows_frame: Option<RowsFrame>,
+    ) -> WindowFuncCall {
+        let agg_call = AggCall {
+            r#type: agg_type as i32,
+            args: vec![Arg {
+                input: Some(InputRefExpr { column_idx: 0 }),
+                r#type: Some(DataType::Int32.to_protobuf()),
+            }],
+            return_type: Some(return_type.to_protobuf()),
+            ..Default::default()
+        };
+        WindowFuncCall {
+            return_type,
+            agg_state_factory: Some(AggStateFactory::new(&agg_call).unwrap()),
+            rows_frame,
+            ..call(
+                WindowFuncKind::Aggregate(AggKind::try_from(agg_type).unwrap()),
+                vec![0],
+                0,
+            )
+        }
+    }
+
+    fn sum_call(rows_frame: Option<RowsFrame>) -> WindowFuncCall {
+        agg_call(AggType::Sum, DataType::Int64, rows_frame)
+    }
+
+    #[test]
+    fn test_evaluate_partition() {
+        let rows = [1, 1, 2, 3, 3, 3]
+            .into_iter()
+            .map(|v| Row::new(vec![Some(ScalarImpl::Int32(v))]))
+            .collect_vec();
+        let calls = vec![
+            call(WindowFuncKind::RowNumber, vec![], 0),
+            call(WindowFuncKind::Rank, vec![], 0),
+            call(WindowFuncKind::DenseRank, vec![], 0),
+            call(WindowFuncKind::Lag, vec![0], 1),
+            call(WindowFuncKind::Lead, vec![0], 2),
+        ];
+        let outputs =
+            evaluate_partition(&calls, &rows, &[DataType::Int32], &[0], 0..rows.len()).unwrap();
+
+        let int64s = |values: &[i64]| {
+            values
+                .iter()
+                .map(|&v| Some(ScalarImpl::Int64(v)))
+                .collect_vec()
+        };
+        let int32s = |values: &[Option<i32>]| {
+            values
+                .iter()
+                .map(|v| v.map(ScalarImpl::Int32))
+                .collect_vec()
+        };
+        assert_eq!(outputs[0], int64s(&[1, 2, 3, 4, 5, 6]));
+        assert_eq!(outputs[1], int64s(&[1, 1, 3, 4, 4, 4]));
+        assert_eq!(outputs[2], int64s(&[1, 1, 2, 3, 3, 3]));
+        assert_eq!(
+            outputs[3],
+            int32s(&[None, Some(1), Some(1), Some(2), Some(3), Some(3)])
+        );
+        assert_eq!(
+            outputs[4],
+            int32s(&[Some(2), Some(3), Some(3), Some(3), None, None])
+        );
+
+        for range in [0..1, 2..5, 4..6] {
+            let partial =
+                evaluate_partition(&calls, &rows, &[DataType::Int32], &[0], range.clone()).unwrap();
+            for (partial, output) in partial.iter().zip_eq(&outputs) {
+                assert_eq!(partial[..], output[range.clone()]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_rows_frame() {
+        let rows = (1..=5)
+            .map(|v| Row::new(vec![Some(ScalarImpl::Int32(v))]))
+            .collect_vec();
+        let frame = |start, end| Some(RowsFrame { start, end });
+        let calls = vec![
+            sum_call(None),
+            sum_call(frame(Some(-1), Some(0))),
+            sum_call(frame(Some(0), Some(1))),
+            sum_call(frame(Some(3), None)),
+        ];
+        let outputs =
+            evaluate_partition(&calls, &rows, &[DataType::Int32], &[0], 0..rows.len()).unwrap();
+
+        let int64s = |values: &[Option<i64>]| {
+            values
+                .iter()
+                .map(|v| v.map(ScalarImpl::Int64))
+                .collect_vec()
+        };
+        assert_eq!(
+            outputs[0],
+            int64s(&[Some(1), Some(3), Some(6), Some(10), Some(15)])
+        );
+        assert_eq!(
+            outputs[1],
+            int64s(&[Some(1), Some(3), Some(5), Some(7), Some(9)])
+        );
+        assert_eq!(
+            outputs[2],
+            int64s(&[Some(3), Some(5), Some(7), Some(9), Some(5)])
+        );
+        assert_eq!(outputs[3], int64s(&[Some(9), Some(5), None, None, None]));
+    }
+
+    #[test]
+    fn test_evaluate_rows_frame_incrementally() {
+        let values = [
+            Some(3),
+            None,
+            Some(-1),
+            Some(4),
+            Some(1),
+            Some(5),
+            Some(-9),
+            Some(2),
+            Some(6),
+        ];
+        let rows = values
+            .iter()
+            .map(|v| Row::new(vec![v.map(ScalarImpl::Int32)]))
+            .collect_vec();
+        let frames = [
+            RowsFrame {
+                start: Some(-2),
+                end: Some(1),
+            },
+            RowsFrame {
+                start: Some(-1),
+                end: Some(-1),
+            },
+            RowsFrame {
+                start: Some(1),
+                end: Some(3),
+            },
+            RowsFrame {
+                start: Some(-1),
+                end: None,
+            },
+            RowsFrame {
+                start: None,
+                end: Some(2),
+            },
+            RowsFrame {
+                start: Some(-3),
+                end: Some(-5),
+            },
+        ];
+
+        for frame in frames {
+            let calls = vec![
+                agg_call(AggType::Sum, DataType::Int64, Some(frame)),
+                agg_call(AggType::Count, DataType::Int64, Some(frame)),
+                agg_call(AggType::Min, DataType::Int32, Some(frame)),
+                agg_call(AggType::Max, DataType::Int32, Some(frame)),
+            ];
+            let mut expected = vec![vec![]; calls.len()];
+            for i in 0..values.len() {
+                let (start, end) = frame.range_of(i, values.len());
+                let frame_values = values[start..end].iter().flatten().copied().collect_vec();
+                let sum = frame_values.iter().map(|&v| v as i64).reduce(|a, b| a + b);
+                expected[0].push(sum.map(ScalarImpl::Int64));
+                expected[1].push(Some(ScalarImpl::Int64(frame_values.len() as i64)));
+                expected[2].push(frame_values.iter().min().copied().map(ScalarImpl::Int32));
+                expected[3].push(frame_values.iter().max().copied().map(ScalarImpl::Int32));
+            }
+
+            for range in [0..rows.len(), 0..2, 3..7, 8..9] {
+                let outputs =
+                    evaluate_partition(&calls, &rows, &[DataType::Int32], &[], range.clone())
+                        .unwrap();
+                for (output, expected) in outputs.iter().zip_eq(&expected) {
+                    assert_eq!(output[..], expected[range.clone()], "{frame}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_preceding_rows() {
+        let frame = |start, end| Some(RowsFrame { start, end });
+        assert_eq!(
+            call(WindowFuncKind::RowNumber, vec![], 0).preceding_rows(),
+            None
+        );
+        assert_eq!(
+            call(WindowFuncKind::Lag, vec![0], 2).preceding_rows(),
+            Some(2)
+        );
+        assert_eq!(
+            call(WindowFuncKind::Lead, vec![0], 2).preceding_rows(),
+            Some(0)
+        );
+        assert_eq!(sum_call(None).preceding_rows(), None);
+        assert_eq!(sum_call(frame(None, Some(0))).preceding_rows(), None);
+        assert_eq!(sum_call(frame(Some(-3), Some(0))).preceding_rows(), Some(3));
+        assert_eq!(sum_call(frame(Some(2), None)).preceding_rows(), Some(0));
+    }
+
+    #[test]
+    fn test_merge_partial() {
+        let int64 = |v: i64| Some(ScalarImpl::Int64(v));
+        assert_eq!(
+            merge_partial(AggKind::Sum, int64(1), int64(2)).unwrap(),
+            int64(3)
+        );
+        assert_eq!(
+            merge_partial(AggKind::Sum, None, int64(2)).unwrap(),
+            int64(2)
+        );
+        assert_eq!(
+            merge_partial(AggKind::Max, int64(1), int64(2)).unwrap(),
+            int64(2)
+        );
+        assert_eq!(
+            merge_partial(AggKind::Min, int64(1), None).unwrap(),
+            int64(1)
+        );
+        assert!(matches!(
+            merge_partial(AggKind::Sum, int64(i64::MAX), int64(1)),
+            Err(ExprError::NumericOutOfRange)
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Evaluation of the `SESSION` window table function over a single partition.
+
+use risingwave_common::array::Row;
+use risingwave_common::bail;
+use risingwave_common::types::{
+    Datum, IntervalUnit, NaiveDateTimeWrapper, ScalarImpl, ScalarRefImpl,
+};
+
+use crate::vector_op::arithmetic_op::{timestamp_interval_add, timestampz_interval_add};
+use crate::Result;
+
+fn time_add_gap(time: ScalarRefImpl<'_>, gap: IntervalUnit) -> Result<ScalarImpl> {
+    match time {
+        ScalarRefImpl::NaiveDateTime(time) => Ok(timestamp_interval_add::<
+            NaiveDateTimeWrapper,
+            IntervalUnit,
+            NaiveDateTimeWrapper,
+        >(time, gap)?
+        .into()),
+        ScalarRefImpl::Int64(time) => {
+            Ok(timestampz_interval_add::<i64, IntervalUnit, i64>(time, gap)?.into())
+        }
+        _ => bail!("unsupported time column of session window: {:?}", time),
+    }
+}
+
+/// Assigns the rows of one partition, which must be sorted by `time_col`, to session windows.
+///
+/// Two adjacent rows belong to the same session if the later one is less than `gap` after the
+/// earlier one. A session starts at the time of its first row and ends `gap` after its last row.
+/// Rows with a null time are not in any session.
+///
+/// Returns the `(window_start, window_end)` of each row.
+pub fn evaluate_sessions(
+    rows: &[Row],
+    time_col: usize,
+    gap: IntervalUnit,
+) -> Result<Vec<(Datum, Datum)>> {
+    let mut outputs = vec![(None, None); rows.len()];
+    // The rows of the current session, with its start time and its end time so far.
+    let mut session: Vec<usize> = vec![];
+    let mut bounds: Option<(ScalarImpl, ScalarImpl)> = None;
+
+    for (i, row) in rows.iter().enumerate() {
+        let Some(time) = &row.0[time_col] else {
+            continue;
+        };
+        let end = time_add_gap(time.as_scalar_ref_impl(), gap)?;
+        bounds = match bounds.take() {
+            Some((start, session_end)) if time < &session_end => Some((start, end)),
+            Some((start, session_end)) => {
+                for j in session.drain(..) {
+                    outputs[j] = (Some(start.clone()), Some(session_end.clone()));
+                }
+                Some((time.clone(), end))
+            }
+            None => Some((time.clone(), end)),
+        };
+        session.push(i);
+    }
+    if let Some((start, end)) = bounds {
+        for j in session {
+            outputs[j] = (Some(start.clone()), Some(end.clone()));
+        }
+    }
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn test_evaluate_sessions() {
+        // Timestamps with time zone, in microseconds.
+        let rows = [Some(0), Some(1), Some(4), Some(6), Some(12), None]
+            .into_iter()
+            .map(|v| Row::new(vec![v.map(|v: i64| ScalarImpl::Int64(v * 1_000_000))]))
+            .collect_vec();
+        let gap = IntervalUnit::from_millis(3000);
+        let outputs = evaluate_sessions(&rows, 0, gap).unwrap();
+
+        let session = |start: i64, end: i64| {
+            (
+                Some(ScalarImpl::Int64(start * 1_000_000)),
+                Some(ScalarImpl::Int64(end * 1_000_000)),
+            )
+        };
+        assert_eq!(
+            outputs,
+            vec![
+                session(0, 4),
+                session(0, 4),
+                session(4, 9),
+                session(4, 9),
+                session(12, 15),
+                (None, None),
+            ]
+        );
+    }
+}
@@ -27,7 +27,7 @@ use std::sync::Arc;
 use anyhow::{anyhow, bail, Result};
 pub use resolve_id::*;
 use risingwave_frontend::handler::{
-    create_index, create_mv, create_source, create_table, drop_table, variable,
+    create_function, create_index, create_mv, create_source, create_table, drop_table, variable,
 };
 use risingwave_frontend::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
 use risingwave_frontend::test_utils::{create_proto_file, get_explain_output, LocalFrontend};
@@ -317,9 +317,17 @@ impl TestCase {
                     name,
                     columns,
                     constraints,
+                    source_watermarks,
                     ..
                 } => {
-                    create_table::handle_create_table(context, name, columns, constraints).await?;
+                    create_table::handle_create_table(
+                        context,
+                        name,
+                        columns,
+                        constraints,
+                        source_watermarks,
+                    )
+                    .await?;
                 }
                 Statement::CreateSource {
                     is_materialized,
@@ -353,9 +361,33 @@ impl TestCase {
                     name,
                     query,
                     columns,
+                    emit_mode,
                     ..
                 } => {
-                    create_mv::handle_create_mv(context, name, *query, columns).await?;
+                    create_mv::handle_create_mv(context, name, *query, columns, emit_mode).await?;
+                }
+                Statement::CreateFunction {
+                    or_replace,
+                    temporary,
+                    name,
+                    args,
+                    return_type,
+                    language,
+                    identifier,
+                    using,
+                } => {
+                    create_function::handle_create_function(
+                        context,
+                        or_replace,
+                        temporary,
+                        name,
+                        args,
+                        return_type,
+                        language,
+                        identifier,
+                        using,
+                    )
+                    .await?;
                 }
                 Statement::Drop(drop_statement) => {
                     drop_table::handle_drop_table(
@@ -502,6 +534,7 @@ impl TestCase {
                     q,
                     ObjectName(vec!["test".into()]),
                     vec![],
+                    None,
                 ) {
                     Ok((stream_plan, _)) => stream_plan,
                     Err(err) => {
@@ -0,0 +1,41 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::catalog::Field;
+use risingwave_common::error::Result;
+use risingwave_sqlparser::ast::Expr;
+
+use crate::binder::Binder;
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::expr::ExprImpl;
+
+impl Binder {
+    /// Bind the columns of a table being created into the context, so that the expressions in its
+    /// definition, e.g. generated columns, can refer to them.
+    pub fn bind_columns_to_context(
+        &mut self,
+        name: String,
+        columns: &[ColumnCatalog],
+    ) -> Result<()> {
+        let columns = columns
+            .iter()
+            .map(|c| (c.is_hidden, Field::from(&c.column_desc)));
+        self.bind_table_to_context(columns, name, None)
+    }
+
+    /// Bind the generation expression or the default value of a column in `CREATE TABLE`.
+    pub fn bind_column_expr(&mut self, expr: Expr) -> Result<ExprImpl> {
+        self.bind_expr(expr)
+    }
+}
@@ -12,13 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::{Expr, ObjectName};
+use risingwave_pb::user::grant_privilege::Action;
+use risingwave_sqlparser::ast::{Expr, ObjectName, SelectItem};
 
 use super::{Binder, BoundBaseTable, BoundTableSource};
 use crate::expr::ExprImpl;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BoundDelete {
     /// Used for injecting deletion chunks to the source.
     pub table_source: BoundTableSource,
@@ -27,6 +29,12 @@ pub struct BoundDelete {
     pub table: BoundBaseTable,
 
     pub selection: Option<ExprImpl>,
+
+    /// Expressions of `RETURNING` over the deleted rows, which have the columns of `table`.
+    pub returning_list: Vec<ExprImpl>,
+
+    /// The output schema of `RETURNING`, or `None` if there is no `RETURNING`.
+    pub returning_schema: Option<Schema>,
 }
 
 impl Binder {
@@ -34,11 +42,12 @@ impl Binder {
         &mut self,
         source_name: ObjectName,
         selection: Option<Expr>,
+        returning_items: Vec<SelectItem>,
     ) -> Result<BoundDelete> {
         let (schema_name, table_name) =
             Self::resolve_schema_qualified_name(&self.db_name, source_name)?;
         let schema_name = schema_name.as_deref();
-        let table_source = self.bind_table_source(schema_name, &table_name)?;
+        let table_source = self.bind_table_source(schema_name, &table_name, Action::Delete)?;
         if table_source.append_only {
             return Err(ErrorCode::BindError(
                 "Append-only table source doesn't support delete".to_string(),
@@ -46,10 +55,19 @@ impl Binder {
             .into());
         }
         let table = self.bind_table(schema_name, &table_name, None)?;
+        let selection = selection.map(|expr| self.bind_expr(expr)).transpose()?;
+        let (returning_list, returning_schema) = if returning_items.is_empty() {
+            (vec![], None)
+        } else {
+            let (returning_list, fields) = self.bind_returning_list(returning_items)?;
+            (returning_list, Some(Schema { fields }))
+        };
         let delete = BoundDelete {
             table_source,
             table,
-            selection: selection.map(|expr| self.bind_expr(expr)).transpose()?,
+            selection,
+            returning_list,
+            returning_schema,
         };
         Ok(delete)
     }
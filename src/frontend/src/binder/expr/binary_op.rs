@@ -50,6 +50,9 @@ impl Binder {
             BinaryOperator::PGBitwiseShiftLeft => ExprType::BitwiseShiftLeft,
             BinaryOperator::PGBitwiseShiftRight => ExprType::BitwiseShiftRight,
             BinaryOperator::Concat => return self.bind_concat_op(bound_left, bound_right),
+            BinaryOperator::Arrow => ExprType::JsonbAccessInner,
+            BinaryOperator::LongArrow => ExprType::JsonbAccessStr,
+            BinaryOperator::HashArrow => ExprType::JsonbAccessPath,
             BinaryOperator::PGRegexMatch => return self.bind_regex_match(bound_left, bound_right),
             BinaryOperator::PGRegexNotMatch => {
                 return self.bind_regex_not_match(bound_left, bound_right)
@@ -73,22 +73,35 @@ impl Binder {
         }
 
         // Try to find a correlated column in `upper_contexts`, starting from the innermost context.
+        // The visible lateral contexts of each upper context, i.e., the preceding items in `FROM`
+        // of a `LATERAL` subquery, are searched at the same depth.
         let mut err = ErrorCode::ItemNotFound(format!("Invalid column: {}", column_name));
-        for (i, (context, _)) in self.upper_subquery_contexts.iter().rev().enumerate() {
+        for (i, (context, lateral_contexts)) in
+            self.upper_subquery_contexts.iter().rev().enumerate()
+        {
             // `depth` starts from 1.
             let depth = i + 1;
-            match context.get_column_binding_index(&table_name, &column_name) {
-                Ok(index) => {
-                    let column = &context.columns[index];
-                    return Ok(CorrelatedInputRef::new(
-                        column.index,
-                        column.field.data_type.clone(),
-                        depth,
-                    )
-                    .into());
-                }
-                Err(e) => {
-                    err = e;
+            let visible_contexts = std::iter::once(context).chain(
+                lateral_contexts
+                    .iter()
+                    .rev()
+                    .filter(|lateral| lateral.is_visible)
+                    .map(|lateral| &lateral.context),
+            );
+            for context in visible_contexts {
+                match context.get_column_binding_index(&table_name, &column_name) {
+                    Ok(index) => {
+                        let column = &context.columns[index];
+                        return Ok(CorrelatedInputRef::new(
+                            column.index,
+                            column.field.data_type.clone(),
+                            depth,
+                        )
+                        .into());
+                    }
+                    Err(e) => {
+                        err = e;
+                    }
                 }
             }
         }
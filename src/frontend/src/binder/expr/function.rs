@@ -16,36 +16,41 @@ use std::iter::once;
 use std::str::FromStr;
 
 use itertools::Itertools;
+use pgwire::pg_field_descriptor::TypeOid;
 use risingwave_common::array::ListValue;
 use risingwave_common::catalog::PG_CATALOG_SCHEMA_NAME;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::session_config::USER_NAME_WILD_CARD;
 use risingwave_common::types::{DataType, Scalar};
 use risingwave_expr::expr::AggKind;
-use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr, WindowSpec};
+use risingwave_expr::window_function::RowsFrame;
+use risingwave_sqlparser::ast::{
+    Function, FunctionArg, FunctionArgExpr, OrderByExpr, WindowFrame, WindowFrameBound,
+    WindowFrameUnits, WindowSpec,
+};
 
 use crate::binder::bind_context::Clause;
 use crate::binder::{Binder, BoundQuery, BoundSetExpr};
+use crate::catalog::pg_catalog::pg_type::PG_TYPE_DATA;
+use crate::catalog::root_catalog::SchemaPath;
 use crate::expr::{
     AggCall, Expr, ExprImpl, ExprType, FunctionCall, Literal, OrderBy, Subquery, SubqueryKind,
-    TableFunction, TableFunctionType, WindowFunction, WindowFunctionType,
+    TableFunction, TableFunctionType, UserDefinedFunction, WindowFunction, WindowFunctionType,
 };
+use crate::handler::util::type_oid_to_data_type;
 use crate::utils::Condition;
 
 impl Binder {
     pub(super) fn bind_function(&mut self, f: Function) -> Result<ExprImpl> {
-        let function_name = match f.name.0.as_slice() {
-            [name] => name.real_value(),
+        let (schema_name, function_name) = match f.name.0.as_slice() {
+            [name] => (None, name.real_value()),
             [schema, name] => {
                 let schema_name = schema.real_value();
                 if schema_name == PG_CATALOG_SCHEMA_NAME {
-                    name.real_value()
+                    (None, name.real_value())
                 } else {
-                    return Err(ErrorCode::BindError(format!(
-                        "Unsupported function name under schema: {}",
-                        schema_name
-                    ))
-                    .into());
+                    // Only user-defined functions live in other schemas.
+                    (Some(schema_name), name.real_value())
                 }
             }
             _ => {
@@ -58,35 +63,81 @@ impl Binder {
         };
 
         // agg calls
-        if let Ok(kind) = function_name.parse() {
-            if f.over.is_some() {
-                return Err(ErrorCode::NotImplemented(
-                    format!("aggregate function as over window function: {}", kind),
-                    4978.into(),
-                )
-                .into());
+        if schema_name.is_none() && let Ok(kind) = function_name.parse() {
+            if let Some(window_spec) = f.over {
+                if matches!(kind, AggKind::PercentileCont | AggKind::PercentileDisc) {
+                    return Err(ErrorCode::InvalidInputSyntax(format!(
+                        "OVER is not supported for ordered-set aggregate {}",
+                        kind
+                    ))
+                    .into());
+                }
+                if f.distinct || !f.order_by.is_empty() || f.filter.is_some() {
+                    return Err(ErrorCode::NotImplemented(
+                        format!("DISTINCT, ORDER BY or FILTER in window function: {}", kind),
+                        4978.into(),
+                    )
+                    .into());
+                }
+                let inputs = f
+                    .args
+                    .into_iter()
+                    .map(|arg| self.bind_function_arg(arg))
+                    .flatten_ok()
+                    .try_collect()?;
+                return self.bind_window_function(
+                    window_spec,
+                    WindowFunctionType::Aggregate(kind),
+                    inputs,
+                );
             }
             return self.bind_agg(f, kind);
         }
 
-        if f.distinct || !f.order_by.is_empty() || f.filter.is_some() {
+        if f.distinct || !f.order_by.is_empty() || f.filter.is_some() || f.within_group.is_some() {
             return Err(ErrorCode::InvalidInputSyntax(format!(
-                    "DISTINCT, ORDER BY or FILTER is only allowed in aggregation functions, but `{}` is not an aggregation function", function_name
+                    "DISTINCT, ORDER BY, FILTER or WITHIN GROUP is only allowed in aggregation functions, but `{}` is not an aggregation function", function_name
                 )
                 )
                 .into());
         }
 
-        let inputs = f
+        let inputs: Vec<ExprImpl> = f
             .args
             .into_iter()
             .map(|arg| self.bind_function_arg(arg))
             .flatten_ok()
             .try_collect()?;
 
+        // user-defined function
+        if f.over.is_none() {
+            let arg_types = inputs.iter().map(|input| input.return_type()).collect_vec();
+            let schema_path = SchemaPath::new(
+                schema_name.as_deref(),
+                &self.search_path,
+                &self.auth_context.user_name,
+            );
+            if let Ok((function, _)) = self.catalog.get_function_by_name_args(
+                &self.db_name,
+                schema_path,
+                &function_name,
+                &arg_types,
+            ) {
+                return Ok(UserDefinedFunction::new(function.clone(), inputs).into());
+            }
+        }
+        if let Some(schema_name) = schema_name {
+            return Err(ErrorCode::BindError(format!(
+                "Unsupported function name under schema: {}",
+                schema_name
+            ))
+            .into());
+        }
+
         // window function
         if let Some(window_spec) = f.over {
-            return self.bind_window_function(window_spec, function_name, inputs);
+            let window_function_type = WindowFunctionType::from_str(&function_name)?;
+            return self.bind_window_function(window_spec, window_function_type, inputs);
         }
 
         // table function
@@ -127,6 +178,16 @@ impl Binder {
             "abs" => ExprType::Abs,
             // temporal/chrono
             "to_timestamp" => ExprType::ToTimestamp,
+            "date_trunc" => {
+                // `timestamp with time zone` is truncated in the session TimeZone if the time
+                // zone is not specified.
+                if inputs.len() == 2 && inputs[1].return_type() == DataType::Timestampz {
+                    inputs.push(ExprImpl::literal_varchar(
+                        self.session_timezone.timezone().to_string(),
+                    ));
+                }
+                ExprType::DateTrunc
+            }
             // string
             "substr" => ExprType::Substr,
             "length" => ExprType::Length,
@@ -157,6 +218,11 @@ impl Binder {
             "array_cat" => ExprType::ArrayCat,
             "array_append" => ExprType::ArrayAppend,
             "array_prepend" => ExprType::ArrayPrepend,
+            // jsonb
+            "jsonb_extract_path" => {
+                inputs = Self::rewrite_jsonb_extract_path(inputs)?;
+                ExprType::JsonbAccessPath
+            }
             // System information operations.
             "pg_typeof" if inputs.len() == 1 => {
                 let input = &inputs[0];
@@ -258,6 +324,17 @@ impl Binder {
                 };
             }
             "pg_table_is_visible" => return Ok(ExprImpl::literal_bool(true)),
+            "format_type" => {
+                inputs = Self::rewrite_format_type_to_case_when(inputs)?;
+                ExprType::Case
+            }
+            // date/time
+            "now" if inputs.is_empty() => ExprType::Now,
+            // grouping sets
+            "grouping" => {
+                self.ensure_grouping_allowed()?;
+                ExprType::Grouping
+            }
             // internal
             "rw_vnode" => ExprType::Vnode,
             _ => {
@@ -347,25 +424,97 @@ impl Binder {
             None => Condition::true_cond(),
         };
 
-        if f.distinct && !f.order_by.is_empty() {
-            // <https://www.postgresql.org/docs/current/sql-expressions.html#SYNTAX-AGGREGATES:~:text=the%20DISTINCT%20list.-,Note,-The%20ability%20to>
-            return Err(ErrorCode::InvalidInputSyntax(
-                "DISTINCT and ORDER BY are not supported to appear at the same time now"
-                    .to_string(),
-            )
+        if matches!(kind, AggKind::PercentileCont | AggKind::PercentileDisc) {
+            let Some(within_group) = f.within_group else {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "WITHIN GROUP is required for ordered-set aggregate {}",
+                    kind
+                ))
+                .into());
+            };
+            if f.distinct || !f.order_by.is_empty() {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "DISTINCT or ORDER BY is not allowed in ordered-set aggregate {}",
+                    kind
+                ))
+                .into());
+            }
+            let (inputs, order_by) = self.bind_percentile_args(kind, inputs, *within_group)?;
+            return Ok(ExprImpl::AggCall(Box::new(AggCall::new(
+                kind, inputs, false, order_by, filter,
+            )?)));
+        }
+        if f.within_group.is_some() {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "WITHIN GROUP is only allowed in ordered-set aggregate functions, but `{}` is not",
+                kind
+            ))
             .into());
         }
+
         let order_by = OrderBy::new(
             f.order_by
                 .into_iter()
                 .map(|e| self.bind_order_by_expr(e))
                 .try_collect()?,
         );
+        if f.distinct && !order_by.sort_exprs.iter().all(|e| inputs.contains(&e.expr)) {
+            // <https://www.postgresql.org/docs/current/sql-expressions.html#SYNTAX-AGGREGATES:~:text=the%20DISTINCT%20list.-,Note,-The%20ability%20to>
+            return Err(ErrorCode::InvalidInputSyntax(
+                "in an aggregate with DISTINCT, ORDER BY expressions must appear in argument list"
+                    .to_string(),
+            )
+            .into());
+        }
         Ok(ExprImpl::AggCall(Box::new(AggCall::new(
             kind, inputs, f.distinct, order_by, filter,
         )?)))
     }
 
+    /// Binds the fraction and the `WITHIN GROUP` ordering of `percentile_cont` or
+    /// `percentile_disc` as the inputs `(sort_expr, fraction)` and the order by clause of the agg
+    /// call.
+    fn bind_percentile_args(
+        &mut self,
+        kind: AggKind,
+        direct_args: Vec<ExprImpl>,
+        within_group: OrderByExpr,
+    ) -> Result<(Vec<ExprImpl>, OrderBy)> {
+        let Ok([fraction]) = <[ExprImpl; 1]>::try_from(direct_args) else {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "{} requires exactly one fraction argument",
+                kind
+            ))
+            .into());
+        };
+        if !fraction.is_const() {
+            return Err(ErrorCode::NotImplemented(
+                format!("non-constant fraction of {}", kind),
+                None.into(),
+            )
+            .into());
+        }
+        let fraction = fraction.cast_implicit(DataType::Float64)?;
+        let fraction = fraction.eval_row_const()?;
+        if let Some(value) = &fraction && !(0.0..=1.0).contains(&value.as_float64().0) {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "percentile value {} is not between 0 and 1",
+                value.as_float64()
+            ))
+            .into());
+        }
+
+        let mut within_group = self.bind_order_by_expr(within_group)?;
+        if kind == AggKind::PercentileCont {
+            within_group.expr = within_group.expr.cast_implicit(DataType::Float64)?;
+        }
+        let inputs = vec![
+            within_group.expr.clone(),
+            Literal::new(fraction, DataType::Float64).into(),
+        ];
+        Ok((inputs, OrderBy::new(vec![within_group])))
+    }
+
     pub(super) fn bind_window_function(
         &mut self,
         WindowSpec {
@@ -373,18 +522,23 @@ impl Binder {
             order_by,
             window_frame,
         }: WindowSpec,
-        function_name: String,
+        window_function_type: WindowFunctionType,
         inputs: Vec<ExprImpl>,
     ) -> Result<ExprImpl> {
         self.ensure_window_function_allowed()?;
-        if let Some(window_frame) = window_frame {
-            return Err(ErrorCode::NotImplemented(
-                format!("window frame: {}", window_frame),
-                None.into(),
-            )
-            .into());
-        }
-        let window_function_type = WindowFunctionType::from_str(&function_name)?;
+        let rows_frame = match window_frame {
+            Some(window_frame) => match window_function_type {
+                WindowFunctionType::Aggregate(_) => Self::bind_window_frame(window_frame)?,
+                _ => {
+                    return Err(ErrorCode::NotImplemented(
+                        format!("window frame: {}", window_frame),
+                        None.into(),
+                    )
+                    .into())
+                }
+            },
+            None => None,
+        };
         let partition_by = partition_by
             .into_iter()
             .map(|arg| self.bind_expr(arg))
@@ -396,7 +550,73 @@ impl Binder {
                 .map(|order_by_expr| self.bind_order_by_expr(order_by_expr))
                 .collect::<Result<_>>()?,
         );
-        Ok(WindowFunction::new(window_function_type, partition_by, order_by, inputs)?.into())
+        Ok(WindowFunction::new(
+            window_function_type,
+            partition_by,
+            order_by,
+            inputs,
+            rows_frame,
+        )?
+        .into())
+    }
+
+    /// Binds the frame of an aggregate window function call. Returns `None` for the default frame
+    /// `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`, which is the only supported `RANGE`
+    /// frame.
+    fn bind_window_frame(window_frame: WindowFrame) -> Result<Option<RowsFrame>> {
+        let end_bound = window_frame
+            .end_bound
+            .clone()
+            .unwrap_or(WindowFrameBound::CurrentRow);
+        match window_frame.units {
+            WindowFrameUnits::Rows => {}
+            WindowFrameUnits::Range
+                if window_frame.start_bound == WindowFrameBound::Preceding(None)
+                    && end_bound == WindowFrameBound::CurrentRow =>
+            {
+                return Ok(None);
+            }
+            _ => {
+                return Err(ErrorCode::NotImplemented(
+                    format!("window frame: {}", window_frame),
+                    None.into(),
+                )
+                .into())
+            }
+        }
+
+        let invalid = match (&window_frame.start_bound, &end_bound) {
+            (WindowFrameBound::Following(None), _) => {
+                Some("frame start cannot be UNBOUNDED FOLLOWING")
+            }
+            (_, WindowFrameBound::Preceding(None)) => {
+                Some("frame end cannot be UNBOUNDED PRECEDING")
+            }
+            (WindowFrameBound::CurrentRow, WindowFrameBound::Preceding(_)) => {
+                Some("frame starting from current row cannot have preceding rows")
+            }
+            (WindowFrameBound::Following(_), WindowFrameBound::Preceding(_)) => {
+                Some("frame starting from following row cannot have preceding rows")
+            }
+            (WindowFrameBound::Following(_), WindowFrameBound::CurrentRow) => {
+                Some("frame starting from following row cannot end with current row")
+            }
+            _ => None,
+        };
+        if let Some(msg) = invalid {
+            return Err(ErrorCode::InvalidInputSyntax(msg.to_string()).into());
+        }
+
+        // The offsets are relative to the current row, and unbounded ones are `None`.
+        let offset = |bound: &WindowFrameBound| match bound {
+            WindowFrameBound::CurrentRow => Some(0),
+            WindowFrameBound::Preceding(n) => n.map(|n| -(n.min(i64::MAX as u64) as i64)),
+            WindowFrameBound::Following(n) => n.map(|n| n.min(i64::MAX as u64) as i64),
+        };
+        Ok(Some(RowsFrame {
+            start: offset(&window_frame.start_bound),
+            end: offset(&end_bound),
+        }))
     }
 
     fn rewrite_concat_to_concat_ws(inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
@@ -413,6 +633,29 @@ impl Binder {
         }
     }
 
+    /// Collect the variadic path arguments into a `text[]`.
+    /// `jsonb_extract_path(j, a, b)` -> `j #> ARRAY[a, b]`.
+    fn rewrite_jsonb_extract_path(inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
+        let mut inputs = inputs.into_iter();
+        let Some(jsonb) = inputs.next() else {
+            return Err(ErrorCode::BindError(
+                "Function `jsonb_extract_path` takes at least 1 arguments (0 given)".to_string(),
+            )
+            .into());
+        };
+        let path = inputs
+            .map(|input| input.cast_implicit(DataType::Varchar))
+            .try_collect()?;
+        let path = FunctionCall::new_unchecked(
+            ExprType::Array,
+            path,
+            DataType::List {
+                datatype: Box::new(DataType::Varchar),
+            },
+        );
+        Ok(vec![jsonb, path.into()])
+    }
+
     /// Make sure inputs only have 2 value and rewrite the arguments.
     /// Nullif(expr1,expr2) -> Case(Equal(expr1 = expr2),null,expr1).
     fn rewrite_nullif_to_case_when(inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
@@ -428,6 +671,45 @@ impl Binder {
         }
     }
 
+    /// Make sure inputs only have 2 value and rewrite the arguments.
+    /// `format_type(type_oid, typemod)` -> Case(IsNull(type_oid), null, Equal(type_oid, oid_1),
+    /// name_1, ..., '???'), where the `typemod` is ignored.
+    fn rewrite_format_type_to_case_when(inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
+        if inputs.len() != 2 {
+            return Err(ErrorCode::BindError(
+                "Too many/few arguments for pg_catalog.format_type()".to_string(),
+            )
+            .into());
+        }
+        let type_oid = inputs
+            .into_iter()
+            .next()
+            .unwrap()
+            .cast_implicit(DataType::Int32)?;
+        let mut case_inputs = vec![
+            FunctionCall::new(ExprType::IsNull, vec![type_oid.clone()])?.into(),
+            ExprImpl::literal_null(DataType::Varchar),
+        ];
+        for (oid, _, array_oid, _) in PG_TYPE_DATA {
+            let data_type = type_oid_to_data_type(TypeOid::as_type(*oid).unwrap());
+            let array_type = DataType::List {
+                datatype: Box::new(data_type.clone()),
+            };
+            for (oid, data_type) in [(*oid, data_type), (*array_oid, array_type)] {
+                case_inputs.push(
+                    FunctionCall::new(
+                        ExprType::Equal,
+                        vec![type_oid.clone(), ExprImpl::literal_int(oid)],
+                    )?
+                    .into(),
+                );
+                case_inputs.push(ExprImpl::literal_varchar(data_type.to_string()));
+            }
+        }
+        case_inputs.push(ExprImpl::literal_varchar("???".to_string()));
+        Ok(case_inputs)
+    }
+
     fn rewrite_two_bool_inputs(mut inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
         if inputs.len() != 2 {
             return Err(
@@ -477,6 +759,22 @@ impl Binder {
         Ok(())
     }
 
+    fn ensure_grouping_allowed(&self) -> Result<()> {
+        if let Some(clause) = self.context.clause {
+            match clause {
+                Clause::Where | Clause::Values | Clause::GroupBy | Clause::Filter => {
+                    return Err(ErrorCode::InvalidInputSyntax(format!(
+                        "grouping operations are not allowed in {}",
+                        clause
+                    ))
+                    .into());
+                }
+                Clause::Having => {}
+            }
+        }
+        Ok(())
+    }
+
     fn ensure_table_function_allowed(&self) -> Result<()> {
         if let Some(clause) = self.context.clause {
             match clause {
@@ -22,7 +22,7 @@ use risingwave_sqlparser::ast::{
 };
 
 use crate::binder::Binder;
-use crate::expr::{Expr as _, ExprImpl, ExprType, FunctionCall, SubqueryKind};
+use crate::expr::{Expr as _, ExprImpl, ExprRewriter, ExprType, FunctionCall, SubqueryKind};
 
 mod binary_op;
 mod column;
@@ -33,6 +33,13 @@ mod value;
 
 impl Binder {
     pub(super) fn bind_expr(&mut self, expr: Expr) -> Result<ExprImpl> {
+        let expr = self.bind_expr_inner(expr)?;
+        // The casts depending on the session TimeZone can be introduced implicitly during type
+        // inference, so the whole bound expression is rewritten.
+        Ok(self.session_timezone.rewrite_expr(expr))
+    }
+
+    fn bind_expr_inner(&mut self, expr: Expr) -> Result<ExprImpl> {
         match expr {
             // literal
             Expr::Value(v) => Ok(ExprImpl::Literal(Box::new(self.bind_value(v)?))),
@@ -41,6 +48,7 @@ impl Binder {
                 s.cast_explicit(bind_data_type(&data_type)?)
             }
             Expr::Row(exprs) => self.bind_row(exprs),
+            Expr::Parameter { index } => self.bind_parameter(index),
             // input ref
             Expr::Identifier(ident) => {
                 if ["session_user", "current_schema"]
@@ -130,8 +138,13 @@ impl Binder {
     }
 
     pub(super) fn bind_extract(&mut self, field: String, expr: Expr) -> Result<ExprImpl> {
-        let arg = self.bind_expr(expr)?;
+        let mut arg = self.bind_expr(expr)?;
         let arg_type = arg.return_type();
+        // Except `EPOCH`, the fields of a `timestamp with time zone` are extracted from the local
+        // time in the session TimeZone.
+        if arg_type == DataType::Timestampz && !field.eq_ignore_ascii_case("EPOCH") {
+            arg = self.session_timezone.at_time_zone(arg);
+        }
         Ok(FunctionCall::new(
             ExprType::Extract,
             vec![self.bind_string(field.clone())?.into(), arg],
@@ -454,6 +467,7 @@ pub fn bind_data_type(data_type: &AstDataType) -> Result<DataType> {
         AstDataType::Timestamp(false) => DataType::Timestamp,
         AstDataType::Timestamp(true) => DataType::Timestampz,
         AstDataType::Interval => DataType::Interval,
+        AstDataType::Jsonb => DataType::Jsonb,
         AstDataType::Array(datatype) => DataType::List {
             datatype: Box::new(bind_data_type(datatype)?),
         },
@@ -19,7 +19,7 @@ use risingwave_expr::vector_op::cast::str_parse;
 use risingwave_sqlparser::ast::{DateTimeField as AstDateTimeField, Expr, Value};
 
 use crate::binder::Binder;
-use crate::expr::{align_types, Expr as _, ExprImpl, ExprType, FunctionCall, Literal};
+use crate::expr::{align_types, Expr as _, ExprImpl, ExprType, FunctionCall, Literal, Parameter};
 
 impl Binder {
     pub fn bind_value(&mut self, value: Value) -> Result<Literal> {
@@ -89,6 +89,16 @@ impl Binder {
         }
     }
 
+    /// Binds a parameter of a prepared statement. When executing the statement, the parameter is
+    /// replaced by a literal of its value, so that the plan is the same as the one of a plain
+    /// query.
+    pub(super) fn bind_parameter(&mut self, index: u64) -> Result<ExprImpl> {
+        if index == 0 || !self.preparing {
+            return Err(ErrorCode::BindError(format!("there is no parameter ${}", index)).into());
+        }
+        Ok(Parameter::new(index, self.param_types.clone()).into())
+    }
+
     /// `ARRAY[...]` is represented as an function call at the binder stage.
     pub(super) fn bind_array(&mut self, exprs: Vec<Expr>) -> Result<ExprImpl> {
         if exprs.is_empty() {
@@ -194,6 +204,71 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_bind_parameter() {
+        use risingwave_common::types::ScalarImpl;
+        use risingwave_sqlparser::parser::Parser;
+
+        use crate::binder::{Binder, BoundSetExpr, BoundStatement};
+        use crate::expr::Literal;
+        use crate::session::SessionImpl;
+
+        let session = SessionImpl::mock();
+
+        // The types of the parameters are inferred from their casts when preparing a statement.
+        let mut binder = Binder::new_with_param_types(&session, vec![None, None]);
+        let param = binder.bind_parameter(1).unwrap();
+        assert!(param.is_unknown());
+        let cast = param.clone().cast_implicit(DataType::Int64).unwrap();
+        assert_eq!(cast, param);
+        assert_eq!(param.return_type(), DataType::Int64);
+        assert_eq!(
+            binder.export_param_types(),
+            vec![DataType::Int64, DataType::Varchar]
+        );
+
+        // The parameters are replaced by literals of their values when executing a statement,
+        // which is bound only once.
+        let stmt = Parser::parse_sql("SELECT $1, (SELECT $2)")
+            .unwrap()
+            .remove(0);
+        let mut binder = Binder::new_with_param_types(&session, vec![Some(DataType::Int64), None]);
+        let bound = binder.bind(stmt).unwrap();
+        let param_types = binder.export_param_types();
+        assert_eq!(param_types, vec![DataType::Int64, DataType::Varchar]);
+        let mut executed = bound.clone();
+        executed
+            .bind_parameter_values(&param_types, &[Some("42".to_string()), None])
+            .unwrap();
+        let BoundStatement::Query(query) = executed else {
+            unreachable!()
+        };
+        let BoundSetExpr::Select(select) = query.body else {
+            unreachable!()
+        };
+        assert_eq!(
+            select.select_items[0],
+            Literal::new(Some(ScalarImpl::Int64(42)), DataType::Int64).into()
+        );
+        let ExprImpl::Subquery(subquery) = &select.select_items[1] else {
+            unreachable!()
+        };
+        let BoundSetExpr::Select(subquery_select) = &subquery.query.body else {
+            unreachable!()
+        };
+        assert_eq!(
+            subquery_select.select_items[0],
+            ExprImpl::literal_null(DataType::Varchar)
+        );
+        assert!(bound
+            .clone()
+            .bind_parameter_values(&param_types, &[None])
+            .is_err());
+
+        // A plain statement has no parameters.
+        assert!(mock_binder().bind_parameter(1).is_err());
+    }
+
     #[test]
     fn test_array_expr() {
         let expr: ExprImpl = FunctionCall::new_unchecked(
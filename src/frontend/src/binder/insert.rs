@@ -12,16 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
-use risingwave_sqlparser::ast::{Ident, ObjectName, Query, SetExpr};
+use risingwave_pb::user::grant_privilege::Action;
+use risingwave_sqlparser::ast::{Ident, ObjectName, Query, SelectItem, SetExpr};
 
+use super::bind_context::BindContext;
 use super::{BoundQuery, BoundSetExpr};
 use crate::binder::{Binder, BoundTableSource};
-use crate::expr::{ExprImpl, InputRef};
+use crate::expr::{ExprImpl, ExprRewriter, InputRef, Literal};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BoundInsert {
     /// Used for injecting deletion chunks to the source.
     pub table_source: BoundTableSource,
@@ -29,26 +34,50 @@ pub struct BoundInsert {
     pub source: BoundQuery,
 
     /// Used as part of an extra `Project` when the column types of `source` query does not match
-    /// `table_source`. This does not include a simple `VALUE`. See comments in code for details.
+    /// `table_source`, or when some columns of `table_source` are not given by `source`. This does
+    /// not include casts in a simple `VALUE`. See comments in code for details.
     pub cast_exprs: Vec<ExprImpl>,
+
+    /// Expressions of `RETURNING` over the inserted rows, which have the columns of
+    /// `table_source`.
+    pub returning_list: Vec<ExprImpl>,
+
+    /// The output schema of `RETURNING`, or `None` if there is no `RETURNING`.
+    pub returning_schema: Option<Schema>,
 }
 
 impl Binder {
     pub(super) fn bind_insert(
         &mut self,
         source_name: ObjectName,
-        _columns: Vec<Ident>,
+        columns: Vec<Ident>,
         source: Query,
+        returning_items: Vec<SelectItem>,
     ) -> Result<BoundInsert> {
         let (schema_name, source_name) =
             Self::resolve_schema_qualified_name(&self.db_name, source_name)?;
-        let table_source = self.bind_table_source(schema_name.as_deref(), &source_name)?;
+        let table_source =
+            self.bind_table_source(schema_name.as_deref(), &source_name, Action::Insert)?;
 
-        let expected_types = table_source
-            .columns
+        // Without a column list, the source fills all the columns except the generated ones, which
+        // are computed when the rows are ingested into the table, and the dropped ones.
+        let target_columns = if columns.is_empty() {
+            table_source
+                .columns
+                .iter()
+                .positions(|c| {
+                    !table_source.generated_column_ids.contains(&c.column_id)
+                        && !table_source.dropped_column_ids.contains(&c.column_id)
+                })
+                .collect_vec()
+        } else {
+            Self::bind_insert_columns(&table_source, columns)?
+        };
+
+        let expected_types = target_columns
             .iter()
-            .map(|c| c.data_type.clone())
-            .collect();
+            .map(|&index| table_source.columns[index].data_type.clone())
+            .collect_vec();
 
         // When the column types of `source` query does not match `expected_types`, casting is
         // needed.
@@ -100,7 +129,7 @@ impl Binder {
                 let actual_types = bound.data_types();
                 let cast_exprs = match expected_types == actual_types {
                     true => vec![],
-                    false => Self::cast_on_insert(
+                    false => self.cast_on_insert(
                         expected_types,
                         actual_types
                             .into_iter()
@@ -113,18 +142,122 @@ impl Binder {
             }
         };
 
+        // Fill the columns not given by `source` with default values, or nulls for generated
+        // columns, which are computed when the rows are ingested into the table, and for dropped
+        // columns.
+        let cast_exprs = if target_columns.len() == table_source.columns.len() {
+            cast_exprs
+        } else {
+            let mut given_exprs = match cast_exprs.is_empty() {
+                true => target_columns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &index)| {
+                        let data_type = table_source.columns[index].data_type.clone();
+                        Some(InputRef::new(i, data_type).into())
+                    })
+                    .collect_vec(),
+                false => cast_exprs.into_iter().map(Some).collect_vec(),
+            };
+            let position_of: HashMap<usize, usize> = target_columns
+                .iter()
+                .enumerate()
+                .map(|(position, &index)| (index, position))
+                .collect();
+            table_source
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(index, column)| match position_of.get(&index) {
+                    Some(&position) => given_exprs[position].take().unwrap(),
+                    None => table_source
+                        .default_values
+                        .get(&column.column_id)
+                        .filter(|_| !table_source.dropped_column_ids.contains(&column.column_id))
+                        .cloned()
+                        .unwrap_or_else(|| Literal::new(None, column.data_type.clone()))
+                        .into(),
+                })
+                .collect()
+        };
+
+        // `RETURNING` refers to the inserted rows rather than the `source` query, so it is bound
+        // in a new context with the columns of the table.
+        let (returning_list, returning_schema) = if returning_items.is_empty() {
+            (vec![], None)
+        } else {
+            self.context = BindContext::new();
+            self.bind_table_to_context(
+                table_source.columns.iter().map(|c| {
+                    (
+                        table_source.dropped_column_ids.contains(&c.column_id),
+                        Field::from(c),
+                    )
+                }),
+                table_source.name.clone(),
+                None,
+            )?;
+            let (returning_list, fields) = self.bind_returning_list(returning_items)?;
+            (returning_list, Some(Schema { fields }))
+        };
+
         let insert = BoundInsert {
             table_source,
             source,
             cast_exprs,
+            returning_list,
+            returning_schema,
         };
 
         Ok(insert)
     }
 
+    /// Resolve the column list of `INSERT` into indices of `table_source.columns`.
+    fn bind_insert_columns(
+        table_source: &BoundTableSource,
+        columns: Vec<Ident>,
+    ) -> Result<Vec<usize>> {
+        let mut indices = Vec::with_capacity(columns.len());
+        for column in columns {
+            let name = column.real_value();
+            let index = table_source
+                .columns
+                .iter()
+                .position(|c| {
+                    c.name == name && !table_source.dropped_column_ids.contains(&c.column_id)
+                })
+                .ok_or_else(|| {
+                    ErrorCode::BindError(format!(
+                        "column \"{}\" of relation \"{}\" does not exist",
+                        name, table_source.name
+                    ))
+                })?;
+            if table_source
+                .generated_column_ids
+                .contains(&table_source.columns[index].column_id)
+            {
+                return Err(ErrorCode::BindError(format!(
+                    "cannot insert a non-DEFAULT value into generated column \"{}\"",
+                    name
+                ))
+                .into());
+            }
+            if indices.contains(&index) {
+                return Err(ErrorCode::BindError(format!(
+                    "column \"{}\" specified more than once",
+                    name
+                ))
+                .into());
+            }
+            indices.push(index);
+        }
+        Ok(indices)
+    }
+
     /// Cast a list of `exprs` to corresponding `expected_types` IN ASSIGNMENT CONTEXT. Make sure
     /// you understand the difference of implicit, assignment and explicit cast before reusing it.
     pub(super) fn cast_on_insert(
+        &mut self,
         expected_types: Vec<DataType>,
         exprs: Vec<ExprImpl>,
     ) -> Result<Vec<ExprImpl>> {
@@ -133,7 +266,10 @@ impl Binder {
                 return exprs
                     .into_iter()
                     .zip_eq(expected_types)
-                    .map(|(e, t)| e.cast_assign(t))
+                    .map(|(e, t)| {
+                        e.cast_assign(t)
+                            .map(|e| self.session_timezone.rewrite_expr(e))
+                    })
                     .try_collect();
             }
             std::cmp::Ordering::Less => "INSERT has more expressions than target columns",
@@ -12,14 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::ErrorCode::PermissionDenied;
 use risingwave_common::error::Result;
 use risingwave_common::session_config::SearchPath;
+use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::{Statement, TableAlias};
 
 mod bind_context;
+mod create;
 mod delete;
 mod expr;
 mod insert;
@@ -38,18 +42,22 @@ pub use expr::{bind_data_type, bind_struct_field};
 pub use insert::BoundInsert;
 pub use query::BoundQuery;
 pub use relation::{
-    BoundBaseTable, BoundJoin, BoundSource, BoundSystemTable, BoundTableSource,
-    BoundWindowTableFunction, Relation, WindowTableFunctionKind,
+    BoundBaseTable, BoundJoin, BoundRecursiveUnion, BoundSource, BoundSystemTable,
+    BoundTableSource, BoundWindowTableFunction, BoundWorkTable, Relation, WindowTableFunctionKind,
 };
 use risingwave_common::error::ErrorCode;
 pub use select::{BoundDistinct, BoundSelect};
-pub use set_expr::BoundSetExpr;
+pub use set_expr::{BoundSetExpr, BoundSetOperator};
 pub use statement::BoundStatement;
 pub use update::BoundUpdate;
 pub use values::BoundValues;
 
 use crate::catalog::catalog_service::CatalogReadGuard;
+use crate::expr::{ExprImpl, ExprRewriter, ParameterTypes, SessionTimezone};
+use crate::handler::privilege::{check_user_privileges, ObjectCheckItem};
 use crate::session::{AuthContext, SessionImpl};
+use crate::user::user_service::UserInfoReader;
+use crate::user::UserId;
 
 /// `Binder` binds the identifiers in AST to columns in relations
 pub struct Binder {
@@ -73,14 +81,38 @@ pub struct Binder {
 
     next_subquery_id: usize,
     next_values_id: usize,
-    /// Map the cte's name to its Relation::Subquery.
-    cte_to_relation: HashMap<String, (BoundQuery, TableAlias)>,
+    /// Map the cte's name to its relation and schema. It's a `Relation::Subquery` for a normal
+    /// cte, and a `Relation::RecursiveUnion` for a recursive one, which is mapped to the
+    /// `Relation::WorkTable` when binding its recursive term.
+    cte_to_relation: HashMap<String, (Relation, Schema, TableAlias)>,
+    next_work_table_id: u32,
+    /// The work tables referenced in the recursive terms being bound.
+    referenced_work_tables: HashSet<u32>,
 
     search_path: SearchPath,
+
+    /// The types of the parameters of a prepared statement, specified or inferred.
+    param_types: ParameterTypes,
+    /// Whether a statement is being prepared, in which case the parameters are bound as
+    /// placeholders until [`BoundStatement::bind_parameter_values`]. A plain statement has no
+    /// parameters.
+    preparing: bool,
+
+    user_info_reader: UserInfoReader,
+    /// The user whose privileges are checked on the bound relations. It's switched to the owner
+    /// of a view when binding the query of the view, the same as PostgreSQL.
+    privilege_user_id: UserId,
+
+    /// Rewrites the expressions depending on the session TimeZone into the explicit ones.
+    session_timezone: SessionTimezone,
 }
 
 impl Binder {
-    pub fn new(session: &SessionImpl) -> Binder {
+    fn new_inner(
+        session: &SessionImpl,
+        param_types: Vec<Option<DataType>>,
+        preparing: bool,
+    ) -> Binder {
         Binder {
             catalog: session.env().catalog_reader().read_guard(),
             db_name: session.database().to_string(),
@@ -91,15 +123,50 @@ impl Binder {
             next_subquery_id: 0,
             next_values_id: 0,
             cte_to_relation: HashMap::new(),
+            next_work_table_id: 0,
+            referenced_work_tables: HashSet::new(),
             search_path: session.config().get_search_path(),
+            param_types: ParameterTypes::new(param_types),
+            preparing,
+            user_info_reader: session.env().user_info_reader().clone(),
+            privilege_user_id: session.user_id(),
+            session_timezone: SessionTimezone::new(session.config().get_timezone().to_string()),
         }
     }
 
+    pub fn new(session: &SessionImpl) -> Binder {
+        Self::new_inner(session, vec![], false)
+    }
+
+    /// Creates a binder for preparing a statement. The types of the parameters not specified are
+    /// inferred during binding.
+    pub fn new_with_param_types(
+        session: &SessionImpl,
+        param_types: Vec<Option<DataType>>,
+    ) -> Binder {
+        Self::new_inner(session, param_types, true)
+    }
+
     /// Bind a [`Statement`].
     pub fn bind(&mut self, stmt: Statement) -> Result<BoundStatement> {
         self.bind_statement(stmt)
     }
 
+    /// The types of the parameters, in order, after binding a statement to prepare.
+    pub fn export_param_types(&self) -> Vec<DataType> {
+        self.param_types.export()
+    }
+
+    /// Checks whether the privilege user has the privilege of `item`, which is done once the
+    /// relation is bound, so that the relations in subqueries, CTEs and views are all covered.
+    fn check_privilege(&self, item: ObjectCheckItem) -> Result<()> {
+        let reader = self.user_info_reader.read_guard();
+        match reader.get_user_by_id(self.privilege_user_id) {
+            Some(user) => check_user_privileges(user, &[item]),
+            None => Err(PermissionDenied("Session user is invalid".to_string()).into()),
+        }
+    }
+
     fn push_context(&mut self) {
         let new_context = std::mem::take(&mut self.context);
         let new_lateral_contexts = std::mem::take(&mut self.lateral_contexts);
@@ -136,6 +203,18 @@ impl Binder {
         Ok(())
     }
 
+    /// Pushes a lateral context holding the current context, i.e., the left side of a join, unless
+    /// the current context is empty, where the preceding items in `FROM` are already in the top
+    /// lateral context. Returns whether a lateral context is pushed.
+    fn push_lateral_context_if_needed(&mut self) -> bool {
+        if self.context.columns.is_empty() {
+            false
+        } else {
+            self.push_lateral_context();
+            true
+        }
+    }
+
     fn try_mark_lateral_as_visible(&mut self) {
         if let Some(mut ctx) = self.lateral_contexts.pop() {
             ctx.is_visible = true;
@@ -156,6 +235,12 @@ impl Binder {
         id
     }
 
+    fn next_work_table_id(&mut self) -> u32 {
+        let id = self.next_work_table_id;
+        self.next_work_table_id += 1;
+        id
+    }
+
     fn next_values_id(&mut self) -> usize {
         let id = self.next_values_id;
         self.next_values_id += 1;
@@ -164,6 +249,12 @@ impl Binder {
 }
 
 #[cfg(test)]
+/// Rewrites an expression of a bound statement in place.
+fn rewrite_expr_in_place(expr: &mut ExprImpl, rewriter: &mut impl ExprRewriter) {
+    let owned = std::mem::replace(expr, ExprImpl::literal_bool(true));
+    *expr = rewriter.rewrite_expr(owned);
+}
+
 pub mod test_utils {
     use super::Binder;
     use crate::session::SessionImpl;
@@ -19,8 +19,8 @@ use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::{Cte, Expr, Fetch, OrderByExpr, Query, Value, With};
 
-use crate::binder::{Binder, BoundSetExpr};
-use crate::expr::{CorrelatedId, Depth, ExprImpl};
+use crate::binder::{rewrite_expr_in_place, Binder, BoundSetExpr};
+use crate::expr::{CorrelatedInputRef, Depth, ExprImpl, ExprRewriter};
 use crate::optimizer::property::{Direction, FieldOrder};
 
 /// A validated sql query, including order and union.
@@ -84,14 +84,22 @@ impl BoundQuery {
                 .any(|e| e.has_correlated_input_ref_by_depth())
     }
 
-    pub fn collect_correlated_indices_by_depth_and_assign_id(
+    pub fn visit_correlated_input_refs_by_depth(
         &mut self,
         depth: Depth,
-        correlated_id: CorrelatedId,
-    ) -> Vec<usize> {
-        // TODO: collect `correlated_input_ref` in `extra_order_exprs`.
-        self.body
-            .collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id)
+        f: &mut dyn FnMut(&mut CorrelatedInputRef),
+    ) {
+        // TODO: visit `correlated_input_ref` in `extra_order_exprs`.
+        self.body.visit_correlated_input_refs_by_depth(depth, f)
+    }
+
+    /// Rewrites all the expressions of the query, including the ones of its subqueries if the
+    /// rewriter descends into them.
+    pub fn rewrite_exprs(&mut self, rewriter: &mut impl ExprRewriter) {
+        self.body.rewrite_exprs(rewriter);
+        self.extra_order_exprs
+            .iter_mut()
+            .for_each(|expr| rewrite_expr_in_place(expr, rewriter));
     }
 }
 
@@ -242,18 +250,14 @@ impl Binder {
     }
 
     fn bind_with(&mut self, with: With) -> Result<()> {
-        if with.recursive {
-            Err(ErrorCode::NotImplemented("recursive cte".into(), None.into()).into())
-        } else {
-            for cte_table in with.cte_tables {
-                let Cte { alias, query, .. } = cte_table;
-                let table_name = alias.name.real_value();
-                let bound_query = self.bind_query(query)?;
-                self.cte_to_relation
-                    .insert(table_name, (bound_query, alias));
-            }
-            Ok(())
+        for cte_table in with.cte_tables {
+            let Cte { alias, query, .. } = cte_table;
+            let table_name = alias.name.real_value();
+            let (relation, schema) = self.bind_cte_query(&alias, query, with.recursive)?;
+            self.cte_to_relation
+                .insert(table_name, (relation, schema, alias));
         }
+        Ok(())
     }
 }
 
@@ -48,12 +48,12 @@ impl Binder {
             self.push_lateral_context();
             let right = self.bind_table_with_joins(t.clone())?;
             self.pop_and_merge_lateral_context()?;
-            root = Relation::Join(Box::new(BoundJoin {
+            root = Self::join_or_apply(BoundJoin {
                 join_type: JoinType::Inner,
                 left: root,
                 right,
                 cond: ExprImpl::literal_bool(true),
-            }));
+            })?;
         }
         Ok(Some(root))
     }
@@ -89,12 +89,27 @@ impl Binder {
                 right,
                 cond,
             };
-            root = Relation::Join(Box::new(join));
+            root = Self::join_or_apply(join)?;
         }
 
         Ok(root)
     }
 
+    /// Returns a `Relation::Apply` if the right side of the join refers to its left side, or a
+    /// plain `Relation::Join` otherwise.
+    fn join_or_apply(join: BoundJoin) -> Result<Relation> {
+        if !join.right.is_lateral() {
+            return Ok(Relation::Join(Box::new(join)));
+        }
+        if !matches!(join.join_type, JoinType::Inner | JoinType::LeftOuter) {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "the combining JOIN type must be INNER or LEFT for a LATERAL reference".into(),
+            )
+            .into());
+        }
+        Ok(Relation::Apply(Box::new(join)))
+    }
+
     fn bind_join_constraint(
         &mut self,
         constraint: JoinConstraint,
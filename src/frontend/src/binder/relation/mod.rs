@@ -23,20 +23,22 @@ use risingwave_common::error::{internal_error, ErrorCode, Result, RwError};
 use risingwave_sqlparser::ast::{FunctionArg, Ident, ObjectName, TableAlias, TableFactor};
 
 use super::bind_context::ColumnBinding;
-use crate::binder::{Binder, BoundSetExpr};
-use crate::expr::{Expr, ExprImpl, TableFunction, TableFunctionType};
+use crate::binder::{rewrite_expr_in_place, Binder};
+use crate::expr::{Expr, ExprImpl, ExprRewriter, TableFunction, TableFunctionType};
 
 mod join;
+mod recursive_union;
 mod subquery;
 mod table_or_source;
 mod window_table_function;
 
 pub use join::BoundJoin;
+pub use recursive_union::{BoundRecursiveUnion, BoundWorkTable};
 pub use subquery::BoundSubquery;
 pub use table_or_source::{BoundBaseTable, BoundSource, BoundSystemTable, BoundTableSource};
 pub use window_table_function::{BoundWindowTableFunction, WindowTableFunctionKind};
 
-use crate::expr::{CorrelatedId, Depth};
+use crate::expr::{CorrelatedId, CorrelatedInputRef, Depth};
 
 /// A validated item that refers to a table-like entity, including base table, subquery, join, etc.
 /// It is usually part of the `from` clause.
@@ -47,25 +49,26 @@ pub enum Relation {
     SystemTable(Box<BoundSystemTable>),
     Subquery(Box<BoundSubquery>),
     Join(Box<BoundJoin>),
+    /// A join whose right side refers to the columns of its left side, i.e., a `LATERAL`
+    /// subquery or table function.
+    Apply(Box<BoundJoin>),
     WindowTableFunction(Box<BoundWindowTableFunction>),
     TableFunction(Box<TableFunction>),
+    RecursiveUnion(Box<BoundRecursiveUnion>),
+    WorkTable(Box<BoundWorkTable>),
 }
 
 impl Relation {
     pub fn contains_sys_table(&self) -> bool {
         match self {
             Relation::SystemTable(_) => true,
-            Relation::Subquery(s) => {
-                if let BoundSetExpr::Select(select) = &s.query.body
-                    && let Some(relation) = &select.from {
-                    relation.contains_sys_table()
-                } else {
-                    false
-                }
-            },
-            Relation::Join(j) => {
+            Relation::Subquery(s) => s.query.body.contains_sys_table(),
+            Relation::Join(j) | Relation::Apply(j) => {
                 j.left.contains_sys_table() || j.right.contains_sys_table()
-            },
+            }
+            Relation::RecursiveUnion(r) => {
+                r.base.body.contains_sys_table() || r.recursive.body.contains_sys_table()
+            }
             _ => false,
         }
     }
@@ -73,11 +76,30 @@ impl Relation {
     pub fn is_correlated(&self) -> bool {
         match self {
             Relation::Subquery(subquery) => subquery.query.is_correlated(),
-            Relation::Join(join) => {
+            Relation::Join(join) | Relation::Apply(join) => {
                 join.cond.has_correlated_input_ref_by_depth()
                     || join.left.is_correlated()
                     || join.right.is_correlated()
             }
+            Relation::RecursiveUnion(recursive_union) => recursive_union.is_correlated(),
+            Relation::TableFunction(table_function) => table_function
+                .args
+                .iter()
+                .any(|arg| arg.has_correlated_input_ref_by_depth()),
+            _ => false,
+        }
+    }
+
+    /// Whether the relation refers to the preceding items in `FROM`, and thus has to be joined
+    /// with them by an apply.
+    pub fn is_lateral(&self) -> bool {
+        match self {
+            Relation::Subquery(subquery) => subquery.lateral && subquery.query.is_correlated(),
+            Relation::TableFunction(table_function) => table_function
+                .args
+                .iter()
+                .any(|arg| arg.has_correlated_input_ref_by_depth()),
+            Relation::Join(join) | Relation::Apply(join) => join.left.is_lateral(),
             _ => false,
         }
     }
@@ -87,27 +109,72 @@ impl Relation {
         depth: Depth,
         correlated_id: CorrelatedId,
     ) -> Vec<usize> {
+        let mut correlated_indices = vec![];
+        self.visit_correlated_input_refs_by_depth(depth, &mut |correlated_input_ref| {
+            correlated_indices.push(correlated_input_ref.index());
+            correlated_input_ref.set_correlated_id(correlated_id);
+        });
+        correlated_indices
+    }
+
+    pub fn visit_correlated_input_refs_by_depth(
+        &mut self,
+        depth: Depth,
+        f: &mut dyn FnMut(&mut CorrelatedInputRef),
+    ) {
         match self {
             Relation::Subquery(subquery) => subquery
                 .query
-                .collect_correlated_indices_by_depth_and_assign_id(depth + 1, correlated_id),
+                .visit_correlated_input_refs_by_depth(depth + 1, f),
             Relation::Join(join) => {
-                let mut correlated_indices = vec![];
-                correlated_indices.extend(
-                    join.cond
-                        .collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id),
-                );
-                correlated_indices.extend(
-                    join.left
-                        .collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id),
-                );
-                correlated_indices.extend(
-                    join.right
-                        .collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id),
-                );
-                correlated_indices
+                join.cond.visit_correlated_input_refs_by_depth(depth, f);
+                join.left.visit_correlated_input_refs_by_depth(depth, f);
+                join.right.visit_correlated_input_refs_by_depth(depth, f);
+            }
+            Relation::Apply(apply) => {
+                apply.cond.visit_correlated_input_refs_by_depth(depth, f);
+                apply.left.visit_correlated_input_refs_by_depth(depth, f);
+                // At depth 0, the correlated input refs of the right side refer to the left side,
+                // which are collected when planning the apply itself.
+                if depth > 0 {
+                    apply.right.visit_correlated_input_refs_by_depth(depth, f);
+                }
+            }
+            Relation::RecursiveUnion(recursive_union) => {
+                recursive_union.visit_correlated_input_refs_by_depth(depth + 1, f)
+            }
+            Relation::TableFunction(table_function) => table_function
+                .args
+                .iter_mut()
+                .for_each(|arg| arg.visit_correlated_input_refs_by_depth(depth + 1, f)),
+            _ => {}
+        }
+    }
+
+    pub fn rewrite_exprs(&mut self, rewriter: &mut impl ExprRewriter) {
+        match self {
+            Relation::Subquery(subquery) => subquery.query.rewrite_exprs(rewriter),
+            Relation::Join(join) | Relation::Apply(join) => {
+                rewrite_expr_in_place(&mut join.cond, rewriter);
+                join.left.rewrite_exprs(rewriter);
+                join.right.rewrite_exprs(rewriter);
             }
-            _ => vec![],
+            Relation::WindowTableFunction(window) => {
+                window.input.rewrite_exprs(rewriter);
+                window
+                    .args
+                    .iter_mut()
+                    .for_each(|arg| rewrite_expr_in_place(arg, rewriter));
+            }
+            Relation::RecursiveUnion(recursive_union) => recursive_union.rewrite_exprs(rewriter),
+            Relation::TableFunction(table_function) => table_function
+                .args
+                .iter_mut()
+                .for_each(|arg| rewrite_expr_in_place(arg, rewriter)),
+            Relation::Source(_)
+            | Relation::BaseTable(_)
+            | Relation::SystemTable(_)
+            | Relation::WorkTable(_) => {}
         }
     }
 }
@@ -250,12 +317,22 @@ impl Binder {
         &mut self,
         name: ObjectName,
         alias: Option<TableAlias>,
+        for_system_time_as_of_proctime: bool,
     ) -> Result<Relation> {
         let (schema_name, table_name) = Self::resolve_schema_qualified_name(&self.db_name, name)?;
-        if schema_name.is_none() && let Some(bound_query) = self.cte_to_relation.get(&table_name) {
+        let not_a_table = || {
+            ErrorCode::BindError(format!(
+                "FOR SYSTEM_TIME AS OF PROCTIME() can only be used on a table, but \"{}\" is not",
+                table_name
+            ))
+        };
+        if schema_name.is_none() && let Some(cte) = self.cte_to_relation.get(&table_name) {
             // Handles CTE
+            if for_system_time_as_of_proctime {
+                return Err(not_a_table().into());
+            }
 
-            let (query, mut original_alias) = bound_query.clone();
+            let (relation, schema, mut original_alias) = cte.clone();
             debug_assert_eq!(original_alias.name.real_value(), table_name); // The original CTE alias ought to be its table name.
 
             if let Some(from_alias) = alias {
@@ -268,20 +345,31 @@ impl Binder {
                 }).collect();
             }
 
+            if let Relation::WorkTable(work_table) = &relation
+                && !self.referenced_work_tables.insert(work_table.work_table_id) {
+                return Err(ErrorCode::BindError(format!(
+                    "recursive reference to query \"{}\" must not appear more than once",
+                    table_name
+                ))
+                .into());
+            }
+
             self.bind_table_to_context(
-                query
-                    .body
-                    .schema()
-                    .fields
-                    .iter()
-                    .map(|f| (false, f.clone())),
+                schema.fields.into_iter().map(|f| (false, f)),
                 table_name,
                 Some(original_alias),
             )?;
-            Ok(Relation::Subquery(Box::new(BoundSubquery { query })))
+            Ok(relation)
         } else {
-
-            self.bind_relation_by_name_inner(schema_name.as_deref(), &table_name, alias)
+            let mut relation =
+                self.bind_relation_by_name_inner(schema_name.as_deref(), &table_name, alias)?;
+            if for_system_time_as_of_proctime {
+                match &mut relation {
+                    Relation::BaseTable(table) => table.for_system_time_as_of_proctime = true,
+                    _ => return Err(not_a_table().into()),
+                }
+            }
+            Ok(relation)
         }
     }
 
@@ -316,18 +404,31 @@ impl Binder {
 
     pub(super) fn bind_table_factor(&mut self, table_factor: TableFactor) -> Result<Relation> {
         match table_factor {
-            TableFactor::Table { name, alias } => self.bind_relation_by_name(name, alias),
+            TableFactor::Table {
+                name,
+                alias,
+                for_system_time_as_of_proctime,
+            } => self.bind_relation_by_name(name, alias, for_system_time_as_of_proctime),
             TableFactor::TableFunction { name, alias, args } => {
                 let func_name = &name.0[0].value;
                 if func_name.eq_ignore_ascii_case(RW_INTERNAL_TABLE_FUNCTION_NAME) {
                     return self.bind_internal_table(args, alias);
                 }
                 if let Ok(table_function_type) = TableFunctionType::from_str(func_name) {
-                    let args: Vec<ExprImpl> = args
+                    // The arguments may refer to the preceding items in `FROM`, as if the table
+                    // function is `LATERAL`. They are bound in a new context, so that such
+                    // references become correlated input refs.
+                    let pushed = self.push_lateral_context_if_needed();
+                    self.try_mark_lateral_as_visible();
+                    self.push_context();
+                    let args: Result<Vec<ExprImpl>> = args
                         .into_iter()
                         .map(|arg| self.bind_function_arg(arg))
                         .flatten_ok()
-                        .try_collect()?;
+                        .try_collect();
+                    self.pop_context()?;
+                    self.try_mark_lateral_as_invisible();
+                    let args = args?;
                     let tf = TableFunction::new(table_function_type, args)?;
                     let columns = [(
                         false,
@@ -345,6 +446,9 @@ impl Binder {
                         tf.function_type.name().to_string(),
                         alias,
                     )?;
+                    if pushed {
+                        self.pop_and_merge_lateral_context()?;
+                    }
 
                     return Ok(Relation::TableFunction(Box::new(tf)));
                 }
@@ -364,22 +468,21 @@ impl Binder {
                 alias,
             } => {
                 if lateral {
+                    let pushed = self.push_lateral_context_if_needed();
                     // If we detect a lateral, we mark the lateral context as visible.
                     self.try_mark_lateral_as_visible();
-
-                    // Bind lateral subquery here.
-
+                    let bound_subquery = self.bind_subquery_relation(*subquery, alias, true);
                     // Mark the lateral context as invisible once again.
                     self.try_mark_lateral_as_invisible();
-                    Err(ErrorCode::NotImplemented(
-                        "lateral subqueries are not yet supported".into(),
-                        Some(3815).into(),
-                    )
-                    .into())
+                    let bound_subquery = bound_subquery?;
+                    if pushed {
+                        self.pop_and_merge_lateral_context()?;
+                    }
+                    Ok(Relation::Subquery(Box::new(bound_subquery)))
                 } else {
                     // Non-lateral subqueries to not have access to the join-tree context.
                     self.push_lateral_context();
-                    let bound_subquery = self.bind_subquery_relation(*subquery, alias)?;
+                    let bound_subquery = self.bind_subquery_relation(*subquery, alias, false)?;
                     self.pop_and_merge_lateral_context()?;
                     Ok(Relation::Subquery(Box::new(bound_subquery)))
                 }
@@ -0,0 +1,186 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_sqlparser::ast::{Query, SetExpr, SetOperator, TableAlias};
+
+use super::{BoundSubquery, Relation};
+use crate::binder::{Binder, BoundQuery};
+use crate::expr::{CorrelatedInputRef, Depth, ExprRewriter};
+
+/// A recursive CTE in the form of `base UNION [ALL] recursive`, where the recursive term refers to
+/// the CTE itself through the [`BoundWorkTable`] with the same `work_table_id`.
+#[derive(Debug, Clone)]
+pub struct BoundRecursiveUnion {
+    pub work_table_id: u32,
+    pub all: bool,
+    pub base: BoundQuery,
+    pub recursive: BoundQuery,
+}
+
+impl BoundRecursiveUnion {
+    pub fn schema(&self) -> &Schema {
+        self.base.schema()
+    }
+
+    pub fn is_correlated(&self) -> bool {
+        self.base.is_correlated() || self.recursive.is_correlated()
+    }
+
+    pub fn visit_correlated_input_refs_by_depth(
+        &mut self,
+        depth: Depth,
+        f: &mut dyn FnMut(&mut CorrelatedInputRef),
+    ) {
+        self.base.visit_correlated_input_refs_by_depth(depth, f);
+        self.recursive
+            .visit_correlated_input_refs_by_depth(depth, f);
+    }
+
+    pub fn rewrite_exprs(&mut self, rewriter: &mut impl ExprRewriter) {
+        self.base.rewrite_exprs(rewriter);
+        self.recursive.rewrite_exprs(rewriter);
+    }
+}
+
+/// The self-reference of a recursive CTE in its recursive term, which contains the rows produced
+/// by the last iteration.
+#[derive(Debug, Clone)]
+pub struct BoundWorkTable {
+    pub work_table_id: u32,
+    pub schema: Schema,
+}
+
+impl Binder {
+    /// Binds the query of a CTE in `WITH RECURSIVE`. Returns the relation it is bound to and the
+    /// schema of the relation.
+    ///
+    /// Only a query in the form of `base UNION [ALL] recursive` may refer to the CTE itself, in its
+    /// recursive term. Other queries are bound as normal CTEs.
+    pub(in crate::binder) fn bind_cte_query(
+        &mut self,
+        alias: &TableAlias,
+        query: Query,
+        recursive: bool,
+    ) -> Result<(Relation, Schema)> {
+        let is_recursive_union = recursive
+            && matches!(
+                &query,
+                Query {
+                    with: None,
+                    body: SetExpr::SetOperation {
+                        op: SetOperator::Union,
+                        ..
+                    },
+                    order_by,
+                    limit: None,
+                    offset: None,
+                    fetch: None,
+                } if order_by.is_empty()
+            );
+        if !is_recursive_union {
+            let query = self.bind_query(query)?;
+            let schema = query.schema().clone();
+            return Ok((
+                Relation::Subquery(Box::new(BoundSubquery {
+                    query,
+                    lateral: false,
+                })),
+                schema,
+            ));
+        }
+        let set_expr = query.body.to_string();
+        let SetExpr::SetOperation { all, left, right, .. } = query.body else {
+            unreachable!()
+        };
+
+        let base = self.bind_query(set_expr_to_query(*left))?;
+        let schema = base.schema().clone();
+
+        // The CTE refers to the work table when binding the recursive term.
+        let work_table_id = self.next_work_table_id();
+        let table_name = alias.name.real_value();
+        let work_table = Relation::WorkTable(Box::new(BoundWorkTable {
+            work_table_id,
+            schema: schema.clone(),
+        }));
+        let shadowed = self.cte_to_relation.insert(
+            table_name.clone(),
+            (work_table, schema.clone(), alias.clone()),
+        );
+        let recursive = self.bind_query(set_expr_to_query(*right));
+        match shadowed {
+            Some(shadowed) => self.cte_to_relation.insert(table_name.clone(), shadowed),
+            None => self.cte_to_relation.remove(&table_name),
+        };
+        let recursive = recursive?;
+
+        if !self.referenced_work_tables.remove(&work_table_id) {
+            // It's a plain `UNION` without recursion.
+            return Err(
+                ErrorCode::NotImplemented(format!("set expr: {}", set_expr), None.into()).into(),
+            );
+        }
+
+        if schema.len() != recursive.schema().len() {
+            return Err(ErrorCode::BindError(format!(
+                "each UNION query of recursive query \"{}\" must have the same number of columns",
+                table_name
+            ))
+            .into());
+        }
+        for (i, (base_type, recursive_type)) in schema
+            .data_types()
+            .into_iter()
+            .zip_eq(recursive.data_types())
+            .enumerate()
+        {
+            if base_type != recursive_type {
+                return Err(ErrorCode::BindError(format!(
+                    "recursive query \"{}\" column {} has type {} in non-recursive term but type {} overall",
+                    table_name,
+                    i + 1,
+                    base_type,
+                    recursive_type
+                ))
+                .into());
+            }
+        }
+
+        let relation = Relation::RecursiveUnion(Box::new(BoundRecursiveUnion {
+            work_table_id,
+            all,
+            base,
+            recursive,
+        }));
+        Ok((relation, schema))
+    }
+}
+
+/// Converts an operand of a set operation to a query, unwrapping the parentheses if any.
+fn set_expr_to_query(set_expr: SetExpr) -> Query {
+    match set_expr {
+        SetExpr::Query(query) => *query,
+        body => Query {
+            with: None,
+            body,
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            fetch: None,
+        },
+    }
+}
@@ -20,6 +20,9 @@ use crate::binder::{Binder, BoundQuery, UNNAMED_SUBQUERY};
 #[derive(Debug, Clone)]
 pub struct BoundSubquery {
     pub query: BoundQuery,
+    /// Whether the subquery is `LATERAL`, i.e., it may refer to the columns of the preceding
+    /// items in `FROM`.
+    pub lateral: bool,
 }
 
 impl Binder {
@@ -31,6 +34,7 @@ impl Binder {
         &mut self,
         query: Query,
         alias: Option<TableAlias>,
+        lateral: bool,
     ) -> Result<BoundSubquery> {
         let query = self.bind_query(query)?;
         let sub_query_id = self.next_subquery_id();
@@ -45,6 +49,6 @@ impl Binder {
             format!("{}_{}", UNNAMED_SUBQUERY, sub_query_id),
             alias,
         )?;
-        Ok(BoundSubquery { query })
+        Ok(BoundSubquery { query, lateral })
     }
 }
@@ -12,13 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::sync::Arc;
 
 use itertools::Itertools;
-use risingwave_common::catalog::{ColumnDesc, Field, PG_CATALOG_SCHEMA_NAME};
+use risingwave_common::catalog::{
+    is_system_schema, valid_table_name, ColumnDesc, ColumnId, Field, PG_CATALOG_SCHEMA_NAME,
+};
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::session_config::USER_NAME_WILD_CARD;
+use risingwave_pb::stream_plan::source_node::Info as StreamPlanInfo;
+use risingwave_pb::user::grant_privilege::{Action, Object};
 use risingwave_sqlparser::ast::{Statement, TableAlias};
 use risingwave_sqlparser::parser::Parser;
 
@@ -30,6 +35,8 @@ use crate::catalog::system_catalog::SystemCatalog;
 use crate::catalog::table_catalog::TableCatalog;
 use crate::catalog::view_catalog::ViewCatalog;
 use crate::catalog::{CatalogError, IndexCatalog, TableId};
+use crate::expr::Literal;
+use crate::handler::privilege::ObjectCheckItem;
 use crate::user::UserId;
 
 #[derive(Debug, Clone)]
@@ -37,10 +44,13 @@ pub struct BoundBaseTable {
     pub table_id: TableId,
     pub table_catalog: TableCatalog,
     pub table_indexes: Vec<Arc<IndexCatalog>>,
+    /// Whether the table is specified with `FOR SYSTEM_TIME AS OF PROCTIME()`, which makes it the
+    /// lookup side of a temporal join.
+    pub for_system_time_as_of_proctime: bool,
 }
 
 /// `BoundTableSource` is used by DML statement on table source like insert, update.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BoundTableSource {
     pub name: String,       // explain-only
     pub source_id: TableId, // TODO: refactor to source id
@@ -48,6 +58,13 @@ pub struct BoundTableSource {
     pub columns: Vec<ColumnDesc>,
     pub append_only: bool,
     pub owner: UserId,
+    /// Generated columns are computed when rows are ingested, so they can't be written directly.
+    pub generated_column_ids: HashSet<ColumnId>,
+    /// Default values used for the columns omitted in `INSERT`.
+    pub default_values: HashMap<ColumnId, Literal>,
+    /// Columns dropped by `ALTER TABLE`. They are still kept in the storage and always written
+    /// with nulls.
+    pub dropped_column_ids: HashSet<ColumnId>,
 }
 
 #[derive(Debug, Clone)]
@@ -91,28 +108,24 @@ impl Binder {
             )
         };
 
-        let resolve_source_relation = |source_catalog: &SourceCatalog| {
-            (
-                Relation::Source(Box::new(source_catalog.into())),
-                source_catalog
-                    .columns
-                    .iter()
-                    .map(|c| (c.is_hidden, Field::from(&c.column_desc)))
-                    .collect_vec(),
-            )
-        };
-
         // start to bind
         let (ret, columns) = {
             match schema_name {
                 Some(schema_name) => {
                     let schema_path = SchemaPath::Name(schema_name);
-                    if schema_name == PG_CATALOG_SCHEMA_NAME {
-                        if let Ok(sys_table_catalog) = self
-                            .catalog
-                            .get_sys_table_by_name(&self.db_name, table_name)
-                        {
+                    if is_system_schema(schema_name) {
+                        if let Ok(sys_table_catalog) = self.catalog.get_sys_table_by_name(
+                            &self.db_name,
+                            schema_name,
+                            table_name,
+                        ) {
                             resolve_sys_table_relation(sys_table_catalog)
+                        } else if schema_name != PG_CATALOG_SCHEMA_NAME {
+                            return Err(CatalogError::NotFound(
+                                "table or source",
+                                table_name.to_string(),
+                            )
+                            .into());
                         } else {
                             return Err(ErrorCode::NotImplemented(
                                 format!(
@@ -136,7 +149,7 @@ impl Binder {
                         self.catalog
                             .get_source_by_name(&self.db_name, schema_path, table_name)
                     {
-                        resolve_source_relation(source_catalog)
+                        self.resolve_source_relation(source_catalog)?
                     } else if let Ok((view_catalog, _)) =
                         self.catalog
                             .get_view_by_name(&self.db_name, schema_path, table_name)
@@ -154,10 +167,10 @@ impl Binder {
                     let user_name = &self.auth_context.user_name;
 
                     for path in self.search_path.path() {
-                        if path == PG_CATALOG_SCHEMA_NAME {
-                            if let Ok(sys_table_catalog) = self
-                                .catalog
-                                .get_sys_table_by_name(&self.db_name, table_name)
+                        if is_system_schema(path) {
+                            if let Ok(sys_table_catalog) =
+                                self.catalog
+                                    .get_sys_table_by_name(&self.db_name, path, table_name)
                             {
                                 return Ok(resolve_sys_table_relation(sys_table_catalog));
                             }
@@ -176,7 +189,7 @@ impl Binder {
                                 } else if let Some(source_catalog) =
                                     schema.get_source_by_name(table_name)
                                 {
-                                    return Ok(resolve_source_relation(source_catalog));
+                                    return self.resolve_source_relation(source_catalog);
                                 } else if let Some(view_catalog) =
                                     schema.get_view_by_name(table_name)
                                 {
@@ -201,6 +214,14 @@ impl Binder {
         schema_name: &str,
     ) -> Result<(Relation, Vec<(bool, Field)>)> {
         let table_id = table_catalog.id();
+        self.check_privilege(
+            ObjectCheckItem::new(
+                table_catalog.owner,
+                Action::Select,
+                Object::TableId(table_id.table_id),
+            )
+            .with_superuser_only(!valid_table_name(&table_catalog.name)),
+        )?;
         let table_catalog = table_catalog.clone();
         let columns = table_catalog
             .columns
@@ -213,15 +234,40 @@ impl Binder {
             table_id,
             table_catalog,
             table_indexes,
+            for_system_time_as_of_proctime: false,
         };
 
         Ok::<_, RwError>((Relation::BaseTable(Box::new(table)), columns))
     }
 
+    fn resolve_source_relation(
+        &self,
+        source_catalog: &SourceCatalog,
+    ) -> Result<(Relation, Vec<(bool, Field)>)> {
+        self.check_privilege(ObjectCheckItem::new(
+            source_catalog.owner,
+            Action::Select,
+            Object::SourceId(source_catalog.id),
+        ))?;
+        Ok((
+            Relation::Source(Box::new(source_catalog.into())),
+            source_catalog
+                .columns
+                .iter()
+                .map(|c| (c.is_hidden, Field::from(&c.column_desc)))
+                .collect_vec(),
+        ))
+    }
+
     fn resolve_view_relation(
         &mut self,
         view_catalog: &ViewCatalog,
     ) -> Result<(Relation, Vec<(bool, Field)>)> {
+        self.check_privilege(ObjectCheckItem::new(
+            view_catalog.owner,
+            Action::Select,
+            Object::ViewId(view_catalog.id),
+        ))?;
         let ast = Parser::parse_sql(&view_catalog.sql)
             .expect("a view's sql should be parsed successfully");
         assert!(ast.len() == 1, "a view should contain only one statement");
@@ -229,15 +275,40 @@ impl Binder {
             Statement::Query(q) => q,
             _ => unreachable!("a view should contain a query statement"),
         };
-        let query = self.bind_query(*query).map_err(|e| {
+        // The relations referenced by the view are accessed with the privileges of its owner.
+        let privilege_user_id = std::mem::replace(&mut self.privilege_user_id, view_catalog.owner);
+        let query = self.bind_query(*query);
+        self.privilege_user_id = privilege_user_id;
+        let query = query.map_err(|e| {
             ErrorCode::BindError(format!(
                 "failed to bind view {}, sql: {}\nerror: {}",
                 view_catalog.name, view_catalog.sql, e
             ))
         })?;
-        let columns = view_catalog.columns.clone();
+        let mut columns = view_catalog.columns.clone();
+        let fields = query.schema().fields();
+        if fields.len() != columns.len() {
+            // The columns added to the relations selected with `*` after the view is created, e.g.
+            // by the schema evolution of a source, are appended to the view.
+            let is_prefix = fields.len() > columns.len()
+                && fields[..columns.len()]
+                    .iter()
+                    .zip_eq(columns.iter())
+                    .all(|(f, c)| f.name == c.name && f.data_type == c.data_type);
+            if !is_prefix {
+                return Err(ErrorCode::BindError(format!(
+                    "the columns of view {} have changed, please recreate it",
+                    view_catalog.name
+                ))
+                .into());
+            }
+            columns.extend(fields[columns.len()..].iter().cloned());
+        }
         Ok((
-            Relation::Subquery(Box::new(BoundSubquery { query })),
+            Relation::Subquery(Box::new(BoundSubquery {
+                query,
+                lateral: false,
+            })),
             columns.iter().map(|c| (false, c.clone())).collect_vec(),
         ))
     }
@@ -286,13 +357,17 @@ impl Binder {
             table_id,
             table_catalog,
             table_indexes,
+            for_system_time_as_of_proctime: false,
         })
     }
 
+    /// Binds the target table of a DML statement, which checks that the session user has the
+    /// privilege of `action` on it.
     pub(crate) fn bind_table_source(
         &mut self,
         schema_name: Option<&str>,
         source_name: &str,
+        action: Action,
     ) -> Result<BoundTableSource> {
         let db_name = &self.db_name;
         let schema_path = match schema_name {
@@ -311,17 +386,47 @@ impl Binder {
         )?;
 
         let source_id = TableId::new(source.id);
+        // The privileges on a table are granted on the table id, rather than its source.
+        self.check_privilege(ObjectCheckItem::new(
+            associate_table.owner,
+            action,
+            Object::TableId(associate_table_id.table_id),
+        ))?;
 
         let append_only = source.append_only;
+        // The row id column is filled by the source executor. Other hidden columns are the ones
+        // dropped by `ALTER TABLE`.
         let columns = source
             .columns
             .iter()
-            .filter(|c| !c.is_hidden)
-            .map(|c| c.column_desc.clone())
+            .enumerate()
+            .filter(|(i, _)| source.row_id_index != Some(*i))
+            .map(|(_, c)| c.column_desc.clone())
+            .collect();
+        let dropped_column_ids = source
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| c.is_hidden && source.row_id_index != Some(*i))
+            .map(|(_, c)| c.column_id())
             .collect();
 
         let owner = source.owner;
 
+        let (generated_column_ids, default_values) = match &source.info {
+            StreamPlanInfo::TableSource(info) => (
+                info.generated_columns
+                    .keys()
+                    .map(|id| ColumnId::new(*id))
+                    .collect(),
+                info.default_columns
+                    .iter()
+                    .map(|(id, expr)| Ok((ColumnId::new(*id), Literal::from_expr_proto(expr)?)))
+                    .collect::<Result<_>>()?,
+            ),
+            StreamPlanInfo::StreamSource(_) => (HashSet::new(), HashMap::new()),
+        };
+
         // Note(bugen): do not bind context here.
 
         Ok(BoundTableSource {
@@ -331,6 +436,9 @@ impl Binder {
             columns,
             append_only,
             owner,
+            generated_column_ids,
+            default_values,
+            dropped_column_ids,
         })
     }
 }
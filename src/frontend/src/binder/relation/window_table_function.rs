@@ -27,6 +27,7 @@ use crate::expr::{ExprImpl, InputRef};
 pub enum WindowTableFunctionKind {
     Tumble,
     Hop,
+    Session,
 }
 
 impl FromStr for WindowTableFunctionKind {
@@ -37,6 +38,8 @@ impl FromStr for WindowTableFunctionKind {
             Ok(WindowTableFunctionKind::Tumble)
         } else if s.eq_ignore_ascii_case("hop") {
             Ok(WindowTableFunctionKind::Hop)
+        } else if s.eq_ignore_ascii_case("session") {
+            Ok(WindowTableFunctionKind::Session)
         } else {
             Err(())
         }
@@ -77,7 +80,7 @@ impl Binder {
             .into()),
         }?;
 
-        let base = self.bind_relation_by_name(table_name.clone(), None)?;
+        let base = self.bind_relation_by_name(table_name.clone(), None, false)?;
 
         let time_col = if let Some(time_col_arg) = args.next()
           && let Some(ExprImpl::InputRef(time_col)) = self.bind_function_arg(time_col_arg)?.into_iter().next()
@@ -22,13 +22,13 @@ use risingwave_sqlparser::ast::{Distinct, Expr, Select, SelectItem};
 
 use super::bind_context::{Clause, ColumnBinding};
 use super::UNNAMED_COLUMN;
-use crate::binder::{Binder, Relation};
+use crate::binder::{rewrite_expr_in_place, Binder, Relation};
 use crate::catalog::check_valid_column_name;
 use crate::catalog::pg_catalog::pg_user::{
     PG_USER_ID_INDEX, PG_USER_NAME_INDEX, PG_USER_TABLE_NAME,
 };
 use crate::expr::{
-    CorrelatedId, CorrelatedInputRef, Depth, Expr as _, ExprImpl, ExprType, FunctionCall, InputRef,
+    CorrelatedInputRef, Depth, Expr as _, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef,
 };
 
 #[derive(Debug, Clone)]
@@ -39,6 +39,9 @@ pub struct BoundSelect {
     pub from: Option<Relation>,
     pub where_clause: Option<ExprImpl>,
     pub group_by: Vec<ExprImpl>,
+    /// The grouping sets of `GROUPING SETS`, `ROLLUP` and `CUBE` as indices into `group_by`. It is
+    /// empty for a plain GROUP BY, which has the only grouping set of all the `group_by`.
+    pub grouping_sets: Vec<Vec<usize>>,
     pub having: Option<ExprImpl>,
     schema: Schema,
 }
@@ -62,8 +65,7 @@ impl BoundSelect {
             .iter_mut()
             .chain(self.group_by.iter_mut())
             .chain(self.where_clause.iter_mut())
-        // TODO: uncomment `having` below after #4850 is fixed
-        // .chain(self.having.iter_mut())
+            .chain(self.having.iter_mut())
     }
 
     pub fn is_correlated(&self) -> bool {
@@ -75,25 +77,45 @@ impl BoundSelect {
             }
     }
 
-    pub fn collect_correlated_indices_by_depth_and_assign_id(
+    pub fn visit_correlated_input_refs_by_depth(
         &mut self,
         depth: Depth,
-        correlated_id: CorrelatedId,
-    ) -> Vec<usize> {
-        let mut correlated_indices = self
-            .exprs_mut()
-            .flat_map(|expr| {
-                expr.collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id)
-            })
-            .collect_vec();
+        f: &mut dyn FnMut(&mut CorrelatedInputRef),
+    ) {
+        self.exprs_mut()
+            .for_each(|expr| expr.visit_correlated_input_refs_by_depth(depth, f));
+
+        if let Some(relation) = self.from.as_mut() {
+            relation.visit_correlated_input_refs_by_depth(depth, f);
+        }
+    }
+
+    pub fn rewrite_exprs(&mut self, rewriter: &mut impl ExprRewriter) {
+        let distinct_on = match &mut self.distinct {
+            BoundDistinct::DistinctOn(exprs) => Some(exprs),
+            _ => None,
+        };
+        self.select_items
+            .iter_mut()
+            .chain(self.group_by.iter_mut())
+            .chain(self.where_clause.iter_mut())
+            .chain(self.having.iter_mut())
+            .chain(distinct_on.into_iter().flatten())
+            .for_each(|expr| rewrite_expr_in_place(expr, rewriter));
 
         if let Some(relation) = self.from.as_mut() {
-            correlated_indices.extend(
-                relation.collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id),
-            );
+            relation.rewrite_exprs(rewriter);
         }
+    }
 
-        correlated_indices
+    /// Appends an item to the select list, e.g. a column added to the table of a bound `SELECT *`
+    /// when it's re-planned.
+    pub fn push_select_item(&mut self, item: ExprImpl, alias: String) {
+        self.schema
+            .fields
+            .push(Field::with_name(item.return_type(), alias.clone()));
+        self.select_items.push(item);
+        self.aliases.push(Some(alias));
     }
 }
 
@@ -137,11 +159,7 @@ impl Binder {
 
         // Bind GROUP BY clause.
         self.context.clause = Some(Clause::GroupBy);
-        let group_by = select
-            .group_by
-            .into_iter()
-            .map(|expr| self.bind_expr(expr))
-            .try_collect()?;
+        let (group_by, grouping_sets) = self.bind_group_by(select.group_by)?;
         self.context.clause = None;
 
         // Bind HAVING clause.
@@ -150,6 +168,19 @@ impl Binder {
         Self::require_bool_clause(&having, "HAVING")?;
         self.context.clause = None;
 
+        if group_by.is_empty()
+            && select_items
+                .iter()
+                .chain(having.iter())
+                .any(|expr| expr.has_grouping())
+        {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "arguments to GROUPING must be grouping expressions of the associated query level"
+                    .into(),
+            )
+            .into());
+        }
+
         // Store field from `ExprImpl` to support binding `field_desc` in `subquery`.
         let fields = select_items
             .iter()
@@ -167,11 +198,114 @@ impl Binder {
             from,
             where_clause: selection,
             group_by,
+            grouping_sets,
             having,
             schema: Schema { fields },
         })
     }
 
+    /// Binds the GROUP BY clause into the group exprs and the grouping sets of
+    /// [`BoundSelect::grouping_sets`]. Like `PostgreSQL`, the grouping sets of multiple items are
+    /// the cross product of the grouping sets of each item, where a plain expr is a single grouping
+    /// set.
+    fn bind_group_by(&mut self, group_by: Vec<Expr>) -> Result<(Vec<ExprImpl>, Vec<Vec<usize>>)> {
+        if !group_by.iter().any(|expr| {
+            matches!(
+                expr,
+                Expr::GroupingSets(_) | Expr::Rollup(_) | Expr::Cube(_)
+            )
+        }) {
+            let group_by = group_by
+                .into_iter()
+                .map(|expr| self.bind_expr(expr))
+                .try_collect()?;
+            return Ok((group_by, vec![]));
+        }
+
+        let mut group_exprs = vec![];
+        let mut grouping_sets = vec![vec![]];
+        for expr in group_by {
+            let item_sets: Vec<Vec<usize>> = match expr {
+                Expr::GroupingSets(sets) => sets
+                    .into_iter()
+                    .map(|set| self.bind_grouping_set(set, &mut group_exprs))
+                    .try_collect()?,
+                Expr::Rollup(elements) => {
+                    let elements: Vec<_> = elements
+                        .into_iter()
+                        .map(|element| self.bind_grouping_set(element, &mut group_exprs))
+                        .try_collect()?;
+                    // `ROLLUP (a, b)` is `GROUPING SETS ((a, b), (a), ())`.
+                    (0..=elements.len())
+                        .rev()
+                        .map(|len| elements[..len].concat())
+                        .collect()
+                }
+                Expr::Cube(elements) => {
+                    if elements.len() > 12 {
+                        return Err(ErrorCode::InvalidInputSyntax(
+                            "CUBE is limited to 12 elements".into(),
+                        )
+                        .into());
+                    }
+                    let elements: Vec<_> = elements
+                        .into_iter()
+                        .map(|element| self.bind_grouping_set(element, &mut group_exprs))
+                        .try_collect()?;
+                    // `CUBE (a, b)` is `GROUPING SETS ((a, b), (a), (b), ())`.
+                    let n = elements.len();
+                    (0..1usize << n)
+                        .rev()
+                        .map(|mask| {
+                            elements
+                                .iter()
+                                .enumerate()
+                                .filter(|(i, _)| mask & (1 << (n - 1 - i)) != 0)
+                                .flat_map(|(_, element)| element.iter().copied())
+                                .collect()
+                        })
+                        .collect()
+                }
+                expr => vec![self.bind_grouping_set(vec![expr], &mut group_exprs)?],
+            };
+            grouping_sets = grouping_sets
+                .iter()
+                .cartesian_product(item_sets.iter())
+                .map(|(set, item_set)| {
+                    set.iter()
+                        .chain(item_set.iter())
+                        .copied()
+                        .sorted()
+                        .dedup()
+                        .collect()
+                })
+                .collect();
+        }
+        Ok((group_exprs, grouping_sets))
+    }
+
+    /// Binds the exprs of a grouping set and returns their indices in `group_exprs`, where the
+    /// exprs are added if not yet.
+    fn bind_grouping_set(
+        &mut self,
+        exprs: Vec<Expr>,
+        group_exprs: &mut Vec<ExprImpl>,
+    ) -> Result<Vec<usize>> {
+        exprs
+            .into_iter()
+            .map(|expr| -> Result<usize> {
+                let expr = self.bind_expr(expr)?;
+                Ok(match group_exprs.iter().position(|e| *e == expr) {
+                    Some(index) => index,
+                    None => {
+                        group_exprs.push(expr);
+                        group_exprs.len() - 1
+                    }
+                })
+            })
+            .try_collect()
+    }
+
     pub fn bind_select_list(
         &mut self,
         select_items: Vec<SelectItem>,
@@ -263,6 +397,36 @@ impl Binder {
         Ok((select_list, aliases))
     }
 
+    /// Binds the `RETURNING` list of `INSERT`, `UPDATE` or `DELETE` against the columns of the
+    /// target table in the current context. Returns the expressions and the fields of the output.
+    pub(super) fn bind_returning_list(
+        &mut self,
+        returning_items: Vec<SelectItem>,
+    ) -> Result<(Vec<ExprImpl>, Vec<Field>)> {
+        let (returning_list, aliases) = self.bind_select_list(returning_items)?;
+        if returning_list.iter().any(|expr| {
+            expr.has_agg_call()
+                || expr.has_table_function()
+                || expr.has_window_function()
+                || expr.has_subquery()
+        }) {
+            return Err(ErrorCode::BindError(
+                "RETURNING does not support aggregate, table or window functions, or subqueries"
+                    .to_string(),
+            )
+            .into());
+        }
+        let fields = returning_list
+            .iter()
+            .zip_eq(aliases)
+            .map(|(expr, alias)| {
+                let name = alias.unwrap_or_else(|| UNNAMED_COLUMN.to_string());
+                Field::with_name(expr.return_type(), name)
+            })
+            .collect();
+        Ok((returning_list, fields))
+    }
+
     /// `bind_get_user_by_id_select` binds a select statement that returns a single user name by id,
     /// this is used for function `pg_catalog.get_user_by_id()`.
     pub fn bind_get_user_by_id_select(&mut self, input: &ExprImpl) -> Result<BoundSelect> {
@@ -309,6 +473,7 @@ impl Binder {
             from,
             where_clause,
             group_by: vec![],
+            grouping_sets: vec![],
             having: None,
             schema,
         })
@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risingwave_common::catalog::Schema;
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::SetExpr;
+use risingwave_sqlparser::ast::{SetExpr, SetOperator};
 
 use crate::binder::{Binder, BoundSelect, BoundValues};
-use crate::expr::{CorrelatedId, Depth};
+use crate::expr::{least_restrictive, CorrelatedInputRef, Depth, ExprImpl, ExprRewriter};
 
 /// Part of a validated query, without order or limit clause. It may be composed of smaller
 /// `BoundSetExpr`s via set operators (e.g. union).
@@ -25,6 +28,30 @@ use crate::expr::{CorrelatedId, Depth};
 pub enum BoundSetExpr {
     Select(Box<BoundSelect>),
     Values(Box<BoundValues>),
+    /// The output columns are named after the left operand, with the types of both operands
+    /// aligned.
+    SetOperation {
+        op: BoundSetOperator,
+        all: bool,
+        left: Box<BoundSetExpr>,
+        right: Box<BoundSetExpr>,
+        schema: Schema,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundSetOperator {
+    Intersect,
+    Except,
+}
+
+impl fmt::Display for BoundSetOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoundSetOperator::Intersect => write!(f, "INTERSECT"),
+            BoundSetOperator::Except => write!(f, "EXCEPT"),
+        }
+    }
 }
 
 impl BoundSetExpr {
@@ -34,6 +61,41 @@ impl BoundSetExpr {
         match self {
             BoundSetExpr::Select(s) => s.schema(),
             BoundSetExpr::Values(v) => v.schema(),
+            BoundSetExpr::SetOperation { schema, .. } => schema,
+        }
+    }
+
+    /// The expressions of all the [`BoundSelect`]s and [`BoundValues`] in this [`BoundSetExpr`].
+    pub fn exprs(&self) -> Box<dyn Iterator<Item = &ExprImpl> + '_> {
+        match self {
+            BoundSetExpr::Select(s) => Box::new(s.exprs()),
+            BoundSetExpr::Values(v) => Box::new(v.exprs()),
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                Box::new(left.exprs().chain(right.exprs()))
+            }
+        }
+    }
+
+    pub fn exprs_mut(&mut self) -> Box<dyn Iterator<Item = &mut ExprImpl> + '_> {
+        match self {
+            BoundSetExpr::Select(s) => Box::new(s.exprs_mut()),
+            BoundSetExpr::Values(v) => Box::new(v.exprs_mut()),
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                Box::new(left.exprs_mut().chain(right.exprs_mut()))
+            }
+        }
+    }
+
+    pub fn contains_sys_table(&self) -> bool {
+        match self {
+            BoundSetExpr::Select(s) => s
+                .from
+                .as_ref()
+                .map_or(false, |relation| relation.contains_sys_table()),
+            BoundSetExpr::Values(_) => false,
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                left.contains_sys_table() || right.contains_sys_table()
+            }
         }
     }
 
@@ -41,20 +103,34 @@ impl BoundSetExpr {
         match self {
             BoundSetExpr::Select(s) => s.is_correlated(),
             BoundSetExpr::Values(v) => v.is_correlated(),
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                left.is_correlated() || right.is_correlated()
+            }
         }
     }
 
-    pub fn collect_correlated_indices_by_depth_and_assign_id(
+    pub fn visit_correlated_input_refs_by_depth(
         &mut self,
         depth: Depth,
-        correlated_id: CorrelatedId,
-    ) -> Vec<usize> {
+        f: &mut dyn FnMut(&mut CorrelatedInputRef),
+    ) {
         match self {
-            BoundSetExpr::Select(s) => {
-                s.collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id)
+            BoundSetExpr::Select(s) => s.visit_correlated_input_refs_by_depth(depth, f),
+            BoundSetExpr::Values(v) => v.visit_correlated_input_refs_by_depth(depth, f),
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                left.visit_correlated_input_refs_by_depth(depth, f);
+                right.visit_correlated_input_refs_by_depth(depth, f);
             }
-            BoundSetExpr::Values(v) => {
-                v.collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id)
+        }
+    }
+
+    pub fn rewrite_exprs(&mut self, rewriter: &mut impl ExprRewriter) {
+        match self {
+            BoundSetExpr::Select(s) => s.rewrite_exprs(rewriter),
+            BoundSetExpr::Values(v) => v.rewrite_exprs(rewriter),
+            BoundSetExpr::SetOperation { left, right, .. } => {
+                left.rewrite_exprs(rewriter);
+                right.rewrite_exprs(rewriter);
             }
         }
     }
@@ -70,11 +146,80 @@ impl Binder {
                 3584.into(),
             )
             .into()),
-            SetExpr::SetOperation { .. } => Err(ErrorCode::NotImplemented(
+            SetExpr::SetOperation {
+                op: SetOperator::Union,
+                ..
+            } => Err(ErrorCode::NotImplemented(
                 format!("set expr: {:}", set_expr),
                 None.into(),
             )
             .into()),
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                let op = match op {
+                    SetOperator::Intersect => BoundSetOperator::Intersect,
+                    SetOperator::Except => BoundSetOperator::Except,
+                    SetOperator::Union => unreachable!(),
+                };
+                self.bind_set_operation(op, all, *left, *right)
+            }
         }
     }
+
+    fn bind_set_operation(
+        &mut self,
+        op: BoundSetOperator,
+        all: bool,
+        left: SetExpr,
+        right: SetExpr,
+    ) -> Result<BoundSetExpr> {
+        let left = self.bind_set_operand(left)?;
+        let right = self.bind_set_operand(right)?;
+
+        if left.schema().len() != right.schema().len() {
+            return Err(ErrorCode::BindError(format!(
+                "each {} query must have the same number of columns",
+                op
+            ))
+            .into());
+        }
+        let fields = left
+            .schema()
+            .fields()
+            .iter()
+            .zip_eq(right.schema().fields())
+            .map(|(l, r)| {
+                let data_type = least_restrictive(l.data_type(), r.data_type()).map_err(|_| {
+                    ErrorCode::BindError(format!(
+                        "{} types {} and {} cannot be matched",
+                        op,
+                        l.data_type(),
+                        r.data_type()
+                    ))
+                })?;
+                Ok(Field::with_name(data_type, l.name.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(BoundSetExpr::SetOperation {
+            op,
+            all,
+            left: Box::new(left),
+            right: Box::new(right),
+            schema: Schema::new(fields),
+        })
+    }
+
+    /// Binds an operand of a set operation with a new [`BindContext`](super::BindContext), as the
+    /// relations in the operands are not visible to each other.
+    fn bind_set_operand(&mut self, set_expr: SetExpr) -> Result<BoundSetExpr> {
+        let context = std::mem::take(&mut self.context);
+        let result = self.bind_set_expr(set_expr);
+        self.context = context;
+        result
+    }
 }
@@ -12,14 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::Statement;
 
 use super::delete::BoundDelete;
 use super::update::BoundUpdate;
-use crate::binder::{Binder, BoundInsert, BoundQuery};
+use crate::binder::{rewrite_expr_in_place, Binder, BoundInsert, BoundQuery};
+use crate::expr::{ExprImpl, ExprRewriter, Literal, Parameter, Subquery};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum BoundStatement {
     Insert(Box<BoundInsert>),
     Delete(Box<BoundDelete>),
@@ -27,6 +30,95 @@ pub enum BoundStatement {
     Query(Box<BoundQuery>),
 }
 
+impl BoundStatement {
+    /// The output schema of the `RETURNING` clause, or `None` if it is not a DML statement with
+    /// `RETURNING`.
+    pub fn returning_schema(&self) -> Option<&Schema> {
+        match self {
+            BoundStatement::Insert(insert) => insert.returning_schema.as_ref(),
+            BoundStatement::Delete(delete) => delete.returning_schema.as_ref(),
+            BoundStatement::Update(update) => update.returning_schema.as_ref(),
+            BoundStatement::Query(_) => None,
+        }
+    }
+
+    /// Replaces the parameters of a prepared statement with the literals of their values, so that
+    /// the plan is the same as the one of a plain statement. It's done on each execution of the
+    /// statement, which is only bound once when prepared.
+    pub fn bind_parameter_values(
+        &mut self,
+        param_types: &[DataType],
+        param_values: &[Option<String>],
+    ) -> Result<()> {
+        let values = param_types
+            .iter()
+            .enumerate()
+            .map(|(i, data_type)| {
+                let Some(value) = param_values.get(i) else {
+                    return Err(ErrorCode::BindError(format!(
+                        "there is no parameter ${}",
+                        i + 1
+                    ))
+                    .into());
+                };
+                let datum = match value {
+                    Some(value) => ExprImpl::literal_varchar(value.clone())
+                        .cast_explicit(data_type.clone())?
+                        .eval_row_const()?,
+                    None => None,
+                };
+                Ok(Literal::new(datum, data_type.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.rewrite_exprs(&mut ParameterValueRewriter { values });
+        Ok(())
+    }
+
+    fn rewrite_exprs(&mut self, rewriter: &mut impl ExprRewriter) {
+        match self {
+            BoundStatement::Insert(insert) => {
+                insert.source.rewrite_exprs(rewriter);
+                insert
+                    .cast_exprs
+                    .iter_mut()
+                    .chain(insert.returning_list.iter_mut())
+                    .for_each(|expr| rewrite_expr_in_place(expr, rewriter));
+            }
+            BoundStatement::Delete(delete) => delete
+                .selection
+                .iter_mut()
+                .chain(delete.returning_list.iter_mut())
+                .for_each(|expr| rewrite_expr_in_place(expr, rewriter)),
+            BoundStatement::Update(update) => {
+                update.table.rewrite_exprs(rewriter);
+                update
+                    .selection
+                    .iter_mut()
+                    .chain(update.exprs.iter_mut())
+                    .chain(update.returning_list.iter_mut())
+                    .for_each(|expr| rewrite_expr_in_place(expr, rewriter));
+            }
+            BoundStatement::Query(query) => query.rewrite_exprs(rewriter),
+        }
+    }
+}
+
+/// Replaces each parameter with the literal of its value, including the ones in subqueries.
+struct ParameterValueRewriter {
+    values: Vec<Literal>,
+}
+
+impl ExprRewriter for ParameterValueRewriter {
+    fn rewrite_parameter(&mut self, parameter: Parameter) -> ExprImpl {
+        self.values[parameter.index as usize - 1].clone().into()
+    }
+
+    fn rewrite_subquery(&mut self, mut subquery: Subquery) -> ExprImpl {
+        subquery.query.rewrite_exprs(self);
+        subquery.into()
+    }
+}
+
 impl Binder {
     pub(super) fn bind_statement(&mut self, stmt: Statement) -> Result<BoundStatement> {
         match stmt {
@@ -34,23 +126,28 @@ impl Binder {
                 table_name,
                 columns,
                 source,
+                returning,
             } => Ok(BoundStatement::Insert(
-                self.bind_insert(table_name, columns, *source)?.into(),
+                self.bind_insert(table_name, columns, *source, returning)?
+                    .into(),
             )),
 
             Statement::Delete {
                 table_name,
                 selection,
+                returning,
             } => Ok(BoundStatement::Delete(
-                self.bind_delete(table_name, selection)?.into(),
+                self.bind_delete(table_name, selection, returning)?.into(),
             )),
 
             Statement::Update {
                 table,
                 assignments,
                 selection,
+                returning,
             } => Ok(BoundStatement::Update(
-                self.bind_update(table, assignments, selection)?.into(),
+                self.bind_update(table, assignments, selection, returning)?
+                    .into(),
             )),
 
             Statement::Query(q) => Ok(BoundStatement::Query(self.bind_query(*q)?.into())),
@@ -16,14 +16,16 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
 use itertools::Itertools;
+use risingwave_common::catalog::Schema;
 use risingwave_common::ensure;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::{Assignment, Expr, TableFactor, TableWithJoins};
+use risingwave_pb::user::grant_privilege::Action;
+use risingwave_sqlparser::ast::{Assignment, Expr, SelectItem, TableFactor, TableWithJoins};
 
 use super::{Binder, BoundTableSource, Relation};
-use crate::expr::{Expr as _, ExprImpl};
+use crate::expr::{Expr as _, ExprImpl, ExprRewriter};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BoundUpdate {
     /// Used for injecting new chunks to the source.
     pub table_source: BoundTableSource,
@@ -36,6 +38,13 @@ pub struct BoundUpdate {
     /// Expression used to project to the updated row. The assigned columns will use the new
     /// expression, and the other columns will be simply `InputRef`.
     pub exprs: Vec<ExprImpl>,
+
+    /// Expressions of `RETURNING` over the updated rows with the new values, which have the
+    /// columns of `table`.
+    pub returning_list: Vec<ExprImpl>,
+
+    /// The output schema of `RETURNING`, or `None` if there is no `RETURNING`.
+    pub returning_schema: Option<Schema>,
 }
 
 impl Binder {
@@ -44,6 +53,7 @@ impl Binder {
         table: TableWithJoins,
         assignments: Vec<Assignment>,
         selection: Option<Expr>,
+        returning_items: Vec<SelectItem>,
     ) -> Result<BoundUpdate> {
         let table_source = {
             ensure!(table.joins.is_empty());
@@ -52,7 +62,7 @@ impl Binder {
                 _ => unreachable!(),
             };
             let (schema_name, name) = Self::resolve_schema_qualified_name(&self.db_name, name)?;
-            self.bind_table_source(schema_name.as_deref(), &name)?
+            self.bind_table_source(schema_name.as_deref(), &name, Action::Update)?
         };
 
         if table_source.append_only {
@@ -62,7 +72,18 @@ impl Binder {
             .into());
         }
 
+        // The rows to update are read from the table, which requires the `SELECT` privilege too.
         let table = self.bind_vec_table_with_joins(vec![table])?.unwrap();
+        // The stream of the table requires the update pairs to have the same primary key.
+        let pk_column_names = match &table {
+            Relation::BaseTable(table) => table
+                .table_catalog
+                .pk()
+                .iter()
+                .map(|order| table.table_catalog.columns[order.index].name().to_string())
+                .collect_vec(),
+            _ => unreachable!(),
+        };
 
         let selection = selection.map(|expr| self.bind_expr(expr)).transpose()?;
 
@@ -97,8 +118,28 @@ impl Binder {
             };
 
             for (id, value) in assignments {
+                let is_generated = table_source.columns.iter().any(|c| {
+                    c.name == id.real_value()
+                        && table_source.generated_column_ids.contains(&c.column_id)
+                });
+                if is_generated {
+                    return Err(ErrorCode::BindError(format!(
+                        "column \"{}\" can only be updated to DEFAULT",
+                        id.real_value()
+                    ))
+                    .into());
+                }
+                if pk_column_names.contains(&id.real_value()) {
+                    return Err(ErrorCode::NotImplemented(
+                        format!("update the primary key column \"{}\"", id.real_value()),
+                        None.into(),
+                    )
+                    .into());
+                }
+
                 let id_expr = self.bind_expr(Expr::Identifier(id.clone()))?;
                 let value_expr = self.bind_expr(value)?.cast_assign(id_expr.return_type())?;
+                let value_expr = self.session_timezone.rewrite_expr(value_expr);
 
                 match assignment_exprs.entry(id_expr) {
                     Entry::Occupied(_) => {
@@ -120,11 +161,20 @@ impl Binder {
             .map(|c| assignment_exprs.remove(&c).unwrap_or(c))
             .collect_vec();
 
+        let (returning_list, returning_schema) = if returning_items.is_empty() {
+            (vec![], None)
+        } else {
+            let (returning_list, fields) = self.bind_returning_list(returning_items)?;
+            (returning_list, Some(Schema { fields }))
+        };
+
         Ok(BoundUpdate {
             table_source,
             table,
             selection,
             exprs,
+            returning_list,
+            returning_schema,
         })
     }
 }
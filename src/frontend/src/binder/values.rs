@@ -19,8 +19,8 @@ use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::Values;
 
 use super::bind_context::Clause;
-use crate::binder::Binder;
-use crate::expr::{align_types, CorrelatedId, Depth, ExprImpl};
+use crate::binder::{rewrite_expr_in_place, Binder};
+use crate::expr::{align_types, CorrelatedInputRef, Depth, ExprImpl, ExprRewriter};
 
 #[derive(Debug, Clone)]
 pub struct BoundValues {
@@ -47,16 +47,18 @@ impl BoundValues {
             .any(|expr| expr.has_correlated_input_ref_by_depth())
     }
 
-    pub fn collect_correlated_indices_by_depth_and_assign_id(
+    pub fn visit_correlated_input_refs_by_depth(
         &mut self,
         depth: Depth,
-        correlated_id: CorrelatedId,
-    ) -> Vec<usize> {
+        f: &mut dyn FnMut(&mut CorrelatedInputRef),
+    ) {
         self.exprs_mut()
-            .flat_map(|expr| {
-                expr.collect_correlated_indices_by_depth_and_assign_id(depth, correlated_id)
-            })
-            .collect()
+            .for_each(|expr| expr.visit_correlated_input_refs_by_depth(depth, f))
+    }
+
+    pub fn rewrite_exprs(&mut self, rewriter: &mut impl ExprRewriter) {
+        self.exprs_mut()
+            .for_each(|expr| rewrite_expr_in_place(expr, rewriter))
     }
 }
 
@@ -94,14 +96,26 @@ impl Binder {
             Some(types) => {
                 bound = bound
                     .into_iter()
-                    .map(|vec| Self::cast_on_insert(types.clone(), vec))
+                    .map(|vec| self.cast_on_insert(types.clone(), vec))
                     .try_collect()?;
 
                 types
             }
-            None => (0..num_columns)
-                .map(|col_index| align_types(bound.iter_mut().map(|row| &mut row[col_index])))
-                .try_collect()?,
+            None => {
+                let types: Vec<DataType> = (0..num_columns)
+                    .map(|col_index| align_types(bound.iter_mut().map(|row| &mut row[col_index])))
+                    .try_collect()?;
+                // The casts added when aligning the types may depend on the session TimeZone.
+                bound = bound
+                    .into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .map(|e| self.session_timezone.rewrite_expr(e))
+                            .collect()
+                    })
+                    .collect();
+                types
+            }
         };
 
         let values_id = self.next_values_id();
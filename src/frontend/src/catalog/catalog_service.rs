@@ -16,19 +16,21 @@ use std::sync::Arc;
 
 use parking_lot::lock_api::ArcRwLockReadGuard;
 use parking_lot::{RawRwLock, RwLock};
-use risingwave_common::catalog::{CatalogVersion, IndexId, TableId};
+use risingwave_common::catalog::{CatalogVersion, ColumnId, IndexId, TableId};
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{Result, RwError};
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable, View as ProstView,
+    Database as ProstDatabase, Function as ProstFunction, Index as ProstIndex,
+    Schema as ProstSchema, Secret as ProstSecret, Sink as ProstSink, Source as ProstSource,
+    Table as ProstTable, TableStatistics as ProstTableStatistics, View as ProstView,
 };
+use risingwave_pb::ddl_service::ReplannedMview;
 use risingwave_pb::stream_plan::StreamFragmentGraph;
 use risingwave_rpc_client::MetaClient;
 use tokio::sync::watch::Receiver;
 
 use super::root_catalog::Catalog;
-use super::DatabaseId;
+use super::{DatabaseId, FunctionId, SecretId};
 use crate::user::UserId;
 
 pub type CatalogReadGuard = ArcRwLockReadGuard<RawRwLock, Catalog>;
@@ -87,6 +89,42 @@ pub trait CatalogWriter: Send + Sync {
 
     async fn create_sink(&self, sink: ProstSink, graph: StreamFragmentGraph) -> Result<()>;
 
+    async fn create_function(&self, function: ProstFunction) -> Result<()>;
+
+    async fn create_secret(&self, secret: ProstSecret) -> Result<()>;
+
+    /// Returns the materialized views among `select_star_mview_ids` flagged as stale, as they are
+    /// not updated with the columns added to the table by `mview_plans`.
+    async fn alter_table(
+        &self,
+        source: ProstSource,
+        table: ProstTable,
+        select_star_mview_ids: Vec<TableId>,
+        mview_plans: Vec<ReplannedMview>,
+    ) -> Result<Vec<TableId>>;
+
+    /// Like `alter_table`, the views in `mview_plans` are updated with the columns appended to the
+    /// source, which are the proposed ones. Returns the views flagged as stale.
+    async fn alter_source(
+        &self,
+        source: ProstSource,
+        select_star_mview_ids: Vec<TableId>,
+        mview_plans: Vec<ReplannedMview>,
+    ) -> Result<Vec<TableId>>;
+
+    async fn update_table_statistics(
+        &self,
+        table_id: TableId,
+        statistics: ProstTableStatistics,
+    ) -> Result<()>;
+
+    async fn comment_on(
+        &self,
+        table_id: TableId,
+        column_id: Option<ColumnId>,
+        comment: String,
+    ) -> Result<()>;
+
     async fn drop_materialized_source(&self, source_id: u32, table_id: TableId) -> Result<()>;
 
     async fn drop_materialized_view(&self, table_id: TableId) -> Result<()>;
@@ -97,6 +135,10 @@ pub trait CatalogWriter: Send + Sync {
 
     async fn drop_sink(&self, sink_id: u32) -> Result<()>;
 
+    async fn drop_function(&self, function_id: FunctionId) -> Result<()>;
+
+    async fn drop_secret(&self, secret_id: SecretId) -> Result<()>;
+
     async fn drop_database(&self, database_id: u32) -> Result<()>;
 
     async fn drop_schema(&self, schema_id: u32) -> Result<()>;
@@ -160,6 +202,16 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn create_function(&self, function: ProstFunction) -> Result<()> {
+        let (_, version) = self.meta_client.create_function(function).await?;
+        self.wait_version(version).await
+    }
+
+    async fn create_secret(&self, secret: ProstSecret) -> Result<()> {
+        let (_, version) = self.meta_client.create_secret(secret).await?;
+        self.wait_version(version).await
+    }
+
     async fn create_index(
         &self,
         index: ProstIndex,
@@ -193,6 +245,75 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn alter_table(
+        &self,
+        source: ProstSource,
+        table: ProstTable,
+        select_star_mview_ids: Vec<TableId>,
+        mview_plans: Vec<ReplannedMview>,
+    ) -> Result<Vec<TableId>> {
+        let (version, stale_mview_ids) = self
+            .meta_client
+            .alter_table(
+                source,
+                table,
+                select_star_mview_ids
+                    .into_iter()
+                    .map(|id| id.table_id)
+                    .collect(),
+                mview_plans,
+            )
+            .await?;
+        self.wait_version(version).await?;
+        Ok(stale_mview_ids.into_iter().map(TableId::new).collect())
+    }
+
+    async fn alter_source(
+        &self,
+        source: ProstSource,
+        select_star_mview_ids: Vec<TableId>,
+        mview_plans: Vec<ReplannedMview>,
+    ) -> Result<Vec<TableId>> {
+        let (version, stale_mview_ids) = self
+            .meta_client
+            .alter_source(
+                source,
+                select_star_mview_ids
+                    .into_iter()
+                    .map(|id| id.table_id)
+                    .collect(),
+                mview_plans,
+            )
+            .await?;
+        self.wait_version(version).await?;
+        Ok(stale_mview_ids.into_iter().map(TableId::new).collect())
+    }
+
+    async fn update_table_statistics(
+        &self,
+        table_id: TableId,
+        statistics: ProstTableStatistics,
+    ) -> Result<()> {
+        let version = self
+            .meta_client
+            .update_table_statistics(table_id.table_id, statistics)
+            .await?;
+        self.wait_version(version).await
+    }
+
+    async fn comment_on(
+        &self,
+        table_id: TableId,
+        column_id: Option<ColumnId>,
+        comment: String,
+    ) -> Result<()> {
+        let version = self
+            .meta_client
+            .comment_on(table_id.table_id, column_id.map(|id| id.get_id()), comment)
+            .await?;
+        self.wait_version(version).await
+    }
+
     async fn drop_materialized_source(&self, source_id: u32, table_id: TableId) -> Result<()> {
         let version = self
             .meta_client
@@ -211,6 +332,16 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
+    async fn drop_function(&self, function_id: FunctionId) -> Result<()> {
+        let version = self.meta_client.drop_function(function_id).await?;
+        self.wait_version(version).await
+    }
+
+    async fn drop_secret(&self, secret_id: SecretId) -> Result<()> {
+        let version = self.meta_client.drop_secret(secret_id).await?;
+        self.wait_version(version).await
+    }
+
     async fn drop_source(&self, source_id: u32) -> Result<()> {
         let version = self.meta_client.drop_source(source_id).await?;
         self.wait_version(version).await
@@ -15,7 +15,7 @@
 use std::collections::HashMap;
 
 use itertools::Itertools;
-use risingwave_common::catalog::PG_CATALOG_SCHEMA_NAME;
+use risingwave_common::catalog::is_system_schema;
 use risingwave_pb::catalog::{Database as ProstDatabase, Schema as ProstSchema};
 
 use crate::catalog::schema_catalog::SchemaCatalog;
@@ -24,7 +24,6 @@ use crate::catalog::{DatabaseId, SchemaId};
 #[derive(Clone, Debug)]
 pub struct DatabaseCatalog {
     id: DatabaseId,
-    #[expect(dead_code)]
     name: String,
     schema_by_name: HashMap<String, SchemaCatalog>,
     schema_name_by_id: HashMap<SchemaId, String>,
@@ -83,13 +82,19 @@ impl DatabaseCatalog {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.schema_by_name.len() == 1 && self.schema_by_name.contains_key(PG_CATALOG_SCHEMA_NAME)
+        self.schema_by_name
+            .keys()
+            .all(|schema_name| is_system_schema(schema_name))
     }
 
     pub fn id(&self) -> DatabaseId {
         self.id
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub fn owner(&self) -> u32 {
         self.owner
     }
@@ -0,0 +1,36 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+
+use crate::catalog::pg_catalog::PgCatalogColumnsDef;
+
+/// The view `columns` contains information about all visible columns of the relations in
+/// `tables`.
+/// Ref: [`https://www.postgresql.org/docs/current/infoschema-columns.html`]
+pub const INFORMATION_SCHEMA_COLUMNS_TABLE_NAME: &str = "columns";
+pub const INFORMATION_SCHEMA_COLUMNS_COLUMNS: &[PgCatalogColumnsDef<'_>] = &[
+    (DataType::Varchar, "table_catalog"),
+    (DataType::Varchar, "table_schema"),
+    (DataType::Varchar, "table_name"),
+    (DataType::Varchar, "column_name"),
+    (DataType::Int32, "ordinal_position"),
+    (DataType::Varchar, "column_default"),
+    (DataType::Varchar, "is_nullable"),
+    // The SQL standard name of the type, or `ARRAY` and `USER-DEFINED` for list and struct types.
+    (DataType::Varchar, "data_type"),
+    (DataType::Varchar, "udt_schema"),
+    // The name of the type in `pg_type`.
+    (DataType::Varchar, "udt_name"),
+];
@@ -0,0 +1,46 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod columns;
+pub mod tables;
+pub mod views;
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use risingwave_common::catalog::{ColumnDesc, TableId, DEFAULT_SUPER_USER_ID};
+
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::catalog::information_schema::columns::*;
+use crate::catalog::information_schema::tables::*;
+use crate::catalog::information_schema::views::*;
+use crate::catalog::pg_catalog::def_sys_catalog;
+use crate::catalog::system_catalog::SystemCatalog;
+
+/// `INFORMATION_SCHEMA_MAP` includes all catalogs in the `information_schema` schema. The ids
+/// start from 201 to avoid conflicts with `pg_catalog` and `rw_catalog`. If you added a new
+/// catalog, be sure to add a corresponding entry here.
+pub(crate) static INFORMATION_SCHEMA_MAP: LazyLock<HashMap<String, SystemCatalog>> = LazyLock::new(
+    || {
+        maplit::hashmap! {
+            INFORMATION_SCHEMA_TABLES_TABLE_NAME.to_string() => def_sys_catalog!(201, INFORMATION_SCHEMA_TABLES_TABLE_NAME, INFORMATION_SCHEMA_TABLES_COLUMNS),
+            INFORMATION_SCHEMA_COLUMNS_TABLE_NAME.to_string() => def_sys_catalog!(202, INFORMATION_SCHEMA_COLUMNS_TABLE_NAME, INFORMATION_SCHEMA_COLUMNS_COLUMNS),
+            INFORMATION_SCHEMA_VIEWS_TABLE_NAME.to_string() => def_sys_catalog!(203, INFORMATION_SCHEMA_VIEWS_TABLE_NAME, INFORMATION_SCHEMA_VIEWS_COLUMNS),
+        }
+    },
+);
+
+pub fn get_all_information_schema_catalogs() -> Vec<SystemCatalog> {
+    INFORMATION_SCHEMA_MAP.values().cloned().collect()
+}
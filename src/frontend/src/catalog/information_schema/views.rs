@@ -0,0 +1,30 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+
+use crate::catalog::pg_catalog::PgCatalogColumnsDef;
+
+/// The view `views` contains all views and materialized views defined in the current database.
+/// Ref: [`https://www.postgresql.org/docs/current/infoschema-views.html`]
+pub const INFORMATION_SCHEMA_VIEWS_TABLE_NAME: &str = "views";
+pub const INFORMATION_SCHEMA_VIEWS_COLUMNS: &[PgCatalogColumnsDef<'_>] = &[
+    (DataType::Varchar, "table_catalog"),
+    (DataType::Varchar, "table_schema"),
+    (DataType::Varchar, "table_name"),
+    (DataType::Varchar, "view_definition"),
+    (DataType::Varchar, "check_option"),
+    (DataType::Varchar, "is_updatable"),
+    (DataType::Varchar, "is_insertable_into"),
+];
@@ -18,7 +18,7 @@
 //! structs. It is accessed via [`catalog_service::CatalogReader`] and
 //! [`catalog_service::CatalogWriter`], which is held by [`crate::session::FrontendEnv`].
 
-use risingwave_common::catalog::{ColumnDesc, PG_CATALOG_SCHEMA_NAME};
+use risingwave_common::catalog::{is_system_schema, ColumnDesc};
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::types::DataType;
 use thiserror::Error;
@@ -26,14 +26,19 @@ pub(crate) mod catalog_service;
 
 pub(crate) mod column_catalog;
 pub(crate) mod database_catalog;
+pub(crate) mod function_catalog;
 pub(crate) mod index_catalog;
+pub(crate) mod information_schema;
 pub(crate) mod pg_catalog;
 pub(crate) mod root_catalog;
+pub(crate) mod rw_catalog;
 pub(crate) mod schema_catalog;
+pub(crate) mod secret_catalog;
 pub(crate) mod sink_catalog;
 pub(crate) mod source_catalog;
 pub(crate) mod system_catalog;
 pub(crate) mod table_catalog;
+pub(crate) mod table_statistics;
 pub(crate) mod view_catalog;
 
 pub use index_catalog::IndexCatalog;
@@ -42,6 +47,8 @@ pub use table_catalog::TableCatalog;
 pub(crate) type SourceId = u32;
 pub(crate) type SinkId = u32;
 pub(crate) type ViewId = u32;
+pub(crate) type FunctionId = u32;
+pub(crate) type SecretId = u32;
 pub(crate) type DatabaseId = u32;
 pub(crate) type SchemaId = u32;
 pub(crate) type TableId = risingwave_common::catalog::TableId;
@@ -56,6 +63,12 @@ pub fn check_valid_column_name(column_name: &str) -> Result<()> {
             ROWID_PREFIX
         ))
         .into())
+    } else if column_name.starts_with(DROPPED_COLUMN_PREFIX) {
+        Err(ErrorCode::InternalError(format!(
+            "column name prefixed with {:?} are reserved word.",
+            DROPPED_COLUMN_PREFIX
+        ))
+        .into())
     } else {
         Ok(())
     }
@@ -63,7 +76,7 @@ pub fn check_valid_column_name(column_name: &str) -> Result<()> {
 
 /// Check if modifications happen to system catalog.
 pub fn check_schema_writable(schema: &str) -> Result<()> {
-    if schema == PG_CATALOG_SCHEMA_NAME {
+    if is_system_schema(schema) {
         Err(ErrorCode::ProtocolError(format!(
             "permission denied to write on \"{}\", System catalog modifications are currently disallowed.",
             schema
@@ -83,6 +96,14 @@ pub fn is_row_id_column_name(name: &str) -> bool {
     name.starts_with(ROWID_PREFIX)
 }
 
+const DROPPED_COLUMN_PREFIX: &str = "_rw_dropped_";
+
+/// The name of a column dropped by `ALTER TABLE`. Dropped columns are kept in the table as hidden
+/// columns, and renamed so that a new column can reuse the name.
+pub fn dropped_column_name(column_id: ColumnId) -> String {
+    format!("{}{}", DROPPED_COLUMN_PREFIX, column_id.get_id())
+}
+
 /// Creates a row ID column (for implicit primary key).
 pub fn row_id_column_desc(column_id: ColumnId) -> ColumnDesc {
     ColumnDesc {
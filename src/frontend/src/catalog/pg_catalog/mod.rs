@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod pg_attribute;
 pub mod pg_cast;
 pub mod pg_class;
+pub mod pg_description;
 pub mod pg_index;
 pub mod pg_matviews_info;
 pub mod pg_namespace;
@@ -21,13 +23,16 @@ pub mod pg_opclass;
 pub mod pg_type;
 pub mod pg_user;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, LazyLock};
 
 use async_trait::async_trait;
 use itertools::Itertools;
 use risingwave_common::array::Row;
-use risingwave_common::catalog::{ColumnDesc, SysCatalogReader, TableId, DEFAULT_SUPER_USER_ID};
+use risingwave_common::catalog::{
+    internal_table_type, ColumnDesc, SysCatalogReader, TableId, DEFAULT_SUPER_USER_ID,
+    PG_CATALOG_SCHEMA_NAME,
+};
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_pb::user::grant_privilege::{Action, Object};
@@ -36,15 +41,23 @@ use serde_json::json;
 
 use crate::catalog::catalog_service::CatalogReader;
 use crate::catalog::column_catalog::ColumnCatalog;
+use crate::catalog::information_schema::columns::*;
+use crate::catalog::information_schema::tables::*;
+use crate::catalog::information_schema::views::*;
+use crate::catalog::pg_catalog::pg_attribute::*;
 use crate::catalog::pg_catalog::pg_cast::*;
 use crate::catalog::pg_catalog::pg_class::*;
+use crate::catalog::pg_catalog::pg_description::*;
 use crate::catalog::pg_catalog::pg_index::*;
 use crate::catalog::pg_catalog::pg_matviews_info::*;
 use crate::catalog::pg_catalog::pg_namespace::*;
 use crate::catalog::pg_catalog::pg_opclass::*;
 use crate::catalog::pg_catalog::pg_type::*;
 use crate::catalog::pg_catalog::pg_user::*;
+use crate::catalog::rw_catalog::rw_internal_tables::*;
 use crate::catalog::system_catalog::SystemCatalog;
+use crate::catalog::table_catalog::TableCatalog;
+use crate::handler::util::data_type_to_type_oid;
 use crate::meta_client::FrontendMetaClient;
 use crate::scheduler::worker_node_manager::WorkerNodeManagerRef;
 use crate::session::AuthContext;
@@ -95,6 +108,12 @@ impl SysCatalogReader for SysCatalogReaderImpl {
             PG_CLASS_TABLE_NAME => self.read_class_info(),
             PG_INDEX_TABLE_NAME => self.read_index_info(),
             PG_OPCLASS_TABLE_NAME => self.read_opclass_info(),
+            PG_ATTRIBUTE_TABLE_NAME => self.read_attribute_info(),
+            PG_DESCRIPTION_TABLE_NAME => self.read_description_info(),
+            RW_INTERNAL_TABLES_TABLE_NAME => self.read_internal_tables().await,
+            INFORMATION_SCHEMA_TABLES_TABLE_NAME => self.read_information_schema_tables(),
+            INFORMATION_SCHEMA_COLUMNS_TABLE_NAME => self.read_information_schema_columns(),
+            INFORMATION_SCHEMA_VIEWS_TABLE_NAME => self.read_information_schema_views(),
             _ => {
                 Err(ErrorCode::ItemNotFound(format!("Invalid system table: {}", table_name)).into())
             }
@@ -168,6 +187,132 @@ fn get_acl_items(
     res.push('}');
     res
 }
+
+/// Builds a row of `pg_class`. The columns not tracked by the catalog are filled with the
+/// defaults of an ordinary permanent relation.
+fn pg_class_row(
+    oid: u32,
+    name: &str,
+    namespace: u32,
+    owner: u32,
+    kind: &str,
+    acl: Option<String>,
+    has_index: bool,
+) -> Row {
+    Row::new(vec![
+        Some(ScalarImpl::Int32(oid as i32)),
+        Some(ScalarImpl::Utf8(name.to_string())),
+        Some(ScalarImpl::Int32(namespace as i32)),
+        Some(ScalarImpl::Int32(owner as i32)),
+        Some(ScalarImpl::Utf8(kind.to_string())),
+        acl.map(ScalarImpl::Utf8),
+        Some(ScalarImpl::Bool(has_index)),
+        Some(ScalarImpl::Int16(0)),
+        Some(ScalarImpl::Bool(false)),
+        Some(ScalarImpl::Bool(false)),
+        Some(ScalarImpl::Bool(false)),
+        Some(ScalarImpl::Bool(false)),
+        Some(ScalarImpl::Bool(false)),
+        Some(ScalarImpl::Int32(0)),
+        Some(ScalarImpl::Int32(0)),
+        Some(ScalarImpl::Utf8("p".to_string())),
+        Some(ScalarImpl::Utf8("d".to_string())),
+    ])
+}
+
+/// Returns the `attnum` of the `idx`-th column of the table, or `None` if the column is hidden.
+fn attnum_of(table: &TableCatalog, idx: usize) -> Option<i16> {
+    let columns = table.columns();
+    if columns[idx].is_hidden() {
+        return None;
+    }
+    Some(columns[..idx].iter().filter(|c| !c.is_hidden()).count() as i16 + 1)
+}
+
+/// Builds the `pg_attribute` rows of the visible columns of a relation.
+fn pg_attribute_rows(relid: u32, columns: &[ColumnCatalog]) -> Vec<Row> {
+    columns
+        .iter()
+        .filter(|column| !column.is_hidden())
+        .enumerate()
+        .map(|(idx, column)| {
+            let type_oid = data_type_to_type_oid(column.data_type().clone()).as_number();
+            let type_len = PG_TYPE_DATA
+                .iter()
+                .find(|(oid, ..)| *oid == type_oid)
+                .map_or(-1, |(.., len)| *len);
+            Row::new(vec![
+                Some(ScalarImpl::Int32(relid as i32)),
+                Some(ScalarImpl::Utf8(column.name().to_string())),
+                Some(ScalarImpl::Int32(type_oid)),
+                Some(ScalarImpl::Int16(type_len)),
+                Some(ScalarImpl::Int16(idx as i16 + 1)),
+                Some(ScalarImpl::Bool(false)),
+                Some(ScalarImpl::Bool(false)),
+                Some(ScalarImpl::Int32(-1)),
+                Some(ScalarImpl::Utf8("".to_string())),
+                Some(ScalarImpl::Utf8("".to_string())),
+            ])
+        })
+        .collect_vec()
+}
+
+/// Returns the `udt_name` of the type in `information_schema.columns`, which is the `typname` in
+/// `pg_type`, prefixed with `_` for array types.
+fn udt_name(data_type: &DataType) -> Option<String> {
+    match data_type {
+        DataType::Struct(_) => None,
+        DataType::List { datatype } => udt_name(datatype).map(|name| format!("_{}", name)),
+        _ => {
+            let type_oid = data_type_to_type_oid(data_type.clone()).as_number();
+            PG_TYPE_DATA
+                .iter()
+                .find(|(oid, ..)| *oid == type_oid)
+                .map(|(_, name, ..)| name.to_string())
+        }
+    }
+}
+
+/// Builds the `information_schema.columns` rows of a relation from its visible columns.
+fn information_schema_column_rows<'a>(
+    database: &str,
+    schema: &str,
+    relation: &str,
+    columns: impl Iterator<Item = (&'a str, &'a DataType)>,
+) -> Vec<Row> {
+    columns
+        .enumerate()
+        .map(|(idx, (name, data_type))| {
+            let standard_type_name = match data_type {
+                DataType::Varchar => "character varying".to_string(),
+                DataType::List { .. } => "ARRAY".to_string(),
+                DataType::Struct(_) => "USER-DEFINED".to_string(),
+                _ => data_type.to_string(),
+            };
+            Row::new(vec![
+                Some(ScalarImpl::Utf8(database.to_string())),
+                Some(ScalarImpl::Utf8(schema.to_string())),
+                Some(ScalarImpl::Utf8(relation.to_string())),
+                Some(ScalarImpl::Utf8(name.to_string())),
+                Some(ScalarImpl::Int32(idx as i32 + 1)),
+                None,
+                Some(ScalarImpl::Utf8("YES".to_string())),
+                Some(ScalarImpl::Utf8(standard_type_name)),
+                Some(ScalarImpl::Utf8(PG_CATALOG_SCHEMA_NAME.to_string())),
+                udt_name(data_type).map(ScalarImpl::Utf8),
+            ])
+        })
+        .collect_vec()
+}
+
+/// Returns the name and type of the visible columns.
+fn visible_columns(columns: &[ColumnCatalog]) -> impl Iterator<Item = (&str, &DataType)> {
+    columns
+        .iter()
+        .filter(|column| !column.is_hidden())
+        .map(|column| (column.name(), column.data_type()))
+}
+
 impl SysCatalogReaderImpl {
     fn read_namespace(&self) -> Result<Vec<Row>> {
         let schemas = self
@@ -221,72 +366,102 @@ impl SysCatalogReaderImpl {
         let reader = self.catalog_reader.read_guard();
         let schemas = reader.iter_schemas(&self.auth_context.database)?;
         let schema_infos = reader.get_all_schema_info(&self.auth_context.database)?;
+        let user_reader = self.user_info_reader.read_guard();
+        let users = user_reader.get_all_users();
+        let username_map = user_reader.get_user_name_map();
 
         Ok(schemas
             .zip_eq(schema_infos.iter())
             .flat_map(|(schema, schema_info)| {
+                let indexed_tables: HashSet<_> = schema
+                    .iter_index()
+                    .map(|index| index.primary_table.id)
+                    .collect();
+
                 let rows = schema
                     .iter_table()
                     .map(|table| {
-                        Row::new(vec![
-                            Some(ScalarImpl::Int32(table.id.table_id() as i32)),
-                            Some(ScalarImpl::Utf8(table.name.clone())),
-                            Some(ScalarImpl::Int32(schema_info.id as i32)),
-                            Some(ScalarImpl::Int32(table.owner as i32)),
-                            Some(ScalarImpl::Utf8("r".to_string())),
-                        ])
+                        pg_class_row(
+                            table.id.table_id(),
+                            &table.name,
+                            schema_info.id,
+                            table.owner,
+                            "r",
+                            Some(get_acl_items(
+                                &Object::TableId(table.id.table_id()),
+                                &users,
+                                username_map,
+                            )),
+                            indexed_tables.contains(&table.id),
+                        )
                     })
                     .collect_vec();
 
                 let mvs = schema
                     .iter_mv()
                     .map(|mv| {
-                        Row::new(vec![
-                            Some(ScalarImpl::Int32(mv.id.table_id() as i32)),
-                            Some(ScalarImpl::Utf8(mv.name.clone())),
-                            Some(ScalarImpl::Int32(schema_info.id as i32)),
-                            Some(ScalarImpl::Int32(mv.owner as i32)),
-                            Some(ScalarImpl::Utf8("m".to_string())),
-                        ])
+                        pg_class_row(
+                            mv.id.table_id(),
+                            &mv.name,
+                            schema_info.id,
+                            mv.owner,
+                            "m",
+                            Some(get_acl_items(
+                                &Object::TableId(mv.id.table_id()),
+                                &users,
+                                username_map,
+                            )),
+                            indexed_tables.contains(&mv.id),
+                        )
                     })
                     .collect_vec();
 
                 let indexes = schema
                     .iter_index()
                     .map(|index| {
-                        Row::new(vec![
-                            Some(ScalarImpl::Int32(index.index_table.id.table_id as i32)),
-                            Some(ScalarImpl::Utf8(index.name.clone())),
-                            Some(ScalarImpl::Int32(schema_info.id as i32)),
-                            Some(ScalarImpl::Int32(index.index_table.owner as i32)),
-                            Some(ScalarImpl::Utf8("i".to_string())),
-                        ])
+                        pg_class_row(
+                            index.index_table.id.table_id,
+                            &index.name,
+                            schema_info.id,
+                            index.index_table.owner,
+                            "i",
+                            None,
+                            false,
+                        )
                     })
                     .collect_vec();
 
                 let sources = schema
                     .iter_source()
                     .map(|source| {
-                        Row::new(vec![
-                            Some(ScalarImpl::Int32(source.id as i32)),
-                            Some(ScalarImpl::Utf8(source.name.clone())),
-                            Some(ScalarImpl::Int32(schema_info.id as i32)),
-                            Some(ScalarImpl::Int32(source.owner as i32)),
-                            Some(ScalarImpl::Utf8("x".to_string())),
-                        ])
+                        pg_class_row(
+                            source.id,
+                            &source.name,
+                            schema_info.id,
+                            source.owner,
+                            "x",
+                            Some(get_acl_items(
+                                &Object::SourceId(source.id),
+                                &users,
+                                username_map,
+                            )),
+                            false,
+                        )
                     })
                     .collect_vec();
 
                 let sys_tables = schema
                     .iter_system_tables()
                     .map(|table| {
-                        Row::new(vec![
-                            Some(ScalarImpl::Int32(table.id.table_id() as i32)),
-                            Some(ScalarImpl::Utf8(table.name.clone())),
-                            Some(ScalarImpl::Int32(schema_info.id as i32)),
-                            Some(ScalarImpl::Int32(table.owner as i32)),
-                            Some(ScalarImpl::Utf8("r".to_string())),
-                        ])
+                        pg_class_row(
+                            table.id.table_id(),
+                            &table.name,
+                            schema_info.id,
+                            table.owner,
+                            "r",
+                            None,
+                            false,
+                        )
                     })
                     .collect_vec();
 
@@ -311,12 +486,222 @@ impl SysCatalogReaderImpl {
                         Some(ScalarImpl::Int32(index.id.index_id() as i32)),
                         Some(ScalarImpl::Int32(index.primary_table.id.table_id() as i32)),
                         Some(ScalarImpl::Int16(index.index_item.len() as i16)),
+                        Some(ScalarImpl::Bool(false)),
+                        Some(ScalarImpl::Bool(false)),
+                        Some(ScalarImpl::Utf8(
+                            index
+                                .index_item
+                                .iter()
+                                .filter_map(|input_ref| {
+                                    attnum_of(&index.primary_table, input_ref.index())
+                                })
+                                .join(" "),
+                        )),
                     ])
                 })
             })
             .collect_vec())
     }
 
+    fn read_attribute_info(&self) -> Result<Vec<Row>> {
+        let reader = self.catalog_reader.read_guard();
+        let schemas = reader.iter_schemas(&self.auth_context.database)?;
+
+        Ok(schemas
+            .flat_map(|schema| {
+                let tables = schema
+                    .iter_table()
+                    .chain(schema.iter_mv())
+                    .flat_map(|table| pg_attribute_rows(table.id.table_id(), table.columns()));
+                let indexes = schema.iter_index().flat_map(|index| {
+                    pg_attribute_rows(index.index_table.id.table_id(), index.index_table.columns())
+                });
+                let sources = schema
+                    .iter_source()
+                    .flat_map(|source| pg_attribute_rows(source.id, &source.columns));
+                let sys_tables = schema
+                    .iter_system_tables()
+                    .flat_map(|table| pg_attribute_rows(table.id.table_id(), table.columns()));
+
+                tables
+                    .chain(indexes)
+                    .chain(sources)
+                    .chain(sys_tables)
+                    .collect_vec()
+            })
+            .collect_vec())
+    }
+
+    /// Reads the comments on the tables and materialized views, and on their visible columns.
+    fn read_description_info(&self) -> Result<Vec<Row>> {
+        let reader = self.catalog_reader.read_guard();
+        let schemas = reader.iter_schemas(&self.auth_context.database)?;
+
+        let row = |objoid: u32, objsubid: i16, description: &str| {
+            Row::new(vec![
+                Some(ScalarImpl::Int32(objoid as i32)),
+                Some(ScalarImpl::Int32(PG_CLASS_OID)),
+                Some(ScalarImpl::Int32(objsubid as i32)),
+                Some(ScalarImpl::Utf8(description.to_string())),
+            ])
+        };
+
+        Ok(schemas
+            .flat_map(|schema| {
+                schema
+                    .iter_table()
+                    .chain(schema.iter_mv())
+                    .flat_map(|table| {
+                        let table_row = table
+                            .description
+                            .as_ref()
+                            .map(|description| row(table.id.table_id(), 0, description));
+                        let column_rows =
+                            table
+                                .columns()
+                                .iter()
+                                .enumerate()
+                                .filter_map(|(idx, column)| {
+                                    let description =
+                                        table.column_descriptions.get(&column.column_id())?;
+                                    let attnum = attnum_of(table, idx)?;
+                                    Some(row(table.id.table_id(), attnum, description))
+                                });
+                        table_row.into_iter().chain(column_rows).collect_vec()
+                    })
+                    .collect_vec()
+            })
+            .collect_vec())
+    }
+
+    fn read_information_schema_tables(&self) -> Result<Vec<Row>> {
+        let database = &self.auth_context.database;
+        let reader = self.catalog_reader.read_guard();
+        let schemas = reader.iter_schemas(database)?;
+
+        Ok(schemas
+            .flat_map(|schema| {
+                let schema_name = schema.name();
+                let row = |name: &str, table_type: &str, is_insertable_into: bool| {
+                    Row::new(vec![
+                        Some(ScalarImpl::Utf8(database.clone())),
+                        Some(ScalarImpl::Utf8(schema_name.clone())),
+                        Some(ScalarImpl::Utf8(name.to_string())),
+                        Some(ScalarImpl::Utf8(table_type.to_string())),
+                        Some(ScalarImpl::Utf8(
+                            if is_insertable_into { "YES" } else { "NO" }.to_string(),
+                        )),
+                    ])
+                };
+
+                schema
+                    .iter_table()
+                    .map(|table| row(&table.name, "BASE TABLE", true))
+                    .chain(schema.iter_mv().map(|mv| row(&mv.name, "VIEW", false)))
+                    .chain(
+                        schema
+                            .iter_view()
+                            .map(|view| row(&view.name, "VIEW", false)),
+                    )
+                    .chain(
+                        schema
+                            .iter_source()
+                            .map(|source| row(&source.name, "FOREIGN", false)),
+                    )
+                    .chain(
+                        schema
+                            .iter_system_tables()
+                            .map(|table| row(&table.name, "BASE TABLE", false)),
+                    )
+                    .collect_vec()
+            })
+            .collect_vec())
+    }
+
+    fn read_information_schema_columns(&self) -> Result<Vec<Row>> {
+        let database = &self.auth_context.database;
+        let reader = self.catalog_reader.read_guard();
+        let schemas = reader.iter_schemas(database)?;
+
+        Ok(schemas
+            .flat_map(|schema| {
+                let schema_name = schema.name();
+                let tables = schema
+                    .iter_table()
+                    .chain(schema.iter_mv())
+                    .flat_map(|table| {
+                        information_schema_column_rows(
+                            database,
+                            &schema_name,
+                            &table.name,
+                            visible_columns(table.columns()),
+                        )
+                    });
+                let views = schema.iter_view().flat_map(|view| {
+                    information_schema_column_rows(
+                        database,
+                        &schema_name,
+                        &view.name,
+                        view.columns
+                            .iter()
+                            .map(|field| (field.name.as_str(), &field.data_type)),
+                    )
+                });
+                let sources = schema.iter_source().flat_map(|source| {
+                    information_schema_column_rows(
+                        database,
+                        &schema_name,
+                        &source.name,
+                        visible_columns(&source.columns),
+                    )
+                });
+                let sys_tables = schema.iter_system_tables().flat_map(|table| {
+                    information_schema_column_rows(
+                        database,
+                        &schema_name,
+                        &table.name,
+                        visible_columns(table.columns()),
+                    )
+                });
+
+                tables
+                    .chain(views)
+                    .chain(sources)
+                    .chain(sys_tables)
+                    .collect_vec()
+            })
+            .collect_vec())
+    }
+
+    fn read_information_schema_views(&self) -> Result<Vec<Row>> {
+        let database = &self.auth_context.database;
+        let reader = self.catalog_reader.read_guard();
+        let schemas = reader.iter_schemas(database)?;
+
+        Ok(schemas
+            .flat_map(|schema| {
+                let schema_name = schema.name();
+                let row = |name: &str, definition: &str| {
+                    Row::new(vec![
+                        Some(ScalarImpl::Utf8(database.clone())),
+                        Some(ScalarImpl::Utf8(schema_name.clone())),
+                        Some(ScalarImpl::Utf8(name.to_string())),
+                        Some(ScalarImpl::Utf8(definition.to_string())),
+                        Some(ScalarImpl::Utf8("NONE".to_string())),
+                        Some(ScalarImpl::Utf8("NO".to_string())),
+                        Some(ScalarImpl::Utf8("NO".to_string())),
+                    ])
+                };
+
+                schema
+                    .iter_mv()
+                    .map(|mv| row(&mv.name, &mv.definition))
+                    .chain(schema.iter_view().map(|view| row(&view.name, &view.sql)))
+                    .collect_vec()
+            })
+            .collect_vec())
+    }
+
     async fn read_mviews_info(&self) -> Result<Vec<Row>> {
         let mut table_ids = Vec::new();
         {
@@ -349,6 +734,7 @@ impl SysCatalogReaderImpl {
                             Some(ScalarImpl::Int32(t.owner as i32)),
                             Some(ScalarImpl::Utf8(json!(fragments).to_string())),
                             Some(ScalarImpl::Utf8(t.definition.clone())),
+                            Some(ScalarImpl::Bool(t.schema_stale)),
                         ]));
                     }
                 });
@@ -356,10 +742,89 @@ impl SysCatalogReaderImpl {
 
         Ok(rows)
     }
+
+    async fn read_internal_tables(&self) -> Result<Vec<Row>> {
+        let is_super = self
+            .user_info_reader
+            .read_guard()
+            .get_user_by_name(&self.auth_context.user_name)
+            .map_or(false, |user| user.is_super);
+        if !is_super {
+            return Err(ErrorCode::PermissionDenied(format!(
+                "must be superuser to read {}",
+                RW_INTERNAL_TABLES_TABLE_NAME
+            ))
+            .into());
+        }
+
+        // Collect all streaming jobs, which are the owners of the internal tables.
+        let mut job_names = HashMap::new();
+        {
+            let reader = self.catalog_reader.read_guard();
+            for schema in reader.iter_schemas(&self.auth_context.database)? {
+                schema.iter_mv().chain(schema.iter_table()).for_each(|t| {
+                    job_names.insert(t.id.table_id, t.name.clone());
+                });
+                schema.iter_index().for_each(|index| {
+                    job_names.insert(index.index_table.id.table_id, index.name.clone());
+                });
+                schema.iter_materialized_source().for_each(|source| {
+                    job_names.insert(source.id, source.name.clone());
+                });
+                schema.iter_sink().for_each(|sink| {
+                    job_names.insert(sink.id, sink.name.clone());
+                });
+            }
+        }
+
+        let job_ids = job_names.keys().copied().collect_vec();
+        let table_fragments = self.meta_client.list_table_fragments(&job_ids).await?;
+        let job_id_by_fragment: HashMap<u32, u32> = table_fragments
+            .iter()
+            .flat_map(|(job_id, info)| {
+                info.fragments
+                    .iter()
+                    .map(move |fragment| (fragment.id, *job_id))
+            })
+            .collect();
+        let table_sizes = self.meta_client.get_table_sizes().await?;
+
+        let reader = self.catalog_reader.read_guard();
+        Ok(reader
+            .iter_schemas(&self.auth_context.database)?
+            .flat_map(|schema| {
+                schema
+                    .iter_internal_table()
+                    .map(|table| {
+                        let job_id = job_id_by_fragment.get(&table.fragment_id);
+                        Row::new(vec![
+                            Some(ScalarImpl::Int32(table.id.table_id as i32)),
+                            Some(ScalarImpl::Utf8(table.name.clone())),
+                            Some(ScalarImpl::Utf8(schema.name())),
+                            job_id.map(|id| ScalarImpl::Int32(*id as i32)),
+                            job_id
+                                .and_then(|id| job_names.get(id))
+                                .map(|name| ScalarImpl::Utf8(name.clone())),
+                            internal_table_type(&table.name)
+                                .map(|table_type| ScalarImpl::Utf8(table_type.to_string())),
+                            Some(ScalarImpl::Int32(table.fragment_id as i32)),
+                            Some(ScalarImpl::Int32(table.owner as i32)),
+                            Some(ScalarImpl::Int64(
+                                table_sizes
+                                    .get(&table.id.table_id)
+                                    .copied()
+                                    .unwrap_or_default() as i64,
+                            )),
+                        ])
+                    })
+                    .collect_vec()
+            })
+            .collect_vec())
+    }
 }
 
 // TODO: support struct column and type name when necessary.
-type PgCatalogColumnsDef<'a> = (DataType, &'a str);
+pub(crate) type PgCatalogColumnsDef<'a> = (DataType, &'a str);
 
 /// `def_sys_catalog` defines a table with given id, name and columns.
 macro_rules! def_sys_catalog {
@@ -387,6 +852,8 @@ macro_rules! def_sys_catalog {
     };
 }
 
+pub(crate) use def_sys_catalog;
+
 /// `PG_CATALOG_MAP` includes all system catalogs. If you added a new system catalog, be
 /// sure to add a corresponding entry here.
 pub(crate) static PG_CATALOG_MAP: LazyLock<HashMap<String, SystemCatalog>> = LazyLock::new(|| {
@@ -399,6 +866,8 @@ pub(crate) static PG_CATALOG_MAP: LazyLock<HashMap<String, SystemCatalog>> = Laz
         PG_CLASS_TABLE_NAME.to_string() => def_sys_catalog!(6, PG_CLASS_TABLE_NAME, PG_CLASS_COLUMNS),
         PG_INDEX_TABLE_NAME.to_string() => def_sys_catalog!(7, PG_INDEX_TABLE_NAME, PG_INDEX_COLUMNS),
         PG_OPCLASS_TABLE_NAME.to_string() => def_sys_catalog!(8, PG_OPCLASS_TABLE_NAME, PG_OPCLASS_COLUMNS),
+        PG_ATTRIBUTE_TABLE_NAME.to_string() => def_sys_catalog!(9, PG_ATTRIBUTE_TABLE_NAME, PG_ATTRIBUTE_COLUMNS),
+        PG_DESCRIPTION_TABLE_NAME.to_string() => def_sys_catalog!(10, PG_DESCRIPTION_TABLE_NAME, PG_DESCRIPTION_COLUMNS),
     }
 });
 
@@ -25,4 +25,16 @@ pub const PG_CLASS_COLUMNS: &[PgCatalogColumnsDef<'_>] = &[
     (DataType::Int32, "relnamespace"),
     (DataType::Int32, "relowner"),
     (DataType::Varchar, "relkind"),
+    (DataType::Varchar, "relacl"),
+    (DataType::Boolean, "relhasindex"),
+    (DataType::Int16, "relchecks"),
+    (DataType::Boolean, "relhasrules"),
+    (DataType::Boolean, "relhastriggers"),
+    (DataType::Boolean, "relrowsecurity"),
+    (DataType::Boolean, "relforcerowsecurity"),
+    (DataType::Boolean, "relispartition"),
+    (DataType::Int32, "reltablespace"),
+    (DataType::Int32, "reloftype"),
+    (DataType::Varchar, "relpersistence"),
+    (DataType::Varchar, "relreplident"),
 ];
@@ -23,4 +23,8 @@ pub const PG_INDEX_COLUMNS: &[PgCatalogColumnsDef<'_>] = &[
     (DataType::Int32, "indexrelid"),
     (DataType::Int32, "indrelid"),
     (DataType::Int16, "indnatts"),
+    (DataType::Boolean, "indisunique"),
+    (DataType::Boolean, "indisprimary"),
+    // The column numbers of the primary table separated by spaces, like `int2vector` in Postgres.
+    (DataType::Varchar, "indkey"),
 ];
@@ -27,4 +27,6 @@ pub const PG_MATVIEWS_INFO_COLUMNS: &[PgCatalogColumnsDef<'_>] = &[
     // (DataType::Boolean, "hasindexes"),
     (DataType::Varchar, "matviewgraph"), // materialized view graph is json encoded fragment infos.
     (DataType::Varchar, "definition"),
+    // Whether the matview is defined with `SELECT *` on a table which has gained columns since.
+    (DataType::Boolean, "schemastale"),
 ];
@@ -23,33 +23,54 @@ use crate::catalog::pg_catalog::PgCatalogColumnsDef;
 /// The catalog `pg_type` stores information about data types.
 /// Ref: [`https://www.postgresql.org/docs/current/catalog-pg-type.html`]
 pub const PG_TYPE_TABLE_NAME: &str = "pg_type";
-pub const PG_TYPE_COLUMNS: &[PgCatalogColumnsDef<'_>] =
-    &[(DataType::Int32, "oid"), (DataType::Varchar, "typname")];
+pub const PG_TYPE_COLUMNS: &[PgCatalogColumnsDef<'_>] = &[
+    (DataType::Int32, "oid"),
+    (DataType::Varchar, "typname"),
+    (DataType::Int32, "typelem"),
+    (DataType::Int32, "typarray"),
+    (DataType::Int16, "typlen"),
+    (DataType::Varchar, "typtype"),
+    (DataType::Boolean, "typnotnull"),
+    (DataType::Int32, "typbasetype"),
+    (DataType::Int32, "typtypmod"),
+    (DataType::Int32, "typcollation"),
+];
 
 // TODO: uniform the default data with `TypeOid` under `pg_field_descriptor`.
-pub const PG_TYPE_DATA: &[(i32, &str)] = &[
-    (16, "bool"),
-    (20, "int8"),
-    (21, "int2"),
-    (23, "int4"),
-    (700, "float4"),
-    (701, "float8"),
-    (1043, "varchar"),
-    (1082, "date"),
-    (1083, "time"),
-    (1114, "timestamp"),
-    (1184, "timestamptz"),
-    (1186, "interval"),
-    (1700, "numeric"),
+/// `(oid, typname, typarray, typlen)` of the supported base types.
+pub const PG_TYPE_DATA: &[(i32, &str, i32, i16)] = &[
+    (16, "bool", 1000, 1),
+    (20, "int8", 1016, 8),
+    (21, "int2", 1005, 2),
+    (23, "int4", 1007, 4),
+    (700, "float4", 1021, 4),
+    (701, "float8", 1022, 8),
+    (1043, "varchar", 1015, -1),
+    (1082, "date", 1182, 4),
+    (1083, "time", 1183, 8),
+    (1114, "timestamp", 1115, 8),
+    (1184, "timestamptz", 1185, 8),
+    (1186, "interval", 1187, 16),
+    (1700, "numeric", 1231, -1),
+    (3802, "jsonb", 3807, -1),
 ];
 
 pub static PG_TYPE_DATA_ROWS: LazyLock<Vec<Row>> = LazyLock::new(|| {
     PG_TYPE_DATA
         .iter()
-        .map(|(oid, name)| {
+        .map(|(oid, name, array_oid, len)| {
             Row::new(vec![
                 Some(ScalarImpl::Int32(*oid)),
                 Some(ScalarImpl::Utf8(name.to_string())),
+                Some(ScalarImpl::Int32(0)),
+                Some(ScalarImpl::Int32(*array_oid)),
+                Some(ScalarImpl::Int16(*len)),
+                // "b" for a base type.
+                Some(ScalarImpl::Utf8("b".to_string())),
+                Some(ScalarImpl::Bool(false)),
+                Some(ScalarImpl::Int32(0)),
+                Some(ScalarImpl::Int32(-1)),
+                Some(ScalarImpl::Int32(0)),
             ])
         })
         .collect_vec()
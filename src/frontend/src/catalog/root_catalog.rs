@@ -16,22 +16,32 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use itertools::Itertools;
-use risingwave_common::catalog::{CatalogVersion, IndexId, TableId, PG_CATALOG_SCHEMA_NAME};
+use risingwave_common::catalog::{
+    CatalogVersion, IndexId, TableId, INFORMATION_SCHEMA_SCHEMA_NAME, PG_CATALOG_SCHEMA_NAME,
+    RW_CATALOG_SCHEMA_NAME,
+};
 use risingwave_common::session_config::{SearchPath, USER_NAME_WILD_CARD};
+use risingwave_common::types::DataType;
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable, View as ProstView,
+    Database as ProstDatabase, Function as ProstFunction, Index as ProstIndex,
+    Schema as ProstSchema, Secret as ProstSecret, Sink as ProstSink, Source as ProstSource,
+    Table as ProstTable, View as ProstView,
 };
 
+use super::function_catalog::FunctionCatalog;
+use super::secret_catalog::SecretCatalog;
 use super::source_catalog::SourceCatalog;
 use super::view_catalog::ViewCatalog;
-use super::{CatalogError, CatalogResult, SinkId, SourceId, ViewId};
+use super::{CatalogError, CatalogResult, FunctionId, SecretId, SinkId, SourceId, ViewId};
 use crate::catalog::database_catalog::DatabaseCatalog;
 use crate::catalog::schema_catalog::SchemaCatalog;
 use crate::catalog::sink_catalog::SinkCatalog;
 use crate::catalog::system_catalog::SystemCatalog;
 use crate::catalog::table_catalog::TableCatalog;
-use crate::catalog::{pg_catalog, DatabaseId, IndexCatalog, SchemaId};
+use crate::catalog::table_statistics::TableStatistics;
+use crate::catalog::{
+    information_schema, pg_catalog, rw_catalog, DatabaseId, IndexCatalog, SchemaId,
+};
 
 #[derive(Copy, Clone)]
 pub enum SchemaPath<'a> {
@@ -110,17 +120,21 @@ impl Catalog {
             .unwrap()
             .create_schema(proto);
 
-        if proto.name == PG_CATALOG_SCHEMA_NAME {
-            pg_catalog::get_all_pg_catalogs()
-                .into_iter()
-                .for_each(|sys_table| {
-                    self.get_database_mut(proto.database_id)
-                        .unwrap()
-                        .get_schema_mut(proto.id)
-                        .unwrap()
-                        .create_sys_table(sys_table);
-                });
-        }
+        let sys_tables = match proto.name.as_str() {
+            PG_CATALOG_SCHEMA_NAME => pg_catalog::get_all_pg_catalogs(),
+            RW_CATALOG_SCHEMA_NAME => rw_catalog::get_all_rw_catalogs(),
+            INFORMATION_SCHEMA_SCHEMA_NAME => {
+                information_schema::get_all_information_schema_catalogs()
+            }
+            _ => vec![],
+        };
+        sys_tables.into_iter().for_each(|sys_table| {
+            self.get_database_mut(proto.database_id)
+                .unwrap()
+                .get_schema_mut(proto.id)
+                .unwrap()
+                .create_sys_table(sys_table);
+        });
     }
 
     pub fn create_table(&mut self, proto: &ProstTable) {
@@ -164,6 +178,22 @@ impl Catalog {
             .create_view(proto);
     }
 
+    pub fn create_function(&mut self, proto: &ProstFunction) {
+        self.get_database_mut(proto.database_id)
+            .unwrap()
+            .get_schema_mut(proto.schema_id)
+            .unwrap()
+            .create_function(proto);
+    }
+
+    pub fn create_secret(&mut self, proto: &ProstSecret) {
+        self.get_database_mut(proto.database_id)
+            .unwrap()
+            .get_schema_mut(proto.schema_id)
+            .unwrap()
+            .create_secret(proto);
+    }
+
     pub fn drop_database(&mut self, db_id: DatabaseId) {
         let name = self.db_name_by_id.remove(&db_id).unwrap();
         let _database = self.database_by_name.remove(&name).unwrap();
@@ -191,6 +221,14 @@ impl Catalog {
             .update_table(proto);
     }
 
+    pub fn update_source(&mut self, proto: &ProstSource) {
+        self.get_database_mut(proto.database_id)
+            .unwrap()
+            .get_schema_mut(proto.schema_id)
+            .unwrap()
+            .update_source(proto);
+    }
+
     pub fn drop_source(&mut self, db_id: DatabaseId, schema_id: SchemaId, source_id: SourceId) {
         self.get_database_mut(db_id)
             .unwrap()
@@ -223,6 +261,27 @@ impl Catalog {
             .drop_view(view_id);
     }
 
+    pub fn drop_function(
+        &mut self,
+        db_id: DatabaseId,
+        schema_id: SchemaId,
+        function_id: FunctionId,
+    ) {
+        self.get_database_mut(db_id)
+            .unwrap()
+            .get_schema_mut(schema_id)
+            .unwrap()
+            .drop_function(function_id);
+    }
+
+    pub fn drop_secret(&mut self, db_id: DatabaseId, schema_id: SchemaId, secret_id: SecretId) {
+        self.get_database_mut(db_id)
+            .unwrap()
+            .get_schema_mut(schema_id)
+            .unwrap()
+            .drop_secret(secret_id);
+    }
+
     pub fn get_database_by_name(&self, db_name: &str) -> CatalogResult<&DatabaseCatalog> {
         self.database_by_name
             .get(db_name)
@@ -352,6 +411,11 @@ impl Catalog {
             .ok_or_else(|| CatalogError::NotFound("table id", table_id.to_string()))
     }
 
+    /// Get the statistics collected by `ANALYZE` of the table, if any.
+    pub fn get_table_statistics_by_id(&self, table_id: &TableId) -> Option<&TableStatistics> {
+        self.table_by_id.get(table_id)?.statistics.as_ref()
+    }
+
     #[cfg(test)]
     pub fn insert_table_id_mapping(&mut self, table_id: TableId, fragment_id: super::FragmentId) {
         self.table_by_id.insert(
@@ -366,10 +430,10 @@ impl Catalog {
     pub fn get_sys_table_by_name(
         &self,
         db_name: &str,
+        schema_name: &str,
         table_name: &str,
     ) -> CatalogResult<&SystemCatalog> {
-        self.get_schema_by_name(db_name, PG_CATALOG_SCHEMA_NAME)
-            .unwrap()
+        self.get_schema_by_name(db_name, schema_name)?
             .get_system_table_by_name(table_name)
             .ok_or_else(|| CatalogError::NotFound("table", table_name.to_string()))
     }
@@ -534,6 +598,115 @@ impl Catalog {
         }
     }
 
+    #[inline(always)]
+    fn get_secret_by_name_with_schema_name(
+        &self,
+        db_name: &str,
+        schema_name: &str,
+        secret_name: &str,
+    ) -> CatalogResult<&Arc<SecretCatalog>> {
+        self.get_schema_by_name(db_name, schema_name)?
+            .get_secret_by_name(secret_name)
+            .ok_or_else(|| CatalogError::NotFound("secret", secret_name.to_string()))
+    }
+
+    pub fn get_secret_by_name<'a>(
+        &self,
+        db_name: &str,
+        schema_path: SchemaPath<'a>,
+        secret_name: &str,
+    ) -> CatalogResult<(&Arc<SecretCatalog>, &'a str)> {
+        match schema_path {
+            SchemaPath::Name(schema_name) => self
+                .get_secret_by_name_with_schema_name(db_name, schema_name, secret_name)
+                .map(|secret_catalog| (secret_catalog, schema_name)),
+            SchemaPath::Path(search_path, user_name) => {
+                for path in search_path.path() {
+                    let mut schema_name: &str = path;
+                    if schema_name == USER_NAME_WILD_CARD {
+                        schema_name = user_name;
+                    }
+
+                    if let Ok(secret_catalog) =
+                        self.get_secret_by_name_with_schema_name(db_name, schema_name, secret_name)
+                    {
+                        return Ok((secret_catalog, schema_name));
+                    }
+                }
+                Err(CatalogError::NotFound("secret", secret_name.to_string()))
+            }
+        }
+    }
+
+    /// Get the secret by id in the given database, along with the name of its schema.
+    pub fn get_secret_by_id(
+        &self,
+        db_name: &str,
+        secret_id: SecretId,
+    ) -> CatalogResult<(&Arc<SecretCatalog>, String)> {
+        self.get_database_by_name(db_name)?
+            .iter_schemas()
+            .find_map(|schema| {
+                schema
+                    .get_secret_by_id(&secret_id)
+                    .map(|secret| (secret, schema.name()))
+            })
+            .ok_or_else(|| CatalogError::NotFound("secret", secret_id.to_string()))
+    }
+
+    #[inline(always)]
+    fn get_function_by_name_args_with_schema_name(
+        &self,
+        db_name: &str,
+        schema_name: &str,
+        function_name: &str,
+        args: &[DataType],
+    ) -> CatalogResult<&Arc<FunctionCatalog>> {
+        self.get_schema_by_name(db_name, schema_name)?
+            .get_function_by_name_args(function_name, args)
+            .ok_or_else(|| CatalogError::NotFound("function", function_name.to_string()))
+    }
+
+    pub fn get_function_by_name_args<'a>(
+        &self,
+        db_name: &str,
+        schema_path: SchemaPath<'a>,
+        function_name: &str,
+        args: &[DataType],
+    ) -> CatalogResult<(&Arc<FunctionCatalog>, &'a str)> {
+        match schema_path {
+            SchemaPath::Name(schema_name) => self
+                .get_function_by_name_args_with_schema_name(
+                    db_name,
+                    schema_name,
+                    function_name,
+                    args,
+                )
+                .map(|function_catalog| (function_catalog, schema_name)),
+            SchemaPath::Path(search_path, user_name) => {
+                for path in search_path.path() {
+                    let mut schema_name: &str = path;
+                    if schema_name == USER_NAME_WILD_CARD {
+                        schema_name = user_name;
+                    }
+
+                    if let Ok(function_catalog) = self.get_function_by_name_args_with_schema_name(
+                        db_name,
+                        schema_name,
+                        function_name,
+                        args,
+                    ) {
+                        return Ok((function_catalog, schema_name));
+                    }
+                }
+                Err(CatalogError::NotFound(
+                    "function",
+                    function_name.to_string(),
+                ))
+            }
+        }
+    }
+
     /// Check the name if duplicated with existing table, materialized view or source.
     pub fn check_relation_name_duplicated(
         &self,
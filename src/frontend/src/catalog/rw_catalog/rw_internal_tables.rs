@@ -0,0 +1,34 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+
+use crate::catalog::pg_catalog::PgCatalogColumnsDef;
+
+/// The catalog `rw_internal_tables` lists the internal state tables of all streaming jobs, e.g.
+/// the aggregation states and join states, along with their approximate sizes in storage.
+/// Only superusers can read it.
+pub const RW_INTERNAL_TABLES_TABLE_NAME: &str = "rw_internal_tables";
+pub const RW_INTERNAL_TABLES_COLUMNS: &[PgCatalogColumnsDef<'_>] = &[
+    (DataType::Int32, "id"),
+    (DataType::Varchar, "name"),
+    (DataType::Varchar, "schema_name"),
+    (DataType::Int32, "job_id"),
+    (DataType::Varchar, "job_name"),
+    (DataType::Varchar, "table_type"),
+    (DataType::Int32, "fragment_id"),
+    (DataType::Int32, "owner"),
+    // approximate size in bytes, estimated from the SSTs of the current hummock version.
+    (DataType::Int64, "approx_size"),
+];
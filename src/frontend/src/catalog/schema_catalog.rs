@@ -16,15 +16,18 @@ use std::collections::hash_map::Entry::{Occupied, Vacant};
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use risingwave_common::catalog::{valid_table_name, IndexId, TableId, PG_CATALOG_SCHEMA_NAME};
+use risingwave_common::catalog::{is_system_schema, valid_table_name, IndexId, TableId};
+use risingwave_common::types::DataType;
 use risingwave_pb::catalog::{
-    Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink, Source as ProstSource,
-    Table as ProstTable, View as ProstView,
+    Function as ProstFunction, Index as ProstIndex, Schema as ProstSchema, Secret as ProstSecret,
+    Sink as ProstSink, Source as ProstSource, Table as ProstTable, View as ProstView,
 };
 
 use super::source_catalog::SourceCatalog;
-use super::ViewId;
+use super::{FunctionId, SecretId, ViewId};
+use crate::catalog::function_catalog::FunctionCatalog;
 use crate::catalog::index_catalog::IndexCatalog;
+use crate::catalog::secret_catalog::SecretCatalog;
 use crate::catalog::sink_catalog::SinkCatalog;
 use crate::catalog::system_catalog::SystemCatalog;
 use crate::catalog::table_catalog::TableCatalog;
@@ -49,8 +52,15 @@ pub struct SchemaCatalog {
     indexes_by_table_id: HashMap<TableId, Vec<Arc<IndexCatalog>>>,
     view_by_name: HashMap<String, Arc<ViewCatalog>>,
     view_by_id: HashMap<ViewId, Arc<ViewCatalog>>,
-
-    // This field only available when schema is "pg_catalog". Meanwhile, others will be empty.
+    /// Functions are overloaded by argument types, so they are indexed by name and then by
+    /// argument types.
+    function_by_name: HashMap<String, HashMap<Vec<DataType>, Arc<FunctionCatalog>>>,
+    function_by_id: HashMap<FunctionId, Arc<FunctionCatalog>>,
+    secret_by_name: HashMap<String, Arc<SecretCatalog>>,
+    secret_by_id: HashMap<SecretId, Arc<SecretCatalog>>,
+
+    // This field only available when schema is "pg_catalog" or "rw_catalog". Meanwhile, others
+    // will be empty.
     system_table_by_name: HashMap<String, SystemCatalog>,
     owner: u32,
 }
@@ -69,7 +79,7 @@ impl SchemaCatalog {
     }
 
     pub fn create_sys_table(&mut self, sys_table: SystemCatalog) {
-        assert_eq!(self.name, PG_CATALOG_SCHEMA_NAME);
+        assert!(is_system_schema(&self.name));
         self.system_table_by_name
             .try_insert(sys_table.name.clone(), sys_table)
             .unwrap();
@@ -144,6 +154,16 @@ impl SchemaCatalog {
         self.source_by_id.try_insert(id, source_ref).unwrap();
     }
 
+    pub fn update_source(&mut self, prost: &ProstSource) {
+        let name = prost.name.clone();
+        let id = prost.id;
+        let source = SourceCatalog::from(prost);
+        let source_ref = Arc::new(source);
+
+        self.source_by_name.insert(name, source_ref.clone());
+        self.source_by_id.insert(id, source_ref);
+    }
+
     pub fn drop_source(&mut self, id: SourceId) {
         let source_ref = self.source_by_id.remove(&id).unwrap();
         self.source_by_name.remove(&source_ref.name).unwrap();
@@ -183,6 +203,40 @@ impl SchemaCatalog {
         self.view_by_name.remove(&view_ref.name).unwrap();
     }
 
+    pub fn create_function(&mut self, prost: &ProstFunction) {
+        let name = prost.name.clone();
+        let id = prost.id;
+        let function = FunctionCatalog::from(prost);
+        let args = function.arg_types.clone();
+        let function_ref = Arc::new(function);
+
+        self.function_by_name
+            .entry(name)
+            .or_default()
+            .try_insert(args, function_ref.clone())
+            .expect("function already exists with same argument types");
+        self.function_by_id
+            .try_insert(id, function_ref)
+            .expect("function id exists");
+    }
+
+    pub fn drop_function(&mut self, id: FunctionId) {
+        let function_ref = self
+            .function_by_id
+            .remove(&id)
+            .expect("function not found by id");
+        let overloads = self
+            .function_by_name
+            .get_mut(&function_ref.name)
+            .expect("function not found by name");
+        overloads
+            .remove(&function_ref.arg_types)
+            .expect("function not found by argument types");
+        if overloads.is_empty() {
+            self.function_by_name.remove(&function_ref.name);
+        }
+    }
+
     pub fn iter_table(&self) -> impl Iterator<Item = &Arc<TableCatalog>> {
         self.table_by_name
             .iter()
@@ -205,6 +259,14 @@ impl SchemaCatalog {
             .map(|(_, v)| v)
     }
 
+    /// Iterate all internal state tables of streaming jobs.
+    pub fn iter_internal_table(&self) -> impl Iterator<Item = &Arc<TableCatalog>> {
+        self.table_by_name
+            .iter()
+            .filter(|(_, v)| !valid_table_name(&v.name))
+            .map(|(_, v)| v)
+    }
+
     /// Iterate all indices
     pub fn iter_index(&self) -> impl Iterator<Item = &Arc<IndexCatalog>> {
         self.index_by_name.values()
@@ -230,6 +292,10 @@ impl SchemaCatalog {
         self.sink_by_name.values()
     }
 
+    pub fn iter_view(&self) -> impl Iterator<Item = &Arc<ViewCatalog>> {
+        self.view_by_name.values()
+    }
+
     pub fn iter_system_tables(&self) -> impl Iterator<Item = &SystemCatalog> {
         self.system_table_by_name.values()
     }
@@ -246,6 +312,10 @@ impl SchemaCatalog {
         self.source_by_name.get(source_name)
     }
 
+    pub fn get_source_by_id(&self, source_id: &SourceId) -> Option<&Arc<SourceCatalog>> {
+        self.source_by_id.get(source_id)
+    }
+
     pub fn get_sink_by_name(&self, sink_name: &str) -> Option<&Arc<SinkCatalog>> {
         self.sink_by_name.get(sink_name)
     }
@@ -265,6 +335,23 @@ impl SchemaCatalog {
             .unwrap_or_default()
     }
 
+    pub fn create_secret(&mut self, prost: &ProstSecret) {
+        let name = prost.name.clone();
+        let id = prost.id;
+        let secret = SecretCatalog::from(prost);
+        let secret_ref = Arc::new(secret);
+
+        self.secret_by_name
+            .try_insert(name, secret_ref.clone())
+            .unwrap();
+        self.secret_by_id.try_insert(id, secret_ref).unwrap();
+    }
+
+    pub fn drop_secret(&mut self, id: SecretId) {
+        let secret_ref = self.secret_by_id.remove(&id).unwrap();
+        self.secret_by_name.remove(&secret_ref.name).unwrap();
+    }
+
     pub fn get_system_table_by_name(&self, table_name: &str) -> Option<&SystemCatalog> {
         self.system_table_by_name.get(table_name)
     }
@@ -279,6 +366,30 @@ impl SchemaCatalog {
         self.view_by_name.get(view_name)
     }
 
+    pub fn get_secret_by_name(&self, secret_name: &str) -> Option<&Arc<SecretCatalog>> {
+        self.secret_by_name.get(secret_name)
+    }
+
+    pub fn get_secret_by_id(&self, secret_id: &SecretId) -> Option<&Arc<SecretCatalog>> {
+        self.secret_by_id.get(secret_id)
+    }
+
+    pub fn get_function_by_name_args(
+        &self,
+        name: &str,
+        args: &[DataType],
+    ) -> Option<&Arc<FunctionCatalog>> {
+        self.function_by_name.get(name)?.get(args)
+    }
+
+    pub fn get_functions_by_name(&self, name: &str) -> Option<Vec<&Arc<FunctionCatalog>>> {
+        let functions = self.function_by_name.get(name)?;
+        if functions.is_empty() {
+            return None;
+        }
+        Some(functions.values().collect())
+    }
+
     pub fn id(&self) -> SchemaId {
         self.id
     }
@@ -310,6 +421,10 @@ impl From<&ProstSchema> for SchemaCatalog {
             system_table_by_name: HashMap::new(),
             view_by_name: HashMap::new(),
             view_by_id: HashMap::new(),
+            function_by_name: HashMap::new(),
+            function_by_id: HashMap::new(),
+            secret_by_name: HashMap::new(),
+            secret_by_id: HashMap::new(),
         }
     }
 }
@@ -0,0 +1,36 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_pb::catalog::Secret as ProstSecret;
+
+use super::SecretId;
+
+/// The catalog of a secret. The value of the secret is only kept in meta, and is never sent to
+/// the frontends.
+#[derive(Clone, Debug)]
+pub struct SecretCatalog {
+    pub id: SecretId,
+    pub name: String,
+    pub owner: u32,
+}
+
+impl From<&ProstSecret> for SecretCatalog {
+    fn from(secret: &ProstSecret) -> Self {
+        SecretCatalog {
+            id: secret.id,
+            name: secret.name.clone(),
+            owner: secret.owner,
+        }
+    }
+}
@@ -23,7 +23,9 @@ pub struct SinkCatalog {
     pub id: SinkId,
     pub name: String,
 
-    pub associated_table_id: TableId,
+    /// The materialized view to sink from, or `None` if the sink is created from a query.
+    pub associated_table_id: Option<TableId>,
+    pub definition: String,
     pub properties: WithOptions,
     pub owner: u32,
 }
@@ -33,7 +35,9 @@ impl From<&ProstSink> for SinkCatalog {
         SinkCatalog {
             id: sink.id,
             name: sink.name.clone(),
-            associated_table_id: TableId::new(sink.associated_table_id),
+            associated_table_id: (sink.associated_table_id != 0)
+                .then(|| TableId::new(sink.associated_table_id)),
+            definition: sink.definition.clone(),
             properties: WithOptions::new(sink.properties.clone()),
             owner: sink.owner,
         }
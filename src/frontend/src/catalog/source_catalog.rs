@@ -15,14 +15,35 @@
 use std::collections::HashMap;
 
 use risingwave_pb::catalog::source::Info;
-use risingwave_pb::catalog::Source as ProstSource;
+use risingwave_pb::catalog::{
+    ColumnIndex as ProstColumnIndex, Source as ProstSource, WatermarkDesc,
+};
 use risingwave_pb::stream_plan::source_node::Info as StreamPlanInfo;
 
 use super::column_catalog::ColumnCatalog;
-use super::{ColumnId, SourceId};
+use super::{ColumnId, DatabaseId, SchemaId, SecretId, SourceId};
 use crate::WithOptions;
 
 pub const KAFKA_CONNECTOR: &str = "kafka";
+pub const PULSAR_CONNECTOR: &str = "pulsar";
+pub const DATAGEN_CONNECTOR: &str = "datagen";
+pub const NEXMARK_CONNECTOR: &str = "nexmark";
+/// The option of a nexmark source to choose the events to generate, one of `Person`, `Auction` and
+/// `Bid`.
+pub const NEXMARK_TABLE_TYPE_KEY: &str = "nexmark.table.type";
+/// The options of a datagen source to emit a change stream, which is in the Debezium JSON format.
+pub const DATAGEN_CHANGE_STREAM_KEYS: [&str; 2] = ["datagen.update.ratio", "datagen.delete.ratio"];
+/// The native MySQL CDC connector, which emits the changes of a table in the Debezium JSON format.
+pub const MYSQL_CDC_CONNECTOR: &str = "mysql-cdc";
+/// The native Postgres CDC connector, which emits the changes of a table in the Debezium JSON
+/// format.
+pub const POSTGRES_CDC_CONNECTOR: &str = "postgres-cdc";
+/// The option of a Kafka source to specify the column holding the timestamps of the messages, so
+/// that the lower bound of the column in the predicates can be pushed down into the connector.
+pub const KAFKA_TIMESTAMP_COLUMN_KEY: &str = "scan.pushdown.timestamp.column";
+/// The connector property with the lower bound of the message timestamps in milliseconds, which is
+/// derived from the predicates by the optimizer.
+pub const KAFKA_TIMESTAMP_MILLIS_KEY: &str = "scan.pushdown.timestamp_millis";
 
 /// this struct `SourceCatalog` is used in frontend and compared with `ProstSource` it only maintain
 /// information which will be used during optimization.
@@ -37,6 +58,11 @@ pub struct SourceCatalog {
     pub info: StreamPlanInfo,
     pub row_id_index: Option<usize>,
     pub properties: HashMap<String, String>,
+    /// The properties referring to secrets, keyed by the property names.
+    pub secret_refs: HashMap<String, SecretId>,
+    pub watermark_descs: Vec<WatermarkDesc>,
+    /// The columns added to the upstream schema, proposed by meta to be added to the source.
+    pub proposed_columns: Vec<ColumnCatalog>,
 }
 
 impl SourceCatalog {
@@ -47,6 +73,34 @@ impl SourceCatalog {
     pub fn is_stream(&self) -> bool {
         matches!(self.info, StreamPlanInfo::StreamSource(_))
     }
+
+    pub fn to_prost(&self, schema_id: SchemaId, database_id: DatabaseId) -> ProstSource {
+        let info = match &self.info {
+            StreamPlanInfo::StreamSource(info) => Info::StreamSource(info.clone()),
+            StreamPlanInfo::TableSource(info) => Info::TableSource(info.clone()),
+        };
+        ProstSource {
+            id: self.id,
+            schema_id,
+            database_id,
+            name: self.name.clone(),
+            row_id_index: self
+                .row_id_index
+                .map(|index| ProstColumnIndex { index: index as _ }),
+            columns: self.columns.iter().map(|c| c.to_protobuf()).collect(),
+            pk_column_ids: self.pk_col_ids.iter().map(|id| id.get_id()).collect(),
+            properties: self.properties.clone(),
+            secret_refs: self.secret_refs.clone(),
+            info: Some(info),
+            owner: self.owner,
+            watermark_descs: self.watermark_descs.clone(),
+            proposed_columns: self
+                .proposed_columns
+                .iter()
+                .map(|c| c.to_protobuf())
+                .collect(),
+        }
+    }
 }
 
 impl From<&ProstSource> for SourceCatalog {
@@ -87,6 +141,14 @@ impl From<&ProstSource> for SourceCatalog {
             info,
             row_id_index,
             properties: with_options.into_inner(),
+            secret_refs: prost.secret_refs.clone(),
+            watermark_descs: prost.watermark_descs.clone(),
+            proposed_columns: prost
+                .proposed_columns
+                .iter()
+                .cloned()
+                .map(ColumnCatalog::from)
+                .collect(),
         }
     }
 }
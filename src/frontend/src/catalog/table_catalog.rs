@@ -14,13 +14,17 @@
 
 use std::collections::{HashMap, HashSet};
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
-use risingwave_common::catalog::{TableDesc, TableId};
+use risingwave_common::catalog::{ColumnId, TableDesc, TableId};
 use risingwave_common::config::constant::hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND;
-use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
+use risingwave_pb::catalog::table::{
+    OptionalAssociatedSourceId, TableVersion as ProstTableVersion,
+};
 use risingwave_pb::catalog::{ColumnIndex as ProstColumnIndex, Table as ProstTable};
 
 use super::column_catalog::ColumnCatalog;
+use super::table_statistics::TableStatistics;
 use super::{DatabaseId, FragmentId, SchemaId};
 use crate::optimizer::property::FieldOrder;
 use crate::WithOptions;
@@ -96,6 +100,74 @@ pub struct TableCatalog {
 
     /// Definition of the materialized view.
     pub definition: String,
+
+    /// The version of the table schema. Only tables created by `CREATE TABLE` have a version,
+    /// which can be altered by `ALTER TABLE`.
+    pub version: Option<TableVersion>,
+
+    /// The column indices which have watermarks, derived from the stream plan of the table.
+    pub watermark_columns: FixedBitSet,
+
+    /// The statistics collected by the last `ANALYZE` on the table, if any.
+    pub statistics: Option<TableStatistics>,
+
+    /// The comment on the table set by `COMMENT ON TABLE`, if any.
+    pub description: Option<String>,
+
+    /// The comments on the columns set by `COMMENT ON COLUMN`.
+    pub column_descriptions: HashMap<ColumnId, String>,
+
+    /// Whether the materialized view is defined with `SELECT *` on a table which has gained
+    /// columns since, without the view being updated.
+    pub schema_stale: bool,
+}
+
+/// The version of a table schema, bumped on each `ALTER TABLE`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableVersion {
+    pub version_id: u64,
+
+    /// The column id to be assigned to the next added column. Column ids are never reused, so
+    /// that the data of dropped columns won't be read as the new ones.
+    pub next_column_id: ColumnId,
+}
+
+impl TableVersion {
+    /// The initial version of a table with the given columns.
+    pub fn new_initial(columns: &[ColumnCatalog]) -> Self {
+        let max_column_id = columns
+            .iter()
+            .map(|c| c.column_id().get_id())
+            .max()
+            .unwrap_or(-1);
+        Self {
+            version_id: 0,
+            next_column_id: ColumnId::new(max_column_id + 1),
+        }
+    }
+
+    /// The next version after a schema change, with `next_column_id` advanced past the added
+    /// columns.
+    pub fn next(&self, next_column_id: ColumnId) -> Self {
+        Self {
+            version_id: self.version_id + 1,
+            next_column_id,
+        }
+    }
+
+    pub fn from_prost(prost: &ProstTableVersion) -> Self {
+        Self {
+            version_id: prost.version,
+            next_column_id: ColumnId::new(prost.next_column_id),
+        }
+    }
+
+    pub fn to_prost(&self) -> ProstTableVersion {
+        ProstTableVersion {
+            version: self.version_id,
+            next_column_id: self.next_column_id.get_id(),
+        }
+    }
 }
 
 impl TableCatalog {
@@ -142,6 +214,7 @@ impl TableCatalog {
                 .retention_seconds
                 .unwrap_or(TABLE_OPTION_DUMMY_RETENTION_SECOND),
             value_indices: self.value_indices.clone(),
+            watermark_columns: self.watermark_columns.clone(),
         }
     }
 
@@ -190,6 +263,16 @@ impl TableCatalog {
                 .map(|i| ProstColumnIndex { index: i as _ }),
             value_indices: self.value_indices.iter().map(|x| *x as _).collect(),
             definition: self.definition.clone(),
+            version: self.version.as_ref().map(TableVersion::to_prost),
+            watermark_indices: self.watermark_columns.ones().map(|x| x as _).collect_vec(),
+            statistics: self.statistics.as_ref().map(TableStatistics::to_prost),
+            description: self.description.clone().unwrap_or_default(),
+            column_descriptions: self
+                .column_descriptions
+                .iter()
+                .map(|(id, comment)| (id.get_id(), comment.clone()))
+                .collect(),
+            schema_stale: self.schema_stale,
         }
     }
 }
@@ -215,6 +298,10 @@ impl From<ProstTable> for TableCatalog {
         }
 
         let pk = tb.pk.iter().map(FieldOrder::from_protobuf).collect();
+        let mut watermark_columns = FixedBitSet::with_capacity(columns.len());
+        for idx in &tb.watermark_indices {
+            watermark_columns.insert(*idx as _);
+        }
 
         Self {
             id: id.into(),
@@ -236,6 +323,16 @@ impl From<ProstTable> for TableCatalog {
             vnode_col_idx: tb.vnode_col_idx.map(|x| x.index as usize),
             value_indices: tb.value_indices.iter().map(|x| *x as _).collect(),
             definition: tb.definition.clone(),
+            version: tb.version.as_ref().map(TableVersion::from_prost),
+            watermark_columns,
+            statistics: tb.statistics.as_ref().map(TableStatistics::from_prost),
+            description: (!tb.description.is_empty()).then_some(tb.description),
+            column_descriptions: tb
+                .column_descriptions
+                .into_iter()
+                .map(|(id, comment)| (ColumnId::new(id), comment))
+                .collect(),
+            schema_stale: tb.schema_stale,
         }
     }
 }
@@ -250,11 +347,14 @@ impl From<&ProstTable> for TableCatalog {
 mod tests {
     use std::collections::HashMap;
 
+    use fixedbitset::FixedBitSet;
     use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId};
     use risingwave_common::config::constant::hummock::PROPERTIES_RETENTION_SECOND_KEY;
     use risingwave_common::test_prelude::*;
     use risingwave_common::types::*;
-    use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
+    use risingwave_pb::catalog::table::{
+        OptionalAssociatedSourceId, TableVersion as ProstTableVersion,
+    };
     use risingwave_pb::catalog::Table as ProstTable;
     use risingwave_pb::plan_common::{
         ColumnCatalog as ProstColumnCatalog, ColumnDesc as ProstColumnDesc,
@@ -262,7 +362,7 @@ mod tests {
 
     use crate::catalog::column_catalog::ColumnCatalog;
     use crate::catalog::row_id_column_desc;
-    use crate::catalog::table_catalog::TableCatalog;
+    use crate::catalog::table_catalog::{TableCatalog, TableVersion};
     use crate::optimizer::property::{Direction, FieldOrder};
     use crate::WithOptions;
 
@@ -320,6 +420,15 @@ mod tests {
             vnode_col_idx: None,
             value_indices: vec![0],
             definition: "".into(),
+            version: Some(ProstTableVersion {
+                version: 0,
+                next_column_id: 2,
+            }),
+            watermark_indices: vec![],
+            statistics: None,
+            description: "a table".into(),
+            column_descriptions: HashMap::from([(0, "the id".into())]),
+            schema_stale: false,
         }
         .into();
 
@@ -377,6 +486,15 @@ mod tests {
                 vnode_col_idx: None,
                 value_indices: vec![0],
                 definition: "".into(),
+                version: Some(TableVersion {
+                    version_id: 0,
+                    next_column_id: ColumnId::new(2),
+                }),
+                watermark_columns: FixedBitSet::with_capacity(2),
+                statistics: None,
+                description: Some("a table".into()),
+                column_descriptions: HashMap::from([(ColumnId::new(0), "the id".into())]),
+                schema_stale: false,
             }
         );
         assert_eq!(table, TableCatalog::from(table.to_prost(0, 0)));
@@ -0,0 +1,169 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use risingwave_common::catalog::ColumnId;
+use risingwave_pb::catalog::{
+    ColumnStatistics as ProstColumnStatistics, TableStatistics as ProstTableStatistics,
+};
+
+/// Statistics of a table collected by `ANALYZE`, which are used by the batch optimizer to estimate
+/// the cardinality of plans.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableStatistics {
+    pub row_count: u64,
+
+    /// Statistics of the analyzed columns. Columns added after the last `ANALYZE` are absent.
+    pub columns: HashMap<ColumnId, ColumnStatistics>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnStatistics {
+    /// The estimated number of distinct non-null values.
+    pub distinct_count: u64,
+
+    pub null_count: u64,
+
+    /// The bounds of an equi-depth histogram over the non-null values in ascending order, i.e.,
+    /// each of the buckets between two adjacent bounds holds roughly the same number of values.
+    pub histogram_bounds: Vec<String>,
+}
+
+impl TableStatistics {
+    pub fn column(&self, column_id: ColumnId) -> Option<&ColumnStatistics> {
+        self.columns.get(&column_id)
+    }
+
+    pub fn from_prost(prost: &ProstTableStatistics) -> Self {
+        Self {
+            row_count: prost.row_count,
+            columns: prost
+                .columns
+                .iter()
+                .map(|c| {
+                    (
+                        ColumnId::new(c.column_id),
+                        ColumnStatistics {
+                            distinct_count: c.distinct_count,
+                            null_count: c.null_count,
+                            histogram_bounds: c.histogram_bounds.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_prost(&self) -> ProstTableStatistics {
+        let mut columns = self
+            .columns
+            .iter()
+            .map(|(column_id, c)| ProstColumnStatistics {
+                column_id: column_id.get_id(),
+                distinct_count: c.distinct_count,
+                null_count: c.null_count,
+                histogram_bounds: c.histogram_bounds.clone(),
+            })
+            .collect::<Vec<_>>();
+        columns.sort_by_key(|c| c.column_id);
+        ProstTableStatistics {
+            row_count: self.row_count,
+            columns,
+        }
+    }
+}
+
+impl ColumnStatistics {
+    /// Estimates the fraction of the non-null values less than `value` by linear interpolation
+    /// inside the histogram bucket containing it. Returns `None` if there's no histogram or the
+    /// bounds are not numeric.
+    pub fn fraction_less_than(&self, value: f64) -> Option<f64> {
+        let bounds = self
+            .histogram_bounds
+            .iter()
+            .map(|b| b.parse::<f64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let (first, last) = (*bounds.first()?, *bounds.last()?);
+        if value <= first {
+            return Some(0.0);
+        }
+        if value > last || bounds.len() == 1 {
+            return Some(1.0);
+        }
+
+        let buckets = (bounds.len() - 1) as f64;
+        let bucket = bounds.windows(2).position(|w| value <= w[1]).unwrap();
+        let (lo, hi) = (bounds[bucket], bounds[bucket + 1]);
+        let within = if hi > lo {
+            (value - lo) / (hi - lo)
+        } else {
+            0.5
+        };
+        Some((bucket as f64 + within) / buckets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fraction_less_than() {
+        let stats = ColumnStatistics {
+            distinct_count: 100,
+            null_count: 0,
+            histogram_bounds: ["0", "10", "20", "100"].map(String::from).to_vec(),
+        };
+        assert_eq!(stats.fraction_less_than(-1.0), Some(0.0));
+        assert_eq!(stats.fraction_less_than(5.0), Some(0.5 / 3.0));
+        assert_eq!(stats.fraction_less_than(20.0), Some(2.0 / 3.0));
+        assert_eq!(stats.fraction_less_than(60.0), Some(2.5 / 3.0));
+        assert_eq!(stats.fraction_less_than(101.0), Some(1.0));
+
+        let stats = ColumnStatistics {
+            histogram_bounds: ["a", "b"].map(String::from).to_vec(),
+            ..Default::default()
+        };
+        assert_eq!(stats.fraction_less_than(1.0), None);
+    }
+
+    #[test]
+    fn test_prost_round_trip() {
+        let stats = TableStatistics {
+            row_count: 42,
+            columns: HashMap::from([
+                (
+                    ColumnId::new(1),
+                    ColumnStatistics {
+                        distinct_count: 10,
+                        null_count: 2,
+                        histogram_bounds: vec!["1".into(), "9".into()],
+                    },
+                ),
+                (ColumnId::new(2), ColumnStatistics::default()),
+            ]),
+        };
+        let prost = stats.to_prost();
+        assert_eq!(
+            prost
+                .columns
+                .iter()
+                .map(|c| c.column_id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(TableStatistics::from_prost(&prost), stats);
+    }
+}
@@ -64,9 +64,13 @@ impl AggCall {
         // The function signatures are aligned with postgres, see
         // https://www.postgresql.org/docs/current/functions-aggregate.html.
         let return_type = match (&agg_kind, inputs) {
-            // Min, Max, FirstValue
-            (AggKind::Min | AggKind::Max | AggKind::FirstValue, [input]) => input.clone(),
-            (AggKind::Min | AggKind::Max | AggKind::FirstValue, _) => return invalid(),
+            // Min, Max, FirstValue, LastValue
+            (AggKind::Min | AggKind::Max | AggKind::FirstValue | AggKind::LastValue, [input]) => {
+                input.clone()
+            }
+            (AggKind::Min | AggKind::Max | AggKind::FirstValue | AggKind::LastValue, _) => {
+                return invalid()
+            }
 
             // Avg
             (AggKind::Avg, [input]) => match input {
@@ -99,6 +103,14 @@ impl AggCall {
             (AggKind::ApproxCountDistinct, [_]) => DataType::Int64,
             (AggKind::ApproxCountDistinct, _) => return invalid(),
 
+            // ApproxCountDistinctMerge
+            (AggKind::ApproxCountDistinctMerge, [DataType::List { datatype }])
+                if **datatype == DataType::Int64 =>
+            {
+                DataType::Int64
+            }
+            (AggKind::ApproxCountDistinctMerge, _) => return invalid(),
+
             // Count
             (AggKind::Count, [] | [_]) => DataType::Int64,
             (AggKind::Count, _) => return invalid(),
@@ -112,6 +124,12 @@ impl AggCall {
                 datatype: Box::new(input.clone()),
             },
             (AggKind::ArrayAgg, _) => return invalid(),
+
+            // PercentileCont, PercentileDisc
+            (AggKind::PercentileCont, [DataType::Float64, DataType::Float64]) => DataType::Float64,
+            (AggKind::PercentileCont, _) => return invalid(),
+            (AggKind::PercentileDisc, [input, DataType::Float64]) => input.clone(),
+            (AggKind::PercentileDisc, _) => return invalid(),
         };
 
         Ok(return_type)
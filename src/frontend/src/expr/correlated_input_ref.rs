@@ -56,6 +56,12 @@ impl CorrelatedInputRef {
         self.index
     }
 
+    /// Make the input ref refer to another column of the outer input, when the outer input is
+    /// changed.
+    pub fn set_index(&mut self, index: usize) {
+        self.index = index;
+    }
+
     pub fn depth(&self) -> usize {
         match self.position {
             Position::Relative(depth) => depth,
@@ -80,8 +86,24 @@ impl Expr for CorrelatedInputRef {
         self.data_type.clone()
     }
 
+    /// Only the `CorrelatedInputRef`s which can not be decorrelated are serialized, and they are
+    /// replaced by the values of the outer row by the nested-loop apply executor.
     fn to_expr_proto(&self) -> risingwave_pb::expr::ExprNode {
-        unreachable!("CorrelatedInputRef {:?} has not been decorrelated", self)
+        use risingwave_pb::expr::expr_node::*;
+        use risingwave_pb::expr::*;
+        assert!(
+            matches!(self.position, Position::Absolute(_)),
+            "CorrelatedInputRef {:?} has not been assigned to an apply",
+            self
+        );
+        ExprNode {
+            expr_type: Type::CorrelatedInputRef.into(),
+            return_type: Some(self.return_type().to_protobuf()),
+            rex_node: Some(RexNode::CorrelatedInputRef(CorrelatedInputRefExpr {
+                correlated_id: self.correlated_id(),
+                column_idx: self.index as i32,
+            })),
+        }
     }
 }
 
@@ -13,8 +13,8 @@
 // limitations under the License.
 
 use super::{
-    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery,
-    TableFunction, WindowFunction,
+    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Parameter, Subquery,
+    TableFunction, UserDefinedFunction, WindowFunction,
 };
 
 /// By default, `ExprRewriter` simply traverses the expression tree and leaves nodes unchanged.
@@ -31,6 +31,8 @@ pub trait ExprRewriter {
             ExprImpl::CorrelatedInputRef(inner) => self.rewrite_correlated_input_ref(*inner),
             ExprImpl::TableFunction(inner) => self.rewrite_table_function(*inner),
             ExprImpl::WindowFunction(inner) => self.rewrite_window_function(*inner),
+            ExprImpl::UserDefinedFunction(inner) => self.rewrite_user_defined_function(*inner),
+            ExprImpl::Parameter(inner) => self.rewrite_parameter(*inner),
         }
     }
     fn rewrite_function_call(&mut self, func_call: FunctionCall) -> ExprImpl {
@@ -65,6 +67,9 @@ pub trait ExprRewriter {
     fn rewrite_correlated_input_ref(&mut self, input_ref: CorrelatedInputRef) -> ExprImpl {
         input_ref.into()
     }
+    fn rewrite_parameter(&mut self, parameter: Parameter) -> ExprImpl {
+        parameter.into()
+    }
     fn rewrite_table_function(&mut self, table_func: TableFunction) -> ExprImpl {
         let TableFunction {
             args,
@@ -89,6 +94,7 @@ pub trait ExprRewriter {
             function_type,
             partition_by,
             order_by,
+            rows_frame,
         } = window_func;
         let args = args
             .into_iter()
@@ -100,7 +106,16 @@ pub trait ExprRewriter {
             function_type,
             partition_by,
             order_by,
+            rows_frame,
         }
         .into()
     }
+    fn rewrite_user_defined_function(&mut self, udf: UserDefinedFunction) -> ExprImpl {
+        let UserDefinedFunction { args, catalog } = udf;
+        let args = args
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+        UserDefinedFunction { args, catalog }.into()
+    }
 }
@@ -107,7 +107,11 @@ impl FunctionCall {
     }
 
     /// Create a cast expr over `child` to `target` type in `allows` context.
-    pub fn new_cast(child: ExprImpl, target: DataType, allows: CastContext) -> Result<ExprImpl> {
+    pub fn new_cast(
+        mut child: ExprImpl,
+        target: DataType,
+        allows: CastContext,
+    ) -> Result<ExprImpl> {
         if is_row_function(&child) {
             return Self::cast_nested(child, target, allows);
         }
@@ -116,6 +120,10 @@ impl FunctionCall {
             Ok(Literal::new(None, target).into())
         } else if source == target {
             Ok(child)
+        // The type of a parameter is inferred from its first cast, which needs no actual cast.
+        } else if let ExprImpl::Parameter(parameter) = &mut child && !parameter.has_infer() {
+            parameter.cast_infer_type(target);
+            Ok(child)
         // Casting from unknown is allowed in all context. And PostgreSQL actually does the parsing
         // in frontend.
         } else if child.is_unknown() || cast_ok(&source, &target, allows) {
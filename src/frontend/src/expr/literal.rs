@@ -13,10 +13,12 @@
 // limitations under the License.
 
 use risingwave_common::array::list_array::display_for_explain;
+use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::to_text::ToText;
 use risingwave_common::types::{literal_type_match, DataType, Datum, ScalarImpl};
-use risingwave_common::util::value_encoding::serialize_datum_to_bytes;
+use risingwave_common::util::value_encoding::{deserialize_datum, serialize_datum_to_bytes};
 use risingwave_pb::expr::expr_node::RexNode;
+use risingwave_pb::expr::ExprNode;
 
 use super::Expr;
 use crate::expr::ExprType;
@@ -36,9 +38,10 @@ impl std::fmt::Debug for Literal {
         } else {
             match &self.data {
                 None => write!(f, "null"),
-                // Add single quotation marks for string and interval literals
+                // Add single quotation marks for string, interval and jsonb literals
                 Some(ScalarImpl::Utf8(v)) => write!(f, "'{}'", v),
                 Some(ScalarImpl::Interval(v)) => write!(f, "'{}'", v),
+                Some(ScalarImpl::Jsonb(v)) => write!(f, "'{}'", v),
                 Some(ScalarImpl::Bool(v)) => write!(f, "{}", v),
                 Some(ScalarImpl::List(v)) => write!(f, "{}", display_for_explain(v)),
                 Some(v) => write!(f, "{}", v.as_scalar_ref_impl().to_text()),
@@ -58,6 +61,24 @@ impl Literal {
         ExprType::ConstantValue
     }
 
+    /// Convert a constant `ExprNode` back to a literal, e.g. a column default value stored in the
+    /// catalog.
+    pub fn from_expr_proto(proto: &ExprNode) -> Result<Self> {
+        let data_type = DataType::from(proto.return_type.as_ref().unwrap());
+        let data = match &proto.rex_node {
+            Some(RexNode::Constant(datum)) => deserialize_datum(datum.body.as_slice(), &data_type)?,
+            None => None,
+            _ => {
+                return Err(ErrorCode::InternalError(format!(
+                    "expect a constant expression, got {:?}",
+                    proto.get_expr_type()
+                ))
+                .into())
+            }
+        };
+        Ok(Self::new(data, data_type))
+    }
+
     pub fn get_data(&self) -> &Datum {
         &self.data
     }
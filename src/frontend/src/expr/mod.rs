@@ -26,8 +26,11 @@ mod correlated_input_ref;
 mod function_call;
 mod input_ref;
 mod literal;
+mod parameter;
+mod session_timezone;
 mod subquery;
 mod table_function;
+mod user_defined_function;
 mod window_function;
 
 mod order_by_expr;
@@ -44,8 +47,11 @@ pub use correlated_input_ref::{CorrelatedId, CorrelatedInputRef, Depth};
 pub use function_call::{FunctionCall, FunctionCallDisplay};
 pub use input_ref::{input_ref_to_column_indices, InputRef, InputRefDisplay};
 pub use literal::Literal;
+pub use parameter::{Parameter, ParameterTypes};
+pub use session_timezone::SessionTimezone;
 pub use subquery::{Subquery, SubqueryKind};
 pub use table_function::{TableFunction, TableFunctionType};
+pub use user_defined_function::UserDefinedFunction;
 pub use window_function::{WindowFunction, WindowFunctionType};
 
 pub type ExprType = risingwave_pb::expr::expr_node::Type;
@@ -92,7 +98,9 @@ impl_expr_impl!(
     AggCall,
     Subquery,
     TableFunction,
-    WindowFunction
+    WindowFunction,
+    UserDefinedFunction,
+    Parameter
 );
 
 impl ExprImpl {
@@ -161,9 +169,11 @@ impl ExprImpl {
         matches!(self, ExprImpl::Literal(literal) if literal.get_data().is_none())
     }
 
-    /// Check whether self is a literal NULL or literal string.
+    /// Check whether self is a literal NULL or literal string, or a parameter whose type is not
+    /// inferred yet.
     pub fn is_unknown(&self) -> bool {
         matches!(self, ExprImpl::Literal(literal) if literal.return_type() == DataType::Varchar)
+            || matches!(self, ExprImpl::Parameter(parameter) if !parameter.has_infer())
     }
 
     /// Shorthand to create cast expr to `target` type in implicit context.
@@ -248,7 +258,10 @@ macro_rules! impl_has_variant {
     };
 }
 
-impl_has_variant! {InputRef, Literal, FunctionCall, AggCall, Subquery, TableFunction, WindowFunction}
+impl_has_variant! {
+    InputRef, Literal, FunctionCall, AggCall, Subquery, TableFunction, WindowFunction,
+    UserDefinedFunction
+}
 
 impl ExprImpl {
     /// This function is not meant to be called. In most cases you would want
@@ -288,18 +301,13 @@ impl ExprImpl {
             }
 
             fn visit_subquery(&mut self, subquery: &Subquery) -> bool {
-                use crate::binder::BoundSetExpr;
-
                 let mut has = false;
                 self.depth += 1;
-                match &subquery.query.body {
-                    BoundSetExpr::Select(select) => {
-                        select.exprs().for_each(|expr| has |= self.visit_expr(expr))
-                    }
-                    BoundSetExpr::Values(values) => {
-                        values.exprs().for_each(|expr| has |= self.visit_expr(expr))
-                    }
-                }
+                subquery
+                    .query
+                    .body
+                    .exprs()
+                    .for_each(|expr| has |= self.visit_expr(expr));
                 self.depth -= 1;
 
                 has
@@ -328,19 +336,13 @@ impl ExprImpl {
             }
 
             fn visit_subquery(&mut self, subquery: &Subquery) -> bool {
-                use crate::binder::BoundSetExpr;
-                match &subquery.query.body {
-                    BoundSetExpr::Select(select) => select
-                        .exprs()
-                        .map(|expr| self.visit_expr(expr))
-                        .reduce(Self::merge)
-                        .unwrap_or_default(),
-                    BoundSetExpr::Values(values) => values
-                        .exprs()
-                        .map(|expr| self.visit_expr(expr))
-                        .reduce(Self::merge)
-                        .unwrap_or_default(),
-                }
+                subquery
+                    .query
+                    .body
+                    .exprs()
+                    .map(|expr| self.visit_expr(expr))
+                    .reduce(Self::merge)
+                    .unwrap_or_default()
             }
         }
 
@@ -348,6 +350,46 @@ impl ExprImpl {
         visitor.visit_expr(self)
     }
 
+    /// Checks whether the expr calls `grouping(...)`, which can only be evaluated over the
+    /// output of an aggregation with GROUP BY. It will not traverse inside subqueries.
+    pub fn has_grouping(&self) -> bool {
+        struct Has {}
+
+        impl ExprVisitor<bool> for Has {
+            fn merge(a: bool, b: bool) -> bool {
+                a | b
+            }
+
+            fn visit_function_call(&mut self, func_call: &FunctionCall) -> bool {
+                func_call.get_expr_type() == ExprType::Grouping
+                    || func_call.inputs().iter().any(|expr| self.visit_expr(expr))
+            }
+        }
+
+        let mut visitor = Has {};
+        visitor.visit_expr(self)
+    }
+
+    /// Checks whether the expr calls `now()`, which can only be evaluated in a batch query, or be
+    /// compared with by a filter in a streaming query. It will not traverse inside subqueries.
+    pub fn has_now(&self) -> bool {
+        struct Has {}
+
+        impl ExprVisitor<bool> for Has {
+            fn merge(a: bool, b: bool) -> bool {
+                a | b
+            }
+
+            fn visit_function_call(&mut self, func_call: &FunctionCall) -> bool {
+                func_call.get_expr_type() == ExprType::Now
+                    || func_call.inputs().iter().any(|expr| self.visit_expr(expr))
+            }
+        }
+
+        let mut visitor = Has {};
+        visitor.visit_expr(self)
+    }
+
     /// Collect `CorrelatedInputRef`s in `ExprImpl` by relative `depth`, return their indices, and
     /// assign absolute `correlated_id` for them.
     pub fn collect_correlated_indices_by_depth_and_assign_id(
@@ -355,54 +397,63 @@ impl ExprImpl {
         depth: Depth,
         correlated_id: CorrelatedId,
     ) -> Vec<usize> {
-        struct Collector {
+        let mut correlated_indices = vec![];
+        self.visit_correlated_input_refs_by_depth(depth, &mut |correlated_input_ref| {
+            correlated_indices.push(correlated_input_ref.index());
+            correlated_input_ref.set_correlated_id(correlated_id);
+        });
+        correlated_indices
+    }
+
+    /// Visit `CorrelatedInputRef`s in `ExprImpl` by relative `depth`, including those in the
+    /// relations of subqueries.
+    pub fn visit_correlated_input_refs_by_depth(
+        &mut self,
+        depth: Depth,
+        f: &mut dyn FnMut(&mut CorrelatedInputRef),
+    ) {
+        struct Visitor<'a> {
             depth: Depth,
-            correlated_indices: Vec<usize>,
-            correlated_id: CorrelatedId,
+            f: &'a mut dyn FnMut(&mut CorrelatedInputRef),
         }
 
-        impl ExprMutator for Collector {
+        impl ExprMutator for Visitor<'_> {
             fn visit_correlated_input_ref(
                 &mut self,
                 correlated_input_ref: &mut CorrelatedInputRef,
             ) {
                 if correlated_input_ref.depth() == self.depth {
-                    self.correlated_indices.push(correlated_input_ref.index());
-                    correlated_input_ref.set_correlated_id(self.correlated_id);
+                    (self.f)(correlated_input_ref);
                 }
             }
 
             fn visit_subquery(&mut self, subquery: &mut Subquery) {
-                use crate::binder::BoundSetExpr;
-
-                self.depth += 1;
-                match &mut subquery.query.body {
-                    BoundSetExpr::Select(select) => {
-                        select.exprs_mut().for_each(|expr| self.visit_expr(expr))
-                    }
-                    BoundSetExpr::Values(values) => {
-                        values.exprs_mut().for_each(|expr| self.visit_expr(expr))
-                    }
-                }
-                self.depth -= 1;
+                subquery
+                    .query
+                    .visit_correlated_input_refs_by_depth(self.depth + 1, self.f);
             }
         }
 
-        let mut collector = Collector {
-            depth,
-            correlated_indices: vec![],
-            correlated_id,
-        };
-        collector.visit_expr(self);
-        collector.correlated_indices
+        let mut visitor = Visitor { depth, f };
+        visitor.visit_expr(self);
     }
 
     /// Checks whether this is a constant expr that can be evaluated over a dummy chunk.
     /// Equivalent to `!has_input_ref && !has_agg_call && !has_subquery &&
-    /// !has_correlated_input_ref` but checks them in one pass.
+    /// !has_correlated_input_ref && !has_now` but checks them in one pass.
     pub fn is_const(&self) -> bool {
+        self.is_const_inner(false)
+    }
+
+    /// Checks whether the expr only depends on `now()`, e.g. `now() - INTERVAL '1 day'`.
+    pub fn is_now_offset(&self) -> bool {
+        self.has_now() && self.is_const_inner(true)
+    }
+
+    fn is_const_inner(&self, allow_now: bool) -> bool {
         struct Has {
             has: bool,
+            allow_now: bool,
         }
         impl ExprVisitor<()> for Has {
             fn merge(_: (), _: ()) {}
@@ -410,12 +461,18 @@ impl ExprImpl {
             fn visit_expr(&mut self, expr: &ExprImpl) {
                 match expr {
                     ExprImpl::Literal(_inner) => {}
+                    ExprImpl::FunctionCall(inner) if inner.get_expr_type() == ExprType::Now => {
+                        self.has |= !self.allow_now
+                    }
                     ExprImpl::FunctionCall(inner) => self.visit_function_call(inner),
                     _ => self.has = true,
                 }
             }
         }
-        let mut visitor = Has { has: false };
+        let mut visitor = Has {
+            has: false,
+            allow_now,
+        };
         visitor.visit_expr(self);
         !visitor.has
     }
@@ -526,16 +583,6 @@ impl ExprImpl {
     }
 
     pub fn as_comparison_const(&self) -> Option<(InputRef, ExprType, ExprImpl)> {
-        fn reverse_comparison(comparison: ExprType) -> ExprType {
-            match comparison {
-                ExprType::LessThan => ExprType::GreaterThan,
-                ExprType::LessThanOrEqual => ExprType::GreaterThanOrEqual,
-                ExprType::GreaterThan => ExprType::LessThan,
-                ExprType::GreaterThanOrEqual => ExprType::LessThanOrEqual,
-                _ => unreachable!(),
-            }
-        }
-
         if let ExprImpl::FunctionCall(function_call) = self {
             match function_call.get_expr_type() {
                 ty @ (ExprType::LessThan
@@ -558,6 +605,32 @@ impl ExprImpl {
         }
     }
 
+    /// Returns the `(input_expr, comparison, now_expr)` of a comparison between an expression of
+    /// the input and an expression only depending on `now()`, where the comparison is reversed if
+    /// the `now()` expression is on the left.
+    pub fn as_now_comparison_cond(&self) -> Option<(ExprImpl, ExprType, ExprImpl)> {
+        if let ExprImpl::FunctionCall(function_call) = self {
+            match function_call.get_expr_type() {
+                ty @ (ExprType::LessThan
+                | ExprType::LessThanOrEqual
+                | ExprType::GreaterThan
+                | ExprType::GreaterThanOrEqual) => {
+                    let (_, op1, op2) = function_call.clone().decompose_as_binary();
+                    if !op1.has_now() && op2.is_now_offset() {
+                        Some((op1, ty, op2))
+                    } else if op1.is_now_offset() && !op2.has_now() {
+                        Some((op2, reverse_comparison(ty), op1))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
     pub fn as_in_const_list(&self) -> Option<(InputRef, Vec<ExprImpl>)> {
         if let ExprImpl::FunctionCall(function_call) = self &&
         function_call.get_expr_type() == ExprType::In {
@@ -610,6 +683,8 @@ impl Expr for ExprImpl {
             ExprImpl::CorrelatedInputRef(expr) => expr.return_type(),
             ExprImpl::TableFunction(expr) => expr.return_type(),
             ExprImpl::WindowFunction(expr) => expr.return_type(),
+            ExprImpl::UserDefinedFunction(expr) => expr.return_type(),
+            ExprImpl::Parameter(expr) => expr.return_type(),
         }
     }
 
@@ -627,6 +702,8 @@ impl Expr for ExprImpl {
             ExprImpl::WindowFunction(_e) => {
                 unreachable!("Window function should not be converted to ExprNode")
             }
+            ExprImpl::UserDefinedFunction(e) => e.to_expr_proto(),
+            ExprImpl::Parameter(e) => e.to_expr_proto(),
         }
     }
 }
@@ -658,6 +735,10 @@ impl std::fmt::Debug for ExprImpl {
                 }
                 Self::TableFunction(arg0) => f.debug_tuple("TableFunction").field(arg0).finish(),
                 Self::WindowFunction(arg0) => f.debug_tuple("WindowFunction").field(arg0).finish(),
+                Self::UserDefinedFunction(arg0) => {
+                    f.debug_tuple("UserDefinedFunction").field(arg0).finish()
+                }
+                Self::Parameter(arg0) => f.debug_tuple("Parameter").field(arg0).finish(),
             };
         }
         match self {
@@ -669,6 +750,8 @@ impl std::fmt::Debug for ExprImpl {
             Self::CorrelatedInputRef(x) => write!(f, "{:?}", x),
             Self::TableFunction(x) => write!(f, "{:?}", x),
             Self::WindowFunction(x) => write!(f, "{:?}", x),
+            Self::UserDefinedFunction(x) => write!(f, "{:?}", x),
+            Self::Parameter(x) => write!(f, "{:?}", x),
         }
     }
 }
@@ -710,6 +793,23 @@ impl std::fmt::Debug for ExprDisplay<'_> {
                 // TODO: WindowFunctionCallVerboseDisplay
                 write!(f, "{:?}", x)
             }
+            ExprImpl::UserDefinedFunction(x) => write!(
+                f,
+                "{}({})",
+                x.catalog.name,
+                x.args
+                    .iter()
+                    .map(|arg| format!(
+                        "{:?}",
+                        ExprDisplay {
+                            expr: arg,
+                            input_schema: self.input_schema
+                        }
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ExprImpl::Parameter(x) => write!(f, "{:?}", x),
         }
     }
 }
@@ -720,6 +820,16 @@ impl std::fmt::Display for ExprDisplay<'_> {
     }
 }
 
+fn reverse_comparison(comparison: ExprType) -> ExprType {
+    match comparison {
+        ExprType::LessThan => ExprType::GreaterThan,
+        ExprType::LessThanOrEqual => ExprType::GreaterThanOrEqual,
+        ExprType::GreaterThan => ExprType::LessThan,
+        ExprType::GreaterThanOrEqual => ExprType::LessThanOrEqual,
+        _ => unreachable!(),
+    }
+}
+
 #[cfg(test)]
 /// Asserts that the expression is an [`InputRef`] with the given index.
 macro_rules! assert_eq_input_ref {
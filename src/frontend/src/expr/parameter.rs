@@ -0,0 +1,141 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use risingwave_common::types::DataType;
+
+use super::Expr;
+
+/// A positional parameter of a prepared statement, e.g. `$1`, bound before its value is known.
+///
+/// The parameters are replaced by literals when the prepared statement is executed, so they never
+/// reach the optimizer.
+#[derive(Clone)]
+pub struct Parameter {
+    pub index: u64,
+    param_types: ParameterTypes,
+}
+
+impl Parameter {
+    pub fn new(index: u64, mut param_types: ParameterTypes) -> Self {
+        param_types.record_new_param(index);
+        Self { index, param_types }
+    }
+
+    /// Whether the type of the parameter has been specified or inferred.
+    pub fn has_infer(&self) -> bool {
+        self.param_types.has_infer(self.index)
+    }
+
+    /// Infers the type of the parameter from its first use, e.g. a cast.
+    pub fn cast_infer_type(&mut self, data_type: DataType) {
+        self.param_types.record_infer_type(self.index, data_type);
+    }
+}
+
+impl PartialEq for Parameter {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl Eq for Parameter {}
+
+impl Hash for Parameter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl Debug for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Parameter(index: {}, type: {:?})",
+            self.index,
+            self.return_type()
+        )
+    }
+}
+
+impl Expr for Parameter {
+    fn return_type(&self) -> DataType {
+        self.param_types
+            .read_type(self.index)
+            .unwrap_or(DataType::Varchar)
+    }
+
+    fn to_expr_proto(&self) -> risingwave_pb::expr::ExprNode {
+        unreachable!(
+            "Parameter {:?} should have been replaced by its value",
+            self
+        )
+    }
+}
+
+/// The types of the parameters of a prepared statement, shared by all the occurrences of the
+/// parameters during binding. A type is `None` until it is specified or inferred.
+#[derive(Clone, Debug, Default)]
+pub struct ParameterTypes(Arc<RwLock<HashMap<u64, Option<DataType>>>>);
+
+impl ParameterTypes {
+    pub fn new(specified_param_types: Vec<Option<DataType>>) -> Self {
+        let map = specified_param_types
+            .into_iter()
+            .enumerate()
+            .map(|(index, data_type)| ((index + 1) as u64, data_type))
+            .collect();
+        Self(Arc::new(RwLock::new(map)))
+    }
+
+    pub fn has_infer(&self, index: u64) -> bool {
+        self.0.read().get(&index).unwrap().is_some()
+    }
+
+    pub fn read_type(&self, index: u64) -> Option<DataType> {
+        self.0.read().get(&index).cloned().flatten()
+    }
+
+    pub fn record_new_param(&mut self, index: u64) {
+        self.0.write().entry(index).or_insert(None);
+    }
+
+    pub fn record_infer_type(&mut self, index: u64, data_type: DataType) {
+        assert!(
+            !self.has_infer(index),
+            "The type of the parameter has been inferred"
+        );
+        self.0.write().insert(index, Some(data_type));
+    }
+
+    /// Exports the types of the parameters in order. Parameters that are never referenced or
+    /// whose types can not be inferred default to `varchar`.
+    pub fn export(&self) -> Vec<DataType> {
+        let map = self.0.read();
+        let len = map.keys().max().copied().unwrap_or(0);
+        (1..=len)
+            .map(|index| {
+                map.get(&index)
+                    .cloned()
+                    .flatten()
+                    .unwrap_or(DataType::Varchar)
+            })
+            .collect()
+    }
+}
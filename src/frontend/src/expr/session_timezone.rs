@@ -0,0 +1,144 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+
+use super::{Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall};
+
+/// `SessionTimezone` rewrites the expressions which implicitly depend on the session TimeZone
+/// into the explicit ones, so that they can be evaluated anywhere, e.g. on the compute nodes.
+///
+/// * `CAST(timestamp AS timestamptz)` and `CAST(timestamptz AS timestamp)` are rewritten to `AT
+///   TIME ZONE`, as well as the casts between `date` / `time` and `timestamptz` that go through
+///   `timestamp`.
+/// * `CAST(varchar AS timestamptz)` and `CAST(timestamptz AS varchar)` are rewritten to
+///   `CastWithTimeZone`, which parses and formats the local time in the time zone.
+///
+/// Note that a view or materialized view keeps the session TimeZone used when it's created.
+pub struct SessionTimezone {
+    timezone: String,
+}
+
+impl SessionTimezone {
+    pub fn new(timezone: String) -> Self {
+        Self { timezone }
+    }
+
+    pub fn timezone(&self) -> &str {
+        &self.timezone
+    }
+
+    /// Returns `input AT TIME ZONE <session time zone>`.
+    pub fn at_time_zone(&self, input: ExprImpl) -> ExprImpl {
+        let return_type = match input.return_type() {
+            DataType::Timestamp => DataType::Timestampz,
+            DataType::Timestampz => DataType::Timestamp,
+            t => unreachable!("AT TIME ZONE on {:?}", t),
+        };
+        FunctionCall::new_unchecked(
+            ExprType::AtTimeZone,
+            vec![input, self.timezone_literal()],
+            return_type,
+        )
+        .into()
+    }
+
+    fn timezone_literal(&self) -> ExprImpl {
+        ExprImpl::literal_varchar(self.timezone.clone())
+    }
+
+    fn rewrite_cast(&self, input: ExprImpl, target: DataType) -> ExprImpl {
+        match (input.return_type(), &target) {
+            (DataType::Timestamp, DataType::Timestampz)
+            | (DataType::Timestampz, DataType::Timestamp) => self.at_time_zone(input),
+            (DataType::Date, DataType::Timestampz) => {
+                let timestamp =
+                    FunctionCall::new_unchecked(ExprType::Cast, vec![input], DataType::Timestamp);
+                self.at_time_zone(timestamp.into())
+            }
+            (DataType::Timestampz, DataType::Date | DataType::Time) => {
+                FunctionCall::new_unchecked(ExprType::Cast, vec![self.at_time_zone(input)], target)
+                    .into()
+            }
+            (DataType::Varchar, DataType::Timestampz)
+            | (DataType::Timestampz, DataType::Varchar) => FunctionCall::new_unchecked(
+                ExprType::CastWithTimeZone,
+                vec![input, self.timezone_literal()],
+                target,
+            )
+            .into(),
+            _ => FunctionCall::new_unchecked(ExprType::Cast, vec![input], target).into(),
+        }
+    }
+}
+
+impl ExprRewriter for SessionTimezone {
+    fn rewrite_function_call(&mut self, func_call: FunctionCall) -> ExprImpl {
+        let (func_type, inputs, ret) = func_call.decompose();
+        let mut inputs: Vec<ExprImpl> = inputs
+            .into_iter()
+            .map(|expr| self.rewrite_expr(expr))
+            .collect();
+        if func_type == ExprType::Cast {
+            return self.rewrite_cast(inputs.pop().unwrap(), ret);
+        }
+        FunctionCall::new_unchecked(func_type, inputs, ret).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::InputRef;
+
+    #[test]
+    fn test_rewrite_cast() {
+        let mut session_timezone = SessionTimezone::new("US/Pacific".to_string());
+        let cast = |input: ExprImpl, target: DataType| -> ExprImpl {
+            FunctionCall::new_unchecked(ExprType::Cast, vec![input], target).into()
+        };
+
+        let timestamp: ExprImpl = InputRef::new(0, DataType::Timestamp).into();
+        let rewritten = session_timezone.rewrite_expr(cast(timestamp, DataType::Timestampz));
+        let func_call = rewritten.as_function_call().unwrap();
+        assert_eq!(func_call.get_expr_type(), ExprType::AtTimeZone);
+        assert_eq!(func_call.return_type(), DataType::Timestampz);
+
+        let timestampz: ExprImpl = InputRef::new(0, DataType::Timestampz).into();
+        let rewritten = session_timezone.rewrite_expr(cast(timestampz.clone(), DataType::Date));
+        let func_call = rewritten.as_function_call().unwrap();
+        assert_eq!(func_call.get_expr_type(), ExprType::Cast);
+        assert_eq!(
+            func_call.inputs()[0]
+                .as_function_call()
+                .unwrap()
+                .get_expr_type(),
+            ExprType::AtTimeZone
+        );
+
+        let rewritten = session_timezone.rewrite_expr(cast(timestampz, DataType::Varchar));
+        let func_call = rewritten.as_function_call().unwrap();
+        assert_eq!(func_call.get_expr_type(), ExprType::CastWithTimeZone);
+        assert_eq!(
+            func_call.inputs()[1],
+            ExprImpl::literal_varchar("US/Pacific".to_string())
+        );
+
+        // Casts independent of the time zone are left unchanged.
+        let int: ExprImpl = InputRef::new(0, DataType::Int32).into();
+        let rewritten = session_timezone.rewrite_expr(cast(int, DataType::Int64));
+        let func_call = rewritten.as_function_call().unwrap();
+        assert_eq!(func_call.get_expr_type(), ExprType::Cast);
+    }
+}
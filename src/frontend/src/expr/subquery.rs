@@ -55,9 +55,12 @@ impl Subquery {
         depth: Depth,
         correlated_id: CorrelatedId,
     ) -> Vec<usize> {
-        let mut correlated_indices = self
-            .query
-            .collect_correlated_indices_by_depth_and_assign_id(depth + 1, correlated_id);
+        let mut correlated_indices = vec![];
+        self.query
+            .visit_correlated_input_refs_by_depth(depth + 1, &mut |correlated_input_ref| {
+                correlated_indices.push(correlated_input_ref.index());
+                correlated_input_ref.set_correlated_id(correlated_id);
+            });
         correlated_indices.sort();
         correlated_indices.dedup();
         correlated_indices
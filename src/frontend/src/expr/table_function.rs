@@ -20,7 +20,7 @@ use risingwave_common::types::{unnested_list_type, DataType, ScalarImpl};
 use risingwave_pb::expr::table_function::Type;
 use risingwave_pb::expr::TableFunction as TableFunctionProst;
 
-use super::{Expr, ExprImpl, ExprRewriter, Result};
+use super::{Expr, ExprImpl, ExprRewriter, Literal, Result};
 
 /// A table function takes a row as input and returns a table. It is also known as Set-Returning
 /// Function.
@@ -37,16 +37,20 @@ pub struct TableFunction {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum TableFunctionType {
     Generate,
+    Range,
     Unnest,
     RegexpMatches,
+    JsonbArrayElements,
 }
 
 impl TableFunctionType {
     fn to_protobuf(self) -> Type {
         match self {
             TableFunctionType::Generate => Type::Generate,
+            TableFunctionType::Range => Type::Range,
             TableFunctionType::Unnest => Type::Unnest,
             TableFunctionType::RegexpMatches => Type::RegexpMatches,
+            TableFunctionType::JsonbArrayElements => Type::JsonbArrayElements,
         }
     }
 }
@@ -55,8 +59,10 @@ impl TableFunctionType {
     pub fn name(&self) -> &str {
         match self {
             TableFunctionType::Generate => "generate_series",
+            TableFunctionType::Range => "range",
             TableFunctionType::Unnest => "unnest",
             TableFunctionType::RegexpMatches => "regexp_matches",
+            TableFunctionType::JsonbArrayElements => "jsonb_array_elements",
         }
     }
 }
@@ -67,10 +73,14 @@ impl FromStr for TableFunctionType {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         if s.eq_ignore_ascii_case("generate_series") {
             Ok(TableFunctionType::Generate)
+        } else if s.eq_ignore_ascii_case("range") {
+            Ok(TableFunctionType::Range)
         } else if s.eq_ignore_ascii_case("unnest") {
             Ok(TableFunctionType::Unnest)
         } else if s.eq_ignore_ascii_case("regexp_matches") {
             Ok(TableFunctionType::RegexpMatches)
+        } else if s.eq_ignore_ascii_case("jsonb_array_elements") {
+            Ok(TableFunctionType::JsonbArrayElements)
         } else {
             Err(())
         }
@@ -85,38 +95,61 @@ impl TableFunction {
         // Current implementation is copied from legacy code.
 
         match func_type {
-            TableFunctionType::Generate => {
+            TableFunctionType::Generate | TableFunctionType::Range => {
                 // generate_series ( start timestamp, stop timestamp, step interval ) or
-                // generate_series ( start i32, stop i32, step i32 )
-
-                fn type_check(exprs: &[ExprImpl]) -> Result<DataType> {
-                    let mut exprs = exprs.iter();
-                    let (start, stop, step) = exprs.next_tuple().unwrap();
-                    match (start.return_type(), stop.return_type(), step.return_type()) {
-                        (DataType::Int32, DataType::Int32, DataType::Int32) => Ok(DataType::Int32),
-                        (DataType::Timestamp, DataType::Timestamp, DataType::Interval) => {
-                            Ok(DataType::Timestamp)
+                // generate_series ( start int, stop int [, step int ] ) or
+                // generate_series ( start bigint, stop bigint [, step bigint ] )
+                //
+                // `range` takes the same arguments but excludes `stop` from the result.
+
+                fn type_check(exprs: Vec<ExprImpl>) -> Result<(Vec<ExprImpl>, DataType)> {
+                    let types = exprs.iter().map(|e| e.return_type()).collect_vec();
+                    let return_type = match types.as_slice() {
+                        [DataType::Timestamp, DataType::Timestamp, DataType::Interval] => {
+                            return Ok((exprs, DataType::Timestamp));
                         }
-                        _ => Err(ErrorCode::BindError(
-                            "Invalid arguments for Generate series function".to_string(),
-                        )
-                        .into()),
+                        types if types.iter().all(|t| t.is_int()) => {
+                            if types.contains(&DataType::Int64) {
+                                DataType::Int64
+                            } else {
+                                DataType::Int32
+                            }
+                        }
+                        _ => {
+                            return Err(ErrorCode::BindError(
+                                "Invalid arguments for Generate series function".to_string(),
+                            )
+                            .into())
+                        }
+                    };
+                    let mut exprs: Vec<_> = exprs
+                        .into_iter()
+                        .map(|e| e.cast_implicit(return_type.clone()))
+                        .try_collect()?;
+                    if exprs.len() == 2 {
+                        let one = match return_type {
+                            DataType::Int64 => ScalarImpl::Int64(1),
+                            _ => ScalarImpl::Int32(1),
+                        };
+                        exprs.push(Literal::new(Some(one), return_type.clone()).into());
                     }
+                    Ok((exprs, return_type))
                 }
 
-                if args.len() != 3 {
-                    return Err(ErrorCode::BindError(
-                        "the length of args of generate series function should be 3".to_string(),
-                    )
+                if args.len() != 2 && args.len() != 3 {
+                    return Err(ErrorCode::BindError(format!(
+                        "the length of args of {} function should be 2 or 3",
+                        func_type.name()
+                    ))
                     .into());
                 }
 
-                let data_type = type_check(&args)?;
+                let (args, data_type) = type_check(args)?;
 
                 Ok(TableFunction {
                     args,
                     return_type: data_type,
-                    function_type: TableFunctionType::Generate,
+                    function_type: func_type,
                 })
             }
             TableFunctionType::Unnest => {
@@ -177,6 +210,26 @@ impl TableFunction {
                     function_type: TableFunctionType::RegexpMatches,
                 })
             }
+            TableFunctionType::JsonbArrayElements => {
+                if args.len() != 1 {
+                    return Err(ErrorCode::BindError(
+                        "the length of args of jsonb_array_elements function should be 1"
+                            .to_string(),
+                    )
+                    .into());
+                }
+
+                let expr = args
+                    .into_iter()
+                    .next()
+                    .unwrap()
+                    .cast_implicit(DataType::Jsonb)?;
+                Ok(TableFunction {
+                    args: vec![expr],
+                    return_type: DataType::Jsonb,
+                    function_type: TableFunctionType::JsonbArrayElements,
+                })
+            }
         }
     }
 
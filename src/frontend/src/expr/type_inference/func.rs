@@ -290,10 +290,34 @@ fn infer_type_for_special(
                 .into()),
             }
         }
+        ExprType::JsonbAccessPath => {
+            ensure_arity!("jsonb_extract_path", | inputs | == 2);
+            let inputs_owned = std::mem::take(inputs);
+            *inputs = inputs_owned
+                .into_iter()
+                .enumerate()
+                .map(|(i, input)| match i {
+                    0 => input.cast_implicit(DataType::Jsonb),
+                    // the path is a `text[]`, which may be written as a `'{a,1}'` literal
+                    _ => input.cast_implicit(DataType::List {
+                        datatype: Box::new(DataType::Varchar),
+                    }),
+                })
+                .try_collect()?;
+            Ok(Some(DataType::Jsonb))
+        }
         ExprType::Vnode => {
             ensure_arity!("vnode", 1 <= | inputs |);
             Ok(Some(DataType::Int16))
         }
+        ExprType::Grouping => {
+            ensure_arity!("grouping", 1 <= | inputs | <= 31);
+            Ok(Some(DataType::Int32))
+        }
+        ExprType::Now => {
+            ensure_arity!("now", | inputs | == 0);
+            Ok(Some(DataType::Timestampz))
+        }
         _ => Ok(None),
     }
 }
@@ -685,6 +709,7 @@ fn build_type_derive_map() -> FuncSigMap {
         T::Timestampz,
         T::Time,
         T::Interval,
+        T::Jsonb,
     ];
     let num_types = [
         T::Int16,
@@ -816,6 +841,22 @@ fn build_type_derive_map() -> FuncSigMap {
     map.insert(E::ToTimestamp, vec![T::Float64], T::Timestampz);
     map.insert(E::AtTimeZone, vec![T::Timestamp, T::Varchar], T::Timestampz);
     map.insert(E::AtTimeZone, vec![T::Timestampz, T::Varchar], T::Timestamp);
+    map.insert(E::DateTrunc, vec![T::Varchar, T::Timestamp], T::Timestamp);
+    map.insert(
+        E::DateTrunc,
+        vec![T::Varchar, T::Timestampz, T::Varchar],
+        T::Timestampz,
+    );
+    map.insert(
+        E::CastWithTimeZone,
+        vec![T::Varchar, T::Varchar],
+        T::Timestampz,
+    );
+    map.insert(
+        E::CastWithTimeZone,
+        vec![T::Timestampz, T::Varchar],
+        T::Varchar,
+    );
 
     // string expressions
     for e in [E::Trim, E::Ltrim, E::Rtrim, E::Lower, E::Upper, E::Md5] {
@@ -860,6 +901,12 @@ fn build_type_derive_map() -> FuncSigMap {
     // TODO: Support more `to_char` types.
     map.insert(E::ToChar, vec![T::Timestamp, T::Varchar], T::Varchar);
 
+    // jsonb expressions
+    for t in [T::Varchar, T::Int32] {
+        map.insert(E::JsonbAccessInner, vec![T::Jsonb, t], T::Jsonb);
+        map.insert(E::JsonbAccessStr, vec![T::Jsonb, t], T::Varchar);
+    }
+
     map
 }
 
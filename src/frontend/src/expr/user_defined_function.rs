@@ -0,0 +1,83 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use risingwave_common::types::DataType;
+use risingwave_pb::expr::expr_node::RexNode;
+use risingwave_pb::expr::UserDefinedFunction as UserDefinedFunctionProst;
+
+use super::{Expr, ExprImpl, ExprNode, ExprType};
+use crate::catalog::function_catalog::FunctionCatalog;
+
+/// A call to a user-defined function. The function is evaluated by the external UDF server or the
+/// WASM module recorded in its catalog.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct UserDefinedFunction {
+    pub args: Vec<ExprImpl>,
+    pub catalog: Arc<FunctionCatalog>,
+}
+
+impl UserDefinedFunction {
+    pub fn new(catalog: Arc<FunctionCatalog>, args: Vec<ExprImpl>) -> Self {
+        Self { args, catalog }
+    }
+}
+
+impl std::fmt::Debug for UserDefinedFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.debug_struct("UserDefinedFunction")
+                .field("name", &self.catalog.name)
+                .field("args", &self.args)
+                .field("return_type", &self.catalog.return_type)
+                .finish()
+        } else {
+            write!(
+                f,
+                "{}({})",
+                self.catalog.name,
+                self.args.iter().map(|arg| format!("{:?}", arg)).join(", ")
+            )
+        }
+    }
+}
+
+impl Expr for UserDefinedFunction {
+    fn return_type(&self) -> DataType {
+        self.catalog.return_type.clone()
+    }
+
+    fn to_expr_proto(&self) -> ExprNode {
+        ExprNode {
+            expr_type: ExprType::Udf.into(),
+            return_type: Some(self.return_type().to_protobuf()),
+            rex_node: Some(RexNode::Udf(UserDefinedFunctionProst {
+                children: self.args.iter().map(Expr::to_expr_proto).collect(),
+                name: self.catalog.name.clone(),
+                arg_types: self
+                    .catalog
+                    .arg_types
+                    .iter()
+                    .map(|t| t.to_protobuf())
+                    .collect(),
+                language: self.catalog.language.clone(),
+                link: self.catalog.link.clone(),
+                identifier: self.catalog.identifier.clone(),
+                wasm_binary: self.catalog.wasm_binary.clone(),
+            })),
+        }
+    }
+}
@@ -182,6 +182,27 @@ pub fn try_get_bool_constant(expr: &ExprImpl) -> Option<bool> {
     None
 }
 
+/// Returns the index of the only input column that `expr` depends on, if `expr` is monotonically
+/// non-decreasing on it. In that case, a watermark of the input column can be transformed into a
+/// watermark of `expr` by evaluating `expr` on the watermark value.
+pub fn try_derive_watermark(expr: &ExprImpl) -> Option<usize> {
+    match expr {
+        ExprImpl::InputRef(input_ref) => Some(input_ref.index()),
+        ExprImpl::FunctionCall(func_call) => {
+            match (func_call.get_expr_type(), func_call.inputs()) {
+                (ExprType::TumbleStart | ExprType::Add | ExprType::Subtract, [lhs, rhs])
+                    if rhs.is_const() =>
+                {
+                    try_derive_watermark(lhs)
+                }
+                (ExprType::Add, [lhs, rhs]) if lhs.is_const() => try_derive_watermark(rhs),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 /// [`boolean_constant_fold_and`] takes the left hand side and right hands side of a [`Type::And`]
 /// operator. It is required that the the lhs should always be a constant.
 fn boolean_constant_fold_and(constant_lhs: ExprImpl, rhs: ExprImpl) -> ExprImpl {
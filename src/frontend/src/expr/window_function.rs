@@ -15,11 +15,13 @@
 use std::str::FromStr;
 
 use itertools::Itertools;
-use parse_display::Display;
 use risingwave_common::error::ErrorCode;
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_expr::expr::AggKind;
+use risingwave_expr::window_function::RowsFrame;
+use risingwave_pb::expr::window_function;
 
-use super::{Expr, ExprImpl, OrderBy, Result};
+use super::{AggCall, Expr, ExprImpl, OrderBy, Result};
 
 /// A window function performs a calculation across a set of table rows that are somehow related to
 /// the current row, according to the window spec `OVER (PARTITION BY .. ORDER BY ..)`.
@@ -34,14 +36,18 @@ pub struct WindowFunction {
     pub function_type: WindowFunctionType,
     pub partition_by: Vec<ExprImpl>,
     pub order_by: OrderBy,
+    /// The `ROWS` frame of an aggregate window function, or `None` for the default frame.
+    pub rows_frame: Option<RowsFrame>,
 }
 
-#[derive(Debug, Display, Copy, Clone, PartialEq, Eq, Hash)]
-#[display(style = "SNAKE_CASE")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum WindowFunctionType {
     RowNumber,
     Rank,
     DenseRank,
+    Lag,
+    Lead,
+    Aggregate(AggKind),
 }
 
 impl WindowFunctionType {
@@ -53,6 +59,32 @@ impl WindowFunctionType {
                 | WindowFunctionType::DenseRank
         )
     }
+
+    pub fn to_prost(self) -> window_function::Type {
+        match self {
+            WindowFunctionType::RowNumber => window_function::Type::RowNumber,
+            WindowFunctionType::Rank => window_function::Type::Rank,
+            WindowFunctionType::DenseRank => window_function::Type::DenseRank,
+            WindowFunctionType::Lag => window_function::Type::Lag,
+            WindowFunctionType::Lead => window_function::Type::Lead,
+            WindowFunctionType::Aggregate(_) => window_function::Type::Aggregate,
+        }
+    }
+}
+
+impl std::fmt::Display for WindowFunctionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFunctionType::RowNumber => f.write_str("ROW_NUMBER"),
+            WindowFunctionType::Rank => f.write_str("RANK"),
+            WindowFunctionType::DenseRank => f.write_str("DENSE_RANK"),
+            WindowFunctionType::Lag => f.write_str("LAG"),
+            WindowFunctionType::Lead => f.write_str("LEAD"),
+            WindowFunctionType::Aggregate(kind) => {
+                write!(f, "{}", kind.to_string().to_ascii_uppercase())
+            }
+        }
+    }
 }
 
 impl FromStr for WindowFunctionType {
@@ -63,8 +95,10 @@ impl FromStr for WindowFunctionType {
             "row_number" => Ok(WindowFunctionType::RowNumber),
             "rank" => Ok(WindowFunctionType::Rank),
             "dense_rank" => Ok(WindowFunctionType::DenseRank),
+            "lag" => Ok(WindowFunctionType::Lag),
+            "lead" => Ok(WindowFunctionType::Lead),
             _ => Err(ErrorCode::NotImplemented(
-                format!("unknown table function kind: {s}"),
+                format!("unknown window function kind: {s}"),
                 None.into(),
             )),
         }
@@ -79,20 +113,68 @@ impl WindowFunction {
         partition_by: Vec<ExprImpl>,
         order_by: OrderBy,
         args: Vec<ExprImpl>,
+        rows_frame: Option<RowsFrame>,
     ) -> Result<Self> {
-        if !args.is_empty() {
-            return Err(ErrorCode::BindError(format!(
-                "the length of args of {function_type} function should be 0"
-            ))
-            .into());
-        }
+        let return_type = match function_type {
+            WindowFunctionType::RowNumber
+            | WindowFunctionType::Rank
+            | WindowFunctionType::DenseRank => {
+                if !args.is_empty() {
+                    return Err(ErrorCode::BindError(format!(
+                        "the length of args of {function_type} function should be 0"
+                    ))
+                    .into());
+                }
+                DataType::Int64
+            }
+            WindowFunctionType::Lag | WindowFunctionType::Lead => {
+                match args.as_slice() {
+                    [_] => {}
+                    [_, offset] => {
+                        let is_valid_offset = offset
+                            .as_literal()
+                            .and_then(|l| l.get_data().as_ref())
+                            .map_or(false, |v| matches!(v, ScalarImpl::Int32(v) if *v >= 0));
+                        if !is_valid_offset {
+                            return Err(ErrorCode::NotImplemented(
+                                format!("non-constant offset of {function_type} function"),
+                                None.into(),
+                            )
+                            .into());
+                        }
+                    }
+                    _ => {
+                        return Err(ErrorCode::NotImplemented(
+                            format!("{function_type} function with {} arguments", args.len()),
+                            None.into(),
+                        )
+                        .into())
+                    }
+                }
+                args[0].return_type()
+            }
+            WindowFunctionType::Aggregate(kind) => match kind {
+                AggKind::Sum | AggKind::Count | AggKind::Min | AggKind::Max => {
+                    let arg_types = args.iter().map(|arg| arg.return_type()).collect_vec();
+                    AggCall::infer_return_type(&kind, &arg_types)?
+                }
+                _ => {
+                    return Err(ErrorCode::NotImplemented(
+                        format!("aggregate function as over window function: {}", kind),
+                        4978.into(),
+                    )
+                    .into())
+                }
+            },
+        };
 
         Ok(Self {
             args,
-            return_type: DataType::Int64,
+            return_type,
             function_type,
             partition_by,
             order_by,
+            rows_frame,
         })
     }
 }
@@ -106,9 +188,15 @@ impl std::fmt::Debug for WindowFunction {
                 .field("args", &self.args)
                 .field("partition_by", &self.partition_by)
                 .field("order_by", &format_args!("{}", self.order_by))
+                .field("rows_frame", &self.rows_frame)
                 .finish()
         } else {
-            write!(f, "{}() OVER(", self.function_type)?;
+            write!(
+                f,
+                "{}({:?}) OVER(",
+                self.function_type,
+                self.args.iter().format(", ")
+            )?;
 
             let mut delim = "";
             if !self.partition_by.is_empty() {
@@ -121,6 +209,10 @@ impl std::fmt::Debug for WindowFunction {
             }
             if !self.order_by.sort_exprs.is_empty() {
                 write!(f, "{delim}{}", self.order_by)?;
+                delim = " ";
+            }
+            if let Some(rows_frame) = &self.rows_frame {
+                write!(f, "{delim}{rows_frame}")?;
             }
             f.write_str(")")?;
 
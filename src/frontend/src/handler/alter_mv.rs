@@ -0,0 +1,113 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_sqlparser::ast::{Ident, ObjectName, SetVariableValue, Value};
+
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::catalog::root_catalog::SchemaPath;
+use crate::session::OptimizerContext;
+
+/// The parameters of a materialized view that can be changed by `ALTER MATERIALIZED VIEW ... SET`.
+const ALTERABLE_MV_PARAMS: [&str; 1] = ["backfill_rate_limit"];
+
+pub async fn handle_alter_mv(
+    context: OptimizerContext,
+    name: ObjectName,
+    param: Ident,
+    value: SetVariableValue,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, mv_name) = Binder::resolve_schema_qualified_name(db_name, name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+
+    let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+
+    let rows_per_second = parse_backfill_rate_limit(param, value)?;
+
+    let created = {
+        let reader = session.env().catalog_reader().read_guard();
+        match reader.get_table_by_name(db_name, schema_path, &mv_name) {
+            Ok((table, schema_name)) => {
+                let schema_catalog = reader.get_schema_by_name(db_name, schema_name).unwrap();
+                let schema_owner = schema_catalog.owner();
+                if session.user_id() != table.owner
+                    && session.user_id() != schema_owner
+                    && !check_super_user(&session)
+                {
+                    return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+                }
+
+                if table.associated_source_id().is_some() || table.is_index {
+                    return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                        "\"{}\" is not a materialized view",
+                        mv_name
+                    ))));
+                }
+
+                let database_id = reader.get_database_by_name(db_name)?.id();
+                Some((database_id, schema_catalog.id()))
+            }
+            Err(_) => None,
+        }
+    };
+
+    // A materialized view in creation is not visible in the catalog, which is the case that the
+    // rate limit matters most. It's resolved by the meta service in the schema that it's created
+    // in, with the privilege to create in the schema required instead of the ownership.
+    let (database_id, schema_id) = match created {
+        Some(ids) => ids,
+        None => session.get_database_and_schema_id_for_create(schema_name)?,
+    };
+
+    session
+        .env()
+        .meta_client()
+        .alter_backfill_rate_limit(database_id, schema_id, mv_name, rows_per_second)
+        .await?;
+
+    Ok(PgResponse::empty_result(
+        StatementType::ALTER_MATERIALIZED_VIEW,
+    ))
+}
+
+/// Parses the backfill rate limit from `value`, where `DEFAULT` and zero mean unlimited.
+fn parse_backfill_rate_limit(param: Ident, value: SetVariableValue) -> Result<u64> {
+    let param = param.real_value().to_lowercase();
+    if !ALTERABLE_MV_PARAMS.contains(&param.as_str()) {
+        return Err(ErrorCode::InvalidParameterValue(format!(
+            "unrecognized materialized view parameter \"{}\", expected one of {:?}",
+            param, ALTERABLE_MV_PARAMS
+        ))
+        .into());
+    }
+
+    match value {
+        SetVariableValue::Ident(ident) if ident.real_value().eq_ignore_ascii_case("default") => {
+            Ok(0)
+        }
+        SetVariableValue::Literal(Value::Number(n)) if let Ok(n) = n.parse::<u64>() => Ok(n),
+        value => Err(ErrorCode::InvalidParameterValue(format!(
+            "invalid value {} for \"{}\", expected a non-negative integer or DEFAULT",
+            value, param
+        ))
+        .into()),
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_pb::ddl_service::reset_source_offsets_request::Target;
+use risingwave_sqlparser::ast::{
+    AlterSourceOperation, Ident, ObjectName, SetVariableValue, SourceOffsetTarget, Value,
+};
+
+use super::alter_table::{find_select_star_mviews, replan_select_star_mviews, SelectStarUpstream};
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::catalog::root_catalog::SchemaPath;
+use crate::catalog::source_catalog::SourceCatalog;
+use crate::catalog::{DatabaseId, SchemaId, SourceId};
+use crate::session::{OptimizerContext, SessionImpl};
+
+/// The parameters of a source that can be changed by `ALTER SOURCE ... SET`. They are the rate
+/// limits of each actor of the source, so the total rate scales with the parallelism.
+const ALTERABLE_SOURCE_PARAMS: [&str; 2] = ["rate_limit", "byte_rate_limit"];
+
+pub async fn handle_alter_source(
+    context: OptimizerContext,
+    source_name: ObjectName,
+    operation: AlterSourceOperation,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, source_name) = Binder::resolve_schema_qualified_name(db_name, source_name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+
+    let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+
+    let (mut source, schema_id, database_id) = {
+        let reader = session.env().catalog_reader().read_guard();
+        let (source, schema_name) =
+            reader.get_source_by_name(db_name, schema_path, &source_name)?;
+
+        let schema_catalog = reader
+            .get_schema_by_name(session.database(), schema_name)
+            .unwrap();
+        let schema_owner = schema_catalog.owner();
+        if session.user_id() != source.owner
+            && session.user_id() != schema_owner
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        // The sources of tables are not visible to users.
+        if source.is_table() {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "\"{}\" is not a source",
+                source_name
+            ))));
+        }
+
+        let database_id = reader.get_database_by_name(db_name)?.id();
+        (source.as_ref().clone(), schema_catalog.id(), database_id)
+    };
+
+    match operation {
+        AlterSourceOperation::SetParameter { param, value } => {
+            set_source_parameter(&mut source, param, value)?;
+            let catalog_writer = session.env().catalog_writer();
+            catalog_writer
+                .alter_source(source.to_prost(schema_id, database_id), vec![], vec![])
+                .await?;
+        }
+        AlterSourceOperation::ResetOffsets { target } => {
+            let (target, timestamp_millis) = match target {
+                SourceOffsetTarget::Earliest => (Target::Earliest, 0),
+                SourceOffsetTarget::Latest => (Target::Latest, 0),
+                SourceOffsetTarget::Timestamp(millis) => {
+                    let millis = i64::try_from(millis).map_err(|_| {
+                        ErrorCode::InvalidParameterValue(format!(
+                            "timestamp {} is out of range",
+                            millis
+                        ))
+                    })?;
+                    (Target::Timestamp, millis)
+                }
+            };
+            session
+                .env()
+                .meta_client()
+                .reset_source_offsets(source.id, target, timestamp_millis)
+                .await?;
+        }
+    }
+
+    Ok(PgResponse::empty_result(StatementType::ALTER_SOURCE))
+}
+
+/// Adds the columns proposed by meta to the source, after new nullable fields are detected in its
+/// upstream schema. The materialized views defined with `SELECT *` on the source are re-planned to
+/// select the new columns if `streaming_propagate_schema_change` is enabled, or marked as stale
+/// otherwise. The session must be on the database of the source.
+pub async fn apply_proposed_source_columns(
+    session: &Arc<SessionImpl>,
+    database_id: DatabaseId,
+    schema_id: SchemaId,
+    source_id: SourceId,
+) -> Result<()> {
+    let source = {
+        let reader = session.env().catalog_reader().read_guard();
+        let schema = reader.get_schema_by_id(&database_id, &schema_id)?;
+        // The source may have been dropped since the proposal.
+        let Some(source) = schema.get_source_by_id(&source_id) else {
+            return Ok(());
+        };
+        source.as_ref().clone()
+    };
+    if source.proposed_columns.is_empty() {
+        return Ok(());
+    }
+
+    // The views are bound against the source before the change.
+    let select_star_mviews = find_select_star_mviews(session, SelectStarUpstream::Source(&source))?;
+    let mut new_source = source;
+    let proposed_columns = std::mem::take(&mut new_source.proposed_columns);
+    new_source.columns.extend(proposed_columns);
+    let (select_star_mview_ids, mview_plans) = replan_select_star_mviews(
+        session,
+        database_id,
+        select_star_mviews,
+        SelectStarUpstream::Source(&new_source),
+    )?;
+
+    let catalog_writer = session.env().catalog_writer();
+    let stale_mview_ids = catalog_writer
+        .alter_source(
+            new_source.to_prost(schema_id, database_id),
+            select_star_mview_ids,
+            mview_plans,
+        )
+        .await?;
+    tracing::info!(
+        "added proposed columns to source {}, with the views {:?} marked as stale",
+        new_source.name,
+        stale_mview_ids.iter().map(|id| id.table_id).collect_vec()
+    );
+    Ok(())
+}
+
+/// Set the parameter `param` of the source to `value`, or remove it if the value is `DEFAULT`.
+fn set_source_parameter(
+    source: &mut SourceCatalog,
+    param: Ident,
+    value: SetVariableValue,
+) -> Result<()> {
+    let param = param.real_value().to_lowercase();
+    if !ALTERABLE_SOURCE_PARAMS.contains(&param.as_str()) {
+        return Err(ErrorCode::InvalidParameterValue(format!(
+            "unrecognized source parameter \"{}\", expected one of {:?}",
+            param, ALTERABLE_SOURCE_PARAMS
+        ))
+        .into());
+    }
+
+    match value {
+        SetVariableValue::Ident(ident) if ident.real_value().eq_ignore_ascii_case("default") => {
+            source.properties.remove(&param);
+        }
+        SetVariableValue::Literal(Value::Number(n)) => {
+            check_rate_limit(&param, &n)?;
+            source.properties.insert(param, n);
+        }
+        value => {
+            return Err(ErrorCode::InvalidParameterValue(format!(
+                "invalid value {} for \"{}\", expected a positive integer or DEFAULT",
+                value, param
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Checks the rate limits in the `WITH` options of a new source.
+pub(super) fn check_source_rate_limits(with_properties: &HashMap<String, String>) -> Result<()> {
+    for param in ALTERABLE_SOURCE_PARAMS {
+        if let Some(value) = with_properties.get(param) {
+            check_rate_limit(param, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// A rate limit must be a positive integer, as it's removed instead of being set to zero.
+fn check_rate_limit(param: &str, value: &str) -> Result<()> {
+    match value.trim().parse::<u64>() {
+        Ok(limit) if limit > 0 => Ok(()),
+        _ => Err(ErrorCode::InvalidParameterValue(format!(
+            "invalid value {} for \"{}\", expected a positive integer of the limit per actor",
+            value, param
+        ))
+        .into()),
+    }
+}
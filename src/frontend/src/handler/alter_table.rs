@@ -0,0 +1,757 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_pb::ddl_service::ReplannedMview;
+use risingwave_pb::stream_plan::source_node::Info as StreamPlanInfo;
+use risingwave_sqlparser::ast::{
+    AlterTableOperation, ColumnDef, Ident, ObjectName, SelectItem, SetExpr, Statement, TableFactor,
+};
+use risingwave_sqlparser::parser::Parser;
+
+use super::create_table::bind_sql_columns;
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::{Binder, BoundQuery, BoundSetExpr, Relation};
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::catalog::root_catalog::SchemaPath;
+use crate::catalog::source_catalog::SourceCatalog;
+use crate::catalog::{dropped_column_name, ColumnId, DatabaseId, SchemaId, TableCatalog, TableId};
+use crate::expr::{ExprImpl, InputRef};
+use crate::planner::Planner;
+use crate::session::{OptimizerContext, SessionImpl};
+use crate::stream_fragmenter::build_graph;
+use crate::utils::WithOptions;
+
+pub async fn handle_alter_table(
+    context: OptimizerContext,
+    table_name: ObjectName,
+    operation: AlterTableOperation,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, table_name) = Binder::resolve_schema_qualified_name(db_name, table_name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+
+    let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+
+    let (mut source, mut table, schema_id, database_id) = {
+        let reader = session.env().catalog_reader().read_guard();
+        let (table, schema_name) = reader.get_table_by_name(db_name, schema_path, &table_name)?;
+
+        let schema_catalog = reader
+            .get_schema_by_name(session.database(), schema_name)
+            .unwrap();
+        let schema_owner = schema_catalog.owner();
+        if session.user_id() != table.owner
+            && session.user_id() != schema_owner
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        // Only tables created by `CREATE TABLE` have a version.
+        if table.version.is_none() {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "\"{}\" is not a table",
+                table_name
+            ))));
+        }
+
+        let (source, _) =
+            reader.get_source_by_name(db_name, SchemaPath::Name(schema_name), &table_name)?;
+        let database_id = reader.get_database_by_name(db_name)?.id();
+
+        (
+            source.as_ref().clone(),
+            table.as_ref().clone(),
+            schema_catalog.id(),
+            database_id,
+        )
+    };
+
+    if let StreamPlanInfo::TableSource(info) = &source.info
+        && !info.generated_columns.is_empty()
+    {
+        return Err(ErrorCode::NotImplemented(
+            "alter table with generated columns".to_string(),
+            None.into(),
+        )
+        .into());
+    }
+
+    let mut notice = None;
+    let mut select_star_mviews = vec![];
+    match operation {
+        AlterTableOperation::AddColumn { column_def } => {
+            // The views are bound against the table before the change.
+            select_star_mviews =
+                find_select_star_mviews(&session, SelectStarUpstream::Table(&table))?;
+            add_column(&mut source, &mut table, column_def)?;
+        }
+        AlterTableOperation::DropColumn {
+            column_name,
+            if_exists,
+            cascade,
+        } => {
+            if cascade {
+                return Err(ErrorCode::NotImplemented(
+                    "drop column with cascade".to_string(),
+                    None.into(),
+                )
+                .into());
+            }
+            // The watermark expressions refer to the columns by index, which may be the dropped
+            // one.
+            if !source.watermark_descs.is_empty() {
+                return Err(ErrorCode::NotImplemented(
+                    "drop column of table with watermarks".to_string(),
+                    None.into(),
+                )
+                .into());
+            }
+            if !drop_column(&mut source, &mut table, column_name.clone())? {
+                if if_exists {
+                    notice = Some(format!(
+                        "column \"{}\" of relation \"{}\" does not exist, skipping",
+                        column_name.real_value(),
+                        table_name
+                    ));
+                } else {
+                    return Err(ErrorCode::InvalidInputSyntax(format!(
+                        "column \"{}\" of relation \"{}\" does not exist",
+                        column_name.real_value(),
+                        table_name
+                    ))
+                    .into());
+                }
+            }
+        }
+        _ => {
+            return Err(ErrorCode::NotImplemented(
+                format!("ALTER TABLE {}", operation),
+                None.into(),
+            )
+            .into())
+        }
+    }
+
+    if let Some(notice) = notice {
+        return Ok(PgResponse::empty_result_with_notice(
+            StatementType::ALTER_TABLE,
+            notice,
+        ));
+    }
+
+    let (select_star_mview_ids, mview_plans) = replan_select_star_mviews(
+        &session,
+        database_id,
+        select_star_mviews,
+        SelectStarUpstream::Table(&table),
+    )?;
+
+    let catalog_writer = session.env().catalog_writer();
+    let stale_mview_ids = catalog_writer
+        .alter_table(
+            source.to_prost(schema_id, database_id),
+            table.to_prost(schema_id, database_id),
+            select_star_mview_ids,
+            mview_plans,
+        )
+        .await?;
+
+    if !stale_mview_ids.is_empty() {
+        let reader = session.env().catalog_reader().read_guard();
+        let names = stale_mview_ids
+            .iter()
+            .filter_map(|id| reader.get_table_by_id(id).ok())
+            .map(|mview| format!("\"{}\"", mview.name))
+            .join(", ");
+        return Ok(PgResponse::empty_result_with_notice(
+            StatementType::ALTER_TABLE,
+            format!(
+                "materialized view(s) {} defined with SELECT * on \"{}\" keep their existing \
+                 columns and are marked as stale",
+                names, table_name
+            ),
+        ));
+    }
+
+    Ok(PgResponse::empty_result(StatementType::ALTER_TABLE))
+}
+
+/// The table or source whose columns are appended by `ALTER TABLE` or `ALTER SOURCE`, on which the
+/// materialized views defined with `SELECT *` are re-planned.
+#[derive(Clone, Copy)]
+pub(super) enum SelectStarUpstream<'a> {
+    Table(&'a TableCatalog),
+    Source(&'a SourceCatalog),
+}
+
+impl SelectStarUpstream<'_> {
+    fn columns(&self) -> &[ColumnCatalog] {
+        match self {
+            Self::Table(table) => &table.columns,
+            Self::Source(source) => &source.columns,
+        }
+    }
+
+    /// Whether the relation bound in a view is the upstream.
+    fn is_bound_to(&self, relation: &Relation) -> bool {
+        match (self, relation) {
+            (Self::Table(table), Relation::BaseTable(base_table)) => {
+                base_table.table_id == table.id && !base_table.for_system_time_as_of_proctime
+            }
+            (Self::Source(source), Relation::Source(bound_source)) => {
+                bound_source.catalog.id == source.id
+            }
+            _ => false,
+        }
+    }
+
+    /// Binds the relation to the upstream after the change. Returns the number of the columns
+    /// before the change.
+    fn rebind(&self, relation: &mut Relation) -> usize {
+        match (self, relation) {
+            (Self::Table(table), Relation::BaseTable(base_table)) => {
+                let num_columns = base_table.table_catalog.columns.len();
+                base_table.table_catalog = (*table).clone();
+                num_columns
+            }
+            (Self::Source(source), Relation::Source(bound_source)) => {
+                let num_columns = bound_source.catalog.columns.len();
+                bound_source.catalog = (*source).clone();
+                num_columns
+            }
+            _ => unreachable!("not a select star"),
+        }
+    }
+}
+
+/// A materialized view defined with `SELECT * FROM <upstream> [WHERE ...]`, with its query bound
+/// before the change of the upstream.
+pub(super) struct SelectStarMview {
+    schema_id: SchemaId,
+    mview: Arc<TableCatalog>,
+    query: BoundQuery,
+}
+
+/// Returns the materialized views in the database defined with `SELECT *` on `upstream`, which
+/// are not stale. The views are told by their bound queries, so that the upstream is resolved by
+/// the search path rather than by its name. Meta checks whether they depend on the upstream only.
+pub(super) fn find_select_star_mviews(
+    session: &SessionImpl,
+    upstream: SelectStarUpstream<'_>,
+) -> Result<Vec<SelectStarMview>> {
+    let mviews = {
+        let reader = session.env().catalog_reader().read_guard();
+        reader
+            .get_database_by_name(session.database())?
+            .iter_schemas()
+            .flat_map(|schema| schema.iter_mv().map(|mview| (schema.id(), mview.clone())))
+            .filter(|(_, mview)| !mview.schema_stale && is_select_star(&mview.definition))
+            .collect_vec()
+    };
+    let mut select_star_mviews = vec![];
+    for (schema_id, mview) in mviews {
+        let Ok(Some(Statement::Query(query))) =
+            Parser::parse_sql(&mview.definition).map(|mut stmts| stmts.pop()) else {
+            continue;
+        };
+        // The views that can't be bound by the user, e.g. without the privileges, are skipped.
+        let Ok(query) = Binder::new(session).bind_query(*query) else {
+            continue;
+        };
+        if is_select_star_on(&query, upstream) {
+            select_star_mviews.push(SelectStarMview {
+                schema_id,
+                mview,
+                query,
+            });
+        }
+    }
+    Ok(select_star_mviews)
+}
+
+/// Whether the definition of a view is a `SELECT *`, which can only be told before binding.
+fn is_select_star(definition: &str) -> bool {
+    let Ok(Some(Statement::Query(query))) =
+        Parser::parse_sql(definition).map(|mut stmts| stmts.pop()) else {
+        return false;
+    };
+    let SetExpr::Select(select) = &query.body else {
+        return false;
+    };
+    query.with.is_none()
+        && query.fetch.is_none()
+        && matches!(
+            select.projection.as_slice(),
+            [SelectItem::Wildcard | SelectItem::QualifiedWildcard(_)]
+        )
+        && select.from.iter().all(|from| {
+            from.joins.is_empty()
+                && matches!(
+                    &from.relation,
+                    TableFactor::Table { alias, .. }
+                        if alias.as_ref().map_or(true, |alias| alias.columns.is_empty())
+                )
+        })
+        && select.lateral_views.is_empty()
+}
+
+/// Whether the bound query of a `SELECT *` only selects all the visible columns of `upstream`,
+/// optionally filtered.
+fn is_select_star_on(query: &BoundQuery, upstream: SelectStarUpstream<'_>) -> bool {
+    if !query.order.is_empty()
+        || query.limit.is_some()
+        || query.offset.is_some()
+        || !query.extra_order_exprs.is_empty()
+    {
+        return false;
+    }
+    let BoundSetExpr::Select(select) = &query.body else {
+        return false;
+    };
+    let Some(relation) = &select.from else {
+        return false;
+    };
+    let visible_indices = upstream
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_hidden)
+        .map(|(i, _)| i)
+        .collect_vec();
+    upstream.is_bound_to(relation)
+        && select.distinct.is_all()
+        && select.group_by.is_empty()
+        && select.having.is_none()
+        && select
+            .where_clause
+            .as_ref()
+            .map_or(true, |expr| !expr.has_subquery())
+        && select.select_items.len() == visible_indices.len()
+        && select.select_items.iter().zip_eq(visible_indices).all(
+            |(item, i)| matches!(item, ExprImpl::InputRef(input_ref) if input_ref.index() == i),
+        )
+}
+
+/// Returns the ids of the views, along with their plans re-planned with the columns appended to
+/// `upstream` if `streaming_propagate_schema_change` is enabled. The views without a new plan are
+/// flagged as stale by meta.
+pub(super) fn replan_select_star_mviews(
+    session: &Arc<SessionImpl>,
+    database_id: DatabaseId,
+    select_star_mviews: Vec<SelectStarMview>,
+    upstream: SelectStarUpstream<'_>,
+) -> Result<(Vec<TableId>, Vec<ReplannedMview>)> {
+    let select_star_mview_ids = select_star_mviews
+        .iter()
+        .map(|select_star_mview| select_star_mview.mview.id)
+        .collect_vec();
+    let mview_plans = if session.config().get_streaming_propagate_schema_change() {
+        select_star_mviews
+            .into_iter()
+            .map(|select_star_mview| {
+                replan_select_star_mview(session, database_id, select_star_mview, upstream)
+            })
+            .collect::<Result<_>>()?
+    } else {
+        vec![]
+    };
+    Ok((select_star_mview_ids, mview_plans))
+}
+
+/// Re-plans the view with the columns appended to `upstream` by the change, which are selected
+/// after the existing columns.
+fn replan_select_star_mview(
+    session: &Arc<SessionImpl>,
+    database_id: DatabaseId,
+    select_star_mview: SelectStarMview,
+    upstream: SelectStarUpstream<'_>,
+) -> Result<ReplannedMview> {
+    let SelectStarMview {
+        schema_id,
+        mview,
+        mut query,
+    } = select_star_mview;
+    let BoundSetExpr::Select(select) = &mut query.body else {
+        unreachable!("not a select star");
+    };
+    let num_columns = upstream.rebind(select.from.as_mut().expect("not a select star"));
+    for (i, column) in upstream.columns().iter().enumerate().skip(num_columns) {
+        if !column.is_hidden {
+            select.push_select_item(
+                InputRef::new(i, column.data_type().clone()).into(),
+                column.name().to_string(),
+            );
+        }
+    }
+
+    let context = OptimizerContext::new(
+        session.clone(),
+        Arc::from(mview.definition.as_str()),
+        WithOptions::default(),
+    );
+    let mut plan_root = Planner::new(context.into()).plan_query(query)?;
+    let materialize =
+        plan_root.gen_create_mv_plan(mview.name.clone(), mview.definition.clone(), None, false)?;
+    let mut mview_prost = materialize.table().to_prost(schema_id, database_id);
+    mview_prost.id = mview.id.table_id;
+    mview_prost.owner = mview.owner;
+    let fragment_graph = build_graph(materialize.into());
+    Ok(ReplannedMview {
+        mview: Some(mview_prost),
+        fragment_graph: Some(fragment_graph),
+    })
+}
+
+/// Appends the new column to both the source and the table, with the next column id of the table,
+/// which is not used by the source either.
+fn add_column(
+    source: &mut SourceCatalog,
+    table: &mut TableCatalog,
+    column_def: ColumnDef,
+) -> Result<()> {
+    let column_name = column_def.name.real_value();
+    if table.columns.iter().any(|c| c.name() == column_name) {
+        return Err(ErrorCode::InvalidInputSyntax(format!(
+            "column \"{}\" of relation \"{}\" already exists",
+            column_name, table.name
+        ))
+        .into());
+    }
+
+    // Column options other than primary key are rejected in `bind_sql_columns`.
+    let (mut column_descs, pk_column_id) = bind_sql_columns(vec![column_def.clone()])?;
+    if pk_column_id.is_some() {
+        return Err(
+            ErrorCode::NotImplemented("add a primary key column".to_string(), None.into()).into(),
+        );
+    }
+
+    let version = table.version.as_ref().unwrap();
+    let mut column_desc = column_descs.remove(0);
+    column_desc.column_id = version.next_column_id;
+    let column = ColumnCatalog {
+        column_desc,
+        is_hidden: false,
+    };
+
+    let next_column_id = ColumnId::new(version.next_column_id.get_id() + 1);
+    table.version = Some(version.next(next_column_id));
+    table.columns.push(column.clone());
+    table.value_indices = (0..table.columns.len()).collect();
+    source.columns.push(column);
+    alter_definition_columns(table, |columns| columns.push(column_def));
+    Ok(())
+}
+
+/// Hides and renames the column in both the source and the table. The data of the column is still
+/// kept in the storage. Returns false if the column does not exist.
+fn drop_column(
+    source: &mut SourceCatalog,
+    table: &mut TableCatalog,
+    column_name: Ident,
+) -> Result<bool> {
+    let column_name = column_name.real_value();
+    let Some(index) = table
+        .columns
+        .iter()
+        .position(|c| !c.is_hidden && c.name() == column_name) else {
+        return Ok(false);
+    };
+    // The columns of the source and the table are in the same order, but may have different ids.
+    let source_column_id = source.columns[index].column_id();
+    if source.pk_col_ids.contains(&source_column_id) {
+        return Err(ErrorCode::InvalidInputSyntax(format!(
+            "cannot drop primary key column \"{}\"",
+            column_name
+        ))
+        .into());
+    }
+    if table.columns.iter().filter(|c| !c.is_hidden).count() == 1 {
+        return Err(ErrorCode::InvalidInputSyntax(format!(
+            "cannot drop the only column \"{}\" of table \"{}\"",
+            column_name, table.name
+        ))
+        .into());
+    }
+
+    let dropped_name = dropped_column_name(table.columns[index].column_id());
+    table
+        .column_descriptions
+        .remove(&table.columns[index].column_id());
+    for column in [&mut table.columns[index], &mut source.columns[index]] {
+        column.is_hidden = true;
+        column.column_desc.name = dropped_name.clone();
+    }
+    if let StreamPlanInfo::TableSource(info) = &mut source.info {
+        info.default_columns.remove(&source_column_id.get_id());
+    }
+
+    let version = table.version.as_ref().unwrap();
+    table.version = Some(version.next(version.next_column_id));
+    alter_definition_columns(table, |columns| {
+        columns.retain(|c| c.name.real_value() != column_name)
+    });
+    Ok(true)
+}
+
+/// Applies the change of columns to the `CREATE TABLE` definition of the table, which is shown by
+/// `SHOW CREATE TABLE`.
+fn alter_definition_columns(table: &mut TableCatalog, f: impl FnOnce(&mut Vec<ColumnDef>)) {
+    // Tables created before the definition is kept have an empty one.
+    if table.definition.is_empty() {
+        return;
+    }
+    let mut stmt = Parser::parse_sql(&table.definition)
+        .expect("a table's definition should be parsed successfully")
+        .swap_remove(0);
+    let Statement::CreateTable { columns, .. } = &mut stmt else {
+        unreachable!("a table's definition should be a CREATE TABLE statement")
+    };
+    f(columns);
+    table.definition = stmt.to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+    use risingwave_common::types::DataType;
+    use risingwave_sqlparser::ast::{AlterTableOperation, Statement};
+    use risingwave_sqlparser::parser::Parser;
+
+    use super::{
+        add_column, find_select_star_mviews, replan_select_star_mview, SelectStarUpstream,
+    };
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::catalog::{row_id_column_name, ColumnId};
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_find_select_star_mviews() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        for sql in [
+            "create table t (v1 int primary key, v2 int);",
+            "create table t2 (v1 int primary key, v2 int);",
+            "create materialized view mv1 as select * from t;",
+            "create materialized view mv2 as select x.* from t as x where v1 > 1;",
+            "create materialized view mv3 as select * from t2;",
+            "create materialized view mv4 as select v1, v2 from t;",
+            "create materialized view mv5 as select *, 1 as c from t;",
+            "create materialized view mv6 as select distinct * from t;",
+            "create materialized view mv7 as select * from t order by v1 limit 1;",
+            "create materialized view mv8 as select t.* from t join t2 on t.v1 = t2.v1;",
+        ] {
+            frontend.run_sql(sql).await.unwrap();
+        }
+
+        let session = frontend.session_ref();
+        let mut table = {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let (table, _) = catalog_reader
+                .get_table_by_name(
+                    DEFAULT_DATABASE_NAME,
+                    SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                    "t",
+                )
+                .unwrap();
+            table.as_ref().clone()
+        };
+        let select_star_mviews =
+            find_select_star_mviews(&session, SelectStarUpstream::Table(&table)).unwrap();
+        let names = select_star_mviews
+            .iter()
+            .map(|select_star_mview| select_star_mview.mview.name.as_str())
+            .sorted()
+            .collect_vec();
+        assert_eq!(names, ["mv1", "mv2"]);
+
+        // The re-planned views select the new column after the existing ones.
+        let mut source = {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let (source, _) = catalog_reader
+                .get_source_by_name(
+                    DEFAULT_DATABASE_NAME,
+                    SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                    "t",
+                )
+                .unwrap();
+            source.as_ref().clone()
+        };
+        let column_def = match Parser::parse_sql("alter table t add column v3 varchar")
+            .unwrap()
+            .remove(0)
+        {
+            Statement::AlterTable {
+                operation: AlterTableOperation::AddColumn { column_def },
+                ..
+            } => column_def,
+            _ => unreachable!(),
+        };
+        add_column(&mut source, &mut table, column_def).unwrap();
+        for select_star_mview in select_star_mviews {
+            let mview_id = select_star_mview.mview.id.table_id;
+            let replanned = replan_select_star_mview(
+                &session,
+                0,
+                select_star_mview,
+                SelectStarUpstream::Table(&table),
+            )
+            .unwrap();
+            let mview = replanned.mview.unwrap();
+            assert_eq!(mview.id, mview_id);
+            let columns = mview
+                .columns
+                .iter()
+                .map(|c| c.column_desc.as_ref().unwrap().name.as_str())
+                .collect_vec();
+            assert_eq!(columns, ["v1", "v2", "v3"]);
+            assert!(replanned.fragment_graph.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replan_select_star_mviews_on_source() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        for sql in [
+            "create source s (v1 int, v2 int) with (connector = 'kafka', kafka.topic = 'abc', \
+             kafka.servers = 'localhost:1001') row format json;",
+            "create materialized view mv1 as select * from s where v1 > 1;",
+            "create materialized view mv2 as select v1 from s;",
+        ] {
+            frontend.run_sql(sql).await.unwrap();
+        }
+
+        let session = frontend.session_ref();
+        let mut source = {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let (source, _) = catalog_reader
+                .get_source_by_name(
+                    DEFAULT_DATABASE_NAME,
+                    SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                    "s",
+                )
+                .unwrap();
+            source.as_ref().clone()
+        };
+        let select_star_mviews =
+            find_select_star_mviews(&session, SelectStarUpstream::Source(&source)).unwrap();
+        let names = select_star_mviews
+            .iter()
+            .map(|select_star_mview| select_star_mview.mview.name.as_str())
+            .collect_vec();
+        assert_eq!(names, ["mv1"]);
+
+        // The columns proposed by meta are appended to the source.
+        let mut column = source.columns[1].clone();
+        column.column_desc.name = "v3".to_string();
+        column.column_desc.column_id = ColumnId::new(
+            source
+                .columns
+                .iter()
+                .map(|c| c.column_id().get_id())
+                .max()
+                .unwrap()
+                + 1,
+        );
+        source.columns.push(column);
+        let replanned = replan_select_star_mview(
+            &session,
+            0,
+            select_star_mviews.into_iter().next().unwrap(),
+            SelectStarUpstream::Source(&source),
+        )
+        .unwrap();
+        let mview = replanned.mview.unwrap();
+        let columns = mview
+            .columns
+            .iter()
+            .filter(|c| !c.is_hidden)
+            .map(|c| c.column_desc.as_ref().unwrap().name.as_str())
+            .collect_vec();
+        assert_eq!(columns, ["v1", "v2", "v3"]);
+        assert!(replanned.fragment_graph.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_alter_table_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t add column v3 varchar;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t drop column v1;")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+
+        let (table, _) = catalog_reader
+            .get_table_by_name(DEFAULT_DATABASE_NAME, schema_path, "t")
+            .unwrap();
+        let (source, _) = catalog_reader
+            .get_source_by_name(DEFAULT_DATABASE_NAME, schema_path, "t")
+            .unwrap();
+
+        // Columns are only appended, and the dropped one is hidden.
+        for columns in [&table.columns, &source.columns] {
+            let columns = columns
+                .iter()
+                .map(|c| (c.name().to_string(), c.data_type().clone(), c.is_hidden))
+                .collect::<Vec<_>>();
+            assert_eq!(columns.len(), 4);
+            assert!(columns[0].0.starts_with("_rw_dropped_") && columns[0].2);
+            assert_eq!(columns[1], ("v2".to_string(), DataType::Int32, false));
+            assert_eq!(columns[2], (row_id_column_name(), DataType::Int64, true));
+            assert_eq!(columns[3], ("v3".to_string(), DataType::Varchar, false));
+        }
+        assert_eq!(table.columns[3].column_id(), source.columns[3].column_id());
+        assert_eq!(table.version.as_ref().unwrap().version_id, 2);
+
+        // The new column can be inserted into, while the dropped one is not visible any more.
+        drop(catalog_reader);
+        frontend
+            .run_sql("explain insert into t (v2, v3) values (1, 'a');")
+            .await
+            .unwrap();
+        assert!(frontend
+            .run_sql("explain insert into t (v1) values (1);")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_sql("alter table t drop column v1;")
+            .await
+            .is_err());
+        frontend
+            .run_sql("alter table t drop column if exists v1;")
+            .await
+            .unwrap();
+    }
+}
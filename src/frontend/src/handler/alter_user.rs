@@ -15,6 +15,7 @@
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::ErrorCode::{InternalError, PermissionDenied};
 use risingwave_common::error::Result;
+use risingwave_pb::user::auth_info::EncryptionType;
 use risingwave_pb::user::update_user_request::UpdateField;
 use risingwave_pb::user::UserInfo;
 use risingwave_sqlparser::ast::{AlterUserStatement, ObjectName, UserOption, UserOptions};
@@ -137,6 +138,13 @@ fn alter_rename_prost_user_info(
     }
 
     user_info.name = Binder::resolve_user_name(new_name)?;
+    // MD5 and SHA-256 passwords are salted with the user name, so they are cleared on renaming. A
+    // SCRAM-SHA-256 verifier does not depend on the user name and is kept.
+    if let Some(auth_info) = &user_info.auth_info
+        && auth_info.encryption_type == EncryptionType::ScramSha256 as i32
+    {
+        return Ok((user_info, vec![UpdateField::Rename]));
+    }
     user_info.auth_info = None;
     Ok((user_info, vec![UpdateField::Rename, UpdateField::AuthInfo]))
 }
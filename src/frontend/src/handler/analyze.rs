@@ -0,0 +1,194 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::TryStreamExt;
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use pgwire::types::Row;
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::DataType;
+use risingwave_sqlparser::ast::{Ident, ObjectName};
+use risingwave_sqlparser::parser::Parser;
+
+use super::privilege::check_super_user;
+use super::{query, RwPgResponse};
+use crate::binder::Binder;
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::catalog::root_catalog::SchemaPath;
+use crate::catalog::table_statistics::{ColumnStatistics, TableStatistics};
+use crate::session::{OptimizerContext, SessionImpl};
+use crate::utils::WithOptions;
+
+/// The number of buckets of the equi-depth histogram collected for each column.
+const HISTOGRAM_BUCKETS: u64 = 100;
+
+/// Collects the statistics of the table by querying it, and stores them in the catalog of the
+/// table via meta, so that they can be used by the batch optimizer.
+///
+/// The row count, the null count and the number of distinct values of each column are collected
+/// by a single aggregation, where the number of distinct values is approximated. The histogram of
+/// each orderable column is collected by another query, picking the values at every
+/// `1 / HISTOGRAM_BUCKETS` of the sorted non-null values.
+pub async fn handle_analyze(
+    context: OptimizerContext,
+    table_name: ObjectName,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, table_name) = Binder::resolve_schema_qualified_name(db_name, table_name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+
+    let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+
+    let (table, schema_name) = {
+        let reader = session.env().catalog_reader().read_guard();
+        let (table, schema_name) = reader.get_table_by_name(db_name, schema_path, &table_name)?;
+
+        let schema_owner = reader
+            .get_schema_by_name(db_name, schema_name)
+            .unwrap()
+            .owner();
+        if session.user_id() != table.owner
+            && session.user_id() != schema_owner
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        (table.clone(), schema_name.to_string())
+    };
+
+    let relation = format!(
+        "{}.{}",
+        Ident::with_quote('"', schema_name),
+        Ident::with_quote('"', table.name())
+    );
+    let columns = table
+        .columns()
+        .iter()
+        .filter(|c| !c.is_hidden())
+        .collect_vec();
+
+    // Row count, and the null count & the number of distinct values of each column.
+    let select_list = std::iter::once("count(*)".to_string())
+        .chain(columns.iter().map(|c| {
+            let column = Ident::with_quote('"', c.name());
+            format!("count({column}), approx_count_distinct({column})")
+        }))
+        .join(", ");
+    let rows = run_query(&session, format!("SELECT {select_list} FROM {relation}")).await?;
+    let [row] = &rows[..] else {
+        return Err(ErrorCode::InternalError(format!(
+            "expect exactly one row of the counts of {relation}, got {}",
+            rows.len()
+        ))
+        .into());
+    };
+    let counts = row.values().iter().map(parse_count).collect_vec();
+    let row_count = counts[0];
+
+    let mut statistics = TableStatistics {
+        row_count,
+        columns: HashMap::new(),
+    };
+    for (column, counts) in columns.iter().zip_eq(counts[1..].chunks(2)) {
+        let non_null_count = counts[0];
+        let histogram_bounds = if is_orderable(column) && non_null_count > 0 {
+            collect_histogram_bounds(&session, &relation, column, non_null_count).await?
+        } else {
+            vec![]
+        };
+        statistics.columns.insert(
+            column.column_id(),
+            ColumnStatistics {
+                // The approximation may exceed the exact number of values.
+                distinct_count: counts[1].min(non_null_count),
+                null_count: row_count - non_null_count,
+                histogram_bounds,
+            },
+        );
+    }
+
+    session
+        .env()
+        .catalog_writer()
+        .update_table_statistics(table.id(), statistics.to_prost())
+        .await?;
+
+    Ok(PgResponse::empty_result(StatementType::ANALYZE))
+}
+
+/// Picks the first and the last values, and the values at the end of each bucket, among the
+/// sorted non-null values of the column.
+async fn collect_histogram_bounds(
+    session: &Arc<SessionImpl>,
+    relation: &str,
+    column: &ColumnCatalog,
+    non_null_count: u64,
+) -> Result<Vec<String>> {
+    let column = Ident::with_quote('"', column.name());
+    let step = (non_null_count + HISTOGRAM_BUCKETS - 1) / HISTOGRAM_BUCKETS;
+    let sql = format!(
+        "SELECT v FROM (\
+            SELECT {column} AS v, row_number() OVER (ORDER BY {column}) AS rn \
+            FROM {relation} WHERE {column} IS NOT NULL\
+        ) AS sorted \
+        WHERE rn = 1 OR rn = {non_null_count} OR rn % {step} = 0 \
+        ORDER BY v"
+    );
+    let rows = run_query(session, sql).await?;
+    let bounds = rows
+        .iter()
+        .filter_map(|row| row.values()[0].as_ref())
+        .map(|v| String::from_utf8_lossy(v).into_owned())
+        .dedup()
+        .collect();
+    Ok(bounds)
+}
+
+async fn run_query(session: &Arc<SessionImpl>, sql: String) -> Result<Vec<Row>> {
+    let stmt = Parser::parse_sql(&sql)
+        .map_err(|e| ErrorCode::InternalError(format!("failed to parse \"{sql}\": {e}")))?
+        .into_iter()
+        .exactly_one()
+        .unwrap();
+    let context = OptimizerContext::new(session.clone(), Arc::from(sql), WithOptions::default());
+    let mut res = query::handle_query(context, stmt, vec![]).await?;
+    let rows: Vec<Vec<Row>> = res
+        .take_values_stream()
+        .unwrap()
+        .try_collect()
+        .await
+        .map_err(|e| RwError::from(ErrorCode::InternalError(e.to_string())))?;
+    Ok(rows.into_iter().flatten().collect())
+}
+
+fn parse_count(value: &Option<bytes::Bytes>) -> u64 {
+    value
+        .as_ref()
+        .and_then(|v| std::str::from_utf8(v).ok()?.parse().ok())
+        .unwrap_or_default()
+}
+
+fn is_orderable(column: &ColumnCatalog) -> bool {
+    !matches!(
+        column.data_type(),
+        DataType::Jsonb | DataType::Struct(_) | DataType::List { .. }
+    )
+}
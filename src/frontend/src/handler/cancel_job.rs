@@ -0,0 +1,44 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::Result;
+
+use super::RwPgResponse;
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+
+/// Cancels the streaming jobs being created. The DDL statements creating them will fail and the
+/// partially created objects will be cleaned up.
+pub async fn handle_cancel(context: OptimizerContext, job_ids: Vec<u32>) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    {
+        let user_reader = session.env().user_info_reader().read_guard();
+        let session_user = user_reader
+            .get_user_by_name(session.user_name())
+            .ok_or_else(|| CatalogError::NotFound("user", session.user_name().to_string()))?;
+        if !session_user.is_super {
+            return Err(PermissionDenied("must be superuser to cancel jobs".to_string()).into());
+        }
+    }
+
+    session
+        .env()
+        .meta_client()
+        .cancel_creating_jobs(job_ids)
+        .await?;
+
+    Ok(PgResponse::empty_result(StatementType::CANCEL_COMMAND))
+}
@@ -0,0 +1,178 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_sqlparser::ast::{CommentObject, ObjectName};
+
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::catalog::root_catalog::SchemaPath;
+use crate::session::OptimizerContext;
+
+/// Sets the comment on a table or a column, which is stored in the catalog of the table. The
+/// comment is removed if it's `NULL` or empty.
+pub async fn handle_comment(
+    context: OptimizerContext,
+    object_type: CommentObject,
+    object_name: ObjectName,
+    comment: Option<String>,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+
+    // The name of a column is qualified by the name of its table.
+    let (table_name, column_name) = match object_type {
+        CommentObject::Table => (object_name, None),
+        CommentObject::Column => {
+            let mut idents = object_name.0;
+            let column_name = idents.pop().unwrap().real_value();
+            if idents.is_empty() {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "column name \"{}\" must be qualified",
+                    column_name
+                ))
+                .into());
+            }
+            (ObjectName(idents), Some(column_name))
+        }
+    };
+
+    let (schema_name, table_name) = Binder::resolve_schema_qualified_name(db_name, table_name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+    let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+
+    let (table_id, column_id) = {
+        let reader = session.env().catalog_reader().read_guard();
+        let (table, schema_name) = reader.get_table_by_name(db_name, schema_path, &table_name)?;
+
+        let schema_owner = reader
+            .get_schema_by_name(db_name, schema_name)
+            .unwrap()
+            .owner();
+        if session.user_id() != table.owner
+            && session.user_id() != schema_owner
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        if table.is_index {
+            return Err(
+                ErrorCode::InvalidInputSyntax(format!("\"{}\" is an index", table_name)).into(),
+            );
+        }
+
+        let column_id = match column_name {
+            Some(column_name) => Some(
+                table
+                    .columns()
+                    .iter()
+                    .find(|c| !c.is_hidden && c.name() == column_name)
+                    .ok_or_else(|| {
+                        ErrorCode::InvalidInputSyntax(format!(
+                            "column \"{}\" of relation \"{}\" does not exist",
+                            column_name, table_name
+                        ))
+                    })?
+                    .column_id(),
+            ),
+            None => None,
+        };
+        (table.id, column_id)
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer
+        .comment_on(table_id, column_id, comment.unwrap_or_default())
+        .await?;
+
+    Ok(PgResponse::empty_result(StatementType::COMMENT))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{ColumnId, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_comment_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("comment on table t is 'the table';")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("comment on column t.v2 is 'the second column';")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("comment on column public.t.v1 is 'the first column';")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("comment on column t.v1 is null;")
+            .await
+            .unwrap();
+        assert!(frontend
+            .run_sql("comment on column t.v3 is 'no such column';")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_sql("comment on column v1 is 'no table';")
+            .await
+            .is_err());
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let (table, _) = catalog_reader
+            .get_table_by_name(
+                DEFAULT_DATABASE_NAME,
+                SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                "t",
+            )
+            .unwrap();
+        assert_eq!(table.description.as_deref(), Some("the table"));
+        let v2_id: ColumnId = table
+            .columns()
+            .iter()
+            .find(|c| c.name() == "v2")
+            .unwrap()
+            .column_id();
+        assert_eq!(
+            table.column_descriptions,
+            [(v2_id, "the second column".to_string())].into()
+        );
+        drop(catalog_reader);
+
+        let rows = frontend.query_formatted_result("describe t").await;
+        assert_eq!(
+            rows,
+            vec![
+                r#"Row([Some(b"v1"), Some(b"Int32"), None])"#.to_string(),
+                r#"Row([Some(b"v2"), Some(b"Int32"), Some(b"the second column")])"#.to_string(),
+                r#"Row([Some(b"table description"), None, Some(b"the table")])"#.to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,581 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::{stream, StreamExt, TryStreamExt};
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use pgwire::pg_server::CopyInData;
+use pgwire::types::Row;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_pb::user::grant_privilege::Action;
+use risingwave_sqlparser::ast::{
+    CopyOption, CopySource, Expr, Ident, ObjectName, Query, Select, SelectItem, SetExpr, Statement,
+    TableFactor, TableWithJoins, Value, Values,
+};
+
+use super::{query, PgResponseStream, RwPgResponse};
+use crate::binder::Binder;
+use crate::session::{OptimizerContext, SessionImpl};
+use crate::utils::WithOptions;
+
+/// The number of rows written by each `INSERT` of `COPY ... FROM STDIN`. The writes are flushed
+/// by batch if `implicit_flush` is on.
+const COPY_IN_BATCH_SIZE: usize = 1024;
+
+/// The format of the data of `COPY`, which is either the text format or CSV.
+#[derive(Clone, Debug)]
+struct CopyFormat {
+    csv: bool,
+    delimiter: char,
+    /// The string of a null value.
+    null: String,
+    /// Whether the first line is a header of the column names.
+    header: bool,
+}
+
+impl CopyFormat {
+    fn new(options: &[CopyOption]) -> Result<Self> {
+        let mut csv = false;
+        for option in options {
+            if let CopyOption::Format(format) = option {
+                csv = match format.real_value().as_str() {
+                    "csv" => true,
+                    "text" => false,
+                    format => {
+                        return Err(ErrorCode::NotImplemented(
+                            format!("COPY format \"{}\"", format),
+                            None.into(),
+                        )
+                        .into())
+                    }
+                };
+            }
+        }
+
+        let mut format = Self {
+            csv,
+            delimiter: if csv { ',' } else { '\t' },
+            null: if csv {
+                String::new()
+            } else {
+                "\\N".to_string()
+            },
+            header: false,
+        };
+        for option in options {
+            match option {
+                CopyOption::Format(_) => {}
+                CopyOption::Delimiter(delimiter) => {
+                    if *delimiter == '\n' || *delimiter == '\r' || (csv && *delimiter == '"') {
+                        return Err(ErrorCode::InvalidInputSyntax(format!(
+                            "COPY delimiter cannot be {:?}",
+                            delimiter
+                        ))
+                        .into());
+                    }
+                    format.delimiter = *delimiter;
+                }
+                CopyOption::Null(null) => format.null = null.clone(),
+                CopyOption::Header(header) => {
+                    if *header && !csv {
+                        return Err(ErrorCode::InvalidInputSyntax(
+                            "COPY HEADER available only in CSV mode".to_string(),
+                        )
+                        .into());
+                    }
+                    format.header = *header;
+                }
+            }
+        }
+        Ok(format)
+    }
+
+    /// Encodes the values in text as a line of the data, ended with a newline.
+    fn encode_line<S: AsRef<str>>(&self, values: &[Option<S>]) -> String {
+        let mut line = String::new();
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                line.push(self.delimiter);
+            }
+            let Some(value) = value else {
+                line.push_str(&self.null);
+                continue;
+            };
+            let value = value.as_ref();
+            if self.csv {
+                // A value is quoted if it could be taken as null or split into several ones.
+                let quoted = value == self.null
+                    || value.contains([self.delimiter, '"', '\n', '\r'])
+                    || value == "\\.";
+                if quoted {
+                    line.push('"');
+                    line.push_str(&value.replace('"', "\"\""));
+                    line.push('"');
+                } else {
+                    line.push_str(value);
+                }
+            } else {
+                for c in value.chars() {
+                    match c {
+                        '\\' => line.push_str("\\\\"),
+                        '\n' => line.push_str("\\n"),
+                        '\r' => line.push_str("\\r"),
+                        '\t' => line.push_str("\\t"),
+                        c if c == self.delimiter => {
+                            line.push('\\');
+                            line.push(c);
+                        }
+                        c => line.push(c),
+                    }
+                }
+            }
+        }
+        line.push('\n');
+        line
+    }
+
+    /// Decodes the values in text of a line of the data, or the lines of a CSV record whose quoted
+    /// values contain newlines.
+    fn decode_line(&self, line: &str) -> Result<Vec<Option<String>>> {
+        let mut values = vec![];
+        // The raw text of the value is compared with the null string.
+        let mut raw = String::new();
+        let mut value = String::new();
+        let mut quoted = false;
+        let mut in_quotes = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if self.csv && c == '"' {
+                if in_quotes && chars.peek() == Some(&'"') {
+                    chars.next();
+                    value.push('"');
+                } else {
+                    quoted = true;
+                    in_quotes = !in_quotes;
+                }
+                raw.push(c);
+            } else if c == self.delimiter && !in_quotes {
+                let is_null = !quoted && raw == self.null;
+                values.push((!is_null).then(|| std::mem::take(&mut value)));
+                value.clear();
+                raw.clear();
+                quoted = false;
+            } else if c == '\\' && !self.csv {
+                let Some(escaped) = chars.next() else {
+                    return Err(ErrorCode::InvalidInputSyntax(
+                        "unexpected end of line after backslash in COPY data".to_string(),
+                    )
+                    .into());
+                };
+                value.push(match escaped {
+                    'b' => '\x08',
+                    'f' => '\x0c',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'v' => '\x0b',
+                    c => c,
+                });
+                raw.push(c);
+                raw.push(escaped);
+            } else {
+                value.push(c);
+                raw.push(c);
+            }
+        }
+        if in_quotes {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "unterminated CSV quoted field in COPY data".to_string(),
+            )
+            .into());
+        }
+        let is_null = !quoted && raw == self.null;
+        values.push((!is_null).then_some(value));
+        Ok(values)
+    }
+}
+
+/// Assembles the rows of `COPY ... FROM STDIN` from the lines of the data.
+struct RowReader {
+    format: CopyFormat,
+    column_cnt: usize,
+    /// Whether the header line is still to be skipped.
+    skip_header: bool,
+    /// The lines read of a CSV record, whose quoted values may contain newlines.
+    record: String,
+    /// Whether the end-of-data marker `\.` has been read. The lines after it are ignored.
+    ended: bool,
+}
+
+impl RowReader {
+    fn new(format: CopyFormat, column_cnt: usize) -> Self {
+        Self {
+            skip_header: format.header,
+            format,
+            column_cnt,
+            record: String::new(),
+            ended: false,
+        }
+    }
+
+    /// Reads a line without the newline. Returns the row if it's complete.
+    fn read_line(&mut self, line: &str) -> Result<Option<Vec<Option<String>>>> {
+        if self.ended {
+            return Ok(None);
+        }
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if self.record.is_empty() && line == "\\." {
+            self.ended = true;
+            return Ok(None);
+        }
+
+        if !self.record.is_empty() {
+            self.record.push('\n');
+        }
+        self.record.push_str(line);
+        // An odd number of quotes means the last quoted value is not ended yet.
+        if self.format.csv && self.record.matches('"').count() % 2 != 0 {
+            return Ok(None);
+        }
+        let record = std::mem::take(&mut self.record);
+        if self.skip_header {
+            self.skip_header = false;
+            return Ok(None);
+        }
+
+        let row = self.format.decode_line(&record)?;
+        if row.len() != self.column_cnt {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "COPY expects {} columns in a row, but got {}",
+                self.column_cnt,
+                row.len()
+            ))
+            .into());
+        }
+        Ok(Some(row))
+    }
+
+    /// Checks that there is no incomplete record at the end of the data.
+    fn finish(&self) -> Result<()> {
+        if !self.record.is_empty() {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "unterminated CSV quoted field in COPY data".to_string(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+pub async fn handle_copy(
+    context: OptimizerContext,
+    source: CopySource,
+    to: bool,
+    options: Vec<CopyOption>,
+    values: Vec<Option<String>>,
+) -> Result<RwPgResponse> {
+    let format = CopyFormat::new(&options)?;
+    if to {
+        return handle_copy_to(context, source, format).await;
+    }
+
+    let CopySource::Table { table_name, columns } = source else {
+        unreachable!("COPY FROM a query is rejected by the parser");
+    };
+    let session = context.session_ctx;
+    let column_cnt = bind_copy_in_columns(&session, table_name.clone(), &columns)?;
+    // The values may follow the statement inline, rather than being sent in the COPY
+    // sub-protocol.
+    if values.is_empty() {
+        return Ok(PgResponse::copy_in(column_cnt));
+    }
+    if values.len() % column_cnt != 0 {
+        return Err(ErrorCode::InvalidInputSyntax(format!(
+            "COPY expects {} columns in a row, but got {} values",
+            column_cnt,
+            values.len()
+        ))
+        .into());
+    }
+    let mut row_cnt = 0;
+    for rows in values.chunks(column_cnt * COPY_IN_BATCH_SIZE) {
+        let rows = rows.chunks(column_cnt).map(|row| row.to_vec()).collect();
+        row_cnt += insert_rows(&session, &context.sql, &table_name, &columns, rows).await?;
+    }
+    Ok(PgResponse::with_row_cnt(StatementType::COPY, row_cnt))
+}
+
+/// Runs `COPY ... FROM STDIN` with the data received from the client, by inserting the rows in
+/// batches.
+pub async fn handle_copy_in(
+    context: OptimizerContext,
+    source: CopySource,
+    options: Vec<CopyOption>,
+    mut data: CopyInData,
+) -> Result<RwPgResponse> {
+    let CopySource::Table { table_name, columns } = source else {
+        unreachable!("COPY FROM a query is rejected by the parser");
+    };
+    let session = context.session_ctx;
+    let format = CopyFormat::new(&options)?;
+    let column_cnt = bind_copy_in_columns(&session, table_name.clone(), &columns)?;
+    let mut reader = RowReader::new(format, column_cnt);
+
+    // The data are split into lines regardless of the boundaries of the messages.
+    let mut buf = vec![];
+    let mut rows = vec![];
+    let mut row_cnt = 0;
+    loop {
+        let chunk = data.next().await;
+        let ended = chunk.is_none();
+        match chunk {
+            Some(chunk) => {
+                let chunk = chunk.map_err(|e| ErrorCode::ProtocolError(e.to_string()))?;
+                buf.extend_from_slice(&chunk);
+            }
+            // The last line may not be ended with a newline.
+            None if !buf.is_empty() => buf.push(b'\n'),
+            None => {}
+        }
+
+        let mut start = 0;
+        while let Some(len) = buf[start..].iter().position(|b| *b == b'\n') {
+            let line = std::str::from_utf8(&buf[start..start + len]).map_err(|e| {
+                ErrorCode::InvalidInputSyntax(format!("invalid UTF-8 in COPY data: {}", e))
+            })?;
+            start += len + 1;
+            if let Some(row) = reader.read_line(line)? {
+                rows.push(row);
+            }
+            if rows.len() == COPY_IN_BATCH_SIZE {
+                let rows = std::mem::take(&mut rows);
+                row_cnt += insert_rows(&session, &context.sql, &table_name, &columns, rows).await?;
+            }
+        }
+        buf.drain(..start);
+
+        if ended {
+            break;
+        }
+    }
+    reader.finish()?;
+    if !rows.is_empty() {
+        row_cnt += insert_rows(&session, &context.sql, &table_name, &columns, rows).await?;
+    }
+    Ok(PgResponse::with_row_cnt(StatementType::COPY, row_cnt))
+}
+
+/// Returns the number of columns of each row copied into the table, checking the privilege of
+/// inserting into it.
+fn bind_copy_in_columns(
+    session: &SessionImpl,
+    table_name: ObjectName,
+    columns: &[Ident],
+) -> Result<usize> {
+    let mut binder = Binder::new(session);
+    let (schema_name, table_name) =
+        Binder::resolve_schema_qualified_name(session.database(), table_name)?;
+    let table_source =
+        binder.bind_table_source(schema_name.as_deref(), &table_name, Action::Insert)?;
+    if !columns.is_empty() {
+        return Ok(columns.len());
+    }
+    // Same as `INSERT` without a column list, the generated and the dropped columns are skipped.
+    Ok(table_source
+        .columns
+        .iter()
+        .filter(|c| {
+            !table_source.generated_column_ids.contains(&c.column_id)
+                && !table_source.dropped_column_ids.contains(&c.column_id)
+        })
+        .count())
+}
+
+/// Inserts the rows of text values, which are cast to the types of the columns. Returns the
+/// number of inserted rows.
+async fn insert_rows(
+    session: &Arc<SessionImpl>,
+    sql: &Arc<str>,
+    table_name: &ObjectName,
+    columns: &[Ident],
+    rows: Vec<Vec<Option<String>>>,
+) -> Result<i32> {
+    let rows = rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|value| {
+                    Expr::Value(match value {
+                        Some(value) => Value::SingleQuotedString(value),
+                        None => Value::Null,
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    let stmt = Statement::Insert {
+        table_name: table_name.clone(),
+        columns: columns.to_vec(),
+        source: Box::new(Query {
+            with: None,
+            body: SetExpr::Values(Values(rows)),
+            order_by: vec![],
+            limit: None,
+            offset: None,
+            fetch: None,
+        }),
+        returning: vec![],
+    };
+    let context = OptimizerContext::new(session.clone(), sql.clone(), WithOptions::default());
+    let res = query::handle_query(context, stmt, vec![]).await?;
+    Ok(res.get_effected_rows_cnt().unwrap_or_default())
+}
+
+/// Runs `COPY ... TO STDOUT` by querying the table or the query, and encoding each of the rows
+/// as a line of the data.
+async fn handle_copy_to(
+    context: OptimizerContext,
+    source: CopySource,
+    format: CopyFormat,
+) -> Result<RwPgResponse> {
+    let query = match source {
+        CopySource::Query(query) => *query,
+        CopySource::Table {
+            table_name,
+            columns,
+        } => {
+            let projection = if columns.is_empty() {
+                vec![SelectItem::Wildcard]
+            } else {
+                columns
+                    .into_iter()
+                    .map(|c| SelectItem::UnnamedExpr(Expr::Identifier(c)))
+                    .collect()
+            };
+            let select = Select {
+                distinct: Default::default(),
+                projection,
+                from: vec![TableWithJoins {
+                    relation: TableFactor::Table {
+                        name: table_name,
+                        for_system_time_as_of_proctime: false,
+                        alias: None,
+                    },
+                    joins: vec![],
+                }],
+                lateral_views: vec![],
+                selection: None,
+                group_by: vec![],
+                having: None,
+            };
+            Query {
+                with: None,
+                body: SetExpr::Select(Box::new(select)),
+                order_by: vec![],
+                limit: None,
+                offset: None,
+                fetch: None,
+            }
+        }
+    };
+
+    let mut res = query::handle_query(context, Statement::Query(Box::new(query)), vec![]).await?;
+    let column_names = res
+        .get_row_desc()
+        .iter()
+        .map(|f| Some(f.get_name().to_string()))
+        .collect_vec();
+    let header = format
+        .header
+        .then(|| Ok(vec![to_line_row(format.encode_line(&column_names))]));
+    let rows = res.take_values_stream().unwrap().map_ok(move |rows| {
+        rows.iter()
+            .map(|row| {
+                let values = row
+                    .values()
+                    .iter()
+                    .map(|v| v.as_ref().map(|v| String::from_utf8_lossy(v)))
+                    .collect_vec();
+                to_line_row(format.encode_line(&values))
+            })
+            .collect()
+    });
+    let lines = stream::iter(header).chain(rows).boxed();
+    Ok(PgResponse::copy_out(
+        PgResponseStream::Rows(lines),
+        column_names.len(),
+    ))
+}
+
+fn to_line_row(line: String) -> Row {
+    Row::new(vec![Some(Bytes::from(line))])
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_sqlparser::ast::{CopyOption, Ident};
+
+    use super::{CopyFormat, RowReader};
+
+    #[test]
+    fn test_copy_text_format() {
+        let format = CopyFormat::new(&[]).unwrap();
+        let values = [Some("a\tb\\c\nd"), None, Some("")];
+        let line = format.encode_line(&values);
+        assert_eq!(line, "a\\tb\\\\c\\nd\t\\N\t\n");
+
+        let mut reader = RowReader::new(format, 3);
+        let row = reader.read_line(line.trim_end_matches('\n')).unwrap();
+        assert_eq!(
+            row,
+            Some(values.iter().map(|v| v.map(String::from)).collect())
+        );
+        assert!(reader.read_line("a\tb").is_err());
+        assert_eq!(reader.read_line("\\.").unwrap(), None);
+        assert_eq!(reader.read_line("ignored").unwrap(), None);
+    }
+
+    #[test]
+    fn test_copy_csv_format() {
+        let format = CopyFormat::new(&[
+            CopyOption::Format(Ident::new("csv")),
+            CopyOption::Header(true),
+        ])
+        .unwrap();
+        let values = [Some("a,\"b\"\nc"), None, Some("")];
+        let line = format.encode_line(&values);
+        assert_eq!(line, "\"a,\"\"b\"\"\nc\",,\"\"\n");
+
+        let mut reader = RowReader::new(format, 3);
+        // The header is skipped, and the quoted value spans the lines.
+        assert_eq!(reader.read_line("v1,v2,v3").unwrap(), None);
+        let mut lines = line.trim_end_matches('\n').split('\n');
+        assert_eq!(reader.read_line(lines.next().unwrap()).unwrap(), None);
+        let row = reader.read_line(lines.next().unwrap()).unwrap();
+        assert_eq!(
+            row,
+            Some(values.iter().map(|v| v.map(String::from)).collect())
+        );
+        reader.finish().unwrap();
+
+        assert!(reader.read_line("\"a").unwrap().is_none());
+        assert!(reader.finish().is_err());
+
+        assert!(CopyFormat::new(&[CopyOption::Header(true)]).is_err());
+        assert!(CopyFormat::new(&[CopyOption::Format(Ident::new("binary"))]).is_err());
+    }
+}
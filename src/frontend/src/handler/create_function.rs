@@ -0,0 +1,128 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_expr::expr::validate_wasm_udf;
+use risingwave_pb::catalog::Function as ProstFunction;
+use risingwave_sqlparser::ast::{CreateFunctionUsing, DataType, Ident, ObjectName};
+
+use super::RwPgResponse;
+use crate::binder::{bind_data_type, Binder};
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_create_function(
+    context: OptimizerContext,
+    or_replace: bool,
+    temporary: bool,
+    name: ObjectName,
+    args: Vec<DataType>,
+    return_type: DataType,
+    language: Ident,
+    identifier: String,
+    using: CreateFunctionUsing,
+) -> Result<RwPgResponse> {
+    if or_replace {
+        return Err(ErrorCode::NotImplemented(
+            "CREATE OR REPLACE FUNCTION".to_string(),
+            None.into(),
+        )
+        .into());
+    }
+    if temporary {
+        return Err(ErrorCode::NotImplemented(
+            "CREATE TEMPORARY FUNCTION".to_string(),
+            None.into(),
+        )
+        .into());
+    }
+    let language = language.real_value().to_lowercase();
+
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, function_name) = Binder::resolve_schema_qualified_name(db_name, name)?;
+    let (database_id, schema_id) = session.get_database_and_schema_id_for_create(schema_name)?;
+
+    let arg_types: Vec<_> = args.iter().map(bind_data_type).try_collect()?;
+    let return_type = bind_data_type(&return_type)?;
+
+    let (link, wasm_binary) = match (language.as_str(), using) {
+        ("python", CreateFunctionUsing::Link(link)) => (link, vec![]),
+        ("wasm", CreateFunctionUsing::Base64(module)) => {
+            let binary = base64::decode(module).map_err(|e| {
+                ErrorCode::InvalidParameterValue(format!("invalid base64 WASM module: {}", e))
+            })?;
+            validate_wasm_udf(&binary, &identifier, &arg_types, &return_type)?;
+            (String::new(), binary)
+        }
+        ("python", _) => {
+            return Err(ErrorCode::InvalidParameterValue(
+                "python functions must be created with USING LINK".to_string(),
+            )
+            .into());
+        }
+        ("wasm", _) => {
+            return Err(ErrorCode::InvalidParameterValue(
+                "wasm functions must be created with USING BASE64".to_string(),
+            )
+            .into());
+        }
+        _ => {
+            return Err(ErrorCode::InvalidParameterValue(format!(
+                "language {} is not supported, only python and wasm are allowed",
+                language
+            ))
+            .into());
+        }
+    };
+
+    // check if the function exists in the catalog
+    {
+        let reader = session.env().catalog_reader().read_guard();
+        let schema = reader.get_schema_by_id(&database_id, &schema_id)?;
+        if schema
+            .get_function_by_name_args(&function_name, &arg_types)
+            .is_some()
+        {
+            let name = format!(
+                "{}({})",
+                function_name,
+                arg_types.iter().map(|t| t.to_string()).join(",")
+            );
+            return Err(CatalogError::Duplicated("function", name).into());
+        }
+    }
+
+    let function = ProstFunction {
+        id: 0,
+        schema_id,
+        database_id,
+        name: function_name,
+        owner: session.user_id(),
+        arg_types: arg_types.iter().map(|t| t.to_protobuf()).collect(),
+        return_type: Some(return_type.to_protobuf()),
+        language,
+        link,
+        identifier,
+        wasm_binary,
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.create_function(function).await?;
+
+    Ok(PgResponse::empty_result(StatementType::CREATE_FUNCTION))
+}
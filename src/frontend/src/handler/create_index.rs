@@ -22,7 +22,7 @@ use risingwave_common::catalog::{IndexId, TableDesc, TableId};
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_pb::catalog::{Index as ProstIndex, Table as ProstTable};
 use risingwave_pb::user::grant_privilege::{Action, Object};
-use risingwave_sqlparser::ast::{Ident, ObjectName, OrderByExpr};
+use risingwave_sqlparser::ast::{Ident, ObjectName, OrderByExpr, Statement};
 
 use super::RwPgResponse;
 use crate::binder::Binder;
@@ -44,6 +44,16 @@ pub(crate) fn gen_create_index_plan(
     include: Vec<Ident>,
     distributed_by: Vec<Ident>,
 ) -> Result<(PlanRef, ProstTable, ProstIndex)> {
+    let definition = Statement::CreateIndex {
+        name: index_name.clone(),
+        table_name: table_name.clone(),
+        columns: columns.clone(),
+        include: include.clone(),
+        distributed_by: distributed_by.clone(),
+        unique: false,
+        if_not_exists: false,
+    }
+    .to_string();
     let columns = check_columns(columns)?;
     let db_name = session.database();
     let (schema_name, table_name) = Binder::resolve_schema_qualified_name(db_name, table_name)?;
@@ -69,7 +79,7 @@ pub(crate) fn gen_create_index_plan(
 
     check_privileges(
         session,
-        &vec![ObjectCheckItem::new(
+        &[ObjectCheckItem::new(
             table.owner,
             Action::Select,
             Object::TableId(table.id.table_id),
@@ -153,6 +163,7 @@ pub(crate) fn gen_create_index_plan(
     let index_table = materialize.table();
     let mut index_table_prost = index_table.to_prost(index_schema_id, index_database_id);
     index_table_prost.owner = session.user_id();
+    index_table_prost.definition = definition;
 
     let index_prost = ProstIndex {
         id: IndexId::placeholder().index_id,
@@ -239,6 +250,7 @@ fn assemble_materialize(
         // Index table has no indexes.
         vec![],
         context,
+        false,
     );
 
     let exprs = index_columns
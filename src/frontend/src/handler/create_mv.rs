@@ -16,10 +16,8 @@ use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_pb::catalog::Table as ProstTable;
-use risingwave_pb::user::grant_privilege::Action;
-use risingwave_sqlparser::ast::{Ident, ObjectName, Query};
+use risingwave_sqlparser::ast::{EmitMode, Ident, ObjectName, Query};
 
-use super::privilege::{check_privileges, resolve_relation_privileges};
 use super::RwPgResponse;
 use crate::binder::{Binder, BoundSetExpr};
 use crate::optimizer::PlanRef;
@@ -34,6 +32,7 @@ pub fn gen_create_mv_plan(
     query: Query,
     name: ObjectName,
     columns: Vec<Ident>,
+    emit_mode: Option<EmitMode>,
 ) -> Result<(PlanRef, ProstTable)> {
     let db_name = session.database();
     let (schema_name, table_name) = Binder::resolve_schema_qualified_name(db_name, name)?;
@@ -57,7 +56,12 @@ pub fn gen_create_mv_plan(
         binder.bind_query(query)?
     };
 
-    if let BoundSetExpr::Select(select) = &bound.body {
+    // The output columns of a set operation are named after its leftmost operand.
+    let mut body = &bound.body;
+    while let BoundSetExpr::SetOperation { left, .. } = body {
+        body = left;
+    }
+    if let BoundSetExpr::Select(select) = body {
         // `InputRef`'s alias will be implicitly assigned in `bind_project`.
         // If user provide columns name (col_names.is_some()), we don't need alias.
         // For other expressions (col_names.is_none()), we require the user to explicitly assign an
@@ -68,11 +72,6 @@ pub fn gen_create_mv_plan(
             )
             .into());
         }
-        if let Some(relation) = &select.from {
-            let mut check_items = Vec::new();
-            resolve_relation_privileges(relation, Action::Select, &mut check_items);
-            check_privileges(session, &check_items)?;
-        }
     }
 
     let mut plan_root = Planner::new(context).plan_query(bound)?;
@@ -93,7 +92,9 @@ pub fn gen_create_mv_plan(
             .into());
         }
     }
-    let materialize = plan_root.gen_create_mv_plan(table_name, definition, col_names)?;
+    let emit_on_window_close = emit_mode == Some(EmitMode::OnWindowClose);
+    let materialize =
+        plan_root.gen_create_mv_plan(table_name, definition, col_names, emit_on_window_close)?;
     let mut table = materialize.table().to_prost(schema_id, database_id);
     if session.config().get_create_compaction_group_for_mv() {
         table.properties.insert(
@@ -119,13 +120,15 @@ pub async fn handle_create_mv(
     name: ObjectName,
     query: Query,
     columns: Vec<Ident>,
+    emit_mode: Option<EmitMode>,
 ) -> Result<RwPgResponse> {
     let session = context.session_ctx.clone();
 
     session.check_relation_name_duplicated(name.clone())?;
 
     let (table, graph) = {
-        let (plan, table) = gen_create_mv_plan(&session, context.into(), query, name, columns)?;
+        let (plan, table) =
+            gen_create_mv_plan(&session, context.into(), query, name, columns, emit_mode)?;
         let graph = build_graph(plan);
 
         (table, graph)
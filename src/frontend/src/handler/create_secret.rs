@@ -0,0 +1,133 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_pb::catalog::Secret as ProstSecret;
+use risingwave_sqlparser::ast::{ObjectName, SqlOption};
+
+use super::RwPgResponse;
+use crate::binder::Binder;
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+use crate::WithOptions;
+
+/// The option of `CREATE SECRET` to give the value of the secret.
+const SECRET_VALUE_KEY: &str = "value";
+
+pub async fn handle_create_secret(
+    context: OptimizerContext,
+    if_not_exists: bool,
+    name: ObjectName,
+    with_options: Vec<SqlOption>,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, secret_name) = Binder::resolve_schema_qualified_name(db_name, name)?;
+    let (database_id, schema_id) = session.get_database_and_schema_id_for_create(schema_name)?;
+
+    let options = WithOptions::try_from(with_options.as_slice())?;
+    if !options.secret_refs().is_empty() || options.keys().any(|key| key != SECRET_VALUE_KEY) {
+        return Err(ErrorCode::InvalidParameterValue(format!(
+            "only `{}` can be given in the options of a secret",
+            SECRET_VALUE_KEY
+        ))
+        .into());
+    }
+    let Some(value) = options.get(SECRET_VALUE_KEY) else {
+        return Err(ErrorCode::InvalidParameterValue(format!(
+            "the `{}` of the secret is missing",
+            SECRET_VALUE_KEY
+        ))
+        .into());
+    };
+
+    // check if the secret exists in the catalog
+    {
+        let reader = session.env().catalog_reader().read_guard();
+        let schema = reader.get_schema_by_id(&database_id, &schema_id)?;
+        if schema.get_secret_by_name(&secret_name).is_some() {
+            return if if_not_exists {
+                Ok(PgResponse::empty_result_with_notice(
+                    StatementType::CREATE_SECRET,
+                    format!("secret \"{}\" exists, skipping", secret_name),
+                ))
+            } else {
+                Err(CatalogError::Duplicated("secret", secret_name).into())
+            };
+        }
+    }
+
+    let secret = ProstSecret {
+        id: 0,
+        schema_id,
+        database_id,
+        name: secret_name,
+        owner: session.user_id(),
+        value: value.as_bytes().to_vec(),
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.create_secret(secret).await?;
+
+    Ok(PgResponse::empty_result(StatementType::CREATE_SECRET))
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_create_secret() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("CREATE SECRET mysql_pwd WITH (value = 'p@ssw0rd')")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("CREATE SECRET IF NOT EXISTS mysql_pwd WITH (value = 'p@ssw0rd')")
+            .await
+            .unwrap();
+        assert!(frontend
+            .run_sql("CREATE SECRET mysql_pwd WITH (value = 'p@ssw0rd')")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_sql("CREATE SECRET another_pwd WITH (password = 'p@ssw0rd')")
+            .await
+            .is_err());
+
+        let sql = r#"CREATE SOURCE s (v INT) WITH (connector = 'kafka', kafka.topic = 'abc',
+    kafka.servers = 'localhost:1001', properties.sasl.password = SECRET mysql_pwd) ROW FORMAT JSON"#;
+        frontend.run_sql(sql).await.unwrap();
+        let sql = r#"CREATE SOURCE s2 (v INT) WITH (connector = 'kafka', kafka.topic = 'abc',
+    kafka.servers = 'localhost:1001', properties.sasl.password = SECRET another_pwd) ROW FORMAT JSON"#;
+        assert!(frontend.run_sql(sql).await.is_err());
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+        let (secret, _) = catalog_reader
+            .get_secret_by_name(DEFAULT_DATABASE_NAME, schema_path, "mysql_pwd")
+            .unwrap();
+        let (source, _) = catalog_reader
+            .get_source_by_name(DEFAULT_DATABASE_NAME, schema_path, "s")
+            .unwrap();
+        assert!(!source.properties.contains_key("properties.sasl.password"));
+        assert_eq!(source.secret_refs["properties.sasl.password"], secret.id);
+    }
+}
@@ -12,29 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::Result;
 use risingwave_pb::catalog::Sink as ProstSink;
-use risingwave_sqlparser::ast::CreateSinkStatement;
+use risingwave_sqlparser::ast::{CreateSink, CreateSinkStatement};
 
 use super::RwPgResponse;
 use crate::binder::Binder;
-use crate::catalog::{DatabaseId, SchemaId};
+use crate::catalog::{DatabaseId, SchemaId, SecretId};
 use crate::optimizer::plan_node::{LogicalScan, StreamSink, StreamTableScan};
 use crate::optimizer::PlanRef;
+use crate::planner::Planner;
 use crate::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
 use crate::stream_fragmenter::build_graph;
 use crate::WithOptions;
 
+#[allow(clippy::too_many_arguments)]
 fn make_prost_sink(
     database_id: DatabaseId,
     schema_id: SchemaId,
     name: String,
     associated_table_id: u32,
     properties: &WithOptions,
+    secret_refs: HashMap<String, SecretId>,
     owner: u32,
+    definition: String,
 ) -> ProstSink {
     ProstSink {
         id: 0,
@@ -43,8 +48,10 @@ fn make_prost_sink(
         name,
         associated_table_id,
         properties: properties.inner().clone(),
+        secret_refs,
         owner,
         dependent_relations: vec![],
+        definition,
     }
 }
 
@@ -53,35 +60,68 @@ pub fn gen_sink_plan(
     context: OptimizerContextRef,
     stmt: CreateSinkStatement,
 ) -> Result<(PlanRef, ProstSink)> {
-    let db_name = session.database();
-    let (schema_name, associated_table_name) =
-        Binder::resolve_schema_qualified_name(db_name, stmt.materialized_view.clone())?;
-
-    let (database_id, schema_id, associated_table_catalog) =
-        session.get_table_catalog_for_create(schema_name, &associated_table_name)?;
+    let definition = format!("CREATE SINK {}", stmt);
+    let properties = context.inner().with_options.clone();
+    let secret_refs = properties.resolve_secret_refs(session)?;
+
+    let (database_id, schema_id, associated_table_id, plan) = match stmt.sink_from {
+        CreateSink::From(materialized_view) => {
+            let db_name = session.database();
+            let (schema_name, associated_table_name) =
+                Binder::resolve_schema_qualified_name(db_name, materialized_view)?;
+
+            let (database_id, schema_id, associated_table_catalog) =
+                session.get_table_catalog_for_create(schema_name, &associated_table_name)?;
+
+            let scan_node = StreamTableScan::new(LogicalScan::create(
+                associated_table_name,
+                false,
+                Rc::new(associated_table_catalog.table_desc()),
+                vec![],
+                context,
+                false,
+            ))
+            .into();
+            let plan: PlanRef =
+                StreamSink::new(scan_node, properties.clone(), secret_refs.clone()).into();
+
+            (
+                database_id,
+                schema_id,
+                associated_table_catalog.id().table_id,
+                plan,
+            )
+        }
+        CreateSink::AsQuery(query) => {
+            // The sink name is not schema-qualified, so it's created in the current schema.
+            let (database_id, schema_id) = session.get_database_and_schema_id_for_create(None)?;
+
+            let bound = {
+                let mut binder = Binder::new(session);
+                binder.bind_query(*query)?
+            };
+            let mut plan_root = Planner::new(context).plan_query(bound)?;
+            let plan: PlanRef = plan_root
+                .gen_sink_plan(properties.clone(), secret_refs.clone())?
+                .into();
+
+            // A sink from a query has no associated table.
+            (database_id, schema_id, 0, plan)
+        }
+    };
 
     let sink_name = Binder::resolve_sink_name(stmt.sink_name)?;
-    let properties = context.inner().with_options.clone();
     let sink = make_prost_sink(
         database_id,
         schema_id,
         sink_name,
-        associated_table_catalog.id().table_id,
+        associated_table_id,
         &properties,
+        secret_refs,
         session.user_id(),
+        definition,
     );
 
-    let scan_node = StreamTableScan::new(LogicalScan::create(
-        associated_table_name,
-        false,
-        Rc::new(associated_table_catalog.table_desc()),
-        vec![],
-        context,
-    ))
-    .into();
-
-    let plan: PlanRef = StreamSink::new(scan_node, properties).into();
-
     let ctx = plan.ctx();
     let explain_trace = ctx.is_explain_trace();
     if explain_trace {
@@ -161,5 +201,34 @@ pub mod tests {
             .get_sink_by_name(DEFAULT_DATABASE_NAME, SchemaPath::Name(schema_name), "snk1")
             .unwrap();
         assert_eq!(sink.name, "snk1");
+        assert_eq!(sink.associated_table_id, Some(table.id()));
+    }
+
+    #[tokio::test]
+    async fn test_create_sink_as_query_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int);")
+            .await
+            .unwrap();
+
+        let sql = r#"CREATE SINK snk AS SELECT v1, v2 + 1 AS v3 FROM t WHERE v1 > 0
+                    WITH (connector = 'mysql', mysql.endpoint = '127.0.0.1:3306', mysql.table =
+                        '<table_name>', mysql.database = '<database_name>', mysql.user = '<user_name>',
+                        mysql.password = '<password>');"#;
+        frontend.run_sql(sql).await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let (sink, _) = catalog_reader
+            .get_sink_by_name(
+                DEFAULT_DATABASE_NAME,
+                SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                "snk",
+            )
+            .unwrap();
+        assert_eq!(sink.name, "snk");
+        assert_eq!(sink.associated_table_id, None);
+        assert!(sink.definition.starts_with("CREATE SINK snk AS SELECT"));
     }
 }
@@ -16,26 +16,43 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::catalog::{ColumnDesc, ColumnId};
 use risingwave_common::error::ErrorCode::ProtocolError;
-use risingwave_common::error::{Result, RwError};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::DataType;
+use risingwave_connector::schema::SchemaEvolutionPolicy;
 use risingwave_pb::catalog::source::Info;
 use risingwave_pb::catalog::{
-    ColumnIndex as ProstColumnIndex, Source as ProstSource, StreamSourceInfo,
+    ColumnIndex as ProstColumnIndex, Source as ProstSource, SourceMetaKind as ProstSourceMetaKind,
+    StreamSourceInfo, WatermarkDesc,
 };
 use risingwave_pb::plan_common::{ColumnCatalog as ProstColumnCatalog, RowFormatType};
-use risingwave_source::{AvroParser, ProtobufParser};
+use risingwave_source::{
+    AvroParser, CsvParser, JsonParser, ProtobufParser, JSON_WHOLE_PAYLOAD_KEY,
+};
 use risingwave_sqlparser::ast::{
-    AvroSchema, CreateSourceStatement, ObjectName, ProtobufSchema, SourceSchema,
+    AvroSchema, CreateSourceStatement, ObjectName, ProtobufSchema, SourceIncludeColumn,
+    SourceMetaKind, SourceSchema, SourceWatermark,
 };
 
+use super::alter_source::check_source_rate_limits;
 use super::create_table::{
     bind_sql_columns, bind_sql_table_constraints, gen_materialized_source_plan,
 };
 use super::RwPgResponse;
 use crate::binder::Binder;
+use crate::catalog::column_catalog::ColumnCatalog;
+use crate::catalog::source_catalog::{
+    DATAGEN_CHANGE_STREAM_KEYS, DATAGEN_CONNECTOR, KAFKA_CONNECTOR, MYSQL_CDC_CONNECTOR,
+    NEXMARK_CONNECTOR, NEXMARK_TABLE_TYPE_KEY, POSTGRES_CDC_CONNECTOR, PULSAR_CONNECTOR,
+};
+use crate::catalog::SecretId;
+use crate::expr::{Expr, ExprImpl};
 use crate::session::{OptimizerContext, SessionImpl};
 use crate::stream_fragmenter::build_graph;
+use crate::WithOptions;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn make_prost_source(
     session: &SessionImpl,
     name: ObjectName,
@@ -43,7 +60,9 @@ pub(crate) fn make_prost_source(
     columns: Vec<ProstColumnCatalog>,
     pk_column_ids: Vec<i32>,
     properties: HashMap<String, String>,
+    secret_refs: HashMap<String, SecretId>,
     source_info: Info,
+    watermark_descs: Vec<WatermarkDesc>,
 ) -> Result<ProstSource> {
     let db_name = session.database();
     let (schema_name, name) = Binder::resolve_schema_qualified_name(db_name, name)?;
@@ -59,17 +78,242 @@ pub(crate) fn make_prost_source(
         columns,
         pk_column_ids,
         properties,
+        secret_refs,
         info: Some(source_info),
         owner: session.user_id(),
+        watermark_descs,
+        proposed_columns: vec![],
     })
 }
 
+/// Binds the `WATERMARK FOR <column> AS <expr>` definitions with all columns of the source in
+/// context. The expression must return the same type as the watermark column. Returns the bound
+/// expressions along with the indices of the watermark columns.
+pub(crate) fn bind_source_watermark(
+    session: &SessionImpl,
+    name: String,
+    source_watermarks: Vec<SourceWatermark>,
+    columns: &[ProstColumnCatalog],
+) -> Result<Vec<(usize, ExprImpl)>> {
+    let columns = columns
+        .iter()
+        .cloned()
+        .map(ColumnCatalog::from)
+        .collect_vec();
+    let mut binder = Binder::new(session);
+    binder.bind_columns_to_context(name, &columns)?;
+
+    let mut watermarks: Vec<(usize, ExprImpl)> = vec![];
+    for SourceWatermark { column, expr } in source_watermarks {
+        let column_name = column.real_value();
+        let Some(watermark_idx) = columns
+            .iter()
+            .position(|c| !c.is_hidden && c.name() == column_name) else {
+            return Err(ErrorCode::BindError(format!(
+                "column \"{column_name}\" named in watermark does not exist"
+            ))
+            .into());
+        };
+        if watermarks.iter().any(|(idx, _)| *idx == watermark_idx) {
+            return Err(ErrorCode::BindError(format!(
+                "multiple watermarks specified for column \"{column_name}\""
+            ))
+            .into());
+        }
+        let expr = binder.bind_column_expr(expr)?;
+        let data_type = columns[watermark_idx].data_type();
+        if &expr.return_type() != data_type {
+            return Err(ErrorCode::BindError(format!(
+                "the return type of the watermark expression must be {}, the type of column \"{}\", \
+                 but got {}",
+                data_type,
+                column_name,
+                expr.return_type()
+            ))
+            .into());
+        }
+        if expr.has_agg_call()
+            || expr.has_window_function()
+            || expr.has_table_function()
+            || expr.has_subquery()
+        {
+            return Err(ErrorCode::BindError(
+                "aggregate functions, window functions, set-returning functions and subqueries \
+                 are not allowed in watermark expressions"
+                    .to_string(),
+            )
+            .into());
+        }
+        watermarks.push((watermark_idx, expr));
+    }
+    Ok(watermarks)
+}
+
+pub(crate) fn to_watermark_descs(watermarks: &[(usize, ExprImpl)]) -> Vec<WatermarkDesc> {
+    watermarks
+        .iter()
+        .map(|(idx, expr)| WatermarkDesc {
+            watermark_idx: *idx as u32,
+            expr: Some(expr.to_expr_proto()),
+        })
+        .collect()
+}
+
+/// Binds the `INCLUDE` clause by appending the meta columns to `columns`. Returns the kinds of the
+/// meta columns keyed by column id.
+fn bind_include_columns(
+    with_options: &WithOptions,
+    include_columns: Vec<SourceIncludeColumn>,
+    columns: &mut Vec<ProstColumnCatalog>,
+) -> Result<HashMap<i32, i32>> {
+    let mut meta_columns = HashMap::new();
+    if include_columns.is_empty() {
+        return Ok(meta_columns);
+    }
+    if !with_options.is_connector(KAFKA_CONNECTOR) && !with_options.is_connector(PULSAR_CONNECTOR) {
+        return Err(ErrorCode::NotImplemented(
+            "INCLUDE on a source with connector other than kafka or pulsar".to_string(),
+            None.into(),
+        )
+        .into());
+    }
+
+    let mut next_column_id = columns
+        .iter()
+        .map(|c| c.column_desc.as_ref().unwrap().column_id)
+        .max()
+        .unwrap_or(0)
+        + 1;
+    for SourceIncludeColumn { kind, alias } in include_columns {
+        let (prost_kind, data_type) = match kind {
+            SourceMetaKind::Timestamp => (ProstSourceMetaKind::Timestamp, DataType::Timestampz),
+            SourceMetaKind::Partition => (ProstSourceMetaKind::Partition, DataType::Varchar),
+            SourceMetaKind::Offset => (ProstSourceMetaKind::Offset, DataType::Varchar),
+            SourceMetaKind::Key => (ProstSourceMetaKind::Key, DataType::Varchar),
+        };
+        if meta_columns.values().any(|k| *k == prost_kind as i32) {
+            return Err(
+                ErrorCode::BindError(format!("{} is included more than once", kind)).into(),
+            );
+        }
+        let name = alias.map_or_else(|| kind.to_string().to_lowercase(), |a| a.real_value());
+        if columns
+            .iter()
+            .any(|c| c.column_desc.as_ref().unwrap().name == name)
+        {
+            return Err(ErrorCode::BindError(format!(
+                "column \"{}\" specified more than once",
+                name
+            ))
+            .into());
+        }
+
+        let column_desc = ColumnDesc {
+            data_type,
+            column_id: ColumnId::new(next_column_id),
+            name,
+            field_descs: vec![],
+            type_name: String::new(),
+        };
+        columns.push(ProstColumnCatalog {
+            column_desc: Some(column_desc.to_protobuf()),
+            is_hidden: false,
+        });
+        meta_columns.insert(next_column_id, prost_kind as i32);
+        next_column_id += 1;
+    }
+    Ok(meta_columns)
+}
+
+/// Appends the standard Nexmark schema of the events chosen by `nexmark.table.type` to `columns`,
+/// which is used when a nexmark source is created without columns.
+fn bind_nexmark_columns(
+    with_properties: &HashMap<String, String>,
+    columns: &mut Vec<ProstColumnCatalog>,
+) -> Result<()> {
+    let table_type = with_properties
+        .get(NEXMARK_TABLE_TYPE_KEY)
+        .map(|s| s.as_str())
+        .unwrap_or_default();
+    let schema = match table_type {
+        "Person" => vec![
+            ("id", DataType::Int64),
+            ("name", DataType::Varchar),
+            ("email_address", DataType::Varchar),
+            ("credit_card", DataType::Varchar),
+            ("city", DataType::Varchar),
+            ("state", DataType::Varchar),
+            ("date_time", DataType::Timestamp),
+            ("extra", DataType::Varchar),
+        ],
+        "Auction" => vec![
+            ("id", DataType::Int64),
+            ("item_name", DataType::Varchar),
+            ("description", DataType::Varchar),
+            ("initial_bid", DataType::Int64),
+            ("reserve", DataType::Int64),
+            ("date_time", DataType::Timestamp),
+            ("expires", DataType::Timestamp),
+            ("seller", DataType::Int64),
+            ("category", DataType::Int64),
+            ("extra", DataType::Varchar),
+        ],
+        "Bid" => vec![
+            ("auction", DataType::Int64),
+            ("bidder", DataType::Int64),
+            ("price", DataType::Int64),
+            ("channel", DataType::Varchar),
+            ("url", DataType::Varchar),
+            ("date_time", DataType::Timestamp),
+            ("extra", DataType::Varchar),
+        ],
+        _ => {
+            return Err(ErrorCode::BindError(format!(
+                "{} of a nexmark source must be one of Person, Auction and Bid, but got \"{}\"",
+                NEXMARK_TABLE_TYPE_KEY, table_type
+            ))
+            .into())
+        }
+    };
+
+    let next_column_id = columns
+        .iter()
+        .map(|c| c.column_desc.as_ref().unwrap().column_id)
+        .max()
+        .unwrap_or(0)
+        + 1;
+    columns.extend(
+        schema
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, data_type))| ProstColumnCatalog {
+                column_desc: Some(
+                    ColumnDesc {
+                        data_type,
+                        column_id: ColumnId::new(next_column_id + i as i32),
+                        name: name.to_string(),
+                        field_descs: vec![],
+                        type_name: String::new(),
+                    }
+                    .to_protobuf(),
+                ),
+                is_hidden: false,
+            }),
+    );
+    Ok(())
+}
+
 /// Map an Avro schema to a relational schema.
 async fn extract_avro_table_schema(
     schema: &AvroSchema,
     with_properties: HashMap<String, String>,
 ) -> Result<Vec<ProstColumnCatalog>> {
-    let parser = AvroParser::new(schema.row_schema_location.0.as_str(), with_properties).await?;
+    let parser = AvroParser::new(
+        schema.row_schema_location.0.as_str(),
+        with_properties,
+        schema.use_schema_registry,
+    )
+    .await?;
     let vec_column_desc = parser.map_to_columns()?;
     Ok(vec_column_desc
         .into_iter()
@@ -89,6 +333,7 @@ async fn extract_protobuf_table_schema(
         &schema.row_schema_location.0,
         &schema.message_name.0,
         with_properties,
+        schema.use_schema_registry,
     )
     .await?;
     let column_descs = parser.map_to_columns()?;
@@ -107,13 +352,44 @@ pub async fn handle_create_source(
     is_materialized: bool,
     stmt: CreateSourceStatement,
 ) -> Result<RwPgResponse> {
+    let columns_omitted = stmt.columns.is_empty();
     let (column_descs, pk_column_id_from_columns) = bind_sql_columns(stmt.columns)?;
     let (mut columns, pk_column_ids, row_id_index) =
         bind_sql_table_constraints(column_descs, pk_column_id_from_columns, stmt.constraints)?;
 
     let mut with_properties = context.with_options.inner().clone();
+    check_source_rate_limits(&with_properties)?;
+
+    for connector in [MYSQL_CDC_CONNECTOR, POSTGRES_CDC_CONNECTOR] {
+        // The captured table is exposed as a materialized source with the same primary key.
+        if context.with_options.is_connector(connector)
+            && (!is_materialized || !matches!(stmt.source_schema, SourceSchema::DebeziumJson))
+        {
+            return Err(RwError::from(ProtocolError(format!(
+                "source with connector {} must be materialized with row format debezium_json",
+                connector
+            ))));
+        }
+    }
+
+    if context.with_options.is_connector(DATAGEN_CONNECTOR)
+        && DATAGEN_CHANGE_STREAM_KEYS
+            .iter()
+            .any(|key| with_properties.contains_key(*key))
+        && !matches!(stmt.source_schema, SourceSchema::DebeziumJson)
+    {
+        return Err(RwError::from(ProtocolError(
+            "datagen source with updates or deletes must be created with row format debezium_json"
+                .to_string(),
+        )));
+    }
 
-    let (columns, source_info) = match &stmt.source_schema {
+    // The events of nexmark have a fixed schema, so that the columns can be omitted.
+    if context.with_options.is_connector(NEXMARK_CONNECTOR) && columns_omitted {
+        bind_nexmark_columns(&with_properties, &mut columns)?;
+    }
+
+    let (mut columns, mut source_info) = match &stmt.source_schema {
         SourceSchema::Protobuf(protobuf_schema) => {
             // the key is identified with SourceParserImpl::create
             const PROTOBUF_MESSAGE_KEY: &str = "proto.message";
@@ -136,6 +412,8 @@ pub async fn handle_create_source(
                 StreamSourceInfo {
                     row_format: RowFormatType::Protobuf as i32,
                     row_schema_location: protobuf_schema.row_schema_location.0.clone(),
+                    use_schema_registry: protobuf_schema.use_schema_registry,
+                    ..Default::default()
                 },
             )
         }
@@ -149,16 +427,53 @@ pub async fn handle_create_source(
                 StreamSourceInfo {
                     row_format: RowFormatType::Avro as i32,
                     row_schema_location: avro_schema.row_schema_location.0.clone(),
+                    use_schema_registry: avro_schema.use_schema_registry,
+                    ..Default::default()
+                },
+            )
+        }
+        SourceSchema::Json => {
+            // The whole payload can only be stored in a single `jsonb` column.
+            if JsonParser::new(&with_properties)?.whole_payload() {
+                let is_single_jsonb_column = columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| Some(*idx) != row_id_index)
+                    .exactly_one()
+                    .map_or(false, |(_, column)| {
+                        ColumnDesc::from(column.column_desc.as_ref().unwrap()).data_type
+                            == DataType::Jsonb
+                    });
+                if !is_single_jsonb_column {
+                    return Err(RwError::from(ProtocolError(format!(
+                        "{} requires the source to have a single column of type jsonb",
+                        JSON_WHOLE_PAYLOAD_KEY
+                    ))));
+                }
+            }
+            (
+                columns,
+                StreamSourceInfo {
+                    row_format: RowFormatType::Json as i32,
+                    row_schema_location: "".to_string(),
+                    use_schema_registry: false,
+                    ..Default::default()
+                },
+            )
+        }
+        SourceSchema::Csv => {
+            // validate the options of delimiter, quote, header, etc.
+            CsvParser::new(&with_properties)?;
+            (
+                columns,
+                StreamSourceInfo {
+                    row_format: RowFormatType::Csv as i32,
+                    row_schema_location: "".to_string(),
+                    use_schema_registry: false,
+                    ..Default::default()
                 },
             )
         }
-        SourceSchema::Json => (
-            columns,
-            StreamSourceInfo {
-                row_format: RowFormatType::Json as i32,
-                row_schema_location: "".to_string(),
-            },
-        ),
         SourceSchema::Maxwell => {
             // return err if user has not specified a pk
             if row_id_index.is_some() {
@@ -172,6 +487,27 @@ pub async fn handle_create_source(
                 StreamSourceInfo {
                     row_format: RowFormatType::Maxwell as i32,
                     row_schema_location: "".to_string(),
+                    use_schema_registry: false,
+                    ..Default::default()
+                },
+            )
+        }
+
+        SourceSchema::CanalJson => {
+            // return err if user has not specified a pk
+            if row_id_index.is_some() {
+                return Err(RwError::from(ProtocolError(
+                    "Primary key must be specified when creating source with row format canal_json."
+                        .to_string(),
+                )));
+            }
+            (
+                columns,
+                StreamSourceInfo {
+                    row_format: RowFormatType::CanalJson as i32,
+                    row_schema_location: "".to_string(),
+                    use_schema_registry: false,
+                    ..Default::default()
                 },
             )
         }
@@ -189,11 +525,28 @@ pub async fn handle_create_source(
                 StreamSourceInfo {
                     row_format: RowFormatType::DebeziumJson as i32,
                     row_schema_location: "".to_string(),
+                    use_schema_registry: false,
+                    ..Default::default()
                 },
             )
         }
     };
 
+    source_info.meta_columns =
+        bind_include_columns(&context.with_options, stmt.include_columns, &mut columns)?;
+
+    // The columns of the table of a materialized source are fixed.
+    if SchemaEvolutionPolicy::from_properties(&with_properties, &source_info)?
+        != SchemaEvolutionPolicy::None
+        && is_materialized
+    {
+        return Err(ErrorCode::NotImplemented(
+            "schema evolution of a materialized source".to_string(),
+            None.into(),
+        )
+        .into());
+    }
+
     let row_id_index = row_id_index.map(|index| ProstColumnIndex { index: index as _ });
     let pk_column_ids = pk_column_ids.into_iter().map(Into::into).collect();
 
@@ -201,6 +554,21 @@ pub async fn handle_create_source(
 
     session.check_relation_name_duplicated(stmt.source_name.clone())?;
 
+    if !stmt.source_watermarks.is_empty() && !context.with_options.append_only() {
+        return Err(ErrorCode::NotImplemented(
+            "watermarks on a source that is not append-only".to_string(),
+            None.into(),
+        )
+        .into());
+    }
+    let watermarks = bind_source_watermark(
+        &session,
+        stmt.source_name.0.last().unwrap().real_value(),
+        stmt.source_watermarks,
+        &columns,
+    )?;
+
+    let secret_refs = context.with_options.resolve_secret_refs(&session)?;
     let source = make_prost_source(
         &session,
         stmt.source_name,
@@ -208,13 +576,19 @@ pub async fn handle_create_source(
         columns,
         pk_column_ids,
         with_properties,
+        secret_refs,
         Info::StreamSource(source_info),
+        to_watermark_descs(&watermarks),
     )?;
     let catalog_writer = session.env().catalog_writer();
     if is_materialized {
         let (graph, table) = {
-            let (plan, table) =
-                gen_materialized_source_plan(context.into(), source.clone(), session.user_id())?;
+            let (plan, table) = gen_materialized_source_plan(
+                context.into(),
+                source.clone(),
+                session.user_id(),
+                vec![],
+            )?;
             let graph = build_graph(plan);
 
             (graph, table)
@@ -233,11 +607,20 @@ pub async fn handle_create_source(
 pub mod tests {
     use std::collections::HashMap;
 
-    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+    use itertools::Itertools;
+    use risingwave_common::catalog::{
+        ColumnDesc, ColumnId, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME,
+    };
     use risingwave_common::types::DataType;
+    use risingwave_pb::catalog::SourceMetaKind;
+    use risingwave_pb::stream_plan::source_node::Info as StreamPlanInfo;
+    use risingwave_sqlparser::parser::Parser;
 
+    use crate::binder::{Binder, BoundStatement};
+    use crate::catalog::column_catalog::ColumnCatalog;
     use crate::catalog::root_catalog::SchemaPath;
     use crate::catalog::row_id_column_name;
+    use crate::handler::alter_source::apply_proposed_source_columns;
     use crate::test_utils::{create_proto_file, LocalFrontend, PROTO_FILE_DATA};
 
     #[tokio::test]
@@ -285,4 +668,257 @@ pub mod tests {
         };
         assert_eq!(columns, expected_columns);
     }
+
+    #[tokio::test]
+    async fn test_create_source_with_include_columns() {
+        let sql = r#"CREATE SOURCE s (v INT) INCLUDE TIMESTAMP AS ts, PARTITION, KEY AS k
+    WITH (connector = 'kafka', kafka.topic = 'abc', kafka.servers = 'localhost:1001')
+    ROW FORMAT JSON"#;
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql(sql).await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+        let (source, _) = catalog_reader
+            .get_source_by_name(DEFAULT_DATABASE_NAME, schema_path, "s")
+            .unwrap();
+
+        let columns = source
+            .columns
+            .iter()
+            .map(|col| (col.name(), col.data_type().clone()))
+            .collect_vec();
+        // the included columns are appended after `_row_id`
+        assert_eq!(columns[0], ("v", DataType::Int32));
+        assert_eq!(
+            &columns[2..],
+            [
+                ("ts", DataType::Timestampz),
+                ("partition", DataType::Varchar),
+                ("k", DataType::Varchar),
+            ]
+        );
+        let StreamPlanInfo::StreamSource(info) = &source.info else {
+            panic!("expect a stream source");
+        };
+        let meta_columns = source.columns[2..]
+            .iter()
+            .map(|col| info.meta_columns[&col.column_id().get_id()])
+            .collect_vec();
+        assert_eq!(
+            meta_columns,
+            [
+                SourceMetaKind::Timestamp as i32,
+                SourceMetaKind::Partition as i32,
+                SourceMetaKind::Key as i32,
+            ]
+        );
+
+        let sql = r#"CREATE SOURCE s2 (v INT) INCLUDE OFFSET AS v
+    WITH (connector = 'kafka', kafka.topic = 'abc', kafka.servers = 'localhost:1001')
+    ROW FORMAT JSON"#;
+        assert!(frontend.run_sql(sql).await.is_err());
+
+        let sql = r#"CREATE SOURCE s3 (v INT) INCLUDE OFFSET
+    WITH (connector = 'datagen') ROW FORMAT JSON"#;
+        assert!(frontend.run_sql(sql).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_nexmark_source_without_columns() {
+        let sql = r#"CREATE SOURCE bid WITH (connector = 'nexmark', nexmark.table.type = 'Bid')
+    ROW FORMAT JSON"#;
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql(sql).await.unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+        let (source, _) = catalog_reader
+            .get_source_by_name(DEFAULT_DATABASE_NAME, schema_path, "bid")
+            .unwrap();
+        let columns = source
+            .columns
+            .iter()
+            .map(|col| (col.name(), col.data_type().clone()))
+            .collect_vec();
+        assert_eq!(
+            &columns[1..],
+            [
+                ("auction", DataType::Int64),
+                ("bidder", DataType::Int64),
+                ("price", DataType::Int64),
+                ("channel", DataType::Varchar),
+                ("url", DataType::Varchar),
+                ("date_time", DataType::Timestamp),
+                ("extra", DataType::Varchar),
+            ]
+        );
+
+        let sql = r#"CREATE SOURCE s WITH (connector = 'nexmark', nexmark.table.type = 'Seller')
+    ROW FORMAT JSON"#;
+        assert!(frontend.run_sql(sql).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_source_with_rate_limit() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        for rate_limit in ["0", "-1", "'fast'"] {
+            let sql = format!(
+                "CREATE SOURCE s (v INT) WITH (connector = 'datagen', rate_limit = {}) \
+                ROW FORMAT JSON",
+                rate_limit
+            );
+            assert!(frontend.run_sql(sql).await.is_err());
+        }
+
+        let sql = r#"CREATE SOURCE s (v INT) WITH (connector = 'datagen', rate_limit = 100,
+    byte_rate_limit = 1024) ROW FORMAT JSON"#;
+        frontend.run_sql(sql).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_datagen_source_with_change_stream() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        let sql = r#"CREATE SOURCE s (v INT) WITH (connector = 'datagen',
+    fields.v.kind = 'sequence', datagen.update.ratio = '0.2') ROW FORMAT JSON"#;
+        assert!(frontend.run_sql(sql).await.is_err());
+
+        let sql = r#"CREATE SOURCE s (v INT PRIMARY KEY) WITH (connector = 'datagen',
+    fields.v.kind = 'sequence', datagen.update.ratio = '0.2') ROW FORMAT DEBEZIUM_JSON"#;
+        frontend.run_sql(sql).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_csv_source() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        let sql = r#"CREATE SOURCE s (v1 INT, v2 VARCHAR) WITH (connector = 's3',
+    csv.delimiter = '||') ROW FORMAT CSV"#;
+        assert!(frontend.run_sql(sql).await.is_err());
+
+        let sql = r#"CREATE SOURCE s (v1 INT, v2 VARCHAR) WITH (connector = 's3',
+    csv.delimiter = '|', csv.header = 'true') ROW FORMAT CSV"#;
+        frontend.run_sql(sql).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_json_source_with_whole_payload() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        let sql = r#"CREATE SOURCE s (v JSONB) WITH (connector = 'kafka', kafka.topic = 'abc',
+    kafka.servers = 'localhost:1001', json.whole_payload = 'yes') ROW FORMAT JSON"#;
+        assert!(frontend.run_sql(sql).await.is_err());
+
+        let sql = r#"CREATE SOURCE s (id INT, v JSONB) WITH (connector = 'kafka', kafka.topic = 'abc',
+    kafka.servers = 'localhost:1001', json.whole_payload = 'true') ROW FORMAT JSON"#;
+        assert!(frontend.run_sql(sql).await.is_err());
+
+        let sql = r#"CREATE SOURCE s (v JSONB) WITH (connector = 'kafka', kafka.topic = 'abc',
+    kafka.servers = 'localhost:1001', json.whole_payload = 'true') ROW FORMAT JSON"#;
+        frontend.run_sql(sql).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_view_of_source_with_added_columns() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        // Only the sources with a schema registry can evolve.
+        let sql = r#"CREATE SOURCE s (v INT) WITH (connector = 'kafka', kafka.topic = 'abc',
+    kafka.servers = 'localhost:1001', schema.evolution.policy = 'backward') ROW FORMAT JSON"#;
+        assert!(frontend.run_sql(sql).await.is_err());
+
+        let sql = r#"CREATE SOURCE s (v INT) WITH (connector = 'kafka', kafka.topic = 'abc',
+    kafka.servers = 'localhost:1001') ROW FORMAT JSON"#;
+        frontend.run_sql(sql).await.unwrap();
+        frontend
+            .run_sql("CREATE VIEW v AS SELECT * FROM s")
+            .await
+            .unwrap();
+
+        frontend
+            .run_sql("CREATE MATERIALIZED VIEW mv AS SELECT * FROM s")
+            .await
+            .unwrap();
+
+        // Propose a column for the source as meta does when the upstream schema evolves.
+        let session = frontend.session_ref();
+        let (source, database_id, schema_id) = {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let (source, _) = catalog_reader
+                .get_source_by_name(
+                    DEFAULT_DATABASE_NAME,
+                    SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                    "s",
+                )
+                .unwrap();
+            let mut source = source.as_ref().clone();
+            let next_column_id = source
+                .columns
+                .iter()
+                .map(|c| c.column_id().get_id())
+                .max()
+                .unwrap()
+                + 1;
+            source.proposed_columns.push(ColumnCatalog {
+                column_desc: ColumnDesc {
+                    data_type: DataType::Varchar,
+                    column_id: ColumnId::new(next_column_id),
+                    name: "w".to_string(),
+                    field_descs: vec![],
+                    type_name: String::new(),
+                },
+                is_hidden: false,
+            });
+            let database_id = catalog_reader
+                .get_database_by_name(DEFAULT_DATABASE_NAME)
+                .unwrap()
+                .id();
+            let schema_id = catalog_reader
+                .get_schema_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME)
+                .unwrap()
+                .id();
+            (
+                source.to_prost(schema_id, database_id),
+                database_id,
+                schema_id,
+            )
+        };
+        let source_id = source.id;
+        session
+            .env()
+            .catalog_writer()
+            .alter_source(source, vec![], vec![])
+            .await
+            .unwrap();
+        apply_proposed_source_columns(&session, database_id, schema_id, source_id)
+            .await
+            .unwrap();
+
+        {
+            let catalog_reader = session.env().catalog_reader().read_guard();
+            let source = catalog_reader
+                .get_schema_by_id(&database_id, &schema_id)
+                .unwrap()
+                .get_source_by_id(&source_id)
+                .unwrap();
+            assert!(source.proposed_columns.is_empty());
+            assert_eq!(source.columns.last().unwrap().name(), "w");
+        }
+
+        let stmt = Parser::parse_sql("SELECT * FROM v").unwrap().remove(0);
+        let BoundStatement::Query(query) = Binder::new(&session).bind(stmt).unwrap() else {
+            panic!("expect a query");
+        };
+        let columns = query
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| (f.name.as_str(), f.data_type.clone()))
+            .collect_vec();
+        assert_eq!(columns, [("v", DataType::Int32), ("w", DataType::Varchar)]);
+    }
 }
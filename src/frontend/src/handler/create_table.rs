@@ -26,15 +26,19 @@ use risingwave_pb::catalog::{
 };
 use risingwave_pb::plan_common::ColumnCatalog as ProstColumnCatalog;
 use risingwave_sqlparser::ast::{
-    ColumnDef, ColumnOption, DataType as AstDataType, ObjectName, TableConstraint,
+    ColumnDef, ColumnOption, DataType as AstDataType, Ident, ObjectName, SourceWatermark,
+    Statement, TableConstraint,
 };
 
-use super::create_source::make_prost_source;
+use super::create_source::{bind_source_watermark, make_prost_source, to_watermark_descs};
 use super::RwPgResponse;
-use crate::binder::{bind_data_type, bind_struct_field};
+use crate::binder::{bind_data_type, bind_struct_field, Binder};
 use crate::catalog::column_catalog::ColumnCatalog;
+use crate::catalog::source_catalog::SourceCatalog;
+use crate::catalog::table_catalog::TableVersion;
 use crate::catalog::{check_valid_column_name, ColumnId};
-use crate::optimizer::plan_node::{LogicalSource, StreamSource};
+use crate::expr::{Expr, ExprImpl, InputRef, Literal};
+use crate::optimizer::plan_node::{LogicalProject, LogicalSource, StreamProject, StreamSource};
 use crate::optimizer::property::{Order, RequiredDist};
 use crate::optimizer::{PlanRef, PlanRoot};
 use crate::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
@@ -110,6 +114,129 @@ pub fn bind_sql_columns(columns: Vec<ColumnDef>) -> Result<(Vec<ColumnDesc>, Opt
     Ok((column_descs, pk_column_id))
 }
 
+/// Takes the generation expressions and default values out of the column definitions, leaving the
+/// other options to [`bind_sql_columns`]. Returns the taken options along with their column
+/// indices.
+fn take_sql_column_exprs(columns: &mut [ColumnDef]) -> Vec<(usize, ColumnOption)> {
+    let mut column_exprs = vec![];
+    for (i, column) in columns.iter_mut().enumerate() {
+        let (exprs, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut column.options)
+            .into_iter()
+            .partition(|option_def| {
+                matches!(
+                    option_def.option,
+                    ColumnOption::Default(_) | ColumnOption::GeneratedColumns(_)
+                )
+            });
+        column.options = rest;
+        column_exprs.extend(exprs.into_iter().map(|option_def| (i, option_def.option)));
+    }
+    column_exprs
+}
+
+/// Binds the generation expressions and default values of the columns with all columns of the
+/// table in context. Returns the generation expressions by column index, which are evaluated when
+/// rows are ingested into the table, together with the info to be persisted in the catalog.
+fn bind_sql_column_exprs(
+    session: &SessionImpl,
+    table_name: String,
+    columns: &[ProstColumnCatalog],
+    pk_column_ids: &[ColumnId],
+    column_exprs: Vec<(usize, ColumnOption)>,
+) -> Result<(Vec<(usize, ExprImpl)>, TableSourceInfo)> {
+    let columns = columns
+        .iter()
+        .cloned()
+        .map(ColumnCatalog::from)
+        .collect_vec();
+    let mut binder = Binder::new(session);
+    binder.bind_columns_to_context(table_name, &columns)?;
+
+    let mut generated_columns = vec![];
+    let mut info = TableSourceInfo::default();
+    for (idx, option) in column_exprs {
+        let column = &columns[idx];
+        let column_id = column.column_id().get_id();
+        if info.generated_columns.contains_key(&column_id)
+            || info.default_columns.contains_key(&column_id)
+        {
+            return Err(ErrorCode::BindError(format!(
+                "multiple default values or generation expressions specified for column \"{}\"",
+                column.name()
+            ))
+            .into());
+        }
+        match option {
+            ColumnOption::GeneratedColumns(expr) => {
+                if pk_column_ids.contains(&column.column_id()) {
+                    return Err(ErrorCode::NotImplemented(
+                        "generated column as primary key".to_string(),
+                        None.into(),
+                    )
+                    .into());
+                }
+                let expr = binder
+                    .bind_column_expr(expr)?
+                    .cast_assign(column.data_type().clone())?;
+                if expr.has_agg_call()
+                    || expr.has_window_function()
+                    || expr.has_table_function()
+                    || expr.has_subquery()
+                {
+                    return Err(ErrorCode::BindError(format!(
+                        "aggregate functions, window functions, set-returning functions and \
+                         subqueries are not allowed in the generation expression of column \"{}\"",
+                        column.name()
+                    ))
+                    .into());
+                }
+                info.generated_columns
+                    .insert(column_id, expr.to_expr_proto());
+                generated_columns.push((idx, expr));
+            }
+            ColumnOption::Default(expr) => {
+                let expr = binder
+                    .bind_column_expr(expr)?
+                    .cast_assign(column.data_type().clone())?;
+                if expr.has_input_ref() {
+                    return Err(ErrorCode::BindError(
+                        "cannot use column reference in DEFAULT expression".to_string(),
+                    )
+                    .into());
+                }
+                if !expr.is_const() {
+                    return Err(ErrorCode::NotImplemented(
+                        format!("non-constant default value of column \"{}\"", column.name()),
+                        None.into(),
+                    )
+                    .into());
+                }
+                let value = Literal::new(expr.eval_row_const()?, column.data_type().clone());
+                info.default_columns
+                    .insert(column_id, value.to_expr_proto());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    // Generated columns are computed in a single pass, so they can not depend on each other.
+    for (_, expr) in &generated_columns {
+        let input_refs = expr.collect_input_refs(columns.len());
+        if let Some((idx, _)) = generated_columns
+            .iter()
+            .find(|(idx, _)| input_refs.contains(*idx))
+        {
+            return Err(ErrorCode::BindError(format!(
+                "cannot use generated column \"{}\" in column generation expression",
+                columns[*idx].name()
+            ))
+            .into());
+        }
+    }
+
+    Ok((generated_columns, info))
+}
+
 /// Binds table constraints given the binding results from column definitions.
 /// It returns the columns together with `pk_column_ids`, and an optional row id column index if
 /// added.
@@ -199,15 +326,112 @@ pub(crate) fn gen_create_table_plan(
     session: &SessionImpl,
     context: OptimizerContextRef,
     table_name: ObjectName,
-    columns: Vec<ColumnDef>,
+    mut columns: Vec<ColumnDef>,
     constraints: Vec<TableConstraint>,
+    source_watermarks: Vec<SourceWatermark>,
 ) -> Result<(PlanRef, ProstSource, ProstTable)> {
+    // The `WITH` options are left out of the definition, since they are kept in the properties of
+    // the table.
+    let definition = Statement::CreateTable {
+        or_replace: false,
+        temporary: false,
+        if_not_exists: false,
+        name: table_name.clone(),
+        columns: columns.clone(),
+        constraints: constraints.clone(),
+        with_options: vec![],
+        source_watermarks: source_watermarks.clone(),
+        query: None,
+    }
+    .to_string();
+    let column_exprs = take_sql_column_exprs(&mut columns);
     let (column_descs, pk_column_id_from_columns) = bind_sql_columns(columns)?;
+    let (plan, source, mut table) = gen_create_table_plan_without_bind(
+        session,
+        context,
+        table_name,
+        column_descs,
+        pk_column_id_from_columns,
+        constraints,
+        column_exprs,
+        source_watermarks,
+    )?;
+    table.definition = definition;
+    Ok((plan, source, table))
+}
+
+/// Generates the `CREATE TABLE` definition of a table whose columns are not declared in the
+/// statement, e.g. by `CREATE TABLE AS`.
+pub(crate) fn gen_definition_from_columns(
+    table_name: &ObjectName,
+    columns: &[ColumnDesc],
+) -> String {
+    format!(
+        "CREATE TABLE {} ({})",
+        table_name,
+        columns
+            .iter()
+            .map(|c| format!("{} {}", Ident::from_real_value(&c.name), c.data_type))
+            .join(", ")
+    )
+}
+
+/// Generates the plan of a table whose columns are already bound into `ColumnDesc`s, e.g. from
+/// the schema of the query in `CREATE TABLE AS`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gen_create_table_plan_without_bind(
+    session: &SessionImpl,
+    context: OptimizerContextRef,
+    table_name: ObjectName,
+    column_descs: Vec<ColumnDesc>,
+    pk_column_id_from_columns: Option<ColumnId>,
+    constraints: Vec<TableConstraint>,
+    column_exprs: Vec<(usize, ColumnOption)>,
+    source_watermarks: Vec<SourceWatermark>,
+) -> Result<(PlanRef, ProstSource, ProstTable)> {
     let (columns, pk_column_ids, row_id_index) =
         bind_sql_table_constraints(column_descs, pk_column_id_from_columns, constraints)?;
+    let (generated_columns, info) = bind_sql_column_exprs(
+        session,
+        table_name.0.last().unwrap().real_value(),
+        &columns,
+        &pk_column_ids,
+        column_exprs,
+    )?;
+    if !source_watermarks.is_empty() && !context.inner().with_options.append_only() {
+        return Err(ErrorCode::NotImplemented(
+            "watermarks on a table that is not append-only".to_string(),
+            None.into(),
+        )
+        .into());
+    }
+    let watermarks = bind_source_watermark(
+        session,
+        table_name.0.last().unwrap().real_value(),
+        source_watermarks,
+        &columns,
+    )?;
+    // Watermarks are generated in the source executor before the generated columns are computed.
+    for (watermark_idx, expr) in &watermarks {
+        let input_refs = expr.collect_input_refs(columns.len());
+        if let Some((idx, _)) = generated_columns
+            .iter()
+            .find(|(idx, _)| idx == watermark_idx || input_refs.contains(*idx))
+        {
+            return Err(ErrorCode::NotImplemented(
+                format!(
+                    "watermark on or referring to generated column \"{}\"",
+                    ColumnCatalog::from(columns[*idx].clone()).name()
+                ),
+                None.into(),
+            )
+            .into());
+        }
+    }
     let row_id_index = row_id_index.map(|index| ProstColumnIndex { index: index as _ });
     let pk_column_ids = pk_column_ids.into_iter().map(Into::into).collect();
     let properties = context.inner().with_options.inner().clone();
+    let secret_refs = context.inner().with_options.resolve_secret_refs(session)?;
     let source = make_prost_source(
         session,
         table_name,
@@ -215,27 +439,63 @@ pub(crate) fn gen_create_table_plan(
         columns,
         pk_column_ids,
         properties,
-        Info::TableSource(TableSourceInfo {}),
+        secret_refs,
+        Info::TableSource(info),
+        to_watermark_descs(&watermarks),
     )?;
-    let (plan, table) = gen_materialized_source_plan(context, source.clone(), session.user_id())?;
+    let (plan, mut table) = gen_materialized_source_plan(
+        context,
+        source.clone(),
+        session.user_id(),
+        generated_columns,
+    )?;
+    let columns = table
+        .columns
+        .iter()
+        .cloned()
+        .map(ColumnCatalog::from)
+        .collect_vec();
+    table.version = Some(TableVersion::new_initial(&columns).to_prost());
     Ok((plan, source, table))
 }
 
 /// Generate a stream plan with `StreamSource` + `StreamMaterialize`, it resembles a
-/// `CREATE MATERIALIZED VIEW AS SELECT * FROM <source>`.
+/// `CREATE MATERIALIZED VIEW AS SELECT * FROM <source>`. If there are `generated_columns`, a
+/// `StreamProject` is added in between to compute them.
 pub(crate) fn gen_materialized_source_plan(
     context: OptimizerContextRef,
     source: ProstSource,
     owner: u32,
+    generated_columns: Vec<(usize, ExprImpl)>,
 ) -> Result<(PlanRef, ProstTable)> {
     let materialize = {
         // Manually assemble the materialization plan for the table.
-        let source_node: PlanRef =
-            StreamSource::new(LogicalSource::new(Rc::new((&source).into()), context)).into();
+        let catalog: Rc<SourceCatalog> = Rc::new((&source).into());
+        // Tables read the DML channel anyway, while the ones defined with a connector merge the
+        // rows written by DML statements into the stream of the connector.
+        let logical_source = if catalog.is_stream() {
+            LogicalSource::with_dml(catalog, context)
+        } else {
+            LogicalSource::new(catalog, context)
+        };
+        let mut source_node: PlanRef = StreamSource::new(logical_source).into();
         let row_id_index = source.row_id_index.as_ref().map(|index| index.index as _);
         let mut required_cols = FixedBitSet::with_capacity(source_node.schema().len());
         required_cols.toggle_range(..);
         let mut out_names = source_node.schema().names();
+        if !generated_columns.is_empty() {
+            let mut exprs = source_node
+                .schema()
+                .fields()
+                .iter()
+                .enumerate()
+                .map(|(i, field)| InputRef::new(i, field.data_type()).into())
+                .collect_vec();
+            for (idx, expr) in generated_columns {
+                exprs[idx] = expr;
+            }
+            source_node = StreamProject::new(LogicalProject::new(source_node, exprs)).into();
+        }
         if let Some(row_id_index) = row_id_index {
             required_cols.toggle(row_id_index);
             out_names.remove(row_id_index);
@@ -248,7 +508,7 @@ pub(crate) fn gen_materialized_source_plan(
             required_cols,
             out_names,
         )
-        .gen_create_mv_plan(source.name.clone(), "".into(), None)?
+        .gen_create_mv_plan(source.name.clone(), "".into(), None, false)?
     };
     let mut table = materialize
         .table()
@@ -262,6 +522,7 @@ pub async fn handle_create_table(
     table_name: ObjectName,
     columns: Vec<ColumnDef>,
     constraints: Vec<TableConstraint>,
+    source_watermarks: Vec<SourceWatermark>,
 ) -> Result<RwPgResponse> {
     let session = context.session_ctx.clone();
 
@@ -274,6 +535,7 @@ pub async fn handle_create_table(
             table_name.clone(),
             columns,
             constraints,
+            source_watermarks,
         )?;
         let graph = build_graph(plan);
 
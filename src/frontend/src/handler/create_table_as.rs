@@ -0,0 +1,209 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::catalog::ColumnDesc;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_pb::catalog::Table as ProstTable;
+use risingwave_sqlparser::ast::{ColumnDef, ObjectName, Query, Statement};
+
+use super::create_table::{gen_create_table_plan_without_bind, gen_definition_from_columns};
+use super::query::handle_query;
+use super::RwPgResponse;
+use crate::binder::{bind_data_type, Binder, BoundStatement};
+use crate::catalog::{check_valid_column_name, CatalogError};
+use crate::session::{OptimizerContext, SessionImpl};
+use crate::stream_fragmenter::build_graph;
+use crate::utils::WithOptions;
+
+/// Handles `CREATE TABLE <name> [(<columns>)] AS <query>`. The table is created with the schema
+/// of the query, and then backfilled by `INSERT INTO <name> <query>`. Afterwards, it is a normal
+/// table that accepts DML. If the backfill fails, the table is dropped again.
+pub async fn handle_create_as(
+    context: OptimizerContext,
+    table_name: ObjectName,
+    columns: Vec<ColumnDef>,
+    query: Box<Query>,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx.clone();
+    let sql = context.sql.clone();
+
+    session.check_relation_name_duplicated(table_name.clone())?;
+
+    let mut column_descs: Vec<ColumnDesc> = {
+        let mut binder = Binder::new(&session);
+        let bound = binder.bind(Statement::Query(query.clone()))?;
+        let BoundStatement::Query(bound) = bound else {
+            unreachable!()
+        };
+        bound
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| ColumnDesc::from_field_with_column_id(field, i as i32))
+            .collect()
+    };
+
+    if columns.len() > column_descs.len() {
+        return Err(ErrorCode::InvalidInputSyntax(
+            "too many column names were specified".to_string(),
+        )
+        .into());
+    }
+    // The column names specified in the statement override the ones from the query. The types
+    // must agree with the query, since the rows are inserted as they are.
+    for (column, column_desc) in columns.into_iter().zip(column_descs.iter_mut()) {
+        if !column.options.is_empty() || column.collation.is_some() {
+            return Err(ErrorCode::NotImplemented(
+                format!("column options in CREATE TABLE AS \"{}\"", column),
+                None.into(),
+            )
+            .into());
+        }
+        let data_type = bind_data_type(&column.data_type)?;
+        if data_type != column_desc.data_type {
+            return Err(ErrorCode::BindError(format!(
+                "column \"{}\" is of type {} but the query returns {}",
+                column.name.real_value(),
+                data_type,
+                column_desc.data_type
+            ))
+            .into());
+        }
+        column_desc.name = column.name.real_value();
+    }
+    for column_desc in &column_descs {
+        check_valid_column_name(&column_desc.name)?;
+    }
+
+    let (graph, source, table) = {
+        let definition = gen_definition_from_columns(&table_name, &column_descs);
+        let (plan, source, mut table) = gen_create_table_plan_without_bind(
+            &session,
+            context.into(),
+            table_name.clone(),
+            column_descs,
+            None,
+            vec![],
+            vec![],
+            vec![],
+        )?;
+        table.definition = definition;
+        let graph = build_graph(plan);
+
+        (graph, source, table)
+    };
+
+    tracing::trace!(
+        "name={}, graph=\n{}",
+        table_name,
+        serde_json::to_string_pretty(&graph).unwrap()
+    );
+
+    let created_table = table.clone();
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer
+        .create_materialized_source(source, table, graph)
+        .await?;
+
+    // Backfill the table through the DML path.
+    let insert = Statement::Insert {
+        table_name,
+        columns: vec![],
+        source: query,
+        returning: vec![],
+    };
+    let context = OptimizerContext::new(session.clone(), sql, WithOptions::new(Default::default()));
+    match handle_query(context, insert, vec![]).await {
+        // Reports the number of rows like `SELECT INTO`, as PostgreSQL does.
+        Ok(response) => Ok(PgResponse::with_row_cnt(
+            StatementType::SELECT,
+            response
+                .get_effected_rows_cnt()
+                .expect("row count should be set"),
+        )),
+        Err(err) => {
+            if let Err(drop_err) = drop_created_table(&session, &created_table).await {
+                tracing::warn!(
+                    "failed to drop table {} after its backfill failed: {}",
+                    created_table.name,
+                    drop_err
+                );
+            }
+            Err(err)
+        }
+    }
+}
+
+/// Drops the table created by `CREATE TABLE AS`, along with its associated source.
+async fn drop_created_table(session: &SessionImpl, table: &ProstTable) -> Result<()> {
+    let (source_id, table_id) = {
+        let reader = session.env().catalog_reader().read_guard();
+        let table = reader
+            .get_schema_by_id(&table.database_id, &table.schema_id)?
+            .get_table_by_name(&table.name)
+            .ok_or_else(|| CatalogError::NotFound("table", table.name.clone()))?;
+        (table.associated_source_id().unwrap(), table.id())
+    };
+    session
+        .env()
+        .catalog_writer()
+        .drop_materialized_source(source_id.table_id(), table_id)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_create_table_as_handler_invalid_columns() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 varchar);")
+            .await
+            .unwrap();
+
+        let err = frontend
+            .run_sql("create table t2 (c1 int, c2 varchar, c3 int) as select v1, v2 from t;")
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("too many column names were specified"));
+
+        let err = frontend
+            .run_sql("create table t2 (c1 varchar) as select v1, v2 from t;")
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("column \"c1\" is of type varchar but the query returns integer"));
+
+        // Nothing is created when the binding fails.
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        assert!(catalog_reader
+            .get_table_by_name(
+                DEFAULT_DATABASE_NAME,
+                SchemaPath::Name(DEFAULT_SCHEMA_NAME),
+                "t2"
+            )
+            .is_err());
+    }
+}
@@ -12,14 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use itertools::Itertools;
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::Row;
-use risingwave_common::catalog::ColumnDesc;
+use risingwave_common::catalog::{ColumnDesc, ColumnId};
 use risingwave_common::error::Result;
 use risingwave_sqlparser::ast::{display_comma_separated, ObjectName};
 
@@ -41,28 +41,32 @@ pub fn handle_describe(context: OptimizerContext, table_name: ObjectName) -> Res
     let catalog_reader = session.env().catalog_reader().read_guard();
 
     // For Source, it doesn't have table catalog so use get source to get column descs.
-    let (columns, indices): (Vec<ColumnDesc>, Vec<Arc<IndexCatalog>>) = {
-        let (catalogs, indices) =
-            match catalog_reader.get_table_by_name(db_name, schema_path, &table_name) {
-                Ok((table, schema_name)) => (
-                    &table.columns,
-                    catalog_reader
-                        .get_schema_by_name(session.database(), schema_name)?
-                        .get_indexes_by_table_id(&table.id),
-                ),
+    let (columns, indices, description, column_descriptions): (
+        Vec<ColumnDesc>,
+        Vec<Arc<IndexCatalog>>,
+        Option<String>,
+        HashMap<ColumnId, String>,
+    ) = {
+        let (catalogs, indices, description, column_descriptions) = match catalog_reader
+            .get_table_by_name(db_name, schema_path, &table_name)
+        {
+            Ok((table, schema_name)) => (
+                &table.columns,
+                catalog_reader
+                    .get_schema_by_name(session.database(), schema_name)?
+                    .get_indexes_by_table_id(&table.id),
+                table.description.clone(),
+                table.column_descriptions.clone(),
+            ),
+            Err(_) => match catalog_reader.get_source_by_name(db_name, schema_path, &table_name) {
+                Ok((source, _)) => (&source.columns, vec![], None, HashMap::new()),
                 Err(_) => {
-                    match catalog_reader.get_source_by_name(db_name, schema_path, &table_name) {
-                        Ok((source, _)) => (&source.columns, vec![]),
-                        Err(_) => {
-                            return Err(CatalogError::NotFound(
-                                "table or source",
-                                table_name.to_string(),
-                            )
-                            .into());
-                        }
-                    }
+                    return Err(
+                        CatalogError::NotFound("table or source", table_name.to_string()).into(),
+                    );
                 }
-            };
+            },
+        };
         (
             catalogs
                 .iter()
@@ -70,11 +74,13 @@ pub fn handle_describe(context: OptimizerContext, table_name: ObjectName) -> Res
                 .map(|c| c.column_desc.clone())
                 .collect(),
             indices,
+            description,
+            column_descriptions,
         )
     };
 
     // Convert all column descs to rows
-    let mut rows = col_descs_to_rows(columns);
+    let mut rows = col_descs_to_rows(columns, &column_descriptions);
 
     // Convert all indexes to rows
     rows.extend(indices.iter().map(|index| {
@@ -130,9 +136,19 @@ pub fn handle_describe(context: OptimizerContext, table_name: ObjectName) -> Res
                     .into(),
                 )
             },
+            None,
         ])
     }));
 
+    // The comment on the table is shown in the last row.
+    if let Some(description) = description {
+        rows.push(Row::new(vec![
+            Some("table description".into()),
+            None,
+            Some(description.into()),
+        ]));
+    }
+
     // TODO: recover the original user statement
     Ok(PgResponse::new_for_stream(
         StatementType::DESCRIBE_TABLE,
@@ -141,6 +157,7 @@ pub fn handle_describe(context: OptimizerContext, table_name: ObjectName) -> Res
         vec![
             PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
             PgFieldDescriptor::new("Type".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Description".to_owned(), TypeOid::Varchar),
         ],
     ))
 }
@@ -0,0 +1,111 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_sqlparser::ast::{DataType, DropMode, ObjectName};
+
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::binder::{bind_data_type, Binder};
+use crate::catalog::root_catalog::SchemaPath;
+use crate::catalog::CatalogError;
+use crate::session::OptimizerContext;
+
+pub async fn handle_drop_function(
+    context: OptimizerContext,
+    if_exists: bool,
+    function_name: ObjectName,
+    args: Option<Vec<DataType>>,
+    drop_mode: Option<DropMode>,
+) -> Result<RwPgResponse> {
+    if drop_mode == Some(DropMode::Cascade) {
+        return Err(
+            ErrorCode::NotImplemented("DROP FUNCTION CASCADE".to_string(), None.into()).into(),
+        );
+    }
+
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, function_name) =
+        Binder::resolve_schema_qualified_name(db_name, function_name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+    let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+
+    let function_id = {
+        let reader = session.env().catalog_reader().read_guard();
+        let res = match args {
+            Some(args) => {
+                let arg_types: Vec<_> = args.iter().map(bind_data_type).try_collect()?;
+                reader
+                    .get_function_by_name_args(db_name, schema_path, &function_name, &arg_types)
+                    .map(|(function, schema_name)| (function.clone(), schema_name.to_string()))
+            }
+            // Without an argument list the function name must be unique in its schema.
+            None => {
+                let schema_name = match schema_path {
+                    SchemaPath::Name(schema_name) => schema_name.to_string(),
+                    SchemaPath::Path(..) => reader
+                        .first_valid_schema(db_name, &search_path, user_name)?
+                        .name(),
+                };
+                let schema = reader.get_schema_by_name(db_name, &schema_name)?;
+                match schema.get_functions_by_name(&function_name) {
+                    Some(functions) if functions.len() > 1 => {
+                        return Err(ErrorCode::CatalogError(
+                            format!("function name \"{}\" is not unique", function_name).into(),
+                        )
+                        .into());
+                    }
+                    Some(functions) => Ok((functions[0].clone(), schema.name())),
+                    None => Err(CatalogError::NotFound("function", function_name.clone())),
+                }
+            }
+        };
+        let (function, schema_name) = match res {
+            Ok(res) => res,
+            Err(e) => {
+                return if if_exists {
+                    Ok(RwPgResponse::empty_result_with_notice(
+                        StatementType::DROP_FUNCTION,
+                        format!("function \"{}\" does not exist, skipping", function_name),
+                    ))
+                } else {
+                    Err(e.into())
+                };
+            }
+        };
+
+        let schema_owner = reader
+            .get_schema_by_name(db_name, &schema_name)
+            .unwrap()
+            .owner();
+        if session.user_id() != function.owner
+            && session.user_id() != schema_owner
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        function.id
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    catalog_writer.drop_function(function_id).await?;
+
+    Ok(PgResponse::empty_result(StatementType::DROP_FUNCTION))
+}
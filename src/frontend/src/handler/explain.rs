@@ -13,37 +13,52 @@
 // limitations under the License.
 
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
 
+use futures::StreamExt;
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::Row;
+use risingwave_batch::executor::ExecutorStatsCollector;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::{ExplainOptions, ExplainType, Statement};
+use risingwave_common::session_config::QueryMode;
+use risingwave_sqlparser::ast::{ExplainFormat, ExplainOptions, ExplainType, Statement};
 
 use super::create_index::gen_create_index_plan;
 use super::create_mv::gen_create_mv_plan;
 use super::create_sink::gen_sink_plan;
 use super::create_table::gen_create_table_plan;
-use super::query::gen_batch_query_plan;
+use super::query::{gen_batch_query_plan, gen_batch_query_plan_with_mode};
 use super::RwPgResponse;
-use crate::optimizer::plan_node::Convention;
-use crate::scheduler::BatchPlanFragmenter;
+use crate::optimizer::plan_node::{Convention, PlanNode};
+use crate::scheduler::plan_fragmenter::plan_node_identity;
+use crate::scheduler::{BatchPlanFragmenter, ExecutionContext, LocalQueryExecution};
 use crate::session::OptimizerContext;
 use crate::stream_fragmenter::build_graph;
-use crate::utils::explain_stream_graph;
+use crate::utils::{explain_plan_dot, explain_plan_json, explain_stream_graph};
+
+/// How long `EXPLAIN ANALYZE` waits for the executor stats of remote tasks after the query output
+/// ends in distributed mode.
+const WAIT_EXECUTOR_STATS_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub(super) fn handle_explain(
     context: OptimizerContext,
     stmt: Statement,
     options: ExplainOptions,
-    analyze: bool,
 ) -> Result<RwPgResponse> {
-    if analyze {
-        return Err(ErrorCode::NotImplemented("explain analyze".to_string(), 4856.into()).into());
-    }
     if options.explain_type == ExplainType::Logical {
         return Err(ErrorCode::NotImplemented("explain logical".to_string(), 4856.into()).into());
     }
+    // Only the physical plan tree can be rendered in other formats.
+    if options.explain_format != ExplainFormat::Text
+        && (options.trace || options.explain_type == ExplainType::DistSql)
+    {
+        return Err(ErrorCode::NotImplemented(
+            format!("explain with options {}", options),
+            4856.into(),
+        )
+        .into());
+    }
 
     let session = context.session_ctx.clone();
     context
@@ -60,8 +75,9 @@ pub(super) fn handle_explain(
             query,
             name,
             columns,
+            emit_mode,
             ..
-        } => gen_create_mv_plan(&session, context.into(), *query, name, columns)?.0,
+        } => gen_create_mv_plan(&session, context.into(), *query, name, columns, emit_mode)?.0,
 
         Statement::CreateSink { stmt } => gen_sink_plan(&session, context.into(), stmt)?.0,
 
@@ -69,8 +85,19 @@ pub(super) fn handle_explain(
             name,
             columns,
             constraints,
+            source_watermarks,
             ..
-        } => gen_create_table_plan(&session, context.into(), name, columns, constraints)?.0,
+        } => {
+            gen_create_table_plan(
+                &session,
+                context.into(),
+                name,
+                columns,
+                constraints,
+                source_watermarks,
+            )?
+            .0
+        }
 
         Statement::CreateIndex {
             name,
@@ -139,7 +166,11 @@ pub(super) fn handle_explain(
     } else {
         // if explain trace is open, the plan has been in the rows
         if !explain_trace {
-            let output = plan.explain_to_string()?;
+            let output = match options.explain_format {
+                ExplainFormat::Text => plan.explain_to_string()?,
+                ExplainFormat::Json => explain_plan_json(&plan)?,
+                ExplainFormat::Dot => explain_plan_dot(&plan),
+            };
             rows.extend(
                 output
                     .lines()
@@ -158,3 +189,164 @@ pub(super) fn handle_explain(
         )],
     ))
 }
+
+/// Runs the batch query while collecting the runtime stats of each executor, and renders them
+/// inline in the plan tree.
+pub(super) async fn handle_explain_analyze(
+    context: OptimizerContext,
+    stmt: Statement,
+    options: ExplainOptions,
+) -> Result<RwPgResponse> {
+    if options.explain_type != ExplainType::Physical
+        || options.trace
+        || options.explain_format != ExplainFormat::Text
+    {
+        return Err(ErrorCode::NotImplemented(
+            format!("explain analyze with options {}", options),
+            4856.into(),
+        )
+        .into());
+    }
+    if !matches!(stmt, Statement::Query(_)) {
+        return Err(ErrorCode::NotImplemented(
+            "explain analyze of statements other than SELECT".to_string(),
+            4856.into(),
+        )
+        .into());
+    }
+
+    let session = context.session_ctx.clone();
+    context
+        .explain_verbose
+        .store(options.verbose, Ordering::Release);
+
+    // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
+    let (query, query_mode, plan_lines) = {
+        let (plan, query_mode, _) = gen_batch_query_plan_with_mode(
+            &session,
+            context.into(),
+            stmt,
+            session.config().get_query_mode(),
+        )?;
+
+        // Keep each line of the plan tree along with the identity of its plan node, so that the
+        // executor stats can be filled in after the execution.
+        let mut plan_lines = vec![];
+        plan.explain_lines(&mut vec![], 0, &mut |node, line| {
+            plan_lines.push((line, plan_node_identity(node.node_type(), node.id())));
+            Ok(())
+        })
+        .map_err(|e| ErrorCode::InternalError(format!("failed to explain: {}", e)))?;
+
+        let plan_fragmenter = BatchPlanFragmenter::new(
+            session.env().worker_node_manager_ref(),
+            session.env().catalog_reader().clone(),
+        );
+        (plan_fragmenter.split(plan)?, query_mode, plan_lines)
+    };
+
+    let pinned_snapshot = session
+        .env()
+        .hummock_snapshot_manager()
+        .acquire(query.query_id())
+        .await?;
+    let collector = ExecutorStatsCollector::new();
+
+    // Run the query to the end and discard the output.
+    let start_time = Instant::now();
+    let mut output_rows = 0;
+    match query_mode {
+        QueryMode::Local => {
+            let execution = LocalQueryExecution::new(
+                query,
+                session.env().clone(),
+                "",
+                pinned_snapshot,
+                session.auth_context(),
+            )
+            .with_executor_stats(collector.clone())
+            .with_timeout(session.config().get_statement_timeout())
+            .with_cancel_rx(session.reset_local_query_canceller());
+            let mut data_stream = execution.run();
+            while let Some(chunk) = data_stream.next().await {
+                output_rows += chunk?.cardinality();
+            }
+        }
+        QueryMode::Distributed => {
+            // The stats of the tasks except the root one, which runs in the frontend, are reported
+            // along with their status.
+            let remote_task_count = query
+                .stage_graph
+                .stages
+                .values()
+                .filter(|stage| stage.id != query.root_stage_id())
+                .map(|stage| stage.parallelism as usize)
+                .sum();
+            let execution_context =
+                ExecutionContext::new(session.clone()).with_executor_stats(collector.clone());
+            let mut data_stream = session
+                .env()
+                .query_manager()
+                .schedule(execution_context.into(), query, pinned_snapshot)
+                .await?;
+            while let Some(chunk) = data_stream.next().await {
+                output_rows += chunk
+                    .map_err(|e| ErrorCode::InternalError(e.to_string()))?
+                    .cardinality();
+            }
+            if tokio::time::timeout(
+                WAIT_EXECUTOR_STATS_TIMEOUT,
+                collector.wait_for_tasks(remote_task_count),
+            )
+            .await
+            .is_err()
+            {
+                tracing::warn!("timed out waiting for the executor stats of remote tasks");
+            }
+        }
+        QueryMode::Auto => unreachable!("query mode should have been decided by the planner"),
+    }
+    let execution_time = start_time.elapsed();
+
+    let stats = collector.snapshot();
+    let mut rows = plan_lines
+        .into_iter()
+        .map(|(line, identity)| {
+            let line = match stats.get(&identity) {
+                Some(stats) if options.timing => format!(
+                    "{} (actual rows: {}, chunks: {}, time: {:.3}ms, output: {} bytes)",
+                    line,
+                    stats.output_rows,
+                    stats.output_chunks,
+                    stats.elapsed.as_secs_f64() * 1000.0,
+                    stats.output_bytes,
+                ),
+                Some(stats) => format!("{} (actual rows: {})", line, stats.output_rows),
+                None => line,
+            };
+            Row::new(vec![Some(line.into())])
+        })
+        .collect::<Vec<_>>();
+    rows.push(Row::new(vec![Some(
+        format!("Output Rows: {}", output_rows).into(),
+    )]));
+    if options.timing {
+        rows.push(Row::new(vec![Some(
+            format!(
+                "Execution Time: {:.3}ms",
+                execution_time.as_secs_f64() * 1000.0
+            )
+            .into(),
+        )]));
+    }
+
+    Ok(PgResponse::new_for_stream(
+        StatementType::EXPLAIN,
+        Some(rows.len() as i32),
+        rows.into(),
+        vec![PgFieldDescriptor::new(
+            "QUERY PLAN".to_owned(),
+            TypeOid::Varchar,
+        )],
+    ))
+}
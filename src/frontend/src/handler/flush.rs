@@ -28,5 +28,6 @@ pub(super) async fn handle_flush(context: OptimizerContext) -> Result<RwPgRespon
         .env()
         .hummock_snapshot_manager()
         .update_epoch(snapshot);
+    context.session_ctx.set_unflushed_writes(false);
     Ok(PgResponse::empty_result(StatementType::FLUSH))
 }
@@ -14,13 +14,16 @@
 
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_pb::user::grant_privilege::{ActionWithGrantOption, Object as ProstObject};
+use risingwave_pb::user::grant_privilege::{
+    Action as ProstAction, ActionWithGrantOption, Object as ProstObject,
+};
 use risingwave_pb::user::GrantPrivilege as ProstPrivilege;
 use risingwave_sqlparser::ast::{GrantObjects, Privileges, Statement};
 
 use super::RwPgResponse;
 use crate::binder::Binder;
 use crate::catalog::root_catalog::SchemaPath;
+use crate::catalog::CatalogError;
 use crate::session::{OptimizerContext, SessionImpl};
 use crate::user::user_privilege::{
     available_privilege_actions, check_privilege_type, get_prost_action,
@@ -35,11 +38,14 @@ fn make_prost_privilege(
 
     let catalog_reader = session.env().catalog_reader();
     let reader = catalog_reader.read_guard();
+    let all_privileges = matches!(privileges, Privileges::All { .. });
     let actions = match privileges {
         Privileges::All { .. } => available_privilege_actions(&objects)?,
         Privileges::Actions(actions) => actions,
     };
     let mut grant_objs = vec![];
+    // The materialized views and views referred by `TABLE` only support `SELECT`.
+    let mut select_only_objs = vec![];
     match objects {
         GrantObjects::Databases(databases) => {
             for db in databases {
@@ -97,6 +103,41 @@ fn make_prost_privilege(
                 grant_objs.push(ProstObject::AllTablesSchemaId(schema.id()));
             }
         }
+        GrantObjects::Tables(tables) => {
+            let db_name = session.database();
+            let search_path = session.config().get_search_path();
+            let user_name = &session.auth_context().user_name;
+
+            for name in tables {
+                let (schema_name, table_name) =
+                    Binder::resolve_schema_qualified_name(db_name, name)?;
+                let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+
+                if let Ok((table, _)) = reader.get_table_by_name(db_name, schema_path, &table_name)
+                {
+                    let object = ProstObject::TableId(table.id().table_id);
+                    if table.associated_source_id().is_none() {
+                        select_only_objs.push(object.clone());
+                    }
+                    grant_objs.push(object);
+                } else if let Ok((view, _)) =
+                    reader.get_view_by_name(db_name, schema_path, &table_name)
+                {
+                    let object = ProstObject::ViewId(view.id);
+                    select_only_objs.push(object.clone());
+                    grant_objs.push(object);
+                } else {
+                    return Err(CatalogError::NotFound("table", table_name).into());
+                }
+            }
+        }
+        GrantObjects::AllTablesInSchema { schemas } => {
+            for schema in schemas {
+                let schema_name = Binder::resolve_schema_name(schema)?;
+                let schema = reader.get_schema_by_name(session.database(), &schema_name)?;
+                grant_objs.push(ProstObject::AllTablesSchemaId(schema.id()));
+            }
+        }
         _ => {
             return Err(ErrorCode::BindError(
                 "GRANT statement does not support this object type".to_string(),
@@ -118,8 +159,27 @@ fn make_prost_privilege(
 
     let mut prost_privileges = vec![];
     for objs in grant_objs {
+        let action_with_opts = if select_only_objs.contains(&objs) {
+            if !all_privileges
+                && action_with_opts
+                    .iter()
+                    .any(|ao| ao.action != ProstAction::Select as i32)
+            {
+                return Err(ErrorCode::BindError(
+                    "Invalid privilege type for the given object.".to_string(),
+                )
+                .into());
+            }
+            action_with_opts
+                .iter()
+                .filter(|ao| ao.action == ProstAction::Select as i32)
+                .cloned()
+                .collect()
+        } else {
+            action_with_opts.clone()
+        };
         prost_privileges.push(ProstPrivilege {
-            action_with_opts: action_with_opts.clone(),
+            action_with_opts,
             object: Some(objs),
         });
     }
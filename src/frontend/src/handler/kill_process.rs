@@ -0,0 +1,83 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::{self, PermissionDenied};
+use risingwave_common::error::Result;
+
+use super::privilege::check_super_user;
+use super::RwPgResponse;
+use crate::session::OptimizerContext;
+
+/// Kills the session with the given process id. The distributed batch queries running in the
+/// session are cancelled, and its connection is closed.
+pub async fn handle_kill(context: OptimizerContext, process_id: i32) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let target = session
+        .env()
+        .sessions_map()
+        .lock()
+        .unwrap()
+        .values()
+        .find(|s| s.session_id().0 == process_id)
+        .cloned()
+        .ok_or_else(|| {
+            ErrorCode::InvalidInputSyntax(format!("process {} does not exist", process_id))
+        })?;
+
+    if target.user_id() != session.user_id() && !check_super_user(&session) {
+        return Err(PermissionDenied(
+            "must be superuser to kill sessions of other users".to_string(),
+        )
+        .into());
+    }
+
+    session
+        .env()
+        .query_manager()
+        .cancel_queries_in_session(target.session_id());
+    target.kill();
+
+    Ok(PgResponse::empty_result(StatementType::KILL))
+}
+
+#[cfg(test)]
+mod tests {
+    use pgwire::pg_server::Session;
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_kill_process() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let session = frontend.session_ref();
+        session
+            .env()
+            .sessions_map()
+            .lock()
+            .unwrap()
+            .insert(session.id(), session.clone());
+
+        let rows = frontend.query_formatted_result("SHOW PROCESSLIST").await;
+        assert_eq!(
+            rows,
+            vec!["Row([Some(b\"0\"), Some(b\"root\"), Some(b\"dev\"), None, None])".to_string()]
+        );
+
+        assert!(frontend.run_sql("KILL 1").await.is_err());
+        frontend.run_sql("KILL 0").await.unwrap();
+        // Resolves immediately since the session has been killed.
+        session.wait_killed().await;
+    }
+}
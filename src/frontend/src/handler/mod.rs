@@ -20,31 +20,46 @@ use futures::stream::{self, BoxStream};
 use futures::{Stream, StreamExt};
 use pgwire::pg_response::StatementType::{ABORT, BEGIN, COMMIT, ROLLBACK, START_TRANSACTION};
 use pgwire::pg_response::{PgResponse, RowSetResult};
-use pgwire::pg_server::BoxedError;
-use pgwire::types::Row;
+use pgwire::pg_server::{BoxedError, CopyInData};
+use pgwire::types::{Format, Row};
 use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::{DropStatement, ObjectType, Statement};
 
 use self::util::DataChunkToRowSetAdapter;
+use crate::binder::BoundStatement;
 use crate::scheduler::{DistributedQueryStream, LocalQueryStream};
 use crate::session::{OptimizerContext, SessionImpl};
 use crate::utils::WithOptions;
 
+mod alter_mv;
+pub mod alter_source;
+mod alter_system;
+mod alter_table;
 pub mod alter_user;
+mod analyze;
+mod cancel_job;
+mod comment;
+mod copy;
 mod create_database;
+pub mod create_function;
 pub mod create_index;
 pub mod create_mv;
 mod create_schema;
+mod create_secret;
 pub mod create_sink;
 pub mod create_source;
 pub mod create_table;
+pub mod create_table_as;
 pub mod create_user;
 mod create_view;
 mod describe;
 mod drop_database;
+mod drop_function;
 mod drop_index;
 pub mod drop_mv;
 mod drop_schema;
+mod drop_secret;
 pub mod drop_sink;
 pub mod drop_source;
 pub mod drop_table;
@@ -53,6 +68,7 @@ mod drop_view;
 mod explain;
 mod flush;
 pub mod handle_privilege;
+mod kill_process;
 pub mod privilege;
 pub mod query;
 mod show;
@@ -90,7 +106,7 @@ pub async fn handle(
     session: Arc<SessionImpl>,
     stmt: Statement,
     sql: &str,
-    format: bool,
+    formats: Vec<Format>,
 ) -> Result<RwPgResponse> {
     let context = OptimizerContext::new(
         session.clone(),
@@ -102,7 +118,13 @@ pub async fn handle(
             statement,
             analyze,
             options,
-        } => explain::handle_explain(context, *statement, options, analyze),
+        } => {
+            if analyze {
+                explain::handle_explain_analyze(context, *statement, options).await
+            } else {
+                explain::handle_explain(context, *statement, options)
+            }
+        }
         Statement::CreateSource {
             is_materialized,
             stmt,
@@ -112,6 +134,7 @@ pub async fn handle(
             name,
             columns,
             constraints,
+            source_watermarks,
             query,
 
             with_options: _, // It is put in OptimizerContext
@@ -141,10 +164,24 @@ pub async fn handle(
                 )
                 .into());
             }
-            if query.is_some() {
-                return Err(ErrorCode::NotImplemented("CREATE AS".to_string(), None.into()).into());
+            if let Some(query) = query {
+                if !constraints.is_empty() || !source_watermarks.is_empty() {
+                    return Err(ErrorCode::NotImplemented(
+                        "constraints or watermarks in CREATE TABLE AS".to_string(),
+                        None.into(),
+                    )
+                    .into());
+                }
+                return create_table_as::handle_create_as(context, name, columns, query).await;
             }
-            create_table::handle_create_table(context, name, columns, constraints).await
+            create_table::handle_create_table(
+                context,
+                name,
+                columns,
+                constraints,
+                source_watermarks,
+            )
+            .await
         }
         Statement::CreateDatabase {
             db_name,
@@ -156,10 +193,36 @@ pub async fn handle(
         } => create_schema::handle_create_schema(context, schema_name, if_not_exists).await,
         Statement::CreateUser(stmt) => create_user::handle_create_user(context, stmt).await,
         Statement::AlterUser(stmt) => alter_user::handle_alter_user(context, stmt).await,
+        Statement::AlterTable { name, operation } => {
+            alter_table::handle_alter_table(context, name, operation).await
+        }
+        Statement::AlterSource { name, operation } => {
+            alter_source::handle_alter_source(context, name, operation).await
+        }
+        Statement::AlterMaterializedView { name, param, value } => {
+            alter_mv::handle_alter_mv(context, name, param, value).await
+        }
+        Statement::AlterSystem { param, value } => {
+            alter_system::handle_alter_system(context, param, value).await
+        }
         Statement::Grant { .. } => handle_privilege::handle_grant_privilege(context, stmt).await,
         Statement::Revoke { .. } => handle_privilege::handle_revoke_privilege(context, stmt).await,
         Statement::Describe { name } => describe::handle_describe(context, name),
-        Statement::ShowObjects(show_object) => show::handle_show_object(context, show_object),
+        Statement::Copy {
+            source,
+            to,
+            options,
+            values,
+        } => copy::handle_copy(context, source, to, options, values).await,
+        Statement::ShowObjects(show_object) => show::handle_show_object(context, show_object).await,
+        Statement::Comment {
+            object_type,
+            object_name,
+            comment,
+        } => comment::handle_comment(context, object_type, object_name, comment).await,
+        Statement::ShowCreateObject { create_type, name } => {
+            show::handle_show_create_object(context, create_type, name)
+        }
         Statement::Drop(DropStatement {
             object_type,
             object_name,
@@ -196,20 +259,58 @@ pub async fn handle(
                 drop_user::handle_drop_user(context, object_name, if_exists, drop_mode.into()).await
             }
             ObjectType::View => drop_view::handle_drop_view(context, object_name, if_exists).await,
+            ObjectType::Secret => {
+                drop_secret::handle_drop_secret(context, object_name, if_exists).await
+            }
             ObjectType::MaterializedSource => Err((ErrorCode::InvalidInputSyntax(
                 "Use `DROP SOURCE` to drop a materialized source.".to_owned(),
             ))
             .into()),
         },
+        Statement::DropFunction {
+            if_exists,
+            name,
+            args,
+            drop_mode,
+        } => drop_function::handle_drop_function(context, if_exists, name, args, drop_mode).await,
+        Statement::CreateFunction {
+            or_replace,
+            temporary,
+            name,
+            args,
+            return_type,
+            language,
+            identifier,
+            using,
+        } => {
+            create_function::handle_create_function(
+                context,
+                or_replace,
+                temporary,
+                name,
+                args,
+                return_type,
+                language,
+                identifier,
+                using,
+            )
+            .await
+        }
+        Statement::CreateSecret {
+            if_not_exists,
+            name,
+            with_options,
+        } => create_secret::handle_create_secret(context, if_not_exists, name, with_options).await,
         Statement::Query(_)
         | Statement::Insert { .. }
         | Statement::Delete { .. }
-        | Statement::Update { .. } => query::handle_query(context, stmt, format).await,
+        | Statement::Update { .. } => query::handle_query(context, stmt, formats).await,
         Statement::CreateView {
             materialized,
             name,
             columns,
             query,
+            emit_mode,
 
             with_options: _, // It is put in OptimizerContext
             or_replace,      // not supported
@@ -222,12 +323,15 @@ pub async fn handle(
                 .into());
             }
             if materialized {
-                create_mv::handle_create_mv(context, name, *query, columns).await
+                create_mv::handle_create_mv(context, name, *query, columns, emit_mode).await
             } else {
                 create_view::handle_create_view(context, name, columns, *query).await
             }
         }
         Statement::Flush => flush::handle_flush(context).await,
+        Statement::Analyze { table_name } => analyze::handle_analyze(context, table_name).await,
+        Statement::CancelJobs(job_ids) => cancel_job::handle_cancel(context, job_ids).await,
+        Statement::Kill(process_id) => kill_process::handle_kill(context, process_id).await,
         Statement::SetVariable {
             local: _,
             variable,
@@ -290,3 +394,47 @@ pub async fn handle(
         ),
     }
 }
+
+/// Runs `COPY ... FROM STDIN` with the data received from the client, after its response of
+/// [`handle`] has started the copy.
+pub async fn handle_copy_in(
+    session: Arc<SessionImpl>,
+    stmt: Statement,
+    sql: &str,
+    data: CopyInData,
+) -> Result<RwPgResponse> {
+    let context = OptimizerContext::new(session, Arc::from(sql), WithOptions::try_from(&stmt)?);
+    match stmt {
+        Statement::Copy {
+            source,
+            to: false,
+            options,
+            ..
+        } => copy::handle_copy_in(context, source, options, data).await,
+        _ => Err(ErrorCode::InternalError(format!("not a COPY FROM STDIN: {}", sql)).into()),
+    }
+}
+
+/// Handles a prepared statement with the values of its parameters in text format. Queries and DML
+/// statements are bound when prepared, and `bound` is the result.
+pub async fn handle_prepared(
+    session: Arc<SessionImpl>,
+    stmt: Statement,
+    bound: Option<BoundStatement>,
+    sql: &str,
+    param_types: &[DataType],
+    params: &[Option<String>],
+    formats: Vec<Format>,
+) -> Result<RwPgResponse> {
+    match bound {
+        Some(bound) => {
+            let stmt_type = query::to_statement_type(&stmt)?;
+            let context =
+                OptimizerContext::new(session, Arc::from(sql), WithOptions::try_from(&stmt)?);
+            query::handle_prepared_query(context, stmt_type, bound, param_types, params, formats)
+                .await
+        }
+        // Other statements can not have parameters.
+        None => handle(session, stmt, sql, formats).await,
+    }
+}
@@ -15,8 +15,8 @@
 use risingwave_common::error::ErrorCode::PermissionDenied;
 use risingwave_common::error::Result;
 use risingwave_pb::user::grant_privilege::{Action as ProstAction, Object as ProstObject};
+use risingwave_pb::user::UserInfo;
 
-use crate::binder::{BoundStatement, Relation};
 use crate::session::SessionImpl;
 use crate::user::UserId;
 
@@ -24,6 +24,8 @@ pub struct ObjectCheckItem {
     owner: UserId,
     action: ProstAction,
     object: ProstObject,
+    // Whether the object can only be accessed by superusers, e.g. internal state tables.
+    superuser_only: bool,
 }
 
 impl ObjectCheckItem {
@@ -32,93 +34,15 @@ impl ObjectCheckItem {
             owner,
             action,
             object,
+            superuser_only: false,
         }
     }
-}
 
-/// resolve privileges in `relation`
-pub(crate) fn resolve_relation_privileges(
-    relation: &Relation,
-    action: ProstAction,
-    objects: &mut Vec<ObjectCheckItem>,
-) {
-    match relation {
-        Relation::Source(source) => {
-            let item = ObjectCheckItem {
-                owner: source.catalog.owner,
-                action,
-                object: ProstObject::SourceId(source.catalog.id),
-            };
-            objects.push(item);
-        }
-        Relation::BaseTable(table) => {
-            let item = ObjectCheckItem {
-                owner: table.table_catalog.owner,
-                action,
-                object: ProstObject::TableId(table.table_id.table_id),
-            };
-            objects.push(item);
-        }
-        Relation::Subquery(query) => {
-            if let crate::binder::BoundSetExpr::Select(select) = &query.query.body {
-                if let Some(sub_relation) = &select.from {
-                    resolve_relation_privileges(sub_relation, action, objects);
-                }
-            }
-        }
-        Relation::Join(join) => {
-            resolve_relation_privileges(&join.left, action, objects);
-            resolve_relation_privileges(&join.right, action, objects);
-        }
-        Relation::WindowTableFunction(table) => {
-            resolve_relation_privileges(&table.input, action, objects)
-        }
-        _ => {}
-    };
-}
-
-/// resolve privileges in `stmt`
-pub(crate) fn resolve_privileges(stmt: &BoundStatement) -> Vec<ObjectCheckItem> {
-    let mut objects = Vec::new();
-    match stmt {
-        BoundStatement::Insert(ref insert) => {
-            let object = ObjectCheckItem {
-                owner: insert.table_source.owner,
-                action: ProstAction::Insert,
-                object: ProstObject::TableId(insert.table_source.source_id.table_id),
-            };
-            objects.push(object);
-            if let crate::binder::BoundSetExpr::Select(select) = &insert.source.body {
-                if let Some(sub_relation) = &select.from {
-                    resolve_relation_privileges(sub_relation, ProstAction::Select, &mut objects);
-                }
-            }
-        }
-        BoundStatement::Delete(ref delete) => {
-            let object = ObjectCheckItem {
-                owner: delete.table_source.owner,
-                action: ProstAction::Delete,
-                object: ProstObject::TableId(delete.table_source.source_id.table_id),
-            };
-            objects.push(object);
-        }
-        BoundStatement::Update(ref update) => {
-            let object = ObjectCheckItem {
-                owner: update.table_source.owner,
-                action: ProstAction::Update,
-                object: ProstObject::TableId(update.table_source.source_id.table_id),
-            };
-            objects.push(object);
-        }
-        BoundStatement::Query(ref query) => {
-            if let crate::binder::BoundSetExpr::Select(select) = &query.body {
-                if let Some(sub_relation) = &select.from {
-                    resolve_relation_privileges(sub_relation, ProstAction::Select, &mut objects);
-                }
-            }
-        }
-    };
-    objects
+    /// The object can only be accessed by superusers, regardless of the granted privileges.
+    pub fn with_superuser_only(mut self, superuser_only: bool) -> Self {
+        self.superuser_only = superuser_only;
+        self
+    }
 }
 
 pub(crate) fn check_super_user(session: &SessionImpl) -> bool {
@@ -133,36 +57,61 @@ pub(crate) fn check_super_user(session: &SessionImpl) -> bool {
 }
 
 /// check whether user in `session` has privileges in `items`
-pub(crate) fn check_privileges(session: &SessionImpl, items: &Vec<ObjectCheckItem>) -> Result<()> {
+pub(crate) fn check_privileges(session: &SessionImpl, items: &[ObjectCheckItem]) -> Result<()> {
     let user_reader = session.env().user_info_reader();
     let reader = user_reader.read_guard();
 
     if let Some(info) = reader.get_user_by_name(session.user_name()) {
-        if info.is_super {
-            return Ok(());
+        check_user_privileges(info, items)
+    } else {
+        Err(PermissionDenied("Session user is invalid".to_string()).into())
+    }
+}
+
+/// check whether `user` has privileges in `items`
+pub(crate) fn check_user_privileges(user: &UserInfo, items: &[ObjectCheckItem]) -> Result<()> {
+    if user.is_super {
+        return Ok(());
+    }
+    for item in items {
+        if item.superuser_only {
+            return Err(PermissionDenied(
+                "must be superuser to access internal tables".to_string(),
+            )
+            .into());
         }
-        for item in items {
-            if item.owner == info.id {
-                continue;
-            }
-            let has_privilege = info.grant_privileges.iter().any(|privilege| {
-                privilege.object.is_some()
-                    && privilege.object.as_ref().unwrap() == &item.object
-                    && privilege
-                        .action_with_opts
-                        .iter()
-                        .any(|ao| ao.action == item.action as i32)
-            });
-            if !has_privilege {
-                return Err(PermissionDenied("Do not have the privilege".to_string()).into());
-            }
+        if item.owner == user.id {
+            continue;
+        }
+        let has_privilege = user.grant_privileges.iter().any(|privilege| {
+            privilege.object.is_some()
+                && privilege.object.as_ref().unwrap() == &item.object
+                && privilege
+                    .action_with_opts
+                    .iter()
+                    .any(|ao| ao.action == item.action as i32)
+        });
+        if !has_privilege {
+            return Err(PermissionDenied(format!(
+                "permission denied for {}",
+                object_kind(&item.object)
+            ))
+            .into());
         }
-    } else {
-        return Err(PermissionDenied("Session user is invalid".to_string()).into());
     }
     Ok(())
 }
 
+fn object_kind(object: &ProstObject) -> &'static str {
+    match object {
+        ProstObject::DatabaseId(_) => "database",
+        ProstObject::SchemaId(_) => "schema",
+        ProstObject::TableId(_) | ProstObject::AllTablesSchemaId(_) => "table",
+        ProstObject::SourceId(_) | ProstObject::AllSourcesSchemaId(_) => "source",
+        ProstObject::ViewId(_) => "view",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SUPER_USER_ID};
@@ -214,4 +163,62 @@ mod tests {
             .unwrap();
         assert!(check_privileges(&session, &check_items).is_ok());
     }
+
+    #[tokio::test]
+    async fn test_relation_privileges() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let session = frontend.session_ref();
+        frontend.run_sql("CREATE TABLE t (v1 int)").await.unwrap();
+        frontend
+            .run_sql(
+                "CREATE USER user WITH NOSUPERUSER PASSWORD 'md5827ccb0eea8a706c4c34a16891f84e7b'",
+            )
+            .await
+            .unwrap();
+        let user_id = {
+            let user_reader = session.env().user_info_reader();
+            user_reader
+                .read_guard()
+                .get_user_by_name("user")
+                .unwrap()
+                .id
+        };
+        let run_user_sql = |sql: &'static str| {
+            frontend.run_user_sql(
+                sql,
+                DEFAULT_DATABASE_NAME.to_string(),
+                "user".to_string(),
+                user_id,
+            )
+        };
+
+        // The relations in subqueries and set operations are checked as well.
+        for sql in [
+            "EXPLAIN SELECT * FROM t",
+            "EXPLAIN SELECT 1 UNION SELECT v1 FROM t",
+            "EXPLAIN SELECT 1 WHERE EXISTS (SELECT * FROM t)",
+            "EXPLAIN INSERT INTO t VALUES (1)",
+        ] {
+            assert!(run_user_sql(sql).await.is_err());
+        }
+
+        frontend.run_sql("GRANT SELECT ON t TO user").await.unwrap();
+        run_user_sql("EXPLAIN SELECT 1 WHERE EXISTS (SELECT * FROM t)")
+            .await
+            .unwrap();
+        assert!(run_user_sql("EXPLAIN INSERT INTO t VALUES (1)")
+            .await
+            .is_err());
+
+        frontend.run_sql("GRANT INSERT ON t TO user").await.unwrap();
+        run_user_sql("EXPLAIN INSERT INTO t SELECT * FROM t")
+            .await
+            .unwrap();
+
+        frontend
+            .run_sql("REVOKE SELECT ON t FROM user")
+            .await
+            .unwrap();
+        assert!(run_user_sql("EXPLAIN SELECT * FROM t").await.is_err());
+    }
 }
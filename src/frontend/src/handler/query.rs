@@ -19,16 +19,17 @@ use futures::StreamExt;
 use itertools::Itertools;
 use pgwire::pg_field_descriptor::PgFieldDescriptor;
 use pgwire::pg_response::{PgResponse, StatementType};
+use pgwire::types::Format;
 use postgres_types::FromSql;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::session_config::QueryMode;
+use risingwave_common::types::DataType;
 use risingwave_sqlparser::ast::Statement;
 
 use super::{PgResponseStream, RwPgResponse};
-use crate::binder::{Binder, BoundSetExpr, BoundStatement};
-use crate::handler::privilege::{check_privileges, resolve_privileges};
-use crate::handler::util::{to_pg_field, DataChunkToRowSetAdapter};
+use crate::binder::{Binder, BoundStatement};
+use crate::handler::util::{column_format, to_pg_field, DataChunkToRowSetAdapter};
 use crate::planner::Planner;
 use crate::scheduler::plan_fragmenter::Query;
 use crate::scheduler::{
@@ -42,6 +43,18 @@ pub fn gen_batch_query_plan(
     session: &SessionImpl,
     context: OptimizerContextRef,
     stmt: Statement,
+) -> Result<(PlanRef, QueryMode, Schema)> {
+    let query_mode = session.config().get_query_mode();
+    gen_batch_query_plan_with_mode(session, context, stmt, query_mode)
+}
+
+/// Generates the batch plan in the given `query_mode`, unless the statement is forced to run in
+/// another mode. The returned mode is never [`QueryMode::Auto`].
+pub fn gen_batch_query_plan_with_mode(
+    session: &SessionImpl,
+    context: OptimizerContextRef,
+    stmt: Statement,
+    query_mode: QueryMode,
 ) -> Result<(PlanRef, QueryMode, Schema)> {
     let stmt_type = to_statement_type(&stmt)?;
 
@@ -50,17 +63,21 @@ pub fn gen_batch_query_plan(
         binder.bind(stmt)?
     };
 
-    let check_items = resolve_privileges(&bound);
-    check_privileges(session, &check_items)?;
+    gen_batch_plan_by_bound(context, stmt_type, bound, query_mode)
+}
 
+fn gen_batch_plan_by_bound(
+    context: OptimizerContextRef,
+    stmt_type: StatementType,
+    bound: BoundStatement,
+    query_mode: QueryMode,
+) -> Result<(PlanRef, QueryMode, Schema)> {
     let mut planner = Planner::new(context);
 
     let mut must_local = false;
     if let BoundStatement::Query(query) = &bound {
-        if let BoundSetExpr::Select(select) = &query.body
-            && let Some(relation) = &select.from
-            && relation.contains_sys_table() {
-                must_local = true;
+        if query.body.contains_sys_table() {
+            must_local = true;
         }
     }
     let must_dist = stmt_type.is_dml();
@@ -74,31 +91,62 @@ pub fn gen_batch_query_plan(
         }
         (true, false) => QueryMode::Distributed,
         (false, true) => QueryMode::Local,
-        (false, false) => session.config().get_query_mode(),
+        (false, false) => query_mode,
     };
 
     let mut logical = planner.plan(bound)?;
     let schema = logical.schema().clone();
 
-    let physical = match query_mode {
-        QueryMode::Local => logical.gen_batch_local_plan()?,
-        QueryMode::Distributed => logical.gen_batch_distributed_plan()?,
-    };
+    let (physical, query_mode) = logical.gen_batch_plan_with_mode(query_mode)?;
     Ok((physical, query_mode, schema))
 }
 
 pub async fn handle_query(
     context: OptimizerContext,
     stmt: Statement,
-    format: bool,
+    formats: Vec<Format>,
 ) -> Result<RwPgResponse> {
     let stmt_type = to_statement_type(&stmt)?;
+    let bound = {
+        let mut binder = Binder::new(&context.session_ctx);
+        binder.bind(stmt)?
+    };
+    execute_bound(context, stmt_type, bound, formats).await
+}
+
+/// Executes a prepared query or DML statement, which is bound when prepared. The parameters are
+/// replaced by literals of their values, so the plan is generated for each execution.
+pub async fn handle_prepared_query(
+    context: OptimizerContext,
+    stmt_type: StatementType,
+    mut bound: BoundStatement,
+    param_types: &[DataType],
+    params: &[Option<String>],
+    formats: Vec<Format>,
+) -> Result<RwPgResponse> {
+    bound.bind_parameter_values(param_types, params)?;
+    execute_bound(context, stmt_type, bound, formats).await
+}
+
+async fn execute_bound(
+    context: OptimizerContext,
+    stmt_type: StatementType,
+    bound: BoundStatement,
+    formats: Vec<Format>,
+) -> Result<RwPgResponse> {
     let session = context.session_ctx.clone();
     let query_start_time = Instant::now();
+    // With `RETURNING`, DML outputs the written rows, which are counted as a query result.
+    let has_returning = bound.returning_schema().is_some();
 
     // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
     let (query, query_mode, output_schema) = {
-        let (plan, query_mode, schema) = gen_batch_query_plan(&session, context.into(), stmt)?;
+        let (plan, query_mode, schema) = gen_batch_plan_by_bound(
+            context.into(),
+            stmt_type,
+            bound,
+            session.config().get_query_mode(),
+        )?;
 
         tracing::trace!(
             "Generated query plan: {:?}, query_mode:{:?}",
@@ -113,16 +161,32 @@ pub async fn handle_query(
     };
     tracing::trace!("Generated query after plan fragmenter: {:?}", &query);
 
-    let pg_descs = output_schema
-        .fields()
-        .iter()
-        .map(to_pg_field)
-        .collect::<Vec<PgFieldDescriptor>>();
+    // DML without `RETURNING` only outputs the number of affected rows, which is not a row set.
+    let pg_descs = if stmt_type.is_dml() && !has_returning {
+        vec![]
+    } else {
+        output_schema
+            .fields()
+            .iter()
+            .map(to_pg_field)
+            .collect::<Vec<PgFieldDescriptor>>()
+    };
     let column_types = output_schema
         .fields()
         .iter()
         .map(|f| f.data_type())
         .collect_vec();
+    let time_zone = session.config().get_timezone().to_string();
+
+    // `UPDATE` and `DELETE` read the rows to change from the committed snapshot, so the previous
+    // writes of the session are flushed first. Otherwise, a row changed twice in an epoch would be
+    // retracted with its stale value.
+    if matches!(stmt_type, StatementType::UPDATE | StatementType::DELETE)
+        && session.has_unflushed_writes()
+    {
+        flush_for_write(&session, stmt_type).await?;
+        session.set_unflushed_writes(false);
+    }
 
     let mut row_stream = {
         // Acquire hummock snapshot for execution.
@@ -135,21 +199,27 @@ pub async fn handle_query(
             QueryMode::Local => PgResponseStream::LocalQuery(DataChunkToRowSetAdapter::new(
                 local_execute(session.clone(), query, pinned_snapshot).await?,
                 column_types,
-                format,
+                formats.clone(),
+                time_zone,
             )),
             // Local mode do not support cancel tasks.
             QueryMode::Distributed => {
                 PgResponseStream::DistributedQuery(DataChunkToRowSetAdapter::new(
                     distribute_execute(session.clone(), query, pinned_snapshot).await?,
                     column_types,
-                    format,
+                    formats.clone(),
+                    time_zone,
                 ))
             }
+            QueryMode::Auto => unreachable!(),
         }
     };
 
     let rows_count = match stmt_type {
         StatementType::SELECT => None,
+        StatementType::INSERT | StatementType::DELETE | StatementType::UPDATE if has_returning => {
+            None
+        }
         StatementType::INSERT | StatementType::DELETE | StatementType::UPDATE => {
             // Get the row from the row_stream.
             let first_row_set = row_stream
@@ -160,7 +230,7 @@ pub async fn handle_query(
             let affected_rows_str = first_row_set[0].values()[0]
                 .as_ref()
                 .expect("compute node should return affected rows in output");
-            if format {
+            if column_format(&formats, 0) == Format::Binary {
                 Some(
                     i64::from_sql(&postgres_types::Type::INT8, affected_rows_str)
                         .unwrap()
@@ -182,6 +252,8 @@ pub async fn handle_query(
     // Implicitly flush the writes.
     if session.config().get_implicit_flush() {
         flush_for_write(&session, stmt_type).await?;
+    } else if stmt_type.is_dml() {
+        session.set_unflushed_writes(true);
     }
 
     // update some metrics
@@ -204,7 +276,7 @@ pub async fn handle_query(
     ))
 }
 
-fn to_statement_type(stmt: &Statement) -> Result<StatementType> {
+pub(super) fn to_statement_type(stmt: &Statement) -> Result<StatementType> {
     use StatementType::*;
 
     match stmt {
@@ -246,7 +318,9 @@ async fn local_execute(
         "",
         pinned_snapshot,
         session.auth_context(),
-    );
+    )
+    .with_timeout(session.config().get_statement_timeout())
+    .with_cancel_rx(session.reset_local_query_canceller());
 
     Ok(execution.stream_rows())
 }
@@ -12,25 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use itertools::Itertools;
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::Row;
-use risingwave_common::catalog::{ColumnDesc, DEFAULT_SCHEMA_NAME};
-use risingwave_common::error::Result;
-use risingwave_sqlparser::ast::{Ident, ObjectName, ShowObject};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, DEFAULT_SCHEMA_NAME};
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_sqlparser::ast::{Ident, ObjectName, ShowCreateType, ShowObject, Statement};
+use risingwave_sqlparser::parser::Parser;
 
+use super::create_table::gen_definition_from_columns;
 use super::RwPgResponse;
 use crate::binder::Binder;
 use crate::catalog::root_catalog::SchemaPath;
 use crate::catalog::CatalogError;
 use crate::handler::util::col_descs_to_rows;
 use crate::session::{OptimizerContext, SessionImpl};
+use crate::utils::WithOptions;
 
+/// Returns the visible columns of the table or source, with the descriptions of the columns set by
+/// `COMMENT ON COLUMN`.
 pub fn get_columns_from_table(
     session: &SessionImpl,
     table_name: ObjectName,
-) -> Result<Vec<ColumnDesc>> {
+) -> Result<(Vec<ColumnDesc>, HashMap<ColumnId, String>)> {
     let db_name = session.database();
     let (schema_name, table_name) = Binder::resolve_schema_qualified_name(db_name, table_name)?;
     let search_path = session.config().get_search_path();
@@ -39,22 +46,24 @@ pub fn get_columns_from_table(
     let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
 
     let catalog_reader = session.env().catalog_reader().read_guard();
-    let catalogs = match catalog_reader.get_table_by_name(db_name, schema_path, &table_name) {
-        Ok((table, _)) => table.columns(),
-        Err(_) => match catalog_reader.get_source_by_name(db_name, schema_path, &table_name) {
-            Ok((source, _)) => &source.columns,
-            Err(_) => {
-                return Err(
-                    CatalogError::NotFound("table or source", table_name.to_string()).into(),
-                );
-            }
-        },
-    };
-    Ok(catalogs
+    let (catalogs, descriptions) =
+        match catalog_reader.get_table_by_name(db_name, schema_path, &table_name) {
+            Ok((table, _)) => (table.columns(), table.column_descriptions.clone()),
+            Err(_) => match catalog_reader.get_source_by_name(db_name, schema_path, &table_name) {
+                Ok((source, _)) => (&source.columns[..], HashMap::new()),
+                Err(_) => {
+                    return Err(
+                        CatalogError::NotFound("table or source", table_name.to_string()).into(),
+                    );
+                }
+            },
+        };
+    let columns = catalogs
         .iter()
         .filter(|c| !c.is_hidden)
         .map(|c| c.column_desc.clone())
-        .collect())
+        .collect();
+    Ok((columns, descriptions))
 }
 
 fn schema_or_default(schema: &Option<Ident>) -> String {
@@ -63,7 +72,82 @@ fn schema_or_default(schema: &Option<Ident>) -> String {
         .map_or_else(|| DEFAULT_SCHEMA_NAME.to_string(), |s| s.real_value())
 }
 
-pub fn handle_show_object(context: OptimizerContext, command: ShowObject) -> Result<RwPgResponse> {
+/// Shows the progress of the streaming jobs being created.
+async fn handle_show_jobs(context: OptimizerContext) -> Result<RwPgResponse> {
+    let client = context.session_ctx.env().meta_client();
+    let rows = client
+        .get_ddl_progress()
+        .await?
+        .into_iter()
+        .map(|p| {
+            Row::new(vec![
+                Some(p.id.to_string().into()),
+                Some(p.statement.into()),
+                Some(p.progress.into()),
+            ])
+        })
+        .collect_vec();
+
+    Ok(PgResponse::new_for_stream(
+        StatementType::SHOW_COMMAND,
+        Some(rows.len() as i32),
+        rows.into(),
+        vec![
+            PgFieldDescriptor::new("Id".to_owned(), TypeOid::BigInt),
+            PgFieldDescriptor::new("Statement".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Progress".to_owned(), TypeOid::Varchar),
+        ],
+    ))
+}
+
+/// Shows the sessions connected to this frontend and the statements they are running.
+fn handle_show_process_list(context: OptimizerContext) -> Result<RwPgResponse> {
+    let sessions_map = context.session_ctx.env().sessions_map().lock().unwrap();
+    let rows = sessions_map
+        .values()
+        .sorted_by_key(|s| s.session_id())
+        .map(|s| {
+            let (elapsed, statement) = match s.current_statement() {
+                Some((statement, elapsed)) => (
+                    Some(format!("{}ms", elapsed.as_millis()).into()),
+                    Some(statement.into()),
+                ),
+                None => (None, None),
+            };
+            Row::new(vec![
+                Some(s.session_id().0.to_string().into()),
+                Some(s.user_name().to_string().into()),
+                Some(s.database().to_string().into()),
+                elapsed,
+                statement,
+            ])
+        })
+        .collect_vec();
+
+    Ok(PgResponse::new_for_stream(
+        StatementType::SHOW_COMMAND,
+        Some(rows.len() as i32),
+        rows.into(),
+        vec![
+            PgFieldDescriptor::new("Id".to_owned(), TypeOid::Int),
+            PgFieldDescriptor::new("User".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Database".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Time".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Info".to_owned(), TypeOid::Varchar),
+        ],
+    ))
+}
+
+pub async fn handle_show_object(
+    context: OptimizerContext,
+    command: ShowObject,
+) -> Result<RwPgResponse> {
+    match command {
+        ShowObject::Jobs => return handle_show_jobs(context).await,
+        ShowObject::ProcessList => return handle_show_process_list(context),
+        _ => {}
+    }
+
     let session = context.session_ctx;
     let catalog_reader = session.env().catalog_reader().read_guard();
 
@@ -98,8 +182,8 @@ pub fn handle_show_object(context: OptimizerContext, command: ShowObject) -> Res
             .map(|t| t.name.clone())
             .collect(),
         ShowObject::Columns { table } => {
-            let columns = get_columns_from_table(&session, table)?;
-            let rows = col_descs_to_rows(columns);
+            let (columns, descriptions) = get_columns_from_table(&session, table)?;
+            let rows = col_descs_to_rows(columns, &descriptions);
 
             return Ok(PgResponse::new_for_stream(
                 StatementType::SHOW_COMMAND,
@@ -108,9 +192,11 @@ pub fn handle_show_object(context: OptimizerContext, command: ShowObject) -> Res
                 vec![
                     PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
                     PgFieldDescriptor::new("Type".to_owned(), TypeOid::Varchar),
+                    PgFieldDescriptor::new("Description".to_owned(), TypeOid::Varchar),
                 ],
             ));
         }
+        ShowObject::Jobs | ShowObject::ProcessList => unreachable!(),
     };
 
     let rows = names
@@ -126,12 +212,155 @@ pub fn handle_show_object(context: OptimizerContext, command: ShowObject) -> Res
     ))
 }
 
+/// Shows the DDL to recreate the table or materialized view, reconstructed from the catalog. The
+/// indexes on a table are shown as well, one statement per row.
+pub fn handle_show_create_object(
+    context: OptimizerContext,
+    show_create_type: ShowCreateType,
+    name: ObjectName,
+) -> Result<RwPgResponse> {
+    let session = context.session_ctx;
+    let db_name = session.database();
+    let (schema_name, object_name) = Binder::resolve_schema_qualified_name(db_name, name)?;
+    let search_path = session.config().get_search_path();
+    let user_name = &session.auth_context().user_name;
+    let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+
+    let catalog_reader = session.env().catalog_reader().read_guard();
+    let (table, schema_name) =
+        catalog_reader.get_table_by_name(db_name, schema_path, &object_name)?;
+    let qualified_name = |name: &str| {
+        ObjectName(vec![
+            Ident::from_real_value(schema_name),
+            Ident::from_real_value(name),
+        ])
+    };
+
+    let mut rows = vec![];
+    match show_create_type {
+        ShowCreateType::Table => {
+            // Only tables created by `CREATE TABLE` have a version.
+            if table.version.is_none() {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "\"{}\" is not a table",
+                    object_name
+                ))
+                .into());
+            }
+            let (source, _) = catalog_reader.get_source_by_name(
+                db_name,
+                SchemaPath::Name(schema_name),
+                &table.name,
+            )?;
+
+            let definition = if table.definition.is_empty() {
+                let columns = table
+                    .columns()
+                    .iter()
+                    .filter(|c| !c.is_hidden)
+                    .map(|c| c.column_desc.clone())
+                    .collect_vec();
+                gen_definition_from_columns(&qualified_name(&table.name), &columns)
+            } else {
+                table.definition.clone()
+            };
+            let mut stmt = parse_definition(&definition);
+            let Statement::CreateTable { name, with_options, .. } = &mut stmt else {
+                unreachable!("a table's definition should be a CREATE TABLE statement")
+            };
+            *name = qualified_name(&table.name);
+            // The `WITH` options are kept in the properties of the source.
+            let mut options = WithOptions::new(source.properties.clone());
+            for (key, secret_id) in &source.secret_refs {
+                let (secret, secret_schema_name) =
+                    catalog_reader.get_secret_by_id(db_name, *secret_id)?;
+                options.insert_secret_ref(
+                    key.clone(),
+                    ObjectName(vec![
+                        Ident::from_real_value(&secret_schema_name),
+                        Ident::from_real_value(&secret.name),
+                    ]),
+                );
+            }
+            *with_options = options.to_masked_sql_options();
+            rows.push((table.name.clone(), stmt.to_string()));
+
+            let indexes = catalog_reader
+                .get_schema_by_name(db_name, schema_name)?
+                .get_indexes_by_table_id(&table.id);
+            for index in indexes.iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+                // Indexes created before the definition is kept have an empty one.
+                if index.index_table.definition.is_empty() {
+                    continue;
+                }
+                let mut stmt = parse_definition(&index.index_table.definition);
+                let Statement::CreateIndex { name, table_name, .. } = &mut stmt else {
+                    unreachable!("an index's definition should be a CREATE INDEX statement")
+                };
+                // Indexes are always created in the schema of the table.
+                *name = ObjectName(vec![Ident::from_real_value(&index.name)]);
+                *table_name = qualified_name(&table.name);
+                rows.push((index.name.clone(), stmt.to_string()));
+            }
+        }
+        ShowCreateType::MaterializedView => {
+            if table.associated_source_id.is_some() || table.is_index {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "\"{}\" is not a materialized view",
+                    object_name
+                ))
+                .into());
+            }
+            // The definition of a materialized view is its query.
+            let Statement::Query(query) = parse_definition(&table.definition) else {
+                unreachable!("a materialized view's definition should be a query")
+            };
+            let stmt = Statement::CreateView {
+                or_replace: false,
+                materialized: true,
+                name: qualified_name(&table.name),
+                columns: table
+                    .columns()
+                    .iter()
+                    .filter(|c| !c.is_hidden)
+                    .map(|c| Ident::from_real_value(c.name()))
+                    .collect(),
+                query,
+                with_options: table.properties.to_masked_sql_options(),
+                emit_mode: None,
+            };
+            rows.push((table.name.clone(), stmt.to_string()));
+        }
+    }
+
+    let rows = rows
+        .into_iter()
+        .map(|(name, sql)| Row::new(vec![Some(name.into()), Some(sql.into())]))
+        .collect_vec();
+    Ok(PgResponse::new_for_stream(
+        StatementType::SHOW_COMMAND,
+        Some(rows.len() as i32),
+        rows.into(),
+        vec![
+            PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
+            PgFieldDescriptor::new("Create Sql".to_owned(), TypeOid::Varchar),
+        ],
+    ))
+}
+
+fn parse_definition(definition: &str) -> Statement {
+    Parser::parse_sql(definition)
+        .expect("a definition should be parsed successfully")
+        .swap_remove(0)
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
     use std::ops::Index;
 
     use futures_async_stream::for_await;
+    use pgwire::pg_field_descriptor::TypeOid;
 
     use crate::test_utils::{create_proto_file, LocalFrontend, PROTO_FILE_DATA};
 
@@ -165,6 +394,31 @@ mod tests {
         assert_eq!(rows, vec!["Row([Some(b\"t2\")])".to_string()]);
     }
 
+    #[tokio::test]
+    async fn test_show_jobs() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+
+        let pg_response = frontend.run_sql("SHOW JOBS").await.unwrap();
+        let type_oids = pg_response
+            .get_row_desc()
+            .iter()
+            .map(|desc| desc.get_type_oid())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            type_oids,
+            vec![TypeOid::BigInt, TypeOid::Varchar, TypeOid::Varchar]
+        );
+
+        let rows = frontend.query_formatted_result("SHOW JOBS").await;
+        assert_eq!(
+            rows,
+            vec![
+                "Row([Some(b\"1\"), Some(b\"CREATE MATERIALIZED VIEW mv AS SELECT * FROM t\"), Some(b\"50.0%\")])"
+                    .to_string()
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_show_column() {
         let proto_file = create_proto_file(PROTO_FILE_DATA);
@@ -210,4 +464,67 @@ mod tests {
 
         assert_eq!(columns, expected_columns);
     }
+
+    #[tokio::test]
+    async fn test_show_create() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql(r#"create table t (v1 int primary key, "V2" varchar) with (foo = 'bar', auth.token = 'abc');"#)
+            .await
+            .unwrap();
+        frontend
+            .run_sql(r#"create index idx on t(v1) include("V2");"#)
+            .await
+            .unwrap();
+        frontend
+            .run_sql("alter table t add column v3 bigint;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql(r#"create materialized view mv as select v1 + 1 as a, "V2" from t;"#)
+            .await
+            .unwrap();
+
+        let rows = frontend.query_formatted_result("show create table t").await;
+        assert_eq!(
+            rows,
+            vec![
+                r#"Row([Some(b"t"), Some(b"CREATE TABLE public.t (v1 INT PRIMARY KEY, \"V2\" CHARACTER VARYING, v3 BIGINT) WITH (auth.token = '[REDACTED]', foo = 'bar')")])"#.to_string(),
+                r#"Row([Some(b"idx"), Some(b"CREATE INDEX idx ON public.t(v1) INCLUDE(\"V2\")")])"#.to_string(),
+            ]
+        );
+
+        let rows = frontend
+            .query_formatted_result("show create materialized view mv")
+            .await;
+        assert_eq!(
+            rows,
+            vec![r#"Row([Some(b"mv"), Some(b"CREATE MATERIALIZED VIEW public.mv (a, \"V2\") AS SELECT v1 + 1 AS a, \"V2\" FROM t")])"#.to_string()]
+        );
+
+        assert!(frontend.run_sql("show create table mv").await.is_err());
+        assert!(frontend
+            .run_sql("show create materialized view t")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_show_create_with_secret() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create secret pwd with (value = 'p@ssw0rd');")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table t (v1 int) with (foo = 'bar', password = secret pwd);")
+            .await
+            .unwrap();
+
+        let rows = frontend.query_formatted_result("show create table t").await;
+        assert_eq!(
+            rows,
+            vec![r#"Row([Some(b"t"), Some(b"CREATE TABLE public.t (v1 INT) WITH (foo = 'bar', password = SECRET public.pwd)")])"#.to_string()]
+        );
+    }
 }
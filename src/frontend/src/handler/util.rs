@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -21,19 +22,20 @@ use itertools::Itertools;
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::RowSetResult;
 use pgwire::pg_server::BoxedError;
-use pgwire::types::Row;
+use pgwire::types::{Format, Row};
 use pin_project_lite::pin_project;
 use risingwave_common::array::DataChunk;
-use risingwave_common::catalog::{ColumnDesc, Field};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Field};
 use risingwave_common::types::{DataType, ScalarRefImpl};
 use risingwave_expr::vector_op::cast::{timestampz_to_utc_binary, timestampz_to_utc_string};
+use risingwave_expr::vector_op::timestampz::timestampz_to_string_in_time_zone;
 
 pin_project! {
     /// Wrapper struct that converts a stream of DataChunk to a stream of RowSet based on formatting
     /// parameters.
     ///
     /// This is essentially `StreamExt::map(self, move |res| res.map(|chunk| to_pg_rows(chunk,
-    /// format)))` but we need a nameable type as part of [`super::PgResponseStream`], but we cannot
+    /// formats)))` but we need a nameable type as part of [`super::PgResponseStream`], but we cannot
     /// name the type of a closure.
     pub struct DataChunkToRowSetAdapter<VS>
     where
@@ -42,18 +44,26 @@ pin_project! {
         #[pin]
         chunk_stream: VS,
         column_types: Vec<DataType>,
-        format: bool,
+        formats: Vec<Format>,
+        // The session TimeZone to display `timestamp with time zone` in.
+        time_zone: String,
     }
 }
 impl<VS> DataChunkToRowSetAdapter<VS>
 where
     VS: Stream<Item = Result<DataChunk, BoxedError>>,
 {
-    pub fn new(chunk_stream: VS, column_types: Vec<DataType>, format: bool) -> Self {
+    pub fn new(
+        chunk_stream: VS,
+        column_types: Vec<DataType>,
+        formats: Vec<Format>,
+        time_zone: String,
+    ) -> Self {
         Self {
             chunk_stream,
             column_types,
-            format,
+            formats,
+            time_zone,
         }
     }
 }
@@ -70,9 +80,12 @@ where
             Poll::Pending => Poll::Pending,
             Poll::Ready(chunk) => match chunk {
                 Some(chunk_result) => match chunk_result {
-                    Ok(chunk) => {
-                        Poll::Ready(Some(Ok(to_pg_rows(this.column_types, chunk, *this.format))))
-                    }
+                    Ok(chunk) => Poll::Ready(Some(Ok(to_pg_rows(
+                        this.column_types,
+                        chunk,
+                        this.formats,
+                        this.time_zone,
+                    )))),
                     Err(err) => Poll::Ready(Some(Err(err))),
                 },
                 None => Poll::Ready(None),
@@ -82,30 +95,57 @@ where
 }
 
 /// Format scalars according to postgres convention.
-fn pg_value_format(data_type: &DataType, d: ScalarRefImpl<'_>, format: bool) -> Bytes {
-    // format == false means TEXT format
-    // format == true means BINARY format
-    if !format {
-        match (data_type, d) {
-            (DataType::Timestampz, ScalarRefImpl::Int64(us)) => timestampz_to_utc_string(us).into(),
+fn pg_value_format(
+    data_type: &DataType,
+    d: ScalarRefImpl<'_>,
+    format: Format,
+    time_zone: &str,
+) -> Bytes {
+    match format {
+        Format::Text => match (data_type, d) {
+            // The session TimeZone is validated when it's set, so it should never fail.
+            (DataType::Timestampz, ScalarRefImpl::Int64(us)) => {
+                timestampz_to_string_in_time_zone(us, time_zone)
+                    .unwrap_or_else(|_| timestampz_to_utc_string(us))
+                    .into()
+            }
             _ => d.text_format().into(),
-        }
-    } else {
-        match (data_type, d) {
+        },
+        Format::Binary => match (data_type, d) {
             (DataType::Timestampz, ScalarRefImpl::Int64(us)) => timestampz_to_utc_binary(us),
             _ => d.binary_format(),
-        }
+        },
+    }
+}
+
+/// Returns the format of the column at `index`. There can be no format (all in text), a single
+/// format for all the columns, or exactly one format for each column.
+pub fn column_format(formats: &[Format], index: usize) -> Format {
+    match formats {
+        [] => Format::Text,
+        [format] => *format,
+        _ => formats[index],
     }
 }
 
-fn to_pg_rows(column_types: &[DataType], chunk: DataChunk, format: bool) -> Vec<Row> {
+fn to_pg_rows(
+    column_types: &[DataType],
+    chunk: DataChunk,
+    formats: &[Format],
+    time_zone: &str,
+) -> Vec<Row> {
     chunk
         .rows()
         .map(|r| {
             Row::new(
                 r.values()
                     .zip_eq(column_types)
-                    .map(|(data, t)| data.map(|data| pg_value_format(t, data, format)))
+                    .enumerate()
+                    .map(|(i, (data, t))| {
+                        data.map(|data| {
+                            pg_value_format(t, data, column_format(formats, i), time_zone)
+                        })
+                    })
                     .collect_vec(),
             )
         })
@@ -113,19 +153,31 @@ fn to_pg_rows(column_types: &[DataType], chunk: DataChunk, format: bool) -> Vec<
 }
 
 /// Convert column descs to rows which conclude name and type
-pub fn col_descs_to_rows(columns: Vec<ColumnDesc>) -> Vec<Row> {
+/// Converts the columns to rows of the name, the type and the description set by `COMMENT ON
+/// COLUMN`. The fields of struct columns are flattened into rows, which have no description.
+pub fn col_descs_to_rows(
+    columns: Vec<ColumnDesc>,
+    descriptions: &HashMap<ColumnId, String>,
+) -> Vec<Row> {
     columns
         .iter()
         .flat_map(|col| {
+            let description = descriptions.get(&col.column_id);
             col.flatten()
                 .into_iter()
-                .map(|c| {
+                .enumerate()
+                .map(|(i, c)| {
                     let type_name = if let DataType::Struct { .. } = c.data_type {
                         c.type_name.clone()
                     } else {
                         format!("{:?}", &c.data_type)
                     };
-                    Row::new(vec![Some(c.name.into()), Some(type_name.into())])
+                    let description = if i == 0 { description } else { None };
+                    Row::new(vec![
+                        Some(c.name.into()),
+                        Some(type_name.into()),
+                        description.map(|d| d.clone().into()),
+                    ])
                 })
                 .collect_vec()
         })
@@ -152,11 +204,31 @@ pub fn data_type_to_type_oid(data_type: DataType) -> TypeOid {
         DataType::Timestampz => TypeOid::Timestamptz,
         DataType::Decimal => TypeOid::Decimal,
         DataType::Interval => TypeOid::Interval,
+        DataType::Jsonb => TypeOid::Jsonb,
         DataType::Struct { .. } => TypeOid::Varchar,
         DataType::List { .. } => TypeOid::Varchar,
     }
 }
 
+pub fn type_oid_to_data_type(type_oid: TypeOid) -> DataType {
+    match type_oid {
+        TypeOid::SmallInt => DataType::Int16,
+        TypeOid::Int => DataType::Int32,
+        TypeOid::BigInt => DataType::Int64,
+        TypeOid::Float4 => DataType::Float32,
+        TypeOid::Float8 => DataType::Float64,
+        TypeOid::Boolean => DataType::Boolean,
+        TypeOid::Varchar => DataType::Varchar,
+        TypeOid::Date => DataType::Date,
+        TypeOid::Time => DataType::Time,
+        TypeOid::Timestamp => DataType::Timestamp,
+        TypeOid::Timestamptz => DataType::Timestampz,
+        TypeOid::Decimal => DataType::Decimal,
+        TypeOid::Interval => DataType::Interval,
+        TypeOid::Jsonb => DataType::Jsonb,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use risingwave_common::array::*;
@@ -191,7 +263,8 @@ mod tests {
                 DataType::Varchar,
             ],
             chunk,
-            false,
+            &[],
+            "UTC",
         );
         let expected: Vec<Vec<Option<Bytes>>> = vec![
             vec![
@@ -221,27 +294,53 @@ mod tests {
     fn test_value_format() {
         use {DataType as T, ScalarRefImpl as S};
 
-        let f = pg_value_format;
-        assert_eq!(&f(&T::Float32, S::Float32(1_f32.into()), false), "1");
-        assert_eq!(&f(&T::Float32, S::Float32(f32::NAN.into()), false), "NaN");
-        assert_eq!(&f(&T::Float64, S::Float64(f64::NAN.into()), false), "NaN");
+        let f = |t: &DataType, d: ScalarRefImpl<'_>, format: Format| {
+            pg_value_format(t, d, format, "UTC")
+        };
+        assert_eq!(&f(&T::Float32, S::Float32(1_f32.into()), Format::Text), "1");
         assert_eq!(
-            &f(&T::Float32, S::Float32(f32::INFINITY.into()), false),
+            &f(&T::Float32, S::Float32(f32::NAN.into()), Format::Text),
+            "NaN"
+        );
+        assert_eq!(
+            &f(&T::Float64, S::Float64(f64::NAN.into()), Format::Text),
+            "NaN"
+        );
+        assert_eq!(
+            &f(&T::Float32, S::Float32(f32::INFINITY.into()), Format::Text),
             "Infinity"
         );
         assert_eq!(
-            &f(&T::Float32, S::Float32(f32::NEG_INFINITY.into()), false),
+            &f(
+                &T::Float32,
+                S::Float32(f32::NEG_INFINITY.into()),
+                Format::Text
+            ),
             "-Infinity"
         );
         assert_eq!(
-            &f(&T::Float64, S::Float64(f64::INFINITY.into()), false),
+            &f(&T::Float64, S::Float64(f64::INFINITY.into()), Format::Text),
             "Infinity"
         );
         assert_eq!(
-            &f(&T::Float64, S::Float64(f64::NEG_INFINITY.into()), false),
+            &f(
+                &T::Float64,
+                S::Float64(f64::NEG_INFINITY.into()),
+                Format::Text
+            ),
             "-Infinity"
         );
-        assert_eq!(&f(&T::Boolean, S::Bool(true), false), "t");
-        assert_eq!(&f(&T::Boolean, S::Bool(false), false), "f");
+        assert_eq!(&f(&T::Boolean, S::Bool(true), Format::Text), "t");
+        assert_eq!(&f(&T::Boolean, S::Bool(false), Format::Text), "f");
+
+        let us = 1_656_633_600_000_000; // 2022-07-01 00:00:00Z
+        assert_eq!(
+            &pg_value_format(&T::Timestampz, S::Int64(us), Format::Text, "US/Pacific"),
+            "2022-06-30 17:00:00-07:00"
+        );
+        assert_eq!(
+            &pg_value_format(&T::Timestampz, S::Int64(us), Format::Text, "UTC"),
+            "2022-07-01 00:00:00+00:00"
+        );
     }
 }
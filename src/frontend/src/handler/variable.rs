@@ -17,6 +17,8 @@ use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::Row;
 use risingwave_common::error::Result;
+use risingwave_common::session_config::Timezone;
+use risingwave_expr::vector_op::timestampz::lookup_time_zone;
 use risingwave_sqlparser::ast::{Ident, SetVariableValue};
 
 use super::RwPgResponse;
@@ -29,6 +31,18 @@ pub fn handle_set(
 ) -> Result<RwPgResponse> {
     let string_vals = value.into_iter().map(|v| v.to_string()).collect_vec();
 
+    // Reject an unknown time zone here, rather than failing the queries using it later.
+    if name.real_value().eq_ignore_ascii_case("timezone") {
+        let time_zone = Timezone::try_from(
+            string_vals
+                .iter()
+                .map(String::as_str)
+                .collect_vec()
+                .as_slice(),
+        )?;
+        lookup_time_zone(time_zone.as_str())?;
+    }
+
     // Currently store the config variable simply as String -> ConfigEntry(String).
     // In future we can add converter/parser to make the API more robust.
     // We remark that the name of session parameter is always case-insensitive.
@@ -105,23 +105,42 @@ use std::future::Future;
 use std::pin::Pin;
 
 use pgwire::pg_protocol::TlsConfig;
-use risingwave_common::config::ServerConfig;
+use risingwave_common::config::{load_config, ServerConfig, SslConfig, SslMode};
 
 /// Start frontend
 pub fn start(opts: FrontendOpts) -> Pin<Box<dyn Future<Output = ()> + Send>> {
     // WARNING: don't change the function signature. Making it `async fn` will cause
     // slow compile in release mode.
     Box::pin(async move {
+        let frontend_config: FrontendConfig = load_config(&opts.config_path).unwrap();
         let session_mgr = Arc::new(SessionManagerImpl::new(&opts).await.unwrap());
-        pg_serve(&opts.host, session_mgr, Some(TlsConfig::new_default()))
+        pg_serve(&opts.host, session_mgr, tls_config(&frontend_config.ssl))
             .await
             .unwrap();
     })
 }
 
+/// Returns the TLS configuration of the pgwire server, or `None` if TLS is disabled.
+fn tls_config(ssl_config: &SslConfig) -> Option<TlsConfig> {
+    let require = match ssl_config.ssl_mode {
+        SslMode::Disable => return None,
+        SslMode::Prefer => false,
+        SslMode::Require => true,
+    };
+    Some(TlsConfig::new(
+        &ssl_config.ssl_cert,
+        &ssl_config.ssl_key,
+        require,
+    ))
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct FrontendConfig {
     // For connection
     #[serde(default)]
     pub server: ServerConfig,
+
+    // For the TLS of client connections
+    #[serde(default)]
+    pub ssl: SslConfig,
 }
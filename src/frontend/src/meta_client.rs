@@ -14,6 +14,8 @@
 
 use std::collections::HashMap;
 
+use risingwave_pb::ddl_service::reset_source_offsets_request::Target as ResetSourceOffsetsTarget;
+use risingwave_pb::ddl_service::DdlProgress;
 use risingwave_pb::hummock::HummockSnapshot;
 use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
 use risingwave_rpc_client::error::Result;
@@ -40,6 +42,29 @@ pub trait FrontendMetaClient: Send + Sync {
     async fn unpin_snapshot(&self) -> Result<()>;
 
     async fn unpin_snapshot_before(&self, epoch: u64) -> Result<()>;
+
+    async fn set_system_param(&self, param: String, value: String) -> Result<()>;
+
+    async fn reset_source_offsets(
+        &self,
+        source_id: u32,
+        target: ResetSourceOffsetsTarget,
+        timestamp_millis: i64,
+    ) -> Result<()>;
+
+    async fn alter_backfill_rate_limit(
+        &self,
+        database_id: u32,
+        schema_id: u32,
+        name: String,
+        rows_per_second: u64,
+    ) -> Result<()>;
+
+    async fn get_ddl_progress(&self) -> Result<Vec<DdlProgress>>;
+
+    async fn cancel_creating_jobs(&self, job_ids: Vec<u32>) -> Result<()>;
+
+    async fn get_table_sizes(&self) -> Result<HashMap<u32, u64>>;
 }
 
 pub struct FrontendMetaClientImpl(pub MetaClient);
@@ -72,4 +97,43 @@ impl FrontendMetaClient for FrontendMetaClientImpl {
     async fn unpin_snapshot_before(&self, epoch: u64) -> Result<()> {
         self.0.unpin_snapshot_before(epoch).await
     }
+
+    async fn set_system_param(&self, param: String, value: String) -> Result<()> {
+        self.0.set_system_param(param, value).await
+    }
+
+    async fn reset_source_offsets(
+        &self,
+        source_id: u32,
+        target: ResetSourceOffsetsTarget,
+        timestamp_millis: i64,
+    ) -> Result<()> {
+        self.0
+            .reset_source_offsets(source_id, target, timestamp_millis)
+            .await
+    }
+
+    async fn alter_backfill_rate_limit(
+        &self,
+        database_id: u32,
+        schema_id: u32,
+        name: String,
+        rows_per_second: u64,
+    ) -> Result<()> {
+        self.0
+            .alter_backfill_rate_limit(database_id, schema_id, name, rows_per_second)
+            .await
+    }
+
+    async fn get_ddl_progress(&self) -> Result<Vec<DdlProgress>> {
+        self.0.get_ddl_progress().await
+    }
+
+    async fn cancel_creating_jobs(&self, job_ids: Vec<u32>) -> Result<()> {
+        self.0.cancel_creating_jobs(job_ids).await
+    }
+
+    async fn get_table_sizes(&self) -> Result<HashMap<u32, u64>> {
+        self.0.get_table_sizes().await
+    }
 }
@@ -19,12 +19,15 @@ use risingwave_common::catalog::CatalogVersion;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::util::compress::decompress_data;
 use risingwave_common_service::observer_manager::{ObserverState, SubscribeFrontend};
+use risingwave_pb::catalog::Source as ProstSource;
 use risingwave_pb::common::WorkerNode;
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use risingwave_pb::meta::SubscribeResponse;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::watch::Sender;
 
 use crate::catalog::root_catalog::Catalog;
+use crate::catalog::{DatabaseId, SchemaId, SourceId};
 use crate::scheduler::worker_node_manager::WorkerNodeManagerRef;
 use crate::scheduler::HummockSnapshotManagerRef;
 use crate::user::user_manager::UserInfoManager;
@@ -37,6 +40,8 @@ pub struct FrontendObserverNode {
     user_info_manager: Arc<RwLock<UserInfoManager>>,
     user_info_updated_tx: Sender<UserInfoVersion>,
     hummock_snapshot_manager: HummockSnapshotManagerRef,
+    /// Receives the sources with columns proposed by meta, which are added by the frontend.
+    proposed_source_tx: UnboundedSender<(DatabaseId, SchemaId, SourceId)>,
 }
 
 impl ObserverState for FrontendObserverNode {
@@ -54,7 +59,9 @@ impl ObserverState for FrontendObserverNode {
             | Info::Source(_)
             | Info::Index(_)
             | Info::Sink(_)
-            | Info::View(_) => {
+            | Info::View(_)
+            | Info::Function(_)
+            | Info::Secret(_) => {
                 self.handle_catalog_notification(resp);
             }
             Info::Node(node) => {
@@ -76,6 +83,9 @@ impl ObserverState for FrontendObserverNode {
             Info::HummockVersionDeltas(_) => {
                 panic!("frontend node should not receive HummockVersionDeltas");
             }
+            Info::SystemParams(_) => {
+                panic!("frontend node should not receive SystemParams");
+            }
         }
     }
 
@@ -96,7 +106,8 @@ impl ObserverState for FrontendObserverNode {
                     catalog_guard.create_table(&table)
                 }
                 for source in snapshot.sources {
-                    catalog_guard.create_source(&source)
+                    catalog_guard.create_source(&source);
+                    self.notify_proposed_columns(&source);
                 }
                 for user in snapshot.users {
                     user_guard.create_user(user)
@@ -107,6 +118,12 @@ impl ObserverState for FrontendObserverNode {
                 for view in snapshot.views {
                     catalog_guard.create_view(&view)
                 }
+                for function in snapshot.functions {
+                    catalog_guard.create_function(&function)
+                }
+                for secret in snapshot.secrets {
+                    catalog_guard.create_secret(&secret)
+                }
                 self.worker_node_manager.refresh(
                     snapshot.nodes,
                     snapshot
@@ -147,6 +164,7 @@ impl FrontendObserverNode {
         user_info_manager: Arc<RwLock<UserInfoManager>>,
         user_info_updated_tx: Sender<UserInfoVersion>,
         hummock_snapshot_manager: HummockSnapshotManagerRef,
+        proposed_source_tx: UnboundedSender<(DatabaseId, SchemaId, SourceId)>,
     ) -> Self {
         Self {
             worker_node_manager,
@@ -155,6 +173,16 @@ impl FrontendObserverNode {
             user_info_manager,
             user_info_updated_tx,
             hummock_snapshot_manager,
+            proposed_source_tx,
+        }
+    }
+
+    fn notify_proposed_columns(&self, source: &ProstSource) {
+        if !source.proposed_columns.is_empty() {
+            // The receiver is gone only when the frontend is shutting down.
+            let _ = self
+                .proposed_source_tx
+                .send((source.database_id, source.schema_id, source.id));
         }
     }
 
@@ -188,6 +216,10 @@ impl FrontendObserverNode {
                 Operation::Delete => {
                     catalog_guard.drop_source(source.database_id, source.schema_id, source.id)
                 }
+                Operation::Update => {
+                    catalog_guard.update_source(source);
+                    self.notify_proposed_columns(source);
+                }
                 _ => panic!("receive an unsupported notify {:?}", resp),
             },
             Info::Sink(sink) => match resp.operation() {
@@ -211,6 +243,22 @@ impl FrontendObserverNode {
                 }
                 _ => panic!("receive an unsupported notify {:?}", resp),
             },
+            Info::Function(function) => match resp.operation() {
+                Operation::Add => catalog_guard.create_function(function),
+                Operation::Delete => catalog_guard.drop_function(
+                    function.database_id,
+                    function.schema_id,
+                    function.id,
+                ),
+                _ => panic!("receive an unsupported notify {:?}", resp),
+            },
+            Info::Secret(secret) => match resp.operation() {
+                Operation::Add => catalog_guard.create_secret(secret),
+                Operation::Delete => {
+                    catalog_guard.drop_secret(secret.database_id, secret.schema_id, secret.id)
+                }
+                _ => panic!("receive an unsupported notify {:?}", resp),
+            },
             _ => unreachable!(),
         }
         assert!(
@@ -312,6 +360,7 @@ impl FrontendObserverNode {
         match operation {
             Operation::Add => self.worker_node_manager.add_worker_node(node),
             Operation::Delete => self.worker_node_manager.remove_worker_node(node),
+            Operation::Update => self.worker_node_manager.update_worker_node(node),
             _ => (),
         }
     }
@@ -0,0 +1,478 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::to_text::ToText;
+use risingwave_pb::plan_common::JoinType;
+
+use super::plan_node::{PlanNodeType, PlanTreeNodeBinary, PlanTreeNodeUnary};
+use super::PlanRef;
+use crate::catalog::catalog_service::CatalogReadGuard;
+use crate::catalog::table_statistics::ColumnStatistics;
+use crate::expr::{to_conjunctions, to_disjunctions, ExprImpl, ExprType};
+use crate::session::OptimizerContextRef;
+use crate::utils::Condition;
+
+/// The selectivity of `col = const` if the number of distinct values of `col` is unknown.
+const DEFAULT_EQ_SELECTIVITY: f64 = 0.005;
+/// The selectivity of `col < const` and the like if there's no histogram of `col`.
+const DEFAULT_INEQ_SELECTIVITY: f64 = 1.0 / 3.0;
+/// The selectivity of `col IS NULL` if the null count of `col` is unknown.
+const DEFAULT_NULL_SELECTIVITY: f64 = 0.005;
+/// The selectivity of the predicates which can't be estimated.
+const DEFAULT_SELECTIVITY: f64 = 0.25;
+
+/// Estimates the number of rows of logical plans, with the statistics of the tables collected by
+/// `ANALYZE`.
+///
+/// The estimation is only available if all the scanned tables have been analyzed, so that the
+/// optimizer can fall back to the rule-based decisions otherwise. The estimator holds the read
+/// guard of the catalog, and thus should be dropped before going on with the optimization.
+pub struct CardinalityEstimator {
+    catalog: CatalogReadGuard,
+}
+
+/// The statistics of a column, along with the row count of the table it belongs to.
+#[derive(Clone, Copy)]
+struct ColumnStats<'a> {
+    stats: &'a ColumnStatistics,
+    row_count: u64,
+}
+
+impl ColumnStats<'_> {
+    fn null_fraction(&self) -> f64 {
+        if self.row_count == 0 {
+            0.0
+        } else {
+            self.stats.null_count as f64 / self.row_count as f64
+        }
+    }
+
+    fn distinct_count(&self) -> f64 {
+        (self.stats.distinct_count as f64).max(1.0)
+    }
+}
+
+impl CardinalityEstimator {
+    pub fn new(ctx: &OptimizerContextRef) -> Self {
+        Self {
+            catalog: ctx.inner().session_ctx.env().catalog_reader().read_guard(),
+        }
+    }
+
+    /// Estimates the number of rows produced by the logical plan. Returns `None` if it's unknown.
+    pub fn row_count(&self, plan: &PlanRef) -> Option<f64> {
+        let rows = match plan.node_type() {
+            PlanNodeType::LogicalScan => {
+                let scan = plan.as_logical_scan().unwrap();
+                if scan.is_sys_table() {
+                    return None;
+                }
+                let table_desc = scan.table_desc();
+                let statistics = self
+                    .catalog
+                    .get_table_statistics_by_id(&table_desc.table_id)?;
+                // The predicate of the scan refers to the columns of the table.
+                let selectivity = self.selectivity(scan.predicate(), |idx| {
+                    let column_id = table_desc.columns[idx].column_id;
+                    Some(ColumnStats {
+                        stats: statistics.column(column_id)?,
+                        row_count: statistics.row_count,
+                    })
+                });
+                statistics.row_count as f64 * selectivity
+            }
+            PlanNodeType::LogicalValues => plan.as_logical_values().unwrap().rows().len() as f64,
+            PlanNodeType::LogicalFilter => {
+                let filter = plan.as_logical_filter().unwrap();
+                let input = filter.input();
+                self.row_count(&input)?
+                    * self.selectivity(filter.predicate(), |idx| self.column_stats(&input, idx))
+            }
+            PlanNodeType::LogicalProject
+            | PlanNodeType::LogicalOverAgg
+            | PlanNodeType::LogicalSessionWindow => self.row_count(&plan.inputs()[0])?,
+            PlanNodeType::LogicalLimit => {
+                let limit = plan.as_logical_limit().unwrap();
+                let limit_rows = limit.limit() as f64;
+                self.row_count(&limit.input()).map_or(limit_rows, |rows| {
+                    (rows - limit.offset() as f64).max(0.0).min(limit_rows)
+                })
+            }
+            PlanNodeType::LogicalTopN => {
+                let topn = plan.as_logical_top_n().unwrap();
+                let input_rows = self.row_count(&topn.input());
+                if topn.group_key().is_empty() {
+                    let limit_rows = topn.limit() as f64;
+                    input_rows.map_or(limit_rows, |rows| {
+                        (rows - topn.offset() as f64).max(0.0).min(limit_rows)
+                    })
+                } else {
+                    input_rows?
+                }
+            }
+            PlanNodeType::LogicalAgg => {
+                let agg = plan.as_logical_agg().unwrap();
+                if agg.group_key().is_empty() {
+                    1.0
+                } else {
+                    let input = agg.input();
+                    let input_rows = self.row_count(&input)?;
+                    // Assume the group key columns are independent. Unknown columns may have as
+                    // many distinct values as the input rows.
+                    let groups = agg
+                        .group_key()
+                        .iter()
+                        .map(|idx| {
+                            self.column_stats(&input, *idx)
+                                .map_or(input_rows, |c| c.distinct_count())
+                        })
+                        .product::<f64>();
+                    groups.min(input_rows)
+                }
+            }
+            PlanNodeType::LogicalJoin => {
+                let join = plan.as_logical_join().unwrap();
+                let left_rows = self.row_count(&join.left())?;
+                let right_rows = self.row_count(&join.right())?;
+                let inner_rows = left_rows
+                    * right_rows
+                    * self.join_selectivity(&join.left(), &join.right(), join.on());
+                match join.join_type() {
+                    JoinType::Inner => inner_rows,
+                    JoinType::LeftOuter => inner_rows.max(left_rows),
+                    JoinType::RightOuter => inner_rows.max(right_rows),
+                    JoinType::FullOuter => inner_rows.max(left_rows).max(right_rows),
+                    JoinType::LeftSemi => inner_rows.min(left_rows),
+                    JoinType::RightSemi => inner_rows.min(right_rows),
+                    JoinType::LeftAnti => (left_rows - inner_rows).max(1.0),
+                    JoinType::RightAnti => (right_rows - inner_rows).max(1.0),
+                    JoinType::Unspecified => unreachable!(),
+                }
+            }
+            PlanNodeType::LogicalUnion => {
+                let union = plan.as_logical_union().unwrap();
+                let rows = plan
+                    .inputs()
+                    .iter()
+                    .map(|input| self.row_count(input))
+                    .sum::<Option<f64>>()?;
+                if union.all() {
+                    rows
+                } else {
+                    // The duplicates removed are unknown.
+                    rows * 0.5
+                }
+            }
+            _ => return None,
+        };
+        Some(rows)
+    }
+
+    /// Estimates the fraction of the rows of the cartesian product of `left` and `right` that
+    /// satisfy the join condition, which refers to the columns of `left` followed by `right`.
+    pub fn join_selectivity(&self, left: &PlanRef, right: &PlanRef, on: &Condition) -> f64 {
+        let left_len = left.schema().len();
+        let column_stats = |idx: usize| {
+            if idx < left_len {
+                self.column_stats(left, idx)
+            } else {
+                self.column_stats(right, idx - left_len)
+            }
+        };
+        let left_rows = self.row_count(left).unwrap_or(1.0);
+        let right_rows = self.row_count(right).unwrap_or(1.0);
+
+        on.conjunctions
+            .iter()
+            .map(|expr| match expr.as_eq_cond() {
+                Some((x, y)) if (x.index() < left_len) != (y.index() < left_len) => {
+                    // Each value on one side matches the ones of the same value on the other
+                    // side. Without statistics, assume that it's a join on the unique key of the
+                    // larger side.
+                    let distinct_count = match (column_stats(x.index()), column_stats(y.index())) {
+                        (Some(x), Some(y)) => x.distinct_count().max(y.distinct_count()),
+                        (Some(c), None) | (None, Some(c)) => c.distinct_count(),
+                        (None, None) => left_rows.max(right_rows).max(1.0),
+                    };
+                    1.0 / distinct_count
+                }
+                _ => self.expr_selectivity(expr, &column_stats),
+            })
+            .product()
+    }
+
+    /// Traces the column of the plan down to the scanned table, and gets its statistics.
+    fn column_stats(&self, plan: &PlanRef, idx: usize) -> Option<ColumnStats<'_>> {
+        match plan.node_type() {
+            PlanNodeType::LogicalScan => {
+                let scan = plan.as_logical_scan().unwrap();
+                if scan.is_sys_table() {
+                    return None;
+                }
+                let table_desc = scan.table_desc();
+                let statistics = self
+                    .catalog
+                    .get_table_statistics_by_id(&table_desc.table_id)?;
+                let column_id = table_desc.columns[scan.output_col_idx()[idx]].column_id;
+                Some(ColumnStats {
+                    stats: statistics.column(column_id)?,
+                    row_count: statistics.row_count,
+                })
+            }
+            PlanNodeType::LogicalFilter
+            | PlanNodeType::LogicalLimit
+            | PlanNodeType::LogicalTopN => self.column_stats(&plan.inputs()[0], idx),
+            PlanNodeType::LogicalOverAgg | PlanNodeType::LogicalSessionWindow => {
+                // The window function calls or session windows are appended to the input columns.
+                let input = &plan.inputs()[0];
+                if idx < input.schema().len() {
+                    self.column_stats(input, idx)
+                } else {
+                    None
+                }
+            }
+            PlanNodeType::LogicalProject => {
+                let project = plan.as_logical_project().unwrap();
+                match &project.exprs()[idx] {
+                    ExprImpl::InputRef(input_ref) => {
+                        self.column_stats(&project.input(), input_ref.index())
+                    }
+                    _ => None,
+                }
+            }
+            PlanNodeType::LogicalAgg => {
+                let agg = plan.as_logical_agg().unwrap();
+                let input_idx = *agg.group_key().get(idx)?;
+                self.column_stats(&agg.input(), input_idx)
+            }
+            PlanNodeType::LogicalJoin => {
+                let join = plan.as_logical_join().unwrap();
+                let internal_idx = join.o2i_col_mapping().try_map(idx)?;
+                if let Some(left_idx) = join.i2l_col_mapping().try_map(internal_idx) {
+                    self.column_stats(&join.left(), left_idx)
+                } else {
+                    let right_idx = join.i2r_col_mapping().try_map(internal_idx)?;
+                    self.column_stats(&join.right(), right_idx)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Estimates the fraction of the rows satisfying the condition, assuming that the
+    /// conjunctions are independent.
+    fn selectivity<'a>(
+        &'a self,
+        condition: &Condition,
+        column_stats: impl Fn(usize) -> Option<ColumnStats<'a>>,
+    ) -> f64 {
+        condition
+            .conjunctions
+            .iter()
+            .map(|expr| self.expr_selectivity(expr, &column_stats))
+            .product()
+    }
+
+    fn expr_selectivity<'a>(
+        &'a self,
+        expr: &ExprImpl,
+        column_stats: &dyn Fn(usize) -> Option<ColumnStats<'a>>,
+    ) -> f64 {
+        let eq_selectivity = |idx: usize| {
+            column_stats(idx).map_or(DEFAULT_EQ_SELECTIVITY, |c| {
+                (1.0 - c.null_fraction()) / c.distinct_count()
+            })
+        };
+
+        if let Some((input_ref, _)) = expr.as_eq_const() {
+            eq_selectivity(input_ref.index())
+        } else if let Some((input_ref, list)) = expr.as_in_const_list() {
+            (eq_selectivity(input_ref.index()) * list.len() as f64).min(1.0)
+        } else if let Some((input_ref, op, value)) = expr.as_comparison_const() {
+            let column = column_stats(input_ref.index());
+            let value = value
+                .eval_row_const()
+                .ok()
+                .flatten()
+                .and_then(|v| v.as_scalar_ref_impl().to_text().parse::<f64>().ok());
+            match (column, value) {
+                (Some(column), Some(value)) => match column.stats.fraction_less_than(value) {
+                    Some(less_than) => {
+                        let fraction = match op {
+                            ExprType::LessThan | ExprType::LessThanOrEqual => less_than,
+                            _ => 1.0 - less_than,
+                        };
+                        fraction * (1.0 - column.null_fraction())
+                    }
+                    None => DEFAULT_INEQ_SELECTIVITY,
+                },
+                _ => DEFAULT_INEQ_SELECTIVITY,
+            }
+        } else if let Some(input_ref) = expr.as_is_null() {
+            column_stats(input_ref.index()).map_or(DEFAULT_NULL_SELECTIVITY, |c| c.null_fraction())
+        } else if let Some((x, y)) = expr.as_eq_cond() {
+            match (column_stats(x.index()), column_stats(y.index())) {
+                (Some(x), Some(y)) => 1.0 / x.distinct_count().max(y.distinct_count()),
+                (Some(c), None) | (None, Some(c)) => 1.0 / c.distinct_count(),
+                (None, None) => DEFAULT_EQ_SELECTIVITY,
+            }
+        } else if let ExprImpl::FunctionCall(func) = expr {
+            match func.get_expr_type() {
+                ExprType::IsNotNull => match &func.inputs()[0] {
+                    ExprImpl::InputRef(input_ref) => {
+                        1.0 - column_stats(input_ref.index())
+                            .map_or(DEFAULT_NULL_SELECTIVITY, |c| c.null_fraction())
+                    }
+                    _ => 1.0 - DEFAULT_NULL_SELECTIVITY,
+                },
+                ExprType::Not => 1.0 - self.expr_selectivity(&func.inputs()[0], column_stats),
+                ExprType::And => to_conjunctions(expr.clone())
+                    .iter()
+                    .map(|e| self.expr_selectivity(e, column_stats))
+                    .product(),
+                ExprType::Or => {
+                    1.0 - to_disjunctions(expr.clone())
+                        .iter()
+                        .map(|e| 1.0 - self.expr_selectivity(e, column_stats))
+                        .product::<f64>()
+                }
+                _ => DEFAULT_SELECTIVITY,
+            }
+        } else {
+            DEFAULT_SELECTIVITY
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::catalog::root_catalog::SchemaPath;
+    use crate::catalog::table_statistics::{ColumnStatistics, TableStatistics};
+    use crate::test_utils::LocalFrontend;
+
+    /// Sets the statistics of the table as if it's analyzed, where the column `k` has the given
+    /// number of distinct values.
+    async fn mock_analyze(frontend: &LocalFrontend, table_name: &str, rows: u64, distinct: u64) {
+        let session = frontend.session_ref();
+        let table = {
+            let reader = session.env().catalog_reader().read_guard();
+            let schema_path = SchemaPath::Name(DEFAULT_SCHEMA_NAME);
+            let (table, _) = reader
+                .get_table_by_name(DEFAULT_DATABASE_NAME, schema_path, table_name)
+                .unwrap();
+            table.clone()
+        };
+        let column_id = table
+            .columns()
+            .iter()
+            .find(|c| c.name() == "k")
+            .unwrap()
+            .column_id();
+        let statistics = TableStatistics {
+            row_count: rows,
+            columns: [(
+                column_id,
+                ColumnStatistics {
+                    distinct_count: distinct,
+                    null_count: 0,
+                    histogram_bounds: vec![],
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+        session
+            .env()
+            .catalog_writer()
+            .update_table_statistics(table.id(), statistics.to_prost())
+            .await
+            .unwrap();
+    }
+
+    /// Returns the indentation of the scan of each table in the plan, i.e., the depth in the tree.
+    fn scan_depths(explain: &str, tables: &[&str]) -> Vec<usize> {
+        tables
+            .iter()
+            .map(|table| {
+                let pattern = format!("BatchScan {{ table: {table},");
+                explain
+                    .lines()
+                    .find_map(|line| line.find(&pattern))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_join_strategy_by_statistics() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table a (k int, v int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table b (k int primary key, v int);")
+            .await
+            .unwrap();
+        let sql = "explain select * from a join b on a.k = b.k;";
+        let swapped_sql = "explain select * from b join a on a.k = b.k;";
+
+        // Without statistics, hash join is used as it is.
+        let explain = frontend.get_explain_output(sql).await;
+        assert!(explain.contains("BatchHashJoin"), "{explain}");
+        let explain = frontend.get_explain_output(swapped_sql).await;
+        assert!(explain.contains("BatchHashJoin"), "{explain}");
+
+        // Looking up the few rows of `a` in `b` is cheaper, no matter which side `a` is on.
+        mock_analyze(&frontend, "a", 10, 10).await;
+        mock_analyze(&frontend, "b", 1_000_000, 1_000_000).await;
+        let explain = frontend.get_explain_output(sql).await;
+        assert!(explain.contains("BatchLookupJoin"), "{explain}");
+        let explain = frontend.get_explain_output(swapped_sql).await;
+        assert!(explain.contains("BatchLookupJoin"), "{explain}");
+
+        // Otherwise, the hash table is built on the smaller side, which is the right side.
+        mock_analyze(&frontend, "a", 200_000, 1000).await;
+        for sql in [sql, swapped_sql] {
+            let explain = frontend.get_explain_output(sql).await;
+            assert!(explain.contains("BatchHashJoin"), "{explain}");
+            let (b, a) = (explain.find("table: b,"), explain.find("table: a,"));
+            assert!(b < a, "{explain}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_join_ordering_by_statistics() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        for table in ["big", "mid", "small"] {
+            frontend
+                .run_sql(format!("create table {table} (k int, v int);"))
+                .await
+                .unwrap();
+        }
+        let sql = "explain select * from big \
+            join mid on big.k = mid.k \
+            join small on mid.k = small.k;";
+
+        // Joining `big` with `mid` produces a lot of rows, so `mid` and `small` are joined first.
+        mock_analyze(&frontend, "big", 100_000, 10).await;
+        mock_analyze(&frontend, "mid", 1000, 1000).await;
+        mock_analyze(&frontend, "small", 10, 10).await;
+        let explain = frontend.get_explain_output(sql).await;
+        let depths = scan_depths(&explain, &["big", "mid", "small"]);
+        assert!(depths[1] == depths[2] && depths[1] > depths[0], "{explain}");
+    }
+}
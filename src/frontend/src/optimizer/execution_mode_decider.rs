@@ -0,0 +1,139 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::optimizer::plan_node::{
+    BatchHashJoin, BatchLookupJoin, BatchNestedLoopJoin, BatchSeqScan,
+};
+use crate::optimizer::plan_visitor::PlanVisitor;
+use crate::PlanRef;
+
+/// Decides whether a batch query is a simple point query, which is cheap enough to be executed in
+/// local mode, saving the cost of distributed scheduling.
+///
+/// A query is considered as a point query if it scans exactly one table, has no join, and each
+/// scan range of the scan is bound to a single vnode, i.e., the equal conditions of the scan
+/// range cover the distribution key of the table.
+#[derive(Default)]
+pub struct ExecutionModeDecider {
+    num_scans: usize,
+}
+
+impl ExecutionModeDecider {
+    /// Returns `true` if the singleton batch plan should be executed in local mode.
+    pub fn run_in_local_mode(plan: PlanRef) -> bool {
+        let mut decider = Self::default();
+        let is_point_query = decider.visit(plan);
+        is_point_query && decider.num_scans == 1
+    }
+
+    fn is_single_vnode_scan(scan: &BatchSeqScan) -> bool {
+        let table_desc = scan.logical().table_desc();
+        let pk = table_desc.order_column_indices();
+        // The length of the shortest pk prefix that covers the distribution key.
+        let dist_key_prefix_len = table_desc
+            .distribution_key
+            .iter()
+            .map(|dist_key| pk.iter().position(|pk| pk == dist_key).map(|pos| pos + 1))
+            .try_fold(0, |len, pos| pos.map(|pos| len.max(pos)));
+        let Some(dist_key_prefix_len) = dist_key_prefix_len else {
+            return false;
+        };
+        !scan.scan_ranges().is_empty()
+            && scan
+                .scan_ranges()
+                .iter()
+                .all(|scan_range| scan_range.eq_conds.len() >= dist_key_prefix_len)
+    }
+}
+
+impl PlanVisitor<bool> for ExecutionModeDecider {
+    fn merge(a: bool, b: bool) -> bool {
+        a & b
+    }
+
+    fn visit_batch_seq_scan(&mut self, plan: &BatchSeqScan) -> bool {
+        self.num_scans += 1;
+        !plan.logical().is_sys_table() && Self::is_single_vnode_scan(plan)
+    }
+
+    fn visit_batch_hash_join(&mut self, _plan: &BatchHashJoin) -> bool {
+        false
+    }
+
+    fn visit_batch_nested_loop_join(&mut self, _plan: &BatchNestedLoopJoin) -> bool {
+        false
+    }
+
+    fn visit_batch_lookup_join(&mut self, _plan: &BatchLookupJoin) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::LocalFrontend;
+
+    async fn query_mode(frontend: &LocalFrontend, sql: &str) -> String {
+        let explain = frontend
+            .get_explain_output(format!("explain (trace) {sql}"))
+            .await;
+        explain
+            .lines()
+            .find_map(|line| line.strip_prefix("Query Mode: "))
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_decide_execution_mode() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (k int primary key, v int);")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table s (k int, v int);")
+            .await
+            .unwrap();
+
+        // Point queries run in local mode.
+        for sql in [
+            "select * from t where k = 1;",
+            "select v + 1 from t where k = 1 and v > 0;",
+            "select count(*) from t where k = 1;",
+        ] {
+            assert_eq!(query_mode(&frontend, sql).await, "local", "{sql}");
+        }
+
+        // Others run in distributed mode.
+        for sql in [
+            "select * from t;",
+            "select * from t where v = 1;",
+            "select * from t where k > 1;",
+            "select * from t join s on t.k = s.k where t.k = 1;",
+        ] {
+            assert_eq!(query_mode(&frontend, sql).await, "distributed", "{sql}");
+        }
+
+        // The mode can be forced by the session config.
+        frontend
+            .run_sql("set query_mode to distributed;")
+            .await
+            .unwrap();
+        assert_eq!(
+            query_mode(&frontend, "select * from t where k = 1;").await,
+            "distributed"
+        );
+    }
+}
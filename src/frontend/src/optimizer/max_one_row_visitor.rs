@@ -16,7 +16,8 @@ use std::collections::HashSet;
 
 use crate::optimizer::plan_node::{
     LogicalAgg, LogicalApply, LogicalExpand, LogicalFilter, LogicalHopWindow, LogicalLimit,
-    LogicalProjectSet, LogicalTopN, LogicalUnion, LogicalValues, PlanTreeNodeUnary,
+    LogicalProjectSet, LogicalRecursiveUnion, LogicalTopN, LogicalUnion, LogicalValues,
+    PlanTreeNodeUnary,
 };
 use crate::optimizer::plan_visitor::PlanVisitor;
 
@@ -63,6 +64,10 @@ impl PlanVisitor<bool> for MaxOneRowVisitor {
         false
     }
 
+    fn visit_logical_recursive_union(&mut self, _plan: &LogicalRecursiveUnion) -> bool {
+        false
+    }
+
     fn visit_logical_expand(&mut self, plan: &LogicalExpand) -> bool {
         plan.column_subsets().len() == 1 && self.visit(plan.input())
     }
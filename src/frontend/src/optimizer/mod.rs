@@ -16,7 +16,10 @@ pub mod plan_node;
 pub use plan_node::PlanRef;
 pub mod property;
 
+pub mod cardinality_estimator;
 mod delta_join_solver;
+mod execution_mode_decider;
+pub use execution_mode_decider::ExecutionModeDecider;
 mod heuristic;
 mod max_one_row_visitor;
 mod plan_correlated_id_finder;
@@ -25,24 +28,31 @@ mod plan_visitor;
 pub use plan_visitor::PlanVisitor;
 mod rule;
 
+use std::collections::HashMap;
+
 use fixedbitset::FixedBitSet;
 use itertools::Itertools as _;
 use property::Order;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::session_config::QueryMode;
 
 use self::heuristic::{ApplyOrder, HeuristicOptimizer};
-use self::plan_node::{BatchProject, Convention, LogicalProject, StreamMaterialize};
+use self::plan_node::{
+    BatchProject, Convention, LogicalProject, StreamMaterialize, StreamProject, StreamSink,
+    StreamSort,
+};
 use self::plan_visitor::{
     has_batch_exchange, has_batch_seq_scan, has_batch_seq_scan_where, has_logical_apply,
-    has_logical_over_agg,
 };
 use self::property::RequiredDist;
 use self::rule::*;
+use crate::catalog::SecretId;
 use crate::optimizer::max_one_row_visitor::HasMaxOneRowApply;
 use crate::optimizer::plan_node::{BatchExchange, PlanNodeType};
 use crate::optimizer::property::Distribution;
 use crate::utils::Condition;
+use crate::WithOptions;
 
 /// `PlanRoot` is used to describe a plan. planner will construct a `PlanRoot` with `LogicalNode`.
 /// and required distribution and order. And `PlanRoot` can generate corresponding streaming or
@@ -168,6 +178,17 @@ impl PlanRoot {
 
     /// Apply logical optimization to the plan.
     pub fn gen_optimized_logical_plan(&self) -> Result<PlanRef> {
+        self.gen_optimized_logical_plan_inner(false)
+    }
+
+    /// Apply logical optimization to the plan of a batch query, where the subqueries that can not
+    /// be unnested are kept as [`plan_node::LogicalApply`] and executed by nested loops, and the
+    /// multi-way joins are reordered by dynamic programming if the inputs have been analyzed.
+    fn gen_optimized_logical_plan_for_batch(&self) -> Result<PlanRef> {
+        self.gen_optimized_logical_plan_inner(true)
+    }
+
+    fn gen_optimized_logical_plan_inner(&self, for_batch: bool) -> Result<PlanRef> {
         let mut plan = self.plan.clone();
         let ctx = plan.ctx();
         let explain_trace = ctx.is_explain_trace();
@@ -201,6 +222,7 @@ impl PlanRoot {
         // General Unnesting.
         // Translate Apply, push Apply down the plan and finally replace Apply with regular inner
         // join.
+        let plan_before_general_unnesting = plan.clone();
         plan = self.optimize_by_rules(
             plan,
             "General Unnesting(Translate Apply)".to_string(),
@@ -216,11 +238,25 @@ impl PlanRoot {
                 ApplyProjRule::create(),
                 ApplyJoinRule::create(),
                 ApplyScanRule::create(),
+                ApplyTableFunctionRule::create(),
             ],
             ApplyOrder::TopDown,
         );
         if has_logical_apply(plan.clone()) {
-            return Err(ErrorCode::InternalError("Subquery can not be unnested.".into()).into());
+            if !for_batch {
+                return Err(
+                    ErrorCode::InternalError("Subquery can not be unnested.".into()).into(),
+                );
+            }
+            // The rest of the optimizations don't support `LogicalApply`, so they are skipped.
+            tracing::warn!(
+                "subquery can not be unnested, fall back to nested-loop apply: {}",
+                ctx.inner().sql
+            );
+            if explain_trace {
+                ctx.trace("Subquery can not be unnested, fall back to nested-loop apply.");
+            }
+            return Ok(plan_before_general_unnesting);
         }
 
         // Predicate Push-down
@@ -240,11 +276,16 @@ impl PlanRoot {
             ApplyOrder::TopDown,
         );
 
-        // Reorder multijoin into left-deep join tree.
+        // Reorder multijoin into left-deep join tree, or bushy join tree for batch if enabled.
+        let join_reorder_rule = if for_batch {
+            DpJoinReorderRule::create()
+        } else {
+            ReorderMultiJoinRule::create()
+        };
         plan = self.optimize_by_rules(
             plan,
             "Join Reorder".to_string(),
-            vec![ReorderMultiJoinRule::create()],
+            vec![join_reorder_rule],
             ApplyOrder::TopDown,
         );
 
@@ -283,11 +324,22 @@ impl PlanRoot {
             ctx.trace(plan.explain_to_string().unwrap());
         }
 
-        // Convert distinct aggregates.
+        // Convert distinct aggregates, except those deduplicated by the streaming agg executor.
+        let distinct_agg_rule = if !for_batch
+            && ctx
+                .inner()
+                .session_ctx
+                .config()
+                .get_streaming_enable_distinct_dedup()
+        {
+            DistinctAggRule::create_for_stream_dedup()
+        } else {
+            DistinctAggRule::create()
+        };
         plan = self.optimize_by_rules(
             plan,
             "Convert Distinct Aggregation".to_string(),
-            vec![DistinctAggRule::create()],
+            vec![distinct_agg_rule],
             ApplyOrder::TopDown,
         );
 
@@ -322,21 +374,13 @@ impl PlanRoot {
             ],
             ApplyOrder::TopDown,
         );
-        if has_logical_over_agg(plan.clone()) {
-            return Err(ErrorCode::InternalError(format!(
-                "OverAgg can not be transformed. Plan:\n{}",
-                plan.explain_to_string().unwrap()
-            ))
-            .into());
-        }
-
         Ok(plan)
     }
 
     /// Optimize and generate a singleton batch physical plan without exchange nodes.
     fn gen_batch_plan(&mut self) -> Result<PlanRef> {
         // Logical optimization
-        let mut plan = self.gen_optimized_logical_plan()?;
+        let mut plan = self.gen_optimized_logical_plan_for_batch()?;
 
         // Convert to physical plan node
         plan = plan.to_batch_with_order_required(&self.required_order)?;
@@ -365,10 +409,50 @@ impl PlanRoot {
         // TODO: join between a normal table and a system table is not supported yet
     }
 
+    /// Optimize and generate a batch query plan in `query_mode`. If it's [`QueryMode::Auto`], the
+    /// mode is decided by [`ExecutionModeDecider`] on the singleton batch plan, so that the
+    /// logical optimization is only done once.
+    ///
+    /// Returns the plan along with the mode it should be executed in.
+    pub fn gen_batch_plan_with_mode(
+        &mut self,
+        query_mode: QueryMode,
+    ) -> Result<(PlanRef, QueryMode)> {
+        let plan = self.gen_batch_plan()?;
+
+        let query_mode = match query_mode {
+            QueryMode::Auto => {
+                if ExecutionModeDecider::run_in_local_mode(plan.clone()) {
+                    QueryMode::Local
+                } else {
+                    QueryMode::Distributed
+                }
+            }
+            query_mode => query_mode,
+        };
+
+        let ctx = plan.ctx();
+        if ctx.is_explain_trace() {
+            ctx.trace(format!("Query Mode: {}", query_mode));
+        }
+
+        let plan = match query_mode {
+            QueryMode::Local => self.batch_plan_to_local(plan)?,
+            QueryMode::Distributed => self.batch_plan_to_distributed(plan)?,
+            QueryMode::Auto => unreachable!(),
+        };
+        Ok((plan, query_mode))
+    }
+
     /// Optimize and generate a batch query plan for distributed execution.
     pub fn gen_batch_distributed_plan(&mut self) -> Result<PlanRef> {
+        let plan = self.gen_batch_plan()?;
+        self.batch_plan_to_distributed(plan)
+    }
+
+    /// Convert the singleton batch plan to a distributed one.
+    fn batch_plan_to_distributed(&mut self, mut plan: PlanRef) -> Result<PlanRef> {
         self.set_required_dist(RequiredDist::single());
-        let mut plan = self.gen_batch_plan()?;
 
         // Convert to distributed plan
         plan = plan.to_distributed_with_required(&self.required_order, &self.required_dist)?;
@@ -402,8 +486,12 @@ impl PlanRoot {
 
     /// Optimize and generate a batch query plan for local execution.
     pub fn gen_batch_local_plan(&mut self) -> Result<PlanRef> {
-        let mut plan = self.gen_batch_plan()?;
+        let plan = self.gen_batch_plan()?;
+        self.batch_plan_to_local(plan)
+    }
 
+    /// Convert the singleton batch plan to a local one.
+    fn batch_plan_to_local(&mut self, mut plan: PlanRef) -> Result<PlanRef> {
         // Convert to local plan node
         plan = plan.to_local_with_order_required(&self.required_order)?;
 
@@ -480,18 +568,33 @@ impl PlanRoot {
     }
 
     /// Optimize and generate a create materialize view plan.
+    ///
+    /// With `emit_on_window_close`, the rows are only emitted to the materialized view once the
+    /// watermark has passed them, i.e. the windows are closed and will never be changed.
     pub fn gen_create_mv_plan(
         &mut self,
         mv_name: String,
         definition: String,
         col_names: Option<Vec<String>>,
+        emit_on_window_close: bool,
     ) -> Result<StreamMaterialize> {
         let out_names = if let Some(col_names) = col_names {
             col_names
         } else {
             self.out_names.clone()
         };
-        let stream_plan = self.gen_stream_plan()?;
+        let mut stream_plan = self.gen_stream_plan()?;
+        if emit_on_window_close {
+            let Some(sort_column_index) = stream_plan.watermark_columns().ones().next() else {
+                return Err(ErrorCode::InvalidInputSyntax(
+                    "EMIT ON WINDOW CLOSE requires a watermark column in the output of the query, \
+                     e.g. the window start of a time window aggregation on a source with watermark"
+                        .to_string(),
+                )
+                .into());
+            };
+            stream_plan = StreamSort::new(stream_plan, sort_column_index).into();
+        }
         StreamMaterialize::create(
             stream_plan,
             mv_name,
@@ -519,6 +622,35 @@ impl PlanRoot {
         )
     }
 
+    /// Optimize and generate a create sink plan, which sinks the output columns of the query.
+    pub fn gen_sink_plan(
+        &mut self,
+        properties: WithOptions,
+        secret_refs: HashMap<String, SecretId>,
+    ) -> Result<StreamSink> {
+        let mut stream_plan = self.gen_stream_plan()?;
+
+        // Remove the hidden columns, e.g. the pk of the query, from the sink.
+        if self.out_fields.count_ones(..) != self.out_fields.len() {
+            stream_plan = StreamProject::new(LogicalProject::with_out_fields(
+                stream_plan,
+                &self.out_fields,
+            ))
+            .into();
+        }
+
+        // The log store of a decoupled sink is distributed by the input. Gather the input to a
+        // single actor if it's not hash distributed, so that the logs written by different actors
+        // are not mixed up.
+        if properties.sink_decouple() && stream_plan.distribution().dist_column_indices().is_empty()
+        {
+            stream_plan =
+                RequiredDist::single().enforce_if_not_satisfies(stream_plan, &Order::any())?;
+        }
+
+        Ok(StreamSink::new(stream_plan, properties, secret_refs))
+    }
+
     /// Set the plan root's required dist.
     pub fn set_required_dist(&mut self, required_dist: RequiredDist) {
         self.required_dist = required_dist;
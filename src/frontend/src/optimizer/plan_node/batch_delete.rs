@@ -75,6 +75,7 @@ impl ToBatchProst for BatchDelete {
         NodeBody::Delete(DeleteNode {
             table_source_id: self.logical.source_id().table_id(),
             associated_mview_id: self.logical.associated_mview_id().table_id(),
+            returning: self.logical.has_returning(),
         })
     }
 }
@@ -75,6 +75,7 @@ impl ToBatchProst for BatchInsert {
             table_source_id: self.logical.source_id().table_id(),
             associated_mview_id: self.logical.associated_mview_id().table_id(),
             column_ids: vec![], // unused
+            returning: self.logical.has_returning(),
         })
     }
 }
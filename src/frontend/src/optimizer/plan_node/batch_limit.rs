@@ -18,7 +18,10 @@ use risingwave_common::error::Result;
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::LimitNode;
 
-use super::{LogicalLimit, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchProst, ToDistributedBatch};
+use super::{
+    BatchSeqScan, LogicalLimit, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchProst,
+    ToDistributedBatch,
+};
 use crate::optimizer::plan_node::ToLocalBatch;
 use crate::optimizer::property::{Order, RequiredDist};
 
@@ -41,9 +44,18 @@ impl BatchLimit {
         BatchLimit { base, logical }
     }
 
+    pub fn limit(&self) -> u64 {
+        self.logical.limit()
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.logical.offset()
+    }
+
     fn two_phase_limit(&self, input: PlanRef) -> Result<PlanRef> {
         let new_limit = self.logical.limit() + self.logical.offset();
         let new_offset = 0;
+        let input = BatchSeqScan::try_push_down_limit(input, new_limit, &Order::any());
         let logical_partial_limit = LogicalLimit::new(input, new_limit, new_offset);
         let batch_partial_limit = Self::new(logical_partial_limit);
         let ensure_single_dist = RequiredDist::single()
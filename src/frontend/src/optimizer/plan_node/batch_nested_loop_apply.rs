@@ -0,0 +1,97 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::NestedLoopApplyNode;
+
+use super::{
+    BatchExchange, LogicalApply, PlanBase, PlanRef, PlanTreeNodeBinary, ToBatchProst,
+    ToDistributedBatch, ToLocalBatch,
+};
+use crate::expr::{Expr, ExprImpl};
+use crate::optimizer::property::{Distribution, Order};
+
+/// `BatchNestedLoopApply` implements [`super::LogicalApply`] which can not be unnested, by
+/// executing the right side once for each row of the left side with the correlated input refs
+/// replaced by the values of the row.
+///
+/// The right side is built during execution in the same task, so the whole apply is executed in a
+/// single stage without any exchange in it.
+#[derive(Debug, Clone)]
+pub struct BatchNestedLoopApply {
+    pub base: PlanBase,
+    logical: LogicalApply,
+}
+
+impl BatchNestedLoopApply {
+    pub fn new(logical: LogicalApply) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let base = PlanBase::new_batch(
+            ctx,
+            logical.schema().clone(),
+            Distribution::Single,
+            Order::any(),
+        );
+        BatchNestedLoopApply { base, logical }
+    }
+}
+
+impl fmt::Display for BatchNestedLoopApply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchNestedLoopApply")
+    }
+}
+
+impl PlanTreeNodeBinary for BatchNestedLoopApply {
+    fn left(&self) -> PlanRef {
+        self.logical.left()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.logical.right()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_left_right(left, right))
+    }
+}
+
+impl_plan_tree_node_for_binary! { BatchNestedLoopApply }
+
+impl ToDistributedBatch for BatchNestedLoopApply {
+    fn to_distributed(&self) -> Result<PlanRef> {
+        // The inputs are kept without exchanges, and the apply is put into a new stage.
+        Ok(BatchExchange::new(self.clone().into(), Order::any(), Distribution::Single).into())
+    }
+}
+
+impl ToLocalBatch for BatchNestedLoopApply {
+    fn to_local(&self) -> Result<PlanRef> {
+        Ok(BatchExchange::new(self.clone().into(), Order::any(), Distribution::Single).into())
+    }
+}
+
+impl ToBatchProst for BatchNestedLoopApply {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        NodeBody::NestedLoopApply(NestedLoopApplyNode {
+            join_type: self.logical.join_type() as i32,
+            join_cond: Some(ExprImpl::from(self.logical.on().clone()).to_expr_proto()),
+            correlated_id: self.logical.correlated_id(),
+            right_schema: self.logical.right().schema().to_prost(),
+        })
+    }
+}
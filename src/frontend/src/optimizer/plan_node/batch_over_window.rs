@@ -0,0 +1,111 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::SortOverWindowNode;
+
+use super::{
+    LogicalOverAgg, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchProst, ToDistributedBatch,
+    ToLocalBatch,
+};
+use crate::optimizer::property::{FieldOrder, RequiredDist};
+
+/// `BatchOverWindow` evaluates a window function over an input sorted by the partition key and
+/// then the window order.
+#[derive(Debug, Clone)]
+pub struct BatchOverWindow {
+    pub base: PlanBase,
+    logical: LogicalOverAgg,
+}
+
+impl BatchOverWindow {
+    pub fn new(logical: LogicalOverAgg) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let input = logical.input();
+        assert!(input.order().satisfies(&logical.input_order()));
+        let base = PlanBase::new_batch(
+            ctx,
+            logical.schema().clone(),
+            input.distribution().clone(),
+            input.order().clone(),
+        );
+        BatchOverWindow { base, logical }
+    }
+}
+
+impl fmt::Display for BatchOverWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchOverWindow")
+    }
+}
+
+impl PlanTreeNodeUnary for BatchOverWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! { BatchOverWindow }
+
+impl ToDistributedBatch for BatchOverWindow {
+    fn to_distributed(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_distributed_with_required(
+            &self.logical.input_order(),
+            &self.logical.input_required_dist(),
+        )?;
+        Ok(self.clone_with_input(new_input).into())
+    }
+}
+
+impl ToBatchProst for BatchOverWindow {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        let window_function = &self.logical.window_function;
+        NodeBody::SortOverWindow(SortOverWindowNode {
+            calls: vec![window_function.to_protobuf()],
+            partition_by: self
+                .logical
+                .partition_key_indices()
+                .into_iter()
+                .map(|idx| idx as u32)
+                .collect(),
+            order_by: window_function
+                .order_by
+                .iter()
+                .map(|field| {
+                    FieldOrder {
+                        index: field.input.index,
+                        direct: field.direction,
+                    }
+                    .to_protobuf()
+                })
+                .collect(),
+        })
+    }
+}
+
+impl ToLocalBatch for BatchOverWindow {
+    fn to_local(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_local()?;
+        let new_input = RequiredDist::single()
+            .enforce_if_not_satisfies(new_input, &self.logical.input_order())?;
+        Ok(self.clone_with_input(new_input).into())
+    }
+}
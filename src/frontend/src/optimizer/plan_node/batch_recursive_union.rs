@@ -0,0 +1,103 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::RecursiveUnionNode;
+
+use super::{
+    BatchExchange, LogicalRecursiveUnion, PlanBase, PlanRef, PlanTreeNode, ToBatchProst,
+    ToDistributedBatch, ToLocalBatch,
+};
+use crate::optimizer::property::{Distribution, Order};
+
+/// `BatchRecursiveUnion` implements [`super::LogicalRecursiveUnion`]
+///
+/// The recursive term is re-executed in each iteration and reads the work table in the same task,
+/// so the whole recursive union is executed in a single stage without any exchange in it.
+#[derive(Debug, Clone)]
+pub struct BatchRecursiveUnion {
+    pub base: PlanBase,
+    logical: LogicalRecursiveUnion,
+}
+
+impl BatchRecursiveUnion {
+    pub fn new(logical: LogicalRecursiveUnion) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let base = PlanBase::new_batch(
+            ctx,
+            logical.schema().clone(),
+            Distribution::Single,
+            Order::any(),
+        );
+        BatchRecursiveUnion { base, logical }
+    }
+}
+
+impl fmt::Display for BatchRecursiveUnion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchRecursiveUnion")
+    }
+}
+
+impl PlanTreeNode for BatchRecursiveUnion {
+    fn inputs(&self) -> smallvec::SmallVec<[crate::optimizer::PlanRef; 2]> {
+        self.logical.inputs()
+    }
+
+    fn clone_with_inputs(&self, inputs: &[crate::optimizer::PlanRef]) -> PlanRef {
+        let [base, recursive] = inputs else {
+            unreachable!("recursive union must have 2 inputs")
+        };
+        Self::new(LogicalRecursiveUnion::new(
+            self.logical.work_table_id(),
+            self.logical.all(),
+            base.clone(),
+            recursive.clone(),
+        ))
+        .into()
+    }
+}
+
+impl ToDistributedBatch for BatchRecursiveUnion {
+    fn to_distributed(&self) -> Result<PlanRef> {
+        // The inputs are kept without exchanges, and the recursive union is put into a new stage.
+        Ok(BatchExchange::new(self.clone().into(), Order::any(), Distribution::Single).into())
+    }
+}
+
+impl ToLocalBatch for BatchRecursiveUnion {
+    fn to_local(&self) -> Result<PlanRef> {
+        Ok(BatchExchange::new(self.clone().into(), Order::any(), Distribution::Single).into())
+    }
+}
+
+impl ToBatchProst for BatchRecursiveUnion {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        let max_depth = self
+            .base
+            .ctx
+            .inner()
+            .session_ctx
+            .config()
+            .get_batch_max_recursion_depth();
+        NodeBody::RecursiveUnion(RecursiveUnionNode {
+            work_table_id: self.logical.work_table_id(),
+            all: self.logical.all(),
+            max_depth,
+        })
+    }
+}
@@ -26,7 +26,8 @@ use risingwave_pb::plan_common::ColumnDesc as ProstColumnDesc;
 use super::{PlanBase, PlanRef, ToBatchProst, ToDistributedBatch};
 use crate::catalog::ColumnId;
 use crate::optimizer::plan_node::{LogicalScan, ToLocalBatch};
-use crate::optimizer::property::{Distribution, DistributionDisplay};
+use crate::optimizer::property::{Distribution, DistributionDisplay, Order};
+use crate::planner::LIMIT_ALL_COUNT;
 
 /// `BatchSeqScan` implements [`super::LogicalScan`] to scan from a row-oriented table
 #[derive(Debug, Clone)]
@@ -34,10 +35,18 @@ pub struct BatchSeqScan {
     pub base: PlanBase,
     logical: LogicalScan,
     scan_ranges: Vec<ScanRange>,
+    /// The maximum number of rows to read from each scan range of each vnode, pushed down from
+    /// the parent `BatchLimit` or `BatchTopN`.
+    limit: Option<u64>,
 }
 
 impl BatchSeqScan {
-    fn new_inner(logical: LogicalScan, dist: Distribution, scan_ranges: Vec<ScanRange>) -> Self {
+    fn new_inner(
+        logical: LogicalScan,
+        dist: Distribution,
+        scan_ranges: Vec<ScanRange>,
+        limit: Option<u64>,
+    ) -> Self {
         let ctx = logical.base.ctx.clone();
         let base = PlanBase::new_batch(
             ctx,
@@ -64,12 +73,13 @@ impl BatchSeqScan {
             base,
             logical,
             scan_ranges,
+            limit,
         }
     }
 
     pub fn new(logical: LogicalScan, scan_ranges: Vec<ScanRange>) -> Self {
         // Use `Single` by default, will be updated later with `clone_with_dist`.
-        Self::new_inner(logical, Distribution::Single, scan_ranges)
+        Self::new_inner(logical, Distribution::Single, scan_ranges, None)
     }
 
     fn clone_with_dist(&self) -> Self {
@@ -102,9 +112,27 @@ impl BatchSeqScan {
                 }
             },
             self.scan_ranges.clone(),
+            self.limit,
         )
     }
 
+    /// Push down a limit into the scan. Only a prefix of each scan range in each vnode will be
+    /// read, so the parent operator must still be kept to produce the exact result.
+    ///
+    /// Returns `None` if the scan is on a system table, which cannot be limited.
+    pub fn clone_with_limit(&self, limit: u64) -> Option<Self> {
+        if self.logical.is_sys_table() {
+            return None;
+        }
+        let limit = self.limit.map_or(limit, |l| l.min(limit));
+        Some(Self::new_inner(
+            self.logical.clone(),
+            self.base.dist.clone(),
+            self.scan_ranges.clone(),
+            Some(limit),
+        ))
+    }
+
     /// Get a reference to the batch seq scan's logical.
     #[must_use]
     pub fn logical(&self) -> &LogicalScan {
@@ -114,6 +142,25 @@ impl BatchSeqScan {
     pub fn scan_ranges(&self) -> &[ScanRange] {
         &self.scan_ranges
     }
+
+    pub fn limit(&self) -> Option<u64> {
+        self.limit
+    }
+
+    /// Try to push down `limit` into `input` if it is a `BatchSeqScan` whose output order
+    /// satisfies `required_order`. Otherwise `input` is returned unchanged.
+    pub fn try_push_down_limit(input: PlanRef, limit: u64, required_order: &Order) -> PlanRef {
+        // A limit of 0 is not pushed down, as it means no limit in the scan node.
+        if limit == 0 || limit >= LIMIT_ALL_COUNT {
+            return input;
+        }
+        match input.as_batch_seq_scan() {
+            Some(scan) if scan.order().satisfies(required_order) => scan
+                .clone_with_limit(limit)
+                .map_or(input.clone(), |scan| scan.into()),
+            _ => input,
+        }
+    }
 }
 
 impl_plan_tree_node_for_leaf! { BatchSeqScan }
@@ -191,6 +238,10 @@ impl fmt::Display for BatchSeqScan {
             write!(f, ", scan_ranges: [{}]", range_strs.join(" , "))?;
         }
 
+        if let Some(limit) = self.limit {
+            write!(f, ", limit: {}", limit)?;
+        }
+
         if verbose {
             write!(
                 f,
@@ -238,6 +289,7 @@ impl ToBatchProst for BatchSeqScan {
                 scan_ranges: self.scan_ranges.iter().map(|r| r.to_protobuf()).collect(),
                 // To be filled by the scheduler.
                 vnode_bitmap: None,
+                limit: self.limit.unwrap_or(0),
             })
         }
     }
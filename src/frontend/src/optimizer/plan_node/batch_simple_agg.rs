@@ -76,7 +76,16 @@ impl ToDistributedBatch for BatchSimpleAgg {
             && self.logical.can_agg_two_phase()
         {
             // partial agg
-            let partial_agg = self.clone_with_input(dist_input).into();
+            let partial_agg = BatchSimpleAgg::new(LogicalAgg::new(
+                self.logical
+                    .agg_calls()
+                    .iter()
+                    .map(PlanAggCall::to_partial_agg_call)
+                    .collect(),
+                vec![],
+                dist_input,
+            ))
+            .into();
 
             // insert exchange
             let exchange =
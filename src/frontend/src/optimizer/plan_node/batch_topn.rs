@@ -18,7 +18,10 @@ use risingwave_common::error::Result;
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::TopNNode;
 
-use super::{LogicalTopN, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchProst, ToDistributedBatch};
+use super::{
+    BatchSeqScan, LogicalTopN, PlanBase, PlanRef, PlanTreeNodeUnary, ToBatchProst,
+    ToDistributedBatch,
+};
 use crate::optimizer::plan_node::ToLocalBatch;
 use crate::optimizer::property::{Order, RequiredDist};
 
@@ -43,9 +46,18 @@ impl BatchTopN {
         BatchTopN { base, logical }
     }
 
+    pub fn limit(&self) -> u64 {
+        self.logical.limit()
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.logical.offset()
+    }
+
     fn two_phase_topn(&self, input: PlanRef) -> Result<PlanRef> {
         let new_limit = self.logical.limit() + self.logical.offset();
         let new_offset = 0;
+        let input = BatchSeqScan::try_push_down_limit(input, new_limit, self.logical.topn_order());
         let logical_partial_topn = LogicalTopN::new(
             input,
             new_limit,
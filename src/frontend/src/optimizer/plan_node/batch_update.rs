@@ -84,6 +84,7 @@ impl ToBatchProst for BatchUpdate {
             table_source_id: self.logical.source_id().table_id(),
             associated_mview_id: self.logical.associated_mview_id().table_id(),
             exprs,
+            returning: self.logical.has_returning(),
         })
     }
 }
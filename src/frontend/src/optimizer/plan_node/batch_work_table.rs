@@ -0,0 +1,75 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::error::Result;
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+use risingwave_pb::batch_plan::WorkTableNode;
+
+use super::{
+    LogicalWorkTable, PlanBase, PlanRef, PlanTreeNodeLeaf, ToBatchProst, ToDistributedBatch,
+    ToLocalBatch,
+};
+use crate::optimizer::property::{Distribution, Order};
+
+/// `BatchWorkTable` implements [`super::LogicalWorkTable`]
+#[derive(Debug, Clone)]
+pub struct BatchWorkTable {
+    pub base: PlanBase,
+    logical: LogicalWorkTable,
+}
+
+impl PlanTreeNodeLeaf for BatchWorkTable {}
+impl_plan_tree_node_for_leaf!(BatchWorkTable);
+
+impl BatchWorkTable {
+    pub fn new(logical: LogicalWorkTable) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let base = PlanBase::new_batch(
+            ctx,
+            logical.schema().clone(),
+            Distribution::Single,
+            Order::any(),
+        );
+        BatchWorkTable { base, logical }
+    }
+}
+
+impl fmt::Display for BatchWorkTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "BatchWorkTable")
+    }
+}
+
+impl ToDistributedBatch for BatchWorkTable {
+    fn to_distributed(&self) -> Result<PlanRef> {
+        Ok(self.clone().into())
+    }
+}
+
+impl ToLocalBatch for BatchWorkTable {
+    fn to_local(&self) -> Result<PlanRef> {
+        Ok(self.clone().into())
+    }
+}
+
+impl ToBatchProst for BatchWorkTable {
+    fn to_batch_prost_body(&self) -> NodeBody {
+        NodeBody::WorkTable(WorkTableNode {
+            work_table_id: self.logical.work_table_id(),
+            fields: self.schema().to_prost(),
+        })
+    }
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::rc::Rc;
 
@@ -28,6 +28,7 @@ use risingwave_pb::stream_plan::{agg_call_state, AggCallState as AggCallStatePro
 
 use super::stream;
 use super::utils::{IndicesDisplay, TableCatalogBuilder};
+use crate::catalog::column_catalog::ColumnCatalog;
 use crate::catalog::source_catalog::SourceCatalog;
 use crate::catalog::IndexCatalog;
 use crate::expr::{Expr, ExprDisplay, ExprImpl, InputRef, InputRefDisplay};
@@ -51,6 +52,9 @@ pub struct DynamicFilter<PlanRef> {
     pub left_index: usize,
     pub left: PlanRef,
     pub right: PlanRef,
+    /// Whether the right value only moves in the direction that makes the predicate stricter, so
+    /// that the left rows failing the predicate never need to be kept.
+    pub is_monotonic: bool,
 }
 
 pub mod dynamic_filter {
@@ -328,7 +332,7 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
             }
 
             match agg_kind {
-                AggKind::ApproxCountDistinct => {
+                AggKind::ApproxCountDistinct | AggKind::ApproxCountDistinctMerge => {
                     // Add register column.
                     internal_table_catalog_builder.add_column(&Field {
                         data_type: DataType::List {
@@ -339,6 +343,17 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
                         type_name: String::default(),
                     });
                 }
+                AggKind::PercentileCont => {
+                    // Add centroids column.
+                    internal_table_catalog_builder.add_column(&Field {
+                        data_type: DataType::List {
+                            datatype: Box::new(DataType::Float64),
+                        },
+                        name: String::from("centroids"),
+                        sub_fields: vec![],
+                        type_name: String::default(),
+                    });
+                }
                 _ => {
                     panic!(
                         "state of agg kind `{}` is not supposed to be `TableState`",
@@ -365,8 +380,13 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
                 | AggKind::Max
                 | AggKind::StringAgg
                 | AggKind::ArrayAgg
-                | AggKind::FirstValue => {
-                    if !in_append_only {
+                | AggKind::FirstValue
+                | AggKind::LastValue => {
+                    // first/last value are only determined by the order of the materialized
+                    // input, so they keep the input state even on append-only streams
+                    let need_input_state = !in_append_only
+                        || matches!(agg_call.agg_kind, AggKind::FirstValue | AggKind::LastValue);
+                    if need_input_state {
                         // columns with order requirement in state table
                         let sort_keys = {
                             match agg_call.agg_kind {
@@ -376,17 +396,27 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
                                 AggKind::Max => {
                                     vec![(OrderType::Descending, agg_call.inputs[0].index)]
                                 }
-                                AggKind::StringAgg | AggKind::ArrayAgg => agg_call
+                                AggKind::StringAgg | AggKind::ArrayAgg | AggKind::FirstValue => {
+                                    agg_call
+                                        .order_by_fields
+                                        .iter()
+                                        .map(|o| (o.direction.to_order(), o.input.index))
+                                        .collect()
+                                }
+                                AggKind::LastValue => agg_call
                                     .order_by_fields
                                     .iter()
-                                    .map(|o| (o.direction.to_order(), o.input.index))
+                                    .map(|o| (o.direction.to_order().reverse(), o.input.index))
                                     .collect(),
                                 _ => unreachable!(),
                             }
                         };
                         // other columns that should be contained in state table
                         let include_keys = match agg_call.agg_kind {
-                            AggKind::StringAgg | AggKind::ArrayAgg => {
+                            AggKind::StringAgg
+                            | AggKind::ArrayAgg
+                            | AggKind::FirstValue
+                            | AggKind::LastValue => {
                                 agg_call.inputs.iter().map(|i| i.index).collect()
                             }
                             _ => vec![],
@@ -410,6 +440,20 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
                         AggCallState::Table(Box::new(state))
                     }
                 }
+                AggKind::ApproxCountDistinctMerge => {
+                    // The input sketches are only emitted by the stateless local agg of an
+                    // append-only stream, so the merged registers never need to shrink.
+                    let state = gen_table_state(agg_call.agg_kind);
+                    AggCallState::Table(Box::new(state))
+                }
+                AggKind::PercentileCont => {
+                    // The t-digest of an append-only stream, see `LogicalAgg::to_stream`.
+                    let state = gen_table_state(agg_call.agg_kind);
+                    AggCallState::Table(Box::new(state))
+                }
+                AggKind::PercentileDisc => {
+                    panic!("percentile_disc is not supported in streaming queries")
+                }
             })
             .collect()
     }
@@ -461,6 +505,72 @@ impl<PlanRef: stream::StreamPlanRef> Agg<PlanRef> {
         internal_table_catalog_builder.build(tb_dist)
     }
 
+    /// Infer dedup tables for the distinct agg calls, keyed by the distinct column index.
+    ///
+    /// The distinct agg calls on the same column share one table, whose pk is the group key and
+    /// the distinct column, and which has a count column for each of the agg calls. Min and max
+    /// are not affected by duplicated inputs, so they need no dedup table.
+    pub fn infer_distinct_dedup_tables(
+        &self,
+        me: &impl GenericPlanRef,
+        vnode_col_idx: Option<usize>,
+    ) -> BTreeMap<usize, TableCatalog> {
+        let in_fields = self.input.schema().fields();
+        let in_dist_key = self.input.distribution().dist_column_indices().to_vec();
+        let dedup_calls = self
+            .agg_calls
+            .iter()
+            .enumerate()
+            .filter(|(_, call)| {
+                call.distinct && !matches!(call.agg_kind, AggKind::Min | AggKind::Max)
+            })
+            .map(|(call_idx, call)| (call.inputs[0].index, call_idx))
+            .into_group_map();
+
+        dedup_calls
+            .into_iter()
+            .map(|(distinct_col, call_indices)| {
+                let mut internal_table_catalog_builder =
+                    TableCatalogBuilder::new(me.ctx().inner().with_options.internal_table_subset());
+
+                let included_upstream_indices = self
+                    .group_key
+                    .iter()
+                    .copied()
+                    .chain([distinct_col])
+                    .collect_vec();
+                for &idx in &included_upstream_indices {
+                    let tb_column_idx = internal_table_catalog_builder.add_column(&in_fields[idx]);
+                    internal_table_catalog_builder
+                        .add_order_column(tb_column_idx, OrderType::Ascending);
+                }
+                let n_key = included_upstream_indices.len();
+                for call_idx in &call_indices {
+                    internal_table_catalog_builder.add_column(&Field::with_name(
+                        DataType::Int64,
+                        format!("count_for_agg_call_{}", call_idx),
+                    ));
+                }
+
+                let mapping = ColIndexMapping::with_included_columns(
+                    &included_upstream_indices,
+                    in_fields.len(),
+                );
+                let tb_dist = mapping.rewrite_dist_key(&in_dist_key);
+                if let Some(tb_vnode_idx) = vnode_col_idx.and_then(|idx| mapping.try_map(idx)) {
+                    internal_table_catalog_builder.set_vnode_col_idx(tb_vnode_idx);
+                }
+                internal_table_catalog_builder
+                    .set_value_indices((n_key..n_key + call_indices.len()).collect());
+
+                (
+                    distinct_col,
+                    internal_table_catalog_builder.build(tb_dist.unwrap_or_default()),
+                )
+            })
+            .collect()
+    }
+
     pub fn decompose(self) -> (Vec<PlanAggCall>, Vec<usize>, PlanRef) {
         (self.agg_calls, self.group_key, self.input)
     }
@@ -635,21 +745,47 @@ impl PlanAggCall {
         }
     }
 
+    /// Returns the agg call computed by the partial phase of a 2-phase agg. Most agg calls
+    /// are computed as is, while `approx_count_distinct` returns its sketch to be merged.
+    pub fn to_partial_agg_call(&self) -> PlanAggCall {
+        match self.agg_kind {
+            AggKind::ApproxCountDistinct => PlanAggCall {
+                return_type: DataType::List {
+                    datatype: Box::new(DataType::Int64),
+                },
+                ..self.clone()
+            },
+            _ => self.clone(),
+        }
+    }
+
     pub fn partial_to_total_agg_call(&self, partial_output_idx: usize) -> PlanAggCall {
         let total_agg_kind = match &self.agg_kind {
-            AggKind::Min | AggKind::Max | AggKind::StringAgg | AggKind::FirstValue => self.agg_kind,
-            AggKind::Count | AggKind::ApproxCountDistinct | AggKind::Sum0 => AggKind::Sum0,
+            AggKind::Min
+            | AggKind::Max
+            | AggKind::StringAgg
+            | AggKind::FirstValue
+            | AggKind::LastValue => self.agg_kind,
+            AggKind::Count | AggKind::Sum0 => AggKind::Sum0,
             AggKind::Sum => AggKind::Sum,
+            AggKind::ApproxCountDistinct => AggKind::ApproxCountDistinctMerge,
             AggKind::Avg => {
                 panic!("Avg aggregation should have been rewritten to Sum+Count")
             }
             AggKind::ArrayAgg => {
                 panic!("2-phase ArrayAgg is not supported yet")
             }
+            AggKind::PercentileCont | AggKind::PercentileDisc => {
+                panic!("2-phase {} is not supported", self.agg_kind)
+            }
+            AggKind::ApproxCountDistinctMerge => {
+                panic!("ApproxCountDistinctMerge is already the total phase of 2-phase agg")
+            }
         };
+        let partial_return_type = self.to_partial_agg_call().return_type;
         PlanAggCall {
             agg_kind: total_agg_kind,
-            inputs: vec![InputRef::new(partial_output_idx, self.return_type.clone())],
+            inputs: vec![InputRef::new(partial_output_idx, partial_return_type)],
             order_by_fields: vec![], // order must make no difference when we use 2-phase agg
             filter: Condition::true_cond(),
             ..self.clone()
@@ -675,6 +811,18 @@ impl PlanAggCall {
     pub fn input_indices(&self) -> Vec<usize> {
         self.inputs.iter().map(|input| input.index()).collect()
     }
+
+    /// Maps the input columns referenced by the arguments, order by fields and filter.
+    pub fn rewrite_input_index(&mut self, mapping: &mut ColIndexMapping) {
+        self.inputs.iter_mut().for_each(|i| {
+            *i = InputRef::new(mapping.map(i.index()), i.return_type());
+        });
+        self.order_by_fields.iter_mut().for_each(|field| {
+            let i = &mut field.input;
+            *i = InputRef::new(mapping.map(i.index()), i.return_type());
+        });
+        self.filter = self.filter.clone().rewrite_expr(mapping);
+    }
 }
 
 pub struct PlanAggCallDisplay<'a> {
@@ -915,6 +1063,9 @@ pub struct Scan {
     pub indexes: Vec<Rc<IndexCatalog>>,
     /// The pushed down predicates. It refers to column indexes of the table.
     pub predicate: Condition,
+    /// Whether the scan is the lookup side of a temporal join, i.e., specified with
+    /// `FOR SYSTEM_TIME AS OF PROCTIME()`.
+    pub for_system_time_as_of_proctime: bool,
 }
 
 impl Scan {
@@ -931,9 +1082,51 @@ impl Scan {
 #[derive(Debug, Clone)]
 pub struct Source {
     pub catalog: Rc<SourceCatalog>,
+    /// The indices of the columns in the catalog to read from the source. All of the columns are
+    /// read unless some are pruned.
+    pub required_col_idx: Vec<usize>,
+    /// The connector properties derived from the predicates pushed down into the source, which are
+    /// passed to the connector together with the properties in the catalog.
+    pub pushdown_properties: BTreeMap<String, String>,
+    /// Whether the rows written by DML statements are merged into the source stream. Only set for
+    /// the connector sources of materialized sources, so that users can patch the ingested rows.
+    pub dml_enabled: bool,
 }
 
 impl Source {
+    pub fn new(catalog: Rc<SourceCatalog>) -> Self {
+        let required_col_idx = (0..catalog.columns.len()).collect();
+        Self {
+            catalog,
+            required_col_idx,
+            pushdown_properties: BTreeMap::new(),
+            dml_enabled: false,
+        }
+    }
+
+    /// The columns to read from the source.
+    pub fn columns(&self) -> impl Iterator<Item = &ColumnCatalog> {
+        self.required_col_idx
+            .iter()
+            .map(|&idx| &self.catalog.columns[idx])
+    }
+
+    /// The index of the row id column in the columns to read.
+    pub fn row_id_index(&self) -> Option<usize> {
+        self.catalog.row_id_index.and_then(|row_id_index| {
+            self.required_col_idx
+                .iter()
+                .position(|&idx| idx == row_id_index)
+        })
+    }
+
+    /// The connector properties, including the ones from the pushed down predicates.
+    pub fn properties(&self) -> HashMap<String, String> {
+        let mut properties = self.catalog.properties.clone();
+        properties.extend(self.pushdown_properties.clone());
+        properties
+    }
+
     pub fn infer_internal_table_catalog(me: &impl GenericPlanRef) -> TableCatalog {
         // note that source's internal table is to store partition_id -> offset mapping and its
         // schema is irrelevant to input schema
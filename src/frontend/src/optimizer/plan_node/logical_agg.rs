@@ -12,13 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::{fmt, iter};
 
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_common::catalog::FieldDisplay;
 use risingwave_common::error::{ErrorCode, Result, TrackingIssue};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_expr::expr::AggKind;
 
 use super::generic::{
@@ -26,13 +27,14 @@ use super::generic::{
     PlanAggOrderByField,
 };
 use super::{
-    BatchHashAgg, BatchSimpleAgg, ColPrunable, LogicalProjectBuilder, PlanBase, PlanRef,
-    PlanTreeNodeUnary, PredicatePushdown, StreamGlobalSimpleAgg, StreamHashAgg,
-    StreamLocalSimpleAgg, StreamProject, ToBatch, ToStream,
+    BatchHashAgg, BatchSimpleAgg, ColPrunable, LogicalExpand, LogicalProjectBuilder, PlanBase,
+    PlanRef, PlanTreeNodeUnary, PredicatePushdown, StreamGlobalSimpleAgg, StreamHashAgg,
+    StreamLocalHashAgg, StreamLocalSimpleAgg, StreamProject, ToBatch, ToStream,
 };
 use crate::catalog::table_catalog::TableCatalog;
 use crate::expr::{
     AggCall, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef, Literal, OrderBy,
+    Subquery, SubqueryKind,
 };
 use crate::optimizer::plan_node::{gen_filter_and_pushdown, BatchSortAgg, LogicalProject};
 use crate::optimizer::property::Direction::{Asc, Desc};
@@ -59,6 +61,15 @@ impl LogicalAgg {
         self.core.infer_result_table(&self.base, vnode_col_idx)
     }
 
+    /// Infer dedup tables for distinct agg calls of streaming agg.
+    pub fn infer_distinct_dedup_tables(
+        &self,
+        vnode_col_idx: Option<usize>,
+    ) -> BTreeMap<usize, TableCatalog> {
+        self.core
+            .infer_distinct_dedup_tables(&self.base, vnode_col_idx)
+    }
+
     /// Infer `AggCallState`s for streaming agg.
     pub fn infer_stream_agg_state(&self, vnode_col_idx: Option<usize>) -> Vec<AggCallState> {
         self.core.infer_stream_agg_state(&self.base, vnode_col_idx)
@@ -68,7 +79,14 @@ impl LogicalAgg {
     /// Should only be used iff input is distributed. Input must be converted to stream form.
     fn gen_stateless_two_phase_streaming_agg_plan(&self, stream_input: PlanRef) -> Result<PlanRef> {
         debug_assert!(self.group_key().is_empty());
-        let local_agg = StreamLocalSimpleAgg::new(self.clone_with_input(stream_input));
+        let local_agg = StreamLocalSimpleAgg::new(LogicalAgg::new(
+            self.agg_calls()
+                .iter()
+                .map(PlanAggCall::to_partial_agg_call)
+                .collect(),
+            vec![],
+            stream_input,
+        ));
         let exchange =
             RequiredDist::single().enforce_if_not_satisfies(local_agg.into(), &Order::any())?;
         let global_agg = StreamGlobalSimpleAgg::new(LogicalAgg::new(
@@ -85,6 +103,41 @@ impl LogicalAgg {
         Ok(global_agg.into())
     }
 
+    /// Generate plan for stateless 2-phase streaming agg with group keys, which shuffles the
+    /// partial results of the local agg by the group keys.
+    /// Should only be used iff input is distributed. Input must be converted to stream form.
+    fn gen_stateless_two_phase_hash_agg_plan(&self, stream_input: PlanRef) -> Result<PlanRef> {
+        debug_assert!(!self.group_key().is_empty());
+        let local_agg = StreamLocalHashAgg::new(LogicalAgg::new(
+            self.agg_calls()
+                .iter()
+                .map(PlanAggCall::to_partial_agg_call)
+                .collect(),
+            self.group_key().to_vec(),
+            stream_input,
+        ));
+        // The group keys are the first columns of the local agg output.
+        let n_group_key = self.group_key().len();
+        let global_group_key = (0..n_group_key).collect_vec();
+        let exchange = RequiredDist::shard_by_key(local_agg.schema().len(), &global_group_key)
+            .enforce_if_not_satisfies(local_agg.into(), &Order::any())?;
+        let global_agg = StreamHashAgg::new(
+            LogicalAgg::new(
+                self.agg_calls()
+                    .iter()
+                    .enumerate()
+                    .map(|(partial_output_idx, agg_call)| {
+                        agg_call.partial_to_total_agg_call(n_group_key + partial_output_idx)
+                    })
+                    .collect(),
+                global_group_key,
+                exchange,
+            ),
+            None,
+        );
+        Ok(global_agg.into())
+    }
+
     /// Generate plan for stateless/stateful 2-phase streaming agg.
     /// Should only be used iff input is distributed. Input must be converted to stream form.
     fn gen_vnode_two_phase_streaming_agg_plan(
@@ -159,10 +212,32 @@ impl LogicalAgg {
     }
 
     fn gen_dist_stream_agg_plan(&self, stream_input: PlanRef) -> Result<PlanRef> {
-        // having group key, is not simple agg. we will just use shuffle agg
-        // TODO(stonepage): in some situation the 2-phase agg is better. maybe some switch or
-        // hints for it.
+        // having group key, is not simple agg. we will use shuffle agg, unless the 2-phase agg is
+        // enabled and the input is not distributed by the group keys yet.
         if !self.group_key().is_empty() {
+            let enable_two_phase = self
+                .base
+                .ctx
+                .inner()
+                .session_ctx
+                .config()
+                .get_streaming_enable_two_phase_agg();
+            let input_dist = stream_input.distribution();
+            if enable_two_phase
+                && self.can_agg_two_phase()
+                && self.all_local_are_stateless(stream_input.append_only())
+                && !self
+                    .agg_calls()
+                    .iter()
+                    .any(|c| c.agg_kind == AggKind::ApproxCountDistinct)
+                && input_dist.satisfies(&RequiredDist::AnyShard)
+                && !input_dist.satisfies(&RequiredDist::shard_by_key(
+                    stream_input.schema().len(),
+                    self.group_key(),
+                ))
+            {
+                return self.gen_stateless_two_phase_hash_agg_plan(stream_input);
+            }
             return Ok(StreamHashAgg::new(
                 self.clone_with_input(
                     RequiredDist::shard_by_key(stream_input.schema().len(), self.group_key())
@@ -193,14 +268,22 @@ impl LogicalAgg {
 
         // stateless 2-phase simple agg
         // can be applied on stateless simple agg calls with input distributed by any shard
-        let all_local_are_stateless = self.agg_calls().iter().all(|c| {
-            matches!(c.agg_kind, AggKind::Sum | AggKind::Count)
-                || (matches!(c.agg_kind, AggKind::Min | AggKind::Max) && input_append_only)
-        });
-        if all_local_are_stateless && input_dist.satisfies(&RequiredDist::AnyShard) {
+        if self.all_local_are_stateless(input_append_only)
+            && input_dist.satisfies(&RequiredDist::AnyShard)
+        {
             return self.gen_stateless_two_phase_streaming_agg_plan(stream_input);
         }
 
+        // the sketches of approx_count_distinct can only be merged when they are never retracted,
+        // which is not the case for the stateful local agg.
+        if self
+            .agg_calls()
+            .iter()
+            .any(|c| c.agg_kind == AggKind::ApproxCountDistinct)
+        {
+            return gen_single_plan(stream_input);
+        }
+
         // try to use the vnode-based 2-phase simple agg
         // can be applied on agg calls not affected by order with input distributed by dist_key
         match input_dist {
@@ -212,18 +295,50 @@ impl LogicalAgg {
         }
     }
 
+    /// Check if all agg calls can be computed by a stateless local agg, which holds no state across
+    /// epochs.
+    fn all_local_are_stateless(&self, input_append_only: bool) -> bool {
+        self.agg_calls().iter().all(|c| {
+            matches!(c.agg_kind, AggKind::Sum | AggKind::Count)
+                || (matches!(
+                    c.agg_kind,
+                    AggKind::Min | AggKind::Max | AggKind::ApproxCountDistinct
+                ) && input_append_only)
+        })
+    }
+
     /// Check if the aggregation result will be affected by order by clause, if any.
     pub(crate) fn is_agg_result_affected_by_order(&self) -> bool {
-        self.agg_calls()
-            .iter()
-            .any(|call| matches!(call.agg_kind, AggKind::StringAgg | AggKind::ArrayAgg))
+        self.agg_calls().iter().any(|call| {
+            matches!(
+                call.agg_kind,
+                AggKind::StringAgg | AggKind::ArrayAgg | AggKind::FirstValue | AggKind::LastValue
+            )
+        })
+    }
+
+    /// Check if the distinct agg calls can be computed by the streaming agg executor, which
+    /// deduplicates their inputs with dedup tables, without being rewritten by `DistinctAggRule`.
+    pub(crate) fn can_dedup_distinct_in_stream(&self) -> bool {
+        self.agg_calls().iter().all(|call| {
+            !call.distinct
+                || (matches!(
+                    call.agg_kind,
+                    AggKind::Count | AggKind::Sum | AggKind::Min | AggKind::Max
+                ) && call.inputs.len() == 1
+                    && call.order_by_fields.is_empty())
+        })
     }
 
     pub(crate) fn can_agg_two_phase(&self) -> bool {
         self.agg_calls().iter().all(|call| {
             matches!(
                 call.agg_kind,
-                AggKind::Min | AggKind::Max | AggKind::Sum | AggKind::Count
+                AggKind::Min
+                    | AggKind::Max
+                    | AggKind::Sum
+                    | AggKind::Count
+                    | AggKind::ApproxCountDistinct
             ) && !call.distinct
             // QUESTION: why do we need `&& call.order_by_fields.is_empty()` ?
             //    && call.order_by_fields.is_empty()
@@ -290,7 +405,8 @@ impl LogicalAgg {
 }
 
 /// `LogicalAggBuilder` extracts agg calls and references to group columns from select list and
-/// build the plan like `LogicalAgg - LogicalProject`.
+/// build the plan like `LogicalAgg - LogicalProject`, or `LogicalAgg - LogicalExpand -
+/// LogicalProject` with grouping sets.
 /// it is constructed by `group_exprs` and collect and rewrite the expression in selection and
 /// having clause.
 struct LogicalAggBuilder {
@@ -298,6 +414,9 @@ struct LogicalAggBuilder {
     input_proj_builder: LogicalProjectBuilder,
     /// the group key column indices in the project's output
     group_key: Vec<usize>,
+    /// the grouping sets as indices into `group_key`, empty for a plain GROUP BY. With grouping
+    /// sets, the flag of `LogicalExpand` is an extra group column following `group_key`.
+    grouping_sets: Vec<Vec<usize>>,
     /// the agg calls
     agg_calls: Vec<PlanAggCall>,
     /// the error during the expression rewriting
@@ -311,7 +430,7 @@ struct LogicalAggBuilder {
 }
 
 impl LogicalAggBuilder {
-    fn new(group_exprs: Vec<ExprImpl>) -> Result<Self> {
+    fn new(group_exprs: Vec<ExprImpl>, grouping_sets: Vec<Vec<usize>>) -> Result<Self> {
         let mut input_proj_builder = LogicalProjectBuilder::default();
 
         let group_key = group_exprs
@@ -324,6 +443,7 @@ impl LogicalAggBuilder {
 
         Ok(LogicalAggBuilder {
             group_key,
+            grouping_sets,
             agg_calls: vec![],
             error: None,
             input_proj_builder,
@@ -335,8 +455,44 @@ impl LogicalAggBuilder {
         // This LogicalProject focuses on the exprs in aggregates and GROUP BY clause.
         let logical_project = self.input_proj_builder.build(input);
 
-        // This LogicalAgg focuses on calculating the aggregates and grouping.
-        LogicalAgg::new(self.agg_calls, self.group_key, logical_project.into())
+        if self.grouping_sets.is_empty() {
+            // This LogicalAgg focuses on calculating the aggregates and grouping.
+            return LogicalAgg::new(self.agg_calls, self.group_key, logical_project.into());
+        }
+
+        // With grouping sets, each row is expanded once per grouping set, where the group columns
+        // not in the grouping set are set to null. The aggregates take the original columns, and
+        // the flag tells the grouping sets apart.
+        let input_len = logical_project.schema().len();
+        let column_subsets = self
+            .grouping_sets
+            .iter()
+            .map(|set| set.iter().map(|&i| self.group_key[i]).collect_vec())
+            .collect_vec();
+        let logical_expand = LogicalExpand::create(logical_project.into(), column_subsets);
+
+        let mut original_columns =
+            ColIndexMapping::with_shift_offset(input_len, input_len as isize);
+        let agg_calls = self
+            .agg_calls
+            .into_iter()
+            .map(|mut agg_call| {
+                agg_call.rewrite_input_index(&mut original_columns);
+                agg_call
+            })
+            .collect();
+        let mut group_key = self.group_key;
+        group_key.push(input_len * 2);
+        LogicalAgg::new(agg_calls, group_key, logical_expand)
+    }
+
+    /// The index of the first agg call in the output of the agg.
+    fn agg_calls_offset(&self) -> usize {
+        if self.grouping_sets.is_empty() {
+            self.group_key.len()
+        } else {
+            self.group_key.len() + 1
+        }
     }
 
     fn rewrite_with_error(&mut self, expr: ExprImpl) -> Result<ExprImpl> {
@@ -367,23 +523,31 @@ impl LogicalAggBuilder {
     /// implement distinct aggregates.
     pub fn syntax_check(&self) -> Result<()> {
         let mut has_distinct = false;
-        let mut has_order_by = false;
-        let mut has_non_distinct_string_agg = false;
+        let mut has_non_distinct_order_by = false;
+        let mut non_distinct_unsplittable_agg = None;
         self.agg_calls.iter().for_each(|agg_call| {
             if agg_call.distinct {
                 has_distinct = true;
-            }
-            if !agg_call.order_by_fields.is_empty() {
-                has_order_by = true;
-            }
-            if !agg_call.distinct && agg_call.agg_kind == AggKind::StringAgg {
-                has_non_distinct_string_agg = true;
+            } else {
+                if !agg_call.order_by_fields.is_empty() {
+                    has_non_distinct_order_by = true;
+                }
+                if matches!(
+                    agg_call.agg_kind,
+                    AggKind::StringAgg
+                        | AggKind::ArrayAgg
+                        | AggKind::PercentileCont
+                        | AggKind::PercentileDisc
+                ) {
+                    non_distinct_unsplittable_agg = Some(agg_call.agg_kind);
+                }
             }
         });
 
         // order by is disallowed occur with distinct because we can not diectly rewrite agg with
-        // order by into 2-phase agg.
-        if has_distinct && has_order_by {
+        // order by into 2-phase agg. Distinct aggregates themselves can be ordered, because their
+        // order keys are always part of their arguments and thus kept by the middle agg.
+        if has_distinct && has_non_distinct_order_by {
             return Err(ErrorCode::InvalidInputSyntax(
                 "Order by aggregates are disallowed to occur with distinct aggregates".into(),
             )
@@ -391,11 +555,11 @@ impl LogicalAggBuilder {
         }
 
         // when there are distinct aggregates, non-distinct aggregates will be rewritten as
-        // two-phase aggregates, while string_agg can not be rewritten as two-phase aggregates, so
-        // we have to ban this case now.
-        if has_distinct && has_non_distinct_string_agg {
+        // two-phase aggregates, while string_agg, array_agg and the percentiles can not be
+        // rewritten as two-phase aggregates, so we have to ban this case now.
+        if has_distinct && let Some(agg_kind) = non_distinct_unsplittable_agg {
             return Err(ErrorCode::NotImplemented(
-                "Non-distinct string_agg can't appear with distinct aggregates".into(),
+                format!("Non-distinct {} can't appear with distinct aggregates", agg_kind),
                 TrackingIssue::none(),
             )
             .into());
@@ -484,7 +648,7 @@ impl LogicalAggBuilder {
                 filter: filter.clone(),
             });
             let left = ExprImpl::from(InputRef::new(
-                self.group_key.len() + self.agg_calls.len() - 1,
+                self.agg_calls_offset() + self.agg_calls.len() - 1,
                 left_return_type,
             ))
             .cast_implicit(return_type)
@@ -503,7 +667,7 @@ impl LogicalAggBuilder {
             });
 
             let right = InputRef::new(
-                self.group_key.len() + self.agg_calls.len() - 1,
+                self.agg_calls_offset() + self.agg_calls.len() - 1,
                 right_return_type,
             );
 
@@ -519,8 +683,66 @@ impl LogicalAggBuilder {
                 order_by_fields,
                 filter,
             });
-            Ok(InputRef::new(self.group_key.len() + self.agg_calls.len() - 1, return_type).into())
+            Ok(InputRef::new(
+                self.agg_calls_offset() + self.agg_calls.len() - 1,
+                return_type,
+            )
+            .into())
+        }
+    }
+
+    /// `grouping(a, b, ...)` is rewritten as a bit mask of whether each argument is excluded from
+    /// the grouping set of the row, with the last argument as the lowest bit. As there are finitely
+    /// many grouping sets, it is a `CASE` over the flag of `LogicalExpand`.
+    fn try_rewrite_grouping(
+        &self,
+        func_call: FunctionCall,
+    ) -> std::result::Result<ExprImpl, ErrorCode> {
+        let group_key_indices: Vec<_> = func_call
+            .inputs()
+            .iter()
+            .map(|expr| {
+                self.try_as_group_expr(expr).ok_or_else(|| {
+                    ErrorCode::InvalidInputSyntax(
+                        "arguments to GROUPING must be grouping expressions of the associated query level"
+                            .into(),
+                    )
+                })
+            })
+            .try_collect()?;
+
+        if self.grouping_sets.is_empty() {
+            return Ok(ExprImpl::literal_int(0));
         }
+        let masks = self
+            .grouping_sets
+            .iter()
+            .map(|set| {
+                group_key_indices
+                    .iter()
+                    .fold(0, |mask, i| (mask << 1) | !set.contains(i) as i32)
+            })
+            .collect_vec();
+        if masks.iter().all_equal() {
+            return Ok(ExprImpl::literal_int(masks[0]));
+        }
+
+        let flag = ExprImpl::from(InputRef::new(self.group_key.len(), DataType::Int64));
+        let mut inputs = vec![];
+        for (i, mask) in masks.iter().enumerate().take(masks.len() - 1) {
+            let when = FunctionCall::new(
+                ExprType::Equal,
+                vec![
+                    flag.clone(),
+                    Literal::new(Some(ScalarImpl::Int64(i as i64)), DataType::Int64).into(),
+                ],
+            )
+            .unwrap();
+            inputs.push(when.into());
+            inputs.push(ExprImpl::literal_int(*mask));
+        }
+        inputs.push(ExprImpl::literal_int(*masks.last().unwrap()));
+        Ok(FunctionCall::new(ExprType::Case, inputs).unwrap().into())
     }
 }
 
@@ -539,6 +761,15 @@ impl ExprRewriter for LogicalAggBuilder {
     /// When there is an `FunctionCall` (outside of agg call), it must refers to a group column.
     /// Or all `InputRef`s appears in it must refer to a group column.
     fn rewrite_function_call(&mut self, func_call: FunctionCall) -> ExprImpl {
+        if func_call.get_expr_type() == ExprType::Grouping {
+            return match self.try_rewrite_grouping(func_call) {
+                Ok(expr) => expr,
+                Err(err) => {
+                    self.error = Some(err);
+                    ExprImpl::literal_int(0)
+                }
+            };
+        }
         let expr = func_call.into();
         if let Some(group_key) = self.try_as_group_expr(&expr) {
             InputRef::new(group_key, expr.return_type()).into()
@@ -572,13 +803,35 @@ impl ExprRewriter for LogicalAggBuilder {
         }
     }
 
-    fn rewrite_subquery(&mut self, subquery: crate::expr::Subquery) -> ExprImpl {
-        if subquery.is_correlated() {
-            self.error = Some(ErrorCode::NotImplemented(
-                "correlated subquery in HAVING or SELECT with agg".into(),
-                2275.into(),
+    /// The correlated input refs in a subquery (outside of agg call) refer to the input of the
+    /// agg, so they must refer to group columns as well, and are rewritten to refer to the output
+    /// of the agg.
+    fn rewrite_subquery(&mut self, mut subquery: Subquery) -> ExprImpl {
+        let mut not_group_column = false;
+        subquery
+            .query
+            .visit_correlated_input_refs_by_depth(1, &mut |correlated_input_ref| {
+                let expr = InputRef::new(
+                    correlated_input_ref.index(),
+                    correlated_input_ref.return_type(),
+                )
+                .into();
+                match self.try_as_group_expr(&expr) {
+                    Some(group_key) => correlated_input_ref.set_index(group_key),
+                    None => not_group_column = true,
+                }
+            });
+        if not_group_column {
+            self.error = Some(ErrorCode::InvalidInputSyntax(
+                "subquery uses ungrouped column from outer query".into(),
             ));
         }
+        subquery.kind = match subquery.kind {
+            SubqueryKind::In(expr) => SubqueryKind::In(self.rewrite_expr(expr)),
+            SubqueryKind::Some(expr, op) => SubqueryKind::Some(self.rewrite_expr(expr), op),
+            SubqueryKind::All(expr, op) => SubqueryKind::All(self.rewrite_expr(expr), op),
+            kind => kind,
+        };
         subquery.into()
     }
 }
@@ -644,15 +897,22 @@ impl LogicalAgg {
     /// LogicalAgg -> LogicalProject -> input
     /// ```
     ///
+    /// or, with grouping sets,
+    ///
+    /// ```text
+    /// LogicalAgg -> LogicalExpand -> LogicalProject -> input
+    /// ```
+    ///
     /// It also returns the rewritten select exprs and having that reference into the aggregated
     /// results.
     pub fn create(
         select_exprs: Vec<ExprImpl>,
         group_exprs: Vec<ExprImpl>,
+        grouping_sets: Vec<Vec<usize>>,
         having: Option<ExprImpl>,
         input: PlanRef,
     ) -> Result<(PlanRef, Vec<ExprImpl>, Option<ExprImpl>)> {
-        let mut agg_builder = LogicalAggBuilder::new(group_exprs)?;
+        let mut agg_builder = LogicalAggBuilder::new(group_exprs, grouping_sets)?;
 
         let rewritten_select_exprs = select_exprs
             .into_iter()
@@ -704,14 +964,7 @@ impl LogicalAgg {
             .iter()
             .cloned()
             .map(|mut agg_call| {
-                agg_call.inputs.iter_mut().for_each(|i| {
-                    *i = InputRef::new(input_col_change.map(i.index()), i.return_type())
-                });
-                agg_call.order_by_fields.iter_mut().for_each(|field| {
-                    let i = &mut field.input;
-                    *i = InputRef::new(input_col_change.map(i.index()), i.return_type())
-                });
-                agg_call.filter = agg_call.filter.rewrite_expr(&mut input_col_change);
+                agg_call.rewrite_input_index(&mut input_col_change);
                 agg_call
             })
             .collect();
@@ -892,8 +1145,22 @@ impl ToBatch for LogicalAgg {
         let new_input = self.input().to_batch_with_order_required(&input_order)?;
         let new_logical = self.clone_with_input(new_input);
         if self.group_key().is_empty() {
-            required_order.enforce_if_not_satisfies(BatchSimpleAgg::new(new_logical).into())
-        } else if self.input_provides_order_on_group_keys(&new_logical) || output_requires_order {
+            return required_order
+                .enforce_if_not_satisfies(BatchSimpleAgg::new(new_logical).into());
+        }
+
+        // Sort aggregation is chosen when the output must be ordered anyway, or, if enabled by the
+        // session config, when the input is already sorted on the group keys.
+        let enable_sort_agg = self
+            .base
+            .ctx
+            .inner()
+            .session_ctx
+            .config()
+            .get_batch_enable_sort_agg();
+        if output_requires_order
+            || (enable_sort_agg && self.input_provides_order_on_group_keys(&new_logical))
+        {
             required_order.enforce_if_not_satisfies(BatchSortAgg::new(new_logical).into())
         } else {
             required_order.enforce_if_not_satisfies(BatchHashAgg::new(new_logical).into())
@@ -922,8 +1189,31 @@ impl ToStream for LogicalAgg {
             .chain(self.agg_calls().iter().cloned())
             .collect_vec();
 
+        let stream_input = self.input().to_stream()?;
+        // In streaming, the percentiles are approximated by a t-digest, which can neither be
+        // retracted nor hold values of other types than `float8`.
+        for agg_call in self.agg_calls() {
+            match agg_call.agg_kind {
+                AggKind::PercentileCont if !stream_input.append_only() => {
+                    return Err(ErrorCode::NotImplemented(
+                        "percentile_cont on a non-append-only stream".to_string(),
+                        None.into(),
+                    )
+                    .into());
+                }
+                AggKind::PercentileDisc => {
+                    return Err(ErrorCode::NotImplemented(
+                        "percentile_disc in streaming queries".to_string(),
+                        None.into(),
+                    )
+                    .into());
+                }
+                _ => {}
+            }
+        }
+
         let logical_agg = LogicalAgg::new(agg_calls, self.group_key().to_vec(), self.input());
-        let stream_agg = logical_agg.gen_dist_stream_agg_plan(self.input().to_stream()?)?;
+        let stream_agg = logical_agg.gen_dist_stream_agg_plan(stream_input)?;
 
         let stream_project = StreamProject::new(LogicalProject::with_out_col_idx(
             stream_agg,
@@ -974,7 +1264,7 @@ mod tests {
                                   group_exprs|
          -> (Vec<ExprImpl>, Vec<PlanAggCall>, Vec<usize>) {
             let (plan, exprs, _) =
-                LogicalAgg::create(select_exprs, group_exprs, None, input.clone()).unwrap();
+                LogicalAgg::create(select_exprs, group_exprs, vec![], None, input.clone()).unwrap();
 
             let logical_agg = plan.as_logical_agg().unwrap();
             let agg_calls = logical_agg.agg_calls().to_vec();
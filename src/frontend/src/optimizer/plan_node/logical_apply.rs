@@ -19,8 +19,8 @@ use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_pb::plan_common::JoinType;
 
 use super::{
-    ColPrunable, LogicalJoin, LogicalProject, PlanBase, PlanRef, PlanTreeNodeBinary,
-    PredicatePushdown, ToBatch, ToStream,
+    BatchNestedLoopApply, ColPrunable, LogicalJoin, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeBinary, PredicatePushdown, ToBatch, ToStream,
 };
 use crate::expr::{CorrelatedId, Expr, ExprImpl, ExprRewriter, InputRef};
 use crate::optimizer::property::FunctionalDependencySet;
@@ -47,7 +47,13 @@ pub struct LogicalApply {
 
 impl fmt::Display for LogicalApply {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut builder = f.debug_struct("LogicalApply");
+        self.fmt_with_name(f, "LogicalApply")
+    }
+}
+
+impl LogicalApply {
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        let mut builder = f.debug_struct(name);
 
         builder.field("type", &format_args!("{:?}", &self.join_type));
 
@@ -145,6 +151,11 @@ impl LogicalApply {
         self.join_type
     }
 
+    /// Get a reference to the logical apply's on condition.
+    pub fn on(&self) -> &Condition {
+        &self.on
+    }
+
     pub fn decompose(
         self,
     ) -> (
@@ -290,10 +301,13 @@ impl PredicatePushdown for LogicalApply {
 }
 
 impl ToBatch for LogicalApply {
+    /// A `LogicalApply` is only left in a batch plan when it can not be unnested, and then it is
+    /// executed by nested loops.
     fn to_batch(&self) -> Result<PlanRef> {
-        Err(RwError::from(ErrorCode::InternalError(
-            "LogicalApply should be unnested".to_string(),
-        )))
+        let left = self.left().to_batch()?;
+        let right = self.right().to_batch()?;
+        let logical = self.clone_with_left_right(left, right);
+        Ok(BatchNestedLoopApply::new(logical).into())
     }
 }
 
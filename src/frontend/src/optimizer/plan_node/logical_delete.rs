@@ -19,8 +19,8 @@ use risingwave_common::error::Result;
 use risingwave_common::types::DataType;
 
 use super::{
-    gen_filter_and_pushdown, BatchDelete, ColPrunable, PlanBase, PlanRef, PlanTreeNodeUnary,
-    PredicatePushdown, ToBatch, ToStream,
+    gen_filter_and_pushdown, BatchDelete, ColPrunable, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, ToBatch, ToStream,
 };
 use crate::catalog::TableId;
 use crate::optimizer::property::FunctionalDependencySet;
@@ -28,7 +28,8 @@ use crate::utils::Condition;
 
 /// [`LogicalDelete`] iterates on input relation and delete the data from specified table.
 ///
-/// It corresponds to the `DELETE` statements in SQL.
+/// It corresponds to the `DELETE` statements in SQL. With `RETURNING`, it outputs the deleted rows
+/// instead of the number of them.
 #[derive(Debug, Clone)]
 pub struct LogicalDelete {
     pub base: PlanBase,
@@ -36,6 +37,7 @@ pub struct LogicalDelete {
     source_id: TableId,        // TODO: use SourceId
     associated_mview_id: TableId,
     input: PlanRef,
+    returning: bool,
 }
 
 impl LogicalDelete {
@@ -45,10 +47,14 @@ impl LogicalDelete {
         table_source_name: String,
         source_id: TableId,
         table_id: TableId,
+        returning: bool,
     ) -> Self {
         let ctx = input.ctx();
-        // TODO: support `RETURNING`.
-        let schema = Schema::new(vec![Field::unnamed(DataType::Int64)]);
+        let schema = if returning {
+            input.schema().clone()
+        } else {
+            Schema::new(vec![Field::unnamed(DataType::Int64)])
+        };
         let fd_set = FunctionalDependencySet::new(schema.len());
         let base = PlanBase::new_logical(ctx, schema, vec![], fd_set);
         Self {
@@ -57,6 +63,7 @@ impl LogicalDelete {
             source_id,
             associated_mview_id: table_id,
             input,
+            returning,
         }
     }
 
@@ -66,12 +73,23 @@ impl LogicalDelete {
         table_source_name: String,
         source_id: TableId,
         table_id: TableId,
+        returning: bool,
     ) -> Result<Self> {
-        Ok(Self::new(input, table_source_name, source_id, table_id))
+        Ok(Self::new(
+            input,
+            table_source_name,
+            source_id,
+            table_id,
+            returning,
+        ))
     }
 
     pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
-        write!(f, "{} {{ table: {} }}", name, self.table_source_name)
+        write!(f, "{} {{ table: {}", name, self.table_source_name)?;
+        if self.returning {
+            write!(f, ", returning: true")?;
+        }
+        write!(f, " }}")
     }
 
     /// Get the logical delete's source id.
@@ -84,6 +102,11 @@ impl LogicalDelete {
     pub fn associated_mview_id(&self) -> TableId {
         self.associated_mview_id
     }
+
+    /// Whether the deleted rows are output, instead of the number of them.
+    pub fn has_returning(&self) -> bool {
+        self.returning
+    }
 }
 
 impl PlanTreeNodeUnary for LogicalDelete {
@@ -97,6 +120,7 @@ impl PlanTreeNodeUnary for LogicalDelete {
             self.table_source_name.clone(),
             self.source_id,
             self.associated_mview_id,
+            self.returning,
         )
     }
 }
@@ -110,10 +134,16 @@ impl fmt::Display for LogicalDelete {
 }
 
 impl ColPrunable for LogicalDelete {
-    fn prune_col(&self, _required_cols: &[usize]) -> PlanRef {
-        let required_cols: Vec<_> = (0..self.input.schema().len()).collect();
-        self.clone_with_input(self.input.prune_col(&required_cols))
-            .into()
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let input_required_cols: Vec<_> = (0..self.input.schema().len()).collect();
+        let plan: PlanRef = self
+            .clone_with_input(self.input.prune_col(&input_required_cols))
+            .into();
+        if self.returning {
+            LogicalProject::with_out_col_idx(plan, required_cols.iter().cloned()).into()
+        } else {
+            plan
+        }
     }
 }
 
@@ -20,7 +20,7 @@ use risingwave_common::error::Result;
 use risingwave_common::types::DataType;
 
 use super::{
-    gen_filter_and_pushdown, generic, BatchExpand, ColPrunable, PlanBase, PlanRef,
+    gen_filter_and_pushdown, generic, BatchExpand, ColPrunable, LogicalProject, PlanBase, PlanRef,
     PlanTreeNodeUnary, PredicatePushdown, StreamExpand, ToBatch, ToStream,
 };
 use crate::optimizer::property::FunctionalDependencySet;
@@ -157,8 +157,24 @@ impl fmt::Display for LogicalExpand {
 }
 
 impl ColPrunable for LogicalExpand {
-    fn prune_col(&self, _required_cols: &[usize]) -> PlanRef {
-        todo!("prune_col of LogicalExpand is not implemented yet.");
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        // Both the expanded columns and the original columns are from the input columns.
+        let input_len = self.input().schema().len();
+        let input_required_cols = required_cols
+            .iter()
+            .filter(|&&i| i < input_len * 2)
+            .map(|&i| i % input_len)
+            .sorted()
+            .dedup()
+            .collect_vec();
+        let input = self.input().prune_col(&input_required_cols);
+        let input_change = ColIndexMapping::with_remaining_columns(&input_required_cols, input_len);
+        let (expand, out_col_change) = self.rewrite_with_input(input, input_change);
+        LogicalProject::with_out_col_idx(
+            expand.into(),
+            required_cols.iter().map(|&i| out_col_change.map(i)),
+        )
+        .into()
     }
 }
 
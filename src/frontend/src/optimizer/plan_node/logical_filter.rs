@@ -16,15 +16,22 @@ use std::fmt;
 
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result};
+use risingwave_common::types::DataType;
 
 use super::generic::{self, GenericPlanNode};
 use super::{
     ColPrunable, CollectInputRef, LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary,
     PredicatePushdown, ToBatch, ToStream,
 };
-use crate::expr::{assert_input_ref, ExprImpl};
-use crate::optimizer::plan_node::{BatchFilter, StreamFilter};
+use crate::expr::{
+    assert_input_ref, try_derive_watermark, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall,
+    InputRef,
+};
+use crate::optimizer::plan_node::{
+    BatchFilter, StreamDynamicFilter, StreamFilter, StreamNow, StreamProject,
+};
+use crate::optimizer::property::{Distribution, Order, RequiredDist};
 use crate::utils::{ColIndexMapping, Condition, ConditionDisplay};
 
 /// `LogicalFilter` iterates over its input and returns elements for which `predicate` evaluates to
@@ -179,11 +186,118 @@ impl ToBatch for LogicalFilter {
     }
 }
 
+impl LogicalFilter {
+    /// Converts a comparison between an expression of the input and an expression of `now()`, e.g.
+    /// `ts > now() - INTERVAL '1 hour'`, into a dynamic filter whose right side is the current time
+    /// maintained by a [`StreamNow`].
+    fn to_stream_temporal_filter(input: PlanRef, cond: ExprImpl) -> Result<PlanRef> {
+        let Some((lhs, comparator, now_expr)) = cond.as_now_comparison_cond() else {
+            return Err(ErrorCode::NotImplemented(
+                format!("now() in the streaming filter {:?}", cond),
+                None.into(),
+            )
+            .into());
+        };
+        let ctx = input.ctx();
+        let input_len = input.schema().len();
+
+        // Evaluate the left side of the comparison as an extra column if it is not a column.
+        let (left, left_index) = match lhs {
+            ExprImpl::InputRef(input_ref) => (input, input_ref.index()),
+            lhs => {
+                let mut exprs = input
+                    .schema()
+                    .data_types()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, ty)| InputRef::new(i, ty).into())
+                    .collect_vec();
+                exprs.push(lhs);
+                let project: PlanRef = StreamProject::new(LogicalProject::new(input, exprs)).into();
+                (project, input_len)
+            }
+        };
+        let left_len = left.schema().len();
+
+        struct RewriteNow;
+        impl ExprRewriter for RewriteNow {
+            fn rewrite_function_call(&mut self, func_call: FunctionCall) -> ExprImpl {
+                if func_call.get_expr_type() == ExprType::Now {
+                    return InputRef::new(0, DataType::Timestampz).into();
+                }
+                let (func_type, inputs, ret) = func_call.decompose();
+                let inputs = inputs
+                    .into_iter()
+                    .map(|expr| self.rewrite_expr(expr))
+                    .collect();
+                FunctionCall::new_unchecked(func_type, inputs, ret).into()
+            }
+        }
+        let now_expr = RewriteNow.rewrite_expr(now_expr);
+        let right_type = now_expr.return_type();
+        // The rows failing a `>` or `>=` comparison with a non-decreasing time never pass again.
+        let is_monotonic = matches!(
+            comparator,
+            ExprType::GreaterThan | ExprType::GreaterThanOrEqual
+        ) && try_derive_watermark(&now_expr) == Some(0);
+
+        let now: PlanRef = StreamNow::new(ctx).into();
+        let right: PlanRef = StreamProject::new(LogicalProject::new(now, vec![now_expr])).into();
+        let right = RequiredDist::PhysicalDist(Distribution::Broadcast)
+            .enforce_if_not_satisfies(right, &Order::any())?;
+
+        let predicate = Condition::with_expr(
+            FunctionCall::new_unchecked(
+                comparator,
+                vec![
+                    InputRef::new(left_index, left.schema()[left_index].data_type()).into(),
+                    InputRef::new(left_len, right_type).into(),
+                ],
+                DataType::Boolean,
+            )
+            .into(),
+        );
+        let plan: PlanRef = if is_monotonic {
+            StreamDynamicFilter::new_monotonic(left_index, predicate, left, right).into()
+        } else {
+            StreamDynamicFilter::new(left_index, predicate, left, right).into()
+        };
+
+        if left_len == input_len {
+            Ok(plan)
+        } else {
+            Ok(StreamProject::new(LogicalProject::with_out_col_idx(plan, 0..input_len)).into())
+        }
+    }
+}
+
 impl ToStream for LogicalFilter {
     fn to_stream(&self) -> Result<PlanRef> {
         let new_input = self.input().to_stream()?;
-        let new_logical = self.clone_with_input(new_input);
-        Ok(StreamFilter::new(new_logical).into())
+        let (now_conds, other_conds): (Vec<_>, Vec<_>) = self
+            .predicate()
+            .conjunctions
+            .iter()
+            .cloned()
+            .partition(|expr| expr.has_now());
+        if now_conds.is_empty() {
+            let new_logical = self.clone_with_input(new_input);
+            return Ok(StreamFilter::new(new_logical).into());
+        }
+
+        // The conditions with `now()` are converted to temporal filters above the other conditions.
+        let mut plan = if other_conds.is_empty() {
+            new_input
+        } else {
+            let other_cond = Condition {
+                conjunctions: other_conds,
+            };
+            StreamFilter::new(LogicalFilter::new(new_input, other_cond)).into()
+        };
+        for cond in now_conds {
+            plan = Self::to_stream_temporal_filter(plan, cond)?;
+        }
+        Ok(plan)
     }
 
     fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
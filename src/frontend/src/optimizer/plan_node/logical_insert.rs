@@ -19,8 +19,8 @@ use risingwave_common::error::Result;
 use risingwave_common::types::DataType;
 
 use super::{
-    gen_filter_and_pushdown, BatchInsert, ColPrunable, PlanBase, PlanRef, PlanTreeNodeUnary,
-    PredicatePushdown, ToBatch, ToStream,
+    gen_filter_and_pushdown, BatchInsert, ColPrunable, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, ToBatch, ToStream,
 };
 use crate::catalog::TableId;
 use crate::optimizer::property::FunctionalDependencySet;
@@ -30,6 +30,8 @@ use crate::utils::Condition;
 ///
 /// It corresponds to the `INSERT` statements in SQL. Especially, for `INSERT ... VALUES`
 /// statements, the input relation would be [`super::LogicalValues`].
+///
+/// With `RETURNING`, it outputs the inserted rows instead of the number of them.
 #[derive(Debug, Clone)]
 pub struct LogicalInsert {
     pub base: PlanBase,
@@ -37,6 +39,7 @@ pub struct LogicalInsert {
     source_id: TableId,        // TODO: use SourceId
     associated_mview_id: TableId,
     input: PlanRef,
+    returning: bool,
 }
 
 impl LogicalInsert {
@@ -46,9 +49,14 @@ impl LogicalInsert {
         table_source_name: String,
         source_id: TableId,
         associated_mview_id: TableId,
+        returning: bool,
     ) -> Self {
         let ctx = input.ctx();
-        let schema = Schema::new(vec![Field::unnamed(DataType::Int64)]);
+        let schema = if returning {
+            input.schema().clone()
+        } else {
+            Schema::new(vec![Field::unnamed(DataType::Int64)])
+        };
         let functional_dependency = FunctionalDependencySet::new(schema.len());
         let base = PlanBase::new_logical(ctx, schema, vec![], functional_dependency);
         Self {
@@ -57,6 +65,7 @@ impl LogicalInsert {
             source_id,
             associated_mview_id,
             input,
+            returning,
         }
     }
 
@@ -66,12 +75,23 @@ impl LogicalInsert {
         table_source_name: String,
         source_id: TableId,
         table_id: TableId,
+        returning: bool,
     ) -> Result<Self> {
-        Ok(Self::new(input, table_source_name, source_id, table_id))
+        Ok(Self::new(
+            input,
+            table_source_name,
+            source_id,
+            table_id,
+            returning,
+        ))
     }
 
     pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
-        write!(f, "{} {{ table: {} }}", name, self.table_source_name)
+        write!(f, "{} {{ table: {}", name, self.table_source_name)?;
+        if self.returning {
+            write!(f, ", returning: true")?;
+        }
+        write!(f, " }}")
     }
 
     /// Get the logical insert's source id.
@@ -84,6 +104,11 @@ impl LogicalInsert {
     pub fn associated_mview_id(&self) -> TableId {
         self.associated_mview_id
     }
+
+    /// Whether the inserted rows are output, instead of the number of them.
+    pub fn has_returning(&self) -> bool {
+        self.returning
+    }
 }
 
 impl PlanTreeNodeUnary for LogicalInsert {
@@ -97,6 +122,7 @@ impl PlanTreeNodeUnary for LogicalInsert {
             self.table_source_name.clone(),
             self.source_id,
             self.associated_mview_id,
+            self.returning,
         )
     }
 }
@@ -110,10 +136,16 @@ impl fmt::Display for LogicalInsert {
 }
 
 impl ColPrunable for LogicalInsert {
-    fn prune_col(&self, _required_cols: &[usize]) -> PlanRef {
-        let required_cols: Vec<_> = (0..self.input.schema().len()).collect();
-        self.clone_with_input(self.input.prune_col(&required_cols))
-            .into()
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let input_required_cols: Vec<_> = (0..self.input.schema().len()).collect();
+        let plan: PlanRef = self
+            .clone_with_input(self.input.prune_col(&input_required_cols))
+            .into();
+        if self.returning {
+            LogicalProject::with_out_col_idx(plan, required_cols.iter().cloned()).into()
+        } else {
+            plan
+        }
     }
 }
 
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt;
+use std::{fmt, iter};
 
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
@@ -21,11 +21,13 @@ use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_pb::plan_common::JoinType;
 
 use super::{
-    generic, BatchProject, ColPrunable, CollectInputRef, LogicalProject, PlanBase, PlanNodeType,
-    PlanRef, PlanTreeNodeBinary, PredicatePushdown, StreamHashJoin, StreamProject, ToBatch,
-    ToStream,
+    generic, BatchProject, ColPrunable, CollectInputRef, IntervalJoinBounds, LogicalProject,
+    LogicalScan, PlanBase, PlanNodeType, PlanRef, PlanTreeNodeBinary, PredicatePushdown,
+    StreamHashJoin, StreamIntervalJoin, StreamLookupJoin, StreamProject, StreamTableScan,
+    StreamTemporalJoin, ToBatch, ToStream,
 };
 use crate::expr::{Expr, ExprImpl, ExprRewriter, ExprType, InputRef};
+use crate::optimizer::cardinality_estimator::CardinalityEstimator;
 use crate::optimizer::max_one_row_visitor::MaxOneRowVisitor;
 use crate::optimizer::plan_node::utils::IndicesDisplay;
 use crate::optimizer::plan_node::{
@@ -36,6 +38,13 @@ use crate::optimizer::plan_visitor::PlanVisitor;
 use crate::optimizer::property::{Distribution, FunctionalDependencySet, Order, RequiredDist};
 use crate::utils::{ColIndexMapping, Condition, ConditionDisplay};
 
+/// Lookup join is chosen by the statistics if the estimated rows of the left side, multiplied by
+/// this factor for the cost of random access, is still less than the rows of the right table.
+const LOOKUP_JOIN_COST_FACTOR: f64 = 10.0;
+
+/// The equal conditions of a join, in the same form as [`EqJoinPredicate::eq_keys`].
+type EqKeys = Vec<(InputRef, InputRef, bool)>;
+
 /// `LogicalJoin` combines two relations according to some condition.
 ///
 /// Each output row has fields from the left and right inputs. The set of output rows is a subset
@@ -108,11 +117,17 @@ impl LogicalJoin {
         let ctx = left.ctx();
         let schema = Self::derive_schema(left.schema(), right.schema(), join_type, &output_indices);
 
+        let right_pk: &[usize] =
+            if Self::is_right_pk_redundant(left.schema().len(), &right, join_type, &on) {
+                &[]
+            } else {
+                right.logical_pk()
+            };
         let pk_indices = Self::derive_pk(
             left.schema().len(),
             right.schema().len(),
             left.logical_pk(),
-            right.logical_pk(),
+            right_pk,
             join_type,
             &output_indices,
         );
@@ -318,6 +333,19 @@ impl LogicalJoin {
         Schema { fields }
     }
 
+    /// An inner join without equal conditions against a scalar, e.g. a dynamic filter, outputs
+    /// each left row at most once, so the pk of the right side is not needed in the join's pk.
+    fn is_right_pk_redundant(
+        left_len: usize,
+        right: &PlanRef,
+        join_type: JoinType,
+        on: &Condition,
+    ) -> bool {
+        join_type == JoinType::Inner
+            && !EqJoinPredicate::create(left_len, right.schema().len(), on.clone()).has_eq()
+            && MaxOneRowVisitor.visit(right.clone())
+    }
+
     pub(super) fn derive_pk(
         left_len: usize,
         right_len: usize,
@@ -435,6 +463,45 @@ impl LogicalJoin {
         )
     }
 
+    /// Swaps the left and right inputs, with the join type, the condition and the output indices
+    /// adjusted accordingly, so that the output stays the same. Returns `None` for semi and anti
+    /// joins, whose internal columns only come from one side.
+    pub fn swap_inputs(&self) -> Option<Self> {
+        let join_type = match self.join_type() {
+            JoinType::Inner => JoinType::Inner,
+            JoinType::LeftOuter => JoinType::RightOuter,
+            JoinType::RightOuter => JoinType::LeftOuter,
+            JoinType::FullOuter => JoinType::FullOuter,
+            _ => return None,
+        };
+        let left_len = self.left().schema().len();
+        let right_len = self.right().schema().len();
+        let mut mapping = ColIndexMapping::new(
+            (0..left_len + right_len)
+                .map(|i| {
+                    Some(if i < left_len {
+                        i + right_len
+                    } else {
+                        i - left_len
+                    })
+                })
+                .collect(),
+        );
+        let output_indices = self
+            .output_indices()
+            .iter()
+            .map(|i| mapping.map(*i))
+            .collect();
+        let on = self.on().clone().rewrite_expr(&mut mapping);
+        Some(Self::with_output_indices(
+            self.right(),
+            self.left(),
+            join_type,
+            on,
+            output_indices,
+        ))
+    }
+
     pub fn is_left_join(&self) -> bool {
         matches!(self.join_type(), JoinType::LeftSemi | JoinType::LeftAnti)
     }
@@ -443,6 +510,14 @@ impl LogicalJoin {
         matches!(self.join_type(), JoinType::RightSemi | JoinType::RightAnti)
     }
 
+    /// Whether the right side is a table scan with `FOR SYSTEM_TIME AS OF PROCTIME()`, which
+    /// makes the join a process-time temporal join.
+    pub fn should_be_temporal_join(&self) -> bool {
+        self.right()
+            .as_logical_scan()
+            .map_or(false, |scan| scan.for_system_time_as_of_proctime())
+    }
+
     /// Try to split and pushdown `predicate` into a join's left/right child or the on clause.
     /// Returns the pushed predicates. The pushed part will be removed from the original predicate.
     ///
@@ -955,6 +1030,233 @@ impl LogicalJoin {
         }
     }
 
+    fn to_stream_temporal_join(&self, predicate: EqJoinPredicate) -> Result<PlanRef> {
+        let not_supported = |msg: &str| {
+            RwError::from(ErrorCode::NotImplemented(
+                format!("temporal join {}", msg),
+                None.into(),
+            ))
+        };
+
+        if !matches!(self.join_type(), JoinType::Inner | JoinType::LeftOuter) {
+            return Err(not_supported(&format!(
+                "with join type {:?}",
+                self.join_type()
+            )));
+        }
+
+        let left_len = self.left().schema().len();
+        let right = self.right();
+        let scan = right.as_logical_scan().unwrap();
+        let Some((lookup_keys, other_eq_keys, left_dist_key)) =
+            Self::split_lookup_keys(scan, &predicate, left_len)
+        else {
+            return Err(not_supported(
+                "without equal conditions on a prefix of the primary key of the right table \
+                 covering its distribution key",
+            ));
+        };
+
+        let left = self
+            .left()
+            .to_stream_with_dist_required(&Self::lookup_left_dist(&left_dist_key))?;
+        if !left.append_only() {
+            return Err(not_supported("with a non-append-only left input"));
+        }
+
+        let (logical_join, predicate) =
+            self.to_stream_lookup_inputs(left, scan, &predicate, lookup_keys, other_eq_keys);
+        Ok(StreamTemporalJoin::new(logical_join, predicate).into())
+    }
+
+    /// Converts a join whose right side is a table to a [`StreamLookupJoin`] if the left input is
+    /// append-only, and the equal conditions are on a prefix of the order key of the table or one
+    /// of its covering indexes, which covers the distribution key. The rows of the table are
+    /// then looked up in its storage on demand, instead of being kept in the join state.
+    ///
+    /// Only enabled by the session config `RW_STREAMING_ENABLE_LOOKUP_JOIN`.
+    fn to_stream_lookup_join(&self, predicate: &EqJoinPredicate) -> Result<Option<PlanRef>> {
+        let config = self.base.ctx.inner().session_ctx.config();
+        if !config.get_streaming_enable_lookup_join() {
+            return Ok(None);
+        }
+        if !matches!(self.join_type(), JoinType::Inner | JoinType::LeftOuter) {
+            return Ok(None);
+        }
+        let right = self.right();
+        let Some(scan) = right.as_logical_scan() else {
+            return Ok(None);
+        };
+        if scan.is_sys_table() {
+            return Ok(None);
+        }
+
+        // The primary table is preferred, and then the indexes covering all the required columns.
+        let left_len = self.left().schema().len();
+        let index_scans = scan.indexes().iter().filter_map(|index| {
+            let p2s_mapping = index.primary_to_secondary_mapping();
+            scan.required_col_idx()
+                .iter()
+                .all(|x| p2s_mapping.contains_key(x))
+                .then(|| {
+                    scan.to_index_scan(
+                        &index.name,
+                        index.index_table.table_desc().into(),
+                        p2s_mapping,
+                    )
+                })
+        });
+        let Some((scan, (lookup_keys, other_eq_keys, left_dist_key))) = iter::once(scan.clone())
+            .chain(index_scans)
+            .find_map(|scan| {
+                let keys = Self::split_lookup_keys(&scan, predicate, left_len)?;
+                Some((scan, keys))
+            })
+        else {
+            return Ok(None);
+        };
+
+        let left = self
+            .left()
+            .to_stream_with_dist_required(&Self::lookup_left_dist(&left_dist_key))?;
+        if !left.append_only() {
+            return Ok(None);
+        }
+
+        let (logical_join, predicate) =
+            self.to_stream_lookup_inputs(left, &scan, predicate, lookup_keys, other_eq_keys);
+        Ok(Some(StreamLookupJoin::new(logical_join, predicate).into()))
+    }
+
+    /// Splits the equal conditions into the ones on a prefix of the order key of the table scanned
+    /// on the right side, in the order of the order key, and the rest. Also returns the left
+    /// columns matching the distribution key of the table, as the lookup is done locally and the
+    /// left side must be shuffled in the same way as the table.
+    ///
+    /// Returns `None` if the prefix is empty or doesn't cover the distribution key.
+    fn split_lookup_keys(
+        scan: &LogicalScan,
+        predicate: &EqJoinPredicate,
+        left_len: usize,
+    ) -> Option<(EqKeys, EqKeys, Vec<usize>)> {
+        let table_desc = scan.table_desc();
+        let output_column_ids = scan.output_column_ids();
+
+        let mut eq_keys = predicate.eq_keys().to_vec();
+        let mut lookup_keys = vec![];
+        for order_col_id in table_desc.order_column_ids() {
+            match eq_keys
+                .iter()
+                .position(|(_, r, _)| output_column_ids[r.index() - left_len] == order_col_id)
+            {
+                Some(i) => lookup_keys.push(eq_keys.remove(i)),
+                None => break,
+            }
+        }
+        if lookup_keys.is_empty() {
+            return None;
+        }
+
+        let left_dist_key = table_desc
+            .distribution_key
+            .iter()
+            .map(|&i| {
+                let column_id = table_desc.columns[i].column_id;
+                lookup_keys
+                    .iter()
+                    .find(|(_, r, _)| output_column_ids[r.index() - left_len] == column_id)
+                    .map(|(l, _, _)| l.index())
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some((lookup_keys, eq_keys, left_dist_key))
+    }
+
+    fn lookup_left_dist(left_dist_key: &[usize]) -> RequiredDist {
+        if left_dist_key.is_empty() {
+            RequiredDist::single()
+        } else {
+            RequiredDist::hash_shard(left_dist_key)
+        }
+    }
+
+    /// Builds the join of a lookup on the table scanned by `scan`, whose right input is the table
+    /// without snapshot. The equal conditions not in `lookup_keys` and the predicate of the scan
+    /// are evaluated as non-equal conditions, as the lookup reads the table directly.
+    fn to_stream_lookup_inputs(
+        &self,
+        left: PlanRef,
+        scan: &LogicalScan,
+        predicate: &EqJoinPredicate,
+        lookup_keys: EqKeys,
+        other_eq_keys: EqKeys,
+    ) -> (LogicalJoin, EqJoinPredicate) {
+        let left_len = self.left().schema().len();
+        let other_eq_cond =
+            EqJoinPredicate::new(Condition::true_cond(), other_eq_keys, left_len).eq_cond();
+
+        let (scan, scan_predicate, _) = scan.predicate_pull_up();
+        let scan_predicate = scan_predicate.rewrite_expr(&mut ColIndexMapping::with_shift_offset(
+            scan.schema().len(),
+            left_len as isize,
+        ));
+        let right = StreamTableScan::new_upstream_only(scan).into();
+
+        let other_cond = predicate
+            .other_cond()
+            .clone()
+            .and(other_eq_cond)
+            .and(scan_predicate);
+        let predicate = EqJoinPredicate::new(other_cond, lookup_keys, left_len);
+        let logical_join = LogicalJoin::with_output_indices(
+            left,
+            right,
+            self.join_type(),
+            predicate.all_cond(),
+            self.output_indices().clone(),
+        );
+        (logical_join, predicate)
+    }
+
+    /// Converts an inner join whose condition bounds the time columns of both sides within an
+    /// interval to a [`StreamIntervalJoin`], if both time columns have watermarks so that its state
+    /// can be cleaned. Otherwise it would be an unbounded hash join or not supported at all.
+    fn to_stream_interval_join(&self, predicate: &EqJoinPredicate) -> Result<Option<PlanRef>> {
+        if self.join_type() != JoinType::Inner {
+            return Ok(None);
+        }
+        let left_len = self.left().schema().len();
+        let mut concat_schema = self.left().schema().fields.clone();
+        concat_schema.extend(self.right().schema().fields.clone());
+        let Some(bounds) = IntervalJoinBounds::extract(
+            predicate.other_cond(),
+            left_len,
+            &Schema::new(concat_schema),
+        ) else {
+            return Ok(None);
+        };
+
+        let (left_dist, right_dist) = if predicate.has_eq() {
+            (
+                RequiredDist::hash_shard(&predicate.left_eq_indexes()),
+                RequiredDist::hash_shard(&predicate.right_eq_indexes()),
+            )
+        } else {
+            (RequiredDist::single(), RequiredDist::single())
+        };
+        let left = self.left().to_stream_with_dist_required(&left_dist)?;
+        let right = self.right().to_stream_with_dist_required(&right_dist)?;
+        if !left.watermark_columns().contains(bounds.left_time_col)
+            || !right.watermark_columns().contains(bounds.right_time_col)
+        {
+            return Ok(None);
+        }
+
+        let logical_join = self.clone_with_left_right(left, right);
+        Ok(Some(
+            StreamIntervalJoin::new(logical_join, predicate.clone(), bounds).into(),
+        ))
+    }
+
     fn to_stream_dynamic_filter(&self, predicate: EqJoinPredicate) -> Result<Option<PlanRef>> {
         assert!(!predicate.has_eq());
         // If there is exactly one predicate, it is a comparison (<, <=, >, >=), and the
@@ -1108,14 +1410,33 @@ impl ToBatch for LogicalJoin {
             self.on().clone(),
         );
 
+        // The estimator must be dropped before converting the inputs, which may estimate again.
+        let (left_rows, right_rows) = {
+            let estimator = CardinalityEstimator::new(&self.base.ctx);
+            (
+                estimator.row_count(&self.left()),
+                estimator.row_count(&self.right()),
+            )
+        };
+
         let left = self.left().to_batch()?;
         let right = self.right().to_batch()?;
-        let logical_join = self.clone_with_left_right(left, right);
+        let logical_join = self.clone_with_left_right(left.clone(), right.clone());
 
         let config = self.base.ctx.inner().session_ctx.config();
 
         if predicate.has_eq() {
-            if config.get_batch_enable_lookup_join() {
+            // Lookup join is forced by the session config. Otherwise, it's chosen only if the
+            // statistics show that looking up the rows of the outer side is cheaper than scanning
+            // the whole inner table.
+            let cheaper_to_look_up = |outer_rows: Option<f64>, inner_rows: Option<f64>| {
+                matches!(
+                    (outer_rows, inner_rows),
+                    (Some(outer_rows), Some(inner_rows))
+                        if outer_rows * LOOKUP_JOIN_COST_FACTOR < inner_rows
+                )
+            };
+            if config.get_batch_enable_lookup_join() || cheaper_to_look_up(left_rows, right_rows) {
                 if let Some(lookup_join) =
                     self.to_batch_lookup_join(predicate.clone(), logical_join.clone())
                 {
@@ -1123,6 +1444,30 @@ impl ToBatch for LogicalJoin {
                 }
             }
 
+            let swapped = self.swap_inputs().map(|swapped| {
+                let predicate = EqJoinPredicate::create(
+                    swapped.left().schema().len(),
+                    swapped.right().schema().len(),
+                    swapped.on().clone(),
+                );
+                let logical_join = swapped.clone_with_left_right(right, left);
+                (swapped, predicate, logical_join)
+            });
+            if let Some((swapped, predicate, logical_join)) = swapped {
+                if cheaper_to_look_up(right_rows, left_rows)
+                    && let Some(lookup_join) =
+                        swapped.to_batch_lookup_join(predicate.clone(), logical_join.clone())
+                {
+                    return Ok(lookup_join);
+                }
+                // The hash table is built on the right side, so put the smaller side there.
+                if let (Some(left_rows), Some(right_rows)) = (left_rows, right_rows)
+                    && right_rows > left_rows
+                {
+                    return swapped.to_batch_hash_join(predicate, logical_join);
+                }
+            }
+
             self.to_batch_hash_join(predicate, logical_join)
         } else {
             // Convert to Nested-loop Join for non-equal joins
@@ -1133,13 +1478,37 @@ impl ToBatch for LogicalJoin {
 
 impl ToStream for LogicalJoin {
     fn to_stream(&self) -> Result<PlanRef> {
+        if self.on().conjunctions.iter().any(|expr| expr.has_now()) {
+            return Err(RwError::from(ErrorCode::NotImplemented(
+                "now() in the join condition of a streaming query".to_string(),
+                None.into(),
+            )));
+        }
+
         let predicate = EqJoinPredicate::create(
             self.left().schema().len(),
             self.right().schema().len(),
             self.on().clone(),
         );
 
-        if predicate.has_eq() {
+        if self.should_be_temporal_join() {
+            self.to_stream_temporal_join(predicate)
+        } else if let Some(interval_join) = self.to_stream_interval_join(&predicate)? {
+            Ok(interval_join)
+        } else if predicate.has_eq()
+            && let Some(lookup_join) = self.to_stream_lookup_join(&predicate)?
+        {
+            Ok(lookup_join)
+        } else if predicate.has_eq()
+            && let Some(swapped) = self.swap_inputs()
+            && let Some(lookup_join) = swapped.to_stream_lookup_join(&EqJoinPredicate::create(
+                swapped.left().schema().len(),
+                swapped.right().schema().len(),
+                swapped.on().clone(),
+            ))?
+        {
+            Ok(lookup_join)
+        } else if predicate.has_eq() {
             self.to_stream_hash_join(predicate)
         } else if let Some(dynamic_filter) = self.to_stream_dynamic_filter(predicate)? {
             Ok(dynamic_filter)
@@ -1177,8 +1546,13 @@ impl ToStream for LogicalJoin {
             .cloned()
             .filter(|i| l2i.try_map(*i).is_none());
 
-        let right_to_add = right
-            .logical_pk()
+        let right_pk: &[usize] =
+            if Self::is_right_pk_redundant(left_len, &right, join.join_type(), join.on()) {
+                &[]
+            } else {
+                right.logical_pk()
+            };
+        let right_to_add = right_pk
             .iter()
             .cloned()
             .filter(|i| r2i.try_map(*i).is_none())
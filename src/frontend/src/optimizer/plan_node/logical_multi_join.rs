@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 
 use itertools::Itertools;
@@ -24,6 +25,7 @@ use super::{
     PlanTreeNodeBinary, PlanTreeNodeUnary, PredicatePushdown, ToBatch, ToStream,
 };
 use crate::expr::{ExprImpl, ExprRewriter};
+use crate::optimizer::cardinality_estimator::CardinalityEstimator;
 use crate::optimizer::plan_node::PlanTreeNode;
 use crate::optimizer::property::FunctionalDependencySet;
 use crate::utils::{ColIndexMapping, Condition, ConditionDisplay, ConnectedComponentLabeller};
@@ -45,7 +47,6 @@ pub struct LogicalMultiJoin {
     // pk_derive soon.
     /// the mapping output_col_idx -> (input_idx, input_col_idx), **"output_col_idx" is internal,
     /// not consider output_indices**
-    #[expect(dead_code)]
     inner_o2i_mapping: Vec<(usize, usize)>,
     inner_i2o_mappings: Vec<ColIndexMapping>,
 }
@@ -122,7 +123,8 @@ impl LogicalMultiJoinBuilder {
 
     fn with_join(plan: PlanRef) -> LogicalMultiJoinBuilder {
         let join: &LogicalJoin = plan.as_logical_join().unwrap();
-        if join.join_type() != JoinType::Inner {
+        // A temporal join must keep the table scan as its right side, so it is not reordered.
+        if join.join_type() != JoinType::Inner || join.should_be_temporal_join() {
             return Self::with_input(plan);
         }
         let left = join.left();
@@ -332,22 +334,18 @@ impl LogicalMultiJoin {
     pub fn as_reordered_left_deep_join(&self, join_ordering: &[usize]) -> PlanRef {
         assert_eq!(join_ordering.len(), self.inputs.len());
         assert!(!join_ordering.is_empty());
+        self.as_reordered_join_tree(&JoinTree::left_deep(join_ordering))
+    }
 
-        let base_plan = self.inputs[join_ordering[0]].clone();
+    /// Builds the joins of the inputs in the shape of the given join tree, which must contain
+    /// every input exactly once.
+    pub fn as_reordered_join_tree(&self, join_tree: &JoinTree) -> PlanRef {
+        let join_ordering = join_tree.leaves();
+        assert_eq!(join_ordering.len(), self.inputs.len());
 
-        // Express as a cross join, we will rely on filter pushdown to push all of the join
+        // Express as cross joins, we will rely on filter pushdown to push all of the join
         // conditions to convert into inner joins.
-        let mut output = join_ordering[1..]
-            .iter()
-            .fold(base_plan, |join_chain, &index| {
-                LogicalJoin::new(
-                    join_chain,
-                    self.inputs[index].clone(),
-                    JoinType::Inner,
-                    Condition::true_cond(),
-                )
-                .into()
-            });
+        let mut output = join_tree.build(&|index| self.inputs[index].clone());
 
         let total_col_num = self.inner2output.source_size();
         let reorder_mapping = {
@@ -397,7 +395,15 @@ impl LogicalMultiJoin {
     ///        b. a projection which reorders the output column ordering to agree with the
     ///           original ordering of the joins.
     ///   The filter will then be pushed down by another filter pushdown pass.
+    ///
+    /// If all the inputs have been analyzed, the statistics are used to order the joins instead of
+    /// 2., see [`Self::cost_based_ordering`]. For batch queries, [`Self::dp_ordering`] is preferred
+    /// when it's applicable.
     pub(crate) fn heuristic_ordering(&self) -> Result<Vec<usize>> {
+        if let Some(join_ordering) = self.cost_based_ordering() {
+            return Ok(join_ordering);
+        }
+
         let mut labeller = ConnectedComponentLabeller::new(self.inputs.len());
 
         let (eq_join_conditions, _) = self.on.clone().split_by_input_col_nums(
@@ -478,11 +484,354 @@ impl LogicalMultiJoin {
         Ok(join_ordering)
     }
 
+    /// Orders the inputs of each connected component greedily by the estimated cardinality. The
+    /// left-deep join starts with the pair of inputs whose join result is the smallest, and then
+    /// repeatedly joins the connected input that keeps the intermediate result the smallest.
+    ///
+    /// Returns `None` if the cardinality of any input is unknown, i.e., not all of the tables are
+    /// analyzed.
+    fn cost_based_ordering(&self) -> Option<Vec<usize>> {
+        let estimator = CardinalityEstimator::new(&self.base.ctx);
+        let rows = self
+            .inputs
+            .iter()
+            .map(|input| estimator.row_count(input))
+            .collect::<Option<Vec<_>>>()?;
+
+        let selectivities = self.eq_join_selectivities(&estimator);
+        let selectivity = |a: usize, b: usize| selectivities.get(&(a.min(b), a.max(b))).copied();
+
+        let mut labeller = ConnectedComponentLabeller::new(self.inputs.len());
+        for &(i, j) in selectivities.keys() {
+            labeller.add_edge(i, j);
+        }
+        let mut edge_sets = labeller.into_edge_sets();
+        edge_sets.sort_by_key(|a| std::cmp::Reverse(a.len()));
+
+        let mut join_ordering = vec![];
+        for component in edge_sets {
+            // Put the larger input on the left, so that the hash table is built on the smaller one.
+            let Some((mut result_rows, first, second)) = component
+                .iter()
+                .map(|&(a, b)| {
+                    let (left, right) = if rows[a] >= rows[b] { (a, b) } else { (b, a) };
+                    (rows[a] * rows[b] * selectivity(a, b).unwrap(), left, right)
+                })
+                .min_by(|x, y| x.0.total_cmp(&y.0).then((x.1, x.2).cmp(&(y.1, y.2))))
+            else {
+                break;
+            };
+            let mut joined = vec![first, second];
+
+            let vertices: BTreeSet<usize> = component.iter().flat_map(|&(a, b)| [a, b]).collect();
+            while let Some((next_rows, next)) = vertices
+                .iter()
+                .filter(|&&v| !joined.contains(&v))
+                .filter_map(|&v| {
+                    let selectivity = joined
+                        .iter()
+                        .filter_map(|&u| selectivity(u, v))
+                        .reduce(|x, y| x * y)?;
+                    Some((result_rows * rows[v] * selectivity, v))
+                })
+                .min_by(|x, y| x.0.total_cmp(&y.0).then(x.1.cmp(&y.1)))
+            {
+                result_rows = next_rows;
+                joined.push(next);
+            }
+            join_ordering.extend(joined);
+        }
+
+        for i in 0..self.inputs.len() {
+            if !join_ordering.contains(&i) {
+                join_ordering.push(i);
+            }
+        }
+        Some(join_ordering)
+    }
+
+    /// Finds the optimal join tree by dynamic programming over the connected subsets of the inputs,
+    /// which minimizes the total estimated rows of the intermediate join results. Cross joins are
+    /// only introduced between the connected components of the join graph, after they have been
+    /// joined respectively. If `bushy` is false, only left-deep join trees are enumerated.
+    ///
+    /// Returns `None` if there are more than `max_relations` (and at most [`MAX_DP_RELATIONS`])
+    /// inputs, or the cardinality of any input is unknown.
+    pub(crate) fn dp_ordering(&self, bushy: bool, max_relations: usize) -> Option<JoinTree> {
+        if self.inputs.len() > max_relations.min(MAX_DP_RELATIONS) {
+            return None;
+        }
+        let estimator = CardinalityEstimator::new(&self.base.ctx);
+        let rows = self
+            .inputs
+            .iter()
+            .map(|input| estimator.row_count(input))
+            .collect::<Option<Vec<_>>>()?;
+        let selectivities = self.eq_join_selectivities(&estimator);
+        drop(estimator);
+
+        let mut labeller = ConnectedComponentLabeller::new(self.inputs.len());
+        for &(i, j) in selectivities.keys() {
+            labeller.add_edge(i, j);
+        }
+        let mut edge_sets = labeller.into_edge_sets();
+        edge_sets.sort_by_key(|a| std::cmp::Reverse(a.len()));
+
+        let mut components = edge_sets
+            .into_iter()
+            .filter(|component| !component.is_empty())
+            .map(|component| {
+                let vertices = component
+                    .iter()
+                    .flat_map(|&(a, b)| [a, b])
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect_vec();
+                JoinEnumerator::new(&vertices, &rows, &selectivities).best_join_tree(bushy)
+            })
+            .collect_vec();
+        for i in 0..self.inputs.len() {
+            if !components.iter().any(|tree| tree.contains(i)) {
+                components.push(JoinTree::Leaf(i));
+            }
+        }
+        components
+            .into_iter()
+            .reduce(|left, right| JoinTree::Join(Box::new(left), Box::new(right)))
+    }
+
+    /// Estimates the selectivity of the eq join conditions between each pair of inputs, keyed by
+    /// the input indices in ascending order.
+    fn eq_join_selectivities(
+        &self,
+        estimator: &CardinalityEstimator,
+    ) -> HashMap<(usize, usize), f64> {
+        let (eq_join_conditions, _) = self
+            .on
+            .clone()
+            .split_by_input_col_nums(&self.input_col_nums(), true);
+        eq_join_conditions
+            .into_iter()
+            .map(|((i, j), cond)| {
+                // Rewrite the condition to refer to the columns of the two inputs only.
+                let left_len = self.inputs[i].schema().len();
+                let mut mapping = ColIndexMapping::new(
+                    self.inner_o2i_mapping
+                        .iter()
+                        .map(|&(input_idx, col_idx)| {
+                            (input_idx == i)
+                                .then_some(col_idx)
+                                .or_else(|| (input_idx == j).then_some(left_len + col_idx))
+                        })
+                        .collect(),
+                );
+                let cond = cond.rewrite_expr(&mut mapping);
+                let selectivity =
+                    estimator.join_selectivity(&self.inputs[i], &self.inputs[j], &cond);
+                ((i, j), selectivity)
+            })
+            .collect()
+    }
+
     pub(crate) fn input_col_nums(&self) -> Vec<usize> {
         self.inputs.iter().map(|i| i.schema().len()).collect()
     }
 }
 
+/// The maximum number of inputs of a multi join that can be reordered by dynamic programming.
+pub const MAX_DP_RELATIONS: usize = 16;
+
+/// A tree of inner joins, whose leaves are the indices of the inputs of a [`LogicalMultiJoin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JoinTree {
+    Leaf(usize),
+    Join(Box<JoinTree>, Box<JoinTree>),
+}
+
+impl JoinTree {
+    /// Builds a left-deep join tree, which joins the inputs one by one in the given order.
+    pub fn left_deep(join_ordering: &[usize]) -> Self {
+        let mut leaves = join_ordering.iter().map(|&index| JoinTree::Leaf(index));
+        let first = leaves.next().expect("join ordering should not be empty");
+        leaves.fold(first, |left, right| {
+            JoinTree::Join(Box::new(left), Box::new(right))
+        })
+    }
+
+    /// Returns the inputs in the order of their columns in the output of the join tree.
+    pub fn leaves(&self) -> Vec<usize> {
+        let mut leaves = vec![];
+        self.collect_leaves(&mut leaves);
+        leaves
+    }
+
+    fn collect_leaves(&self, leaves: &mut Vec<usize>) {
+        match self {
+            JoinTree::Leaf(index) => leaves.push(*index),
+            JoinTree::Join(left, right) => {
+                left.collect_leaves(leaves);
+                right.collect_leaves(leaves);
+            }
+        }
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        match self {
+            JoinTree::Leaf(leaf) => *leaf == index,
+            JoinTree::Join(left, right) => left.contains(index) || right.contains(index),
+        }
+    }
+
+    /// Builds the plan of the join tree as cross joins of the inputs.
+    fn build(&self, input: &impl Fn(usize) -> PlanRef) -> PlanRef {
+        match self {
+            JoinTree::Leaf(index) => input(*index),
+            JoinTree::Join(left, right) => LogicalJoin::new(
+                left.build(input),
+                right.build(input),
+                JoinType::Inner,
+                Condition::true_cond(),
+            )
+            .into(),
+        }
+    }
+}
+
+/// Enumerates the join trees of a connected component of the join graph by dynamic programming.
+/// A subset of the vertices is represented as a bitmap, where the `k`-th bit stands for
+/// `vertices[k]`.
+struct JoinEnumerator<'a> {
+    vertices: &'a [usize],
+    rows: &'a [f64],
+    selectivities: &'a HashMap<(usize, usize), f64>,
+    /// The bitmap of the neighbours of each vertex.
+    neighbours: Vec<u32>,
+}
+
+impl<'a> JoinEnumerator<'a> {
+    fn new(
+        vertices: &'a [usize],
+        rows: &'a [f64],
+        selectivities: &'a HashMap<(usize, usize), f64>,
+    ) -> Self {
+        assert!(vertices.len() <= MAX_DP_RELATIONS);
+        let neighbours = vertices
+            .iter()
+            .map(|&u| {
+                vertices
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &v)| selectivities.contains_key(&(u.min(v), u.max(v))))
+                    .fold(0, |bitmap, (k, _)| bitmap | (1 << k))
+            })
+            .collect();
+        Self {
+            vertices,
+            rows,
+            selectivities,
+            neighbours,
+        }
+    }
+
+    fn members(&self, set: u32) -> impl Iterator<Item = usize> + '_ {
+        (0..self.vertices.len()).filter(move |k| set & (1 << k) != 0)
+    }
+
+    /// Estimates the rows of joining all the vertices in the set.
+    fn rows(&self, set: u32) -> f64 {
+        let members = self.members(set).map(|k| self.vertices[k]).collect_vec();
+        let rows: f64 = members.iter().map(|&v| self.rows[v]).product();
+        members
+            .iter()
+            .tuple_combinations()
+            .filter_map(|(&u, &v)| self.selectivities.get(&(u.min(v), u.max(v))))
+            .fold(rows, |rows, selectivity| rows * selectivity)
+    }
+
+    /// Whether there is any eq join condition between the two sets.
+    fn connected(&self, left: u32, right: u32) -> bool {
+        self.members(left).any(|k| self.neighbours[k] & right != 0)
+    }
+
+    /// Returns the join tree of all the vertices with the minimum cost, which is the total rows of
+    /// the intermediate join results. If `bushy` is false, the right side of each join must be a
+    /// single vertex.
+    fn best_join_tree(&self, bushy: bool) -> JoinTree {
+        let n = self.vertices.len();
+        let full: u32 = (1 << n) - 1;
+
+        // The cost, the rows and the best join tree of each connected subset.
+        let mut best: Vec<Option<(f64, f64, JoinTree)>> = vec![None; 1 << n];
+        for k in 0..n {
+            let vertex = self.vertices[k];
+            best[1 << k] = Some((0.0, self.rows[vertex], JoinTree::Leaf(vertex)));
+        }
+
+        let mut sets = (1..=full).filter(|set| set.count_ones() > 1).collect_vec();
+        sets.sort_by_key(|set| set.count_ones());
+        for set in sets {
+            let rows = self.rows(set);
+            let splits: Vec<(u32, u32)> = if bushy {
+                // Each pair of complementary subsets is visited once, with the lowest vertex in
+                // the left one.
+                let lowest = set & set.wrapping_neg();
+                let mut splits = vec![];
+                let mut left = (set - 1) & set;
+                while left != 0 {
+                    if left & lowest != 0 {
+                        splits.push((left, set ^ left));
+                    }
+                    left = (left - 1) & set;
+                }
+                splits
+            } else {
+                self.members(set)
+                    .map(|k| (set ^ (1 << k), 1 << k))
+                    .collect()
+            };
+
+            let mut best_split: Option<(f64, u32, u32)> = None;
+            for (left, right) in splits {
+                let (Some((left_cost, ..)), Some((right_cost, ..))) =
+                    (&best[left as usize], &best[right as usize])
+                else {
+                    continue;
+                };
+                if !self.connected(left, right) {
+                    continue;
+                }
+                let cost = rows + left_cost + right_cost;
+                if best_split.map_or(true, |(best_cost, ..)| cost < best_cost) {
+                    best_split = Some((cost, left, right));
+                }
+            }
+
+            if let Some((cost, left, right)) = best_split {
+                let (_, left_rows, left_tree) = best[left as usize].clone().unwrap();
+                let (_, right_rows, right_tree) = best[right as usize].clone().unwrap();
+                // Put the larger side on the left, so that the hash table is built on the smaller
+                // one. A left-deep join tree only allows swapping the first join.
+                let swap = (bushy || left.count_ones() == 1) && right_rows > left_rows;
+                let (left_tree, right_tree) = if swap {
+                    (right_tree, left_tree)
+                } else {
+                    (left_tree, right_tree)
+                };
+                best[set as usize] = Some((
+                    cost,
+                    rows,
+                    JoinTree::Join(Box::new(left_tree), Box::new(right_tree)),
+                ));
+            }
+        }
+
+        let (_, _, join_tree) = best[full as usize]
+            .take()
+            .expect("the vertices should be connected");
+        join_tree
+    }
+}
+
 impl ToStream for LogicalMultiJoin {
     fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
         panic!(
@@ -651,4 +1000,50 @@ mod test {
             .collect();
         assert_eq!(expected_fd_set, fd_set);
     }
+
+    fn is_left_deep(join_tree: &JoinTree) -> bool {
+        match join_tree {
+            JoinTree::Leaf(_) => true,
+            JoinTree::Join(left, right) => {
+                matches!(**right, JoinTree::Leaf(_)) && is_left_deep(left)
+            }
+        }
+    }
+
+    #[test]
+    fn test_dp_join_reorder_star_schema() {
+        // A fact table with 1000000 rows joins 6 dimension tables on their primary keys, where each
+        // dimension table has 100 rows in total and 10, 20, ..., 60 rows left after filtering.
+        let rows = [1e6, 40.0, 10.0, 60.0, 20.0, 50.0, 30.0];
+        let selectivities = (1..rows.len()).map(|dim| ((0, dim), 0.01)).collect();
+        let vertices = (0..rows.len()).collect_vec();
+        let join_tree = JoinEnumerator::new(&vertices, &rows, &selectivities).best_join_tree(false);
+        // The most selective dimension tables are joined first.
+        assert_eq!(join_tree.leaves(), vec![0, 2, 4, 6, 1, 5, 3]);
+        assert!(is_left_deep(&join_tree));
+    }
+
+    #[test]
+    fn test_dp_join_reorder_bushy() {
+        // Join graph: A - B - C - D, where A ⋈ B and C ⋈ D are very selective.
+        let rows = [1000.0, 1000.0, 1000.0, 1000.0];
+        let selectivities = [((0, 1), 1e-5), ((1, 2), 0.1), ((2, 3), 1e-5)]
+            .into_iter()
+            .collect();
+        let vertices = vec![0, 1, 2, 3];
+        let enumerator = JoinEnumerator::new(&vertices, &rows, &selectivities);
+
+        let left_deep = enumerator.best_join_tree(false);
+        assert!(is_left_deep(&left_deep));
+
+        // (A ⋈ B) ⋈ (C ⋈ D) avoids the large intermediate result of joining B and C.
+        let bushy = enumerator.best_join_tree(true);
+        assert_eq!(
+            bushy,
+            JoinTree::Join(
+                Box::new(JoinTree::left_deep(&[0, 1])),
+                Box::new(JoinTree::left_deep(&[2, 3])),
+            )
+        );
+    }
 }
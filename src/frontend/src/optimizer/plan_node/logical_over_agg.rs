@@ -18,14 +18,17 @@ use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_expr::window_function::RowsFrame;
+use risingwave_pb::expr::{AggCall as ProstAggCall, WindowFunction as ProstWindowFunction};
 
 use super::generic::{PlanAggOrderByField, PlanAggOrderByFieldDisplay};
 use super::{
-    gen_filter_and_pushdown, ColPrunable, LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary,
-    PredicatePushdown, ToBatch, ToStream,
+    gen_filter_and_pushdown, BatchOverWindow, ColPrunable, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, StreamOverWindow, ToBatch, ToStream,
 };
 use crate::expr::{Expr, ExprImpl, InputRef, InputRefDisplay, WindowFunction, WindowFunctionType};
+use crate::optimizer::property::{Direction, FieldOrder, Order, RequiredDist};
 use crate::utils::{ColIndexMapping, Condition};
 
 /// Rewritten version of [`WindowFunction`] which uses `InputRef` instead of `ExprImpl`.
@@ -33,10 +36,64 @@ use crate::utils::{ColIndexMapping, Condition};
 pub struct PlanWindowFunction {
     pub function_type: WindowFunctionType,
     pub return_type: DataType,
+    pub args: Vec<InputRef>,
+    /// The offset of `LAG` and `LEAD`.
+    pub offset: usize,
     pub partition_by: Vec<InputRef>,
     /// TODO: rename & move `PlanAggOrderByField` so that it can be better shared like
     /// [`crate::expr::OrderByExpr`]
     pub order_by: Vec<PlanAggOrderByField>,
+    /// The `ROWS` frame of an aggregate window function, or `None` for the default frame.
+    pub rows_frame: Option<RowsFrame>,
+}
+
+impl PlanWindowFunction {
+    pub fn to_protobuf(&self) -> ProstWindowFunction {
+        let args = self
+            .args
+            .iter()
+            .map(InputRef::to_agg_arg_proto)
+            .collect_vec();
+        let agg_call = match self.function_type {
+            WindowFunctionType::Aggregate(kind) => Some(ProstAggCall {
+                r#type: kind.to_prost().into(),
+                args: args.clone(),
+                return_type: Some(self.return_type.to_protobuf()),
+                ..Default::default()
+            }),
+            _ => None,
+        };
+        ProstWindowFunction {
+            r#type: self.function_type.to_prost().into(),
+            args,
+            return_type: Some(self.return_type.to_protobuf()),
+            offset: self.offset as u64,
+            agg_call,
+            rows_frame: self.rows_frame.map(RowsFrame::to_protobuf),
+        }
+    }
+
+    fn rewrite_input_refs(&self, mapping: &ColIndexMapping) -> Self {
+        let rewrite = |input_ref: &InputRef| {
+            InputRef::new(mapping.map(input_ref.index), input_ref.return_type())
+        };
+        Self {
+            function_type: self.function_type,
+            return_type: self.return_type.clone(),
+            args: self.args.iter().map(rewrite).collect(),
+            offset: self.offset,
+            partition_by: self.partition_by.iter().map(rewrite).collect(),
+            order_by: self
+                .order_by
+                .iter()
+                .map(|field| PlanAggOrderByField {
+                    input: rewrite(&field.input),
+                    ..field.clone()
+                })
+                .collect(),
+            rows_frame: self.rows_frame,
+        }
+    }
 }
 
 struct PlanWindowFunctionDisplay<'a> {
@@ -51,11 +108,33 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
             f.debug_struct("WindowFunction")
                 .field("function_type", &window_function.function_type)
                 .field("return_type", &window_function.return_type)
+                .field("args", &window_function.args)
+                .field("offset", &window_function.offset)
                 .field("partition_by", &window_function.partition_by)
                 .field("order_by", &window_function.order_by)
                 .finish()
         } else {
-            write!(f, "{}() OVER(", window_function.function_type)?;
+            write!(
+                f,
+                "{}({}",
+                window_function.function_type,
+                window_function
+                    .args
+                    .iter()
+                    .format_with(", ", |input_ref, f| {
+                        f(&InputRefDisplay {
+                            input_ref,
+                            input_schema: self.input_schema,
+                        })
+                    })
+            )?;
+            if matches!(
+                window_function.function_type,
+                WindowFunctionType::Lag | WindowFunctionType::Lead
+            ) {
+                write!(f, ", {}", window_function.offset)?;
+            }
+            f.write_str(") OVER(")?;
 
             let mut delim = "";
             if !window_function.partition_by.is_empty() {
@@ -88,6 +167,10 @@ impl<'a> std::fmt::Debug for PlanWindowFunctionDisplay<'a> {
                         ))
                     })
                 )?;
+                delim = " ";
+            }
+            if let Some(rows_frame) = &window_function.rows_frame {
+                write!(f, "{delim}{rows_frame}")?;
             }
             f.write_str(")")?;
 
@@ -155,21 +238,12 @@ impl LogicalOverAgg {
             }
         }
         for f in &window_funcs {
-            if f.function_type.is_rank_function() {
-                if f.order_by.sort_exprs.is_empty() {
-                    return Err(ErrorCode::InvalidInputSyntax(format!(
-                        "window rank function without order by: {:?}",
-                        f
-                    ))
-                    .into());
-                }
-                if f.function_type == WindowFunctionType::DenseRank {
-                    return Err(ErrorCode::NotImplemented(
-                        format!("window rank function: {}", f.function_type),
-                        4847.into(),
-                    )
-                    .into());
-                }
+            if f.function_type.is_rank_function() && f.order_by.sort_exprs.is_empty() {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "window rank function without order by: {:?}",
+                    f
+                ))
+                .into());
             }
         }
         if window_funcs.len() > 1 {
@@ -185,9 +259,34 @@ impl LogicalOverAgg {
             function_type,
             partition_by,
             order_by,
+            rows_frame,
         } = window_funcs.into_iter().next().unwrap();
-        assert!(args.is_empty());
-        assert!(return_type == DataType::Int64);
+
+        // The offset of `LAG` and `LEAD` is a constant checked by the binder, and the other
+        // arguments must be columns of the input.
+        let mut offset = 1;
+        if matches!(
+            function_type,
+            WindowFunctionType::Lag | WindowFunctionType::Lead
+        ) && let Some(ExprImpl::Literal(literal)) = args.get(1)
+            && let Some(ScalarImpl::Int32(v)) = literal.get_data()
+        {
+            offset = *v as usize;
+        }
+        let args = match function_type {
+            WindowFunctionType::Lag | WindowFunctionType::Lead => &args[..1],
+            _ => &args[..],
+        }
+        .iter()
+        .map(|e| match e.as_input_ref() {
+            Some(i) => Ok(*i.clone()),
+            None => Err(ErrorCode::NotImplemented(
+                "argument expression in window function".to_string(),
+                None.into(),
+            )
+            .into()),
+        })
+        .collect::<Result<Vec<_>>>()?;
 
         // TODO: rewrite ORDER BY & PARTITION BY expr to InputRef like `LogicalAgg`
         let order_by = order_by
@@ -222,13 +321,56 @@ impl LogicalOverAgg {
             PlanWindowFunction {
                 function_type,
                 return_type,
+                args,
+                offset,
                 partition_by,
                 order_by,
+                rows_frame,
             },
             input,
         );
         Ok((over_agg.into(), select_exprs))
     }
+
+    pub fn partition_key_indices(&self) -> Vec<usize> {
+        self.window_function
+            .partition_by
+            .iter()
+            .map(|input_ref| input_ref.index)
+            .collect()
+    }
+
+    /// The order the input must provide: sorted by the partition key and then the window order.
+    pub fn input_order(&self) -> Order {
+        let mut field_order = self
+            .window_function
+            .partition_by
+            .iter()
+            .map(|input_ref| FieldOrder {
+                index: input_ref.index,
+                direct: Direction::Asc,
+            })
+            .collect_vec();
+        for field in &self.window_function.order_by {
+            if !field_order.iter().any(|o| o.index == field.input.index) {
+                field_order.push(FieldOrder {
+                    index: field.input.index,
+                    direct: field.direction,
+                });
+            }
+        }
+        Order::new(field_order)
+    }
+
+    /// The distribution the input must provide, so that each partition is handled as a whole.
+    pub fn input_required_dist(&self) -> RequiredDist {
+        let partition_key_indices = self.partition_key_indices();
+        if partition_key_indices.is_empty() {
+            RequiredDist::single()
+        } else {
+            RequiredDist::shard_by_key(self.input.schema().len(), &partition_key_indices)
+        }
+    }
 }
 
 impl PlanTreeNodeUnary for LogicalOverAgg {
@@ -239,13 +381,31 @@ impl PlanTreeNodeUnary for LogicalOverAgg {
     fn clone_with_input(&self, input: PlanRef) -> Self {
         Self::new(self.window_function.clone(), input)
     }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let new_input_len = input.schema().len();
+        let mut map = (0..input_col_change.source_size())
+            .map(|i| input_col_change.try_map(i))
+            .collect_vec();
+        map.push(Some(new_input_len));
+        let window_function = self.window_function.rewrite_input_refs(&input_col_change);
+        (
+            Self::new(window_function, input),
+            ColIndexMapping::with_target_size(map, new_input_len + 1),
+        )
+    }
 }
 
 impl_plan_tree_node_for_unary! { LogicalOverAgg }
 
-impl fmt::Display for LogicalOverAgg {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut builder = f.debug_struct("LogicalOverAgg");
+impl LogicalOverAgg {
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        let mut builder = f.debug_struct(name);
         builder.field(
             "window_function",
             &PlanWindowFunctionDisplay {
@@ -257,6 +417,12 @@ impl fmt::Display for LogicalOverAgg {
     }
 }
 
+impl fmt::Display for LogicalOverAgg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalOverAgg")
+    }
+}
+
 impl ColPrunable for LogicalOverAgg {
     fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
         let mapping = ColIndexMapping::with_remaining_columns(required_cols, self.schema().len());
@@ -275,16 +441,25 @@ impl PredicatePushdown for LogicalOverAgg {
 
 impl ToBatch for LogicalOverAgg {
     fn to_batch(&self) -> Result<PlanRef> {
-        Err(ErrorCode::NotImplemented("OverAgg to batch".to_string(), 4847.into()).into())
+        let new_input = self
+            .input()
+            .to_batch_with_order_required(&self.input_order())?;
+        Ok(BatchOverWindow::new(self.clone_with_input(new_input)).into())
     }
 }
 
 impl ToStream for LogicalOverAgg {
     fn to_stream(&self) -> Result<PlanRef> {
-        Err(ErrorCode::NotImplemented("OverAgg to stream".to_string(), 4847.into()).into())
+        let new_input = self.input().to_stream()?;
+        let new_input = self
+            .input_required_dist()
+            .enforce_if_not_satisfies(new_input, &Order::any())?;
+        Ok(StreamOverWindow::new(self.clone_with_input(new_input)).into())
     }
 
-    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, crate::utils::ColIndexMapping)> {
-        Err(ErrorCode::NotImplemented("OverAgg to stream".to_string(), 4847.into()).into())
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input().logical_rewrite_for_stream()?;
+        let (over_agg, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((over_agg.into(), out_col_change))
     }
 }
@@ -17,7 +17,7 @@ use std::fmt;
 
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result};
 
 use super::generic::{self, GenericPlanNode, Project};
 use super::{
@@ -53,6 +53,9 @@ impl LogicalProjectBuilder {
         if expr.has_window_function() {
             return Err("window function");
         }
+        if expr.has_grouping() {
+            return Err("grouping operation");
+        }
         if let Some(idx) = self.exprs_index.get(expr) {
             Ok(*idx)
         } else {
@@ -334,6 +337,13 @@ impl ToBatch for LogicalProject {
 
 impl ToStream for LogicalProject {
     fn to_stream_with_dist_required(&self, required_dist: &RequiredDist) -> Result<PlanRef> {
+        if self.exprs().iter().any(|expr| expr.has_now()) {
+            return Err(ErrorCode::NotImplemented(
+                "now() in the select list of a streaming query".into(),
+                None.into(),
+            )
+            .into());
+        }
         let input_required = if required_dist.satisfies(&RequiredDist::AnyShard) {
             RequiredDist::Any
         } else {
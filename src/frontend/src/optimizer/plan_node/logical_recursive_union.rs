@@ -0,0 +1,158 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+
+use super::{
+    BatchRecursiveUnion, ColPrunable, LogicalFilter, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNode, PredicatePushdown, ToBatch, ToStream,
+};
+use crate::optimizer::property::FunctionalDependencySet;
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalRecursiveUnion` evaluates a recursive CTE. It outputs the rows of its first input, the
+/// base term, and then repeatedly evaluates its second input, the recursive term, whose
+/// [`super::LogicalWorkTable`] with the same `work_table_id` contains the rows produced by the last
+/// iteration, until no new rows are produced.
+/// If `all` is false, it needs to eliminate duplicates.
+#[derive(Debug, Clone)]
+pub struct LogicalRecursiveUnion {
+    pub base: PlanBase,
+    work_table_id: u32,
+    all: bool,
+    inputs: [PlanRef; 2],
+}
+
+impl LogicalRecursiveUnion {
+    pub fn new(work_table_id: u32, all: bool, base: PlanRef, recursive: PlanRef) -> Self {
+        let ctx = base.ctx();
+        let schema = base.schema().clone();
+        let functional_dependency = FunctionalDependencySet::new(schema.len());
+        let plan_base = PlanBase::new_logical(ctx, schema, vec![], functional_dependency);
+        LogicalRecursiveUnion {
+            base: plan_base,
+            work_table_id,
+            all,
+            inputs: [base, recursive],
+        }
+    }
+
+    pub fn create(work_table_id: u32, all: bool, base: PlanRef, recursive: PlanRef) -> PlanRef {
+        Self::new(work_table_id, all, base, recursive).into()
+    }
+
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ work_table_id: {}, all: {} }}",
+            name, self.work_table_id, self.all
+        )
+    }
+
+    pub fn work_table_id(&self) -> u32 {
+        self.work_table_id
+    }
+
+    pub fn all(&self) -> bool {
+        self.all
+    }
+}
+
+impl PlanTreeNode for LogicalRecursiveUnion {
+    fn inputs(&self) -> smallvec::SmallVec<[crate::optimizer::PlanRef; 2]> {
+        self.inputs.iter().cloned().collect()
+    }
+
+    fn clone_with_inputs(&self, inputs: &[crate::optimizer::PlanRef]) -> PlanRef {
+        let [base, recursive] = inputs else {
+            unreachable!("recursive union must have 2 inputs")
+        };
+        Self::new(
+            self.work_table_id,
+            self.all,
+            base.clone(),
+            recursive.clone(),
+        )
+        .into()
+    }
+}
+
+impl fmt::Display for LogicalRecursiveUnion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalRecursiveUnion")
+    }
+}
+
+impl ColPrunable for LogicalRecursiveUnion {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        // All the columns are written into the work table, so the inputs are not pruned.
+        let all_cols = (0..self.schema().len()).collect_vec();
+        let new_inputs = self
+            .inputs()
+            .iter()
+            .map(|input| input.prune_col(&all_cols))
+            .collect_vec();
+        let new_node = self.clone_with_inputs(&new_inputs);
+        if required_cols == all_cols {
+            new_node
+        } else {
+            LogicalProject::with_out_col_idx(new_node, required_cols.iter().copied()).into()
+        }
+    }
+}
+
+impl PredicatePushdown for LogicalRecursiveUnion {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        // The predicate can't be pushed into the recursive term, as it would filter the rows of the
+        // work table as well.
+        let new_inputs = self
+            .inputs()
+            .iter()
+            .map(|input| input.predicate_pushdown(Condition::true_cond()))
+            .collect_vec();
+        LogicalFilter::create(self.clone_with_inputs(&new_inputs), predicate)
+    }
+}
+
+impl ToBatch for LogicalRecursiveUnion {
+    fn to_batch(&self) -> Result<PlanRef> {
+        let [base, recursive] = &self.inputs;
+        let new_logical = Self::new(
+            self.work_table_id,
+            self.all,
+            base.to_batch()?,
+            recursive.to_batch()?,
+        );
+        Ok(BatchRecursiveUnion::new(new_logical).into())
+    }
+}
+
+impl ToStream for LogicalRecursiveUnion {
+    fn to_stream(&self) -> Result<PlanRef> {
+        Err(RwError::from(ErrorCode::NotImplemented(
+            "recursive CTE in streaming queries".to_string(),
+            None.into(),
+        )))
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        Err(RwError::from(ErrorCode::NotImplemented(
+            "recursive CTE in streaming queries".to_string(),
+            None.into(),
+        )))
+    }
+}
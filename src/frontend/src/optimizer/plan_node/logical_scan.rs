@@ -52,6 +52,7 @@ impl LogicalScan {
         indexes: Vec<Rc<IndexCatalog>>,
         ctx: OptimizerContextRef,
         predicate: Condition, // refers to column indexes of the table
+        for_system_time_as_of_proctime: bool,
     ) -> Self {
         // here we have 3 concepts
         // 1. column_id: ColumnId, stored in catalog and a ID to access data from storage.
@@ -107,6 +108,7 @@ impl LogicalScan {
                 table_desc,
                 indexes,
                 predicate,
+                for_system_time_as_of_proctime,
             },
         }
     }
@@ -118,6 +120,7 @@ impl LogicalScan {
         table_desc: Rc<TableDesc>,
         indexes: Vec<Rc<IndexCatalog>>,
         ctx: OptimizerContextRef,
+        for_system_time_as_of_proctime: bool,
     ) -> Self {
         Self::new(
             table_name,
@@ -127,6 +130,7 @@ impl LogicalScan {
             indexes,
             ctx,
             Condition::true_cond(),
+            for_system_time_as_of_proctime,
         )
     }
 
@@ -180,6 +184,10 @@ impl LogicalScan {
         self.core.is_sys_table
     }
 
+    pub fn for_system_time_as_of_proctime(&self) -> bool {
+        self.core.for_system_time_as_of_proctime
+    }
+
     /// Get a reference to the logical scan's table desc.
     pub fn table_desc(&self) -> &TableDesc {
         self.core.table_desc.as_ref()
@@ -303,6 +311,7 @@ impl LogicalScan {
             vec![],
             self.ctx(),
             new_predicate,
+            self.for_system_time_as_of_proctime(),
         )
     }
 
@@ -336,7 +345,7 @@ impl LogicalScan {
     }
 
     /// Undo predicate push down when predicate in scan is not supported.
-    fn predicate_pull_up(&self) -> (LogicalScan, Condition, Option<Vec<ExprImpl>>) {
+    pub(super) fn predicate_pull_up(&self) -> (LogicalScan, Condition, Option<Vec<ExprImpl>>) {
         let mut predicate = self.predicate().clone();
         if predicate.always_true() {
             return (self.clone(), Condition::true_cond(), None);
@@ -355,6 +364,7 @@ impl LogicalScan {
             self.indexes().to_vec(),
             self.ctx(),
             Condition::true_cond(),
+            self.for_system_time_as_of_proctime(),
         );
         let project_expr = if self.required_col_idx() != self.output_col_idx() {
             Some(self.output_idx_to_input_ref())
@@ -373,6 +383,7 @@ impl LogicalScan {
             self.indexes().to_vec(),
             self.base.ctx.clone(),
             predicate,
+            self.for_system_time_as_of_proctime(),
         )
     }
 
@@ -385,6 +396,7 @@ impl LogicalScan {
             self.indexes().to_vec(),
             self.base.ctx.clone(),
             self.predicate().clone(),
+            self.for_system_time_as_of_proctime(),
         )
     }
 
@@ -583,9 +595,29 @@ impl ToStream for LogicalScan {
                 None.into(),
             )));
         }
+        if self.for_system_time_as_of_proctime() {
+            return Err(RwError::from(ErrorCode::NotImplemented(
+                "FOR SYSTEM_TIME AS OF PROCTIME() on a table other than the right side of a join"
+                    .to_string(),
+                None.into(),
+            )));
+        }
         if self.predicate().always_true() {
             Ok(StreamTableScan::new(self.clone()).into())
         } else {
+            // Point or range predicates on the leading columns of a covering index can be
+            // served by scanning the index table instead of the primary table. Index lookup
+            // joins are not used here since they cannot be maintained incrementally. The
+            // streaming job then depends on the index, which can't be dropped before the job.
+            if !self.indexes().is_empty() {
+                let index_selection_rule = IndexSelectionRule::create();
+                if let Some(applied) = index_selection_rule.apply(self.clone().into())
+                    && let Some(scan) = applied.as_logical_scan()
+                {
+                    return scan.to_stream();
+                }
+            }
+
             let (scan, predicate, project_expr) = self.predicate_pull_up();
             let mut plan = LogicalFilter::create(scan.into(), predicate);
             if let Some(exprs) = project_expr {
@@ -0,0 +1,259 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use itertools::Itertools;
+use risingwave_common::catalog::Field;
+use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, IntervalUnit};
+
+use super::{
+    gen_filter_and_pushdown, BatchSessionWindow, ColPrunable, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, StreamSessionWindow, ToBatch, ToStream,
+};
+use crate::expr::{InputRef, InputRefDisplay};
+use crate::optimizer::property::{Direction, FieldOrder, Order, RequiredDist};
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalSessionWindow` implements the `SESSION` window table function. It appends the
+/// `window_start` and `window_end` of the session of each input row, where the sessions are
+/// assigned within each partition by the gaps on the time column.
+#[derive(Debug, Clone)]
+pub struct LogicalSessionWindow {
+    pub base: PlanBase,
+    input: PlanRef,
+    pub time_col: InputRef,
+    pub gap: IntervalUnit,
+    pub partition_by: Vec<InputRef>,
+}
+
+impl LogicalSessionWindow {
+    fn new(
+        input: PlanRef,
+        time_col: InputRef,
+        gap: IntervalUnit,
+        partition_by: Vec<InputRef>,
+    ) -> Self {
+        let ctx = input.ctx();
+        let output_type = DataType::window_of(&time_col.data_type).unwrap();
+        let mut schema = input.schema().clone();
+        schema.fields.extend([
+            Field::with_name(output_type.clone(), "window_start"),
+            Field::with_name(output_type, "window_end"),
+        ]);
+
+        let logical_pk = input.logical_pk().to_vec();
+
+        let mapping =
+            ColIndexMapping::identity_or_none(input.schema().len(), input.schema().len() + 2);
+        let fd_set = input.functional_dependency().clone();
+        let mut fd_set = mapping.rewrite_functional_dependency_set(fd_set);
+        // A session is identified by its start within a partition.
+        let window_start = input.schema().len();
+        let mut session_key = partition_by
+            .iter()
+            .map(|input_ref| input_ref.index)
+            .collect_vec();
+        session_key.push(window_start);
+        fd_set.add_functional_dependency_by_column_indices(&session_key, &[window_start + 1]);
+
+        let base = PlanBase::new_logical(ctx, schema, logical_pk, fd_set);
+
+        Self {
+            base,
+            input,
+            time_col,
+            gap,
+            partition_by,
+        }
+    }
+
+    pub fn create(
+        input: PlanRef,
+        time_col: InputRef,
+        gap: IntervalUnit,
+        partition_by: Vec<InputRef>,
+    ) -> PlanRef {
+        Self::new(input, time_col, gap, partition_by).into()
+    }
+
+    pub fn partition_key_indices(&self) -> Vec<usize> {
+        self.partition_by
+            .iter()
+            .map(|input_ref| input_ref.index)
+            .collect()
+    }
+
+    /// The order the input must provide: sorted by the partition key and then the time column.
+    pub fn input_order(&self) -> Order {
+        let mut field_order = self
+            .partition_by
+            .iter()
+            .map(|input_ref| FieldOrder {
+                index: input_ref.index,
+                direct: Direction::Asc,
+            })
+            .collect_vec();
+        if !self
+            .partition_by
+            .iter()
+            .any(|input_ref| input_ref.index == self.time_col.index)
+        {
+            field_order.push(FieldOrder {
+                index: self.time_col.index,
+                direct: Direction::Asc,
+            });
+        }
+        Order::new(field_order)
+    }
+
+    /// The distribution the input must provide, so that each partition is handled as a whole.
+    pub fn input_required_dist(&self) -> RequiredDist {
+        let partition_key_indices = self.partition_key_indices();
+        if partition_key_indices.is_empty() {
+            RequiredDist::single()
+        } else {
+            RequiredDist::shard_by_key(self.input.schema().len(), &partition_key_indices)
+        }
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalSessionWindow {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(
+            input,
+            self.time_col.clone(),
+            self.gap,
+            self.partition_by.clone(),
+        )
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let new_input_len = input.schema().len();
+        let mut map = (0..input_col_change.source_size())
+            .map(|i| input_col_change.try_map(i))
+            .collect_vec();
+        map.push(Some(new_input_len));
+        map.push(Some(new_input_len + 1));
+        let rewrite = |input_ref: &InputRef| {
+            InputRef::new(
+                input_col_change.map(input_ref.index),
+                input_ref.data_type.clone(),
+            )
+        };
+        (
+            Self::new(
+                input,
+                rewrite(&self.time_col),
+                self.gap,
+                self.partition_by.iter().map(rewrite).collect(),
+            ),
+            ColIndexMapping::with_target_size(map, new_input_len + 2),
+        )
+    }
+}
+
+impl_plan_tree_node_for_unary! { LogicalSessionWindow }
+
+impl LogicalSessionWindow {
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        let input_schema = self.input.schema();
+        let mut builder = f.debug_struct(name);
+        builder.field(
+            "time_col",
+            &InputRefDisplay {
+                input_ref: &self.time_col,
+                input_schema,
+            },
+        );
+        builder.field("gap", &format_args!("{}", self.gap));
+        if !self.partition_by.is_empty() {
+            builder.field(
+                "partition_by",
+                &self
+                    .partition_by
+                    .iter()
+                    .map(|input_ref| InputRefDisplay {
+                        input_ref,
+                        input_schema,
+                    })
+                    .collect_vec(),
+            );
+        }
+        builder.finish()
+    }
+}
+
+impl fmt::Display for LogicalSessionWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalSessionWindow")
+    }
+}
+
+impl ColPrunable for LogicalSessionWindow {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let mapping = ColIndexMapping::with_remaining_columns(required_cols, self.schema().len());
+        LogicalProject::with_mapping(self.clone().into(), mapping).into()
+    }
+}
+
+impl PredicatePushdown for LogicalSessionWindow {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        // Filtering the input rows changes the sessions, unless whole partitions are filtered, so
+        // only the predicates on the partition key can be pushed down.
+        let mut non_partition_cols = FixedBitSet::with_capacity(self.schema().len());
+        non_partition_cols.insert_range(..);
+        for idx in self.partition_key_indices() {
+            non_partition_cols.set(idx, false);
+        }
+        let (session_pred, partition_pred) = predicate.split_disjoint(&non_partition_cols);
+        gen_filter_and_pushdown(self, session_pred, partition_pred)
+    }
+}
+
+impl ToBatch for LogicalSessionWindow {
+    fn to_batch(&self) -> Result<PlanRef> {
+        let new_input = self
+            .input()
+            .to_batch_with_order_required(&self.input_order())?;
+        Ok(BatchSessionWindow::new(self.clone_with_input(new_input)).into())
+    }
+}
+
+impl ToStream for LogicalSessionWindow {
+    fn to_stream(&self) -> Result<PlanRef> {
+        let new_input = self.input().to_stream()?;
+        let new_input = self
+            .input_required_dist()
+            .enforce_if_not_satisfies(new_input, &Order::any())?;
+        Ok(StreamSessionWindow::new(self.clone_with_input(new_input)).into())
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input().logical_rewrite_for_stream()?;
+        let (session_window, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((session_window.into(), out_col_change))
+    }
+}
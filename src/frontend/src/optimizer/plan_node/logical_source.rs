@@ -16,18 +16,25 @@ use std::collections::HashMap;
 use std::fmt;
 use std::rc::Rc;
 
+use fixedbitset::FixedBitSet;
+use itertools::Itertools;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_source::JsonParser;
 
 use super::{
     generic, ColPrunable, LogicalFilter, LogicalProject, PlanBase, PlanRef, PredicatePushdown,
     StreamSource, ToBatch, ToStream,
 };
-use crate::catalog::source_catalog::SourceCatalog;
+use crate::catalog::source_catalog::{
+    SourceCatalog, KAFKA_CONNECTOR, KAFKA_TIMESTAMP_COLUMN_KEY, KAFKA_TIMESTAMP_MILLIS_KEY,
+};
+use crate::expr::ExprType;
 use crate::optimizer::property::FunctionalDependencySet;
 use crate::session::OptimizerContextRef;
 use crate::utils::{ColIndexMapping, Condition};
-use crate::TableCatalog;
+use crate::{TableCatalog, WithOptions};
 
 /// `LogicalSource` returns contents of a table or other equivalent object
 #[derive(Debug, Clone)]
@@ -38,18 +45,32 @@ pub struct LogicalSource {
 
 impl LogicalSource {
     pub fn new(source_catalog: Rc<SourceCatalog>, ctx: OptimizerContextRef) -> Self {
+        Self::with_core(generic::Source::new(source_catalog), ctx)
+    }
+
+    /// Creates a source accepting DML statements, whose rows are merged into the stream of the
+    /// connector by the source executor.
+    pub fn with_dml(source_catalog: Rc<SourceCatalog>, ctx: OptimizerContextRef) -> Self {
+        let core = generic::Source {
+            dml_enabled: true,
+            ..generic::Source::new(source_catalog)
+        };
+        Self::with_core(core, ctx)
+    }
+
+    fn with_core(core: generic::Source, ctx: OptimizerContextRef) -> Self {
         let mut id_to_idx = HashMap::new();
 
-        let fields = source_catalog
-            .columns
-            .iter()
+        let fields = core
+            .columns()
             .enumerate()
             .map(|(idx, c)| {
                 id_to_idx.insert(c.column_id(), idx);
                 (&c.column_desc).into()
             })
             .collect();
-        let pk_indices = source_catalog
+        let pk_indices = core
+            .catalog
             .pk_col_ids
             .iter()
             .map(|c| id_to_idx.get(c).copied())
@@ -63,12 +84,7 @@ impl LogicalSource {
             None => (FunctionalDependencySet::new(schema.len()), vec![]),
         };
         let base = PlanBase::new_logical(ctx, schema, pk_indices, functional_dependency);
-        LogicalSource {
-            base,
-            core: generic::Source {
-                catalog: source_catalog,
-            },
-        }
+        LogicalSource { base, core }
     }
 
     pub(super) fn column_names(&self) -> Vec<String> {
@@ -86,6 +102,60 @@ impl LogicalSource {
     pub fn infer_internal_table_catalog(&self) -> TableCatalog {
         generic::Source::infer_internal_table_catalog(&self.base)
     }
+
+    /// Derives the lower bound of the message timestamps of a Kafka source from the predicate, if
+    /// the column holding the timestamps is specified by the source.
+    fn kafka_timestamp_lower_bound(&self, predicate: &Condition) -> Option<i64> {
+        let catalog = &self.core.catalog;
+        if !WithOptions::new(catalog.properties.clone()).is_connector(KAFKA_CONNECTOR) {
+            return None;
+        }
+        let column_name = catalog.properties.get(KAFKA_TIMESTAMP_COLUMN_KEY)?;
+        let (column_idx, field) = self
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .find(|(_, field)| &field.name == column_name)?;
+        let pushed_down = self
+            .core
+            .pushdown_properties
+            .get(KAFKA_TIMESTAMP_MILLIS_KEY)
+            .and_then(|millis| millis.parse().ok());
+
+        predicate
+            .conjunctions
+            .iter()
+            .filter_map(|expr| {
+                let (input_ref, value) = match expr.as_comparison_const() {
+                    Some((
+                        input_ref,
+                        ExprType::GreaterThan | ExprType::GreaterThanOrEqual,
+                        value,
+                    )) => (input_ref, value),
+                    Some(_) => return None,
+                    None => expr.as_eq_const()?,
+                };
+                if input_ref.index() != column_idx {
+                    return None;
+                }
+                to_timestamp_millis(&field.data_type, value.eval_row_const().ok()??)
+            })
+            .chain(pushed_down)
+            .max()
+    }
+}
+
+/// Converts a value of the timestamp column of a Kafka source to the milliseconds since the unix
+/// epoch, as the timestamps of Kafka messages are.
+fn to_timestamp_millis(data_type: &DataType, value: ScalarImpl) -> Option<i64> {
+    match (data_type, value) {
+        (DataType::Timestamp, ScalarImpl::NaiveDateTime(v)) => Some(v.0.timestamp_millis()),
+        (DataType::Timestampz, ScalarImpl::Int64(v)) => Some(v.div_euclid(1000)),
+        (DataType::Date, ScalarImpl::NaiveDate(v)) => Some(v.0.and_hms(0, 0, 0).timestamp_millis()),
+        (DataType::Int64, ScalarImpl::Int64(v)) => Some(v),
+        _ => None,
+    }
 }
 
 impl_plan_tree_node_for_leaf! {LogicalSource}
@@ -103,14 +173,63 @@ impl fmt::Display for LogicalSource {
 
 impl ColPrunable for LogicalSource {
     fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
-        let mapping = ColIndexMapping::with_remaining_columns(required_cols, self.schema().len());
-        LogicalProject::with_mapping(self.clone().into(), mapping).into()
+        let project_all = || {
+            let mapping =
+                ColIndexMapping::with_remaining_columns(required_cols, self.schema().len());
+            LogicalProject::with_mapping(self.clone().into(), mapping).into()
+        };
+        // Only the columns of connector sources are pruned, so that the connector and the parser
+        // don't need to handle the pruned columns. The watermarks refer to the columns by index, so
+        // the sources with watermarks are not pruned either.
+        let catalog = self.source_catalog();
+        if !catalog.is_stream() || !catalog.watermark_descs.is_empty() {
+            return project_all();
+        }
+
+        let mut kept = FixedBitSet::with_capacity(self.schema().len());
+        kept.extend(required_cols.iter().copied());
+        kept.extend(self.logical_pk().iter().copied());
+        kept.extend(self.core.row_id_index());
+        let kept = kept.ones().collect_vec();
+        if kept.len() == self.schema().len() {
+            return project_all();
+        }
+        // A source storing the whole payload has a single column to parse, which is never pruned.
+        if JsonParser::new(&self.core.properties()).map_or(false, |parser| parser.whole_payload()) {
+            return project_all();
+        }
+
+        let core = generic::Source {
+            required_col_idx: kept
+                .iter()
+                .map(|&idx| self.core.required_col_idx[idx])
+                .collect(),
+            ..self.core.clone()
+        };
+        let source = Self::with_core(core, self.ctx());
+        let mapping = ColIndexMapping::with_remaining_columns(&kept, self.schema().len());
+        LogicalProject::with_out_col_idx(
+            source.into(),
+            required_cols.iter().map(|&idx| mapping.map(idx)),
+        )
+        .into()
     }
 }
 
 impl PredicatePushdown for LogicalSource {
     fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
-        LogicalFilter::create(self.clone().into(), predicate)
+        // The predicate is pushed down into the connector in the form of properties, but it's still
+        // applied after the source as the connector may skip only a part of the unmatched data.
+        let source = match self.kafka_timestamp_lower_bound(&predicate) {
+            Some(millis) => {
+                let mut core = self.core.clone();
+                core.pushdown_properties
+                    .insert(KAFKA_TIMESTAMP_MILLIS_KEY.to_string(), millis.to_string());
+                Self::with_core(core, self.ctx()).into()
+            }
+            None => self.clone().into(),
+        };
+        LogicalFilter::create(source, predicate)
     }
 }
 
@@ -135,3 +254,46 @@ impl ToStream for LogicalSource {
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_source_pushdown() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        let sql = r#"CREATE SOURCE s (v1 int, v2 varchar, ts timestamp)
+    WITH (connector = 'kafka', kafka.topic = 'abc', kafka.brokers = 'localhost:1001', scan.pushdown.timestamp.column = 'ts')
+    ROW FORMAT JSON"#;
+        frontend.run_sql(sql).await.unwrap();
+
+        // Only the required columns are read, and the lower bound of the timestamp column is pushed
+        // down into the connector.
+        let explain = frontend
+            .get_explain_output(
+                "explain create materialized view mv1 as select v1 from s \
+                 where ts >= '2022-01-01 00:00:00' and ts > '2021-12-31 00:00:00'",
+            )
+            .await;
+        assert!(explain.contains("columns: [_row_id, v1, ts]"), "{explain}");
+        assert!(
+            explain.contains(r#"pushdown: {"scan.pushdown.timestamp_millis": "1640995200000"}"#),
+            "{explain}"
+        );
+        // The predicate is still applied after the source.
+        assert!(explain.contains("StreamFilter"), "{explain}");
+
+        // Nothing to push down if there is no lower bound of the timestamp column.
+        let explain = frontend
+            .get_explain_output(
+                "explain create materialized view mv1 as select v1, v2 from s \
+                 where ts < '2022-01-01 00:00:00'",
+            )
+            .await;
+        assert!(
+            explain.contains("columns: [_row_id, v1, v2, ts]"),
+            "{explain}"
+        );
+        assert!(!explain.contains("pushdown"), "{explain}");
+    }
+}
@@ -19,8 +19,8 @@ use risingwave_common::error::Result;
 use risingwave_common::types::DataType;
 
 use super::{
-    gen_filter_and_pushdown, BatchUpdate, ColPrunable, PlanBase, PlanRef, PlanTreeNodeUnary,
-    PredicatePushdown, ToBatch, ToStream,
+    gen_filter_and_pushdown, BatchUpdate, ColPrunable, LogicalProject, PlanBase, PlanRef,
+    PlanTreeNodeUnary, PredicatePushdown, ToBatch, ToStream,
 };
 use crate::catalog::TableId;
 use crate::expr::ExprImpl;
@@ -30,7 +30,8 @@ use crate::utils::Condition;
 /// [`LogicalUpdate`] iterates on input relation, set some columns, and inject update records into
 /// specified table.
 ///
-/// It corresponds to the `UPDATE` statements in SQL.
+/// It corresponds to the `UPDATE` statements in SQL. With `RETURNING`, it outputs the updated rows
+/// with the new values instead of the number of them.
 #[derive(Debug, Clone)]
 pub struct LogicalUpdate {
     pub base: PlanBase,
@@ -39,6 +40,7 @@ pub struct LogicalUpdate {
     associated_mview_id: TableId,
     input: PlanRef,
     exprs: Vec<ExprImpl>,
+    returning: bool,
 }
 
 impl LogicalUpdate {
@@ -49,10 +51,16 @@ impl LogicalUpdate {
         source_id: TableId,
         associated_mview_id: TableId,
         exprs: Vec<ExprImpl>,
+        returning: bool,
     ) -> Self {
         let ctx = input.ctx();
-        // TODO: support `RETURNING`.
-        let schema = Schema::new(vec![Field::unnamed(DataType::Int64)]);
+        // The new values are assigned with the types of the columns, so the updated rows have the
+        // same schema as the input.
+        let schema = if returning {
+            input.schema().clone()
+        } else {
+            Schema::new(vec![Field::unnamed(DataType::Int64)])
+        };
         let fd_set = FunctionalDependencySet::new(schema.len());
         let base = PlanBase::new_logical(ctx, schema, vec![], fd_set);
         Self {
@@ -62,6 +70,7 @@ impl LogicalUpdate {
             associated_mview_id,
             input,
             exprs,
+            returning,
         }
     }
 
@@ -72,6 +81,7 @@ impl LogicalUpdate {
         source_id: TableId,
         table_id: TableId,
         exprs: Vec<ExprImpl>,
+        returning: bool,
     ) -> Result<Self> {
         Ok(Self::new(
             input,
@@ -79,15 +89,20 @@ impl LogicalUpdate {
             source_id,
             table_id,
             exprs,
+            returning,
         ))
     }
 
     pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
         write!(
             f,
-            "{} {{ table: {}, exprs: {:?} }}",
+            "{} {{ table: {}, exprs: {:?}",
             name, self.table_source_name, self.exprs
-        )
+        )?;
+        if self.returning {
+            write!(f, ", returning: true")?;
+        }
+        write!(f, " }}")
     }
 
     /// Get the logical update's source id.
@@ -104,6 +119,11 @@ impl LogicalUpdate {
     pub fn exprs(&self) -> &[ExprImpl] {
         self.exprs.as_ref()
     }
+
+    /// Whether the updated rows are output, instead of the number of them.
+    pub fn has_returning(&self) -> bool {
+        self.returning
+    }
 }
 
 impl PlanTreeNodeUnary for LogicalUpdate {
@@ -118,6 +138,7 @@ impl PlanTreeNodeUnary for LogicalUpdate {
             self.source_id,
             self.associated_mview_id,
             self.exprs.clone(),
+            self.returning,
         )
     }
 }
@@ -131,10 +152,16 @@ impl fmt::Display for LogicalUpdate {
 }
 
 impl ColPrunable for LogicalUpdate {
-    fn prune_col(&self, _required_cols: &[usize]) -> PlanRef {
-        let required_cols: Vec<_> = (0..self.input.schema().len()).collect();
-        self.clone_with_input(self.input.prune_col(&required_cols))
-            .into()
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let input_required_cols: Vec<_> = (0..self.input.schema().len()).collect();
+        let plan: PlanRef = self
+            .clone_with_input(self.input.prune_col(&input_required_cols))
+            .into();
+        if self.returning {
+            LogicalProject::with_out_col_idx(plan, required_cols.iter().cloned()).into()
+        } else {
+            plan
+        }
     }
 }
 
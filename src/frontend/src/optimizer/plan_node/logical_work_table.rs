@@ -0,0 +1,106 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+
+use super::{
+    BatchWorkTable, ColPrunable, LogicalFilter, LogicalProject, PlanBase, PlanRef,
+    PredicatePushdown, ToBatch, ToStream,
+};
+use crate::optimizer::property::FunctionalDependencySet;
+use crate::session::OptimizerContextRef;
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalWorkTable` is the self-reference of a recursive CTE in its recursive term, which outputs
+/// the rows produced by the last iteration of the [`super::LogicalRecursiveUnion`] with the same
+/// `work_table_id`.
+#[derive(Debug, Clone)]
+pub struct LogicalWorkTable {
+    pub base: PlanBase,
+    work_table_id: u32,
+}
+
+impl LogicalWorkTable {
+    pub fn new(work_table_id: u32, schema: Schema, ctx: OptimizerContextRef) -> Self {
+        let functional_dependency = FunctionalDependencySet::new(schema.len());
+        let base = PlanBase::new_logical(ctx, schema, vec![], functional_dependency);
+        Self {
+            base,
+            work_table_id,
+        }
+    }
+
+    pub fn create(work_table_id: u32, schema: Schema, ctx: OptimizerContextRef) -> PlanRef {
+        Self::new(work_table_id, schema, ctx).into()
+    }
+
+    pub(super) fn fmt_with_name(&self, f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+        write!(f, "{} {{ work_table_id: {} }}", name, self.work_table_id)
+    }
+
+    pub fn work_table_id(&self) -> u32 {
+        self.work_table_id
+    }
+}
+
+impl_plan_tree_node_for_leaf! { LogicalWorkTable }
+
+impl fmt::Display for LogicalWorkTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalWorkTable")
+    }
+}
+
+impl ColPrunable for LogicalWorkTable {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        // The rows in the work table always have all the columns.
+        if required_cols.iter().copied().eq(0..self.schema().len()) {
+            self.clone().into()
+        } else {
+            LogicalProject::with_out_col_idx(self.clone().into(), required_cols.iter().copied())
+                .into()
+        }
+    }
+}
+
+impl PredicatePushdown for LogicalWorkTable {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        LogicalFilter::create(self.clone().into(), predicate)
+    }
+}
+
+impl ToBatch for LogicalWorkTable {
+    fn to_batch(&self) -> Result<PlanRef> {
+        Ok(BatchWorkTable::new(self.clone()).into())
+    }
+}
+
+impl ToStream for LogicalWorkTable {
+    fn to_stream(&self) -> Result<PlanRef> {
+        Err(RwError::from(ErrorCode::NotImplemented(
+            "recursive CTE in streaming queries".to_string(),
+            None.into(),
+        )))
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        Err(RwError::from(ErrorCode::NotImplemented(
+            "recursive CTE in streaming queries".to_string(),
+            None.into(),
+        )))
+    }
+}
@@ -33,6 +33,7 @@ use std::rc::Rc;
 
 use downcast_rs::{impl_downcast, Downcast};
 use dyn_clone::{self, DynClone};
+use fixedbitset::FixedBitSet;
 use paste::paste;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result};
@@ -112,31 +113,45 @@ impl dyn PlanNode {
         level: usize,
         f: &mut impl std::fmt::Write,
     ) -> std::fmt::Result {
+        self.explain_lines(is_last, level, &mut |_, line| writeln!(f, "{}", line))
+    }
+
+    /// Explain the whole plan tree line by line, calling `f` with each plan node and its line.
+    pub fn explain_lines(
+        &self,
+        is_last: &mut Vec<bool>,
+        level: usize,
+        f: &mut impl FnMut(&dyn PlanNode, String) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        use std::fmt::Write;
+
+        let mut line = String::new();
         if level > 0 {
             let mut last_iter = is_last.iter().peekable();
             while let Some(last) = last_iter.next() {
                 // We are at the current level
                 if last_iter.peek().is_none() {
                     if *last {
-                        writeln!(f, "└─{}", self)?;
+                        write!(line, "└─{}", self)?;
                     } else {
-                        writeln!(f, "├─{}", self)?;
+                        write!(line, "├─{}", self)?;
                     }
                 } else if *last {
-                    write!(f, "  ")?;
+                    write!(line, "  ")?;
                 } else {
-                    write!(f, "| ")?;
+                    write!(line, "| ")?;
                 }
             }
         } else {
-            writeln!(f, "{}", self)?;
+            write!(line, "{}", self)?;
         }
+        f(self, line)?;
         let inputs = self.inputs();
         let mut inputs_iter = inputs.iter().peekable();
         while let Some(input) = inputs_iter.next() {
             let last = inputs_iter.peek().is_none();
             is_last.push(last);
-            input.explain(is_last, level + 1, f)?;
+            input.explain_lines(is_last, level + 1, f)?;
             is_last.pop();
         }
         Ok(())
@@ -182,6 +197,10 @@ impl dyn PlanNode {
         &self.plan_base().functional_dependency
     }
 
+    pub fn watermark_columns(&self) -> &FixedBitSet {
+        &self.plan_base().watermark_columns
+    }
+
     /// Serialize the plan node and its children to a stream plan proto.
     ///
     /// Note that [`StreamTableScan`] has its own implementation of `to_stream_prost`. We have a
@@ -272,10 +291,14 @@ mod batch_hop_window;
 mod batch_insert;
 mod batch_limit;
 mod batch_lookup_join;
+mod batch_nested_loop_apply;
 mod batch_nested_loop_join;
+mod batch_over_window;
 mod batch_project;
 mod batch_project_set;
+mod batch_recursive_union;
 mod batch_seq_scan;
+mod batch_session_window;
 mod batch_simple_agg;
 mod batch_sort;
 mod batch_sort_agg;
@@ -284,6 +307,7 @@ mod batch_topn;
 mod batch_union;
 mod batch_update;
 mod batch_values;
+mod batch_work_table;
 mod logical_agg;
 mod logical_apply;
 mod logical_delete;
@@ -297,13 +321,16 @@ mod logical_multi_join;
 mod logical_over_agg;
 mod logical_project;
 mod logical_project_set;
+mod logical_recursive_union;
 mod logical_scan;
+mod logical_session_window;
 mod logical_source;
 mod logical_table_function;
 mod logical_topn;
 mod logical_union;
 mod logical_update;
 mod logical_values;
+mod logical_work_table;
 mod stream_delta_join;
 mod stream_dynamic_filter;
 mod stream_exchange;
@@ -315,13 +342,21 @@ mod stream_hash_agg;
 mod stream_hash_join;
 mod stream_hop_window;
 mod stream_index_scan;
+mod stream_interval_join;
+mod stream_local_hash_agg;
 mod stream_local_simple_agg;
+mod stream_lookup_join;
 mod stream_materialize;
+mod stream_now;
+mod stream_over_window;
 mod stream_project;
 mod stream_project_set;
+mod stream_session_window;
 mod stream_sink;
+mod stream_sort;
 mod stream_source;
 mod stream_table_scan;
+mod stream_temporal_join;
 mod stream_topn;
 
 pub mod utils;
@@ -337,10 +372,14 @@ pub use batch_hop_window::BatchHopWindow;
 pub use batch_insert::BatchInsert;
 pub use batch_limit::BatchLimit;
 pub use batch_lookup_join::BatchLookupJoin;
+pub use batch_nested_loop_apply::BatchNestedLoopApply;
 pub use batch_nested_loop_join::BatchNestedLoopJoin;
+pub use batch_over_window::BatchOverWindow;
 pub use batch_project::BatchProject;
 pub use batch_project_set::BatchProjectSet;
+pub use batch_recursive_union::BatchRecursiveUnion;
 pub use batch_seq_scan::BatchSeqScan;
+pub use batch_session_window::BatchSessionWindow;
 pub use batch_simple_agg::BatchSimpleAgg;
 pub use batch_sort::BatchSort;
 pub use batch_sort_agg::BatchSortAgg;
@@ -349,6 +388,7 @@ pub use batch_topn::BatchTopN;
 pub use batch_union::BatchUnion;
 pub use batch_update::BatchUpdate;
 pub use batch_values::BatchValues;
+pub use batch_work_table::BatchWorkTable;
 pub use logical_agg::LogicalAgg;
 pub use logical_apply::LogicalApply;
 pub use logical_delete::LogicalDelete;
@@ -358,17 +398,20 @@ pub use logical_hop_window::LogicalHopWindow;
 pub use logical_insert::LogicalInsert;
 pub use logical_join::LogicalJoin;
 pub use logical_limit::LogicalLimit;
-pub use logical_multi_join::{LogicalMultiJoin, LogicalMultiJoinBuilder};
+pub use logical_multi_join::{JoinTree, LogicalMultiJoin, LogicalMultiJoinBuilder};
 pub use logical_over_agg::{LogicalOverAgg, PlanWindowFunction};
 pub use logical_project::{LogicalProject, LogicalProjectBuilder};
 pub use logical_project_set::LogicalProjectSet;
+pub use logical_recursive_union::LogicalRecursiveUnion;
 pub use logical_scan::LogicalScan;
+pub use logical_session_window::LogicalSessionWindow;
 pub use logical_source::LogicalSource;
 pub use logical_table_function::LogicalTableFunction;
 pub use logical_topn::LogicalTopN;
 pub use logical_union::LogicalUnion;
 pub use logical_update::LogicalUpdate;
 pub use logical_values::LogicalValues;
+pub use logical_work_table::LogicalWorkTable;
 pub use stream_delta_join::StreamDeltaJoin;
 pub use stream_dynamic_filter::StreamDynamicFilter;
 pub use stream_exchange::StreamExchange;
@@ -380,13 +423,21 @@ pub use stream_hash_agg::StreamHashAgg;
 pub use stream_hash_join::StreamHashJoin;
 pub use stream_hop_window::StreamHopWindow;
 pub use stream_index_scan::StreamIndexScan;
+pub use stream_interval_join::{IntervalJoinBounds, StreamIntervalJoin};
+pub use stream_local_hash_agg::StreamLocalHashAgg;
 pub use stream_local_simple_agg::StreamLocalSimpleAgg;
+pub use stream_lookup_join::StreamLookupJoin;
 pub use stream_materialize::StreamMaterialize;
+pub use stream_now::StreamNow;
+pub use stream_over_window::StreamOverWindow;
 pub use stream_project::StreamProject;
 pub use stream_project_set::StreamProjectSet;
+pub use stream_session_window::StreamSessionWindow;
 pub use stream_sink::StreamSink;
+pub use stream_sort::StreamSort;
 pub use stream_source::StreamSource;
 pub use stream_table_scan::StreamTableScan;
+pub use stream_temporal_join::StreamTemporalJoin;
 pub use stream_topn::StreamTopN;
 
 use crate::session::OptimizerContextRef;
@@ -428,6 +479,9 @@ macro_rules! for_all_plan_nodes {
             , { Logical, ProjectSet }
             , { Logical, Union }
             , { Logical, OverAgg }
+            , { Logical, SessionWindow }
+            , { Logical, RecursiveUnion }
+            , { Logical, WorkTable }
             // , { Logical, Sort } we don't need a LogicalSort, just require the Order
             , { Batch, SimpleAgg }
             , { Batch, HashAgg }
@@ -452,6 +506,11 @@ macro_rules! for_all_plan_nodes {
             , { Batch, ProjectSet }
             , { Batch, Union }
             , { Batch, GroupTopN }
+            , { Batch, OverWindow }
+            , { Batch, SessionWindow }
+            , { Batch, RecursiveUnion }
+            , { Batch, WorkTable }
+            , { Batch, NestedLoopApply }
             , { Stream, Project }
             , { Stream, Filter }
             , { Stream, TableScan }
@@ -461,6 +520,7 @@ macro_rules! for_all_plan_nodes {
             , { Stream, Exchange }
             , { Stream, HashAgg }
             , { Stream, LocalSimpleAgg }
+            , { Stream, LocalHashAgg }
             , { Stream, GlobalSimpleAgg }
             , { Stream, Materialize }
             , { Stream, TopN }
@@ -471,6 +531,13 @@ macro_rules! for_all_plan_nodes {
             , { Stream, DynamicFilter }
             , { Stream, ProjectSet }
             , { Stream, GroupTopN }
+            , { Stream, OverWindow }
+            , { Stream, SessionWindow }
+            , { Stream, TemporalJoin }
+            , { Stream, Sort }
+            , { Stream, IntervalJoin }
+            , { Stream, Now }
+            , { Stream, LookupJoin }
         }
     };
 }
@@ -500,6 +567,9 @@ macro_rules! for_logical_plan_nodes {
             , { Logical, ProjectSet }
             , { Logical, Union }
             , { Logical, OverAgg }
+            , { Logical, SessionWindow }
+            , { Logical, RecursiveUnion }
+            , { Logical, WorkTable }
             // , { Logical, Sort} not sure if we will support Order by clause in subquery/view/MV
             // if we don't support that, we don't need LogicalSort, just require the Order at the top of query
         }
@@ -534,6 +604,11 @@ macro_rules! for_batch_plan_nodes {
             , { Batch, ProjectSet }
             , { Batch, Union }
             , { Batch, GroupTopN }
+            , { Batch, OverWindow }
+            , { Batch, SessionWindow }
+            , { Batch, RecursiveUnion }
+            , { Batch, WorkTable }
+            , { Batch, NestedLoopApply }
         }
     };
 }
@@ -552,6 +627,7 @@ macro_rules! for_stream_plan_nodes {
             , { Stream, Source }
             , { Stream, HashAgg }
             , { Stream, LocalSimpleAgg }
+            , { Stream, LocalHashAgg }
             , { Stream, GlobalSimpleAgg }
             , { Stream, Materialize }
             , { Stream, TopN }
@@ -562,6 +638,13 @@ macro_rules! for_stream_plan_nodes {
             , { Stream, DynamicFilter }
             , { Stream, ProjectSet }
             , { Stream, GroupTopN }
+            , { Stream, OverWindow }
+            , { Stream, SessionWindow }
+            , { Stream, TemporalJoin }
+            , { Stream, Sort }
+            , { Stream, IntervalJoin }
+            , { Stream, Now }
+            , { Stream, LookupJoin }
         }
     };
 }
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use generic::PlanAggCall;
 use pb::stream_node as pb_node;
 use risingwave_common::catalog::{ColumnDesc, Field, Schema};
@@ -23,7 +25,8 @@ use risingwave_pb::stream_plan as pb;
 use super::generic::{GenericPlanNode, GenericPlanRef};
 use super::utils::TableCatalogBuilder;
 use super::{generic, EqJoinPredicate, PlanNodeId};
-use crate::expr::{Expr, ExprImpl};
+use crate::catalog::SecretId;
+use crate::expr::{try_derive_watermark, Expr, ExprImpl};
 use crate::optimizer::property::{Distribution, FieldOrder};
 use crate::session::OptimizerContextRef;
 use crate::stream_fragmenter::BuildFragmentGraphState;
@@ -296,6 +299,7 @@ pub struct Project {
 pub struct Sink {
     pub input: PlanRef,
     pub properties: WithOptions,
+    pub secret_refs: HashMap<String, SecretId>,
 }
 
 /// [`Source`] represents a table/connector source at the very beginning of the graph.
@@ -393,6 +397,7 @@ pub fn to_stream_prost_body(
                 condition,
                 left_table: Some(left_table.to_internal_table_prost()),
                 right_table: Some(right_table.to_internal_table_prost()),
+                is_monotonic: me.is_monotonic,
             })
         }
         Node::DeltaJoin(me) => {
@@ -478,6 +483,7 @@ pub fn to_stream_prost_body(
             let me = &me.core;
             let result_table = me.infer_result_table(base, None);
             let agg_states = me.infer_stream_agg_state(base, None);
+            let distinct_dedup_tables = me.infer_distinct_dedup_tables(base, None);
 
             ProstNode::GlobalSimpleAgg(SimpleAggNode {
                 agg_calls: me.agg_calls.iter().map(PlanAggCall::to_protobuf).collect(),
@@ -497,6 +503,17 @@ pub fn to_stream_prost_body(
                         .with_id(state.gen_table_id_wrapped())
                         .to_internal_table_prost(),
                 ),
+                distinct_dedup_tables: distinct_dedup_tables
+                    .into_iter()
+                    .map(|(distinct_col, table)| {
+                        (
+                            distinct_col as u32,
+                            table
+                                .with_id(state.gen_table_id_wrapped())
+                                .to_internal_table_prost(),
+                        )
+                    })
+                    .collect(),
             })
         }
         Node::GroupTopN(me) => {
@@ -518,6 +535,7 @@ pub fn to_stream_prost_body(
         Node::HashAgg(me) => {
             let result_table = me.core.infer_result_table(base, me.vnode_col_idx);
             let agg_states = me.core.infer_stream_agg_state(base, me.vnode_col_idx);
+            let distinct_dedup_tables = me.core.infer_distinct_dedup_tables(base, me.vnode_col_idx);
 
             ProstNode::HashAgg(HashAggNode {
                 group_key: me.core.group_key.iter().map(|&idx| idx as u32).collect(),
@@ -538,6 +556,17 @@ pub fn to_stream_prost_body(
                         .with_id(state.gen_table_id_wrapped())
                         .to_internal_table_prost(),
                 ),
+                distinct_dedup_tables: distinct_dedup_tables
+                    .into_iter()
+                    .map(|(distinct_col, table)| {
+                        (
+                            distinct_col as u32,
+                            table
+                                .with_id(state.gen_table_id_wrapped())
+                                .to_internal_table_prost(),
+                        )
+                    })
+                    .collect(),
             })
         }
         Node::HashJoin(me) => {
@@ -610,6 +639,7 @@ pub fn to_stream_prost_body(
                     .collect(),
                 agg_call_states: vec![],
                 result_table: None,
+                distinct_dedup_tables: Default::default(),
                 is_append_only: me.input.0.append_only,
             })
         }
@@ -634,39 +664,58 @@ pub fn to_stream_prost_body(
         }
         Node::Project(me) => {
             let me = &me.core;
+            let (watermark_input_key, watermark_output_key) = me
+                .exprs
+                .iter()
+                .enumerate()
+                .filter_map(|(output_idx, expr)| {
+                    try_derive_watermark(expr)
+                        .map(|input_idx| (input_idx as u32, output_idx as u32))
+                })
+                .unzip();
             ProstNode::Project(ProjectNode {
                 select_list: me.exprs.iter().map(Expr::to_expr_proto).collect(),
+                watermark_input_key,
+                watermark_output_key,
             })
         }
         Node::Sink(me) => {
             let (_, input_node) = &*me.input;
-            let table_desc = match input_node {
-                Node::TableScan(table_scan) => &*table_scan.core.table_desc,
-                _ => unreachable!(),
+            // A sink created from a query has no associated table.
+            let table_id = match input_node {
+                Node::TableScan(table_scan) => table_scan.core.table_desc.table_id.table_id(),
+                _ => 0,
             };
 
             ProstNode::Sink(SinkNode {
-                table_id: table_desc.table_id.table_id(),
+                table_id,
                 column_ids: vec![], // TODO(nanderstabel): fix empty Vector
                 properties: me.properties.inner().clone(),
+                log_store_table: None,
+                secret_refs: me.secret_refs.clone(),
+                // Will be filled by meta.
+                sink_id: 0,
             })
         }
         Node::Source(me) => {
-            let me = &me.core.catalog;
+            let me = &me.core;
             ProstNode::Source(SourceNode {
-                source_id: me.id,
+                source_id: me.catalog.id,
                 state_table: Some(
                     generic::Source::infer_internal_table_catalog(base)
                         .with_id(state.gen_table_id_wrapped())
                         .to_internal_table_prost(),
                 ),
-                info: Some(me.info.clone()),
+                info: Some(me.catalog.info.clone()),
                 row_id_index: me
-                    .row_id_index
+                    .row_id_index()
                     .map(|index| ColumnIndex { index: index as _ }),
-                columns: me.columns.iter().map(|c| c.to_protobuf()).collect(),
-                pk_column_ids: me.pk_col_ids.iter().map(Into::into).collect(),
-                properties: me.properties.clone(),
+                columns: me.columns().map(|c| c.to_protobuf()).collect(),
+                pk_column_ids: me.catalog.pk_col_ids.iter().map(Into::into).collect(),
+                properties: me.properties(),
+                watermark_descs: me.catalog.watermark_descs.clone(),
+                secret_refs: me.catalog.secret_refs.clone(),
+                dml_enabled: me.dml_enabled,
             })
         }
         Node::TopN(me) => {
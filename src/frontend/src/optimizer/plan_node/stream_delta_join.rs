@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use risingwave_common::catalog::{ColumnDesc, Schema};
 use risingwave_pb::plan_common::JoinType;
 use risingwave_pb::stream_plan::stream_node::NodeBody;
@@ -25,8 +26,8 @@ use crate::optimizer::plan_node::utils::IndicesDisplay;
 use crate::optimizer::plan_node::{EqJoinPredicate, EqJoinPredicateDisplay};
 use crate::stream_fragmenter::BuildFragmentGraphState;
 
-/// [`StreamDeltaJoin`] implements [`super::LogicalJoin`] with delta join. It requires its two
-/// inputs to be indexes.
+/// [`StreamDeltaJoin`] implements [`super::LogicalJoin`] with delta join. It requires each of its
+/// two inputs to be either an index or another delta join on the same join key.
 #[derive(Debug, Clone)]
 pub struct StreamDeltaJoin {
     pub base: PlanBase,
@@ -62,6 +63,7 @@ impl StreamDeltaJoin {
             logical.functional_dependency().clone(),
             dist,
             append_only,
+            FixedBitSet::with_capacity(logical.schema().len()),
         );
 
         Self {
@@ -145,12 +147,28 @@ impl_plan_tree_node_for_binary! { StreamDeltaJoin }
 
 impl StreamNode for StreamDeltaJoin {
     fn to_stream_prost_body(&self, _state: &mut BuildFragmentGraphState) -> NodeBody {
-        let left = self.left();
-        let right = self.right();
-        let left_table = left.as_stream_index_scan().unwrap();
-        let right_table = right.as_stream_index_scan().unwrap();
-        let left_table_desc = left_table.logical().table_desc();
-        let right_table_desc = right_table.logical().table_desc();
+        /// Returns the table id and the arrangement info of the index scanned by `input`, or
+        /// nothing if `input` is another delta join.
+        fn arrangement_of(input: PlanRef) -> (u32, Option<ArrangementInfo>) {
+            if let Some(index_scan) = input.as_stream_index_scan() {
+                let table_desc = index_scan.logical().table_desc();
+                let info = ArrangementInfo {
+                    arrange_key_orders: table_desc.arrange_key_orders_prost(),
+                    column_descs: index_scan
+                        .logical()
+                        .column_descs()
+                        .iter()
+                        .map(ColumnDesc::to_protobuf)
+                        .collect(),
+                };
+                (table_desc.table_id.table_id(), Some(info))
+            } else {
+                assert!(input.as_stream_delta_join().is_some());
+                (0, None)
+            }
+        }
+        let (left_table_id, left_info) = arrangement_of(self.left());
+        let (right_table_id, right_info) = arrangement_of(self.right());
 
         // TODO: add a separate delta join node in proto, or move fragmenter to frontend so that we
         // don't need an intermediate representation.
@@ -173,26 +191,10 @@ impl StreamNode for StreamDeltaJoin {
                 .other_cond()
                 .as_expr_unless_true()
                 .map(|x| x.to_expr_proto()),
-            left_table_id: left_table_desc.table_id.table_id(),
-            right_table_id: right_table_desc.table_id.table_id(),
-            left_info: Some(ArrangementInfo {
-                arrange_key_orders: left_table_desc.arrange_key_orders_prost(),
-                column_descs: left_table
-                    .logical()
-                    .column_descs()
-                    .iter()
-                    .map(ColumnDesc::to_protobuf)
-                    .collect(),
-            }),
-            right_info: Some(ArrangementInfo {
-                arrange_key_orders: right_table_desc.arrange_key_orders_prost(),
-                column_descs: right_table
-                    .logical()
-                    .column_descs()
-                    .iter()
-                    .map(ColumnDesc::to_protobuf)
-                    .collect(),
-            }),
+            left_table_id,
+            right_table_id,
+            left_info,
+            right_info,
             output_indices: self
                 .logical
                 .output_indices()
@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_common::catalog::Schema;
 use risingwave_pb::stream_plan::stream_node::NodeBody;
@@ -35,6 +36,27 @@ pub struct StreamDynamicFilter {
 
 impl StreamDynamicFilter {
     pub fn new(left_index: usize, predicate: Condition, left: PlanRef, right: PlanRef) -> Self {
+        Self::new_inner(left_index, predicate, left, right, false)
+    }
+
+    /// Creates a dynamic filter whose right value only moves in the direction that makes the
+    /// predicate stricter, e.g. the time of `now()` compared by `>`.
+    pub fn new_monotonic(
+        left_index: usize,
+        predicate: Condition,
+        left: PlanRef,
+        right: PlanRef,
+    ) -> Self {
+        Self::new_inner(left_index, predicate, left, right, true)
+    }
+
+    fn new_inner(
+        left_index: usize,
+        predicate: Condition,
+        left: PlanRef,
+        right: PlanRef,
+        is_monotonic: bool,
+    ) -> Self {
         // TODO: derive from input
         let base = PlanBase::new_stream(
             left.ctx(),
@@ -44,12 +66,14 @@ impl StreamDynamicFilter {
             left.distribution().clone(),
             false, /* we can have a new abstraction for append only and monotonically increasing
                     * in the future */
+            FixedBitSet::with_capacity(left.schema().len()),
         );
         let core = generic::DynamicFilter {
             predicate,
             left_index,
             left,
             right,
+            is_monotonic,
         };
         Self { base, core }
     }
@@ -100,11 +124,12 @@ impl PlanTreeNodeBinary for StreamDynamicFilter {
     }
 
     fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
-        Self::new(
+        Self::new_inner(
             self.core.left_index,
             self.core.predicate.clone(),
             left,
             right,
+            self.core.is_monotonic,
         )
     }
 }
@@ -130,6 +155,7 @@ impl StreamNode for StreamDynamicFilter {
             condition,
             left_table: Some(left_table.to_internal_table_prost()),
             right_table: Some(right_table.to_internal_table_prost()),
+            is_monotonic: self.core.is_monotonic,
         })
     }
 }
@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use risingwave_pb::stream_plan::expand_node::Subset;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 use risingwave_pb::stream_plan::ExpandNode;
@@ -38,6 +39,15 @@ impl StreamExpand {
             Distribution::Broadcast => unreachable!(),
         };
 
+        let input = logical.input();
+        let input_len = input.schema().len();
+        let mut watermark_columns = FixedBitSet::with_capacity(logical.schema().len());
+        for idx in input.watermark_columns().ones() {
+            // The input columns are copied to the first and second half of the output.
+            watermark_columns.insert(idx);
+            watermark_columns.insert(idx + input_len);
+        }
+
         let base = PlanBase::new_stream(
             logical.base.ctx.clone(),
             logical.schema().clone(),
@@ -45,6 +55,7 @@ impl StreamExpand {
             logical.functional_dependency().clone(),
             dist,
             logical.input().append_only(),
+            watermark_columns,
         );
         StreamExpand { base, logical }
     }
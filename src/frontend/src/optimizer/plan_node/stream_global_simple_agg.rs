@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 
 use super::generic::PlanAggCall;
@@ -47,6 +48,7 @@ impl StreamGlobalSimpleAgg {
             logical.functional_dependency().clone(),
             dist,
             false,
+            FixedBitSet::with_capacity(logical.schema().len()),
         );
         StreamGlobalSimpleAgg { base, logical }
     }
@@ -87,6 +89,7 @@ impl StreamNode for StreamGlobalSimpleAgg {
         use risingwave_pb::stream_plan::*;
         let result_table = self.logical.infer_result_table(None);
         let agg_states = self.logical.infer_stream_agg_state(None);
+        let distinct_dedup_tables = self.logical.infer_distinct_dedup_tables(None);
 
         ProstStreamNode::GlobalSimpleAgg(SimpleAggNode {
             agg_calls: self
@@ -111,6 +114,17 @@ impl StreamNode for StreamGlobalSimpleAgg {
                     .with_id(state.gen_table_id_wrapped())
                     .to_internal_table_prost(),
             ),
+            distinct_dedup_tables: distinct_dedup_tables
+                .into_iter()
+                .map(|(distinct_col, table)| {
+                    (
+                        distinct_col as u32,
+                        table
+                            .with_id(state.gen_table_id_wrapped())
+                            .to_internal_table_prost(),
+                    )
+                })
+                .collect(),
         })
     }
 }
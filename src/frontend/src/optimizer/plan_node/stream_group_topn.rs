@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 
 use super::{LogicalTopN, PlanBase, PlanTreeNodeUnary, StreamNode};
@@ -42,6 +43,7 @@ impl StreamGroupTopN {
             input.functional_dependency().clone(),
             input.distribution().clone(),
             false,
+            FixedBitSet::with_capacity(logical.schema().len()),
         );
         StreamGroupTopN {
             base,
@@ -86,8 +88,11 @@ impl StreamNode for StreamGroupTopN {
             table: Some(table.to_internal_table_prost()),
             order_by_len: self.topn_order().len() as u32,
         };
-
-        ProstStreamNode::GroupTopN(group_topn_node)
+        if self.input().append_only() && !self.with_ties() {
+            ProstStreamNode::AppendOnlyGroupTopN(group_topn_node)
+        } else {
+            ProstStreamNode::GroupTopN(group_topn_node)
+        }
     }
 }
 
@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 
 use super::generic::PlanAggCall;
@@ -42,6 +43,15 @@ impl StreamHashAgg {
                 .rewrite_provided_distribution(input_dist),
             d => d.clone(),
         };
+
+        let mut watermark_columns = FixedBitSet::with_capacity(logical.schema().len());
+        // Watermark column(s) must be in group key.
+        for (idx, input_idx) in logical.group_key().iter().enumerate() {
+            if input.watermark_columns().contains(*input_idx) {
+                watermark_columns.insert(idx);
+            }
+        }
+
         // Hash agg executor might change the append-only behavior of the stream.
         let base = PlanBase::new_stream(
             ctx,
@@ -50,6 +60,7 @@ impl StreamHashAgg {
             logical.functional_dependency().clone(),
             dist,
             false,
+            watermark_columns,
         );
         StreamHashAgg {
             base,
@@ -93,6 +104,7 @@ impl StreamNode for StreamHashAgg {
         use risingwave_pb::stream_plan::*;
         let result_table = self.logical.infer_result_table(self.vnode_col_idx);
         let agg_states = self.logical.infer_stream_agg_state(self.vnode_col_idx);
+        let distinct_dedup_tables = self.logical.infer_distinct_dedup_tables(self.vnode_col_idx);
 
         ProstStreamNode::HashAgg(HashAggNode {
             group_key: self.group_key().iter().map(|idx| *idx as u32).collect(),
@@ -112,6 +124,17 @@ impl StreamNode for StreamHashAgg {
                     .with_id(state.gen_table_id_wrapped())
                     .to_internal_table_prost(),
             ),
+            distinct_dedup_tables: distinct_dedup_tables
+                .into_iter()
+                .map(|(distinct_col, table)| {
+                    (
+                        distinct_col as u32,
+                        table
+                            .with_id(state.gen_table_id_wrapped())
+                            .to_internal_table_prost(),
+                    )
+                })
+                .collect(),
         })
     }
 }
@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_common::catalog::Schema;
 use risingwave_pb::plan_common::JoinType;
@@ -59,6 +60,30 @@ impl StreamHashJoin {
             &logical,
         );
 
+        let watermark_columns = {
+            let l2i = logical.l2i_col_mapping();
+            let r2i = logical.r2i_col_mapping();
+            let i2o = logical.i2o_col_mapping();
+            let mut watermark_columns = FixedBitSet::with_capacity(logical.schema().len());
+            // A join key is a watermark column only if both sides have watermarks on it, and the
+            // output watermark is the smaller one of them.
+            for (left_idx, right_idx) in eq_join_predicate.eq_indexes() {
+                if logical.left().watermark_columns().contains(left_idx)
+                    && logical.right().watermark_columns().contains(right_idx)
+                {
+                    for internal_idx in [l2i.try_map(left_idx), r2i.try_map(right_idx)]
+                        .into_iter()
+                        .flatten()
+                    {
+                        if let Some(output_idx) = i2o.try_map(internal_idx) {
+                            watermark_columns.insert(output_idx);
+                        }
+                    }
+                }
+            }
+            watermark_columns
+        };
+
         // TODO: derive from input
         let base = PlanBase::new_stream(
             ctx,
@@ -67,6 +92,7 @@ impl StreamHashJoin {
             logical.functional_dependency().clone(),
             dist,
             append_only,
+            watermark_columns,
         );
 
         Self {
@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 use risingwave_pb::stream_plan::HopWindowNode;
 
@@ -36,6 +37,21 @@ impl StreamHopWindow {
         let i2o = logical.i2o_col_mapping();
         let dist = i2o.rewrite_provided_distribution(input.distribution());
 
+        let mut watermark_columns = FixedBitSet::with_capacity(logical.schema().len());
+        let input_watermark = input.watermark_columns();
+        for (output_idx, &input_idx) in logical.core.output_indices.iter().enumerate() {
+            let has_watermark = if input_idx < input.schema().len() {
+                input_watermark.contains(input_idx)
+            } else {
+                // `window_start` and `window_end` are derived from the watermark on the time
+                // column.
+                input_watermark.contains(logical.core.time_col.index)
+            };
+            if has_watermark {
+                watermark_columns.insert(output_idx);
+            }
+        }
+
         let base = PlanBase::new_stream(
             ctx,
             logical.schema().clone(),
@@ -43,6 +59,7 @@ impl StreamHopWindow {
             logical.functional_dependency().clone(),
             dist,
             logical.input().append_only(),
+            watermark_columns,
         );
         Self { base, logical }
     }
@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 use risingwave_pb::stream_plan::StreamNode as ProstStreamPlan;
@@ -48,6 +49,7 @@ impl StreamIndexScan {
             logical.functional_dependency().clone(),
             Distribution::HashShard(logical.distribution_key().unwrap()),
             false, // TODO: determine the `append-only` field of table scan
+            FixedBitSet::with_capacity(logical.schema().len()),
         );
         Self {
             base,
@@ -152,6 +154,9 @@ impl StreamIndexScan {
                 table_id: self.logical.table_desc().table_id.table_id,
                 same_worker_node: true,
                 disable_rearrange: true,
+                // The arrangement shares the state of the upstream table, so the snapshot must be
+                // consumed before the changes of the upstream.
+                chain_type: ChainType::Chain as i32,
                 // The fields from upstream
                 upstream_fields: self
                     .logical
@@ -171,6 +176,10 @@ impl StreamIndexScan {
                     .map(|&i| i as _)
                     .collect(),
                 is_singleton: false,
+                upstream_only: false,
+                // The upstream is blocked until the snapshot is consumed, so the snapshot can't be
+                // throttled without stalling the barriers.
+                rate_limit: 0,
             })),
             stream_key,
             operator_id: self.base.id.0 as u64,
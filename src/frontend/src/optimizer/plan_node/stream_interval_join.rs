@@ -0,0 +1,384 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use itertools::Itertools;
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_pb::plan_common::JoinType;
+use risingwave_pb::stream_plan::stream_node::NodeBody;
+use risingwave_pb::stream_plan::IntervalJoinNode;
+
+use super::utils::TableCatalogBuilder;
+use super::{LogicalJoin, PlanBase, PlanRef, PlanTreeNodeBinary, StreamHashJoin, StreamNode};
+use crate::expr::{Expr, ExprImpl, ExprType};
+use crate::optimizer::plan_node::utils::IndicesDisplay;
+use crate::optimizer::plan_node::{EqJoinPredicate, EqJoinPredicateDisplay};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+use crate::utils::Condition;
+use crate::TableCatalog;
+
+/// The bounds of an interval join, i.e. the join condition implies
+/// `right_time + lower_bound_ms <= left_time <= right_time + upper_bound_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntervalJoinBounds {
+    /// The index of the time column in the left input.
+    pub left_time_col: usize,
+    /// The index of the time column in the right input.
+    pub right_time_col: usize,
+    pub lower_bound_ms: i64,
+    pub upper_bound_ms: i64,
+}
+
+impl IntervalJoinBounds {
+    /// Finds a pair of time columns whose difference is bounded on both sides by the comparisons
+    /// in `cond`, like `l.ts BETWEEN r.ts - INTERVAL '1' HOUR AND r.ts + INTERVAL '1' HOUR`. The
+    /// offsets must be constant intervals without months, which have fixed lengths.
+    pub fn extract(cond: &Condition, left_len: usize, input_schema: &Schema) -> Option<Self> {
+        // The lower and upper bounds of each pair of left and right time columns.
+        let mut bounds: Vec<((usize, usize), Option<i64>, Option<i64>)> = vec![];
+        for expr in &cond.conjunctions {
+            let ExprImpl::FunctionCall(func_call) = expr else {
+                continue;
+            };
+            let ty = func_call.get_expr_type();
+            if !matches!(
+                ty,
+                ExprType::LessThan
+                    | ExprType::LessThanOrEqual
+                    | ExprType::GreaterThan
+                    | ExprType::GreaterThanOrEqual
+            ) {
+                continue;
+            }
+            let (_, lhs, rhs) = func_call.clone().decompose_as_binary();
+            let (Some((lhs_col, lhs_offset)), Some((rhs_col, rhs_offset))) =
+                (as_time_with_offset(&lhs), as_time_with_offset(&rhs)) else {
+                continue;
+            };
+            // Normalize it to `left_time <op> right_time + offset`.
+            let (left_col, right_col, offset, is_lower) = if lhs_col < left_len
+                && rhs_col >= left_len
+            {
+                let is_lower = matches!(ty, ExprType::GreaterThan | ExprType::GreaterThanOrEqual);
+                (lhs_col, rhs_col, rhs_offset - lhs_offset, is_lower)
+            } else if rhs_col < left_len && lhs_col >= left_len {
+                let is_lower = matches!(ty, ExprType::LessThan | ExprType::LessThanOrEqual);
+                (rhs_col, lhs_col, lhs_offset - rhs_offset, is_lower)
+            } else {
+                continue;
+            };
+            if !matches!(
+                input_schema.fields()[left_col].data_type(),
+                DataType::Timestamp | DataType::Timestampz
+            ) {
+                continue;
+            }
+
+            let key = (left_col, right_col - left_len);
+            let idx = match bounds.iter().position(|(k, _, _)| *k == key) {
+                Some(idx) => idx,
+                None => {
+                    bounds.push((key, None, None));
+                    bounds.len() - 1
+                }
+            };
+            let (_, lower, upper) = &mut bounds[idx];
+            if is_lower {
+                *lower = Some(lower.map_or(offset, |lower| lower.max(offset)));
+            } else {
+                *upper = Some(upper.map_or(offset, |upper| upper.min(offset)));
+            }
+        }
+
+        bounds
+            .into_iter()
+            .find_map(|((left_time_col, right_time_col), lower, upper)| {
+                Some(Self {
+                    left_time_col,
+                    right_time_col,
+                    lower_bound_ms: lower?,
+                    upper_bound_ms: upper?,
+                })
+            })
+    }
+}
+
+/// Matches `col`, `col + interval` or `col - interval`, and returns the column index and the
+/// offset in milliseconds.
+fn as_time_with_offset(expr: &ExprImpl) -> Option<(usize, i64)> {
+    let interval_ms = |expr: &ExprImpl| {
+        if !expr.is_const() {
+            return None;
+        }
+        match expr.eval_row_const().ok()? {
+            Some(ScalarImpl::Interval(interval)) if interval.get_months() == 0 => {
+                Some(interval.get_days() as i64 * 24 * 60 * 60 * 1000 + interval.get_ms())
+            }
+            _ => None,
+        }
+    };
+    match expr {
+        ExprImpl::InputRef(input_ref) => Some((input_ref.index(), 0)),
+        ExprImpl::FunctionCall(func_call)
+            if matches!(
+                func_call.get_expr_type(),
+                ExprType::Add | ExprType::Subtract
+            ) =>
+        {
+            match func_call.clone().decompose_as_binary() {
+                (ExprType::Add, ExprImpl::InputRef(input_ref), offset)
+                | (ExprType::Add, offset, ExprImpl::InputRef(input_ref)) => {
+                    Some((input_ref.index(), interval_ms(&offset)?))
+                }
+                (ExprType::Subtract, ExprImpl::InputRef(input_ref), offset) => {
+                    Some((input_ref.index(), -interval_ms(&offset)?))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// [`StreamIntervalJoin`] implements an inner [`LogicalJoin`] whose condition bounds the time
+/// column of the left input within an interval around the time column of the right input. Both
+/// time columns have watermarks, so the rows that can no longer be matched by any future row of
+/// the other side are cleaned from the state, which is indexed by time bucket.
+#[derive(Debug, Clone)]
+pub struct StreamIntervalJoin {
+    pub base: PlanBase,
+    logical: LogicalJoin,
+
+    /// The join condition must be equivalent to `logical.on`, but separated into equal and
+    /// non-equal parts to facilitate execution later
+    eq_join_predicate: EqJoinPredicate,
+
+    bounds: IntervalJoinBounds,
+}
+
+impl StreamIntervalJoin {
+    pub fn new(
+        logical: LogicalJoin,
+        eq_join_predicate: EqJoinPredicate,
+        bounds: IntervalJoinBounds,
+    ) -> Self {
+        let ctx = logical.base.ctx.clone();
+        assert_eq!(logical.join_type(), JoinType::Inner);
+        assert!(logical
+            .left()
+            .watermark_columns()
+            .contains(bounds.left_time_col));
+        assert!(logical
+            .right()
+            .watermark_columns()
+            .contains(bounds.right_time_col));
+
+        let append_only = logical.left().append_only() && logical.right().append_only();
+        let dist = StreamHashJoin::derive_dist(
+            logical.left().distribution(),
+            logical.right().distribution(),
+            &logical,
+        );
+
+        // Both time columns have watermarks derived from the ones of the inputs.
+        let mut watermark_columns = FixedBitSet::with_capacity(logical.schema().len());
+        let l2o = logical
+            .l2i_col_mapping()
+            .composite(&logical.i2o_col_mapping());
+        let r2o = logical
+            .r2i_col_mapping()
+            .composite(&logical.i2o_col_mapping());
+        for output_idx in [
+            l2o.try_map(bounds.left_time_col),
+            r2o.try_map(bounds.right_time_col),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            watermark_columns.insert(output_idx);
+        }
+
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            logical.base.logical_pk.to_vec(),
+            logical.functional_dependency().clone(),
+            dist,
+            append_only,
+            watermark_columns,
+        );
+
+        Self {
+            base,
+            logical,
+            eq_join_predicate,
+            bounds,
+        }
+    }
+
+    /// Get a reference to the interval join's eq join predicate.
+    pub fn eq_join_predicate(&self) -> &EqJoinPredicate {
+        &self.eq_join_predicate
+    }
+
+    /// The state table of each side is ordered by the join key, the time column and then the
+    /// stream key of the input.
+    fn infer_state_table(
+        input: &PlanRef,
+        join_key_indices: Vec<usize>,
+        time_col: usize,
+    ) -> TableCatalog {
+        let mut internal_table_catalog_builder =
+            TableCatalogBuilder::new(input.ctx().inner().with_options.internal_table_subset());
+
+        input.schema().fields().iter().for_each(|field| {
+            internal_table_catalog_builder.add_column(field);
+        });
+
+        let mut pk_indices = join_key_indices;
+        pk_indices.push(time_col);
+        for idx in input.logical_pk() {
+            if !pk_indices.contains(idx) {
+                pk_indices.push(*idx);
+            }
+        }
+        pk_indices.iter().for_each(|idx| {
+            internal_table_catalog_builder.add_order_column(*idx, OrderType::Ascending)
+        });
+
+        let dist_keys = input.distribution().dist_column_indices().to_vec();
+        internal_table_catalog_builder.build(dist_keys)
+    }
+}
+
+impl fmt::Display for StreamIntervalJoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verbose = self.base.ctx.is_explain_verbose();
+        let mut builder = f.debug_struct("StreamIntervalJoin");
+        builder.field("type", &format_args!("{:?}", self.logical.join_type()));
+
+        let mut concat_schema = self.left().schema().fields.clone();
+        concat_schema.extend(self.right().schema().fields.clone());
+        let concat_schema = Schema::new(concat_schema);
+        builder.field(
+            "predicate",
+            &format_args!(
+                "{}",
+                EqJoinPredicateDisplay {
+                    eq_join_predicate: self.eq_join_predicate(),
+                    input_schema: &concat_schema
+                }
+            ),
+        );
+
+        if verbose {
+            if self
+                .logical
+                .output_indices()
+                .iter()
+                .copied()
+                .eq(0..self.logical.internal_column_num())
+            {
+                builder.field("output", &format_args!("all"));
+            } else {
+                builder.field(
+                    "output",
+                    &format_args!(
+                        "{:?}",
+                        &IndicesDisplay {
+                            indices: self.logical.output_indices(),
+                            input_schema: &concat_schema,
+                        }
+                    ),
+                );
+            }
+        }
+
+        builder.finish()
+    }
+}
+
+impl PlanTreeNodeBinary for StreamIntervalJoin {
+    fn left(&self) -> PlanRef {
+        self.logical.left()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.logical.right()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(
+            self.logical.clone_with_left_right(left, right),
+            self.eq_join_predicate.clone(),
+            self.bounds,
+        )
+    }
+}
+
+impl_plan_tree_node_for_binary! { StreamIntervalJoin }
+
+impl StreamNode for StreamIntervalJoin {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> NodeBody {
+        let left_key_indices = self.eq_join_predicate.left_eq_indexes();
+        let right_key_indices = self.eq_join_predicate.right_eq_indexes();
+
+        let left_table = Self::infer_state_table(
+            &self.left(),
+            left_key_indices.clone(),
+            self.bounds.left_time_col,
+        )
+        .with_id(state.gen_table_id_wrapped());
+        let right_table = Self::infer_state_table(
+            &self.right(),
+            right_key_indices.clone(),
+            self.bounds.right_time_col,
+        )
+        .with_id(state.gen_table_id_wrapped());
+
+        NodeBody::IntervalJoin(IntervalJoinNode {
+            join_type: self.logical.join_type() as i32,
+            left_key: left_key_indices
+                .into_iter()
+                .map(|idx| idx as i32)
+                .collect_vec(),
+            right_key: right_key_indices
+                .into_iter()
+                .map(|idx| idx as i32)
+                .collect_vec(),
+            null_safe: self.eq_join_predicate.null_safes(),
+            condition: self
+                .eq_join_predicate
+                .other_cond()
+                .as_expr_unless_true()
+                .map(|x| x.to_expr_proto()),
+            output_indices: self
+                .logical
+                .output_indices()
+                .iter()
+                .map(|&x| x as u32)
+                .collect(),
+            left_table: Some(left_table.to_internal_table_prost()),
+            right_table: Some(right_table.to_internal_table_prost()),
+            left_time_col: self.bounds.left_time_col as _,
+            right_time_col: self.bounds.right_time_col as _,
+            lower_bound_ms: self.bounds.lower_bound_ms,
+            upper_bound_ms: self.bounds.upper_bound_ms,
+        })
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+
+use super::generic::PlanAggCall;
+use super::{LogicalAgg, PlanBase, PlanRef, PlanTreeNodeUnary, StreamNode};
+use crate::optimizer::property::RequiredDist;
+use crate::stream_fragmenter::BuildFragmentGraphState;
+
+/// Streaming local hash agg.
+///
+/// Should only be used for stateless agg, including `sum`, `count` and *append-only* `min`/`max`.
+/// The partial results of the groups changed in an epoch are emitted as insertions on barrier, so
+/// the same group key may appear in multiple rows and the result can only be used by a global
+/// `StreamHashAgg` grouped by the same keys.
+#[derive(Debug, Clone)]
+pub struct StreamLocalHashAgg {
+    pub base: PlanBase,
+    logical: LogicalAgg,
+}
+
+impl StreamLocalHashAgg {
+    pub fn new(logical: LogicalAgg) -> Self {
+        let ctx = logical.base.ctx.clone();
+        let pk_indices = logical.base.logical_pk.to_vec();
+        let input = logical.input();
+        let input_dist = input.distribution();
+        debug_assert!(input_dist.satisfies(&RequiredDist::AnyShard));
+
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            pk_indices,
+            logical.functional_dependency().clone(),
+            logical
+                .i2o_col_mapping()
+                .rewrite_provided_distribution(input_dist),
+            input.append_only(),
+            FixedBitSet::with_capacity(logical.schema().len()),
+        );
+        StreamLocalHashAgg { base, logical }
+    }
+
+    pub fn agg_calls(&self) -> &[PlanAggCall] {
+        self.logical.agg_calls()
+    }
+
+    pub fn group_key(&self) -> &[usize] {
+        self.logical.group_key()
+    }
+}
+
+impl fmt::Display for StreamLocalHashAgg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamStatelessLocalHashAgg")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamLocalHashAgg {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+impl_plan_tree_node_for_unary! { StreamLocalHashAgg }
+
+impl StreamNode for StreamLocalHashAgg {
+    fn to_stream_prost_body(&self, _state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        use risingwave_pb::stream_plan::*;
+        ProstStreamNode::LocalHashAgg(HashAggNode {
+            group_key: self.group_key().iter().map(|idx| *idx as u32).collect(),
+            agg_calls: self
+                .agg_calls()
+                .iter()
+                .map(PlanAggCall::to_protobuf)
+                .collect(),
+            agg_call_states: vec![],
+            result_table: None,
+            distinct_dedup_tables: Default::default(),
+            is_append_only: self.input().append_only(),
+        })
+    }
+}
@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 
@@ -49,6 +50,7 @@ impl StreamLocalSimpleAgg {
             logical.functional_dependency().clone(),
             input_dist.clone(),
             input.append_only(),
+            FixedBitSet::with_capacity(logical.schema().len()),
         );
         StreamLocalSimpleAgg { base, logical }
     }
@@ -93,6 +95,7 @@ impl StreamNode for StreamLocalSimpleAgg {
                 .collect_vec(),
             agg_call_states: vec![],
             result_table: None,
+            distinct_dedup_tables: Default::default(),
             is_append_only: self.input().append_only(),
         })
     }
@@ -0,0 +1,205 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use itertools::Itertools;
+use risingwave_common::catalog::Schema;
+use risingwave_pb::plan_common::JoinType;
+use risingwave_pb::stream_plan::stream_node::NodeBody;
+use risingwave_pb::stream_plan::LookupJoinNode;
+
+use super::stream::HashJoin;
+use super::{LogicalJoin, PlanBase, PlanRef, PlanTreeNodeBinary, StreamNode};
+use crate::catalog::ColumnId;
+use crate::expr::Expr;
+use crate::optimizer::plan_node::utils::IndicesDisplay;
+use crate::optimizer::plan_node::{EqJoinPredicate, EqJoinPredicateDisplay};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+
+/// [`StreamLookupJoin`] joins an append-only left stream with a table, whose rows are looked up in
+/// the storage of the table by a prefix of its order key instead of being kept in the join state.
+/// Only the left rows are kept in the state, so that the changes of the table can be joined with
+/// them.
+///
+/// The right side is always a [`super::StreamTableScan`] without snapshot, which keeps the lookup
+/// co-located with the table and provides its changes.
+#[derive(Debug, Clone)]
+pub struct StreamLookupJoin {
+    pub base: PlanBase,
+    logical: LogicalJoin,
+
+    /// The equal conditions are on a prefix of the order key of the right table, in the order of
+    /// the order key.
+    eq_join_predicate: EqJoinPredicate,
+}
+
+impl StreamLookupJoin {
+    pub fn new(logical: LogicalJoin, eq_join_predicate: EqJoinPredicate) -> Self {
+        let ctx = logical.base.ctx.clone();
+        assert!(logical.left().append_only());
+        assert!(matches!(
+            logical.join_type(),
+            JoinType::Inner | JoinType::LeftOuter
+        ));
+
+        // The rows joined with the changes of the table are on the same partition as the left rows
+        // with the same key, so the output follows the distribution of the left side.
+        let l2o = logical
+            .l2i_col_mapping()
+            .composite(&logical.i2o_col_mapping());
+        let dist = l2o.rewrite_provided_distribution(logical.left().distribution());
+        let append_only = logical.join_type() == JoinType::Inner && logical.right().append_only();
+
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            logical.base.logical_pk.to_vec(),
+            logical.functional_dependency().clone(),
+            dist,
+            append_only,
+            FixedBitSet::with_capacity(logical.schema().len()),
+        );
+
+        Self {
+            base,
+            logical,
+            eq_join_predicate,
+        }
+    }
+
+    /// Get a reference to the lookup join's eq join predicate.
+    pub fn eq_join_predicate(&self) -> &EqJoinPredicate {
+        &self.eq_join_predicate
+    }
+}
+
+impl fmt::Display for StreamLookupJoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verbose = self.base.ctx.is_explain_verbose();
+        let mut builder = f.debug_struct("StreamLookupJoin");
+        builder.field("type", &format_args!("{:?}", self.logical.join_type()));
+
+        let right = self.right();
+        let scan = right.as_stream_table_scan().unwrap().logical();
+        builder.field("table", &format_args!("{}", scan.table_name()));
+
+        let mut concat_schema = self.left().schema().fields.clone();
+        concat_schema.extend(self.right().schema().fields.clone());
+        let concat_schema = Schema::new(concat_schema);
+        builder.field(
+            "predicate",
+            &format_args!(
+                "{}",
+                EqJoinPredicateDisplay {
+                    eq_join_predicate: self.eq_join_predicate(),
+                    input_schema: &concat_schema
+                }
+            ),
+        );
+
+        if verbose {
+            if self
+                .logical
+                .output_indices()
+                .iter()
+                .copied()
+                .eq(0..self.logical.internal_column_num())
+            {
+                builder.field("output", &format_args!("all"));
+            } else {
+                builder.field(
+                    "output",
+                    &format_args!(
+                        "{:?}",
+                        &IndicesDisplay {
+                            indices: self.logical.output_indices(),
+                            input_schema: &concat_schema,
+                        }
+                    ),
+                );
+            }
+        }
+
+        builder.finish()
+    }
+}
+
+impl PlanTreeNodeBinary for StreamLookupJoin {
+    fn left(&self) -> PlanRef {
+        self.logical.left()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.logical.right()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(
+            self.logical.clone_with_left_right(left, right),
+            self.eq_join_predicate.clone(),
+        )
+    }
+}
+
+impl_plan_tree_node_for_binary! { StreamLookupJoin }
+
+impl StreamNode for StreamLookupJoin {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> NodeBody {
+        let left_key_indices = self.eq_join_predicate.left_eq_indexes();
+        let right = self.right();
+        let scan = right.as_stream_table_scan().unwrap().logical();
+
+        // The left rows are kept in the same way as the hash join, without the degrees.
+        let (left_table, _) = HashJoin::infer_internal_and_degree_table_catalog(
+            self.left().plan_base(),
+            left_key_indices.clone(),
+        );
+        let left_table = left_table.with_id(state.gen_table_id_wrapped());
+
+        NodeBody::LookupJoin(LookupJoinNode {
+            join_type: self.logical.join_type() as i32,
+            left_key: left_key_indices
+                .into_iter()
+                .map(|idx| idx as i32)
+                .collect_vec(),
+            right_key: self
+                .eq_join_predicate
+                .right_eq_indexes()
+                .into_iter()
+                .map(|idx| idx as i32)
+                .collect_vec(),
+            null_safe: self.eq_join_predicate.null_safes(),
+            condition: self
+                .eq_join_predicate
+                .other_cond()
+                .as_expr_unless_true()
+                .map(|x| x.to_expr_proto()),
+            output_indices: self
+                .logical
+                .output_indices()
+                .iter()
+                .map(|&x| x as u32)
+                .collect(),
+            table_desc: Some(scan.table_desc().to_protobuf()),
+            table_output_column_ids: scan
+                .output_column_ids()
+                .into_iter()
+                .map(ColumnId::get_id)
+                .collect(),
+            left_table: Some(left_table.to_internal_table_prost()),
+        })
+    }
+}
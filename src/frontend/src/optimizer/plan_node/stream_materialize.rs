@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::assert_matches::assert_matches;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 use fixedbitset::FixedBitSet;
@@ -61,6 +61,7 @@ impl StreamMaterialize {
             input.functional_dependency().clone(),
             input.distribution().clone(),
             input.append_only(),
+            input.watermark_columns().clone(),
         ))
     }
 
@@ -185,6 +186,12 @@ impl StreamMaterialize {
             vnode_col_idx: None,
             value_indices,
             definition,
+            version: None,
+            watermark_columns: input.watermark_columns().clone(),
+            statistics: None,
+            description: None,
+            column_descriptions: HashMap::new(),
+            schema_stale: false,
         };
 
         Ok(Self { base, input, table })
@@ -0,0 +1,75 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::DataType;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::NowNode;
+
+use super::utils::TableCatalogBuilder;
+use super::{PlanBase, StreamNode};
+use crate::optimizer::property::{Distribution, FunctionalDependencySet};
+use crate::session::OptimizerContextRef;
+use crate::stream_fragmenter::BuildFragmentGraphState;
+
+/// [`StreamNow`] produces a single row of the current time, which is replaced by the time of the
+/// new epoch on every barrier. It is used as the right side of a temporal filter like
+/// `ts > now() - INTERVAL '1 hour'`.
+#[derive(Debug, Clone)]
+pub struct StreamNow {
+    pub base: PlanBase,
+}
+
+impl StreamNow {
+    pub fn new(ctx: OptimizerContextRef) -> Self {
+        let schema = Schema::new(vec![Field::with_name(DataType::Timestampz, "now")]);
+        let mut watermark_columns = FixedBitSet::with_capacity(1);
+        watermark_columns.set(0, true);
+        let base = PlanBase::new_stream(
+            ctx,
+            schema,
+            vec![],
+            FunctionalDependencySet::new(1),
+            Distribution::Single,
+            false,
+            watermark_columns,
+        );
+        Self { base }
+    }
+}
+
+impl_plan_tree_node_for_leaf! { StreamNow }
+
+impl fmt::Display for StreamNow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamNow").finish()
+    }
+}
+
+impl StreamNode for StreamNow {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        let mut table_catalog_builder =
+            TableCatalogBuilder::new(self.base.ctx.inner().with_options.internal_table_subset());
+        table_catalog_builder.add_column(&self.schema().fields()[0]);
+        let table_catalog = table_catalog_builder
+            .build(vec![])
+            .with_id(state.gen_table_id_wrapped());
+        ProstStreamNode::Now(NowNode {
+            state_table: Some(table_catalog.to_internal_table_prost()),
+        })
+    }
+}
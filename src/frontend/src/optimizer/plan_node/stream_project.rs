@@ -14,11 +14,12 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 use risingwave_pb::stream_plan::ProjectNode;
 
 use super::{LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary, StreamNode};
-use crate::expr::Expr;
+use crate::expr::{try_derive_watermark, Expr};
 use crate::stream_fragmenter::BuildFragmentGraphState;
 
 /// `StreamProject` implements [`super::LogicalProject`] to evaluate specified expressions on input
@@ -27,6 +28,9 @@ use crate::stream_fragmenter::BuildFragmentGraphState;
 pub struct StreamProject {
     pub base: PlanBase,
     logical: LogicalProject,
+    /// All the watermark derivations, (input_column_index, output_column_index). And the
+    /// derivation expression is the project's expression itself.
+    watermark_derivations: Vec<(usize, usize)>,
 }
 
 impl fmt::Display for StreamProject {
@@ -43,6 +47,18 @@ impl StreamProject {
         let distribution = logical
             .i2o_col_mapping()
             .rewrite_provided_distribution(input.distribution());
+
+        let mut watermark_derivations = vec![];
+        let mut watermark_columns = FixedBitSet::with_capacity(logical.schema().len());
+        for (output_idx, expr) in logical.exprs().iter().enumerate() {
+            if let Some(input_idx) = try_derive_watermark(expr)
+                && input.watermark_columns().contains(input_idx)
+            {
+                watermark_derivations.push((input_idx, output_idx));
+                watermark_columns.insert(output_idx);
+            }
+        }
+
         // Project executor won't change the append-only behavior of the stream, so it depends on
         // input's `append_only`.
         let base = PlanBase::new_stream(
@@ -52,8 +68,13 @@ impl StreamProject {
             logical.functional_dependency().clone(),
             distribution,
             logical.input().append_only(),
+            watermark_columns,
         );
-        StreamProject { base, logical }
+        StreamProject {
+            base,
+            logical,
+            watermark_derivations,
+        }
     }
 
     pub fn as_logical(&self) -> &LogicalProject {
@@ -74,6 +95,11 @@ impl_plan_tree_node_for_unary! {StreamProject}
 
 impl StreamNode for StreamProject {
     fn to_stream_prost_body(&self, _state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        let (watermark_input_key, watermark_output_key) = self
+            .watermark_derivations
+            .iter()
+            .map(|&(input_idx, output_idx)| (input_idx as u32, output_idx as u32))
+            .unzip();
         ProstStreamNode::Project(ProjectNode {
             select_list: self
                 .logical
@@ -81,6 +107,8 @@ impl StreamNode for StreamProject {
                 .iter()
                 .map(Expr::to_expr_proto)
                 .collect(),
+            watermark_input_key,
+            watermark_output_key,
         })
     }
 }
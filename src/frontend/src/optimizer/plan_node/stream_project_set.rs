@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 use risingwave_pb::stream_plan::ProjectSetNode;
@@ -44,6 +45,7 @@ impl StreamProjectSet {
             logical.functional_dependency().clone(),
             distribution,
             logical.input().append_only(),
+            FixedBitSet::with_capacity(logical.schema().len()),
         );
         StreamProjectSet { base, logical }
     }
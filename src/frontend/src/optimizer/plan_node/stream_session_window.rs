@@ -0,0 +1,117 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+
+use super::utils::TableCatalogBuilder;
+use super::{LogicalSessionWindow, PlanBase, PlanRef, PlanTreeNodeUnary, StreamNode};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+use crate::TableCatalog;
+
+/// `StreamSessionWindow` assigns the rows of its input, which is distributed by the partition key,
+/// to session windows. The output has the same stream key as the input.
+#[derive(Debug, Clone)]
+pub struct StreamSessionWindow {
+    pub base: PlanBase,
+    logical: LogicalSessionWindow,
+}
+
+impl StreamSessionWindow {
+    pub fn new(logical: LogicalSessionWindow) -> Self {
+        let input = logical.input();
+        let base = PlanBase::new_stream(
+            input.ctx(),
+            logical.schema().clone(),
+            input.logical_pk().to_vec(),
+            logical.functional_dependency().clone(),
+            input.distribution().clone(),
+            false,
+            FixedBitSet::with_capacity(logical.schema().len()),
+        );
+        StreamSessionWindow { base, logical }
+    }
+
+    /// The state table stores all input rows, firstly ordered by the partition key, then by the
+    /// time column and the input stream key, so that a partition can be read in the order of the
+    /// time column with a prefix scan.
+    pub fn infer_state_table(&self) -> TableCatalog {
+        let input = self.input();
+        let mut builder =
+            TableCatalogBuilder::new(self.base.ctx.inner().with_options.internal_table_subset());
+        input.schema().fields().iter().for_each(|field| {
+            builder.add_column(field);
+        });
+
+        let mut order_cols = HashSet::new();
+        for idx in self.logical.partition_key_indices() {
+            if order_cols.insert(idx) {
+                builder.add_order_column(idx, OrderType::Ascending);
+            }
+        }
+        if order_cols.insert(self.logical.time_col.index) {
+            builder.add_order_column(self.logical.time_col.index, OrderType::Ascending);
+        }
+        for &idx in input.logical_pk() {
+            if order_cols.insert(idx) {
+                builder.add_order_column(idx, OrderType::Ascending);
+            }
+        }
+
+        builder.build(input.distribution().dist_column_indices().to_vec())
+    }
+}
+
+impl fmt::Display for StreamSessionWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamSessionWindow")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamSessionWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamSessionWindow }
+
+impl StreamNode for StreamSessionWindow {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        use risingwave_pb::stream_plan::*;
+
+        let state_table = self
+            .infer_state_table()
+            .with_id(state.gen_table_id_wrapped());
+        ProstStreamNode::SessionWindow(SessionWindowNode {
+            time_col: self.logical.time_col.index as u32,
+            gap: Some(self.logical.gap.into()),
+            partition_by: self
+                .logical
+                .partition_key_indices()
+                .into_iter()
+                .map(|idx| idx as u32)
+                .collect(),
+            state_table: Some(state_table.to_internal_table_prost()),
+        })
+    }
+}
@@ -12,15 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+use risingwave_common::catalog::{Field, LOG_STORE_PREFIX_LEN};
 use risingwave_common::error::Result;
+use risingwave_common::types::DataType;
+use risingwave_common::util::sort_util::OrderType;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 
+use super::utils::TableCatalogBuilder;
 use super::{PlanBase, PlanRef, StreamNode};
+use crate::catalog::SecretId;
 use crate::optimizer::plan_node::PlanTreeNodeUnary;
 use crate::stream_fragmenter::BuildFragmentGraphState;
-use crate::WithOptions;
+use crate::{TableCatalog, WithOptions};
 
 /// [`StreamSink`] represents a table/connector sink at the very end of the graph.
 #[derive(Debug, Clone)]
@@ -28,6 +34,8 @@ pub struct StreamSink {
     pub base: PlanBase,
     input: PlanRef,
     properties: WithOptions,
+    /// The properties referring to secrets, which are filled by meta.
+    secret_refs: HashMap<String, SecretId>,
 }
 
 impl StreamSink {
@@ -44,18 +52,64 @@ impl StreamSink {
             input.functional_dependency().clone(),
             input.distribution().clone(),
             input.append_only(),
+            input.watermark_columns().clone(),
         ))
     }
 
     #[must_use]
-    pub fn new(input: PlanRef, properties: WithOptions) -> Self {
+    pub fn new(
+        input: PlanRef,
+        properties: WithOptions,
+        secret_refs: HashMap<String, SecretId>,
+    ) -> Self {
         let base = Self::derive_plan_base(&input).unwrap();
         Self {
             base,
             input,
             properties,
+            secret_refs,
         }
     }
+
+    /// The log store of a decoupled sink stores the input rows ordered by the epoch and the
+    /// sequence number in the epoch, followed by the input stream key. It's distributed the same as
+    /// the input, so that each actor owns the logs it writes.
+    pub fn infer_log_store_table(&self) -> TableCatalog {
+        let input = self.input();
+        let mut builder =
+            TableCatalogBuilder::new(self.base.ctx.inner().with_options.internal_table_subset());
+        let epoch_idx =
+            builder.add_column(&Field::with_name(DataType::Int64, "kv_log_store_epoch"));
+        let seq_id_idx =
+            builder.add_column(&Field::with_name(DataType::Int32, "kv_log_store_seq_id"));
+        builder.add_column(&Field::with_name(DataType::Int16, "kv_log_store_op"));
+        input.schema().fields().iter().for_each(|field| {
+            builder.add_column(field);
+        });
+
+        builder.add_order_column(epoch_idx, OrderType::Ascending);
+        builder.add_order_column(seq_id_idx, OrderType::Ascending);
+        // The distribution key must be a part of the primary key.
+        let dist_key = input
+            .distribution()
+            .dist_column_indices()
+            .iter()
+            .map(|idx| idx + LOG_STORE_PREFIX_LEN)
+            .collect::<Vec<_>>();
+        let mut order_cols = HashSet::new();
+        for idx in input
+            .logical_pk()
+            .iter()
+            .map(|idx| idx + LOG_STORE_PREFIX_LEN)
+            .chain(dist_key.iter().copied())
+        {
+            if order_cols.insert(idx) {
+                builder.add_order_column(idx, OrderType::Ascending);
+            }
+        }
+
+        builder.build(dist_key)
+    }
 }
 
 impl PlanTreeNodeUnary for StreamSink {
@@ -64,7 +118,7 @@ impl PlanTreeNodeUnary for StreamSink {
     }
 
     fn clone_with_input(&self, input: PlanRef) -> Self {
-        Self::new(input, self.properties.clone())
+        Self::new(input, self.properties.clone(), self.secret_refs.clone())
         // TODO(nanderstabel): Add assertions (assert_eq!)
     }
 }
@@ -79,17 +133,29 @@ impl fmt::Display for StreamSink {
 }
 
 impl StreamNode for StreamSink {
-    fn to_stream_prost_body(&self, _state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
         use risingwave_pb::stream_plan::*;
 
-        let input = self.input.clone();
-        let table = input.as_stream_table_scan().unwrap();
-        let table_desc = table.logical().table_desc();
+        // A sink created from a query has no associated table.
+        let table_id = self
+            .input
+            .as_stream_table_scan()
+            .map_or(0, |table| table.logical().table_desc().table_id.table_id());
+
+        let log_store_table = self.properties.sink_decouple().then(|| {
+            self.infer_log_store_table()
+                .with_id(state.gen_table_id_wrapped())
+                .to_internal_table_prost()
+        });
 
         ProstStreamNode::Sink(SinkNode {
-            table_id: table_desc.table_id.table_id(),
+            table_id,
             column_ids: vec![], // TODO(nanderstabel): fix empty Vector
             properties: self.properties.inner().clone(),
+            log_store_table,
+            secret_refs: self.secret_refs.clone(),
+            // Will be filled by meta.
+            sink_id: 0,
         })
     }
 }
@@ -0,0 +1,119 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::catalog::FieldDisplay;
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::SortNode;
+
+use super::utils::TableCatalogBuilder;
+use super::{PlanBase, PlanRef, PlanTreeNodeUnary, StreamNode};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+use crate::TableCatalog;
+
+/// [`StreamSort`] buffers its input and outputs the rows in the order of the watermark column
+/// `sort_column_index`, once the watermark of the column has passed them. Used to implement
+/// `EMIT ON WINDOW CLOSE`.
+#[derive(Debug, Clone)]
+pub struct StreamSort {
+    pub base: PlanBase,
+    input: PlanRef,
+    sort_column_index: usize,
+}
+
+impl StreamSort {
+    pub fn new(input: PlanRef, sort_column_index: usize) -> Self {
+        assert!(input.watermark_columns().contains(sort_column_index));
+        let ctx = input.ctx();
+        let pk_indices = input.logical_pk().to_vec();
+        let dist = input.distribution().clone();
+        // Sort executor only outputs the rows below the watermark, which will never be changed
+        // afterwards.
+        let base = PlanBase::new_stream(
+            ctx,
+            input.schema().clone(),
+            pk_indices,
+            input.functional_dependency().clone(),
+            dist,
+            true,
+            input.watermark_columns().clone(),
+        );
+        StreamSort {
+            base,
+            input,
+            sort_column_index,
+        }
+    }
+
+    fn infer_state_table(&self) -> TableCatalog {
+        let mut internal_table_catalog_builder =
+            TableCatalogBuilder::new(self.ctx().inner().with_options.internal_table_subset());
+
+        self.schema().fields().iter().for_each(|field| {
+            internal_table_catalog_builder.add_column(field);
+        });
+
+        // The pk of the state table is the sort column and the pk of the input.
+        let mut order_cols = vec![self.sort_column_index];
+        order_cols.extend(
+            self.logical_pk()
+                .iter()
+                .filter(|idx| **idx != self.sort_column_index),
+        );
+        order_cols.iter().for_each(|idx| {
+            internal_table_catalog_builder.add_order_column(*idx, OrderType::Ascending)
+        });
+
+        let dist_keys = self.distribution().dist_column_indices().to_vec();
+        internal_table_catalog_builder.build(dist_keys)
+    }
+}
+
+impl fmt::Display for StreamSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamSort")
+            .field(
+                "sort_column",
+                &FieldDisplay(&self.input.schema().fields()[self.sort_column_index]),
+            )
+            .finish()
+    }
+}
+
+impl PlanTreeNodeUnary for StreamSort {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(input, self.sort_column_index)
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamSort }
+
+impl StreamNode for StreamSort {
+    fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
+        ProstStreamNode::Sort(SortNode {
+            state_table: Some(
+                self.infer_state_table()
+                    .with_id(state.gen_table_id_wrapped())
+                    .to_internal_table_prost(),
+            ),
+            sort_column_index: self.sort_column_index as _,
+        })
+    }
+}
@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_pb::catalog::ColumnIndex;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
@@ -32,6 +33,10 @@ pub struct StreamSource {
 
 impl StreamSource {
     pub fn new(logical: LogicalSource) -> Self {
+        let mut watermark_columns = FixedBitSet::with_capacity(logical.schema().len());
+        for desc in &logical.source_catalog().watermark_descs {
+            watermark_columns.insert(desc.watermark_idx as usize);
+        }
         let base = PlanBase::new_stream(
             logical.ctx(),
             logical.schema().clone(),
@@ -39,6 +44,7 @@ impl StreamSource {
             logical.functional_dependency().clone(),
             Distribution::SomeShard,
             logical.source_catalog().append_only,
+            watermark_columns,
         );
         Self { base, logical }
     }
@@ -62,14 +68,19 @@ impl fmt::Display for StreamSource {
             .field(
                 "columns",
                 &format_args!("[{}]", &self.column_names().join(", ")),
-            )
-            .finish()
+            );
+        let pushdown_properties = &self.logical.core.pushdown_properties;
+        if !pushdown_properties.is_empty() {
+            builder.field("pushdown", pushdown_properties);
+        }
+        builder.finish()
     }
 }
 
 impl StreamNode for StreamSource {
     fn to_stream_prost_body(&self, state: &mut BuildFragmentGraphState) -> ProstStreamNode {
-        let source_catalog = self.logical.source_catalog();
+        let core = &self.logical.core;
+        let source_catalog = &core.catalog;
         ProstStreamNode::Source(SourceNode {
             source_id: source_catalog.id,
             state_table: Some(
@@ -79,20 +90,19 @@ impl StreamNode for StreamSource {
                     .to_internal_table_prost(),
             ),
             info: Some(source_catalog.info.clone()),
-            row_id_index: source_catalog
-                .row_id_index
+            row_id_index: core
+                .row_id_index()
                 .map(|index| ColumnIndex { index: index as _ }),
-            columns: source_catalog
-                .columns
-                .iter()
-                .map(|c| c.to_protobuf())
-                .collect_vec(),
+            columns: core.columns().map(|c| c.to_protobuf()).collect_vec(),
             pk_column_ids: source_catalog
                 .pk_col_ids
                 .iter()
                 .map(Into::into)
                 .collect_vec(),
-            properties: source_catalog.properties.clone(),
+            properties: core.properties(),
+            watermark_descs: source_catalog.watermark_descs.clone(),
+            secret_refs: source_catalog.secret_refs.clone(),
+            dml_enabled: core.dml_enabled,
         })
     }
 }
@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::fmt;
 use std::rc::Rc;
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_common::catalog::{Field, TableDesc};
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
@@ -35,10 +36,22 @@ pub struct StreamTableScan {
     pub base: PlanBase,
     logical: LogicalScan,
     batch_plan_id: PlanNodeId,
+    /// Whether the chain only forwards the changes of upstream without the snapshot.
+    upstream_only: bool,
 }
 
 impl StreamTableScan {
     pub fn new(logical: LogicalScan) -> Self {
+        Self::new_inner(logical, false)
+    }
+
+    /// Creates a scan which only forwards the changes of upstream. Used by the lookup side of
+    /// [`super::StreamTemporalJoin`].
+    pub fn new_upstream_only(logical: LogicalScan) -> Self {
+        Self::new_inner(logical, true)
+    }
+
+    fn new_inner(logical: LogicalScan, upstream_only: bool) -> Self {
         let ctx = logical.base.ctx.clone();
 
         let batch_plan_id = ctx.next_plan_node_id();
@@ -54,6 +67,12 @@ impl StreamTableScan {
                 Distribution::UpstreamHashShard(distribution_key, logical.table_desc().table_id)
             }
         };
+        let mut watermark_columns = FixedBitSet::with_capacity(logical.schema().len());
+        for (output_idx, &table_idx) in logical.output_column_indices().iter().enumerate() {
+            if logical.table_desc().watermark_columns.contains(table_idx) {
+                watermark_columns.insert(output_idx);
+            }
+        }
         let base = PlanBase::new_stream(
             ctx,
             logical.schema().clone(),
@@ -61,11 +80,13 @@ impl StreamTableScan {
             logical.functional_dependency().clone(),
             distribution,
             logical.table_desc().appendonly,
+            watermark_columns,
         );
         Self {
             base,
             logical,
             batch_plan_id,
+            upstream_only,
         }
     }
 
@@ -193,6 +214,7 @@ impl StreamTableScan {
                 table_id: self.logical.table_desc().table_id.table_id,
                 same_worker_node: false,
                 disable_rearrange: false,
+                chain_type: ChainType::Backfill as i32,
                 // The fields from upstream
                 upstream_fields: self
                     .logical
@@ -212,6 +234,14 @@ impl StreamTableScan {
                     .map(|&i| i as _)
                     .collect(),
                 is_singleton: *self.distribution() == Distribution::Single,
+                upstream_only: self.upstream_only,
+                rate_limit: self
+                    .base
+                    .ctx
+                    .inner()
+                    .session_ctx
+                    .config()
+                    .get_streaming_backfill_rate_limit(),
             })),
             stream_key,
             operator_id: self.base.id.0 as u64,
@@ -0,0 +1,185 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::Schema;
+use risingwave_pb::stream_plan::stream_node::NodeBody;
+use risingwave_pb::stream_plan::TemporalJoinNode;
+
+use super::{LogicalJoin, PlanBase, PlanRef, PlanTreeNodeBinary, StreamNode};
+use crate::catalog::ColumnId;
+use crate::expr::Expr;
+use crate::optimizer::plan_node::utils::IndicesDisplay;
+use crate::optimizer::plan_node::{EqJoinPredicate, EqJoinPredicateDisplay};
+use crate::stream_fragmenter::BuildFragmentGraphState;
+
+/// [`StreamTemporalJoin`] joins an append-only left stream with the latest version of a table at
+/// processing time. Each left row is looked up in the storage of the right table by a prefix of
+/// its primary key, and changes of the right table do not produce any output.
+///
+/// The right side is always a [`super::StreamTableScan`] without snapshot, which is only used to
+/// keep the lookup co-located with the table and to know which rows have been updated.
+#[derive(Debug, Clone)]
+pub struct StreamTemporalJoin {
+    pub base: PlanBase,
+    logical: LogicalJoin,
+
+    /// The equal conditions are on a prefix of the primary key of the right table, in the order
+    /// of the primary key.
+    eq_join_predicate: EqJoinPredicate,
+}
+
+impl StreamTemporalJoin {
+    pub fn new(logical: LogicalJoin, eq_join_predicate: EqJoinPredicate) -> Self {
+        let ctx = logical.base.ctx.clone();
+        assert!(logical.left().append_only());
+
+        // The output rows are only generated from the left rows.
+        let l2o = logical
+            .l2i_col_mapping()
+            .composite(&logical.i2o_col_mapping());
+        let dist = l2o.rewrite_provided_distribution(logical.left().distribution());
+        let watermark_columns = l2o.rewrite_bitset(logical.left().watermark_columns());
+
+        let base = PlanBase::new_stream(
+            ctx,
+            logical.schema().clone(),
+            logical.base.logical_pk.to_vec(),
+            logical.functional_dependency().clone(),
+            dist,
+            true,
+            watermark_columns,
+        );
+
+        Self {
+            base,
+            logical,
+            eq_join_predicate,
+        }
+    }
+
+    /// Get a reference to the temporal join's eq join predicate.
+    pub fn eq_join_predicate(&self) -> &EqJoinPredicate {
+        &self.eq_join_predicate
+    }
+}
+
+impl fmt::Display for StreamTemporalJoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verbose = self.base.ctx.is_explain_verbose();
+        let mut builder = f.debug_struct("StreamTemporalJoin");
+        builder.field("type", &format_args!("{:?}", self.logical.join_type()));
+
+        let mut concat_schema = self.left().schema().fields.clone();
+        concat_schema.extend(self.right().schema().fields.clone());
+        let concat_schema = Schema::new(concat_schema);
+        builder.field(
+            "predicate",
+            &format_args!(
+                "{}",
+                EqJoinPredicateDisplay {
+                    eq_join_predicate: self.eq_join_predicate(),
+                    input_schema: &concat_schema
+                }
+            ),
+        );
+
+        if verbose {
+            if self
+                .logical
+                .output_indices()
+                .iter()
+                .copied()
+                .eq(0..self.logical.internal_column_num())
+            {
+                builder.field("output", &format_args!("all"));
+            } else {
+                builder.field(
+                    "output",
+                    &format_args!(
+                        "{:?}",
+                        &IndicesDisplay {
+                            indices: self.logical.output_indices(),
+                            input_schema: &concat_schema,
+                        }
+                    ),
+                );
+            }
+        }
+
+        builder.finish()
+    }
+}
+
+impl PlanTreeNodeBinary for StreamTemporalJoin {
+    fn left(&self) -> PlanRef {
+        self.logical.left()
+    }
+
+    fn right(&self) -> PlanRef {
+        self.logical.right()
+    }
+
+    fn clone_with_left_right(&self, left: PlanRef, right: PlanRef) -> Self {
+        Self::new(
+            self.logical.clone_with_left_right(left, right),
+            self.eq_join_predicate.clone(),
+        )
+    }
+}
+
+impl_plan_tree_node_for_binary! { StreamTemporalJoin }
+
+impl StreamNode for StreamTemporalJoin {
+    fn to_stream_prost_body(&self, _state: &mut BuildFragmentGraphState) -> NodeBody {
+        let right = self.right();
+        let scan = right.as_stream_table_scan().unwrap().logical();
+
+        NodeBody::TemporalJoin(TemporalJoinNode {
+            join_type: self.logical.join_type() as i32,
+            left_key: self
+                .eq_join_predicate
+                .left_eq_indexes()
+                .into_iter()
+                .map(|idx| idx as i32)
+                .collect_vec(),
+            right_key: self
+                .eq_join_predicate
+                .right_eq_indexes()
+                .into_iter()
+                .map(|idx| idx as i32)
+                .collect_vec(),
+            null_safe: self.eq_join_predicate.null_safes(),
+            condition: self
+                .eq_join_predicate
+                .other_cond()
+                .as_expr_unless_true()
+                .map(|x| x.to_expr_proto()),
+            output_indices: self
+                .logical
+                .output_indices()
+                .iter()
+                .map(|&x| x as u32)
+                .collect(),
+            table_desc: Some(scan.table_desc().to_protobuf()),
+            table_output_column_ids: scan
+                .output_column_ids()
+                .into_iter()
+                .map(ColumnId::get_id)
+                .collect(),
+        })
+    }
+}
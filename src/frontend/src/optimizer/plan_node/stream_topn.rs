@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use fixedbitset::FixedBitSet;
 use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
 
 use super::{LogicalTopN, PlanBase, PlanRef, PlanTreeNodeUnary, StreamNode};
@@ -44,6 +45,7 @@ impl StreamTopN {
             logical.functional_dependency().clone(),
             dist,
             false,
+            FixedBitSet::with_capacity(logical.schema().len()),
         );
         StreamTopN { base, logical }
     }
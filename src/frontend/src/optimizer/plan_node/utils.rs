@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 use std::{fmt, vec};
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use risingwave_common::catalog::{ColumnDesc, Field, Schema};
 use risingwave_common::util::sort_util::OrderType;
@@ -123,6 +124,12 @@ impl TableCatalogBuilder {
                 .value_indices
                 .unwrap_or_else(|| (0..self.columns.len()).collect_vec()),
             definition: "".into(),
+            version: None,
+            watermark_columns: FixedBitSet::with_capacity(self.columns.len()),
+            statistics: None,
+            description: None,
+            column_descriptions: HashMap::new(),
+            schema_stale: false,
         }
     }
 
@@ -27,7 +27,7 @@ pub struct ApplyScanRule {}
 impl Rule for ApplyScanRule {
     fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
         let apply = plan.as_logical_apply()?;
-        let (left, right, on, join_type, _correlated_id, correlated_indices, max_one_row) =
+        let (left, right, on, join_type, correlated_id, correlated_indices, max_one_row) =
             apply.clone().decompose();
 
         if max_one_row {
@@ -46,6 +46,16 @@ impl Rule for ApplyScanRule {
         ) {
             return None;
         }
+        // `LogicalValues` with correlated input refs can not be unnested.
+        if let Some(values) = right.as_logical_values()
+            && values
+                .rows()
+                .iter()
+                .flatten()
+                .any(|expr| expr.has_correlated_input_ref_by_correlated_id(correlated_id))
+        {
+            return None;
+        }
 
         // Record the mapping from `CorrelatedInputRef`'s index to `InputRef`'s index.
         // We currently can remove DAG only if ALL the `CorrelatedInputRef` are equal joined to
@@ -0,0 +1,92 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_pb::plan_common::JoinType;
+
+use super::{BoxedRule, Rule};
+use crate::expr::{CorrelatedId, CorrelatedInputRef, Expr, ExprImpl, ExprRewriter, InputRef};
+use crate::optimizer::plan_node::{LogicalFilter, LogicalProject, LogicalProjectSet};
+use crate::optimizer::PlanRef;
+use crate::utils::ColIndexMapping;
+
+/// Transform `LogicalApply` whose right side is a `LogicalTableFunction` into `LogicalProjectSet`,
+/// which evaluates the table function for each row of the left side, with the `CorrelatedInputRef`s
+/// in its arguments replaced by `InputRef`s to the left side.
+pub struct ApplyTableFunctionRule {}
+impl Rule for ApplyTableFunctionRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let apply = plan.as_logical_apply()?;
+        let (left, right, on, join_type, correlated_id, correlated_indices, max_one_row) =
+            apply.clone().decompose();
+
+        if max_one_row || join_type != JoinType::Inner {
+            return None;
+        }
+        let table_function = right.as_logical_table_function()?;
+
+        let mut rewriter = Rewriter {
+            index_mapping: ColIndexMapping::new(
+                correlated_indices.into_iter().map(Some).collect_vec(),
+            )
+            .inverse(),
+            correlated_id,
+        };
+        let table_function =
+            rewriter.rewrite_expr(ExprImpl::from(table_function.table_function.clone()));
+
+        // The output of `LogicalProjectSet` is `[projected_row_id, left..., table_function]`.
+        let left_len = left.schema().len();
+        let mut select_list: Vec<ExprImpl> = left
+            .schema()
+            .data_types()
+            .into_iter()
+            .enumerate()
+            .map(|(index, data_type)| InputRef::new(index, data_type).into())
+            .collect();
+        select_list.push(table_function);
+        let project_set = LogicalProjectSet::create(left, select_list);
+        let project = LogicalProject::with_out_col_idx(project_set, 1..=left_len + 1);
+
+        Some(LogicalFilter::create(project.into(), on))
+    }
+}
+
+impl ApplyTableFunctionRule {
+    pub fn create() -> BoxedRule {
+        Box::new(ApplyTableFunctionRule {})
+    }
+}
+
+/// Convert `CorrelatedInputRef` to `InputRef` of the left side.
+struct Rewriter {
+    index_mapping: ColIndexMapping,
+    correlated_id: CorrelatedId,
+}
+impl ExprRewriter for Rewriter {
+    fn rewrite_correlated_input_ref(
+        &mut self,
+        correlated_input_ref: CorrelatedInputRef,
+    ) -> ExprImpl {
+        if correlated_input_ref.correlated_id() == self.correlated_id {
+            InputRef::new(
+                self.index_mapping.map(correlated_input_ref.index()),
+                correlated_input_ref.return_type(),
+            )
+            .into()
+        } else {
+            correlated_input_ref.into()
+        }
+    }
+}
@@ -29,10 +29,17 @@ use crate::optimizer::PlanRef;
 use crate::utils::{ColIndexMapping, Condition};
 
 /// Transform distinct aggregates to `LogicalAgg` -> `LogicalAgg` -> `Expand` -> `Input`.
-pub struct DistinctAggRule {}
+pub struct DistinctAggRule {
+    /// Whether to keep the distinct aggregates that can be deduplicated by the streaming agg
+    /// executor with dedup tables.
+    for_stream_dedup: bool,
+}
 impl Rule for DistinctAggRule {
     fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
         let agg: &LogicalAgg = plan.as_logical_agg()?;
+        if self.for_stream_dedup && agg.can_dedup_distinct_in_stream() {
+            return None;
+        }
         let (mut agg_calls, mut agg_group_keys, input) = agg.clone().decompose();
         let original_group_keys_len = agg_group_keys.len();
 
@@ -51,7 +58,16 @@ impl Rule for DistinctAggRule {
 
 impl DistinctAggRule {
     pub fn create() -> BoxedRule {
-        Box::new(DistinctAggRule {})
+        Box::new(DistinctAggRule {
+            for_stream_dedup: false,
+        })
+    }
+
+    /// Create a rule that keeps the distinct aggregates which can be deduplicated in streaming.
+    pub fn create_for_stream_dedup() -> BoxedRule {
+        Box::new(DistinctAggRule {
+            for_stream_dedup: true,
+        })
     }
 
     /// Construct `Expand` for distinct aggregates.
@@ -158,6 +174,9 @@ impl DistinctAggRule {
             for input in &mut agg_call.inputs {
                 input.index = mapping.map(input.index);
             }
+            for field in &mut agg_call.order_by_fields {
+                field.input.index = mapping.map(field.input.index);
+            }
             agg_call.filter = mem::replace(&mut agg_call.filter, Condition::true_cond())
                 .rewrite_expr(&mut mapping);
         }
@@ -222,10 +241,19 @@ impl DistinctAggRule {
             let flag_value = if agg_call.distinct {
                 agg_call.distinct = false;
 
+                // order keys of distinct agg are always part of its arguments, so they are
+                // remapped to the same columns of the middle agg as the arguments.
+                let mut argument_mapping = HashMap::new();
                 agg_call.inputs.iter_mut().for_each(|input_ref| {
+                    argument_mapping
+                        .entry(input_ref.index)
+                        .or_insert(index_of_distinct_agg_argument);
                     input_ref.index = index_of_distinct_agg_argument;
                     index_of_distinct_agg_argument += 1;
                 });
+                agg_call.order_by_fields.iter_mut().for_each(|field| {
+                    field.input.index = argument_mapping[&field.input.index];
+                });
 
                 // distinct-agg with real filter has its corresponding middle agg, which is count(*)
                 // with its original filter.
@@ -268,7 +296,10 @@ impl DistinctAggRule {
                     | AggKind::Avg
                     | AggKind::StringAgg
                     | AggKind::ArrayAgg
-                    | AggKind::FirstValue => (),
+                    | AggKind::FirstValue
+                    | AggKind::LastValue
+                    | AggKind::PercentileCont
+                    | AggKind::PercentileDisc => (),
                     AggKind::Count => {
                         agg_call.agg_kind = AggKind::Sum0;
                     }
@@ -276,6 +307,9 @@ impl DistinctAggRule {
                     AggKind::ApproxCountDistinct => {
                         agg_call.agg_kind = AggKind::Sum0;
                     }
+                    AggKind::ApproxCountDistinctMerge => {
+                        unreachable!("approx_count_distinct_merge is only used in two-phase agg")
+                    }
                 }
 
                 // the index of non-distinct aggs' subset in `column_subsets` is always 0 if it
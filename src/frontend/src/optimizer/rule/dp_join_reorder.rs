@@ -0,0 +1,46 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::super::plan_node::*;
+use super::Rule;
+use crate::optimizer::rule::BoxedRule;
+
+/// Reorders a multi join of a batch query into the join tree found by dynamic programming on the
+/// estimated cardinalities, see [`LogicalMultiJoin::dp_ordering`]. Falls back to the left deep join
+/// of the heuristic ordering if it's not applicable.
+pub struct DpJoinReorderRule {}
+
+impl Rule for DpJoinReorderRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let join = plan.as_logical_multi_join()?;
+        let (bushy, max_relations) = {
+            let config = join.base.ctx.inner().session_ctx.config();
+            (
+                config.get_batch_enable_bushy_join(),
+                config.get_batch_join_reorder_dp_limit(),
+            )
+        };
+        if let Some(join_tree) = join.dp_ordering(bushy, max_relations) {
+            return Some(join.as_reordered_join_tree(&join_tree));
+        }
+        let join_ordering = join.heuristic_ordering().ok()?;
+        Some(join.as_reordered_left_deep_join(&join_ordering))
+    }
+}
+
+impl DpJoinReorderRule {
+    pub fn create() -> BoxedRule {
+        Box::new(DpJoinReorderRule {})
+    }
+}
@@ -22,6 +22,10 @@ use super::{BoxedRule, Rule};
 use crate::optimizer::property::{Distribution, Order, RequiredDist};
 
 /// Use index scan and delta joins for supported queries.
+///
+/// Multi-way joins are supported if all joins are on the same join key, e.g., `a join b on a.x =
+/// b.x join c on b.x = c.x`: the inner join is rewritten into a delta join first, which is then
+/// used as an input of the outer delta join.
 pub struct IndexDeltaJoinRule {}
 
 impl Rule for IndexDeltaJoinRule {
@@ -44,10 +48,6 @@ impl Rule for IndexDeltaJoinRule {
             }
         }
 
-        let input_left_dyn = match_through_exchange(Rc::clone(&join.inputs()[0]))?;
-        let input_left = input_left_dyn.as_stream_table_scan()?;
-        let input_right_dyn = match_through_exchange(Rc::clone(&join.inputs()[1]))?;
-        let input_right = input_right_dyn.as_stream_table_scan()?;
         let left_indices = join.eq_join_predicate().left_eq_indexes();
         let right_indices = join.eq_join_predicate().right_eq_indexes();
 
@@ -105,23 +105,34 @@ impl Rule for IndexDeltaJoinRule {
             None
         }
 
-        if let Some(left) = match_indexes(&left_indices, input_left) {
-            if let Some(right) = match_indexes(&right_indices, input_right) {
-                // We already ensured that index and join use the same distribution, so we directly
-                // replace the children with stream index scan without inserting any exchanges.
-
-                fn upstream_hash_shard_to_hash_shard(plan: PlanRef) -> PlanRef {
-                    if let Distribution::UpstreamHashShard(key, _) = plan.distribution() {
-                        RequiredDist::hash_shard(key)
-                            .enforce_if_not_satisfies(plan, &Order::any())
-                            .unwrap()
-                    } else {
-                        plan
-                    }
-                }
-                let left = upstream_hash_shard_to_hash_shard(left);
-                let right = upstream_hash_shard_to_hash_shard(right);
+        fn upstream_hash_shard_to_hash_shard(plan: PlanRef) -> PlanRef {
+            if let Distribution::UpstreamHashShard(key, _) = plan.distribution() {
+                RequiredDist::hash_shard(key)
+                    .enforce_if_not_satisfies(plan, &Order::any())
+                    .unwrap()
+            } else {
+                plan
+            }
+        }
+
+        /// An input can be either a table scan with an index matching the join key, or another
+        /// delta join already distributed by the join key, i.e., on the same join key.
+        fn match_input(join_indices: &[usize], input: PlanRef) -> Option<PlanRef> {
+            if input.as_stream_delta_join().is_some() {
+                return match input.distribution() {
+                    Distribution::HashShard(key) if key == join_indices => Some(input),
+                    _ => None,
+                };
+            }
+            let input = match_through_exchange(input)?;
+            // We already ensured that index and join use the same distribution, so we directly
+            // replace the children with stream index scan without inserting any exchanges.
+            match_indexes(join_indices, input.as_stream_table_scan()?)
+                .map(upstream_hash_shard_to_hash_shard)
+        }
 
+        if let Some(left) = match_input(&left_indices, Rc::clone(&join.inputs()[0])) {
+            if let Some(right) = match_input(&right_indices, Rc::clone(&join.inputs()[1])) {
                 Some(
                     join.to_delta_join()
                         .clone_with_left_right(left, right)
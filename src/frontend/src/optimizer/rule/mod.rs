@@ -41,6 +41,8 @@ mod index_delta_join;
 pub use index_delta_join::*;
 mod reorder_multijoin;
 pub use reorder_multijoin::*;
+mod dp_join_reorder;
+pub use dp_join_reorder::*;
 mod apply_agg;
 pub use apply_agg::*;
 mod apply_filter;
@@ -49,6 +51,8 @@ mod apply_proj;
 pub use apply_proj::*;
 mod apply_scan;
 pub use apply_scan::*;
+mod apply_table_function;
+pub use apply_table_function::*;
 mod translate_apply;
 pub use translate_apply::*;
 mod merge_multijoin;
@@ -78,6 +82,7 @@ macro_rules! for_all_rules {
             ,{ApplyFilterRule}
             ,{ApplyProjRule}
             ,{ApplyScanRule}
+            ,{ApplyTableFunctionRule}
             ,{ApplyJoinRule}
             ,{ApplyToJoinRule}
             ,{MaxOneRowEliminateRule}
@@ -89,6 +94,7 @@ macro_rules! for_all_rules {
             ,{ProjectMergeRule}
             ,{PullUpCorrelatedPredicateRule}
             ,{ReorderMultiJoinRule}
+            ,{DpJoinReorderRule}
             ,{TranslateApplyRule}
             ,{PushCalculationOfJoinRule}
             ,{IndexSelectionRule}
@@ -41,14 +41,29 @@ impl Rule for TranslateApplyRule {
         let mut index = 0;
 
         let new_apply_left = {
-            let rewritten_left = Self::rewrite(
+            let rewritten_left = match Self::rewrite(
                 &left,
                 correlated_indices.clone(),
                 0,
                 &mut index_mapping,
                 &mut data_types,
                 &mut index,
-            )?;
+            ) {
+                Some(rewritten_left) => rewritten_left,
+                // The correlated columns can not be found in the scans of the left, e.g., when
+                // they are from `VALUES` or a table function, so the domain is computed from the
+                // left itself.
+                None => {
+                    for &correlated_index in &correlated_indices {
+                        index_mapping.put(correlated_index, Some(correlated_index));
+                        data_types.insert(
+                            correlated_index,
+                            left.schema().fields()[correlated_index].data_type(),
+                        );
+                    }
+                    left.clone()
+                }
+            };
             // This `LogicalProject` is used to make sure that after `LogicalApply`'s left was
             // rewritten, the new index of `correlated_index` is always at its position in
             // `correlated_indices`.
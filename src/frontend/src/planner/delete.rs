@@ -17,7 +17,7 @@ use risingwave_common::error::Result;
 
 use super::Planner;
 use crate::binder::BoundDelete;
-use crate::optimizer::plan_node::{LogicalDelete, LogicalFilter};
+use crate::optimizer::plan_node::{LogicalDelete, LogicalFilter, LogicalProject};
 use crate::optimizer::property::{Order, RequiredDist};
 use crate::optimizer::{PlanRef, PlanRoot};
 
@@ -32,13 +32,21 @@ impl Planner {
         } else {
             scan
         };
-        let plan: PlanRef = LogicalDelete::create(input, name, source_id, table_id)?.into();
+        let returning = delete.returning_schema.is_some();
+        let mut plan: PlanRef =
+            LogicalDelete::create(input, name, source_id, table_id, returning)?.into();
+        if returning {
+            plan = LogicalProject::create(plan, delete.returning_list);
+        }
 
         // For delete, frontend will only schedule one task so do not need this to be single.
         let dist = RequiredDist::Any;
         let mut out_fields = FixedBitSet::with_capacity(plan.schema().len());
         out_fields.insert_range(..);
-        let out_names = plan.schema().names();
+        let out_names = match delete.returning_schema {
+            Some(schema) => schema.names(),
+            None => plan.schema().names(),
+        };
 
         let root = PlanRoot::new(plan, dist, Order::any(), out_fields, out_names);
         Ok(root)
@@ -27,19 +27,27 @@ impl Planner {
         if !insert.cast_exprs.is_empty() {
             input = LogicalProject::create(input, insert.cast_exprs);
         }
+        let returning = insert.returning_schema.is_some();
         // `columns` not used by backend yet.
-        let plan: PlanRef = LogicalInsert::create(
+        let mut plan: PlanRef = LogicalInsert::create(
             input,
             insert.table_source.name,
             insert.table_source.source_id,
             insert.table_source.associated_mview_id,
+            returning,
         )?
         .into();
+        if returning {
+            plan = LogicalProject::create(plan, insert.returning_list);
+        }
         // For insert, frontend will only schedule one task so do not need this to be single.
         let dist = RequiredDist::Any;
         let mut out_fields = FixedBitSet::with_capacity(plan.schema().len());
         out_fields.insert_range(..);
-        let out_names = plan.schema().names();
+        let out_names = match insert.returning_schema {
+            Some(schema) => schema.names(),
+            None => plan.schema().names(),
+        };
         let root = PlanRoot::new(plan, dist, Order::any(), out_fields, out_names);
         Ok(root)
     }
@@ -16,18 +16,20 @@ use std::rc::Rc;
 
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::ScalarImpl;
+use risingwave_common::types::{DataType, ScalarImpl};
 
 use crate::binder::{
-    BoundBaseTable, BoundJoin, BoundSource, BoundSystemTable, BoundWindowTableFunction, Relation,
-    WindowTableFunctionKind,
+    BoundBaseTable, BoundJoin, BoundRecursiveUnion, BoundSource, BoundSystemTable,
+    BoundWindowTableFunction, Relation, WindowTableFunctionKind,
 };
 use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef, TableFunction};
 use crate::optimizer::plan_node::{
-    LogicalHopWindow, LogicalJoin, LogicalProject, LogicalScan, LogicalSource,
-    LogicalTableFunction, PlanRef,
+    LogicalApply, LogicalHopWindow, LogicalJoin, LogicalProject, LogicalRecursiveUnion,
+    LogicalScan, LogicalSessionWindow, LogicalSource, LogicalTableFunction, LogicalWorkTable,
+    PlanRef,
 };
 use crate::planner::Planner;
+use crate::utils::Condition;
 
 impl Planner {
     pub fn plan_relation(&mut self, relation: Relation) -> Result<PlanRef> {
@@ -37,9 +39,16 @@ impl Planner {
             // TODO: order is ignored in the subquery
             Relation::Subquery(q) => Ok(self.plan_query(q.query)?.into_subplan()),
             Relation::Join(join) => self.plan_join(*join),
+            Relation::Apply(join) => self.plan_apply(*join),
             Relation::WindowTableFunction(tf) => self.plan_window_table_function(*tf),
             Relation::Source(s) => self.plan_source(*s),
             Relation::TableFunction(tf) => self.plan_table_function(*tf),
+            Relation::RecursiveUnion(r) => self.plan_recursive_union(*r),
+            Relation::WorkTable(w) => Ok(LogicalWorkTable::create(
+                w.work_table_id,
+                w.schema,
+                self.ctx(),
+            )),
         }
     }
 
@@ -50,6 +59,7 @@ impl Planner {
             Rc::new(sys_table.sys_table_catalog.table_desc()),
             vec![],
             self.ctx(),
+            false,
         )
         .into())
     }
@@ -65,6 +75,7 @@ impl Planner {
                 .map(|x| x.as_ref().clone().into())
                 .collect(),
             self.ctx(),
+            base_table.for_system_time_as_of_proctime,
         )
         .into())
     }
@@ -73,6 +84,20 @@ impl Planner {
         Ok(LogicalSource::new(Rc::new(source.catalog), self.ctx()).into())
     }
 
+    pub(super) fn plan_recursive_union(
+        &mut self,
+        recursive_union: BoundRecursiveUnion,
+    ) -> Result<PlanRef> {
+        let base = self.plan_query(recursive_union.base)?.into_subplan();
+        let recursive = self.plan_query(recursive_union.recursive)?.into_subplan();
+        Ok(LogicalRecursiveUnion::create(
+            recursive_union.work_table_id,
+            recursive_union.all,
+            base,
+            recursive,
+        ))
+    }
+
     pub(super) fn plan_join(&mut self, join: BoundJoin) -> Result<PlanRef> {
         let left = self.plan_relation(join.left)?;
         let right = self.plan_relation(join.right)?;
@@ -81,6 +106,25 @@ impl Planner {
         Ok(LogicalJoin::create(left, right, join_type, on_clause))
     }
 
+    pub(super) fn plan_apply(&mut self, mut join: BoundJoin) -> Result<PlanRef> {
+        let correlated_id = self.ctx.next_correlated_id();
+        let correlated_indices = join
+            .right
+            .collect_correlated_indices_by_depth_and_assign_id(0, correlated_id);
+        let left = self.plan_relation(join.left)?;
+        let right = self.plan_relation(join.right)?;
+
+        Ok(LogicalApply::create(
+            left,
+            right,
+            join.join_type,
+            Condition::with_expr(join.cond),
+            correlated_id,
+            correlated_indices,
+            false,
+        ))
+    }
+
     pub(super) fn plan_window_table_function(
         &mut self,
         table_function: BoundWindowTableFunction,
@@ -97,6 +141,11 @@ impl Planner {
                 table_function.time_col,
                 table_function.args,
             ),
+            Session => self.plan_session_window(
+                table_function.input,
+                table_function.time_col,
+                table_function.args,
+            ),
         }
     }
 
@@ -206,4 +255,57 @@ impl Planner {
             window_size,
         ))
     }
+
+    /// Plans `SESSION(table, time_col, gap [, partition_col ...])`. The optional trailing columns
+    /// partition the input, so that sessions are assigned within each partition.
+    fn plan_session_window(
+        &mut self,
+        input: Relation,
+        time_col: InputRef,
+        args: Vec<ExprImpl>,
+    ) -> Result<PlanRef> {
+        let input = self.plan_relation(input)?;
+        let mut args = args.into_iter();
+        let Some(ExprImpl::Literal(gap)) = args.next() else {
+            return Err(ErrorCode::BindError("Invalid arguments for SESSION window function".to_string()).into());
+        };
+        let Some(ScalarImpl::Interval(gap)) = *gap.get_data() else {
+            return Err(ErrorCode::BindError("Invalid arguments for SESSION window function".to_string()).into());
+        };
+        if !gap.is_positive() || gap.get_months() != 0 {
+            return Err(ErrorCode::BindError(format!(
+                "gap {} of SESSION window function must be positive and without months",
+                gap
+            ))
+            .into());
+        }
+        if !matches!(
+            time_col.data_type,
+            DataType::Timestamp | DataType::Timestampz
+        ) {
+            return Err(ErrorCode::BindError(
+                "the time column of SESSION window function should be a timestamp or timestamp with time zone column".to_string(),
+            )
+            .into());
+        }
+
+        let input_len = input.schema().len();
+        let partition_by = args
+            .map(|arg| match arg {
+                ExprImpl::InputRef(input_ref) if input_ref.index < input_len => Ok(*input_ref),
+                _ => Err(ErrorCode::BindError(
+                    "the partition keys of SESSION window function should be columns of the input"
+                        .to_string(),
+                )
+                .into()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(LogicalSessionWindow::create(
+            input,
+            time_col,
+            gap,
+            partition_by,
+        ))
+    }
 }
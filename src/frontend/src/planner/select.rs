@@ -16,19 +16,19 @@ use itertools::Itertools;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::DataType;
-use risingwave_expr::expr::AggKind;
 use risingwave_pb::plan_common::JoinType;
 
 use crate::binder::{BoundDistinct, BoundSelect};
 use crate::expr::{
-    AggCall, CorrelatedId, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef, OrderBy,
-    Subquery, SubqueryKind,
+    CorrelatedId, Expr, ExprImpl, ExprRewriter, ExprType, FunctionCall, InputRef, Subquery,
+    SubqueryKind,
 };
 pub use crate::optimizer::plan_node::LogicalFilter;
 use crate::optimizer::plan_node::{
-    LogicalAgg, LogicalApply, LogicalOverAgg, LogicalProject, LogicalProjectSet, LogicalValues,
-    PlanAggCall, PlanRef,
+    LogicalAgg, LogicalApply, LogicalOverAgg, LogicalProject, LogicalProjectSet, LogicalTopN,
+    LogicalValues, PlanAggCall, PlanRef,
 };
+use crate::optimizer::property::{FieldOrder, Order};
 use crate::planner::Planner;
 use crate::utils::Condition;
 
@@ -40,11 +40,13 @@ impl Planner {
             where_clause,
             mut select_items,
             group_by,
+            grouping_sets,
             mut having,
             distinct,
             ..
         }: BoundSelect,
         extra_order_exprs: Vec<ExprImpl>,
+        order: &[FieldOrder],
     ) -> Result<PlanRef> {
         // Append expressions in ORDER BY.
         if distinct.is_distinct() && !extra_order_exprs.is_empty() {
@@ -53,20 +55,29 @@ impl Planner {
             )
             .into());
         }
-        // The DISTINCT ON expression(s) must match the leftmost ORDER BY expression(s).
+        select_items.extend(extra_order_exprs);
+        // The DISTINCT ON expressions must match the leftmost ORDER BY expressions, in any order.
+        // They are appended to the select items as well, so that they are planned together, e.g.
+        // rewritten by the aggregation, and serve as the group key of the Top-N.
+        let output_len = select_items.len();
         if let BoundDistinct::DistinctOn(exprs) = &distinct {
-            #[allow(clippy::disallowed_methods)]
-            for (expr, order_expr) in exprs.iter().zip(extra_order_exprs.iter()) {
-                if expr != order_expr {
+            let mut unmatched = exprs.iter().collect_vec();
+            for field in order {
+                if unmatched.is_empty() {
+                    break;
+                }
+                let order_expr = &select_items[field.index];
+                if !exprs.contains(order_expr) {
                     return Err(ErrorCode::InvalidInputSyntax(
-                        "the SELECT DISTINCT ON expressions must match the leftmost SELECT DISTINCT ON expressions"
+                        "SELECT DISTINCT ON expressions must match initial ORDER BY expressions"
                             .into(),
                     )
                     .into());
                 }
+                unmatched.retain(|expr| *expr != order_expr);
             }
+            select_items.extend(exprs.iter().cloned());
         }
-        select_items.extend(extra_order_exprs);
 
         // Plan the FROM clause.
         let mut root = match from {
@@ -78,11 +89,11 @@ impl Planner {
             root = self.plan_where(root, where_clause)?;
         }
         // Plan the SELECT clause.
-        // TODO: select-agg, group-by, having can also contain subquery exprs.
+        // TODO: group-by can also contain subquery exprs.
         let has_agg_call = select_items.iter().any(|expr| expr.has_agg_call());
-        if !group_by.is_empty() || having.is_some() || has_agg_call {
+        if !group_by.is_empty() || !grouping_sets.is_empty() || having.is_some() || has_agg_call {
             (root, select_items, having) =
-                LogicalAgg::create(select_items, group_by, having, root)?;
+                LogicalAgg::create(select_items, group_by, grouping_sets, having, root)?;
         }
 
         if let Some(having) = having {
@@ -96,17 +107,27 @@ impl Planner {
             (root, select_items) = LogicalOverAgg::create(root, select_items)?;
         }
 
-        if let BoundDistinct::DistinctOn(distinct_list) = &distinct {
-            (root, select_items) =
-                self.plan_distinct_on(root, select_items, distinct_list.clone())?;
-        }
-
         if select_items.iter().any(|e| e.has_table_function()) {
-            root = LogicalProjectSet::create(root, select_items)
+            root = LogicalProjectSet::create(root, select_items);
+            if matches!(distinct, BoundDistinct::DistinctOn(_)) {
+                // Hide the `projected_row_id` before the rows are deduplicated.
+                root =
+                    LogicalProject::with_out_col_idx(root.clone(), 1..root.schema().len()).into();
+            }
         } else {
             root = LogicalProject::create(root, select_items);
         }
 
+        if let BoundDistinct::DistinctOn(_) = &distinct {
+            // Keep the first row of each group of the DISTINCT ON expressions, in the order of
+            // ORDER BY.
+            let group_key = (output_len..root.schema().len()).collect();
+            root =
+                LogicalTopN::with_group(root, 1, 0, false, Order::new(order.to_vec()), group_key)
+                    .into();
+            root = LogicalProject::with_out_col_idx(root, 0..output_len).into();
+        }
+
         if let BoundDistinct::Distinct = distinct {
             let group_key = (0..root.schema().fields().len()).collect();
             root = LogicalAgg::new(vec![], group_key, root).into();
@@ -331,33 +352,4 @@ impl Planner {
             max_one_row,
         )
     }
-
-    fn plan_distinct_on(
-        &self,
-        root: PlanRef,
-        select_items: Vec<ExprImpl>,
-        distinct_list: Vec<ExprImpl>,
-    ) -> Result<(PlanRef, Vec<ExprImpl>)> {
-        // apply a `first_value()` to the select items which are not in the DISTINCT ON clause.
-        let mut first_aggs = vec![];
-        for expr in select_items {
-            let expr = if distinct_list.contains(&expr) {
-                expr
-            } else {
-                ExprImpl::AggCall(
-                    AggCall::new(
-                        AggKind::FirstValue,
-                        vec![expr],
-                        false,
-                        OrderBy::any(),
-                        Condition::true_cond(),
-                    )?
-                    .into(),
-                )
-            };
-            first_aggs.push(expr);
-        }
-        let (root, select_items, _) = LogicalAgg::create(first_aggs, distinct_list, None, root)?;
-        Ok((root, select_items))
-    }
 }
@@ -12,22 +12,186 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use itertools::Itertools;
+use risingwave_common::catalog::Schema;
 use risingwave_common::error::Result;
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_pb::plan_common::JoinType;
 
-use crate::binder::BoundSetExpr;
-use crate::expr::ExprImpl;
-use crate::optimizer::plan_node::PlanRef;
+use crate::binder::{BoundSetExpr, BoundSetOperator};
+use crate::expr::{
+    Expr, ExprImpl, ExprType, FunctionCall, InputRef, Literal, TableFunction, TableFunctionType,
+};
+use crate::optimizer::plan_node::{
+    LogicalAgg, LogicalFilter, LogicalJoin, LogicalProject, LogicalProjectSet, PlanAggCall, PlanRef,
+};
+use crate::optimizer::property::FieldOrder;
 use crate::planner::Planner;
+use crate::utils::Condition;
 
 impl Planner {
     pub(super) fn plan_set_expr(
         &mut self,
         set_expr: BoundSetExpr,
         extra_order_exprs: Vec<ExprImpl>,
+        order: &[FieldOrder],
     ) -> Result<PlanRef> {
         match set_expr {
-            BoundSetExpr::Select(s) => self.plan_select(*s, extra_order_exprs),
+            BoundSetExpr::Select(s) => self.plan_select(*s, extra_order_exprs, order),
             BoundSetExpr::Values(v) => self.plan_values(*v),
+            BoundSetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+                schema,
+            } => self.plan_set_operation(op, all, *left, *right, &schema),
         }
     }
+
+    /// Plans `INTERSECT` and `EXCEPT`.
+    ///
+    /// Without `ALL`, the left input is semi-joined (anti-joined for `EXCEPT`) with the right one
+    /// on null-safe equality of all columns, and then deduplicated by a [`LogicalAgg`].
+    ///
+    /// With `ALL`, both inputs are grouped by all columns with their duplicate counts, joined
+    /// together, and each row is then repeated by `generate_series` as many times as it should
+    /// appear in the output, i.e. `least(l, r)` for `INTERSECT ALL` and `l - r` for `EXCEPT ALL`.
+    fn plan_set_operation(
+        &mut self,
+        op: BoundSetOperator,
+        all: bool,
+        left: BoundSetExpr,
+        right: BoundSetExpr,
+        schema: &Schema,
+    ) -> Result<PlanRef> {
+        let left = self.plan_set_operand(left, schema)?;
+        let right = self.plan_set_operand(right, schema)?;
+        let n = schema.len();
+        let data_types = schema.data_types();
+
+        if !all {
+            let join_type = match op {
+                BoundSetOperator::Intersect => JoinType::LeftSemi,
+                BoundSetOperator::Except => JoinType::LeftAnti,
+            };
+            let on = Self::null_safe_eq_cond(&data_types, 0, n);
+            let join = LogicalJoin::new(left, right, join_type, on);
+            return Ok(LogicalAgg::new(vec![], (0..n).collect(), join.into()).into());
+        }
+
+        // Output of each side: `[cols..., count]`.
+        let count_rows = |input: PlanRef| {
+            LogicalAgg::new(vec![PlanAggCall::count_star()], (0..n).collect(), input)
+        };
+        let left = count_rows(left);
+        let right = count_rows(right);
+        let join_type = match op {
+            BoundSetOperator::Intersect => JoinType::Inner,
+            BoundSetOperator::Except => JoinType::LeftOuter,
+        };
+        let on = Self::null_safe_eq_cond(&data_types, 0, n + 1);
+        let join: PlanRef = LogicalJoin::new(left.into(), right.into(), join_type, on).into();
+
+        let left_count: ExprImpl = InputRef::new(n, DataType::Int64).into();
+        let right_count: ExprImpl = InputRef::new(2 * n + 1, DataType::Int64).into();
+        let count: ExprImpl = match op {
+            BoundSetOperator::Intersect => {
+                let lt = FunctionCall::new(
+                    ExprType::LessThan,
+                    vec![left_count.clone(), right_count.clone()],
+                )?;
+                FunctionCall::new(ExprType::Case, vec![lt.into(), left_count, right_count])?.into()
+            }
+            BoundSetOperator::Except => {
+                let right_count =
+                    FunctionCall::new(ExprType::Coalesce, vec![right_count, Self::zero()])?;
+                FunctionCall::new(ExprType::Subtract, vec![left_count, right_count.into()])?.into()
+            }
+        };
+        let mut exprs = Self::input_refs(&data_types, 0);
+        exprs.push(count);
+        let mut root = LogicalProject::create(join, exprs);
+        if op == BoundSetOperator::Except {
+            let positive = FunctionCall::new(
+                ExprType::GreaterThan,
+                vec![InputRef::new(n, DataType::Int64).into(), Self::zero()],
+            )?;
+            root = LogicalFilter::create_with_expr(root, positive.into());
+        }
+
+        // Output of the project set: `[projected_row_id, cols..., generate_series]`.
+        let count = InputRef::new(n, DataType::Int64).into();
+        let series = TableFunction::new(
+            TableFunctionType::Generate,
+            vec![
+                ExprImpl::literal_int(1),
+                ExprImpl::cast_explicit(count, DataType::Int32)?,
+                ExprImpl::literal_int(1),
+            ],
+        )?;
+        let mut select_list = Self::input_refs(&data_types, 0);
+        select_list.push(series.into());
+        let root = LogicalProjectSet::create(root, select_list);
+        Ok(LogicalProject::create(
+            root,
+            Self::input_refs(&data_types, 1),
+        ))
+    }
+
+    /// Plans an operand of a set operation, casting its columns to the types of the result.
+    fn plan_set_operand(&mut self, set_expr: BoundSetExpr, schema: &Schema) -> Result<PlanRef> {
+        let plan = self.plan_set_expr(set_expr, vec![], &[])?;
+        if plan.schema().data_types() == schema.data_types() {
+            return Ok(plan);
+        }
+        let exprs = plan
+            .schema()
+            .fields()
+            .iter()
+            .zip_eq(schema.fields())
+            .enumerate()
+            .map(|(i, (from, to))| {
+                ExprImpl::from(InputRef::new(i, from.data_type())).cast_implicit(to.data_type())
+            })
+            .collect::<Result<_>>()?;
+        Ok(LogicalProject::create(plan, exprs))
+    }
+
+    fn zero() -> ExprImpl {
+        Literal::new(Some(ScalarImpl::Int64(0)), DataType::Int64).into()
+    }
+
+    fn input_refs(data_types: &[DataType], offset: usize) -> Vec<ExprImpl> {
+        data_types
+            .iter()
+            .enumerate()
+            .map(|(i, t)| InputRef::new(i + offset, t.clone()).into())
+            .collect()
+    }
+
+    /// `IS NOT DISTINCT FROM` on each pair of columns starting at `left_offset` and
+    /// `right_offset` of the join input.
+    fn null_safe_eq_cond(
+        data_types: &[DataType],
+        left_offset: usize,
+        right_offset: usize,
+    ) -> Condition {
+        let conjunctions = data_types
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                FunctionCall::new_unchecked(
+                    ExprType::IsNotDistinctFrom,
+                    vec![
+                        InputRef::new(left_offset + i, t.clone()).into(),
+                        InputRef::new(right_offset + i, t.clone()).into(),
+                    ],
+                    DataType::Boolean,
+                )
+                .into()
+            })
+            .collect();
+        Condition { conjunctions }
+    }
 }
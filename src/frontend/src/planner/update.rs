@@ -18,7 +18,7 @@ use risingwave_common::error::Result;
 use super::select::LogicalFilter;
 use super::Planner;
 use crate::binder::BoundUpdate;
-use crate::optimizer::plan_node::LogicalUpdate;
+use crate::optimizer::plan_node::{LogicalProject, LogicalUpdate};
 use crate::optimizer::property::{Order, RequiredDist};
 use crate::optimizer::{PlanRef, PlanRoot};
 
@@ -33,14 +33,22 @@ impl Planner {
         } else {
             scan
         };
-        let plan: PlanRef =
-            LogicalUpdate::create(input, name, source_id, table_id, update.exprs)?.into();
+        let returning = update.returning_schema.is_some();
+        let mut plan: PlanRef =
+            LogicalUpdate::create(input, name, source_id, table_id, update.exprs, returning)?
+                .into();
+        if returning {
+            plan = LogicalProject::create(plan, update.returning_list);
+        }
 
         // For update, frontend will only schedule one task so do not need this to be single.
         let dist = RequiredDist::Any;
         let mut out_fields = FixedBitSet::with_capacity(plan.schema().len());
         out_fields.insert_range(..);
-        let out_names = plan.schema().names();
+        let out_names = match update.returning_schema {
+            Some(schema) => schema.names(),
+            None => plan.schema().names(),
+        };
 
         let root = PlanRoot::new(plan, dist, Order::any(), out_fields, out_names);
         Ok(root)
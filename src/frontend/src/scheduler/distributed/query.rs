@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::default::Default;
 use std::mem;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::anyhow;
 use pgwire::pg_server::SessionId;
@@ -88,6 +89,9 @@ struct QueryRunner {
 
     // Used for cleaning up `QueryExecution` after execution.
     query_execution_info: QueryExecutionInfoRef,
+
+    /// The query will be aborted if it's still running after the timeout.
+    timeout: Option<Duration>,
 }
 
 impl QueryExecution {
@@ -122,6 +126,7 @@ impl QueryExecution {
     ) -> SchedulerResult<QueryResultFetcher> {
         let mut state = self.state.write().await;
         let cur_state = mem::replace(&mut *state, QueryState::Failed);
+        let timeout = context.session().config().get_statement_timeout();
 
         // Because the snapshot may be released before all stages are scheduled, we only pass a
         // reference of `pinned_snapshot`. Its ownership will be moved into `QueryRunner` so that it
@@ -150,6 +155,7 @@ impl QueryExecution {
                     scheduled_stages_count: 0,
                     compute_client_pool,
                     query_execution_info,
+                    timeout,
                 };
 
                 // Not trace the error here, it will be processed in scheduler.
@@ -239,7 +245,35 @@ impl QueryRunner {
         let mut stages_with_table_scan = self.query.stages_with_table_scan();
         // To convince the compiler that `pinned_snapshot` will only be dropped once.
         let mut pinned_snapshot_to_drop = Some(pinned_snapshot);
-        while let Some(msg_inner) = self.msg_receiver.recv().await {
+        let deadline = self
+            .timeout
+            .map(|timeout| tokio::time::Instant::now() + timeout);
+        loop {
+            let msg = match deadline {
+                Some(deadline) => {
+                    match tokio::time::timeout_at(deadline, self.msg_receiver.recv()).await {
+                        Ok(msg) => msg,
+                        Err(_) => {
+                            // The query may have ended with all its results fetched.
+                            let is_running = self
+                                .query_execution_info
+                                .read()
+                                .unwrap()
+                                .has_query(&self.query.query_id);
+                            if is_running {
+                                warn!("Query {:?} timed out.", self.query.query_id);
+                                self.handle_cancel_or_failed_stage(SchedulerError::QueryTimeout)
+                                    .await;
+                            }
+                            break;
+                        }
+                    }
+                }
+                None => self.msg_receiver.recv().await,
+            };
+            let Some(msg_inner) = msg else {
+                break;
+            };
             match msg_inner {
                 Stage(Scheduled(stage_id)) => {
                     tracing::trace!(
@@ -379,6 +413,7 @@ pub(crate) mod tests {
     use std::rc::Rc;
     use std::sync::{Arc, RwLock};
 
+    use fixedbitset::FixedBitSet;
     use risingwave_common::catalog::{ColumnDesc, TableDesc};
     use risingwave_common::config::constant::hummock::TABLE_OPTION_DUMMY_RETENTION_SECOND;
     use risingwave_common::types::DataType;
@@ -477,9 +512,11 @@ pub(crate) mod tests {
                 appendonly: false,
                 retention_seconds: TABLE_OPTION_DUMMY_RETENTION_SECOND,
                 value_indices: vec![0, 1, 2],
+                watermark_columns: FixedBitSet::with_capacity(3),
             }),
             vec![],
             ctx,
+            false,
         )
         .to_batch()
         .unwrap()
@@ -557,6 +594,7 @@ pub(crate) mod tests {
             }),
             state: risingwave_pb::common::worker_node::State::Running as i32,
             parallel_units: generate_parallel_units(0, 0),
+            unschedulable: false,
         };
         let worker2 = WorkerNode {
             id: 1,
@@ -567,6 +605,7 @@ pub(crate) mod tests {
             }),
             state: risingwave_pb::common::worker_node::State::Running as i32,
             parallel_units: generate_parallel_units(8, 1),
+            unschedulable: false,
         };
         let worker3 = WorkerNode {
             id: 2,
@@ -577,6 +616,7 @@ pub(crate) mod tests {
             }),
             state: risingwave_pb::common::worker_node::State::Running as i32,
             parallel_units: generate_parallel_units(16, 2),
+            unschedulable: false,
         };
         let workers = vec![worker1, worker2, worker3];
         let worker_node_manager = Arc::new(WorkerNodeManager::mock(workers));
@@ -116,6 +116,10 @@ impl QueryExecutionInfo {
         self.query_execution_map.remove(query_id);
     }
 
+    pub fn has_query(&self, query_id: &QueryId) -> bool {
+        self.query_execution_map.contains_key(query_id)
+    }
+
     pub fn abort_queries(&self, session_id: SessionId) {
         for query in self.query_execution_map.values() {
             // `QueryExecutionInfo` might have queries from different sessions.
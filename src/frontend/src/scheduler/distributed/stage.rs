@@ -12,10 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::cell::RefCell;
 use std::collections::HashMap;
 use std::mem;
-use std::rc::Rc;
 use std::sync::Arc;
 
 use anyhow::anyhow;
@@ -376,7 +374,13 @@ impl StageRunner {
                             // Do not process this as task status like Running/Finished/ etc.
 
                             use risingwave_pb::task_service::task_info::TaskStatus as TaskStatusProst;
-                            match TaskStatusProst::from_i32(status.task_info.as_ref().unwrap().task_status).unwrap() {
+                            let task_info = status.task_info.as_ref().unwrap();
+                            let task_status = TaskStatusProst::from_i32(task_info.task_status).unwrap();
+                            // The executor stats are reported once the task ends.
+                            if task_status != TaskStatusProst::Running && let Some(collector) = self.ctx.executor_stats() {
+                                collector.merge_from_protobuf(&task_info.executor_stats);
+                            }
+                            match task_status {
                                 TaskStatusProst::Running => {
                                     running_task_cnt += 1;
                                     // The task running count should always less or equal than the registered tasks
@@ -683,7 +687,12 @@ impl StageRunner {
 
         let t_id = task_id.task_id;
         let stream_status = compute_client
-            .create_task(task_id, plan_fragment, self.epoch)
+            .create_task(
+                task_id,
+                plan_fragment,
+                self.epoch,
+                self.ctx.executor_stats().is_some(),
+            )
             .await
             .map_err(|e| anyhow!(e))?;
 
@@ -700,11 +709,7 @@ impl StageRunner {
         task_id: TaskId,
         partition: Option<PartitionInfo>,
     ) -> PlanFragment {
-        // Used to maintain auto-increment identity_id of a task.
-        let identity_id: Rc<RefCell<u64>> = Rc::new(RefCell::new(0));
-
-        let plan_node_prost =
-            self.convert_plan_node(&self.stage.root, task_id, partition, identity_id);
+        let plan_node_prost = self.convert_plan_node(&self.stage.root, task_id, partition);
         let exchange_info = self.stage.exchange_info.clone();
 
         PlanFragment {
@@ -718,15 +723,9 @@ impl StageRunner {
         execution_plan_node: &ExecutionPlanNode,
         task_id: TaskId,
         partition: Option<PartitionInfo>,
-        identity_id: Rc<RefCell<u64>>,
     ) -> PlanNodeProst {
-        // Generate identity
-        let identity = {
-            let identity_type = execution_plan_node.plan_node_type;
-            let id = *identity_id.borrow();
-            identity_id.replace(id + 1);
-            format!("{:?}-{}", identity_type, id)
-        };
+        // The identity is also the key of the executor stats.
+        let identity = execution_plan_node.identity();
 
         match execution_plan_node.plan_node_type {
             PlanNodeType::BatchExchange => {
@@ -768,9 +767,12 @@ impl StageRunner {
                 let NodeBody::RowSeqScan(mut scan_node) = node_body else {
                     unreachable!();
                 };
-                let partition = partition.expect("no partition info for seq scan");
-                scan_node.vnode_bitmap = Some(partition.vnode_bitmap);
-                scan_node.scan_ranges = partition.scan_ranges;
+                // There's no partition info for the scans in a recursive union or a
+                // nested-loop apply, see `BatchPlanFragmenter::collect_stage_table_scan`.
+                if let Some(partition) = partition {
+                    scan_node.vnode_bitmap = Some(partition.vnode_bitmap);
+                    scan_node.scan_ranges = partition.scan_ranges;
+                }
                 PlanNodeProst {
                     children: vec![],
                     identity,
@@ -781,9 +783,7 @@ impl StageRunner {
                 let children = execution_plan_node
                     .children
                     .iter()
-                    .map(|e| {
-                        self.convert_plan_node(e, task_id, partition.clone(), identity_id.clone())
-                    })
+                    .map(|e| self.convert_plan_node(e, task_id, partition.clone()))
                     .collect();
 
                 PlanNodeProst {
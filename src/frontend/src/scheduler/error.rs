@@ -37,6 +37,10 @@ pub enum SchedulerError {
     #[error("Canceled by user")]
     QueryCancelError,
 
+    /// Used when the query runs longer than the `statement_timeout` of the session.
+    #[error("Canceling statement due to statement timeout")]
+    QueryTimeout,
+
     #[error(transparent)]
     Internal(#[from] anyhow::Error),
 }
@@ -17,12 +17,13 @@ use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
-use futures::Stream;
+use futures::{future, Stream, StreamExt};
 use futures_async_stream::try_stream;
 use itertools::Itertools;
 use pgwire::pg_server::BoxedError;
-use risingwave_batch::executor::{BoxedDataChunkStream, ExecutorBuilder};
+use risingwave_batch::executor::{BoxedDataChunkStream, ExecutorBuilder, ExecutorStatsCollector};
 use risingwave_batch::task::TaskId;
 use risingwave_common::array::DataChunk;
 use risingwave_common::bail;
@@ -34,15 +35,15 @@ use risingwave_pb::batch_plan::{
     ExchangeInfo, ExchangeSource, LocalExecutePlan, PlanFragment, PlanNode as PlanNodeProst,
     TaskId as ProstTaskId, TaskOutputId,
 };
+use tokio::sync::oneshot;
 use tracing::debug;
-use uuid::Uuid;
 
 use super::plan_fragmenter::{PartitionInfo, QueryStageRef};
 use super::HummockSnapshotGuard;
 use crate::optimizer::plan_node::PlanNodeType;
 use crate::scheduler::plan_fragmenter::{ExecutionPlanNode, Query, StageId};
 use crate::scheduler::task_context::FrontendBatchTaskContext;
-use crate::scheduler::SchedulerResult;
+use crate::scheduler::{SchedulerError, SchedulerResult};
 use crate::session::{AuthContext, FrontendEnv};
 
 pub struct LocalQueryStream {
@@ -73,6 +74,12 @@ pub struct LocalQueryExecution {
     // The snapshot will be released when LocalQueryExecution is dropped.
     snapshot: HummockSnapshotGuard,
     auth_context: Arc<AuthContext>,
+    // Collects the runtime stats of executors if set, used by `EXPLAIN ANALYZE`.
+    executor_stats: Option<ExecutorStatsCollector>,
+    /// The query will be aborted if it's still running after the timeout.
+    timeout: Option<Duration>,
+    /// The query will be aborted once a cancel request of the session is received.
+    cancel_rx: Option<oneshot::Receiver<()>>,
 }
 
 impl LocalQueryExecution {
@@ -89,18 +96,40 @@ impl LocalQueryExecution {
             front_env,
             snapshot,
             auth_context,
+            executor_stats: None,
+            timeout: None,
+            cancel_rx: None,
         }
     }
 
+    /// Aborts the query if it's still running after the timeout.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Aborts the query once a message is received from `cancel_rx`.
+    pub fn with_cancel_rx(mut self, cancel_rx: oneshot::Receiver<()>) -> Self {
+        self.cancel_rx = Some(cancel_rx);
+        self
+    }
+
+    /// Collects the runtime stats of executors in all stages during execution.
+    pub fn with_executor_stats(mut self, collector: ExecutorStatsCollector) -> Self {
+        self.executor_stats = Some(collector);
+        self
+    }
+
     #[try_stream(ok = DataChunk, error = RwError)]
-    pub async fn run_inner(self) {
+    pub async fn run_inner(mut self) {
         debug!(
             "Starting to run query: {:?}, sql: '{}'",
             self.query.query_id, self.sql
         );
 
         let context =
-            FrontendBatchTaskContext::new(self.front_env.clone(), self.auth_context.clone());
+            FrontendBatchTaskContext::new(self.front_env.clone(), self.auth_context.clone())
+                .with_executor_stats(self.executor_stats.clone());
 
         let task_id = TaskId {
             query_id: self.query.query_id.id.clone(),
@@ -119,8 +148,10 @@ impl LocalQueryExecution {
         );
         let executor = executor.build().await?;
 
+        let data_stream =
+            abort_on_timeout_or_cancel(executor.execute(), self.timeout, self.cancel_rx.take());
         #[for_await]
-        for chunk in executor.execute() {
+        for chunk in data_stream {
             yield chunk?;
         }
     }
@@ -247,6 +278,7 @@ impl LocalQueryExecution {
                             plan: Some(second_stage_plan_fragment),
                             // TODO: Add support to use current epoch when needed
                             epoch: self.snapshot.get_committed_epoch(),
+                            collect_executor_stats: self.executor_stats.is_some(),
                         };
                         let exchange_source = ExchangeSource {
                             task_output_id: Some(TaskOutputId {
@@ -277,12 +309,15 @@ impl LocalQueryExecution {
                         plan: Some(second_stage_plan_fragment),
                         // TODO: Add support to use current epoch when needed
                         epoch: self.snapshot.get_committed_epoch(),
+                        collect_executor_stats: self.executor_stats.is_some(),
                     };
 
                     let workers = if second_stage.parallelism == 1 {
                         vec![self.front_env.worker_node_manager().next_random()?]
                     } else {
-                        self.front_env.worker_node_manager().list_worker_nodes()
+                        self.front_env
+                            .worker_node_manager()
+                            .list_schedulable_worker_nodes()
                     };
                     *sources = workers
                         .iter()
@@ -309,7 +344,7 @@ impl LocalQueryExecution {
                     /// Since all the rest plan is embedded into the exchange node,
                     /// there is no children any more.
                     children: vec![],
-                    identity: Uuid::new_v4().to_string(),
+                    identity: execution_plan_node.identity(),
                     node_body: Some(node_body),
                 })
             }
@@ -328,8 +363,7 @@ impl LocalQueryExecution {
 
                 Ok(PlanNodeProst {
                     children: vec![],
-                    // TODO: Generate meaningful identify
-                    identity: Uuid::new_v4().to_string(),
+                    identity: execution_plan_node.identity(),
                     node_body: Some(node_body),
                 })
             }
@@ -354,8 +388,10 @@ impl LocalQueryExecution {
                             .ok()
                             .flatten()
                             .unwrap_or_default();
-                        node.worker_nodes =
-                            self.front_env.worker_node_manager().list_worker_nodes();
+                        node.worker_nodes = self
+                            .front_env
+                            .worker_node_manager()
+                            .list_worker_nodes_for_batch()?;
                     }
                     _ => unreachable!(),
                 }
@@ -368,7 +404,7 @@ impl LocalQueryExecution {
 
                 Ok(PlanNodeProst {
                     children: vec![left_child],
-                    identity: Uuid::new_v4().to_string(),
+                    identity: execution_plan_node.identity(),
                     node_body: Some(node_body),
                 })
             }
@@ -381,11 +417,113 @@ impl LocalQueryExecution {
 
                 Ok(PlanNodeProst {
                     children,
-                    // TODO: Generate meaningful identify
-                    identity: Uuid::new_v4().to_string(),
+                    identity: execution_plan_node.identity(),
                     node_body: Some(execution_plan_node.node.clone()),
                 })
             }
         }
     }
 }
+
+/// Forwards the chunks of `data_stream`, but aborts with an error once `timeout` elapses or a
+/// message is received from `cancel_rx`, whichever comes first.
+#[try_stream(ok = DataChunk, error = RwError)]
+async fn abort_on_timeout_or_cancel(
+    data_stream: BoxedDataChunkStream,
+    timeout: Option<Duration>,
+    cancel_rx: Option<oneshot::Receiver<()>>,
+) {
+    let timeout = async move {
+        match timeout {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => future::pending().await,
+        }
+    };
+    let cancelled = async move {
+        match cancel_rx {
+            // The query is not cancelled if the sender is dropped.
+            Some(cancel_rx) if cancel_rx.await.is_ok() => {}
+            _ => future::pending().await,
+        }
+    };
+    tokio::pin!(timeout, cancelled);
+
+    let mut data_stream = data_stream.fuse();
+    loop {
+        let chunk = tokio::select! {
+            biased;
+            _ = &mut cancelled => Err(SchedulerError::QueryCancelError),
+            _ = &mut timeout => Err(SchedulerError::QueryTimeout),
+            chunk = data_stream.next() => Ok(chunk),
+        };
+        match chunk? {
+            Some(chunk) => yield chunk?,
+            None => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+
+    fn data_chunk() -> DataChunk {
+        DataChunk::new_dummy(3)
+    }
+
+    fn pending_after_one_chunk() -> BoxedDataChunkStream {
+        stream::once(async { Ok(data_chunk()) })
+            .chain(stream::pending())
+            .boxed()
+    }
+
+    #[tokio::test]
+    async fn test_finished_query() {
+        let data_stream = stream::iter(vec![Ok(data_chunk()), Ok(data_chunk())]).boxed();
+        let (_cancel_tx, cancel_rx) = oneshot::channel();
+        let chunks =
+            abort_on_timeout_or_cancel(data_stream, Some(Duration::from_secs(60)), Some(cancel_rx))
+                .collect::<Vec<_>>()
+                .await;
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|chunk| chunk.is_ok()));
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout() {
+        let mut data_stream = abort_on_timeout_or_cancel(
+            pending_after_one_chunk(),
+            Some(Duration::from_millis(10)),
+            None,
+        )
+        .boxed();
+        assert!(data_stream.next().await.unwrap().is_ok());
+        let err = data_stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("statement timeout"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_query_cancel() {
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        let mut data_stream =
+            abort_on_timeout_or_cancel(pending_after_one_chunk(), None, Some(cancel_rx)).boxed();
+        assert!(data_stream.next().await.unwrap().is_ok());
+        cancel_tx.send(()).unwrap();
+        let err = data_stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("Canceled by user"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_dropped_canceller() {
+        let (cancel_tx, cancel_rx) = oneshot::channel::<()>();
+        drop(cancel_tx);
+        let data_stream = stream::iter(vec![Ok(data_chunk())]).boxed();
+        let chunks = abort_on_timeout_or_cancel(data_stream, None, Some(cancel_rx))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].is_ok());
+    }
+}
@@ -17,6 +17,7 @@
 use std::sync::Arc;
 
 use futures::Stream;
+use risingwave_batch::executor::ExecutorStatsCollector;
 use risingwave_common::array::DataChunk;
 use risingwave_common::error::Result;
 
@@ -45,20 +46,35 @@ pub trait DataChunkStream = Stream<Item = Result<DataChunk>>;
 /// Context for mpp query execution.
 pub struct ExecutionContext {
     session: Arc<SessionImpl>,
+    /// Collects the runtime stats of executors in all stages if set, used by `EXPLAIN ANALYZE`.
+    executor_stats: Option<ExecutorStatsCollector>,
 }
 
 pub type ExecutionContextRef = Arc<ExecutionContext>;
 
 impl ExecutionContext {
     pub fn new(session: Arc<SessionImpl>) -> Self {
-        Self { session }
+        Self {
+            session,
+            executor_stats: None,
+        }
+    }
+
+    pub fn with_executor_stats(mut self, collector: ExecutorStatsCollector) -> Self {
+        self.executor_stats = Some(collector);
+        self
     }
 
     pub fn session(&self) -> &SessionImpl {
         &self.session
     }
 
+    pub fn executor_stats(&self) -> Option<&ExecutorStatsCollector> {
+        self.executor_stats.as_ref()
+    }
+
     pub fn to_batch_task_context(&self) -> FrontendBatchTaskContext {
         FrontendBatchTaskContext::new(self.session.env().clone(), self.session.auth_context())
+            .with_executor_stats(self.executor_stats.clone())
     }
 }
@@ -107,6 +107,17 @@ impl ExecutionPlanNode {
     pub fn node_type(&self) -> PlanNodeType {
         self.plan_node_type
     }
+
+    /// The identity of the plan node used by executors in local execution mode, which is also
+    /// the key of the executor stats.
+    pub fn identity(&self) -> String {
+        plan_node_identity(self.plan_node_type, self.plan_node_id)
+    }
+}
+
+/// Identity of a batch plan node, e.g. `BatchProject-3`.
+pub fn plan_node_identity(plan_node_type: PlanNodeType, plan_node_id: PlanNodeId) -> String {
+    format!("{:?}-{}", plan_node_type, plan_node_id.0)
 }
 
 /// `BatchPlanFragmenter` splits a query plan into fragments.
@@ -476,7 +487,7 @@ impl BatchPlanFragmenter {
                     {
                         lookup_join_parallelism
                     } else {
-                        self.worker_node_manager.worker_node_count()
+                        self.worker_node_manager.schedulable_worker_node_count()
                     }
                 }
                 Some(info) => info.partitions.as_ref().map(|m| m.len()).unwrap_or(1),
@@ -553,6 +564,15 @@ impl BatchPlanFragmenter {
             // Do not visit next stage.
             return Ok(None);
         }
+        if matches!(
+            node.node_type(),
+            PlanNodeType::BatchRecursiveUnion | PlanNodeType::BatchNestedLoopApply
+        ) {
+            // The scans in a recursive union or a nested-loop apply may be of different tables,
+            // which are all executed in a single task. Each of them scans all the vnodes with its
+            // own scan ranges.
+            return Ok(None);
+        }
 
         if let Some(scan_node) = node.as_batch_seq_scan() {
             let name = scan_node.logical().table_name().to_owned();
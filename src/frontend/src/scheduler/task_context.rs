@@ -14,8 +14,8 @@
 
 use std::sync::Arc;
 
-use risingwave_batch::executor::BatchTaskMetricsWithTaskLabels;
-use risingwave_batch::task::{BatchTaskContext, TaskOutput, TaskOutputId};
+use risingwave_batch::executor::{BatchTaskMetricsWithTaskLabels, ExecutorStatsCollector};
+use risingwave_batch::task::{BatchMemoryTrackerRef, BatchTaskContext, TaskOutput, TaskOutputId};
 use risingwave_common::catalog::SysCatalogReaderRef;
 use risingwave_common::config::BatchConfig;
 use risingwave_common::error::Result;
@@ -31,11 +31,22 @@ use crate::session::{AuthContext, FrontendEnv};
 pub struct FrontendBatchTaskContext {
     env: FrontendEnv,
     auth_context: Arc<AuthContext>,
+    executor_stats: Option<ExecutorStatsCollector>,
 }
 
 impl FrontendBatchTaskContext {
     pub fn new(env: FrontendEnv, auth_context: Arc<AuthContext>) -> Self {
-        Self { env, auth_context }
+        Self {
+            env,
+            auth_context,
+            executor_stats: None,
+        }
+    }
+
+    /// Collects the runtime stats of executors, used by `EXPLAIN ANALYZE`.
+    pub fn with_executor_stats(mut self, collector: Option<ExecutorStatsCollector>) -> Self {
+        self.executor_stats = collector;
+        self
     }
 }
 
@@ -70,6 +81,10 @@ impl BatchTaskContext for FrontendBatchTaskContext {
         None
     }
 
+    fn executor_stats(&self) -> Option<ExecutorStatsCollector> {
+        self.executor_stats.clone()
+    }
+
     fn client_pool(&self) -> ComputeClientPoolRef {
         self.env.client_pool()
     }
@@ -77,4 +92,8 @@ impl BatchTaskContext for FrontendBatchTaskContext {
     fn get_config(&self) -> &BatchConfig {
         self.env.batch_config()
     }
+
+    fn memory_tracker(&self) -> Option<BatchMemoryTrackerRef> {
+        None
+    }
 }
@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
+use itertools::Itertools;
 use rand::seq::SliceRandom;
 use risingwave_common::bail;
 use risingwave_common::types::{ParallelUnitId, VnodeMapping};
@@ -36,6 +37,36 @@ struct WorkerNodeManagerInner {
     fragment_vnode_mapping: HashMap<FragmentId, VnodeMapping>,
 }
 
+impl WorkerNodeManagerInner {
+    /// Lists the schedulable worker nodes, with the parallel units of the cordoned ones spread
+    /// over them. Batch tasks read from the shared storage, so a task placed by the vnode mapping
+    /// can run on any worker.
+    fn list_worker_nodes_for_batch(&self) -> SchedulerResult<Vec<WorkerNode>> {
+        let (mut schedulable, cordoned): (Vec<_>, Vec<_>) = self
+            .worker_nodes
+            .iter()
+            .cloned()
+            .partition(|x| !x.unschedulable);
+        let cordoned_parallel_units = cordoned
+            .into_iter()
+            .flat_map(|x| x.parallel_units)
+            .sorted_by_key(|pu| pu.id)
+            .collect_vec();
+        if cordoned_parallel_units.is_empty() {
+            return Ok(schedulable);
+        }
+        if schedulable.is_empty() {
+            return Err(SchedulerError::EmptyWorkerNodes);
+        }
+        schedulable.sort_by_key(|x| x.id);
+        let len = schedulable.len();
+        for (i, pu) in cordoned_parallel_units.into_iter().enumerate() {
+            schedulable[i % len].parallel_units.push(pu);
+        }
+        Ok(schedulable)
+    }
+}
+
 pub type WorkerNodeManagerRef = Arc<WorkerNodeManager>;
 
 impl Default for WorkerNodeManager {
@@ -68,12 +99,41 @@ impl WorkerNodeManager {
         self.inner.write().unwrap().worker_nodes.push(node);
     }
 
+    pub fn update_worker_node(&self, node: WorkerNode) {
+        let mut write_guard = self.inner.write().unwrap();
+        if let Some(worker_node) = write_guard
+            .worker_nodes
+            .iter_mut()
+            .find(|x| x.id == node.id)
+        {
+            *worker_node = node;
+        }
+    }
+
     pub fn remove_worker_node(&self, node: WorkerNode) {
         self.inner
             .write()
             .unwrap()
             .worker_nodes
-            .retain(|x| *x != node);
+            .retain(|x| x.id != node.id);
+    }
+
+    /// Lists the worker nodes that batch tasks placed by parallel units are sent to. Cordoned
+    /// workers are left out, and their parallel units are taken over by the schedulable ones.
+    pub fn list_worker_nodes_for_batch(&self) -> SchedulerResult<Vec<WorkerNode>> {
+        self.inner.read().unwrap().list_worker_nodes_for_batch()
+    }
+
+    /// Lists worker nodes that are not cordoned, to which batch tasks can be scheduled.
+    pub fn list_schedulable_worker_nodes(&self) -> Vec<WorkerNode> {
+        self.inner
+            .read()
+            .unwrap()
+            .worker_nodes
+            .iter()
+            .filter(|x| !x.unschedulable)
+            .cloned()
+            .collect()
     }
 
     pub fn refresh(&self, nodes: Vec<WorkerNode>, mapping: HashMap<FragmentId, VnodeMapping>) {
@@ -82,16 +142,15 @@ impl WorkerNodeManager {
         write_guard.fragment_vnode_mapping = mapping;
     }
 
-    /// Get a random worker node.
+    /// Get a random schedulable worker node.
     pub fn next_random(&self) -> SchedulerResult<WorkerNode> {
-        let inner = self.inner.read().unwrap();
-        if inner.worker_nodes.is_empty() {
+        let worker_nodes = self.list_schedulable_worker_nodes();
+        if worker_nodes.is_empty() {
             tracing::error!("No worker node available.");
             return Err(SchedulerError::EmptyWorkerNodes);
         }
 
-        Ok(inner
-            .worker_nodes
+        Ok(worker_nodes
             .choose(&mut rand::thread_rng())
             .unwrap()
             .clone())
@@ -101,9 +160,21 @@ impl WorkerNodeManager {
         self.inner.read().unwrap().worker_nodes.len()
     }
 
+    pub fn schedulable_worker_node_count(&self) -> usize {
+        self.inner
+            .read()
+            .unwrap()
+            .worker_nodes
+            .iter()
+            .filter(|x| !x.unschedulable)
+            .count()
+    }
+
     /// If parallel unit ids is empty, the scheduler may fail to schedule any task and stuck at
     /// schedule next stage. If we do not return error in this case, needs more complex control
     /// logic above. Report in this function makes the schedule root fail reason more clear.
+    ///
+    /// The parallel units of cordoned workers are mapped to schedulable workers instead.
     pub fn get_workers_by_parallel_unit_ids(
         &self,
         parallel_unit_ids: &[ParallelUnitId],
@@ -111,7 +182,7 @@ impl WorkerNodeManager {
         if parallel_unit_ids.is_empty() {
             return Err(SchedulerError::EmptyWorkerNodes);
         }
-        let pu_to_worker = get_pu_to_worker_mapping(&self.inner.read().unwrap().worker_nodes);
+        let pu_to_worker = get_pu_to_worker_mapping(&self.list_worker_nodes_for_batch()?);
 
         let mut workers = Vec::with_capacity(parallel_unit_ids.len());
         for parallel_unit_id in parallel_unit_ids {
@@ -167,7 +238,7 @@ impl WorkerNodeManager {
 mod tests {
 
     use risingwave_common::util::addr::HostAddr;
-    use risingwave_pb::common::{worker_node, WorkerType};
+    use risingwave_pb::common::{worker_node, WorkerNode, WorkerType};
 
     #[test]
     fn test_worker_node_manager() {
@@ -184,6 +255,7 @@ mod tests {
                 host: Some(HostAddr::try_from("127.0.0.1:1234").unwrap().to_protobuf()),
                 state: worker_node::State::Running as i32,
                 parallel_units: vec![],
+                unschedulable: false,
             },
             WorkerNode {
                 id: 2,
@@ -191,6 +263,7 @@ mod tests {
                 host: Some(HostAddr::try_from("127.0.0.1:1235").unwrap().to_protobuf()),
                 state: worker_node::State::Running as i32,
                 parallel_units: vec![],
+                unschedulable: false,
             },
         ];
         worker_nodes
@@ -199,6 +272,17 @@ mod tests {
         assert_eq!(manager.worker_node_count(), 2);
         assert_eq!(manager.list_worker_nodes(), worker_nodes);
 
+        // A cordoned worker is not schedulable.
+        manager.update_worker_node(WorkerNode {
+            unschedulable: true,
+            ..worker_nodes[1].clone()
+        });
+        assert_eq!(manager.worker_node_count(), 2);
+        assert_eq!(manager.schedulable_worker_node_count(), 1);
+        assert_eq!(manager.next_random().unwrap(), worker_nodes[0]);
+        manager.update_worker_node(worker_nodes[1].clone());
+        assert_eq!(manager.schedulable_worker_node_count(), 2);
+
         manager.remove_worker_node(worker_nodes[0].clone());
         assert_eq!(manager.worker_node_count(), 1);
         assert_eq!(
@@ -206,4 +290,120 @@ mod tests {
             worker_nodes.as_slice()[1..].to_vec()
         );
     }
+
+    /// Three workers with parallel units `[0, 1]`, `[2, 3]` and `[4, 5]`.
+    fn worker_nodes_with_parallel_units() -> Vec<WorkerNode> {
+        use risingwave_pb::common::ParallelUnit;
+
+        (1..=3)
+            .map(|id| WorkerNode {
+                id,
+                r#type: WorkerType::ComputeNode as i32,
+                state: worker_node::State::Running as i32,
+                parallel_units: (id * 2 - 2..id * 2)
+                    .map(|pu| ParallelUnit {
+                        id: pu,
+                        worker_node_id: id,
+                    })
+                    .collect(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn worker_ids(workers: Vec<WorkerNode>) -> Vec<u32> {
+        workers.into_iter().map(|w| w.id).collect()
+    }
+
+    #[test]
+    fn test_cordon_for_parallel_units() {
+        use super::*;
+
+        let worker_nodes = worker_nodes_with_parallel_units();
+        let manager = WorkerNodeManager::mock(worker_nodes.clone());
+        let all_parallel_units = (0..6).collect_vec();
+        assert_eq!(
+            worker_ids(
+                manager
+                    .get_workers_by_parallel_unit_ids(&all_parallel_units)
+                    .unwrap()
+            ),
+            vec![1, 1, 2, 2, 3, 3]
+        );
+
+        // The tasks of the parallel units on a cordoned worker are spread over the others.
+        manager.update_worker_node(WorkerNode {
+            unschedulable: true,
+            ..worker_nodes[1].clone()
+        });
+        assert_eq!(
+            worker_ids(
+                manager
+                    .get_workers_by_parallel_unit_ids(&all_parallel_units)
+                    .unwrap()
+            ),
+            vec![1, 1, 1, 3, 3, 3]
+        );
+        let batch_workers = manager.list_worker_nodes_for_batch().unwrap();
+        assert_eq!(worker_ids(batch_workers.clone()), vec![1, 3]);
+        assert_eq!(
+            batch_workers
+                .iter()
+                .map(|w| w.parallel_units.iter().map(|pu| pu.id).collect_vec())
+                .collect_vec(),
+            vec![vec![0, 1, 2], vec![4, 5, 3]]
+        );
+
+        // Nothing can be scheduled if all workers are cordoned.
+        for worker in &worker_nodes {
+            manager.update_worker_node(WorkerNode {
+                unschedulable: true,
+                ..worker.clone()
+            });
+        }
+        assert!(manager
+            .get_workers_by_parallel_unit_ids(&all_parallel_units)
+            .is_err());
+        assert!(manager.list_worker_nodes_for_batch().is_err());
+
+        manager.refresh(worker_nodes, HashMap::new());
+        assert_eq!(manager.list_worker_nodes_for_batch().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_drain_for_vnode_mapping() {
+        use super::*;
+
+        let worker_nodes = worker_nodes_with_parallel_units();
+        let manager = WorkerNodeManager::mock(worker_nodes.clone());
+        let fragment_id = 1;
+        manager.insert_fragment_mapping(fragment_id, vec![0, 1, 2, 3, 4, 5]);
+
+        // Draining worker 2 cordons it first, while its actors are still being migrated.
+        manager.update_worker_node(WorkerNode {
+            unschedulable: true,
+            ..worker_nodes[1].clone()
+        });
+        let scan_workers = |manager: &WorkerNodeManager| {
+            let parallel_units = manager
+                .get_fragment_mapping(&fragment_id)
+                .unwrap()
+                .into_iter()
+                .sorted()
+                .dedup()
+                .collect_vec();
+            worker_ids(
+                manager
+                    .get_workers_by_parallel_unit_ids(&parallel_units)
+                    .unwrap(),
+            )
+        };
+        assert!(!scan_workers(&manager).contains(&2));
+
+        // Then the actors are moved away and the vnode mapping no longer refers to worker 2.
+        manager.update_fragment_mapping(fragment_id, vec![0, 1, 4, 5, 0, 4]);
+        assert_eq!(scan_workers(&manager), vec![1, 1, 3, 3]);
+        manager.remove_worker_node(worker_nodes[1].clone());
+        assert_eq!(scan_workers(&manager), vec![1, 1, 3, 3]);
+    }
 }
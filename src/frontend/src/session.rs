@@ -19,22 +19,26 @@ use std::marker::Sync;
 use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
 // use tokio::sync::Mutex;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use parking_lot::{RwLock, RwLockReadGuard};
+use pgwire::pg_extended::PreparedStatement;
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::PgResponse;
-use pgwire::pg_server::{BoxedError, Session, SessionId, SessionManager, UserAuthenticator};
+use pgwire::pg_server::{
+    BoxedError, CopyInData, Session, SessionId, SessionManager, UserAuthenticator,
+};
+use pgwire::scram::ScramSecret;
+use pgwire::types::Format;
 use rand::RngCore;
-use risingwave_common::catalog::DEFAULT_SCHEMA_NAME;
 #[cfg(test)]
-use risingwave_common::catalog::{
-    DEFAULT_DATABASE_NAME, DEFAULT_SUPER_USER, DEFAULT_SUPER_USER_ID,
-};
+use risingwave_common::catalog::DEFAULT_DATABASE_NAME;
+use risingwave_common::catalog::{DEFAULT_SCHEMA_NAME, DEFAULT_SUPER_USER, DEFAULT_SUPER_USER_ID};
 use risingwave_common::config::{load_config, BatchConfig};
-use risingwave_common::error::{Result, RwError};
+use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::monitor::process_linux::monitor_process;
 use risingwave_common::session_config::ConfigMap;
+use risingwave_common::types::DataType;
 use risingwave_common::util::addr::HostAddr;
 use risingwave_common_service::observer_manager::ObserverManager;
 use risingwave_common_service::MetricsManager;
@@ -44,23 +48,24 @@ use risingwave_pb::user::grant_privilege::{Action, Object};
 use risingwave_rpc_client::{ComputeClientPool, ComputeClientPoolRef, MetaClient};
 use risingwave_sqlparser::ast::{ObjectName, ShowObject, Statement};
 use risingwave_sqlparser::parser::Parser;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
 use tokio::sync::oneshot::Sender;
-use tokio::sync::watch;
+use tokio::sync::{oneshot, watch};
 use tokio::task::JoinHandle;
 
-use crate::binder::Binder;
+use crate::binder::{Binder, BoundStatement};
 use crate::catalog::catalog_service::{CatalogReader, CatalogWriter, CatalogWriterImpl};
 use crate::catalog::root_catalog::{Catalog, SchemaPath};
-use crate::catalog::{check_schema_writable, DatabaseId, SchemaId};
+use crate::catalog::{check_schema_writable, DatabaseId, SchemaId, SourceId};
 use crate::expr::CorrelatedId;
-use crate::handler::handle;
+use crate::handler::alter_source::apply_proposed_source_columns;
 use crate::handler::privilege::{check_privileges, ObjectCheckItem};
-use crate::handler::util::to_pg_field;
+use crate::handler::util::{data_type_to_type_oid, to_pg_field, type_oid_to_data_type};
+use crate::handler::{handle, handle_copy_in, handle_prepared};
 use crate::meta_client::{FrontendMetaClient, FrontendMetaClientImpl};
 use crate::monitor::FrontendMetrics;
 use crate::observer::FrontendObserverNode;
 use crate::optimizer::plan_node::PlanNodeId;
-use crate::planner::Planner;
 use crate::scheduler::worker_node_manager::{WorkerNodeManager, WorkerNodeManagerRef};
 use crate::scheduler::{HummockSnapshotManager, HummockSnapshotManagerRef, QueryManager};
 use crate::user::user_authentication::md5_hash_with_salt;
@@ -210,7 +215,7 @@ pub struct FrontendEnv {
     batch_config: BatchConfig,
 }
 
-type SessionMapRef = Arc<Mutex<HashMap<(i32, i32), Arc<SessionImpl>>>>;
+pub type SessionMapRef = Arc<Mutex<HashMap<(i32, i32), Arc<SessionImpl>>>>;
 
 impl FrontendEnv {
     pub fn mock() -> Self {
@@ -319,6 +324,7 @@ impl FrontendEnv {
             user_info_updated_rx,
         ));
 
+        let (proposed_source_tx, proposed_source_rx) = mpsc::unbounded_channel();
         let frontend_observer_node = FrontendObserverNode::new(
             worker_node_manager.clone(),
             catalog,
@@ -326,6 +332,7 @@ impl FrontendEnv {
             user_info_manager,
             user_info_updated_tx,
             hummock_snapshot_manager.clone(),
+            proposed_source_tx,
         );
         let observer_manager =
             ObserverManager::new_with_meta_client(meta_client.clone(), frontend_observer_node)
@@ -346,28 +353,74 @@ impl FrontendEnv {
             MetricsManager::boot_metrics_service(opts.prometheus_listener_addr.clone(), registry);
         }
 
+        let env = Self {
+            catalog_reader,
+            catalog_writer,
+            user_info_reader,
+            user_info_writer,
+            worker_node_manager,
+            meta_client: frontend_meta_client,
+            query_manager,
+            hummock_snapshot_manager,
+            server_addr: frontend_address,
+            client_pool,
+            frontend_metrics,
+            sessions_map: Arc::new(Mutex::new(HashMap::new())),
+            batch_config,
+        };
+        tokio::spawn(
+            env.clone()
+                .apply_proposed_source_columns(proposed_source_rx),
+        );
+
         Ok((
-            Self {
-                catalog_reader,
-                catalog_writer,
-                user_info_reader,
-                user_info_writer,
-                worker_node_manager,
-                meta_client: frontend_meta_client,
-                query_manager,
-                hummock_snapshot_manager,
-                server_addr: frontend_address,
-                client_pool,
-                frontend_metrics,
-                sessions_map: Arc::new(Mutex::new(HashMap::new())),
-                batch_config,
-            },
+            env,
             observer_join_handle,
             heartbeat_join_handle,
             heartbeat_shutdown_sender,
         ))
     }
 
+    /// Adds the columns proposed by meta to the sources, each in an internal session of the
+    /// superuser on the database of the source. All the frontends are notified of a proposal, and
+    /// meta accepts the first of them to apply it.
+    async fn apply_proposed_source_columns(
+        self,
+        mut proposed_source_rx: UnboundedReceiver<(DatabaseId, SchemaId, SourceId)>,
+    ) {
+        while let Some((database_id, schema_id, source_id)) = proposed_source_rx.recv().await {
+            let database = match self
+                .catalog_reader
+                .read_guard()
+                .get_database_by_id(&database_id)
+            {
+                Ok(database) => database.name().to_string(),
+                // The database has been dropped since the proposal.
+                Err(_) => continue,
+            };
+            let session = Arc::new(SessionImpl::new(
+                self.clone(),
+                Arc::new(AuthContext::new(
+                    database,
+                    DEFAULT_SUPER_USER.to_string(),
+                    DEFAULT_SUPER_USER_ID,
+                )),
+                UserAuthenticator::None,
+                // The internal session is not registered, so it's never cancelled by id.
+                (-1, 0),
+            ));
+            if let Err(e) =
+                apply_proposed_source_columns(&session, database_id, schema_id, source_id).await
+            {
+                tracing::warn!(
+                    "failed to add the proposed columns to source {}: {}",
+                    source_id,
+                    e
+                );
+            }
+        }
+    }
+
     /// Get a reference to the frontend env's catalog writer.
     pub fn catalog_writer(&self) -> &dyn CatalogWriter {
         &*self.catalog_writer
@@ -408,6 +461,10 @@ impl FrontendEnv {
         &self.query_manager
     }
 
+    pub fn sessions_map(&self) -> &SessionMapRef {
+        &self.sessions_map
+    }
+
     pub fn hummock_snapshot_manager(&self) -> &HummockSnapshotManagerRef {
         &self.hummock_snapshot_manager
     }
@@ -451,6 +508,46 @@ pub struct SessionImpl {
 
     /// Identified by process_id, secret_key. Corresponds to SessionManager.
     id: (i32, i32),
+
+    /// The statement being run and the time it started, shown by `SHOW PROCESSLIST`.
+    current_statement: Mutex<Option<(String, Instant)>>,
+    /// Set to true by `KILL`, on which the connection of the session will be closed.
+    killed: watch::Sender<bool>,
+    /// Used to cancel the query running in local mode, if any.
+    local_query_canceller: Mutex<Option<Sender<()>>>,
+
+    /// Statements prepared by the extended query protocol, keyed by the SQL and the specified
+    /// parameter types.
+    prepared_statements: Mutex<HashMap<(String, Vec<Option<DataType>>), Arc<CachedStatement>>>,
+
+    /// Whether the session has written to tables without flushing, in which case the changes are
+    /// not visible to the batch scans of `UPDATE` and `DELETE` yet.
+    unflushed_writes: AtomicBool,
+}
+
+/// The maximum number of prepared statements cached in a session. The cache is cleared when full.
+const PREPARED_STATEMENT_CACHE_CAPACITY: usize = 1024;
+
+/// A statement parsed and bound for the extended query protocol. It is reused until the catalog,
+/// the users or the session configs affecting the binding change. The plan is still generated on
+/// each execution, with the parameters replaced by literals.
+struct CachedStatement {
+    /// `None` for an empty query.
+    stmt: Option<Statement>,
+    /// The bound statement with the parameters as placeholders, only for queries and DML.
+    bound: Option<BoundStatement>,
+    param_types: Vec<DataType>,
+    row_description: Vec<PgFieldDescriptor>,
+    bind_versions: BindVersions,
+}
+
+/// The states that a statement is bound with.
+#[derive(PartialEq, Eq)]
+struct BindVersions {
+    catalog_version: u64,
+    user_info_version: u64,
+    search_path: String,
+    timezone: String,
 }
 
 impl SessionImpl {
@@ -466,6 +563,11 @@ impl SessionImpl {
             user_authenticator,
             config_map: RwLock::new(Default::default()),
             id,
+            current_statement: Mutex::new(None),
+            killed: watch::channel(false).0,
+            local_query_canceller: Mutex::new(None),
+            prepared_statements: Default::default(),
+            unflushed_writes: AtomicBool::new(false),
         }
     }
 
@@ -482,6 +584,11 @@ impl SessionImpl {
             config_map: Default::default(),
             // Mock session use non-sense id.
             id: (0, 0),
+            current_statement: Mutex::new(None),
+            killed: watch::channel(false).0,
+            local_query_canceller: Mutex::new(None),
+            prepared_statements: Default::default(),
+            unflushed_writes: AtomicBool::new(false),
         }
     }
 
@@ -517,6 +624,108 @@ impl SessionImpl {
         self.id
     }
 
+    pub fn has_unflushed_writes(&self) -> bool {
+        self.unflushed_writes.load(Ordering::Relaxed)
+    }
+
+    pub fn set_unflushed_writes(&self, unflushed_writes: bool) {
+        self.unflushed_writes
+            .store(unflushed_writes, Ordering::Relaxed);
+    }
+
+    /// Returns the statement being run and how long it has been running.
+    pub fn current_statement(&self) -> Option<(String, Duration)> {
+        self.current_statement
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|(sql, start_time)| (sql.clone(), start_time.elapsed()))
+    }
+
+    /// Marks the session as killed. The connection will be closed by pgwire.
+    pub fn kill(&self) {
+        self.killed.send_replace(true);
+    }
+
+    /// Returns the receiver for cancelling the local query to be run, which replaces the previous
+    /// one of the session.
+    pub fn reset_local_query_canceller(&self) -> oneshot::Receiver<()> {
+        let (tx, rx) = oneshot::channel();
+        *self.local_query_canceller.lock().unwrap() = Some(tx);
+        rx
+    }
+
+    /// Cancels the query running in local mode, if any.
+    pub fn cancel_local_query(&self) {
+        if let Some(tx) = self.local_query_canceller.lock().unwrap().take() {
+            // The query may have finished and dropped the receiver.
+            let _ = tx.send(());
+        }
+    }
+
+    /// Parses and binds a statement of the extended query protocol, inferring the types of the
+    /// parameters that are not specified. The result is cached by the SQL and the specified types.
+    fn prepare_statement(
+        &self,
+        sql: &str,
+        param_types: Vec<Option<DataType>>,
+    ) -> std::result::Result<Arc<CachedStatement>, BoxedError> {
+        let bind_versions = BindVersions {
+            catalog_version: self.env.catalog_reader().read_guard().version(),
+            user_info_version: self.env.user_info_reader().read_guard().version(),
+            search_path: self.config().get_search_path().to_string(),
+            timezone: self.config().get_timezone().to_string(),
+        };
+        let key = (sql.to_string(), param_types);
+        if let Some(statement) = self.prepared_statements.lock().unwrap().get(&key)
+            && statement.bind_versions == bind_versions
+        {
+            return Ok(statement.clone());
+        }
+
+        let mut stmts = Parser::parse_sql(sql)?;
+        if stmts.len() > 1 {
+            return Err(Box::new(Error::new(
+                ErrorKind::InvalidInput,
+                "cannot insert multiple commands into a prepared statement",
+            )));
+        }
+        let statement = match stmts.pop() {
+            None => CachedStatement {
+                stmt: None,
+                bound: None,
+                param_types: vec![],
+                row_description: vec![],
+                bind_versions,
+            },
+            Some(stmt) => {
+                let mut binder = Binder::new_with_param_types(self, key.1.clone());
+                let (row_description, bound) = infer_row_description(&mut binder, stmt.clone())?;
+                CachedStatement {
+                    stmt: Some(stmt),
+                    bound,
+                    param_types: binder.export_param_types(),
+                    row_description,
+                    bind_versions,
+                }
+            }
+        };
+        let statement = Arc::new(statement);
+
+        let mut cache = self.prepared_statements.lock().unwrap();
+        if cache.len() + 2 > PREPARED_STATEMENT_CACHE_CAPACITY {
+            cache.clear();
+        }
+        // Also cache it by the inferred types, which are all specified on execution.
+        let inferred_key = (
+            key.0.clone(),
+            statement.param_types.iter().cloned().map(Some).collect(),
+        );
+        cache.insert(inferred_key, statement.clone());
+        cache.insert(key, statement.clone());
+        Ok(statement)
+    }
+
     pub fn check_relation_name_duplicated(&self, name: ObjectName) -> Result<()> {
         let db_name = self.database();
         let catalog_reader = self.env().catalog_reader().read_guard();
@@ -557,7 +766,7 @@ impl SessionImpl {
         if schema.name() != DEFAULT_SCHEMA_NAME {
             check_privileges(
                 self,
-                &vec![ObjectCheckItem::new(
+                &[ObjectCheckItem::new(
                     schema.owner(),
                     Action::Create,
                     Object::SchemaId(schema.id()),
@@ -591,7 +800,7 @@ impl SessionImpl {
         if schema_name != DEFAULT_SCHEMA_NAME {
             check_privileges(
                 self,
-                &vec![ObjectCheckItem::new(
+                &[ObjectCheckItem::new(
                     schema.owner(),
                     Action::Create,
                     Object::SchemaId(schema.id()),
@@ -657,7 +866,22 @@ impl SessionManager<PgResponseStream> for SessionManagerImpl {
                 None => UserAuthenticator::None,
                 Some(auth_info) => {
                     if auth_info.encryption_type == EncryptionType::Plaintext as i32 {
+                        tracing::warn!(
+                            "user \"{}\" is using the deprecated cleartext password, \
+                             please reset it with ALTER USER ... PASSWORD",
+                            user_name
+                        );
                         UserAuthenticator::ClearText(auth_info.encrypted_value.clone())
+                    } else if auth_info.encryption_type == EncryptionType::ScramSha256 as i32 {
+                        let Some(secret) = std::str::from_utf8(&auth_info.encrypted_value)
+                            .ok()
+                            .and_then(ScramSecret::parse) else {
+                            return Err(Box::new(Error::new(
+                                ErrorKind::InvalidData,
+                                "malformed SCRAM-SHA-256 password",
+                            )));
+                        };
+                        UserAuthenticator::ScramSha256(secret)
                     } else if auth_info.encryption_type == EncryptionType::Md5 as i32 {
                         let mut salt = [0; 4];
                         let mut rng = rand::thread_rng();
@@ -679,9 +903,11 @@ impl SessionManager<PgResponseStream> for SessionManagerImpl {
             };
 
             // Assign a session id and insert into sessions map (for cancel request).
-            let secret_key = self.number.fetch_add(1, Ordering::Relaxed);
-            // Use a trivial strategy: process_id and secret_key are equal.
-            let id = (secret_key, secret_key);
+            let process_id = self.number.fetch_add(1, Ordering::Relaxed);
+            // The secret key is random so that a cancel request can't be forged from the
+            // process id, which is visible to other users, e.g. in `SHOW PROCESSLIST`.
+            let secret_key = rand::random::<i32>();
+            let id = (process_id, secret_key);
             let session_impl: Arc<SessionImpl> = SessionImpl::new(
                 self.env.clone(),
                 Arc::new(AuthContext::new(
@@ -707,6 +933,16 @@ impl SessionManager<PgResponseStream> for SessionManagerImpl {
     /// Used when cancel request happened, returned corresponding session ref.
     fn cancel_queries_in_session(&self, session_id: SessionId) {
         self.env.query_manager.cancel_queries_in_session(session_id);
+        let session = self
+            .env
+            .sessions_map
+            .lock()
+            .unwrap()
+            .get(&session_id)
+            .cloned();
+        if let Some(session) = session {
+            session.cancel_local_query();
+        }
     }
 
     fn end_session(&self, session: &Self::Session) {
@@ -766,75 +1002,97 @@ impl Session<PgResponseStream> for SessionImpl {
             ));
         }
         let stmt = stmts.swap_remove(0);
-        let rsp = handle(self, stmt, sql, format).await.map_err(|e| {
-            tracing::error!("failed to handle sql:\n{}:\n{}", sql, e);
+        let formats = if format { vec![Format::Binary] } else { vec![] };
+        let redacted_sql = redact_sql(sql, &stmt);
+        *self.current_statement.lock().unwrap() = Some((redacted_sql.clone(), Instant::now()));
+        let rsp = handle(self.clone(), stmt, sql, formats).await;
+        *self.current_statement.lock().unwrap() = None;
+        let rsp = rsp.map_err(|e| {
+            tracing::error!("failed to handle sql:\n{}:\n{}", redacted_sql, e);
             e
         })?;
         Ok(rsp)
     }
 
-    async fn infer_return_type(
+    async fn parse(
         self: Arc<Self>,
         sql: &str,
-    ) -> std::result::Result<Vec<PgFieldDescriptor>, BoxedError> {
-        // Parse sql.
-        let mut stmts = Parser::parse_sql(sql).map_err(|e| {
+        param_types: Vec<Option<TypeOid>>,
+    ) -> std::result::Result<PreparedStatement, BoxedError> {
+        let param_types = param_types
+            .into_iter()
+            .map(|t| t.map(type_oid_to_data_type))
+            .collect();
+        let statement = self.prepare_statement(sql, param_types).map_err(|e| {
             tracing::error!("failed to parse sql:\n{}:\n{}", sql, e);
             e
         })?;
-        if stmts.is_empty() {
-            return Ok(vec![]);
-        }
-        if stmts.len() > 1 {
-            return Err(Box::new(Error::new(
-                ErrorKind::InvalidInput,
-                "cannot insert multiple commands into statement",
-            )));
-        }
-        let stmt = stmts.swap_remove(0);
-        // This part refers from src/frontend/handler/ so the Vec<PgFieldDescripyor> is same as
-        // result of run_statement().
-        let rsp = match stmt {
-            Statement::Query(_) => infer(self, stmt, sql).map_err(|e| {
-                tracing::error!("failed to handle sql:\n{}:\n{}", sql, e);
-                e
-            })?,
-            Statement::ShowObjects(show_object) => match show_object {
-                ShowObject::Columns { table: _ } => {
-                    vec![
-                        PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
-                        PgFieldDescriptor::new("Type".to_owned(), TypeOid::Varchar),
-                    ]
-                }
-                _ => {
-                    vec![PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar)]
-                }
-            },
-            Statement::ShowVariable { variable } => {
-                let name = &variable[0].value.to_lowercase();
-                if name.eq_ignore_ascii_case("ALL") {
-                    vec![
-                        PgFieldDescriptor::new("Name".to_string(), TypeOid::Varchar),
-                        PgFieldDescriptor::new("Setting".to_string(), TypeOid::Varchar),
-                        PgFieldDescriptor::new("Description".to_string(), TypeOid::Varchar),
-                    ]
-                } else {
-                    vec![PgFieldDescriptor::new(
-                        name.to_ascii_lowercase(),
-                        TypeOid::Varchar,
-                    )]
-                }
-            }
-            Statement::Describe { name: _ } => {
-                vec![
-                    PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
-                    PgFieldDescriptor::new("Type".to_owned(), TypeOid::Varchar),
-                ]
-            }
-            _ => {
-                panic!("infer_return_type only support query statement");
-            }
+        Ok(PreparedStatement::new(
+            sql.to_string(),
+            statement
+                .param_types
+                .iter()
+                .cloned()
+                .map(data_type_to_type_oid)
+                .collect(),
+            statement.row_description.clone(),
+        ))
+    }
+
+    async fn run_prepared_statement(
+        self: Arc<Self>,
+        prepared_statement: &PreparedStatement,
+        params: Vec<Option<String>>,
+        result_formats: Vec<Format>,
+    ) -> std::result::Result<PgResponse<PgResponseStream>, BoxedError> {
+        let sql = prepared_statement.sql();
+        let param_types = prepared_statement
+            .param_types()
+            .iter()
+            .map(|t| Some(type_oid_to_data_type(*t)))
+            .collect();
+        // The statement is cached by `parse` under its inferred parameter types as well.
+        let statement = self.prepare_statement(sql, param_types)?;
+        let Some(stmt) = statement.stmt.clone() else {
+            return Ok(PgResponse::empty_result(
+                pgwire::pg_response::StatementType::EMPTY,
+            ));
         };
+
+        let redacted_sql = redact_sql(sql, &stmt);
+        *self.current_statement.lock().unwrap() = Some((redacted_sql.clone(), Instant::now()));
+        let rsp = handle_prepared(
+            self.clone(),
+            stmt,
+            statement.bound.clone(),
+            sql,
+            &statement.param_types,
+            &params,
+            result_formats,
+        )
+        .await;
+        *self.current_statement.lock().unwrap() = None;
+        let rsp = rsp.map_err(|e| {
+            tracing::error!("failed to handle sql:\n{}:\n{}", redacted_sql, e);
+            e
+        })?;
+        Ok(rsp)
+    }
+
+    async fn run_copy_in(
+        self: Arc<Self>,
+        sql: &str,
+        data: CopyInData,
+    ) -> std::result::Result<PgResponse<PgResponseStream>, BoxedError> {
+        // The statement has been parsed and handled once by `run_statement`.
+        let stmt = Parser::parse_sql(sql)?.swap_remove(0);
+        *self.current_statement.lock().unwrap() = Some((sql.to_string(), Instant::now()));
+        let rsp = handle_copy_in(self.clone(), stmt, sql, data).await;
+        *self.current_statement.lock().unwrap() = None;
+        let rsp = rsp.map_err(|e| {
+            tracing::error!("failed to handle sql:\n{}:\n{}", sql, e);
+            e
+        })?;
         Ok(rsp)
     }
 
@@ -845,28 +1103,121 @@ impl Session<PgResponseStream> for SessionImpl {
     fn id(&self) -> SessionId {
         self.id
     }
+
+    async fn wait_killed(&self) {
+        let mut killed = self.killed.subscribe();
+        while !*killed.borrow_and_update() {
+            // The sender is owned by the session itself, so it can not be dropped here.
+            killed.changed().await.unwrap();
+        }
+    }
 }
 
-/// Returns row description of the statement
-fn infer(session: Arc<SessionImpl>, stmt: Statement, sql: &str) -> Result<Vec<PgFieldDescriptor>> {
-    let context = OptimizerContext::new(session, Arc::from(sql), WithOptions::try_from(&stmt)?);
-    let session = context.session_ctx.clone();
+/// Returns the row description of the statement.
+/// Returns the SQL to be logged or shown in the process list, in which the values of secrets are
+/// redacted.
+fn redact_sql(sql: &str, stmt: &Statement) -> String {
+    match stmt {
+        Statement::CreateSecret { .. } => stmt.to_string(),
+        _ => sql.to_string(),
+    }
+}
 
-    let bound = {
-        let mut binder = Binder::new(&session);
-        binder.bind(stmt)?
+/// Returns the row description of a statement, and the bound statement if it's a query or DML,
+/// which is cached to be executed with the values of the parameters.
+fn infer_row_description(
+    binder: &mut Binder,
+    stmt: Statement,
+) -> Result<(Vec<PgFieldDescriptor>, Option<BoundStatement>)> {
+    // This part refers from src/frontend/handler/ so the Vec<PgFieldDescripyor> is same as
+    // result of run_statement().
+    let fields = match stmt {
+        Statement::Query(_) => {
+            let bound = binder.bind(stmt)?;
+            let BoundStatement::Query(query) = &bound else {
+                unreachable!()
+            };
+            let fields = query.schema().fields().iter().map(to_pg_field).collect();
+            return Ok((fields, Some(bound)));
+        }
+        // The parameters of DML statements are inferred. Only the number of affected rows is
+        // returned, unless there is `RETURNING`.
+        Statement::Insert { .. } | Statement::Delete { .. } | Statement::Update { .. } => {
+            let bound = binder.bind(stmt)?;
+            let fields = match bound.returning_schema() {
+                Some(schema) => schema.fields().iter().map(to_pg_field).collect(),
+                None => vec![],
+            };
+            return Ok((fields, Some(bound)));
+        }
+        Statement::ShowObjects(show_object) => match show_object {
+            ShowObject::Jobs => {
+                vec![
+                    PgFieldDescriptor::new("Id".to_owned(), TypeOid::BigInt),
+                    PgFieldDescriptor::new("Statement".to_owned(), TypeOid::Varchar),
+                    PgFieldDescriptor::new("Progress".to_owned(), TypeOid::Varchar),
+                ]
+            }
+            ShowObject::ProcessList => {
+                vec![
+                    PgFieldDescriptor::new("Id".to_owned(), TypeOid::Int),
+                    PgFieldDescriptor::new("User".to_owned(), TypeOid::Varchar),
+                    PgFieldDescriptor::new("Database".to_owned(), TypeOid::Varchar),
+                    PgFieldDescriptor::new("Time".to_owned(), TypeOid::Varchar),
+                    PgFieldDescriptor::new("Info".to_owned(), TypeOid::Varchar),
+                ]
+            }
+            ShowObject::Columns { table: _ } => {
+                vec![
+                    PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
+                    PgFieldDescriptor::new("Type".to_owned(), TypeOid::Varchar),
+                    PgFieldDescriptor::new("Description".to_owned(), TypeOid::Varchar),
+                ]
+            }
+            _ => {
+                vec![PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar)]
+            }
+        },
+        Statement::ShowCreateObject { .. } => {
+            vec![
+                PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
+                PgFieldDescriptor::new("Create Sql".to_owned(), TypeOid::Varchar),
+            ]
+        }
+        Statement::ShowVariable { variable } => {
+            let name = &variable[0].value.to_lowercase();
+            if name.eq_ignore_ascii_case("ALL") {
+                vec![
+                    PgFieldDescriptor::new("Name".to_string(), TypeOid::Varchar),
+                    PgFieldDescriptor::new("Setting".to_string(), TypeOid::Varchar),
+                    PgFieldDescriptor::new("Description".to_string(), TypeOid::Varchar),
+                ]
+            } else {
+                vec![PgFieldDescriptor::new(
+                    name.to_ascii_lowercase(),
+                    TypeOid::Varchar,
+                )]
+            }
+        }
+        Statement::Describe { name: _ } => {
+            vec![
+                PgFieldDescriptor::new("Name".to_owned(), TypeOid::Varchar),
+                PgFieldDescriptor::new("Type".to_owned(), TypeOid::Varchar),
+                PgFieldDescriptor::new("Description".to_owned(), TypeOid::Varchar),
+            ]
+        }
+        // The COPY sub-protocol is only started by the simple query protocol.
+        Statement::Copy { .. } => {
+            return Err(ErrorCode::NotImplemented(
+                "COPY in the extended query protocol".to_string(),
+                None.into(),
+            )
+            .into());
+        }
+        // Other statements return no rows.
+        _ => vec![],
     };
-
-    let root = Planner::new(context.into()).plan(bound)?;
-
-    let pg_descs = root
-        .schema()
-        .fields()
-        .iter()
-        .map(to_pg_field)
-        .collect::<Vec<PgFieldDescriptor>>();
-
-    Ok(pg_descs)
+    Ok((fields, None))
 }
 
 #[cfg(test)]
@@ -202,6 +202,12 @@ fn build_fragment(
     match stream_node.get_node_body()? {
         NodeBody::Source(_) => current_fragment.fragment_type = FragmentType::Source,
 
+        // The `Now` executor is a leaf that receives barriers like a source, and emits one row.
+        NodeBody::Now(_) => {
+            current_fragment.fragment_type = FragmentType::Source;
+            current_fragment.is_singleton = true;
+        }
+
         NodeBody::Materialize(_) => current_fragment.fragment_type = FragmentType::Sink,
 
         // TODO: Force singleton for TopN as a workaround. We should implement two phase TopN.
@@ -76,246 +76,266 @@ fn build_lookup_for_delta_join(
     }
 }
 
-fn build_delta_join_inner(
-    state: &mut BuildFragmentGraphState,
-    current_fragment: &mut StreamFragment,
-    arrange_0_frag: StreamFragment,
-    arrange_1_frag: StreamFragment,
-    node: &StreamNode,
-    is_local_table_id: bool,
-) -> Result<StreamNode> {
-    let delta_join_node = match &node.node_body {
-        Some(NodeBody::DeltaIndexJoin(node)) => node,
-        _ => unreachable!(),
-    };
-    let output_indices = &delta_join_node.output_indices;
+/// An index scanned by a (possibly multi-way) delta join, which serves both as a stream input and
+/// as an arrangement to be looked up by the other inputs.
+struct DeltaJoinArrangement {
+    fragment: StreamFragment,
+    table_id: u32,
+    info: ArrangementInfo,
+    /// The join key of this arrangement. Join keys of all arrangements in a delta join are
+    /// aligned, i.e., the `i`-th column of each join key are equal to each other.
+    join_key: Vec<i32>,
+    /// The offset of the first column of this arrangement in the concatenated columns of all
+    /// arrangements.
+    offset: usize,
+}
 
-    let arrange_0 = arrange_0_frag.node.as_ref().unwrap();
-    let arrange_1 = arrange_1_frag.node.as_ref().unwrap();
-    let exchange_a0l0 = build_exchange_for_delta_join(state, arrange_0);
-    let exchange_a0l1 = build_exchange_for_delta_join(state, arrange_0);
-    let exchange_a1l0 = build_exchange_for_delta_join(state, arrange_1);
-    let exchange_a1l1 = build_exchange_for_delta_join(state, arrange_1);
+impl DeltaJoinArrangement {
+    fn node(&self) -> &StreamNode {
+        self.fragment.node.as_ref().unwrap()
+    }
+}
 
-    let i0_length = arrange_0.fields.len();
-    let i1_length = arrange_1.fields.len();
+// TODO: when distribution key is added to catalog, chain and delta join won't have any
+// exchange in-between. Then we can safely remove this function.
+fn pass_through_exchange(mut node: StreamNode) -> StreamNode {
+    if let Some(NodeBody::Exchange(exchange)) = &node.node_body {
+        if let DispatcherType::NoShuffle = exchange.strategy.as_ref().unwrap().get_type().unwrap() {
+            return node.input.remove(0);
+        }
+        panic!("exchange other than no_shuffle not allowed between delta join and arrange");
+    } else {
+        node
+    }
+}
 
-    let lookup_0_column_reordering = {
-        let tmp: Vec<i32> = (i1_length..i1_length + i0_length)
-            .chain(0..i1_length)
-            .map(|x| x as _)
-            .collect_vec();
-        output_indices
-            .iter()
-            .map(|&x| tmp[x as usize])
-            .collect_vec()
-    };
-    // lookup left table by right stream
-    let lookup_0 = build_lookup_for_delta_join(
-        state,
-        (&exchange_a1l0, &exchange_a0l0),
-        (node.fields.clone(), node.stream_key.clone()),
-        LookupNode {
-            stream_key: delta_join_node.right_key.clone(),
-            arrange_key: delta_join_node.left_key.clone(),
-            use_current_epoch: false,
-            // will be updated later to a global id
-            arrangement_table_id: if is_local_table_id {
-                Some(ArrangementTableId::TableId(delta_join_node.left_table_id))
-            } else {
-                Some(ArrangementTableId::IndexId(delta_join_node.left_table_id))
-            },
-            column_mapping: lookup_0_column_reordering,
-            arrangement_table_info: delta_join_node.left_info.clone(),
-            arrangement_table: Some(
-                infer_internal_table_catalog(
-                    delta_join_node.left_info.as_ref(),
-                    // Use Arrange node's dist key.
-                    try_match_expand!(arrange_0.get_node_body().unwrap(), NodeBody::Arrange)?
-                        .distribution_key
-                        .clone()
-                        .iter()
-                        .map(|x| *x as usize)
-                        .collect(),
-                )
-                .to_internal_table_prost(),
-            ),
-        },
-    );
-    let lookup_1_column_reordering = {
-        let tmp: Vec<i32> = (0..i0_length + i1_length)
-            .chain(0..i1_length)
-            .map(|x| x as _)
-            .collect_vec();
-        output_indices
-            .iter()
-            .map(|&x| tmp[x as usize])
-            .collect_vec()
-    };
-    // lookup right table by left stream
-    let lookup_1 = build_lookup_for_delta_join(
-        state,
-        (&exchange_a0l1, &exchange_a1l1),
-        (node.fields.clone(), node.stream_key.clone()),
-        LookupNode {
-            stream_key: delta_join_node.left_key.clone(),
-            arrange_key: delta_join_node.right_key.clone(),
-            use_current_epoch: true,
-            // will be updated later to a global id
-            arrangement_table_id: if is_local_table_id {
-                Some(ArrangementTableId::TableId(delta_join_node.right_table_id))
-            } else {
-                Some(ArrangementTableId::IndexId(delta_join_node.right_table_id))
-            },
-            column_mapping: lookup_1_column_reordering,
-            arrangement_table_info: delta_join_node.right_info.clone(),
-            arrangement_table: Some(
-                infer_internal_table_catalog(
-                    delta_join_node.right_info.as_ref(),
-                    // Use Arrange node's dist key.
-                    try_match_expand!(arrange_1.get_node_body().unwrap(), NodeBody::Arrange)?
-                        .distribution_key
-                        .clone()
-                        .iter()
-                        .map(|x| *x as usize)
-                        .collect(),
-                )
-                .to_internal_table_prost(),
-            ),
-        },
-    );
+/// Flattens a tree of delta joins into the arrangements at its leaves, and returns the mapping
+/// from the output columns of `node` to the concatenated columns of all arrangements.
+fn flatten_delta_join(
+    state: &mut BuildFragmentGraphState,
+    mut node: StreamNode,
+    arrangements: &mut Vec<DeltaJoinArrangement>,
+) -> Result<Vec<usize>> {
+    let delta_join_node =
+        try_match_expand!(node.node_body.take().unwrap(), NodeBody::DeltaIndexJoin)?;
+    let [left, right]: [_; 2] = std::mem::take(&mut node.input).try_into().unwrap();
 
-    let lookup_0_frag = build_and_add_fragment(state, lookup_0)?;
-    let lookup_1_frag = build_and_add_fragment(state, lookup_1)?;
+    let mut internal_mapping = vec![];
+    for (input, join_key, table_id, info) in [
+        (
+            left,
+            delta_join_node.left_key,
+            delta_join_node.left_table_id,
+            delta_join_node.left_info,
+        ),
+        (
+            right,
+            delta_join_node.right_key,
+            delta_join_node.right_table_id,
+            delta_join_node.right_info,
+        ),
+    ] {
+        let input = pass_through_exchange(input);
+        if let Some(NodeBody::DeltaIndexJoin(_)) = &input.node_body {
+            internal_mapping.extend(flatten_delta_join(state, input, arrangements)?);
+        } else {
+            let offset = arrangements
+                .iter()
+                .map(|arrangement| arrangement.node().fields.len())
+                .sum();
+            let fragment = build_and_add_fragment(state, input)?;
+            let arrangement = DeltaJoinArrangement {
+                fragment,
+                table_id,
+                info: info.unwrap(),
+                join_key,
+                offset,
+            };
+            internal_mapping.extend(offset..offset + arrangement.node().fields.len());
+            arrangements.push(arrangement);
+        }
+    }
 
-    state.fragment_graph.add_edge(
-        arrange_0_frag.fragment_id,
-        lookup_0_frag.fragment_id,
-        StreamFragmentEdge {
-            dispatch_strategy: dispatch_no_shuffle(),
-            same_worker_node: true,
-            link_id: exchange_a0l0.operator_id,
-        },
-    );
+    Ok(delta_join_node
+        .output_indices
+        .iter()
+        .map(|&x| internal_mapping[x as usize])
+        .collect())
+}
 
-    state.fragment_graph.add_edge(
-        arrange_0_frag.fragment_id,
-        lookup_1_frag.fragment_id,
-        StreamFragmentEdge {
-            dispatch_strategy: dispatch_no_shuffle(),
-            // stream input doesn't need to be on the same worker node as lookup
-            same_worker_node: false,
-            link_id: exchange_a0l1.operator_id,
-        },
-    );
+/// Builds the lookup path for the changes of `arrangements[stream_idx]`, which looks up all other
+/// arrangements in order. An arrangement before the stream input is looked up in the previous
+/// epoch, and an arrangement after the stream input is looked up in the current epoch, so that:
+///
+/// ```plain
+/// d(A join B join C) = dA join (B + dB) join (C + dC) + dB join A join (C + dC) + dC join A join B
+/// ```
+///
+/// Returns the last lookup fragment of the path.
+fn build_lookup_path(
+    state: &mut BuildFragmentGraphState,
+    arrangements: &[DeltaJoinArrangement],
+    stream_idx: usize,
+    output_mapping: &[usize],
+    node: &StreamNode,
+) -> Result<StreamFragment> {
+    let stream_arrangement = &arrangements[stream_idx];
+    let mut stream_fragment = stream_arrangement.fragment.clone();
+    let mut stream_node = stream_arrangement.node().clone();
+    // Positions of each arrangement in the output of the current lookup.
+    let mut positions = vec![None; arrangements.len()];
+    positions[stream_idx] = Some(0);
 
-    state.fragment_graph.add_edge(
-        arrange_1_frag.fragment_id,
-        lookup_0_frag.fragment_id,
-        StreamFragmentEdge {
-            dispatch_strategy: dispatch_no_shuffle(),
-            // stream input doesn't need to be on the same worker node as lookup
-            same_worker_node: false,
-            link_id: exchange_a1l0.operator_id,
-        },
-    );
+    let lookup_order = (0..arrangements.len())
+        .filter(|&idx| idx != stream_idx)
+        .collect_vec();
+    for (step, &arrange_idx) in lookup_order.iter().enumerate() {
+        let arrangement = &arrangements[arrange_idx];
+        let arrange_node = arrangement.node();
+        let exchange_arrangement = build_exchange_for_delta_join(state, arrange_node);
+        let exchange_stream = build_exchange_for_delta_join(state, &stream_node);
+        positions[arrange_idx] = Some(stream_node.fields.len());
 
-    state.fragment_graph.add_edge(
-        arrange_1_frag.fragment_id,
-        lookup_1_frag.fragment_id,
-        StreamFragmentEdge {
-            dispatch_strategy: dispatch_no_shuffle(),
-            same_worker_node: true,
-            link_id: exchange_a1l1.operator_id,
-        },
-    );
+        let ((fields, stream_key), column_mapping) = if step + 1 == lookup_order.len() {
+            // The last lookup outputs the columns of the delta join.
+            let column_mapping = output_mapping
+                .iter()
+                .map(|&x| {
+                    let idx = arrangements
+                        .iter()
+                        .rposition(|arrangement| arrangement.offset <= x)
+                        .unwrap();
+                    (positions[idx].unwrap() + x - arrangements[idx].offset) as i32
+                })
+                .collect_vec();
+            (
+                (node.fields.clone(), node.stream_key.clone()),
+                column_mapping,
+            )
+        } else {
+            let stream_len = stream_node.fields.len();
+            let fields = stream_node
+                .fields
+                .iter()
+                .chain(arrange_node.fields.iter())
+                .cloned()
+                .collect_vec();
+            let stream_key = stream_node
+                .stream_key
+                .iter()
+                .copied()
+                .chain(
+                    arrange_node
+                        .stream_key
+                        .iter()
+                        .map(|&x| x + stream_len as u32),
+                )
+                .collect_vec();
+            let column_mapping = (0..fields.len() as i32).collect_vec();
+            ((fields, stream_key), column_mapping)
+        };
 
-    let exchange_l0m = build_exchange_for_delta_join(state, node);
-    let exchange_l1m = build_exchange_for_delta_join(state, node);
+        let lookup = build_lookup_for_delta_join(
+            state,
+            (&exchange_arrangement, &exchange_stream),
+            (fields, stream_key),
+            LookupNode {
+                stream_key: stream_arrangement.join_key.clone(),
+                arrange_key: arrangement.join_key.clone(),
+                use_current_epoch: arrange_idx > stream_idx,
+                // will be updated later to a global id
+                arrangement_table_id: Some(ArrangementTableId::IndexId(arrangement.table_id)),
+                column_mapping,
+                arrangement_table_info: Some(arrangement.info.clone()),
+                arrangement_table: Some(
+                    infer_internal_table_catalog(
+                        &arrangement.info,
+                        // The index is distributed by the join key.
+                        arrangement.join_key.iter().map(|x| *x as usize).collect(),
+                    )
+                    .to_internal_table_prost(),
+                ),
+            },
+        );
+        let lookup_frag = build_and_add_fragment(state, lookup)?;
 
-    let union = StreamNode {
-        operator_id: state.gen_operator_id() as u64,
-        identity: "Union".into(),
-        fields: node.fields.clone(),
-        stream_key: node.stream_key.clone(),
-        node_body: Some(NodeBody::LookupUnion(LookupUnionNode { order: vec![1, 0] })),
-        input: vec![exchange_l0m.clone(), exchange_l1m.clone()],
-        append_only: node.append_only,
-    };
+        state.fragment_graph.add_edge(
+            arrangement.fragment.fragment_id,
+            lookup_frag.fragment_id,
+            StreamFragmentEdge {
+                dispatch_strategy: dispatch_no_shuffle(),
+                same_worker_node: true,
+                link_id: exchange_arrangement.operator_id,
+            },
+        );
 
-    state.fragment_graph.add_edge(
-        lookup_0_frag.fragment_id,
-        current_fragment.fragment_id,
-        StreamFragmentEdge {
-            dispatch_strategy: dispatch_no_shuffle(),
-            same_worker_node: false,
-            link_id: exchange_l0m.operator_id,
-        },
-    );
+        state.fragment_graph.add_edge(
+            stream_fragment.fragment_id,
+            lookup_frag.fragment_id,
+            StreamFragmentEdge {
+                dispatch_strategy: dispatch_no_shuffle(),
+                // stream input doesn't need to be on the same worker node as lookup
+                same_worker_node: false,
+                link_id: exchange_stream.operator_id,
+            },
+        );
 
-    state.fragment_graph.add_edge(
-        lookup_1_frag.fragment_id,
-        current_fragment.fragment_id,
-        StreamFragmentEdge {
-            dispatch_strategy: dispatch_no_shuffle(),
-            same_worker_node: false,
-            link_id: exchange_l1m.operator_id,
-        },
-    );
+        stream_node = lookup_frag.node.as_deref().unwrap().clone();
+        stream_fragment = lookup_frag;
+    }
 
-    Ok(union)
+    Ok(stream_fragment)
 }
 
+/// Rewrites a tree of inner delta joins into a lookup path for each of the arrangements, and unions
+/// the outputs of all paths. All delta joins in the tree must be on the same join key, so that all
+/// exchanges between the lookups are one-to-one.
 pub(crate) fn build_delta_join_without_arrange(
     state: &mut BuildFragmentGraphState,
     current_fragment: &mut StreamFragment,
-    mut node: StreamNode,
+    node: StreamNode,
 ) -> Result<StreamNode> {
-    match &node.node_body {
-        Some(NodeBody::DeltaIndexJoin(node)) => node,
-        _ => unreachable!(),
-    };
+    let mut arrangements = vec![];
+    let output_mapping = flatten_delta_join(state, node.clone(), &mut arrangements)?;
 
-    let [arrange_0, arrange_1]: [_; 2] = std::mem::take(&mut node.input).try_into().unwrap();
-
-    // TODO: when distribution key is added to catalog, chain and delta join won't have any
-    // exchange in-between. Then we can safely remove this function.
-    fn pass_through_exchange(mut node: StreamNode) -> StreamNode {
-        if let Some(NodeBody::Exchange(exchange)) = node.node_body {
-            if let DispatcherType::NoShuffle =
-                exchange.strategy.as_ref().unwrap().get_type().unwrap()
-            {
-                return node.input.remove(0);
-            }
-            panic!("exchange other than no_shuffle not allowed between delta join and arrange");
-        } else {
-            unimplemented!()
-        }
+    let mut union_inputs = vec![];
+    for stream_idx in 0..arrangements.len() {
+        let lookup_frag =
+            build_lookup_path(state, &arrangements, stream_idx, &output_mapping, &node)?;
+        let exchange = build_exchange_for_delta_join(state, &node);
+        state.fragment_graph.add_edge(
+            lookup_frag.fragment_id,
+            current_fragment.fragment_id,
+            StreamFragmentEdge {
+                dispatch_strategy: dispatch_no_shuffle(),
+                same_worker_node: false,
+                link_id: exchange.operator_id,
+            },
+        );
+        union_inputs.push(exchange);
     }
 
-    let arrange_0 = pass_through_exchange(arrange_0);
-    let arrange_1 = pass_through_exchange(arrange_1);
-
-    let arrange_0_frag = build_and_add_fragment(state, arrange_0)?;
-    let arrange_1_frag = build_and_add_fragment(state, arrange_1)?;
-
-    let union = build_delta_join_inner(
-        state,
-        current_fragment,
-        arrange_0_frag,
-        arrange_1_frag,
-        &node,
-        false,
-    )?;
+    // The paths looking up the previous epochs must be piped first, so that the changes are
+    // applied in order, i.e., from the last path to the first path.
+    let union = StreamNode {
+        operator_id: state.gen_operator_id() as u64,
+        identity: "Union".into(),
+        fields: node.fields.clone(),
+        stream_key: node.stream_key.clone(),
+        node_body: Some(NodeBody::LookupUnion(LookupUnionNode {
+            order: (0..union_inputs.len() as u32).rev().collect(),
+        })),
+        input: union_inputs,
+        append_only: node.append_only,
+    };
 
     Ok(union)
 }
 
 fn infer_internal_table_catalog(
-    arrangement_info: Option<&ArrangementInfo>,
+    arrangement_info: &ArrangementInfo,
     distribution_key: Vec<usize>,
 ) -> TableCatalog {
-    let arrangement_info = arrangement_info.unwrap();
     // FIXME(st1page)
     let mut internal_table_catalog_builder = TableCatalogBuilder::new(WithOptions::default());
     for column_desc in &arrangement_info.column_descs {
@@ -23,15 +23,19 @@ use pgwire::pg_response::StatementType;
 use pgwire::pg_server::{BoxedError, Session, SessionId, SessionManager, UserAuthenticator};
 use pgwire::types::Row;
 use risingwave_common::catalog::{
-    IndexId, TableId, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, DEFAULT_SUPER_USER,
-    DEFAULT_SUPER_USER_ID, NON_RESERVED_USER_ID, PG_CATALOG_SCHEMA_NAME,
+    ColumnId, IndexId, TableId, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, DEFAULT_SUPER_USER,
+    DEFAULT_SUPER_USER_ID, INFORMATION_SCHEMA_SCHEMA_NAME, NON_RESERVED_USER_ID,
+    PG_CATALOG_SCHEMA_NAME, RW_CATALOG_SCHEMA_NAME,
 };
 use risingwave_common::error::Result;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable, View as ProstView,
+    Database as ProstDatabase, Function as ProstFunction, Index as ProstIndex,
+    Schema as ProstSchema, Secret as ProstSecret, Sink as ProstSink, Source as ProstSource,
+    Table as ProstTable, TableStatistics as ProstTableStatistics, View as ProstView,
 };
+use risingwave_pb::ddl_service::reset_source_offsets_request::Target as ResetSourceOffsetsTarget;
+use risingwave_pb::ddl_service::{DdlProgress, ReplannedMview};
 use risingwave_pb::hummock::HummockSnapshot;
 use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
 use risingwave_pb::stream_plan::StreamFragmentGraph;
@@ -42,7 +46,7 @@ use tempfile::{Builder, NamedTempFile};
 
 use crate::catalog::catalog_service::CatalogWriter;
 use crate::catalog::root_catalog::Catalog;
-use crate::catalog::{DatabaseId, SchemaId};
+use crate::catalog::{DatabaseId, FunctionId, SchemaId, SecretId};
 use crate::handler::RwPgResponse;
 use crate::meta_client::FrontendMetaClient;
 use crate::session::{AuthContext, FrontendEnv, SessionImpl};
@@ -193,6 +197,10 @@ impl CatalogWriter for MockCatalogWriter {
             .await?;
         self.create_schema(database_id, PG_CATALOG_SCHEMA_NAME, owner)
             .await?;
+        self.create_schema(database_id, RW_CATALOG_SCHEMA_NAME, owner)
+            .await?;
+        self.create_schema(database_id, INFORMATION_SCHEMA_SCHEMA_NAME, owner)
+            .await?;
         Ok(())
     }
 
@@ -249,6 +257,21 @@ impl CatalogWriter for MockCatalogWriter {
         self.create_sink_inner(sink, graph)
     }
 
+    async fn create_function(&self, mut function: ProstFunction) -> Result<()> {
+        function.id = self.gen_id();
+        self.catalog.write().create_function(&function);
+        self.add_table_or_source_id(function.id, function.schema_id, function.database_id);
+        Ok(())
+    }
+
+    async fn create_secret(&self, mut secret: ProstSecret) -> Result<()> {
+        secret.id = self.gen_id();
+        secret.value.clear();
+        self.catalog.write().create_secret(&secret);
+        self.add_table_or_source_id(secret.id, secret.schema_id, secret.database_id);
+        Ok(())
+    }
+
     async fn create_index(
         &self,
         mut index: ProstIndex,
@@ -269,6 +292,82 @@ impl CatalogWriter for MockCatalogWriter {
         Ok(())
     }
 
+    async fn alter_table(
+        &self,
+        source: ProstSource,
+        table: ProstTable,
+        select_star_mview_ids: Vec<TableId>,
+        _mview_plans: Vec<ReplannedMview>,
+    ) -> Result<Vec<TableId>> {
+        self.catalog.write().update_source(&source);
+        self.catalog.write().update_table(&table);
+        // There is no stream plan to update in place, so the views are always reported as stale.
+        Ok(select_star_mview_ids)
+    }
+
+    async fn alter_source(
+        &self,
+        source: ProstSource,
+        select_star_mview_ids: Vec<TableId>,
+        _mview_plans: Vec<ReplannedMview>,
+    ) -> Result<Vec<TableId>> {
+        self.catalog.write().update_source(&source);
+        Ok(select_star_mview_ids)
+    }
+
+    async fn update_table_statistics(
+        &self,
+        table_id: TableId,
+        statistics: ProstTableStatistics,
+    ) -> Result<()> {
+        let schema_id = *self
+            .table_id_to_schema_id
+            .read()
+            .get(&table_id.table_id)
+            .unwrap();
+        let database_id = self.get_database_id_by_schema(schema_id);
+        let mut table = self
+            .catalog
+            .read()
+            .get_table_by_id(&table_id)?
+            .to_prost(schema_id, database_id);
+        table.statistics = Some(statistics);
+        self.catalog.write().update_table(&table);
+        Ok(())
+    }
+
+    async fn comment_on(
+        &self,
+        table_id: TableId,
+        column_id: Option<ColumnId>,
+        comment: String,
+    ) -> Result<()> {
+        let schema_id = *self
+            .table_id_to_schema_id
+            .read()
+            .get(&table_id.table_id)
+            .unwrap();
+        let database_id = self.get_database_id_by_schema(schema_id);
+        let mut table = self
+            .catalog
+            .read()
+            .get_table_by_id(&table_id)?
+            .to_prost(schema_id, database_id);
+        match column_id {
+            Some(column_id) if comment.is_empty() => {
+                table.column_descriptions.remove(&column_id.get_id());
+            }
+            Some(column_id) => {
+                table
+                    .column_descriptions
+                    .insert(column_id.get_id(), comment);
+            }
+            None => table.description = comment,
+        }
+        self.catalog.write().update_table(&table);
+        Ok(())
+    }
+
     async fn drop_materialized_source(&self, source_id: u32, table_id: TableId) -> Result<()> {
         let (database_id, schema_id) = self.drop_table_or_source_id(source_id);
         self.drop_table_or_source_id(table_id.table_id);
@@ -323,6 +422,22 @@ impl CatalogWriter for MockCatalogWriter {
         Ok(())
     }
 
+    async fn drop_function(&self, function_id: FunctionId) -> Result<()> {
+        let (database_id, schema_id) = self.drop_table_or_source_id(function_id);
+        self.catalog
+            .write()
+            .drop_function(database_id, schema_id, function_id);
+        Ok(())
+    }
+
+    async fn drop_secret(&self, secret_id: SecretId) -> Result<()> {
+        let (database_id, schema_id) = self.drop_table_or_source_id(secret_id);
+        self.catalog
+            .write()
+            .drop_secret(database_id, schema_id, secret_id);
+        Ok(())
+    }
+
     async fn drop_index(&self, index_id: IndexId) -> Result<()> {
         let &schema_id = self
             .table_id_to_schema_id
@@ -381,12 +496,26 @@ impl MockCatalogWriter {
             database_id: 0,
             owner: DEFAULT_SUPER_USER_ID,
         });
+        catalog.write().create_schema(&ProstSchema {
+            id: 3,
+            name: RW_CATALOG_SCHEMA_NAME.to_string(),
+            database_id: 0,
+            owner: DEFAULT_SUPER_USER_ID,
+        });
+        catalog.write().create_schema(&ProstSchema {
+            id: 4,
+            name: INFORMATION_SCHEMA_SCHEMA_NAME.to_string(),
+            database_id: 0,
+            owner: DEFAULT_SUPER_USER_ID,
+        });
         let mut map: HashMap<u32, DatabaseId> = HashMap::new();
         map.insert(1_u32, 0_u32);
         map.insert(2_u32, 0_u32);
+        map.insert(3_u32, 0_u32);
+        map.insert(4_u32, 0_u32);
         Self {
             catalog,
-            id: AtomicU32::new(2),
+            id: AtomicU32::new(4),
             table_id_to_schema_id: Default::default(),
             schema_id_to_database_id: RwLock::new(map),
         }
@@ -651,6 +780,46 @@ impl FrontendMetaClient for MockFrontendMetaClient {
     async fn unpin_snapshot_before(&self, _epoch: u64) -> RpcResult<()> {
         Ok(())
     }
+
+    async fn set_system_param(&self, _param: String, _value: String) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn reset_source_offsets(
+        &self,
+        _source_id: u32,
+        _target: ResetSourceOffsetsTarget,
+        _timestamp_millis: i64,
+    ) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn alter_backfill_rate_limit(
+        &self,
+        _database_id: u32,
+        _schema_id: u32,
+        _name: String,
+        _rows_per_second: u64,
+    ) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn get_ddl_progress(&self) -> RpcResult<Vec<DdlProgress>> {
+        // Pretend a streaming job is being created, for `SHOW JOBS` to have something to show.
+        Ok(vec![DdlProgress {
+            id: 1,
+            statement: "CREATE MATERIALIZED VIEW mv AS SELECT * FROM t".to_string(),
+            progress: "50.0%".to_string(),
+        }])
+    }
+
+    async fn cancel_creating_jobs(&self, _job_ids: Vec<u32>) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn get_table_sizes(&self) -> RpcResult<HashMap<u32, u64>> {
+        Ok(HashMap::new())
+    }
 }
 
 #[cfg(test)]
@@ -12,12 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use pgwire::scram::ScramSecret;
 use risingwave_pb::user::auth_info::EncryptionType;
 use risingwave_pb::user::AuthInfo;
 use sha2::{Digest, Sha256};
 
-// SHA-256 is not supported in PostgreSQL protocol. We need to implement SCRAM-SHA-256 instead
-// if necessary.
+// SHA-256 is not supported in PostgreSQL protocol, which is kept for the passwords stored before.
+// SCRAM-SHA-256 is used instead.
 const SHA256_ENCRYPTED_PREFIX: &str = "SHA-256:";
 const MD5_ENCRYPTED_PREFIX: &str = "md5";
 
@@ -26,10 +27,14 @@ const VALID_MD5_ENCRYPTED_LEN: usize = MD5_ENCRYPTED_PREFIX.len() + 32;
 
 /// Try to extract the encryption password from given password. The password is always stored
 /// encrypted in the system catalogs. The ENCRYPTED keyword has no effect, but is accepted for
-/// backwards compatibility. The method of encryption is by default SHA-256-encrypted. If the
-/// presented password string is already in MD5-encrypted or SHA-256-encrypted format, then it is
-/// stored as-is regardless of `password_encryption` (since the system cannot decrypt the specified
-/// encrypted password string, to encrypt it in a different format).
+/// backwards compatibility. The method of encryption is by default SCRAM-SHA-256. If the
+/// presented password string is already in SCRAM-SHA-256, MD5-encrypted or SHA-256-encrypted
+/// format, then it is stored as-is regardless of `password_encryption` (since the system cannot
+/// decrypt the specified encrypted password string, to encrypt it in a different format).
+///
+/// For a SCRAM-SHA-256 encrypted password, rolpassword column will be the verifier
+/// `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>`, the same as PostgreSQL. The
+/// password can not be recovered from it, and is checked by the SCRAM exchange on login.
 ///
 /// For an MD5 encrypted password, rolpassword column will begin with the string md5 followed by a
 /// 32-character hexadecimal MD5 hash. The MD5 hash will be of the user's password concatenated to
@@ -49,7 +54,12 @@ pub fn encrypted_password(name: &str, password: &str) -> Option<AuthInfo> {
         return None;
     }
 
-    if valid_sha256_password(password) {
+    if ScramSecret::parse(password).is_some() {
+        Some(AuthInfo {
+            encryption_type: EncryptionType::ScramSha256 as i32,
+            encrypted_value: password.into(),
+        })
+    } else if valid_sha256_password(password) {
         Some(AuthInfo {
             encryption_type: EncryptionType::Sha256 as i32,
             encrypted_value: password.trim_start_matches(SHA256_ENCRYPTED_PREFIX).into(),
@@ -60,16 +70,16 @@ pub fn encrypted_password(name: &str, password: &str) -> Option<AuthInfo> {
             encrypted_value: password.trim_start_matches(MD5_ENCRYPTED_PREFIX).into(),
         })
     } else {
-        Some(encrypt_default(name, password))
+        Some(encrypt_default(password))
     }
 }
 
-/// Encrypt the password with MD5 as default.
+/// Encrypt the password with SCRAM-SHA-256 as default.
 #[inline(always)]
-fn encrypt_default(name: &str, password: &str) -> AuthInfo {
+fn encrypt_default(password: &str) -> AuthInfo {
     AuthInfo {
-        encryption_type: EncryptionType::Md5 as i32,
-        encrypted_value: md5_hash(name, password),
+        encryption_type: EncryptionType::ScramSha256 as i32,
+        encrypted_value: ScramSecret::new(password).to_string().into_bytes(),
     }
 }
 
@@ -134,17 +144,21 @@ mod tests {
             sha256_hash(user_name, password)
         );
 
+        // A plain password is stored as a SCRAM-SHA-256 verifier with a random salt.
+        let scram = encrypted_password(user_name, password).unwrap();
+        assert_eq!(scram.encryption_type, EncryptionType::ScramSha256 as i32);
+        let secret = String::from_utf8(scram.encrypted_value.clone()).unwrap();
+        assert!(ScramSecret::parse(&secret).is_some());
+        assert_ne!(encrypted_password(user_name, password), Some(scram.clone()));
+
         let input_passwords = vec![
-            "bar",
+            secret.as_str(),
             "",
             "md596948aad3fcae80c08a35c9b5958cd89",
             "SHA-256:88ecde925da3c6f8ec3d140683da9d2a422f26c1ae1d9212da1e5a53416dcc88",
         ];
         let expected_output_passwords = vec![
-            Some(AuthInfo {
-                encryption_type: EncryptionType::Md5 as i32,
-                encrypted_value: md5_hash(user_name, password),
-            }),
+            Some(scram),
             None,
             Some(AuthInfo {
                 encryption_type: EncryptionType::Md5 as i32,
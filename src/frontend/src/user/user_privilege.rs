@@ -31,6 +31,12 @@ static AVAILABLE_ACTION_ON_SOURCE: &[Action] = &[
     Action::Insert { columns: None },
     Action::Delete,
 ];
+static AVAILABLE_ACTION_ON_TABLE: &[Action] = &[
+    Action::Select { columns: None },
+    Action::Update { columns: None },
+    Action::Insert { columns: None },
+    Action::Delete,
+];
 static AVAILABLE_ACTION_ON_MVIEW: &[Action] = &[Action::Select { columns: None }];
 static AVAILABLE_ACTION_ON_VIEW: &[Action] = AVAILABLE_ACTION_ON_MVIEW;
 
@@ -51,6 +57,9 @@ pub fn check_privilege_type(privilege: &Privileges, objects: &GrantObjects) -> R
                 GrantObjects::Mviews(_) | GrantObjects::AllMviewsInSchema { .. } => actions
                     .iter()
                     .all(|action| AVAILABLE_ACTION_ON_MVIEW.contains(action)),
+                GrantObjects::Tables(_) | GrantObjects::AllTablesInSchema { .. } => actions
+                    .iter()
+                    .all(|action| AVAILABLE_ACTION_ON_TABLE.contains(action)),
                 _ => true,
             };
             if !valid {
@@ -75,6 +84,9 @@ pub fn available_privilege_actions(objects: &GrantObjects) -> Result<Vec<Action>
         GrantObjects::Mviews(_) | GrantObjects::AllMviewsInSchema { .. } => {
             Ok(AVAILABLE_ACTION_ON_MVIEW.to_vec())
         }
+        GrantObjects::Tables(_) | GrantObjects::AllTablesInSchema { .. } => {
+            Ok(AVAILABLE_ACTION_ON_TABLE.to_vec())
+        }
         _ => Err(
             ErrorCode::BindError("Invalid privilege type for the given object.".to_string()).into(),
         ),
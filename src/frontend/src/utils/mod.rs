@@ -18,6 +18,8 @@ mod condition;
 pub use condition::*;
 mod connected_components;
 pub(crate) use connected_components::*;
+mod plan_formatter;
+pub use plan_formatter::*;
 mod stream_graph_formatter;
 pub use stream_graph_formatter::*;
 mod with_options;
@@ -0,0 +1,181 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools;
+use risingwave_common::error::{ErrorCode, Result};
+use serde_json::{json, Value};
+
+use crate::optimizer::plan_node::{PlanNodeType, PlanRef};
+use crate::optimizer::property::{DistributionDisplay, OrderDisplay};
+
+/// Explains the plan tree as a JSON document, for `explain (format json) ...`.
+pub fn explain_plan_json(plan: &PlanRef) -> Result<String> {
+    serde_json::to_string_pretty(&plan_to_json(plan))
+        .map_err(|e| ErrorCode::InternalError(format!("failed to explain plan: {}", e)).into())
+}
+
+/// Explains the plan tree as a Graphviz DOT digraph, for `explain (format dot) ...`. The data
+/// flows from the children to their parents, and the edges out of exchanges are dashed.
+pub fn explain_plan_dot(plan: &PlanRef) -> String {
+    let mut output = String::from("digraph plan {\n  node [shape=box];\n");
+    plan_to_dot(plan, &mut output);
+    output.push_str("}\n");
+    output
+}
+
+fn plan_to_json(plan: &PlanRef) -> Value {
+    json!({
+        "id": plan.id().0,
+        "name": format!("{:?}", plan.node_type()),
+        "detail": plan.to_string(),
+        "schema": plan.schema().names(),
+        "distribution": distribution_string(plan),
+        "order": OrderDisplay {
+            order: plan.order(),
+            input_schema: plan.schema(),
+        }
+        .to_string(),
+        "estimated_cardinality": estimated_cardinality(plan),
+        "children": plan.inputs().iter().map(plan_to_json).collect_vec(),
+    })
+}
+
+fn plan_to_dot(plan: &PlanRef, output: &mut String) {
+    let mut label = format!(
+        "{}\\ndistribution: {}",
+        escape_dot(&plan.to_string()),
+        escape_dot(&distribution_string(plan))
+    );
+    if let Some(cardinality) = estimated_cardinality(plan) {
+        label.push_str(&format!("\\nestimated rows: {}", cardinality));
+    }
+    output.push_str(&format!("  n{} [label=\"{}\"];\n", plan.id().0, label));
+
+    let style = if is_exchange(plan) {
+        " [style=dashed]"
+    } else {
+        ""
+    };
+    for input in plan.inputs() {
+        plan_to_dot(&input, output);
+        output.push_str(&format!(
+            "  n{} -> n{}{};\n",
+            input.id().0,
+            plan.id().0,
+            style
+        ));
+    }
+}
+
+fn distribution_string(plan: &PlanRef) -> String {
+    DistributionDisplay {
+        distribution: plan.distribution(),
+        input_schema: plan.schema(),
+    }
+    .to_string()
+}
+
+fn is_exchange(plan: &PlanRef) -> bool {
+    matches!(
+        plan.node_type(),
+        PlanNodeType::BatchExchange | PlanNodeType::StreamExchange
+    )
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Estimates the number of rows produced by the plan node. It's only known for the nodes with a
+/// bounded output, e.g. values, limit and simple aggregation, and the nodes that keep the
+/// cardinality of such inputs.
+fn estimated_cardinality(plan: &PlanRef) -> Option<u64> {
+    let input_cardinality = || estimated_cardinality(&plan.inputs()[0]);
+    let bounded_by_limit = |limit: u64, offset: u64| {
+        Some(input_cardinality().map_or(limit, |input| input.saturating_sub(offset).min(limit)))
+    };
+
+    match plan.node_type() {
+        PlanNodeType::LogicalValues => Some(plan.as_logical_values()?.rows().len() as u64),
+        PlanNodeType::BatchValues => Some(plan.as_batch_values()?.logical().rows().len() as u64),
+        PlanNodeType::BatchLimit => {
+            let limit = plan.as_batch_limit()?;
+            bounded_by_limit(limit.limit(), limit.offset())
+        }
+        PlanNodeType::BatchTopN => {
+            let topn = plan.as_batch_top_n()?;
+            bounded_by_limit(topn.limit(), topn.offset())
+        }
+        PlanNodeType::StreamTopN => {
+            let topn = plan.as_stream_top_n()?;
+            bounded_by_limit(topn.limit(), topn.offset())
+        }
+        PlanNodeType::BatchSimpleAgg | PlanNodeType::StreamGlobalSimpleAgg => Some(1),
+        PlanNodeType::BatchProject
+        | PlanNodeType::BatchExchange
+        | PlanNodeType::BatchSort
+        | PlanNodeType::StreamProject
+        | PlanNodeType::StreamExchange => input_cardinality(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::{DataType, Datum};
+
+    use super::*;
+    use crate::expr::{ExprImpl, Literal};
+    use crate::optimizer::plan_node::{BatchLimit, BatchValues, LogicalLimit, LogicalValues};
+    use crate::session::OptimizerContext;
+
+    fn literal(val: i32) -> ExprImpl {
+        Literal::new(Datum::Some(val.into()), DataType::Int32).into()
+    }
+
+    /// `BatchLimit { limit: 1 }` over `BatchValues` with 2 rows.
+    async fn limit_over_values() -> PlanRef {
+        let ctx = OptimizerContext::mock().await;
+        let schema = Schema::new(vec![Field::with_name(DataType::Int32, "v1")]);
+        let values = LogicalValues::new(vec![vec![literal(0)], vec![literal(1)]], schema, ctx);
+        let values: PlanRef = BatchValues::new(values).into();
+        BatchLimit::new(LogicalLimit::new(values, 1, 0)).into()
+    }
+
+    #[tokio::test]
+    async fn test_explain_plan_json() {
+        let plan = limit_over_values().await;
+        let json: Value = serde_json::from_str(&explain_plan_json(&plan).unwrap()).unwrap();
+
+        assert_eq!(json["name"], "BatchLimit");
+        assert_eq!(json["schema"], json!(["v1"]));
+        assert_eq!(json["estimated_cardinality"], 1);
+        let children = json["children"].as_array().unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0]["name"], "BatchValues");
+        assert_eq!(children[0]["estimated_cardinality"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_explain_plan_dot() {
+        let plan = limit_over_values().await;
+        let input = plan.inputs()[0].clone();
+        let dot = explain_plan_dot(&plan);
+
+        assert!(dot.starts_with("digraph plan {\n"));
+        assert!(dot.contains("estimated rows: 1\"];"));
+        assert!(dot.contains(&format!("  n{} -> n{};\n", input.id().0, plan.id().0)));
+    }
+}
@@ -160,8 +160,15 @@ impl StreamGraphFormatter {
                             agg_call_state::Inner::TableState(TableState { table })
                             | agg_call_state::Inner::MaterializedInputState(
                                 MaterializedInputState { table, .. },
-                            ) => Some(self.add_table(table.as_ref().unwrap())),
+                            ) => Some(table.as_ref().unwrap()),
                         })
+                        .chain(
+                            node.distinct_dedup_tables
+                                .iter()
+                                .sorted_by_key(|(distinct_col, _)| **distinct_col)
+                                .map(|(_, table)| table)
+                        )
+                        .map(|table| self.add_table(table))
                         .join(", ")
                 )),
                 stream_node::NodeBody::HashAgg(node) => Some(format!(
@@ -174,8 +181,15 @@ impl StreamGraphFormatter {
                             agg_call_state::Inner::TableState(TableState { table })
                             | agg_call_state::Inner::MaterializedInputState(
                                 MaterializedInputState { table, .. },
-                            ) => Some(self.add_table(table.as_ref().unwrap())),
+                            ) => Some(table.as_ref().unwrap()),
                         })
+                        .chain(
+                            node.distinct_dedup_tables
+                                .iter()
+                                .sorted_by_key(|(distinct_col, _)| **distinct_col)
+                                .map(|(_, table)| table)
+                        )
+                        .map(|table| self.add_table(table))
                         .join(", ")
                 )),
                 stream_node::NodeBody::AppendOnlyTopN(node) => Some(format!(
@@ -212,10 +226,43 @@ impl StreamGraphFormatter {
                     self.add_table(node.get_left_table().unwrap()),
                     self.add_table(node.get_right_table().unwrap()),
                 )),
+                stream_node::NodeBody::IntervalJoin(node) => Some(format!(
+                    "left table: {}, right table {}",
+                    self.add_table(node.get_left_table().unwrap()),
+                    self.add_table(node.get_right_table().unwrap()),
+                )),
+                stream_node::NodeBody::LookupJoin(node) => Some(format!(
+                    "left table: {}",
+                    self.add_table(node.get_left_table().unwrap()),
+                )),
                 stream_node::NodeBody::GroupTopN(node) => Some(format!(
                     "state table: {}",
                     self.add_table(node.get_table().unwrap())
                 )),
+                stream_node::NodeBody::AppendOnlyGroupTopN(node) => Some(format!(
+                    "state table: {}",
+                    self.add_table(node.get_table().unwrap())
+                )),
+                stream_node::NodeBody::OverWindow(node) => Some(format!(
+                    "state table: {}",
+                    self.add_table(node.get_state_table().unwrap())
+                )),
+                stream_node::NodeBody::SessionWindow(node) => Some(format!(
+                    "state table: {}",
+                    self.add_table(node.get_state_table().unwrap())
+                )),
+                stream_node::NodeBody::Now(node) => Some(format!(
+                    "state table: {}",
+                    self.add_table(node.get_state_table().unwrap())
+                )),
+                stream_node::NodeBody::Sort(node) => Some(format!(
+                    "state table: {}",
+                    self.add_table(node.get_state_table().unwrap())
+                )),
+                stream_node::NodeBody::Sink(node) => node
+                    .log_store_table
+                    .as_ref()
+                    .map(|table| format!("log store table: {}", self.add_table(table))),
                 _ => None,
             };
         if let Some(explain_table_oneline) = explain_table_oneline {
@@ -12,17 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
 use std::num::NonZeroU32;
 
 use itertools::Itertools;
-use risingwave_common::error::{ErrorCode, RwError};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result as RwResult, RwError};
 use risingwave_sqlparser::ast::{
-    CreateSinkStatement, CreateSourceStatement, SqlOption, Statement, Value,
+    CreateSinkStatement, CreateSourceStatement, Ident, ObjectName, SqlOption, Statement, Value,
 };
 
+use crate::binder::Binder;
+use crate::catalog::root_catalog::SchemaPath;
 use crate::catalog::source_catalog::KAFKA_CONNECTOR;
+use crate::catalog::SecretId;
+use crate::handler::privilege::check_super_user;
+use crate::session::SessionImpl;
 
 mod options {
     use risingwave_common::catalog::hummock::PROPERTIES_RETENTION_SECOND_KEY;
@@ -30,12 +36,22 @@ mod options {
     pub const APPEND_ONLY: &str = "appendonly";
     pub const CONNECTOR: &str = "connector";
     pub const RETENTION_SECONDS: &str = PROPERTIES_RETENTION_SECOND_KEY;
+    pub const SINK_DECOUPLE: &str = "sink_decouple";
 }
 
+/// The options whose keys contain any of these words are considered secrets, e.g.
+/// `properties.sasl.password` of Kafka or `aws.credentials.secret_access_key` of Kinesis.
+const SENSITIVE_KEY_WORDS: [&str; 4] = ["password", "secret", "token", "credential"];
+
+/// The value shown in place of the secrets.
+const MASKED_VALUE: &str = "[REDACTED]";
+
 /// Options or properties extracted from the `WITH` clause of DDLs.
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct WithOptions {
     inner: HashMap<String, String>,
+    /// The options given by `SECRET secret_name`, whose values are only resolved by meta.
+    secret_refs: BTreeMap<String, ObjectName>,
 }
 
 impl std::ops::Deref for WithOptions {
@@ -49,7 +65,10 @@ impl std::ops::Deref for WithOptions {
 impl WithOptions {
     /// Create a new [`WithOptions`] from a [`HashMap`].
     pub fn new(inner: HashMap<String, String>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            secret_refs: Default::default(),
+        }
     }
 
     /// Get the reference of the inner map.
@@ -85,6 +104,21 @@ impl WithOptions {
         false
     }
 
+    /// Parse the sink decouple property from the options. A decoupled sink buffers its input in a
+    /// log store, so that a slow external system does not backpressure the upstream.
+    pub fn sink_decouple(&self) -> bool {
+        self.inner
+            .get(options::SINK_DECOUPLE)
+            .map_or(false, |val| val.eq_ignore_ascii_case("true"))
+    }
+
+    /// Whether the options specify the given connector.
+    pub fn is_connector(&self, connector: &str) -> bool {
+        self.inner
+            .get(options::CONNECTOR)
+            .map_or(false, |val| val.eq_ignore_ascii_case(connector))
+    }
+
     /// Get a subset of the options from the given keys.
     pub fn subset(&self, keys: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
         let inner = keys
@@ -96,7 +130,78 @@ impl WithOptions {
             })
             .collect();
 
-        Self { inner }
+        Self::new(inner)
+    }
+
+    /// Get the options referring to secrets, keyed by the option names.
+    pub fn secret_refs(&self) -> &BTreeMap<String, ObjectName> {
+        &self.secret_refs
+    }
+
+    /// Add an option referring to the secret of the given name.
+    pub fn insert_secret_ref(&mut self, key: String, secret_name: ObjectName) {
+        self.secret_refs.insert(key, secret_name);
+    }
+
+    /// Resolve the secrets referred to by the options into their ids. As secrets cannot be
+    /// granted, only their owners or superusers can refer to them.
+    pub fn resolve_secret_refs(
+        &self,
+        session: &SessionImpl,
+    ) -> RwResult<HashMap<String, SecretId>> {
+        if self.secret_refs.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let db_name = session.database();
+        let search_path = session.config().get_search_path();
+        let user_name = &session.auth_context().user_name;
+        let is_super_user = check_super_user(session);
+
+        let reader = session.env().catalog_reader().read_guard();
+        self.secret_refs
+            .iter()
+            .map(|(key, secret_name)| {
+                let (schema_name, secret_name) =
+                    Binder::resolve_schema_qualified_name(db_name, secret_name.clone())?;
+                let schema_path = SchemaPath::new(schema_name.as_deref(), &search_path, user_name);
+                let (secret, _) = reader.get_secret_by_name(db_name, schema_path, &secret_name)?;
+                if secret.owner != session.user_id() && !is_super_user {
+                    return Err(PermissionDenied(format!(
+                        "must be the owner of secret \"{}\" to use it",
+                        secret_name
+                    ))
+                    .into());
+                }
+                Ok((key.clone(), secret.id))
+            })
+            .try_collect()
+    }
+
+    /// Convert the options back to the `WITH` clause sorted by keys, with the values of secrets
+    /// like passwords masked. The options referring to secrets are shown as `SECRET secret_name`.
+    pub fn to_masked_sql_options(&self) -> Vec<SqlOption> {
+        let options = self.inner.iter().map(|(k, v)| {
+            let key = k.to_lowercase();
+            let value = if SENSITIVE_KEY_WORDS.iter().any(|w| key.contains(w)) {
+                MASKED_VALUE.to_string()
+            } else {
+                v.clone()
+            };
+            (k, Value::SingleQuotedString(value))
+        });
+        let secret_refs = self
+            .secret_refs
+            .iter()
+            .map(|(k, name)| (k, Value::SecretRef(name.clone())));
+        options
+            .chain(secret_refs)
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(k, value)| SqlOption {
+                name: ObjectName(k.split('.').map(Ident::from_real_value).collect()),
+                value,
+            })
+            .collect()
     }
 
     /// Get the subset of the options for internal table catalogs.
@@ -111,21 +216,30 @@ impl TryFrom<&[SqlOption]> for WithOptions {
     type Error = RwError;
 
     fn try_from(options: &[SqlOption]) -> Result<Self, Self::Error> {
-        let inner = options
-            .iter()
-            .cloned()
-            .map(|x| match x.value {
-                Value::SingleQuotedString(s) => Ok((x.name.real_value(), s)),
-                Value::Number(n) => Ok((x.name.real_value(), n)),
-                Value::Boolean(b) => Ok((x.name.real_value(), b.to_string())),
-                _ => Err(ErrorCode::InvalidParameterValue(
-                    "`with options` or `with properties` only support single quoted string value"
-                        .to_owned(),
-                )),
-            })
-            .try_collect()?;
+        let mut inner = HashMap::new();
+        let mut secret_refs = BTreeMap::new();
+        for option in options.iter().cloned() {
+            let name = option.name.real_value();
+            let value =
+                match option.value {
+                    Value::SingleQuotedString(s) => s,
+                    Value::Number(n) => n,
+                    Value::Boolean(b) => b.to_string(),
+                    Value::SecretRef(secret_name) => {
+                        secret_refs.insert(name, secret_name);
+                        continue;
+                    }
+                    _ => return Err(ErrorCode::InvalidParameterValue(
+                        "`with options` or `with properties` only support single quoted string \
+                         value"
+                            .to_owned(),
+                    )
+                    .into()),
+                };
+            inner.insert(name, value);
+        }
 
-        Ok(Self { inner })
+        Ok(Self { inner, secret_refs })
     }
 }
 
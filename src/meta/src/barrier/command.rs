@@ -111,6 +111,11 @@ pub enum Command {
     /// `SourceSplitAssignment` generates Plain(Mutation::Splits) for pushing initialized splits or
     /// newly added splits.
     SourceSplitAssignment(SplitAssignment),
+
+    /// `SourceSplitReset` generates Plain(Mutation::ResetSplits) for overwriting the states of
+    /// the splits with the given ones, used for rewinding or fast-forwarding the offsets. The
+    /// assignment of the splits to the actors is unchanged.
+    SourceSplitReset(SplitAssignment),
 }
 
 impl Command {
@@ -147,7 +152,9 @@ impl Command {
                     .collect();
                 CommandChanges::Actor { to_add, to_remove }
             }
-            Command::SourceSplitAssignment(_) => CommandChanges::None,
+            Command::SourceSplitAssignment(_) | Command::SourceSplitReset(_) => {
+                CommandChanges::None
+            }
         }
     }
 
@@ -238,6 +245,18 @@ where
                 }))
             }
 
+            Command::SourceSplitReset(reset) => {
+                let mut splits = HashMap::new();
+
+                for actor_splits in reset.values() {
+                    splits.extend(actor_splits.clone());
+                }
+
+                Some(Mutation::ResetSplits(SourceChangeSplitMutation {
+                    actor_splits: build_actor_connector_splits(&splits),
+                }))
+            }
+
             Command::DropMaterializedViews(table_ids) => {
                 let actors = self.fragment_manager.get_table_actor_ids(table_ids).await?;
                 Some(Mutation::Stop(StopMutation { actors }))
@@ -445,6 +464,10 @@ where
                     .await;
             }
 
+            // The new states of the splits are persisted by the source actors on this barrier,
+            // and the assignment of the splits is unchanged.
+            Command::SourceSplitReset(_) => {}
+
             Command::DropMaterializedViews(table_ids) => {
                 // Tell compute nodes to drop actors.
                 let node_actors = self.fragment_manager.table_node_actors(table_ids).await?;
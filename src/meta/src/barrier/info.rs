@@ -17,7 +17,7 @@ use std::collections::HashMap;
 use risingwave_pb::common::WorkerNode;
 
 use crate::manager::{ActorInfos, WorkerId};
-use crate::model::ActorId;
+use crate::model::{ActorId, FragmentId};
 
 /// [`BarrierActorInfo`] resolves the actor info read from meta store for
 /// [`crate::barrier::GlobalBarrierManager`].
@@ -30,6 +30,9 @@ pub struct BarrierActorInfo {
 
     /// node_id => source actors
     pub actor_map_to_send: HashMap<WorkerId, Vec<ActorId>>,
+
+    /// actor_id => (fragment_id, upstream actors)
+    pub actor_topology: HashMap<ActorId, (FragmentId, Vec<ActorId>)>,
 }
 
 impl BarrierActorInfo {
@@ -48,6 +51,7 @@ impl BarrierActorInfo {
             node_map,
             actor_map: actor_infos.actor_maps,
             actor_map_to_send: actor_infos.source_actor_maps,
+            actor_topology: actor_infos.actor_topology,
         }
     }
 
@@ -0,0 +1,205 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use itertools::Itertools;
+use risingwave_pb::stream_service::BarrierCompleteResponse;
+
+use super::info::BarrierActorInfo;
+use crate::manager::WorkerId;
+use crate::model::{ActorId, FragmentId};
+
+/// Only report slow actors if some actor takes longer than this to collect a barrier.
+const SLOW_ACTOR_REPORT_THRESHOLD: Duration = Duration::from_secs(10);
+
+/// The number of slowest actors to report.
+const SLOW_ACTOR_REPORT_NUM: usize = 5;
+
+/// The breakdown of the time for an actor to collect a barrier.
+#[derive(Debug)]
+pub struct ActorBarrierLatency {
+    pub actor_id: ActorId,
+    pub fragment_id: FragmentId,
+    pub worker_id: WorkerId,
+    /// Time before the barrier is collected from all upstream actors, i.e. the time the barrier
+    /// is queued waiting for its upstreams.
+    pub queued: Duration,
+    /// Time for the actor itself to process the barrier after all its upstreams.
+    pub processing: Duration,
+}
+
+/// Break down the collect latency reported by the compute nodes into the queued and processing
+/// time of each actor, according to the actor topology.
+///
+/// Note that the latencies are measured from the barrier first arriving at each compute node, so
+/// the breakdown across nodes is approximate.
+pub fn break_down_barrier_latency(
+    info: &BarrierActorInfo,
+    resps: &[BarrierCompleteResponse],
+) -> Vec<ActorBarrierLatency> {
+    let collect_latency: HashMap<ActorId, (WorkerId, Duration)> = resps
+        .iter()
+        .flat_map(|resp| {
+            resp.actor_barrier_latency.iter().map(|l| {
+                (
+                    l.actor_id,
+                    (resp.worker_id, Duration::from_micros(l.collect_latency_us)),
+                )
+            })
+        })
+        .collect();
+
+    collect_latency
+        .iter()
+        .filter_map(|(&actor_id, &(worker_id, latency))| {
+            let (fragment_id, upstreams) = info.actor_topology.get(&actor_id)?;
+            let queued = upstreams
+                .iter()
+                .filter_map(|upstream| collect_latency.get(upstream))
+                .map(|(_, latency)| *latency)
+                .max()
+                .unwrap_or_default()
+                .min(latency);
+            Some(ActorBarrierLatency {
+                actor_id,
+                fragment_id: *fragment_id,
+                worker_id,
+                queued,
+                processing: latency - queued,
+            })
+        })
+        .collect()
+}
+
+/// Returns the slowest actors by processing time if the barrier is slow to be collected.
+pub fn slow_actors(latencies: &[ActorBarrierLatency]) -> Option<Vec<&ActorBarrierLatency>> {
+    let max_latency = latencies.iter().map(|l| l.queued + l.processing).max()?;
+    if max_latency < SLOW_ACTOR_REPORT_THRESHOLD {
+        return None;
+    }
+    Some(
+        latencies
+            .iter()
+            .sorted_by_key(|l| std::cmp::Reverse(l.processing))
+            .take(SLOW_ACTOR_REPORT_NUM)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::stream_service::barrier_complete_response::ActorBarrierLatency as ProstActorBarrierLatency;
+
+    use super::*;
+
+    fn resp(worker_id: WorkerId, latencies: &[(ActorId, u64)]) -> BarrierCompleteResponse {
+        BarrierCompleteResponse {
+            worker_id,
+            actor_barrier_latency: latencies
+                .iter()
+                .map(|&(actor_id, secs)| ProstActorBarrierLatency {
+                    actor_id,
+                    collect_latency_us: secs * 1_000_000,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn latency(actor_id: ActorId, queued: u64, processing: u64) -> ActorBarrierLatency {
+        ActorBarrierLatency {
+            actor_id,
+            fragment_id: 0,
+            worker_id: 0,
+            queued: Duration::from_secs(queued),
+            processing: Duration::from_secs(processing),
+        }
+    }
+
+    #[test]
+    fn test_break_down_barrier_latency() {
+        // Actor 1 is the source of actors 2 and 3, which are the upstreams of actor 4.
+        let info = BarrierActorInfo {
+            node_map: HashMap::new(),
+            actor_map: HashMap::new(),
+            actor_map_to_send: HashMap::new(),
+            actor_topology: HashMap::from([
+                (1, (10, vec![])),
+                (2, (20, vec![1])),
+                (3, (20, vec![1])),
+                (4, (30, vec![2, 3])),
+                (5, (30, vec![2])),
+            ]),
+        };
+        // Actor 5 is collected before its upstream due to the clock difference between the
+        // workers, and actor 6 is not in the topology.
+        let resps = [
+            resp(1, &[(1, 2), (2, 5), (6, 1)]),
+            resp(2, &[(3, 3), (4, 6), (5, 4)]),
+        ];
+
+        let latencies = break_down_barrier_latency(&info, &resps)
+            .into_iter()
+            .map(|l| {
+                (
+                    l.actor_id,
+                    l.fragment_id,
+                    l.worker_id,
+                    l.queued.as_secs(),
+                    l.processing.as_secs(),
+                )
+            })
+            .sorted()
+            .collect_vec();
+        assert_eq!(
+            latencies,
+            vec![
+                (1, 10, 1, 0, 2),
+                (2, 20, 1, 2, 3),
+                (3, 20, 2, 2, 1),
+                (4, 30, 2, 5, 1),
+                (5, 30, 2, 4, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slow_actors() {
+        assert!(slow_actors(&[]).is_none());
+
+        // Not reported if the barrier is collected in time.
+        let latencies = [latency(1, 3, 6), latency(2, 0, 9)];
+        assert!(slow_actors(&latencies).is_none());
+
+        // Otherwise the actors taking the longest time to process the barrier are reported, even
+        // if it is not the ones with the longest latency.
+        let latencies = [
+            latency(1, 0, 1),
+            latency(2, 1, 5),
+            latency(3, 6, 4),
+            latency(4, 10, 2),
+            latency(5, 12, 0),
+            latency(6, 0, 7),
+            latency(7, 0, 3),
+        ];
+        let slow_actors = slow_actors(&latencies)
+            .unwrap()
+            .into_iter()
+            .map(|l| l.actor_id)
+            .collect_vec();
+        assert_eq!(slow_actors, vec![6, 2, 3, 7, 4]);
+    }
+}
@@ -25,10 +25,13 @@ use prometheus::HistogramTimer;
 use risingwave_common::bail;
 use risingwave_common::catalog::TableId;
 use risingwave_common::util::epoch::INVALID_EPOCH;
+use risingwave_hummock_sdk::compaction_group::hummock_version_ext::HummockVersionExt;
 use risingwave_hummock_sdk::{HummockSstableId, LocalSstableInfo};
 use risingwave_pb::common::worker_node::State::Running;
 use risingwave_pb::common::WorkerType;
+use risingwave_pb::ddl_service::DdlProgress;
 use risingwave_pb::meta::table_fragments::actor_status::ActorState;
+use risingwave_pb::meta::SystemParams;
 use risingwave_pb::stream_plan::Barrier;
 use risingwave_pb::stream_service::{
     BarrierCompleteRequest, BarrierCompleteResponse, InjectBarrierRequest,
@@ -36,11 +39,14 @@ use risingwave_pb::stream_service::{
 use risingwave_rpc_client::StreamClientPoolRef;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot::{Receiver, Sender};
+use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
+use tokio::time::Interval;
 use uuid::Uuid;
 
 use self::command::CommandContext;
 use self::info::BarrierActorInfo;
+use self::latency::{break_down_barrier_latency, slow_actors};
 use self::notifier::Notifier;
 use self::progress::TrackingCommand;
 use self::snapshot::SnapshotManagerRef;
@@ -49,16 +55,18 @@ use crate::barrier::snapshot::SnapshotManager;
 use crate::barrier::BarrierEpochState::{Completed, InFlight};
 use crate::hummock::HummockManagerRef;
 use crate::manager::{
-    CatalogManagerRef, ClusterManagerRef, FragmentManagerRef, MetaSrvEnv, WorkerId,
+    CatalogManagerRef, ClusterManagerRef, FragmentManagerRef, MetaSrvEnv, SystemParamManagerRef,
+    WorkerId,
 };
 use crate::model::{ActorId, BarrierManagerState};
 use crate::rpc::metrics::MetaMetrics;
 use crate::storage::meta_store::MetaStore;
-use crate::stream::SourceManagerRef;
+use crate::stream::{SinkCoordinatorRef, SourceManagerRef};
 use crate::{MetaError, MetaResult};
 
 mod command;
 mod info;
+mod latency;
 mod notifier;
 mod progress;
 mod recovery;
@@ -131,9 +139,16 @@ pub struct GlobalBarrierManager<S: MetaStore> {
 
     source_manager: SourceManagerRef<S>,
 
+    sink_coordinator: SinkCoordinatorRef<S>,
+
+    system_param_manager: SystemParamManagerRef<S>,
+
     metrics: Arc<MetaMetrics>,
 
     pub(crate) env: MetaSrvEnv<S>,
+
+    /// Tracks the progress of the creating mviews.
+    tracker: Mutex<CreateMviewProgressTracker<S>>,
 }
 
 /// Controls the concurrent execution of commands.
@@ -450,6 +465,8 @@ where
         fragment_manager: FragmentManagerRef<S>,
         hummock_manager: HummockManagerRef<S>,
         source_manager: SourceManagerRef<S>,
+        sink_coordinator: SinkCoordinatorRef<S>,
+        system_param_manager: SystemParamManagerRef<S>,
         metrics: Arc<MetaMetrics>,
     ) -> Self {
         let enable_recovery = env.opts.enable_recovery;
@@ -475,8 +492,11 @@ where
             hummock_manager,
             snapshot_manager,
             source_manager,
+            sink_coordinator,
+            system_param_manager,
             metrics,
             env,
+            tracker: Mutex::new(CreateMviewProgressTracker::new()),
         }
     }
 
@@ -491,7 +511,6 @@ where
 
     /// Start an infinite loop to take scheduled barriers and send them.
     async fn run(&self, mut shutdown_rx: Receiver<()>) {
-        let mut tracker = CreateMviewProgressTracker::new();
         let mut state = BarrierManagerState::create(self.env.meta_store()).await;
         if self.enable_recovery {
             // handle init, here we simply trigger a recovery process to achieve the consistency. We
@@ -507,8 +526,9 @@ where
                 .await
                 .unwrap();
         }
-        let mut min_interval = tokio::time::interval(self.interval);
-        min_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut params_rx = self.system_param_manager.watch_params();
+        let params = params_rx.borrow_and_update().clone();
+        let mut min_interval = self.apply_system_params(&params);
         let mut barrier_timer: Option<HistogramTimer> = None;
         let (barrier_complete_tx, mut barrier_complete_rx) = tokio::sync::mpsc::unbounded_channel();
         let mut checkpoint_control = CheckpointControl::new(self.metrics.clone());
@@ -520,6 +540,12 @@ where
                     tracing::info!("Barrier manager is stopped");
                     return;
                 }
+                // System params changed.
+                Ok(()) = params_rx.changed() => {
+                    let params = params_rx.borrow_and_update().clone();
+                    min_interval = self.apply_system_params(&params);
+                    continue;
+                }
                 result = barrier_complete_rx.recv() => {
                     checkpoint_control.update_barrier_nums_metrics();
 
@@ -528,7 +554,6 @@ where
                         prev_epoch,
                         result,
                         &mut state,
-                        &mut tracker,
                         &mut checkpoint_control,
                     )
                     .await;
@@ -595,6 +620,30 @@ where
         }
     }
 
+    /// Apply the barrier interval and checkpoint frequency in system params, falling back to the
+    /// startup options if not set. Returns the new timer for the minimal barrier interval.
+    fn apply_system_params(&self, params: &SystemParams) -> Interval {
+        let interval = match params.barrier_interval_ms {
+            0 => self.interval,
+            ms => Duration::from_millis(ms as u64),
+        };
+        let checkpoint_frequency = match params.checkpoint_frequency {
+            0 => self.env.opts.checkpoint_frequency,
+            frequency => frequency as usize,
+        };
+        tracing::info!(
+            "Barrier manager uses: interval={:?}, checkpoint_frequency={}",
+            interval,
+            checkpoint_frequency
+        );
+        self.scheduled_barriers
+            .set_checkpoint_frequency(checkpoint_frequency);
+
+        let mut min_interval = tokio::time::interval(interval);
+        min_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        min_interval
+    }
+
     /// Inject a barrier to all CNs and spawn a task to collect it
     async fn inject_barrier(
         &self,
@@ -719,14 +768,13 @@ where
         prev_epoch: u64,
         result: MetaResult<Vec<BarrierCompleteResponse>>,
         state: &mut BarrierManagerState,
-        tracker: &mut CreateMviewProgressTracker<S>,
         checkpoint_control: &mut CheckpointControl<S>,
     ) {
         if let Err(err) = result {
             fail_point!("inject_barrier_err_success");
             let fail_node = checkpoint_control.barrier_failed();
             tracing::warn!("Failed to commit epoch {}: {:?}", prev_epoch, err);
-            self.do_recovery(err, fail_node, state, tracker, checkpoint_control)
+            self.do_recovery(err, fail_node, state, checkpoint_control)
                 .await;
             return;
         }
@@ -736,10 +784,7 @@ where
         let (mut index, mut err_msg) = (0, None);
         for (i, node) in complete_nodes.iter_mut().enumerate() {
             assert!(matches!(node.state, Completed(_)));
-            if let Err(err) = self
-                .complete_barrier(node, tracker, checkpoint_control)
-                .await
-            {
+            if let Err(err) = self.complete_barrier(node, checkpoint_control).await {
                 index = i;
                 err_msg = Some(err);
                 break;
@@ -750,7 +795,7 @@ where
             let fail_nodes = complete_nodes
                 .drain(index..)
                 .chain(checkpoint_control.barrier_failed().into_iter());
-            self.do_recovery(err, fail_nodes, state, tracker, checkpoint_control)
+            self.do_recovery(err, fail_nodes, state, checkpoint_control)
                 .await;
         }
     }
@@ -760,7 +805,6 @@ where
         err: MetaError,
         fail_nodes: impl IntoIterator<Item = EpochNode<S>>,
         state: &mut BarrierManagerState,
-        tracker: &mut CreateMviewProgressTracker<S>,
         checkpoint_control: &mut CheckpointControl<S>,
     ) {
         checkpoint_control.clear_changes();
@@ -777,7 +821,7 @@ where
         }
         if self.enable_recovery {
             // If failed, enter recovery mode.
-            *tracker = CreateMviewProgressTracker::new();
+            *self.tracker.lock().await = CreateMviewProgressTracker::new();
             let new_epoch = self.recovery(state.in_flight_prev_epoch).await;
             state.in_flight_prev_epoch = new_epoch;
             state
@@ -793,7 +837,6 @@ where
     async fn complete_barrier(
         &self,
         node: &mut EpochNode<S>,
-        tracker: &mut CreateMviewProgressTracker<S>,
         checkpoint_control: &mut CheckpointControl<S>,
     ) -> MetaResult<()> {
         let prev_epoch = node.command_ctx.prev_epoch.0;
@@ -830,6 +873,11 @@ where
                     self.hummock_manager
                         .commit_epoch(node.command_ctx.prev_epoch.0, synced_ssts, sst_to_worker)
                         .await?;
+                    // The two-phase sinks commit the transactions of the checkpoint once it's
+                    // durable.
+                    self.sink_coordinator
+                        .commit_epoch(node.command_ctx.prev_epoch.0)
+                        .await?;
                 } else {
                     self.hummock_manager.update_current_epoch(prev_epoch)?;
                     // if we collect a barrier(checkpoint = false),
@@ -840,6 +888,8 @@ where
 
                 node.command_ctx.post_collect().await?;
 
+                self.report_barrier_latency(&node.command_ctx, resps);
+
                 // Notify about collected.
                 let mut notifiers = take(&mut node.notifiers);
                 notifiers.iter_mut().for_each(|notifier| {
@@ -847,12 +897,23 @@ where
                 });
 
                 // Save `finished_commands` for Create MVs.
+                let upstream_total_key_count = self
+                    .estimate_upstream_total_key_count(&node.command_ctx.command)
+                    .await;
                 let finished_commands = {
+                    let mut tracker = self.tracker.lock().await;
+                    if let Command::DropMaterializedViews(table_ids) = &node.command_ctx.command {
+                        // The creating mviews may be dropped by cancellation.
+                        tracker.cancel(table_ids);
+                    }
                     let mut commands = vec![];
-                    if let Some(command) = tracker.add(TrackingCommand {
-                        context: node.command_ctx.clone(),
-                        notifiers,
-                    }) {
+                    if let Some(command) = tracker.add(
+                        TrackingCommand {
+                            context: node.command_ctx.clone(),
+                            notifiers,
+                        },
+                        upstream_total_key_count,
+                    ) {
                         commands.push(command);
                     }
                     for progress in resps.iter().flat_map(|r| &r.create_mview_progress) {
@@ -884,6 +945,87 @@ where
         }
     }
 
+    /// Update the per-fragment barrier latency metrics, and report the slowest actors in the logs
+    /// and the metrics if the barrier is slow to be collected.
+    fn report_barrier_latency(
+        &self,
+        command_ctx: &CommandContext<S>,
+        resps: &[BarrierCompleteResponse],
+    ) {
+        let latencies = break_down_barrier_latency(&command_ctx.info, resps);
+
+        let mut fragment_latency: HashMap<_, (Duration, Duration)> = HashMap::new();
+        for l in &latencies {
+            let (queued, processing) = fragment_latency.entry(l.fragment_id).or_default();
+            *queued = (*queued).max(l.queued);
+            *processing = (*processing).max(l.processing);
+        }
+        self.metrics.fragment_barrier_queued_latency.reset();
+        self.metrics.fragment_barrier_processing_latency.reset();
+        for (fragment_id, (queued, processing)) in fragment_latency {
+            let fragment_id = fragment_id.to_string();
+            self.metrics
+                .fragment_barrier_queued_latency
+                .with_label_values(&[&fragment_id])
+                .set(queued.as_secs_f64());
+            self.metrics
+                .fragment_barrier_processing_latency
+                .with_label_values(&[&fragment_id])
+                .set(processing.as_secs_f64());
+        }
+
+        self.metrics.slow_actor_barrier_processing_latency.reset();
+        if let Some(slow_actors) = slow_actors(&latencies) {
+            for l in &slow_actors {
+                self.metrics
+                    .slow_actor_barrier_processing_latency
+                    .with_label_values(&[
+                        &l.actor_id.to_string(),
+                        &l.fragment_id.to_string(),
+                        &l.worker_id.to_string(),
+                    ])
+                    .set(l.processing.as_secs_f64());
+            }
+            tracing::warn!(
+                "barrier with prev epoch {} is slow to collect, slowest actors: {:?}",
+                command_ctx.prev_epoch.0,
+                slow_actors
+            );
+        }
+    }
+
+    /// Estimate the rows of the upstream tables to be consumed by a `CreateMaterializedView`
+    /// command, with the key count of the SSTs containing these tables. Returns 0 for other
+    /// commands.
+    async fn estimate_upstream_total_key_count(&self, command: &Command) -> u64 {
+        let Command::CreateMaterializedView { table_sink_map, .. } = command else {
+            return 0;
+        };
+        let upstream_table_ids: HashSet<u32> =
+            table_sink_map.keys().map(|id| id.table_id).collect();
+        let version = self.hummock_manager.get_current_version().await;
+        version
+            .get_combined_levels()
+            .iter()
+            .flat_map(|level| level.table_infos.iter())
+            .map(|sst| {
+                let matched = sst
+                    .table_ids
+                    .iter()
+                    .filter(|id| upstream_table_ids.contains(id))
+                    .count() as u64;
+                // Assume the keys are evenly distributed among the tables in the SST.
+                let live_key_count = sst.total_key_count.saturating_sub(sst.stale_key_count);
+                live_key_count * matched / (sst.table_ids.len() as u64).max(1)
+            })
+            .sum()
+    }
+
+    /// Returns the progress of all creating mviews.
+    pub async fn get_ddl_progress(&self) -> HashMap<u32, DdlProgress> {
+        self.tracker.lock().await.gen_ddl_progress()
+    }
+
     /// Resolve actor information from cluster, fragment manager and `ChangedTableId`.
     /// We use `changed_table_id` to modify the actors to be sent or collected. Because these actor
     /// will create or drop before this barrier flow through them.
@@ -13,24 +13,37 @@
 // limitations under the License.
 
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use risingwave_common::catalog::TableId;
 use risingwave_common::util::epoch::Epoch;
+use risingwave_pb::ddl_service::DdlProgress;
 use risingwave_pb::stream_service::barrier_complete_response::CreateMviewProgress;
 
 use super::command::CommandContext;
 use super::notifier::Notifier;
+use super::Command;
 use crate::model::ActorId;
 use crate::storage::MetaStore;
 
 type CreateMviewEpoch = Epoch;
+type ConsumedRows = u64;
 
 #[derive(Clone, Copy)]
 enum ChainState {
     ConsumingSnapshot,
-    ConsumingUpstream(Epoch),
-    Done,
+    ConsumingUpstream(Epoch, ConsumedRows),
+    Done(ConsumedRows),
+}
+
+impl ChainState {
+    fn consumed_rows(&self) -> ConsumedRows {
+        match self {
+            ChainState::ConsumingSnapshot => 0,
+            ChainState::ConsumingUpstream(_, rows) | ChainState::Done(rows) => *rows,
+        }
+    }
 }
 
 /// Progress of all actors containing chain nodes while creating mview.
@@ -38,11 +51,17 @@ struct Progress {
     states: HashMap<ActorId, ChainState>,
 
     done_count: usize,
+
+    /// Estimated number of rows in the upstream tables to be consumed by the chain nodes.
+    upstream_total_key_count: u64,
+
+    /// Sum of the rows consumed by all chain nodes.
+    consumed_rows: u64,
 }
 
 impl Progress {
     /// Create a [`Progress`] for some creating mview, with all `actors` containing the chain nodes.
-    fn new(actors: impl IntoIterator<Item = ActorId>) -> Self {
+    fn new(actors: impl IntoIterator<Item = ActorId>, upstream_total_key_count: u64) -> Self {
         let states = actors
             .into_iter()
             .map(|a| (a, ChainState::ConsumingSnapshot))
@@ -52,19 +71,23 @@ impl Progress {
         Self {
             states,
             done_count: 0,
+            upstream_total_key_count,
+            consumed_rows: 0,
         }
     }
 
     /// Update the progress of `actor`.
     fn update(&mut self, actor: ActorId, new_state: ChainState) {
         match self.states.get_mut(&actor).unwrap() {
-            state @ (ChainState::ConsumingSnapshot | ChainState::ConsumingUpstream(_)) => {
-                if matches!(new_state, ChainState::Done) {
+            state @ (ChainState::ConsumingSnapshot | ChainState::ConsumingUpstream(..)) => {
+                if matches!(new_state, ChainState::Done(_)) {
                     self.done_count += 1;
                 }
+                self.consumed_rows -= state.consumed_rows();
+                self.consumed_rows += new_state.consumed_rows();
                 *state = new_state;
             }
-            ChainState::Done => panic!("should not report done multiple times"),
+            ChainState::Done(_) => panic!("should not report done multiple times"),
         }
     }
 
@@ -73,6 +96,29 @@ impl Progress {
         self.done_count == self.states.len()
     }
 
+    /// Returns a human-readable description of the progress.
+    fn calculate_progress(&self) -> String {
+        if self.is_done() {
+            return "100.00%".to_owned();
+        }
+        if self.upstream_total_key_count == 0 {
+            return format!(
+                "{} rows consumed, {}/{} actors done",
+                self.consumed_rows,
+                self.done_count,
+                self.states.len()
+            );
+        }
+        // The estimation may be smaller than the actual rows, never report 100% before done.
+        let ratio = (self.consumed_rows as f64 / self.upstream_total_key_count as f64).min(0.9999);
+        format!(
+            "{:.2}% ({}/{} rows)",
+            ratio * 100.0,
+            self.consumed_rows,
+            self.upstream_total_key_count
+        )
+    }
+
     /// Returns the ids of all actors containing the chain nodes for the mview tracked by this
     /// [`Progress`].
     fn actors(&self) -> impl Iterator<Item = ActorId> + '_ {
@@ -89,6 +135,18 @@ pub(super) struct TrackingCommand<S: MetaStore> {
     pub notifiers: Vec<Notifier>,
 }
 
+impl<S: MetaStore> TrackingCommand<S> {
+    /// The id of the creating mview of this command, if any.
+    fn table_id(&self) -> Option<TableId> {
+        match &self.context.command {
+            Command::CreateMaterializedView {
+                table_fragments, ..
+            } => Some(table_fragments.table_id()),
+            _ => None,
+        }
+    }
+}
+
 /// Track the progress of all creating mviews. When creation is done, `notify_finished` will be
 /// called on registered notifiers.
 pub(super) struct CreateMviewProgressTracker<S: MetaStore> {
@@ -107,10 +165,55 @@ impl<S: MetaStore> CreateMviewProgressTracker<S> {
         }
     }
 
-    /// Add a new create-mview DDL command to track.
+    /// Returns the progress of all creating mviews, keyed by the id of the mview. The statements
+    /// are left empty for the caller to fill.
+    pub fn gen_ddl_progress(&self) -> HashMap<u32, DdlProgress> {
+        self.progress_map
+            .values()
+            .filter_map(|(progress, command)| {
+                let table_id = command.table_id()?.table_id;
+                Some((
+                    table_id,
+                    DdlProgress {
+                        id: table_id as u64,
+                        statement: String::new(),
+                        progress: progress.calculate_progress(),
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Stop tracking the creating mviews in `table_ids`, which are being cancelled. The notifiers
+    /// of them are dropped without being notified.
+    pub fn cancel(&mut self, table_ids: &HashSet<TableId>) {
+        let epochs = self
+            .progress_map
+            .iter()
+            .filter(|(_, (_, command))| {
+                command
+                    .table_id()
+                    .map_or(false, |table_id| table_ids.contains(&table_id))
+            })
+            .map(|(epoch, _)| *epoch)
+            .collect::<Vec<_>>();
+        for epoch in epochs {
+            let (progress, _) = self.progress_map.remove(&epoch).unwrap();
+            for actor in progress.actors() {
+                self.actor_map.remove(&actor);
+            }
+            tracing::debug!("stop tracking cancelled mview with epoch {}", epoch);
+        }
+    }
+
+    /// Add a new create-mview DDL command to track, with the estimated number of rows to consume.
     ///
     /// If the actors to track is empty, return the given command as it can be finished immediately.
-    pub fn add(&mut self, command: TrackingCommand<S>) -> Option<TrackingCommand<S>> {
+    pub fn add(
+        &mut self,
+        command: TrackingCommand<S>,
+        upstream_total_key_count: u64,
+    ) -> Option<TrackingCommand<S>> {
         let actors = command.context.actors_to_track();
         if actors.is_empty() {
             // The command can be finished immediately.
@@ -122,7 +225,7 @@ impl<S: MetaStore> CreateMviewProgressTracker<S> {
             self.actor_map.insert(actor, ddl_epoch);
         }
 
-        let progress = Progress::new(actors);
+        let progress = Progress::new(actors, upstream_total_key_count);
         let old = self.progress_map.insert(ddl_epoch, (progress, command));
         assert!(old.is_none());
         None
@@ -134,13 +237,15 @@ impl<S: MetaStore> CreateMviewProgressTracker<S> {
     pub fn update(&mut self, progress: &CreateMviewProgress) -> Option<TrackingCommand<S>> {
         let actor = progress.chain_actor_id;
         let Some(epoch) = self.actor_map.get(&actor).copied() else {
-            panic!("no tracked progress for actor {}, is it already finished?", actor);
+            // The mview may have been cancelled.
+            tracing::warn!("no tracked progress for actor {}, is it already finished or cancelled?", actor);
+            return None;
         };
 
         let new_state = if progress.done {
-            ChainState::Done
+            ChainState::Done(progress.consumed_rows)
         } else {
-            ChainState::ConsumingUpstream(progress.consumed_epoch.into())
+            ChainState::ConsumingUpstream(progress.consumed_epoch.into(), progress.consumed_rows)
         };
 
         match self.progress_map.entry(epoch) {
@@ -99,6 +99,17 @@ where
         self.clean_dirty_fragments()
             .await
             .expect("clean dirty fragments");
+        // Decide the commits of the two-phase sinks before the actors are rebuilt, so that the
+        // commits left by the failed actors are resolved on the first barrier.
+        let committed_epoch = self
+            .hummock_manager
+            .get_current_version()
+            .await
+            .max_committed_epoch;
+        self.sink_coordinator
+            .recover(committed_epoch, prev_epoch.0)
+            .await
+            .expect("recover sink coordinator");
         let retry_strategy = Self::get_retry_strategy();
         let (new_epoch, _responses) = tokio_retry::Retry::spawn(retry_strategy, || async {
             let mut info = self.resolve_actor_info_for_recovery().await;
@@ -190,7 +201,9 @@ where
             let new_nodes = current_nodes
                 .into_iter()
                 .filter(|node| {
-                    !info.node_map.contains_key(&node.id) && !node_map.contains_key(&node.id)
+                    !info.node_map.contains_key(&node.id)
+                        && !node_map.contains_key(&node.id)
+                        && !node.unschedulable
                 })
                 .collect_vec();
             for new_node in new_nodes {
@@ -269,12 +282,17 @@ where
                 })
                 .await?;
 
+            let mut stream_actors = node_actors.get(node_id).cloned().unwrap_or_default();
+            self.catalog_manager
+                .fill_actor_secrets(&mut stream_actors)
+                .await?;
+
             let request_id = Uuid::new_v4().to_string();
             tracing::debug!(request_id = request_id.as_str(), actors = ?actors, "update actors");
             client
                 .update_actors(UpdateActorsRequest {
                     request_id,
-                    actors: node_actors.get(node_id).cloned().unwrap_or_default(),
+                    actors: stream_actors,
                     ..Default::default()
                 })
                 .await?;
@@ -44,7 +44,9 @@ struct Inner {
     /// Force checkpoint in next barrier.
     force_checkpoint: AtomicBool,
 
-    checkpoint_frequency: usize,
+    /// Inject a barrier (checkpoint = true) every `checkpoint_frequency` barriers. Can be changed
+    /// at runtime by system params.
+    checkpoint_frequency: AtomicUsize,
 }
 
 /// The sender side of the barrier scheduling queue.
@@ -72,7 +74,7 @@ impl<S: MetaStore> BarrierScheduler<S> {
             queue: RwLock::new(VecDeque::new()),
             changed_tx: watch::channel(()).0,
             num_uncheckpointed_barrier: AtomicUsize::new(0),
-            checkpoint_frequency,
+            checkpoint_frequency: AtomicUsize::new(checkpoint_frequency),
             force_checkpoint: AtomicBool::new(false),
         });
 
@@ -262,10 +264,25 @@ impl ScheduledBarriers {
         self.inner
             .num_uncheckpointed_barrier
             .load(Ordering::Relaxed)
-            >= self.inner.checkpoint_frequency
+            >= self.inner.checkpoint_frequency.load(Ordering::Relaxed)
             || self.inner.force_checkpoint.load(Ordering::Relaxed)
     }
 
+    /// Update the checkpoint frequency, which takes effect from the next barrier.
+    pub(crate) fn set_checkpoint_frequency(&self, checkpoint_frequency: usize) {
+        let old = self
+            .inner
+            .checkpoint_frequency
+            .swap(checkpoint_frequency, Ordering::Relaxed);
+        if old != checkpoint_frequency {
+            tracing::info!(
+                "Update checkpoint_frequency from {} to {}",
+                old,
+                checkpoint_frequency
+            );
+        }
+    }
+
     /// Make the `checkpoint` of the next barrier must be true
     pub(crate) fn force_checkpoint_in_next_barrier(&self) {
         self.inner.force_checkpoint.store(true, Ordering::Relaxed)
@@ -41,6 +41,7 @@ pub struct DashboardService<S: MetaStore> {
     pub dashboard_addr: SocketAddr,
     pub cluster_manager: ClusterManagerRef<S>,
     pub fragment_manager: FragmentManagerRef<S>,
+    pub prometheus_endpoint: Option<String>,
 
     // TODO: replace with catalog manager.
     pub meta_store: Arc<S>,
@@ -55,6 +56,7 @@ mod handlers {
     use risingwave_pb::common::WorkerNode;
     use risingwave_pb::meta::{ActorLocation, TableFragments as ProstTableFragments};
     use risingwave_pb::stream_plan::StreamActor;
+    use serde::{Deserialize, Serialize};
     use serde_json::json;
 
     use super::*;
@@ -165,6 +167,106 @@ mod handlers {
             .collect_vec();
         Ok(Json(table_fragments))
     }
+
+    /// Streaming metrics of the fragments, used to color the edges of the fragment graph.
+    #[derive(Serialize, Default)]
+    pub struct FragmentMetrics {
+        /// Fragment id -> the ratio of time its actors are blocked on sending to the downstream,
+        /// i.e. the back pressure the fragment receives. The maximum among actors is taken.
+        back_pressures: HashMap<u32, f64>,
+        /// The p99 barrier latency of the whole cluster, in seconds.
+        barrier_latency_p99: Option<f64>,
+    }
+
+    #[derive(Deserialize)]
+    struct PrometheusResponse {
+        data: PrometheusData,
+    }
+
+    #[derive(Deserialize)]
+    struct PrometheusData {
+        result: Vec<PrometheusSample>,
+    }
+
+    #[derive(Deserialize)]
+    struct PrometheusSample {
+        metric: HashMap<String, String>,
+        value: (f64, String),
+    }
+
+    /// Runs an instant query against the Prometheus HTTP API.
+    async fn query_prometheus(endpoint: &str, query: &str) -> Result<Vec<PrometheusSample>> {
+        let url = Url::parse_with_params(
+            &format!("{}/api/v1/query", endpoint.trim_end_matches('/')),
+            &[("query", query)],
+        )
+        .map_err(err)?;
+        let body = reqwest::get(url)
+            .await
+            .map_err(err)?
+            .error_for_status()
+            .map_err(err)?
+            .text()
+            .await
+            .map_err(err)?;
+        let resp: PrometheusResponse = serde_json::from_str(&body).map_err(err)?;
+        Ok(resp.data.result)
+    }
+
+    pub async fn list_fragment_metrics<S: MetaStore>(
+        Extension(srv): Extension<Service<S>>,
+    ) -> Result<Json<FragmentMetrics>> {
+        let endpoint = match &srv.prometheus_endpoint {
+            Some(endpoint) => endpoint,
+            // Metrics are optional for the dashboard, return nothing if Prometheus is not set.
+            None => return Ok(Json(FragmentMetrics::default())),
+        };
+
+        let actor_to_fragment: HashMap<u32, u32> = srv
+            .fragment_manager
+            .list_table_fragments()
+            .await
+            .map_err(err)?
+            .iter()
+            .flat_map(|tf| tf.fragments.values())
+            .flat_map(|f| f.actors.iter().map(|a| (a.actor_id, f.fragment_id)))
+            .collect();
+
+        let mut back_pressures = HashMap::new();
+        let samples = query_prometheus(
+            endpoint,
+            "rate(stream_actor_output_buffer_blocking_duration_ns[1m]) / 1000000000",
+        )
+        .await?;
+        for sample in samples {
+            let fragment_id = sample
+                .metric
+                .get("actor_id")
+                .and_then(|id| id.parse::<u32>().ok())
+                .and_then(|actor_id| actor_to_fragment.get(&actor_id));
+            let ratio = sample.value.1.parse::<f64>().ok().filter(|r| !r.is_nan());
+            let (fragment_id, ratio) = match (fragment_id, ratio) {
+                (Some(fragment_id), Some(ratio)) => (fragment_id, ratio),
+                _ => continue,
+            };
+            let entry = back_pressures.entry(*fragment_id).or_insert(0.0f64);
+            *entry = entry.max(ratio);
+        }
+
+        let barrier_latency_p99 = query_prometheus(
+            endpoint,
+            "histogram_quantile(0.99, sum(rate(meta_barrier_duration_seconds_bucket[1m])) by (le))",
+        )
+        .await?
+        .first()
+        .and_then(|sample| sample.value.1.parse::<f64>().ok())
+        .filter(|latency| !latency.is_nan());
+
+        Ok(Json(FragmentMetrics {
+            back_pressures,
+            barrier_latency_p99,
+        }))
+    }
 }
 
 #[derive(Clone)]
@@ -259,6 +361,7 @@ where
             .route("/fragments2", get(list_fragments::<S>))
             .route("/materialized_views", get(list_materialized_views::<S>))
             .route("/sources", get(list_sources::<S>))
+            .route("/metrics/fragments", get(list_fragment_metrics::<S>))
             .layer(
                 ServiceBuilder::new()
                     .layer(AddExtensionLayer::new(srv.clone()))
@@ -53,6 +53,10 @@ pub trait LevelSelector: Sync + Send {
         option: ManualCompactionOption,
     ) -> Option<CompactionTask>;
 
+    /// Estimates the bytes that must be rewritten to bring the LSM tree back to its target shape,
+    /// excluding data already being compacted by pending tasks.
+    fn compaction_pending_bytes(&self, levels: &Levels, level_handlers: &[LevelHandler]) -> u64;
+
     fn name(&self) -> &'static str;
 }
 
@@ -317,6 +321,29 @@ impl LevelSelector for DynamicLevelSelector {
         Some(self.create_compaction_task(ret, ctx.base_level))
     }
 
+    fn compaction_pending_bytes(&self, levels: &Levels, level_handlers: &[LevelHandler]) -> u64 {
+        let ctx = self.calculate_level_base_size(levels);
+        // All idle data in L0 needs to be compacted into the base level.
+        let mut pending_bytes = levels
+            .l0
+            .as_ref()
+            .unwrap()
+            .total_file_size
+            .saturating_sub(level_handlers[0].get_pending_file_size());
+        // The bottommost level never needs to be compacted.
+        for level in &levels.levels {
+            let level_idx = level.level_idx as usize;
+            if level_idx < ctx.base_level || level_idx >= self.config.max_level as usize {
+                continue;
+            }
+            let idle_size = level
+                .total_file_size
+                .saturating_sub(level_handlers[level_idx].get_pending_file_size());
+            pending_bytes += idle_size.saturating_sub(ctx.level_max_bytes[level_idx]);
+        }
+        pending_bytes
+    }
+
     fn name(&self) -> &'static str {
         "DynamicLevelSelector"
     }
@@ -548,6 +575,51 @@ pub mod tests {
         assert_eq!(ctx.level_max_bytes[4], 3000);
     }
 
+    #[test]
+    fn test_compaction_pending_bytes() {
+        let config = CompactionConfigBuilder::new()
+            .max_bytes_for_level_base(100)
+            .max_level(4)
+            .max_bytes_for_level_multiplier(5)
+            .max_compaction_bytes(10000)
+            .level0_trigger_file_number(1)
+            .level0_tier_compact_file_number(2)
+            .compaction_mode(CompactionMode::Range as i32)
+            .build();
+        let selector =
+            DynamicLevelSelector::new(Arc::new(config), Arc::new(RangeOverlapStrategy::default()));
+        let levels = vec![
+            generate_level(1, vec![]),
+            generate_level(2, generate_tables(0..5, 0..1000, 3, 10)),
+            generate_level(3, generate_tables(5..10, 0..1000, 2, 50)),
+            generate_level(4, generate_tables(10..15, 0..1000, 1, 200)),
+        ];
+        let mut levels = Levels {
+            levels,
+            l0: Some(generate_l0_nonoverlapping_sublevels(vec![])),
+        };
+        let mut levels_handlers = (0..5).map(LevelHandler::new).collect_vec();
+        // L3 exceeds its target size (200) by 50, and L4 is the bottommost level.
+        assert_eq!(
+            selector.compaction_pending_bytes(&levels, &levels_handlers),
+            50
+        );
+
+        // All data in L0 is pending.
+        push_tables_level0_nonoverlapping(&mut levels, generate_tables(15..21, 0..600, 4, 100));
+        assert_eq!(
+            selector.compaction_pending_bytes(&levels, &levels_handlers),
+            650
+        );
+
+        // Data picked by a task is no longer pending.
+        let compaction = selector
+            .pick_compaction(1, &levels, &mut levels_handlers)
+            .unwrap();
+        assert_compaction_task(&compaction, &levels_handlers);
+        assert!(selector.compaction_pending_bytes(&levels, &levels_handlers) < 650);
+    }
+
     #[test]
     fn test_pick_compaction() {
         let config = CompactionConfigBuilder::new()
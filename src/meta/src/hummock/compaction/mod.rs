@@ -240,6 +240,34 @@ impl CompactStatus {
         self.compaction_group_id
     }
 
+    /// Returns the estimated bytes waiting to be compacted, which don't belong to any running
+    /// task yet.
+    pub fn compaction_pending_bytes(
+        &self,
+        levels: &Levels,
+        compaction_config: CompactionConfig,
+    ) -> u64 {
+        self.create_level_selector(compaction_config)
+            .compaction_pending_bytes(levels, &self.level_handlers)
+    }
+
+    /// Returns the total bytes of input SSTs of running tasks.
+    pub fn compacting_bytes(&self) -> u64 {
+        self.level_handlers
+            .iter()
+            .map(|handler| handler.get_pending_file_size())
+            .sum()
+    }
+
+    /// Returns the number of running tasks, including those not assigned to any compactor yet.
+    pub fn running_task_count(&self) -> usize {
+        self.level_handlers
+            .iter()
+            .flat_map(|handler| handler.pending_tasks_ids())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
     /// Creates a level selector.
     ///
     /// The method should be lightweight because we recreate a level selector everytime so that the
@@ -12,12 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use function_name::named;
 use itertools::Itertools;
 use risingwave_hummock_sdk::{CompactionGroupId, HummockCompactionTaskId, HummockContextId};
-use risingwave_pb::hummock::{CompactTaskAssignment, CompactionConfig};
+use risingwave_pb::hummock::{CompactTaskAssignment, CompactionConfig, CompactionGroupBacklog};
 
 use crate::hummock::compaction::CompactStatus;
 use crate::hummock::error::Result;
@@ -121,6 +121,41 @@ where
             .compaction_config()
     }
 
+    /// Reports the compaction backlog of each compaction group, which can be used as the scaling
+    /// signal of compactors.
+    #[named]
+    pub async fn get_compaction_backlog(&self) -> Vec<CompactionGroupBacklog> {
+        // Query the group configs before taking the locks, so that neither `compaction` nor
+        // `versioning` is held across the compaction group manager.
+        let compaction_configs: HashMap<_, _> = self
+            .compaction_group_manager
+            .compaction_groups()
+            .await
+            .into_iter()
+            .map(|group| (group.group_id(), group.compaction_config()))
+            .collect();
+        let compaction = read_lock!(self, compaction).await;
+        let versioning = read_lock!(self, versioning).await;
+        let mut backlogs = vec![];
+        for (compaction_group_id, compact_status) in &compaction.compaction_statuses {
+            let levels = match versioning.current_version.levels.get(compaction_group_id) {
+                Some(levels) => levels,
+                None => continue,
+            };
+            let compaction_config = match compaction_configs.get(compaction_group_id) {
+                Some(compaction_config) => compaction_config.clone(),
+                None => continue,
+            };
+            backlogs.push(CompactionGroupBacklog {
+                compaction_group_id: *compaction_group_id,
+                pending_bytes: compact_status.compaction_pending_bytes(levels, compaction_config),
+                compacting_bytes: compact_status.compacting_bytes(),
+                running_task_count: compact_status.running_task_count() as u64,
+            });
+        }
+        backlogs
+    }
+
     #[named]
     pub async fn list_all_tasks_ids(&self) -> Vec<HummockCompactionTaskId> {
         let compaction = read_lock!(self, compaction).await;
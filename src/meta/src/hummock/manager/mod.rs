@@ -269,12 +269,23 @@ where
                         tracing::info!("CancelTask operation for task_id {} has been sent to node with context_id {context_id}", task.task_id);
                     }
 
-                    if let Err(e) = hummock_manager
+                    match hummock_manager
                         .cancel_compact_task(&mut task, TaskStatus::HeartbeatCanceled)
                         .await
                     {
-                        tracing::error!("Attempt to remove compaction task due to elapsed heartbeat failed. We will continue to track its heartbeat
-                            until we can successfully report its status. {context_id}, task_id: {}, ERR: {e:?}", task.task_id);
+                        Ok(_) => {
+                            // The input SSTs of the cancelled task are released, so reschedule the
+                            // compaction group to let another compactor pick them up.
+                            if let Err(e) = hummock_manager
+                                .try_send_compaction_request(task.compaction_group_id)
+                            {
+                                tracing::warn!("Failed to reschedule compaction group {} after cancelling task {}. {e:?}", task.compaction_group_id, task.task_id);
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Attempt to remove compaction task due to elapsed heartbeat failed. We will continue to track its heartbeat
+                                until we can successfully report its status. {context_id}, task_id: {}, ERR: {e:?}", task.task_id);
+                        }
                     }
                 }
             }
@@ -781,6 +792,57 @@ where
             .await
     }
 
+    /// Cancels an assigned compaction task identified by `task_id`, e.g. on request of risectl.
+    /// The assignee compactor is notified to abort the task, and the compaction group is
+    /// rescheduled so that the released input SSTs can be picked by another compactor.
+    ///
+    /// Returns `Ok(false)` if no assigned task with `task_id` is found.
+    #[named]
+    pub async fn cancel_compact_task_by_id(
+        &self,
+        task_id: HummockCompactionTaskId,
+        task_status: TaskStatus,
+    ) -> Result<bool> {
+        let assignment = read_lock!(self, compaction)
+            .await
+            .compact_task_assignment
+            .get(&task_id)
+            .cloned();
+        let (context_id, mut compact_task) = match assignment {
+            Some(CompactTaskAssignment {
+                compact_task: Some(compact_task),
+                context_id,
+            }) => (context_id, compact_task),
+            _ => return Ok(false),
+        };
+        if let Some(compactor) = self.compactor_manager.get_compactor(context_id) {
+            // Best effort. The task is cancelled in meta anyway.
+            if let Err(e) = compactor.cancel_task(task_id).await {
+                tracing::warn!(
+                    "Failed to send CancelTask for task_id {} to node with context_id {}. {}",
+                    task_id,
+                    context_id,
+                    e
+                );
+            }
+        }
+        if !self
+            .cancel_compact_task(&mut compact_task, task_status)
+            .await?
+        {
+            return Ok(false);
+        }
+        if let Err(e) = self.try_send_compaction_request(compact_task.compaction_group_id) {
+            tracing::warn!(
+                "Failed to reschedule compaction group {} after cancelling task {}. {}",
+                compact_task.compaction_group_id,
+                task_id,
+                e
+            );
+        }
+        Ok(true)
+    }
+
     pub async fn get_compact_task(
         &self,
         compaction_group_id: CompactionGroupId,
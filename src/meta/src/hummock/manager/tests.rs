@@ -1195,3 +1195,61 @@ async fn test_extend_ssts_to_delete() {
         orphan_sst_num as usize + 3
     );
 }
+
+#[tokio::test]
+async fn test_cancel_compact_task_by_id() {
+    let (_, hummock_manager, _, worker_node) = setup_compute_env(80).await;
+    let context_id = worker_node.id;
+    let epoch: u64 = 1;
+    let original_tables = generate_test_tables(epoch, get_sst_ids(&hummock_manager, 2).await);
+    register_sstable_infos_to_compaction_group(
+        hummock_manager.compaction_group_manager(),
+        &original_tables,
+        StaticCompactionGroupId::StateDefault.into(),
+    )
+    .await;
+    commit_from_meta_node(
+        hummock_manager.borrow(),
+        epoch,
+        to_local_sstable_info(&original_tables),
+    )
+    .await
+    .unwrap();
+    let compactor_manager = hummock_manager.compactor_manager_ref_for_test();
+    let _receiver = compactor_manager.add_compactor(context_id, u64::MAX);
+
+    let compact_task = hummock_manager
+        .get_compact_task(StaticCompactionGroupId::StateDefault.into())
+        .await
+        .unwrap()
+        .unwrap();
+    // An unassigned task cannot be cancelled by id.
+    assert!(!hummock_manager
+        .cancel_compact_task_by_id(compact_task.task_id, TaskStatus::ManualCanceled)
+        .await
+        .unwrap());
+    hummock_manager
+        .assign_compaction_task(&compact_task, context_id)
+        .await
+        .unwrap();
+    assert_eq!(hummock_manager.get_assigned_compact_task_num().await, 1);
+
+    assert!(hummock_manager
+        .cancel_compact_task_by_id(compact_task.task_id, TaskStatus::ManualCanceled)
+        .await
+        .unwrap());
+    assert_eq!(hummock_manager.get_assigned_compact_task_num().await, 0);
+    assert!(compactor_manager.get_expired_tasks().is_empty());
+    // The task no longer exists.
+    assert!(!hummock_manager
+        .cancel_compact_task_by_id(compact_task.task_id, TaskStatus::ManualCanceled)
+        .await
+        .unwrap());
+
+    // The input SSTs are released, so a new task can be picked.
+    assert!(hummock_manager
+        .get_compact_task(StaticCompactionGroupId::StateDefault.into())
+        .await
+        .unwrap()
+        .is_some());
+}
@@ -38,6 +38,7 @@ pub use compaction_scheduler::CompactionScheduler;
 pub use compactor_manager::*;
 #[cfg(any(test, feature = "test"))]
 pub use mock_hummock_meta_client::MockHummockMetaClient;
+use risingwave_pb::common::WorkerType;
 use sync_point::sync_point;
 use tokio::sync::oneshot::Sender;
 use tokio::task::JoinHandle;
@@ -116,6 +117,15 @@ where
                                 .await
                                 .expect("retry until success");
                             tracing::info!("Released hummock context {}", worker_node.id);
+                            if worker_node.r#type == WorkerType::Compactor as i32 {
+                                // Tasks of the compactor are cancelled, reschedule them to other
+                                // compactors.
+                                for cg_id in hummock_manager.compaction_group_manager().compaction_group_ids().await {
+                                    if let Err(e) = hummock_manager.try_send_compaction_request(cg_id) {
+                                        tracing::warn!("Failed to schedule compaction for compaction group {}. {}", cg_id, e);
+                                    }
+                                }
+                            }
                             sync_point!("AFTER_RELEASE_HUMMOCK_CONTEXTS_ASYNC");
                         },
                         Some(LocalNotification::CompactionTaskNeedCancel(compact_task)) => {
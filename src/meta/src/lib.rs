@@ -47,6 +47,7 @@ mod model;
 mod rpc;
 pub mod storage;
 mod stream;
+mod telemetry;
 
 use std::time::Duration;
 
@@ -105,6 +106,11 @@ pub struct MetaNodeOpts {
     #[clap(long)]
     dashboard_ui_path: Option<String>,
 
+    /// Endpoint of the Prometheus server scraping the compute nodes, e.g.
+    /// `http://127.0.0.1:9500`. The dashboard queries the back-pressure of actors from it.
+    #[clap(long)]
+    prometheus_endpoint: Option<String>,
+
     /// No given `config_path` means to use default config.
     #[clap(long, default_value = "")]
     pub config_path: String,
@@ -150,6 +156,29 @@ pub struct MetaNodeOpts {
 
     #[clap(long, default_value = "10")]
     node_num_monitor_interval_sec: u64,
+
+    /// Maximum allowed interval in seconds without any progress reported for a compaction task,
+    /// after which the task is cancelled and its input SSTs become available for rescheduling.
+    #[clap(long, default_value = "60")]
+    compaction_task_max_heartbeat_interval_secs: u64,
+
+    /// Disable the anonymous telemetry report, which contains the cluster size, version and
+    /// feature usage counters. Use `risectl telemetry show` to inspect the report.
+    #[clap(long, env = "RW_TELEMETRY_DISABLED")]
+    disable_telemetry: bool,
+
+    /// The endpoint that the telemetry reports are sent to.
+    #[clap(
+        long,
+        env = "RW_TELEMETRY_REPORT_URL",
+        default_value = telemetry::DEFAULT_TELEMETRY_REPORT_URL
+    )]
+    telemetry_report_url: String,
+
+    /// The private key to encrypt the secrets created by `CREATE SECRET` in the meta store. It
+    /// must be kept unchanged, otherwise the existing secrets can't be decrypted.
+    #[clap(long, env = "RW_SECRET_STORE_PRIVATE_KEY")]
+    secret_store_private_key: Option<String>,
 }
 
 use std::future::Future;
@@ -202,6 +231,7 @@ pub fn start(opts: MetaNodeOpts) -> Pin<Box<dyn Future<Output = ()> + Send>> {
             prometheus_addr,
             dashboard_addr,
             ui_path: opts.dashboard_ui_path,
+            prometheus_endpoint: opts.prometheus_endpoint,
         };
         let (join_handle, _shutdown_send) = rpc_serve(
             add_info,
@@ -222,6 +252,11 @@ pub fn start(opts: MetaNodeOpts) -> Pin<Box<dyn Future<Output = ()> + Send>> {
                 enable_committed_sst_sanity_check: opts.enable_committed_sst_sanity_check,
                 periodic_compaction_interval_sec: opts.periodic_compaction_interval_sec,
                 node_num_monitor_interval_sec: opts.node_num_monitor_interval_sec,
+                compaction_task_max_heartbeat_interval_secs: opts
+                    .compaction_task_max_heartbeat_interval_secs,
+                telemetry_enabled: !opts.disable_telemetry,
+                telemetry_report_url: opts.telemetry_report_url,
+                secret_store_private_key: opts.secret_store_private_key,
             },
         )
         .await
@@ -16,9 +16,14 @@ use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use itertools::Itertools;
-use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table, View};
-
-use super::{DatabaseId, RelationId, SchemaId, SinkId, SourceId, ViewId};
+use risingwave_pb::catalog::{
+    Database, Function, Index, Schema, Secret, Sink, Source, Table, View,
+};
+use risingwave_pb::user::grant_privilege::Object;
+
+use super::{
+    DatabaseId, FunctionId, RelationId, SchemaId, SecretId, SinkId, SourceId, UserId, ViewId,
+};
 use crate::manager::{IndexId, MetaSrvEnv, TableId};
 use crate::model::MetadataModel;
 use crate::storage::MetaStore;
@@ -32,6 +37,8 @@ pub type Catalog = (
     Vec<Sink>,
     Vec<Index>,
     Vec<View>,
+    Vec<Function>,
+    Vec<Secret>,
 );
 
 type DatabaseKey = String;
@@ -56,6 +63,10 @@ pub struct DatabaseManager<S: MetaStore> {
     pub(super) tables: BTreeMap<TableId, Table>,
     /// Cached view information.
     pub(super) views: BTreeMap<ViewId, View>,
+    /// Cached function information.
+    pub(super) functions: BTreeMap<FunctionId, Function>,
+    /// Cached secret information, whose values are encrypted.
+    pub(super) secrets: BTreeMap<SecretId, Secret>,
 
     /// Relation refer count mapping.
     // TODO(zehua): avoid key conflicts after distinguishing table's and source's id generator.
@@ -68,6 +79,10 @@ pub struct DatabaseManager<S: MetaStore> {
     pub(super) in_progress_creation_streaming_job: HashSet<TableId>,
     // In-progress creating tables, including internal tables.
     pub(super) in_progress_creating_tables: HashMap<TableId, Table>,
+    // In-progress altering tables.
+    pub(super) in_progress_altering_tables: HashSet<TableId>,
+    // In-progress sources with the proposed columns being added.
+    pub(super) in_progress_altering_sources: HashSet<SourceId>,
 }
 
 impl<S> DatabaseManager<S>
@@ -82,6 +97,8 @@ where
         let tables = Table::list(env.meta_store()).await?;
         let indexes = Index::list(env.meta_store()).await?;
         let views = View::list(env.meta_store()).await?;
+        let functions = Function::list(env.meta_store()).await?;
+        let secrets = Secret::list(env.meta_store()).await?;
 
         let mut relation_ref_count = HashMap::new();
 
@@ -111,6 +128,12 @@ where
             }
             (view.id, view)
         }));
+        let functions = BTreeMap::from_iter(
+            functions
+                .into_iter()
+                .map(|function| (function.id, function)),
+        );
+        let secrets = BTreeMap::from_iter(secrets.into_iter().map(|secret| (secret.id, secret)));
 
         Ok(Self {
             env,
@@ -121,10 +144,14 @@ where
             views,
             tables,
             indexes,
+            functions,
+            secrets,
             relation_ref_count,
             in_progress_creation_tracker: HashSet::default(),
             in_progress_creation_streaming_job: HashSet::default(),
             in_progress_creating_tables: HashMap::default(),
+            in_progress_altering_tables: HashSet::default(),
+            in_progress_altering_sources: HashSet::default(),
         })
     }
 
@@ -137,6 +164,8 @@ where
             Sink::list(self.env.meta_store()).await?,
             Index::list(self.env.meta_store()).await?,
             View::list(self.env.meta_store()).await?,
+            Function::list(self.env.meta_store()).await?,
+            Secret::list(self.env.meta_store()).await?,
         ))
     }
 
@@ -176,6 +205,33 @@ where
         }
     }
 
+    /// Functions are identified by their names and argument types, so that they can be
+    /// overloaded.
+    pub fn check_function_duplicated(&self, function: &Function) -> MetaResult<()> {
+        if self.functions.values().any(|x| {
+            x.database_id == function.database_id
+                && x.schema_id == function.schema_id
+                && x.name.eq(&function.name)
+                && x.arg_types == function.arg_types
+        }) {
+            Err(MetaError::catalog_duplicated("function", &function.name))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn check_secret_duplicated(&self, secret: &Secret) -> MetaResult<()> {
+        if self.secrets.values().any(|x| {
+            x.database_id == secret.database_id
+                && x.schema_id == secret.schema_id
+                && x.name.eq(&secret.name)
+        }) {
+            Err(MetaError::catalog_duplicated("secret", &secret.name))
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn list_creating_tables(&self) -> Vec<Table> {
         self.in_progress_creating_tables
             .values()
@@ -195,6 +251,18 @@ where
             .collect_vec()
     }
 
+    /// Returns the owner of the object of a privilege, which always has all the privileges on it.
+    pub fn get_object_owner(&self, object: &Object) -> Option<UserId> {
+        match object {
+            Object::DatabaseId(id) => self.databases.get(id).map(|d| d.owner),
+            Object::SchemaId(id) => self.schemas.get(id).map(|s| s.owner),
+            Object::TableId(id) => self.tables.get(id).map(|t| t.owner),
+            Object::SourceId(id) => self.sources.get(id).map(|s| s.owner),
+            Object::ViewId(id) => self.views.get(id).map(|v| v.owner),
+            Object::AllTablesSchemaId(_) | Object::AllSourcesSchemaId(_) => None,
+        }
+    }
+
     pub fn list_stream_job_ids(&self) -> impl Iterator<Item = RelationId> + '_ {
         self.tables
             .keys()
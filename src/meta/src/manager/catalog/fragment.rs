@@ -85,6 +85,9 @@ pub struct ActorInfos {
 
     /// all reachable source actors
     pub source_actor_maps: HashMap<WorkerId, Vec<ActorId>>,
+
+    /// actor_id => (fragment_id, upstream actor_ids) of all reachable actors
+    pub actor_topology: HashMap<ActorId, (FragmentId, Vec<ActorId>)>,
 }
 
 pub struct FragmentVNodeInfo {
@@ -203,6 +206,28 @@ where
         commit_meta!(self, table_fragments)
     }
 
+    /// Updates the rate limit of the chain nodes in the fragments of `table_id` in place, which
+    /// may be in creation and updated by the barriers concurrently. Returns the actors containing
+    /// the chain nodes.
+    pub async fn update_backfill_rate_limit(
+        &self,
+        table_id: &TableId,
+        rows_per_second: u64,
+    ) -> MetaResult<HashSet<ActorId>> {
+        let map = &mut self.core.write().await.table_fragments;
+
+        let mut table_fragments = BTreeMapTransaction::new(map);
+        let mut table_fragment = table_fragments
+            .get_mut(*table_id)
+            .context(format!("table_fragment not exist: id={}", table_id))?;
+        let actor_ids = table_fragment.update_backfill_rate_limit(rows_per_second);
+        if !actor_ids.is_empty() {
+            commit_meta!(self, table_fragments)?;
+        }
+
+        Ok(actor_ids)
+    }
+
     /// Called after the barrier collection of `CreateMaterializedView` command, which updates the
     /// materialized view's state from `State::Initial` to `State::Creating`, updates the
     /// actors' state to `ActorState::Running`, besides also updates all dependent tables'
@@ -328,6 +353,7 @@ where
     ) -> ActorInfos {
         let mut actor_maps = HashMap::new();
         let mut source_actor_maps = HashMap::new();
+        let mut actor_topology = HashMap::new();
 
         let map = &self.core.read().await.table_fragments;
         for fragments in map.values() {
@@ -342,6 +368,15 @@ where
                 }
             }
 
+            for fragment in fragments.fragments.values() {
+                for actor in &fragment.actors {
+                    actor_topology.insert(
+                        actor.actor_id,
+                        (fragment.fragment_id, actor.upstream_actor_id.clone()),
+                    );
+                }
+            }
+
             let source_actors = fragments.worker_source_actor_states();
             for (worker_id, actor_states) in source_actors {
                 for (actor_id, actor_state) in actor_states {
@@ -358,6 +393,7 @@ where
         ActorInfos {
             actor_maps,
             source_actor_maps,
+            actor_topology,
         }
     }
 
@@ -14,6 +14,7 @@
 
 mod database;
 mod fragment;
+mod secret;
 mod user;
 
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -27,15 +28,22 @@ use itertools::Itertools;
 use risingwave_common::catalog::{
     valid_table_name, DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, DEFAULT_SUPER_USER,
     DEFAULT_SUPER_USER_FOR_PG, DEFAULT_SUPER_USER_FOR_PG_ID, DEFAULT_SUPER_USER_ID,
-    PG_CATALOG_SCHEMA_NAME,
+    INFORMATION_SCHEMA_SCHEMA_NAME, PG_CATALOG_SCHEMA_NAME, RW_CATALOG_SCHEMA_NAME,
 };
 use risingwave_common::{bail, ensure};
+use risingwave_pb::catalog::source::Info as SourceInfo;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
-use risingwave_pb::catalog::{Database, Index, Schema, Sink, Source, Table, View};
+use risingwave_pb::catalog::{
+    Database, Function, Index, Schema, Secret, Sink, Source, Table, TableStatistics, View,
+};
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
+use risingwave_pb::plan_common::ColumnCatalog;
+use risingwave_pb::stream_plan::stream_node::NodeBody;
+use risingwave_pb::stream_plan::{StreamActor, StreamNode};
 use risingwave_pb::user::grant_privilege::{ActionWithGrantOption, Object};
 use risingwave_pb::user::update_user_request::UpdateField;
 use risingwave_pb::user::{GrantPrivilege, UserInfo};
+use secret::SecretEncryptor;
 use tokio::sync::{Mutex, MutexGuard};
 use user::*;
 
@@ -52,6 +60,8 @@ pub type SinkId = u32;
 pub type RelationId = u32;
 pub type IndexId = u32;
 pub type ViewId = u32;
+pub type FunctionId = u32;
+pub type SecretId = u32;
 
 pub type UserId = u32;
 
@@ -172,7 +182,12 @@ where
         let mut schemas = BTreeMapTransaction::new(&mut database_core.schemas);
         databases.insert(database.id, database.clone());
         let mut schemas_added = vec![];
-        for schema_name in [DEFAULT_SCHEMA_NAME, PG_CATALOG_SCHEMA_NAME] {
+        for schema_name in [
+            DEFAULT_SCHEMA_NAME,
+            PG_CATALOG_SCHEMA_NAME,
+            RW_CATALOG_SCHEMA_NAME,
+            INFORMATION_SCHEMA_SCHEMA_NAME,
+        ] {
             let schema = Schema {
                 id: self
                     .env
@@ -219,6 +234,8 @@ where
         let mut tables = BTreeMapTransaction::new(&mut database_core.tables);
         let mut indexes = BTreeMapTransaction::new(&mut database_core.indexes);
         let mut views = BTreeMapTransaction::new(&mut database_core.views);
+        let mut functions = BTreeMapTransaction::new(&mut database_core.functions);
+        let mut secrets = BTreeMapTransaction::new(&mut database_core.secrets);
         let mut users = BTreeMapTransaction::new(&mut user_core.user_info);
 
         let database = databases.remove(database_id);
@@ -270,6 +287,20 @@ where
                 }
             }
 
+            let function_ids = functions.tree_ref().keys().copied().collect_vec();
+            for function_id in &function_ids {
+                if database_id == functions.get(function_id).unwrap().database_id {
+                    functions.remove(*function_id);
+                }
+            }
+
+            let secret_ids = secrets.tree_ref().keys().copied().collect_vec();
+            for secret_id in &secret_ids {
+                if database_id == secrets.get(secret_id).unwrap().database_id {
+                    secrets.remove(*secret_id);
+                }
+            }
+
             let mut objects = Vec::with_capacity(
                 1 + schemas_to_drop.len()
                     + tables_to_drop.len()
@@ -292,7 +323,10 @@ where
 
             let users_need_update = Self::update_user_privileges(&mut users, &objects);
 
-            commit_meta!(self, databases, schemas, sources, sinks, tables, indexes, views, users)?;
+            commit_meta!(
+                self, databases, schemas, sources, sinks, tables, indexes, views, functions,
+                secrets, users
+            )?;
 
             database_core.relation_ref_count.retain(|k, _| {
                 (!table_ids.contains(k)) && (!source_ids.contains(k) && (!view_ids.contains(k)))
@@ -365,6 +399,14 @@ where
                 .tables
                 .values()
                 .any(|t| t.database_id == schema.database_id && t.schema_id == schema_id)
+                || database_core
+                    .functions
+                    .values()
+                    .any(|f| f.database_id == schema.database_id && f.schema_id == schema_id)
+                || database_core
+                    .secrets
+                    .values()
+                    .any(|s| s.database_id == schema.database_id && s.schema_id == schema_id)
             {
                 bail!("schema is not empty!");
             }
@@ -456,6 +498,189 @@ where
         }
     }
 
+    pub async fn create_function(&self, function: &Function) -> MetaResult<NotificationVersion> {
+        let core = &mut *self.core.lock().await;
+        let database_core = &mut core.database;
+        database_core.ensure_database_id(function.database_id)?;
+        database_core.ensure_schema_id(function.schema_id)?;
+        database_core.check_function_duplicated(function)?;
+        #[cfg(not(test))]
+        core.user.ensure_user_id(function.owner)?;
+
+        let mut functions = BTreeMapTransaction::new(&mut database_core.functions);
+        functions.insert(function.id, function.clone());
+        commit_meta!(self, functions)?;
+
+        let version = self
+            .notify_frontend(Operation::Add, Info::Function(function.to_owned()))
+            .await;
+
+        Ok(version)
+    }
+
+    pub async fn drop_function(&self, function_id: FunctionId) -> MetaResult<NotificationVersion> {
+        let core = &mut *self.core.lock().await;
+        let database_core = &mut core.database;
+        let mut functions = BTreeMapTransaction::new(&mut database_core.functions);
+
+        let function = functions
+            .remove(function_id)
+            .ok_or_else(|| MetaError::catalog_not_found("function", function_id.to_string()))?;
+        commit_meta!(self, functions)?;
+
+        let version = self
+            .notify_frontend(Operation::Delete, Info::Function(function))
+            .await;
+
+        Ok(version)
+    }
+
+    fn secret_encryptor(&self) -> MetaResult<SecretEncryptor> {
+        let private_key = self
+            .env
+            .opts
+            .secret_store_private_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("`--secret-store-private-key` of meta is not set"))?;
+        Ok(SecretEncryptor::new(private_key))
+    }
+
+    /// Creates a secret, whose value is given in plaintext and encrypted before persisted.
+    pub async fn create_secret(&self, secret: &Secret) -> MetaResult<NotificationVersion> {
+        let encrypted = self.secret_encryptor()?.encrypt(&secret.value);
+
+        let core = &mut *self.core.lock().await;
+        let database_core = &mut core.database;
+        database_core.ensure_database_id(secret.database_id)?;
+        database_core.ensure_schema_id(secret.schema_id)?;
+        database_core.check_secret_duplicated(secret)?;
+        #[cfg(not(test))]
+        core.user.ensure_user_id(secret.owner)?;
+
+        let mut secrets = BTreeMapTransaction::new(&mut database_core.secrets);
+        secrets.insert(
+            secret.id,
+            Secret {
+                value: encrypted,
+                ..secret.clone()
+            },
+        );
+        commit_meta!(self, secrets)?;
+
+        let version = self
+            .notify_frontend(
+                Operation::Add,
+                Info::Secret(Secret {
+                    value: vec![],
+                    ..secret.clone()
+                }),
+            )
+            .await;
+
+        Ok(version)
+    }
+
+    pub async fn drop_secret(&self, secret_id: SecretId) -> MetaResult<NotificationVersion> {
+        let core = &mut *self.core.lock().await;
+        let database_core = &mut core.database;
+        if let Some(source) = database_core
+            .sources
+            .values()
+            .find(|source| source.secret_refs.values().contains(&secret_id))
+        {
+            bail!("secret is referred to by source {}", source.name);
+        }
+        if let Some(sink) = database_core
+            .sinks
+            .values()
+            .find(|sink| sink.secret_refs.values().contains(&secret_id))
+        {
+            bail!("secret is referred to by sink {}", sink.name);
+        }
+
+        let mut secrets = BTreeMapTransaction::new(&mut database_core.secrets);
+        let secret = secrets
+            .remove(secret_id)
+            .ok_or_else(|| MetaError::catalog_not_found("secret", secret_id.to_string()))?;
+        commit_meta!(self, secrets)?;
+
+        let version = self
+            .notify_frontend(
+                Operation::Delete,
+                Info::Secret(Secret {
+                    value: vec![],
+                    ..secret
+                }),
+            )
+            .await;
+
+        Ok(version)
+    }
+
+    /// Fills the properties referring to secrets with the decrypted values of the secrets.
+    fn fill_secrets_inner(
+        &self,
+        database_core: &DatabaseManager<S>,
+        properties: &mut HashMap<String, String>,
+        secret_refs: &HashMap<String, SecretId>,
+    ) -> MetaResult<()> {
+        if secret_refs.is_empty() {
+            return Ok(());
+        }
+        let encryptor = self.secret_encryptor()?;
+        for (key, secret_id) in secret_refs {
+            let secret = database_core
+                .secrets
+                .get(secret_id)
+                .ok_or_else(|| MetaError::catalog_not_found("secret", secret_id.to_string()))?;
+            let value = String::from_utf8(encryptor.decrypt(&secret.value)?)
+                .map_err(|_| anyhow!("secret {} is not valid UTF-8", secret.name))?;
+            properties.insert(key.clone(), value);
+        }
+        Ok(())
+    }
+
+    /// Fills the properties of a connector referring to secrets with their values. The values
+    /// only live in memory, and are never persisted or sent to the frontends.
+    pub async fn fill_secrets(
+        &self,
+        properties: &mut HashMap<String, String>,
+        secret_refs: &HashMap<String, SecretId>,
+    ) -> MetaResult<()> {
+        let core = self.core.lock().await;
+        self.fill_secrets_inner(&core.database, properties, secret_refs)
+    }
+
+    /// Fills the secrets of the source and sink nodes of the actors, right before the actors are
+    /// sent to the compute nodes.
+    pub async fn fill_actor_secrets(&self, actors: &mut [StreamActor]) -> MetaResult<()> {
+        let core = self.core.lock().await;
+        let mut nodes = actors
+            .iter_mut()
+            .filter_map(|actor| actor.nodes.as_mut())
+            .collect_vec();
+        while let Some(StreamNode {
+            node_body, input, ..
+        }) = nodes.pop()
+        {
+            match node_body {
+                Some(NodeBody::Source(source)) => self.fill_secrets_inner(
+                    &core.database,
+                    &mut source.properties,
+                    &source.secret_refs,
+                )?,
+                Some(NodeBody::Sink(sink)) => self.fill_secrets_inner(
+                    &core.database,
+                    &mut sink.properties,
+                    &sink.secret_refs,
+                )?,
+                _ => {}
+            }
+            nodes.extend(input.iter_mut());
+        }
+        Ok(())
+    }
+
     pub async fn start_create_stream_job_procedure(
         &self,
         stream_job: &StreamingJob,
@@ -742,10 +967,23 @@ where
                     .get(&index_table_id)
                     .cloned()
                 {
-                    Some(ref_count) => Err(MetaError::permission_denied(format!(
-                        "Fail to delete table `{}` because {} other relation(s) depend on it",
-                        table.name, ref_count
-                    ))),
+                    Some(ref_count) => {
+                        // The streaming scans with filters on the indexed table may be served by
+                        // the index, which makes the materialized views depend on it even if
+                        // they don't mention it.
+                        let dependents = tables
+                            .tree_ref()
+                            .values()
+                            .filter(|t| t.dependent_relations.contains(&index_table_id))
+                            .map(|t| format!("`{}`", t.name))
+                            .join(", ");
+                        Err(MetaError::permission_denied(format!(
+                            "Fail to delete index `{}` because {} other relation(s) depend on it: \
+                             {}. The materialized views with filters on the indexed table may \
+                             scan the index instead of the table",
+                            table.name, ref_count, dependents
+                        )))
+                    }
                     None => {
                         let dependent_relations = table.dependent_relations.clone();
 
@@ -1108,6 +1346,377 @@ where
         }
     }
 
+    pub async fn start_alter_table_procedure(
+        &self,
+        source: &Source,
+        table: &Table,
+    ) -> MetaResult<()> {
+        let core = &mut self.core.lock().await.database;
+        let (Some(original_source), Some(original_table)) =
+            (core.sources.get(&source.id), core.tables.get(&table.id)) else {
+            return Err(MetaError::catalog_not_found("table", table.name.clone()));
+        };
+        ensure!(
+            original_table.optional_associated_source_id
+                == Some(OptionalAssociatedSourceId::AssociatedSourceId(
+                    original_source.id
+                )),
+            "table {} is not associated with source {}",
+            table.id,
+            source.id
+        );
+        ensure!(
+            matches!(original_source.info, Some(SourceInfo::TableSource(_))),
+            "only tables can be altered"
+        );
+
+        // The request must be generated from the latest catalog of the table.
+        let original_version = original_table.version.as_ref().map(|v| v.version);
+        let version = table.version.as_ref().map(|v| v.version);
+        if original_version.is_none() || version != original_version.map(|v| v + 1) {
+            bail!("table version is stale, please retry");
+        }
+
+        // Columns can only be appended, and dropped columns are kept as hidden ones.
+        let column_ids = |columns: &[ColumnCatalog]| {
+            columns
+                .iter()
+                .map(|c| c.column_desc.as_ref().unwrap().column_id)
+                .collect_vec()
+        };
+        ensure!(
+            column_ids(&table.columns).starts_with(&column_ids(&original_table.columns))
+                && column_ids(&source.columns).starts_with(&column_ids(&original_source.columns)),
+            "existing columns of table {} can not be changed",
+            table.name
+        );
+        ensure!(
+            source.columns.len() == table.columns.len(),
+            "columns of source {} mismatch with the table",
+            source.name
+        );
+
+        let has_dropped_columns = original_table
+            .columns
+            .iter()
+            .zip_eq(&table.columns[..original_table.columns.len()])
+            .any(|(original, column)| !original.is_hidden && column.is_hidden);
+        if has_dropped_columns {
+            if let Some(ref_count) = core.relation_ref_count.get(&table.id) {
+                return Err(MetaError::permission_denied(format!(
+                    "Fail to drop columns of table `{}` because {} other relation(s) depend on it",
+                    table.name, ref_count
+                )));
+            }
+        }
+
+        if !core.in_progress_altering_tables.insert(table.id) {
+            bail!("table is in altering procedure");
+        }
+        Ok(())
+    }
+
+    /// Returns the materialized views among `mview_ids` which depend on the table only, excluding
+    /// the stale ones.
+    pub async fn get_dependent_mviews(
+        &self,
+        table_id: TableId,
+        mview_ids: &[TableId],
+    ) -> Vec<Table> {
+        let core = &self.core.lock().await.database;
+        mview_ids
+            .iter()
+            .filter_map(|id| core.tables.get(id))
+            .filter(|mview| {
+                mview.dependent_relations == [table_id]
+                    && !mview.schema_stale
+                    && !mview.is_index
+                    && mview.optional_associated_source_id.is_none()
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Commits the altered `source` and `table`, along with the materialized views on the table
+    /// changed by the schema change, which are skipped if dropped in the meantime.
+    pub async fn finish_alter_table_procedure(
+        &self,
+        source: &Source,
+        table: &Table,
+        mviews: &[Table],
+    ) -> MetaResult<NotificationVersion> {
+        let core = &mut self.core.lock().await.database;
+        let mut tables = BTreeMapTransaction::new(&mut core.tables);
+        let mut sources = BTreeMapTransaction::new(&mut core.sources);
+        if tables.contains_key(&table.id)
+            && sources.contains_key(&source.id)
+            && core.in_progress_altering_tables.remove(&table.id)
+        {
+            let mviews = mviews
+                .iter()
+                .filter(|mview| tables.contains_key(&mview.id))
+                .collect_vec();
+            sources.insert(source.id, source.clone());
+            tables.insert(table.id, table.clone());
+            for mview in &mviews {
+                tables.insert(mview.id, (*mview).clone());
+            }
+            commit_meta!(self, sources, tables)?;
+
+            self.notify_frontend(Operation::Update, Info::Source(source.to_owned()))
+                .await;
+            for mview in mviews {
+                self.notify_frontend(Operation::Update, Info::Table(mview.to_owned()))
+                    .await;
+            }
+            let version = self
+                .notify_frontend(Operation::Update, Info::Table(table.to_owned()))
+                .await;
+
+            Ok(version)
+        } else {
+            bail!("table not exist or not in altering procedure");
+        }
+    }
+
+    pub async fn cancel_alter_table_procedure(&self, table: &Table) {
+        let core = &mut self.core.lock().await.database;
+        core.in_progress_altering_tables.remove(&table.id);
+    }
+
+    /// Replaces the properties of the source after `ALTER SOURCE`. Other fields of the catalog
+    /// can not be changed.
+    pub async fn alter_source(&self, source: &Source) -> MetaResult<NotificationVersion> {
+        let core = &mut self.core.lock().await.database;
+        let Some(mut original_source) = core.sources.get(&source.id).cloned() else {
+            return Err(MetaError::catalog_not_found("source", source.name.clone()));
+        };
+        ensure!(
+            !matches!(original_source.info, Some(SourceInfo::TableSource(_))),
+            "source of table {} can not be altered",
+            original_source.name
+        );
+        original_source.properties = source.properties.clone();
+
+        let mut sources = BTreeMapTransaction::new(&mut core.sources);
+        sources.insert(original_source.id, original_source.clone());
+        commit_meta!(self, sources)?;
+
+        let version = self
+            .notify_frontend(Operation::Update, Info::Source(original_source))
+            .await;
+
+        Ok(version)
+    }
+
+    /// Proposes the columns added to the upstream schema to be added to the source, which is done
+    /// by the frontends notified. The columns whose names already exist are skipped. The sources
+    /// of the tables and the materialized sources can not evolve, as the columns of their tables
+    /// are fixed.
+    pub async fn propose_source_columns(
+        &self,
+        source_id: SourceId,
+        columns: Vec<ColumnCatalog>,
+    ) -> MetaResult<()> {
+        let core = &mut self.core.lock().await.database;
+        let Some(mut source) = core.sources.get(&source_id).cloned() else {
+            return Err(MetaError::catalog_id_not_found("source", source_id));
+        };
+        Self::check_source_columns_alterable(core, &source)?;
+        if core.in_progress_altering_sources.contains(&source_id) {
+            bail!("source is in altering procedure");
+        }
+
+        let name = |c: &ColumnCatalog| c.column_desc.as_ref().unwrap().name.clone();
+        let proposed_columns = columns
+            .into_iter()
+            .filter(|column| !source.columns.iter().any(|c| name(c) == name(column)))
+            .collect_vec();
+        if proposed_columns == source.proposed_columns {
+            return Ok(());
+        }
+        tracing::info!(
+            "proposing columns {:?} for source {} as its upstream schema evolves",
+            proposed_columns.iter().map(name).collect_vec(),
+            source.name
+        );
+        source.proposed_columns = proposed_columns;
+
+        let mut sources = BTreeMapTransaction::new(&mut core.sources);
+        sources.insert(source_id, source.clone());
+        commit_meta!(self, sources)?;
+
+        self.notify_frontend(Operation::Update, Info::Source(source))
+            .await;
+        Ok(())
+    }
+
+    fn check_source_columns_alterable(
+        core: &DatabaseManager<S>,
+        source: &Source,
+    ) -> MetaResult<()> {
+        ensure!(
+            !matches!(source.info, Some(SourceInfo::TableSource(_))),
+            "columns can not be added to the source of table {}",
+            source.name
+        );
+        ensure!(
+            !core.tables.values().any(|table| {
+                table.optional_associated_source_id
+                    == Some(OptionalAssociatedSourceId::AssociatedSourceId(source.id))
+            }),
+            "columns can not be added to materialized source {}",
+            source.name
+        );
+        Ok(())
+    }
+
+    /// Starts adding the proposed columns to the source. `source` must have the proposed columns
+    /// appended to the existing ones, while the other fields of it are ignored.
+    pub async fn start_alter_source_columns_procedure(&self, source: &Source) -> MetaResult<()> {
+        let core = &mut self.core.lock().await.database;
+        let Some(original_source) = core.sources.get(&source.id) else {
+            return Err(MetaError::catalog_not_found("source", source.name.clone()));
+        };
+        Self::check_source_columns_alterable(core, original_source)?;
+        let expected_columns = original_source
+            .columns
+            .iter()
+            .chain(&original_source.proposed_columns)
+            .cloned()
+            .collect_vec();
+        // The request must be generated from the latest proposal.
+        if original_source.proposed_columns.is_empty() || source.columns != expected_columns {
+            bail!("proposed columns of source {} are stale", source.name);
+        }
+
+        if !core.in_progress_altering_sources.insert(source.id) {
+            bail!("source is in altering procedure");
+        }
+        Ok(())
+    }
+
+    /// Commits the columns of `source` with the proposals cleared, along with the materialized
+    /// views on the source changed by the schema change, which are skipped if dropped in the
+    /// meantime.
+    pub async fn finish_alter_source_columns_procedure(
+        &self,
+        source: &Source,
+        mviews: &[Table],
+    ) -> MetaResult<NotificationVersion> {
+        let core = &mut self.core.lock().await.database;
+        let mut tables = BTreeMapTransaction::new(&mut core.tables);
+        let mut sources = BTreeMapTransaction::new(&mut core.sources);
+        if let Some(original_source) = sources.get(&source.id).cloned()
+            && core.in_progress_altering_sources.remove(&source.id)
+        {
+            let mviews = mviews
+                .iter()
+                .filter(|mview| tables.contains_key(&mview.id))
+                .collect_vec();
+            let source = Source {
+                columns: source.columns.clone(),
+                proposed_columns: vec![],
+                ..original_source
+            };
+            sources.insert(source.id, source.clone());
+            for mview in &mviews {
+                tables.insert(mview.id, (*mview).clone());
+            }
+            commit_meta!(self, sources, tables)?;
+
+            for mview in mviews {
+                self.notify_frontend(Operation::Update, Info::Table(mview.to_owned()))
+                    .await;
+            }
+            let version = self
+                .notify_frontend(Operation::Update, Info::Source(source))
+                .await;
+
+            Ok(version)
+        } else {
+            bail!("source not exist or not in altering procedure");
+        }
+    }
+
+    pub async fn cancel_alter_source_columns_procedure(&self, source: &Source) {
+        let core = &mut self.core.lock().await.database;
+        core.in_progress_altering_sources.remove(&source.id);
+    }
+
+    /// Replaces the statistics of the table collected by `ANALYZE`.
+    pub async fn update_table_statistics(
+        &self,
+        table_id: TableId,
+        statistics: TableStatistics,
+    ) -> MetaResult<NotificationVersion> {
+        let core = &mut self.core.lock().await.database;
+        if core.in_progress_altering_tables.contains(&table_id) {
+            bail!("table is in altering procedure");
+        }
+        let Some(mut table) = core.tables.get(&table_id).cloned() else {
+            return Err(MetaError::catalog_not_found("table", table_id.to_string()));
+        };
+        table.statistics = Some(statistics);
+
+        let mut tables = BTreeMapTransaction::new(&mut core.tables);
+        tables.insert(table_id, table.clone());
+        commit_meta!(self, tables)?;
+
+        let version = self
+            .notify_frontend(Operation::Update, Info::Table(table))
+            .await;
+
+        Ok(version)
+    }
+
+    /// Sets the comment on the table, or on one of its columns if `column_id` is given. An empty
+    /// comment removes it.
+    pub async fn comment_on(
+        &self,
+        table_id: TableId,
+        column_id: Option<i32>,
+        comment: String,
+    ) -> MetaResult<NotificationVersion> {
+        let core = &mut self.core.lock().await.database;
+        if core.in_progress_altering_tables.contains(&table_id) {
+            bail!("table is in altering procedure");
+        }
+        let Some(mut table) = core.tables.get(&table_id).cloned() else {
+            return Err(MetaError::catalog_not_found("table", table_id.to_string()));
+        };
+        match column_id {
+            Some(column_id) => {
+                if !table
+                    .columns
+                    .iter()
+                    .any(|c| c.column_desc.as_ref().unwrap().column_id == column_id)
+                {
+                    return Err(MetaError::catalog_not_found(
+                        "column",
+                        format!("{} of table {}", column_id, table_id),
+                    ));
+                }
+                if comment.is_empty() {
+                    table.column_descriptions.remove(&column_id);
+                } else {
+                    table.column_descriptions.insert(column_id, comment);
+                }
+            }
+            None => table.description = comment,
+        }
+
+        let mut tables = BTreeMapTransaction::new(&mut core.tables);
+        tables.insert(table_id, table.clone());
+        commit_meta!(self, tables)?;
+
+        let version = self
+            .notify_frontend(Operation::Update, Info::Table(table))
+            .await;
+
+        Ok(version)
+    }
+
     pub async fn start_create_index_procedure(
         &self,
         index: &Index,
@@ -1193,7 +1802,10 @@ where
         let database_core = &mut core.database;
         database_core.ensure_database_id(sink.database_id)?;
         database_core.ensure_schema_id(sink.schema_id)?;
-        database_core.ensure_table_id(sink.associated_table_id)?;
+        // A sink created from a query has no associated table.
+        if sink.associated_table_id != 0 {
+            database_core.ensure_table_id(sink.associated_table_id)?;
+        }
         let key = (sink.database_id, sink.schema_id, sink.name.clone());
         database_core.check_relation_name_duplicated(&key)?;
         #[cfg(not(test))]
@@ -1282,6 +1894,37 @@ where
         self.core.lock().await.database.list_sources()
     }
 
+    /// Returns the materialized view named `name` in the schema, which can be either created or in
+    /// creation.
+    pub async fn get_materialized_view_by_name(
+        &self,
+        database_id: DatabaseId,
+        schema_id: SchemaId,
+        name: &str,
+    ) -> MetaResult<Table> {
+        let core = &self.core.lock().await.database;
+        core.tables
+            .values()
+            .chain(core.in_progress_creating_tables.values())
+            .find(|table| {
+                table.database_id == database_id
+                    && table.schema_id == schema_id
+                    && table.name == name
+                    && !table.is_index
+                    && table.optional_associated_source_id.is_none()
+            })
+            .cloned()
+            .ok_or_else(|| MetaError::catalog_not_found("materialized view", name))
+    }
+
+    pub async fn get_source(&self, source_id: SourceId) -> MetaResult<Source> {
+        let core = &self.core.lock().await.database;
+        core.sources
+            .get(&source_id)
+            .cloned()
+            .ok_or_else(|| MetaError::catalog_not_found("source", source_id.to_string()))
+    }
+
     pub async fn list_source_ids(&self, schema_id: SchemaId) -> Vec<SourceId> {
         self.core.lock().await.database.list_source_ids(schema_id)
     }
@@ -1520,7 +2163,9 @@ where
         new_grant_privileges: &[GrantPrivilege],
         grantor: UserId,
     ) -> MetaResult<NotificationVersion> {
-        let core = &mut self.core.lock().await.user;
+        let core = &mut *self.core.lock().await;
+        let database_core = &core.database;
+        let core = &mut core.user;
         let mut users = BTreeMapTransaction::new(&mut core.user_info);
         let mut user_updated = Vec::with_capacity(user_ids.len());
         let grantor_info = users
@@ -1540,6 +2185,10 @@ where
             }
             if !grantor_info.is_super {
                 for new_grant_privilege in new_grant_privileges {
+                    let object = new_grant_privilege.object.as_ref().unwrap();
+                    if database_core.get_object_owner(object) == Some(grantor) {
+                        continue;
+                    }
                     if let Some(privilege) = grantor_info
                         .grant_privileges
                         .iter()
@@ -1637,7 +2286,9 @@ where
         revoke_grant_option: bool,
         cascade: bool,
     ) -> MetaResult<NotificationVersion> {
-        let core = &mut self.core.lock().await.user;
+        let core = &mut *self.core.lock().await;
+        let database_core = &core.database;
+        let core = &mut core.user;
         let mut users = BTreeMapTransaction::new(&mut core.user_info);
         let mut user_updated = HashMap::new();
         let mut users_info: VecDeque<UserInfo> = VecDeque::new();
@@ -1649,6 +2300,10 @@ where
         let same_user = granted_by == revoke_by.id;
         if !revoke_by.is_super {
             for privilege in revoke_grant_privileges {
+                let object = privilege.object.as_ref().unwrap();
+                if database_core.get_object_owner(object) == Some(revoke_by.id) {
+                    continue;
+                }
                 if let Some(user_privilege) = revoke_by
                     .grant_privileges
                     .iter()
@@ -0,0 +1,109 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::anyhow;
+use hkdf::Hkdf;
+use rand::RngCore;
+use sha2::Sha256;
+
+use crate::MetaResult;
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// The context of the key derived from the private key, which separates it from the keys derived
+/// for other purposes.
+const KEY_INFO: &[u8] = b"risingwave secret encryption";
+
+/// Encrypts the values of secrets before they are persisted in the meta store.
+///
+/// The values are encrypted and authenticated by AES-256-GCM with a random nonce, whose key is
+/// derived by HKDF-SHA256 from the private key given by `--secret-store-private-key`. The
+/// encrypted value is laid out as `nonce || ciphertext || tag`.
+pub struct SecretEncryptor {
+    cipher: Aes256Gcm,
+}
+
+impl SecretEncryptor {
+    pub fn new(private_key: &str) -> Self {
+        let mut key = [0; 32];
+        Hkdf::<Sha256>::new(None, private_key.as_bytes())
+            .expand(KEY_INFO, &mut key)
+            .expect("32 bytes is a valid length for HKDF-SHA256");
+        Self {
+            cipher: Aes256Gcm::new_from_slice(&key).expect("the key of AES-256 is 32 bytes"),
+        }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut nonce = [0; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .expect("the secret is too large to be encrypted");
+
+        let mut encrypted = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        encrypted.extend_from_slice(&nonce);
+        encrypted.extend_from_slice(&ciphertext);
+        encrypted
+    }
+
+    pub fn decrypt(&self, encrypted: &[u8]) -> MetaResult<Vec<u8>> {
+        if encrypted.len() < NONCE_LEN + TAG_LEN {
+            return Err(anyhow!("the encrypted secret is truncated").into());
+        }
+        let (nonce, ciphertext) = encrypted.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                anyhow!("failed to decrypt the secret, the private key may have been changed")
+            })?;
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let encryptor = SecretEncryptor::new("private key");
+        for plaintext in [&b""[..], b"password", &[7; 100]] {
+            let encrypted = encryptor.encrypt(plaintext);
+            assert_eq!(encrypted.len(), NONCE_LEN + plaintext.len() + TAG_LEN);
+            assert_eq!(encryptor.decrypt(&encrypted).unwrap(), plaintext);
+        }
+
+        // The nonce is random, so the same plaintext is encrypted differently.
+        assert_ne!(
+            encryptor.encrypt(b"password"),
+            encryptor.encrypt(b"password")
+        );
+
+        let mut encrypted = encryptor.encrypt(b"password");
+        encrypted[NONCE_LEN] ^= 1;
+        assert!(encryptor.decrypt(&encrypted).is_err());
+        assert!(encryptor.decrypt(&encrypted[..TAG_LEN]).is_err());
+
+        let encrypted = encryptor.encrypt(b"password");
+        assert!(SecretEncryptor::new("another key")
+            .decrypt(&encrypted)
+            .is_err());
+    }
+}
@@ -154,6 +154,7 @@ where
                     host: Some(host_address.clone()),
                     state: State::Starting as i32,
                     parallel_units,
+                    unschedulable: false,
                 };
 
                 let worker = Worker::from_protobuf(worker_node.clone());
@@ -221,6 +222,44 @@ where
         Ok(worker_type)
     }
 
+    /// Cordons or uncordons the given workers. No new actors or batch tasks will be scheduled to
+    /// an unschedulable worker, while its existing actors keep running until they are migrated
+    /// away, e.g. by rescheduling.
+    pub async fn update_schedulability(
+        &self,
+        worker_ids: &[WorkerId],
+        unschedulable: bool,
+    ) -> MetaResult<()> {
+        let mut core = self.core.write().await;
+        let mut workers = Vec::with_capacity(worker_ids.len());
+        for worker_id in worker_ids {
+            let worker = core
+                .get_worker_by_id(*worker_id)
+                .ok_or_else(|| MetaError::invalid_worker(*worker_id))?;
+            if worker.worker_type() != WorkerType::ComputeNode {
+                return Err(anyhow::anyhow!("worker {} is not a compute node", worker_id).into());
+            }
+            workers.push(worker);
+        }
+
+        for mut worker in workers {
+            if worker.worker_node.unschedulable == unschedulable {
+                continue;
+            }
+            worker.worker_node.unschedulable = unschedulable;
+            worker.insert(self.env.meta_store()).await?;
+            core.update_worker_node(worker.clone());
+
+            // Notify frontends to stop or resume scheduling batch tasks to the worker.
+            self.env
+                .notification_manager()
+                .notify_frontend(Operation::Update, Info::Node(worker.worker_node))
+                .await;
+        }
+
+        Ok(())
+    }
+
     /// Invoked when it receives a heartbeat from a worker node.
     pub async fn heartbeat(
         &self,
@@ -339,6 +378,13 @@ where
         core.list_active_parallel_units()
     }
 
+    /// Lists the parallel units of running and uncordoned compute nodes, to which new actors can be
+    /// scheduled.
+    pub async fn list_schedulable_parallel_units(&self) -> Vec<ParallelUnit> {
+        let core = self.core.read().await;
+        core.list_schedulable_parallel_units()
+    }
+
     pub async fn get_active_parallel_unit_count(&self) -> usize {
         let core = self.core.read().await;
         core.get_active_parallel_unit_count()
@@ -467,6 +513,21 @@ impl ClusterManagerCore {
             .collect()
     }
 
+    fn list_schedulable_parallel_units(&self) -> Vec<ParallelUnit> {
+        let schedulable_workers: HashSet<_> = self
+            .list_worker_node(WorkerType::ComputeNode, Some(State::Running))
+            .into_iter()
+            .filter(|w| !w.unschedulable)
+            .map(|w| w.id)
+            .collect();
+
+        self.parallel_units
+            .iter()
+            .filter(|p| schedulable_workers.contains(&p.worker_node_id))
+            .cloned()
+            .collect()
+    }
+
     fn count_worker_node(&self) -> HashMap<WorkerType, u64> {
         const MONITORED_WORKER_TYPES: [WorkerType; 3] = [
             WorkerType::Compactor,
@@ -528,7 +589,7 @@ mod tests {
         // Since no worker is active, the parallel unit count should be 0.
         assert_cluster_manager(&cluster_manager, 0).await;
 
-        for worker_node in worker_nodes {
+        for worker_node in &worker_nodes {
             cluster_manager
                 .activate_worker_node(worker_node.get_host().unwrap().clone())
                 .await
@@ -544,6 +605,33 @@ mod tests {
         let parallel_count = fake_parallelism * worker_count;
         assert_cluster_manager(&cluster_manager, parallel_count).await;
 
+        // Cordoned workers are still active, but not schedulable.
+        let cordoned_worker_id = worker_nodes[0].id;
+        cluster_manager
+            .update_schedulability(&[cordoned_worker_id], true)
+            .await
+            .unwrap();
+        assert_cluster_manager(&cluster_manager, parallel_count).await;
+        let schedulable_parallel_units = cluster_manager.list_schedulable_parallel_units().await;
+        assert_eq!(
+            schedulable_parallel_units.len(),
+            parallel_count - fake_parallelism
+        );
+        assert!(schedulable_parallel_units
+            .iter()
+            .all(|pu| pu.worker_node_id != cordoned_worker_id));
+        cluster_manager
+            .update_schedulability(&[cordoned_worker_id], false)
+            .await
+            .unwrap();
+        assert_eq!(
+            cluster_manager
+                .list_schedulable_parallel_units()
+                .await
+                .len(),
+            parallel_count
+        );
+
         let worker_to_delete_count = 4usize;
         for i in 0..worker_to_delete_count {
             let fake_host_address = HostAddress {
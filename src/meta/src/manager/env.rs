@@ -95,6 +95,15 @@ pub struct MetaOpts {
     pub periodic_compaction_interval_sec: u64,
     /// Interval of reporting the number of nodes in the cluster.
     pub node_num_monitor_interval_sec: u64,
+    /// A compaction task is cancelled if it shows no progress within this period.
+    pub compaction_task_max_heartbeat_interval_secs: u64,
+    /// Whether to periodically send the anonymous telemetry report.
+    pub telemetry_enabled: bool,
+    /// The endpoint that the telemetry reports are sent to.
+    pub telemetry_report_url: String,
+    /// The private key to encrypt the secrets created by `CREATE SECRET`. Secrets can't be
+    /// created or used if it's not set.
+    pub secret_store_private_key: Option<String>,
 }
 
 impl Default for MetaOpts {
@@ -113,6 +122,10 @@ impl Default for MetaOpts {
             enable_committed_sst_sanity_check: false,
             periodic_compaction_interval_sec: 60,
             node_num_monitor_interval_sec: 10,
+            compaction_task_max_heartbeat_interval_secs: 60,
+            telemetry_enabled: false,
+            telemetry_report_url: "".to_string(),
+            secret_store_private_key: None,
         }
     }
 }
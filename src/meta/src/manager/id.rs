@@ -140,6 +140,8 @@ pub mod IdCategory {
     pub const Sink: IdCategoryType = 13;
     pub const Index: IdCategoryType = 14;
     pub const CompactionGroup: IdCategoryType = 15;
+    pub const Function: IdCategoryType = 16;
+    pub const Secret: IdCategoryType = 17;
 }
 
 pub type IdGeneratorManagerRef<S> = Arc<IdGeneratorManager<S>>;
@@ -161,6 +163,8 @@ pub struct IdGeneratorManager<S> {
     hummock_compaction_task: Arc<StoredIdGenerator<S>>,
     parallel_unit: Arc<StoredIdGenerator<S>>,
     compaction_group: Arc<StoredIdGenerator<S>>,
+    function: Arc<StoredIdGenerator<S>>,
+    secret: Arc<StoredIdGenerator<S>>,
 }
 
 impl<S> IdGeneratorManager<S>
@@ -218,6 +222,8 @@ where
                 )
                 .await,
             ),
+            function: Arc::new(StoredIdGenerator::new(meta_store.clone(), "function", None).await),
+            secret: Arc::new(StoredIdGenerator::new(meta_store, "secret", None).await),
         }
     }
 
@@ -237,6 +243,8 @@ where
             IdCategory::ParallelUnit => &self.parallel_unit,
             IdCategory::HummockCompactionTask => &self.hummock_compaction_task,
             IdCategory::CompactionGroup => &self.compaction_group,
+            IdCategory::Function => &self.function,
+            IdCategory::Secret => &self.secret,
             _ => unreachable!(),
         }
     }
@@ -20,6 +20,7 @@ mod id;
 mod idle;
 mod notification;
 mod streaming_job;
+mod system_param;
 pub use background_deleter::*;
 pub use catalog::*;
 pub use cluster::*;
@@ -28,3 +29,4 @@ pub use id::*;
 pub use idle::*;
 pub use notification::*;
 pub use streaming_job::*;
+pub use system_param::*;
@@ -89,6 +89,7 @@ impl StreamingJob {
         match self {
             Self::MaterializedView(table) => table.definition.clone(),
             Self::MaterializedSource(_, table) => table.definition.clone(),
+            Self::Sink(sink) => sink.definition.clone(),
             _ => "".to_owned(),
         }
     }
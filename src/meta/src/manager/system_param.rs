@@ -0,0 +1,169 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use risingwave_pb::meta::subscribe_response::{Info, Operation};
+use risingwave_pb::meta::SystemParams;
+use tokio::sync::{watch, RwLock};
+
+use crate::manager::MetaSrvEnv;
+use crate::model::{MetadataModel, SYSTEM_PARAMS_KEY};
+use crate::storage::MetaStore;
+use crate::MetaResult;
+
+pub type SystemParamManagerRef<S> = Arc<SystemParamManager<S>>;
+
+/// `SystemParamManager` keeps the system parameters changed at runtime by `ALTER SYSTEM SET`. The
+/// parameters are persisted in meta store, and pushed to compute nodes and compactors through
+/// notification. Components in meta can watch the changes by [`Self::watch_params`].
+pub struct SystemParamManager<S: MetaStore> {
+    env: MetaSrvEnv<S>,
+    params: RwLock<SystemParams>,
+    params_tx: watch::Sender<SystemParams>,
+}
+
+impl<S> SystemParamManager<S>
+where
+    S: MetaStore,
+{
+    pub async fn new(env: MetaSrvEnv<S>) -> MetaResult<Self> {
+        let params = SystemParams::select(env.meta_store(), &SYSTEM_PARAMS_KEY)
+            .await?
+            .unwrap_or_default();
+        let (params_tx, _) = watch::channel(params.clone());
+        Ok(Self {
+            env,
+            params: RwLock::new(params),
+            params_tx,
+        })
+    }
+
+    pub async fn get_params(&self) -> SystemParams {
+        self.params.read().await.clone()
+    }
+
+    /// Subscribes to the changes of system parameters. The receiver holds the current parameters
+    /// initially.
+    pub fn watch_params(&self) -> watch::Receiver<SystemParams> {
+        self.params_tx.subscribe()
+    }
+
+    pub async fn set_param(&self, param: &str, value: &str) -> MetaResult<()> {
+        let mut params = self.params.write().await;
+        let mut new_params = params.clone();
+        set_system_param(&mut new_params, param, value)?;
+        new_params.insert(self.env.meta_store()).await?;
+        *params = new_params.clone();
+        self.params_tx.send_replace(new_params.clone());
+
+        self.env
+            .notification_manager()
+            .notify_hummock(Operation::Update, Info::SystemParams(new_params.clone()))
+            .await;
+        self.env
+            .notification_manager()
+            .notify_compactor(Operation::Update, Info::SystemParams(new_params))
+            .await;
+        tracing::info!("system param {} is set to {}", param, value);
+        Ok(())
+    }
+}
+
+/// The sstables are expected to be read as a whole by compaction, so they must not be too large.
+const MAX_SSTABLE_SIZE_MB: u32 = 4096;
+
+fn parse_positive<T: FromStr + Default + PartialEq>(param: &str, value: &str) -> MetaResult<T> {
+    match value.parse::<T>() {
+        Ok(v) if v != T::default() => Ok(v),
+        _ => Err(anyhow!("invalid value {} for system param {}", value, param).into()),
+    }
+}
+
+fn set_system_param(params: &mut SystemParams, param: &str, value: &str) -> MetaResult<()> {
+    match param {
+        "barrier_interval_ms" => params.barrier_interval_ms = parse_positive(param, value)?,
+        "checkpoint_frequency" => params.checkpoint_frequency = parse_positive(param, value)?,
+        "block_cache_capacity_mb" => params.block_cache_capacity_mb = parse_positive(param, value)?,
+        "meta_cache_capacity_mb" => params.meta_cache_capacity_mb = parse_positive(param, value)?,
+        "sstable_size_mb" => {
+            params.sstable_size_mb = parse_positive(param, value)?;
+            if params.sstable_size_mb > MAX_SSTABLE_SIZE_MB {
+                return Err(anyhow!(
+                    "system param {} must not exceed {}",
+                    param,
+                    MAX_SSTABLE_SIZE_MB
+                )
+                .into());
+            }
+        }
+        "max_sub_compaction" => params.max_sub_compaction = parse_positive(param, value)?,
+        _ => return Err(anyhow!("unrecognized system param {}", param).into()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemStore;
+
+    #[tokio::test]
+    async fn test_set_param() {
+        let env = MetaSrvEnv::for_test().await;
+        let manager = SystemParamManager::<MemStore>::new(env.clone())
+            .await
+            .unwrap();
+        let mut rx = manager.watch_params();
+
+        manager
+            .set_param("checkpoint_frequency", "5")
+            .await
+            .unwrap();
+        rx.changed().await.unwrap();
+        assert_eq!(rx.borrow().checkpoint_frequency, 5);
+
+        manager
+            .set_param("checkpoint_frequency", "0")
+            .await
+            .unwrap_err();
+        manager
+            .set_param("barrier_interval_ms", "abc")
+            .await
+            .unwrap_err();
+        manager.set_param("unknown", "1").await.unwrap_err();
+        assert_eq!(manager.get_params().await.checkpoint_frequency, 5);
+
+        manager.set_param("sstable_size_mb", "128").await.unwrap();
+        manager.set_param("max_sub_compaction", "8").await.unwrap();
+        manager
+            .set_param("sstable_size_mb", "100000")
+            .await
+            .unwrap_err();
+        manager
+            .set_param("max_sub_compaction", "-1")
+            .await
+            .unwrap_err();
+        let params = manager.get_params().await;
+        assert_eq!(params.sstable_size_mb, 128);
+        assert_eq!(params.max_sub_compaction, 8);
+
+        // The params are recovered from meta store.
+        let manager = SystemParamManager::<MemStore>::new(env).await.unwrap();
+        assert_eq!(manager.get_params().await.checkpoint_frequency, 5);
+        assert_eq!(manager.get_params().await.barrier_interval_ms, 0);
+    }
+}
@@ -17,7 +17,9 @@ mod catalog;
 mod cluster;
 mod error;
 mod notification;
+mod sink;
 mod stream;
+mod system_param;
 mod user;
 
 use std::collections::btree_map::{Entry, VacantEntry};
@@ -32,7 +34,9 @@ pub use cluster::*;
 pub use error::*;
 pub use notification::*;
 use prost::Message;
+pub use sink::*;
 pub use stream::*;
+pub use system_param::*;
 pub use user::*;
 
 use crate::storage::{MetaStore, MetaStoreError, Transaction};
@@ -0,0 +1,50 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_pb::meta::SinkPreparedCommit;
+
+use crate::model::{MetadataModel, MetadataModelResult};
+
+/// Column family name for the prepared commits of the two-phase sinks.
+const SINK_PREPARED_COMMIT_CF_NAME: &str = "cf/sink_prepared_commit";
+
+impl MetadataModel for SinkPreparedCommit {
+    type KeyType = String;
+    type ProstType = Self;
+
+    fn cf_name() -> String {
+        SINK_PREPARED_COMMIT_CF_NAME.to_string()
+    }
+
+    fn to_protobuf(&self) -> Self::ProstType {
+        self.clone()
+    }
+
+    fn from_protobuf(prost: Self::ProstType) -> Self {
+        prost
+    }
+
+    fn key(&self) -> MetadataModelResult<Self::KeyType> {
+        Ok(sink_prepared_commit_key(
+            self.sink_id,
+            self.actor_id,
+            self.epoch,
+        ))
+    }
+}
+
+/// The key of the commit prepared by the actor of the sink at the epoch.
+pub fn sink_prepared_commit_key(sink_id: u32, actor_id: u32, epoch: u64) -> String {
+    format!("{}-{}-{}", sink_id, actor_id, epoch)
+}
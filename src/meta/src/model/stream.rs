@@ -19,12 +19,17 @@ use risingwave_common::catalog::TableId;
 use risingwave_common::types::ParallelUnitId;
 use risingwave_common::util::is_stream_source;
 use risingwave_connector::source::SplitImpl;
+use risingwave_pb::catalog::{Source, Table};
 use risingwave_pb::common::{Buffer, ParallelUnit, ParallelUnitMapping};
 use risingwave_pb::meta::table_fragments::actor_status::ActorState;
 use risingwave_pb::meta::table_fragments::{ActorStatus, Fragment, State};
 use risingwave_pb::meta::TableFragments as ProstTableFragments;
+use risingwave_pb::plan_common::{ColumnCatalog, Field};
 use risingwave_pb::stream_plan::stream_node::NodeBody;
-use risingwave_pb::stream_plan::{FragmentType, SourceNode, StreamActor, StreamNode};
+use risingwave_pb::stream_plan::{
+    ChainNode, ChainType, FragmentType, MergeNode, MviewSchemaChange, SourceNode, StreamActor,
+    StreamFragmentGraph, StreamNode,
+};
 
 use super::{ActorId, FragmentId};
 use crate::manager::{SourceId, WorkerId};
@@ -54,6 +59,10 @@ pub struct TableFragments {
 
     /// The splits of actors
     pub(crate) actor_splits: HashMap<ActorId, Vec<SplitImpl>>,
+
+    /// The version of the stream plan, bumped each time the fragments are updated in place by a
+    /// schema change.
+    plan_version: u64,
 }
 
 impl MetadataModel for TableFragments {
@@ -71,6 +80,7 @@ impl MetadataModel for TableFragments {
             fragments: self.fragments.clone().into_iter().collect(),
             actor_status: self.actor_status.clone().into_iter().collect(),
             actor_splits: build_actor_connector_splits(&self.actor_splits),
+            plan_version: self.plan_version,
         }
     }
 
@@ -81,6 +91,7 @@ impl MetadataModel for TableFragments {
             fragments: prost.fragments.into_iter().collect(),
             actor_status: prost.actor_status.into_iter().collect(),
             actor_splits: build_actor_split_impls(&prost.actor_splits),
+            plan_version: prost.plan_version,
         }
     }
 
@@ -98,6 +109,7 @@ impl TableFragments {
             fragments,
             actor_status: BTreeMap::default(),
             actor_splits: HashMap::default(),
+            plan_version: 0,
         }
     }
 
@@ -124,6 +136,11 @@ impl TableFragments {
         self.state
     }
 
+    /// Returns the version of the stream plan.
+    pub fn plan_version(&self) -> u64 {
+        self.plan_version
+    }
+
     /// Returns whether the table fragments is in `Created` state.
     pub fn is_created(&self) -> bool {
         self.state == State::Created
@@ -524,4 +541,502 @@ impl TableFragments {
             .values()
             .flat_map(|f| f.state_table_ids.clone())
     }
+
+    /// Update the columns of the table source and the materialized table after `ALTER TABLE`,
+    /// so that the actors built from these fragments later (e.g. on recovery) have the new
+    /// schema. All nodes of a table pass through the full set of columns, so their output fields
+    /// are updated as well.
+    pub fn update_table_schema(&mut self, source: &Source, table: &Table) {
+        fn update_stream_node(
+            stream_node: &mut StreamNode,
+            source: &Source,
+            table: &Table,
+            fields: &[Field],
+        ) {
+            match stream_node.node_body.as_mut().unwrap() {
+                NodeBody::Source(node) if node.source_id == source.id => {
+                    node.columns = source.columns.clone();
+                }
+                NodeBody::Materialize(node) if node.table_id == table.id => {
+                    let node_table = node.table.as_mut().unwrap();
+                    node_table.columns = table.columns.clone();
+                    node_table.value_indices = (0..table.columns.len() as i32).collect();
+                    node_table.version = table.version.clone();
+                }
+                NodeBody::Merge(node) => node.fields = fields.to_vec(),
+                _ => {}
+            }
+            stream_node.fields = fields.to_vec();
+            for input in &mut stream_node.input {
+                update_stream_node(input, source, table, fields);
+            }
+        }
+
+        let fields = table
+            .columns
+            .iter()
+            .map(|c| {
+                let column_desc = c.column_desc.as_ref().unwrap();
+                Field {
+                    data_type: column_desc.column_type.clone(),
+                    name: column_desc.name.clone(),
+                }
+            })
+            .collect_vec();
+        for fragment in self.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                update_stream_node(actor.nodes.as_mut().unwrap(), source, table, &fields);
+            }
+        }
+        self.plan_version += 1;
+    }
+
+    /// Replace the plan of the materialized view `mview`, defined with `SELECT *` on a table or a
+    /// source, with `fragment_graph` re-planned by the frontend after `ALTER TABLE ... ADD COLUMN`
+    /// or the columns proposed for the source are added. The running actors are kept, so the new
+    /// plan must have the same shape as the existing one, which is only changed by passing the
+    /// columns appended to the upstream through to the materialize node. The columns of
+    /// `new_mview` must be appended to the existing ones as well. Returns the change to be applied
+    /// to the running actors, or `None` if the view can not be updated.
+    pub fn replace_mview_plan(
+        &mut self,
+        mview: &Table,
+        new_mview: &Table,
+        fragment_graph: &StreamFragmentGraph,
+    ) -> Option<MviewSchemaChange> {
+        /// Takes the node body and the fields from `new`, except for the states and the
+        /// connections of the running actor kept in `old`.
+        fn replace_stream_node(
+            old: &StreamNode,
+            new: &StreamNode,
+            new_mview: &Table,
+        ) -> Option<StreamNode> {
+            if old.input.len() != new.input.len() {
+                return None;
+            }
+            let node_body = match (old.node_body.as_ref()?, new.node_body.as_ref()?) {
+                (NodeBody::Materialize(old_node), NodeBody::Materialize(_)) => {
+                    let mut node = old_node.clone();
+                    let node_table = node.table.as_mut()?;
+                    node_table.columns = new_mview.columns.clone();
+                    node_table.value_indices = new_mview.value_indices.clone();
+                    NodeBody::Materialize(node)
+                }
+                (NodeBody::Filter(_), NodeBody::Filter(new_node)) => {
+                    NodeBody::Filter(new_node.clone())
+                }
+                (NodeBody::Chain(old_node), NodeBody::Chain(new_node))
+                    if old_node.table_id == new_node.table_id
+                        && old_node.upstream_only == new_node.upstream_only =>
+                {
+                    NodeBody::Chain(ChainNode {
+                        upstream_fields: new_node.upstream_fields.clone(),
+                        upstream_column_indices: new_node.upstream_column_indices.clone(),
+                        ..old_node.clone()
+                    })
+                }
+                // The exchanges of the new plan are not resolved to merges yet.
+                (NodeBody::Merge(old_node), NodeBody::Merge(_) | NodeBody::Exchange(_)) => {
+                    NodeBody::Merge(MergeNode {
+                        fields: new.fields.clone(),
+                        ..old_node.clone()
+                    })
+                }
+                // The states of the splits and the resolved secrets are kept.
+                (NodeBody::Source(old_node), NodeBody::Source(new_node))
+                    if old_node.source_id == new_node.source_id
+                        && old_node.row_id_index == new_node.row_id_index
+                        && old_node.pk_column_ids == new_node.pk_column_ids =>
+                {
+                    NodeBody::Source(SourceNode {
+                        columns: new_node.columns.clone(),
+                        ..old_node.clone()
+                    })
+                }
+                (NodeBody::BatchPlan(old_node), NodeBody::BatchPlan(new_node)) => {
+                    let mut node = old_node.clone();
+                    let table_desc = node.table_desc.as_mut()?;
+                    let new_table_desc = new_node.table_desc.as_ref()?;
+                    table_desc.columns = new_table_desc.columns.clone();
+                    table_desc.value_indices = new_table_desc.value_indices.clone();
+                    node.column_ids = new_node.column_ids.clone();
+                    NodeBody::BatchPlan(node)
+                }
+                _ => return None,
+            };
+            let input = old
+                .input
+                .iter()
+                .zip_eq(&new.input)
+                .map(|(old, new)| replace_stream_node(old, new, new_mview))
+                .collect::<Option<_>>()?;
+            Some(StreamNode {
+                node_body: Some(node_body),
+                fields: new.fields.clone(),
+                input,
+                ..old.clone()
+            })
+        }
+
+        fn find_chain(stream_node: &StreamNode) -> Option<&ChainNode> {
+            match stream_node.node_body.as_ref().unwrap() {
+                NodeBody::Chain(node) => Some(node),
+                _ => stream_node.input.iter().find_map(find_chain),
+            }
+        }
+
+        fn has_source(stream_node: &StreamNode) -> bool {
+            matches!(stream_node.node_body.as_ref().unwrap(), NodeBody::Source(_))
+                || stream_node.input.iter().any(has_source)
+        }
+
+        // A view on a table has a single fragment, and a view on a source has a source fragment
+        // besides, so the fragments are paired by their types.
+        if !self.is_created()
+            || self.fragments.len() != fragment_graph.fragments.len()
+            || !self
+                .fragments
+                .values()
+                .map(|f| f.fragment_type)
+                .all_unique()
+        {
+            return None;
+        }
+        let column_key = |c: &ColumnCatalog| {
+            let column_desc = c.column_desc.as_ref().unwrap();
+            (
+                column_desc.column_id,
+                column_desc.name.clone(),
+                column_desc.column_type.clone(),
+                c.is_hidden,
+            )
+        };
+        let columns = mview.columns.iter().map(column_key).collect_vec();
+        let new_columns = new_mview.columns.iter().map(column_key).collect_vec();
+        if new_columns.len() <= columns.len()
+            || !new_columns.starts_with(&columns)
+            || new_mview.pk != mview.pk
+            || new_mview.distribution_key != mview.distribution_key
+        {
+            return None;
+        }
+
+        let is_mview_root = |fragment: &Fragment| {
+            fragment.actors.first().map_or(false, |actor| {
+                matches!(
+                    actor.nodes.as_ref().unwrap().node_body.as_ref().unwrap(),
+                    NodeBody::Materialize(node) if node.table_id == mview.id
+                )
+            })
+        };
+        if !self.fragments.values().any(is_mview_root) {
+            return None;
+        }
+
+        // All actors of a fragment have the same plan, except for the upstreams of the merge
+        // nodes, which are kept.
+        let mut new_nodes = HashMap::new();
+        for new_fragment in fragment_graph.fragments.values() {
+            let new_root = new_fragment.node.as_ref()?;
+            let fragment = self
+                .fragments
+                .values()
+                .find(|f| f.fragment_type == new_fragment.fragment_type)?;
+            let nodes = fragment
+                .actors
+                .iter()
+                .map(|actor| {
+                    replace_stream_node(actor.nodes.as_ref().unwrap(), new_root, new_mview)
+                })
+                .collect::<Option<Vec<_>>>()?;
+            new_nodes.insert(fragment.fragment_id, nodes);
+        }
+        if new_nodes.len() != self.fragments.len() {
+            return None;
+        }
+
+        let mut change = MviewSchemaChange {
+            table_id: mview.id,
+            columns: new_mview.columns.clone(),
+            ..Default::default()
+        };
+        for fragment in self.fragments.values() {
+            let nodes = new_nodes[&fragment.fragment_id].first()?;
+            let actor_ids = fragment.actors.iter().map(|a| a.actor_id);
+            if let Some(chain) = find_chain(nodes) {
+                change.chain_actor_ids.extend(actor_ids);
+                change.upstream_column_indices = chain.upstream_column_indices.clone();
+            } else if has_source(nodes) {
+                change.source_actor_ids.extend(actor_ids);
+            }
+        }
+        // The new columns are passed through from either the chain or the source.
+        if change.chain_actor_ids.is_empty() == change.source_actor_ids.is_empty() {
+            return None;
+        }
+
+        for fragment in self.fragments.values_mut() {
+            let nodes = new_nodes.remove(&fragment.fragment_id).unwrap();
+            for (actor, nodes) in fragment.actors.iter_mut().zip_eq(nodes) {
+                actor.nodes = Some(nodes);
+            }
+        }
+        self.plan_version += 1;
+
+        Some(change)
+    }
+
+    /// Update the properties of the source nodes after `ALTER SOURCE`. Returns whether any node
+    /// of the fragments reads from the source.
+    pub fn update_source_properties(&mut self, source: &Source) -> bool {
+        fn update_stream_node(stream_node: &mut StreamNode, source: &Source) -> bool {
+            let mut updated = false;
+            if let NodeBody::Source(node) = stream_node.node_body.as_mut().unwrap()
+                && node.source_id == source.id
+            {
+                node.properties = source.properties.clone();
+                updated = true;
+            }
+            for input in &mut stream_node.input {
+                updated |= update_stream_node(input, source);
+            }
+            updated
+        }
+
+        let mut updated = false;
+        for fragment in self.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                updated |= update_stream_node(actor.nodes.as_mut().unwrap(), source);
+            }
+        }
+        updated
+    }
+
+    /// Update the rate limit of the chain nodes after `ALTER MATERIALIZED VIEW`. Returns the
+    /// actors containing the chain nodes. The chain nodes of `ChainType::Chain` are skipped, whose
+    /// upstream is blocked until the snapshot is consumed, so they are never throttled.
+    pub fn update_backfill_rate_limit(&mut self, rows_per_second: u64) -> HashSet<ActorId> {
+        // The same as how the chain executors are built on the compute nodes.
+        fn is_throttled(node: &ChainNode) -> bool {
+            match node.chain_type() {
+                _ if node.upstream_only => false,
+                ChainType::Unspecified => !node.disable_rearrange,
+                ChainType::Rearrange | ChainType::Backfill => true,
+                ChainType::Chain => false,
+            }
+        }
+
+        fn update_stream_node(stream_node: &mut StreamNode, rows_per_second: u64) -> bool {
+            let mut updated = false;
+            if let NodeBody::Chain(node) = stream_node.node_body.as_mut().unwrap() {
+                if is_throttled(node) {
+                    node.rate_limit = rows_per_second;
+                    updated = true;
+                }
+            }
+            for input in &mut stream_node.input {
+                updated |= update_stream_node(input, rows_per_second);
+            }
+            updated
+        }
+
+        let mut actor_ids = HashSet::new();
+        for fragment in self.fragments.values_mut() {
+            for actor in &mut fragment.actors {
+                if update_stream_node(actor.nodes.as_mut().unwrap(), rows_per_second) {
+                    actor_ids.insert(actor.actor_id);
+                }
+            }
+        }
+        actor_ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::data::data_type::TypeName;
+    use risingwave_pb::data::DataType;
+    use risingwave_pb::plan_common::ColumnDesc;
+    use risingwave_pb::stream_plan::stream_fragment_graph::StreamFragment;
+    use risingwave_pb::stream_plan::{ExchangeNode, MaterializeNode};
+
+    use super::*;
+
+    fn column(column_id: i32, name: &str) -> ColumnCatalog {
+        ColumnCatalog {
+            column_desc: Some(ColumnDesc {
+                column_type: Some(DataType {
+                    type_name: TypeName::Int64 as i32,
+                    ..Default::default()
+                }),
+                column_id,
+                name: name.to_string(),
+                ..Default::default()
+            }),
+            is_hidden: false,
+        }
+    }
+
+    fn fields(columns: &[ColumnCatalog]) -> Vec<Field> {
+        columns
+            .iter()
+            .map(|c| {
+                let column_desc = c.column_desc.as_ref().unwrap();
+                Field {
+                    data_type: column_desc.column_type.clone(),
+                    name: column_desc.name.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn source_node(source_id: u32, columns: &[ColumnCatalog]) -> StreamNode {
+        StreamNode {
+            node_body: Some(NodeBody::Source(SourceNode {
+                source_id,
+                columns: columns.to_vec(),
+                pk_column_ids: vec![0],
+                ..Default::default()
+            })),
+            fields: fields(columns),
+            ..Default::default()
+        }
+    }
+
+    fn mview_node(mview: &Table, input: NodeBody) -> StreamNode {
+        let fields = fields(&mview.columns);
+        StreamNode {
+            node_body: Some(NodeBody::Materialize(MaterializeNode {
+                table_id: mview.id,
+                table: Some(mview.clone()),
+                ..Default::default()
+            })),
+            fields: fields.clone(),
+            input: vec![StreamNode {
+                node_body: Some(input),
+                fields,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn fragment(
+        fragment_id: FragmentId,
+        fragment_type: FragmentType,
+        actor_ids: &[ActorId],
+        nodes: StreamNode,
+    ) -> Fragment {
+        Fragment {
+            fragment_id,
+            fragment_type: fragment_type as i32,
+            actors: actor_ids
+                .iter()
+                .map(|&actor_id| StreamActor {
+                    actor_id,
+                    fragment_id,
+                    nodes: Some(nodes.clone()),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn fragment_graph(source_id: u32, new_mview: &Table) -> StreamFragmentGraph {
+        StreamFragmentGraph {
+            fragments: HashMap::from([
+                (
+                    0,
+                    StreamFragment {
+                        fragment_id: 0,
+                        node: Some(source_node(source_id, &new_mview.columns)),
+                        fragment_type: FragmentType::Source as i32,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    1,
+                    StreamFragment {
+                        fragment_id: 1,
+                        node: Some(mview_node(
+                            new_mview,
+                            NodeBody::Exchange(ExchangeNode::default()),
+                        )),
+                        fragment_type: FragmentType::Others as i32,
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_replace_mview_plan_on_source() {
+        let columns = vec![column(0, "_row_id"), column(1, "v1")];
+        let new_columns = [columns.clone(), vec![column(2, "v2")]].concat();
+        let mview = Table {
+            id: 10,
+            columns: columns.clone(),
+            ..Default::default()
+        };
+        let new_mview = Table {
+            columns: new_columns.clone(),
+            ..mview.clone()
+        };
+
+        let merge = NodeBody::Merge(MergeNode {
+            upstream_actor_id: vec![1, 2],
+            upstream_fragment_id: 1,
+            ..Default::default()
+        });
+        let mut table_fragments = TableFragments::new(
+            TableId::new(10),
+            BTreeMap::from([
+                (
+                    1,
+                    fragment(1, FragmentType::Source, &[1, 2], source_node(1, &columns)),
+                ),
+                (
+                    2,
+                    fragment(2, FragmentType::Others, &[3, 4], mview_node(&mview, merge)),
+                ),
+            ]),
+        );
+        table_fragments.set_state(State::Created);
+
+        // The plan reading from another source can not replace the existing one.
+        assert!(table_fragments
+            .clone()
+            .replace_mview_plan(&mview, &new_mview, &fragment_graph(2, &new_mview))
+            .is_none());
+
+        let change = table_fragments
+            .replace_mview_plan(&mview, &new_mview, &fragment_graph(1, &new_mview))
+            .unwrap();
+        assert_eq!(change.table_id, 10);
+        assert_eq!(change.source_actor_ids, [1, 2]);
+        assert!(change.chain_actor_ids.is_empty());
+        assert_eq!(change.columns, new_columns);
+        assert_eq!(table_fragments.plan_version(), 1);
+
+        // The source actors read the new columns, while the merges keep their upstreams.
+        for actor in &table_fragments.fragments[&1].actors {
+            let NodeBody::Source(node) = actor.nodes.as_ref().unwrap().node_body.as_ref().unwrap()
+            else {
+                panic!("expect a source node");
+            };
+            assert_eq!(node.source_id, 1);
+            assert_eq!(node.columns, new_columns);
+        }
+        for actor in &table_fragments.fragments[&2].actors {
+            let nodes = actor.nodes.as_ref().unwrap();
+            let NodeBody::Merge(node) = nodes.input[0].node_body.as_ref().unwrap() else {
+                panic!("expect a merge node");
+            };
+            assert_eq!(node.upstream_actor_id, [1, 2]);
+            assert_eq!(node.fields, fields(&new_columns));
+        }
+    }
 }
@@ -15,10 +15,11 @@
 use std::sync::atomic::AtomicU64;
 
 use prometheus::{
-    exponential_buckets, histogram_opts, register_histogram_vec_with_registry,
-    register_histogram_with_registry, register_int_counter_vec_with_registry,
-    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Histogram,
-    HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Registry,
+    exponential_buckets, histogram_opts, register_gauge_vec_with_registry,
+    register_histogram_vec_with_registry, register_histogram_with_registry,
+    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, GaugeVec, Histogram, HistogramVec, IntCounterVec, IntGauge,
+    IntGaugeVec, Registry,
 };
 
 pub struct MetaMetrics {
@@ -39,6 +40,13 @@ pub struct MetaMetrics {
     pub all_barrier_nums: IntGauge,
     /// The number of in-flight barriers
     pub in_flight_barrier_nums: IntGauge,
+    /// The max time of the last barrier waiting for the upstreams of the actors in each fragment
+    pub fragment_barrier_queued_latency: GaugeVec,
+    /// The max time of the last barrier being processed by the actors in each fragment
+    pub fragment_barrier_processing_latency: GaugeVec,
+    /// The time of the last barrier being processed by the slowest actors, only if it is slow to
+    /// be collected
+    pub slow_actor_barrier_processing_latency: GaugeVec,
 
     /// Max committed epoch
     pub max_committed_epoch: IntGauge,
@@ -120,6 +128,27 @@ impl MetaMetrics {
             registry
         )
         .unwrap();
+        let fragment_barrier_queued_latency = register_gauge_vec_with_registry!(
+            "meta_fragment_barrier_queued_duration_seconds",
+            "max time of the last barrier waiting for the upstreams of the actors in the fragment",
+            &["fragment_id"],
+            registry
+        )
+        .unwrap();
+        let fragment_barrier_processing_latency = register_gauge_vec_with_registry!(
+            "meta_fragment_barrier_processing_duration_seconds",
+            "max time of the last barrier being processed by the actors in the fragment",
+            &["fragment_id"],
+            registry
+        )
+        .unwrap();
+        let slow_actor_barrier_processing_latency = register_gauge_vec_with_registry!(
+            "meta_slow_actor_barrier_processing_duration_seconds",
+            "time of the last barrier being processed by the slowest actors if it is slow",
+            &["actor_id", "fragment_id", "worker_id"],
+            registry
+        )
+        .unwrap();
 
         let max_committed_epoch = register_int_gauge_with_registry!(
             "storage_max_committed_epoch",
@@ -229,6 +258,9 @@ impl MetaMetrics {
             barrier_send_latency,
             all_barrier_nums,
             in_flight_barrier_nums,
+            fragment_barrier_queued_latency,
+            fragment_barrier_processing_latency,
+            slow_actor_barrier_processing_latency,
 
             max_committed_epoch,
             safe_epoch,
@@ -28,7 +28,10 @@ use risingwave_pb::meta::cluster_service_server::ClusterServiceServer;
 use risingwave_pb::meta::heartbeat_service_server::HeartbeatServiceServer;
 use risingwave_pb::meta::notification_service_server::NotificationServiceServer;
 use risingwave_pb::meta::scale_service_server::ScaleServiceServer;
+use risingwave_pb::meta::sink_coordination_service_server::SinkCoordinationServiceServer;
 use risingwave_pb::meta::stream_manager_service_server::StreamManagerServiceServer;
+use risingwave_pb::meta::system_params_service_server::SystemParamsServiceServer;
+use risingwave_pb::meta::telemetry_service_server::TelemetryServiceServer;
 use risingwave_pb::meta::{MetaLeaderInfo, MetaLeaseInfo};
 use risingwave_pb::user::user_service_server::UserServiceServer;
 use tokio::sync::oneshot::Sender;
@@ -44,17 +47,21 @@ use crate::hummock::compaction_group::manager::CompactionGroupManager;
 use crate::hummock::{CompactionScheduler, HummockManager};
 use crate::manager::{
     CatalogManager, ClusterManager, FragmentManager, IdleManager, MetaOpts, MetaSrvEnv,
-    StreamingJobBackgroundDeleter,
+    StreamingJobBackgroundDeleter, SystemParamManager,
 };
 use crate::rpc::metrics::MetaMetrics;
 use crate::rpc::service::cluster_service::ClusterServiceImpl;
 use crate::rpc::service::heartbeat_service::HeartbeatServiceImpl;
 use crate::rpc::service::hummock_service::HummockServiceImpl;
+use crate::rpc::service::sink_coordination_service::SinkCoordinationServiceImpl;
 use crate::rpc::service::stream_service::StreamServiceImpl;
+use crate::rpc::service::system_params_service::SystemParamsServiceImpl;
+use crate::rpc::service::telemetry_service::TelemetryServiceImpl;
 use crate::rpc::service::user_service::UserServiceImpl;
 use crate::rpc::{META_CF_NAME, META_LEADER_KEY, META_LEASE_KEY};
 use crate::storage::{EtcdMetaStore, MemStore, MetaStore, MetaStoreError, Transaction};
-use crate::stream::{GlobalStreamManager, SourceManager};
+use crate::stream::{GlobalStreamManager, SinkCoordinator, SourceManager};
+use crate::telemetry::{TelemetryReporter, TELEMETRY_REPORT_INTERVAL};
 use crate::{hummock, MetaResult};
 
 #[derive(Debug)]
@@ -73,6 +80,9 @@ pub struct AddressInfo {
     pub prometheus_addr: Option<SocketAddr>,
     pub dashboard_addr: Option<SocketAddr>,
     pub ui_path: Option<String>,
+    /// The Prometheus server scraping the cluster, from which the dashboard queries the streaming
+    /// metrics.
+    pub prometheus_endpoint: Option<String>,
 }
 
 impl Default for AddressInfo {
@@ -83,6 +93,7 @@ impl Default for AddressInfo {
             prometheus_addr: None,
             dashboard_addr: None,
             ui_path: None,
+            prometheus_endpoint: None,
         }
     }
 }
@@ -314,9 +325,12 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
     let registry = meta_metrics.registry();
     monitor_process(registry).unwrap();
     let compactor_manager = Arc::new(
-        hummock::CompactorManager::with_meta(env.clone(), max_heartbeat_interval.as_secs())
-            .await
-            .unwrap(),
+        hummock::CompactorManager::with_meta(
+            env.clone(),
+            env.opts.compaction_task_max_heartbeat_interval_secs,
+        )
+        .await
+        .unwrap(),
     );
 
     let cluster_manager = Arc::new(
@@ -342,6 +356,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
             dashboard_addr,
             cluster_manager: cluster_manager.clone(),
             fragment_manager: fragment_manager.clone(),
+            prometheus_endpoint: address_info.prometheus_endpoint.clone(),
             meta_store: env.meta_store_ref(),
         };
         // TODO: join dashboard service back to local thread.
@@ -349,6 +364,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
     }
 
     let catalog_manager = Arc::new(CatalogManager::new(env.clone()).await.unwrap());
+    let system_param_manager = Arc::new(SystemParamManager::new(env.clone()).await.unwrap());
 
     let (barrier_scheduler, scheduled_barriers) =
         BarrierScheduler::new_pair(hummock_manager.clone(), env.opts.checkpoint_frequency);
@@ -363,6 +379,8 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         .unwrap(),
     );
 
+    let sink_coordinator = Arc::new(SinkCoordinator::new(env.clone()).await.unwrap());
+
     let barrier_manager = Arc::new(GlobalBarrierManager::new(
         scheduled_barriers,
         env.clone(),
@@ -371,6 +389,8 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         fragment_manager.clone(),
         hummock_manager.clone(),
         source_manager.clone(),
+        sink_coordinator.clone(),
+        system_param_manager.clone(),
         meta_metrics.clone(),
     ));
 
@@ -387,6 +407,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
             fragment_manager.clone(),
             barrier_scheduler.clone(),
             cluster_manager.clone(),
+            catalog_manager.clone(),
             source_manager.clone(),
             compaction_group_manager.clone(),
         )
@@ -424,6 +445,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         source_manager.clone(),
         cluster_manager.clone(),
         fragment_manager.clone(),
+        barrier_manager.clone(),
         table_background_deleter,
     );
 
@@ -454,11 +476,20 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
     let notification_manager = env.notification_manager_ref();
     let notification_srv = NotificationServiceImpl::new(
         env.clone(),
-        catalog_manager,
+        catalog_manager.clone(),
         cluster_manager.clone(),
         hummock_manager.clone(),
         fragment_manager.clone(),
+        system_param_manager.clone(),
     );
+    let system_params_srv = SystemParamsServiceImpl::new(system_param_manager);
+    let telemetry_reporter = Arc::new(TelemetryReporter::new(
+        env.clone(),
+        cluster_manager.clone(),
+        catalog_manager,
+    ));
+    let telemetry_srv = TelemetryServiceImpl::new(telemetry_reporter.clone());
+    let sink_coordination_srv = SinkCoordinationServiceImpl::new(sink_coordinator);
     let health_srv = HealthServiceImpl::new();
 
     if let Some(prometheus_addr) = address_info.prometheus_addr {
@@ -493,6 +524,16 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
     );
     sub_tasks.push(HummockManager::start_compaction_heartbeat(hummock_manager).await);
     sub_tasks.push((lease_handle, lease_shutdown));
+    #[cfg(not(madsim))]
+    if env.opts.telemetry_enabled {
+        sub_tasks.push(
+            TelemetryReporter::start_telemetry_reporting(
+                telemetry_reporter,
+                TELEMETRY_REPORT_INTERVAL,
+            )
+            .await,
+        );
+    }
     sub_tasks.push((deleter_handle, deleter_shutdown));
     if cfg!(not(test)) {
         sub_tasks.push(
@@ -531,6 +572,9 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
             .add_service(DdlServiceServer::new(ddl_srv))
             .add_service(UserServiceServer::new(user_srv))
             .add_service(ScaleServiceServer::new(scale_srv))
+            .add_service(SystemParamsServiceServer::new(system_params_srv))
+            .add_service(TelemetryServiceServer::new(telemetry_srv))
+            .add_service(SinkCoordinationServiceServer::new(sink_coordination_srv))
             .add_service(HealthServer::new(health_srv))
             .serve(address_info.listen_addr)
             .await
@@ -16,7 +16,8 @@ use risingwave_pb::meta::cluster_service_server::ClusterService;
 use risingwave_pb::meta::{
     ActivateWorkerNodeRequest, ActivateWorkerNodeResponse, AddWorkerNodeRequest,
     AddWorkerNodeResponse, DeleteWorkerNodeRequest, DeleteWorkerNodeResponse, ListAllNodesRequest,
-    ListAllNodesResponse,
+    ListAllNodesResponse, UpdateWorkerNodeSchedulabilityRequest,
+    UpdateWorkerNodeSchedulabilityResponse,
 };
 use tonic::{Request, Response, Status};
 
@@ -100,4 +101,17 @@ where
             nodes: node_list,
         }))
     }
+
+    async fn update_worker_node_schedulability(
+        &self,
+        request: Request<UpdateWorkerNodeSchedulabilityRequest>,
+    ) -> Result<Response<UpdateWorkerNodeSchedulabilityResponse>, Status> {
+        let req = request.into_inner();
+        self.cluster_manager
+            .update_schedulability(&req.worker_ids, req.unschedulable)
+            .await?;
+        Ok(Response::new(UpdateWorkerNodeSchedulabilityResponse {
+            status: None,
+        }))
+    }
 }
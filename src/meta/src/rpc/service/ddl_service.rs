@@ -12,19 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
+use itertools::Itertools;
 use risingwave_common::catalog::CatalogVersion;
+use risingwave_connector::source::{fill_postgres_cdc_source_id, SourceOffsetTarget};
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::*;
 use risingwave_pb::common::worker_node::State;
 use risingwave_pb::common::WorkerType;
+use risingwave_pb::ddl_service::comment_on_request::OptionalColumnId;
 use risingwave_pb::ddl_service::ddl_service_server::DdlService;
+use risingwave_pb::ddl_service::reset_source_offsets_request::Target;
 use risingwave_pb::ddl_service::*;
 use risingwave_pb::stream_plan::stream_node::NodeBody;
 use risingwave_pb::stream_plan::{StreamFragmentGraph, StreamNode};
 use tonic::{Request, Response, Status};
 
+use crate::barrier::BarrierManagerRef;
 use crate::manager::{
     CatalogManagerRef, ClusterManagerRef, FragmentManagerRef, IdCategory, IdCategoryType,
     MetaSrvEnv, NotificationVersion, SourceId, StreamingJob, StreamingJobBackgroundDeleterRef,
@@ -46,6 +51,7 @@ pub struct DdlServiceImpl<S: MetaStore> {
     source_manager: SourceManagerRef<S>,
     cluster_manager: ClusterManagerRef<S>,
     fragment_manager: FragmentManagerRef<S>,
+    barrier_manager: BarrierManagerRef<S>,
     table_background_deleter: StreamingJobBackgroundDeleterRef,
 }
 
@@ -61,6 +67,7 @@ where
         source_manager: SourceManagerRef<S>,
         cluster_manager: ClusterManagerRef<S>,
         fragment_manager: FragmentManagerRef<S>,
+        barrier_manager: BarrierManagerRef<S>,
         table_background_deleter: StreamingJobBackgroundDeleterRef,
     ) -> Self {
         Self {
@@ -70,6 +77,7 @@ where
             source_manager,
             cluster_manager,
             fragment_manager,
+            barrier_manager,
             table_background_deleter,
         }
     }
@@ -153,6 +161,7 @@ where
 
         let id = self.gen_unique_id::<{ IdCategory::Table }>().await?;
         source.id = id;
+        fill_postgres_cdc_source_id(&mut source.properties, id);
 
         self.catalog_manager
             .start_create_source_procedure(&source)
@@ -406,6 +415,216 @@ where
         }))
     }
 
+    async fn alter_table(
+        &self,
+        request: Request<AlterTableRequest>,
+    ) -> Result<Response<AlterTableResponse>, Status> {
+        let request = request.into_inner();
+        let source = request.source.unwrap();
+        let table = request.table.unwrap();
+
+        let (version, stale_mview_ids) = self
+            .alter_table_inner(
+                source,
+                table,
+                &request.select_star_mview_ids,
+                request.mview_plans,
+            )
+            .await?;
+
+        Ok(Response::new(AlterTableResponse {
+            status: None,
+            version,
+            stale_mview_ids,
+        }))
+    }
+
+    async fn alter_source(
+        &self,
+        request: Request<AlterSourceRequest>,
+    ) -> Result<Response<AlterSourceResponse>, Status> {
+        let request = request.into_inner();
+        let source = request.source.unwrap();
+
+        let original_source = self.catalog_manager.get_source(source.id).await?;
+        if source.columns != original_source.columns {
+            let (version, stale_mview_ids) = self
+                .alter_source_columns_inner(
+                    source,
+                    &request.select_star_mview_ids,
+                    request.mview_plans,
+                )
+                .await?;
+            return Ok(Response::new(AlterSourceResponse {
+                status: None,
+                version,
+                stale_mview_ids,
+            }));
+        }
+
+        // Like `alter_table`, the running actors are changed before the new catalog is visible.
+        self.stream_manager.alter_source(&source).await?;
+        let version = self.catalog_manager.alter_source(&source).await?;
+
+        Ok(Response::new(AlterSourceResponse {
+            status: None,
+            version,
+            stale_mview_ids: vec![],
+        }))
+    }
+
+    async fn reset_source_offsets(
+        &self,
+        request: Request<ResetSourceOffsetsRequest>,
+    ) -> Result<Response<ResetSourceOffsetsResponse>, Status> {
+        let request = request.into_inner();
+        let target = match request.get_target()? {
+            Target::Earliest => SourceOffsetTarget::Earliest,
+            Target::Latest => SourceOffsetTarget::Latest,
+            Target::Timestamp => SourceOffsetTarget::Timestamp(request.timestamp_millis),
+            Target::Unspecified => {
+                return Err(Status::invalid_argument(
+                    "target of the offsets is unspecified",
+                ))
+            }
+        };
+
+        let source = self.catalog_manager.get_source(request.source_id).await?;
+        if !matches!(source.info, Some(source::Info::StreamSource(_))) {
+            return Err(Status::invalid_argument(format!(
+                "the offsets of {} can not be reset",
+                source.name
+            )));
+        }
+        self.source_manager
+            .reset_source_offsets(&source, target)
+            .await?;
+
+        Ok(Response::new(ResetSourceOffsetsResponse { status: None }))
+    }
+
+    async fn alter_backfill_rate_limit(
+        &self,
+        request: Request<AlterBackfillRateLimitRequest>,
+    ) -> Result<Response<AlterBackfillRateLimitResponse>, Status> {
+        let request = request.into_inner();
+        let table = self
+            .catalog_manager
+            .get_materialized_view_by_name(request.database_id, request.schema_id, &request.name)
+            .await?;
+        self.stream_manager
+            .alter_backfill_rate_limit(table.id.into(), request.rows_per_second)
+            .await?;
+
+        Ok(Response::new(AlterBackfillRateLimitResponse {
+            status: None,
+        }))
+    }
+
+    async fn update_table_statistics(
+        &self,
+        request: Request<UpdateTableStatisticsRequest>,
+    ) -> Result<Response<UpdateTableStatisticsResponse>, Status> {
+        let request = request.into_inner();
+        let statistics = request.get_statistics()?.clone();
+
+        let version = self
+            .catalog_manager
+            .update_table_statistics(request.table_id, statistics)
+            .await?;
+
+        Ok(Response::new(UpdateTableStatisticsResponse {
+            status: None,
+            version,
+        }))
+    }
+
+    async fn comment_on(
+        &self,
+        request: Request<CommentOnRequest>,
+    ) -> Result<Response<CommentOnResponse>, Status> {
+        let request = request.into_inner();
+        let column_id = request.optional_column_id.map(|id| match id {
+            OptionalColumnId::ColumnId(id) => id,
+        });
+
+        let version = self
+            .catalog_manager
+            .comment_on(request.table_id, column_id, request.comment)
+            .await?;
+
+        Ok(Response::new(CommentOnResponse {
+            status: None,
+            version,
+        }))
+    }
+
+    async fn create_function(
+        &self,
+        request: Request<CreateFunctionRequest>,
+    ) -> Result<Response<CreateFunctionResponse>, Status> {
+        let req = request.into_inner();
+        let id = self.gen_unique_id::<{ IdCategory::Function }>().await?;
+        let mut function = req.get_function()?.clone();
+        function.id = id;
+        let version = self.catalog_manager.create_function(&function).await?;
+
+        Ok(Response::new(CreateFunctionResponse {
+            status: None,
+            function_id: id,
+            version,
+        }))
+    }
+
+    async fn drop_function(
+        &self,
+        request: Request<DropFunctionRequest>,
+    ) -> Result<Response<DropFunctionResponse>, Status> {
+        let req = request.into_inner();
+        let version = self
+            .catalog_manager
+            .drop_function(req.get_function_id())
+            .await?;
+
+        Ok(Response::new(DropFunctionResponse {
+            status: None,
+            version,
+        }))
+    }
+
+    async fn create_secret(
+        &self,
+        request: Request<CreateSecretRequest>,
+    ) -> Result<Response<CreateSecretResponse>, Status> {
+        let req = request.into_inner();
+        let id = self.gen_unique_id::<{ IdCategory::Secret }>().await?;
+        let mut secret = req.get_secret()?.clone();
+        secret.id = id;
+        let version = self.catalog_manager.create_secret(&secret).await?;
+
+        Ok(Response::new(CreateSecretResponse {
+            status: None,
+            secret_id: id,
+            version,
+        }))
+    }
+
+    async fn drop_secret(
+        &self,
+        request: Request<DropSecretRequest>,
+    ) -> Result<Response<DropSecretResponse>, Status> {
+        let req = request.into_inner();
+        let version = self
+            .catalog_manager
+            .drop_secret(req.get_secret_id())
+            .await?;
+
+        Ok(Response::new(DropSecretResponse {
+            status: None,
+            version,
+        }))
+    }
+
     async fn risectl_list_state_tables(
         &self,
         _request: Request<RisectlListStateTablesRequest>,
@@ -414,6 +633,44 @@ where
         let tables = Table::list(self.env.meta_store()).await?;
         Ok(Response::new(RisectlListStateTablesResponse { tables }))
     }
+
+    async fn get_ddl_progress(
+        &self,
+        _request: Request<GetDdlProgressRequest>,
+    ) -> Result<Response<GetDdlProgressResponse>, Status> {
+        let mut progress = self.barrier_manager.get_ddl_progress().await;
+        for (table_id, definition) in self.stream_manager.creating_job_definitions() {
+            // Jobs whose actors are still being built are not tracked by the barrier manager yet.
+            progress
+                .entry(table_id.table_id)
+                .or_insert_with(|| DdlProgress {
+                    id: table_id.table_id as u64,
+                    statement: String::new(),
+                    progress: "0.00%".to_owned(),
+                })
+                .statement = definition;
+        }
+        let ddl_progress = progress.into_values().sorted_by_key(|p| p.id).collect_vec();
+        Ok(Response::new(GetDdlProgressResponse { ddl_progress }))
+    }
+
+    async fn cancel_creating_jobs(
+        &self,
+        request: Request<CancelCreatingJobsRequest>,
+    ) -> Result<Response<CancelCreatingJobsResponse>, Status> {
+        use risingwave_common::catalog::TableId;
+
+        let req = request.into_inner();
+        let table_ids = req.job_ids.into_iter().map(TableId::new).collect_vec();
+        let cancelled = self.stream_manager.cancel_creating_jobs(&table_ids);
+        if let Some(table_id) = table_ids.iter().find(|id| !cancelled.contains(id)) {
+            return Err(Status::not_found(format!(
+                "creating streaming job {} not found",
+                table_id
+            )));
+        }
+        Ok(Response::new(CancelCreatingJobsResponse { status: None }))
+    }
 }
 
 impl<S> DdlServiceImpl<S>
@@ -511,6 +768,9 @@ where
         {
             actor_graph_builder.fill_mview_id(table);
         }
+        if let StreamingJob::Sink(sink) = stream_job {
+            actor_graph_builder.fill_sink_id(sink.id);
+        }
 
         let graph = actor_graph_builder
             .generate_graph(self.env.id_gen_manager_ref(), &mut ctx)
@@ -630,6 +890,7 @@ where
         // Generate source id.
         let source_id = self.gen_unique_id::<{ IdCategory::Table }>().await?; // TODO: use source category
         source.id = source_id;
+        fill_postgres_cdc_source_id(&mut source.properties, source_id);
 
         // Fill in the correct source id for stream node.
         fn fill_source_id(stream_node: &mut StreamNode, source_id: u32) -> usize {
@@ -637,6 +898,7 @@ where
             if let NodeBody::Source(source_node) = stream_node.node_body.as_mut().unwrap() {
                 // TODO: refactor using source id.
                 source_node.source_id = source_id;
+                fill_postgres_cdc_source_id(&mut source_node.properties, source_id);
                 source_count += 1;
             }
             for input in &mut stream_node.input {
@@ -711,6 +973,133 @@ where
         Ok(version)
     }
 
+    /// Returns the catalog version, and the materialized views among `select_star_mview_ids`
+    /// flagged as stale as they are not updated with the new columns by `mview_plans`.
+    async fn alter_table_inner(
+        &self,
+        source: Source,
+        table: Table,
+        select_star_mview_ids: &[u32],
+        mview_plans: Vec<ReplannedMview>,
+    ) -> MetaResult<(CatalogVersion, Vec<u32>)> {
+        self.catalog_manager
+            .start_alter_table_procedure(&source, &table)
+            .await?;
+
+        let mviews = self
+            .catalog_manager
+            .get_dependent_mviews(table.id, select_star_mview_ids)
+            .await;
+        let mut mview_plans: HashMap<_, _> = mview_plans
+            .into_iter()
+            .filter_map(|plan| Some((plan.mview?, plan.fragment_graph?)))
+            .map(|(new_mview, fragment_graph)| (new_mview.id, (new_mview, fragment_graph)))
+            .collect();
+        let mviews_to_update = mviews
+            .iter()
+            .filter_map(|mview| {
+                let (new_mview, fragment_graph) = mview_plans.remove(&mview.id)?;
+                Some((mview.clone(), new_mview, fragment_graph))
+            })
+            .collect_vec();
+
+        // The running actors must be changed before the new catalog is visible to the frontend,
+        // otherwise DML with the new columns may be sent to the old actors.
+        let mut changed_mviews = match self
+            .stream_manager
+            .alter_table(&source, &table, &mviews_to_update)
+            .await
+        {
+            Ok(updated_mviews) => updated_mviews,
+            Err(e) => {
+                self.catalog_manager
+                    .cancel_alter_table_procedure(&table)
+                    .await;
+                return Err(e);
+            }
+        };
+
+        let mut stale_mview_ids = vec![];
+        for mview in mviews {
+            if !changed_mviews.iter().any(|m| m.id == mview.id) {
+                stale_mview_ids.push(mview.id);
+                changed_mviews.push(Table {
+                    schema_stale: true,
+                    ..mview
+                });
+            }
+        }
+
+        let version = self
+            .catalog_manager
+            .finish_alter_table_procedure(&source, &table, &changed_mviews)
+            .await?;
+        Ok((version, stale_mview_ids))
+    }
+
+    /// Adds the columns proposed for the source, with the materialized views on it re-planned like
+    /// `alter_table_inner`. Returns the catalog version, and the stale materialized views.
+    async fn alter_source_columns_inner(
+        &self,
+        source: Source,
+        select_star_mview_ids: &[u32],
+        mview_plans: Vec<ReplannedMview>,
+    ) -> MetaResult<(CatalogVersion, Vec<u32>)> {
+        self.catalog_manager
+            .start_alter_source_columns_procedure(&source)
+            .await?;
+
+        let mviews = self
+            .catalog_manager
+            .get_dependent_mviews(source.id, select_star_mview_ids)
+            .await;
+        let mut mview_plans: HashMap<_, _> = mview_plans
+            .into_iter()
+            .filter_map(|plan| Some((plan.mview?, plan.fragment_graph?)))
+            .map(|(new_mview, fragment_graph)| (new_mview.id, (new_mview, fragment_graph)))
+            .collect();
+        let mviews_to_update = mviews
+            .iter()
+            .filter_map(|mview| {
+                let (new_mview, fragment_graph) = mview_plans.remove(&mview.id)?;
+                Some((mview.clone(), new_mview, fragment_graph))
+            })
+            .collect_vec();
+
+        let mut changed_mviews = match self
+            .stream_manager
+            .alter_source_columns(&source, &mviews_to_update)
+            .await
+        {
+            Ok(updated_mviews) => updated_mviews,
+            Err(e) => {
+                self.catalog_manager
+                    .cancel_alter_source_columns_procedure(&source)
+                    .await;
+                return Err(e);
+            }
+        };
+
+        let mut stale_mview_ids = vec![];
+        for mview in mviews {
+            if !changed_mviews.iter().any(|m| m.id == mview.id) {
+                stale_mview_ids.push(mview.id);
+                changed_mviews.push(Table {
+                    schema_stale: true,
+                    ..mview
+                });
+            }
+        }
+
+        let version = self
+            .catalog_manager
+            .finish_alter_source_columns_procedure(&source, &changed_mviews)
+            .await?;
+        // The added columns are not detected as new ones any more.
+        self.source_manager.update_source_columns(&source).await;
+        Ok((version, stale_mview_ids))
+    }
+
     async fn gen_unique_id<const C: IdCategoryType>(&self) -> MetaResult<u32> {
         let id = self.env.id_gen_manager().generate::<C>().await? as u32;
         Ok(id)
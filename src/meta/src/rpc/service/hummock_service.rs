@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 
 use itertools::Itertools;
@@ -92,6 +92,29 @@ where
         }))
     }
 
+    async fn get_table_sizes(
+        &self,
+        _request: Request<GetTableSizesRequest>,
+    ) -> Result<Response<GetTableSizesResponse>, Status> {
+        let current_version = self.hummock_manager.get_current_version().await;
+        let mut table_sizes = HashMap::new();
+        for sst in current_version
+            .get_combined_levels()
+            .iter()
+            .flat_map(|level| level.table_infos.iter())
+        {
+            if sst.table_ids.is_empty() {
+                continue;
+            }
+            // Assume the data is evenly distributed among the tables in the SST.
+            let size = sst.file_size / sst.table_ids.len() as u64;
+            for table_id in &sst.table_ids {
+                *table_sizes.entry(*table_id).or_default() += size;
+            }
+        }
+        Ok(Response::new(GetTableSizesResponse { table_sizes }))
+    }
+
     async fn reset_current_version(
         &self,
         _request: Request<ResetCurrentVersionRequest>,
@@ -502,6 +525,35 @@ where
         }))
     }
 
+    async fn get_compaction_backlog(
+        &self,
+        _request: Request<GetCompactionBacklogRequest>,
+    ) -> Result<Response<GetCompactionBacklogResponse>, Status> {
+        let backlogs = self.hummock_manager.get_compaction_backlog().await;
+        let assigned_task_number = self.hummock_manager.get_assigned_compact_task_num().await;
+        Ok(Response::new(GetCompactionBacklogResponse {
+            backlogs,
+            compactor_num: self.compactor_manager.compactor_num() as u64,
+            max_concurrent_task_number: self.compactor_manager.max_concurrent_task_number() as u64,
+            assigned_task_number,
+        }))
+    }
+
+    async fn rise_ctl_cancel_compact_task(
+        &self,
+        request: Request<RiseCtlCancelCompactTaskRequest>,
+    ) -> Result<Response<RiseCtlCancelCompactTaskResponse>, Status> {
+        let task_id = request.into_inner().task_id;
+        let canceled = self
+            .hummock_manager
+            .cancel_compact_task_by_id(task_id, compact_task::TaskStatus::ManualCanceled)
+            .await?;
+        Ok(Response::new(RiseCtlCancelCompactTaskResponse {
+            status: None,
+            canceled,
+        }))
+    }
+
     async fn init_metadata_for_replay(
         &self,
         request: Request<InitMetadataForReplayRequest>,
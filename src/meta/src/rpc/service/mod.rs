@@ -19,7 +19,10 @@ pub mod heartbeat_service;
 pub mod hummock_service;
 pub mod notification_service;
 pub mod scale_service;
+pub mod sink_coordination_service;
 pub mod stream_service;
+pub mod system_params_service;
+pub mod telemetry_service;
 pub mod user_service;
 
 use std::pin::Pin;
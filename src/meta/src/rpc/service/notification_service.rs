@@ -15,7 +15,7 @@
 use std::collections::HashSet;
 
 use itertools::Itertools;
-use risingwave_pb::catalog::Table;
+use risingwave_pb::catalog::{Secret, Table};
 use risingwave_pb::common::worker_node::State::Running;
 use risingwave_pb::common::WorkerType;
 use risingwave_pb::meta::notification_service_server::NotificationService;
@@ -27,7 +27,8 @@ use tonic::{Request, Response, Status};
 
 use crate::hummock::HummockManagerRef;
 use crate::manager::{
-    CatalogManagerRef, ClusterManagerRef, FragmentManagerRef, MetaSrvEnv, Notification, WorkerKey,
+    CatalogManagerRef, ClusterManagerRef, FragmentManagerRef, MetaSrvEnv, Notification,
+    SystemParamManagerRef, WorkerKey,
 };
 use crate::storage::MetaStore;
 
@@ -38,6 +39,7 @@ pub struct NotificationServiceImpl<S: MetaStore> {
     cluster_manager: ClusterManagerRef<S>,
     hummock_manager: HummockManagerRef<S>,
     fragment_manager: FragmentManagerRef<S>,
+    system_param_manager: SystemParamManagerRef<S>,
 }
 
 impl<S> NotificationServiceImpl<S>
@@ -50,6 +52,7 @@ where
         cluster_manager: ClusterManagerRef<S>,
         hummock_manager: HummockManagerRef<S>,
         fragment_manager: FragmentManagerRef<S>,
+        system_param_manager: SystemParamManagerRef<S>,
     ) -> Self {
         Self {
             env,
@@ -57,6 +60,7 @@ where
             cluster_manager,
             hummock_manager,
             fragment_manager,
+            system_param_manager,
         }
     }
 }
@@ -83,8 +87,16 @@ where
         let (tx, rx) = mpsc::unbounded_channel();
 
         let catalog_guard = self.catalog_manager.get_catalog_core_guard().await;
-        let (databases, schemas, mut tables, sources, sinks, indexes, views) =
+        let (databases, schemas, mut tables, sources, sinks, indexes, views, functions, secrets) =
             catalog_guard.database.get_catalog().await?;
+        // The values of secrets are never sent out of meta.
+        let secrets = secrets
+            .into_iter()
+            .map(|secret| Secret {
+                value: vec![],
+                ..secret
+            })
+            .collect_vec();
         let creating_tables = catalog_guard.database.list_creating_tables();
         let users = catalog_guard.user.list_users();
 
@@ -105,6 +117,7 @@ where
 
         let cluster_guard = self.cluster_manager.get_cluster_core_guard().await;
         let nodes = cluster_guard.list_worker_node(WorkerType::ComputeNode, Some(Running));
+        let system_params = Some(self.system_param_manager.get_params().await);
 
         match subscribe_type {
             SubscribeType::Compactor | SubscribeType::Hummock => {
@@ -140,11 +153,15 @@ where
                 parallel_unit_mappings,
                 hummock_snapshot,
                 views,
+                functions,
+                secrets,
                 compaction_groups: vec![],
+                system_params: None,
             },
 
             SubscribeType::Compactor => MetaSnapshot {
                 tables,
+                system_params,
                 ..Default::default()
             },
 
@@ -159,6 +176,7 @@ where
                     .iter()
                     .map(|group| group.into())
                     .collect_vec(),
+                system_params,
                 ..Default::default()
             },
 
@@ -0,0 +1,93 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_pb::meta::sink_coordination_service_server::SinkCoordinationService;
+use risingwave_pb::meta::{
+    FinishSinkCommitRequest, FinishSinkCommitResponse, GetSinkCommitDecisionRequest,
+    GetSinkCommitDecisionResponse, PreCommitSinkRequest, PreCommitSinkResponse, RecoverSinkRequest,
+    RecoverSinkResponse,
+};
+use tonic::{Request, Response, Status};
+
+use crate::storage::MetaStore;
+use crate::stream::SinkCoordinatorRef;
+
+pub struct SinkCoordinationServiceImpl<S: MetaStore> {
+    sink_coordinator: SinkCoordinatorRef<S>,
+}
+
+impl<S> SinkCoordinationServiceImpl<S>
+where
+    S: MetaStore,
+{
+    pub fn new(sink_coordinator: SinkCoordinatorRef<S>) -> Self {
+        Self { sink_coordinator }
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> SinkCoordinationService for SinkCoordinationServiceImpl<S>
+where
+    S: MetaStore,
+{
+    async fn pre_commit_sink(
+        &self,
+        request: Request<PreCommitSinkRequest>,
+    ) -> Result<Response<PreCommitSinkResponse>, Status> {
+        let commit = request.into_inner().commit.unwrap();
+        self.sink_coordinator.pre_commit(commit).await?;
+        Ok(Response::new(PreCommitSinkResponse { status: None }))
+    }
+
+    async fn get_sink_commit_decision(
+        &self,
+        request: Request<GetSinkCommitDecisionRequest>,
+    ) -> Result<Response<GetSinkCommitDecisionResponse>, Status> {
+        let req = request.into_inner();
+        let decision = self
+            .sink_coordinator
+            .wait_decision(req.sink_id, req.actor_id, req.epoch)
+            .await?;
+        Ok(Response::new(GetSinkCommitDecisionResponse {
+            status: None,
+            decision: decision as i32,
+        }))
+    }
+
+    async fn finish_sink_commit(
+        &self,
+        request: Request<FinishSinkCommitRequest>,
+    ) -> Result<Response<FinishSinkCommitResponse>, Status> {
+        let req = request.into_inner();
+        self.sink_coordinator
+            .finish_commit(req.sink_id, req.actor_id, req.epoch)
+            .await?;
+        Ok(Response::new(FinishSinkCommitResponse { status: None }))
+    }
+
+    async fn recover_sink(
+        &self,
+        request: Request<RecoverSinkRequest>,
+    ) -> Result<Response<RecoverSinkResponse>, Status> {
+        let req = request.into_inner();
+        let commits = self
+            .sink_coordinator
+            .recover_sink(req.sink_id, req.actor_id)
+            .await?;
+        Ok(Response::new(RecoverSinkResponse {
+            status: None,
+            commits,
+        }))
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_pb::meta::telemetry_service_server::TelemetryService;
+use risingwave_pb::meta::{GetTelemetryReportRequest, GetTelemetryReportResponse};
+use tonic::{Request, Response, Status};
+
+use crate::storage::MetaStore;
+use crate::telemetry::TelemetryReporterRef;
+
+pub struct TelemetryServiceImpl<S: MetaStore> {
+    telemetry_reporter: TelemetryReporterRef<S>,
+}
+
+impl<S> TelemetryServiceImpl<S>
+where
+    S: MetaStore,
+{
+    pub fn new(telemetry_reporter: TelemetryReporterRef<S>) -> Self {
+        Self { telemetry_reporter }
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> TelemetryService for TelemetryServiceImpl<S>
+where
+    S: MetaStore,
+{
+    async fn get_telemetry_report(
+        &self,
+        _request: Request<GetTelemetryReportRequest>,
+    ) -> Result<Response<GetTelemetryReportResponse>, Status> {
+        let report = self.telemetry_reporter.report_json().await?;
+        Ok(Response::new(GetTelemetryReportResponse {
+            status: None,
+            enabled: self.telemetry_reporter.enabled(),
+            report,
+        }))
+    }
+}
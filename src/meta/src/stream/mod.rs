@@ -15,6 +15,7 @@
 mod mapping;
 mod scale;
 mod scheduler;
+mod sink_coordinator;
 mod source_manager;
 mod stream_graph;
 mod stream_manager;
@@ -25,6 +26,7 @@ mod test_scale;
 pub use mapping::*;
 pub use scale::*;
 pub use scheduler::*;
+pub use sink_coordinator::*;
 pub use source_manager::*;
 pub use stream_graph::*;
 pub use stream_manager::*;
@@ -414,8 +414,10 @@ where
             match fragment.get_fragment_type()? {
                 FragmentType::Source => {
                     let stream_node = fragment.actors.first().unwrap().get_nodes().unwrap();
-                    let source_node = TableFragments::find_source_node(stream_node).unwrap();
-                    if is_stream_source(source_node) {
+                    // The `Now` fragments are also source fragments, but without a source node.
+                    if let Some(source_node) = TableFragments::find_source_node(stream_node)
+                        && is_stream_source(source_node)
+                    {
                         stream_source_fragment_ids.insert(*fragment_id);
                     }
                 }
@@ -450,8 +452,19 @@ where
                 }
             }
             for added in added_parallel_units {
-                if !parallel_unit_id_to_worker_id.contains_key(added) {
-                    bail!("parallel unit {} not available", added);
+                let worker_id = match parallel_unit_id_to_worker_id.get(added) {
+                    Some(worker_id) => worker_id,
+                    None => bail!("parallel unit {} not available", added),
+                };
+                if worker_nodes
+                    .get(worker_id)
+                    .map_or(false, |worker| worker.unschedulable)
+                {
+                    bail!(
+                        "parallel unit {} is on the cordoned worker {}",
+                        added,
+                        worker_id
+                    );
                 }
                 if current_parallel_units.contains(added) && !removed_parallel_units.contains(added)
                 {
@@ -920,10 +933,14 @@ where
         for (node_id, stream_actors) in &node_actors_to_create {
             let node = ctx.worker_nodes.get(node_id).unwrap();
             let client = self.client_pool.get(node).await?;
+            let mut stream_actors = stream_actors.clone();
+            self.catalog_manager
+                .fill_actor_secrets(&mut stream_actors)
+                .await?;
             let request_id = Uuid::new_v4().to_string();
             let request = UpdateActorsRequest {
                 request_id,
-                actors: stream_actors.clone(),
+                actors: stream_actors,
                 hanging_channels: worker_hanging_channels.remove(node_id).unwrap_or_default(),
             };
 
@@ -0,0 +1,330 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use risingwave_pb::meta::sink_prepared_commit::Decision;
+use risingwave_pb::meta::SinkPreparedCommit;
+use tokio::sync::{Mutex, Notify};
+
+use crate::manager::MetaSrvEnv;
+use crate::model::{sink_prepared_commit_key, ActorId, MetadataModel, Transactional};
+use crate::storage::{MetaStore, Transaction};
+use crate::MetaResult;
+
+pub type SinkCoordinatorRef<S> = Arc<SinkCoordinator<S>>;
+
+/// [`SinkCoordinator`] decides the commits of the two-phase sinks. An actor of a two-phase sink
+/// reports the transaction of a checkpoint before preparing it, and commits the transaction only
+/// after meta decides to, which happens once the checkpoint is durable. The transactions of the
+/// checkpoints abandoned by a recovery are aborted instead, as their data is written again after
+/// the recovery.
+///
+/// The decisions are persisted, so that the transactions left by the actors failed or stopped
+/// before resolving them are resolved by the other actors of the sink on their first barrier.
+pub struct SinkCoordinator<S: MetaStore> {
+    env: MetaSrvEnv<S>,
+    core: Mutex<SinkCoordinatorCore>,
+
+    /// Notified once some commits are decided.
+    decided: Notify,
+}
+
+#[derive(Default)]
+struct SinkCoordinatorCore {
+    /// The reported commits that are not finished yet, keyed by [`sink_prepared_commit_key`].
+    commits: BTreeMap<String, SinkPreparedCommit>,
+
+    /// The last epoch sent before the last recovery. The commits of the epochs up to it can only
+    /// be reported by the actors running before the recovery, which are rejected.
+    fenced_epoch: u64,
+
+    /// The actors started since the last recovery, which resolve their commits by themselves.
+    running_actors: HashSet<ActorId>,
+
+    /// The keys of the commits claimed by the actors since the last recovery.
+    claimed_commits: HashSet<String>,
+}
+
+impl<S> SinkCoordinator<S>
+where
+    S: MetaStore,
+{
+    pub async fn new(env: MetaSrvEnv<S>) -> MetaResult<Self> {
+        let commits = SinkPreparedCommit::list(env.meta_store())
+            .await?
+            .into_iter()
+            .map(|commit| Ok((commit.key()?, commit)))
+            .collect::<MetaResult<_>>()?;
+        Ok(Self {
+            env,
+            core: Mutex::new(SinkCoordinatorCore {
+                commits,
+                ..Default::default()
+            }),
+            decided: Notify::new(),
+        })
+    }
+
+    /// Records the transaction to be prepared by the actor of the sink at the checkpoint `epoch`,
+    /// before the actor passes the barrier on.
+    pub async fn pre_commit(&self, mut commit: SinkPreparedCommit) -> MetaResult<()> {
+        let mut core = self.core.lock().await;
+        if commit.epoch <= core.fenced_epoch {
+            return Err(anyhow!(
+                "the commit of sink {} at epoch {} is reported by actor {} running before recovery",
+                commit.sink_id,
+                commit.epoch,
+                commit.actor_id
+            )
+            .into());
+        }
+        commit.set_decision(Decision::Unspecified);
+        commit.insert(self.env.meta_store()).await?;
+        core.commits.insert(commit.key()?, commit);
+        Ok(())
+    }
+
+    /// Waits until the commit of the actor of the sink at `epoch` is decided.
+    pub async fn wait_decision(
+        &self,
+        sink_id: u32,
+        actor_id: ActorId,
+        epoch: u64,
+    ) -> MetaResult<Decision> {
+        let key = sink_prepared_commit_key(sink_id, actor_id, epoch);
+        loop {
+            // Created before checking the decision, so that the notification in between is not
+            // missed.
+            let decided = self.decided.notified();
+            {
+                let core = self.core.lock().await;
+                let commit = core.commits.get(&key).ok_or_else(|| {
+                    anyhow!(
+                        "the commit of sink {} at epoch {} by actor {} is not found",
+                        sink_id,
+                        epoch,
+                        actor_id
+                    )
+                })?;
+                match commit.decision() {
+                    Decision::Unspecified => {}
+                    decision => return Ok(decision),
+                }
+            }
+            decided.await;
+        }
+    }
+
+    /// Forgets the commit after its transaction is resolved by the decision.
+    pub async fn finish_commit(
+        &self,
+        sink_id: u32,
+        actor_id: ActorId,
+        epoch: u64,
+    ) -> MetaResult<()> {
+        let key = sink_prepared_commit_key(sink_id, actor_id, epoch);
+        let mut core = self.core.lock().await;
+        if core.commits.contains_key(&key) {
+            SinkPreparedCommit::delete(self.env.meta_store(), &key).await?;
+            core.commits.remove(&key);
+        }
+        core.claimed_commits.remove(&key);
+        Ok(())
+    }
+
+    /// Registers the actor of the sink on its first barrier, and claims the decided commits of
+    /// the sink left by the actors not running anymore, for the actor to resolve.
+    pub async fn recover_sink(
+        &self,
+        sink_id: u32,
+        actor_id: ActorId,
+    ) -> MetaResult<Vec<SinkPreparedCommit>> {
+        let mut core = self.core.lock().await;
+        let core = &mut *core;
+        core.running_actors.insert(actor_id);
+        let commits = core
+            .commits
+            .iter()
+            .filter(|(key, commit)| {
+                commit.sink_id == sink_id
+                    && commit.decision() != Decision::Unspecified
+                    && (commit.actor_id == actor_id
+                        || !core.running_actors.contains(&commit.actor_id))
+                    && !core.claimed_commits.contains(*key)
+            })
+            .map(|(key, commit)| (key.clone(), commit.clone()))
+            .collect::<Vec<_>>();
+        Ok(commits
+            .into_iter()
+            .map(|(key, commit)| {
+                core.claimed_commits.insert(key);
+                commit
+            })
+            .collect())
+    }
+
+    /// Decides to commit the transactions of the checkpoints up to `committed_epoch`, which has
+    /// been committed to the state store.
+    pub async fn commit_epoch(&self, committed_epoch: u64) -> MetaResult<()> {
+        let mut core = self.core.lock().await;
+        self.decide(&mut core, |commit| {
+            (commit.epoch <= committed_epoch).then_some(Decision::Commit)
+        })
+        .await
+    }
+
+    /// Decides the undecided commits on recovery. The transactions of the checkpoints up to
+    /// `committed_epoch` are durable and committed, while the others are aborted. The commits
+    /// reported by the actors running before the recovery, which are of the epochs up to
+    /// `prev_epoch`, are rejected afterwards.
+    pub async fn recover(&self, committed_epoch: u64, prev_epoch: u64) -> MetaResult<()> {
+        let mut core = self.core.lock().await;
+        core.fenced_epoch = prev_epoch;
+        core.running_actors.clear();
+        core.claimed_commits.clear();
+        self.decide(&mut core, |commit| {
+            Some(if commit.epoch <= committed_epoch {
+                Decision::Commit
+            } else {
+                Decision::Abort
+            })
+        })
+        .await
+    }
+
+    async fn decide(
+        &self,
+        core: &mut SinkCoordinatorCore,
+        decide: impl Fn(&SinkPreparedCommit) -> Option<Decision>,
+    ) -> MetaResult<()> {
+        let decided_commits = core
+            .commits
+            .values()
+            .filter(|commit| commit.decision() == Decision::Unspecified)
+            .filter_map(|commit| {
+                decide(commit).map(|decision| {
+                    let mut commit = commit.clone();
+                    commit.set_decision(decision);
+                    commit
+                })
+            })
+            .collect::<Vec<_>>();
+        if decided_commits.is_empty() {
+            return Ok(());
+        }
+
+        let mut trx = Transaction::default();
+        for commit in &decided_commits {
+            commit.upsert_in_transaction(&mut trx)?;
+        }
+        self.env.meta_store().txn(trx).await?;
+        for commit in decided_commits {
+            core.commits.insert(commit.key()?, commit);
+        }
+        self.decided.notify_waiters();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn prepared_commit(sink_id: u32, actor_id: ActorId, epoch: u64) -> SinkPreparedCommit {
+        SinkPreparedCommit {
+            sink_id,
+            actor_id,
+            epoch,
+            transaction_id: format!("sink-{}-{}-{}", sink_id, actor_id, epoch),
+            decision: Decision::Unspecified as i32,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_commit_after_checkpoint() -> MetaResult<()> {
+        let env = MetaSrvEnv::for_test().await;
+        let coordinator = Arc::new(SinkCoordinator::new(env).await?);
+        coordinator.pre_commit(prepared_commit(1, 1, 10)).await?;
+        coordinator.pre_commit(prepared_commit(1, 1, 20)).await?;
+
+        let waiter = {
+            let coordinator = coordinator.clone();
+            tokio::spawn(async move { coordinator.wait_decision(1, 1, 10).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!waiter.is_finished());
+
+        coordinator.commit_epoch(10).await?;
+        assert_eq!(waiter.await.unwrap()?, Decision::Commit);
+        // The checkpoint of epoch 20 is not durable yet.
+        let core = coordinator.core.lock().await;
+        assert_eq!(
+            core.commits[&sink_prepared_commit_key(1, 1, 20)].decision(),
+            Decision::Unspecified
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recover() -> MetaResult<()> {
+        let env = MetaSrvEnv::for_test().await;
+        let coordinator = SinkCoordinator::new(env.clone()).await?;
+        coordinator.pre_commit(prepared_commit(1, 1, 10)).await?;
+        coordinator.pre_commit(prepared_commit(1, 1, 20)).await?;
+        coordinator.pre_commit(prepared_commit(1, 2, 20)).await?;
+        coordinator.pre_commit(prepared_commit(2, 3, 20)).await?;
+
+        // The checkpoint of epoch 20 is abandoned, after epoch 30 is sent.
+        coordinator.recover(10, 30).await?;
+        assert_eq!(coordinator.wait_decision(1, 1, 10).await?, Decision::Commit);
+        assert_eq!(coordinator.wait_decision(1, 1, 20).await?, Decision::Abort);
+        assert_eq!(coordinator.wait_decision(2, 3, 20).await?, Decision::Abort);
+
+        // The commits reported by the actors running before the recovery are rejected.
+        assert!(coordinator
+            .pre_commit(prepared_commit(1, 1, 30))
+            .await
+            .is_err());
+        coordinator.pre_commit(prepared_commit(1, 1, 40)).await?;
+
+        // The decisions survive a restart of meta.
+        let coordinator = SinkCoordinator::new(env).await?;
+        assert_eq!(coordinator.wait_decision(1, 2, 20).await?, Decision::Abort);
+
+        // Actor 4 replaces actor 2 and claims its commit, while actor 1 is still running.
+        coordinator.recover_sink(1, 1).await?;
+        let claimed = coordinator.recover_sink(1, 4).await?;
+        assert_eq!(
+            claimed,
+            vec![prepared_commit(1, 2, 20)]
+                .into_iter()
+                .map(|mut commit| {
+                    commit.set_decision(Decision::Abort);
+                    commit
+                })
+                .collect::<Vec<_>>()
+        );
+        // A commit is claimed only once.
+        assert!(coordinator.recover_sink(1, 5).await?.is_empty());
+
+        coordinator.finish_commit(1, 2, 20).await?;
+        assert!(coordinator.wait_decision(1, 2, 20).await.is_err());
+        Ok(())
+    }
+}
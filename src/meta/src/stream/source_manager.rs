@@ -22,11 +22,14 @@ use std::time::Duration;
 use anyhow::anyhow;
 use itertools::Itertools;
 use risingwave_common::catalog::TableId;
+use risingwave_connector::schema::{detect_added_columns, SchemaEvolutionPolicy};
+use risingwave_connector::source::dead_letter::ParseErrorConfig;
 use risingwave_connector::source::{
-    ConnectorProperties, SplitEnumeratorImpl, SplitId, SplitImpl, SplitMetaData,
+    ConnectorProperties, SourceOffsetTarget, SplitEnumeratorImpl, SplitId, SplitImpl, SplitMetaData,
 };
 use risingwave_pb::catalog::source::Info::StreamSource;
-use risingwave_pb::catalog::Source;
+use risingwave_pb::catalog::{Source, StreamSourceInfo};
+use risingwave_pb::plan_common::ColumnCatalog;
 use risingwave_pb::source::{ConnectorSplit, ConnectorSplits};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::sync::{oneshot, Mutex};
@@ -47,29 +50,73 @@ pub type SplitAssignment = HashMap<FragmentId, HashMap<ActorId, Vec<SplitImpl>>>
 pub struct SourceManager<S: MetaStore> {
     pub(crate) paused: Mutex<()>,
     barrier_scheduler: BarrierScheduler<S>,
+    catalog_manager: CatalogManagerRef<S>,
     core: Mutex<SourceManagerCore<S>>,
 }
 
 struct SharedSplitMap {
     splits: Option<BTreeMap<SplitId, SplitImpl>>,
+    /// The columns of the source, which are extended only after the proposed columns are added to
+    /// the source catalog.
+    source_columns: Vec<ColumnCatalog>,
+    /// The columns added to the upstream schema found by the last detection, which are proposed
+    /// to be added to the source.
+    added_columns: Vec<ColumnCatalog>,
 }
 
 type SharedSplitMapRef = Arc<Mutex<SharedSplitMap>>;
 
+/// Detects the columns added to the upstream schema of a source with
+/// [`SchemaEvolutionPolicy::Backward`].
+struct SchemaWatcher {
+    info: StreamSourceInfo,
+    properties: HashMap<String, String>,
+}
+
+impl SchemaWatcher {
+    /// Returns the columns added to the upstream schema compared with `columns` of the source.
+    async fn detect(&self, columns: &[ColumnCatalog]) -> MetaResult<Vec<ColumnCatalog>> {
+        detect_added_columns(&self.info, &self.properties, columns)
+            .await
+            .map_err(|e| anyhow!(e.to_string()).into())
+    }
+}
+
 struct ConnectorSourceWorker {
     current_splits: SharedSplitMapRef,
     enumerator: SplitEnumeratorImpl,
+    properties: ConnectorProperties,
+    schema_watcher: Option<SchemaWatcher>,
     period: Duration,
 }
 
 impl ConnectorSourceWorker {
     pub async fn create(source: &Source, period: Duration) -> MetaResult<Self> {
         let properties = ConnectorProperties::extract(source.properties.clone())?;
-        let enumerator = SplitEnumeratorImpl::create(properties).await?;
-        let splits = Arc::new(Mutex::new(SharedSplitMap { splits: None }));
+        let enumerator = SplitEnumeratorImpl::create(properties.clone()).await?;
+        let splits = Arc::new(Mutex::new(SharedSplitMap {
+            splits: None,
+            source_columns: source.columns.clone(),
+            added_columns: vec![],
+        }));
+        let schema_watcher = match &source.info {
+            Some(StreamSource(info))
+                if SchemaEvolutionPolicy::from_properties(&source.properties, info)
+                    .map_err(|e| anyhow!(e.to_string()))?
+                    == SchemaEvolutionPolicy::Backward =>
+            {
+                Some(SchemaWatcher {
+                    info: info.clone(),
+                    properties: source.properties.clone(),
+                })
+            }
+            _ => None,
+        };
         Ok(Self {
             current_splits: splits,
             enumerator,
+            properties,
+            schema_watcher,
             period,
         })
     }
@@ -92,11 +139,31 @@ impl ConnectorSourceWorker {
                     if let Err(e) = self.tick().await {
                         tracing::error!("error happened when tick from connector source worker: {}", e.to_string());
                     }
+                    self.detect_schema_change().await;
                 }
             }
         }
     }
 
+    /// Fetches the latest upstream schema, and records the added columns to be proposed by the
+    /// source manager. The columns are detected against the source until they are added, so that
+    /// they are proposed again if proposing them fails. It's only done in the periodic ticks,
+    /// so that the forced ticks for creating streaming jobs are not slowed down.
+    async fn detect_schema_change(&mut self) {
+        let Some(watcher) = &self.schema_watcher else {
+            return;
+        };
+        let source_columns = self.current_splits.lock().await.source_columns.clone();
+        match watcher.detect(&source_columns).await {
+            Ok(added) => {
+                self.current_splits.lock().await.added_columns = added;
+            }
+            Err(e) => {
+                tracing::warn!("failed to detect the change of the upstream schema: {}", e);
+            }
+        }
+    }
+
     async fn tick(&mut self) -> MetaResult<()> {
         let splits = self.enumerator.list_splits().await?;
         let mut current_splits = self.current_splits.lock().await;
@@ -115,12 +182,27 @@ struct ConnectorSourceWorkerHandle {
     handle: JoinHandle<()>,
     sync_call_tx: UnboundedSender<oneshot::Sender<MetaResult<()>>>,
     splits: SharedSplitMapRef,
+    /// The properties of the source, by which the upstream resources are dropped along with the
+    /// source.
+    properties: ConnectorProperties,
 }
 
 impl ConnectorSourceWorkerHandle {
     async fn discovered_splits(&self) -> Option<BTreeMap<SplitId, SplitImpl>> {
         self.splits.lock().await.splits.clone()
     }
+
+    async fn added_columns(&self) -> Vec<ColumnCatalog> {
+        self.splits.lock().await.added_columns.clone()
+    }
+
+    /// Records the columns of the source after the proposed columns are added, so that they are
+    /// not detected again.
+    async fn update_source_columns(&self, columns: &[ColumnCatalog]) {
+        let mut splits = self.splits.lock().await;
+        splits.source_columns = columns.to_vec();
+        splits.added_columns.clear();
+    }
 }
 
 pub struct SourceManagerCore<S: MetaStore> {
@@ -368,8 +450,11 @@ where
         {
             let sources = catalog_manager.list_sources().await;
 
-            for source in sources {
+            for mut source in sources {
                 if let Some(StreamSource(_)) = source.info {
+                    catalog_manager
+                        .fill_secrets(&mut source.properties, &source.secret_refs)
+                        .await?;
                     Self::create_source_worker(&source, &mut managed_sources, false).await?
                 }
             }
@@ -391,6 +476,7 @@ where
 
         Ok(Self {
             barrier_scheduler,
+            catalog_manager,
             core,
             paused: Mutex::new(()),
         })
@@ -510,11 +596,24 @@ where
         }
 
         if let Some(StreamSource(_)) = source.info {
-            Self::create_source_worker(source, &mut core.managed_sources, true).await?;
+            // Reject the invalid options early, as they are only parsed on compute nodes.
+            ParseErrorConfig::from_properties(&source.properties)?;
+            let source = self.with_secrets(source).await?;
+            Self::create_source_worker(&source, &mut core.managed_sources, true).await?;
         }
         Ok(())
     }
 
+    /// Returns the source with the properties referring to secrets filled, which is only used to
+    /// connect to the external system and never persisted.
+    async fn with_secrets(&self, source: &Source) -> MetaResult<Source> {
+        let mut source = source.clone();
+        self.catalog_manager
+            .fill_secrets(&mut source.properties, &source.secret_refs)
+            .await?;
+        Ok(source)
+    }
+
     async fn create_source_worker(
         source: &Source,
         managed_sources: &mut HashMap<SourceId, ConnectorSourceWorkerHandle>,
@@ -522,6 +621,7 @@ where
     ) -> MetaResult<()> {
         let mut worker = ConnectorSourceWorker::create(source, Duration::from_secs(10)).await?;
         let current_splits_ref = worker.current_splits.clone();
+        let properties = worker.properties.clone();
         tracing::info!("spawning new watcher for source {}", source.id);
 
         // don't force tick in process of recovery. One source down should not lead to meta recovery
@@ -541,6 +641,7 @@ where
                 handle,
                 sync_call_tx,
                 splits: current_splits_ref,
+                properties,
             },
         );
 
@@ -552,6 +653,14 @@ where
         for source_id in source_ids {
             if let Some(handle) = core.managed_sources.remove(&source_id) {
                 handle.handle.abort();
+                // The source is dropped anyway, so the failures are only logged.
+                if let Err(e) = handle.properties.drop_upstream_resources().await {
+                    tracing::warn!(
+                        "failed to drop the upstream resources of source {}: {}",
+                        source_id,
+                        e
+                    );
+                }
             }
 
             assert!(
@@ -589,11 +698,49 @@ where
         Ok(())
     }
 
+    /// Proposes the columns detected by the source workers to be added to the sources. The
+    /// frontends add them with the materialized views selecting `*` from the sources re-planned,
+    /// while other streaming jobs keep parsing the messages with their columns at creation. The
+    /// same columns are proposed on every tick until they are added, which is a no-op in the
+    /// catalog.
+    async fn evolve_source_schemas(&self) {
+        let added_columns = {
+            let core = self.core.lock().await;
+            let mut added_columns = vec![];
+            for (source_id, handle) in &core.managed_sources {
+                let columns = handle.added_columns().await;
+                if !columns.is_empty() {
+                    added_columns.push((*source_id, columns));
+                }
+            }
+            added_columns
+        };
+
+        for (source_id, columns) in added_columns {
+            if let Err(e) = self
+                .catalog_manager
+                .propose_source_columns(source_id, columns)
+                .await
+            {
+                tracing::warn!("failed to propose columns for source {}: {}", source_id, e);
+            }
+        }
+    }
+
+    /// Records the columns of `source` after the proposed columns are added.
+    pub async fn update_source_columns(&self, source: &Source) {
+        let core = self.core.lock().await;
+        if let Some(handle) = core.managed_sources.get(&source.id) {
+            handle.update_source_columns(&source.columns).await;
+        }
+    }
+
     pub async fn run(&self) -> MetaResult<()> {
         let mut ticker = time::interval(Self::SOURCE_TICK_INTERVAL);
         ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
         loop {
             ticker.tick().await;
+            self.evolve_source_schemas().await;
             let _pause_guard = self.paused.lock().await;
             if let Err(e) = self.tick().await {
                 tracing::error!(
@@ -604,6 +751,75 @@ where
         }
     }
 
+    /// Rewind or fast-forward the offsets of the splits of the source to `target`. The sources are
+    /// paused while the new states of the splits are pushed down, so that all actors of the source
+    /// switch to the new offsets at the same barrier.
+    pub async fn reset_source_offsets(
+        &self,
+        source: &Source,
+        target: SourceOffsetTarget,
+    ) -> MetaResult<()> {
+        let properties = self.with_secrets(source).await?.properties;
+        let mut properties = ConnectorProperties::extract(properties)?;
+        properties.set_startup_offset(target)?;
+        let mut enumerator = SplitEnumeratorImpl::create(properties).await?;
+        let reset_splits: HashMap<_, _> = enumerator
+            .list_splits()
+            .await?
+            .into_iter()
+            .map(|split| (split.id(), split))
+            .collect();
+
+        // Hold the pause guard so that no split assignment is pushed down by `tick` meanwhile.
+        let _pause_guard = self.paused.lock().await;
+        let reset = {
+            let core = self.core.lock().await;
+            let fragment_ids = core
+                .source_fragments
+                .get(&source.id)
+                .ok_or_else(|| anyhow!("source {} is not read by any streaming job", source.id))?;
+
+            let mut reset = SplitAssignment::new();
+            for fragment_id in fragment_ids {
+                let actor_ids = core
+                    .fragment_manager
+                    .get_running_actors_of_fragment(*fragment_id)
+                    .await?;
+                // Splits that are no longer listed, e.g. the closed shards of Kinesis, are not
+                // reset.
+                let actor_splits = actor_ids
+                    .into_iter()
+                    .map(|actor_id| {
+                        let splits = core
+                            .actor_splits
+                            .get(&actor_id)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|split| reset_splits.get(&split.id()).cloned())
+                            .collect_vec();
+                        (actor_id, splits)
+                    })
+                    .collect();
+                reset.insert(*fragment_id, actor_splits);
+            }
+            reset
+        };
+
+        tracing::info!(
+            "reset the offsets of source {} to {:?}: {:#?}",
+            source.id,
+            target,
+            reset
+        );
+        self.barrier_scheduler
+            .run_multiple_commands(vec![
+                Command::pause(),
+                Command::SourceSplitReset(reset),
+                Command::resume(),
+            ])
+            .await
+    }
+
     pub async fn get_source_ids_in_fragments(&self) -> Vec<SourceId> {
         self.core
             .lock()
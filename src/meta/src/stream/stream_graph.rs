@@ -600,6 +600,9 @@ impl StreamGraphBuilder {
                                 update_table(s.table.as_mut().unwrap(), "HashAgg");
                             }
                         }
+                        for table in node.distinct_dedup_tables.values_mut() {
+                            update_table(table, "HashAggDedup");
+                        }
                     }
 
                     NodeBody::AppendOnlyTopN(node) => {
@@ -619,6 +622,42 @@ impl StreamGraphBuilder {
                         }
                     }
 
+                    NodeBody::AppendOnlyGroupTopN(node) => {
+                        if let Some(table) = &mut node.table {
+                            update_table(table, "AppendOnlyGroupTopNNode");
+                        }
+                    }
+
+                    NodeBody::OverWindow(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            update_table(table, "OverWindowNode");
+                        }
+                    }
+
+                    NodeBody::SessionWindow(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            update_table(table, "SessionWindowNode");
+                        }
+                    }
+
+                    NodeBody::Now(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            update_table(table, "NowNode");
+                        }
+                    }
+
+                    NodeBody::Sink(node) => {
+                        if let Some(table) = &mut node.log_store_table {
+                            update_table(table, "SinkNode");
+                        }
+                    }
+
+                    NodeBody::Sort(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            update_table(table, "SortNode");
+                        }
+                    }
+
                     NodeBody::GlobalSimpleAgg(node) => {
                         assert_eq!(node.agg_call_states.len(), node.agg_calls.len());
                         // In-place update the table id. Convert from local to global.
@@ -630,6 +669,9 @@ impl StreamGraphBuilder {
                                 update_table(s.table.as_mut().unwrap(), "GlobalSimpleAgg");
                             }
                         }
+                        for table in node.distinct_dedup_tables.values_mut() {
+                            update_table(table, "GlobalSimpleAggDedup");
+                        }
                     }
 
                     NodeBody::DynamicFilter(node) => {
@@ -640,6 +682,21 @@ impl StreamGraphBuilder {
                             update_table(table, "DynamicFilterRight");
                         }
                     }
+
+                    NodeBody::IntervalJoin(node) => {
+                        if let Some(table) = &mut node.left_table {
+                            update_table(table, "IntervalJoinLeft");
+                        }
+                        if let Some(table) = &mut node.right_table {
+                            update_table(table, "IntervalJoinRight");
+                        }
+                    }
+
+                    NodeBody::LookupJoin(node) => {
+                        if let Some(table) = &mut node.left_table {
+                            update_table(table, "LookupJoinLeft");
+                        }
+                    }
                     _ => {}
                 }
 
@@ -833,6 +890,34 @@ impl ActorGraphBuilder {
         );
     }
 
+    /// Fills the id of the sink catalog in the sink nodes, by which the two-phase commits of the
+    /// sink are coordinated.
+    pub fn fill_sink_id(&mut self, sink_id: u32) {
+        fn fill_sink_id_inner(stream_node: &mut StreamNode, sink_id: u32) -> usize {
+            let mut sink_count = 0;
+            if let NodeBody::Sink(sink_node) = stream_node.node_body.as_mut().unwrap() {
+                sink_node.sink_id = sink_id;
+                sink_count += 1;
+            }
+            for input in &mut stream_node.input {
+                sink_count += fill_sink_id_inner(input, sink_id);
+            }
+            sink_count
+        }
+
+        let sink_count: usize = self
+            .fragment_graph
+            .fragments_mut()
+            .values_mut()
+            .map(|fragment| fill_sink_id_inner(fragment.node.as_mut().unwrap(), sink_id))
+            .sum();
+
+        assert_eq!(
+            sink_count, 1,
+            "require exactly 1 sink node when creating sink"
+        );
+    }
+
     pub async fn generate_graph<S>(
         &self,
         id_gen_manager: IdGeneratorManagerRef<S>,
@@ -1081,6 +1166,11 @@ impl ActorGraphBuilder {
                     }) => Some(table.as_ref().unwrap().get_id()),
                 })
                 .chain(iter::once(node.get_result_table().unwrap().get_id()))
+                .chain(
+                    node.distinct_dedup_tables
+                        .values()
+                        .map(|table| table.get_id()),
+                )
                 .collect_vec(),
             NodeBody::GlobalSimpleAgg(node) => node
                 .agg_call_states
@@ -1094,6 +1184,11 @@ impl ActorGraphBuilder {
                     }) => Some(table.as_ref().unwrap().get_id()),
                 })
                 .chain(iter::once(node.get_result_table().unwrap().get_id()))
+                .chain(
+                    node.distinct_dedup_tables
+                        .values()
+                        .map(|table| table.get_id()),
+                )
                 .collect_vec(),
             NodeBody::HashJoin(node) => {
                 vec![
@@ -1109,12 +1204,34 @@ impl ActorGraphBuilder {
                     node.right_table.as_ref().unwrap().id,
                 ]
             }
+            NodeBody::IntervalJoin(node) => {
+                vec![
+                    node.left_table.as_ref().unwrap().id,
+                    node.right_table.as_ref().unwrap().id,
+                ]
+            }
+            NodeBody::LookupJoin(node) => {
+                vec![node.left_table.as_ref().unwrap().id]
+            }
             NodeBody::AppendOnlyTopN(node) => {
                 vec![node.table.as_ref().unwrap().id]
             }
-            NodeBody::GroupTopN(node) => {
+            NodeBody::GroupTopN(node) | NodeBody::AppendOnlyGroupTopN(node) => {
                 vec![node.table.as_ref().unwrap().id]
             }
+            NodeBody::OverWindow(node) => {
+                vec![node.state_table.as_ref().unwrap().id]
+            }
+            NodeBody::SessionWindow(node) => {
+                vec![node.state_table.as_ref().unwrap().id]
+            }
+            NodeBody::Now(node) => {
+                vec![node.state_table.as_ref().unwrap().id]
+            }
+            NodeBody::Sink(node) => node.log_store_table.iter().map(|table| table.id).collect(),
+            NodeBody::Sort(node) => {
+                vec![node.state_table.as_ref().unwrap().id]
+            }
             NodeBody::TopN(node) => {
                 vec![node.table.as_ref().unwrap().id]
             }
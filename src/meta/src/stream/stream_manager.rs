@@ -20,30 +20,36 @@ use itertools::Itertools;
 use risingwave_common::bail;
 use risingwave_common::catalog::TableId;
 use risingwave_common::types::VIRTUAL_NODE_COUNT;
-use risingwave_pb::catalog::Table;
+use risingwave_connector::source::SourceRateLimit;
+use risingwave_pb::catalog::{Source, Table};
 use risingwave_pb::common::{ActorInfo, Buffer, WorkerType};
 use risingwave_pb::meta::table_fragments::actor_status::ActorState;
 use risingwave_pb::meta::table_fragments::fragment::FragmentDistributionType;
 use risingwave_pb::meta::table_fragments::ActorStatus;
+use risingwave_pb::stream_plan::barrier::Mutation;
 use risingwave_pb::stream_plan::stream_node::NodeBody;
-use risingwave_pb::stream_plan::{ActorMapping, Dispatcher, DispatcherType, StreamNode};
+use risingwave_pb::stream_plan::{
+    ActorMapping, BackfillRateLimitMutation, Dispatcher, DispatcherType, SchemaChangeMutation,
+    SourceRateLimitMutation, StreamFragmentGraph, StreamNode,
+};
 use risingwave_pb::stream_service::{
     BroadcastActorInfoTableRequest, BuildActorsRequest, HangingChannel, UpdateActorsRequest,
 };
 use risingwave_rpc_client::StreamClientPoolRef;
+use tokio::sync::oneshot;
 use uuid::Uuid;
 
 use super::ScheduledLocations;
 use crate::barrier::{BarrierScheduler, Command};
 use crate::hummock::compaction_group::manager::CompactionGroupManagerRef;
 use crate::manager::{
-    ClusterManagerRef, DatabaseId, FragmentManagerRef, FragmentVNodeInfo, IdGeneratorManagerRef,
-    MetaSrvEnv, SchemaId, WorkerId,
+    CatalogManagerRef, ClusterManagerRef, DatabaseId, FragmentManagerRef, FragmentVNodeInfo,
+    IdGeneratorManagerRef, MetaSrvEnv, SchemaId, WorkerId,
 };
 use crate::model::{ActorId, FragmentId, TableFragments};
 use crate::storage::MetaStore;
 use crate::stream::{parallel_unit_mapping_to_actor_mapping, Scheduler, SourceManagerRef};
-use crate::MetaResult;
+use crate::{MetaError, MetaResult};
 
 pub type GlobalStreamManagerRef<S> = Arc<GlobalStreamManager<S>>;
 
@@ -89,6 +95,14 @@ impl CreateMaterializedViewContext {
     }
 }
 
+/// Info of a streaming job that is being created, used for showing and cancelling it.
+struct CreatingStreamingJob {
+    /// The SQL definition of the job.
+    definition: String,
+    /// Notifies the creation to be cancelled.
+    cancel_tx: oneshot::Sender<()>,
+}
+
 /// `GlobalStreamManager` manages all the streams in the system.
 pub struct GlobalStreamManager<S: MetaStore> {
     /// Manages definition and status of fragments and actors
@@ -100,6 +114,9 @@ pub struct GlobalStreamManager<S: MetaStore> {
     /// Maintains information of the cluster
     pub(crate) cluster_manager: ClusterManagerRef<S>,
 
+    /// Resolves the secrets referred to by the actors
+    catalog_manager: CatalogManagerRef<S>,
+
     /// Maintains streaming sources from external system like kafka
     pub(crate) source_manager: SourceManagerRef<S>,
 
@@ -110,6 +127,9 @@ pub struct GlobalStreamManager<S: MetaStore> {
     pub(crate) id_gen_manager: IdGeneratorManagerRef<S>,
 
     compaction_group_manager: CompactionGroupManagerRef<S>,
+
+    /// The streaming jobs being created, keyed by the table id.
+    creating_jobs: parking_lot::Mutex<HashMap<TableId, CreatingStreamingJob>>,
 }
 
 impl<S> GlobalStreamManager<S>
@@ -121,6 +141,7 @@ where
         fragment_manager: FragmentManagerRef<S>,
         barrier_scheduler: BarrierScheduler<S>,
         cluster_manager: ClusterManagerRef<S>,
+        catalog_manager: CatalogManagerRef<S>,
         source_manager: SourceManagerRef<S>,
         compaction_group_manager: CompactionGroupManagerRef<S>,
     ) -> MetaResult<Self> {
@@ -128,13 +149,39 @@ where
             fragment_manager,
             barrier_scheduler,
             cluster_manager,
+            catalog_manager,
             source_manager,
             client_pool: env.stream_client_pool_ref(),
             compaction_group_manager,
             id_gen_manager: env.id_gen_manager_ref(),
+            creating_jobs: Default::default(),
         })
     }
 
+    /// Returns the definitions of the streaming jobs being created, keyed by the table id.
+    pub fn creating_job_definitions(&self) -> HashMap<TableId, String> {
+        self.creating_jobs
+            .lock()
+            .iter()
+            .map(|(table_id, job)| (*table_id, job.definition.clone()))
+            .collect()
+    }
+
+    /// Cancel the streaming jobs being created. The creating actors will be dropped and the
+    /// creation will fail with an error. Returns the ids of the jobs that are found and cancelled.
+    pub fn cancel_creating_jobs(&self, table_ids: &[TableId]) -> Vec<TableId> {
+        let mut creating_jobs = self.creating_jobs.lock();
+        table_ids
+            .iter()
+            .filter_map(|table_id| {
+                let job = creating_jobs.remove(table_id)?;
+                // The creation may have just finished, ignore the error in this case.
+                let _ = job.cancel_tx.send(());
+                Some(*table_id)
+            })
+            .collect()
+    }
+
     async fn resolve_chain_node(
         &self,
         table_fragments: &mut TableFragments,
@@ -385,6 +432,7 @@ where
             dependent_table_ids,
             table_properties,
             chain_fragment_upstream_table_map,
+            mview_definition,
             ..
         }: &mut CreateMaterializedViewContext,
     ) -> MetaResult<()> {
@@ -406,7 +454,7 @@ where
             if workers.is_empty() {
                 bail!("no available compute node in the cluster");
             }
-            let parallel_units = self.cluster_manager.list_active_parallel_units().await;
+            let parallel_units = self.cluster_manager.list_schedulable_parallel_units().await;
 
             // Create empty locations and the scheduler.
             let mut locations = ScheduledLocations::with_workers(workers);
@@ -608,10 +656,13 @@ where
                 })
                 .await?;
 
-            let stream_actors = actors
+            let mut stream_actors = actors
                 .iter()
                 .map(|actor_id| actor_map.get(actor_id).cloned().unwrap())
                 .collect::<Vec<_>>();
+            self.catalog_manager
+                .fill_actor_secrets(&mut stream_actors)
+                .await?;
 
             let request_id = Uuid::new_v4().to_string();
             tracing::debug!(request_id = request_id.as_str(), actors = ?actors, "update actors");
@@ -676,16 +727,41 @@ where
 
         let split_assignment = self.source_manager.pre_allocate_splits(&table_id).await?;
 
-        if let Err(err) = self
-            .barrier_scheduler
-            .run_command(Command::CreateMaterializedView {
-                table_fragments,
-                table_sink_map: table_sink_map.clone(),
-                dispatchers: dispatchers.clone(),
-                init_split_assignment: split_assignment,
-            })
-            .await
-        {
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        self.creating_jobs.lock().insert(
+            table_id,
+            CreatingStreamingJob {
+                definition: mview_definition.clone(),
+                cancel_tx,
+            },
+        );
+        let create_command = Command::CreateMaterializedView {
+            table_fragments,
+            table_sink_map: table_sink_map.clone(),
+            dispatchers: dispatchers.clone(),
+            init_split_assignment: split_assignment,
+        };
+        let result = tokio::select! {
+            result = self.barrier_scheduler.run_command(create_command) => result,
+            Ok(()) = cancel_rx => {
+                tracing::info!("cancelling the creation of streaming job {}", table_id);
+                // Drop the creating actors and the table fragments.
+                match self
+                    .barrier_scheduler
+                    .run_command(Command::DropMaterializedViews(HashSet::from([table_id])))
+                    .await
+                {
+                    Ok(()) => Err(MetaError::from(anyhow::anyhow!(
+                        "streaming job {} is cancelled",
+                        table_id
+                    ))),
+                    Err(err) => Err(err),
+                }
+            }
+        };
+        self.creating_jobs.lock().remove(&table_id);
+
+        if let Err(err) = result {
             self.fragment_manager
                 .drop_table_fragments_vec(&HashSet::from_iter(std::iter::once(table_id)))
                 .await?;
@@ -749,6 +825,187 @@ where
 
         Ok(())
     }
+
+    /// Change the schema of the table with the new `source` and `table` catalog. A barrier is
+    /// issued to change the schema of the running actors, and the fragments are only persisted
+    /// after it's collected. If the barrier fails, the actors are recovered from the fragments of
+    /// the old schema, which matches the catalog as the change is cancelled.
+    ///
+    /// The materialized views in `mviews`, defined with `SELECT *` on the table, are replaced with
+    /// their plans re-planned with the appended columns by the same barrier if possible. Each view
+    /// comes with its new catalog and fragment graph. Returns the catalogs of the updated views.
+    pub async fn alter_table(
+        &self,
+        source: &Source,
+        table: &Table,
+        mviews: &[(Table, Table, StreamFragmentGraph)],
+    ) -> MetaResult<Vec<Table>> {
+        let mut table_fragments = self
+            .fragment_manager
+            .select_table_fragments_by_table_id(&table.id.into())
+            .await?;
+        table_fragments.update_table_schema(source, table);
+        let mut updated_table_fragments = vec![table_fragments];
+
+        let mut mview_changes = vec![];
+        let mut updated_mviews = vec![];
+        for (mview, new_mview, fragment_graph) in mviews {
+            let mut table_fragments = self
+                .fragment_manager
+                .select_table_fragments_by_table_id(&mview.id.into())
+                .await?;
+            let Some(change) =
+                table_fragments.replace_mview_plan(mview, new_mview, fragment_graph) else {
+                continue;
+            };
+            tracing::info!(
+                "update materialized view {} to plan version {} with the schema change of {}",
+                mview.name,
+                table_fragments.plan_version(),
+                table.name
+            );
+            updated_mviews.push(Table {
+                columns: new_mview.columns.clone(),
+                value_indices: new_mview.value_indices.clone(),
+                schema_stale: false,
+                ..mview.clone()
+            });
+            mview_changes.push(change);
+            updated_table_fragments.push(table_fragments);
+        }
+
+        self.barrier_scheduler
+            .run_command(Command::Plain(Some(Mutation::SchemaChange(
+                SchemaChangeMutation {
+                    source_id: source.id,
+                    table_id: table.id,
+                    source_columns: source.columns.clone(),
+                    table_columns: table.columns.clone(),
+                    mview_changes,
+                },
+            ))))
+            .await?;
+
+        self.fragment_manager
+            .batch_update_table_fragments(&updated_table_fragments)
+            .await?;
+
+        Ok(updated_mviews)
+    }
+
+    /// Add the columns proposed for the source to the materialized views defined with `SELECT *` on
+    /// it, of which `mviews` are the existing and the re-planned ones. Like `alter_table`, returns
+    /// the views updated. Other streaming jobs reading from the source keep their columns.
+    pub async fn alter_source_columns(
+        &self,
+        source: &Source,
+        mviews: &[(Table, Table, StreamFragmentGraph)],
+    ) -> MetaResult<Vec<Table>> {
+        let mut updated_table_fragments = vec![];
+        let mut mview_changes = vec![];
+        let mut updated_mviews = vec![];
+        for (mview, new_mview, fragment_graph) in mviews {
+            let mut table_fragments = self
+                .fragment_manager
+                .select_table_fragments_by_table_id(&mview.id.into())
+                .await?;
+            let Some(change) =
+                table_fragments.replace_mview_plan(mview, new_mview, fragment_graph) else {
+                continue;
+            };
+            tracing::info!(
+                "update materialized view {} to plan version {} with the columns added to {}",
+                mview.name,
+                table_fragments.plan_version(),
+                source.name
+            );
+            updated_mviews.push(Table {
+                columns: new_mview.columns.clone(),
+                value_indices: new_mview.value_indices.clone(),
+                schema_stale: false,
+                ..mview.clone()
+            });
+            mview_changes.push(change);
+            updated_table_fragments.push(table_fragments);
+        }
+        if mview_changes.is_empty() {
+            return Ok(updated_mviews);
+        }
+
+        self.barrier_scheduler
+            .run_command(Command::Plain(Some(Mutation::SchemaChange(
+                SchemaChangeMutation {
+                    source_id: source.id,
+                    table_id: 0,
+                    source_columns: source.columns.clone(),
+                    table_columns: vec![],
+                    mview_changes,
+                },
+            ))))
+            .await?;
+
+        self.fragment_manager
+            .batch_update_table_fragments(&updated_table_fragments)
+            .await?;
+
+        Ok(updated_mviews)
+    }
+
+    /// Change the rate limits of the source with the new `source` catalog. The persisted
+    /// fragments of all streaming jobs reading from the source are updated before the running
+    /// actors are notified by a barrier.
+    pub async fn alter_source(&self, source: &Source) -> MetaResult<()> {
+        let rate_limit = SourceRateLimit::from_properties(&source.properties)?;
+
+        let mut updated_table_fragments = vec![];
+        for mut table_fragments in self.fragment_manager.list_table_fragments().await? {
+            if table_fragments.update_source_properties(source) {
+                updated_table_fragments.push(table_fragments);
+            }
+        }
+        if updated_table_fragments.is_empty() {
+            return Ok(());
+        }
+        self.fragment_manager
+            .batch_update_table_fragments(&updated_table_fragments)
+            .await?;
+
+        self.barrier_scheduler
+            .run_command(Command::Plain(Some(Mutation::SourceRateLimit(
+                SourceRateLimitMutation {
+                    source_id: source.id,
+                    rows_per_second: rate_limit.rows_per_second,
+                    bytes_per_second: rate_limit.bytes_per_second,
+                },
+            ))))
+            .await
+    }
+
+    /// Change the rate limit on the snapshot side of the chain actors of the materialized view
+    /// with `table_id`. Like `alter_source`, the persisted fragments are updated before the running
+    /// actors are notified by a barrier.
+    pub async fn alter_backfill_rate_limit(
+        &self,
+        table_id: TableId,
+        rows_per_second: u64,
+    ) -> MetaResult<()> {
+        let actor_ids = self
+            .fragment_manager
+            .update_backfill_rate_limit(&table_id, rows_per_second)
+            .await?;
+        if actor_ids.is_empty() {
+            return Ok(());
+        }
+
+        self.barrier_scheduler
+            .run_command(Command::Plain(Some(Mutation::BackfillRateLimit(
+                BackfillRateLimitMutation {
+                    actor_ids: actor_ids.into_iter().collect(),
+                    rows_per_second,
+                },
+            ))))
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -783,11 +1040,12 @@ mod tests {
     use crate::hummock::{CompactorManager, HummockManager};
     use crate::manager::{
         CatalogManager, CatalogManagerRef, ClusterManager, FragmentManager, MetaSrvEnv,
+        SystemParamManager,
     };
     use crate::model::ActorId;
     use crate::rpc::metrics::MetaMetrics;
     use crate::storage::MemStore;
-    use crate::stream::SourceManager;
+    use crate::stream::{SinkCoordinator, SourceManager};
     use crate::MetaOpts;
 
     struct FakeFragmentState {
@@ -967,6 +1225,9 @@ mod tests {
                 .await?,
             );
 
+            let system_param_manager = Arc::new(SystemParamManager::new(env.clone()).await?);
+            let sink_coordinator = Arc::new(SinkCoordinator::new(env.clone()).await?);
+
             let barrier_manager = Arc::new(GlobalBarrierManager::new(
                 scheduled_barriers,
                 env.clone(),
@@ -975,6 +1236,8 @@ mod tests {
                 fragment_manager.clone(),
                 hummock_manager,
                 source_manager.clone(),
+                sink_coordinator,
+                system_param_manager,
                 meta_metrics.clone(),
             ));
 
@@ -983,6 +1246,7 @@ mod tests {
                 fragment_manager.clone(),
                 barrier_scheduler.clone(),
                 cluster_manager.clone(),
+                catalog_manager.clone(),
                 source_manager.clone(),
                 compaction_group_manager.clone(),
             )?;
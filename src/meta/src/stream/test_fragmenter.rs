@@ -250,6 +250,7 @@ fn make_stream_fragments() -> Vec<StreamFragment> {
             is_append_only: false,
             agg_call_states: vec![make_agg_call_result_state(), make_agg_call_result_state()],
             result_table: Some(make_empty_table(1)),
+            distinct_dedup_tables: Default::default(),
         })),
         input: vec![filter_node],
         fields: vec![], // TODO: fill this later
@@ -292,6 +293,7 @@ fn make_stream_fragments() -> Vec<StreamFragment> {
             is_append_only: false,
             agg_call_states: vec![make_agg_call_result_state(), make_agg_call_result_state()],
             result_table: Some(make_empty_table(2)),
+            distinct_dedup_tables: Default::default(),
         })),
         fields: vec![], // TODO: fill this later
         input: vec![exchange_node_1],
@@ -319,6 +321,7 @@ fn make_stream_fragments() -> Vec<StreamFragment> {
                 make_inputref(0),
                 make_inputref(1),
             ],
+            ..Default::default()
         })),
         fields: vec![], // TODO: fill this later
         input: vec![simple_agg_node_1],
@@ -0,0 +1,324 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use prost::Message;
+use risingwave_common::catalog::valid_table_name;
+use risingwave_pb::common::WorkerType;
+use serde::Serialize;
+use tokio::sync::oneshot::Sender;
+use tokio::sync::OnceCell;
+use tokio::task::JoinHandle;
+
+use crate::manager::{CatalogManagerRef, ClusterManagerRef, MetaSrvEnv};
+use crate::rpc::{META_CF_NAME, META_LEADER_KEY};
+use crate::storage::{MetaStore, MetaStoreError, Transaction, DEFAULT_COLUMN_FAMILY};
+use crate::MetaResult;
+
+/// Where the telemetry reports are sent to by default.
+pub const DEFAULT_TELEMETRY_REPORT_URL: &str = "https://telemetry.risingwave.dev/api/v1/report";
+
+/// Interval of sending telemetry reports.
+pub const TELEMETRY_REPORT_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+
+const TELEMETRY_TRACKING_ID_KEY: &[u8] = b"telemetry_tracking_id";
+
+/// The anonymous report sent by telemetry. It contains no user data, e.g. names of relations or
+/// addresses of nodes.
+#[derive(Debug, Serialize)]
+pub struct TelemetryReport {
+    /// A random id generated when the cluster is created, used to deduplicate reports.
+    pub tracking_id: String,
+    pub version: String,
+    pub uptime_secs: u64,
+    pub node_count: NodeCount,
+    pub feature_usage: FeatureUsage,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct NodeCount {
+    pub compute: usize,
+    pub frontend: usize,
+    pub compactor: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct FeatureUsage {
+    pub databases: usize,
+    pub tables: usize,
+    pub materialized_views: usize,
+    pub indexes: usize,
+    pub sources: usize,
+    pub sinks: usize,
+    pub views: usize,
+}
+
+pub type TelemetryReporterRef<S> = Arc<TelemetryReporter<S>>;
+
+/// `TelemetryReporter` collects the cluster size, version and feature usage counters, and sends
+/// them periodically if telemetry is enabled.
+pub struct TelemetryReporter<S: MetaStore> {
+    env: MetaSrvEnv<S>,
+    cluster_manager: ClusterManagerRef<S>,
+    catalog_manager: CatalogManagerRef<S>,
+    start_time: Instant,
+    tracking_id: OnceCell<String>,
+}
+
+impl<S> TelemetryReporter<S>
+where
+    S: MetaStore,
+{
+    pub fn new(
+        env: MetaSrvEnv<S>,
+        cluster_manager: ClusterManagerRef<S>,
+        catalog_manager: CatalogManagerRef<S>,
+    ) -> Self {
+        Self {
+            env,
+            cluster_manager,
+            catalog_manager,
+            start_time: Instant::now(),
+            tracking_id: OnceCell::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.env.opts.telemetry_enabled
+    }
+
+    /// Builds the report that is sent in the next round.
+    pub async fn report(&self) -> MetaResult<TelemetryReport> {
+        let node_count = NodeCount {
+            compute: self.worker_count(WorkerType::ComputeNode).await,
+            frontend: self.worker_count(WorkerType::Frontend).await,
+            compactor: self.worker_count(WorkerType::Compactor).await,
+        };
+
+        let (databases, _, tables, sources, sinks, indexes, views, _, _) = self
+            .catalog_manager
+            .get_catalog_core_guard()
+            .await
+            .database
+            .get_catalog()
+            .await?;
+        let mut feature_usage = FeatureUsage {
+            databases: databases.len(),
+            indexes: indexes.len(),
+            sources: sources.len(),
+            sinks: sinks.len(),
+            views: views.len(),
+            ..Default::default()
+        };
+        for table in tables
+            .iter()
+            .filter(|table| !table.is_index && valid_table_name(&table.name))
+        {
+            if table.optional_associated_source_id.is_some() {
+                feature_usage.tables += 1;
+            } else {
+                feature_usage.materialized_views += 1;
+            }
+        }
+
+        Ok(TelemetryReport {
+            tracking_id: self
+                .tracking_id
+                .get_or_try_init(|| self.get_or_create_tracking_id())
+                .await?
+                .clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime_secs: self.start_time.elapsed().as_secs(),
+            node_count,
+            feature_usage,
+        })
+    }
+
+    /// Serializes the report in JSON, which is exactly the payload to send.
+    pub async fn report_json(&self) -> MetaResult<String> {
+        let report = self.report().await?;
+        serde_json::to_string_pretty(&report).map_err(|e| anyhow!(e).into())
+    }
+
+    async fn worker_count(&self, worker_type: WorkerType) -> usize {
+        self.cluster_manager
+            .list_worker_node(worker_type, None)
+            .await
+            .len()
+    }
+
+    async fn get_or_create_tracking_id(&self) -> MetaResult<String> {
+        let store = self.env.meta_store();
+        match store
+            .get_cf(DEFAULT_COLUMN_FAMILY, TELEMETRY_TRACKING_ID_KEY)
+            .await
+        {
+            Ok(bytes) => String::from_utf8(bytes).map_err(|e| anyhow!(e).into()),
+            Err(MetaStoreError::ItemNotFound(_)) => {
+                // Only the current leader can create the tracking id, so that a stale leader
+                // doesn't overwrite it with another one.
+                let tracking_id = uuid::Uuid::new_v4().to_string();
+                let mut txn = Transaction::default();
+                txn.check_equal(
+                    META_CF_NAME.to_owned(),
+                    META_LEADER_KEY.as_bytes().to_vec(),
+                    self.env.get_leader_info().encode_to_vec(),
+                );
+                txn.put(
+                    DEFAULT_COLUMN_FAMILY.to_owned(),
+                    TELEMETRY_TRACKING_ID_KEY.to_vec(),
+                    tracking_id.clone().into_bytes(),
+                );
+                store.txn(txn).await?;
+                Ok(tracking_id)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn send_report(&self, client: &reqwest::Client) -> anyhow::Result<()> {
+        let report = self.report_json().await?;
+        client
+            .post(&self.env.opts.telemetry_report_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(report)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    pub async fn start_telemetry_reporting(
+        reporter: TelemetryReporterRef<S>,
+        interval: Duration,
+    ) -> (JoinHandle<()>, Sender<()>) {
+        let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel();
+        let join_handle = tokio::spawn(async move {
+            let client = reqwest::Client::new();
+            let mut min_interval = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    // Wait for interval
+                    _ = min_interval.tick() => {},
+                    // Shutdown
+                    _ = &mut shutdown_rx => {
+                        tracing::info!("Telemetry reporter is stopped");
+                        return;
+                    }
+                }
+                // Telemetry is best effort, so failures are only logged.
+                if let Err(e) = reporter.send_report(&client).await {
+                    tracing::debug!("Failed to send telemetry report: {:?}", e);
+                }
+            }
+        });
+        (join_handle, shutdown_tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::common::HostAddress;
+    use risingwave_pb::meta::MetaLeaderInfo;
+
+    use super::*;
+    use crate::manager::{CatalogManager, ClusterManager};
+    use crate::storage::MemStore;
+
+    #[tokio::test]
+    async fn test_telemetry_report() {
+        let env = MetaSrvEnv::for_test().await;
+        let cluster_manager = Arc::new(
+            ClusterManager::new(env.clone(), Duration::new(0, 0))
+                .await
+                .unwrap(),
+        );
+        let catalog_manager = Arc::new(CatalogManager::new(env.clone()).await.unwrap());
+        cluster_manager
+            .add_worker_node(
+                WorkerType::ComputeNode,
+                HostAddress {
+                    host: "127.0.0.1".to_string(),
+                    port: 5688,
+                },
+                4,
+            )
+            .await
+            .unwrap();
+
+        let reporter = TelemetryReporter::<MemStore>::new(env, cluster_manager, catalog_manager);
+        assert!(!reporter.enabled());
+        let report = reporter.report().await.unwrap();
+        assert_eq!(report.node_count.compute, 1);
+        assert_eq!(report.node_count.frontend, 0);
+        // The default database is created on startup.
+        assert_eq!(report.feature_usage.databases, 1);
+        assert_eq!(report.feature_usage.materialized_views, 0);
+
+        // The tracking id is persisted.
+        let tracking_id = report.tracking_id;
+        assert_eq!(reporter.report().await.unwrap().tracking_id, tracking_id);
+    }
+
+    #[tokio::test]
+    async fn test_tracking_id_created_by_leader() {
+        let env = MetaSrvEnv::for_test().await;
+        let cluster_manager = Arc::new(
+            ClusterManager::new(env.clone(), Duration::new(0, 0))
+                .await
+                .unwrap(),
+        );
+        let catalog_manager = Arc::new(CatalogManager::new(env.clone()).await.unwrap());
+
+        // Another meta node has become the leader.
+        let new_leader = MetaLeaderInfo {
+            lease_id: 1,
+            node_address: "127.0.0.1:5690".to_string(),
+        };
+        env.meta_store()
+            .put_cf(
+                META_CF_NAME,
+                META_LEADER_KEY.as_bytes().to_vec(),
+                new_leader.encode_to_vec(),
+            )
+            .await
+            .unwrap();
+        let reporter = TelemetryReporter::<MemStore>::new(
+            env.clone(),
+            cluster_manager.clone(),
+            catalog_manager.clone(),
+        );
+        reporter.report().await.unwrap_err();
+        env.meta_store()
+            .get_cf(DEFAULT_COLUMN_FAMILY, TELEMETRY_TRACKING_ID_KEY)
+            .await
+            .unwrap_err();
+
+        // The tracking id created by the leader is shared by the reporters.
+        env.meta_store()
+            .put_cf(
+                META_CF_NAME,
+                META_LEADER_KEY.as_bytes().to_vec(),
+                env.get_leader_info().encode_to_vec(),
+            )
+            .await
+            .unwrap();
+        let tracking_id = reporter.report().await.unwrap().tracking_id;
+        let reporter = TelemetryReporter::<MemStore>::new(env, cluster_manager, catalog_manager);
+        assert_eq!(reporter.report().await.unwrap().tracking_id, tracking_id);
+    }
+}
@@ -36,6 +36,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         "source",
         "monitor_service",
         "health",
+        "udf",
     ];
     let protos: Vec<String> = proto_files
         .iter()
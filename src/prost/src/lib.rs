@@ -65,6 +65,9 @@ pub mod monitor_service;
 #[rustfmt::skip]
 #[cfg_attr(madsim, path = "sim/health.rs")]
 pub mod health;
+#[rustfmt::skip]
+#[cfg_attr(madsim, path = "sim/udf.rs")]
+pub mod udf;
 
 #[rustfmt::skip]
 #[path = "catalog.serde.rs"]
@@ -111,6 +114,9 @@ pub mod source_serde;
 #[rustfmt::skip]
 #[path = "monitor_service.serde.rs"]
 pub mod monitor_service_serde;
+#[rustfmt::skip]
+#[path = "udf.serde.rs"]
+pub mod udf_serde;
 
 
 #[derive(Clone, PartialEq, Eq, Debug)]
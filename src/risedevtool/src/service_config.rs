@@ -28,7 +28,6 @@ pub struct ComputeNodeConfig {
     pub listen_address: String,
     pub exporter_port: u16,
     pub async_stack_trace: String,
-    pub enable_managed_cache: bool,
     pub enable_tiered_cache: bool,
 
     pub provide_minio: Option<Vec<MinioConfig>>,
@@ -69,6 +68,7 @@ pub struct MetaNodeConfig {
     pub enable_committed_sst_sanity_check: bool,
     pub periodic_compaction_interval_sec: u64,
     pub enable_compaction_deterministic: bool,
+    pub disable_telemetry: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
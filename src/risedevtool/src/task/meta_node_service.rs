@@ -104,6 +104,10 @@ impl MetaNodeService {
             cmd.arg("--enable-committed-sst-sanity-check");
         }
 
+        if config.disable_telemetry {
+            cmd.arg("--disable-telemetry");
+        }
+
         Ok(())
     }
 }
@@ -111,6 +111,7 @@ impl ComputeClient {
         task_id: TaskId,
         plan: PlanFragment,
         epoch: u64,
+        collect_executor_stats: bool,
     ) -> Result<Streaming<TaskInfoResponse>> {
         Ok(self
             .task_client
@@ -119,6 +120,7 @@ impl ComputeClient {
                 task_id: Some(task_id),
                 plan: Some(plan),
                 epoch,
+                collect_executor_stats,
             })
             .await?
             .into_inner())
@@ -46,11 +46,13 @@ mod compute_client;
 mod hummock_meta_client;
 mod meta_client;
 mod stream_client;
+mod udf_client;
 
 pub use compute_client::{ComputeClient, ComputeClientPool, ComputeClientPoolRef};
 pub use hummock_meta_client::HummockMetaClient;
 pub use meta_client::MetaClient;
 pub use stream_client::{StreamClient, StreamClientPool, StreamClientPoolRef};
+pub use udf_client::UdfClient;
 
 #[async_trait]
 pub trait RpcClient: Send + Sync + 'static + Clone {
@@ -25,11 +25,14 @@ use risingwave_hummock_sdk::{
     SstIdRange,
 };
 use risingwave_pb::catalog::{
-    Database as ProstDatabase, Index as ProstIndex, Schema as ProstSchema, Sink as ProstSink,
-    Source as ProstSource, Table as ProstTable, View as ProstView,
+    Database as ProstDatabase, Function as ProstFunction, Index as ProstIndex,
+    Schema as ProstSchema, Secret as ProstSecret, Sink as ProstSink, Source as ProstSource,
+    Table as ProstTable, TableStatistics as ProstTableStatistics, View as ProstView,
 };
 use risingwave_pb::common::WorkerType;
+use risingwave_pb::ddl_service::comment_on_request::OptionalColumnId;
 use risingwave_pb::ddl_service::ddl_service_client::DdlServiceClient;
+use risingwave_pb::ddl_service::reset_source_offsets_request::Target as ResetSourceOffsetsTarget;
 use risingwave_pb::ddl_service::*;
 use risingwave_pb::hummock::hummock_manager_service_client::HummockManagerServiceClient;
 use risingwave_pb::hummock::rise_ctl_update_compaction_config_request::mutable_config::MutableConfig;
@@ -41,7 +44,11 @@ use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
 use risingwave_pb::meta::notification_service_client::NotificationServiceClient;
 use risingwave_pb::meta::reschedule_request::Reschedule as ProstReschedule;
 use risingwave_pb::meta::scale_service_client::ScaleServiceClient;
+use risingwave_pb::meta::sink_coordination_service_client::SinkCoordinationServiceClient;
+use risingwave_pb::meta::sink_prepared_commit::Decision;
 use risingwave_pb::meta::stream_manager_service_client::StreamManagerServiceClient;
+use risingwave_pb::meta::system_params_service_client::SystemParamsServiceClient;
+use risingwave_pb::meta::telemetry_service_client::TelemetryServiceClient;
 use risingwave_pb::meta::*;
 use risingwave_pb::stream_plan::StreamFragmentGraph;
 use risingwave_pb::user::update_user_request::UpdateField;
@@ -231,6 +238,22 @@ impl MetaClient {
         Ok((resp.view_id, resp.version))
     }
 
+    pub async fn create_function(&self, function: ProstFunction) -> Result<(u32, CatalogVersion)> {
+        let request = CreateFunctionRequest {
+            function: Some(function),
+        };
+        let resp = self.inner.create_function(request).await?;
+        Ok((resp.function_id, resp.version))
+    }
+
+    pub async fn create_secret(&self, secret: ProstSecret) -> Result<(u32, CatalogVersion)> {
+        let request = CreateSecretRequest {
+            secret: Some(secret),
+        };
+        let resp = self.inner.create_secret(request).await?;
+        Ok((resp.secret_id, resp.version))
+    }
+
     pub async fn create_index(
         &self,
         index: ProstIndex,
@@ -267,6 +290,18 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    pub async fn drop_function(&self, function_id: u32) -> Result<CatalogVersion> {
+        let request = DropFunctionRequest { function_id };
+        let resp = self.inner.drop_function(request).await?;
+        Ok(resp.version)
+    }
+
+    pub async fn drop_secret(&self, secret_id: u32) -> Result<CatalogVersion> {
+        let request = DropSecretRequest { secret_id };
+        let resp = self.inner.drop_secret(request).await?;
+        Ok(resp.version)
+    }
+
     pub async fn drop_source(&self, source_id: u32) -> Result<CatalogVersion> {
         let request = DropSourceRequest { source_id };
         let resp = self.inner.drop_source(request).await?;
@@ -287,6 +322,102 @@ impl MetaClient {
         Ok(resp.version)
     }
 
+    /// Returns the catalog version, and the ids of the materialized views among
+    /// `select_star_mview_ids` flagged as stale by the change.
+    pub async fn alter_table(
+        &self,
+        source: ProstSource,
+        table: ProstTable,
+        select_star_mview_ids: Vec<u32>,
+        mview_plans: Vec<ReplannedMview>,
+    ) -> Result<(CatalogVersion, Vec<u32>)> {
+        let request = AlterTableRequest {
+            source: Some(source),
+            table: Some(table),
+            select_star_mview_ids,
+            mview_plans,
+        };
+        let resp = self.inner.alter_table(request).await?;
+        Ok((resp.version, resp.stale_mview_ids))
+    }
+
+    /// Returns the catalog version, and the ids of the materialized views among
+    /// `select_star_mview_ids` flagged as stale by the change.
+    pub async fn alter_source(
+        &self,
+        source: ProstSource,
+        select_star_mview_ids: Vec<u32>,
+        mview_plans: Vec<ReplannedMview>,
+    ) -> Result<(CatalogVersion, Vec<u32>)> {
+        let request = AlterSourceRequest {
+            source: Some(source),
+            select_star_mview_ids,
+            mview_plans,
+        };
+        let resp = self.inner.alter_source(request).await?;
+        Ok((resp.version, resp.stale_mview_ids))
+    }
+
+    pub async fn reset_source_offsets(
+        &self,
+        source_id: u32,
+        target: ResetSourceOffsetsTarget,
+        timestamp_millis: i64,
+    ) -> Result<()> {
+        let request = ResetSourceOffsetsRequest {
+            source_id,
+            target: target as i32,
+            timestamp_millis,
+        };
+        self.inner.reset_source_offsets(request).await?;
+        Ok(())
+    }
+
+    pub async fn alter_backfill_rate_limit(
+        &self,
+        database_id: u32,
+        schema_id: u32,
+        name: String,
+        rows_per_second: u64,
+    ) -> Result<()> {
+        let request = AlterBackfillRateLimitRequest {
+            database_id,
+            schema_id,
+            name,
+            rows_per_second,
+        };
+        self.inner.alter_backfill_rate_limit(request).await?;
+        Ok(())
+    }
+
+    pub async fn update_table_statistics(
+        &self,
+        table_id: u32,
+        statistics: ProstTableStatistics,
+    ) -> Result<CatalogVersion> {
+        let request = UpdateTableStatisticsRequest {
+            table_id,
+            statistics: Some(statistics),
+        };
+        let resp = self.inner.update_table_statistics(request).await?;
+        Ok(resp.version)
+    }
+
+    pub async fn comment_on(
+        &self,
+        table_id: u32,
+        column_id: Option<i32>,
+        comment: String,
+    ) -> Result<CatalogVersion> {
+        let request = CommentOnRequest {
+            table_id,
+            optional_column_id: column_id.map(OptionalColumnId::ColumnId),
+            comment,
+        };
+        let resp = self.inner.comment_on(request).await?;
+        Ok(resp.version)
+    }
+
     pub async fn drop_database(&self, database_id: u32) -> Result<CatalogVersion> {
         let request = DropDatabaseRequest { database_id };
         let resp = self.inner.drop_database(request).await?;
@@ -376,6 +507,86 @@ impl MetaClient {
         Ok(())
     }
 
+    /// Cordons or uncordons the given compute nodes.
+    pub async fn update_schedulability(
+        &self,
+        worker_ids: &[u32],
+        unschedulable: bool,
+    ) -> Result<()> {
+        let request = UpdateWorkerNodeSchedulabilityRequest {
+            worker_ids: worker_ids.to_vec(),
+            unschedulable,
+        };
+        self.inner
+            .update_worker_node_schedulability(request)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn set_system_param(&self, param: String, value: String) -> Result<()> {
+        let request = SetSystemParamRequest { param, value };
+        self.inner.set_system_param(request).await?;
+        Ok(())
+    }
+
+    pub async fn get_system_params(&self) -> Result<SystemParams> {
+        let request = GetSystemParamsRequest {};
+        let resp = self.inner.get_system_params(request).await?;
+        Ok(resp.params.unwrap())
+    }
+
+    pub async fn get_telemetry_report(&self) -> Result<GetTelemetryReportResponse> {
+        let request = GetTelemetryReportRequest {};
+        let resp = self.inner.get_telemetry_report(request).await?;
+        Ok(resp)
+    }
+
+    /// Reports the transaction to be prepared by an actor of a two-phase sink at a checkpoint.
+    pub async fn pre_commit_sink(&self, commit: SinkPreparedCommit) -> Result<()> {
+        let request = PreCommitSinkRequest {
+            commit: Some(commit),
+        };
+        self.inner.pre_commit_sink(request).await?;
+        Ok(())
+    }
+
+    /// Waits until meta decides the transaction prepared by the actor of the sink at `epoch`.
+    pub async fn get_sink_commit_decision(
+        &self,
+        sink_id: u32,
+        actor_id: u32,
+        epoch: u64,
+    ) -> Result<Decision> {
+        let request = GetSinkCommitDecisionRequest {
+            sink_id,
+            actor_id,
+            epoch,
+        };
+        let resp = self.inner.get_sink_commit_decision(request).await?;
+        Ok(resp.decision())
+    }
+
+    pub async fn finish_sink_commit(&self, sink_id: u32, actor_id: u32, epoch: u64) -> Result<()> {
+        let request = FinishSinkCommitRequest {
+            sink_id,
+            actor_id,
+            epoch,
+        };
+        self.inner.finish_sink_commit(request).await?;
+        Ok(())
+    }
+
+    /// Claims the decided transactions of the sink left by the actors not running anymore.
+    pub async fn recover_sink(
+        &self,
+        sink_id: u32,
+        actor_id: u32,
+    ) -> Result<Vec<SinkPreparedCommit>> {
+        let request = RecoverSinkRequest { sink_id, actor_id };
+        let resp = self.inner.recover_sink(request).await?;
+        Ok(resp.commits)
+    }
+
     /// Starts a heartbeat worker.
     ///
     /// When sending heartbeat RPC, it also carries extra info from `extra_info_sources`.
@@ -432,6 +643,24 @@ impl MetaClient {
         Ok(resp.tables)
     }
 
+    pub async fn get_ddl_progress(&self) -> Result<Vec<DdlProgress>> {
+        let request = GetDdlProgressRequest {};
+        let resp = self.inner.get_ddl_progress(request).await?;
+        Ok(resp.ddl_progress)
+    }
+
+    pub async fn cancel_creating_jobs(&self, job_ids: Vec<u32>) -> Result<()> {
+        let request = CancelCreatingJobsRequest { job_ids };
+        self.inner.cancel_creating_jobs(request).await?;
+        Ok(())
+    }
+
+    pub async fn get_table_sizes(&self) -> Result<HashMap<u32, u64>> {
+        let request = GetTableSizesRequest {};
+        let resp = self.inner.get_table_sizes(request).await?;
+        Ok(resp.table_sizes)
+    }
+
     pub async fn flush(&self, checkpoint: bool) -> Result<HummockSnapshot> {
         let request = FlushRequest { checkpoint };
         let resp = self.inner.flush(request).await?;
@@ -607,6 +836,19 @@ impl MetaClient {
         let _resp = self.inner.rise_ctl_update_compaction_config(req).await?;
         Ok(())
     }
+
+    pub async fn get_compaction_backlog(&self) -> Result<GetCompactionBacklogResponse> {
+        let req = GetCompactionBacklogRequest {};
+        let resp = self.inner.get_compaction_backlog(req).await?;
+        Ok(resp)
+    }
+
+    /// Returns false if no assigned compaction task with `task_id` is found.
+    pub async fn risectl_cancel_compact_task(&self, task_id: u64) -> Result<bool> {
+        let req = RiseCtlCancelCompactTaskRequest { task_id };
+        let resp = self.inner.rise_ctl_cancel_compact_task(req).await?;
+        Ok(resp.canceled)
+    }
 }
 
 #[async_trait]
@@ -775,6 +1017,9 @@ struct GrpcMetaClient {
     stream_client: StreamManagerServiceClient<Channel>,
     user_client: UserServiceClient<Channel>,
     scale_client: ScaleServiceClient<Channel>,
+    system_params_client: SystemParamsServiceClient<Channel>,
+    telemetry_client: TelemetryServiceClient<Channel>,
+    sink_coordination_client: SinkCoordinationServiceClient<Channel>,
 }
 
 impl GrpcMetaClient {
@@ -821,7 +1066,10 @@ impl GrpcMetaClient {
         let notification_client = NotificationServiceClient::new(channel.clone());
         let stream_client = StreamManagerServiceClient::new(channel.clone());
         let user_client = UserServiceClient::new(channel.clone());
-        let scale_client = ScaleServiceClient::new(channel);
+        let scale_client = ScaleServiceClient::new(channel.clone());
+        let system_params_client = SystemParamsServiceClient::new(channel.clone());
+        let telemetry_client = TelemetryServiceClient::new(channel.clone());
+        let sink_coordination_client = SinkCoordinationServiceClient::new(channel);
         Ok(Self {
             cluster_client,
             heartbeat_client,
@@ -831,6 +1079,9 @@ impl GrpcMetaClient {
             stream_client,
             user_client,
             scale_client,
+            system_params_client,
+            telemetry_client,
+            sink_coordination_client,
         })
     }
 }
@@ -841,6 +1092,7 @@ macro_rules! for_all_meta_rpc {
              { cluster_client, add_worker_node, AddWorkerNodeRequest, AddWorkerNodeResponse }
             ,{ cluster_client, activate_worker_node, ActivateWorkerNodeRequest, ActivateWorkerNodeResponse }
             ,{ cluster_client, delete_worker_node, DeleteWorkerNodeRequest, DeleteWorkerNodeResponse }
+            ,{ cluster_client, update_worker_node_schedulability, UpdateWorkerNodeSchedulabilityRequest, UpdateWorkerNodeSchedulabilityResponse }
             //(not used) ,{ cluster_client, list_all_nodes, ListAllNodesRequest, ListAllNodesResponse }
             ,{ heartbeat_client, heartbeat, HeartbeatRequest, HeartbeatResponse }
             ,{ stream_client, flush, FlushRequest, FlushResponse }
@@ -856,14 +1108,27 @@ macro_rules! for_all_meta_rpc {
             ,{ ddl_client, drop_materialized_source, DropMaterializedSourceRequest, DropMaterializedSourceResponse }
             ,{ ddl_client, drop_materialized_view, DropMaterializedViewRequest, DropMaterializedViewResponse }
             ,{ ddl_client, drop_view, DropViewRequest, DropViewResponse }
+            ,{ ddl_client, alter_table, AlterTableRequest, AlterTableResponse }
+            ,{ ddl_client, alter_source, AlterSourceRequest, AlterSourceResponse }
+            ,{ ddl_client, reset_source_offsets, ResetSourceOffsetsRequest, ResetSourceOffsetsResponse }
+            ,{ ddl_client, alter_backfill_rate_limit, AlterBackfillRateLimitRequest, AlterBackfillRateLimitResponse }
+            ,{ ddl_client, update_table_statistics, UpdateTableStatisticsRequest, UpdateTableStatisticsResponse }
+            ,{ ddl_client, comment_on, CommentOnRequest, CommentOnResponse }
+            ,{ ddl_client, create_function, CreateFunctionRequest, CreateFunctionResponse }
+            ,{ ddl_client, drop_function, DropFunctionRequest, DropFunctionResponse }
+            ,{ ddl_client, create_secret, CreateSecretRequest, CreateSecretResponse }
+            ,{ ddl_client, drop_secret, DropSecretRequest, DropSecretResponse }
             ,{ ddl_client, drop_source, DropSourceRequest, DropSourceResponse }
             ,{ ddl_client, drop_sink, DropSinkRequest, DropSinkResponse }
             ,{ ddl_client, drop_database, DropDatabaseRequest, DropDatabaseResponse }
             ,{ ddl_client, drop_schema, DropSchemaRequest, DropSchemaResponse }
             ,{ ddl_client, drop_index, DropIndexRequest, DropIndexResponse }
             ,{ ddl_client, risectl_list_state_tables, RisectlListStateTablesRequest, RisectlListStateTablesResponse }
+            ,{ ddl_client, get_ddl_progress, GetDdlProgressRequest, GetDdlProgressResponse }
+            ,{ ddl_client, cancel_creating_jobs, CancelCreatingJobsRequest, CancelCreatingJobsResponse }
             ,{ hummock_client, unpin_version_before, UnpinVersionBeforeRequest, UnpinVersionBeforeResponse }
             ,{ hummock_client, get_current_version, GetCurrentVersionRequest, GetCurrentVersionResponse }
+            ,{ hummock_client, get_table_sizes, GetTableSizesRequest, GetTableSizesResponse }
             ,{ hummock_client, reset_current_version, ResetCurrentVersionRequest, ResetCurrentVersionResponse }
             ,{ hummock_client, replay_version_delta, ReplayVersionDeltaRequest, ReplayVersionDeltaResponse }
             ,{ hummock_client, list_version_deltas, ListVersionDeltasRequest, ListVersionDeltasResponse }
@@ -888,6 +1153,8 @@ macro_rules! for_all_meta_rpc {
             ,{ hummock_client, rise_ctl_get_pinned_snapshots_summary, RiseCtlGetPinnedSnapshotsSummaryRequest, RiseCtlGetPinnedSnapshotsSummaryResponse }
             ,{ hummock_client, rise_ctl_list_compaction_group, RiseCtlListCompactionGroupRequest, RiseCtlListCompactionGroupResponse }
             ,{ hummock_client, rise_ctl_update_compaction_config, RiseCtlUpdateCompactionConfigRequest, RiseCtlUpdateCompactionConfigResponse }
+            ,{ hummock_client, get_compaction_backlog, GetCompactionBacklogRequest, GetCompactionBacklogResponse }
+            ,{ hummock_client, rise_ctl_cancel_compact_task, RiseCtlCancelCompactTaskRequest, RiseCtlCancelCompactTaskResponse }
             ,{ hummock_client, init_metadata_for_replay, InitMetadataForReplayRequest, InitMetadataForReplayResponse }
             ,{ user_client, create_user, CreateUserRequest, CreateUserResponse }
             ,{ user_client, update_user, UpdateUserRequest, UpdateUserResponse }
@@ -899,6 +1166,13 @@ macro_rules! for_all_meta_rpc {
             ,{ scale_client, get_cluster_info, GetClusterInfoRequest, GetClusterInfoResponse }
             ,{ scale_client, reschedule, RescheduleRequest, RescheduleResponse }
             ,{ notification_client, subscribe, SubscribeRequest, Streaming<SubscribeResponse> }
+            ,{ system_params_client, set_system_param, SetSystemParamRequest, SetSystemParamResponse }
+            ,{ system_params_client, get_system_params, GetSystemParamsRequest, GetSystemParamsResponse }
+            ,{ telemetry_client, get_telemetry_report, GetTelemetryReportRequest, GetTelemetryReportResponse }
+            ,{ sink_coordination_client, pre_commit_sink, PreCommitSinkRequest, PreCommitSinkResponse }
+            ,{ sink_coordination_client, get_sink_commit_decision, GetSinkCommitDecisionRequest, GetSinkCommitDecisionResponse }
+            ,{ sink_coordination_client, finish_sink_commit, FinishSinkCommitRequest, FinishSinkCommitResponse }
+            ,{ sink_coordination_client, recover_sink, RecoverSinkRequest, RecoverSinkResponse }
         }
     };
 }
@@ -0,0 +1,121 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{ArrayRef, DataChunk};
+use risingwave_pb::udf::udf_service_client::UdfServiceClient;
+use risingwave_pb::udf::EvalRequest;
+use tokio::sync::OnceCell;
+use tokio_retry::strategy::{jitter, ExponentialBackoff};
+use tonic::transport::{Channel, Endpoint};
+use tonic::Code;
+
+use crate::error::{Result, RpcError};
+
+/// Client to an external UDF server, which evaluates user-defined functions in batches of rows.
+///
+/// The connection is established on the first call, and an `Eval` request failing with a
+/// transient error (unavailable server or timeout) is retried with exponential backoff.
+pub struct UdfClient {
+    link: String,
+    client: OnceCell<UdfServiceClient<Channel>>,
+}
+
+impl UdfClient {
+    // Timeout for connecting to the UDF server.
+    const CONNECT_TIMEOUT_SEC: u64 = 5;
+    // Max retry times for a failed `Eval` request.
+    const EVAL_MAX_RETRIES: usize = 3;
+    // Retry base interval in ms for a failed `Eval` request.
+    const EVAL_RETRY_BASE_INTERVAL_MS: u64 = 100;
+    // Timeout for a single `Eval` request.
+    const EVAL_TIMEOUT_SEC: u64 = 30;
+
+    /// Creates a client to the UDF server at `link`, e.g. `http://localhost:8815`.
+    pub fn new(link: &str) -> Self {
+        Self {
+            link: link.to_string(),
+            client: OnceCell::new(),
+        }
+    }
+
+    async fn client(&self) -> Result<UdfServiceClient<Channel>> {
+        let client = self
+            .client
+            .get_or_try_init(|| async {
+                let channel = Endpoint::from_shared(self.link.clone())?
+                    .connect_timeout(Duration::from_secs(Self::CONNECT_TIMEOUT_SEC))
+                    .timeout(Duration::from_secs(Self::EVAL_TIMEOUT_SEC))
+                    .connect()
+                    .await?;
+                Ok::<_, RpcError>(UdfServiceClient::new(channel))
+            })
+            .await?;
+        Ok(client.clone())
+    }
+
+    /// Evaluates the function `identifier` over each row of `input`, whose columns are the
+    /// arguments.
+    pub async fn eval(&self, identifier: &str, input: &DataChunk) -> Result<ArrayRef> {
+        let client = self.client().await?;
+        let request = EvalRequest {
+            identifier: identifier.to_string(),
+            input: Some(input.to_protobuf()),
+        };
+        let retry_strategy = ExponentialBackoff::from_millis(Self::EVAL_RETRY_BASE_INTERVAL_MS)
+            .map(jitter)
+            .take(Self::EVAL_MAX_RETRIES);
+        let response = tokio_retry::RetryIf::spawn(
+            retry_strategy,
+            || {
+                let mut client = client.clone();
+                let request = request.clone();
+                async move { client.eval(request).await }
+            },
+            |status: &tonic::Status| {
+                let retryable = matches!(status.code(), Code::Unavailable | Code::DeadlineExceeded);
+                if retryable {
+                    tracing::warn!(
+                        "failed to call UDF {} at {}, retrying: {}",
+                        identifier,
+                        self.link,
+                        status
+                    );
+                }
+                retryable
+            },
+        )
+        .await?
+        .into_inner();
+
+        let output = response
+            .output
+            .ok_or_else(|| anyhow::anyhow!("UDF {} returned no output", identifier))?;
+        let column = Column::from_protobuf(&output, input.cardinality()).map_err(|e| {
+            anyhow::anyhow!("failed to decode the output of UDF {}: {}", identifier, e)
+        })?;
+        if column.len() != input.cardinality() {
+            return Err(anyhow::anyhow!(
+                "UDF {} returned {} rows, but {} are expected",
+                identifier,
+                column.len(),
+                input.cardinality()
+            )
+            .into());
+        }
+        Ok(column.into_inner())
+    }
+}
@@ -59,6 +59,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
             data_type: DataType::Int32,
             column_id: ColumnId::from(0),
             skip_parse: false,
+            meta_kind: None,
             fields: vec![],
         },
         SourceColumnDesc {
@@ -66,6 +67,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
             data_type: DataType::Boolean,
             column_id: ColumnId::from(2),
             skip_parse: false,
+            meta_kind: None,
             fields: vec![],
         },
         SourceColumnDesc {
@@ -73,6 +75,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
             data_type: DataType::Int16,
             column_id: ColumnId::from(3),
             skip_parse: false,
+            meta_kind: None,
             fields: vec![],
         },
         SourceColumnDesc {
@@ -80,6 +83,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
             data_type: DataType::Int64,
             column_id: ColumnId::from(4),
             skip_parse: false,
+            meta_kind: None,
             fields: vec![],
         },
         SourceColumnDesc {
@@ -87,6 +91,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
             data_type: DataType::Float32,
             column_id: ColumnId::from(5),
             skip_parse: false,
+            meta_kind: None,
             fields: vec![],
         },
         SourceColumnDesc {
@@ -94,6 +99,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
             data_type: DataType::Float64,
             column_id: ColumnId::from(6),
             skip_parse: false,
+            meta_kind: None,
             fields: vec![],
         },
         SourceColumnDesc {
@@ -101,6 +107,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
             data_type: DataType::Varchar,
             column_id: ColumnId::from(7),
             skip_parse: false,
+            meta_kind: None,
             fields: vec![],
         },
         SourceColumnDesc {
@@ -108,6 +115,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
             data_type: DataType::Date,
             column_id: ColumnId::from(8),
             skip_parse: false,
+            meta_kind: None,
             fields: vec![],
         },
         SourceColumnDesc {
@@ -115,6 +123,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
             data_type: DataType::Timestamp,
             column_id: ColumnId::from(9),
             skip_parse: false,
+            meta_kind: None,
             fields: vec![],
         },
     ]
@@ -122,7 +131,7 @@ fn get_descs() -> Vec<SourceColumnDesc> {
 
 fn bench_json_parser(c: &mut Criterion) {
     let descs = get_descs();
-    let parser = JsonParser {};
+    let parser = JsonParser::default();
     let records = generate_all_json();
     c.bench_function("json_parser", |b| {
         b.iter(|| {
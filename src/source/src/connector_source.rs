@@ -21,8 +21,12 @@ use futures::StreamExt;
 use futures_async_stream::try_stream;
 use itertools::Itertools;
 use risingwave_common::catalog::{ColumnId, TableId};
+use risingwave_common::error::ErrorCode::ConnectorError;
 use risingwave_common::error::{internal_error, Result, RwError, ToRwResult};
 use risingwave_common::util::select_all;
+use risingwave_connector::source::dead_letter::{
+    DeadLetterQueue, ParseErrorConfig, ParseErrorPolicy,
+};
 use risingwave_connector::source::{
     Column, ConnectorProperties, ConnectorState, SourceMessage, SplitId, SplitMetaData,
     SplitReaderImpl,
@@ -67,6 +71,7 @@ struct InnerConnectorSourceReader {
 pub struct ConnectorSourceReader {
     parser: Arc<SourceParserImpl>,
     columns: Vec<SourceColumnDesc>,
+    parse_error_handler: ParseErrorHandler,
 
     // merge all streams of inner reader into one
     // TODO: make this static dispatch instead of box
@@ -133,6 +138,79 @@ impl InnerConnectorSourceReader {
     }
 }
 
+/// Handles the messages failing to be parsed according to the [`ParseErrorPolicy`] of the source.
+struct ParseErrorHandler {
+    policy: ParseErrorPolicy,
+    dead_letter_queue: Option<DeadLetterQueue>,
+
+    metrics: Arc<SourceMetrics>,
+    context: SourceContext,
+}
+
+impl ParseErrorHandler {
+    async fn new(
+        config: &ParseErrorConfig,
+        metrics: Arc<SourceMetrics>,
+        context: SourceContext,
+    ) -> Result<Self> {
+        let dead_letter_queue = match &config.dead_letter {
+            Some(dead_letter) => Some(
+                DeadLetterQueue::new(dead_letter)
+                    .await
+                    .map_err(|e| RwError::from(ConnectorError(e.into())))?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            policy: config.policy,
+            dead_letter_queue,
+            metrics,
+            context,
+        })
+    }
+
+    /// Returns an error if the source should be failed by the message.
+    async fn handle(&self, msg: &SourceMessage, payload: &[u8], error: RwError) -> Result<()> {
+        self.metrics
+            .parse_error_count
+            .with_label_values(&[
+                &self.context.actor_id.to_string(),
+                &self.context.source_id.to_string(),
+                self.policy.as_str(),
+            ])
+            .inc();
+
+        match self.policy {
+            ParseErrorPolicy::Skip => {
+                tracing::warn!("message parsing failed {}, skipping", error);
+                Ok(())
+            }
+            ParseErrorPolicy::DeadLetter => {
+                tracing::warn!(
+                    "message parsing failed {}, sending to dead letter queue",
+                    error
+                );
+                self.dead_letter_queue
+                    .as_ref()
+                    .unwrap()
+                    .send(
+                        self.context.source_id.table_id(),
+                        &msg.split_id,
+                        &msg.offset,
+                        payload,
+                        &error.to_string(),
+                    )
+                    .await
+                    .map_err(|e| RwError::from(ConnectorError(e.into())))
+            }
+            ParseErrorPolicy::Fail => Err(internal_error(format!(
+                "failed to parse message of split {} at offset {}: {}",
+                msg.split_id, msg.offset, error
+            ))),
+        }
+    }
+}
+
 impl ConnectorSourceReader {
     #[try_stream(boxed, ok = StreamChunkWithState, error = RwError)]
     pub async fn into_stream(self) {
@@ -144,13 +222,22 @@ impl ConnectorSourceReader {
             let mut split_offset_mapping: HashMap<SplitId, String> = HashMap::new();
 
             for msg in batch {
-                if let Some(content) = msg.payload {
-                    split_offset_mapping.insert(msg.split_id, msg.offset);
-                    if let Err(e) = self.parser.parse(content.as_ref(), builder.row_writer()) {
-                        tracing::warn!("message parsing failed {}, skipping", e.to_string());
-                        continue;
+                if let Some(content) = &msg.payload {
+                    let result = match self.parser.prepare(content.as_ref()).await {
+                        Ok(()) => self
+                            .parser
+                            .parse(content.as_ref(), builder.row_writer().with_message(&msg))
+                            .map(|_| ()),
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        self.parse_error_handler
+                            .handle(&msg, content.as_ref(), e)
+                            .await?;
                     }
                 }
+                // The messages without payload, e.g. heartbeats, still advance the offsets.
+                split_offset_mapping.insert(msg.split_id, msg.offset);
             }
             yield StreamChunkWithState {
                 chunk: builder.finish(),
@@ -165,6 +252,7 @@ pub struct ConnectorSource {
     pub config: ConnectorProperties,
     pub columns: Vec<SourceColumnDesc>,
     pub parser: Arc<SourceParserImpl>,
+    pub parse_error: ParseErrorConfig,
     pub connector_message_buffer_size: usize,
 }
 
@@ -197,6 +285,8 @@ impl ConnectorSource {
         let config = self.config.clone();
         let columns = self.get_target_columns(column_ids)?;
         let source_metrics = metrics.clone();
+        let parse_error_handler =
+            ParseErrorHandler::new(&self.parse_error, metrics.clone(), context.clone()).await?;
 
         let to_reader_splits = match splits {
             Some(vec_split_impl) => vec_split_impl
@@ -223,6 +313,7 @@ impl ConnectorSource {
         Ok(ConnectorSourceReader {
             parser: self.parser.clone(),
             columns,
+            parse_error_handler,
             stream,
         })
     }
@@ -54,6 +54,8 @@ pub enum SourceFormat {
     DebeziumJson,
     Avro,
     Maxwell,
+    CanalJson,
+    Csv,
 }
 
 #[derive(Debug, EnumAsInner)]
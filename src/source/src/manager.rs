@@ -23,8 +23,9 @@ use risingwave_common::error::ErrorCode::{ConnectorError, InternalError, Protoco
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::try_match_expand;
 use risingwave_common::types::DataType;
+use risingwave_connector::source::dead_letter::ParseErrorConfig;
 use risingwave_connector::source::ConnectorProperties;
-use risingwave_pb::catalog::ColumnIndex as ProstColumnIndex;
+use risingwave_pb::catalog::{ColumnIndex as ProstColumnIndex, SourceMetaKind};
 use risingwave_pb::plan_common::{ColumnCatalog as ProstColumnCatalog, RowFormatType};
 use risingwave_pb::stream_plan::source_node::Info as ProstSourceInfo;
 
@@ -43,8 +44,11 @@ pub struct SourceColumnDesc {
     pub data_type: DataType,
     pub column_id: ColumnId,
     pub fields: Vec<ColumnDesc>,
-    /// Now `skip_parse` is used to indicate whether the column is a row id column.
+    /// Whether the column is not parsed from the payload, e.g. the row id column and the meta
+    /// columns.
     pub skip_parse: bool,
+    /// The kind of the message metadata held by the column, if it's included by `INCLUDE`.
+    pub meta_kind: Option<SourceMetaKind>,
 }
 
 impl SourceColumnDesc {
@@ -61,6 +65,7 @@ impl SourceColumnDesc {
             column_id,
             fields: vec![],
             skip_parse: false,
+            meta_kind: None,
         }
     }
 }
@@ -73,6 +78,7 @@ impl From<&ColumnDesc> for SourceColumnDesc {
             column_id: c.column_id,
             fields: c.field_descs.clone(),
             skip_parse: false,
+            meta_kind: None,
         }
     }
 }
@@ -226,6 +232,11 @@ impl SourceDescBuilder {
         }
     }
 
+    /// Replace the columns to read, after the columns are added to the connector source.
+    pub fn set_columns(&mut self, columns: Vec<ProstColumnCatalog>) {
+        self.columns = columns;
+    }
+
     pub async fn build(&self) -> Result<SourceDescRef> {
         match &self.info {
             ProstSourceInfo::TableSource(_) => self.build_table_source(),
@@ -233,6 +244,12 @@ impl SourceDescBuilder {
         }
     }
 
+    /// Build the DML channel of a connector source, which is registered in the manager the same
+    /// way as a table source, so that the DML statements can write to it.
+    pub fn build_dml_source(&self) -> Result<SourceDescRef> {
+        self.build_table_source()
+    }
+
     fn build_table_source(&self) -> Result<SourceDescRef> {
         self.source_manager.insert_source(
             self.source_id,
@@ -250,6 +267,8 @@ impl SourceDescBuilder {
             RowFormatType::DebeziumJson => SourceFormat::DebeziumJson,
             RowFormatType::Avro => SourceFormat::Avro,
             RowFormatType::Maxwell => SourceFormat::Maxwell,
+            RowFormatType::CanalJson => SourceFormat::CanalJson,
+            RowFormatType::Csv => SourceFormat::Csv,
             RowFormatType::RowUnspecified => unreachable!(),
         };
 
@@ -258,9 +277,13 @@ impl SourceDescBuilder {
                 "protobuf file location not provided".to_string(),
             )));
         }
-        let source_parser_rs =
-            SourceParserImpl::create(&format, &self.properties, info.row_schema_location.as_str())
-                .await;
+        let source_parser_rs = SourceParserImpl::create(
+            &format,
+            &self.properties,
+            info.row_schema_location.as_str(),
+            info.use_schema_registry,
+        )
+        .await;
         let parser = if let Ok(source_parser) = source_parser_rs {
             source_parser
         } else {
@@ -276,6 +299,12 @@ impl SourceDescBuilder {
             columns[row_id_index.index as usize].skip_parse = true;
             row_id_index.index as usize
         });
+        for column in &mut columns {
+            if let Some(kind) = info.meta_columns.get(&column.column_id.get_id()) {
+                column.skip_parse = true;
+                column.meta_kind = SourceMetaKind::from_i32(*kind);
+            }
+        }
         assert!(
             !self.pk_column_ids.is_empty(),
             "source should have at least one pk column"
@@ -284,10 +313,14 @@ impl SourceDescBuilder {
         let config = ConnectorProperties::extract(self.properties.clone())
             .map_err(|e| RwError::from(ConnectorError(e.into())))?;
 
+        let parse_error = ParseErrorConfig::from_properties(&self.properties)
+            .map_err(|e| RwError::from(ConnectorError(e.into())))?;
+
         let source = SourceImpl::Connector(ConnectorSource {
             config,
             columns: columns.clone(),
             parser,
+            parse_error,
             connector_message_buffer_size: self.source_manager.msg_buf_size(),
         });
 
@@ -336,7 +369,7 @@ pub mod test_utils {
                 is_hidden: false,
             })
             .collect();
-        let info = ProstSourceInfo::TableSource(TableSourceInfo {});
+        let info = ProstSourceInfo::TableSource(TableSourceInfo::default());
         SourceDescBuilder {
             source_id,
             row_id_index,
@@ -384,6 +417,8 @@ mod tests {
         let info = StreamSourceInfo {
             row_format: 0,
             row_schema_location: "".to_string(),
+            use_schema_registry: false,
+            ..Default::default()
         };
         let source_id = TableId::default();
 
@@ -434,7 +469,7 @@ mod tests {
             })
             .collect();
         let pk_column_ids = vec![1];
-        let info = TableSourceInfo {};
+        let info = TableSourceInfo::default();
 
         let _keyspace = Keyspace::table_root(MemoryStateStore::new(), &table_id);
 
@@ -19,6 +19,7 @@ use prometheus::{register_int_counter_vec_with_registry, Registry};
 pub struct SourceMetrics {
     pub registry: Registry,
     pub partition_input_count: GenericCounterVec<AtomicU64>,
+    pub parse_error_count: GenericCounterVec<AtomicU64>,
 }
 
 impl SourceMetrics {
@@ -30,9 +31,17 @@ impl SourceMetrics {
             registry
         )
         .unwrap();
+        let parse_error_count = register_int_counter_vec_with_registry!(
+            "source_parse_error_count",
+            "Total number of messages that failed to be parsed, handled by the policy",
+            &["actor_id", "source_id", "policy"],
+            registry
+        )
+        .unwrap();
         SourceMetrics {
             registry,
             partition_input_count,
+            parse_error_count,
         }
     }
 
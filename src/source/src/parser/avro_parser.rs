@@ -14,9 +14,10 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::path::Path;
+use std::sync::Arc;
 
 use apache_avro::types::Value;
-use apache_avro::{Reader, Schema};
+use apache_avro::{from_avro_datum, Decimal as AvroDecimal, Reader, Schema};
 use chrono::{Datelike, NaiveDate};
 use hyper::http::uri::InvalidUri;
 use hyper_tls::HttpsConnector;
@@ -27,16 +28,19 @@ use risingwave_common::error::ErrorCode::{
 };
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{
-    DataType, Datum, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, OrderedF32, OrderedF64,
+    Datum, Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, OrderedF32, OrderedF64,
     ScalarImpl,
 };
 use risingwave_connector::aws_utils::{default_conn_config, s3_client, AwsConfigV2};
+use risingwave_connector::schema::avro::avro_schema_to_columns;
+use risingwave_connector::schema::schema_registry::{extract_schema_id, ConfluentSchemaResolver};
 use risingwave_pb::plan_common::ColumnDesc;
 use url::Url;
 
 use crate::{SourceParser, SourceStreamChunkRowWriter, WriteGuard};
 
 const AVRO_SCHEMA_LOCATION_S3_REGION: &str = "region";
+const KAFKA_TOPIC_KEY: &str = "kafka.topic";
 
 pub fn unix_epoch_days() -> i32 {
     NaiveDate::from_ymd(1970, 1, 1).num_days_from_ce()
@@ -44,11 +48,38 @@ pub fn unix_epoch_days() -> i32 {
 
 #[derive(Debug)]
 pub struct AvroParser {
+    /// The reader schema, which the messages written by older or newer schemas are resolved to.
     schema: Schema,
+    /// Resolves the writer schemas of the messages if the schema registry is used.
+    schema_resolver: Option<Arc<ConfluentSchemaResolver>>,
 }
 
 impl AvroParser {
-    pub async fn new(schema_location: &str, props: HashMap<String, String>) -> Result<Self> {
+    /// Creates the parser with the schema at `schema_location`. If `use_schema_registry` is set,
+    /// `schema_location` is the url of a Confluent Schema Registry, and the latest schema of the
+    /// subject `<topic>-value` is used as the reader schema.
+    pub async fn new(
+        schema_location: &str,
+        props: HashMap<String, String>,
+        use_schema_registry: bool,
+    ) -> Result<Self> {
+        if use_schema_registry {
+            let topic = props.get(KAFKA_TOPIC_KEY).ok_or_else(|| {
+                RwError::from(InvalidConfigValue {
+                    config_entry: KAFKA_TOPIC_KEY.to_string(),
+                    config_value: "NONE".to_string(),
+                })
+            })?;
+            let resolver = ConfluentSchemaResolver::new(schema_location);
+            let (_, schema) = resolver
+                .get_by_subject_latest(&format!("{}-value", topic))
+                .await?;
+            return Ok(Self {
+                schema: schema.as_ref().clone(),
+                schema_resolver: Some(Arc::new(resolver)),
+            });
+        }
+
         let url = Url::parse(schema_location).map_err(|e| {
             InternalError(format!("failed to parse url ({}): {}", schema_location, e))
         })?;
@@ -64,101 +95,49 @@ impl AvroParser {
         }?;
         let schema = Schema::parse_str(&schema_content)
             .map_err(|e| RwError::from(InternalError(format!("Avro schema parse error {}", e))))?;
-        Ok(Self { schema })
+        Ok(Self {
+            schema,
+            schema_resolver: None,
+        })
     }
 
-    pub fn map_to_columns(&self) -> Result<Vec<ColumnDesc>> {
-        // there must be a record at top level
-        if let Schema::Record { fields, .. } = &self.schema {
-            let mut index = 0;
-            let fields = fields
-                .iter()
-                .map(|field| {
-                    Self::avro_field_to_column_desc(&field.name, &field.schema, &mut index)
-                })
-                .collect::<Result<Vec<_>>>()?;
-            tracing::info!("fields is {:?}", fields);
-            Ok(fields)
-        } else {
-            Err(RwError::from(InternalError(
-                "schema invalid, record required".into(),
-            )))
+    /// Fetches the writer schema of the message from the schema registry if it is not cached, so
+    /// that the message can be parsed.
+    pub async fn prepare(&self, payload: &[u8]) -> Result<()> {
+        if let Some(resolver) = &self.schema_resolver {
+            let (schema_id, _) = extract_schema_id(payload)?;
+            resolver.get_by_id(schema_id).await?;
         }
+        Ok(())
     }
 
-    fn avro_field_to_column_desc(
-        name: &str,
-        schema: &Schema,
-        index: &mut i32,
-    ) -> Result<ColumnDesc> {
-        let data_type = Self::avro_type_mapping(schema)?;
-        match schema {
-            Schema::Record {
-                name: schema_name,
-                fields,
-                ..
-            } => {
-                let vec_column = fields
-                    .iter()
-                    .map(|f| Self::avro_field_to_column_desc(&f.name, &f.schema, index))
-                    .collect::<Result<Vec<_>>>()?;
-                *index += 1;
-                Ok(ColumnDesc {
-                    column_type: Some(data_type.to_protobuf()),
-                    column_id: *index,
-                    name: name.to_owned(),
-                    field_descs: vec_column,
-                    type_name: schema_name.to_string(),
-                })
-            }
-            _ => {
-                *index += 1;
-                Ok(ColumnDesc {
-                    column_type: Some(data_type.to_protobuf()),
-                    column_id: *index,
-                    name: name.to_owned(),
-                    ..Default::default()
-                })
+    /// Decodes the message into a value of the reader schema.
+    fn decode(&self, payload: &[u8]) -> Result<Value> {
+        if let Some(resolver) = &self.schema_resolver {
+            let (schema_id, mut datum) = extract_schema_id(payload)?;
+            let writer_schema = resolver.get_cached(schema_id).ok_or_else(|| {
+                RwError::from(InternalError(format!(
+                    "schema of id {} is not fetched",
+                    schema_id
+                )))
+            })?;
+            from_avro_datum(&writer_schema, &mut datum, Some(&self.schema))
+                .map_err(|e| RwError::from(ProtocolError(e.to_string())))
+        } else {
+            let mut reader = Reader::with_schema(&self.schema, payload)
+                .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+            match reader.next() {
+                Some(Ok(value)) => Ok(value),
+                Some(Err(e)) => Err(RwError::from(ProtocolError(e.to_string()))),
+                None => Err(RwError::from(ProtocolError(
+                    "avro parse unexpected eof".to_string(),
+                ))),
             }
         }
     }
 
-    fn avro_type_mapping(schema: &Schema) -> Result<DataType> {
-        let data_type = match schema {
-            Schema::String => DataType::Varchar,
-            Schema::Int => DataType::Int32,
-            Schema::Long => DataType::Int64,
-            Schema::Boolean => DataType::Boolean,
-            Schema::Float => DataType::Float32,
-            Schema::Double => DataType::Float64,
-            Schema::Date => DataType::Date,
-            Schema::TimestampMillis => DataType::Timestamp,
-            Schema::TimestampMicros => DataType::Timestamp,
-            Schema::Duration => DataType::Interval,
-            Schema::Enum { .. } => DataType::Varchar,
-            Schema::Record { fields, .. } => {
-                let struct_fields = fields
-                    .iter()
-                    .map(|f| Self::avro_type_mapping(&f.schema))
-                    .collect::<Result<Vec<_>>>()?;
-                let struct_names = fields.iter().map(|f| f.name.clone()).collect_vec();
-                DataType::new_struct(struct_fields, struct_names)
-            }
-            Schema::Array(item_schema) => {
-                let item_type = Self::avro_type_mapping(item_schema.as_ref())?;
-                DataType::List {
-                    datatype: Box::new(item_type),
-                }
-            }
-            _ => {
-                return Err(RwError::from(InternalError(format!(
-                    "unsupported type in Avro: {:?}",
-                    schema
-                ))));
-            }
-        };
-
-        Ok(data_type)
+    pub fn map_to_columns(&self) -> Result<Vec<ColumnDesc>> {
+        avro_schema_to_columns(&self.schema)
     }
 }
 
@@ -171,9 +150,38 @@ impl AvroParser {
 ///  - string: String
 ///  - Date (the number of days from the unix epoch, 1970-1-1 UTC)
 ///  - Timestamp (the number of milliseconds from the unix epoch,  1970-1-1 00:00:00.000 UTC)
+///  - Decimal (the unscaled value, whose scale is in the schema)
+///  - Union of null and another type
+///
+/// `schema` is the reader schema of the value.
 #[inline]
-fn from_avro_value(value: Value) -> Result<Datum> {
+fn from_avro_value(value: Value, schema: &Schema) -> Result<Datum> {
     let v = match value {
+        Value::Null => return Ok(None),
+        Value::Union(index, value) => {
+            let Schema::Union(union) = schema else {
+                return Err(RwError::from(InternalError(format!(
+                    "avro parse error.union value of schema {:?}",
+                    schema
+                ))));
+            };
+            let variant = union.variants().get(index as usize).ok_or_else(|| {
+                RwError::from(InternalError(format!(
+                    "avro parse error.no variant {} in {:?}",
+                    index, schema
+                )))
+            })?;
+            return from_avro_value(*value, variant);
+        }
+        Value::Decimal(decimal) => {
+            let Schema::Decimal { scale, .. } = schema else {
+                return Err(RwError::from(InternalError(format!(
+                    "avro parse error.decimal value of schema {:?}",
+                    schema
+                ))));
+            };
+            ScalarImpl::Decimal(avro_decimal_to_decimal(&decimal, *scale)?)
+        }
         Value::Boolean(b) => ScalarImpl::Bool(b),
         Value::String(s) => ScalarImpl::Utf8(s),
         Value::Int(i) => ScalarImpl::Int32(i),
@@ -220,16 +228,29 @@ fn from_avro_value(value: Value) -> Result<Datum> {
         }
         Value::Enum(_, symbol) => ScalarImpl::Utf8(symbol),
         Value::Record(descs) => {
+            let Schema::Record { fields, .. } = schema else {
+                return Err(RwError::from(InternalError(format!(
+                    "avro parse error.record value of schema {:?}",
+                    schema
+                ))));
+            };
             let rw_values = descs
                 .into_iter()
-                .map(|(_, value)| from_avro_value(value))
+                .zip_eq(fields)
+                .map(|((_, value), field)| from_avro_value(value, &field.schema))
                 .collect::<Result<Vec<Datum>>>()?;
             ScalarImpl::Struct(StructValue::new(rw_values))
         }
         Value::Array(values) => {
+            let Schema::Array(item_schema) = schema else {
+                return Err(RwError::from(InternalError(format!(
+                    "avro parse error.array value of schema {:?}",
+                    schema
+                ))));
+            };
             let rw_values = values
                 .into_iter()
-                .map(from_avro_value)
+                .map(|value| from_avro_value(value, item_schema))
                 .collect::<Result<Vec<Datum>>>()?;
             ScalarImpl::List(ListValue::new(rw_values))
         }
@@ -242,30 +263,51 @@ fn from_avro_value(value: Value) -> Result<Datum> {
     Ok(Some(v))
 }
 
+/// Converts the big-endian two's-complement unscaled value of an Avro decimal to a decimal.
+fn avro_decimal_to_decimal(decimal: &AvroDecimal, scale: usize) -> Result<Decimal> {
+    let bytes = Vec::<u8>::try_from(decimal)
+        .map_err(|e| RwError::from(InternalError(format!("avro parse error.{}", e))))?;
+    if bytes.len() > 16 {
+        return Err(RwError::from(InternalError(format!(
+            "avro parse error.decimal of {} bytes is out of range",
+            bytes.len()
+        ))));
+    }
+    let negative = bytes.first().map_or(false, |b| b & 0x80 != 0);
+    let mut buf = if negative { [0xff; 16] } else { [0; 16] };
+    buf[16 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(Decimal::from_i128_with_scale(
+        i128::from_be_bytes(buf),
+        scale as u32,
+    ))
+}
+
 impl SourceParser for AvroParser {
     fn parse(&self, payload: &[u8], writer: SourceStreamChunkRowWriter<'_>) -> Result<WriteGuard> {
-        match Reader::with_schema(&self.schema, payload) {
-            Ok(mut reader) => match reader.next() {
-                Some(Ok(Value::Record(fields))) => writer.insert(|column| {
-                    let tuple = fields.iter().find(|val| column.name.eq(&val.0)).unwrap();
-                    from_avro_value(tuple.1.clone()).map_err(|e| {
-                        tracing::error!(
-                            "failed to process value ({}): {}",
-                            String::from_utf8_lossy(payload),
-                            e
-                        );
+        let Schema::Record { fields: schema_fields, .. } = &self.schema else {
+            return Err(RwError::from(InternalError(
+                "schema invalid, record required".into(),
+            )));
+        };
+        match self.decode(payload)? {
+            Value::Record(fields) => writer.insert(|column| {
+                // The fields of the value are in the order of the reader schema.
+                let (idx, (_, value)) = fields
+                    .iter()
+                    .find_position(|val| column.name.eq(&val.0))
+                    .unwrap();
+                from_avro_value(value.clone(), &schema_fields[idx].schema).map_err(|e| {
+                    tracing::error!(
+                        "failed to process value ({}): {}",
+                        String::from_utf8_lossy(payload),
                         e
-                    })
-                }),
-                Some(Ok(_)) => Err(RwError::from(ProtocolError(
-                    "avro parse unexpected value".to_string(),
-                ))),
-                Some(Err(e)) => Err(RwError::from(ProtocolError(e.to_string()))),
-                None => Err(RwError::from(ProtocolError(
-                    "avro parse unexpected eof".to_string(),
-                ))),
-            },
-            Err(e) => Err(RwError::from(ProtocolError(e.to_string()))),
+                    );
+                    e
+                })
+            }),
+            _ => Err(RwError::from(ProtocolError(
+                "avro parse unexpected value".to_string(),
+            ))),
         }
     }
 }
@@ -341,19 +383,24 @@ mod test {
     use std::ops::Sub;
 
     use apache_avro::types::{Record, Value};
-    use apache_avro::{Codec, Days, Duration, Millis, Months, Schema, Writer};
+    use apache_avro::{
+        from_avro_datum, to_avro_datum, Codec, Days, Decimal as AvroDecimal, Duration, Millis,
+        Months, Schema, Writer,
+    };
     use chrono::NaiveDate;
+    use itertools::Itertools;
     use risingwave_common::array::Op;
     use risingwave_common::catalog::ColumnId;
     use risingwave_common::error;
     use risingwave_common::types::{
-        DataType, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, ScalarImpl,
+        DataType, Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper, ScalarImpl,
     };
+    use risingwave_connector::schema::avro::avro_type_mapping;
     use url::Url;
 
     use crate::parser::avro_parser::{
-        read_schema_from_https, read_schema_from_local, read_schema_from_s3, unix_epoch_days,
-        AvroParser,
+        from_avro_value, read_schema_from_https, read_schema_from_local, read_schema_from_s3,
+        unix_epoch_days, AvroParser,
     };
     use crate::{SourceColumnDesc, SourceParser, SourceStreamChunkBuilder};
 
@@ -408,7 +455,7 @@ mod test {
 
     async fn new_avro_parser_from_local(file_name: &str) -> error::Result<AvroParser> {
         let schema_path = "file://".to_owned() + &test_data_path(file_name);
-        AvroParser::new(schema_path.as_str(), HashMap::new()).await
+        AvroParser::new(schema_path.as_str(), HashMap::new(), false).await
     }
 
     #[tokio::test]
@@ -505,6 +552,7 @@ mod test {
                 data_type: DataType::Int32,
                 column_id: ColumnId::from(0),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -512,6 +560,7 @@ mod test {
                 data_type: DataType::Int64,
                 column_id: ColumnId::from(1),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -519,6 +568,7 @@ mod test {
                 data_type: DataType::Varchar,
                 column_id: ColumnId::from(2),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -526,6 +576,7 @@ mod test {
                 data_type: DataType::Float32,
                 column_id: ColumnId::from(3),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -533,6 +584,7 @@ mod test {
                 data_type: DataType::Float64,
                 column_id: ColumnId::from(4),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -540,6 +592,7 @@ mod test {
                 data_type: DataType::Boolean,
                 column_id: ColumnId::from(5),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -547,6 +600,7 @@ mod test {
                 data_type: DataType::Date,
                 column_id: ColumnId::from(6),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -554,6 +608,7 @@ mod test {
                 data_type: DataType::Timestamp,
                 column_id: ColumnId::from(7),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -561,6 +616,7 @@ mod test {
                 data_type: DataType::Timestamp,
                 column_id: ColumnId::from(8),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -568,6 +624,7 @@ mod test {
                 data_type: DataType::Interval,
                 column_id: ColumnId::from(9),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
         ]
@@ -649,4 +706,71 @@ mod test {
         let avro_parser = avro_parser_rs.unwrap();
         println!("avro_parser = {:?}", avro_parser);
     }
+
+    #[test]
+    fn test_avro_schema_evolution() {
+        let writer_schema = Schema::parse_str(
+            r#"{"type": "record", "name": "t", "fields": [{"name": "id", "type": "int"}]}"#,
+        )
+        .unwrap();
+        // The reader schema adds a nullable decimal with a default value.
+        let reader_schema = Schema::parse_str(
+            r#"{"type": "record", "name": "t", "fields": [
+                {"name": "id", "type": "int"},
+                {"name": "price", "type": ["null", {"type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2}], "default": null}
+            ]}"#,
+        )
+        .unwrap();
+        let Schema::Record { fields, .. } = &reader_schema else {
+            unreachable!()
+        };
+        assert_eq!(
+            avro_type_mapping(&fields[1].schema).unwrap(),
+            DataType::Decimal
+        );
+
+        let parse = |value: Value| {
+            let Value::Record(values) = value else {
+                unreachable!()
+            };
+            values
+                .into_iter()
+                .zip_eq(fields)
+                .map(|((_, value), field)| from_avro_value(value, &field.schema).unwrap())
+                .collect_vec()
+        };
+
+        // A message of the old schema gets the default value.
+        let datum = to_avro_datum(
+            &writer_schema,
+            Value::Record(vec![("id".into(), Value::Int(1))]),
+        )
+        .unwrap();
+        let value = from_avro_datum(&writer_schema, &mut &datum[..], Some(&reader_schema)).unwrap();
+        assert_eq!(parse(value), vec![Some(ScalarImpl::Int32(1)), None]);
+
+        // A message of the new schema.
+        let datum = to_avro_datum(
+            &reader_schema,
+            Value::Record(vec![
+                ("id".into(), Value::Int(2)),
+                (
+                    "price".into(),
+                    Value::Union(
+                        1,
+                        Box::new(Value::Decimal(AvroDecimal::from(vec![0x04, 0xd2]))),
+                    ),
+                ),
+            ]),
+        )
+        .unwrap();
+        let value = from_avro_datum(&reader_schema, &mut &datum[..], Some(&reader_schema)).unwrap();
+        assert_eq!(
+            parse(value),
+            vec![
+                Some(ScalarImpl::Int32(2)),
+                Some(ScalarImpl::Decimal(Decimal::from_i128_with_scale(1234, 2)))
+            ]
+        );
+    }
 }
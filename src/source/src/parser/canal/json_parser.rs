@@ -0,0 +1,134 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use risingwave_common::error::ErrorCode::{NotImplemented, ProtocolError};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_expr::vector_op::cast::{
+    str_parse, str_to_bool, str_to_date, str_to_time, str_to_timestamp, str_to_timestampz,
+};
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::operators::*;
+use crate::{SourceParser, SourceStreamChunkRowWriter, WriteGuard};
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanalJsonEvent {
+    pub data: Option<Vec<BTreeMap<String, Value>>>,
+    pub old: Option<Vec<BTreeMap<String, Value>>>,
+    #[serde(rename = "type")]
+    pub op: String,
+    #[serde(rename = "isDdl", default)]
+    pub is_ddl: bool,
+}
+
+#[derive(Debug)]
+pub struct CanalJsonParser;
+
+impl SourceParser for CanalJsonParser {
+    fn parse(&self, payload: &[u8], writer: SourceStreamChunkRowWriter<'_>) -> Result<WriteGuard> {
+        let event: CanalJsonEvent = serde_json::from_slice(payload)
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+
+        if event.is_ddl {
+            return Err(RwError::from(ProtocolError(format!(
+                "canal ddl event {} is not supported",
+                event.op
+            ))));
+        }
+
+        // Canal batches the changes of multiple rows of the same type in one event.
+        let data = event.data.ok_or_else(|| {
+            RwError::from(ProtocolError("data is missing in canal event".to_string()))
+        })?;
+
+        match event.op.as_str() {
+            CANAL_INSERT_OP => writer.insert_rows(&data, |after, column| {
+                canal_parse_value(&column.data_type, after.get(&column.name))
+            }),
+            CANAL_UPDATE_OP => {
+                let old = event.old.ok_or_else(|| {
+                    RwError::from(ProtocolError(
+                        "old is missing for updating event".to_string(),
+                    ))
+                })?;
+                if old.len() != data.len() {
+                    return Err(RwError::from(ProtocolError(format!(
+                        "the number of old rows {} mismatches the number of rows {}",
+                        old.len(),
+                        data.len()
+                    ))));
+                }
+
+                writer.update_rows(old.iter().zip(&data), |(before, after), column| {
+                    // old only contains the changed columns but data contains all columns.
+                    let before_value = before.get(&column.name).or_else(|| after.get(&column.name));
+                    let before = canal_parse_value(&column.data_type, before_value)?;
+                    let after = canal_parse_value(&column.data_type, after.get(&column.name))?;
+                    Ok((before, after))
+                })
+            }
+            CANAL_DELETE_OP => writer.delete_rows(&data, |before, column| {
+                canal_parse_value(&column.data_type, before.get(&column.name))
+            }),
+            other => Err(RwError::from(ProtocolError(format!(
+                "unknown canal op: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Canal encodes all the values as strings, which are parsed as the text representation of the
+/// column type.
+fn canal_parse_value(dtype: &DataType, value: Option<&Value>) -> Result<Datum> {
+    let v = match value {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(v)) => canal_parse_str(dtype, v),
+        Some(v) => canal_parse_str(dtype, &v.to_string()),
+    };
+    v.map(Some).map_err(|e| {
+        RwError::from(ProtocolError(format!(
+            "failed to parse type '{}' from canal json: {}",
+            dtype, e
+        )))
+    })
+}
+
+fn canal_parse_str(dtype: &DataType, v: &str) -> Result<ScalarImpl> {
+    let v = match dtype {
+        DataType::Boolean => str_to_bool(v)?.into(),
+        DataType::Int16 => ScalarImpl::Int16(str_parse(v)?),
+        DataType::Int32 => ScalarImpl::Int32(str_parse(v)?),
+        DataType::Int64 => ScalarImpl::Int64(str_parse(v)?),
+        DataType::Float32 => ScalarImpl::Float32(str_parse::<f32>(v)?.into()),
+        DataType::Float64 => ScalarImpl::Float64(str_parse::<f64>(v)?.into()),
+        DataType::Decimal => ScalarImpl::Decimal(str_parse(v)?),
+        DataType::Varchar => ScalarImpl::Utf8(v.to_string()),
+        DataType::Date => str_to_date(v)?.into(),
+        DataType::Time => str_to_time(v)?.into(),
+        DataType::Timestamp => str_to_timestamp(v)?.into(),
+        DataType::Timestampz => str_to_timestampz(v)?.into(),
+        DataType::Interval => ScalarImpl::Interval(str_parse(v)?),
+        DataType::Jsonb => ScalarImpl::Jsonb(str_parse(v)?),
+        DataType::Struct(_) | DataType::List { .. } => {
+            return Err(NotImplemented(format!("canal json of type {}", dtype), None.into()).into())
+        }
+    };
+    Ok(v)
+}
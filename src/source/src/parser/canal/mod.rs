@@ -0,0 +1,86 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod json_parser;
+mod operators;
+
+pub use json_parser::*;
+
+#[cfg(test)]
+mod test {
+    use risingwave_common::array::{Op, Row};
+    use risingwave_common::types::{DataType, ScalarImpl};
+    use risingwave_expr::vector_op::cast::str_to_timestamp;
+
+    use super::*;
+    use crate::{SourceColumnDesc, SourceParser, SourceStreamChunkBuilder};
+
+    #[test]
+    fn test_canal_json_parser() {
+        let parser = CanalJsonParser;
+        let descs = vec![
+            SourceColumnDesc::simple("id", DataType::Int32, 0.into()),
+            SourceColumnDesc::simple("name", DataType::Varchar, 1.into()),
+            SourceColumnDesc::simple("is_adult", DataType::Boolean, 2.into()),
+            SourceColumnDesc::simple("birthday", DataType::Timestamp, 3.into()),
+        ];
+
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 5);
+        let payloads = vec![
+            br#"{"data":[{"id":"1","name":"tom","is_adult":"0","birthday":"2017-12-31 16:00:01"},{"id":"2","name":"alex","is_adult":"1","birthday":"1999-12-31 16:00:01"}],"database":"test","es":1666937996000,"id":1,"isDdl":false,"mysqlType":{"id":"int","name":"varchar(255)","is_adult":"tinyint(1)","birthday":"datetime"},"old":null,"pkNames":["id"],"sql":"","table":"t","ts":1666937996529,"type":"INSERT"}"#.as_slice(),
+            br#"{"data":[{"id":"2","name":"chi","is_adult":"1","birthday":"1999-12-31 16:00:01"}],"database":"test","es":1666938068000,"id":2,"isDdl":false,"old":[{"name":"alex"}],"pkNames":["id"],"sql":"","table":"t","ts":1666938068529,"type":"UPDATE"}"#.as_slice(),
+            br#"{"data":[{"id":"1","name":"tom","is_adult":"0","birthday":"2017-12-31 16:00:01"}],"database":"test","es":1666938100000,"id":3,"isDdl":false,"old":null,"pkNames":["id"],"sql":"","table":"t","ts":1666938100529,"type":"DELETE"}"#.as_slice(),
+        ];
+        for payload in payloads {
+            let writer = builder.row_writer();
+            parser.parse(payload, writer).unwrap();
+        }
+
+        // DDL events are not supported and write nothing.
+        let ddl = br#"{"data":null,"database":"test","es":1666938200000,"id":4,"isDdl":true,"old":null,"pkNames":null,"sql":"ALTER TABLE t ADD COLUMN c int","table":"t","ts":1666938200529,"type":"ALTER"}"#;
+        assert!(parser.parse(ddl, builder.row_writer()).is_err());
+        // A failed row does not leave the other rows of the event written.
+        let invalid = br#"{"data":[{"id":"3","name":"bob","is_adult":"1","birthday":"2000-01-01 00:00:00"},{"id":"x","name":"eve","is_adult":"1","birthday":"2000-01-01 00:00:00"}],"isDdl":false,"old":null,"type":"INSERT"}"#;
+        assert!(parser.parse(invalid, builder.row_writer()).is_err());
+
+        let chunk = builder.finish();
+        let rows = chunk
+            .rows()
+            .map(|(op, row)| (op, row.to_owned_row()))
+            .collect::<Vec<_>>();
+        let row = |id: i32, name: &str, is_adult: bool, birthday: &str| {
+            Row::new(vec![
+                Some(ScalarImpl::Int32(id)),
+                Some(ScalarImpl::Utf8(name.to_string())),
+                Some(ScalarImpl::Bool(is_adult)),
+                Some(ScalarImpl::NaiveDateTime(
+                    str_to_timestamp(birthday).unwrap(),
+                )),
+            ])
+        };
+        assert_eq!(
+            rows,
+            vec![
+                (Op::Insert, row(1, "tom", false, "2017-12-31 16:00:01")),
+                (Op::Insert, row(2, "alex", true, "1999-12-31 16:00:01")),
+                (
+                    Op::UpdateDelete,
+                    row(2, "alex", true, "1999-12-31 16:00:01")
+                ),
+                (Op::UpdateInsert, row(2, "chi", true, "1999-12-31 16:00:01")),
+                (Op::Delete, row(1, "tom", false, "2017-12-31 16:00:01")),
+            ]
+        );
+    }
+}
@@ -16,7 +16,7 @@ use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use num_traits::FromPrimitive;
 use risingwave_common::array::{ListValue, StructValue};
-use risingwave_common::types::{DataType, Datum, Decimal, ScalarImpl};
+use risingwave_common::types::{DataType, Datum, Decimal, JsonbVal, ScalarImpl};
 use risingwave_expr::vector_op::cast::{
     str_to_date, str_to_time, str_to_timestamp, str_to_timestampz,
 };
@@ -126,6 +126,7 @@ fn do_parse_json_value(dtype: &DataType, v: &Value) -> Result<ScalarImpl> {
                 return Err(anyhow!(err_msg));
             }
         }
+        DataType::Jsonb => JsonbVal::new(v.clone()).into(),
         DataType::Interval => unimplemented!(),
     };
     Ok(v)
@@ -204,6 +205,7 @@ fn do_parse_simd_json_value(dtype: &DataType, v: &BorrowedValue<'_>) -> Result<S
                 return Err(anyhow!(err_msg));
             }
         }
+        DataType::Jsonb => v.to_string().parse::<JsonbVal>()?.into(),
         DataType::Interval => unimplemented!(),
     };
     Ok(v)
@@ -0,0 +1,382 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use itertools::Itertools;
+use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum, Decimal, IntervalUnit, JsonbVal, ScalarImpl};
+use risingwave_expr::vector_op::cast::{
+    str_parse, str_to_bool, str_to_date, str_to_list, str_to_time, str_to_timestamp,
+    str_to_timestampz,
+};
+
+use crate::{SourceParser, SourceStreamChunkRowWriter, WriteGuard};
+
+/// The single character separating the fields, default `,`.
+pub const CSV_DELIMITER_KEY: &str = "csv.delimiter";
+/// The character quoting a field that contains the delimiter, default `"`.
+pub const CSV_QUOTE_KEY: &str = "csv.quote";
+/// The character escaping the quote inside a quoted field, default the quote itself, i.e. a quote
+/// is written as two quotes.
+pub const CSV_ESCAPE_KEY: &str = "csv.escape";
+/// Whether the files begin with a header line of the column names, default false. The fields are
+/// mapped to the columns by the names in the header if present, otherwise by their positions.
+pub const CSV_HEADER_KEY: &str = "csv.header";
+/// The unquoted string representing a null value, default the empty string.
+pub const CSV_NULL_KEY: &str = "csv.null";
+
+/// A field of a CSV record.
+#[derive(Debug, PartialEq)]
+struct CsvField {
+    value: String,
+    /// Whether any part of the field is quoted. A quoted field is never null.
+    quoted: bool,
+}
+
+/// Parser for the CSV format, where each message is a record.
+#[derive(Debug)]
+pub struct CsvParser {
+    delimiter: u8,
+    quote: u8,
+    escape: u8,
+    has_header: bool,
+    null: String,
+}
+
+impl CsvParser {
+    pub fn new(properties: &HashMap<String, String>) -> Result<Self> {
+        let delimiter = parse_char_option(properties, CSV_DELIMITER_KEY, b',')?;
+        let quote = parse_char_option(properties, CSV_QUOTE_KEY, b'"')?;
+        let escape = parse_char_option(properties, CSV_ESCAPE_KEY, quote)?;
+        if delimiter == quote || delimiter == escape {
+            return Err(RwError::from(ProtocolError(format!(
+                "{} must be different from {} and {}",
+                CSV_DELIMITER_KEY, CSV_QUOTE_KEY, CSV_ESCAPE_KEY
+            ))));
+        }
+        let has_header = match properties.get(CSV_HEADER_KEY) {
+            Some(header) => header.parse::<bool>().map_err(|_| {
+                RwError::from(ProtocolError(format!(
+                    "{} must be true or false, but got {}",
+                    CSV_HEADER_KEY, header
+                )))
+            })?,
+            None => false,
+        };
+        let null = properties.get(CSV_NULL_KEY).cloned().unwrap_or_default();
+        Ok(Self {
+            delimiter,
+            quote,
+            escape,
+            has_header,
+            null,
+        })
+    }
+
+    /// Splits the record into fields, removing the quotes and the escapes.
+    fn split_record(&self, record: &[u8]) -> Result<Vec<CsvField>> {
+        let mut fields = vec![];
+        let mut value = vec![];
+        let mut quoted = false;
+        let mut in_quotes = false;
+        let mut i = 0;
+        while i < record.len() {
+            let c = record[i];
+            if in_quotes {
+                let next = record.get(i + 1).copied();
+                if c == self.escape && (next == Some(self.quote) || next == Some(self.escape)) {
+                    value.push(next.unwrap());
+                    i += 1;
+                } else if c == self.quote {
+                    in_quotes = false;
+                } else {
+                    value.push(c);
+                }
+            } else if c == self.delimiter {
+                fields.push(Self::make_field(std::mem::take(&mut value), quoted)?);
+                quoted = false;
+            } else if c == self.quote {
+                in_quotes = true;
+                quoted = true;
+            } else {
+                value.push(c);
+            }
+            i += 1;
+        }
+        if in_quotes {
+            return Err(RwError::from(ProtocolError(
+                "unterminated quoted field in CSV record".to_string(),
+            )));
+        }
+        fields.push(Self::make_field(value, quoted)?);
+        Ok(fields)
+    }
+
+    fn make_field(value: Vec<u8>, quoted: bool) -> Result<CsvField> {
+        let value =
+            String::from_utf8(value).map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+        Ok(CsvField { value, quoted })
+    }
+
+    fn parse_field(&self, field: &CsvField, data_type: &DataType) -> Result<Datum> {
+        if !field.quoted && field.value == self.null {
+            return Ok(None);
+        }
+        csv_parse_value(data_type, &field.value)
+            .map(Some)
+            .map_err(|e| {
+                RwError::from(ProtocolError(format!(
+                    "failed to parse {:?} as {}: {}",
+                    field.value, data_type, e
+                )))
+            })
+    }
+}
+
+fn parse_char_option(properties: &HashMap<String, String>, key: &str, default: u8) -> Result<u8> {
+    match properties.get(key).map(|s| s.as_str()) {
+        None => Ok(default),
+        Some("\\t") => Ok(b'\t'),
+        Some(s) if s.len() == 1 && !matches!(s.as_bytes()[0], b'\n' | b'\r') => Ok(s.as_bytes()[0]),
+        Some(s) => Err(RwError::from(ProtocolError(format!(
+            "{} must be a single ASCII character other than a newline, but got {:?}",
+            key, s
+        )))),
+    }
+}
+
+fn csv_parse_value(data_type: &DataType, value: &str) -> anyhow::Result<ScalarImpl> {
+    let v = match data_type {
+        DataType::Boolean => str_to_bool(value)?.into(),
+        DataType::Int16 => ScalarImpl::Int16(str_parse(value)?),
+        DataType::Int32 => ScalarImpl::Int32(str_parse(value)?),
+        DataType::Int64 => ScalarImpl::Int64(str_parse(value)?),
+        DataType::Float32 => ScalarImpl::Float32(str_parse::<f32>(value)?.into()),
+        DataType::Float64 => ScalarImpl::Float64(str_parse::<f64>(value)?.into()),
+        DataType::Decimal => str_parse::<Decimal>(value)?.into(),
+        DataType::Varchar => value.to_string().into(),
+        DataType::Date => str_to_date(value)?.into(),
+        DataType::Time => str_to_time(value)?.into(),
+        DataType::Timestamp => str_to_timestamp(value)?.into(),
+        DataType::Timestampz => str_to_timestampz(value)?.into(),
+        DataType::Interval => str_parse::<IntervalUnit>(value)?.into(),
+        DataType::Jsonb => value.parse::<JsonbVal>()?.into(),
+        DataType::List { datatype } => str_to_list(value, datatype)?.into(),
+        DataType::Struct(_) => return Err(anyhow!("struct is not supported in CSV")),
+    };
+    Ok(v)
+}
+
+impl SourceParser for CsvParser {
+    fn parse(&self, payload: &[u8], writer: SourceStreamChunkRowWriter<'_>) -> Result<WriteGuard> {
+        let fields = self.split_record(payload)?;
+
+        if self.has_header {
+            let header = writer
+                .message
+                .and_then(|message| message.meta.header.as_ref())
+                .ok_or_else(|| {
+                    RwError::from(ProtocolError(
+                        "the header of the CSV file is missing".to_string(),
+                    ))
+                })?;
+            let names = self
+                .split_record(header)?
+                .into_iter()
+                .map(|field| field.value)
+                .collect_vec();
+            if fields.len() != names.len() {
+                return Err(RwError::from(ProtocolError(format!(
+                    "expect {} fields as the header, but got {}",
+                    names.len(),
+                    fields.len()
+                ))));
+            }
+            // The columns absent from the header are null.
+            writer.insert(|desc| {
+                let index = names
+                    .iter()
+                    .position(|name| *name == desc.name)
+                    .or_else(|| {
+                        names
+                            .iter()
+                            .position(|name| name.eq_ignore_ascii_case(&desc.name))
+                    });
+                match index {
+                    Some(index) => self.parse_field(&fields[index], &desc.data_type),
+                    None => Ok(None),
+                }
+            })
+        } else {
+            let column_num = writer
+                .descs
+                .iter()
+                .filter(|desc| !desc.skip_parse && desc.meta_kind.is_none())
+                .count();
+            if fields.len() != column_num {
+                return Err(RwError::from(ProtocolError(format!(
+                    "expect {} fields, but got {}",
+                    column_num,
+                    fields.len()
+                ))));
+            }
+            let mut fields = fields.iter();
+            writer.insert(|desc| self.parse_field(fields.next().unwrap(), &desc.data_type))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::{convert_args, hashmap};
+    use risingwave_common::array::Op;
+    use risingwave_common::types::ToOwnedDatum;
+    use risingwave_connector::source::{SourceMessage, SourceMeta};
+
+    use super::*;
+    use crate::{SourceColumnDesc, SourceStreamChunkBuilder};
+
+    fn fields(values: &[(&str, bool)]) -> Vec<CsvField> {
+        values
+            .iter()
+            .map(|(value, quoted)| CsvField {
+                value: value.to_string(),
+                quoted: *quoted,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_record() {
+        let parser = CsvParser::new(&HashMap::new()).unwrap();
+        assert_eq!(
+            parser
+                .split_record(br#"1,"a,b",,"","say ""hi""",x"y"z"#)
+                .unwrap(),
+            fields(&[
+                ("1", false),
+                ("a,b", true),
+                ("", false),
+                ("", true),
+                (r#"say "hi""#, true),
+                ("xyz", true),
+            ])
+        );
+        assert!(parser.split_record(br#"1,"a"#).is_err());
+
+        let parser = CsvParser::new(&convert_args!(hashmap!(
+            CSV_DELIMITER_KEY => "\\t",
+            CSV_QUOTE_KEY => "'",
+            CSV_ESCAPE_KEY => "\\",
+        )))
+        .unwrap();
+        assert_eq!(
+            parser.split_record(b"'it\\'s'\t'a\\\\b'\tc,d").unwrap(),
+            fields(&[("it's", true), ("a\\b", true), ("c,d", false)])
+        );
+
+        assert!(CsvParser::new(&convert_args!(hashmap!(CSV_DELIMITER_KEY => "||"))).is_err());
+        assert!(CsvParser::new(&convert_args!(hashmap!(CSV_DELIMITER_KEY => "\""))).is_err());
+    }
+
+    #[test]
+    fn test_csv_parser() {
+        let descs = vec![
+            SourceColumnDesc::simple("id", DataType::Int32, 0.into()),
+            SourceColumnDesc::simple("name", DataType::Varchar, 1.into()),
+            SourceColumnDesc::simple("ts", DataType::Timestamp, 2.into()),
+        ];
+        let parser = CsvParser::new(&convert_args!(hashmap!(CSV_NULL_KEY => "NULL"))).unwrap();
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 2);
+        for payload in [
+            br#"1,"Smith, John",2022-01-01 00:00:00"#.as_slice(),
+            br#"2,"NULL",NULL"#.as_slice(),
+        ] {
+            parser.parse(payload, builder.row_writer()).unwrap();
+        }
+        // the number of fields mismatches
+        assert!(parser.parse(b"3,x", builder.row_writer()).is_err());
+        assert!(parser.parse(b"x,y,z", builder.row_writer()).is_err());
+
+        let chunk = builder.finish();
+        assert_eq!(chunk.ops(), [Op::Insert, Op::Insert]);
+        let mut rows = chunk.rows();
+        let row = rows.next().unwrap().1;
+        assert_eq!(row.value_at(0).to_owned_datum(), Some(ScalarImpl::Int32(1)));
+        assert_eq!(
+            row.value_at(1).to_owned_datum(),
+            Some(ScalarImpl::Utf8("Smith, John".into()))
+        );
+        assert_eq!(
+            row.value_at(2).to_owned_datum(),
+            Some(str_to_timestamp("2022-01-01 00:00:00").unwrap().into())
+        );
+        let row = rows.next().unwrap().1;
+        // a quoted null literal is a string
+        assert_eq!(
+            row.value_at(1).to_owned_datum(),
+            Some(ScalarImpl::Utf8("NULL".into()))
+        );
+        assert_eq!(row.value_at(2).to_owned_datum(), None);
+    }
+
+    #[test]
+    fn test_csv_parser_with_header() {
+        let descs = vec![
+            SourceColumnDesc::simple("id", DataType::Int32, 0.into()),
+            SourceColumnDesc::simple("name", DataType::Varchar, 1.into()),
+            SourceColumnDesc::simple("score", DataType::Float64, 2.into()),
+        ];
+        let parser = CsvParser::new(&convert_args!(hashmap!(CSV_HEADER_KEY => "true"))).unwrap();
+        let message = |payload: &'static [u8]| SourceMessage {
+            payload: Some(payload.into()),
+            offset: "0".to_string(),
+            split_id: "a.csv".into(),
+            meta: SourceMeta {
+                header: Some(b"NAME,extra,id".as_slice().into()),
+                ..Default::default()
+            },
+        };
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 1);
+        let msg = message(b"alice,foo,1");
+        parser
+            .parse(
+                msg.payload.as_ref().unwrap(),
+                builder.row_writer().with_message(&msg),
+            )
+            .unwrap();
+        let msg = message(b"bob,2");
+        assert!(parser
+            .parse(
+                msg.payload.as_ref().unwrap(),
+                builder.row_writer().with_message(&msg),
+            )
+            .is_err());
+        // the header is required
+        assert!(parser.parse(b"carol,bar,3", builder.row_writer()).is_err());
+
+        let chunk = builder.finish();
+        assert_eq!(chunk.cardinality(), 1);
+        let row = chunk.rows().next().unwrap().1;
+        assert_eq!(row.value_at(0).to_owned_datum(), Some(ScalarImpl::Int32(1)));
+        assert_eq!(
+            row.value_at(1).to_owned_datum(),
+            Some(ScalarImpl::Utf8("alice".into()))
+        );
+        assert_eq!(row.value_at(2).to_owned_datum(), None);
+    }
+}
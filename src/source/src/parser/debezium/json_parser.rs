@@ -24,11 +24,7 @@ use super::operators::*;
 use crate::parser::common::json_parse_value;
 use crate::{SourceParser, SourceStreamChunkRowWriter, WriteGuard};
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DebeziumEvent {
-    pub payload: Payload,
-}
+const PAYLOAD: &str = "payload";
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Payload {
@@ -42,10 +38,17 @@ pub struct DebeziumJsonParser;
 
 impl SourceParser for DebeziumJsonParser {
     fn parse(&self, payload: &[u8], writer: SourceStreamChunkRowWriter<'_>) -> Result<WriteGuard> {
-        let event: DebeziumEvent = serde_json::from_slice(payload)
+        let mut event: Value = serde_json::from_slice(payload)
             .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
 
-        let mut payload = event.payload;
+        // The envelope is the whole event if it is produced without schema.
+        let payload = if event.get(PAYLOAD).is_some() {
+            event[PAYLOAD].take()
+        } else {
+            event
+        };
+        let mut payload: Payload = serde_json::from_value(payload)
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
 
         match payload.op.as_str() {
             DEBEZIUM_UPDATE_OP => {
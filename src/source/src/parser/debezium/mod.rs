@@ -62,6 +62,7 @@ mod test {
                 data_type: DataType::Int32,
                 column_id: ColumnId::from(0),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -69,6 +70,7 @@ mod test {
                 data_type: DataType::Varchar,
                 column_id: ColumnId::from(1),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -76,6 +78,7 @@ mod test {
                 data_type: DataType::Varchar,
                 column_id: ColumnId::from(2),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
             SourceColumnDesc {
@@ -83,6 +86,7 @@ mod test {
                 data_type: DataType::Float64,
                 column_id: ColumnId::from(3),
                 skip_parse: false,
+                meta_kind: None,
                 fields: vec![],
             },
         ];
@@ -206,6 +210,29 @@ mod test {
         assert!(row2[3].eq(&Some(ScalarImpl::Float64(9.1.into()))));
     }
 
+    #[test]
+    fn test_debezium_json_parser_without_schema() {
+        // The envelope produced with `value.converter.schemas.enable=false`.
+        let data = br#"{"before":{"id":102,"name":"car battery","description":"12V car battery","weight":8.1},"after":{"id":102,"name":"car battery","description":"24V car battery","weight":9.1},"source":{"version":"1.7.1.Final","connector":"mysql","name":"dbserver1","ts_ms":1639551901000,"snapshot":"false","db":"inventory","sequence":null,"table":"products","server_id":223344,"gtid":null,"file":"mysql-bin.000003","pos":1382,"row":0,"thread":null,"query":null},"op":"u","ts_ms":1639551901165,"transaction":null}"#;
+        let parser = DebeziumJsonParser;
+        let columns = get_test_columns();
+
+        let [(op1, row1), (op2, row2)]: [_; 2] =
+            parse_one(parser, columns, data).try_into().unwrap();
+
+        assert_eq!(op1, Op::UpdateDelete);
+        assert_eq!(op2, Op::UpdateInsert);
+        assert!(row1[2].eq(&Some(ScalarImpl::Utf8("12V car battery".to_string()))));
+        assert!(row2[2].eq(&Some(ScalarImpl::Utf8("24V car battery".to_string()))));
+
+        let data = br#"{"before":{"id":101,"name":"scooter","description":"Small 2-wheel scooter","weight":1.234},"after":null,"op":"d","ts_ms":1639551767775}"#;
+        let [(op, row)]: [_; 1] = parse_one(DebeziumJsonParser, get_test_columns(), data)
+            .try_into()
+            .unwrap();
+        assert_eq!(op, Op::Delete);
+        assert!(row[0].eq(&Some(ScalarImpl::Int32(101))));
+    }
+
     #[test]
     fn test_update_with_before_null() {
         // the test case it identical with test_debezium_json_parser_insert but op is 'u'
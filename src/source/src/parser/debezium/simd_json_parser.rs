@@ -22,6 +22,7 @@ use super::operators::*;
 use crate::parser::common::simd_json_parse_value;
 use crate::{SourceParser, SourceStreamChunkRowWriter, WriteGuard};
 
+const PAYLOAD: &str = "payload";
 const BEFORE: &str = "before";
 const AFTER: &str = "after";
 const OP: &str = "op";
@@ -44,16 +45,17 @@ impl SourceParser for DebeziumJsonParser {
         let event: BorrowedValue<'_> = simd_json::to_borrowed_value(&mut payload_mut)
             .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
 
-        let payload = event
-            .get("payload")
-            .and_then(ensure_not_null)
-            .ok_or_else(|| {
+        // The envelope is the whole event if it is produced without schema.
+        let payload = match event.get(PAYLOAD) {
+            Some(payload) => ensure_not_null(payload).ok_or_else(|| {
                 RwError::from(ProtocolError("no payload in debezium event".to_owned()))
-            })?;
+            })?,
+            None => &event,
+        };
 
         let op = payload.get(OP).and_then(|v| v.as_str()).ok_or_else(|| {
             RwError::from(ProtocolError(
-                "op field not found in maxwell json".to_owned(),
+                "op field not found in debezium json".to_owned(),
             ))
         })?;
 
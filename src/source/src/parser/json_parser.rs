@@ -12,14 +12,60 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use risingwave_common::error::ErrorCode::ProtocolError;
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{JsonbVal, ScalarImpl};
 
 use crate::{SourceParser, SourceStreamChunkRowWriter, WriteGuard};
 
+/// Whether the whole payload is stored in the only column of the source, which must be of type
+/// `jsonb`, default false. Otherwise the columns take the values of the fields of their names.
+pub const JSON_WHOLE_PAYLOAD_KEY: &str = "json.whole_payload";
+
 /// Parser for JSON format
-#[derive(Debug)]
-pub struct JsonParser;
+#[derive(Debug, Default)]
+pub struct JsonParser {
+    whole_payload: bool,
+}
+
+impl JsonParser {
+    pub fn new(properties: &HashMap<String, String>) -> Result<Self> {
+        let whole_payload = match properties.get(JSON_WHOLE_PAYLOAD_KEY) {
+            Some(whole_payload) => whole_payload.parse::<bool>().map_err(|_| {
+                RwError::from(ProtocolError(format!(
+                    "{} must be true or false, but got {}",
+                    JSON_WHOLE_PAYLOAD_KEY, whole_payload
+                )))
+            })?,
+            None => false,
+        };
+        Ok(Self { whole_payload })
+    }
+
+    /// Whether the whole payload is stored in the only column of the source.
+    pub fn whole_payload(&self) -> bool {
+        self.whole_payload
+    }
+}
+
+/// Stores the whole payload in the only column of the source.
+fn parse_whole_payload(
+    payload: &[u8],
+    writer: SourceStreamChunkRowWriter<'_>,
+) -> Result<WriteGuard> {
+    if !writer.is_single_jsonb_column() {
+        return Err(RwError::from(ProtocolError(format!(
+            "{} requires the source to have a single column of type jsonb",
+            JSON_WHOLE_PAYLOAD_KEY
+        ))));
+    }
+    let value: serde_json::Value =
+        serde_json::from_slice(payload).map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+    let value = ScalarImpl::Jsonb(JsonbVal::new(value));
+    writer.insert(|_| Ok(Some(value.clone())))
+}
 
 #[cfg(not(any(
     target_feature = "sse4.2",
@@ -32,6 +78,10 @@ impl SourceParser for JsonParser {
         use serde_json::Value;
 
         use crate::parser::common::json_parse_value;
+        if self.whole_payload {
+            return parse_whole_payload(payload, writer);
+        }
+
         let value: Value = serde_json::from_slice(payload)
             .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
 
@@ -59,6 +109,10 @@ impl SourceParser for JsonParser {
         use simd_json::{BorrowedValue, ValueAccess};
 
         use crate::parser::common::simd_json_parse_value;
+        if self.whole_payload {
+            return parse_whole_payload(payload, writer);
+        }
+
         let mut payload_mut = payload.to_vec();
 
         let value: BorrowedValue<'_> = simd_json::to_borrowed_value(&mut payload_mut)
@@ -79,6 +133,7 @@ impl SourceParser for JsonParser {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::str::FromStr;
 
     use itertools::Itertools;
@@ -86,13 +141,18 @@ mod tests {
     use risingwave_common::catalog::ColumnDesc;
     use risingwave_common::test_prelude::StreamChunkTestExt;
     use risingwave_common::types::{DataType, Decimal, ScalarImpl, ToOwnedDatum};
+    use risingwave_connector::source::{SourceMessage, SourceMeta};
     use risingwave_expr::vector_op::cast::{str_to_date, str_to_timestamp};
+    use risingwave_pb::catalog::SourceMetaKind;
 
-    use crate::{JsonParser, SourceColumnDesc, SourceParser, SourceStreamChunkBuilder};
+    use crate::{
+        JsonParser, SourceColumnDesc, SourceParser, SourceStreamChunkBuilder,
+        JSON_WHOLE_PAYLOAD_KEY,
+    };
 
     #[test]
     fn test_json_parser() {
-        let parser = JsonParser;
+        let parser = JsonParser::default();
         let descs = vec![
             SourceColumnDesc::simple("i32", DataType::Int32, 0.into()),
             SourceColumnDesc::simple("bool", DataType::Boolean, 2.into()),
@@ -199,7 +259,7 @@ mod tests {
 
     #[test]
     fn test_json_parser_failed() {
-        let parser = JsonParser;
+        let parser = JsonParser::default();
         let descs = vec![
             SourceColumnDesc::simple("v1", DataType::Int32, 0.into()),
             SourceColumnDesc::simple("v2", DataType::Int16, 1.into()),
@@ -237,7 +297,7 @@ mod tests {
 
     #[test]
     fn test_json_parse_struct() {
-        let parser = JsonParser;
+        let parser = JsonParser::default();
 
         let descs = vec![
             ColumnDesc::new_struct(
@@ -312,4 +372,115 @@ mod tests {
         ];
         assert_eq!(row, expected);
     }
+
+    #[test]
+    fn test_json_parse_jsonb() {
+        let parser = JsonParser::default();
+        let payload = br#"{"id": 1, "tags": ["a", {"b": null}]}"#;
+
+        // A `jsonb` column takes the value of its field.
+        let descs = vec![
+            SourceColumnDesc::simple("id", DataType::Int32, 0.into()),
+            SourceColumnDesc::simple("tags", DataType::Jsonb, 1.into()),
+        ];
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 1);
+        parser.parse(payload, builder.row_writer()).unwrap();
+        let chunk = builder.finish();
+        let (_, row) = chunk.rows().next().unwrap();
+        assert_eq!(
+            row.value_at(1).to_owned_datum(),
+            Some(ScalarImpl::Jsonb(r#"["a", {"b": null}]"#.parse().unwrap()))
+        );
+
+        // So does a single `jsonb` column, unless the whole payload is required.
+        let descs = vec![SourceColumnDesc::simple("tags", DataType::Jsonb, 0.into())];
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 1);
+        parser.parse(payload, builder.row_writer()).unwrap();
+        let chunk = builder.finish();
+        let (_, row) = chunk.rows().next().unwrap();
+        assert_eq!(
+            row.value_at(0).to_owned_datum(),
+            Some(ScalarImpl::Jsonb(r#"["a", {"b": null}]"#.parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_json_parse_whole_payload() {
+        let properties = HashMap::from([(JSON_WHOLE_PAYLOAD_KEY.to_string(), "true".to_string())]);
+        let parser = JsonParser::new(&properties).unwrap();
+        let payload = br#"{"id": 1, "tags": ["a", {"b": null}]}"#;
+
+        let descs = vec![SourceColumnDesc::simple("tags", DataType::Jsonb, 0.into())];
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 1);
+        parser.parse(payload, builder.row_writer()).unwrap();
+        let chunk = builder.finish();
+        let (_, row) = chunk.rows().next().unwrap();
+        assert_eq!(
+            row.value_at(0).to_owned_datum(),
+            Some(ScalarImpl::Jsonb(
+                std::str::from_utf8(payload).unwrap().parse().unwrap()
+            ))
+        );
+
+        // The only column must be of type `jsonb`.
+        for descs in [
+            vec![SourceColumnDesc::simple("id", DataType::Int32, 0.into())],
+            vec![
+                SourceColumnDesc::simple("id", DataType::Int32, 0.into()),
+                SourceColumnDesc::simple("tags", DataType::Jsonb, 1.into()),
+            ],
+        ] {
+            let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 1);
+            assert!(parser.parse(payload, builder.row_writer()).is_err());
+        }
+
+        let properties = HashMap::from([(JSON_WHOLE_PAYLOAD_KEY.to_string(), "yes".to_string())]);
+        assert!(JsonParser::new(&properties).is_err());
+    }
+
+    #[test]
+    fn test_json_parser_with_meta_columns() {
+        let parser = JsonParser::default();
+        let meta_column = |name: &str, data_type, id: i32, kind| SourceColumnDesc {
+            skip_parse: true,
+            meta_kind: Some(kind),
+            ..SourceColumnDesc::simple(name, data_type, id.into())
+        };
+        let descs = vec![
+            SourceColumnDesc::simple("v", DataType::Int32, 0.into()),
+            meta_column("ts", DataType::Timestampz, 1, SourceMetaKind::Timestamp),
+            meta_column("partition", DataType::Varchar, 2, SourceMetaKind::Partition),
+            meta_column("offset", DataType::Varchar, 3, SourceMetaKind::Offset),
+            meta_column("key", DataType::Varchar, 4, SourceMetaKind::Key),
+        ];
+        let message = SourceMessage {
+            payload: Some(br#"{"v": 1}"#.as_slice().into()),
+            offset: "42".to_string(),
+            split_id: "0".into(),
+            meta: SourceMeta {
+                timestamp: Some(1672531200000),
+                key: Some(b"k".as_slice().into()),
+                ..Default::default()
+            },
+        };
+        let mut builder = SourceStreamChunkBuilder::with_capacity(descs, 1);
+        parser
+            .parse(
+                message.payload.as_ref().unwrap(),
+                builder.row_writer().with_message(&message),
+            )
+            .unwrap();
+        let chunk = builder.finish();
+        let (_, row) = chunk.rows().next().unwrap();
+        assert_eq!(
+            row.to_owned_row().0,
+            vec![
+                Some(ScalarImpl::Int32(1)),
+                Some(ScalarImpl::Int64(1672531200000000)),
+                Some(ScalarImpl::Utf8("0".to_string())),
+                Some(ScalarImpl::Utf8("42".to_string())),
+                Some(ScalarImpl::Utf8("k".to_string())),
+            ]
+        );
+    }
 }
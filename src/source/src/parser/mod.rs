@@ -17,6 +17,7 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 pub use avro_parser::*;
+pub use csv_parser::*;
 pub use debezium::*;
 use itertools::Itertools;
 pub use json_parser::*;
@@ -24,13 +25,18 @@ pub use pb_parser::*;
 use risingwave_common::array::{ArrayBuilderImpl, Op, StreamChunk};
 use risingwave_common::error::ErrorCode::ProtocolError;
 use risingwave_common::error::{Result, RwError};
-use risingwave_common::types::Datum;
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_connector::source::SourceMessage;
+use risingwave_pb::catalog::SourceMetaKind;
 
+use crate::parser::canal::CanalJsonParser;
 use crate::parser::maxwell::MaxwellParser;
 use crate::{SourceColumnDesc, SourceFormat};
 
 mod avro_parser;
+mod canal;
 mod common;
+mod csv_parser;
 mod debezium;
 mod json_parser;
 mod maxwell;
@@ -61,6 +67,7 @@ impl SourceStreamChunkBuilder {
             descs: &self.descs,
             builders: &mut self.builders,
             op_builder: &mut self.op_builder,
+            message: None,
         }
     }
 
@@ -82,6 +89,8 @@ pub struct SourceStreamChunkRowWriter<'a> {
     descs: &'a [SourceColumnDesc],
     builders: &'a mut [ArrayBuilderImpl],
     op_builder: &'a mut Vec<Op>,
+    /// The message being parsed, whose metadata fills the meta columns.
+    message: Option<&'a SourceMessage>,
 }
 
 /// `WriteGuard` can't be constructed directly in other mods due to a private field, so it can be
@@ -95,11 +104,14 @@ trait OpAction {
 
     const DEFAULT_OUTPUT: Self::Output;
 
+    /// The output of a column not parsed from the payload, e.g. a meta column.
+    fn output_of(datum: Datum) -> Self::Output;
+
     fn apply(builder: &mut ArrayBuilderImpl, output: Self::Output);
 
     fn rollback(builder: &mut ArrayBuilderImpl);
 
-    fn finish(writer: SourceStreamChunkRowWriter<'_>);
+    fn finish(writer: &mut SourceStreamChunkRowWriter<'_>);
 }
 
 struct OpActionInsert;
@@ -109,6 +121,11 @@ impl OpAction for OpActionInsert {
 
     const DEFAULT_OUTPUT: Self::Output = None;
 
+    #[inline(always)]
+    fn output_of(datum: Datum) -> Self::Output {
+        datum
+    }
+
     #[inline(always)]
     fn apply(builder: &mut ArrayBuilderImpl, output: Datum) {
         builder.append_datum(&output)
@@ -120,7 +137,7 @@ impl OpAction for OpActionInsert {
     }
 
     #[inline(always)]
-    fn finish(writer: SourceStreamChunkRowWriter<'_>) {
+    fn finish(writer: &mut SourceStreamChunkRowWriter<'_>) {
         writer.op_builder.push(Op::Insert)
     }
 }
@@ -132,6 +149,11 @@ impl OpAction for OpActionDelete {
 
     const DEFAULT_OUTPUT: Self::Output = None;
 
+    #[inline(always)]
+    fn output_of(datum: Datum) -> Self::Output {
+        datum
+    }
+
     #[inline(always)]
     fn apply(builder: &mut ArrayBuilderImpl, output: Datum) {
         builder.append_datum(&output)
@@ -143,7 +165,7 @@ impl OpAction for OpActionDelete {
     }
 
     #[inline(always)]
-    fn finish(writer: SourceStreamChunkRowWriter<'_>) {
+    fn finish(writer: &mut SourceStreamChunkRowWriter<'_>) {
         writer.op_builder.push(Op::Delete)
     }
 }
@@ -155,6 +177,11 @@ impl OpAction for OpActionUpdate {
 
     const DEFAULT_OUTPUT: Self::Output = (None, None);
 
+    #[inline(always)]
+    fn output_of(datum: Datum) -> Self::Output {
+        (datum.clone(), datum)
+    }
+
     #[inline(always)]
     fn apply(builder: &mut ArrayBuilderImpl, output: (Datum, Datum)) {
         builder.append_datum(&output.0);
@@ -168,28 +195,69 @@ impl OpAction for OpActionUpdate {
     }
 
     #[inline(always)]
-    fn finish(writer: SourceStreamChunkRowWriter<'_>) {
+    fn finish(writer: &mut SourceStreamChunkRowWriter<'_>) {
         writer.op_builder.push(Op::UpdateDelete);
         writer.op_builder.push(Op::UpdateInsert);
     }
 }
 
-impl SourceStreamChunkRowWriter<'_> {
+/// Returns the value of a meta column from the metadata of the message.
+fn meta_datum(message: Option<&SourceMessage>, kind: SourceMetaKind) -> Datum {
+    let message = message?;
+    match kind {
+        // The timestamps are stored in microseconds.
+        SourceMetaKind::Timestamp => message
+            .meta
+            .timestamp
+            .map(|millis| ScalarImpl::Int64(millis * 1000)),
+        SourceMetaKind::Partition => Some(ScalarImpl::Utf8(message.split_id.to_string())),
+        SourceMetaKind::Offset => Some(ScalarImpl::Utf8(message.offset.clone())),
+        SourceMetaKind::Key => message
+            .meta
+            .key
+            .as_ref()
+            .map(|key| ScalarImpl::Utf8(String::from_utf8_lossy(key).into_owned())),
+        SourceMetaKind::Unspecified => None,
+    }
+}
+
+impl<'a> SourceStreamChunkRowWriter<'a> {
+    /// Fills the meta columns of the rows written with the metadata of `message`.
+    pub fn with_message(self, message: &'a SourceMessage) -> Self {
+        Self {
+            message: Some(message),
+            ..self
+        }
+    }
+
+    /// Returns whether the only column to be parsed is of type `jsonb`, which is required to store
+    /// the whole payload in it.
+    fn is_single_jsonb_column(&self) -> bool {
+        self.descs
+            .iter()
+            .filter(|desc| !desc.skip_parse)
+            .exactly_one()
+            .map_or(false, |desc| desc.data_type == DataType::Jsonb)
+    }
+
     fn do_action<A: OpAction>(
-        self,
+        mut self,
         mut f: impl FnMut(&SourceColumnDesc) -> Result<A::Output>,
     ) -> Result<WriteGuard> {
         // The closure `f` may fail so that a part of builders were appended incompletely.
         // Loop invariant: `builders[0..appended_idx)` has been appended on every iter ended or loop
         // exited.
         let mut appended_idx = 0;
+        let message = self.message;
 
         self.descs
             .iter()
             .zip_eq(self.builders.iter_mut())
             .enumerate()
             .try_for_each(|(idx, (desc, builder))| -> Result<()> {
-                let output = if desc.skip_parse {
+                let output = if let Some(kind) = desc.meta_kind {
+                    A::output_of(meta_datum(message, kind))
+                } else if desc.skip_parse {
                     A::DEFAULT_OUTPUT
                 } else {
                     f(desc)?
@@ -205,7 +273,48 @@ impl SourceStreamChunkRowWriter<'_> {
                     .for_each(A::rollback);
             })?;
 
-        A::finish(self);
+        A::finish(&mut self);
+
+        Ok(WriteGuard(()))
+    }
+
+    fn do_actions<A: OpAction, R>(
+        mut self,
+        rows: impl IntoIterator<Item = R>,
+        mut f: impl FnMut(&R, &SourceColumnDesc) -> Result<A::Output>,
+    ) -> Result<WriteGuard> {
+        // Parse all the rows before appending any of them, so that a failure leaves the builders
+        // untouched.
+        let mut outputs = vec![];
+        for row in rows {
+            let output = self
+                .descs
+                .iter()
+                .map(|desc| {
+                    if let Some(kind) = desc.meta_kind {
+                        Ok(A::output_of(meta_datum(self.message, kind)))
+                    } else if desc.skip_parse {
+                        Ok(A::DEFAULT_OUTPUT)
+                    } else {
+                        f(&row, desc)
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            outputs.push(output);
+        }
+        if outputs.is_empty() {
+            return Err(RwError::from(ProtocolError(
+                "no record to write in the message".to_string(),
+            )));
+        }
+
+        for output in outputs {
+            self.builders
+                .iter_mut()
+                .zip_eq(output)
+                .for_each(|(builder, output)| A::apply(builder, output));
+            A::finish(&mut self);
+        }
 
         Ok(WriteGuard(()))
     }
@@ -243,6 +352,36 @@ impl SourceStreamChunkRowWriter<'_> {
     ) -> Result<WriteGuard> {
         self.do_action::<OpActionUpdate>(f)
     }
+
+    /// Write an `Insert` record for each of the `rows` to the [`StreamChunk`], for the formats
+    /// that batch multiple rows in one message. No record is written if any of them fails.
+    pub fn insert_rows<R>(
+        self,
+        rows: impl IntoIterator<Item = R>,
+        f: impl FnMut(&R, &SourceColumnDesc) -> Result<Datum>,
+    ) -> Result<WriteGuard> {
+        self.do_actions::<OpActionInsert, R>(rows, f)
+    }
+
+    /// Write a `Delete` record for each of the `rows` to the [`StreamChunk`]. No record is written
+    /// if any of them fails.
+    pub fn delete_rows<R>(
+        self,
+        rows: impl IntoIterator<Item = R>,
+        f: impl FnMut(&R, &SourceColumnDesc) -> Result<Datum>,
+    ) -> Result<WriteGuard> {
+        self.do_actions::<OpActionDelete, R>(rows, f)
+    }
+
+    /// Write an `Update` record for each of the `rows` to the [`StreamChunk`]. No record is written
+    /// if any of them fails.
+    pub fn update_rows<R>(
+        self,
+        rows: impl IntoIterator<Item = R>,
+        f: impl FnMut(&R, &SourceColumnDesc) -> Result<(Datum, Datum)>,
+    ) -> Result<WriteGuard> {
+        self.do_actions::<OpActionUpdate, R>(rows, f)
+    }
 }
 
 /// `SourceParser` is the message parser, `ChunkReader` will parse the messages in `SourceReader`
@@ -256,7 +395,8 @@ pub trait SourceParser: Send + Sync + Debug + 'static {
     ///
     /// - `self`: A needs to be a member method because some format like Protobuf needs to be
     ///   pre-compiled.
-    /// - writer: Write exactly one record during a `parse` call.
+    /// - writer: Write exactly one record during a `parse` call, or the records of all the rows
+    ///   with the `*_rows` methods if the format batches multiple rows in one message.
     ///
     /// # Returns
     ///
@@ -271,6 +411,8 @@ pub enum SourceParserImpl {
     DebeziumJson(DebeziumJsonParser),
     Avro(AvroParser),
     Maxwell(MaxwellParser),
+    CanalJson(CanalJsonParser),
+    Csv(CsvParser),
 }
 
 impl SourceParserImpl {
@@ -285,6 +427,17 @@ impl SourceParserImpl {
             Self::DebeziumJson(parser) => parser.parse(payload, writer),
             Self::Avro(avro_parser) => avro_parser.parse(payload, writer),
             Self::Maxwell(maxwell_parser) => maxwell_parser.parse(payload, writer),
+            Self::CanalJson(canal_parser) => canal_parser.parse(payload, writer),
+            Self::Csv(parser) => parser.parse(payload, writer),
+        }
+    }
+
+    /// Prepares what is required to parse the payload, e.g. fetches the writer schema of an avro
+    /// message from the schema registry.
+    pub async fn prepare(&self, payload: &[u8]) -> Result<()> {
+        match self {
+            Self::Avro(avro_parser) => avro_parser.prepare(payload).await,
+            _ => Ok(()),
         }
     }
 
@@ -292,10 +445,11 @@ impl SourceParserImpl {
         format: &SourceFormat,
         properties: &HashMap<String, String>,
         schema_location: &str,
+        use_schema_registry: bool,
     ) -> Result<Arc<Self>> {
         const PROTOBUF_MESSAGE_KEY: &str = "proto.message";
         let parser = match format {
-            SourceFormat::Json => SourceParserImpl::Json(JsonParser),
+            SourceFormat::Json => SourceParserImpl::Json(JsonParser::new(properties)?),
             SourceFormat::Protobuf => {
                 let message_name = properties.get(PROTOBUF_MESSAGE_KEY).ok_or_else(|| {
                     RwError::from(ProtocolError(format!(
@@ -304,14 +458,22 @@ impl SourceParserImpl {
                     )))
                 })?;
                 SourceParserImpl::Protobuf(
-                    ProtobufParser::new(schema_location, message_name, properties.clone()).await?,
+                    ProtobufParser::new(
+                        schema_location,
+                        message_name,
+                        properties.clone(),
+                        use_schema_registry,
+                    )
+                    .await?,
                 )
             }
             SourceFormat::DebeziumJson => SourceParserImpl::DebeziumJson(DebeziumJsonParser),
-            SourceFormat::Avro => {
-                SourceParserImpl::Avro(AvroParser::new(schema_location, properties.clone()).await?)
-            }
+            SourceFormat::Avro => SourceParserImpl::Avro(
+                AvroParser::new(schema_location, properties.clone(), use_schema_registry).await?,
+            ),
             SourceFormat::Maxwell => SourceParserImpl::Maxwell(MaxwellParser),
+            SourceFormat::CanalJson => SourceParserImpl::CanalJson(CanalJsonParser),
+            SourceFormat::Csv => SourceParserImpl::Csv(CsvParser::new(properties)?),
             _ => {
                 return Err(RwError::from(ProtocolError(
                     "format not support".to_string(),
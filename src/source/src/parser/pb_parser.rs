@@ -17,18 +17,21 @@ use std::path::Path;
 
 use hyper::http::uri::InvalidUri;
 use hyper_tls::HttpsConnector;
-use itertools::Itertools;
 use prost_reflect::{
-    Cardinality, DescriptorPool, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor,
-    ReflectMessage, Value,
+    Cardinality, DynamicMessage, FieldDescriptor, MessageDescriptor, ReflectMessage, Value,
 };
 use risingwave_common::array::{ListValue, StructValue};
 use risingwave_common::error::ErrorCode::{
-    InternalError, InvalidConfigValue, InvalidParameterValue, NotImplemented, ProtocolError,
+    InternalError, InvalidConfigValue, InvalidParameterValue, ProtocolError,
 };
 use risingwave_common::error::{Result, RwError};
-use risingwave_common::types::{DataType, Datum, Decimal, OrderedF32, OrderedF64, ScalarImpl};
+use risingwave_common::types::{Datum, Decimal, JsonbVal, OrderedF32, OrderedF64, ScalarImpl};
 use risingwave_connector::aws_utils::{default_conn_config, s3_client, AwsConfigV2};
+use risingwave_connector::schema::protobuf::{
+    compile_proto, get_message_descriptor, is_well_known_type, pb_schema_to_columns, PB_STRUCT,
+    PB_TIMESTAMP,
+};
+use risingwave_connector::schema::schema_registry::{extract_schema_id, ConfluentSchemaResolver};
 use risingwave_pb::plan_common::ColumnDesc;
 use url::Url;
 
@@ -39,6 +42,8 @@ const PB_SCHEMA_LOCATION_S3_REGION: &str = "region";
 #[derive(Debug, Clone)]
 pub struct ProtobufParser {
     pub message_descriptor: MessageDescriptor,
+    /// Whether the messages are in the Confluent wire format, as produced with a schema registry.
+    confluent_wire_type: bool,
 }
 
 impl ProtobufParser {
@@ -46,7 +51,25 @@ impl ProtobufParser {
         location: &str,
         message_name: &str,
         props: HashMap<String, String>,
+        use_schema_registry: bool,
     ) -> Result<Self> {
+        if use_schema_registry {
+            let topic = props.get("kafka.topic").ok_or_else(|| {
+                RwError::from(ProtocolError(
+                    "kafka.topic is required to find the subject in schema registry".to_string(),
+                ))
+            })?;
+            let resolver = ConfluentSchemaResolver::new(location);
+            let (_, proto) = resolver
+                .get_raw_by_subject_latest(&format!("{}-value", topic))
+                .await?;
+            let schema_bytes = compile_proto(proto)?;
+            return Ok(Self {
+                message_descriptor: get_message_descriptor(&schema_bytes, location, message_name)?,
+                confluent_wire_type: true,
+            });
+        }
+
         let url = Url::parse(location)
             .map_err(|e| InternalError(format!("failed to parse url ({}): {}", location, e)))?;
 
@@ -72,19 +95,10 @@ impl ProtobufParser {
             )))),
         }?;
 
-        let pool = DescriptorPool::decode(&schema_bytes[..]).map_err(|e| {
-            ProtocolError(format!(
-                "cannot build descriptor pool from schema: {}, error: {}",
-                location, e
-            ))
-        })?;
-        let message_descriptor = pool.get_message_by_name(message_name).ok_or_else(|| {
-            ProtocolError(format!(
-                "cannot find message {} in schema: {}.\n poll is {:?}",
-                message_name, location, pool
-            ))
-        })?;
-        Ok(Self { message_descriptor })
+        Ok(Self {
+            message_descriptor: get_message_descriptor(&schema_bytes, location, message_name)?,
+            confluent_wire_type: false,
+        })
     }
 
     /// read binary schema from a local file
@@ -100,47 +114,24 @@ impl ProtobufParser {
 
     /// Maps the protobuf schema to relational schema.
     pub fn map_to_columns(&self) -> Result<Vec<ColumnDesc>> {
-        let mut columns = Vec::with_capacity(self.message_descriptor.fields().len());
-        let mut index = 0;
-        for field in self.message_descriptor.fields() {
-            columns.push(Self::pb_field_to_col_desc(&field, &mut index)?);
-        }
-
-        Ok(columns)
+        pb_schema_to_columns(&self.message_descriptor)
     }
+}
 
-    /// Maps a protobuf field to a RW column.
-    fn pb_field_to_col_desc(
-        field_descriptor: &FieldDescriptor,
-        index: &mut i32,
-    ) -> Result<ColumnDesc> {
-        let field_type = protobuf_type_mapping(field_descriptor)?;
-        if let Kind::Message(m) = field_descriptor.kind() {
-            let field_descs = if let DataType::List { .. } = field_type {
-                vec![]
-            } else {
-                m.fields()
-                    .map(|f| Self::pb_field_to_col_desc(&f, index))
-                    .collect::<Result<Vec<_>>>()?
-            };
-            *index += 1;
-            Ok(ColumnDesc {
-                column_id: *index,
-                name: field_descriptor.name().to_string(),
-                column_type: Some(field_type.to_protobuf()),
-                field_descs,
-                type_name: m.full_name().to_string(),
-            })
-        } else {
-            *index += 1;
-            Ok(ColumnDesc {
-                column_id: *index,
-                name: field_descriptor.name().to_string(),
-                column_type: Some(field_type.to_protobuf()),
-                ..Default::default()
-            })
-        }
+/// Strips the header of a message in the Confluent wire format, which is the schema id followed
+/// by the indexes of the message type in the `.proto` file.
+fn resolve_pb_header(payload: &[u8]) -> Result<&[u8]> {
+    let (_, mut payload) = extract_schema_id(payload)?;
+    let decode_varint = |payload: &mut &[u8]| {
+        prost::encoding::decode_varint(payload)
+            .map_err(|e| RwError::from(ProtocolError(format!("invalid message indexes: {}", e))))
+    };
+    // A single 0 is the shortcut of the first message type.
+    let count = decode_varint(&mut payload)?;
+    for _ in 0..count {
+        decode_varint(&mut payload)?;
     }
+    Ok(payload)
 }
 
 // TODO(Tao): Probably we should never allow to use S3 URI.
@@ -225,6 +216,9 @@ fn from_protobuf_value(field_desc: &FieldDescriptor, value: &Value) -> Result<Da
             })?;
             ScalarImpl::Utf8(enum_symbol.name().to_owned())
         }
+        Value::Message(dyn_msg) if is_well_known_type(&dyn_msg.descriptor()) => {
+            from_well_known_message(dyn_msg)?
+        }
         Value::Message(dyn_msg) => {
             let mut rw_values = Vec::with_capacity(dyn_msg.descriptor().fields().len());
             // fields is a btree map in descriptor
@@ -264,40 +258,81 @@ fn from_protobuf_value(field_desc: &FieldDescriptor, value: &Value) -> Result<Da
     Ok(Some(v))
 }
 
-/// Maps protobuf type to RW type.
-fn protobuf_type_mapping(field_descriptor: &FieldDescriptor) -> Result<DataType> {
-    let field_type = field_descriptor.kind();
-    let mut t = match field_type {
-        Kind::Bool => DataType::Boolean,
-        Kind::Double => DataType::Float64,
-        Kind::Float => DataType::Float32,
-        Kind::Int32 | Kind::Sfixed32 | Kind::Fixed32 => DataType::Int32,
-        Kind::Int64 | Kind::Sfixed64 | Kind::Fixed64 | Kind::Uint32 => DataType::Int64,
-        Kind::Uint64 => DataType::Decimal,
-        Kind::String => DataType::Varchar,
-        Kind::Message(m) => {
-            let fields = m
-                .fields()
-                .map(|f| protobuf_type_mapping(&f))
-                .collect::<Result<Vec<_>>>()?;
-            let field_names = m.fields().map(|f| f.name().to_string()).collect_vec();
-            DataType::new_struct(fields, field_names)
+fn from_well_known_message(dyn_msg: &DynamicMessage) -> Result<ScalarImpl> {
+    match dyn_msg.descriptor().full_name() {
+        PB_TIMESTAMP => {
+            let get_field = |name| dyn_msg.get_field_by_name(name).unwrap().into_owned();
+            let seconds = get_field("seconds").as_i64().unwrap_or_default();
+            let nanos = get_field("nanos").as_i32().unwrap_or_default();
+            Ok(ScalarImpl::Int64(seconds * 1_000_000 + nanos as i64 / 1000))
+        }
+        PB_STRUCT => Ok(ScalarImpl::Jsonb(JsonbVal::new(pb_json_to_json(dyn_msg)?))),
+        name => Err(RwError::from(InternalError(format!(
+            "protobuf parse error.unsupported well-known type {}",
+            name
+        )))),
+    }
+}
+
+/// Converts a `google.protobuf.Struct`, `ListValue` or `Value` to json.
+fn pb_json_to_json(dyn_msg: &DynamicMessage) -> Result<serde_json::Value> {
+    let invalid = || {
+        RwError::from(ProtocolError(format!(
+            "protobuf parse error.invalid json value {:?}",
+            dyn_msg
+        )))
+    };
+    let get_field = |name| dyn_msg.get_field_by_name(name).ok_or_else(invalid);
+    let json = match dyn_msg.descriptor().full_name() {
+        PB_STRUCT => {
+            let fields = get_field("fields")?;
+            let fields = fields.as_map().ok_or_else(invalid)?;
+            let mut object = serde_json::Map::with_capacity(fields.len());
+            for (key, value) in fields {
+                let key = key.as_str().ok_or_else(invalid)?;
+                let value = value.as_message().ok_or_else(invalid)?;
+                object.insert(key.to_string(), pb_json_to_json(value)?);
+            }
+            serde_json::Value::Object(object)
         }
-        Kind::Enum(_) => DataType::Varchar,
-        actual_type => {
-            return Err(NotImplemented(
-                format!("unsupported field type: {:?}", actual_type),
-                None.into(),
+        "google.protobuf.ListValue" => {
+            let values = get_field("values")?;
+            let values = values.as_list().ok_or_else(invalid)?;
+            serde_json::Value::Array(
+                values
+                    .iter()
+                    .map(|value| pb_json_to_json(value.as_message().ok_or_else(invalid)?))
+                    .collect::<Result<Vec<_>>>()?,
             )
-            .into());
         }
-    };
-    if field_descriptor.cardinality() == Cardinality::Repeated {
-        t = DataType::List {
-            datatype: Box::new(t),
+        "google.protobuf.Value" => {
+            // `kind` is a oneof, so at most one of the fields is set.
+            let Some(field_desc) = dyn_msg
+                .descriptor()
+                .fields()
+                .find(|field_desc| dyn_msg.has_field(field_desc))
+            else {
+                return Ok(serde_json::Value::Null);
+            };
+            let value = dyn_msg.get_field(&field_desc);
+            match field_desc.name() {
+                "number_value" => value
+                    .as_f64()
+                    .and_then(serde_json::Number::from_f64)
+                    .map_or(serde_json::Value::Null, serde_json::Value::Number),
+                "string_value" => {
+                    serde_json::Value::String(value.as_str().ok_or_else(invalid)?.to_string())
+                }
+                "bool_value" => serde_json::Value::Bool(value.as_bool().ok_or_else(invalid)?),
+                "struct_value" | "list_value" => {
+                    pb_json_to_json(value.as_message().ok_or_else(invalid)?)?
+                }
+                _ => serde_json::Value::Null,
+            }
         }
-    }
-    Ok(t)
+        _ => return Err(invalid()),
+    };
+    Ok(json)
 }
 
 impl SourceParser for ProtobufParser {
@@ -306,6 +341,11 @@ impl SourceParser for ProtobufParser {
         payload: &[u8],
         writer: crate::SourceStreamChunkRowWriter<'_>,
     ) -> Result<WriteGuard> {
+        let payload = if self.confluent_wire_type {
+            resolve_pb_header(payload)?
+        } else {
+            payload
+        };
         let message = DynamicMessage::decode(self.message_descriptor.clone(), payload)
             .map_err(|e| ProtocolError(format!("parse message failed: {}", e)))?;
         writer.insert(|column_desc| {
@@ -335,6 +375,7 @@ mod test {
 
     use std::path::PathBuf;
 
+    use itertools::Itertools;
     use risingwave_pb::data::data_type::TypeName as ProstTypeName;
 
     use super::*;
@@ -360,7 +401,7 @@ mod test {
         let location = schema_dir() + "/simple-schema";
         let message_name = "test.TestRecord";
         println!("location: {}", location);
-        let parser = ProtobufParser::new(&location, message_name, HashMap::new()).await?;
+        let parser = ProtobufParser::new(&location, message_name, HashMap::new(), false).await?;
         let value = DynamicMessage::decode(parser.message_descriptor, PRE_GEN_PROTO_DATA).unwrap();
 
         assert_eq!(
@@ -396,7 +437,7 @@ mod test {
         let location = schema_dir() + "/complex-schema";
         let message_name = "test.User";
 
-        let parser = ProtobufParser::new(&location, message_name, HashMap::new()).await?;
+        let parser = ProtobufParser::new(&location, message_name, HashMap::new(), false).await?;
         let columns = parser.map_to_columns().unwrap();
 
         assert_eq!(columns[0].name, "id".to_string());
@@ -436,4 +477,61 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_resolve_pb_header() {
+        // The shortcut of message indexes `[0]`.
+        let payload = [0, 0, 0, 0, 1, 0, 42, 43];
+        assert_eq!(resolve_pb_header(&payload).unwrap(), &[42, 43]);
+        // Message indexes `[1, 0]`.
+        let payload = [0, 0, 0, 0, 1, 2, 2, 0, 42, 43];
+        assert_eq!(resolve_pb_header(&payload).unwrap(), &[42, 43]);
+
+        assert!(resolve_pb_header(&[0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn test_well_known_types() -> Result<()> {
+        let proto = r#"
+            syntax = "proto3";
+            package test;
+            import "google/protobuf/timestamp.proto";
+            import "google/protobuf/struct.proto";
+            message Event {
+                int32 id = 1;
+                google.protobuf.Timestamp time = 2;
+                google.protobuf.Struct payload = 3;
+            }
+        "#;
+        let schema_bytes = compile_proto(proto.to_string())?;
+        let message_descriptor = get_message_descriptor(&schema_bytes, "registry", "test.Event")?;
+        let parser = ProtobufParser {
+            message_descriptor: message_descriptor.clone(),
+            confluent_wire_type: true,
+        };
+        let columns = parser.map_to_columns()?;
+        let types = columns
+            .iter()
+            .map(|c| c.column_type.as_ref().unwrap().get_type_name().unwrap())
+            .collect_vec();
+        assert_eq!(
+            types,
+            vec![
+                ProstTypeName::Int32,
+                ProstTypeName::Timestampz,
+                ProstTypeName::Jsonb
+            ]
+        );
+        assert!(columns[2].field_descs.is_empty());
+
+        let time_desc = message_descriptor.get_field_by_name("time").unwrap();
+        let mut time = DynamicMessage::new(time_desc.kind().as_message().unwrap().clone());
+        time.set_field_by_name("seconds", Value::I64(1));
+        time.set_field_by_name("nanos", Value::I32(2000));
+        assert_eq!(
+            from_protobuf_value(&time_desc, &Value::Message(time))?,
+            Some(ScalarImpl::Int64(1_000_002))
+        );
+        Ok(())
+    }
 }
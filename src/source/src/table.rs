@@ -12,13 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use anyhow::Context;
 use futures_async_stream::try_stream;
+use itertools::Itertools;
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use rand::seq::IteratorRandom;
+use risingwave_common::array::column::Column;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::{ColumnDesc, ColumnId};
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::DataType;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::{mpsc, oneshot};
 
@@ -43,8 +48,8 @@ struct TableSourceCore {
 pub struct TableSource {
     core: RwLock<TableSourceCore>,
 
-    /// All columns in this table.
-    column_descs: Vec<ColumnDesc>,
+    /// All columns in this table. New columns may be appended by `ALTER TABLE ADD COLUMN`.
+    column_descs: RwLock<Vec<ColumnDesc>>,
 }
 
 impl TableSource {
@@ -55,10 +60,16 @@ impl TableSource {
 
         Self {
             core: RwLock::new(core),
-            column_descs,
+            column_descs: RwLock::new(column_descs),
         }
     }
 
+    /// Replace the columns of this table after a schema change. Readers created afterwards will
+    /// select columns from the new descs.
+    pub fn update_column_descs(&self, column_descs: Vec<ColumnDesc>) {
+        *self.column_descs.write() = column_descs;
+    }
+
     /// Asynchronously write stream chunk into table. Changes written here will be simply passed to
     /// the associated streaming task via channel, and then be materialized to storage there.
     ///
@@ -82,12 +93,20 @@ impl TableSource {
                 .choose(&mut rand::thread_rng())
                 .context("no available table reader in streaming source executors")?;
 
+            // The chunk may come from a DML planned before some columns are appended, in which
+            // case the trailing columns are missing.
             #[cfg(debug_assertions)]
-            risingwave_common::util::schema_check::schema_check(
-                self.column_descs.iter().map(|c| &c.data_type),
-                chunk.columns(),
-            )
-            .expect("table source write chunk schema check failed");
+            {
+                let column_descs = self.column_descs.read();
+                risingwave_common::util::schema_check::schema_check(
+                    column_descs
+                        .iter()
+                        .take(chunk.columns().len())
+                        .map(|c| &c.data_type),
+                    chunk.columns(),
+                )
+                .expect("table source write chunk schema check failed");
+            }
 
             let (notifier_tx, notifier_rx) = oneshot::channel();
 
@@ -120,6 +139,10 @@ pub struct TableStreamReader {
 
     /// Mappings from the source column to the column to be read.
     column_indices: Vec<usize>,
+
+    /// Data types of the columns to be read. Used to fill the columns missing in the chunks
+    /// written before a schema change with NULLs.
+    data_types: Vec<DataType>,
 }
 
 impl TableStreamReader {
@@ -128,10 +151,19 @@ impl TableStreamReader {
         while let Some((chunk, notifier)) = self.rx.recv().await {
             let (ops, columns, bitmap) = chunk.into_inner();
 
+            let cardinality = ops.len();
             let selected_columns = self
                 .column_indices
                 .iter()
-                .map(|i| columns[*i].clone())
+                .zip_eq(&self.data_types)
+                .map(|(i, data_type)| match columns.get(*i) {
+                    Some(column) => column.clone(),
+                    None => {
+                        let mut builder = data_type.create_array_builder(cardinality);
+                        (0..cardinality).for_each(|_| builder.append_null());
+                        Column::new(Arc::new(builder.finish()))
+                    }
+                })
                 .collect();
             let chunk = StreamChunk::new(ops, selected_columns, bitmap);
 
@@ -147,21 +179,28 @@ impl TableSource {
     /// Create a new stream reader.
     #[expect(clippy::unused_async)]
     pub async fn stream_reader(&self, column_ids: Vec<ColumnId>) -> Result<TableStreamReader> {
-        let column_indices = column_ids
+        let column_descs = self.column_descs.read();
+        let (column_indices, data_types) = column_ids
             .into_iter()
             .map(|id| {
-                self.column_descs
+                let index = column_descs
                     .iter()
                     .position(|c| c.column_id == id)
-                    .expect("column id not exists")
+                    .expect("column id not exists");
+                (index, column_descs[index].data_type.clone())
             })
-            .collect();
+            .unzip();
+        drop(column_descs);
 
         let mut core = self.core.write();
         let (tx, rx) = mpsc::unbounded_channel();
         core.changes_txs.push(tx);
 
-        Ok(TableStreamReader { rx, column_indices })
+        Ok(TableStreamReader {
+            rx,
+            column_indices,
+            data_types,
+        })
     }
 }
 
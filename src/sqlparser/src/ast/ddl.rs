@@ -20,7 +20,9 @@ use core::fmt;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::ast::{display_comma_separated, display_separated, DataType, Expr, Ident, ObjectName};
+use crate::ast::{
+    display_comma_separated, display_separated, DataType, Expr, Ident, ObjectName, SetVariableValue,
+};
 use crate::tokenizer::Token;
 
 /// An `ALTER TABLE` (`Statement::AlterTable`) operation
@@ -133,6 +135,52 @@ impl fmt::Display for AlterTableOperation {
     }
 }
 
+/// An `ALTER SOURCE` (`Statement::AlterSource`) operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AlterSourceOperation {
+    /// `SET <param> = <value>`
+    SetParameter {
+        param: Ident,
+        value: SetVariableValue,
+    },
+    /// `RESET OFFSETS TO { EARLIEST | LATEST | TIMESTAMP <millis> }`
+    ResetOffsets { target: SourceOffsetTarget },
+}
+
+impl fmt::Display for AlterSourceOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlterSourceOperation::SetParameter { param, value } => {
+                write!(f, "SET {} = {}", param, value)
+            }
+            AlterSourceOperation::ResetOffsets { target } => {
+                write!(f, "RESET OFFSETS TO {}", target)
+            }
+        }
+    }
+}
+
+/// The position to rewind or fast-forward the offsets of a source to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SourceOffsetTarget {
+    Earliest,
+    Latest,
+    /// The first message whose timestamp is not earlier than the given milliseconds.
+    Timestamp(u64),
+}
+
+impl fmt::Display for SourceOffsetTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceOffsetTarget::Earliest => write!(f, "EARLIEST"),
+            SourceOffsetTarget::Latest => write!(f, "LATEST"),
+            SourceOffsetTarget::Timestamp(millis) => write!(f, "TIMESTAMP {}", millis),
+        }
+    }
+}
+
 /// An `ALTER COLUMN` (`Statement::AlterTable`) operation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -252,6 +300,60 @@ impl fmt::Display for TableConstraint {
     }
 }
 
+/// Watermark definition of a source or table column: `WATERMARK FOR <column> AS <expr>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SourceWatermark {
+    pub column: Ident,
+    pub expr: Expr,
+}
+
+impl fmt::Display for SourceWatermark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WATERMARK FOR {} AS {}", self.column, self.expr)
+    }
+}
+
+/// The metadata of the messages from a message broker included as a column of a source:
+/// `<kind> [AS <column>]` in `INCLUDE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SourceIncludeColumn {
+    pub kind: SourceMetaKind,
+    /// The name of the column, which defaults to the name of the kind.
+    pub alias: Option<Ident>,
+}
+
+impl fmt::Display for SourceIncludeColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(alias) = &self.alias {
+            write!(f, " AS {}", alias)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SourceMetaKind {
+    Timestamp,
+    Partition,
+    Offset,
+    Key,
+}
+
+impl fmt::Display for SourceMetaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceMetaKind::Timestamp => write!(f, "TIMESTAMP"),
+            SourceMetaKind::Partition => write!(f, "PARTITION"),
+            SourceMetaKind::Offset => write!(f, "OFFSET"),
+            SourceMetaKind::Key => write!(f, "KEY"),
+        }
+    }
+}
+
 /// SQL column definition
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -343,6 +445,8 @@ pub enum ColumnOption {
     },
     /// `CHECK (<expr>)`
     Check(Expr),
+    /// `AS <generation_expr>`, the column is computed from other columns of the row.
+    GeneratedColumns(Expr),
     /// Dialect-specific options, such as:
     /// - MySQL's `AUTO_INCREMENT` or SQLite's `AUTOINCREMENT`
     /// - ...
@@ -378,6 +482,7 @@ impl fmt::Display for ColumnOption {
                 Ok(())
             }
             Check(expr) => write!(f, "CHECK ({})", expr),
+            GeneratedColumns(expr) => write!(f, "AS {}", expr),
             DialectSpecific(val) => write!(f, "{}", display_separated(val, " ")),
         }
     }
@@ -32,8 +32,9 @@ use serde::{Deserialize, Serialize};
 
 pub use self::data_type::{DataType, StructField};
 pub use self::ddl::{
-    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef,
-    ReferentialAction, TableConstraint,
+    AlterColumnOperation, AlterSourceOperation, AlterTableOperation, ColumnDef, ColumnOption,
+    ColumnOptionDef, ReferentialAction, SourceIncludeColumn, SourceMetaKind, SourceOffsetTarget,
+    SourceWatermark, TableConstraint,
 };
 pub use self::operator::{BinaryOperator, UnaryOperator};
 pub use self::query::{
@@ -43,7 +44,7 @@ pub use self::query::{
 };
 pub use self::statement::*;
 pub use self::value::{DateTimeField, TrimWhereField, Value};
-use crate::keywords::Keyword;
+use crate::keywords::{Keyword, ALL_KEYWORDS};
 use crate::parser::{Parser, ParserError};
 
 pub struct DisplaySeparated<'a, T>
@@ -128,6 +129,25 @@ impl Ident {
             _ => self.value.to_lowercase(),
         }
     }
+
+    /// Create an identifier whose [`Ident::real_value`] is the given value, which is double-quoted
+    /// only if it can't be written as a bare identifier, e.g. with upper case letters or being a
+    /// keyword.
+    pub fn from_real_value(value: &str) -> Self {
+        let needs_quote = value.is_empty()
+            || value.starts_with(|c: char| c.is_ascii_digit())
+            || !value
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            || ALL_KEYWORDS
+                .binary_search(&value.to_uppercase().as_str())
+                .is_ok();
+        if needs_quote {
+            Ident::with_quote('"', value)
+        } else {
+            Ident::new(value)
+        }
+    }
 }
 
 impl From<&str> for Ident {
@@ -334,6 +354,8 @@ pub enum Expr {
     Array(Vec<Expr>),
     /// An array index expression e.g. `(ARRAY[1, 2])[1]` or `(current_schemas(FALSE))[1]`
     ArrayIndex { obj: Box<Expr>, index: Box<Expr> },
+    /// A positional parameter of a prepared statement, e.g. `$1`
+    Parameter { index: u64 },
 }
 
 impl fmt::Display for Expr {
@@ -528,6 +550,7 @@ impl fmt::Display for Expr {
                 write!(f, "{}[{}]", obj, index)?;
                 Ok(())
             }
+            Expr::Parameter { index } => write!(f, "${}", index),
             Expr::Array(exprs) => write!(
                 f,
                 "ARRAY[{}]",
@@ -708,6 +731,24 @@ pub enum ShowObject {
     Sink { schema: Option<Ident> },
     MaterializedSource { schema: Option<Ident> },
     Columns { table: ObjectName },
+    Jobs,
+    ProcessList,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShowCreateType {
+    Table,
+    MaterializedView,
+}
+
+impl fmt::Display for ShowCreateType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShowCreateType::Table => f.write_str("TABLE"),
+            ShowCreateType::MaterializedView => f.write_str("MATERIALIZED VIEW"),
+        }
+    }
 }
 
 impl fmt::Display for ShowObject {
@@ -735,6 +776,8 @@ impl fmt::Display for ShowObject {
             }
             ShowObject::Sink { schema } => write!(f, "SINKS{}", fmt_schema(schema)),
             ShowObject::Columns { table } => write!(f, "COLUMNS FROM {}", table),
+            ShowObject::Jobs => f.write_str("JOBS"),
+            ShowObject::ProcessList => f.write_str("PROCESSLIST"),
         }
     }
 }
@@ -773,6 +816,24 @@ impl fmt::Display for ExplainType {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExplainFormat {
+    Text,
+    Json,
+    Dot,
+}
+
+impl fmt::Display for ExplainFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExplainFormat::Text => f.write_str("TEXT"),
+            ExplainFormat::Json => f.write_str("JSON"),
+            ExplainFormat::Dot => f.write_str("DOT"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExplainOptions {
@@ -782,6 +843,10 @@ pub struct ExplainOptions {
     pub trace: bool,
     // explain's plan type
     pub explain_type: ExplainType,
+    // explain's output format
+    pub explain_format: ExplainFormat,
+    // Display the time and memory spent in each executor of explain analyze
+    pub timing: bool,
 }
 impl Default for ExplainOptions {
     fn default() -> Self {
@@ -789,6 +854,8 @@ impl Default for ExplainOptions {
             verbose: false,
             trace: false,
             explain_type: ExplainType::Physical,
+            explain_format: ExplainFormat::Text,
+            timing: true,
         }
     }
 }
@@ -808,6 +875,12 @@ impl fmt::Display for ExplainOptions {
             if self.explain_type == default.explain_type {
                 option_strs.push(self.explain_type.to_string());
             }
+            if self.explain_format != default.explain_format {
+                option_strs.push(format!("FORMAT {}", self.explain_format));
+            }
+            if !self.timing {
+                option_strs.push("TIMING false".to_string());
+            }
             write!(f, "{}", option_strs.iter().format(","))
         }
     }
@@ -832,13 +905,17 @@ pub enum Statement {
         columns: Vec<Ident>,
         /// A SQL query that specifies what to insert
         source: Box<Query>,
+        /// RETURNING
+        returning: Vec<SelectItem>,
     },
     Copy {
-        /// TABLE
-        table_name: ObjectName,
-        /// COLUMNS
-        columns: Vec<Ident>,
-        /// VALUES a vector of values to be copied
+        /// The table or the query to copy
+        source: CopySource,
+        /// Whether it's `COPY ... TO STDOUT`, otherwise `COPY ... FROM STDIN`
+        to: bool,
+        /// WITH options
+        options: Vec<CopyOption>,
+        /// VALUES a vector of values to be copied, which follow the statement inline
         values: Vec<Option<String>>,
     },
     /// UPDATE
@@ -849,6 +926,8 @@ pub enum Statement {
         assignments: Vec<Assignment>,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING
+        returning: Vec<SelectItem>,
     },
     /// DELETE
     Delete {
@@ -856,6 +935,8 @@ pub enum Statement {
         table_name: ObjectName,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING
+        returning: Vec<SelectItem>,
     },
     /// CREATE VIEW
     CreateView {
@@ -866,6 +947,8 @@ pub enum Statement {
         columns: Vec<Ident>,
         query: Box<Query>,
         with_options: Vec<SqlOption>,
+        /// `EMIT { IMMEDIATELY | ON WINDOW CLOSE }`, only for materialized views
+        emit_mode: Option<EmitMode>,
     },
     /// CREATE TABLE
     CreateTable {
@@ -877,6 +960,8 @@ pub enum Statement {
         /// Optional schema
         columns: Vec<ColumnDef>,
         constraints: Vec<TableConstraint>,
+        /// `WATERMARK FOR <column> AS <expr>` definitions
+        source_watermarks: Vec<SourceWatermark>,
         with_options: Vec<SqlOption>,
         /// `AS ( query )`
         query: Option<Box<Query>>,
@@ -899,12 +984,50 @@ pub enum Statement {
     },
     /// CREATE SINK
     CreateSink { stmt: CreateSinkStatement },
+    /// CREATE FUNCTION
+    CreateFunction {
+        or_replace: bool,
+        temporary: bool,
+        name: ObjectName,
+        args: Vec<DataType>,
+        return_type: DataType,
+        /// `LANGUAGE lang_name`
+        language: Ident,
+        /// `AS 'identifier'`, the name of the function on the UDF server or in the WASM module
+        identifier: String,
+        using: CreateFunctionUsing,
+    },
+    /// CREATE SECRET
+    CreateSecret {
+        if_not_exists: bool,
+        name: ObjectName,
+        /// The options of the secret, including its value. They are redacted when displayed.
+        with_options: Vec<SqlOption>,
+    },
     /// ALTER TABLE
     AlterTable {
         /// Table name
         name: ObjectName,
         operation: AlterTableOperation,
     },
+    /// ALTER SYSTEM SET <param> = <value>
+    AlterSystem {
+        param: Ident,
+        value: SetVariableValue,
+    },
+    /// ALTER SOURCE
+    AlterSource {
+        /// Source name
+        name: ObjectName,
+        operation: AlterSourceOperation,
+    },
+    /// ALTER MATERIALIZED VIEW <name> SET <param> = <value>
+    AlterMaterializedView {
+        /// View name
+        name: ObjectName,
+        param: Ident,
+        value: SetVariableValue,
+    },
     /// DESCRIBE TABLE OR SOURCE
     Describe {
         /// Table or Source name
@@ -912,8 +1035,23 @@ pub enum Statement {
     },
     /// SHOW COMMAND
     ShowObjects(ShowObject),
+    /// SHOW CREATE COMMAND
+    ShowCreateObject {
+        /// Show create object type
+        create_type: ShowCreateType,
+        /// Show create object name
+        name: ObjectName,
+    },
     /// DROP
     Drop(DropStatement),
+    /// DROP FUNCTION
+    DropFunction {
+        if_exists: bool,
+        name: ObjectName,
+        /// Argument types. `None` if the argument list is omitted.
+        args: Option<Vec<DataType>>,
+        drop_mode: Option<DropMode>,
+    },
     /// SET <variable>
     ///
     /// Note: this is not a standard SQL statement, but it is supported by at
@@ -1012,6 +1150,15 @@ pub enum Statement {
     ///
     /// Note: RisingWave specific statement.
     Flush,
+    /// `CANCEL { JOB | JOBS } id [, ...]`
+    ///
+    /// Note: RisingWave specific statement.
+    CancelJobs(Vec<u32>),
+    /// `KILL process_id`, which cancels the running queries of the session and closes its
+    /// connection.
+    ///
+    /// Note: RisingWave specific statement.
+    Kill(i32),
 }
 
 impl fmt::Display for Statement {
@@ -1051,29 +1198,44 @@ impl fmt::Display for Statement {
                 write!(f, "SHOW {}", show_object)?;
                 Ok(())
             }
+            Statement::ShowCreateObject { create_type, name } => {
+                write!(f, "SHOW CREATE {} {}", create_type, name)?;
+                Ok(())
+            }
             Statement::Insert {
                 table_name,
                 columns,
                 source,
+                returning,
             } => {
                 write!(f, "INSERT INTO {table_name} ", table_name = table_name,)?;
                 if !columns.is_empty() {
                     write!(f, "({}) ", display_comma_separated(columns))?;
                 }
-                write!(f, "{}", source)
+                write!(f, "{}", source)?;
+                if !returning.is_empty() {
+                    write!(f, " RETURNING {}", display_comma_separated(returning))?;
+                }
+                Ok(())
             }
 
             Statement::Copy {
-                table_name,
-                columns,
+                source,
+                to,
+                options,
                 values,
             } => {
-                write!(f, "COPY {}", table_name)?;
-                if !columns.is_empty() {
-                    write!(f, " ({})", display_comma_separated(columns))?;
+                write!(f, "COPY {}", source)?;
+                if *to {
+                    write!(f, " TO STDOUT")?;
+                } else {
+                    write!(f, " FROM STDIN")?;
+                }
+                if !options.is_empty() {
+                    write!(f, " ({})", display_comma_separated(options))?;
                 }
-                write!(f, " FROM stdin; ")?;
                 if !values.is_empty() {
+                    write!(f, "; ")?;
                     writeln!(f)?;
                     let mut delim = "";
                     for v in values {
@@ -1085,13 +1247,15 @@ impl fmt::Display for Statement {
                             write!(f, "\\N")?;
                         }
                     }
+                    write!(f, "\n\\.")?;
                 }
-                write!(f, "\n\\.")
+                Ok(())
             }
             Statement::Update {
                 table,
                 assignments,
                 selection,
+                returning,
             } => {
                 write!(f, "UPDATE {}", table)?;
                 if !assignments.is_empty() {
@@ -1100,16 +1264,23 @@ impl fmt::Display for Statement {
                 if let Some(selection) = selection {
                     write!(f, " WHERE {}", selection)?;
                 }
+                if !returning.is_empty() {
+                    write!(f, " RETURNING {}", display_comma_separated(returning))?;
+                }
                 Ok(())
             }
             Statement::Delete {
                 table_name,
                 selection,
+                returning,
             } => {
                 write!(f, "DELETE FROM {}", table_name)?;
                 if let Some(selection) = selection {
                     write!(f, " WHERE {}", selection)?;
                 }
+                if !returning.is_empty() {
+                    write!(f, " RETURNING {}", display_comma_separated(returning))?;
+                }
                 Ok(())
             }
             Statement::CreateDatabase {
@@ -1130,6 +1301,7 @@ impl fmt::Display for Statement {
                 query,
                 materialized,
                 with_options,
+                emit_mode,
             } => {
                 write!(
                     f,
@@ -1138,18 +1310,23 @@ impl fmt::Display for Statement {
                     materialized = if *materialized { "MATERIALIZED " } else { "" },
                     name = name
                 )?;
+                if !columns.is_empty() {
+                    write!(f, " ({})", display_comma_separated(columns))?;
+                }
                 if !with_options.is_empty() {
                     write!(f, " WITH ({})", display_comma_separated(with_options))?;
                 }
-                if !columns.is_empty() {
-                    write!(f, " ({})", display_comma_separated(columns))?;
+                write!(f, " AS {}", query)?;
+                if let Some(emit_mode) = emit_mode {
+                    write!(f, " EMIT {}", emit_mode)?;
                 }
-                write!(f, " AS {}", query)
+                Ok(())
             }
             Statement::CreateTable {
                 name,
                 columns,
                 constraints,
+                source_watermarks,
                 with_options,
                 or_replace,
                 if_not_exists,
@@ -1171,12 +1348,18 @@ impl fmt::Display for Statement {
                     temporary = if *temporary { "TEMPORARY " } else { "" },
                     name = name,
                 )?;
-                if !columns.is_empty() || !constraints.is_empty() {
+                if !columns.is_empty() || !constraints.is_empty() || !source_watermarks.is_empty() {
                     write!(f, " ({}", display_comma_separated(columns))?;
                     if !columns.is_empty() && !constraints.is_empty() {
                         write!(f, ", ")?;
                     }
-                    write!(f, "{})", display_comma_separated(constraints))?;
+                    write!(f, "{}", display_comma_separated(constraints))?;
+                    if !(columns.is_empty() && constraints.is_empty())
+                        && !source_watermarks.is_empty()
+                    {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{})", display_comma_separated(source_watermarks))?;
                 } else if query.is_none() {
                     // PostgreSQL allows `CREATE TABLE t ();`, but requires empty parens
                     write!(f, " ()")?;
@@ -1233,7 +1416,72 @@ impl fmt::Display for Statement {
             Statement::AlterTable { name, operation } => {
                 write!(f, "ALTER TABLE {} {}", name, operation)
             }
+            Statement::AlterSystem { param, value } => {
+                write!(f, "ALTER SYSTEM SET {} = {}", param, value)
+            }
+            Statement::AlterSource { name, operation } => {
+                write!(f, "ALTER SOURCE {} {}", name, operation)
+            }
+            Statement::AlterMaterializedView { name, param, value } => {
+                write!(f, "ALTER MATERIALIZED VIEW {} SET {} = {}", name, param, value)
+            }
+            Statement::CreateFunction {
+                or_replace,
+                temporary,
+                name,
+                args,
+                return_type,
+                language,
+                identifier,
+                using,
+            } => write!(
+                f,
+                "CREATE {or_replace}{temp}FUNCTION {name}({args}) RETURNS {return_type} \
+                 LANGUAGE {language} AS '{identifier}' {using}",
+                or_replace = if *or_replace { "OR REPLACE " } else { "" },
+                temp = if *temporary { "TEMPORARY " } else { "" },
+                args = display_comma_separated(args),
+                identifier = value::escape_single_quote_string(identifier),
+            ),
+            Statement::CreateSecret {
+                if_not_exists,
+                name,
+                with_options,
+            } => {
+                write!(
+                    f,
+                    "CREATE SECRET {if_not_exists}{name}",
+                    if_not_exists = if *if_not_exists { "IF NOT EXISTS " } else { "" },
+                )?;
+                if !with_options.is_empty() {
+                    let redacted = with_options
+                        .iter()
+                        .map(|option| format!("{} = '[REDACTED]'", option.name))
+                        .join(", ");
+                    write!(f, " WITH ({})", redacted)?;
+                }
+                Ok(())
+            }
             Statement::Drop(stmt) => write!(f, "DROP {}", stmt),
+            Statement::DropFunction {
+                if_exists,
+                name,
+                args,
+                drop_mode,
+            } => {
+                write!(f, "DROP FUNCTION")?;
+                if *if_exists {
+                    write!(f, " IF EXISTS")?;
+                }
+                write!(f, " {}", name)?;
+                if let Some(args) = args {
+                    write!(f, "({})", display_comma_separated(args))?;
+                }
+                if let Some(drop_mode) = drop_mode {
+                    write!(f, " {}", drop_mode)?;
+                }
+                Ok(())
+            }
             Statement::SetVariable {
                 local,
                 variable,
@@ -1390,6 +1638,12 @@ impl fmt::Display for Statement {
             Statement::Flush => {
                 write!(f, "FLUSH")
             }
+            Statement::CancelJobs(job_ids) => {
+                write!(f, "CANCEL JOBS {}", display_comma_separated(job_ids))
+            }
+            Statement::Kill(process_id) => {
+                write!(f, "KILL {}", process_id)
+            }
             Statement::BEGIN { modes } => {
                 write!(f, "BEGIN")?;
                 if !modes.is_empty() {
@@ -1662,6 +1916,8 @@ pub struct Function {
     // aggregate functions may contain order_by_clause
     pub order_by: Vec<OrderByExpr>,
     pub filter: Option<Box<Expr>>,
+    // ordered-set aggregate functions specify eg `WITHIN GROUP (ORDER BY x)`
+    pub within_group: Option<Box<OrderByExpr>>,
 }
 
 impl Function {
@@ -1673,6 +1929,7 @@ impl Function {
             distinct: false,
             order_by: vec![],
             filter: None,
+            within_group: None,
         }
     }
 }
@@ -1692,6 +1949,9 @@ impl fmt::Display for Function {
             },
             display_comma_separated(&self.order_by),
         )?;
+        if let Some(o) = &self.within_group {
+            write!(f, " WITHIN GROUP (ORDER BY {})", o)?;
+        }
         if let Some(o) = &self.over {
             write!(f, " OVER ({})", o)?;
         }
@@ -1702,6 +1962,56 @@ impl fmt::Display for Function {
     }
 }
 
+/// The `USING` clause of `CREATE FUNCTION`, which tells where the function body comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CreateFunctionUsing {
+    /// `USING LINK 'link'`, the address of an external UDF server
+    Link(String),
+    /// `USING BASE64 'module'`, a base64-encoded WASM module
+    Base64(String),
+}
+
+impl fmt::Display for CreateFunctionUsing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CreateFunctionUsing::Link(link) => {
+                write!(
+                    f,
+                    "USING LINK '{}'",
+                    value::escape_single_quote_string(link)
+                )
+            }
+            CreateFunctionUsing::Base64(module) => {
+                write!(
+                    f,
+                    "USING BASE64 '{}'",
+                    value::escape_single_quote_string(module)
+                )
+            }
+        }
+    }
+}
+
+/// When a materialized view emits its changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EmitMode {
+    /// Emit the changes as soon as possible, which is the default.
+    Immediately,
+    /// Emit only the final results of a window once it's closed by the watermark.
+    OnWindowClose,
+}
+
+impl fmt::Display for EmitMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EmitMode::Immediately => "IMMEDIATELY",
+            EmitMode::OnWindowClose => "ON WINDOW CLOSE",
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ObjectType {
@@ -1715,6 +2025,7 @@ pub enum ObjectType {
     Sink,
     Database,
     User,
+    Secret,
 }
 
 impl fmt::Display for ObjectType {
@@ -1730,6 +2041,7 @@ impl fmt::Display for ObjectType {
             ObjectType::Sink => "SINK",
             ObjectType::Database => "DATABASE",
             ObjectType::User => "USER",
+            ObjectType::Secret => "SECRET",
         })
     }
 }
@@ -1756,9 +2068,11 @@ impl ParseTo for ObjectType {
             ObjectType::Database
         } else if parser.parse_keyword(Keyword::USER) {
             ObjectType::User
+        } else if parser.parse_keyword(Keyword::SECRET) {
+            ObjectType::Secret
         } else {
             return parser.expected(
-                "TABLE, VIEW, INDEX, MATERIALIZED VIEW, SOURCE, MATERIALIZED SOURCE, SINK, SCHEMA, DATABASE or USER after DROP",
+                "TABLE, VIEW, INDEX, MATERIALIZED VIEW, SOURCE, MATERIALIZED SOURCE, SINK, SCHEMA, DATABASE, USER or SECRET after DROP",
                 parser.peek_token(),
             );
         };
@@ -1779,6 +2093,61 @@ impl fmt::Display for SqlOption {
     }
 }
 
+/// The source or destination of `COPY`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CopySource {
+    Table {
+        table_name: ObjectName,
+        columns: Vec<Ident>,
+    },
+    Query(Box<Query>),
+}
+
+impl fmt::Display for CopySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopySource::Table {
+                table_name,
+                columns,
+            } => {
+                write!(f, "{}", table_name)?;
+                if !columns.is_empty() {
+                    write!(f, " ({})", display_comma_separated(columns))?;
+                }
+                Ok(())
+            }
+            CopySource::Query(query) => write!(f, "({})", query),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CopyOption {
+    Format(Ident),
+    Delimiter(char),
+    Null(String),
+    Header(bool),
+}
+
+impl fmt::Display for CopyOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyOption::Format(format) => write!(f, "FORMAT {}", format),
+            CopyOption::Delimiter(delimiter) => write!(
+                f,
+                "DELIMITER {}",
+                Value::SingleQuotedString(delimiter.to_string())
+            ),
+            CopyOption::Null(null) => {
+                write!(f, "NULL {}", Value::SingleQuotedString(null.clone()))
+            }
+            CopyOption::Header(header) => write!(f, "HEADER {}", header),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TransactionMode {
@@ -330,6 +330,9 @@ impl fmt::Display for TableWithJoins {
 pub enum TableFactor {
     Table {
         name: ObjectName,
+        /// Whether `FOR SYSTEM_TIME AS OF PROCTIME()` is specified, i.e., the table is the
+        /// lookup side of a process-time temporal join.
+        for_system_time_as_of_proctime: bool,
         alias: Option<TableAlias>,
     },
     Derived {
@@ -355,8 +358,15 @@ pub enum TableFactor {
 impl fmt::Display for TableFactor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TableFactor::Table { name, alias } => {
+            TableFactor::Table {
+                name,
+                for_system_time_as_of_proctime,
+                alias,
+            } => {
                 write!(f, "{}", name)?;
+                if *for_system_time_as_of_proctime {
+                    write!(f, " FOR SYSTEM_TIME AS OF PROCTIME()")?;
+                }
                 if let Some(alias) = alias {
                     write!(f, " AS {}", alias)?;
                 }
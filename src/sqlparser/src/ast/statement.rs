@@ -20,7 +20,8 @@ use serde::{Deserialize, Serialize};
 
 use super::ObjectType;
 use crate::ast::{
-    display_comma_separated, display_separated, ColumnDef, ObjectName, SqlOption, TableConstraint,
+    display_comma_separated, display_separated, ColumnDef, ObjectName, Query, SourceIncludeColumn,
+    SourceWatermark, SqlOption, TableConstraint,
 };
 use crate::keywords::Keyword;
 use crate::parser::{Parser, ParserError};
@@ -75,6 +76,9 @@ pub struct CreateSourceStatement {
     pub if_not_exists: bool,
     pub columns: Vec<ColumnDef>,
     pub constraints: Vec<TableConstraint>,
+    pub source_watermarks: Vec<SourceWatermark>,
+    /// The metadata of the messages included as columns: `INCLUDE <kind> [AS <column>], ...`.
+    pub include_columns: Vec<SourceIncludeColumn>,
     pub source_name: ObjectName,
     pub with_properties: WithProperties,
     pub source_schema: SourceSchema,
@@ -88,7 +92,9 @@ pub enum SourceSchema {
     Json,             // Keyword::JSON
     DebeziumJson,     // Keyword::DEBEZIUM_JSON
     Avro(AvroSchema), // Keyword::AVRO
-    Maxwell,
+    Maxwell,          // Keyword::MAXWELL
+    CanalJson,        // Keyword::CANAL_JSON
+    Csv,              // Keyword::CSV
 }
 
 impl ParseTo for SourceSchema {
@@ -105,9 +111,14 @@ impl ParseTo for SourceSchema {
             SourceSchema::Avro(avro_schema)
         } else if p.parse_keywords(&[Keyword::MAXWELL]) {
             SourceSchema::Maxwell
+        } else if p.parse_keywords(&[Keyword::CANAL_JSON]) {
+            SourceSchema::CanalJson
+        } else if p.parse_keywords(&[Keyword::CSV]) {
+            SourceSchema::Csv
         } else {
             return Err(ParserError::ParserError(
-                "expected JSON | PROTOBUF | DEBEZIUM JSON | AVRO after ROW FORMAT".to_string(),
+                "expected JSON | PROTOBUF | DEBEZIUM_JSON | AVRO | MAXWELL | CANAL_JSON | CSV after ROW FORMAT"
+                    .to_string(),
             ));
         };
         Ok(schema)
@@ -120,6 +131,8 @@ impl fmt::Display for SourceSchema {
             SourceSchema::Protobuf(protobuf_schema) => write!(f, "PROTOBUF {}", protobuf_schema),
             SourceSchema::Json => write!(f, "JSON"),
             SourceSchema::Maxwell => write!(f, "MAXWELL"),
+            SourceSchema::CanalJson => write!(f, "CANAL_JSON"),
+            SourceSchema::Csv => write!(f, "CSV"),
             SourceSchema::DebeziumJson => write!(f, "DEBEZIUM JSON"),
             SourceSchema::Avro(avro_schema) => write!(f, "AVRO {}", avro_schema),
         }
@@ -130,6 +143,7 @@ impl fmt::Display for SourceSchema {
 //     [Keyword::MESSAGE],
 //     message_name: AstString,
 //     [Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION],
+//     use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
 //     row_schema_location: AstString,
 // });
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -137,6 +151,8 @@ impl fmt::Display for SourceSchema {
 pub struct ProtobufSchema {
     pub message_name: AstString,
     pub row_schema_location: AstString,
+    /// Whether `row_schema_location` is the url of a Confluent Schema Registry.
+    pub use_schema_registry: bool,
 }
 
 impl ParseTo for ProtobufSchema {
@@ -144,10 +160,15 @@ impl ParseTo for ProtobufSchema {
         impl_parse_to!([Keyword::MESSAGE], p);
         impl_parse_to!(message_name: AstString, p);
         impl_parse_to!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], p);
+        impl_parse_to!(
+            use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
+            p
+        );
         impl_parse_to!(row_schema_location: AstString, p);
         Ok(Self {
             message_name,
             row_schema_location,
+            use_schema_registry,
         })
     }
 }
@@ -158,6 +179,11 @@ impl fmt::Display for ProtobufSchema {
         impl_fmt_display!([Keyword::MESSAGE], v);
         impl_fmt_display!(message_name, v, self);
         impl_fmt_display!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], v);
+        impl_fmt_display!(
+            use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
+            v,
+            self
+        );
         impl_fmt_display!(row_schema_location, v, self);
         v.iter().join(" ").fmt(f)
     }
@@ -167,6 +193,7 @@ impl fmt::Display for ProtobufSchema {
 //     [Keyword::MESSAGE],
 //     message_name: AstString,
 //     [Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION],
+//     use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
 //     row_schema_location: AstString,
 // });
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -174,6 +201,8 @@ impl fmt::Display for ProtobufSchema {
 pub struct AvroSchema {
     pub message_name: AstString,
     pub row_schema_location: AstString,
+    /// Whether `row_schema_location` is the url of a Confluent Schema Registry.
+    pub use_schema_registry: bool,
 }
 
 impl ParseTo for AvroSchema {
@@ -181,10 +210,15 @@ impl ParseTo for AvroSchema {
         impl_parse_to!([Keyword::MESSAGE], p);
         impl_parse_to!(message_name: AstString, p);
         impl_parse_to!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], p);
+        impl_parse_to!(
+            use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
+            p
+        );
         impl_parse_to!(row_schema_location: AstString, p);
         Ok(Self {
             message_name,
             row_schema_location,
+            use_schema_registry,
         })
     }
 }
@@ -195,6 +229,11 @@ impl fmt::Display for AvroSchema {
         impl_fmt_display!([Keyword::MESSAGE], v);
         impl_fmt_display!(message_name, v, self);
         impl_fmt_display!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], v);
+        impl_fmt_display!(
+            use_schema_registry => [Keyword::CONFLUENT, Keyword::SCHEMA, Keyword::REGISTRY],
+            v,
+            self
+        );
         impl_fmt_display!(row_schema_location, v, self);
         v.iter().join(" ").fmt(f)
     }
@@ -206,7 +245,8 @@ impl ParseTo for CreateSourceStatement {
         impl_parse_to!(source_name: ObjectName, p);
 
         // parse columns
-        let (columns, constraints) = p.parse_columns()?;
+        let (columns, constraints, source_watermarks) = p.parse_columns()?;
+        let include_columns = p.parse_include_columns()?;
 
         impl_parse_to!(with_properties: WithProperties, p);
         impl_parse_to!([Keyword::ROW, Keyword::FORMAT], p);
@@ -215,6 +255,8 @@ impl ParseTo for CreateSourceStatement {
             if_not_exists,
             columns,
             constraints,
+            source_watermarks,
+            include_columns,
             source_name,
             with_properties,
             source_schema,
@@ -227,6 +269,12 @@ impl fmt::Display for CreateSourceStatement {
         let mut v: Vec<String> = vec![];
         impl_fmt_display!(if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS], v, self);
         impl_fmt_display!(source_name, v, self);
+        if !self.include_columns.is_empty() {
+            v.push(format!(
+                "INCLUDE {}",
+                display_comma_separated(&self.include_columns)
+            ));
+        }
         impl_fmt_display!(with_properties, v, self);
         impl_fmt_display!([Keyword::ROW, Keyword::FORMAT], v);
         impl_fmt_display!(source_schema, v, self);
@@ -234,11 +282,42 @@ impl fmt::Display for CreateSourceStatement {
     }
 }
 
+/// The relation a sink reads from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CreateSink {
+    /// `FROM <materialized_view>`
+    From(ObjectName),
+    /// `AS <query>`
+    AsQuery(Box<Query>),
+}
+
+impl ParseTo for CreateSink {
+    fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
+        if p.parse_keyword(Keyword::FROM) {
+            impl_parse_to!(materialized_view: ObjectName, p);
+            Ok(Self::From(materialized_view))
+        } else if p.parse_keyword(Keyword::AS) {
+            Ok(Self::AsQuery(Box::new(p.parse_query()?)))
+        } else {
+            p.expected("FROM or AS", p.peek_token())
+        }
+    }
+}
+
+impl fmt::Display for CreateSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::From(materialized_view) => write!(f, "FROM {}", materialized_view),
+            Self::AsQuery(query) => write!(f, "AS {}", query),
+        }
+    }
+}
+
 // sql_grammar!(CreateSinkStatement {
 //     if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS],
 //     sink_name: Ident,
-//     [Keyword::FROM],
-//     materialized_view: Ident,
+//     sink_from: CreateSink,
 //     with_properties: AstOption<WithProperties>,
 // });
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -247,23 +326,20 @@ pub struct CreateSinkStatement {
     pub if_not_exists: bool,
     pub sink_name: ObjectName,
     pub with_properties: WithProperties,
-    pub materialized_view: ObjectName,
+    pub sink_from: CreateSink,
 }
 
 impl ParseTo for CreateSinkStatement {
     fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
         impl_parse_to!(if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS], p);
         impl_parse_to!(sink_name: ObjectName, p);
-
-        p.expect_keyword(Keyword::FROM)?;
-        impl_parse_to!(materialized_view: ObjectName, p);
-
+        impl_parse_to!(sink_from: CreateSink, p);
         impl_parse_to!(with_properties: WithProperties, p);
         Ok(Self {
             if_not_exists,
             sink_name,
             with_properties,
-            materialized_view,
+            sink_from,
         })
     }
 }
@@ -273,8 +349,7 @@ impl fmt::Display for CreateSinkStatement {
         let mut v: Vec<String> = vec![];
         impl_fmt_display!(if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS], v, self);
         impl_fmt_display!(sink_name, v, self);
-        impl_fmt_display!([Keyword::FROM], v);
-        impl_fmt_display!(materialized_view, v, self);
+        impl_fmt_display!(sink_from, v, self);
         impl_fmt_display!(with_properties, v, self);
         v.iter().join(" ").fmt(f)
     }
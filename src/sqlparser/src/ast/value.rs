@@ -17,6 +17,8 @@ use core::fmt;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use super::ObjectName;
+
 /// Primitive SQL values such as number and string
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -54,6 +56,8 @@ pub enum Value {
     },
     /// `NULL` value
     Null,
+    /// `SECRET secret_name`, a reference to a secret in the `WITH` options of connectors
+    SecretRef(ObjectName),
 }
 
 impl fmt::Display for Value {
@@ -106,6 +110,7 @@ impl fmt::Display for Value {
                 Ok(())
             }
             Value::Null => write!(f, "NULL"),
+            Value::SecretRef(name) => write!(f, "SECRET {}", name),
         }
     }
 }
@@ -91,6 +91,7 @@ define_keywords!(
     AUTHORIZATION,
     AVG,
     AVRO,
+    BASE64,
     BEGIN,
     BEGIN_FRAME,
     BEGIN_PARTITION,
@@ -107,6 +108,8 @@ define_keywords!(
     CACHE,
     CALL,
     CALLED,
+    CANAL_JSON,
+    CANCEL,
     CARDINALITY,
     CASCADE,
     CASCADED,
@@ -134,6 +137,7 @@ define_keywords!(
     COMMITTED,
     CONCURRENTLY,
     CONDITION,
+    CONFLUENT,
     CONNECT,
     CONSTRAINT,
     CONTAINS,
@@ -178,6 +182,7 @@ define_keywords!(
     DEFAULT,
     DEFERRABLE,
     DELETE,
+    DELIMITER,
     DENSE_RANK,
     DEREF,
     DESC,
@@ -189,12 +194,15 @@ define_keywords!(
     DISTRIBUTED,
     DISTSQL,
     DO,
+    DOT,
     DOUBLE,
     DROP,
     DYNAMIC,
     EACH,
+    EARLIEST,
     ELEMENT,
     ELSE,
+    EMIT,
     ENCRYPTED,
     END,
     END_EXEC = "END-EXEC",
@@ -247,6 +255,7 @@ define_keywords!(
     IF,
     IGNORE,
     ILIKE,
+    IMMEDIATELY,
     IN,
     INCLUDE,
     INDEX,
@@ -265,18 +274,24 @@ define_keywords!(
     IS,
     ISNULL,
     ISOLATION,
+    JOB,
+    JOBS,
     JOIN,
     JSON,
+    JSONB,
     KEY,
+    KILL,
     LANGUAGE,
     LARGE,
     LAST,
+    LATEST,
     LATERAL,
     LEADING,
     LEFT,
     LEVEL,
     LIKE,
     LIMIT,
+    LINK,
     LN,
     LOCAL,
     LOCALTIME,
@@ -327,6 +342,7 @@ define_keywords!(
     OCTET_LENGTH,
     OF,
     OFFSET,
+    OFFSETS,
     OLD,
     ON,
     ONLY,
@@ -365,6 +381,7 @@ define_keywords!(
     PRIMARY,
     PRIVILEGES,
     PROCEDURE,
+    PROCESSLIST,
     PROTOBUF,
     PURGE,
     RANGE,
@@ -378,6 +395,7 @@ define_keywords!(
     REFERENCES,
     REFERENCING,
     REGCLASS,
+    REGISTRY,
     REGR_AVGX,
     REGR_AVGY,
     REGR_COUNT,
@@ -392,6 +410,7 @@ define_keywords!(
     REPAIR,
     REPEATABLE,
     REPLACE,
+    RESET,
     RESTRICT,
     RESULT,
     RETURN,
@@ -412,6 +431,7 @@ define_keywords!(
     SCROLL,
     SEARCH,
     SECOND,
+    SECRET,
     SELECT,
     SENSITIVE,
     SEQUENCE,
@@ -446,6 +466,7 @@ define_keywords!(
     STDDEV_POP,
     STDDEV_SAMP,
     STDIN,
+    STDOUT,
     STORED,
     STRING,
     STRUCT,
@@ -474,6 +495,7 @@ define_keywords!(
     TIMESTAMP,
     TIMEZONE_HOUR,
     TIMEZONE_MINUTE,
+    TIMING,
     TINYINT,
     TO,
     TOP,
@@ -518,6 +540,7 @@ define_keywords!(
     VIEW,
     VIEWS,
     VIRTUAL,
+    WATERMARK,
     WHEN,
     WHENEVER,
     WHERE,
@@ -555,6 +578,8 @@ pub const RESERVED_FOR_TABLE_ALIAS: &[Keyword] = &[
     Keyword::UNION,
     Keyword::EXCEPT,
     Keyword::INTERSECT,
+    Keyword::EMIT,
+    Keyword::RETURNING,
     // Reserved only as a table alias in the `FROM`/`JOIN` clauses:
     Keyword::ON,
     Keyword::JOIN,
@@ -593,6 +618,8 @@ pub const RESERVED_FOR_COLUMN_ALIAS: &[Keyword] = &[
     Keyword::UNION,
     Keyword::EXCEPT,
     Keyword::INTERSECT,
+    Keyword::EMIT,
+    Keyword::RETURNING,
     Keyword::CLUSTER,
     // Reserved only as a column alias in the `SELECT` clause
     Keyword::FROM,
@@ -202,6 +202,8 @@ impl Parser {
                 Keyword::PREPARE => Ok(self.parse_prepare()?),
                 Keyword::COMMENT => Ok(self.parse_comment()?),
                 Keyword::FLUSH => Ok(Statement::Flush),
+                Keyword::CANCEL => Ok(self.parse_cancel_jobs()?),
+                Keyword::KILL => Ok(self.parse_kill_process()?),
                 _ => self.expected("an SQL statement", Token::Word(w)),
             },
             Token::LParen => {
@@ -476,6 +478,12 @@ impl Parser {
                 self.prev_token();
                 Ok(Expr::Value(self.parse_value()?))
             }
+            Token::Parameter(number) => {
+                let index = number.parse().map_err(|_| {
+                    ParserError::ParserError(format!("invalid parameter ${}", number))
+                })?;
+                Ok(Expr::Parameter { index })
+            }
 
             Token::LParen => {
                 let expr =
@@ -566,6 +574,15 @@ impl Parser {
         self.expect_token(&Token::LParen)?;
         let distinct = self.parse_all_or_distinct()?;
         let (args, order_by) = self.parse_optional_args()?;
+        let within_group = if self.parse_keywords(&[Keyword::WITHIN, Keyword::GROUP]) {
+            self.expect_token(&Token::LParen)?;
+            self.expect_keywords(&[Keyword::ORDER, Keyword::BY])?;
+            let order_by = self.parse_order_by_expr()?;
+            self.expect_token(&Token::RParen)?;
+            Some(Box::new(order_by))
+        } else {
+            None
+        };
         let over = if self.parse_keyword(Keyword::OVER) {
             // TBD: support window names (`OVER mywin`) in place of inline specification
             self.expect_token(&Token::LParen)?;
@@ -614,6 +631,7 @@ impl Parser {
             distinct,
             order_by,
             filter,
+            within_group,
         }))
     }
 
@@ -671,7 +689,7 @@ impl Parser {
     fn parse_group_by_expr(&mut self) -> Result<Expr, ParserError> {
         if self.parse_keywords(&[Keyword::GROUPING, Keyword::SETS]) {
             self.expect_token(&Token::LParen)?;
-            let result = self.parse_comma_separated(|p| p.parse_tuple(false, true))?;
+            let result = self.parse_comma_separated(|p| p.parse_tuple(true, true))?;
             self.expect_token(&Token::RParen)?;
             Ok(Expr::GroupingSets(result))
         } else if self.parse_keyword(Keyword::CUBE) {
@@ -1021,6 +1039,9 @@ impl Parser {
             Token::ShiftLeft => Some(BinaryOperator::PGBitwiseShiftLeft),
             Token::ShiftRight => Some(BinaryOperator::PGBitwiseShiftRight),
             Token::Sharp => Some(BinaryOperator::PGBitwiseXor),
+            Token::Arrow => Some(BinaryOperator::Arrow),
+            Token::LongArrow => Some(BinaryOperator::LongArrow),
+            Token::HashArrow => Some(BinaryOperator::HashArrow),
             Token::Tilde => Some(BinaryOperator::PGRegexMatch),
             Token::TildeAsterisk => Some(BinaryOperator::PGRegexIMatch),
             Token::ExclamationMarkTilde => Some(BinaryOperator::PGRegexNotMatch),
@@ -1239,7 +1260,13 @@ impl Parser {
             | Token::ExclamationMarkTildeAsterisk
             | Token::Spaceship => Ok(20),
             Token::Pipe => Ok(21),
-            Token::Caret | Token::Sharp | Token::ShiftRight | Token::ShiftLeft => Ok(22),
+            Token::Caret
+            | Token::Sharp
+            | Token::ShiftRight
+            | Token::ShiftLeft
+            | Token::Arrow
+            | Token::LongArrow
+            | Token::HashArrow => Ok(22),
             Token::Ampersand => Ok(23),
             Token::Plus | Token::Minus => Ok(Self::PLUS_MINUS_PREC),
             Token::Mul | Token::Div | Token::Mod | Token::Concat => Ok(40),
@@ -1481,6 +1508,8 @@ impl Parser {
             self.parse_create_source(true, or_replace)
         } else if self.parse_keyword(Keyword::SINK) {
             self.parse_create_sink(or_replace)
+        } else if self.parse_keyword(Keyword::FUNCTION) {
+            self.parse_create_function(or_replace, temporary)
         } else if or_replace {
             self.expected(
                 "[EXTERNAL] TABLE or [MATERIALIZED] VIEW after CREATE OR REPLACE",
@@ -1496,6 +1525,8 @@ impl Parser {
             self.parse_create_database()
         } else if self.parse_keyword(Keyword::USER) {
             self.parse_create_user()
+        } else if self.parse_keyword(Keyword::SECRET) {
+            self.parse_create_secret()
         } else {
             self.expected("an object type after CREATE", self.peek_token())
         }
@@ -1531,6 +1562,11 @@ impl Parser {
         let with_options = self.parse_options(Keyword::WITH)?;
         self.expect_keyword(Keyword::AS)?;
         let query = Box::new(self.parse_query()?);
+        let emit_mode = if materialized {
+            self.parse_emit_mode()?
+        } else {
+            None
+        };
         // Optional `WITH [ CASCADED | LOCAL ] CHECK OPTION` is widely supported here.
         Ok(Statement::CreateView {
             name,
@@ -1539,9 +1575,23 @@ impl Parser {
             materialized,
             or_replace,
             with_options,
+            emit_mode,
         })
     }
 
+    /// Parse the optional `EMIT { IMMEDIATELY | ON WINDOW CLOSE }` clause of a materialized view.
+    pub fn parse_emit_mode(&mut self) -> Result<Option<EmitMode>, ParserError> {
+        if !self.parse_keyword(Keyword::EMIT) {
+            return Ok(None);
+        }
+        if self.parse_keyword(Keyword::IMMEDIATELY) {
+            Ok(Some(EmitMode::Immediately))
+        } else {
+            self.expect_keywords(&[Keyword::ON, Keyword::WINDOW, Keyword::CLOSE])?;
+            Ok(Some(EmitMode::OnWindowClose))
+        }
+    }
+
     // CREATE [OR REPLACE]?
     // [MATERIALIZED] SOURCE
     // [IF NOT EXISTS]?
@@ -1565,8 +1615,7 @@ impl Parser {
     // SINK
     // [IF NOT EXISTS]?
     // <sink_name: Ident>
-    // FROM
-    // <materialized_view: Ident>
+    // [FROM <materialized_view: Ident> | AS <query>]
     // [WITH (properties)]?
     pub fn parse_create_sink(&mut self, _or_replace: bool) -> Result<Statement, ParserError> {
         Ok(Statement::CreateSink {
@@ -1585,14 +1634,105 @@ impl Parser {
         Ok(Statement::CreateUser(CreateUserStatement::parse_to(self)?))
     }
 
+    /// ```sql
+    /// CREATE SECRET [ IF NOT EXISTS ] name WITH ( value = 'value' )
+    /// ```
+    fn parse_create_secret(&mut self) -> Result<Statement, ParserError> {
+        let if_not_exists = self.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
+        let name = self.parse_object_name()?;
+        let with_options = self.parse_with_properties()?;
+        Ok(Statement::CreateSecret {
+            if_not_exists,
+            name,
+            with_options,
+        })
+    }
+
     fn parse_with_properties(&mut self) -> Result<Vec<SqlOption>, ParserError> {
         Ok(self.parse_options(Keyword::WITH)?.to_vec())
     }
 
     pub fn parse_drop(&mut self) -> Result<Statement, ParserError> {
+        if self.parse_keyword(Keyword::FUNCTION) {
+            return self.parse_drop_function();
+        }
         Ok(Statement::Drop(DropStatement::parse_to(self)?))
     }
 
+    /// ```sql
+    /// DROP FUNCTION [ IF EXISTS ] name [ ( [ argtype [, ...] ] ) ] [ CASCADE | RESTRICT ]
+    /// ```
+    fn parse_drop_function(&mut self) -> Result<Statement, ParserError> {
+        let if_exists = self.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+        let name = self.parse_object_name()?;
+        let args = if self.consume_token(&Token::LParen) {
+            if self.consume_token(&Token::RParen) {
+                Some(vec![])
+            } else {
+                let args = self.parse_comma_separated(Parser::parse_data_type)?;
+                self.expect_token(&Token::RParen)?;
+                Some(args)
+            }
+        } else {
+            None
+        };
+        let drop_mode = match self.parse_one_of_keywords(&[Keyword::CASCADE, Keyword::RESTRICT]) {
+            Some(Keyword::CASCADE) => Some(DropMode::Cascade),
+            Some(Keyword::RESTRICT) => Some(DropMode::Restrict),
+            _ => None,
+        };
+        Ok(Statement::DropFunction {
+            if_exists,
+            name,
+            args,
+            drop_mode,
+        })
+    }
+
+    /// ```sql
+    /// CREATE [ OR REPLACE ] FUNCTION name ( [ argtype [, ...] ] ) RETURNS rettype
+    ///     LANGUAGE lang_name AS 'identifier' USING { LINK 'link' | BASE64 'module' }
+    /// ```
+    pub fn parse_create_function(
+        &mut self,
+        or_replace: bool,
+        temporary: bool,
+    ) -> Result<Statement, ParserError> {
+        let name = self.parse_object_name()?;
+        self.expect_token(&Token::LParen)?;
+        let args = if self.consume_token(&Token::RParen) {
+            vec![]
+        } else {
+            let args = self.parse_comma_separated(Parser::parse_data_type)?;
+            self.expect_token(&Token::RParen)?;
+            args
+        };
+        self.expect_keyword(Keyword::RETURNS)?;
+        let return_type = self.parse_data_type()?;
+        self.expect_keyword(Keyword::LANGUAGE)?;
+        let language = self.parse_identifier()?;
+        self.expect_keyword(Keyword::AS)?;
+        let identifier = self.parse_literal_string()?;
+        self.expect_keyword(Keyword::USING)?;
+        let using = if self.parse_keyword(Keyword::LINK) {
+            CreateFunctionUsing::Link(self.parse_literal_string()?)
+        } else if self.parse_keyword(Keyword::BASE64) {
+            CreateFunctionUsing::Base64(self.parse_literal_string()?)
+        } else {
+            return self.expected("LINK or BASE64 after USING", self.peek_token());
+        };
+        Ok(Statement::CreateFunction {
+            or_replace,
+            temporary,
+            name,
+            args,
+            return_type,
+            language,
+            identifier,
+            using,
+        })
+    }
+
     pub fn parse_create_index(&mut self, unique: bool) -> Result<Statement, ParserError> {
         let if_not_exists = self.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let index_name = self.parse_object_name()?;
@@ -1632,7 +1772,7 @@ impl Parser {
         let if_not_exists = self.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let table_name = self.parse_object_name()?;
         // parse optional column list (schema)
-        let (columns, constraints) = self.parse_columns()?;
+        let (columns, constraints, source_watermarks) = self.parse_columns()?;
 
         // PostgreSQL supports `WITH ( options )`, before `AS`
         let with_options = self.parse_with_properties()?;
@@ -1648,6 +1788,7 @@ impl Parser {
             temporary,
             columns,
             constraints,
+            source_watermarks,
             with_options,
             or_replace,
             if_not_exists,
@@ -1655,16 +1796,22 @@ impl Parser {
         })
     }
 
-    pub fn parse_columns(&mut self) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>), ParserError> {
+    #[allow(clippy::type_complexity)]
+    pub fn parse_columns(
+        &mut self,
+    ) -> Result<(Vec<ColumnDef>, Vec<TableConstraint>, Vec<SourceWatermark>), ParserError> {
         let mut columns = vec![];
         let mut constraints = vec![];
+        let mut source_watermarks = vec![];
         if !self.consume_token(&Token::LParen) || self.consume_token(&Token::RParen) {
-            return Ok((columns, constraints));
+            return Ok((columns, constraints, source_watermarks));
         }
 
         loop {
             if let Some(constraint) = self.parse_optional_table_constraint()? {
                 constraints.push(constraint);
+            } else if let Some(watermark) = self.parse_optional_watermark()? {
+                source_watermarks.push(watermark);
             } else if let Token::Word(_) = self.peek_token() {
                 columns.push(self.parse_column_def()?);
             } else {
@@ -1679,7 +1826,46 @@ impl Parser {
             }
         }
 
-        Ok((columns, constraints))
+        Ok((columns, constraints, source_watermarks))
+    }
+
+    /// Parses the optional `INCLUDE <kind> [AS <column>], ...` of `CREATE SOURCE`.
+    pub fn parse_include_columns(&mut self) -> Result<Vec<SourceIncludeColumn>, ParserError> {
+        if !self.parse_keyword(Keyword::INCLUDE) {
+            return Ok(vec![]);
+        }
+        self.parse_comma_separated(|p| {
+            let kind = if p.parse_keyword(Keyword::TIMESTAMP) {
+                SourceMetaKind::Timestamp
+            } else if p.parse_keyword(Keyword::PARTITION) {
+                SourceMetaKind::Partition
+            } else if p.parse_keyword(Keyword::OFFSET) {
+                SourceMetaKind::Offset
+            } else if p.parse_keyword(Keyword::KEY) {
+                SourceMetaKind::Key
+            } else {
+                return p.expected("TIMESTAMP, PARTITION, OFFSET or KEY", p.peek_token());
+            };
+            let alias = if p.parse_keyword(Keyword::AS) {
+                Some(p.parse_identifier_non_reserved()?)
+            } else {
+                None
+            };
+            Ok(SourceIncludeColumn { kind, alias })
+        })
+    }
+
+    /// Parses `WATERMARK FOR <column> AS <expr>` in the column list. A column named `watermark` is
+    /// still parsed as a column definition.
+    fn parse_optional_watermark(&mut self) -> Result<Option<SourceWatermark>, ParserError> {
+        if self.parse_keywords(&[Keyword::WATERMARK, Keyword::FOR]) {
+            let column = self.parse_identifier_non_reserved()?;
+            self.expect_keyword(Keyword::AS)?;
+            let expr = self.parse_expr()?;
+            Ok(Some(SourceWatermark { column, expr }))
+        } else {
+            Ok(None)
+        }
     }
 
     fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
@@ -1757,6 +1943,8 @@ impl Parser {
             let expr = self.parse_expr()?;
             self.expect_token(&Token::RParen)?;
             Ok(Some(ColumnOption::Check(expr)))
+        } else if self.parse_keyword(Keyword::AS) {
+            Ok(Some(ColumnOption::GeneratedColumns(self.parse_expr()?)))
         } else {
             Ok(None)
         }
@@ -1861,18 +2049,82 @@ impl Parser {
     pub fn parse_sql_option(&mut self) -> Result<SqlOption, ParserError> {
         let name = self.parse_object_name()?;
         self.expect_token(&Token::Eq)?;
-        let value = self.parse_value()?;
+        let value = if self.parse_keyword(Keyword::SECRET) {
+            Value::SecretRef(self.parse_object_name()?)
+        } else {
+            self.parse_value()?
+        };
         Ok(SqlOption { name, value })
     }
 
     pub fn parse_alter(&mut self) -> Result<Statement, ParserError> {
         if self.parse_keyword(Keyword::TABLE) {
             self.parse_alter_table()
+        } else if self.parse_keyword(Keyword::SOURCE) {
+            self.parse_alter_source()
+        } else if self.parse_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW]) {
+            self.parse_alter_materialized_view()
         } else if self.parse_keyword(Keyword::USER) {
             self.parse_alter_user()
+        } else if self.parse_keyword(Keyword::SYSTEM) {
+            self.parse_alter_system()
+        } else {
+            self.expected(
+                "TABLE, SOURCE, MATERIALIZED VIEW, USER or SYSTEM after ALTER",
+                self.peek_token(),
+            )
+        }
+    }
+
+    pub fn parse_alter_system(&mut self) -> Result<Statement, ParserError> {
+        self.expect_keyword(Keyword::SET)?;
+        let (param, value) = self.parse_set_parameter()?;
+        Ok(Statement::AlterSystem { param, value })
+    }
+
+    pub fn parse_alter_source(&mut self) -> Result<Statement, ParserError> {
+        let name = self.parse_object_name()?;
+        let operation = if self.parse_keyword(Keyword::SET) {
+            let (param, value) = self.parse_set_parameter()?;
+            AlterSourceOperation::SetParameter { param, value }
+        } else if self.parse_keyword(Keyword::RESET) {
+            self.expect_keywords(&[Keyword::OFFSETS, Keyword::TO])?;
+            let target = if self.parse_keyword(Keyword::EARLIEST) {
+                SourceOffsetTarget::Earliest
+            } else if self.parse_keyword(Keyword::LATEST) {
+                SourceOffsetTarget::Latest
+            } else if self.parse_keyword(Keyword::TIMESTAMP) {
+                SourceOffsetTarget::Timestamp(self.parse_literal_uint()?)
+            } else {
+                return self.expected("EARLIEST, LATEST or TIMESTAMP", self.peek_token());
+            };
+            AlterSourceOperation::ResetOffsets { target }
         } else {
-            self.expected("TABLE or USER after ALTER", self.peek_token())
+            return self.expected("SET or RESET after ALTER SOURCE", self.peek_token());
+        };
+        Ok(Statement::AlterSource { name, operation })
+    }
+
+    pub fn parse_alter_materialized_view(&mut self) -> Result<Statement, ParserError> {
+        let name = self.parse_object_name()?;
+        self.expect_keyword(Keyword::SET)?;
+        let (param, value) = self.parse_set_parameter()?;
+        Ok(Statement::AlterMaterializedView { name, param, value })
+    }
+
+    /// Parse `<param> { = | TO } <value>` after `SET` of `ALTER`.
+    fn parse_set_parameter(&mut self) -> Result<(Ident, SetVariableValue), ParserError> {
+        let param = self.parse_identifier()?;
+        if !self.consume_token(&Token::Eq) && !self.parse_keyword(Keyword::TO) {
+            return self.expected("= or TO", self.peek_token());
         }
+        let token = self.peek_token();
+        let value = match (self.parse_value(), token) {
+            (Ok(value), _) => SetVariableValue::Literal(value),
+            (Err(_), Token::Word(ident)) => SetVariableValue::Ident(ident.to_ident()),
+            (Err(_), unexpected) => self.expected("parameter value", unexpected)?,
+        };
+        Ok((param, value))
     }
 
     pub fn parse_alter_user(&mut self) -> Result<Statement, ParserError> {
@@ -1968,18 +2220,129 @@ impl Parser {
 
     /// Parse a copy statement
     pub fn parse_copy(&mut self) -> Result<Statement, ParserError> {
-        let table_name = self.parse_object_name()?;
-        let columns = self.parse_parenthesized_column_list(Optional)?;
-        self.expect_keywords(&[Keyword::FROM, Keyword::STDIN])?;
-        self.expect_token(&Token::SemiColon)?;
-        let values = self.parse_tsv();
+        let source = if self.consume_token(&Token::LParen) {
+            let query = self.parse_query()?;
+            self.expect_token(&Token::RParen)?;
+            CopySource::Query(Box::new(query))
+        } else {
+            let table_name = self.parse_object_name()?;
+            let columns = self.parse_parenthesized_column_list(Optional)?;
+            CopySource::Table {
+                table_name,
+                columns,
+            }
+        };
+        let to = match self.parse_one_of_keywords(&[Keyword::FROM, Keyword::TO]) {
+            Some(Keyword::FROM) => {
+                if matches!(source, CopySource::Query(_)) {
+                    return parser_err!("COPY FROM does not support a query");
+                }
+                self.expect_keyword(Keyword::STDIN)?;
+                false
+            }
+            Some(Keyword::TO) => {
+                self.expect_keyword(Keyword::STDOUT)?;
+                true
+            }
+            _ => return self.expected("FROM or TO", self.peek_token()),
+        };
+        let options = self.parse_copy_options()?;
+        // The values to copy may follow the statement inline.
+        let values = if !to && self.consume_token(&Token::SemiColon) {
+            if self.peek_token() == Token::EOF {
+                vec![]
+            } else {
+                self.parse_tsv()
+            }
+        } else {
+            vec![]
+        };
         Ok(Statement::Copy {
-            table_name,
-            columns,
+            source,
+            to,
+            options,
             values,
         })
     }
 
+    /// Parse the options of `COPY`, in either `[WITH] (option [, ...])` or the syntax before
+    /// PostgreSQL 9.0, e.g. `CSV HEADER DELIMITER ','`.
+    fn parse_copy_options(&mut self) -> Result<Vec<CopyOption>, ParserError> {
+        let with = self.parse_keyword(Keyword::WITH);
+        if self.consume_token(&Token::LParen) {
+            let options = self.parse_comma_separated(Parser::parse_copy_option)?;
+            self.expect_token(&Token::RParen)?;
+            return Ok(options);
+        }
+        let mut options = vec![];
+        loop {
+            let option = if self.parse_keyword(Keyword::CSV) {
+                CopyOption::Format(Ident::new("csv"))
+            } else if self.parse_keyword(Keyword::HEADER) {
+                CopyOption::Header(true)
+            } else if self.parse_keyword(Keyword::DELIMITER) {
+                self.parse_keyword(Keyword::AS);
+                CopyOption::Delimiter(self.parse_copy_delimiter()?)
+            } else if self.parse_keyword(Keyword::NULL) {
+                self.parse_keyword(Keyword::AS);
+                CopyOption::Null(self.parse_literal_string()?)
+            } else {
+                break;
+            };
+            options.push(option);
+        }
+        if with && options.is_empty() {
+            return self.expected("COPY options after WITH", self.peek_token());
+        }
+        Ok(options)
+    }
+
+    fn parse_copy_option(&mut self) -> Result<CopyOption, ParserError> {
+        match self.parse_one_of_keywords(&[
+            Keyword::FORMAT,
+            Keyword::DELIMITER,
+            Keyword::NULL,
+            Keyword::HEADER,
+        ]) {
+            Some(Keyword::FORMAT) => Ok(CopyOption::Format(self.parse_identifier()?)),
+            Some(Keyword::DELIMITER) => Ok(CopyOption::Delimiter(self.parse_copy_delimiter()?)),
+            Some(Keyword::NULL) => Ok(CopyOption::Null(self.parse_literal_string()?)),
+            Some(Keyword::HEADER) => {
+                // The value of a boolean option can be omitted, which means true.
+                let header = match self.peek_token() {
+                    Token::Comma | Token::RParen => true,
+                    _ => self.parse_copy_boolean()?,
+                };
+                Ok(CopyOption::Header(header))
+            }
+            _ => self.expected("FORMAT, DELIMITER, NULL or HEADER", self.peek_token()),
+        }
+    }
+
+    fn parse_copy_delimiter(&mut self) -> Result<char, ParserError> {
+        let delimiter = self.parse_literal_string()?;
+        let mut chars = delimiter.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii() => Ok(c),
+            _ => parser_err!("COPY delimiter must be a single one-byte character"),
+        }
+    }
+
+    fn parse_copy_boolean(&mut self) -> Result<bool, ParserError> {
+        let tok = self.next_token();
+        let value = match &tok {
+            Token::Word(w) => w.value.to_lowercase(),
+            Token::Number(n) => n.clone(),
+            Token::SingleQuotedString(s) => s.to_lowercase(),
+            _ => String::new(),
+        };
+        match value.as_str() {
+            "true" | "on" | "1" => Ok(true),
+            "false" | "off" | "0" => Ok(false),
+            _ => self.expected("a boolean value", tok),
+        }
+    }
+
     /// Parse a tab separated values in
     /// COPY payload
     fn parse_tsv(&mut self) -> Vec<Option<String>> {
@@ -2188,6 +2551,7 @@ impl Parser {
                 }
                 Keyword::STRUCT => Ok(DataType::Struct(self.parse_struct_data_type()?)),
                 Keyword::BYTEA => Ok(DataType::Bytea),
+                Keyword::JSONB => Ok(DataType::Jsonb),
                 Keyword::NUMERIC | Keyword::DECIMAL | Keyword::DEC => {
                     let (precision, scale) = self.parse_optional_precision_scale()?;
                     Ok(DataType::Decimal(precision, scale))
@@ -2410,9 +2774,12 @@ impl Parser {
             None
         };
 
+        let returning = self.parse_returning()?;
+
         Ok(Statement::Delete {
             table_name,
             selection,
+            returning,
         })
     }
 
@@ -2438,10 +2805,13 @@ impl Parser {
                 Keyword::LOGICAL,
                 Keyword::PHYSICAL,
                 Keyword::DISTSQL,
+                Keyword::FORMAT,
+                Keyword::TIMING,
             ])?;
             match keyword {
                 Keyword::VERBOSE => options.verbose = parser.parse_optional_boolean(true),
                 Keyword::TRACE => options.trace = parser.parse_optional_boolean(true),
+                Keyword::TIMING => options.timing = parser.parse_optional_boolean(true),
                 Keyword::TYPE => {
                     let explain_type = parser.expect_one_of_keywords(&[
                         Keyword::LOGICAL,
@@ -2458,6 +2828,19 @@ impl Parser {
                 Keyword::LOGICAL => options.explain_type = ExplainType::Logical,
                 Keyword::PHYSICAL => options.explain_type = ExplainType::Physical,
                 Keyword::DISTSQL => options.explain_type = ExplainType::DistSql,
+                Keyword::FORMAT => {
+                    let explain_format = parser.expect_one_of_keywords(&[
+                        Keyword::TEXT,
+                        Keyword::JSON,
+                        Keyword::DOT,
+                    ])?;
+                    match explain_format {
+                        Keyword::TEXT => options.explain_format = ExplainFormat::Text,
+                        Keyword::JSON => options.explain_format = ExplainFormat::Json,
+                        Keyword::DOT => options.explain_format = ExplainFormat::Dot,
+                        _ => unreachable!("{}", keyword),
+                    }
+                }
                 _ => unreachable!("{}", keyword),
             };
             Ok(())
@@ -2713,6 +3096,20 @@ impl Parser {
 
     pub fn parse_set(&mut self) -> Result<Statement, ParserError> {
         let modifier = self.parse_one_of_keywords(&[Keyword::SESSION, Keyword::LOCAL]);
+        if self.parse_keywords(&[Keyword::TIME, Keyword::ZONE]) {
+            // `SET TIME ZONE <value>` is an alias of `SET timezone = <value>`.
+            let token = self.peek_token();
+            let value = match (self.parse_value(), token) {
+                (Ok(value), _) => SetVariableValue::Literal(value),
+                (Err(_), Token::Word(ident)) => SetVariableValue::Ident(ident.to_ident()),
+                (Err(_), unexpected) => self.expected("time zone value", unexpected)?,
+            };
+            return Ok(Statement::SetVariable {
+                local: modifier == Some(Keyword::LOCAL),
+                variable: Ident::new("timezone"),
+                value: vec![value],
+            });
+        }
         let variable = self.parse_identifier()?;
         if self.consume_token(&Token::Eq) || self.parse_keyword(Keyword::TO) {
             let mut values = vec![];
@@ -2801,6 +3198,15 @@ impl Parser {
                             .expected("VIEWS or SOURCES after MATERIALIZED", self.peek_token());
                     }
                 }
+                Keyword::JOBS => {
+                    return Ok(Statement::ShowObjects(ShowObject::Jobs));
+                }
+                Keyword::PROCESSLIST => {
+                    return Ok(Statement::ShowObjects(ShowObject::ProcessList));
+                }
+                Keyword::CREATE => {
+                    return self.parse_show_create();
+                }
                 Keyword::COLUMNS => {
                     if self.parse_keyword(Keyword::FROM) {
                         return Ok(Statement::ShowObjects(ShowObject::Columns {
@@ -2814,11 +3220,57 @@ impl Parser {
             }
         }
         self.index = index;
+        if self.parse_keywords(&[Keyword::TIME, Keyword::ZONE]) {
+            // `SHOW TIME ZONE` is an alias of `SHOW timezone`.
+            return Ok(Statement::ShowVariable {
+                variable: vec![Ident::new("timezone")],
+            });
+        }
         Ok(Statement::ShowVariable {
             variable: self.parse_identifiers()?,
         })
     }
 
+    /// Parse `SHOW CREATE { TABLE | MATERIALIZED VIEW } name`, assuming `SHOW CREATE` is already
+    /// consumed.
+    pub fn parse_show_create(&mut self) -> Result<Statement, ParserError> {
+        let create_type = if self.parse_keyword(Keyword::TABLE) {
+            ShowCreateType::Table
+        } else if self.parse_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW]) {
+            ShowCreateType::MaterializedView
+        } else {
+            return self.expected("TABLE or MATERIALIZED VIEW after CREATE", self.peek_token());
+        };
+        Ok(Statement::ShowCreateObject {
+            create_type,
+            name: self.parse_object_name()?,
+        })
+    }
+
+    /// Parse `CANCEL { JOB | JOBS } id [, ...]`, assuming the `CANCEL` keyword is already consumed.
+    pub fn parse_cancel_jobs(&mut self) -> Result<Statement, ParserError> {
+        if self
+            .parse_one_of_keywords(&[Keyword::JOB, Keyword::JOBS])
+            .is_none()
+        {
+            return self.expected("JOB or JOBS after CANCEL", self.peek_token());
+        }
+        let job_ids = self.parse_comma_separated(|parser| {
+            let id = parser.parse_literal_uint()?;
+            u32::try_from(id)
+                .map_err(|_| ParserError::ParserError(format!("invalid job id {}", id)))
+        })?;
+        Ok(Statement::CancelJobs(job_ids))
+    }
+
+    /// Parse `KILL process_id`, assuming the `KILL` keyword is already consumed.
+    pub fn parse_kill_process(&mut self) -> Result<Statement, ParserError> {
+        let process_id = self.parse_literal_uint()?;
+        let process_id = i32::try_from(process_id)
+            .map_err(|_| ParserError::ParserError(format!("invalid process id {}", process_id)))?;
+        Ok(Statement::Kill(process_id))
+    }
+
     /// Parser `from schema` after `show tables` and `show materialized views`, if not conclude
     /// `from` then use default schema name.
     pub fn parse_from_and_identifier(&mut self) -> Result<Option<Ident>, ParserError> {
@@ -2974,12 +3426,32 @@ impl Parser {
                 let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
                 Ok(TableFactor::TableFunction { name, alias, args })
             } else {
+                let for_system_time_as_of_proctime = self.parse_for_system_time_as_of_proctime()?;
                 let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
-                Ok(TableFactor::Table { name, alias })
+                Ok(TableFactor::Table {
+                    name,
+                    for_system_time_as_of_proctime,
+                    alias,
+                })
             }
         }
     }
 
+    /// Parses the optional `FOR SYSTEM_TIME AS OF PROCTIME()` after a table name.
+    pub fn parse_for_system_time_as_of_proctime(&mut self) -> Result<bool, ParserError> {
+        if !self.parse_keyword(Keyword::FOR) {
+            return Ok(false);
+        }
+        self.expect_keywords(&[Keyword::SYSTEM_TIME, Keyword::AS, Keyword::OF])?;
+        let ident = self.parse_identifier()?;
+        if ident.real_value() != "proctime" {
+            return parser_err!(format!("Expected PROCTIME(), found: {}", ident));
+        }
+        self.expect_token(&Token::LParen)?;
+        self.expect_token(&Token::RParen)?;
+        Ok(true)
+    }
+
     pub fn parse_derived_table_factor(
         &mut self,
         lateral: IsLateral,
@@ -3195,11 +3667,13 @@ impl Parser {
         let columns = self.parse_parenthesized_column_list(Optional)?;
 
         let source = Box::new(self.parse_query()?);
+        let returning = self.parse_returning()?;
 
         Ok(Statement::Insert {
             table_name,
             columns,
             source,
+            returning,
         })
     }
 
@@ -3212,13 +3686,24 @@ impl Parser {
         } else {
             None
         };
+        let returning = self.parse_returning()?;
         Ok(Statement::Update {
             table,
             assignments,
             selection,
+            returning,
         })
     }
 
+    /// Parse the optional `RETURNING` clause of `INSERT`, `UPDATE` and `DELETE`.
+    fn parse_returning(&mut self) -> Result<Vec<SelectItem>, ParserError> {
+        if self.parse_keyword(Keyword::RETURNING) {
+            self.parse_comma_separated(Parser::parse_select_item)
+        } else {
+            Ok(vec![])
+        }
+    }
+
     /// Parse a `var = expr` assignment, used in an UPDATE statement
     pub fn parse_assignment(&mut self) -> Result<Assignment, ParserError> {
         let id = self.parse_identifiers_non_keywords()?;
@@ -138,6 +138,14 @@ pub enum Token {
     PGSquareRoot,
     /// `||/` , a cube root math operator in PostgreSQL
     PGCubeRoot,
+    /// `->`, access JSON object field or array element in PostgreSQL
+    Arrow,
+    /// `->>`, access JSON object field or array element as text in PostgreSQL
+    LongArrow,
+    /// `#>`, extract JSON sub-object at the specified path in PostgreSQL
+    HashArrow,
+    /// A positional parameter of a prepared statement, e.g. `$1`
+    Parameter(String),
 }
 
 impl fmt::Display for Token {
@@ -193,6 +201,10 @@ impl fmt::Display for Token {
             Token::ShiftRight => f.write_str(">>"),
             Token::PGSquareRoot => f.write_str("|/"),
             Token::PGCubeRoot => f.write_str("||/"),
+            Token::Arrow => f.write_str("->"),
+            Token::LongArrow => f.write_str("->>"),
+            Token::HashArrow => f.write_str("#>"),
+            Token::Parameter(ref s) => write!(f, "${}", s),
         }
     }
 }
@@ -469,6 +481,13 @@ impl<'a> Tokenizer<'a> {
                                 comment,
                             })))
                         }
+                        Some('>') => {
+                            chars.next(); // consume the '>'
+                            match chars.peek() {
+                                Some('>') => self.consume_and_return(chars, Token::LongArrow),
+                                _ => Ok(Some(Token::Arrow)),
+                            }
+                        }
                         // a regular '-' operator
                         _ => Ok(Some(Token::Minus)),
                     }
@@ -570,8 +589,23 @@ impl<'a> Tokenizer<'a> {
                         _ => Ok(Some(Token::Tilde)),
                     }
                 }
-                '#' => self.consume_and_return(chars, Token::Sharp),
+                '#' => {
+                    chars.next(); // consume the '#'
+                    match chars.peek() {
+                        Some('>') => self.consume_and_return(chars, Token::HashArrow),
+                        _ => Ok(Some(Token::Sharp)),
+                    }
+                }
                 '@' => self.consume_and_return(chars, Token::AtSign),
+                '$' => {
+                    chars.next(); // consume the '$'
+                    let s = peeking_take_while(chars, |ch| matches!(ch, '0'..='9'));
+                    if s.is_empty() {
+                        Ok(Some(Token::Char('$')))
+                    } else {
+                        Ok(Some(Token::Parameter(s)))
+                    }
+                }
                 other => self.consume_and_return(chars, Token::Char(other)),
             },
             None => Ok(None),
@@ -1056,6 +1090,23 @@ mod tests {
         compare(expected, tokens);
     }
 
+    #[test]
+    fn tokenize_jsonb_operators() {
+        let sql = String::from("v->'a'->>1#>'{b}'");
+        let mut tokenizer = Tokenizer::new(&sql);
+        let tokens = tokenizer.tokenize().unwrap();
+        let expected = vec![
+            Token::make_word("v", None),
+            Token::Arrow,
+            Token::SingleQuotedString(String::from("a")),
+            Token::LongArrow,
+            Token::Number(String::from("1")),
+            Token::HashArrow,
+            Token::SingleQuotedString(String::from("{b}")),
+        ];
+        compare(expected, tokens);
+    }
+
     #[test]
     fn tokenize_right_arrow() {
         let sql = String::from("FUNCTION(key=>value)");
@@ -144,11 +144,13 @@ fn parse_update_with_table_alias() {
             table,
             assignments,
             selection,
+            ..
         } => {
             assert_eq!(
                 TableWithJoins {
                     relation: TableFactor::Table {
                         name: ObjectName(vec![Ident::new("users")]),
+                        for_system_time_as_of_proctime: false,
                         alias: Some(TableAlias {
                             name: Ident::new("u"),
                             columns: vec![]
@@ -235,6 +237,32 @@ fn parse_where_delete_statement() {
     }
 }
 
+#[test]
+fn parse_dml_returning() {
+    verified_stmt("INSERT INTO t VALUES (1, 2) RETURNING *");
+    verified_stmt("INSERT INTO t (a) SELECT b FROM s RETURNING a, a + 1 AS b");
+    verified_stmt("UPDATE t SET a = 1 WHERE b = 2 RETURNING a, b");
+    verified_stmt("DELETE FROM t WHERE a = 1 RETURNING t.*");
+
+    match verified_stmt("DELETE FROM t RETURNING a AS x") {
+        Statement::Delete {
+            selection,
+            returning,
+            ..
+        } => {
+            assert!(selection.is_none());
+            assert_eq!(
+                returning,
+                vec![SelectItem::ExprWithAlias {
+                    expr: Expr::Identifier(Ident::new("a")),
+                    alias: Ident::new("x"),
+                }]
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
 #[test]
 fn parse_top_level() {
     verified_stmt("SELECT 1");
@@ -390,7 +418,8 @@ fn parse_select_count_wildcard() {
             over: None,
             distinct: false,
             order_by: vec![],
-            filter: None
+            filter: None,
+            within_group: None,
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -410,7 +439,8 @@ fn parse_select_count_distinct() {
             over: None,
             distinct: true,
             order_by: vec![],
-            filter: None
+            filter: None,
+            within_group: None,
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -1107,7 +1137,8 @@ fn parse_select_having() {
                 over: None,
                 distinct: false,
                 order_by: vec![],
-                filter: None
+                filter: None,
+                within_group: None,
             })),
             op: BinaryOperator::Gt,
             right: Box::new(Expr::Value(number("1"))),
@@ -1803,6 +1834,14 @@ fn parse_explain_analyze_with_simple_select() {
             ..Default::default()
         },
     );
+    run_explain_analyze(
+        "EXPLAIN ANALYZE (TIMING false) SELECT sqrt(id) FROM foo",
+        true,
+        ExplainOptions {
+            timing: false,
+            ..Default::default()
+        },
+    );
 
     run_explain_analyze(
         "EXPLAIN (VERBOSE  , TRACE) SELECT sqrt(id) FROM foo",
@@ -1820,6 +1859,7 @@ fn parse_explain_analyze_with_simple_select() {
             trace: true,
             verbose: true,
             explain_type: ExplainType::DistSql,
+            ..Default::default()
         },
     );
     run_explain_analyze(
@@ -1829,6 +1869,7 @@ fn parse_explain_analyze_with_simple_select() {
             trace: false,
             verbose: true,
             explain_type: ExplainType::DistSql,
+            ..Default::default()
         },
     );
     run_explain_analyze(
@@ -1838,6 +1879,25 @@ fn parse_explain_analyze_with_simple_select() {
             trace: false,
             verbose: true,
             explain_type: ExplainType::DistSql,
+            ..Default::default()
+        },
+    );
+    run_explain_analyze(
+        "EXPLAIN (FORMAT JSON, VERBOSE) SELECT sqrt(id) FROM foo",
+        false,
+        ExplainOptions {
+            verbose: true,
+            explain_format: ExplainFormat::Json,
+            ..Default::default()
+        },
+    );
+    run_explain_analyze(
+        "EXPLAIN (TYPE DISTSQL, FORMAT DOT) SELECT sqrt(id) FROM foo",
+        false,
+        ExplainOptions {
+            explain_type: ExplainType::DistSql,
+            explain_format: ExplainFormat::Dot,
+            ..Default::default()
         },
     );
 }
@@ -1847,7 +1907,7 @@ fn parse_explain_with_invalid_options() {
     let res = parse_sql_statements("EXPLAIN (V) SELECT sqrt(id) FROM foo");
     assert_eq!(
         ParserError::ParserError(
-            "Expected one of VERBOSE or TRACE or TYPE or LOGICAL or PHYSICAL or DISTSQL, found: V"
+            "Expected one of VERBOSE or TRACE or TYPE or LOGICAL or PHYSICAL or DISTSQL or FORMAT or TIMING, found: V"
                 .to_string()
         ),
         res.unwrap_err()
@@ -1862,7 +1922,7 @@ fn parse_explain_with_invalid_options() {
     let res = parse_sql_statements("EXPLAIN () SELECT sqrt(id) FROM foo");
     assert_eq!(
         ParserError::ParserError(
-            "Expected one of VERBOSE or TRACE or TYPE or LOGICAL or PHYSICAL or DISTSQL, found: )"
+            "Expected one of VERBOSE or TRACE or TYPE or LOGICAL or PHYSICAL or DISTSQL or FORMAT or TIMING, found: )"
                 .to_string()
         ),
         res.unwrap_err()
@@ -1871,7 +1931,7 @@ fn parse_explain_with_invalid_options() {
     let res = parse_sql_statements("EXPLAIN (VERBOSE, ) SELECT sqrt(id) FROM foo");
     assert_eq!(
         ParserError::ParserError(
-            "Expected one of VERBOSE or TRACE or TYPE or LOGICAL or PHYSICAL or DISTSQL, found: )"
+            "Expected one of VERBOSE or TRACE or TYPE or LOGICAL or PHYSICAL or DISTSQL or FORMAT or TIMING, found: )"
                 .to_string()
         ),
         res.unwrap_err()
@@ -1904,6 +1964,7 @@ fn parse_named_argument_function() {
             distinct: false,
             order_by: vec![],
             filter: None,
+            within_group: None,
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -1939,6 +2000,7 @@ fn parse_window_functions() {
             distinct: false,
             order_by: vec![],
             filter: None,
+            within_group: None,
         }),
         expr_from_projection(&select.projection[0])
     );
@@ -1977,6 +2039,7 @@ fn parse_aggregate_with_order_by() {
                 }
             ],
             filter: None,
+            within_group: None,
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -2006,6 +2069,7 @@ fn parse_aggregate_with_filter() {
                     Expr::Identifier(Ident::new("a"))
                 )))))
             })),
+            within_group: None,
         }),
         expr_from_projection(only(&select.projection)),
     );
@@ -2221,7 +2285,7 @@ fn parse_delimited_identifiers() {
     );
     // check FROM
     match only(select.from).relation {
-        TableFactor::Table { name, alias } => {
+        TableFactor::Table { name, alias, .. } => {
             assert_eq!(vec![Ident::with_quote('"', "a table")], name.0);
             assert_eq!(Ident::with_quote('"', "alias"), alias.unwrap().name);
         }
@@ -2244,6 +2308,7 @@ fn parse_delimited_identifiers() {
             distinct: false,
             order_by: vec![],
             filter: None,
+            within_group: None,
         }),
         expr_from_projection(&select.projection[1]),
     );
@@ -2346,6 +2411,7 @@ fn parse_implicit_join() {
             TableWithJoins {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t1".into()]),
+                    for_system_time_as_of_proctime: false,
                     alias: None,
                 },
                 joins: vec![],
@@ -2353,6 +2419,7 @@ fn parse_implicit_join() {
             TableWithJoins {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t2".into()]),
+                    for_system_time_as_of_proctime: false,
                     alias: None,
                 },
                 joins: vec![],
@@ -2368,11 +2435,13 @@ fn parse_implicit_join() {
             TableWithJoins {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t1a".into()]),
+                    for_system_time_as_of_proctime: false,
                     alias: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t1b".into()]),
+                        for_system_time_as_of_proctime: false,
                         alias: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
@@ -2381,11 +2450,13 @@ fn parse_implicit_join() {
             TableWithJoins {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t2a".into()]),
+                    for_system_time_as_of_proctime: false,
                     alias: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t2b".into()]),
+                        for_system_time_as_of_proctime: false,
                         alias: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
@@ -2404,6 +2475,7 @@ fn parse_cross_join() {
         Join {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new("t2")]),
+                for_system_time_as_of_proctime: false,
                 alias: None,
             },
             join_operator: JoinOperator::CrossJoin
@@ -2422,6 +2494,7 @@ fn parse_joins_on() {
         Join {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new(relation.into())]),
+                for_system_time_as_of_proctime: false,
                 alias,
             },
             join_operator: f(JoinConstraint::On(Expr::BinaryOp {
@@ -2473,6 +2546,7 @@ fn parse_joins_using() {
         Join {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new(relation.into())]),
+                for_system_time_as_of_proctime: false,
                 alias,
             },
             join_operator: f(JoinConstraint::Using(vec!["c1".into()])),
@@ -2516,6 +2590,7 @@ fn parse_natural_join() {
         Join {
             relation: TableFactor::Table {
                 name: ObjectName(vec![Ident::new("t2")]),
+                for_system_time_as_of_proctime: false,
                 alias: None,
             },
             join_operator: f(JoinConstraint::Natural),
@@ -2753,6 +2828,7 @@ fn parse_derived_tables() {
             joins: vec![Join {
                 relation: TableFactor::Table {
                     name: ObjectName(vec!["t2".into()]),
+                    for_system_time_as_of_proctime: false,
                     alias: None,
                 },
                 join_operator: JoinOperator::Inner(JoinConstraint::Natural),
@@ -2966,6 +3042,7 @@ fn parse_create_view() {
             or_replace,
             materialized,
             with_options,
+            emit_mode,
         } => {
             assert_eq!("myschema.myview", name.to_string());
             assert_eq!(Vec::<Ident>::new(), columns);
@@ -2973,6 +3050,7 @@ fn parse_create_view() {
             assert!(!materialized);
             assert!(!or_replace);
             assert_eq!(with_options, vec![]);
+            assert_eq!(emit_mode, None);
         }
         _ => unreachable!(),
     }
@@ -3012,10 +3090,12 @@ fn parse_create_view_with_columns() {
             with_options,
             query,
             materialized,
+            emit_mode,
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec![Ident::new("has"), Ident::new("cols")]);
             assert_eq!(with_options, vec![]);
+            assert_eq!(emit_mode, None);
             assert_eq!("SELECT 1, 2", query.to_string());
             assert!(!materialized);
             assert!(!or_replace)
@@ -3034,10 +3114,12 @@ fn parse_create_or_replace_view() {
             with_options,
             query,
             materialized,
+            emit_mode,
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec![]);
             assert_eq!(with_options, vec![]);
+            assert_eq!(emit_mode, None);
             assert_eq!("SELECT 1", query.to_string());
             assert!(!materialized);
             assert!(or_replace)
@@ -3061,10 +3143,12 @@ fn parse_create_or_replace_materialized_view() {
             with_options,
             query,
             materialized,
+            emit_mode,
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec![]);
             assert_eq!(with_options, vec![]);
+            assert_eq!(emit_mode, None);
             assert_eq!("SELECT 1", query.to_string());
             assert!(materialized);
             assert!(or_replace)
@@ -3084,18 +3168,45 @@ fn parse_create_materialized_view() {
             query,
             materialized,
             with_options,
+            emit_mode,
         } => {
             assert_eq!("myschema.myview", name.to_string());
             assert_eq!(Vec::<Ident>::new(), columns);
             assert_eq!("SELECT foo FROM bar", query.to_string());
             assert!(materialized);
             assert_eq!(with_options, vec![]);
+            assert_eq!(emit_mode, None);
             assert!(!or_replace);
         }
         _ => unreachable!(),
     }
 }
 
+#[test]
+fn parse_create_materialized_view_emit_mode() {
+    let sql = "CREATE MATERIALIZED VIEW v AS SELECT foo FROM bar EMIT ON WINDOW CLOSE";
+    match verified_stmt(sql) {
+        Statement::CreateView {
+            query, emit_mode, ..
+        } => {
+            assert_eq!("SELECT foo FROM bar", query.to_string());
+            assert_eq!(emit_mode, Some(EmitMode::OnWindowClose));
+        }
+        _ => unreachable!(),
+    }
+
+    let sql = "CREATE MATERIALIZED VIEW v AS SELECT foo FROM bar EMIT IMMEDIATELY";
+    match verified_stmt(sql) {
+        Statement::CreateView { emit_mode, .. } => {
+            assert_eq!(emit_mode, Some(EmitMode::Immediately));
+        }
+        _ => unreachable!(),
+    }
+
+    let res = parse_sql_statements("CREATE VIEW v AS SELECT foo FROM bar EMIT ON WINDOW CLOSE");
+    assert!(res.is_err());
+}
+
 #[test]
 fn parse_drop_table() {
     let sql = "DROP TABLE foo";
@@ -382,6 +382,42 @@ PHP	₱ USD $
     // assert_eq!(sql, ast.to_string());
 }
 
+#[test]
+fn parse_copy_stdin_stdout() {
+    let stmt = verified_stmt("COPY t (a, b) FROM STDIN (FORMAT csv, DELIMITER '|', HEADER true)");
+    assert_eq!(
+        stmt,
+        Statement::Copy {
+            source: CopySource::Table {
+                table_name: ObjectName(vec![Ident::new("t")]),
+                columns: vec![Ident::new("a"), Ident::new("b")],
+            },
+            to: false,
+            options: vec![
+                CopyOption::Format(Ident::new("csv")),
+                CopyOption::Delimiter('|'),
+                CopyOption::Header(true),
+            ],
+            values: vec![],
+        }
+    );
+
+    verified_stmt("COPY (SELECT a FROM t) TO STDOUT");
+    verified_stmt("COPY t TO STDOUT (NULL 'null', HEADER false)");
+    one_statement_parses_to(
+        "COPY t FROM STDIN WITH (FORMAT csv, HEADER);",
+        "COPY t FROM STDIN (FORMAT csv, HEADER true)",
+    );
+    one_statement_parses_to(
+        "COPY t TO STDOUT WITH CSV HEADER DELIMITER AS ';' NULL ''",
+        "COPY t TO STDOUT (FORMAT csv, HEADER true, DELIMITER ';', NULL '')",
+    );
+
+    assert!(parse_sql_statements("COPY (SELECT 1) FROM STDIN").is_err());
+    assert!(parse_sql_statements("COPY t FROM STDIN (DELIMITER '||')").is_err());
+    assert!(parse_sql_statements("COPY t TO STDIN").is_err());
+}
+
 #[test]
 fn parse_set() {
     let stmt = verified_stmt("SET a = b");
@@ -438,6 +474,8 @@ fn parse_set() {
 
     one_statement_parses_to("SET a TO b", "SET a = b");
     one_statement_parses_to("SET SESSION a = b", "SET a = b");
+    one_statement_parses_to("SET TIME ZONE 'US/Pacific'", "SET timezone = 'US/Pacific'");
+    one_statement_parses_to("SET TIME ZONE LOCAL", "SET timezone = LOCAL");
 
     assert_eq!(
         parse_sql_statements("SET"),
@@ -477,7 +515,9 @@ fn parse_show() {
         Statement::ShowVariable {
             variable: vec!["ALL".into(), "ALL".into()]
         }
-    )
+    );
+
+    one_statement_parses_to("SHOW TIME ZONE", "SHOW timezone");
 }
 
 #[test]
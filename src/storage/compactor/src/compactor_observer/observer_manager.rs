@@ -22,10 +22,14 @@ use risingwave_hummock_sdk::filter_key_extractor::{
 };
 use risingwave_pb::catalog::Table;
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
-use risingwave_pb::meta::SubscribeResponse;
+use risingwave_pb::meta::{SubscribeResponse, SystemParams};
+use risingwave_storage::hummock::compactor::CompactionParamsRef;
+use risingwave_storage::hummock::SstableStoreRef;
 
 pub struct CompactorObserverNode {
     filter_key_extractor_manager: FilterKeyExtractorManagerRef,
+    sstable_store: SstableStoreRef,
+    compaction_params: CompactionParamsRef,
     version: u64,
 }
 
@@ -51,6 +55,10 @@ impl ObserverState for CompactorObserverNode {
 
             Info::HummockVersionDeltas(_) => {}
 
+            Info::SystemParams(system_params) => {
+                self.handle_system_params(system_params);
+            }
+
             _ => {
                 panic!("error type notification");
             }
@@ -63,6 +71,9 @@ impl ObserverState for CompactorObserverNode {
         match resp.info {
             Some(Info::Snapshot(snapshot)) => {
                 self.handle_catalog_snapshot(snapshot.tables);
+                if let Some(system_params) = snapshot.system_params {
+                    self.handle_system_params(system_params);
+                }
                 self.version = resp.version;
             }
             _ => {
@@ -79,13 +90,32 @@ impl ObserverState for CompactorObserverNode {
 }
 
 impl CompactorObserverNode {
-    pub fn new(filter_key_extractor_manager: FilterKeyExtractorManagerRef) -> Self {
+    pub fn new(
+        filter_key_extractor_manager: FilterKeyExtractorManagerRef,
+        sstable_store: SstableStoreRef,
+        compaction_params: CompactionParamsRef,
+    ) -> Self {
         Self {
             filter_key_extractor_manager,
+            sstable_store,
+            compaction_params,
             version: 0,
         }
     }
 
+    /// Compactor only reads blocks for compaction without filling block cache, so only the
+    /// capacity of meta cache is applied, along with the compaction knobs.
+    fn handle_system_params(&mut self, system_params: SystemParams) {
+        if system_params.meta_cache_capacity_mb != 0 {
+            self.sstable_store
+                .set_meta_cache_capacity(system_params.meta_cache_capacity_mb as usize * (1 << 20));
+        }
+        self.compaction_params.update(
+            system_params.sstable_size_mb,
+            system_params.max_sub_compaction,
+        );
+    }
+
     fn handle_catalog_snapshot(&mut self, tables: Vec<Table>) {
         let all_filter_key_extractors: HashMap<u32, Arc<FilterKeyExtractorImpl>> = tables
             .iter()
@@ -59,6 +59,13 @@ pub struct CompactorOpts {
 
     #[clap(long)]
     pub compaction_worker_threads_number: Option<usize>,
+
+    /// Run as an ephemeral worker for serverless compaction. An ephemeral compactor unregisters
+    /// itself from meta on shutdown, so that its tasks are reassigned immediately instead of
+    /// after the heartbeat expires. This allows compactors to join and leave the cluster at any
+    /// time, e.g. driven by an autoscaler.
+    #[clap(long)]
+    pub ephemeral: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
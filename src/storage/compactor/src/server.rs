@@ -26,7 +26,9 @@ use risingwave_object_store::object::parse_remote_object_store;
 use risingwave_pb::common::WorkerType;
 use risingwave_pb::hummock::compactor_service_server::CompactorServiceServer;
 use risingwave_rpc_client::MetaClient;
-use risingwave_storage::hummock::compactor::{CompactionExecutor, CompactorContext, Context};
+use risingwave_storage::hummock::compactor::{
+    CompactionExecutor, CompactionParams, CompactorContext, Context,
+};
 use risingwave_storage::hummock::hummock_meta_client::MonitoredHummockMetaClient;
 use risingwave_storage::hummock::{
     CompactorMemoryCollector, CompactorSstableStore, MemoryLimiter, SstableIdManager, SstableStore,
@@ -94,7 +96,12 @@ pub async fn compactor_serve(
     ));
 
     let filter_key_extractor_manager = Arc::new(FilterKeyExtractorManager::default());
-    let compactor_observer_node = CompactorObserverNode::new(filter_key_extractor_manager.clone());
+    let compaction_params = Arc::new(CompactionParams::new(&storage_config));
+    let compactor_observer_node = CompactorObserverNode::new(
+        filter_key_extractor_manager.clone(),
+        sstable_store.clone(),
+        compaction_params.clone(),
+    );
     let observer_manager =
         ObserverManager::new_with_meta_client(meta_client.clone(), compactor_observer_node).await;
 
@@ -128,6 +135,7 @@ pub async fn compactor_serve(
         read_memory_limiter: memory_limiter,
         sstable_id_manager: sstable_id_manager.clone(),
         task_progress_manager: Default::default(),
+        compaction_params,
     });
     let compactor_context = Arc::new(CompactorContext {
         context,
@@ -147,6 +155,7 @@ pub async fn compactor_serve(
     ];
 
     let (shutdown_send, mut shutdown_recv) = tokio::sync::oneshot::channel();
+    let ephemeral = opts.ephemeral;
     let join_handle = tokio::spawn(async move {
         tonic::transport::Server::builder()
             .add_service(CompactorServiceServer::new(CompactorServiceImpl {}))
@@ -165,6 +174,12 @@ pub async fn compactor_serve(
                         }
                     },
                 }
+                if ephemeral {
+                    // Leave the cluster right away so that meta reassigns our tasks.
+                    if let Err(err) = meta_client.unregister(client_addr).await {
+                        tracing::warn!("Failed to unregister ephemeral compactor: {:?}", err);
+                    }
+                }
             })
             .await
             .unwrap();
@@ -476,6 +476,12 @@ mod tests {
             vnode_col_idx: None,
             value_indices: vec![0],
             definition: "".into(),
+            version: None,
+            watermark_indices: vec![],
+            statistics: None,
+            description: "".into(),
+            column_descriptions: HashMap::new(),
+            schema_stale: false,
         }
     }
 
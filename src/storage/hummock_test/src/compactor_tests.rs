@@ -144,6 +144,7 @@ mod tests {
                 storage.options().sstable_id_remote_fetch_number,
             )),
             task_progress_manager: Default::default(),
+            compaction_params: storage.compaction_params().clone(),
         });
         CompactorContext {
             sstable_store: Arc::new(CompactorSstableStore::new(
@@ -27,7 +27,7 @@ use risingwave_meta::manager::MetaSrvEnv;
 use risingwave_meta::storage::MemStore;
 use risingwave_pb::common::WorkerNode;
 use risingwave_rpc_client::HummockMetaClient;
-use risingwave_storage::hummock::compactor::Context;
+use risingwave_storage::hummock::compactor::{CompactionParams, Context};
 use risingwave_storage::hummock::event_handler::hummock_event_handler::BufferTracker;
 use risingwave_storage::hummock::event_handler::{HummockEvent, HummockEventHandler};
 use risingwave_storage::hummock::iterator::test_utils::mock_sstable_store;
@@ -67,6 +67,7 @@ pub async fn prepare_hummock_event_handler(
 
     let compactor_context = Arc::new(Context::new_local_compact_context(
         opt.clone(),
+        Arc::new(CompactionParams::new(&opt)),
         sstable_store_ref,
         hummock_meta_client,
         Arc::new(StateStoreMetrics::unused()),
@@ -27,7 +27,7 @@ use risingwave_meta::storage::MemStore;
 use risingwave_pb::common::WorkerNode;
 use risingwave_pb::hummock::pin_version_response::Payload;
 use risingwave_pb::hummock::HummockVersion;
-use risingwave_storage::hummock::compactor::Context;
+use risingwave_storage::hummock::compactor::{CompactionParams, Context};
 use risingwave_storage::hummock::event_handler::hummock_event_handler::BufferTracker;
 use risingwave_storage::hummock::event_handler::HummockEventHandler;
 use risingwave_storage::hummock::iterator::test_utils::mock_sstable_store;
@@ -70,6 +70,7 @@ pub async fn prepare_local_version_manager(
     let buffer_tracker = BufferTracker::from_storage_config(&opt);
     let compactor_context = Arc::new(Context::new_local_compact_context(
         opt.clone(),
+        Arc::new(CompactionParams::new(&opt)),
         sstable_store,
         hummock_meta_client,
         Arc::new(StateStoreMetrics::unused()),
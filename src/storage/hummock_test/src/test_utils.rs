@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use bytes::{BufMut, Bytes};
+use risingwave_common::config::StorageConfig;
 use risingwave_common::error::Result;
 use risingwave_common::util::addr::HostAddr;
 use risingwave_common_service::observer_manager::{Channel, NotificationClient, ObserverManager};
@@ -26,7 +27,9 @@ use risingwave_pb::common::WorkerNode;
 use risingwave_pb::hummock::pin_version_response;
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use risingwave_pb::meta::{MetaSnapshot, SubscribeResponse, SubscribeType};
+use risingwave_storage::hummock::compactor::CompactionParams;
 use risingwave_storage::hummock::event_handler::HummockEvent;
+use risingwave_storage::hummock::iterator::test_utils::mock_sstable_store;
 use risingwave_storage::hummock::local_version::pinned_version::PinnedVersion;
 use risingwave_storage::hummock::observer_manager::HummockObserverNode;
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
@@ -117,7 +120,12 @@ pub async fn prepare_first_valid_version(
         get_test_notification_client(env, hummock_manager_ref.clone(), worker_node.clone());
     let observer_manager = ObserverManager::new(
         notification_client,
-        HummockObserverNode::new(Arc::new(FilterKeyExtractorManager::default()), tx.clone()),
+        HummockObserverNode::new(
+            Arc::new(FilterKeyExtractorManager::default()),
+            tx.clone(),
+            mock_sstable_store(),
+            Arc::new(CompactionParams::new(&StorageConfig::default())),
+        ),
     )
     .await;
     let _ = observer_manager.start().await.unwrap();
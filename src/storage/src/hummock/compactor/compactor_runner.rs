@@ -42,7 +42,8 @@ pub struct CompactorRunner {
 
 impl CompactorRunner {
     pub fn new(split_index: usize, context: &CompactorContext, task: CompactTask) -> Self {
-        let max_target_file_size = context.context.options.sstable_size_mb as usize * (1 << 20);
+        let max_target_file_size =
+            context.context.compaction_params.sstable_size_mb() as usize * (1 << 20);
         let total_file_size = task
             .input_ssts
             .iter()
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
 use risingwave_common::config::StorageConfig;
@@ -51,11 +52,15 @@ pub struct Context {
     pub sstable_id_manager: SstableIdManagerRef,
 
     pub task_progress_manager: TaskProgressManagerRef,
+
+    /// The compaction knobs that can be changed at runtime.
+    pub compaction_params: CompactionParamsRef,
 }
 
 impl Context {
     pub fn new_local_compact_context(
         options: Arc<StorageConfig>,
+        compaction_params: CompactionParamsRef,
         sstable_store: SstableStoreRef,
         hummock_meta_client: Arc<dyn HummockMetaClient>,
         stats: Arc<StateStoreMetrics>,
@@ -82,6 +87,48 @@ impl Context {
             read_memory_limiter: memory_limiter,
             sstable_id_manager,
             task_progress_manager: Default::default(),
+            compaction_params,
+        }
+    }
+}
+
+pub type CompactionParamsRef = Arc<CompactionParams>;
+
+/// The compaction knobs of [`StorageConfig`] that can be changed at runtime by system params. They
+/// are initialized from the config, and updated by the observer of the node.
+#[derive(Debug)]
+pub struct CompactionParams {
+    sstable_size_mb: AtomicU32,
+    max_sub_compaction: AtomicU32,
+}
+
+impl CompactionParams {
+    pub fn new(options: &StorageConfig) -> Self {
+        Self {
+            sstable_size_mb: AtomicU32::new(options.sstable_size_mb),
+            max_sub_compaction: AtomicU32::new(options.max_sub_compaction),
+        }
+    }
+
+    /// The target size of the sstables built by compaction.
+    pub fn sstable_size_mb(&self) -> u32 {
+        self.sstable_size_mb.load(Ordering::Relaxed)
+    }
+
+    /// The maximum number of sub tasks a compact task is split into.
+    pub fn max_sub_compaction(&self) -> u32 {
+        self.max_sub_compaction.load(Ordering::Relaxed)
+    }
+
+    /// Applies the knobs set in the system params, whose zero values mean unset.
+    pub fn update(&self, sstable_size_mb: u32, max_sub_compaction: u32) {
+        if sstable_size_mb != 0 {
+            self.sstable_size_mb
+                .store(sstable_size_mb, Ordering::Relaxed);
+        }
+        if max_sub_compaction != 0 {
+            self.max_sub_compaction
+                .store(max_sub_compaction, Ordering::Relaxed);
         }
     }
 }
@@ -91,3 +138,29 @@ pub struct CompactorContext {
     pub context: Arc<Context>,
     pub sstable_store: CompactorSstableStoreRef,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_compaction_params() {
+        let options = StorageConfig {
+            sstable_size_mb: 256,
+            max_sub_compaction: 4,
+            ..Default::default()
+        };
+        let params = CompactionParams::new(&options);
+        assert_eq!(params.sstable_size_mb(), 256);
+        assert_eq!(params.max_sub_compaction(), 4);
+
+        // The unset knobs are kept.
+        params.update(0, 8);
+        assert_eq!(params.sstable_size_mb(), 256);
+        assert_eq!(params.max_sub_compaction(), 8);
+
+        params.update(64, 0);
+        assert_eq!(params.sstable_size_mb(), 64);
+        assert_eq!(params.max_sub_compaction(), 8);
+    }
+}
@@ -32,7 +32,7 @@ pub use compaction_filter::{
     CompactionFilter, DummyCompactionFilter, MultiCompactionFilter, StateCleanUpCompactionFilter,
     TtlCompactionFilter,
 };
-pub use context::{CompactorContext, Context};
+pub use context::{CompactionParams, CompactionParamsRef, CompactorContext, Context};
 use futures::future::try_join_all;
 use futures::{stream, StreamExt, TryFutureExt};
 pub use iterator::ConcatSstableIterator;
@@ -820,7 +820,7 @@ async fn generate_splits(compact_task: &mut CompactTask, context: Arc<Context>)
         .map(|table_info| table_info.file_size)
         .sum::<u64>();
 
-    let sstable_size = (context.options.sstable_size_mb as u64) << 20;
+    let sstable_size = (context.compaction_params.sstable_size_mb() as u64) << 20;
     if compaction_size > sstable_size * 2 {
         let mut indexes = vec![];
         // preload the meta and get the smallest key to split sub_compaction
@@ -851,7 +851,7 @@ async fn generate_splits(compact_task: &mut CompactTask, context: Arc<Context>)
         splits.push(KeyRange_vec::new(vec![], vec![]));
         let parallelism = std::cmp::min(
             indexes.len() as u64,
-            context.options.max_sub_compaction as u64,
+            context.compaction_params.max_sub_compaction() as u64,
         );
         let sub_compaction_data_size = std::cmp::max(compaction_size / parallelism, sstable_size);
         let parallelism = compaction_size / sub_compaction_data_size;
@@ -128,7 +128,7 @@ async fn compact_shared_buffer(
         splits.last_mut().unwrap().right = key_before_last.clone();
         splits.push(KeyRange::new(key_before_last.clone(), Bytes::new()));
     };
-    let sstable_size = (context.options.sstable_size_mb as u64) << 20;
+    let sstable_size = (context.compaction_params.sstable_size_mb() as u64) << 20;
     let parallelism = std::cmp::min(
         context.options.share_buffers_sync_parallelism as u64,
         size_and_start_user_keys.len() as u64,
@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::ops::RangeBounds;
 use std::sync::Arc;
 
+use async_stack_trace::StackTraceReport;
 use bytes::Bytes;
 use parking_lot::{RwLock, RwLockWriteGuard};
 use risingwave_common::catalog::TableId;
@@ -84,6 +85,11 @@ impl LocalVersionManager {
         &self.buffer_tracker
     }
 
+    /// Get stack trace reports for all running upload tasks.
+    pub fn get_upload_task_traces(&self) -> HashMap<u64, StackTraceReport> {
+        self.shared_buffer_uploader.get_upload_task_traces()
+    }
+
     /// Updates cached version if the new version is of greater id.
     /// You shouldn't unpin even the method returns false, as it is possible `hummock_version` is
     /// being referenced by some readers.
@@ -14,10 +14,12 @@
 
 //! Hummock is the state store of the streaming system.
 
+use std::collections::HashMap;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 
 use arc_swap::ArcSwap;
+use async_stack_trace::StackTraceReport;
 use bytes::Bytes;
 #[cfg(any(test, feature = "test"))]
 use parking_lot::RwLock;
@@ -80,7 +82,7 @@ use self::key::user_key;
 pub use self::sstable_store::*;
 use super::monitor::StateStoreMetrics;
 use crate::error::StorageResult;
-use crate::hummock::compactor::Context;
+use crate::hummock::compactor::{CompactionParams, CompactionParamsRef, Context};
 use crate::hummock::event_handler::hummock_event_handler::BufferTracker;
 use crate::hummock::event_handler::{HummockEvent, HummockEventHandler};
 use crate::hummock::iterator::{
@@ -115,11 +117,12 @@ impl Drop for HummockStorageShutdownGuard {
 /// Hummock is the state store backend.
 #[derive(Clone)]
 pub struct HummockStorage {
-    #[allow(dead_code)]
     local_version_manager: LocalVersionManagerRef,
 
     filter_key_extractor_manager: FilterKeyExtractorManagerRef,
 
+    compaction_params: CompactionParamsRef,
+
     hummock_event_sender: UnboundedSender<HummockEvent>,
 
     _shutdown_guard: Arc<HummockStorageShutdownGuard>,
@@ -158,11 +161,17 @@ impl HummockStorage {
         ));
 
         let filter_key_extractor_manager = Arc::new(FilterKeyExtractorManager::default());
+        let compaction_params = Arc::new(CompactionParams::new(&options));
         let (event_tx, mut event_rx) = unbounded_channel();
 
         let observer_manager = ObserverManager::new(
             notification_client,
-            HummockObserverNode::new(filter_key_extractor_manager.clone(), event_tx.clone()),
+            HummockObserverNode::new(
+                filter_key_extractor_manager.clone(),
+                event_tx.clone(),
+                sstable_store.clone(),
+                compaction_params.clone(),
+            ),
         )
         .await;
         let _ = observer_manager
@@ -184,6 +193,7 @@ impl HummockStorage {
 
         let compactor_context = Arc::new(Context::new_local_compact_context(
             options.clone(),
+            compaction_params.clone(),
             sstable_store.clone(),
             hummock_meta_client.clone(),
             stats.clone(),
@@ -232,6 +242,7 @@ impl HummockStorage {
         let instance = Self {
             local_version_manager,
             filter_key_extractor_manager,
+            compaction_params,
             _shutdown_guard: Arc::new(HummockStorageShutdownGuard {
                 shutdown_sender: event_tx.clone(),
             }),
@@ -265,6 +276,11 @@ impl HummockStorage {
         &self.filter_key_extractor_manager
     }
 
+    /// The compaction knobs updated by system params, shared by the compactors of the node.
+    pub fn compaction_params(&self) -> &CompactionParamsRef {
+        &self.compaction_params
+    }
+
     pub fn get_memory_limiter(&self) -> Arc<MemoryLimiter> {
         self.storage_core.get_memory_limiter()
     }
@@ -272,6 +288,11 @@ impl HummockStorage {
     pub fn get_pinned_version(&self) -> PinnedVersion {
         self.storage_core.read_version().read().committed().clone()
     }
+
+    /// Get stack trace reports for all running upload tasks of the shared buffer.
+    pub fn get_upload_task_traces(&self) -> HashMap<u64, StackTraceReport> {
+        self.local_version_manager.get_upload_task_traces()
+    }
 }
 
 #[cfg(any(test, feature = "test"))]
@@ -456,6 +477,8 @@ pub struct HummockStorageV1 {
 
     filter_key_extractor_manager: FilterKeyExtractorManagerRef,
 
+    compaction_params: CompactionParamsRef,
+
     hummock_event_sender: UnboundedSender<HummockEvent>,
 
     _shutdown_guard: Arc<HummockStorageShutdownGuard>,
@@ -486,11 +509,17 @@ impl HummockStorageV1 {
         ));
 
         let filter_key_extractor_manager = Arc::new(FilterKeyExtractorManager::default());
+        let compaction_params = Arc::new(CompactionParams::new(&options));
         let (event_tx, mut event_rx) = unbounded_channel();
 
         let observer_manager = ObserverManager::new(
             notification_client,
-            HummockObserverNode::new(filter_key_extractor_manager.clone(), event_tx.clone()),
+            HummockObserverNode::new(
+                filter_key_extractor_manager.clone(),
+                event_tx.clone(),
+                sstable_store.clone(),
+                compaction_params.clone(),
+            ),
         )
         .await;
         let _ = observer_manager
@@ -512,6 +541,7 @@ impl HummockStorageV1 {
 
         let compactor_context = Arc::new(Context::new_local_compact_context(
             options.clone(),
+            compaction_params.clone(),
             sstable_store.clone(),
             hummock_meta_client.clone(),
             stats.clone(),
@@ -542,6 +572,7 @@ impl HummockStorageV1 {
             stats,
             sstable_id_manager,
             filter_key_extractor_manager,
+            compaction_params,
             _shutdown_guard: Arc::new(HummockStorageShutdownGuard {
                 shutdown_sender: event_tx.clone(),
             }),
@@ -573,6 +604,11 @@ impl HummockStorageV1 {
         &self.filter_key_extractor_manager
     }
 
+    /// The compaction knobs updated by system params, shared by the compactors of the node.
+    pub fn compaction_params(&self) -> &CompactionParamsRef {
+        &self.compaction_params
+    }
+
     pub fn get_memory_limiter(&self) -> Arc<MemoryLimiter> {
         self.local_version_manager
             .buffer_tracker()
@@ -583,6 +619,11 @@ impl HummockStorageV1 {
     pub fn get_pinned_version(&self) -> PinnedVersion {
         self.local_version_manager.get_pinned_version()
     }
+
+    /// Get stack trace reports for all running upload tasks of the shared buffer.
+    pub fn get_upload_task_traces(&self) -> HashMap<u64, StackTraceReport> {
+        self.local_version_manager.get_upload_task_traces()
+    }
 }
 
 pub(crate) trait HummockIteratorType: 'static {
@@ -23,16 +23,22 @@ use risingwave_hummock_sdk::filter_key_extractor::{
 use risingwave_pb::catalog::Table;
 use risingwave_pb::hummock::pin_version_response;
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
-use risingwave_pb::meta::SubscribeResponse;
+use risingwave_pb::meta::{SubscribeResponse, SystemParams};
 use tokio::sync::mpsc::UnboundedSender;
 
+use crate::hummock::compactor::CompactionParamsRef;
 use crate::hummock::event_handler::HummockEvent;
+use crate::hummock::SstableStoreRef;
 
 pub struct HummockObserverNode {
     filter_key_extractor_manager: FilterKeyExtractorManagerRef,
 
     version_update_sender: UnboundedSender<HummockEvent>,
 
+    sstable_store: SstableStoreRef,
+
+    compaction_params: CompactionParamsRef,
+
     version: u64,
 }
 
@@ -67,6 +73,10 @@ impl ObserverState for HummockObserverNode {
                     });
             }
 
+            Info::SystemParams(system_params) => {
+                self.handle_system_params(system_params);
+            }
+
             _ => {
                 panic!("error type notification");
             }
@@ -79,6 +89,9 @@ impl ObserverState for HummockObserverNode {
         match resp.info {
             Some(Info::Snapshot(snapshot)) => {
                 self.handle_catalog_snapshot(snapshot.tables);
+                if let Some(system_params) = snapshot.system_params {
+                    self.handle_system_params(system_params);
+                }
                 let _ = self
                     .version_update_sender
                     .send(HummockEvent::VersionUpdate(
@@ -110,14 +123,36 @@ impl HummockObserverNode {
     pub fn new(
         filter_key_extractor_manager: FilterKeyExtractorManagerRef,
         version_update_sender: UnboundedSender<HummockEvent>,
+        sstable_store: SstableStoreRef,
+        compaction_params: CompactionParamsRef,
     ) -> Self {
         Self {
             filter_key_extractor_manager,
             version_update_sender,
+            sstable_store,
+            compaction_params,
             version: 0,
         }
     }
 
+    /// Resize the block cache and meta cache if the capacities are changed by `ALTER SYSTEM SET`,
+    /// and apply the compaction knobs to the compactors of the node.
+    fn handle_system_params(&mut self, system_params: SystemParams) {
+        if system_params.block_cache_capacity_mb != 0 {
+            self.sstable_store.set_block_cache_capacity(
+                system_params.block_cache_capacity_mb as usize * (1 << 20),
+            );
+        }
+        if system_params.meta_cache_capacity_mb != 0 {
+            self.sstable_store
+                .set_meta_cache_capacity(system_params.meta_cache_capacity_mb as usize * (1 << 20));
+        }
+        self.compaction_params.update(
+            system_params.sstable_size_mb,
+            system_params.max_sub_compaction,
+        );
+    }
+
     fn handle_catalog_snapshot(&mut self, tables: Vec<Table>) {
         let all_filter_key_extractors: HashMap<u32, Arc<FilterKeyExtractorImpl>> = tables
             .iter()
@@ -13,8 +13,12 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
+use async_stack_trace::{StackTraceManager, StackTraceReport, TraceConfig};
+use parking_lot::Mutex;
 use risingwave_common::catalog::TableId;
 use risingwave_hummock_sdk::{CompactionGroupId, HummockEpoch, LocalSstableInfo};
 
@@ -27,11 +31,28 @@ pub(crate) type UploadTaskResult = HummockResult<Vec<LocalSstableInfo>>;
 
 pub struct SharedBufferUploader {
     compactor_context: Arc<Context>,
+
+    /// Stack traces of the running upload tasks, used to debug stuck uploads.
+    stack_trace_manager: Mutex<StackTraceManager<u64>>,
+    next_task_id: AtomicU64,
 }
 
 impl SharedBufferUploader {
     pub fn new(compactor_context: Arc<Context>) -> Self {
-        Self { compactor_context }
+        Self {
+            compactor_context,
+            stack_trace_manager: Mutex::new(StackTraceManager::default()),
+            next_task_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Get stack trace reports for all running upload tasks.
+    pub fn get_upload_task_traces(&self) -> HashMap<u64, StackTraceReport> {
+        self.stack_trace_manager
+            .lock()
+            .get_all()
+            .map(|(k, v)| (*k, v.clone()))
+            .collect()
     }
 }
 
@@ -41,6 +62,27 @@ impl SharedBufferUploader {
         payload: UploadTaskPayload,
         epoch: HummockEpoch,
         compaction_group_index: Arc<HashMap<TableId, CompactionGroupId>>,
+    ) -> HummockResult<Vec<LocalSstableInfo>> {
+        let task_id = self.next_task_id.fetch_add(1, Ordering::Relaxed);
+        let trace_reporter = self.stack_trace_manager.lock().register(task_id);
+        trace_reporter
+            .trace(
+                self.flush_inner(payload, epoch, compaction_group_index),
+                format!("Upload Task {task_id} (epoch {epoch})"),
+                TraceConfig {
+                    report_detached: true,
+                    verbose: false,
+                    interval: Duration::from_secs(1),
+                },
+            )
+            .await
+    }
+
+    async fn flush_inner(
+        &self,
+        payload: UploadTaskPayload,
+        epoch: HummockEpoch,
+        compaction_group_index: Arc<HashMap<TableId, CompactionGroupId>>,
     ) -> HummockResult<Vec<LocalSstableInfo>> {
         if payload.is_empty() {
             return Ok(vec![]);
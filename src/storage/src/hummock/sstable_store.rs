@@ -313,6 +313,14 @@ impl SstableStore {
         self.block_cache.clone()
     }
 
+    pub fn set_block_cache_capacity(&self, capacity: usize) {
+        self.block_cache.set_capacity(capacity);
+    }
+
+    pub fn set_meta_cache_capacity(&self, capacity: usize) {
+        self.meta_cache.set_capacity(capacity);
+    }
+
     #[cfg(any(test, feature = "test"))]
     pub fn clear_block_cache(&self) {
         self.block_cache.clear();
@@ -218,6 +218,20 @@ impl<S: StateStore> StorageTable<S> {
     pub fn pk_indices(&self) -> &[usize] {
         &self.pk_indices
     }
+
+    /// The serializer of the primary key, by which the keys yielded by the iterators are
+    /// serialized.
+    pub fn pk_serializer(&self) -> &OrderedRowSerde {
+        &self.pk_serializer
+    }
+
+    /// Update the vnode bitmap of the table, returns the previous vnode bitmap. Used by the
+    /// streaming executors that read a table with the partition of their actors, after scaling.
+    #[must_use = "the executor should decide whether to manipulate the cache based on the previous vnode bitmap"]
+    pub fn update_vnode_bitmap(&mut self, new_vnodes: Arc<Bitmap>) -> Arc<Bitmap> {
+        assert_eq!(self.vnodes.len(), new_vnodes.len());
+        std::mem::replace(&mut self.vnodes, new_vnodes)
+    }
 }
 
 /// Point get
@@ -294,6 +308,9 @@ impl<S: PkAndRowStream + Unpin> TableIter for S {
 impl<S: StateStore> StorageTable<S> {
     /// Get multiple [`StorageTableIter`] based on the specified vnodes of this table with
     /// `vnode_hint`, and merge or concat them by given `ordered`.
+    ///
+    /// If `limit` is set, each iterator stops pulling from the storage after yielding `limit` rows
+    /// of its vnode.
     async fn iter_with_encoded_key_range<R, B>(
         &self,
         prefix_hint: Option<Vec<u8>>,
@@ -301,6 +318,7 @@ impl<S: StateStore> StorageTable<S> {
         wait_epoch: HummockReadEpoch,
         vnode_hint: Option<VirtualNode>,
         ordered: bool,
+        limit: Option<usize>,
     ) -> StorageResult<StorageTableIter<S>>
     where
         R: RangeBounds<B> + Send + Clone,
@@ -346,7 +364,8 @@ impl<S: StateStore> StorageTable<S> {
                     wait_epoch,
                 )
                 .await?
-                .into_stream();
+                .into_stream()
+                .take(limit.unwrap_or(usize::MAX));
 
                 Ok::<_, StorageError>(iter)
             }
@@ -375,6 +394,7 @@ impl<S: StateStore> StorageTable<S> {
         pk_prefix: &Row,
         next_col_bounds: impl RangeBounds<Datum>,
         ordered: bool,
+        limit: Option<usize>,
     ) -> StorageResult<StorageTableIter<S>> {
         fn serialize_pk_bound(
             pk_serializer: &OrderedRowSerde,
@@ -477,25 +497,47 @@ impl<S: StateStore> StorageTable<S> {
             epoch,
             self.try_compute_vnode_by_pk_prefix(pk_prefix),
             ordered,
+            limit,
         )
         .await
     }
 
     /// Construct a [`StorageTableIter`] for batch executors.
     /// Differs from the streaming one, this iterator will wait for the epoch before iteration
+    ///
+    /// If `limit` is set, at most `limit` rows will be read from each vnode.
     pub async fn batch_iter_with_pk_bounds(
         &self,
         epoch: HummockReadEpoch,
         pk_prefix: &Row,
         next_col_bounds: impl RangeBounds<Datum>,
+        limit: Option<usize>,
     ) -> StorageResult<StorageTableIter<S>> {
-        self.iter_with_pk_bounds(epoch, pk_prefix, next_col_bounds, true)
+        self.iter_with_pk_bounds(epoch, pk_prefix, next_col_bounds, true, limit)
+            .await
+    }
+
+    /// Construct a [`StorageTableIter`] on the rows whose serialized primary keys are greater than
+    /// `start_pk`, or on all rows if it's `None`. The rows of all vnodes are merged in the order of
+    /// the primary key, so that the iteration can be resumed from the last yielded key.
+    pub async fn batch_iter_from_pk(
+        &self,
+        epoch: HummockReadEpoch,
+        start_pk: Option<&[u8]>,
+    ) -> StorageResult<StorageTableIter<S>> {
+        // Storage doesn't support excluded begin key, while no full primary key is a prefix of
+        // another, so the next key of `start_pk` is used instead.
+        let start_key = match start_pk {
+            Some(start_pk) => Included(next_key(start_pk)),
+            None => Unbounded,
+        };
+        self.iter_with_encoded_key_range(None, (start_key, Unbounded), epoch, None, true, None)
             .await
     }
 
     // The returned iterator will iterate data from a snapshot corresponding to the given `epoch`.
     pub async fn batch_iter(&self, epoch: HummockReadEpoch) -> StorageResult<StorageTableIter<S>> {
-        self.batch_iter_with_pk_bounds(epoch, Row::empty(), ..)
+        self.batch_iter_with_pk_bounds(epoch, Row::empty(), .., None)
             .await
     }
 }
@@ -317,10 +317,35 @@ impl<S: StateStoreRead + StateStoreWrite> StateTable<S> {
         self.disable_sanity_check = true;
     }
 
-    fn table_id(&self) -> TableId {
+    pub fn table_id(&self) -> TableId {
         self.keyspace.table_id()
     }
 
+    /// Update the columns of this table after a schema change. Columns can only be appended, so
+    /// that the rows written before can still be deserialized with the trailing columns as nulls.
+    pub fn update_columns(&mut self, columns: &[ColumnDesc]) -> StorageResult<()> {
+        if self.value_indices.is_some() {
+            return Err(StorageError::SchemaChange(format!(
+                "table {} doesn't store all columns in value",
+                self.table_id()
+            )));
+        }
+        let data_types = columns.iter().map(|c| c.data_type.clone()).collect_vec();
+        let old_data_types = self.row_deserializer.data_types();
+        if data_types.len() < old_data_types.len()
+            || data_types[..old_data_types.len()] != *old_data_types
+        {
+            return Err(StorageError::SchemaChange(format!(
+                "columns of table {} can only be appended, from {:?} to {:?}",
+                self.table_id(),
+                old_data_types,
+                data_types
+            )));
+        }
+        self.row_deserializer = RowDeserializer::new(data_types);
+        Ok(())
+    }
+
     /// get the newest epoch of the state store and panic if the `init_epoch()` has never be called
     pub fn init_epoch(&mut self, epoch: EpochPair) {
         match self.epoch {
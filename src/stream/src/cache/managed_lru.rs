@@ -25,7 +25,7 @@ use lru::{DefaultHasher, LruCache};
 pub struct ManagedLruCache<K, V, S = DefaultHasher, A: Clone + Allocator = Global> {
     pub(super) inner: LruCache<K, V, S, A>,
     /// The entry with epoch less than water should be evicted.
-    /// Should only be updated by the `LruManager`.
+    /// Should only be updated by the global memory manager of the compute node.
     pub(super) watermark_epoch: Arc<AtomicU64>,
 }
 
@@ -20,25 +20,43 @@ use risingwave_expr::expr::Expression;
 use risingwave_expr::ExprError;
 use static_assertions::const_assert;
 
+#[async_trait::async_trait]
 pub trait InfallibleExpression: Expression {
     fn eval_infallible(&self, input: &DataChunk, on_err: impl Fn(ExprError)) -> ArrayRef {
         const_assert!(!crate::STRICT_MODE);
 
         #[expect(clippy::disallowed_methods)]
-        self.eval(input).unwrap_or_else(|_err| {
-            // When eval failed, recompute in row-based execution
-            // and pad with NULL for each failed row.
-            let mut array_builder = self.return_type().create_array_builder(input.cardinality());
-            for row in input.rows_with_holes() {
-                if let Some(row) = row {
-                    let datum = self.eval_row_infallible(&row.to_owned_row(), &on_err);
-                    array_builder.append_datum(&datum);
-                } else {
-                    array_builder.append_null();
-                }
+        self.eval(input)
+            .unwrap_or_else(|_err| self.eval_rows_infallible(input, on_err))
+    }
+
+    /// Like [`InfallibleExpression::eval_infallible`], but calls [`Expression::eval_async`] so
+    /// that expressions calling external services, e.g. UDFs, don't block the actor.
+    async fn eval_infallible_async(
+        &self,
+        input: &DataChunk,
+        on_err: impl Fn(ExprError) + Send,
+    ) -> ArrayRef {
+        const_assert!(!crate::STRICT_MODE);
+
+        #[expect(clippy::disallowed_methods)]
+        let result = self.eval_async(input).await;
+        result.unwrap_or_else(|_err| self.eval_rows_infallible(input, on_err))
+    }
+
+    /// Recomputes in row-based execution when eval failed, and pads with NULL for each failed
+    /// row.
+    fn eval_rows_infallible(&self, input: &DataChunk, on_err: impl Fn(ExprError)) -> ArrayRef {
+        let mut array_builder = self.return_type().create_array_builder(input.cardinality());
+        for row in input.rows_with_holes() {
+            if let Some(row) = row {
+                let datum = self.eval_row_infallible(&row.to_owned_row(), &on_err);
+                array_builder.append_datum(&datum);
+            } else {
+                array_builder.append_null();
             }
-            Arc::new(array_builder.finish())
-        })
+        }
+        Arc::new(array_builder.finish())
     }
 
     fn eval_row_infallible(&self, input: &Row, on_err: impl Fn(ExprError)) -> Datum {
@@ -18,13 +18,14 @@ use risingwave_common::array::stream_chunk::Ops;
 use risingwave_common::array::*;
 use risingwave_common::bail;
 use risingwave_common::buffer::Bitmap;
-use risingwave_common::types::{Datum, ScalarImpl};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
 use risingwave_storage::table::streaming_table::state_table::StateTable;
 use risingwave_storage::StateStore;
 
 use super::approx_distinct_utils::{
     deserialize_buckets_from_list, serialize_buckets, RegisterBucket, StreamingApproxCountDistinct,
 };
+use super::StreamingAggImpl;
 use crate::common::iter_state_table;
 use crate::executor::aggregation::table::TableStateImpl;
 use crate::executor::StreamExecutorResult;
@@ -65,6 +66,10 @@ pub struct AppendOnlyStreamingApproxCountDistinct {
     registers: Vec<AppendOnlyRegisterBucket>,
 
     initial_count: i64,
+
+    /// Whether the input rows are sketches emitted by [`StreamingApproxCountDistinctSketch`]
+    /// rather than the values to count.
+    merge_sketches: bool,
 }
 
 impl StreamingApproxCountDistinct for AppendOnlyStreamingApproxCountDistinct {
@@ -74,6 +79,7 @@ impl StreamingApproxCountDistinct for AppendOnlyStreamingApproxCountDistinct {
         Self {
             registers: vec![AppendOnlyRegisterBucket::new(); registers_num as usize],
             initial_count,
+            merge_sketches: false,
         }
     }
 
@@ -102,7 +108,11 @@ impl<S: StateStore> TableStateImpl<S> for AppendOnlyStreamingApproxCountDistinct
         visibility: Option<&Bitmap>,
         data: &[&ArrayImpl],
     ) -> StreamExecutorResult<()> {
-        self.apply_batch_inner(ops, visibility, data)
+        if self.merge_sketches {
+            self.apply_sketches(ops, visibility, data)
+        } else {
+            self.apply_batch_inner(ops, visibility, data)
+        }
     }
 
     fn get_output(&mut self) -> StreamExecutorResult<Datum> {
@@ -150,18 +160,7 @@ impl<S: StateStore> TableStateImpl<S> for AppendOnlyStreamingApproxCountDistinct
         let mut current_row = group_key
             .map(|row| row.values().cloned().collect_vec())
             .unwrap_or_default();
-        current_row.push(Some(ScalarImpl::List(ListValue::new(
-            serialize_buckets(
-                &self
-                    .registers()
-                    .iter()
-                    .map(|register| register.get_max())
-                    .collect_vec(),
-            )
-            .into_iter()
-            .map(|x| Some(ScalarImpl::Int64(x as i64)))
-            .collect_vec(),
-        ))));
+        current_row.push(Some(ScalarImpl::List(self.to_sketch())));
         let current_row = Row::new(current_row);
 
         let state_row = {
@@ -190,4 +189,148 @@ impl AppendOnlyStreamingApproxCountDistinct {
     pub fn new() -> Self {
         Self::with_no_initial()
     }
+
+    /// Creates the global phase of a two-phase `approx_count_distinct`, which merges the sketches
+    /// emitted by the local phase.
+    pub fn new_merger() -> Self {
+        Self {
+            merge_sketches: true,
+            ..Self::new()
+        }
+    }
+
+    /// Serializes the registers into a sketch, which is also the format of the state table.
+    fn to_sketch(&self) -> ListValue {
+        ListValue::new(
+            serialize_buckets(
+                &self
+                    .registers()
+                    .iter()
+                    .map(|register| register.get_max())
+                    .collect_vec(),
+            )
+            .into_iter()
+            .map(|x| Some(ScalarImpl::Int64(x as i64)))
+            .collect_vec(),
+        )
+    }
+
+    fn apply_sketches(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+    ) -> StreamExecutorResult<()> {
+        for (i, (op, sketch)) in ops.iter().zip_eq(data[0].iter()).enumerate() {
+            // Sketches are emitted by the local phase of an append-only stream and only grow, so
+            // the retracted ones can be ignored.
+            if !visibility.map_or(true, |v| v.is_set(i))
+                || !matches!(op, Op::Insert | Op::UpdateInsert)
+            {
+                continue;
+            }
+            let Some(sketch) = sketch else {
+                continue;
+            };
+            let ScalarImpl::List(sketch) = sketch.into_scalar_impl() else {
+                bail!("HyperLogLog: Invalid sketch to merge");
+            };
+            let buckets = deserialize_buckets_from_list(sketch.values());
+            for (bucket, count) in self.registers_mut().iter_mut().zip_eq(buckets) {
+                if count != 0 {
+                    bucket.update_bucket(count as usize, true)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The local phase of a two-phase `approx_count_distinct` on an append-only stream. Instead of the
+/// estimated count, it outputs the registers as a sketch, which is merged by the global phase
+/// created with [`AppendOnlyStreamingApproxCountDistinct::new_merger`].
+#[derive(Clone, Debug)]
+pub struct StreamingApproxCountDistinctSketch {
+    inner: AppendOnlyStreamingApproxCountDistinct,
+}
+
+impl StreamingApproxCountDistinctSketch {
+    pub fn new() -> Self {
+        Self {
+            inner: AppendOnlyStreamingApproxCountDistinct::new(),
+        }
+    }
+}
+
+impl StreamingAggImpl for StreamingApproxCountDistinctSketch {
+    fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+    ) -> StreamExecutorResult<()> {
+        self.inner.apply_batch_inner(ops, visibility, data)
+    }
+
+    fn get_output(&self) -> StreamExecutorResult<Datum> {
+        Ok(Some(ScalarImpl::List(self.inner.to_sketch())))
+    }
+
+    fn new_builder(&self) -> ArrayBuilderImpl {
+        DataType::List {
+            datatype: Box::new(DataType::Int64),
+        }
+        .create_array_builder(0)
+    }
+
+    fn reset(&mut self) {
+        StreamingAggImpl::reset(&mut self.inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array_nonnull;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+
+    #[test]
+    fn test_merge_sketches() {
+        let chunks: [ArrayImpl; 2] = [
+            array_nonnull!(I64Array, [1, 2, 3, 4]).into(),
+            array_nonnull!(I64Array, [3, 4, 5, 6]).into(),
+        ];
+
+        // Count the values in a single phase.
+        let mut agg = AppendOnlyStreamingApproxCountDistinct::new();
+        for chunk in &chunks {
+            StreamingAggImpl::apply_batch(&mut agg, &[Op::Insert; 4], None, &[chunk]).unwrap();
+        }
+        let expected = StreamingAggImpl::get_output(&agg).unwrap();
+
+        // Count them again in two phases, with a sketch emitted for each chunk.
+        let mut local_agg = StreamingApproxCountDistinctSketch::new();
+        let mut sketches = local_agg.new_builder();
+        for chunk in &chunks {
+            local_agg
+                .apply_batch(&[Op::Insert; 4], None, &[chunk])
+                .unwrap();
+            sketches.append_datum(&local_agg.get_output().unwrap());
+            local_agg.reset();
+        }
+        let sketches = sketches.finish();
+
+        let mut global_agg = AppendOnlyStreamingApproxCountDistinct::new_merger();
+        TableStateImpl::<MemoryStateStore>::apply_batch(
+            &mut global_agg,
+            &[Op::Insert, Op::Insert],
+            None,
+            &[&sketches],
+        )
+        .unwrap();
+        let actual = TableStateImpl::<MemoryStateStore>::get_output(&mut global_agg).unwrap();
+
+        assert_eq!(actual, expected);
+    }
 }
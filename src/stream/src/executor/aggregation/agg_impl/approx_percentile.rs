@@ -0,0 +1,334 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::{pin_mut, StreamExt};
+use itertools::Itertools;
+use risingwave_common::array::stream_chunk::Ops;
+use risingwave_common::array::*;
+use risingwave_common::bail;
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::types::{Datum, ScalarImpl};
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::StateStore;
+
+use crate::common::iter_state_table;
+use crate::executor::aggregation::table::TableStateImpl;
+use crate::executor::StreamExecutorResult;
+
+/// The compression of the t-digest, which bounds the number of centroids.
+const COMPRESSION: f64 = 100.0;
+
+/// The scale function of the t-digest. A centroid can only span the quantiles whose scales differ
+/// by at most 1, so that the centroids near the both ends are smaller.
+fn scale(q: f64) -> f64 {
+    COMPRESSION / (2.0 * std::f64::consts::PI) * (2.0 * q - 1.0).clamp(-1.0, 1.0).asin()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Approximates `percentile_cont` of an append-only stream with a [t-digest], which summarizes the
+/// input values as a bounded number of weighted centroids. The centroids are small near the both
+/// ends, so that the extreme percentiles stay accurate, and a few dozen of inputs are kept as is,
+/// so that their percentiles are exact.
+///
+/// [t-digest]: https://arxiv.org/abs/1902.04023
+#[derive(Clone, Debug, Default)]
+pub struct StreamingApproxPercentile {
+    /// Centroids sorted by their means.
+    centroids: Vec<Centroid>,
+
+    /// The constant fraction of `percentile_cont`, taken from the second argument.
+    fraction: Option<f64>,
+
+    /// Whether the values are ordered descending in `WITHIN GROUP`.
+    descending: bool,
+}
+
+impl StreamingApproxPercentile {
+    pub fn new(descending: bool) -> Self {
+        Self {
+            descending,
+            ..Default::default()
+        }
+    }
+
+    /// Merges the values into the centroids. Adjacent centroids are combined as long as the
+    /// combined one spans the quantiles allowed by [`scale`].
+    fn merge_values(&mut self, values: impl IntoIterator<Item = f64>) {
+        let mut centroids = std::mem::take(&mut self.centroids);
+        centroids.extend(
+            values
+                .into_iter()
+                .map(|mean| Centroid { mean, weight: 1.0 }),
+        );
+        centroids.sort_unstable_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total_weight: f64 = centroids.iter().map(|c| c.weight).sum();
+
+        let mut weight_so_far = 0.0;
+        for centroid in centroids {
+            if let Some(last) = self.centroids.last_mut() {
+                let weight = last.weight + centroid.weight;
+                let q_left = weight_so_far / total_weight;
+                let q_right = (weight_so_far + weight) / total_weight;
+                if scale(q_right) - scale(q_left) <= 1.0 {
+                    last.mean += (centroid.mean - last.mean) * centroid.weight / weight;
+                    last.weight = weight;
+                    continue;
+                }
+                weight_so_far += last.weight;
+            }
+            self.centroids.push(centroid);
+        }
+    }
+
+    /// Estimates the value at the fraction by interpolating between the centroids, each of which
+    /// is regarded as its weight of values centered at its mean. Like `percentile_cont`, the
+    /// result is exact when all the centroids are single values.
+    fn quantile(&self, fraction: f64) -> Option<f64> {
+        let total_weight: f64 = self.centroids.iter().map(|c| c.weight).sum();
+        let target = fraction * (total_weight - 1.0);
+
+        let mut weight_so_far = 0.0;
+        let mut prev: Option<(f64, f64)> = None;
+        for centroid in &self.centroids {
+            let center = weight_so_far + (centroid.weight - 1.0) / 2.0;
+            if target <= center {
+                return Some(match prev {
+                    Some((prev_center, prev_mean)) => {
+                        prev_mean
+                            + (centroid.mean - prev_mean) * (target - prev_center)
+                                / (center - prev_center)
+                    }
+                    None => centroid.mean,
+                });
+            }
+            prev = Some((center, centroid.mean));
+            weight_so_far += centroid.weight;
+        }
+        self.centroids.last().map(|c| c.mean)
+    }
+
+    /// Serializes the centroids as interleaved means and weights, which is the format of the
+    /// state table.
+    fn to_list(&self) -> ListValue {
+        ListValue::new(
+            self.centroids
+                .iter()
+                .flat_map(|c| [c.mean, c.weight])
+                .map(|x| Some(ScalarImpl::Float64(x.into())))
+                .collect_vec(),
+        )
+    }
+
+    fn update_from_list(&mut self, list: &ListValue) {
+        self.centroids = list
+            .values()
+            .iter()
+            .map(|x| x.as_ref().unwrap().as_float64().0)
+            .tuples()
+            .map(|(mean, weight)| Centroid { mean, weight })
+            .collect();
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: StateStore> TableStateImpl<S> for StreamingApproxPercentile {
+    fn apply_batch(
+        &mut self,
+        ops: Ops<'_>,
+        visibility: Option<&Bitmap>,
+        data: &[&ArrayImpl],
+    ) -> StreamExecutorResult<()> {
+        if self.fraction.is_none() {
+            self.fraction = data[1].iter().flatten().next().map(|f| f.into_float64().0);
+        }
+
+        let mut values = vec![];
+        for (i, (op, value)) in ops.iter().zip_eq(data[0].iter()).enumerate() {
+            if !visibility.map_or(true, |v| v.is_set(i)) {
+                continue;
+            }
+            if !matches!(op, Op::Insert | Op::UpdateInsert) {
+                bail!("t-digest: Deletion in append-only percentile_cont");
+            }
+            if let Some(value) = value {
+                values.push(value.into_float64().0);
+            }
+        }
+        self.merge_values(values);
+        Ok(())
+    }
+
+    fn get_output(&mut self) -> StreamExecutorResult<Datum> {
+        let Some(fraction) = self.fraction else {
+            return Ok(None);
+        };
+        let fraction = if self.descending {
+            1.0 - fraction
+        } else {
+            fraction
+        };
+        Ok(self
+            .quantile(fraction)
+            .map(|x| ScalarImpl::Float64(x.into())))
+    }
+
+    async fn update_from_state_table(
+        &mut self,
+        state_table: &StateTable<S>,
+        group_key: Option<&Row>,
+    ) -> StreamExecutorResult<()> {
+        let state_row = {
+            let data_iter = iter_state_table(state_table, group_key).await?;
+            pin_mut!(data_iter);
+            if let Some(state_row) = data_iter.next().await {
+                Some(state_row?)
+            } else {
+                None
+            }
+        };
+        if let Some(state_row) = state_row {
+            if let ScalarImpl::List(list) = state_row
+                [group_key.map(|row| row.size()).unwrap_or_default()]
+            .as_ref()
+            .unwrap()
+            {
+                self.update_from_list(list);
+            } else {
+                panic!("The state of append-only percentile_cont must be List.");
+            }
+        }
+        Ok(())
+    }
+
+    async fn flush_state_if_needed(
+        &self,
+        state_table: &mut StateTable<S>,
+        group_key: Option<&Row>,
+    ) -> StreamExecutorResult<()> {
+        let mut current_row = group_key
+            .map(|row| row.values().cloned().collect_vec())
+            .unwrap_or_default();
+        current_row.push(Some(ScalarImpl::List(self.to_list())));
+        let current_row = Row::new(current_row);
+
+        let state_row = {
+            let data_iter = iter_state_table(state_table, group_key).await?;
+            pin_mut!(data_iter);
+            if let Some(state_row) = data_iter.next().await {
+                Some(state_row?)
+            } else {
+                None
+            }
+        };
+        match state_row {
+            Some(state_row) => {
+                state_table.update(state_row.into_owned(), current_row);
+            }
+            None => {
+                state_table.insert(current_row);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array_nonnull;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+
+    fn percentile(agg: &mut StreamingApproxPercentile, values: Vec<f64>, fraction: f64) -> f64 {
+        let len = values.len();
+        let values: ArrayImpl =
+            F64Array::from_slice(&values.into_iter().map(|x| Some(x.into())).collect_vec()).into();
+        let fractions: ArrayImpl = F64Array::from_slice(&vec![Some(fraction.into()); len]).into();
+        TableStateImpl::<MemoryStateStore>::apply_batch(
+            agg,
+            &vec![Op::Insert; len],
+            None,
+            &[&values, &fractions],
+        )
+        .unwrap();
+        TableStateImpl::<MemoryStateStore>::get_output(agg)
+            .unwrap()
+            .unwrap()
+            .into_float64()
+            .0
+    }
+
+    #[test]
+    fn test_exact_for_few_values() {
+        let mut agg = StreamingApproxPercentile::new(false);
+        assert_eq!(percentile(&mut agg, vec![4.0, 1.0, 3.0, 2.0], 0.25), 1.75);
+
+        let mut agg = StreamingApproxPercentile::new(true);
+        assert_eq!(percentile(&mut agg, vec![4.0, 1.0, 3.0, 2.0], 0.25), 3.25);
+
+        let mut agg = StreamingApproxPercentile::new(false);
+        let fractions: ArrayImpl = array_nonnull!(F64Array, [0.5]).into();
+        let values: ArrayImpl = F64Array::from_slice(&[None]).into();
+        TableStateImpl::<MemoryStateStore>::apply_batch(
+            &mut agg,
+            &[Op::Insert],
+            None,
+            &[&values, &fractions],
+        )
+        .unwrap();
+        assert_eq!(
+            TableStateImpl::<MemoryStateStore>::get_output(&mut agg).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_approximation() {
+        let mut agg = StreamingApproxPercentile::new(false);
+        for chunk in 0..100 {
+            // A permutation of `0..10000`.
+            let values = (0..100)
+                .map(|i| ((chunk * 100 + i) * 7919 % 10000) as f64)
+                .collect_vec();
+            percentile(&mut agg, values, 0.5);
+        }
+        assert!(agg.centroids.len() <= COMPRESSION as usize);
+
+        for (fraction, expected) in [(0.01, 99.99), (0.5, 4999.5), (0.99, 9899.01)] {
+            let actual = agg.quantile(fraction).unwrap();
+            assert!(
+                (actual - expected).abs() < 10000.0 * 0.01,
+                "fraction: {}, expected: {}, actual: {}",
+                fraction,
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialization() {
+        let mut agg = StreamingApproxPercentile::new(false);
+        percentile(&mut agg, (0..1000).map(|x| x as f64).collect_vec(), 0.5);
+        let mut recovered = StreamingApproxPercentile::new(false);
+        recovered.update_from_list(&agg.to_list());
+        assert_eq!(recovered.centroids, agg.centroids);
+    }
+}
@@ -18,8 +18,11 @@
 use std::any::Any;
 
 pub use approx_count_distinct::*;
-pub use approx_distinct_append::AppendOnlyStreamingApproxCountDistinct;
+pub use approx_distinct_append::{
+    AppendOnlyStreamingApproxCountDistinct, StreamingApproxCountDistinctSketch,
+};
 use approx_distinct_utils::StreamingApproxCountDistinct;
+pub use approx_percentile::StreamingApproxPercentile;
 use dyn_clone::DynClone;
 pub use foldable::*;
 use risingwave_common::array::stream_chunk::Ops;
@@ -39,6 +42,7 @@ use crate::executor::{StreamExecutorError, StreamExecutorResult};
 mod approx_count_distinct;
 mod approx_distinct_append;
 mod approx_distinct_utils;
+mod approx_percentile;
 mod foldable;
 mod row_count;
 
@@ -132,6 +136,9 @@ pub fn create_streaming_agg_impl(
                         Box::new(<$state_impl>::new())
                     }
                 )*
+                (AggKind::ApproxCountDistinct, _, DataType::List { .. }, None) => {
+                    Box::new(StreamingApproxCountDistinctSketch::new())
+                }
                 (AggKind::ApproxCountDistinct, _, DataType::Int64, Some(datum)) => {
                     Box::new(UpdatableStreamingApproxCountDistinct::<{approx_count_distinct::DENSE_BITS_DEFAULT}>::with_datum(datum))
                 }
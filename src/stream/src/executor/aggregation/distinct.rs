@@ -0,0 +1,239 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{Op, Row};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::types::ScalarImpl;
+use risingwave_common::util::epoch::EpochPair;
+use risingwave_expr::expr::AggKind;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::StateStore;
+
+use super::AggCall;
+use crate::executor::StreamExecutorResult;
+
+/// Dedup counts of a distinct key, one for each distinct agg call on the column.
+type DedupCounts = Vec<i64>;
+
+/// Deduplicater for the distinct agg calls on the same column.
+struct ColumnDeduplicater<S: StateStore> {
+    /// Indices of the distinct agg calls on the column. The `i`-th count column of the dedup
+    /// table belongs to the `i`-th agg call here.
+    agg_call_indices: Vec<usize>,
+
+    /// Dedup table with the group key and the distinct column as pk, followed by the counts.
+    table: StateTable<S>,
+
+    /// Distinct keys changed in the current epoch, mapped to their counts before the epoch (`None`
+    /// if not in the table) and the latest counts.
+    cache: HashMap<Row, (Option<DedupCounts>, DedupCounts)>,
+}
+
+impl<S: StateStore> ColumnDeduplicater<S> {
+    async fn get_counts(
+        &mut self,
+        key: Row,
+    ) -> StreamExecutorResult<&mut (Option<DedupCounts>, DedupCounts)> {
+        if !self.cache.contains_key(&key) {
+            let n_calls = self.agg_call_indices.len();
+            let counts = self.table.get_row(&key).await?.map(|row| {
+                row.0[row.size() - n_calls..]
+                    .iter()
+                    .map(|count| count.as_ref().map_or(0, |count| *count.as_int64()))
+                    .collect_vec()
+            });
+            let latest = counts.clone().unwrap_or_else(|| vec![0; n_calls]);
+            self.cache.insert(key.clone(), (counts, latest));
+        }
+        Ok(self.cache.get_mut(&key).unwrap())
+    }
+
+    /// Update the dedup counts with the visible rows of the chunk, and mask out the rows that do
+    /// not change the set of distinct values of each agg call.
+    async fn dedup(
+        &mut self,
+        ops: &[Op],
+        column: &Column,
+        visibilities: &mut [Option<Bitmap>],
+        group_key: Option<&Row>,
+    ) -> StreamExecutorResult<()> {
+        let column = column.array_ref();
+        let mut new_visibilities = self
+            .agg_call_indices
+            .iter()
+            .map(|_| vec![false; ops.len()])
+            .collect_vec();
+
+        for (row_idx, op) in ops.iter().enumerate() {
+            let visible = self
+                .agg_call_indices
+                .iter()
+                .map(|&call_idx| {
+                    visibilities[call_idx]
+                        .as_ref()
+                        .map_or(true, |vis| vis.is_set(row_idx))
+                })
+                .collect_vec();
+            if !visible.iter().any(|v| *v) {
+                continue;
+            }
+
+            let key = group_key
+                .unwrap_or_else(Row::empty)
+                .concat(std::iter::once(column.datum_at(row_idx)));
+            let (_, counts) = self.get_counts(key).await?;
+            for (i, visible) in visible.into_iter().enumerate() {
+                if !visible {
+                    continue;
+                }
+                let count = &mut counts[i];
+                new_visibilities[i][row_idx] = match op {
+                    Op::Insert | Op::UpdateInsert => {
+                        *count += 1;
+                        *count == 1
+                    }
+                    Op::Delete | Op::UpdateDelete => {
+                        *count -= 1;
+                        *count == 0
+                    }
+                };
+            }
+        }
+
+        for (&call_idx, vis) in self.agg_call_indices.iter().zip_eq(new_visibilities) {
+            visibilities[call_idx] = Some(vis.into_iter().collect());
+        }
+        Ok(())
+    }
+
+    /// Write the changed counts into the dedup table.
+    fn flush(&mut self) {
+        let to_row = |key: &Row, counts: DedupCounts| {
+            key.concat(
+                counts
+                    .into_iter()
+                    .map(|count| Some(ScalarImpl::Int64(count))),
+            )
+        };
+        for (key, (prev_counts, counts)) in self.cache.drain() {
+            let is_empty = counts.iter().all(|count| *count == 0);
+            match prev_counts {
+                None if is_empty => {}
+                None => self.table.insert(to_row(&key, counts)),
+                Some(prev_counts) if is_empty => self.table.delete(to_row(&key, prev_counts)),
+                Some(prev_counts) if prev_counts == counts => {}
+                Some(prev_counts) => self
+                    .table
+                    .update(to_row(&key, prev_counts), to_row(&key, counts)),
+            }
+        }
+    }
+}
+
+/// Whether the input of the agg call is deduplicated by a dedup table. Distinct `min` and `max`
+/// are not, as duplicated values don't change their results, the same as the dedup tables inferred
+/// by the frontend.
+pub fn is_deduplicated(agg_call: &AggCall) -> bool {
+    agg_call.distinct && !matches!(agg_call.kind, AggKind::Min | AggKind::Max)
+}
+
+/// Deduplicates the input of distinct agg calls before they are applied to the agg states.
+///
+/// The distinct agg calls on the same column share one dedup table, which records how many times
+/// each distinct value appears in each group for every agg call. A row is passed to a distinct agg
+/// call only if it inserts the first occurrence or deletes the last occurrence of the value.
+pub struct DistinctDeduplicater<S: StateStore> {
+    /// Deduplicaters keyed by the distinct column index.
+    deduplicaters: HashMap<usize, ColumnDeduplicater<S>>,
+}
+
+impl<S: StateStore> DistinctDeduplicater<S> {
+    /// Create a deduplicater from the dedup tables keyed by the distinct column index.
+    pub fn new(agg_calls: &[AggCall], dedup_tables: HashMap<usize, StateTable<S>>) -> Self {
+        let deduplicaters = dedup_tables
+            .into_iter()
+            .map(|(distinct_col, table)| {
+                let agg_call_indices = agg_calls
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, call)| {
+                        is_deduplicated(call) && call.args.val_indices()[0] == distinct_col
+                    })
+                    .map(|(call_idx, _)| call_idx)
+                    .collect();
+                (
+                    distinct_col,
+                    ColumnDeduplicater {
+                        agg_call_indices,
+                        table,
+                        cache: HashMap::new(),
+                    },
+                )
+            })
+            .collect();
+        Self { deduplicaters }
+    }
+
+    /// Deduplicate the rows of `ops` and `columns` for the distinct agg calls in the group of
+    /// `group_key`, by masking out the duplicated rows in their visibilities.
+    pub async fn dedup_chunk(
+        &mut self,
+        ops: &[Op],
+        columns: &[Column],
+        mut visibilities: Vec<Option<Bitmap>>,
+        group_key: Option<&Row>,
+    ) -> StreamExecutorResult<Vec<Option<Bitmap>>> {
+        for (distinct_col, deduplicater) in &mut self.deduplicaters {
+            deduplicater
+                .dedup(ops, &columns[*distinct_col], &mut visibilities, group_key)
+                .await?;
+        }
+        Ok(visibilities)
+    }
+
+    fn tables_mut(&mut self) -> impl Iterator<Item = &mut StateTable<S>> {
+        self.deduplicaters
+            .values_mut()
+            .map(|deduplicater| &mut deduplicater.table)
+    }
+
+    pub fn init_epoch(&mut self, epoch: EpochPair) {
+        self.tables_mut().for_each(|table| table.init_epoch(epoch));
+    }
+
+    /// Flush the changed counts of the current epoch and commit the dedup tables.
+    pub async fn flush(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
+        for deduplicater in self.deduplicaters.values_mut() {
+            deduplicater.flush();
+            deduplicater.table.commit(epoch).await?;
+        }
+        Ok(())
+    }
+
+    pub fn commit_no_data_expected(&mut self, epoch: EpochPair) {
+        self.tables_mut()
+            .for_each(|table| table.commit_no_data_expected(epoch));
+    }
+
+    pub fn update_vnode_bitmap(&mut self, vnode_bitmap: Arc<Bitmap>) {
+        self.tables_mut().for_each(|table| {
+            let _ = table.update_vnode_bitmap(vnode_bitmap.clone());
+        });
+    }
+}
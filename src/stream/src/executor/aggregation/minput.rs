@@ -85,6 +85,13 @@ impl<S: StateStore> MaterializedInputState<S> {
                     OrderType::Descending
                 };
                 (vec![arg_col_indices[0]], vec![order_type])
+            } else if agg_call.kind == AggKind::LastValue {
+                // `last_value` is the first value in the reversed order.
+                agg_call
+                    .order_pairs
+                    .iter()
+                    .map(|p| (p.column_idx, p.order_type.reverse()))
+                    .unzip()
             } else {
                 agg_call
                     .order_pairs
@@ -123,28 +130,30 @@ impl<S: StateStore> MaterializedInputState<S> {
             .collect_vec();
         let cache_key_serializer = OrderedRowSerde::new(cache_key_data_types, order_types);
 
-        let cache_capacity = if matches!(agg_call.kind, AggKind::Min | AggKind::Max) {
+        let cache_capacity = if matches!(
+            agg_call.kind,
+            AggKind::Min | AggKind::Max | AggKind::FirstValue | AggKind::LastValue
+        ) {
             extreme_cache_size
         } else {
             usize::MAX
         };
 
-        let cache: Box<dyn StateCache> =
-            match agg_call.kind {
-                AggKind::Min | AggKind::Max | AggKind::FirstValue => Box::new(
-                    GenericStateCache::new(ExtremeAgg, cache_capacity, row_count),
-                ),
-                AggKind::StringAgg => {
-                    Box::new(GenericStateCache::new(StringAgg, cache_capacity, row_count))
-                }
-                AggKind::ArrayAgg => {
-                    Box::new(GenericStateCache::new(ArrayAgg, cache_capacity, row_count))
-                }
-                _ => panic!(
-                    "Agg kind `{}` is not expected to have materialized input state",
-                    agg_call.kind
-                ),
-            };
+        let cache: Box<dyn StateCache> = match agg_call.kind {
+            AggKind::Min | AggKind::Max | AggKind::FirstValue | AggKind::LastValue => Box::new(
+                GenericStateCache::new(ExtremeAgg, cache_capacity, row_count),
+            ),
+            AggKind::StringAgg => {
+                Box::new(GenericStateCache::new(StringAgg, cache_capacity, row_count))
+            }
+            AggKind::ArrayAgg => {
+                Box::new(GenericStateCache::new(ArrayAgg, cache_capacity, row_count))
+            }
+            _ => panic!(
+                "Agg kind `{}` is not expected to have materialized input state",
+                agg_call.kind
+            ),
+        };
 
         Self {
             arg_col_indices,
@@ -351,6 +360,7 @@ mod tests {
             order_pairs: vec![],
             append_only: false,
             filter: None,
+            distinct: false,
         }
     }
 
@@ -1062,6 +1072,7 @@ mod tests {
             ],
             append_only: false,
             filter: None,
+            distinct: false,
         };
         let group_key = None;
 
@@ -1163,6 +1174,7 @@ mod tests {
             ],
             append_only: false,
             filter: None,
+            distinct: false,
         };
         let group_key = None;
 
@@ -1250,4 +1262,97 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_first_last_value_state_cache_maintenance() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: int32, b: int32, _row_id: int64)
+        // where `a` is the column to aggregate, ordered by `b`
+
+        let input_pk_indices = vec![2];
+        let field1 = Field::unnamed(DataType::Int32);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3]);
+
+        for (kind, table_order_type, expected) in [
+            // first_value(a ORDER BY b ASC), state table ordered by b ASC
+            (AggKind::FirstValue, OrderType::Ascending, [10, 40, 40]),
+            // last_value(a ORDER BY b ASC), state table ordered by b DESC
+            (AggKind::LastValue, OrderType::Descending, [20, 30, 40]),
+        ] {
+            let agg_call = AggCall {
+                kind,
+                args: AggArgs::Unary(DataType::Int32, 0),
+                return_type: DataType::Int32,
+                order_pairs: vec![OrderPair::new(1, OrderType::Ascending)], // b ASC
+                append_only: false,
+                filter: None,
+                distinct: false,
+            };
+            let group_key = None;
+
+            let (mut table, mapping) = create_mem_state_table(
+                &input_schema,
+                vec![1, 2, 0],
+                vec![
+                    table_order_type,     // b
+                    OrderType::Ascending, // _row_id ASC
+                ],
+            );
+
+            let mut state = MaterializedInputState::new(
+                &agg_call,
+                &input_pk_indices,
+                &mapping,
+                0,
+                1, // cache capacity = 1 so that deletes have to reload from the state table
+                &input_schema,
+            );
+
+            let epoch = EpochPair::new_test_epoch(1);
+            table.init_epoch(epoch);
+            epoch.inc();
+
+            for (pretty, expected) in [
+                (
+                    " i  i I
+                    + 10 1 100
+                    + 20 3 101
+                    + 30 2 102",
+                    expected[0],
+                ),
+                (
+                    " i  i I
+                    - 20 3 101
+                    + 40 0 103",
+                    expected[1],
+                ),
+                (
+                    " i  i I
+                    - 30 2 102
+                    - 10 1 100",
+                    expected[2],
+                ),
+            ] {
+                let chunk = create_chunk(pretty, &mut table, &mapping);
+                let (ops, columns, visibility) = chunk.into_inner();
+                let columns: Vec<_> = columns.iter().map(|col| col.array_ref()).collect();
+                state.apply_chunk(&ops, visibility.as_ref(), &columns)?;
+
+                table.commit_for_test(epoch).await.unwrap();
+                epoch.inc();
+
+                let res = state.get_output(&table, group_key.as_ref()).await?;
+                match res {
+                    Some(ScalarImpl::Int32(s)) => {
+                        assert_eq!(s, expected);
+                    }
+                    _ => panic!("unexpected output"),
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
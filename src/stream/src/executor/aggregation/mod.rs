@@ -15,6 +15,7 @@
 pub use agg_call::*;
 pub use agg_group::*;
 pub use agg_state::*;
+pub use distinct::*;
 use risingwave_common::array::column::Column;
 use risingwave_common::array::ArrayImpl::Bool;
 use risingwave_common::array::DataChunk;
@@ -34,6 +35,7 @@ mod agg_call;
 mod agg_group;
 pub mod agg_impl;
 mod agg_state;
+mod distinct;
 mod minput;
 mod state_cache;
 mod table;
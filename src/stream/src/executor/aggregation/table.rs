@@ -17,11 +17,12 @@ use risingwave_common::array::stream_chunk::Ops;
 use risingwave_common::array::*;
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::types::Datum;
+use risingwave_common::util::sort_util::{OrderPair, OrderType};
 use risingwave_expr::expr::AggKind;
 use risingwave_storage::table::streaming_table::state_table::StateTable;
 use risingwave_storage::StateStore;
 
-use super::agg_impl::AppendOnlyStreamingApproxCountDistinct;
+use super::agg_impl::{AppendOnlyStreamingApproxCountDistinct, StreamingApproxPercentile};
 use super::AggCall;
 use crate::executor::StreamExecutorResult;
 
@@ -77,6 +78,16 @@ impl<S: StateStore> TableState<S> {
                 AggKind::ApproxCountDistinct => {
                     Box::new(AppendOnlyStreamingApproxCountDistinct::new())
                 }
+                AggKind::ApproxCountDistinctMerge => {
+                    Box::new(AppendOnlyStreamingApproxCountDistinct::new_merger())
+                }
+                AggKind::PercentileCont => Box::new(StreamingApproxPercentile::new(matches!(
+                    agg_call.order_pairs.first(),
+                    Some(OrderPair {
+                        order_type: OrderType::Descending,
+                        ..
+                    })
+                ))),
                 _ => panic!(
                     "Agg kind `{}` is not expected to have table state",
                     agg_call.kind
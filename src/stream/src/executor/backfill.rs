@@ -0,0 +1,595 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_stack_trace::StackTrace;
+use either::Either;
+use futures::stream::select_with_strategy;
+use futures::{pin_mut, stream, StreamExt};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Op, StreamChunk};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::Schema;
+use risingwave_common::util::ordered::OrderedRowSerde;
+use risingwave_hummock_sdk::HummockReadEpoch;
+use risingwave_storage::table::batch_table::storage_table::StorageTable;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::rate_limiter::RateLimiter;
+use super::{expect_first_barrier, BoxedExecutor, Executor, ExecutorInfo, Message};
+use crate::task::{ActorId, CreateMviewProgress};
+
+/// [`BackfillExecutor`] creates an MV on an existing MV or table like
+/// [`super::ChainExecutor`], but without stalling the upstream while consuming the snapshot.
+///
+/// The snapshot of the upstream table is read in the order of the primary key, over the vnodes of
+/// the actor. Between two barriers, the snapshot of the previous epoch is read from the position
+/// where the last read stopped, while the upstream chunks are buffered. On the barrier, the
+/// buffered rows whose primary keys are not greater than the position are forwarded, and the
+/// others are dropped since they will be read from the later snapshots. Once the snapshot is
+/// exhausted, the upstream is forwarded directly.
+///
+/// The snapshot is read with the uncommitted data of the upstream, which relies on the actor being
+/// scheduled on the same parallel unit as its upstream.
+///
+/// The rows read from the snapshot are throttled by the rate limit, which can be changed by the
+/// barriers. The upstream is never throttled.
+pub struct BackfillExecutor<S: StateStore> {
+    /// The upstream table to read the snapshot from.
+    table: StorageTable<S>,
+
+    upstream: BoxedExecutor,
+
+    upstream_indices: Arc<[usize]>,
+
+    progress: CreateMviewProgress,
+
+    /// Throttles the rows read from the snapshot.
+    rate_limiter: RateLimiter,
+
+    actor_id: ActorId,
+
+    info: ExecutorInfo,
+}
+
+impl<S> BackfillExecutor<S>
+where
+    S: StateStore,
+{
+    /// The number of rows in a chunk read from the snapshot.
+    const CHUNK_SIZE: usize = 1024;
+
+    pub fn new(
+        table: StorageTable<S>,
+        upstream: BoxedExecutor,
+        upstream_indices: Vec<usize>,
+        progress: CreateMviewProgress,
+        schema: Schema,
+        rate_limit: u64,
+    ) -> Self {
+        Self {
+            info: ExecutorInfo {
+                schema,
+                pk_indices: upstream.pk_indices().to_owned(),
+                identity: "Backfill".to_owned(),
+            },
+            table,
+            upstream,
+            upstream_indices: upstream_indices.into(),
+            rate_limiter: RateLimiter::with_rows_per_second(rate_limit),
+            actor_id: progress.actor_id(),
+            progress,
+        }
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(mut self) {
+        let mut upstream_indices = self.upstream_indices.clone();
+        let mut upstream = self.upstream.execute();
+
+        // 1. Poll the upstream to get the first barrier.
+        let first_barrier = expect_first_barrier(&mut upstream).await?;
+
+        // If the barrier is a conf change of creating this mview, backfill from its epoch.
+        // Otherwise, it means we've recovered and the backfill is already done.
+        let to_backfill = first_barrier.is_add_dispatcher(self.actor_id);
+        let mut snapshot_read_epoch = first_barrier.epoch.prev;
+
+        // The first barrier message should be propagated.
+        yield Message::Barrier(first_barrier);
+
+        if to_backfill {
+            // The serialized primary key of the last row read from the snapshot, or `None` if no
+            // row has been read.
+            let mut current_pos: Option<Vec<u8>> = None;
+            let mut consumed_rows = 0;
+
+            // 2. Consume the snapshot epoch by epoch until it's exhausted.
+            'backfill: loop {
+                let mut upstream_chunk_buffer = vec![];
+                let mut pending_barrier = None;
+                {
+                    let left_upstream = upstream.by_ref().map(Either::Left);
+                    let right_snapshot = Self::snapshot_read(
+                        &self.table,
+                        &self.rate_limiter,
+                        snapshot_read_epoch,
+                        current_pos.clone(),
+                    )
+                    .map(Either::Right);
+                    // Prefer the upstream, so that the barriers are not delayed by the snapshot.
+                    let backfill_stream =
+                        select_with_strategy(left_upstream, right_snapshot, |_: &mut ()| {
+                            stream::PollNext::Left
+                        });
+
+                    #[for_await]
+                    for either in backfill_stream {
+                        match either {
+                            Either::Left(msg) => match msg? {
+                                Message::Barrier(barrier) => {
+                                    pending_barrier = Some(barrier);
+                                    break;
+                                }
+                                Message::Chunk(chunk) => {
+                                    upstream_chunk_buffer.push(chunk.compact())
+                                }
+                                // The watermarks can't be forwarded before the whole snapshot is
+                                // consumed.
+                                Message::Watermark(_) => {}
+                            },
+                            Either::Right(msg) => match msg? {
+                                Some((chunk, last_pk)) => {
+                                    current_pos = Some(last_pk);
+                                    consumed_rows += chunk.cardinality() as u64;
+                                    yield Message::Chunk(chunk);
+                                }
+                                // The snapshot is exhausted, so that all changes in the buffer
+                                // are after the snapshot.
+                                None => {
+                                    for chunk in upstream_chunk_buffer {
+                                        yield Message::Chunk(mapping(&upstream_indices, chunk));
+                                    }
+                                    break 'backfill;
+                                }
+                            },
+                        }
+                    }
+                }
+
+                let Some(barrier) = pending_barrier else {
+                    unreachable!("the snapshot should end before the upstream");
+                };
+
+                // 3. Forward the buffered changes on the rows that have been read from the
+                // snapshot. The changes on the other rows are included in the next snapshot.
+                if let Some(current_pos) = &current_pos {
+                    for chunk in upstream_chunk_buffer {
+                        yield Message::Chunk(mark_chunk(
+                            chunk,
+                            current_pos,
+                            self.table.pk_indices(),
+                            self.table.pk_serializer(),
+                            &upstream_indices,
+                        ));
+                    }
+                }
+
+                if let Some(rate_limit) = barrier.as_backfill_rate_limit(self.actor_id) {
+                    tracing::info!(
+                        actor_id = self.actor_id,
+                        "change the backfill rate limit to {} rows per second",
+                        rate_limit
+                    );
+                    self.rate_limiter.set_rows_per_second(rate_limit);
+                }
+
+                snapshot_read_epoch = barrier.epoch.prev;
+                self.progress
+                    .update(barrier.epoch.curr, snapshot_read_epoch, consumed_rows);
+                yield Message::Barrier(barrier);
+            }
+
+            // 4. Continuously consume the upstream. Report that we've finished the creation on
+            // the first barrier.
+            #[for_await]
+            for msg in upstream {
+                let msg = msg?;
+                if let Message::Barrier(barrier) = &msg {
+                    self.progress.finish(barrier.epoch.curr, consumed_rows);
+                    if let Some(indices) = barrier.as_chain_schema_change(self.actor_id) {
+                        upstream_indices = indices.into();
+                    }
+                }
+                if let Some(msg) = mapping_message(&upstream_indices, msg) {
+                    yield msg;
+                }
+            }
+        } else {
+            // If there's no need to backfill, we directly forward the messages from the upstream.
+            #[for_await]
+            for msg in upstream {
+                let msg = msg?;
+                if let Message::Barrier(barrier) = &msg
+                    && let Some(indices) = barrier.as_chain_schema_change(self.actor_id)
+                {
+                    upstream_indices = indices.into();
+                }
+                if let Some(msg) = mapping_message(&upstream_indices, msg) {
+                    yield msg;
+                }
+            }
+        }
+    }
+
+    /// Reads the snapshot of `epoch` after `current_pos` in chunks, each with the serialized
+    /// primary key of its last row. Yields `None` when the snapshot is exhausted.
+    ///
+    /// The chunks are throttled by `rate_limiter`. A chunk holds the rows of one second at most, so
+    /// that a low limit is not exceeded by a burst of large chunks.
+    #[expect(clippy::needless_lifetimes, reason = "code generated by try_stream")]
+    #[try_stream(ok = Option<(StreamChunk, Vec<u8>)>, error = StreamExecutorError)]
+    async fn snapshot_read(
+        table: &StorageTable<S>,
+        rate_limiter: &RateLimiter,
+        epoch: u64,
+        current_pos: Option<Vec<u8>>,
+    ) {
+        let iter = table
+            .batch_iter_from_pk(HummockReadEpoch::NoWait(epoch), current_pos.as_deref())
+            .await?;
+        pin_mut!(iter);
+
+        let chunk_size = match rate_limiter.rate_limit().rows_per_second {
+            0 => Self::CHUNK_SIZE,
+            rows_per_second => Self::CHUNK_SIZE.min(rows_per_second as usize),
+        };
+        let data_types = table.schema().data_types();
+        loop {
+            let mut rows = Vec::with_capacity(chunk_size);
+            let mut last_pk = None;
+            while rows.len() < chunk_size {
+                match iter
+                    .next()
+                    .stack_trace("backfill_snapshot_read")
+                    .await
+                    .transpose()?
+                {
+                    Some((pk, row)) => {
+                        last_pk = Some(pk);
+                        rows.push((Op::Insert, row));
+                    }
+                    None => break,
+                }
+            }
+
+            match last_pk {
+                Some(last_pk) => {
+                    let chunk = StreamChunk::from_rows(&rows, &data_types);
+                    rate_limiter
+                        .acquire(&chunk)
+                        .stack_trace("backfill_rate_limit")
+                        .await;
+                    yield Some((chunk, last_pk));
+                }
+                None => {
+                    yield None;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Projects the upstream chunk with `upstream_indices`.
+fn mapping(upstream_indices: &[usize], chunk: StreamChunk) -> StreamChunk {
+    let (ops, columns, visibility) = chunk.into_inner();
+    let mapped_columns = upstream_indices
+        .iter()
+        .map(|&i| columns[i].clone())
+        .collect();
+    StreamChunk::new(ops, mapped_columns, visibility)
+}
+
+/// Projects the upstream message with `upstream_indices`. Returns `None` if it's a watermark on a
+/// column that is not in the output.
+fn mapping_message(upstream_indices: &[usize], msg: Message) -> Option<Message> {
+    match msg {
+        Message::Watermark(watermark) => watermark
+            .transform_with_indices(upstream_indices)
+            .map(Message::Watermark),
+        Message::Chunk(chunk) => Some(Message::Chunk(mapping(upstream_indices, chunk))),
+        Message::Barrier(_) => Some(msg),
+    }
+}
+
+/// Hides the rows of the compacted upstream chunk whose primary keys are greater than
+/// `current_pos`, and projects it with `upstream_indices`.
+fn mark_chunk(
+    chunk: StreamChunk,
+    current_pos: &[u8],
+    pk_indices: &[usize],
+    pk_serializer: &OrderedRowSerde,
+    upstream_indices: &[usize],
+) -> StreamChunk {
+    let visibility: Bitmap = chunk
+        .data_chunk()
+        .rows()
+        .map(|row| {
+            let mut pk = vec![];
+            pk_serializer.serialize_datum_refs(row.datum_refs_by_indices(pk_indices), &mut pk);
+            pk.as_slice() <= current_pos
+        })
+        .collect();
+    let (ops, columns, _) = chunk.into_inner();
+    let mapped_columns = upstream_indices
+        .iter()
+        .map(|&i| columns[i].clone())
+        .collect_vec();
+    StreamChunk::new(ops, mapped_columns, Some(visibility))
+}
+
+impl<S> Executor for BackfillExecutor<S>
+where
+    S: StateStore,
+{
+    fn execute(self: Box<Self>) -> super::BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> super::PkIndicesRef<'_> {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::Row;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, TableId};
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::epoch::EpochPair;
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_pb::stream_plan::Dispatcher;
+    use risingwave_storage::memory::MemoryStateStore;
+    use risingwave_storage::table::streaming_table::state_table::StateTable;
+
+    use super::*;
+    use crate::executor::test_utils::{MessageSender, MockSource};
+    use crate::executor::{Barrier, BoxedMessageStream, Mutation};
+    use crate::task::{CreateMviewProgress, LocalBarrierManager};
+
+    fn row(pk: i64, v: i64) -> Row {
+        Row(vec![Some(pk.into()), Some(v.into())])
+    }
+
+    /// Creates the upstream table with the rows `(1, 10)`, `(2, 20)` and `(3, 30)` committed in
+    /// epoch 1, and a backfill executor on it with the upstream of the returned sender.
+    async fn create_backfill() -> (
+        StateTable<MemoryStateStore>,
+        MessageSender,
+        BoxedMessageStream,
+    ) {
+        let store = MemoryStateStore::new();
+        let table_id = TableId::new(0x42);
+        let column_descs = vec![
+            ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+            ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),
+        ];
+        let mut state_table = StateTable::new_without_distribution(
+            store.clone(),
+            table_id,
+            column_descs.clone(),
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+        let table = StorageTable::for_test(
+            store,
+            table_id,
+            column_descs,
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+
+        state_table.init_epoch(EpochPair::new_test_epoch(1));
+        for pk in 1..=3 {
+            state_table.insert(row(pk, pk * 10));
+        }
+        state_table
+            .commit_for_test(EpochPair::new_test_epoch(2))
+            .await
+            .unwrap();
+
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let (tx, upstream) = MockSource::channel(schema.clone(), vec![0]);
+        let barrier_manager = LocalBarrierManager::for_test();
+        let progress =
+            CreateMviewProgress::for_test(Arc::new(parking_lot::Mutex::new(barrier_manager)));
+        let backfill =
+            BackfillExecutor::new(table, Box::new(upstream), vec![0, 1], progress, schema, 0);
+        (state_table, tx, Box::new(backfill).execute())
+    }
+
+    fn add_backfill_actor(actor_id: ActorId) -> Mutation {
+        Mutation::Add {
+            adds: maplit::hashmap! {
+                0 => vec![Dispatcher {
+                    downstream_actor_id: vec![actor_id],
+                    ..Default::default()
+                }],
+            },
+            splits: Default::default(),
+        }
+    }
+
+    async fn next_chunk(backfill: &mut BoxedMessageStream) -> StreamChunk {
+        backfill
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_chunk()
+            .unwrap()
+    }
+
+    async fn next_barrier(backfill: &mut BoxedMessageStream) -> Barrier {
+        backfill
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap()
+    }
+
+    /// The upstream changes are interleaved with the snapshot. Only the changes on the rows that
+    /// have been read from the snapshot are forwarded, and the others are read from the snapshot
+    /// of the next epoch.
+    #[tokio::test]
+    async fn test_backfill_interleave_with_upstream() {
+        let (mut state_table, mut tx, mut backfill) = create_backfill().await;
+        // `CreateMviewProgress::for_test` is created for actor 0.
+        tx.push_barrier_with_mutation(2, add_backfill_actor(0));
+        next_barrier(&mut backfill).await;
+
+        // The snapshot of epoch 1 is read while the upstream is idle.
+        assert_eq!(
+            next_chunk(&mut backfill).await,
+            StreamChunk::from_pretty(
+                " I  I
+                + 1 10
+                + 2 20
+                + 3 30"
+            )
+        );
+
+        // The upstream updates a row that has been read, and inserts a row after the snapshot.
+        state_table.update(row(2, 20), row(2, 21));
+        state_table.insert(row(4, 40));
+        state_table
+            .commit_for_test(EpochPair::new_test_epoch(3))
+            .await
+            .unwrap();
+        tx.push_chunk(StreamChunk::from_pretty(
+            "  I  I
+            U- 2 20
+            U+ 2 21",
+        ));
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I
+            + 4 40",
+        ));
+        tx.push_barrier(3, false);
+
+        // The update is forwarded on the barrier, while the insertion is hidden.
+        assert_eq!(
+            next_chunk(&mut backfill).await.compact(),
+            StreamChunk::from_pretty(
+                "  I  I
+                U- 2 20
+                U+ 2 21"
+            )
+        );
+        assert_eq!(next_chunk(&mut backfill).await.compact().cardinality(), 0);
+        assert_eq!(next_barrier(&mut backfill).await.epoch.curr, 3);
+
+        // The inserted row is read from the snapshot of epoch 2, after which the snapshot is
+        // exhausted and the upstream is forwarded directly.
+        assert_eq!(
+            next_chunk(&mut backfill).await,
+            StreamChunk::from_pretty(
+                " I  I
+                + 4 40"
+            )
+        );
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I
+            + 5 50",
+        ));
+        assert_eq!(
+            next_chunk(&mut backfill).await,
+            StreamChunk::from_pretty(
+                " I  I
+                + 5 50"
+            )
+        );
+    }
+
+    /// The upstream changes before any row is read from the snapshot are all dropped, as they are
+    /// included in the snapshot of the next epoch.
+    #[tokio::test]
+    async fn test_backfill_upstream_before_snapshot() {
+        let (mut state_table, mut tx, mut backfill) = create_backfill().await;
+        tx.push_barrier_with_mutation(2, add_backfill_actor(0));
+        state_table.update(row(2, 20), row(2, 21));
+        state_table.delete(row(3, 30));
+        state_table
+            .commit_for_test(EpochPair::new_test_epoch(3))
+            .await
+            .unwrap();
+        tx.push_chunk(StreamChunk::from_pretty(
+            "  I  I
+            U- 2 20
+            U+ 2 21
+            -  3 30",
+        ));
+        tx.push_barrier(3, false);
+
+        assert_eq!(next_barrier(&mut backfill).await.epoch.curr, 2);
+        assert_eq!(next_barrier(&mut backfill).await.epoch.curr, 3);
+        assert_eq!(
+            next_chunk(&mut backfill).await,
+            StreamChunk::from_pretty(
+                " I  I
+                + 1 10
+                + 2 21"
+            )
+        );
+    }
+
+    /// After recovery, the first barrier doesn't add the actor, which means the backfill has
+    /// finished before. The upstream is forwarded directly without reading the snapshot.
+    #[tokio::test]
+    async fn test_backfill_recovery() {
+        let (_state_table, mut tx, mut backfill) = create_backfill().await;
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I
+            + 4 40",
+        ));
+
+        assert_eq!(next_barrier(&mut backfill).await.epoch.curr, 2);
+        assert_eq!(
+            next_chunk(&mut backfill).await,
+            StreamChunk::from_pretty(
+                " I  I
+                + 4 40"
+            )
+        );
+    }
+}
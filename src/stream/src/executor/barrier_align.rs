@@ -23,7 +23,7 @@ use futures_async_stream::try_stream;
 use risingwave_common::bail;
 
 use super::error::StreamExecutorError;
-use super::{Barrier, BoxedMessageStream, Message, StreamChunk, StreamExecutorResult};
+use super::{Barrier, BoxedMessageStream, Message, StreamChunk, StreamExecutorResult, Watermark};
 use crate::executor::monitor::StreamingMetrics;
 use crate::task::ActorId;
 
@@ -35,6 +35,8 @@ pub enum AlignedMessage {
     Barrier(Barrier),
     Left(StreamChunk),
     Right(StreamChunk),
+    WatermarkLeft(Watermark),
+    WatermarkRight(Watermark),
 }
 
 #[try_stream(ok = AlignedMessage, error = StreamExecutorError)]
@@ -60,8 +62,8 @@ pub async fn barrier_align(
                 // left stream end, passthrough right chunks
                 while let Some(msg) = right.next().await {
                     match msg? {
-                        Message::Watermark(_) => {
-                            todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkRight(watermark)
                         }
                         Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
                         Message::Barrier(_) => {
@@ -75,8 +77,8 @@ pub async fn barrier_align(
                 // right stream end, passthrough left chunks
                 while let Some(msg) = left.next().await {
                     match msg? {
-                        Message::Watermark(_) => {
-                            todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkLeft(watermark)
                         }
                         Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
                         Message::Barrier(_) => {
@@ -87,9 +89,7 @@ pub async fn barrier_align(
                 break;
             }
             Either::Left((Some(msg), _)) => match msg? {
-                Message::Watermark(_) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                }
+                Message::Watermark(watermark) => yield AlignedMessage::WatermarkLeft(watermark),
                 Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
                 Message::Barrier(_) => loop {
                     let start_time = Instant::now();
@@ -99,8 +99,8 @@ pub async fn barrier_align(
                         .await
                         .context("failed to poll right message, stream closed unexpectedly")??
                     {
-                        Message::Watermark(_) => {
-                            todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkRight(watermark)
                         }
                         Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
                         Message::Barrier(barrier) => {
@@ -115,9 +115,7 @@ pub async fn barrier_align(
                 },
             },
             Either::Right((Some(msg), _)) => match msg? {
-                Message::Watermark(_) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                }
+                Message::Watermark(watermark) => yield AlignedMessage::WatermarkRight(watermark),
                 Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
                 Message::Barrier(_) => loop {
                     let start_time = Instant::now();
@@ -127,8 +125,8 @@ pub async fn barrier_align(
                         .await
                         .context("failed to poll left message, stream closed unexpectedly")??
                     {
-                        Message::Watermark(_) => {
-                            todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkLeft(watermark)
                         }
                         Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
                         Message::Barrier(barrier) => {
@@ -32,6 +32,10 @@ pub struct ChainExecutor {
 
     upstream_indices: Vec<usize>,
 
+    /// Only consume the upstream without the snapshot, which is used when the historical data is
+    /// read directly from the storage by the downstream, e.g. the temporal join.
+    upstream_only: bool,
+
     progress: CreateMviewProgress,
 
     actor_id: ActorId,
@@ -55,6 +59,7 @@ impl ChainExecutor {
         upstream_indices: Vec<usize>,
         progress: CreateMviewProgress,
         schema: Schema,
+        upstream_only: bool,
     ) -> Self {
         Self {
             info: ExecutorInfo {
@@ -65,6 +70,7 @@ impl ChainExecutor {
             snapshot,
             upstream,
             upstream_indices,
+            upstream_only,
             actor_id: progress.actor_id(),
             progress,
         }
@@ -81,20 +87,25 @@ impl ChainExecutor {
         // If the barrier is a conf change of creating this mview, init snapshot from its epoch
         // and begin to consume the snapshot.
         // Otherwise, it means we've recovered and the snapshot is already consumed.
-        let to_consume_snapshot = barrier.is_add_dispatcher(self.actor_id);
+        let to_consume_snapshot = barrier.is_add_dispatcher(self.actor_id) && !self.upstream_only;
 
         // The first barrier message should be propagated.
         yield Message::Barrier(barrier);
 
         // 2. Consume the snapshot if needed. Note that the snapshot is already projected, so
         // there's no mapping required.
+        let mut consumed_rows = 0;
         if to_consume_snapshot {
             // Init the snapshot with reading epoch.
             let snapshot = self.snapshot.execute_with_epoch(prev_epoch);
 
             #[for_await]
             for msg in snapshot {
-                yield msg?;
+                let msg = msg?;
+                if let Message::Chunk(chunk) = &msg {
+                    consumed_rows += chunk.cardinality() as u64;
+                }
+                yield msg;
             }
         }
 
@@ -103,14 +114,23 @@ impl ChainExecutor {
         #[for_await]
         for msg in upstream {
             match msg? {
-                Message::Watermark(_) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                Message::Watermark(watermark) => {
+                    if let Some(watermark) =
+                        watermark.transform_with_indices(&self.upstream_indices)
+                    {
+                        yield Message::Watermark(watermark);
+                    }
                 }
                 Message::Chunk(chunk) => {
                     yield Message::Chunk(mapping(&self.upstream_indices, chunk));
                 }
                 Message::Barrier(barrier) => {
-                    self.progress.finish(barrier.epoch.curr);
+                    self.progress.finish(barrier.epoch.curr, consumed_rows);
+                    // The upstream columns appended by a schema change are output after the
+                    // barrier.
+                    if let Some(indices) = barrier.as_chain_schema_change(self.actor_id) {
+                        self.upstream_indices = indices;
+                    }
                     yield Message::Barrier(barrier);
                 }
             }
@@ -191,7 +211,7 @@ mod test {
             ],
         ));
 
-        let chain = ChainExecutor::new(first, second, vec![0], progress, schema);
+        let chain = ChainExecutor::new(first, second, vec![0], progress, schema, false);
 
         let mut chain = Box::new(chain).execute();
         chain.next().await;
@@ -18,7 +18,9 @@ use std::sync::Arc;
 use futures::{pin_mut, StreamExt};
 use futures_async_stream::try_stream;
 use itertools::Itertools;
-use risingwave_common::array::{Array, ArrayImpl, DataChunk, Op, RowDeserializer, StreamChunk};
+use risingwave_common::array::{
+    Array, ArrayImpl, DataChunk, Op, Row, RowDeserializer, StreamChunk,
+};
 use risingwave_common::bail;
 use risingwave_common::buffer::{Bitmap, BitmapBuilder};
 use risingwave_common::catalog::Schema;
@@ -51,6 +53,9 @@ pub struct DynamicFilterExecutor<S: StateStore> {
     range_cache: RangeCache<S>,
     right_table: StateTable<S>,
     is_right_table_writer: bool,
+    /// Whether the right value only moves in the direction that makes the condition stricter, so
+    /// that the left rows failing the condition can be cleaned from the state.
+    is_monotonic: bool,
     schema: Schema,
     metrics: Arc<StreamingMetrics>,
     /// The maximum size of the chunk produced by executor at a time.
@@ -70,6 +75,7 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
         mut state_table_l: StateTable<S>,
         mut state_table_r: StateTable<S>,
         is_right_table_writer: bool,
+        is_monotonic: bool,
         metrics: Arc<StreamingMetrics>,
         chunk_size: usize,
     ) -> Self {
@@ -89,6 +95,7 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
             range_cache: RangeCache::new(state_table_l, usize::MAX),
             right_table: state_table_r,
             is_right_table_writer,
+            is_monotonic,
             metrics,
             schema,
             chunk_size,
@@ -166,10 +173,14 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
                 },
             }
 
+            // Rows failing a monotonic condition will never satisfy it, so they are not stored
+            // either.
+            let permanently_filtered = self.is_monotonic && eval_results.is_some() && !res;
+
             // Store the rows without a null left key
             // null key in left side of predicate should never be stored
             // (it will never satisfy the filter condition)
-            if let Some(val) = left_val {
+            if let Some(val) = left_val && !permanently_filtered {
                 match *op {
                     Op::Insert | Op::UpdateInsert => {
                         self.range_cache.insert(val, row.to_owned_row())?;
@@ -233,6 +244,17 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
         }
     }
 
+    /// Returns the range of left keys failing the condition with the given right value.
+    fn get_failing_range(&self, curr: ScalarImpl) -> (Bound<ScalarImpl>, Bound<ScalarImpl>) {
+        match self.comparator {
+            GreaterThan => (Unbounded, Included(curr)),
+            GreaterThanOrEqual => (Unbounded, Excluded(curr)),
+            LessThan => (Included(curr), Unbounded),
+            LessThanOrEqual => (Excluded(curr), Unbounded),
+            _ => unreachable!(),
+        }
+    }
+
     #[try_stream(ok = Message, error = StreamExecutorError)]
     async fn into_stream(mut self) {
         let input_l = self.source_l.take().unwrap();
@@ -256,6 +278,9 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
         let mut prev_epoch_value: Option<Datum> = None;
         let mut current_epoch_value: Option<Datum> = None;
         let mut current_epoch_row = None;
+        // The right row persisted in the state table, which must be deleted if the right value is
+        // retracted without a new one, e.g. when the right input is a Top-1 over an empty table.
+        let mut committed_right_row = None;
 
         let aligned_stream = barrier_align(
             input_l.execute(),
@@ -270,6 +295,13 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
         self.right_table.init_epoch(barrier.epoch);
         self.range_cache.init(barrier.epoch);
 
+        // Recover the right value of the last epoch, so that its updates are consistent.
+        if let Some(row) = self.right_table.get_row(&Row::empty()).await? {
+            prev_epoch_value = Some(row[0].clone());
+            current_epoch_value = Some(row[0].clone());
+            committed_right_row = Some(row);
+        }
+
         // The first barrier message should be propagated.
         yield Message::Barrier(barrier);
 
@@ -306,6 +338,9 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
                         yield Message::Chunk(new_chunk)
                     }
                 }
+                // Rows below the watermark may still be emitted when the right value changes, so
+                // the watermarks are not forwarded.
+                AlignedMessage::WatermarkLeft(_) | AlignedMessage::WatermarkRight(_) => {}
                 AlignedMessage::Right(chunk) => {
                     // Record the latest update to the right value
                     let chunk = chunk.compact(); // Is this unnecessary work?
@@ -341,7 +376,8 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
                     let curr: Datum = current_epoch_value.clone().flatten();
                     let prev: Datum = prev_epoch_value.flatten();
                     let row_deserializer = RowDeserializer::new(self.schema.data_types());
-                    if prev != curr {
+                    let right_changed = prev != curr;
+                    if right_changed {
                         let (range, latest_is_lower, is_insert) = self.get_range(&curr, prev);
                         for (_, rows) in self.range_cache.range(range, latest_is_lower) {
                             for row in rows {
@@ -361,13 +397,36 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
 
                     if self.is_right_table_writer {
                         if let Some(row) = current_epoch_row.take() {
-                            self.right_table.insert(row);
+                            self.right_table.insert(row.clone());
+                            self.right_table.commit(barrier.epoch).await?;
+                            committed_right_row = Some(row);
+                        } else if current_epoch_value.is_none()
+                            && let Some(row) = committed_right_row.take()
+                        {
+                            self.right_table.delete(row);
                             self.right_table.commit(barrier.epoch).await?;
                         } else {
                             self.right_table.commit_no_data_expected(barrier.epoch);
                         }
                     }
 
+                    // Clean the left rows that will never satisfy the condition again.
+                    if self.is_monotonic && right_changed && let Some(curr) = curr.clone() {
+                        let range = self.get_failing_range(curr);
+                        let mut rows_to_clean = vec![];
+                        for (key, rows) in self.range_cache.range(range, false) {
+                            for row in rows {
+                                rows_to_clean.push((
+                                    key.clone(),
+                                    row_deserializer.deserialize(row.row.as_ref())?,
+                                ));
+                            }
+                        }
+                        for (key, row) in rows_to_clean {
+                            self.range_cache.delete(&key, row)?;
+                        }
+                    }
+
                     self.range_cache.flush(barrier.epoch).await?;
 
                     prev_epoch_value = Some(curr);
@@ -442,6 +501,7 @@ mod tests {
 
     fn create_executor(
         comparator: ExprNodeType,
+        is_monotonic: bool,
     ) -> (MessageSender, MessageSender, BoxedMessageStream) {
         let schema = Schema {
             fields: vec![Field::unnamed(DataType::Int64)],
@@ -461,6 +521,7 @@ mod tests {
             mem_state_l,
             mem_state_r,
             true,
+            is_monotonic,
             Arc::new(StreamingMetrics::unused()),
             1024,
         );
@@ -492,7 +553,8 @@ mod tests {
             "  I
              + 4",
         );
-        let (mut tx_l, mut tx_r, mut dynamic_filter) = create_executor(ExprNodeType::GreaterThan);
+        let (mut tx_l, mut tx_r, mut dynamic_filter) =
+            create_executor(ExprNodeType::GreaterThan, false);
 
         // push the init barrier for left and right
         tx_l.push_barrier(1, false);
@@ -596,7 +658,7 @@ mod tests {
              + 5",
         );
         let (mut tx_l, mut tx_r, mut dynamic_filter) =
-            create_executor(ExprNodeType::GreaterThanOrEqual);
+            create_executor(ExprNodeType::GreaterThanOrEqual, false);
 
         // push the init barrier for left and right
         tx_l.push_barrier(1, false);
@@ -699,7 +761,8 @@ mod tests {
             "  I
              + 1",
         );
-        let (mut tx_l, mut tx_r, mut dynamic_filter) = create_executor(ExprNodeType::LessThan);
+        let (mut tx_l, mut tx_r, mut dynamic_filter) =
+            create_executor(ExprNodeType::LessThan, false);
 
         // push the init barrier for left and right
         tx_l.push_barrier(1, false);
@@ -803,7 +866,7 @@ mod tests {
              + 0",
         );
         let (mut tx_l, mut tx_r, mut dynamic_filter) =
-            create_executor(ExprNodeType::LessThanOrEqual);
+            create_executor(ExprNodeType::LessThanOrEqual, false);
 
         // push the init barrier for left and right
         tx_l.push_barrier(1, false);
@@ -880,4 +943,150 @@ mod tests {
             )
         );
     }
+
+    #[tokio::test]
+    async fn test_dynamic_filter_monotonic() {
+        let chunk_l1 = StreamChunk::from_pretty(
+            "  I
+             + 1
+             + 2
+             + 3",
+        );
+        let chunk_l2 = StreamChunk::from_pretty(
+            "  I
+             - 1",
+        );
+        let chunk_r1 = StreamChunk::from_pretty(
+            "  I
+             + 2",
+        );
+        let chunk_r2 = StreamChunk::from_pretty(
+            "  I
+             + 1",
+        );
+        let (mut tx_l, mut tx_r, mut dynamic_filter) =
+            create_executor(ExprNodeType::GreaterThan, true);
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        dynamic_filter.next().await.unwrap().unwrap();
+
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+
+        // push the 1st right chunk
+        tx_r.push_chunk(chunk_r1);
+
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+
+        let chunk = dynamic_filter.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I
+                + 3"
+            )
+        );
+
+        // Get the barrier
+        dynamic_filter.next().await.unwrap().unwrap();
+
+        // push the 2nd left chunk, `1` has been cleaned from the state and is not emitted
+        tx_l.push_chunk(chunk_l2);
+
+        // push the 2nd right chunk, which breaks the monotonicity on purpose to show that `2` has
+        // been cleaned from the state as well
+        tx_r.push_chunk(chunk_r2);
+
+        tx_l.push_barrier(3, false);
+        tx_r.push_barrier(3, false);
+
+        assert!(dynamic_filter
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .as_barrier()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_dynamic_filter_right_delete() {
+        let chunk_l1 = StreamChunk::from_pretty(
+            "  I
+             + 1
+             + 2
+             + 3",
+        );
+        let chunk_r1 = StreamChunk::from_pretty(
+            "  I
+             + 2",
+        );
+        // The right value is retracted without a new one, as a Top-1 over an empty table does.
+        let chunk_r2 = StreamChunk::from_pretty(
+            "  I
+             - 2",
+        );
+        let chunk_r3 = StreamChunk::from_pretty(
+            "  I
+             + 1",
+        );
+        let (mut tx_l, mut tx_r, mut dynamic_filter) =
+            create_executor(ExprNodeType::GreaterThan, false);
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        dynamic_filter.next().await.unwrap().unwrap();
+
+        tx_l.push_chunk(chunk_l1);
+        tx_r.push_chunk(chunk_r1);
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+
+        let chunk = dynamic_filter.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I
+                + 3"
+            )
+        );
+
+        // Get the barrier
+        dynamic_filter.next().await.unwrap().unwrap();
+
+        // A missing right value means the predicate is not true
+        tx_r.push_chunk(chunk_r2);
+        tx_l.push_barrier(3, false);
+        tx_r.push_barrier(3, false);
+
+        let chunk = dynamic_filter.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I
+                - 3"
+            )
+        );
+
+        // Get the barrier
+        dynamic_filter.next().await.unwrap().unwrap();
+
+        tx_r.push_chunk(chunk_r3);
+        tx_l.push_barrier(4, false);
+        tx_r.push_barrier(4, false);
+
+        let chunk = dynamic_filter.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I
+                + 2
+                + 3"
+            )
+        );
+    }
 }
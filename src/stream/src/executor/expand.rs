@@ -51,6 +51,7 @@ impl ExpandExecutor {
 
     #[try_stream(ok = Message, error = StreamExecutorError)]
     async fn execute_inner(self) {
+        let input_len = self.input.schema().len();
         #[for_await]
         for msg in self.input.execute() {
             match msg? {
@@ -72,6 +73,14 @@ impl ExpandExecutor {
                         yield Message::Chunk(stream_chunk)
                     }
                 }
+                Message::Watermark(watermark) => {
+                    // The input columns are copied to the second half of the output, and also to
+                    // the first half with some of the values replaced by nulls, which doesn't
+                    // break the watermark either.
+                    let shifted = watermark.clone().with_idx(watermark.col_idx + input_len);
+                    yield Message::Watermark(watermark);
+                    yield Message::Watermark(shifted);
+                }
                 m => yield m,
             }
         }
@@ -22,7 +22,7 @@ use risingwave_expr::expr::BoxedExpression;
 
 use super::{
     ActorContextRef, Executor, ExecutorInfo, PkIndicesRef, SimpleExecutor, SimpleExecutorWrapper,
-    StreamExecutorResult,
+    StreamExecutorResult, Watermark,
 };
 use crate::common::InfallibleExpression;
 
@@ -84,8 +84,13 @@ impl Debug for SimpleFilterExecutor {
     }
 }
 
+#[async_trait::async_trait]
 impl SimpleExecutor for SimpleFilterExecutor {
-    fn map_filter_chunk(
+    fn handle_watermark(&self, watermark: Watermark) -> StreamExecutorResult<Vec<Watermark>> {
+        Ok(vec![watermark])
+    }
+
+    async fn map_filter_chunk(
         &mut self,
         chunk: StreamChunk,
     ) -> StreamExecutorResult<Option<StreamChunk>> {
@@ -93,9 +98,12 @@ impl SimpleExecutor for SimpleFilterExecutor {
 
         let (data_chunk, ops) = chunk.into_parts();
 
-        let pred_output = self.expr.eval_infallible(&data_chunk, |err| {
-            self.ctx.on_compute_error(err, self.identity())
-        });
+        let pred_output = self
+            .expr
+            .eval_infallible_async(&data_chunk, |err| {
+                self.ctx.on_compute_error(err, &self.info.identity)
+            })
+            .await;
 
         let (columns, vis) = data_chunk.into_parts();
 
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use futures::StreamExt;
 use futures_async_stream::try_stream;
 use risingwave_common::array::{Row, StreamChunk};
@@ -20,7 +22,7 @@ use risingwave_storage::table::streaming_table::state_table::StateTable;
 use risingwave_storage::StateStore;
 
 use super::aggregation::{
-    agg_call_filter_res, iter_table_storage, AggChangesInfo, AggStateStorage,
+    agg_call_filter_res, iter_table_storage, AggChangesInfo, AggStateStorage, DistinctDeduplicater,
 };
 use super::*;
 use crate::error::StreamResult;
@@ -63,6 +65,9 @@ pub struct GlobalSimpleAggExecutor<S: StateStore> {
     /// table when `flush_data` is called.
     result_table: StateTable<S>,
 
+    /// Deduplicater for the input of distinct agg calls.
+    distinct_dedup: DistinctDeduplicater<S>,
+
     /// Extreme state cache size
     extreme_cache_size: usize,
 
@@ -96,12 +101,14 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
         agg_calls: Vec<AggCall>,
         storages: Vec<AggStateStorage<S>>,
         result_table: StateTable<S>,
+        distinct_dedup_tables: HashMap<usize, StateTable<S>>,
         pk_indices: PkIndices,
         executor_id: u64,
         extreme_cache_size: usize,
     ) -> StreamResult<Self> {
         let input_info = input.info();
         let schema = generate_agg_schema(input.as_ref(), &agg_calls, None);
+        let distinct_dedup = DistinctDeduplicater::new(&agg_calls, distinct_dedup_tables);
 
         Ok(Self {
             ctx,
@@ -116,6 +123,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
             agg_calls,
             storages,
             result_table,
+            distinct_dedup,
             extreme_cache_size,
             state_changed: false,
         })
@@ -128,6 +136,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
         agg_calls: &[AggCall],
         storages: &mut [AggStateStorage<S>],
         result_table: &mut StateTable<S>,
+        distinct_dedup: &mut DistinctDeduplicater<S>,
         input_pk_indices: &PkIndices,
         input_schema: &Schema,
         agg_group: &mut Option<AggGroup<S>>,
@@ -194,6 +203,11 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
                 }
             });
 
+        // Deduplicate the input for distinct agg calls.
+        let visibilities = distinct_dedup
+            .dedup_chunk(&ops, &columns, visibilities, None)
+            .await?;
+
         // Apply chunk to each of the state (per agg_call)
         agg_group.apply_chunk(storages, &ops, &columns, visibilities)?;
 
@@ -206,6 +220,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
         epoch: EpochPair,
         storages: &mut [AggStateStorage<S>],
         result_table: &mut StateTable<S>,
+        distinct_dedup: &mut DistinctDeduplicater<S>,
         state_changed: &mut bool,
     ) -> StreamExecutorResult<Option<StreamChunk>> {
         if *state_changed {
@@ -217,6 +232,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
                 iter_table_storage(storages).map(|state_table| state_table.commit(epoch)),
             )
             .await?;
+            distinct_dedup.flush(epoch).await?;
 
             // Create array builders.
             // As the datatype is retrieved from schema, it contains both group key and aggregation
@@ -266,6 +282,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
                 state_table.commit_no_data_expected(epoch);
             });
             result_table.commit_no_data_expected(epoch);
+            distinct_dedup.commit_no_data_expected(epoch);
             Ok(None)
         }
     }
@@ -282,6 +299,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
             extreme_cache_size,
             mut storages,
             mut result_table,
+            mut distinct_dedup,
             mut state_changed,
         } = self;
 
@@ -293,6 +311,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
             state_table.init_epoch(barrier.epoch);
         });
         result_table.init_epoch(barrier.epoch);
+        distinct_dedup.init_epoch(barrier.epoch);
 
         yield Message::Barrier(barrier);
 
@@ -300,9 +319,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
         for msg in input {
             let msg = msg?;
             match msg {
-                Message::Watermark(_) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                }
+                Message::Watermark(_) => {}
 
                 Message::Chunk(chunk) => {
                     Self::apply_chunk(
@@ -311,6 +328,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
                         &agg_calls,
                         &mut storages,
                         &mut result_table,
+                        &mut distinct_dedup,
                         &input_pk_indices,
                         &input_schema,
                         &mut agg_group,
@@ -327,6 +345,7 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
                         barrier.epoch,
                         &mut storages,
                         &mut result_table,
+                        &mut distinct_dedup,
                         &mut state_changed,
                     )
                     .await?
@@ -397,6 +416,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
@@ -405,6 +425,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
@@ -413,6 +434,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Min,
@@ -421,6 +443,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
         ];
 
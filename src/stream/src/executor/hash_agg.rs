@@ -25,12 +25,15 @@ use risingwave_common::array::{Row, StreamChunk};
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::Schema;
 use risingwave_common::hash::{HashCode, HashKey, PrecomputedBuildHasher};
+use risingwave_common::types::ScalarImpl;
 use risingwave_common::util::epoch::EpochPair;
 use risingwave_common::util::hash_util::Crc32FastBuilder;
 use risingwave_storage::table::streaming_table::state_table::StateTable;
 use risingwave_storage::StateStore;
 
-use super::aggregation::{agg_call_filter_res, iter_table_storage, AggStateStorage};
+use super::aggregation::{
+    agg_call_filter_res, iter_table_storage, AggStateStorage, DistinctDeduplicater,
+};
 use super::{expect_first_barrier, ActorContextRef, Executor, PkIndicesRef, StreamExecutorResult};
 use crate::cache::{cache_may_stale, EvictableHashMap, ExecutorCache, LruManagerRef};
 use crate::error::StreamResult;
@@ -91,6 +94,9 @@ struct HashAggExecutorExtra<K: HashKey, S: StateStore> {
     /// table when `flush_data` is called.
     result_table: StateTable<S>,
 
+    /// Deduplicater for the input of distinct agg calls.
+    distinct_dedup: DistinctDeduplicater<S>,
+
     /// Indices of the columns
     /// all of the aggregation functions in this executor should depend on same group of keys
     group_key_indices: Vec<usize>,
@@ -144,6 +150,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         agg_calls: Vec<AggCall>,
         storages: Vec<AggStateStorage<S>>,
         result_table: StateTable<S>,
+        distinct_dedup_tables: HashMap<usize, StateTable<S>>,
         pk_indices: PkIndices,
         executor_id: u64,
         group_key_indices: Vec<usize>,
@@ -155,6 +162,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
     ) -> StreamResult<Self> {
         let input_info = input.info();
         let schema = generate_agg_schema(input.as_ref(), &agg_calls, Some(&group_key_indices));
+        let distinct_dedup = DistinctDeduplicater::new(&agg_calls, distinct_dedup_tables);
 
         Ok(Self {
             input,
@@ -168,6 +176,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 agg_calls,
                 storages,
                 result_table,
+                distinct_dedup,
                 group_key_indices,
                 group_by_cache_size,
                 extreme_cache_size,
@@ -240,6 +249,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
             ref agg_calls,
             ref mut storages,
             ref result_table,
+            ref mut distinct_dedup,
             ref input_schema,
             ref input_pk_indices,
             ref extreme_cache_size,
@@ -361,6 +371,9 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 .map(|v| v.map_or_else(|| vis_map.clone(), |v| v & vis_map))
                 .map(Some)
                 .collect();
+            let visibilities = distinct_dedup
+                .dedup_chunk(&ops, &columns, visibilities, agg_group.group_key())
+                .await?;
             agg_group.apply_chunk(storages, &ops, &columns, visibilities)?;
         }
 
@@ -375,6 +388,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
             ref schema,
             ref mut storages,
             ref mut result_table,
+            ref mut distinct_dedup,
             ref mut group_change_set,
             ref lookup_miss_count,
             ref total_lookup_count,
@@ -470,6 +484,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
             )
             .await?;
             result_table.commit(epoch).await?;
+            distinct_dedup.flush(epoch).await?;
 
             // Evict cache to target capacity.
             agg_groups.evict();
@@ -480,10 +495,36 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 state_table.commit_no_data_expected(epoch);
             });
             result_table.commit_no_data_expected(epoch);
+            distinct_dedup.commit_no_data_expected(epoch);
             return Ok(());
         }
     }
 
+    /// Evict the groups whose group key at `group_key_idx` is below the watermark from the cache,
+    /// as they will never be updated again.
+    ///
+    /// TODO: also clean the state tables when range delete is supported.
+    fn evict_below_watermark(
+        agg_groups: &mut AggGroupMap<K, S>,
+        group_key_idx: usize,
+        watermark: &ScalarImpl,
+    ) {
+        let expired_keys = agg_groups
+            .iter()
+            .filter(|(_, agg_group)| {
+                agg_group
+                    .as_ref()
+                    .and_then(|agg_group| agg_group.group_key())
+                    .and_then(|group_key| group_key[group_key_idx].as_ref())
+                    .map_or(false, |val| val < watermark)
+            })
+            .map(|(key, _)| key.clone())
+            .collect_vec();
+        for key in expired_keys {
+            agg_groups.pop(&key);
+        }
+    }
+
     #[try_stream(ok = Message, error = StreamExecutorError)]
     async fn execute_inner(self) {
         let HashAggExecutor {
@@ -500,6 +541,9 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
             ))
         };
 
+        // The latest watermark on each group key column since the last barrier.
+        let mut buffered_watermarks = vec![None; extra.group_key_indices.len()];
+
         // First barrier
         let mut input = input.execute();
         let barrier = expect_first_barrier(&mut input).await?;
@@ -507,6 +551,7 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
             state_table.init_epoch(barrier.epoch);
         });
         extra.result_table.init_epoch(barrier.epoch);
+        extra.distinct_dedup.init_epoch(barrier.epoch);
         agg_states.update_epoch(barrier.epoch.curr);
 
         yield Message::Barrier(barrier);
@@ -515,8 +560,16 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
         for msg in input {
             let msg = msg?;
             match msg {
-                Message::Watermark(_) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                Message::Watermark(watermark) => {
+                    // Only watermarks on the group key are kept, which are applied and emitted on
+                    // the next barrier after all changes of the groups below them are flushed.
+                    if let Some(group_key_idx) = extra
+                        .group_key_indices
+                        .iter()
+                        .position(|&idx| idx == watermark.col_idx)
+                    {
+                        buffered_watermarks[group_key_idx] = Some(watermark);
+                    }
                 }
 
                 Message::Chunk(chunk) => {
@@ -528,11 +581,24 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                         yield Message::Chunk(chunk?);
                     }
 
+                    for (group_key_idx, watermark) in buffered_watermarks.iter_mut().enumerate() {
+                        if let Some(watermark) = watermark.take() {
+                            if let Some(val) = &watermark.val {
+                                Self::evict_below_watermark(&mut agg_states, group_key_idx, val);
+                            }
+                            // The group keys are the first columns of the output.
+                            yield Message::Watermark(watermark.with_idx(group_key_idx));
+                        }
+                    }
+
                     // Update the vnode bitmap for state tables of all agg calls if asked.
                     if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(extra.ctx.id) {
                         iter_table_storage(&mut extra.storages).for_each(|state_table| {
                             let _ = state_table.update_vnode_bitmap(vnode_bitmap.clone());
                         });
+                        extra
+                            .distinct_dedup
+                            .update_vnode_bitmap(vnode_bitmap.clone());
                         let previous_vnode_bitmap =
                             extra.result_table.update_vnode_bitmap(vnode_bitmap.clone());
 
@@ -571,7 +637,9 @@ mod tests {
 
     use crate::executor::aggregation::{AggArgs, AggCall};
     use crate::executor::monitor::StreamingMetrics;
-    use crate::executor::test_utils::agg_executor::{create_agg_state_table, create_result_table};
+    use crate::executor::test_utils::agg_executor::{
+        create_agg_state_table, create_distinct_dedup_tables, create_result_table,
+    };
     use crate::executor::test_utils::*;
     use crate::executor::{ActorContext, Executor, HashAggExecutor, Message, PkIndices};
 
@@ -601,12 +669,19 @@ mod tests {
             })
             .collect();
         let result_table = create_result_table(
-            store,
+            store.clone(),
             TableId::new(agg_calls.len() as u32),
             &agg_calls,
             &group_key_indices,
             input.as_ref(),
         );
+        let distinct_dedup_tables = create_distinct_dedup_tables(
+            store,
+            agg_calls.len() as u32 + 1,
+            &agg_calls,
+            &group_key_indices,
+            input.as_ref(),
+        );
 
         HashAggExecutor::<SerializedKey, S>::new(
             ActorContext::create(123),
@@ -614,6 +689,7 @@ mod tests {
             agg_calls,
             agg_state_tables,
             result_table,
+            distinct_dedup_tables,
             pk_indices,
             executor_id,
             group_key_indices,
@@ -639,6 +715,16 @@ mod tests {
         test_global_hash_aggregation_count(MemoryStateStore::new()).await
     }
 
+    #[tokio::test]
+    async fn test_hash_aggregation_distinct_in_memory() {
+        test_hash_aggregation_distinct(MemoryStateStore::new()).await
+    }
+
+    #[tokio::test]
+    async fn test_hash_aggregation_distinct_min_in_memory() {
+        test_hash_aggregation_distinct_min(MemoryStateStore::new()).await
+    }
+
     #[tokio::test]
     async fn test_local_hash_aggregation_min_in_memory() {
         test_local_hash_aggregation_min(MemoryStateStore::new()).await
@@ -681,6 +767,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Count,
@@ -689,6 +776,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Count,
@@ -697,6 +785,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
         ];
 
@@ -782,6 +871,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
@@ -790,6 +880,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
             // This is local hash aggregation, so we add another sum state
             AggCall {
@@ -799,6 +890,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
         ];
 
@@ -847,6 +939,257 @@ mod tests {
         );
     }
 
+    async fn test_hash_aggregation_distinct<S: StateStore>(store: S) {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+
+        let (mut tx, source) = MockSource::channel(schema, PkIndices::new());
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 10
+            + 1 10
+            + 1 20
+            + 2 10",
+        ));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            - 1 10
+            - 1 20
+            + 2 10",
+        ));
+        tx.push_barrier(3, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 1 20",
+        ));
+        tx.push_barrier(4, false);
+
+        // `count(*)`, `count(distinct v)` and `sum(distinct v)`, where the two distinct agg calls
+        // share the same dedup table.
+        let key_indices = vec![0];
+        let append_only = false;
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only,
+                filter: None,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only,
+                filter: None,
+                distinct: true,
+            },
+            AggCall {
+                kind: AggKind::Sum,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only,
+                filter: None,
+                distinct: true,
+            },
+        ];
+
+        let hash_agg = new_boxed_hash_agg_executor(
+            store,
+            Box::new(source),
+            agg_calls,
+            key_indices,
+            vec![],
+            1 << 16,
+            1 << 10,
+            1,
+        );
+        let mut hash_agg = hash_agg.execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 1 3 2 30
+                + 2 1 1 10"
+            )
+            .sorted_rows(),
+        );
+
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                "  I I I I
+                U- 1 3 2 30
+                U+ 1 1 1 10
+                U- 2 1 1 10
+                U+ 2 2 1 10"
+            )
+            .sorted_rows(),
+        );
+
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // The dedup counts of the previous epochs are read from the dedup table.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                "  I I I I
+                U- 1 1 1 10
+                U+ 1 2 2 30"
+            )
+            .sorted_rows(),
+        );
+    }
+
+    async fn test_hash_aggregation_distinct_min<S: StateStore>(store: S) {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+
+        let (mut tx, source) = MockSource::channel(schema, vec![2]);
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I    I
+            + 1 10 1001
+            + 1 10 1002
+            + 1 20 1003
+            + 2 30 1004",
+        ));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I    I
+            - 1 10 1001
+            - 1 20 1003",
+        ));
+        tx.push_barrier(3, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I    I
+            + 1 20 1005
+            - 1 10 1002",
+        ));
+        tx.push_barrier(4, false);
+
+        // `count(*)`, `count(distinct v)` and `min(distinct v)` on the same column, where only
+        // `count(distinct v)` has a count column in the dedup table.
+        let key_indices = vec![0];
+        let append_only = false;
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only,
+                filter: None,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only,
+                filter: None,
+                distinct: true,
+            },
+            AggCall {
+                kind: AggKind::Min,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only,
+                filter: None,
+                distinct: true,
+            },
+        ];
+
+        let hash_agg = new_boxed_hash_agg_executor(
+            store,
+            Box::new(source),
+            agg_calls,
+            key_indices,
+            vec![2],
+            1 << 16,
+            1 << 10,
+            1,
+        );
+        let mut hash_agg = hash_agg.execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                " I I I  I
+                + 1 3 2 10
+                + 2 1 1 30"
+            )
+            .sorted_rows(),
+        );
+
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                "  I I I  I
+                U- 1 3 2 10
+                U+ 1 1 1 10"
+            )
+            .sorted_rows(),
+        );
+
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // The dedup counts of the previous epochs are read from the dedup table.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap().sorted_rows(),
+            StreamChunk::from_pretty(
+                "  I I I  I
+                U- 1 1 1 10
+                U+ 1 1 1 20"
+            )
+            .sorted_rows(),
+        );
+    }
+
     async fn test_local_hash_aggregation_min<S: StateStore>(store: S) {
         let schema = Schema {
             fields: vec![
@@ -885,6 +1228,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only: false,
                 filter: None,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Min,
@@ -893,6 +1237,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only: false,
                 filter: None,
+                distinct: false,
             },
         ];
 
@@ -984,6 +1329,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Min,
@@ -992,6 +1338,7 @@ mod tests {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
         ];
 
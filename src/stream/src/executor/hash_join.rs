@@ -24,7 +24,7 @@ use risingwave_common::array::{Op, Row, RowRef, StreamChunk};
 use risingwave_common::bail;
 use risingwave_common::catalog::Schema;
 use risingwave_common::hash::HashKey;
-use risingwave_common::types::{DataType, ToOwnedDatum};
+use risingwave_common::types::{DataType, ScalarImpl, ToOwnedDatum};
 use risingwave_common::util::epoch::EpochPair;
 use risingwave_expr::expr::BoxedExpression;
 use risingwave_storage::table::streaming_table::state_table::StateTable;
@@ -37,6 +37,7 @@ use super::managed_state::join::*;
 use super::monitor::StreamingMetrics;
 use super::{
     ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef,
+    Watermark,
 };
 use crate::cache::LruManagerRef;
 use crate::common::{InfallibleExpression, StreamChunkBuilder};
@@ -247,6 +248,8 @@ pub struct HashJoinExecutor<K: HashKey, S: StateStore, const T: JoinTypePrimitiv
     metrics: Arc<StreamingMetrics>,
     /// The maximum size of the chunk produced by executor at a time
     chunk_size: usize,
+    /// The latest watermarks of each join key column on the left and right sides.
+    join_key_watermarks: Vec<[Option<ScalarImpl>; 2]>,
 }
 
 impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> std::fmt::Debug
@@ -552,6 +555,8 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
             StreamChunkBuilder::get_i2o_mapping(output_indices.iter().cloned(), left_len, right_len)
         };
 
+        let join_key_watermarks = vec![[None, None]; join_key_indices_l.len()];
+
         Self {
             ctx: ctx.clone(),
             input_l: Some(input_l),
@@ -611,6 +616,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
             append_only_optimize,
             metrics,
             chunk_size,
+            join_key_watermarks,
         }
     }
 
@@ -658,13 +664,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                         self.append_only_optimize,
                         self.chunk_size,
                     ) {
-                        yield chunk.map(|v| match v {
-                            Message::Watermark(_) => {
-                                todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                            }
-                            Message::Chunk(chunk) => Message::Chunk(chunk),
-                            barrier @ Message::Barrier(_) => barrier,
-                        })?;
+                        yield chunk?;
                     }
                 }
                 AlignedMessage::Right(chunk) => {
@@ -680,13 +680,17 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                         self.append_only_optimize,
                         self.chunk_size,
                     ) {
-                        yield chunk.map(|v| match v {
-                            Message::Watermark(_) => {
-                                todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                            }
-                            Message::Chunk(chunk) => Message::Chunk(chunk),
-                            barrier @ Message::Barrier(_) => barrier,
-                        })?;
+                        yield chunk?;
+                    }
+                }
+                AlignedMessage::WatermarkLeft(watermark) => {
+                    for watermark in self.handle_watermark(SideType::Left, watermark)? {
+                        yield Message::Watermark(watermark);
+                    }
+                }
+                AlignedMessage::WatermarkRight(watermark) => {
+                    for watermark in self.handle_watermark(SideType::Right, watermark)? {
+                        yield Message::Watermark(watermark);
                     }
                 }
                 AlignedMessage::Barrier(barrier) => {
@@ -742,6 +746,63 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         Ok(())
     }
 
+    /// Buffer the watermark on a join key column. Any row with a join key below the minimum
+    /// watermark of both sides will never be matched or updated again, so its state is cleaned,
+    /// and the minimum becomes the watermark of the output columns from that join key. Watermarks
+    /// on other columns are dropped.
+    fn handle_watermark(
+        &mut self,
+        side: SideTypePrimitive,
+        watermark: Watermark,
+    ) -> StreamExecutorResult<Vec<Watermark>> {
+        let side_update = if side == SideType::Left {
+            &self.side_l
+        } else {
+            &self.side_r
+        };
+        let Some(key_idx) = side_update
+            .join_key_indices
+            .iter()
+            .position(|&idx| idx == watermark.col_idx) else {
+            return Ok(vec![]);
+        };
+        let Some(val) = watermark.val else {
+            return Ok(vec![]);
+        };
+
+        self.join_key_watermarks[key_idx][side as usize] = Some(val);
+        let [Some(left), Some(right)] = &self.join_key_watermarks[key_idx] else {
+            return Ok(vec![]);
+        };
+        let min_watermark = std::cmp::min(left, right).clone();
+
+        self.side_l
+            .ht
+            .clean_below_watermark(key_idx, &min_watermark)?;
+        self.side_r
+            .ht
+            .clean_below_watermark(key_idx, &min_watermark)?;
+
+        let output_watermarks = [&self.side_l, &self.side_r]
+            .into_iter()
+            .flat_map(|side| {
+                let key_col_idx = side.join_key_indices[key_idx];
+                side.i2o_mapping
+                    .iter()
+                    .filter(move |(input_idx, _)| *input_idx == key_col_idx)
+                    .map(|(_, output_idx)| *output_idx)
+            })
+            .map(|output_idx| {
+                Watermark::new(
+                    output_idx,
+                    watermark.data_type.clone(),
+                    Some(min_watermark.clone()),
+                )
+            })
+            .collect();
+        Ok(output_watermarks)
+    }
+
     /// the data the hash table and match the coming
     /// data chunk with the executor state
     async fn hash_eq_match(
@@ -2900,6 +2961,85 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_streaming_hash_full_outer_join_with_nonequi_condition_retraction() {
+        let chunk_l1 = StreamChunk::from_pretty(
+            "  I I
+             + 1 4
+             + 2 5",
+        );
+        let chunk_l2 = StreamChunk::from_pretty(
+            "  I I
+             - 1 4",
+        );
+        let chunk_r1 = StreamChunk::from_pretty(
+            "  I I
+             + 1 6
+             + 2 3",
+        );
+        let chunk_r2 = StreamChunk::from_pretty(
+            "  I I
+             - 1 6
+             - 2 3",
+        );
+        let (mut tx_l, mut tx_r, mut hash_join) =
+            create_executor::<{ JoinType::FullOuter }>(true, false);
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 1 4 . .
+                + 2 5 . ."
+            )
+        );
+
+        // push the 1st right chunk, `2 3` matches on the join key but not on the condition
+        tx_r.push_chunk(chunk_r1);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                "  I I I I
+                U- 1 4 . .
+                U+ 1 4 1 6
+                +  . . 2 3"
+            )
+        );
+
+        // push the 2nd right chunk, the null-padded left row should be restored
+        tx_r.push_chunk(chunk_r2);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                "  I I I I
+                U- 1 4 1 6
+                U+ 1 4 . .
+                -  . . 2 3"
+            )
+        );
+
+        // push the 2nd left chunk, the degree of `1 4` should be back to zero
+        tx_l.push_chunk(chunk_l2);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                - 1 4 . ."
+            )
+        );
+    }
+
     #[tokio::test]
     async fn test_streaming_hash_inner_join_with_nonequi_condition() {
         let chunk_l1 = StreamChunk::from_pretty(
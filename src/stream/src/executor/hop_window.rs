@@ -18,7 +18,7 @@ use futures::StreamExt;
 use futures_async_stream::try_stream;
 use num_traits::CheckedSub;
 use risingwave_common::array::column::Column;
-use risingwave_common::array::{DataChunk, StreamChunk, Vis};
+use risingwave_common::array::{DataChunk, Row, StreamChunk, Vis};
 use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
 use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
 use risingwave_expr::expr::{Expression, InputRefExpression, LiteralExpression};
@@ -26,7 +26,7 @@ use risingwave_expr::ExprError;
 use risingwave_pb::expr::expr_node;
 
 use super::error::StreamExecutorError;
-use super::{ActorContextRef, BoxedExecutor, Executor, ExecutorInfo, Message};
+use super::{ActorContextRef, BoxedExecutor, Executor, ExecutorInfo, Message, Watermark};
 use crate::common::InfallibleExpression;
 
 pub struct HopWindowExecutor {
@@ -243,6 +243,36 @@ impl HopWindowExecutor {
                     let new_chunk = StreamChunk::new(ops.clone(), new_cols, None);
                     yield Message::Chunk(new_chunk);
                 }
+            } else if let Message::Watermark(watermark) = msg {
+                if watermark.col_idx == time_col_idx {
+                    // Rows after the watermark all fall into the windows starting from the first
+                    // window of the watermark, so the watermarks of `window_start` and
+                    // `window_end` can be derived from it.
+                    let mut row = vec![None; window_start_col_index];
+                    row[time_col_idx] = watermark.val.clone();
+                    let hop_start = hop_start.eval_row_infallible(&Row::new(row), |err| {
+                        ctx.on_compute_error(err, &info.identity)
+                    });
+                    let hop_start_row = Row::new(vec![hop_start]);
+                    for (idx, exprs) in [
+                        (window_start_col_index, &window_start_exprs),
+                        (window_end_col_index, &window_end_exprs),
+                    ] {
+                        if let Some(out_idx) = output_indices.iter().position(|&i| i == idx) {
+                            let val = exprs[0].eval_row_infallible(&hop_start_row, |err| {
+                                ctx.on_compute_error(err, &info.identity)
+                            });
+                            yield Message::Watermark(Watermark::new(
+                                out_idx,
+                                output_type.clone(),
+                                val,
+                            ));
+                        }
+                    }
+                }
+                if let Some(watermark) = watermark.transform_with_indices(&output_indices) {
+                    yield Message::Watermark(watermark);
+                }
             } else {
                 yield msg;
                 continue;
@@ -256,10 +286,11 @@ mod tests {
     use futures::StreamExt;
     use risingwave_common::array::stream_chunk::StreamChunkTestExt;
     use risingwave_common::catalog::{Field, Schema};
-    use risingwave_common::types::{DataType, IntervalUnit};
+    use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
+    use risingwave_expr::vector_op::cast::str_to_timestamp;
 
     use crate::executor::test_utils::MockSource;
-    use crate::executor::{ActorContext, Executor, ExecutorInfo, StreamChunk};
+    use crate::executor::{ActorContext, Executor, ExecutorInfo, Message, StreamChunk, Watermark};
 
     #[tokio::test]
     async fn test_execute() {
@@ -421,4 +452,72 @@ mod tests {
             )
         );
     }
+
+    #[tokio::test]
+    async fn test_watermark() {
+        let field1 = Field::unnamed(DataType::Int64);
+        let field2 = Field::with_name(DataType::Timestamp, "created_at");
+        let schema = Schema::new(vec![field1, field2]);
+        let pk_indices = vec![0];
+
+        let timestamp = |s: &str| {
+            Some(ScalarImpl::NaiveDateTime(
+                str_to_timestamp(&format!("2022-02-02 {}", s)).unwrap(),
+            ))
+        };
+        let input = MockSource::with_messages(
+            schema.clone(),
+            pk_indices.clone(),
+            vec![
+                Message::Watermark(Watermark::new(
+                    0,
+                    DataType::Int64,
+                    Some(ScalarImpl::Int64(1)),
+                )),
+                Message::Watermark(Watermark::new(
+                    1,
+                    DataType::Timestamp,
+                    timestamp("10:22:00"),
+                )),
+            ],
+        )
+        .boxed();
+
+        let window_slide = IntervalUnit::from_minutes(15);
+        let window_size = IntervalUnit::from_minutes(30);
+        let executor = super::HopWindowExecutor::new(
+            ActorContext::create(123),
+            input,
+            ExecutorInfo {
+                // TODO: the schema is incorrect, but it seems useless here.
+                schema: schema.clone(),
+                pk_indices,
+                identity: "test".to_string(),
+            },
+            1,
+            window_slide,
+            window_size,
+            vec![3, 0, 2, 1],
+        )
+        .boxed();
+
+        let watermarks = executor
+            .execute()
+            .take(4)
+            .map(|msg| msg.unwrap().into_watermark().unwrap())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            watermarks,
+            vec![
+                // The watermark on an irrelevant column is forwarded.
+                Watermark::new(1, DataType::Int64, Some(ScalarImpl::Int64(1))),
+                // The watermark on the time column derives the ones on `window_start` and
+                // `window_end`.
+                Watermark::new(2, DataType::Timestamp, timestamp("10:00:00")),
+                Watermark::new(0, DataType::Timestamp, timestamp("10:30:00")),
+                Watermark::new(3, DataType::Timestamp, timestamp("10:22:00")),
+            ]
+        );
+    }
 }
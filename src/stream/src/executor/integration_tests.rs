@@ -60,6 +60,7 @@ async fn test_merger_sum_aggr() {
                     order_pairs: vec![],
                     append_only,
                     filter: None,
+                    distinct: false,
                 },
                 AggCall {
                     kind: AggKind::Sum,
@@ -68,6 +69,7 @@ async fn test_merger_sum_aggr() {
                     order_pairs: vec![],
                     append_only,
                     filter: None,
+                    distinct: false,
                 },
             ],
             vec![],
@@ -151,6 +153,7 @@ async fn test_merger_sum_aggr() {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
             AggCall {
                 kind: AggKind::Sum,
@@ -159,6 +162,7 @@ async fn test_merger_sum_aggr() {
                 order_pairs: vec![],
                 append_only,
                 filter: None,
+                distinct: false,
             },
         ],
         vec![],
@@ -174,6 +178,7 @@ async fn test_merger_sum_aggr() {
             Box::new(InputRefExpression::new(DataType::Int64, 1)),
         ],
         3,
+        vec![],
     );
 
     let items = Arc::new(Mutex::new(vec![]));
@@ -246,9 +251,7 @@ impl StreamConsumer for MockConsumer {
         async move {
             while let Some(item) = input.next().await {
                 match item? {
-                    Message::Watermark(_) => {
-                        todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                    }
+                    Message::Watermark(_) => {}
                     Message::Chunk(chunk) => data.lock().unwrap().push(chunk),
                     Message::Barrier(barrier) => yield barrier,
                 }
@@ -0,0 +1,649 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+use std::sync::Arc;
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Op, Row, RowRef, StreamChunk};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{DataType, NaiveDateTimeWrapper, ScalarImpl, ToOwnedDatum};
+use risingwave_common::util::select_all;
+use risingwave_expr::expr::BoxedExpression;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::StateStore;
+
+use super::barrier_align::*;
+use super::error::{StreamExecutorError, StreamExecutorResult};
+use super::monitor::StreamingMetrics;
+use super::{
+    ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef,
+    Watermark,
+};
+use crate::common::{InfallibleExpression, StreamChunkBuilder};
+use crate::executor::expect_first_barrier_from_aligned_stream;
+
+/// Returns the value of a time column in milliseconds since the epoch.
+fn time_to_ms(time: &ScalarImpl) -> i64 {
+    match time {
+        ScalarImpl::NaiveDateTime(time) => time.0.timestamp_millis(),
+        // `timestamp with time zone` is represented in microseconds.
+        ScalarImpl::Int64(time) => time.div_euclid(1000),
+        _ => unreachable!("unsupported time column: {:?}", time),
+    }
+}
+
+/// Adds milliseconds to the value of a time column.
+fn time_add_ms(time: &ScalarImpl, ms: i64) -> ScalarImpl {
+    match time {
+        ScalarImpl::NaiveDateTime(time) => ScalarImpl::NaiveDateTime(NaiveDateTimeWrapper(
+            time.0 + chrono::Duration::milliseconds(ms),
+        )),
+        ScalarImpl::Int64(time) => ScalarImpl::Int64(time + ms * 1000),
+        _ => unreachable!("unsupported time column: {:?}", time),
+    }
+}
+
+/// The state of one side of the interval join.
+struct IntervalJoinSide<S: StateStore> {
+    state_table: StateTable<S>,
+    key_indices: Vec<usize>,
+    time_col: usize,
+    /// The stream key of the input, which identifies a row among the ones of the same join key.
+    pk_indices: Vec<usize>,
+    /// The rows of each time bucket, keyed by the join key and then the stream key. A bucket is
+    /// the time in milliseconds divided by the bucket width.
+    buckets: BTreeMap<i64, HashMap<Row, HashMap<Row, Row>>>,
+    /// The latest watermark of the time column.
+    watermark: Option<ScalarImpl>,
+}
+
+impl<S: StateStore> IntervalJoinSide<S> {
+    fn insert(&mut self, bucket: i64, key: Row, row: Row) {
+        let pk = row.by_indices(&self.pk_indices);
+        self.buckets
+            .entry(bucket)
+            .or_default()
+            .entry(key)
+            .or_default()
+            .insert(pk, row.clone());
+        self.state_table.insert(row);
+    }
+
+    fn delete(&mut self, bucket: i64, key: &Row, row: &Row) {
+        let pk = row.by_indices(&self.pk_indices);
+        // The row may have been cleaned by the watermark already.
+        if let Some(rows) = self.buckets.get_mut(&bucket)
+            && let Some(rows_of_key) = rows.get_mut(key)
+            && let Some(row) = rows_of_key.remove(&pk)
+        {
+            if rows_of_key.is_empty() {
+                rows.remove(key);
+            }
+            self.state_table.delete(row);
+        }
+    }
+
+    /// Cleans the buckets whose rows are all earlier than `bound_ms`.
+    fn clean_below(&mut self, bound_ms: i64, bucket_width_ms: i64) {
+        while let Some(entry) = self.buckets.first_entry() {
+            if (*entry.key() + 1) * bucket_width_ms > bound_ms {
+                break;
+            }
+            for row in entry.remove().into_values().flat_map(HashMap::into_values) {
+                self.state_table.delete(row);
+            }
+        }
+    }
+
+    /// Loads all rows of the vnodes owned by this executor from the state table, on
+    /// initialization or scaling.
+    async fn load(&mut self, bucket_width_ms: i64) -> StreamExecutorResult<()> {
+        self.buckets.clear();
+        let vnode_bitmap = self.state_table.vnode_bitmap().to_owned();
+        let mut rows_per_vnode = vec![];
+        for vnode in vnode_bitmap
+            .iter()
+            .enumerate()
+            .filter(|(_, is_set)| *is_set)
+            .map(|(vnode, _)| vnode)
+        {
+            let rows = self
+                .state_table
+                .iter_with_pk_range(&(Bound::Unbounded, Bound::Unbounded), vnode as _)
+                .await?;
+            rows_per_vnode.push(Box::pin(rows));
+        }
+
+        let mut loaded = vec![];
+        if !rows_per_vnode.is_empty() {
+            let mut stream = select_all(rows_per_vnode);
+            while let Some(row) = stream.next().await {
+                loaded.push(row?.into_owned());
+            }
+        }
+        for row in loaded {
+            let time = row[self.time_col].as_ref().unwrap();
+            let bucket = time_to_ms(time).div_euclid(bucket_width_ms);
+            let key = row.by_indices(&self.key_indices);
+            let pk = row.by_indices(&self.pk_indices);
+            self.buckets
+                .entry(bucket)
+                .or_default()
+                .entry(key)
+                .or_default()
+                .insert(pk, row);
+        }
+        Ok(())
+    }
+}
+
+/// [`IntervalJoinExecutor`] is an inner join whose condition implies
+/// `right_time + lower_bound <= left_time <= right_time + upper_bound`. The rows of both sides are
+/// indexed by time bucket, so that a row only probes the buckets within the interval of the other
+/// side.
+///
+/// As the time columns of both sides have watermarks, future rows of one side never match the
+/// rows of the other side earlier than its watermark minus the interval, so these rows are cleaned
+/// by whole buckets. The output watermarks of the time columns are derived in the same way.
+pub struct IntervalJoinExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+    left: Option<BoxedExecutor>,
+    right: Option<BoxedExecutor>,
+
+    side_l: IntervalJoinSide<S>,
+    side_r: IntervalJoinSide<S>,
+    null_safe: Vec<bool>,
+    condition: Option<BoxedExpression>,
+
+    lower_bound_ms: i64,
+    upper_bound_ms: i64,
+    /// The width of the time buckets, which is the length of the interval, so that a row probes
+    /// at most two buckets of the other side.
+    bucket_width_ms: i64,
+
+    output_data_types: Vec<DataType>,
+    left_to_output: Vec<(usize, usize)>,
+    right_to_output: Vec<(usize, usize)>,
+
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    metrics: Arc<StreamingMetrics>,
+    /// The maximum size of the chunk produced by executor at a time.
+    chunk_size: usize,
+}
+
+impl<S: StateStore> IntervalJoinExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        left: BoxedExecutor,
+        right: BoxedExecutor,
+        state_table_l: StateTable<S>,
+        state_table_r: StateTable<S>,
+        left_key_indices: Vec<usize>,
+        right_key_indices: Vec<usize>,
+        null_safe: Vec<bool>,
+        condition: Option<BoxedExpression>,
+        left_time_col: usize,
+        right_time_col: usize,
+        lower_bound_ms: i64,
+        upper_bound_ms: i64,
+        output_indices: Vec<usize>,
+        schema: Schema,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        metrics: Arc<StreamingMetrics>,
+        chunk_size: usize,
+    ) -> Self {
+        assert_eq!(left_key_indices.len(), right_key_indices.len());
+
+        let (left_to_output, right_to_output) = StreamChunkBuilder::get_i2o_mapping(
+            output_indices.iter().cloned(),
+            left.schema().len(),
+            right.schema().len(),
+        );
+
+        let side_l = IntervalJoinSide {
+            state_table: state_table_l,
+            key_indices: left_key_indices,
+            time_col: left_time_col,
+            pk_indices: left.pk_indices().to_vec(),
+            buckets: BTreeMap::new(),
+            watermark: None,
+        };
+        let side_r = IntervalJoinSide {
+            state_table: state_table_r,
+            key_indices: right_key_indices,
+            time_col: right_time_col,
+            pk_indices: right.pk_indices().to_vec(),
+            buckets: BTreeMap::new(),
+            watermark: None,
+        };
+
+        Self {
+            ctx,
+            left: Some(left),
+            right: Some(right),
+            side_l,
+            side_r,
+            null_safe,
+            condition,
+            lower_bound_ms,
+            upper_bound_ms,
+            bucket_width_ms: (upper_bound_ms - lower_bound_ms).max(1),
+            output_data_types: schema.data_types(),
+            left_to_output,
+            right_to_output,
+            schema,
+            pk_indices,
+            identity: format!("IntervalJoinExecutor {:X}", executor_id),
+            metrics,
+            chunk_size,
+        }
+    }
+
+    /// Joins a chunk from one side with the state of the other side, and then updates the state of
+    /// its own side.
+    fn process_chunk(
+        &mut self,
+        chunk: StreamChunk,
+        is_left: bool,
+    ) -> StreamExecutorResult<Vec<StreamChunk>> {
+        let (side_update, side_match, update_to_output, match_to_output) = if is_left {
+            (
+                &mut self.side_l,
+                &self.side_r,
+                &self.left_to_output,
+                &self.right_to_output,
+            )
+        } else {
+            (
+                &mut self.side_r,
+                &self.side_l,
+                &self.right_to_output,
+                &self.left_to_output,
+            )
+        };
+        let mut builder = StreamChunkBuilder::new(
+            self.chunk_size,
+            &self.output_data_types,
+            update_to_output.clone(),
+            match_to_output.clone(),
+        )?;
+        let mut chunks = vec![];
+
+        for (op, row) in chunk.rows() {
+            // A row with a null time or join key never matches any row.
+            let Some(time) = row.value_at(side_update.time_col).to_owned_datum() else {
+                continue;
+            };
+            let key = row.row_by_indices(&side_update.key_indices);
+            let has_null = key
+                .values()
+                .zip_eq(&self.null_safe)
+                .any(|(datum, &null_safe)| datum.is_none() && !null_safe);
+            if has_null {
+                continue;
+            }
+
+            // The range of the time of the matched rows.
+            let time_ms = time_to_ms(&time);
+            let (start_ms, end_ms) = if is_left {
+                (time_ms - self.upper_bound_ms, time_ms - self.lower_bound_ms)
+            } else {
+                (time_ms + self.lower_bound_ms, time_ms + self.upper_bound_ms)
+            };
+            let op = match op {
+                Op::Insert | Op::UpdateInsert => Op::Insert,
+                Op::Delete | Op::UpdateDelete => Op::Delete,
+            };
+            if start_ms <= end_ms {
+                let buckets = start_ms.div_euclid(self.bucket_width_ms)
+                    ..=end_ms.div_euclid(self.bucket_width_ms);
+                let time_col = side_match.time_col;
+                for rows in side_match
+                    .buckets
+                    .range(buckets)
+                    .filter_map(|(_, rows)| rows.get(&key))
+                {
+                    for matched_row in rows.values() {
+                        let matched_ms = time_to_ms(matched_row[time_col].as_ref().unwrap());
+                        if (start_ms..=end_ms).contains(&matched_ms)
+                            && check_condition(
+                                &self.ctx,
+                                &self.identity,
+                                &self.condition,
+                                &row,
+                                matched_row,
+                                is_left,
+                            )
+                            && let Some(chunk) = builder.append_row(op, &row, matched_row)?
+                        {
+                            chunks.push(chunk);
+                        }
+                    }
+                }
+            }
+
+            let bucket = time_ms.div_euclid(self.bucket_width_ms);
+            match op {
+                Op::Insert => side_update.insert(bucket, key, row.to_owned_row()),
+                _ => side_update.delete(bucket, &key, &row.to_owned_row()),
+            }
+        }
+
+        if let Some(chunk) = builder.take()? && chunk.cardinality() > 0 {
+            chunks.push(chunk);
+        }
+        Ok(chunks)
+    }
+
+    /// Cleans the rows of the other side that no future row of this side can match, and derives
+    /// the watermarks of the time columns of the output once both sides have watermarks. Watermarks
+    /// on other columns are dropped.
+    fn handle_watermark(&mut self, watermark: Watermark, is_left: bool) -> Vec<Watermark> {
+        let side = if is_left {
+            &mut self.side_l
+        } else {
+            &mut self.side_r
+        };
+        if watermark.col_idx != side.time_col {
+            return vec![];
+        }
+        let Some(val) = watermark.val else {
+            return vec![];
+        };
+        side.watermark = Some(val.clone());
+
+        if is_left {
+            // Future left rows only match the right rows later than `left_time - upper_bound`.
+            self.side_r
+                .clean_below(time_to_ms(&val) - self.upper_bound_ms, self.bucket_width_ms);
+        } else {
+            // Future right rows only match the left rows later than `right_time + lower_bound`.
+            self.side_l
+                .clean_below(time_to_ms(&val) + self.lower_bound_ms, self.bucket_width_ms);
+        }
+
+        let (Some(left), Some(right)) = (&self.side_l.watermark, &self.side_r.watermark) else {
+            return vec![];
+        };
+        // An output row is either from a future left row, or from a future right row matching a
+        // left row no earlier than `right_time + lower_bound`, and vice versa.
+        let left_watermark = std::cmp::min(left.clone(), time_add_ms(right, self.lower_bound_ms));
+        let right_watermark = std::cmp::min(right.clone(), time_add_ms(left, -self.upper_bound_ms));
+        let mut watermarks = vec![];
+        for (to_output, time_col, val) in [
+            (&self.left_to_output, self.side_l.time_col, left_watermark),
+            (&self.right_to_output, self.side_r.time_col, right_watermark),
+        ] {
+            for (_, output_idx) in to_output.iter().filter(|(idx, _)| *idx == time_col) {
+                watermarks.push(Watermark::new(
+                    *output_idx,
+                    watermark.data_type.clone(),
+                    Some(val.clone()),
+                ));
+            }
+        }
+        watermarks
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn into_stream(mut self) {
+        let left = self.left.take().unwrap();
+        let right = self.right.take().unwrap();
+        let aligned_stream = barrier_align(
+            left.execute(),
+            right.execute(),
+            self.ctx.id,
+            self.metrics.clone(),
+        );
+        pin_mut!(aligned_stream);
+
+        let barrier = expect_first_barrier_from_aligned_stream(&mut aligned_stream).await?;
+        self.side_l.state_table.init_epoch(barrier.epoch);
+        self.side_r.state_table.init_epoch(barrier.epoch);
+
+        // The first barrier message should be propagated.
+        yield Message::Barrier(barrier);
+
+        // Recover the buckets from the state tables.
+        self.side_l.load(self.bucket_width_ms).await?;
+        self.side_r.load(self.bucket_width_ms).await?;
+
+        #[for_await]
+        for msg in aligned_stream {
+            match msg? {
+                AlignedMessage::Left(chunk) => {
+                    for chunk in self.process_chunk(chunk, true)? {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                AlignedMessage::Right(chunk) => {
+                    for chunk in self.process_chunk(chunk, false)? {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                AlignedMessage::WatermarkLeft(watermark) => {
+                    for watermark in self.handle_watermark(watermark, true) {
+                        yield Message::Watermark(watermark);
+                    }
+                }
+                AlignedMessage::WatermarkRight(watermark) => {
+                    for watermark in self.handle_watermark(watermark, false) {
+                        yield Message::Watermark(watermark);
+                    }
+                }
+                AlignedMessage::Barrier(barrier) => {
+                    self.side_l.state_table.commit(barrier.epoch).await?;
+                    self.side_r.state_table.commit(barrier.epoch).await?;
+
+                    // Update the vnode bitmap for state tables of both sides if asked, and reload
+                    // the buckets of the owned vnodes.
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        self.side_l
+                            .state_table
+                            .update_vnode_bitmap(vnode_bitmap.clone());
+                        self.side_r.state_table.update_vnode_bitmap(vnode_bitmap);
+                        self.side_l.load(self.bucket_width_ms).await?;
+                        self.side_r.load(self.bucket_width_ms).await?;
+                    }
+
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+/// Checks the join condition on the concatenation of the left and the right rows.
+fn check_condition(
+    ctx: &ActorContextRef,
+    identity: &str,
+    condition: &Option<BoxedExpression>,
+    update_row: &RowRef<'_>,
+    matched_row: &Row,
+    is_left: bool,
+) -> bool {
+    let Some(condition) = condition else {
+        return true;
+    };
+    let update_row = update_row.values().map(|datum| datum.to_owned_datum());
+    let matched_row = matched_row.values().cloned();
+    let row = if is_left {
+        Row(update_row.chain(matched_row).collect())
+    } else {
+        Row(matched_row.chain(update_row).collect())
+    };
+    condition
+        .eval_row_infallible(&row, |err| ctx.on_compute_error(err, identity))
+        .map(|s| *s.as_bool())
+        .unwrap_or(false)
+}
+
+impl<S: StateStore> Executor for IntervalJoinExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.into_stream().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, TableId};
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::test_utils::{MessageSender, MockSource};
+    use crate::executor::ActorContext;
+
+    fn create_state_table(table_id: u32) -> StateTable<MemoryStateStore> {
+        let column_descs = (0..3)
+            .map(|id| ColumnDesc::unnamed(ColumnId::new(id), DataType::Int64))
+            .collect_vec();
+        // The state is ordered by join key, time and stream key.
+        StateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            TableId::new(table_id),
+            column_descs,
+            vec![OrderType::Ascending; 3],
+            vec![0, 1, 2],
+        )
+    }
+
+    /// Both sides have columns (join key, time in microseconds, id), and are joined on
+    /// `l.time BETWEEN r.time - 2ms AND r.time + 2ms`.
+    fn create_executor() -> (MessageSender, MessageSender, BoxedMessageStream) {
+        let schema = Schema::new(vec![Field::unnamed(DataType::Int64); 3]);
+        let (tx_l, source_l) = MockSource::channel(schema.clone(), vec![2]);
+        let (tx_r, source_r) = MockSource::channel(schema, vec![2]);
+        let output_schema = Schema::new(vec![Field::unnamed(DataType::Int64); 6]);
+        let executor = IntervalJoinExecutor::new(
+            ActorContext::create(123),
+            Box::new(source_l),
+            Box::new(source_r),
+            create_state_table(0),
+            create_state_table(1),
+            vec![0],
+            vec![0],
+            vec![false],
+            None,
+            1,
+            1,
+            -2,
+            2,
+            (0..6).collect_vec(),
+            output_schema,
+            vec![2, 5],
+            1,
+            Arc::new(StreamingMetrics::unused()),
+            1024,
+        );
+        (tx_l, tx_r, Box::new(executor).execute())
+    }
+
+    #[tokio::test]
+    async fn test_interval_join() {
+        let chunk_l = StreamChunk::from_pretty(
+            "  I     I I
+             + 1  1000 1
+             + 1 10000 2
+             + 2  1000 3",
+        );
+        let chunk_r1 = StreamChunk::from_pretty(
+            "  I    I  I
+             + 1 2000 10
+             + 1 5000 11
+             + 2 9000 12",
+        );
+        let chunk_r2 = StreamChunk::from_pretty(
+            "  I    I  I
+             + 1 3000 13
+             + 1 9000 14",
+        );
+        let (mut tx_l, mut tx_r, mut interval_join) = create_executor();
+
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        interval_join.next().await.unwrap().unwrap();
+
+        // No output as the right side is empty.
+        tx_l.push_chunk(chunk_l);
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        interval_join
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+
+        // Only the rows of the same key within the interval are matched.
+        tx_r.push_chunk(chunk_r1);
+        let chunk = interval_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I    I I I    I  I
+                + 1 1000 1 1 2000 10"
+            )
+        );
+
+        // The right rows earlier than 6ms are cleaned by the left watermark, and there is no output
+        // watermark until both sides have one.
+        tx_l.push_watermark(1, DataType::Int64, Some(ScalarImpl::Int64(8000)));
+        // The left rows earlier than 4ms are cleaned by the right watermark.
+        tx_r.push_watermark(1, DataType::Int64, Some(ScalarImpl::Int64(6000)));
+        let watermark = interval_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            watermark.as_watermark().unwrap(),
+            &Watermark::new(1, DataType::Int64, Some(ScalarImpl::Int64(4000)))
+        );
+        let watermark = interval_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            watermark.as_watermark().unwrap(),
+            &Watermark::new(4, DataType::Int64, Some(ScalarImpl::Int64(6000)))
+        );
+
+        // The left row at 1ms is cleaned and no longer matched.
+        tx_r.push_chunk(chunk_r2);
+        let chunk = interval_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I     I I I    I  I
+                + 1 10000 2 1 9000 14"
+            )
+        );
+    }
+}
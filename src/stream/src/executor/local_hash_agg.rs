@@ -0,0 +1,371 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::column::Column;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::Schema;
+
+use super::aggregation::agg_impl::{create_streaming_agg_impl, StreamingAggImpl};
+use super::aggregation::{agg_call_filter_res, generate_agg_schema, AggCall};
+use super::error::StreamExecutorError;
+use super::*;
+use crate::error::StreamResult;
+
+/// The partial agg states of the groups changed in the current epoch, in the order of their first
+/// appearances.
+#[derive(Default)]
+struct AggGroups {
+    index: HashMap<Row, usize>,
+    groups: Vec<(Row, Vec<Box<dyn StreamingAggImpl>>)>,
+}
+
+/// [`LocalHashAggExecutor`] is the stateless local phase of a two-phase hash agg. It aggregates the
+/// changes of each group in an epoch and emits the partial results as insertions on barrier, which
+/// are then shuffled by the group keys to the global [`HashAggExecutor`]. Thus, a hot group key
+/// only results in one row per epoch and per actor to be shuffled.
+///
+/// Should only be used for agg calls whose partial results can be merged without any state, i.e.,
+/// `count`, `sum`, and `min`/`max` on append-only input.
+pub struct LocalHashAggExecutor {
+    ctx: ActorContextRef,
+    input: Box<dyn Executor>,
+    info: ExecutorInfo,
+    agg_calls: Vec<AggCall>,
+    group_key_indices: Vec<usize>,
+}
+
+impl Executor for LocalHashAggExecutor {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.info.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.info.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+}
+
+impl LocalHashAggExecutor {
+    fn create_aggregators(
+        agg_calls: &[AggCall],
+    ) -> StreamExecutorResult<Vec<Box<dyn StreamingAggImpl>>> {
+        agg_calls
+            .iter()
+            .map(|agg_call| {
+                create_streaming_agg_impl(
+                    agg_call.args.arg_types(),
+                    &agg_call.kind,
+                    &agg_call.return_type,
+                    None,
+                )
+            })
+            .try_collect()
+    }
+
+    fn apply_chunk(
+        ctx: &ActorContextRef,
+        identity: &str,
+        group_key_indices: &[usize],
+        agg_calls: &[AggCall],
+        agg_groups: &mut AggGroups,
+        chunk: StreamChunk,
+    ) -> StreamExecutorResult<()> {
+        let capacity = chunk.capacity();
+
+        // Find the groups of the visible rows, and generate the visibility map of each group.
+        let mut group_vis_maps: Vec<(usize, Vec<bool>)> = vec![];
+        let mut group_vis_map_index = HashMap::new();
+        for row_idx in 0..capacity {
+            let (row, visible) = chunk.data_chunk().row_at(row_idx);
+            if !visible {
+                continue;
+            }
+            let key = row.row_by_indices(group_key_indices);
+            let group_idx = match agg_groups.index.get(&key) {
+                Some(group_idx) => *group_idx,
+                None => {
+                    let group_idx = agg_groups.groups.len();
+                    let aggregators = Self::create_aggregators(agg_calls)?;
+                    agg_groups.index.insert(key.clone(), group_idx);
+                    agg_groups.groups.push((key, aggregators));
+                    group_idx
+                }
+            };
+            let vis_map_idx = *group_vis_map_index.entry(group_idx).or_insert_with(|| {
+                group_vis_maps.push((group_idx, vec![false; capacity]));
+                group_vis_maps.len() - 1
+            });
+            group_vis_maps[vis_map_idx].1[row_idx] = true;
+        }
+
+        let (ops, columns, visibility) = chunk.into_inner();
+        let visibilities: Vec<_> = agg_calls
+            .iter()
+            .map(|agg_call| {
+                agg_call_filter_res(
+                    ctx,
+                    identity,
+                    agg_call,
+                    &columns,
+                    visibility.as_ref(),
+                    capacity,
+                )
+            })
+            .try_collect()?;
+
+        for (group_idx, vis_map) in group_vis_maps {
+            let vis_map: Bitmap = vis_map.into_iter().collect();
+            let aggregators = &mut agg_groups.groups[group_idx].1;
+            agg_calls
+                .iter()
+                .zip_eq(visibilities.iter())
+                .zip_eq(aggregators.iter_mut())
+                .try_for_each(|((agg_call, visibility), state)| {
+                    let visibility = visibility
+                        .as_ref()
+                        .map_or_else(|| vis_map.clone(), |v| v & &vis_map);
+                    let col_refs = agg_call
+                        .args
+                        .val_indices()
+                        .iter()
+                        .map(|idx| columns[*idx].array_ref())
+                        .collect_vec();
+                    state.apply_batch(&ops, Some(&visibility), &col_refs)
+                })?;
+        }
+        Ok(())
+    }
+
+    fn flush_data(
+        schema: &Schema,
+        agg_groups: &mut AggGroups,
+    ) -> StreamExecutorResult<Option<StreamChunk>> {
+        if agg_groups.groups.is_empty() {
+            return Ok(None);
+        }
+        agg_groups.index.clear();
+        let groups = std::mem::take(&mut agg_groups.groups);
+
+        let mut builders = schema.create_array_builders(groups.len());
+        for (key, aggregators) in &groups {
+            let (key_builders, agg_builders) = builders.split_at_mut(key.size());
+            key_builders
+                .iter_mut()
+                .zip_eq(key.values())
+                .for_each(|(builder, datum)| builder.append_datum(datum));
+            aggregators
+                .iter()
+                .zip_eq(agg_builders.iter_mut())
+                .try_for_each(|(state, builder)| {
+                    let data = state.get_output()?;
+                    builder.append_datum(&data);
+                    Ok::<_, StreamExecutorError>(())
+                })?;
+        }
+        let columns: Vec<Column> = builders
+            .into_iter()
+            .map(|builder| Ok::<_, StreamExecutorError>(builder.finish().into()))
+            .try_collect()?;
+        let ops = vec![Op::Insert; groups.len()];
+
+        Ok(Some(StreamChunk::new(ops, columns, None)))
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(self) {
+        let LocalHashAggExecutor {
+            ctx,
+            input,
+            info,
+            agg_calls,
+            group_key_indices,
+        } = self;
+        let input = input.execute();
+        let mut agg_groups = AggGroups::default();
+
+        #[for_await]
+        for msg in input {
+            let msg = msg?;
+            match msg {
+                Message::Watermark(_) => {}
+
+                Message::Chunk(chunk) => {
+                    Self::apply_chunk(
+                        &ctx,
+                        &info.identity,
+                        &group_key_indices,
+                        &agg_calls,
+                        &mut agg_groups,
+                        chunk,
+                    )?;
+                }
+                m @ Message::Barrier(_) => {
+                    if let Some(chunk) = Self::flush_data(&info.schema, &mut agg_groups)? {
+                        yield Message::Chunk(chunk);
+                    }
+
+                    yield m;
+                }
+            }
+        }
+    }
+}
+
+impl LocalHashAggExecutor {
+    pub fn new(
+        ctx: ActorContextRef,
+        input: Box<dyn Executor>,
+        agg_calls: Vec<AggCall>,
+        group_key_indices: Vec<usize>,
+        pk_indices: PkIndices,
+        executor_id: u64,
+    ) -> StreamResult<Self> {
+        let schema = generate_agg_schema(input.as_ref(), &agg_calls, Some(&group_key_indices));
+        let info = ExecutorInfo {
+            schema,
+            pk_indices,
+            identity: format!("LocalHashAggExecutor-{}", executor_id),
+        };
+
+        Ok(LocalHashAggExecutor {
+            ctx,
+            input,
+            info,
+            agg_calls,
+            group_key_indices,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::schema_test_utils;
+    use risingwave_common::types::DataType;
+    use risingwave_expr::expr::AggKind;
+
+    use super::*;
+    use crate::executor::aggregation::{AggArgs, AggCall};
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::{Executor, LocalHashAggExecutor};
+
+    #[tokio::test]
+    async fn test_local_hash_agg() {
+        let schema = schema_test_utils::iii();
+        let (mut tx, source) = MockSource::channel(schema, vec![2]);
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I    I
+            + 1 10 1001
+            + 2 20 1002
+            + 1 30 1003",
+        ));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  I    I
+            - 1 10 1001
+            - 2 20 1002 D
+            + 3  7 1004",
+        ));
+        tx.push_barrier(3, false);
+        tx.push_barrier(4, false);
+
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only: false,
+                filter: None,
+                distinct: false,
+            },
+            AggCall {
+                kind: AggKind::Sum,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only: false,
+                filter: None,
+                distinct: false,
+            },
+        ];
+
+        let hash_agg = Box::new(
+            LocalHashAggExecutor::new(
+                ActorContext::create(123),
+                Box::new(source),
+                agg_calls,
+                vec![0],
+                vec![],
+                1,
+            )
+            .unwrap(),
+        );
+        let mut hash_agg = hash_agg.execute();
+
+        // Consume the init barrier
+        hash_agg.next().await.unwrap().unwrap();
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I  I
+                + 1 2 40
+                + 2 1 20"
+            )
+        );
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // The partial results of the changes in the epoch are emitted as insertions.
+        let msg = hash_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I  I   I
+                + 1 -1 -10
+                + 3  1   7"
+            )
+        );
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // Nothing is emitted for an epoch without changes.
+        assert_matches!(
+            hash_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+    }
+}
@@ -97,9 +97,8 @@ pub async fn poll_until_barrier(stream: impl MessageStream, expected_barrier: Ba
     #[for_await]
     for item in stream {
         match item? {
-            Message::Watermark(_) => {
-                todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-            }
+            // Lookup join doesn't support watermarks yet, so they are dropped here.
+            Message::Watermark(_) => {}
             c @ Message::Chunk(_) => yield c,
             Message::Barrier(b) => {
                 if b.epoch != expected_barrier.epoch {
@@ -152,12 +151,9 @@ pub async fn align_barrier(left: impl MessageStream, right: impl MessageStream)
                     yield Either::Right(Message::Barrier(b.clone()));
                     break 'inner (SideStatus::RightBarrier, b);
                 }
-                Some(Either::Right(Ok(Message::Watermark(_)))) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                }
-                Some(Either::Left(Ok(Message::Watermark(_)))) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                }
+                // Lookup join doesn't support watermarks yet, so they are dropped here.
+                Some(Either::Left(Ok(Message::Watermark(_))))
+                | Some(Either::Right(Ok(Message::Watermark(_)))) => {}
                 Some(Either::Left(Err(e))) | Some(Either::Right(Err(e))) => return Err(e),
                 None => {
                     break 'outer;
@@ -234,11 +230,8 @@ pub async fn stream_lookup_arrange_prev_epoch(
                         break;
                     }
                 }
-                Either::Left(Message::Watermark(_)) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                }
-                Either::Right(Message::Watermark(_)) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                Either::Left(Message::Watermark(_)) | Either::Right(Message::Watermark(_)) => {
+                    unreachable!("watermarks are dropped in `align_barrier`")
                 }
             }
         }
@@ -250,7 +243,7 @@ pub async fn stream_lookup_arrange_prev_epoch(
                 .expect("unexpected close of barrier aligner")?
             {
                 Either::Left(Message::Watermark(_)) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                    unreachable!("watermarks are dropped in `align_barrier`")
                 }
                 Either::Left(Message::Chunk(msg)) => yield ArrangeMessage::Stream(msg),
                 Either::Left(Message::Barrier(b)) => {
@@ -318,11 +311,8 @@ pub async fn stream_lookup_arrange_this_epoch(
                     }
                     break 'inner Status::ArrangeReady;
                 }
-                Either::Left(Message::Watermark(_)) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                }
-                Either::Right(Message::Watermark(_)) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                Either::Left(Message::Watermark(_)) | Either::Right(Message::Watermark(_)) => {
+                    unreachable!("watermarks are dropped in `align_barrier`")
                 }
             }
         };
@@ -340,11 +330,8 @@ pub async fn stream_lookup_arrange_this_epoch(
                         yield ArrangeMessage::Barrier(b);
                         break;
                     }
-                    Either::Left(Message::Watermark(_)) => {
-                        todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                    }
-                    Either::Right(Message::Watermark(_)) => {
-                        todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                    Either::Left(Message::Watermark(_)) | Either::Right(Message::Watermark(_)) => {
+                        unreachable!("watermarks are dropped in `align_barrier`")
                     }
                     Either::Right(_) => unreachable!(),
                 }
@@ -373,7 +360,7 @@ pub async fn stream_lookup_arrange_this_epoch(
                         break;
                     }
                     Either::Right(Message::Watermark(_)) => {
-                        todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                        unreachable!("watermarks are dropped in `align_barrier`")
                     }
                 }
             },
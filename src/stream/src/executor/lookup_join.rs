@@ -0,0 +1,574 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Op, Row};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::DataType;
+use risingwave_expr::expr::BoxedExpression;
+use risingwave_hummock_sdk::HummockReadEpoch;
+use risingwave_pb::plan_common::JoinType;
+use risingwave_storage::table::batch_table::storage_table::StorageTable;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::table::TableIter;
+use risingwave_storage::StateStore;
+
+use super::barrier_align::*;
+use super::error::{StreamExecutorError, StreamExecutorResult};
+use super::monitor::StreamingMetrics;
+use super::{
+    ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef,
+};
+use crate::cache::{EvictableHashMap, ExecutorCache, LruManagerRef};
+use crate::common::{InfallibleExpression, StreamChunkBuilder};
+use crate::executor::expect_first_barrier_from_aligned_stream;
+
+/// [`LookupJoinExecutor`] joins an append-only left stream with a table, whose rows are looked up
+/// in its storage by a prefix of its order key instead of being kept in the join state. Only the
+/// left rows are kept in the state table, which are joined with the changes of the table from the
+/// right input.
+///
+/// The table is read at the epoch of the last barrier, with the changes of the current epoch from
+/// the right input applied on top of it. So each row is joined with the same rows of the other side
+/// as in the hash join.
+pub struct LookupJoinExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+    left: Option<BoxedExecutor>,
+    right: Option<BoxedExecutor>,
+
+    /// The table to look up, whose output columns are the same as the right input.
+    right_table: StorageTable<S>,
+    /// The rows of the left side, whose primary key is the left join key followed by the stream
+    /// key of the left input.
+    left_table: StateTable<S>,
+
+    /// Join keys of the left side, in the order of the order key prefix of the right table.
+    left_key_indices: Vec<usize>,
+    /// Join keys of the right side, which are the order key prefix of the right table.
+    right_key_indices: Vec<usize>,
+    null_safe: Vec<bool>,
+
+    join_type: JoinType,
+    condition: Option<BoxedExpression>,
+
+    /// The committed rows of the right table at the epoch of the last barrier, keyed by the order
+    /// key prefix.
+    cache: ExecutorCache<Row, Vec<Row>>,
+    /// The changes of the right table in the current epoch, keyed by the order key prefix.
+    right_changes: HashMap<Row, Vec<(Op, Row)>>,
+
+    output_data_types: Vec<DataType>,
+    left_to_output: Vec<(usize, usize)>,
+    right_to_output: Vec<(usize, usize)>,
+
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    metrics: Arc<StreamingMetrics>,
+    /// The maximum size of the chunk produced by executor at a time.
+    chunk_size: usize,
+}
+
+impl<S: StateStore> LookupJoinExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        left: BoxedExecutor,
+        right: BoxedExecutor,
+        right_table: StorageTable<S>,
+        left_table: StateTable<S>,
+        left_key_indices: Vec<usize>,
+        right_key_indices: Vec<usize>,
+        null_safe: Vec<bool>,
+        join_type: JoinType,
+        condition: Option<BoxedExpression>,
+        output_indices: Vec<usize>,
+        schema: Schema,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        lru_manager: Option<LruManagerRef>,
+        cache_size: usize,
+        metrics: Arc<StreamingMetrics>,
+        chunk_size: usize,
+    ) -> Self {
+        assert!(matches!(join_type, JoinType::Inner | JoinType::LeftOuter));
+        assert_eq!(left_key_indices.len(), right_key_indices.len());
+
+        let (left_to_output, right_to_output) = StreamChunkBuilder::get_i2o_mapping(
+            output_indices.iter().cloned(),
+            left.schema().len(),
+            right.schema().len(),
+        );
+
+        let cache = if let Some(lru_manager) = lru_manager {
+            ExecutorCache::Managed(lru_manager.create_cache())
+        } else {
+            ExecutorCache::Local(EvictableHashMap::new(cache_size))
+        };
+
+        Self {
+            ctx,
+            left: Some(left),
+            right: Some(right),
+            right_table,
+            left_table,
+            left_key_indices,
+            right_key_indices,
+            null_safe,
+            join_type,
+            condition,
+            cache,
+            right_changes: HashMap::new(),
+            output_data_types: schema.data_types(),
+            left_to_output,
+            right_to_output,
+            schema,
+            pk_indices,
+            identity: format!("LookupJoinExecutor {:X}", executor_id),
+            metrics,
+            chunk_size,
+        }
+    }
+
+    /// Whether the key never matches any row, as it contains null on a column that is not
+    /// null-safe.
+    fn never_matches(&self, key: &Row) -> bool {
+        key.values()
+            .zip_eq(&self.null_safe)
+            .any(|(datum, &null_safe)| datum.is_none() && !null_safe)
+    }
+
+    /// Looks up the committed rows of the right table by the key at the given epoch, or from the
+    /// cache if present.
+    async fn lookup_committed(&mut self, key: &Row, epoch: u64) -> StreamExecutorResult<Vec<Row>> {
+        if let Some(rows) = self.cache.get(key) {
+            return Ok(rows.clone());
+        }
+
+        let mut rows = vec![];
+        {
+            let iter = self
+                .right_table
+                .batch_iter_with_pk_bounds(HummockReadEpoch::NoWait(epoch), key, .., None)
+                .await?;
+            pin_mut!(iter);
+            while let Some(row) = iter.next_row().await? {
+                rows.push(row);
+            }
+        }
+
+        self.cache.push(key.clone(), rows.clone());
+        Ok(rows)
+    }
+
+    /// Looks up the current rows of the right table by the key, which are the committed ones with
+    /// the changes of the current epoch applied.
+    async fn lookup(&mut self, key: &Row, epoch: u64) -> StreamExecutorResult<Vec<Row>> {
+        let mut rows = self.lookup_committed(key, epoch).await?;
+        for (op, row) in self.right_changes.get(key).into_iter().flatten() {
+            match op {
+                Op::Insert | Op::UpdateInsert => rows.push(row.clone()),
+                Op::Delete | Op::UpdateDelete => {
+                    if let Some(i) = rows.iter().position(|r| r == row) {
+                        rows.swap_remove(i);
+                    }
+                }
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Returns the left rows of the key in the state table.
+    async fn left_rows(&self, key: &Row) -> StreamExecutorResult<Vec<Row>> {
+        let iter = self.left_table.iter_with_pk_prefix(key).await?;
+        pin_mut!(iter);
+        let mut rows = vec![];
+        while let Some(row) = iter.next().await {
+            rows.push(row?.into_owned());
+        }
+        Ok(rows)
+    }
+
+    fn check_condition(&self, left_row: &Row, right_row: &Row) -> bool {
+        let Some(condition) = &self.condition else {
+            return true;
+        };
+        let row = Row(left_row
+            .values()
+            .chain(right_row.values())
+            .cloned()
+            .collect());
+        condition
+            .eval_row_infallible(&row, |err| self.ctx.on_compute_error(err, &self.identity))
+            .map(|s| *s.as_bool())
+            .unwrap_or(false)
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn into_stream(mut self) {
+        let left = self.left.take().unwrap();
+        let right = self.right.take().unwrap();
+        let aligned_stream = barrier_align(
+            left.execute(),
+            right.execute(),
+            self.ctx.id,
+            self.metrics.clone(),
+        );
+        pin_mut!(aligned_stream);
+
+        let barrier = expect_first_barrier_from_aligned_stream(&mut aligned_stream).await?;
+        self.left_table.init_epoch(barrier.epoch);
+        // The right table is read at the epoch of the last barrier, whose data is always complete
+        // in the storage.
+        let mut read_epoch = barrier.epoch.prev;
+        self.cache.update_epoch(barrier.epoch.curr);
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in aligned_stream {
+            match msg? {
+                AlignedMessage::Left(chunk) => {
+                    let mut builder = StreamChunkBuilder::new(
+                        self.chunk_size,
+                        &self.output_data_types,
+                        self.left_to_output.clone(),
+                        self.right_to_output.clone(),
+                    )?;
+
+                    for (op, left_row) in chunk.rows() {
+                        let op = match op {
+                            Op::Insert | Op::UpdateInsert => Op::Insert,
+                            Op::Delete | Op::UpdateDelete => Op::Delete,
+                        };
+                        let key = left_row.row_by_indices(&self.left_key_indices);
+                        let left_owned_row = left_row.to_owned_row();
+
+                        // The rows never matching any right row are not kept in the state.
+                        let right_rows = if self.never_matches(&key) {
+                            vec![]
+                        } else {
+                            match op {
+                                Op::Insert => self.left_table.insert(left_owned_row.clone()),
+                                _ => self.left_table.delete(left_owned_row.clone()),
+                            }
+                            self.lookup(&key, read_epoch).await?
+                        };
+
+                        let mut matched = false;
+                        for right_row in right_rows {
+                            if self.check_condition(&left_owned_row, &right_row) {
+                                matched = true;
+                                if let Some(chunk) =
+                                    builder.append_row(op, &left_row, &right_row)?
+                                {
+                                    yield Message::Chunk(chunk);
+                                }
+                            }
+                        }
+                        if !matched && self.join_type == JoinType::LeftOuter {
+                            if let Some(chunk) = builder.append_row_update(op, &left_row)? {
+                                yield Message::Chunk(chunk);
+                            }
+                        }
+                    }
+
+                    if let Some(chunk) = builder.take()? && chunk.cardinality() > 0 {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                AlignedMessage::Right(chunk) => {
+                    let mut builder = StreamChunkBuilder::new(
+                        self.chunk_size,
+                        &self.output_data_types,
+                        self.right_to_output.clone(),
+                        self.left_to_output.clone(),
+                    )?;
+
+                    for (op, right_row) in chunk.rows() {
+                        let op = match op {
+                            Op::Insert | Op::UpdateInsert => Op::Insert,
+                            Op::Delete | Op::UpdateDelete => Op::Delete,
+                        };
+                        let key = right_row.row_by_indices(&self.right_key_indices);
+                        if self.never_matches(&key) {
+                            continue;
+                        }
+                        let right_owned_row = right_row.to_owned_row();
+
+                        // For the left outer join, the right rows before the change decide whether
+                        // the null-padded rows of the left rows should be retracted or restored.
+                        let right_rows_before = if self.join_type == JoinType::LeftOuter {
+                            self.lookup(&key, read_epoch).await?
+                        } else {
+                            vec![]
+                        };
+                        self.right_changes
+                            .entry(key.clone())
+                            .or_default()
+                            .push((op, right_owned_row.clone()));
+
+                        for left_row in self.left_rows(&key).await? {
+                            if !self.check_condition(&left_row, &right_owned_row) {
+                                continue;
+                            }
+                            // The number of the matched right rows before the change, which is
+                            // only needed by the left outer join.
+                            let degree = (self.join_type == JoinType::LeftOuter).then(|| {
+                                right_rows_before
+                                    .iter()
+                                    .filter(|r| self.check_condition(&left_row, r))
+                                    .count()
+                            });
+
+                            if op == Op::Insert {
+                                if degree == Some(0) {
+                                    if let Some(chunk) =
+                                        builder.append_row_matched(Op::Delete, &left_row)?
+                                    {
+                                        yield Message::Chunk(chunk);
+                                    }
+                                }
+                                if let Some(chunk) =
+                                    builder.append_row(Op::Insert, &right_row, &left_row)?
+                                {
+                                    yield Message::Chunk(chunk);
+                                }
+                            } else {
+                                if let Some(chunk) =
+                                    builder.append_row(Op::Delete, &right_row, &left_row)?
+                                {
+                                    yield Message::Chunk(chunk);
+                                }
+                                if degree == Some(1) {
+                                    if let Some(chunk) =
+                                        builder.append_row_matched(Op::Insert, &left_row)?
+                                    {
+                                        yield Message::Chunk(chunk);
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(chunk) = builder.take()? && chunk.cardinality() > 0 {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                // The output rows may be produced by the changes of the right table with any old
+                // left rows, so the watermarks of both sides can't be propagated.
+                AlignedMessage::WatermarkLeft(_) | AlignedMessage::WatermarkRight(_) => {}
+                AlignedMessage::Barrier(barrier) => {
+                    self.left_table.commit(barrier.epoch).await?;
+
+                    // The changes of the current epoch are committed to the right table, so the
+                    // cached rows of the updated keys become stale.
+                    for (key, _) in self.right_changes.drain() {
+                        self.cache.pop(&key);
+                    }
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        let _ = self.left_table.update_vnode_bitmap(vnode_bitmap.clone());
+                        let _ = self.right_table.update_vnode_bitmap(vnode_bitmap);
+                        self.cache.clear();
+                    }
+                    self.cache.evict();
+                    self.cache.update_epoch(barrier.epoch.curr);
+                    read_epoch = barrier.epoch.prev;
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for LookupJoinExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.into_stream().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, TableId};
+    use risingwave_common::util::epoch::EpochPair;
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::ActorContext;
+
+    #[tokio::test]
+    async fn test_lookup_left_outer_join() {
+        let store = MemoryStateStore::new();
+        let column_descs = vec![
+            ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+            ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),
+        ];
+
+        // The right table `(k, v)` with the primary key `k`.
+        let right_table_id = TableId::new(0x42);
+        let mut right_state_table = StateTable::new_without_distribution(
+            store.clone(),
+            right_table_id,
+            column_descs.clone(),
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+        let right_table = StorageTable::for_test(
+            store.clone(),
+            right_table_id,
+            column_descs.clone(),
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+        // The left rows `(k, id)`, keyed by the join key and the stream key `id`.
+        let left_table = StateTable::new_without_distribution(
+            store,
+            TableId::new(0x43),
+            column_descs,
+            vec![OrderType::Ascending, OrderType::Ascending],
+            vec![0, 1],
+        );
+
+        // The table contains `(1, 10)` at epoch 1.
+        right_state_table.init_epoch(EpochPair::new_test_epoch(1));
+        right_state_table.insert(Row(vec![Some(1_i64.into()), Some(10_i64.into())]));
+        right_state_table
+            .commit_for_test(EpochPair::new_test_epoch(2))
+            .await
+            .unwrap();
+
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![1]);
+        let (mut tx_r, source_r) = MockSource::channel(schema, vec![0]);
+        let output_schema = Schema::new(vec![Field::unnamed(DataType::Int64); 4]);
+        let mut lookup_join = Box::new(LookupJoinExecutor::new(
+            ActorContext::create(123),
+            Box::new(source_l),
+            Box::new(source_r),
+            right_table,
+            left_table,
+            vec![0],
+            vec![0],
+            vec![false],
+            JoinType::LeftOuter,
+            None,
+            vec![0, 1, 2, 3],
+            output_schema,
+            vec![1, 2],
+            1,
+            None,
+            1024,
+            Arc::new(StreamingMetrics::unused()),
+            1024,
+        ))
+        .execute();
+
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        lookup_join.next().await.unwrap().unwrap();
+
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             + 1 1
+             + 2 2",
+        ));
+        let chunk = lookup_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 1 1 1 10
+                + 2 2 . ."
+            )
+        );
+
+        // The inserted right row retracts the null-padded row of the matched left row.
+        right_state_table.insert(Row(vec![Some(2_i64.into()), Some(20_i64.into())]));
+        right_state_table
+            .commit_for_test(EpochPair::new_test_epoch(3))
+            .await
+            .unwrap();
+        tx_r.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             + 2 20",
+        ));
+        let chunk = lookup_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                - 2 2 . .
+                + 2 2 2 20"
+            )
+        );
+
+        // The change of the current epoch is visible to the left rows before the next barrier.
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             + 2 3",
+        ));
+        let chunk = lookup_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 2 3 2 20"
+            )
+        );
+
+        tx_l.push_barrier(3, false);
+        tx_r.push_barrier(3, false);
+        lookup_join.next().await.unwrap().unwrap();
+
+        // The deleted right row restores the null-padded rows of all the matched left rows.
+        tx_r.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             - 2 20",
+        ));
+        let chunk = lookup_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                - 2 2 2 20
+                + 2 2 . .
+                - 2 3 2 20
+                + 2 3 . ."
+            )
+        );
+    }
+}
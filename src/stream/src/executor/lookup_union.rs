@@ -113,7 +113,7 @@ impl LookupUnionExecutor {
                     end = false;
                     match msg {
                         Message::Watermark(_) => {
-                            todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                            // Inputs may have different watermarks, which are not merged yet.
                         }
 
                         msg @ Message::Chunk(_) => yield msg,
@@ -538,6 +538,36 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
         self.state.table.delete(value);
     }
 
+    /// Remove the cached entries whose join key at `key_idx` is below the watermark, and delete
+    /// their rows from the state tables, as they will never be matched or updated again.
+    ///
+    /// TODO: entries not in the cache are still kept in the storage until we support range delete.
+    pub fn clean_below_watermark(
+        &mut self,
+        key_idx: usize,
+        watermark: &ScalarImpl,
+    ) -> StreamExecutorResult<()> {
+        let mut expired_keys = vec![];
+        for (key, _) in self.inner.iter() {
+            let key_row = key.clone().deserialize(&self.join_key_data_types)?;
+            if let Some(val) = &key_row[key_idx] && val < watermark {
+                expired_keys.push(key.clone());
+            }
+        }
+
+        for key in expired_keys {
+            let mut entry = self.inner.pop(&key).unwrap();
+            for (_, join_row) in entry.values_mut(&self.state.all_data_types) {
+                let (row, degree) = join_row?.into_table_rows(&self.state.order_key_indices);
+                self.state.table.delete(row);
+                if self.need_degree_table {
+                    self.degree_state.table.delete(degree);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Insert a [`JoinEntryState`]
     pub fn insert_state(&mut self, key: &K, state: JoinEntryState) {
         self.inner.put(key.clone(), state);
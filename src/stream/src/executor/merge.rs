@@ -415,7 +415,7 @@ mod tests {
     use futures::FutureExt;
     use itertools::Itertools;
     use risingwave_common::array::{Op, StreamChunk};
-    use risingwave_common::types::ScalarImpl;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_pb::stream_plan::StreamMessage;
     use risingwave_pb::task_service::exchange_service_server::{
         ExchangeService, ExchangeServiceServer,
@@ -465,6 +465,7 @@ mod tests {
                     } else {
                         tx.send(Message::Watermark(Watermark {
                             col_idx: (epoch as usize / 20 + tx_id) % CHANNEL_NUMBER,
+                            data_type: DataType::Int64,
                             val: Some(ScalarImpl::Int64(epoch as i64)),
                         }))
                         .await
@@ -25,20 +25,22 @@ use minitrace::prelude::*;
 use risingwave_common::array::column::Column;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::buffer::Bitmap;
-use risingwave_common::catalog::Schema;
+use risingwave_common::catalog::{Schema, TableId};
 use risingwave_common::types::{DataType, Datum};
 use risingwave_common::util::epoch::EpochPair;
 use risingwave_common::util::value_encoding::{deserialize_datum, serialize_datum_to_bytes};
-use risingwave_connector::source::SplitImpl;
+use risingwave_connector::source::{SourceRateLimit, SplitImpl};
 use risingwave_pb::data::{Datum as ProstDatum, Epoch as ProstEpoch};
+use risingwave_pb::plan_common::ColumnCatalog as ProstColumnCatalog;
 use risingwave_pb::stream_plan::add_mutation::Dispatchers;
 use risingwave_pb::stream_plan::barrier::Mutation as ProstMutation;
 use risingwave_pb::stream_plan::stream_message::StreamMessage;
 use risingwave_pb::stream_plan::update_mutation::{DispatcherUpdate, MergeUpdate};
 use risingwave_pb::stream_plan::{
-    AddMutation, Barrier as ProstBarrier, Dispatcher as ProstDispatcher, PauseMutation,
-    ResumeMutation, SourceChangeSplitMutation, StopMutation, StreamMessage as ProstStreamMessage,
-    UpdateMutation, Watermark as ProstWatermark,
+    AddMutation, BackfillRateLimitMutation, Barrier as ProstBarrier, Dispatcher as ProstDispatcher,
+    MviewSchemaChange as ProstMviewSchemaChange, PauseMutation, ResumeMutation,
+    SchemaChangeMutation, SourceChangeSplitMutation, SourceRateLimitMutation, StopMutation,
+    StreamMessage as ProstStreamMessage, UpdateMutation, Watermark as ProstWatermark,
 };
 use smallvec::SmallVec;
 
@@ -51,6 +53,7 @@ pub mod exchange;
 pub mod monitor;
 
 pub mod aggregation;
+mod backfill;
 mod batch_query;
 mod chain;
 mod dispatch;
@@ -62,21 +65,29 @@ mod global_simple_agg;
 mod hash_agg;
 pub mod hash_join;
 mod hop_window;
+mod interval_join;
+mod local_hash_agg;
 mod local_simple_agg;
 mod lookup;
+mod lookup_join;
 mod lookup_union;
 mod managed_state;
 mod merge;
 mod mview;
+mod now;
+mod over_window;
 mod project;
 mod project_set;
+mod rate_limiter;
 mod rearranged_chain;
 mod receiver;
+mod session_window;
 mod simple;
 mod sink;
 mod sort;
 pub mod source;
 pub mod subtask;
+mod temporal_join;
 mod top_n;
 mod union;
 mod wrapper;
@@ -88,6 +99,7 @@ mod test_utils;
 
 pub use actor::{Actor, ActorContext, ActorContextRef};
 use anyhow::Context;
+pub use backfill::BackfillExecutor;
 pub use batch_query::BatchQueryExecutor;
 pub use chain::ChainExecutor;
 pub use dispatch::{DispatchExecutor, DispatcherImpl};
@@ -99,22 +111,31 @@ pub use global_simple_agg::GlobalSimpleAggExecutor;
 pub use hash_agg::HashAggExecutor;
 pub use hash_join::*;
 pub use hop_window::HopWindowExecutor;
+pub use interval_join::IntervalJoinExecutor;
+pub use local_hash_agg::LocalHashAggExecutor;
 pub use local_simple_agg::LocalSimpleAggExecutor;
 pub use lookup::*;
+pub use lookup_join::LookupJoinExecutor;
 pub use lookup_union::LookupUnionExecutor;
 pub use managed_state::join::JoinManagedCache;
 pub use merge::MergeExecutor;
 pub use mview::*;
+pub use now::NowExecutor;
+pub use over_window::OverWindowExecutor;
 pub use project::ProjectExecutor;
 pub use project_set::*;
 pub use rearranged_chain::RearrangedChainExecutor;
 pub use receiver::ReceiverExecutor;
 use risingwave_pb::source::{ConnectorSplit, ConnectorSplits};
+pub use session_window::SessionWindowExecutor;
 use simple::{SimpleExecutor, SimpleExecutorWrapper};
 pub use sink::SinkExecutor;
 pub use sort::SortExecutor;
 pub use source::*;
-pub use top_n::{AppendOnlyTopNExecutor, GroupTopNExecutor, TopNExecutor};
+pub use temporal_join::TemporalJoinExecutor;
+pub use top_n::{
+    AppendOnlyGroupTopNExecutor, AppendOnlyTopNExecutor, GroupTopNExecutor, TopNExecutor,
+};
 pub use union::UnionExecutor;
 pub use wrapper::WrapperExecutor;
 
@@ -205,8 +226,33 @@ pub enum Mutation {
         splits: HashMap<ActorId, Vec<SplitImpl>>,
     },
     SourceChangeSplit(HashMap<ActorId, Vec<SplitImpl>>),
+    /// Reset the splits of the sources to the given states, which overwrite the persisted ones.
+    /// Used for rewinding or fast-forwarding the offsets of sources.
+    SourceResetSplit(HashMap<ActorId, Vec<SplitImpl>>),
     Pause,
     Resume,
+    /// Change the columns of the table source with `source_id` and its materialized table with
+    /// `table_id`. Columns are only appended or hidden by the change. The appended columns are
+    /// also added to the materialized views in `mview_changes`. For a connector source, `table_id`
+    /// is zero and only the source actors of the views read the appended columns.
+    SchemaChange {
+        source_id: TableId,
+        table_id: TableId,
+        source_columns: Vec<ProstColumnCatalog>,
+        table_columns: Vec<ProstColumnCatalog>,
+        mview_changes: Vec<ProstMviewSchemaChange>,
+    },
+    /// Change the rate limits of the source with `source_id`.
+    SourceRateLimit {
+        source_id: TableId,
+        rate_limit: SourceRateLimit,
+    },
+    /// Change the maximum rows per second read from the snapshot by the chain actors. Zero means
+    /// unlimited.
+    BackfillRateLimit {
+        actor_ids: HashSet<ActorId>,
+        rows_per_second: u64,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -307,6 +353,60 @@ impl Barrier {
                 _ => None,
             })
     }
+
+    /// Returns the columns after the change if this barrier is to change the schema of the
+    /// materialized table with `table_id`, which is either the table itself or one of the
+    /// materialized views updated with it.
+    pub fn as_schema_change(&self, table_id: TableId) -> Option<&[ProstColumnCatalog]> {
+        self.mutation
+            .as_deref()
+            .and_then(|mutation| match mutation {
+                Mutation::SchemaChange {
+                    table_id: id,
+                    table_columns,
+                    ..
+                } if *id == table_id => Some(table_columns.as_slice()),
+                Mutation::SchemaChange { mview_changes, .. } => mview_changes
+                    .iter()
+                    .find(|change| change.table_id == table_id.table_id())
+                    .map(|change| change.columns.as_slice()),
+                _ => None,
+            })
+    }
+
+    /// Returns the new upstream column indices if this barrier is to change the schema of the
+    /// materialized view containing the chain actor with `actor_id`.
+    pub fn as_chain_schema_change(&self, actor_id: ActorId) -> Option<Vec<usize>> {
+        self.mutation
+            .as_deref()
+            .and_then(|mutation| match mutation {
+                Mutation::SchemaChange { mview_changes, .. } => mview_changes
+                    .iter()
+                    .find(|change| change.chain_actor_ids.contains(&actor_id)),
+                _ => None,
+            })
+            .map(|change| {
+                change
+                    .upstream_column_indices
+                    .iter()
+                    .map(|&i| i as usize)
+                    .collect()
+            })
+    }
+
+    /// Returns the new rate limit if this barrier is to change the backfill rate limit of the
+    /// chain actor with `actor_id`.
+    pub fn as_backfill_rate_limit(&self, actor_id: ActorId) -> Option<u64> {
+        self.mutation
+            .as_deref()
+            .and_then(|mutation| match mutation {
+                Mutation::BackfillRateLimit {
+                    actor_ids,
+                    rows_per_second,
+                } if actor_ids.contains(&actor_id) => Some(*rows_per_second),
+                _ => None,
+            })
+    }
 }
 
 impl PartialEq for Barrier {
@@ -370,26 +470,41 @@ impl Mutation {
                 ..Default::default()
             }),
             Mutation::SourceChangeSplit(changes) => {
-                ProstMutation::Splits(SourceChangeSplitMutation {
-                    actor_splits: changes
-                        .iter()
-                        .map(|(&actor_id, splits)| {
-                            (
-                                actor_id,
-                                ConnectorSplits {
-                                    splits: splits
-                                        .clone()
-                                        .iter()
-                                        .map(ConnectorSplit::from)
-                                        .collect(),
-                                },
-                            )
-                        })
-                        .collect(),
-                })
+                ProstMutation::Splits(Self::splits_to_protobuf(changes))
+            }
+            Mutation::SourceResetSplit(changes) => {
+                ProstMutation::ResetSplits(Self::splits_to_protobuf(changes))
             }
             Mutation::Pause => ProstMutation::Pause(PauseMutation {}),
             Mutation::Resume => ProstMutation::Resume(ResumeMutation {}),
+            Mutation::SchemaChange {
+                source_id,
+                table_id,
+                source_columns,
+                table_columns,
+                mview_changes,
+            } => ProstMutation::SchemaChange(SchemaChangeMutation {
+                source_id: source_id.table_id(),
+                table_id: table_id.table_id(),
+                source_columns: source_columns.clone(),
+                table_columns: table_columns.clone(),
+                mview_changes: mview_changes.clone(),
+            }),
+            Mutation::SourceRateLimit {
+                source_id,
+                rate_limit,
+            } => ProstMutation::SourceRateLimit(SourceRateLimitMutation {
+                source_id: source_id.table_id(),
+                rows_per_second: rate_limit.rows_per_second,
+                bytes_per_second: rate_limit.bytes_per_second,
+            }),
+            Mutation::BackfillRateLimit {
+                actor_ids,
+                rows_per_second,
+            } => ProstMutation::BackfillRateLimit(BackfillRateLimitMutation {
+                actor_ids: actor_ids.iter().copied().collect(),
+                rows_per_second: *rows_per_second,
+            }),
         }
     }
 
@@ -456,28 +571,69 @@ impl Mutation {
                     .collect(),
             },
 
-            ProstMutation::Splits(s) => {
-                let mut change_splits: Vec<(ActorId, Vec<SplitImpl>)> =
-                    Vec::with_capacity(s.actor_splits.len());
-                for (&actor_id, splits) in &s.actor_splits {
-                    if !splits.splits.is_empty() {
-                        change_splits.push((
-                            actor_id,
-                            splits
-                                .splits
-                                .iter()
-                                .map(SplitImpl::try_from)
-                                .try_collect()?,
-                        ));
-                    }
-                }
-                Mutation::SourceChangeSplit(change_splits.into_iter().collect())
+            ProstMutation::Splits(s) => Mutation::SourceChangeSplit(Self::splits_from_protobuf(s)?),
+            ProstMutation::ResetSplits(s) => {
+                Mutation::SourceResetSplit(Self::splits_from_protobuf(s)?)
             }
             ProstMutation::Pause(_) => Mutation::Pause,
             ProstMutation::Resume(_) => Mutation::Resume,
+            ProstMutation::SchemaChange(change) => Mutation::SchemaChange {
+                source_id: TableId::new(change.source_id),
+                table_id: TableId::new(change.table_id),
+                source_columns: change.source_columns.clone(),
+                table_columns: change.table_columns.clone(),
+                mview_changes: change.mview_changes.clone(),
+            },
+            ProstMutation::SourceRateLimit(change) => Mutation::SourceRateLimit {
+                source_id: TableId::new(change.source_id),
+                rate_limit: SourceRateLimit {
+                    rows_per_second: change.rows_per_second,
+                    bytes_per_second: change.bytes_per_second,
+                },
+            },
+            ProstMutation::BackfillRateLimit(change) => Mutation::BackfillRateLimit {
+                actor_ids: change.actor_ids.iter().copied().collect(),
+                rows_per_second: change.rows_per_second,
+            },
         };
         Ok(mutation)
     }
+
+    fn splits_to_protobuf(changes: &HashMap<ActorId, Vec<SplitImpl>>) -> SourceChangeSplitMutation {
+        SourceChangeSplitMutation {
+            actor_splits: changes
+                .iter()
+                .map(|(&actor_id, splits)| {
+                    (
+                        actor_id,
+                        ConnectorSplits {
+                            splits: splits.clone().iter().map(ConnectorSplit::from).collect(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn splits_from_protobuf(
+        s: &SourceChangeSplitMutation,
+    ) -> StreamExecutorResult<HashMap<ActorId, Vec<SplitImpl>>> {
+        let mut change_splits: Vec<(ActorId, Vec<SplitImpl>)> =
+            Vec::with_capacity(s.actor_splits.len());
+        for (&actor_id, splits) in &s.actor_splits {
+            if !splits.splits.is_empty() {
+                change_splits.push((
+                    actor_id,
+                    splits
+                        .splits
+                        .iter()
+                        .map(SplitImpl::try_from)
+                        .try_collect()?,
+                ));
+            }
+        }
+        Ok(change_splits.into_iter().collect())
+    }
 }
 
 impl Barrier {
@@ -520,8 +676,9 @@ impl Barrier {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Watermark {
-    col_idx: usize,
-    val: Datum,
+    pub col_idx: usize,
+    pub data_type: DataType,
+    pub val: Datum,
 }
 
 impl PartialOrd for Watermark {
@@ -545,22 +702,49 @@ impl Ord for Watermark {
 }
 
 impl Watermark {
+    pub fn new(col_idx: usize, data_type: DataType, val: Datum) -> Self {
+        Self {
+            col_idx,
+            data_type,
+            val,
+        }
+    }
+
+    /// Moves the watermark to another column index, e.g. when the column is projected to a
+    /// different position in the output.
+    pub fn with_idx(self, idx: usize) -> Self {
+        Self {
+            col_idx: idx,
+            ..self
+        }
+    }
+
+    /// Transforms the watermark with the given output indices. Returns `None` if the watermark
+    /// column is not in the output.
+    pub fn transform_with_indices(self, output_indices: &[usize]) -> Option<Self> {
+        output_indices
+            .iter()
+            .position(|p| *p == self.col_idx)
+            .map(|new_col_idx| self.with_idx(new_col_idx))
+    }
+
     pub fn to_protobuf(&self) -> ProstWatermark {
         ProstWatermark {
             col_idx: self.col_idx as _,
             val: Some(ProstDatum {
                 body: serialize_datum_to_bytes(self.val.as_ref()),
             }),
+            data_type: Some(self.data_type.to_protobuf()),
         }
     }
 
-    pub fn from_protobuf(
-        prost: &ProstWatermark,
-        data_type: &DataType,
-    ) -> StreamExecutorResult<Self> {
+    pub fn from_protobuf(prost: &ProstWatermark) -> StreamExecutorResult<Self> {
+        let data_type = DataType::from(prost.get_data_type()?);
+        let val = deserialize_datum(&*prost.get_val()?.body, &data_type)?;
         Ok(Watermark {
             col_idx: prost.col_idx as _,
-            val: deserialize_datum(&*prost.get_val()?.body, data_type)?,
+            data_type,
+            val,
         })
     }
 }
@@ -607,7 +791,7 @@ impl Message {
                 StreamMessage::StreamChunk(prost_stream_chunk)
             }
             Self::Barrier(barrier) => StreamMessage::Barrier(barrier.clone().to_protobuf()),
-            Self::Watermark(_) => todo!("https://github.com/risingwavelabs/risingwave/issues/6042"),
+            Self::Watermark(watermark) => StreamMessage::Watermark(watermark.to_protobuf()),
         };
         ProstStreamMessage {
             stream_message: Some(prost),
@@ -622,6 +806,9 @@ impl Message {
             StreamMessage::Barrier(ref barrier) => {
                 Message::Barrier(Barrier::from_protobuf(barrier)?)
             }
+            StreamMessage::Watermark(ref watermark) => {
+                Message::Watermark(Watermark::from_protobuf(watermark)?)
+            }
         };
         Ok(res)
     }
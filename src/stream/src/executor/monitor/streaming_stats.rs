@@ -16,8 +16,9 @@ use prometheus::core::{AtomicF64, AtomicI64, AtomicU64, GenericCounterVec, Gener
 use prometheus::{
     exponential_buckets, histogram_opts, register_gauge_vec_with_registry,
     register_histogram_vec_with_registry, register_histogram_with_registry,
-    register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, Histogram,
-    HistogramVec, Registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Histogram,
+    HistogramVec, IntCounter, IntGauge, Registry,
 };
 
 pub struct StreamingMetrics {
@@ -63,8 +64,16 @@ pub struct StreamingMetrics {
     pub barrier_inflight_latency: Histogram,
     /// The duration of sync to storage.
     pub barrier_sync_latency: Histogram,
+    /// The number of barriers issued to this node but not yet collected from each actor.
+    pub actor_in_flight_barrier_nums: GenericGaugeVec<AtomicI64>,
 
     pub sink_commit_duration: HistogramVec,
+
+    // Memory management
+    pub lru_current_watermark_time_ms: IntGauge,
+    pub lru_physical_now_ms: IntGauge,
+    pub lru_runtime_loop_count: IntCounter,
+    pub jemalloc_allocated_bytes: IntGauge,
 }
 
 impl StreamingMetrics {
@@ -323,6 +332,15 @@ impl StreamingMetrics {
             exponential_buckets(0.1, 1.5, 16).unwrap() // max 43s
         );
         let barrier_sync_latency = register_histogram_with_registry!(opts, registry).unwrap();
+
+        let actor_in_flight_barrier_nums = register_int_gauge_vec_with_registry!(
+            "stream_actor_in_flight_barrier_nums",
+            "Number of barriers issued but not yet collected from the actor",
+            &["actor_id"],
+            registry
+        )
+        .unwrap();
+
         let sink_commit_duration = register_histogram_vec_with_registry!(
             "sink_commit_duration",
             "Duration of commit op in sink",
@@ -331,6 +349,34 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let lru_current_watermark_time_ms = register_int_gauge_with_registry!(
+            "lru_current_watermark_time_ms",
+            "Current watermark time (ms) of the managed caches",
+            registry
+        )
+        .unwrap();
+
+        let lru_physical_now_ms = register_int_gauge_with_registry!(
+            "lru_physical_now_ms",
+            "Current physical time (ms) of the memory manager",
+            registry
+        )
+        .unwrap();
+
+        let lru_runtime_loop_count = register_int_counter_with_registry!(
+            "lru_runtime_loop_count",
+            "Number of iterations of the memory control loop",
+            registry
+        )
+        .unwrap();
+
+        let jemalloc_allocated_bytes = register_int_gauge_with_registry!(
+            "jemalloc_allocated_bytes",
+            "Allocated memory reported by jemalloc",
+            registry
+        )
+        .unwrap();
+
         Self {
             registry,
             executor_row_count,
@@ -365,7 +411,12 @@ impl StreamingMetrics {
             agg_cached_keys,
             barrier_inflight_latency,
             barrier_sync_latency,
+            actor_in_flight_barrier_nums,
             sink_commit_duration,
+            lru_current_watermark_time_ms,
+            lru_physical_now_ms,
+            lru_runtime_loop_count,
+            jemalloc_allocated_bytes,
         }
     }
 
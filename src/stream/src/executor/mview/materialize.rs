@@ -133,9 +133,7 @@ impl<S: StateStore> MaterializeExecutor<S> {
         for msg in input {
             let msg = msg?;
             yield match msg {
-                Message::Watermark(_) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                }
+                Message::Watermark(w) => Message::Watermark(w),
                 Message::Chunk(chunk) => {
                     self.state_table.write_chunk(chunk.clone());
                     Message::Chunk(chunk)
@@ -148,6 +146,16 @@ impl<S: StateStore> MaterializeExecutor<S> {
                         let _ = self.state_table.update_vnode_bitmap(vnode_bitmap);
                     }
 
+                    // Update the columns of the state table if the schema is changed. The chunks
+                    // after this barrier will contain the appended columns.
+                    if let Some(columns) = b.as_schema_change(self.state_table.table_id()) {
+                        let columns = columns
+                            .iter()
+                            .map(|c| ColumnDesc::from(c.get_column_desc().unwrap()))
+                            .collect_vec();
+                        self.state_table.update_columns(&columns)?;
+                    }
+
                     Message::Barrier(b)
                 }
             }
@@ -0,0 +1,223 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures::StreamExt;
+use futures_async_stream::try_stream;
+use risingwave_common::array::{DataChunk, Op, Row, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_common::util::epoch::Epoch;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::StateStore;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use super::{
+    Barrier, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef, StreamExecutorError,
+    Watermark,
+};
+
+/// [`NowExecutor`] emits the current time as a single-row stream. On every barrier, the time of the
+/// previous epoch is retracted and replaced by the time of the new one, so that the downstream can
+/// filter the rows by a sliding time range.
+pub struct NowExecutor<S: StateStore> {
+    /// Receiver of barrier channel.
+    barrier_receiver: UnboundedReceiver<Barrier>,
+
+    /// Stores the last emitted time.
+    state_table: StateTable<S>,
+
+    schema: Schema,
+
+    pk_indices: PkIndices,
+
+    identity: String,
+}
+
+impl<S: StateStore> NowExecutor<S> {
+    pub fn new(
+        barrier_receiver: UnboundedReceiver<Barrier>,
+        executor_id: u64,
+        state_table: StateTable<S>,
+    ) -> Self {
+        let schema = Schema::new(vec![Field::with_name(DataType::Timestampz, "now")]);
+        Self {
+            barrier_receiver,
+            state_table,
+            schema,
+            pk_indices: vec![],
+            identity: format!("NowExecutor {:X}", executor_id),
+        }
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn into_stream(self) {
+        let Self {
+            mut barrier_receiver,
+            mut state_table,
+            ..
+        } = self;
+
+        let barrier = barrier_receiver.recv().await.unwrap();
+        state_table.init_epoch(barrier.epoch);
+
+        // Recover the last emitted time, which is retracted on the next barrier.
+        let mut last_timestamp: Datum = state_table
+            .get_row(&Row::empty())
+            .await?
+            .and_then(|row| row[0].clone());
+
+        // The first barrier message should be propagated.
+        yield Message::Barrier(barrier);
+
+        while let Some(barrier) = barrier_receiver.recv().await {
+            // The time of the epoch that the emitted chunk belongs to.
+            let timestamp = Some(ScalarImpl::Int64(
+                Epoch(barrier.epoch.prev).as_unix_millis() as i64 * 1000,
+            ));
+
+            let chunk = if let Some(last_timestamp) = last_timestamp.clone() {
+                let data_chunk = DataChunk::from_rows(
+                    &[
+                        Row::new(vec![Some(last_timestamp)]),
+                        Row::new(vec![timestamp.clone()]),
+                    ],
+                    &[DataType::Timestampz],
+                );
+                state_table.update(
+                    Row::new(vec![Some(last_timestamp)]),
+                    Row::new(vec![timestamp.clone()]),
+                );
+                StreamChunk::from_parts(vec![Op::UpdateDelete, Op::UpdateInsert], data_chunk)
+            } else {
+                let data_chunk = DataChunk::from_rows(
+                    &[Row::new(vec![timestamp.clone()])],
+                    &[DataType::Timestampz],
+                );
+                state_table.insert(Row::new(vec![timestamp.clone()]));
+                StreamChunk::from_parts(vec![Op::Insert], data_chunk)
+            };
+            yield Message::Chunk(chunk);
+
+            yield Message::Watermark(Watermark::new(0, DataType::Timestampz, timestamp.clone()));
+
+            state_table.commit(barrier.epoch).await?;
+            last_timestamp = timestamp;
+
+            yield Message::Barrier(barrier);
+        }
+    }
+}
+
+impl<S: StateStore> Executor for NowExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.into_stream().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId};
+    use risingwave_storage::memory::MemoryStateStore;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_now() {
+        let state_table = StateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            TableId::new(0),
+            vec![ColumnDesc::unnamed(ColumnId::new(0), DataType::Timestampz)],
+            vec![],
+            vec![],
+        );
+        let (tx, rx) = unbounded_channel();
+        let mut now_executor = Box::new(NowExecutor::new(rx, 1, state_table)).execute();
+
+        let epoch = |millis: u64| Epoch::from_physical_time(millis).0;
+        let timestamp = |barrier: &Barrier| {
+            Some(ScalarImpl::Int64(
+                Epoch(barrier.epoch.prev).as_unix_millis() as i64 * 1000,
+            ))
+        };
+
+        tx.send(Barrier::new_test_barrier(epoch(1000))).unwrap();
+        now_executor.next().await.unwrap().unwrap();
+
+        // The time of the first epoch is inserted.
+        let barrier_2 = Barrier::new_test_barrier(epoch(2000));
+        tx.send(barrier_2.clone()).unwrap();
+        let chunk = now_executor
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_chunk()
+            .unwrap();
+        let (data_chunk, ops) = chunk.into_parts();
+        assert_eq!(ops, vec![Op::Insert]);
+        assert_eq!(
+            data_chunk.row_at(0).0.to_owned_row()[0],
+            timestamp(&barrier_2)
+        );
+        let watermark = now_executor
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_watermark()
+            .unwrap();
+        assert_eq!(watermark.val, timestamp(&barrier_2));
+        now_executor
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_barrier()
+            .unwrap();
+
+        // Then it is replaced by the time of the next epoch.
+        let barrier_3 = Barrier::new_test_barrier(epoch(3000));
+        tx.send(barrier_3.clone()).unwrap();
+        let chunk = now_executor
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .into_chunk()
+            .unwrap();
+        let (data_chunk, ops) = chunk.into_parts();
+        assert_eq!(ops, vec![Op::UpdateDelete, Op::UpdateInsert]);
+        assert_eq!(
+            data_chunk.row_at(0).0.to_owned_row()[0],
+            timestamp(&barrier_2)
+        );
+        assert_eq!(
+            data_chunk.row_at(1).0.to_owned_row()[0],
+            timestamp(&barrier_3)
+        );
+    }
+}
@@ -0,0 +1,532 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::DataType;
+use risingwave_expr::window_function::{evaluate_partition, WindowFuncCall};
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::{
+    expect_first_barrier, ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message,
+    PkIndices, PkIndicesRef, StreamExecutorResult,
+};
+use crate::cache::{cache_may_stale, EvictableHashMap, ExecutorCache, LruManagerRef};
+
+/// The output rows of a partition, i.e. the input rows followed by the results of the window
+/// functions, keyed and ordered by the memcmp-encoded primary key of the state table.
+type Partition = BTreeMap<Vec<u8>, Row>;
+
+/// [`OverWindowExecutor`] evaluates window functions (`OVER` clause) over its input.
+///
+/// All input rows are kept in the state table, ordered by the partition key, the window order and
+/// the stream key. The output rows of the recently changed partitions are cached in the same
+/// order, unless a partition has too many rows. A change to a partition only re-evaluates the
+/// rows whose window function results may change, i.e. the ones from the first changed row, or
+/// from the first frame reaching it, to the last changed row, or to the last frame reaching it.
+/// They are compared with their old output rows, which are re-evaluated if not cached, and
+/// emitted as diffs if their results changed.
+pub struct OverWindowExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+
+    /// We make it `Option` here due to lifetime restrictions. It will be taken (`Option.take()`)
+    /// after executing.
+    input: Option<BoxedExecutor>,
+
+    calls: Vec<WindowFuncCall>,
+    partition_key_indices: Vec<usize>,
+    order_key_indices: Vec<usize>,
+    state_table: StateTable<S>,
+
+    /// The cached partitions, keyed by the partition key.
+    cache: ExecutorCache<Row, Partition>,
+    /// The partitions with more rows are not cached.
+    cached_partition_rows: usize,
+
+    input_data_types: Vec<DataType>,
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+}
+
+impl<S: StateStore> OverWindowExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        input: BoxedExecutor,
+        calls: Vec<WindowFuncCall>,
+        partition_key_indices: Vec<usize>,
+        order_key_indices: Vec<usize>,
+        state_table: StateTable<S>,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        lru_manager: Option<LruManagerRef>,
+        cache_size: usize,
+        cached_partition_rows: usize,
+    ) -> Self {
+        let input_data_types = input.schema().data_types();
+        let mut schema = input.schema().clone();
+        schema
+            .fields
+            .extend(calls.iter().map(|call| Field::unnamed(call.return_type())));
+        let cache = if let Some(lru_manager) = lru_manager {
+            ExecutorCache::Managed(lru_manager.create_cache())
+        } else {
+            ExecutorCache::Local(EvictableHashMap::new(cache_size))
+        };
+        Self {
+            ctx,
+            input: Some(input),
+            calls,
+            partition_key_indices,
+            order_key_indices,
+            state_table,
+            cache,
+            cached_partition_rows,
+            input_data_types,
+            schema,
+            pk_indices,
+            identity: format!("OverWindowExecutor {:X}", executor_id),
+        }
+    }
+}
+
+impl<S: StateStore> Executor for OverWindowExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+}
+
+impl<S: StateStore> OverWindowExecutor<S> {
+    /// Encodes the primary key of the input row in the state table, which orders the rows of a
+    /// partition in the window order.
+    fn encode_key(&self, row: &Row) -> Vec<u8> {
+        let mut key = vec![];
+        self.state_table
+            .pk_serde()
+            .serialize(&row.by_indices(self.state_table.pk_indices()), &mut key);
+        key
+    }
+
+    /// Takes the input rows of the partition, along with its output rows if cached, out of the
+    /// cache, or reads the input rows from the state table if not cached.
+    async fn take_partition(
+        &mut self,
+        partition_key: &Row,
+    ) -> StreamExecutorResult<(BTreeMap<Vec<u8>, Row>, Option<Partition>)> {
+        let input_len = self.input_data_types.len();
+        if let Some(partition) = self.cache.pop(partition_key) {
+            let input_rows = partition
+                .iter()
+                .map(|(key, row)| (key.clone(), Row::new(row.0[..input_len].to_vec())))
+                .collect();
+            return Ok((input_rows, Some(partition)));
+        }
+
+        let iter = self.state_table.iter_with_pk_prefix(partition_key).await?;
+        pin_mut!(iter);
+        let mut input_rows = BTreeMap::new();
+        while let Some(row) = iter.next().await {
+            let row = row?.into_owned();
+            input_rows.insert(self.encode_key(&row), row);
+        }
+        Ok((input_rows, None))
+    }
+
+    /// Evaluates the window functions for the rows in `range` of a partition, whose input rows
+    /// are in the window order, and returns their output rows.
+    fn evaluate(&self, rows: &[Row], range: Range<usize>) -> StreamExecutorResult<Vec<Row>> {
+        let outputs = evaluate_partition(
+            &self.calls,
+            rows,
+            &self.input_data_types,
+            &self.order_key_indices,
+            range.clone(),
+        )?;
+        Ok(rows[range]
+            .iter()
+            .enumerate()
+            .map(|(i, row)| row.concat(outputs.iter().map(|output| output[i].clone())))
+            .collect())
+    }
+
+    /// Returns the position from which the output rows of a partition may change, given that the
+    /// first `unchanged` input rows stay the same and `first_changed` is the next one in either
+    /// the old or the new version of the partition.
+    fn first_affected(&self, rows: &[Row], unchanged: usize, first_changed: &Row) -> usize {
+        let is_peer = |row: &Row| {
+            self.order_key_indices
+                .iter()
+                .all(|&idx| row.0[idx] == first_changed.0[idx])
+        };
+
+        let mut start = unchanged;
+        for call in &self.calls {
+            // The results of the rows whose frames reach the changed row may change.
+            match call.following_rows() {
+                Some(following) => start = start.min(unchanged.saturating_sub(following)),
+                None => return 0,
+            }
+            // So do the ones of the peers of the changed row.
+            if call.depends_on_peers() {
+                let peers = rows[..unchanged]
+                    .iter()
+                    .rev()
+                    .take_while(|&row| is_peer(row))
+                    .count();
+                start = start.min(unchanged - peers);
+            }
+        }
+        start
+    }
+
+    /// Returns the number of rows after the last changed one whose output rows may change, given
+    /// that there are `following` rows after it.
+    fn last_affected(&self, following: usize) -> usize {
+        self.calls
+            .iter()
+            .map(|call| call.preceding_rows())
+            .fold(Some(0), |affected, preceding| {
+                Some(affected?.max(preceding?))
+            })
+            .map_or(following, |affected| affected.min(following))
+    }
+
+    async fn apply_chunk(&mut self, chunk: StreamChunk) -> StreamExecutorResult<StreamChunk> {
+        let mut partitions: HashMap<Row, Vec<(Op, Row)>> = HashMap::new();
+        for (op, row) in chunk.rows() {
+            let row = row.to_owned_row();
+            partitions
+                .entry(row.by_indices(&self.partition_key_indices))
+                .or_default()
+                .push((op, row));
+        }
+
+        let mut output_rows = vec![];
+        for (partition_key, changes) in partitions {
+            let (old_input_rows, old_partition) = self.take_partition(&partition_key).await?;
+            let mut new_input_rows = old_input_rows.clone();
+
+            // The rows before the first changed one and after the last changed one are the same in
+            // both versions.
+            let mut first_changed: Option<(Vec<u8>, Row)> = None;
+            let mut last_changed: Option<Vec<u8>> = None;
+            for (op, row) in changes {
+                let key = self.encode_key(&row);
+                if first_changed
+                    .as_ref()
+                    .map_or(true, |(first, _)| &key < first)
+                {
+                    first_changed = Some((key.clone(), row.clone()));
+                }
+                if last_changed.as_ref().map_or(true, |last| &key > last) {
+                    last_changed = Some(key.clone());
+                }
+                match op {
+                    Op::Insert | Op::UpdateInsert => {
+                        self.state_table.insert(row.clone());
+                        new_input_rows.insert(key, row);
+                    }
+                    Op::Delete | Op::UpdateDelete => {
+                        self.state_table.delete(row);
+                        new_input_rows.remove(&key);
+                    }
+                }
+            }
+            let (first_key, first_row) = first_changed.expect("no change to the partition");
+            let last_key = last_changed.unwrap();
+
+            let (old_keys, old_rows): (Vec<_>, Vec<_>) = old_input_rows.into_iter().unzip();
+            let (new_keys, new_rows): (Vec<_>, Vec<_>) = new_input_rows.into_iter().unzip();
+            let unchanged = old_keys.partition_point(|key| key < &first_key);
+            let start = self.first_affected(&old_rows, unchanged, &first_row);
+            let old_following = old_keys.partition_point(|key| key <= &last_key);
+            let new_following = new_keys.partition_point(|key| key <= &last_key);
+            let affected_following = self.last_affected(old_keys.len() - old_following);
+            let old_range = start..old_following + affected_following;
+            let new_range = start..new_following + affected_following;
+
+            let old_outputs = match &old_partition {
+                Some(partition) => partition
+                    .values()
+                    .skip(old_range.start)
+                    .take(old_range.len())
+                    .cloned()
+                    .collect(),
+                None => self.evaluate(&old_rows, old_range.clone())?,
+            };
+            // A partition to be cached is fully evaluated if its old output rows are not cached.
+            let cache_partition = new_rows.len() <= self.cached_partition_rows;
+            let evaluated = if cache_partition && old_partition.is_none() {
+                0..new_rows.len()
+            } else {
+                new_range.clone()
+            };
+            let new_outputs = self.evaluate(&new_rows, evaluated.clone())?;
+            let new_affected =
+                &new_outputs[new_range.start - evaluated.start..new_range.end - evaluated.start];
+
+            let mut old_affected: HashMap<_, _> = old_keys[old_range.clone()]
+                .iter()
+                .zip_eq(&old_outputs)
+                .collect();
+            let mut inserts = vec![];
+            for (key, new_row) in new_keys[new_range.clone()].iter().zip_eq(new_affected) {
+                match old_affected.remove(key) {
+                    Some(old_row) if old_row == new_row => {}
+                    Some(old_row) => {
+                        output_rows.push((Op::UpdateDelete, old_row.clone()));
+                        output_rows.push((Op::UpdateInsert, new_row.clone()));
+                    }
+                    None => inserts.push((Op::Insert, new_row.clone())),
+                }
+            }
+            output_rows.extend(
+                old_affected
+                    .into_values()
+                    .map(|old_row| (Op::Delete, old_row.clone())),
+            );
+            output_rows.extend(inserts);
+
+            if cache_partition {
+                let new_partition = match old_partition {
+                    Some(mut partition) => {
+                        for key in &old_keys[old_range] {
+                            partition.remove(key);
+                        }
+                        partition.extend(
+                            new_keys[new_range]
+                                .iter()
+                                .cloned()
+                                .zip_eq(new_affected.iter().cloned()),
+                        );
+                        partition
+                    }
+                    None => new_keys.into_iter().zip_eq(new_outputs).collect(),
+                };
+                self.cache.push(partition_key, new_partition);
+            }
+        }
+
+        Ok(StreamChunk::from_rows(
+            &output_rows,
+            &self.schema.data_types(),
+        ))
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(mut self: Box<Self>) {
+        let mut input = self.input.take().unwrap().execute();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        self.state_table.init_epoch(barrier.epoch);
+        self.cache.update_epoch(barrier.epoch.curr);
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                Message::Watermark(_) => {}
+                Message::Chunk(chunk) => {
+                    let chunk = self.apply_chunk(chunk).await?;
+                    if chunk.cardinality() > 0 {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                Message::Barrier(barrier) => {
+                    self.state_table.commit(barrier.epoch).await?;
+                    self.cache.evict();
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        let previous_vnode_bitmap =
+                            self.state_table.update_vnode_bitmap(vnode_bitmap.clone());
+                        if cache_may_stale(&previous_vnode_bitmap, &vnode_bitmap) {
+                            self.cache.clear();
+                        }
+                    }
+                    self.cache.update_epoch(barrier.epoch.curr);
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId};
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_pb::expr::agg_call::{Arg, Type as AggType};
+    use risingwave_pb::expr::window_function::{FrameBound, RowsFrame, Type};
+    use risingwave_pb::expr::{AggCall, InputRefExpr, WindowFunction as ProstWindowFunction};
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::ActorContext;
+
+    #[tokio::test]
+    async fn test_over_window() {
+        test_over_window_with_cached_partition_rows(1024).await;
+    }
+
+    #[tokio::test]
+    async fn test_over_window_without_cache() {
+        test_over_window_with_cached_partition_rows(0).await;
+    }
+
+    async fn test_over_window_with_cached_partition_rows(cached_partition_rows: usize) {
+        // The input columns are `(partition, order, id)`, whose stream key is `id`.
+        let column_descs = (0..3)
+            .map(|id| ColumnDesc::unnamed(ColumnId::new(id), DataType::Int32))
+            .collect_vec();
+        let state_table = StateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            TableId::new(0x42),
+            column_descs,
+            vec![OrderType::Ascending; 3],
+            vec![0, 1, 2],
+        );
+
+        // `ROW_NUMBER()` and `SUM(order) ROWS BETWEEN 1 PRECEDING AND CURRENT ROW`.
+        let row_number = ProstWindowFunction {
+            r#type: Type::RowNumber as i32,
+            return_type: Some(DataType::Int64.to_protobuf()),
+            ..Default::default()
+        };
+        let arg = Arg {
+            input: Some(InputRefExpr { column_idx: 1 }),
+            r#type: Some(DataType::Int32.to_protobuf()),
+        };
+        let sliding_sum = ProstWindowFunction {
+            r#type: Type::Aggregate as i32,
+            args: vec![arg.clone()],
+            return_type: Some(DataType::Int64.to_protobuf()),
+            agg_call: Some(AggCall {
+                r#type: AggType::Sum as i32,
+                args: vec![arg],
+                return_type: Some(DataType::Int64.to_protobuf()),
+                ..Default::default()
+            }),
+            rows_frame: Some(RowsFrame {
+                start: Some(FrameBound { offset: -1 }),
+                end: Some(FrameBound { offset: 0 }),
+            }),
+            ..Default::default()
+        };
+        let calls = [row_number, sliding_sum]
+            .iter()
+            .map(|call| WindowFuncCall::from_protobuf(call).unwrap())
+            .collect();
+
+        let schema = Schema::new(vec![Field::unnamed(DataType::Int32); 3]);
+        let (mut tx, source) = MockSource::channel(schema, vec![2]);
+        let mut over_window = Box::new(OverWindowExecutor::new(
+            ActorContext::create(123),
+            Box::new(source),
+            calls,
+            vec![0],
+            vec![1],
+            state_table,
+            vec![2],
+            1,
+            None,
+            1024,
+            cached_partition_rows,
+        ))
+        .execute();
+
+        tx.push_barrier(1, false);
+        over_window.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " i  i i
+            + 1 10 1
+            + 1 30 3
+            + 2  5 4",
+        ));
+        let chunk = over_window.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap().sort_rows(),
+            StreamChunk::from_pretty(
+                " i  i i I  I
+                + 1 10 1 1 10
+                + 1 30 3 2 40
+                + 2  5 4 1  5"
+            )
+            .sort_rows()
+        );
+
+        // Inserting a row in the middle of a partition changes the results of the following rows,
+        // while deleting the only row of a partition deletes its output.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " i  i i
+            + 1 20 2
+            - 2  5 4",
+        ));
+        let chunk = over_window.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap().sort_rows(),
+            StreamChunk::from_pretty(
+                "  i  i i I  I
+                +  1 20 2 2 30
+                -  2  5 4 1  5
+                U- 1 30 3 2 40
+                U+ 1 30 3 3 50"
+            )
+            .sort_rows()
+        );
+
+        tx.push_barrier(2, false);
+        over_window.next().await.unwrap().unwrap();
+
+        // Deleting the first row changes the results of all the others.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " i  i i
+            - 1 10 1",
+        ));
+        let chunk = over_window.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap().sort_rows(),
+            StreamChunk::from_pretty(
+                "  i  i i I  I
+                -  1 10 1 1 10
+                U- 1 20 2 2 30
+                U+ 1 20 2 1 20
+                U- 1 30 3 3 50
+                U+ 1 30 3 2 50"
+            )
+            .sort_rows()
+        );
+    }
+}
@@ -16,13 +16,13 @@ use std::fmt::{Debug, Formatter};
 
 use itertools::Itertools;
 use risingwave_common::array::column::Column;
-use risingwave_common::array::StreamChunk;
+use risingwave_common::array::{Row, StreamChunk};
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_expr::expr::BoxedExpression;
 
 use super::{
     ActorContextRef, Executor, ExecutorInfo, PkIndices, PkIndicesRef, SimpleExecutor,
-    SimpleExecutorWrapper, StreamExecutorResult,
+    SimpleExecutorWrapper, StreamExecutorResult, Watermark,
 };
 use crate::common::InfallibleExpression;
 
@@ -35,6 +35,7 @@ impl ProjectExecutor {
         pk_indices: PkIndices,
         exprs: Vec<BoxedExpression>,
         execuotr_id: u64,
+        watermark_derivations: Vec<(usize, usize)>,
     ) -> Self {
         let info = ExecutorInfo {
             schema: input.schema().to_owned(),
@@ -43,7 +44,7 @@ impl ProjectExecutor {
         };
         SimpleExecutorWrapper {
             input,
-            inner: SimpleProjectExecutor::new(ctx, info, exprs, execuotr_id),
+            inner: SimpleProjectExecutor::new(ctx, info, exprs, execuotr_id, watermark_derivations),
         }
     }
 }
@@ -57,6 +58,14 @@ pub struct SimpleProjectExecutor {
 
     /// Expressions of the current projection.
     exprs: Vec<BoxedExpression>,
+
+    /// Number of the input columns.
+    input_len: usize,
+
+    /// Pairs of the input and output column indices. The output column is derived from the input
+    /// column by a monotonic expression, so that a watermark of the input column can be
+    /// transformed into a watermark of the output column.
+    watermark_derivations: Vec<(usize, usize)>,
 }
 
 impl SimpleProjectExecutor {
@@ -65,7 +74,9 @@ impl SimpleProjectExecutor {
         input_info: ExecutorInfo,
         exprs: Vec<BoxedExpression>,
         executor_id: u64,
+        watermark_derivations: Vec<(usize, usize)>,
     ) -> Self {
+        let input_len = input_info.schema.len();
         let schema = Schema {
             fields: exprs
                 .iter()
@@ -80,6 +91,8 @@ impl SimpleProjectExecutor {
                 identity: format!("ProjectExecutor {:X}", executor_id),
             },
             exprs,
+            input_len,
+            watermark_derivations,
         }
     }
 }
@@ -92,8 +105,23 @@ impl Debug for SimpleProjectExecutor {
     }
 }
 
+#[async_trait::async_trait]
 impl SimpleExecutor for SimpleProjectExecutor {
-    fn map_filter_chunk(
+    fn handle_watermark(&self, watermark: Watermark) -> StreamExecutorResult<Vec<Watermark>> {
+        let mut row = Row::new(vec![None; self.input_len]);
+        row.0[watermark.col_idx] = watermark.val.clone();
+        self.watermark_derivations
+            .iter()
+            .filter(|(input_idx, _)| *input_idx == watermark.col_idx)
+            .map(|&(_, output_idx)| -> StreamExecutorResult<_> {
+                let expr = &self.exprs[output_idx];
+                let val = expr.eval_row(&row)?;
+                Ok(Watermark::new(output_idx, expr.return_type(), val))
+            })
+            .collect()
+    }
+
+    async fn map_filter_chunk(
         &mut self,
         chunk: StreamChunk,
     ) -> StreamExecutorResult<Option<StreamChunk>> {
@@ -101,15 +129,15 @@ impl SimpleExecutor for SimpleProjectExecutor {
 
         let (data_chunk, ops) = chunk.into_parts();
 
-        let projected_columns = self
-            .exprs
-            .iter_mut()
-            .map(|expr| {
-                Column::new(expr.eval_infallible(&data_chunk, |err| {
+        let mut projected_columns = Vec::with_capacity(self.exprs.len());
+        for expr in &self.exprs {
+            let array = expr
+                .eval_infallible_async(&data_chunk, |err| {
                     self.ctx.on_compute_error(err, &self.info.identity)
-                }))
-            })
-            .collect();
+                })
+                .await;
+            projected_columns.push(Column::new(array));
+        }
 
         let new_chunk = StreamChunk::new(ops, projected_columns, None);
         Ok(Some(new_chunk))
@@ -134,9 +162,9 @@ mod tests {
     use risingwave_common::array::stream_chunk::StreamChunkTestExt;
     use risingwave_common::array::StreamChunk;
     use risingwave_common::catalog::{Field, Schema};
-    use risingwave_common::types::DataType;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
-    use risingwave_expr::expr::InputRefExpression;
+    use risingwave_expr::expr::{InputRefExpression, LiteralExpression};
     use risingwave_pb::expr::expr_node::Type;
 
     use super::super::test_utils::MockSource;
@@ -180,6 +208,7 @@ mod tests {
             vec![],
             vec![test_expr],
             1,
+            vec![],
         ));
         let mut project = project.execute();
 
@@ -206,4 +235,52 @@ mod tests {
 
         assert!(project.next().await.unwrap().unwrap().is_stop());
     }
+
+    #[tokio::test]
+    async fn test_project_watermark() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let (mut tx, source) = MockSource::channel(schema, PkIndices::new());
+
+        let test_expr = new_binary_expr(
+            Type::Add,
+            DataType::Int64,
+            Box::new(InputRefExpression::new(DataType::Int64, 0)),
+            Box::new(LiteralExpression::new(
+                DataType::Int64,
+                Some(ScalarImpl::Int64(1)),
+            )),
+        )
+        .unwrap();
+        let project = Box::new(ProjectExecutor::new(
+            ActorContext::create(123),
+            Box::new(source),
+            vec![],
+            vec![
+                test_expr,
+                Box::new(InputRefExpression::new(DataType::Int64, 1)),
+            ],
+            1,
+            vec![(0, 0)],
+        ));
+        let mut project = project.execute();
+
+        tx.push_barrier(1, false);
+        tx.push_watermark(0, DataType::Int64, Some(ScalarImpl::Int64(10)));
+        // The watermark of the column not derived to the output is dropped.
+        tx.push_watermark(1, DataType::Int64, Some(ScalarImpl::Int64(20)));
+        tx.push_barrier(2, true);
+
+        project.next().await.unwrap().unwrap();
+        let msg = project.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.as_watermark().unwrap(),
+            &Watermark::new(0, DataType::Int64, Some(ScalarImpl::Int64(11)))
+        );
+        assert!(project.next().await.unwrap().unwrap().is_stop());
+    }
 }
@@ -0,0 +1,245 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_stack_trace::StackTrace;
+use futures_async_stream::try_stream;
+use parking_lot::Mutex;
+use risingwave_common::array::StreamChunk;
+use risingwave_connector::source::SourceRateLimit;
+use tokio::sync::Notify;
+use tokio::time::{Duration, Instant};
+
+use super::error::StreamExecutorError;
+use super::{BoxedMessageStream, Message};
+
+/// A token bucket refilled at `rate` tokens per second, which holds the tokens of one second at
+/// most. A rate of zero means unlimited.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: u64,
+    /// The available tokens, which is negative if more tokens than the bucket holds are acquired.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        Self {
+            rate,
+            tokens: rate as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * self.rate as f64).min(self.rate as f64);
+        self.last_refill = now;
+    }
+
+    fn set_rate(&mut self, rate: u64, now: Instant) {
+        if self.rate == 0 {
+            *self = Self::new(rate, now);
+        } else {
+            self.refill(now);
+            self.rate = rate;
+            self.tokens = self.tokens.min(rate as f64);
+        }
+    }
+
+    /// Returns the time to wait before `amount` tokens can be acquired. An amount larger than the
+    /// bucket can be acquired once the bucket is full, and the excess is paid off by the following
+    /// acquisitions.
+    fn wait_time(&mut self, amount: u64, now: Instant) -> Duration {
+        if self.rate == 0 {
+            return Duration::ZERO;
+        }
+        self.refill(now);
+        let required = amount.min(self.rate) as f64;
+        if self.tokens >= required {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((required - self.tokens) / self.rate as f64)
+        }
+    }
+
+    fn acquire(&mut self, amount: u64) {
+        if self.rate != 0 {
+            self.tokens -= amount as f64;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBuckets {
+    rows: TokenBucket,
+    bytes: TokenBucket,
+}
+
+/// Limits the rows and bytes per second of the chunks, e.g., read from a source or from the
+/// snapshot of backfilling. The limits can be changed at runtime, which takes effect on the chunks
+/// being throttled as well.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    buckets: Mutex<TokenBuckets>,
+    changed: Notify,
+}
+
+impl RateLimiter {
+    pub fn new(rate_limit: SourceRateLimit) -> Self {
+        let now = Instant::now();
+        Self {
+            buckets: Mutex::new(TokenBuckets {
+                rows: TokenBucket::new(rate_limit.rows_per_second, now),
+                bytes: TokenBucket::new(rate_limit.bytes_per_second, now),
+            }),
+            changed: Notify::new(),
+        }
+    }
+
+    pub fn rate_limit(&self) -> SourceRateLimit {
+        let buckets = self.buckets.lock();
+        SourceRateLimit {
+            rows_per_second: buckets.rows.rate,
+            bytes_per_second: buckets.bytes.rate,
+        }
+    }
+
+    pub fn set_rate_limit(&self, rate_limit: SourceRateLimit) {
+        {
+            let mut buckets = self.buckets.lock();
+            let now = Instant::now();
+            buckets.rows.set_rate(rate_limit.rows_per_second, now);
+            buckets.bytes.set_rate(rate_limit.bytes_per_second, now);
+        }
+        self.changed.notify_waiters();
+    }
+
+    /// Creates a limiter on the rows per second only.
+    pub fn with_rows_per_second(rows_per_second: u64) -> Self {
+        Self::new(SourceRateLimit {
+            rows_per_second,
+            bytes_per_second: 0,
+        })
+    }
+
+    /// Changes the limit on the rows per second only.
+    pub fn set_rows_per_second(&self, rows_per_second: u64) {
+        self.set_rate_limit(SourceRateLimit {
+            rows_per_second,
+            bytes_per_second: 0,
+        });
+    }
+
+    /// Waits until the chunk can be emitted under the limits.
+    pub async fn acquire(&self, chunk: &StreamChunk) {
+        let rows = chunk.cardinality() as u64;
+        // The size of the chunk is estimated by the value encoding of the rows.
+        let bytes = if self.rate_limit().bytes_per_second != 0 {
+            chunk
+                .data_chunk()
+                .serialize()
+                .iter()
+                .map(|row| row.len() as u64)
+                .sum()
+        } else {
+            0
+        };
+
+        loop {
+            // Register for the change of limits before checking the buckets, so that a change
+            // between the check and the wait is not missed.
+            let changed = self.changed.notified();
+            let wait_time = {
+                let mut buckets = self.buckets.lock();
+                let now = Instant::now();
+                let wait_time = buckets
+                    .rows
+                    .wait_time(rows, now)
+                    .max(buckets.bytes.wait_time(bytes, now));
+                if wait_time.is_zero() {
+                    buckets.rows.acquire(rows);
+                    buckets.bytes.acquire(bytes);
+                }
+                wait_time
+            };
+            if wait_time.is_zero() {
+                return;
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(wait_time) => {}
+                _ = changed => {}
+            }
+        }
+    }
+}
+
+/// Throttles the chunks of the message stream with `rate_limiter`, while the other messages are
+/// forwarded directly. Used for the snapshot side of the chain executors.
+#[try_stream(ok = Message, error = StreamExecutorError)]
+pub(crate) async fn throttle(stream: BoxedMessageStream, rate_limiter: Arc<RateLimiter>) {
+    #[for_await]
+    for msg in stream {
+        let msg = msg?;
+        if let Message::Chunk(chunk) = &msg {
+            rate_limiter
+                .acquire(chunk)
+                .stack_trace("chain_rate_limit")
+                .await;
+        }
+        yield msg;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(4, now);
+
+        // The bucket is full initially.
+        assert_eq!(bucket.wait_time(4, now), Duration::ZERO);
+        bucket.acquire(4);
+        assert_eq!(bucket.wait_time(2, now), Duration::from_millis(500));
+        let now = now + Duration::from_millis(500);
+        assert_eq!(bucket.wait_time(2, now), Duration::ZERO);
+        bucket.acquire(2);
+
+        // A large amount is acquired once the bucket is full, and paid off afterwards.
+        let now = now + Duration::from_secs(10);
+        assert_eq!(bucket.wait_time(10, now), Duration::ZERO);
+        bucket.acquire(10);
+        assert_eq!(bucket.wait_time(1, now), Duration::from_millis(1750));
+
+        // Lower the rate.
+        let now = now + Duration::from_secs(10);
+        bucket.set_rate(2, now);
+        assert_eq!(bucket.wait_time(2, now), Duration::ZERO);
+        bucket.acquire(2);
+        assert_eq!(bucket.wait_time(1, now), Duration::from_millis(500));
+
+        // Unlimited.
+        bucket.set_rate(0, now);
+        assert_eq!(bucket.wait_time(u64::MAX, now), Duration::ZERO);
+        bucket.set_rate(1, now);
+        assert_eq!(bucket.wait_time(1, now), Duration::ZERO);
+    }
+}
@@ -16,14 +16,16 @@ use std::sync::Arc;
 
 use futures::channel::{mpsc, oneshot};
 use futures::stream::select_with_strategy;
-use futures::{stream, StreamExt};
+use futures::{future, stream, StreamExt};
 use futures_async_stream::try_stream;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::Schema;
 
 use super::error::StreamExecutorError;
+use super::rate_limiter::{throttle, RateLimiter};
 use super::{
     expect_first_barrier, Barrier, BoxedExecutor, Executor, ExecutorInfo, Message, MessageStream,
+    Watermark,
 };
 use crate::task::{ActorId, CreateMviewProgress};
 
@@ -43,16 +45,22 @@ pub struct RearrangedChainExecutor {
 
     progress: CreateMviewProgress,
 
+    /// Throttles the rows read from the snapshot. The limit can be changed by the rearranged
+    /// barriers.
+    rate_limiter: Arc<RateLimiter>,
+
     actor_id: ActorId,
 
     info: ExecutorInfo,
 }
 
-fn mapping(upstream_indices: &[usize], msg: Message) -> Message {
+/// Projects the message with `upstream_indices`. Returns `None` if it's a watermark on a column
+/// that is not in the output.
+fn mapping(upstream_indices: &[usize], msg: Message) -> Option<Message> {
     match msg {
-        Message::Watermark(_) => {
-            todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-        }
+        Message::Watermark(watermark) => watermark
+            .transform_with_indices(upstream_indices)
+            .map(Message::Watermark),
 
         Message::Chunk(chunk) => {
             let (ops, columns, visibility) = chunk.into_inner();
@@ -60,9 +68,13 @@ fn mapping(upstream_indices: &[usize], msg: Message) -> Message {
                 .iter()
                 .map(|&i| columns[i].clone())
                 .collect();
-            Message::Chunk(StreamChunk::new(ops, mapped_columns, visibility))
+            Some(Message::Chunk(StreamChunk::new(
+                ops,
+                mapped_columns,
+                visibility,
+            )))
         }
-        _ => msg,
+        _ => Some(msg),
     }
 }
 
@@ -71,6 +83,7 @@ enum RearrangedMessage {
     RearrangedBarrier(Barrier),
     PhantomBarrier(Barrier),
     Chunk(StreamChunk),
+    Watermark(Watermark),
 }
 
 impl RearrangedMessage {
@@ -79,6 +92,7 @@ impl RearrangedMessage {
             RearrangedMessage::RearrangedBarrier(_) => None,
             RearrangedMessage::PhantomBarrier(barrier) => Message::Barrier(barrier).into(),
             RearrangedMessage::Chunk(chunk) => Message::Chunk(chunk).into(),
+            RearrangedMessage::Watermark(watermark) => Message::Watermark(watermark).into(),
         }
     }
 }
@@ -86,9 +100,7 @@ impl RearrangedMessage {
 impl RearrangedMessage {
     fn rearranged_from(msg: Message) -> Self {
         match msg {
-            Message::Watermark(_) => {
-                todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-            }
+            Message::Watermark(_) => unreachable!("there's no watermark in the snapshot"),
 
             Message::Chunk(chunk) => RearrangedMessage::Chunk(chunk),
             Message::Barrier(barrier) => RearrangedMessage::RearrangedBarrier(barrier),
@@ -97,9 +109,9 @@ impl RearrangedMessage {
 
     fn phantom_from(msg: Message) -> Self {
         match msg {
-            Message::Watermark(_) => {
-                todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-            }
+            // The upstream messages are consumed after the whole snapshot, so that it's safe to
+            // forward the watermarks in place.
+            Message::Watermark(watermark) => RearrangedMessage::Watermark(watermark),
 
             Message::Chunk(chunk) => RearrangedMessage::Chunk(chunk),
             Message::Barrier(barrier) => RearrangedMessage::PhantomBarrier(barrier),
@@ -114,6 +126,7 @@ impl RearrangedChainExecutor {
         upstream_indices: Vec<usize>,
         progress: CreateMviewProgress,
         schema: Schema,
+        rate_limit: u64,
     ) -> Self {
         Self {
             info: ExecutorInfo {
@@ -124,6 +137,7 @@ impl RearrangedChainExecutor {
             snapshot,
             upstream,
             upstream_indices: upstream_indices.into(),
+            rate_limiter: Arc::new(RateLimiter::with_rows_per_second(rate_limit)),
             actor_id: progress.actor_id(),
             progress,
         }
@@ -131,12 +145,22 @@ impl RearrangedChainExecutor {
 
     #[try_stream(ok = Message, error = StreamExecutorError)]
     async fn execute_inner(mut self) {
-        // 0. Project the upstream with `upstream_indices`.
-        let upstream_indices = self.upstream_indices.clone();
-        let mut upstream = self
-            .upstream
-            .execute()
-            .map(move |result| result.map(|msg| mapping(&upstream_indices, msg)));
+        // 0. Project the upstream with `upstream_indices`, which may be changed by a schema change
+        // of the materialized view after the creation.
+        let actor_id = self.actor_id;
+        let mut upstream_indices = self.upstream_indices.clone();
+        let mut upstream = self.upstream.execute().filter_map(move |result| {
+            if let Ok(Message::Barrier(barrier)) = &result
+                && let Some(indices) = barrier.as_chain_schema_change(actor_id)
+            {
+                upstream_indices = indices.into();
+            }
+            future::ready(
+                result
+                    .map(|msg| mapping(&upstream_indices, msg))
+                    .transpose(),
+            )
+        });
 
         // 1. Poll the upstream to get the first barrier.
         let first_barrier = expect_first_barrier(&mut upstream).await?;
@@ -171,7 +195,11 @@ impl RearrangedChainExecutor {
             );
 
             // 4. Init the snapshot with reading epoch.
-            let snapshot = self.snapshot.execute_with_epoch(create_epoch.prev);
+            let snapshot = throttle(
+                self.snapshot.execute_with_epoch(create_epoch.prev),
+                self.rate_limiter.clone(),
+            )
+            .boxed();
 
             // Chain the `snapshot` and `upstream_rx` to get a unified `rearranged_chunks` stream.
             let rearranged_chunks = snapshot
@@ -188,6 +216,11 @@ impl RearrangedChainExecutor {
             let mut last_rearranged_epoch = create_epoch;
             let mut stop_rearrange_tx = Some(stop_rearrange_tx);
 
+            // Record the progress to report. We're on the snapshot of `create_epoch.prev` until
+            // the first phantom barrier is received.
+            let mut consumed_epoch = create_epoch.prev;
+            let mut consumed_rows = 0;
+
             // 6. Consume the merged `rearranged` stream.
             #[for_await]
             for rearranged_msg in &mut rearranged {
@@ -199,8 +232,12 @@ impl RearrangedChainExecutor {
                     // consumed the whole snapshot and be on the upstream now.
                     RearrangedMessage::PhantomBarrier(barrier) => {
                         // Update the progress since we've consumed all chunks before this phantom.
-                        self.progress
-                            .update(last_rearranged_epoch.curr, barrier.epoch.curr);
+                        consumed_epoch = barrier.epoch.curr;
+                        self.progress.update(
+                            last_rearranged_epoch.curr,
+                            consumed_epoch,
+                            consumed_rows,
+                        );
 
                         if barrier.epoch.curr >= last_rearranged_epoch.curr {
                             // Stop the background rearrangement task.
@@ -213,10 +250,26 @@ impl RearrangedChainExecutor {
 
                     // If we received a message, yield it.
                     RearrangedMessage::RearrangedBarrier(barrier) => {
+                        if let Some(rate_limit) = barrier.as_backfill_rate_limit(self.actor_id) {
+                            tracing::info!(
+                                actor_id = self.actor_id,
+                                "change the backfill rate limit to {} rows per second",
+                                rate_limit
+                            );
+                            self.rate_limiter.set_rows_per_second(rate_limit);
+                        }
                         last_rearranged_epoch = barrier.epoch;
+                        self.progress
+                            .update(barrier.epoch.curr, consumed_epoch, consumed_rows);
                         yield Message::Barrier(barrier);
                     }
-                    RearrangedMessage::Chunk(chunk) => yield Message::Chunk(chunk),
+                    RearrangedMessage::Chunk(chunk) => {
+                        consumed_rows += chunk.cardinality() as u64;
+                        yield Message::Chunk(chunk);
+                    }
+                    RearrangedMessage::Watermark(watermark) => {
+                        yield Message::Watermark(watermark);
+                    }
                 }
             }
 
@@ -228,10 +281,12 @@ impl RearrangedChainExecutor {
             }
 
             // 8. Consume remainings.
-            let mut finish_on_barrier = |msg: &Message| {
-                if let Some(barrier) = msg.as_barrier() {
-                    self.progress.finish(barrier.epoch.curr);
+            let mut update_progress = |msg: &Message| match msg {
+                Message::Barrier(barrier) => {
+                    self.progress.finish(barrier.epoch.curr, consumed_rows)
                 }
+                Message::Chunk(chunk) => consumed_rows += chunk.cardinality() as u64,
+                Message::Watermark(_) => {}
             };
 
             // Note that there may still be some messages in `rearranged`. However the rearranged
@@ -240,7 +295,7 @@ impl RearrangedChainExecutor {
             for msg in rearranged {
                 let msg: RearrangedMessage = msg?;
                 let Some(msg) = msg.phantom_into() else { continue };
-                finish_on_barrier(&msg);
+                update_progress(&msg);
                 yield msg;
             }
 
@@ -252,7 +307,7 @@ impl RearrangedChainExecutor {
             #[for_await]
             for msg in &mut remaining_upstream {
                 let msg: Message = msg?;
-                finish_on_barrier(&msg);
+                update_progress(&msg);
                 yield msg;
             }
         } else {
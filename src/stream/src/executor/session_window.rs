@@ -0,0 +1,212 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::{DataType, IntervalUnit};
+use risingwave_expr::window_function::evaluate_sessions;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::{
+    expect_first_barrier, ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message,
+    PkIndices, PkIndicesRef, StreamExecutorResult,
+};
+
+/// [`SessionWindowExecutor`] assigns its input rows to session windows, appending the
+/// `window_start` and `window_end` of their sessions.
+///
+/// All input rows are kept in the state table, ordered by the partition key, the time column and
+/// the stream key. A new row may extend a session or merge adjacent sessions, and a removed row may
+/// split a session, so any change to a partition makes its sessions re-evaluated, and only the
+/// output rows whose sessions changed are emitted as diffs.
+pub struct SessionWindowExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+
+    /// We make it `Option` here due to lifetime restrictions. It will be taken (`Option.take()`)
+    /// after executing.
+    input: Option<BoxedExecutor>,
+
+    time_col: usize,
+    gap: IntervalUnit,
+    partition_key_indices: Vec<usize>,
+    state_table: StateTable<S>,
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+}
+
+impl<S: StateStore> SessionWindowExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        input: BoxedExecutor,
+        time_col: usize,
+        gap: IntervalUnit,
+        partition_key_indices: Vec<usize>,
+        state_table: StateTable<S>,
+        pk_indices: PkIndices,
+        executor_id: u64,
+    ) -> Self {
+        let output_type = DataType::window_of(&input.schema()[time_col].data_type).unwrap();
+        let mut schema = input.schema().clone();
+        schema.fields.extend([
+            Field::with_name(output_type.clone(), "window_start"),
+            Field::with_name(output_type, "window_end"),
+        ]);
+        Self {
+            ctx,
+            input: Some(input),
+            time_col,
+            gap,
+            partition_key_indices,
+            state_table,
+            schema,
+            pk_indices,
+            identity: format!("SessionWindowExecutor {:X}", executor_id),
+        }
+    }
+}
+
+impl<S: StateStore> Executor for SessionWindowExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.execute_inner().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+}
+
+impl<S: StateStore> SessionWindowExecutor<S> {
+    /// Reads all rows of the partition from the state table, in the order of the time column.
+    async fn read_partition(
+        state_table: &StateTable<S>,
+        partition_key: &Row,
+    ) -> StreamExecutorResult<Vec<Row>> {
+        let iter = state_table.iter_with_pk_prefix(partition_key).await?;
+        pin_mut!(iter);
+        let mut rows = vec![];
+        while let Some(row) = iter.next().await {
+            rows.push(row?.into_owned());
+        }
+        Ok(rows)
+    }
+
+    /// Assigns the rows of the partition to sessions, returning the output rows keyed by the
+    /// stream key.
+    fn evaluate(&self, rows: Vec<Row>) -> StreamExecutorResult<Vec<(Row, Row)>> {
+        let sessions = evaluate_sessions(&rows, self.time_col, self.gap)?;
+        Ok(rows
+            .into_iter()
+            .zip_eq(sessions)
+            .map(|(row, (window_start, window_end))| {
+                let key = row.by_indices(&self.pk_indices);
+                (key, row.concat([window_start, window_end]))
+            })
+            .collect())
+    }
+
+    async fn apply_chunk(&mut self, chunk: StreamChunk) -> StreamExecutorResult<StreamChunk> {
+        let mut partitions: HashMap<Row, Vec<(Op, Row)>> = HashMap::new();
+        for (op, row) in chunk.rows() {
+            let row = row.to_owned_row();
+            partitions
+                .entry(row.by_indices(&self.partition_key_indices))
+                .or_default()
+                .push((op, row));
+        }
+
+        let mut output_rows = vec![];
+        for (partition_key, changes) in partitions {
+            let old_rows = Self::read_partition(&self.state_table, &partition_key).await?;
+            let old_outputs = self.evaluate(old_rows)?;
+            for (op, row) in changes {
+                match op {
+                    Op::Insert | Op::UpdateInsert => self.state_table.insert(row),
+                    Op::Delete | Op::UpdateDelete => self.state_table.delete(row),
+                }
+            }
+            let new_rows = Self::read_partition(&self.state_table, &partition_key).await?;
+            let new_outputs = self.evaluate(new_rows)?;
+
+            let mut old_outputs: HashMap<Row, Row> = old_outputs.into_iter().collect();
+            let mut inserts = vec![];
+            for (key, new_row) in new_outputs {
+                match old_outputs.remove(&key) {
+                    Some(old_row) if old_row == new_row => {}
+                    Some(old_row) => {
+                        output_rows.push((Op::UpdateDelete, old_row));
+                        output_rows.push((Op::UpdateInsert, new_row));
+                    }
+                    None => inserts.push((Op::Insert, new_row)),
+                }
+            }
+            output_rows.extend(
+                old_outputs
+                    .into_values()
+                    .map(|old_row| (Op::Delete, old_row)),
+            );
+            output_rows.extend(inserts);
+        }
+
+        Ok(StreamChunk::from_rows(
+            &output_rows,
+            &self.schema.data_types(),
+        ))
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_inner(mut self: Box<Self>) {
+        let mut input = self.input.take().unwrap().execute();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        self.state_table.init_epoch(barrier.epoch);
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                Message::Watermark(_) => {}
+                Message::Chunk(chunk) => {
+                    let chunk = self.apply_chunk(chunk).await?;
+                    if chunk.cardinality() > 0 {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                Message::Barrier(barrier) => {
+                    self.state_table.commit(barrier.epoch).await?;
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        self.state_table.update_vnode_bitmap(vnode_bitmap);
+                    }
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
@@ -17,13 +17,21 @@ use futures_async_stream::try_stream;
 use risingwave_common::catalog::Schema;
 
 use super::error::{StreamExecutorError, StreamExecutorResult};
-use super::{BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndicesRef, StreamChunk};
+use super::{
+    BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndicesRef, StreamChunk, Watermark,
+};
 
 /// Executor which can handle [`StreamChunk`]s one by one.
+#[async_trait::async_trait]
 pub trait SimpleExecutor: Send + 'static {
     /// convert a single chunk to zero or one chunks.
-    fn map_filter_chunk(&mut self, chunk: StreamChunk)
-        -> StreamExecutorResult<Option<StreamChunk>>;
+    async fn map_filter_chunk(
+        &mut self,
+        chunk: StreamChunk,
+    ) -> StreamExecutorResult<Option<StreamChunk>>;
+
+    /// convert a watermark of the input to zero or more watermarks of the output.
+    fn handle_watermark(&self, watermark: Watermark) -> StreamExecutorResult<Vec<Watermark>>;
 
     /// See [`super::Executor::schema`].
     fn schema(&self) -> &Schema;
@@ -74,10 +82,12 @@ where
         for msg in input {
             let msg = msg?;
             match msg {
-                Message::Watermark(_) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
+                Message::Watermark(watermark) => {
+                    for watermark in inner.handle_watermark(watermark)? {
+                        yield Message::Watermark(watermark);
+                    }
                 }
-                Message::Chunk(chunk) => match inner.map_filter_chunk(chunk)? {
+                Message::Chunk(chunk) => match inner.map_filter_chunk(chunk).await? {
                     Some(new_chunk) => yield Message::Chunk(new_chunk),
                     None => continue,
                 },
@@ -15,63 +15,131 @@
 use core::default::Default;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use futures::StreamExt;
+use anyhow::anyhow;
+use futures::{pin_mut, StreamExt};
 use futures_async_stream::try_stream;
+use prometheus::Histogram;
+use risingwave_common::array::Row;
+use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::Schema;
 use risingwave_connector::sink::{Sink, SinkConfig, SinkImpl};
+use risingwave_pb::catalog::Table;
+use risingwave_pb::meta::sink_prepared_commit::Decision;
+use risingwave_pb::meta::SinkPreparedCommit;
+use risingwave_rpc_client::MetaClient;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
 use risingwave_storage::StateStore;
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
 
+use self::log_store::{EpochLog, LogStoreReader, LogStoreWriter};
 use super::error::{StreamExecutorError, StreamExecutorResult};
-use super::{BoxedExecutor, Executor, Message};
+use super::{expect_first_barrier, ActorContextRef, BoxedExecutor, Executor, Message};
 use crate::executor::monitor::StreamingMetrics;
 use crate::executor::PkIndices;
+use crate::task::ActorId;
+
+mod log_store;
+
+/// The initial interval to retry writing the logs to a decoupled sink after a failure.
+const LOG_SINK_INITIAL_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+/// The max interval to retry writing the logs to a decoupled sink after a failure.
+const LOG_SINK_MAX_RETRY_INTERVAL: Duration = Duration::from_secs(60);
 
 pub struct SinkExecutor<S: StateStore> {
+    ctx: ActorContextRef,
     input: BoxedExecutor,
-    _store: S,
+    store: S,
     metrics: Arc<StreamingMetrics>,
     properties: HashMap<String, String>,
     identity: String,
     pk_indices: PkIndices,
+
+    /// The id of the sink catalog, by which meta coordinates the commits of a two-phase sink.
+    sink_id: u32,
+    meta_client: Option<MetaClient>,
+
+    /// The log store to decouple the sink from the upstream, if `sink_decouple` is enabled.
+    log_store_table: Option<Table>,
+    vnodes: Option<Arc<Bitmap>>,
 }
 
-async fn build_sink(config: SinkConfig) -> StreamExecutorResult<Box<SinkImpl>> {
-    Ok(Box::new(SinkImpl::new(config).await?))
+async fn build_sink(
+    config: SinkConfig,
+    pk_indices: PkIndices,
+) -> StreamExecutorResult<Box<SinkImpl>> {
+    Ok(Box::new(SinkImpl::new(config, pk_indices).await?))
 }
 
 impl<S: StateStore> SinkExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        ctx: ActorContextRef,
         materialize_executor: BoxedExecutor,
-        _store: S,
+        store: S,
         metrics: Arc<StreamingMetrics>,
         mut properties: HashMap<String, String>,
+        sink_id: u32,
+        meta_client: Option<MetaClient>,
         executor_id: u64,
+        pk_indices: PkIndices,
+        log_store_table: Option<Table>,
+        vnodes: Option<Arc<Bitmap>>,
     ) -> Self {
-        // This field can be used to distinguish a specific actor in parallelism to prevent
-        // transaction execution errors
-        properties.insert("identifier".to_string(), format!("sink-{:?}", executor_id));
+        // This field distinguishes the writers of the sinks, so that their transactions don't
+        // conflict. It's derived from the sink id and the actor id, so the transactions left by an
+        // actor can be identified after rescheduling.
+        properties.insert(
+            "identifier".to_string(),
+            format!("sink-{}-{}", sink_id, ctx.id),
+        );
         Self {
+            ctx,
             input: materialize_executor,
-            _store,
+            store,
             metrics,
             properties,
             identity: format!("SinkExecutor_{:?}", executor_id),
-            pk_indices: Default::default(), // todo
+            pk_indices,
+            sink_id,
+            meta_client,
+            log_store_table,
+            vnodes,
         }
     }
 
     #[try_stream(ok = Message, error = StreamExecutorError)]
     async fn execute_inner(self) {
         let sink_config = SinkConfig::from_hashmap(self.properties.clone())?;
-        let mut sink = build_sink(sink_config.clone()).await?;
+        let mut sink = build_sink(sink_config.clone(), self.pk_indices.clone()).await?;
 
         // the flag is required because kafka transaction requires at least one
         // message, so we should abort the transaction if the flag is true.
         let mut empty_epoch_flag = true;
         let mut in_transaction = false;
         let mut epoch = 0;
+        let mut is_first_barrier = true;
+        let two_phase = sink.is_two_phase();
+        let actor_id = self.ctx.id;
+        // The commits of a two-phase sink are coordinated by meta.
+        let meta_client = if two_phase {
+            let meta_client = self
+                .meta_client
+                .clone()
+                .ok_or_else(|| anyhow!("two-phase sink {} requires a meta client", self.sink_id))?;
+            Some(meta_client)
+        } else {
+            None
+        };
+        let observe_commit = |start_time: Instant| {
+            self.metrics
+                .sink_commit_duration
+                .with_label_values(&[self.identity.as_str(), sink_config.get_connector()])
+                .observe(start_time.elapsed().as_millis() as f64);
+        };
 
         let schema = self.schema().clone();
 
@@ -85,9 +153,7 @@ impl<S: StateStore> SinkExecutor<S> {
         #[for_await]
         for msg in input {
             match msg? {
-                Message::Watermark(_) => {
-                    todo!("https://github.com/risingwavelabs/risingwave/issues/6042")
-                }
+                Message::Watermark(_) => {}
                 Message::Chunk(chunk) => {
                     if !in_transaction {
                         sink.begin_epoch(epoch).await?;
@@ -104,28 +170,187 @@ impl<S: StateStore> SinkExecutor<S> {
                     yield Message::Chunk(chunk);
                 }
                 Message::Barrier(barrier) => {
-                    if in_transaction {
+                    if is_first_barrier {
+                        // Resolve the transactions pre-committed before a failure by the decisions
+                        // of meta, including those of the actors not running anymore.
+                        if let Some(meta_client) = &meta_client {
+                            for commit in meta_client.recover_sink(self.sink_id, actor_id).await? {
+                                sink.resolve_prepared(
+                                    &commit.transaction_id,
+                                    commit.decision() == Decision::Commit,
+                                )
+                                .await?;
+                                meta_client
+                                    .finish_sink_commit(
+                                        commit.sink_id,
+                                        commit.actor_id,
+                                        commit.epoch,
+                                    )
+                                    .await?;
+                            }
+                        }
+                        is_first_barrier = false;
+                    }
+
+                    // The transaction is committed on checkpoints only, so that the data committed
+                    // to the external system is aligned with the checkpoints to recover from.
+                    let mut pre_committed_epoch = None;
+                    if in_transaction && barrier.checkpoint {
                         if empty_epoch_flag {
                             sink.abort().await?;
                             tracing::debug!(
                                 "transaction abort due to empty epoch, epoch: {:?}",
                                 epoch
                             );
+                        } else if let Some(meta_client) = &meta_client {
+                            // The transaction is reported before it's prepared, so that it's
+                            // aborted on recovery if the checkpoint fails in between.
+                            let checkpoint_epoch = barrier.epoch.prev;
+                            meta_client
+                                .pre_commit_sink(SinkPreparedCommit {
+                                    sink_id: self.sink_id,
+                                    actor_id,
+                                    epoch: checkpoint_epoch,
+                                    transaction_id: sink.transaction_id(checkpoint_epoch),
+                                    decision: Decision::Unspecified as i32,
+                                })
+                                .await?;
+                            sink.pre_commit(checkpoint_epoch).await?;
+                            pre_committed_epoch = Some(checkpoint_epoch);
                         } else {
                             let start_time = Instant::now();
                             sink.commit().await?;
-                            self.metrics
-                                .sink_commit_duration
-                                .with_label_values(&[
-                                    self.identity.as_str(),
-                                    sink_config.get_connector(),
-                                ])
-                                .observe(start_time.elapsed().as_millis() as f64);
+                            observe_commit(start_time);
                         }
                     }
-                    in_transaction = false;
-                    empty_epoch_flag = true;
+                    if barrier.checkpoint {
+                        in_transaction = false;
+                        empty_epoch_flag = true;
+                    }
                     epoch = barrier.epoch.curr;
+
+                    // The actor is dropped right after passing the barrier on, so the transaction
+                    // is resolved in the background.
+                    if let Some(checkpoint_epoch) = pre_committed_epoch
+                        && barrier.is_stop_or_update_drop_actor(actor_id)
+                    {
+                        let meta_client = meta_client.unwrap();
+                        let sink_id = self.sink_id;
+                        tokio::spawn(async move {
+                            if let Err(e) = commit_by_decision(
+                                &mut sink,
+                                &meta_client,
+                                sink_id,
+                                actor_id,
+                                checkpoint_epoch,
+                            )
+                            .await
+                            {
+                                tracing::warn!(
+                                    "failed to resolve the transaction of sink {} at epoch {}: {}",
+                                    sink_id,
+                                    checkpoint_epoch,
+                                    e
+                                );
+                            }
+                        });
+                        yield Message::Barrier(barrier);
+                        return Ok(());
+                    }
+                    yield Message::Barrier(barrier);
+
+                    // A pre-committed transaction is committed after the checkpoint is durable.
+                    // If the checkpoint is abandoned by a recovery instead, it's aborted.
+                    if let Some(checkpoint_epoch) = pre_committed_epoch {
+                        let start_time = Instant::now();
+                        commit_by_decision(
+                            &mut sink,
+                            meta_client.as_ref().unwrap(),
+                            self.sink_id,
+                            actor_id,
+                            checkpoint_epoch,
+                        )
+                        .await?;
+                        observe_commit(start_time);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Executes a decoupled sink. The input is appended to the log store and the barriers are
+    /// passed on once the logs are flushed, while a background task writes the logs of the
+    /// checkpointed epochs to the sink and sends them back to be truncated after the sink commits.
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn execute_decoupled(self, log_store_table: Table) {
+        let sink_config = SinkConfig::from_hashmap(self.properties.clone())?;
+        let schema = self.schema().clone();
+        let commit_duration = self
+            .metrics
+            .sink_commit_duration
+            .with_label_values(&[self.identity.as_str(), sink_config.get_connector()]);
+
+        let mut writer = LogStoreWriter::new(StateTable::from_table_catalog(
+            &log_store_table,
+            self.store.clone(),
+            self.vnodes.clone(),
+        ));
+        let spawn_log_sinker = |vnodes: Option<Arc<Bitmap>>| {
+            LogSinker::spawn(
+                LogStoreReader::new(&log_store_table, self.store.clone(), vnodes),
+                sink_config.clone(),
+                self.pk_indices.clone(),
+                schema.clone(),
+                commit_duration.clone(),
+            )
+        };
+
+        let mut input = self.input.execute();
+        let barrier = expect_first_barrier(&mut input).await?;
+        writer.init(barrier.epoch);
+        let mut log_sinker = spawn_log_sinker(self.vnodes.clone()).await?;
+        yield Message::Barrier(barrier);
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                Message::Watermark(_) => {}
+                Message::Chunk(chunk) => {
+                    writer.write_chunk(&chunk);
+                    yield Message::Chunk(chunk);
+                }
+                Message::Barrier(barrier) => {
+                    // Truncate the logs that have been committed to the sink.
+                    loop {
+                        match log_sinker.committed_rx.try_recv() {
+                            Ok(log_rows) => writer.truncate(log_rows?),
+                            Err(TryRecvError::Empty) => break,
+                            Err(TryRecvError::Disconnected) => {
+                                return Err(anyhow!(
+                                    "log sinker of {} exited unexpectedly",
+                                    self.identity
+                                )
+                                .into());
+                            }
+                        }
+                    }
+                    writer.flush(barrier.epoch).await?;
+                    if barrier.checkpoint {
+                        log_sinker
+                            .checkpoint_tx
+                            .send(barrier.epoch.prev)
+                            .map_err(|_| {
+                                anyhow!("log sinker of {} exited unexpectedly", self.identity)
+                            })?;
+                    }
+
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        writer.update_vnode_bitmap(vnode_bitmap.clone());
+                        // The logs not truncated yet are replayed by the new log sinker, which
+                        // reads the logs of the new vnodes from the beginning.
+                        log_sinker = spawn_log_sinker(Some(vnode_bitmap)).await?;
+                    }
+
                     yield Message::Barrier(barrier);
                 }
             }
@@ -133,9 +358,153 @@ impl<S: StateStore> SinkExecutor<S> {
     }
 }
 
+/// Commits or aborts the transaction pre-committed by the actor at the checkpoint `epoch`, by the
+/// decision of meta, which is made once the checkpoint is durable or abandoned.
+async fn commit_by_decision(
+    sink: &mut SinkImpl,
+    meta_client: &MetaClient,
+    sink_id: u32,
+    actor_id: ActorId,
+    epoch: u64,
+) -> StreamExecutorResult<()> {
+    match meta_client
+        .get_sink_commit_decision(sink_id, actor_id, epoch)
+        .await?
+    {
+        Decision::Commit => sink.commit().await?,
+        Decision::Abort | Decision::Unspecified => sink.abort().await?,
+    }
+    meta_client
+        .finish_sink_commit(sink_id, actor_id, epoch)
+        .await?;
+    Ok(())
+}
+
+/// The handle of the background task writing the logs of a decoupled sink to the external system.
+/// The task is aborted once the handle is dropped.
+struct LogSinker {
+    /// Sends the epochs of the checkpoints whose logs are to be written to the sink.
+    checkpoint_tx: UnboundedSender<u64>,
+
+    /// Receives the log store rows that have been committed to the sink, or the error that
+    /// stopped the task.
+    committed_rx: UnboundedReceiver<StreamExecutorResult<Vec<Row>>>,
+
+    join_handle: JoinHandle<()>,
+}
+
+impl LogSinker {
+    async fn spawn<S: StateStore>(
+        reader: LogStoreReader<S>,
+        sink_config: SinkConfig,
+        pk_indices: PkIndices,
+        schema: Schema,
+        commit_duration: Histogram,
+    ) -> StreamExecutorResult<Self> {
+        let mut sink = build_sink(sink_config, pk_indices).await?;
+        if sink.needs_preparation() {
+            sink.prepare(&schema).await?;
+        }
+
+        let (checkpoint_tx, checkpoint_rx) = unbounded_channel();
+        let (committed_tx, committed_rx) = unbounded_channel();
+        let join_handle = tokio::spawn(async move {
+            if let Err(e) = Self::run(
+                reader,
+                sink,
+                schema,
+                checkpoint_rx,
+                &committed_tx,
+                commit_duration,
+            )
+            .await
+            {
+                let _ = committed_tx.send(Err(e));
+            }
+        });
+
+        Ok(Self {
+            checkpoint_tx,
+            committed_rx,
+            join_handle,
+        })
+    }
+
+    async fn run<S: StateStore>(
+        mut reader: LogStoreReader<S>,
+        mut sink: Box<SinkImpl>,
+        schema: Schema,
+        mut checkpoint_rx: UnboundedReceiver<u64>,
+        committed_tx: &UnboundedSender<StreamExecutorResult<Vec<Row>>>,
+        commit_duration: Histogram,
+    ) -> StreamExecutorResult<()> {
+        while let Some(checkpoint_epoch) = checkpoint_rx.recv().await {
+            let logs = reader.read(checkpoint_epoch);
+            pin_mut!(logs);
+            while let Some(log) = logs.next().await {
+                let log = log?;
+                // Retry until the logs are committed, since they are durable in the log store and
+                // the failure of the sink does not block the upstream.
+                let mut retry_interval = LOG_SINK_INITIAL_RETRY_INTERVAL;
+                loop {
+                    let start_time = Instant::now();
+                    match Self::write_epoch_log(&mut sink, &log, &schema).await {
+                        Ok(()) => {
+                            commit_duration.observe(start_time.elapsed().as_millis() as f64);
+                            break;
+                        }
+                        Err(e) => {
+                            tracing::warn!(
+                                "failed to write the logs of epoch {} to the sink, retry in {:?}: {}",
+                                log.epoch,
+                                retry_interval,
+                                e
+                            );
+                            if let Err(e) = sink.abort().await {
+                                tracing::warn!("failed to abort the sink transaction: {}", e);
+                            }
+                            tokio::time::sleep(retry_interval).await;
+                            retry_interval = (retry_interval * 2).min(LOG_SINK_MAX_RETRY_INTERVAL);
+                        }
+                    }
+                }
+
+                if committed_tx.send(Ok(log.log_rows)).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn write_epoch_log(
+        sink: &mut SinkImpl,
+        log: &EpochLog,
+        schema: &Schema,
+    ) -> StreamExecutorResult<()> {
+        sink.begin_epoch(log.epoch).await?;
+        for chunk in &log.chunks {
+            sink.write_batch(chunk.clone(), schema).await?;
+        }
+        // The logs are durable already, so the transaction is committed in one phase. If it fails
+        // before the logs are truncated, the logs are written again.
+        sink.commit().await?;
+        Ok(())
+    }
+}
+
+impl Drop for LogSinker {
+    fn drop(&mut self) {
+        self.join_handle.abort();
+    }
+}
+
 impl<S: StateStore> Executor for SinkExecutor<S> {
-    fn execute(self: Box<Self>) -> super::BoxedMessageStream {
-        self.execute_inner().boxed()
+    fn execute(mut self: Box<Self>) -> super::BoxedMessageStream {
+        match self.log_store_table.take() {
+            Some(log_store_table) => self.execute_decoupled(log_store_table).boxed(),
+            None => self.execute_inner().boxed(),
+        }
     }
 
     fn schema(&self) -> &Schema {
@@ -165,7 +534,7 @@ mod test {
         use risingwave_common::types::DataType;
         use risingwave_storage::memory::MemoryStateStore;
 
-        use crate::executor::Barrier;
+        use crate::executor::{ActorContext, Barrier};
 
         let properties = maplit::hashmap! {
         "connector".into() => "mysql".into(),
@@ -197,11 +566,17 @@ mod test {
         );
 
         let sink_executor = SinkExecutor::new(
+            ActorContext::create(0),
             Box::new(mock),
             MemoryStateStore::new(),
             Arc::new(StreamingMetrics::unused()),
             properties,
             0,
+            None,
+            0,
+            vec![],
+            None,
+            None,
         );
 
         let mut executor = SinkExecutor::execute(Box::new(sink_executor));
@@ -0,0 +1,351 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The log store of a decoupled sink. The input of the sink is appended to the log store, which is
+//! a state table ordered by the epoch, so that the sink can be written asynchronously from the
+//! committed logs, without backpressuring the upstream or blocking the checkpoints.
+
+use std::ops::Bound;
+use std::sync::Arc;
+
+use futures::pin_mut;
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::{ColumnDesc, TableId, TableOption, LOG_STORE_PREFIX_LEN};
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_common::util::epoch::EpochPair;
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_hummock_sdk::HummockReadEpoch;
+use risingwave_pb::catalog::Table;
+use risingwave_pb::plan_common::OrderType as ProstOrderType;
+use risingwave_storage::table::batch_table::storage_table::StorageTable;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::table::{Distribution, TableIter};
+use risingwave_storage::StateStore;
+
+use crate::executor::{StreamExecutorError, StreamExecutorResult};
+
+const EPOCH_COLUMN_INDEX: usize = 0;
+const SEQ_ID_COLUMN_INDEX: usize = 1;
+const OP_COLUMN_INDEX: usize = 2;
+
+/// The max number of rows in a chunk read from the log store.
+const LOG_STORE_CHUNK_SIZE: usize = 1024;
+
+/// Appends the input of the sink to the log store, and truncates the logs consumed by the sink.
+pub struct LogStoreWriter<S: StateStore> {
+    state_table: StateTable<S>,
+
+    /// The sequence number of the next row in the current epoch.
+    seq_id: i32,
+}
+
+impl<S: StateStore> LogStoreWriter<S> {
+    pub fn new(state_table: StateTable<S>) -> Self {
+        Self {
+            state_table,
+            seq_id: 0,
+        }
+    }
+
+    pub fn init(&mut self, epoch: EpochPair) {
+        self.state_table.init_epoch(epoch);
+    }
+
+    pub fn write_chunk(&mut self, chunk: &StreamChunk) {
+        let epoch = self.state_table.epoch();
+        for (op, row) in chunk.rows() {
+            let log_row = Row::new(vec![
+                Some(ScalarImpl::Int64(epoch as i64)),
+                Some(ScalarImpl::Int32(self.seq_id)),
+                Some(ScalarImpl::Int16(op.to_protobuf() as i16)),
+            ])
+            .concat(row.to_owned_row().0);
+            self.state_table.insert(log_row);
+            self.seq_id += 1;
+        }
+    }
+
+    /// Deletes the logs that have been committed to the sink.
+    pub fn truncate(&mut self, log_rows: Vec<Row>) {
+        for log_row in log_rows {
+            self.state_table.delete(log_row);
+        }
+    }
+
+    pub async fn flush(&mut self, new_epoch: EpochPair) -> StreamExecutorResult<()> {
+        self.state_table.commit(new_epoch).await?;
+        self.seq_id = 0;
+        Ok(())
+    }
+
+    pub fn update_vnode_bitmap(&mut self, vnodes: Arc<Bitmap>) {
+        self.state_table.update_vnode_bitmap(vnodes);
+    }
+}
+
+/// The logs of an epoch read from the log store.
+pub struct EpochLog {
+    pub epoch: u64,
+
+    pub chunks: Vec<StreamChunk>,
+
+    /// The rows in the log store, to be truncated after the logs are committed to the sink.
+    pub log_rows: Vec<Row>,
+}
+
+/// Reads the logs of the checkpointed epochs from the log store.
+pub struct LogStoreReader<S: StateStore> {
+    table: StorageTable<S>,
+
+    /// The data types of the input columns.
+    data_types: Vec<DataType>,
+
+    /// All logs up to this epoch have been read. It starts from zero, so that the logs not
+    /// truncated before a failure or a scaling are replayed.
+    read_epoch: u64,
+}
+
+impl<S: StateStore> LogStoreReader<S> {
+    pub fn new(table_catalog: &Table, store: S, vnodes: Option<Arc<Bitmap>>) -> Self {
+        let table_columns = table_catalog
+            .columns
+            .iter()
+            .map(|col| ColumnDesc::from(col.column_desc.as_ref().unwrap()))
+            .collect_vec();
+        let column_ids = table_columns.iter().map(|col| col.column_id).collect_vec();
+        let data_types = table_columns[LOG_STORE_PREFIX_LEN..]
+            .iter()
+            .map(|col| col.data_type.clone())
+            .collect_vec();
+        let order_types = table_catalog
+            .pk
+            .iter()
+            .map(|col_order| {
+                OrderType::from_prost(&ProstOrderType::from_i32(col_order.order_type).unwrap())
+            })
+            .collect_vec();
+        let pk_indices = table_catalog
+            .pk
+            .iter()
+            .map(|col_order| col_order.index as usize)
+            .collect_vec();
+        let dist_key_indices = table_catalog
+            .distribution_key
+            .iter()
+            .map(|&k| k as usize)
+            .collect_vec();
+        let distribution = match vnodes {
+            Some(vnodes) => Distribution {
+                dist_key_indices,
+                vnodes,
+            },
+            None => Distribution::fallback(),
+        };
+        let value_indices = table_catalog
+            .value_indices
+            .iter()
+            .map(|&k| k as usize)
+            .collect_vec();
+
+        let table = StorageTable::new_partial(
+            store,
+            TableId::new(table_catalog.id),
+            table_columns,
+            column_ids,
+            order_types,
+            pk_indices,
+            distribution,
+            TableOption::build_table_option(&table_catalog.properties),
+            value_indices,
+        );
+
+        Self {
+            table,
+            data_types,
+            read_epoch: 0,
+        }
+    }
+
+    /// Reads the logs of the epochs up to the given checkpoint epoch in order, after waiting for
+    /// the checkpoint to be committed. The logs are streamed from the state store one epoch at a
+    /// time, so that a sink fallen far behind doesn't load its whole backlog into memory.
+    #[try_stream(ok = EpochLog, error = StreamExecutorError)]
+    pub async fn read(&mut self, checkpoint_epoch: u64) {
+        let iter = self
+            .table
+            .batch_iter_with_pk_bounds(
+                HummockReadEpoch::Committed(checkpoint_epoch),
+                Row::empty(),
+                (
+                    Bound::Excluded(Some(ScalarImpl::Int64(self.read_epoch as i64))),
+                    Bound::Included(Some(ScalarImpl::Int64(checkpoint_epoch as i64))),
+                ),
+                None,
+            )
+            .await?;
+        pin_mut!(iter);
+
+        let mut current_log: Option<EpochLog> = None;
+        let mut rows = vec![];
+        while let Some(log_row) = iter.next_row().await? {
+            let epoch = *log_row.0[EPOCH_COLUMN_INDEX].as_ref().unwrap().as_int64() as u64;
+            let op = *log_row.0[OP_COLUMN_INDEX].as_ref().unwrap().as_int16();
+            debug_assert!(log_row.0[SEQ_ID_COLUMN_INDEX].is_some());
+
+            if current_log.as_ref().map_or(false, |log| log.epoch != epoch) {
+                let mut log = current_log.take().unwrap();
+                if !rows.is_empty() {
+                    log.chunks
+                        .push(StreamChunk::from_rows(&rows, &self.data_types));
+                    rows.clear();
+                }
+                yield log;
+            }
+            let log = current_log.get_or_insert_with(|| EpochLog {
+                epoch,
+                chunks: vec![],
+                log_rows: vec![],
+            });
+
+            rows.push((
+                Op::from_protobuf(&(op as i32))?,
+                Row::new(log_row.0[LOG_STORE_PREFIX_LEN..].to_vec()),
+            ));
+            log.log_rows.push(log_row);
+            if rows.len() >= LOG_STORE_CHUNK_SIZE {
+                log.chunks
+                    .push(StreamChunk::from_rows(&rows, &self.data_types));
+                rows.clear();
+            }
+        }
+        if let Some(mut log) = current_log {
+            if !rows.is_empty() {
+                log.chunks
+                    .push(StreamChunk::from_rows(&rows, &self.data_types));
+            }
+            yield log;
+        }
+
+        self.read_epoch = checkpoint_epoch;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use risingwave_common::array::StreamChunkTestExt;
+    use risingwave_common::catalog::ColumnId;
+    use risingwave_pb::plan_common::{ColumnCatalog, ColumnOrder};
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+
+    /// The log store of a sink whose input is `(v1 BIGINT, v2 BIGINT)` with stream key `v1`.
+    fn gen_log_store_table() -> Table {
+        let columns = [
+            DataType::Int64,
+            DataType::Int32,
+            DataType::Int16,
+            DataType::Int64,
+            DataType::Int64,
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, data_type)| ColumnCatalog {
+            column_desc: Some(
+                ColumnDesc::unnamed(ColumnId::new(i as i32), data_type).to_protobuf(),
+            ),
+            is_hidden: false,
+        })
+        .collect_vec();
+        let pk = [0, 1, 3]
+            .into_iter()
+            .map(|index| ColumnOrder {
+                order_type: ProstOrderType::Ascending as i32,
+                index,
+            })
+            .collect_vec();
+        Table {
+            id: 1,
+            columns,
+            pk,
+            value_indices: (0..5).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_store_read_and_truncate() {
+        let store = MemoryStateStore::new();
+        let table = gen_log_store_table();
+        let mut writer =
+            LogStoreWriter::new(StateTable::from_table_catalog(&table, store.clone(), None));
+        let mut reader = LogStoreReader::new(&table, store.clone(), None);
+
+        let epoch1 = EpochPair::new_test_epoch(1);
+        writer.init(epoch1);
+        let chunk1 = StreamChunk::from_pretty(
+            " I I
+            + 1 10
+            + 2 20",
+        );
+        writer.write_chunk(&chunk1);
+        let epoch2 = epoch1.inc();
+        writer.flush(epoch2).await.unwrap();
+
+        let chunk2 = StreamChunk::from_pretty(
+            "  I I
+            U- 1 10
+            U+ 1 11",
+        );
+        writer.write_chunk(&chunk2);
+        let epoch3 = epoch2.inc();
+        writer.flush(epoch3).await.unwrap();
+
+        let mut logs = reader
+            .read(epoch3.prev)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap()
+            .into_iter();
+        let log1 = logs.next().unwrap();
+        assert_eq!(log1.epoch, epoch2.prev);
+        assert_eq!(log1.chunks, vec![chunk1]);
+        let log2 = logs.next().unwrap();
+        assert_eq!(log2.epoch, epoch3.prev);
+        assert_eq!(log2.chunks, vec![chunk2.clone()]);
+        assert!(logs.next().is_none());
+
+        // Nothing new to read at the next checkpoint.
+        writer.truncate(log1.log_rows);
+        let epoch4 = epoch3.inc();
+        writer.flush(epoch4).await.unwrap();
+        assert!(reader
+            .read(epoch4.prev)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap()
+            .is_empty());
+
+        // A new reader replays the logs not truncated.
+        let mut reader = LogStoreReader::new(&table, store, None);
+        let logs: Vec<_> = reader.read(epoch4.prev).try_collect().await.unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].epoch, epoch3.prev);
+        assert_eq!(logs[0].chunks, vec![chunk2]);
+    }
+}
@@ -121,7 +121,7 @@ impl<S: StateStore> SortExecutor<S> {
         for msg in input {
             match msg? {
                 Message::Watermark(watermark) => {
-                    let Watermark { col_idx, val } = watermark.clone();
+                    let Watermark { col_idx, val, .. } = watermark.clone();
 
                     // Sort executor only sends a stream chunk to downstream when
                     // `self.sort_column_index` matches the watermark's column index. Otherwise, it
@@ -180,22 +180,35 @@ impl<S: StateStore> SortExecutor<S> {
 
                 Message::Chunk(chunk) => {
                     for (op, row_ref) in chunk.rows() {
+                        let row = row_ref.to_owned_row();
+                        let timestamp = row
+                            .0
+                            .get(self.sort_column_index)
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "column index {} out of range in row {:?}",
+                                    self.sort_column_index,
+                                    row
+                                )
+                            })?
+                            .clone();
+                        let pk = row.by_indices(&self.pk_indices);
                         match op {
-                            Op::Insert => {
+                            Op::Insert | Op::UpdateInsert => {
                                 // For insert operation, we buffer the record in memory.
-                                let row = row_ref.to_owned_row();
-                                let timestamp = row.0.get(self.sort_column_index).ok_or_else(|| {
-                                    anyhow!(
-                                        "column index {} out of range in row {:?}",
-                                        self.sort_column_index,
-                                        row
-                                    )
-                                })?.clone();
-                                let pk = row.by_indices(&self.pk_indices);
                                 self.buffer.insert((timestamp, pk), (row, false));
-                            },
-                            // Other operations are not supported currently.
-                            _ => unimplemented!("operations other than insert currently are not supported by sort executor")
+                            }
+                            Op::Delete | Op::UpdateDelete => {
+                                // For delete operation, we remove the record from memory, and also
+                                // from state store if it has been persisted. A record that is not
+                                // in the buffer has already been sent to downstream, which can not
+                                // be retracted since the output of sort executor is append-only.
+                                if let Some((row, persisted)) = self.buffer.remove(&(timestamp, pk))
+                                    && persisted
+                                {
+                                    self.state_table.delete(row);
+                                }
+                            }
                         }
                     }
                 }
@@ -362,8 +375,12 @@ mod tests {
         sort_executor.next().await.unwrap().unwrap();
 
         // Init watermark
-        tx.push_watermark(0, Some(ScalarImpl::Int64(0)));
-        tx.push_watermark(sort_column_index, Some(ScalarImpl::Int64(0)));
+        tx.push_watermark(0, DataType::Int64, Some(ScalarImpl::Int64(0)));
+        tx.push_watermark(
+            sort_column_index,
+            DataType::Int64,
+            Some(ScalarImpl::Int64(0)),
+        );
 
         // Consume the watermark
         sort_executor.next().await.unwrap().unwrap();
@@ -373,13 +390,13 @@ mod tests {
         tx.push_chunk(chunk1);
 
         // Push watermark1 on an irrelevant column
-        tx.push_watermark(0, watermark1.clone());
+        tx.push_watermark(0, DataType::Int64, watermark1.clone());
 
         // Consume the watermark
         sort_executor.next().await.unwrap().unwrap();
 
         // Push watermark1 on sorted column
-        tx.push_watermark(sort_column_index, watermark1);
+        tx.push_watermark(sort_column_index, DataType::Int64, watermark1);
 
         // Consume the data chunk
         let chunk_msg = sort_executor.next().await.unwrap().unwrap();
@@ -406,13 +423,13 @@ mod tests {
         sort_executor.next().await.unwrap().unwrap();
 
         // Push watermark2 on an irrelevant column
-        tx.push_watermark(0, watermark2.clone());
+        tx.push_watermark(0, DataType::Int64, watermark2.clone());
 
         // Consume the watermark
         sort_executor.next().await.unwrap().unwrap();
 
         // Push watermark2 on sorted column
-        tx.push_watermark(sort_column_index, watermark2);
+        tx.push_watermark(sort_column_index, DataType::Int64, watermark2);
 
         // Consume the data chunk
         let chunk_msg = sort_executor.next().await.unwrap().unwrap();
@@ -430,6 +447,62 @@ mod tests {
         sort_executor.next().await.unwrap().unwrap();
     }
 
+    #[tokio::test]
+    async fn test_sort_executor_with_delete() {
+        let sort_column_index = 1;
+        let chunk1 = StreamChunk::from_pretty(
+            " I I
+            + 1 1
+            + 2 2
+            + 3 6",
+        );
+        let chunk2 = StreamChunk::from_pretty(
+            "  I I
+            -  1 1
+            U- 3 6
+            U+ 3 4",
+        );
+        let watermark = Some(ScalarImpl::Int64(5));
+
+        let state_table = create_state_table();
+        let (mut tx, mut sort_executor) = create_executor(sort_column_index, state_table);
+
+        // Init barrier
+        tx.push_barrier(1, false);
+
+        // Consume the barrier
+        sort_executor.next().await.unwrap().unwrap();
+
+        // Push data chunk1
+        tx.push_chunk(chunk1);
+
+        // Push a checkpoint barrier to persist the records
+        tx.push_barrier(2, false);
+
+        // Consume the barrier
+        sort_executor.next().await.unwrap().unwrap();
+
+        // Push data chunk2, which deletes persisted records
+        tx.push_chunk(chunk2);
+
+        // Push watermark on sorted column
+        tx.push_watermark(sort_column_index, DataType::Int64, watermark);
+
+        // Consume the data chunk. The deleted records are not sent to downstream.
+        let chunk_msg = sort_executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk_msg.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I
+                + 2 2
+                + 3 4"
+            )
+        );
+
+        // Consume the watermark
+        sort_executor.next().await.unwrap().unwrap();
+    }
+
     #[tokio::test]
     async fn test_sort_executor_fail_over() {
         let sort_column_index = 1;
@@ -452,8 +525,12 @@ mod tests {
         sort_executor.next().await.unwrap().unwrap();
 
         // Init watermark
-        tx.push_watermark(0, Some(ScalarImpl::Int64(0)));
-        tx.push_watermark(sort_column_index, Some(ScalarImpl::Int64(0)));
+        tx.push_watermark(0, DataType::Int64, Some(ScalarImpl::Int64(0)));
+        tx.push_watermark(
+            sort_column_index,
+            DataType::Int64,
+            Some(ScalarImpl::Int64(0)),
+        );
 
         // Consume the watermark
         sort_executor.next().await.unwrap().unwrap();
@@ -479,7 +556,7 @@ mod tests {
         recovered_sort_executor.next().await.unwrap().unwrap();
 
         // Push watermark on sorted column
-        recovered_tx.push_watermark(sort_column_index, watermark);
+        recovered_tx.push_watermark(sort_column_index, DataType::Int64, watermark);
 
         // Consume the data chunk
         let chunk_msg = recovered_sort_executor.next().await.unwrap().unwrap();
@@ -13,29 +13,50 @@
 // limitations under the License.
 
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::Poll;
 
 use async_stack_trace::StackTrace;
 use either::Either;
-use futures::stream::{select_with_strategy, BoxStream, PollNext, SelectWithStrategy};
+use futures::stream::{
+    select, select_with_strategy, BoxStream, PollNext, Select, SelectWithStrategy,
+};
 use futures::{Stream, StreamExt, TryStreamExt};
 use futures_async_stream::try_stream;
+use risingwave_common::array::StreamChunk;
 use risingwave_common::bail;
+use risingwave_connector::source::SourceRateLimit;
 use risingwave_source::*;
 use tokio::sync::mpsc::UnboundedReceiver;
 
 use crate::executor::error::{StreamExecutorError, StreamExecutorResult};
+use crate::executor::rate_limiter::RateLimiter;
 use crate::executor::Barrier;
 
-type SourceReaderMessage = StreamExecutorResult<Either<Barrier, StreamChunkWithState>>;
+/// The chunks read by the [`SourceReaderStream`].
+#[derive(Debug)]
+pub(super) enum SourceChunk {
+    /// Read from the source, along with the states of the splits.
+    Source(StreamChunkWithState),
+    /// Written by the DML statements on a materialized source with a connector.
+    Dml(StreamChunk),
+}
+
+type SourceReaderMessage = StreamExecutorResult<Either<Barrier, SourceChunk>>;
 type SourceReaderArm = BoxStream<'static, SourceReaderMessage>;
-type SourceReaderStreamInner =
-    SelectWithStrategy<SourceReaderArm, SourceReaderArm, impl FnMut(&mut ()) -> PollNext, ()>;
+type SourceReaderStreamInner = SelectWithStrategy<
+    SourceReaderArm,
+    Select<SourceReaderArm, SourceReaderArm>,
+    impl FnMut(&mut ()) -> PollNext,
+    (),
+>;
 
 pub(super) struct SourceReaderStream {
     inner: SourceReaderStreamInner,
     /// Whether the source stream is paused.
     paused: bool,
+    /// Throttles the chunks from the source stream.
+    rate_limiter: Arc<RateLimiter>,
 }
 
 impl SourceReaderStream {
@@ -48,14 +69,21 @@ impl SourceReaderStream {
         bail!("barrier reader closed unexpectedly");
     }
 
-    /// Receive chunks and states from the source reader, hang up on error.
+    /// Receive chunks and states from the source reader, hang up on error. The chunks are
+    /// throttled by the `rate_limiter`, while the barriers are not.
     #[try_stream(ok = StreamChunkWithState, error = StreamExecutorError)]
-    async fn source_stream(stream: BoxSourceWithStateStream) {
+    async fn source_stream(stream: BoxSourceWithStateStream, rate_limiter: Arc<RateLimiter>) {
         // TODO: support stack trace for Stream
         #[for_await]
         for chunk in stream {
             match chunk {
-                Ok(chunk) => yield chunk,
+                Ok(chunk) => {
+                    rate_limiter
+                        .acquire(&chunk.chunk)
+                        .stack_trace("source_rate_limit")
+                        .await;
+                    yield chunk
+                }
                 Err(err) => {
                     error!("hang up stream reader due to polling error: {}", err);
                     futures::future::pending().stack_trace("source_error").await
@@ -64,30 +92,71 @@ impl SourceReaderStream {
         }
     }
 
+    /// Receive chunks from the DML channel. Unlike the source stream, the chunks are not throttled,
+    /// as DML statements wait for them to be taken.
+    #[try_stream(ok = StreamChunk, error = StreamExecutorError)]
+    async fn dml_stream(stream: BoxSourceWithStateStream) {
+        #[for_await]
+        for chunk in stream {
+            yield chunk.map_err(StreamExecutorError::connector_error)?.chunk;
+        }
+        bail!("dml reader closed unexpectedly");
+    }
+
     /// Convert this reader to a stream.
     pub fn new(
         barrier_receiver: UnboundedReceiver<Barrier>,
         source_stream: BoxSourceWithStateStream,
+        rate_limit: SourceRateLimit,
     ) -> Self {
+        let rate_limiter = Arc::new(RateLimiter::new(rate_limit));
         let barrier_receiver = Self::barrier_receiver(barrier_receiver);
-        let source_stream = Self::source_stream(source_stream);
+        let source_stream = Self::source_stream(source_stream, rate_limiter.clone());
+
+        // The chunks from the source and the DML channel are polled in turn, so that neither of
+        // them starves the other. There's no DML channel until `set_dml_stream` is called.
+        let data_stream = select(
+            source_stream
+                .map_ok(|chunk| Either::Right(SourceChunk::Source(chunk)))
+                .boxed(),
+            futures::stream::pending::<SourceReaderMessage>().boxed(),
+        );
 
         let inner = select_with_strategy(
             barrier_receiver.map_ok(Either::Left).boxed(),
-            source_stream.map_ok(Either::Right).boxed(),
-            // We prefer barrier on the left hand side over source chunks.
+            data_stream,
+            // We prefer barrier on the left hand side over source chunks, so that the chunks
+            // taken from the DML channel before a barrier are always emitted in its epoch.
             |_: &mut ()| PollNext::Left,
         );
 
         Self {
             inner,
             paused: false,
+            rate_limiter,
         }
     }
 
-    /// Replace the source stream with a new one for given `stream`. Used for split change.
+    /// Replace the source stream with a new one for given `stream`. Used for split change. The DML
+    /// stream is kept, so the chunks written to it are not lost.
     pub fn replace_source_stream(&mut self, stream: BoxSourceWithStateStream) {
-        *self.inner.get_mut().1 = Self::source_stream(stream).map_ok(Either::Right).boxed();
+        *self.inner.get_mut().1.get_mut().0 =
+            Self::source_stream(stream, self.rate_limiter.clone())
+                .map_ok(|chunk| Either::Right(SourceChunk::Source(chunk)))
+                .boxed();
+    }
+
+    /// Merge the chunks written by DML statements into the stream, which are read along with the
+    /// source stream and are paused together with it.
+    pub fn set_dml_stream(&mut self, stream: BoxSourceWithStateStream) {
+        *self.inner.get_mut().1.get_mut().1 = Self::dml_stream(stream)
+            .map_ok(|chunk| Either::Right(SourceChunk::Dml(chunk)))
+            .boxed();
+    }
+
+    /// Change the rate limits of the source stream, which takes effect immediately.
+    pub fn set_rate_limit(&self, rate_limit: SourceRateLimit) {
+        self.rate_limiter.set_rate_limit(rate_limit);
     }
 
     /// Pause the source stream.
@@ -138,7 +207,7 @@ mod tests {
             .unwrap()
             .into_stream();
 
-        let stream = SourceReaderStream::new(barrier_rx, source_stream);
+        let stream = SourceReaderStream::new(barrier_rx, source_stream, SourceRateLimit::default());
         pin_mut!(stream);
 
         macro_rules! next {
@@ -176,4 +245,60 @@ mod tests {
         // Then we can receive the chunk sent when the stream is paused.
         assert_matches!(next!().unwrap(), Either::Right(_));
     }
+
+    #[tokio::test]
+    async fn test_merge_dml_stream() {
+        let (barrier_tx, barrier_rx) = mpsc::unbounded_channel();
+
+        let source = TableSource::new(vec![]);
+        let source_stream = source.stream_reader(vec![]).await.unwrap().into_stream();
+        let dml_source = TableSource::new(vec![]);
+        let dml_stream = dml_source
+            .stream_reader(vec![])
+            .await
+            .unwrap()
+            .into_stream();
+
+        let stream = SourceReaderStream::new(barrier_rx, source_stream, SourceRateLimit::default());
+        pin_mut!(stream);
+        stream.set_dml_stream(dml_stream);
+
+        macro_rules! next {
+            () => {
+                stream
+                    .next()
+                    .now_or_never()
+                    .flatten()
+                    .map(|result| result.unwrap())
+            };
+        }
+
+        // Write a chunk through DML, and we should receive it.
+        dml_source.write_chunk(StreamChunk::default()).unwrap();
+        assert_matches!(next!().unwrap(), Either::Right(SourceChunk::Dml(_)));
+
+        // Write chunks to both of the streams, and then a barrier.
+        source.write_chunk(StreamChunk::default()).unwrap();
+        dml_source.write_chunk(StreamChunk::default()).unwrap();
+        barrier_tx.send(Barrier::new_test_barrier(1)).unwrap();
+
+        // The barrier is preferred, and then the chunks of both streams are received.
+        assert_matches!(next!().unwrap(), Either::Left(_));
+        let mut chunks = [next!().unwrap(), next!().unwrap()];
+        chunks.sort_by_key(|msg| matches!(msg, Either::Right(SourceChunk::Dml(_))));
+        assert_matches!(chunks[0], Either::Right(SourceChunk::Source(_)));
+        assert_matches!(chunks[1], Either::Right(SourceChunk::Dml(_)));
+        assert!(next!().is_none());
+
+        // Replace the source stream, and the chunks written through DML are still received.
+        dml_source.write_chunk(StreamChunk::default()).unwrap();
+        let new_source = TableSource::new(vec![]);
+        let new_source_stream = new_source
+            .stream_reader(vec![])
+            .await
+            .unwrap()
+            .into_stream();
+        stream.replace_source_stream(new_source_stream);
+        assert_matches!(next!().unwrap(), Either::Right(SourceChunk::Dml(_)));
+    }
 }
@@ -21,16 +21,23 @@ use futures_async_stream::try_stream;
 use risingwave_common::array::column::Column;
 use risingwave_common::array::stream_chunk::Ops;
 use risingwave_common::array::{ArrayBuilder, I64ArrayBuilder, Op, StreamChunk};
-use risingwave_common::catalog::{ColumnId, Schema, TableId};
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, Schema, TableId};
+use risingwave_common::types::{to_datum_ref, Datum};
 use risingwave_common::util::epoch::UNIX_SINGULARITY_DATE_EPOCH;
-use risingwave_connector::source::{ConnectorState, SplitId, SplitImpl, SplitMetaData};
+use risingwave_connector::source::{
+    ConnectorProperties, ConnectorState, SourceRateLimit, SplitId, SplitImpl, SplitMetaData,
+};
+use risingwave_expr::expr::BoxedExpression;
+use risingwave_hummock_sdk::HummockReadEpoch;
+use risingwave_pb::plan_common::ColumnCatalog as ProstColumnCatalog;
 use risingwave_source::connector_source::SourceContext;
 use risingwave_source::row_id::RowIdGenerator;
 use risingwave_source::*;
 use risingwave_storage::StateStore;
-use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
-use super::reader::SourceReaderStream;
+use super::reader::{SourceChunk, SourceReaderStream};
+use crate::common::InfallibleExpression;
 use crate::error::StreamResult;
 use crate::executor::error::StreamExecutorError;
 use crate::executor::monitor::StreamingMetrics;
@@ -70,9 +77,38 @@ pub struct SourceExecutor<S: StateStore> {
 
     state_cache: HashMap<SplitId, SplitImpl>,
 
+    /// The assigned splits left out of the reader until their parents are read to the end, e.g.
+    /// the Kinesis shards created by resharding, so that the records of a key are read in order.
+    pending_splits: HashSet<SplitId>,
+
+    /// The states of the splits since the last checkpoint, which are committed to the upstream
+    /// once the checkpoint is durable, if required by the connector.
+    offsets_to_commit: HashMap<SplitId, SplitImpl>,
+
+    /// Sends the states to commit along with the epoch of the checkpoint.
+    offset_commit_tx: Option<UnboundedSender<(u64, Vec<SplitImpl>)>>,
+
     #[expect(dead_code)]
     /// Expected barrier latency
     expected_barrier_latency_ms: u64,
+
+    /// Generators of the watermarks defined on the source columns.
+    watermark_generators: Vec<WatermarkGenerator>,
+
+    /// The rate limits of reading the source, which can be changed by `ALTER SOURCE`.
+    rate_limit: SourceRateLimit,
+
+    /// Whether to merge the rows written by DML statements into the output. Only takes effect on
+    /// connector sources, as table sources always read from the DML channel.
+    dml_enabled: bool,
+}
+
+/// Generates the watermark of a source column as the max value of `expr` evaluated on the rows
+/// seen so far.
+struct WatermarkGenerator {
+    col_idx: usize,
+    expr: BoxedExpression,
+    watermark: Datum,
 }
 
 impl<S: StateStore> SourceExecutor<S> {
@@ -92,6 +128,9 @@ impl<S: StateStore> SourceExecutor<S> {
         _op_info: String,
         streaming_metrics: Arc<StreamingMetrics>,
         expected_barrier_latency_ms: u64,
+        watermark_descs: Vec<(usize, BoxedExpression)>,
+        rate_limit: SourceRateLimit,
+        dml_enabled: bool,
     ) -> StreamResult<Self> {
         // Using vnode range start for row id generator.
         let vnode_id = vnodes.next_set_bit(0).unwrap_or(0);
@@ -113,7 +152,20 @@ impl<S: StateStore> SourceExecutor<S> {
             source_identify: "Table_".to_string() + &source_id.table_id().to_string(),
             split_state_store: state_table,
             state_cache: HashMap::new(),
+            pending_splits: HashSet::new(),
+            offsets_to_commit: HashMap::new(),
+            offset_commit_tx: None,
             expected_barrier_latency_ms,
+            watermark_generators: watermark_descs
+                .into_iter()
+                .map(|(col_idx, expr)| WatermarkGenerator {
+                    col_idx,
+                    expr,
+                    watermark: None,
+                })
+                .collect(),
+            rate_limit,
+            dml_enabled,
         })
     }
 
@@ -166,6 +218,58 @@ impl<S: StateStore> SourceExecutor<S> {
             chunk
         }
     }
+
+    /// Counts the rows emitted by this executor in the metrics.
+    fn report_output_rows(&self, chunk: &StreamChunk) {
+        self.metrics
+            .source_output_row_count
+            .with_label_values(&[self.source_identify.as_str()])
+            .inc_by(chunk.cardinality() as u64);
+    }
+
+    /// Drops the late rows, whose value in a watermark column is less than the current watermark,
+    /// and then advances the watermarks with the remaining rows. Returns the advanced watermarks.
+    fn apply_watermarks(&mut self, chunk: StreamChunk) -> (StreamChunk, Vec<Watermark>) {
+        if self.watermark_generators.is_empty() {
+            return (chunk, vec![]);
+        }
+
+        let (ops, columns, visibility) = chunk.into_inner();
+        let visibility: Bitmap = (0..ops.len())
+            .map(|i| {
+                visibility.as_ref().map_or(true, |vis| vis.is_set(i))
+                    && self.watermark_generators.iter().all(|generator| {
+                        let value = columns[generator.col_idx].array_ref().value_at(i);
+                        value.is_none() || value >= to_datum_ref(&generator.watermark)
+                    })
+            })
+            .collect();
+        let chunk = StreamChunk::new(ops, columns, Some(visibility));
+
+        let mut watermarks = vec![];
+        for generator in &mut self.watermark_generators {
+            let values = generator.expr.eval_infallible(chunk.data_chunk(), |err| {
+                self.ctx.on_compute_error(err, &self.identity)
+            });
+            let max = chunk
+                .visibility()
+                .unwrap()
+                .iter()
+                .enumerate()
+                .filter(|(_, visible)| *visible)
+                .filter_map(|(i, _)| values.value_at(i))
+                .max();
+            if let Some(max) = max && Some(max) > to_datum_ref(&generator.watermark) {
+                generator.watermark = Some(max.into_scalar_impl());
+                watermarks.push(Watermark::new(
+                    generator.col_idx,
+                    generator.expr.return_type(),
+                    generator.watermark.clone(),
+                ));
+            }
+        }
+        (chunk, watermarks)
+    }
 }
 
 impl<S: StateStore> SourceExecutor<S> {
@@ -219,6 +323,31 @@ impl<S: StateStore> SourceExecutor<S> {
         Ok(())
     }
 
+    /// Spawns a task committing the offsets of the splits to the upstream, after the epochs in
+    /// which they are persisted are committed.
+    fn spawn_offset_committer(&mut self, properties: ConnectorProperties) {
+        let (offset_commit_tx, mut offset_commit_rx) = unbounded_channel();
+        let store = self.split_state_store.store().clone();
+        let actor_id = self.ctx.id;
+        tokio::spawn(async move {
+            while let Some((epoch, splits)) = offset_commit_rx.recv().await {
+                if let Err(e) = store
+                    .try_wait_epoch(HummockReadEpoch::Committed(epoch))
+                    .await
+                {
+                    tracing::warn!(actor_id, "failed to wait for epoch {}: {}", epoch, e);
+                    continue;
+                }
+                // The offsets are committed again in the later checkpoints, so the failures are
+                // tolerable.
+                if let Err(e) = properties.commit_offsets(&splits).await {
+                    tracing::warn!(actor_id, "failed to commit the offsets of source: {}", e);
+                }
+            }
+        });
+        self.offset_commit_tx = Some(offset_commit_tx);
+    }
+
     async fn build_stream_source_reader(
         &mut self,
         source_desc: &SourceDescRef,
@@ -253,7 +382,7 @@ impl<S: StateStore> SourceExecutor<S> {
             .await
             .unwrap();
 
-        let source_desc = self
+        let mut source_desc = self
             .source_desc_builder
             .build()
             .await
@@ -266,6 +395,12 @@ impl<S: StateStore> SourceExecutor<S> {
             .map(|idx| source_desc.columns[idx].column_id)
             .and_then(|ref cid| self.column_ids.iter().position(|id| id.eq(cid)));
 
+        if let SourceImpl::Connector(source) = &source_desc.source {
+            if source.config.need_commit_offsets() {
+                self.spawn_offset_committer(source.config.clone());
+            }
+        }
+
         // If the first barrier is configuration change, then the source executor must be newly
         // created, and we should start with the paused state.
         let start_with_paused = barrier.is_update();
@@ -299,6 +434,7 @@ impl<S: StateStore> SourceExecutor<S> {
             }
         }
 
+        let boot_state = self.filter_ready_splits(boot_state).await?;
         let recover_state: ConnectorState = (!boot_state.is_empty()).then_some(boot_state);
 
         // todo: use epoch from msg to restore state from state store
@@ -308,11 +444,29 @@ impl<S: StateStore> SourceExecutor<S> {
             .await?;
 
         // Merge the chunks from source and the barriers into a single stream.
-        let mut stream = SourceReaderStream::new(barrier_receiver, source_chunk_reader);
+        let mut stream =
+            SourceReaderStream::new(barrier_receiver, source_chunk_reader, self.rate_limit);
         if start_with_paused {
             stream.pause_source();
         }
 
+        // Register the DML channel of the connector source. The returned desc holds the channel
+        // alive in the source manager as long as this executor is running.
+        let dml_source_desc = match &source_desc.source {
+            SourceImpl::Connector(_) if self.dml_enabled => Some(
+                self.source_desc_builder
+                    .build_dml_source()
+                    .map_err(StreamExecutorError::connector_error)?,
+            ),
+            _ => None,
+        };
+        if let Some(dml_source_desc) = &dml_source_desc {
+            let dml_reader = self
+                .build_stream_source_reader(dml_source_desc, None)
+                .await?;
+            stream.set_dml_stream(dml_reader);
+        }
+
         yield Message::Barrier(barrier);
 
         while let Some(msg) = stream.next().await {
@@ -327,8 +481,47 @@ impl<S: StateStore> SourceExecutor<S> {
                                 self.apply_split_change(&source_desc, &mut stream, actor_splits)
                                     .await?
                             }
+                            Mutation::SourceResetSplit(actor_splits) => {
+                                self.apply_split_reset(&source_desc, &mut stream, actor_splits)
+                                    .await?
+                            }
                             Mutation::Pause => stream.pause_source(),
                             Mutation::Resume => stream.resume_source(),
+                            Mutation::SchemaChange {
+                                source_id,
+                                source_columns,
+                                mview_changes,
+                                ..
+                            } if *source_id == self.source_id => {
+                                if matches!(source_desc.source, SourceImpl::Table(_)) {
+                                    self.apply_schema_change(
+                                        &source_desc,
+                                        &mut stream,
+                                        source_columns,
+                                    )
+                                    .await?
+                                } else if mview_changes
+                                    .iter()
+                                    .any(|change| change.source_actor_ids.contains(&self.ctx.id))
+                                    && let Some(new_source_desc) = self
+                                        .apply_source_columns_change(&mut stream, source_columns)
+                                        .await?
+                                {
+                                    source_desc = new_source_desc;
+                                }
+                            }
+                            Mutation::SourceRateLimit {
+                                source_id,
+                                rate_limit,
+                            } if *source_id == self.source_id => {
+                                tracing::info!(
+                                    actor_id = self.ctx.id,
+                                    "change the rate limits of source to {:?}",
+                                    rate_limit
+                                );
+                                self.rate_limit = *rate_limit;
+                                stream.set_rate_limit(*rate_limit);
+                            }
                             Mutation::Update {
                                 vnode_bitmaps,
                                 actor_splits,
@@ -354,15 +547,25 @@ impl<S: StateStore> SourceExecutor<S> {
                             _ => {}
                         }
                     }
+                    if !self.pending_splits.is_empty() {
+                        self.start_ready_splits(&source_desc, &mut stream).await?;
+                    }
                     self.take_snapshot(epoch).await?;
+                    if let Some(offset_commit_tx) = &self.offset_commit_tx {
+                        self.offsets_to_commit.extend(self.state_cache.drain());
+                        if barrier.checkpoint && !self.offsets_to_commit.is_empty() {
+                            let splits = self.offsets_to_commit.drain().map(|(_, s)| s).collect();
+                            let _ = offset_commit_tx.send((epoch.prev, splits));
+                        }
+                    }
                     self.state_cache.clear();
                     yield Message::Barrier(barrier);
                 }
 
-                Either::Right(StreamChunkWithState {
-                    mut chunk,
+                Either::Right(SourceChunk::Source(StreamChunkWithState {
+                    chunk,
                     split_offset_mapping,
-                }) => {
+                })) => {
                     if let Some(mapping) = split_offset_mapping {
                         let state: HashMap<_, _> = mapping
                             .iter()
@@ -384,7 +587,7 @@ impl<S: StateStore> SourceExecutor<S> {
                     }
 
                     // Refill row id column for source.
-                    chunk = match &source_desc.source {
+                    let chunk = match &source_desc.source {
                         SourceImpl::Connector(_) => {
                             self.refill_row_id_column(chunk, true, row_id_index).await
                         }
@@ -393,11 +596,25 @@ impl<S: StateStore> SourceExecutor<S> {
                         }
                     };
 
-                    self.metrics
-                        .source_output_row_count
-                        .with_label_values(&[self.source_identify.as_str()])
-                        .inc_by(chunk.cardinality() as u64);
+                    let (chunk, watermarks) = self.apply_watermarks(chunk);
+                    self.report_output_rows(&chunk);
                     yield Message::Chunk(chunk);
+                    for watermark in watermarks {
+                        yield Message::Watermark(watermark);
+                    }
+                }
+
+                // The rows deleted or updated by DML statements keep their row ids, the same as
+                // the ones written to tables.
+                Either::Right(SourceChunk::Dml(chunk)) => {
+                    let chunk = self.refill_row_id_column(chunk, false, row_id_index).await;
+
+                    let (chunk, watermarks) = self.apply_watermarks(chunk);
+                    self.report_output_rows(&chunk);
+                    yield Message::Chunk(chunk);
+                    for watermark in watermarks {
+                        yield Message::Watermark(watermark);
+                    }
                 }
             }
         }
@@ -425,6 +642,210 @@ impl<S: StateStore> SourceExecutor<S> {
         Ok(())
     }
 
+    /// Overwrite the states of the splits of this actor with the given ones, which are persisted
+    /// in the snapshot of the current barrier, and rebuild the reader from them. The splits not
+    /// given are read from their current states.
+    async fn apply_split_reset(
+        &mut self,
+        source_desc: &SourceDescRef,
+        stream: &mut SourceReaderStream,
+        mapping: &HashMap<ActorId, Vec<SplitImpl>>,
+    ) -> StreamExecutorResult<()> {
+        let Some(reset_splits) = mapping.get(&self.ctx.id) else {
+            return Ok(());
+        };
+        let reset_splits: HashMap<_, _> = reset_splits
+            .iter()
+            .map(|split| (split.id(), split.clone()))
+            .collect();
+
+        let mut target_state = Vec::with_capacity(self.stream_source_splits.len());
+        for split in self.stream_source_splits.clone() {
+            let state = if let Some(reset_split) = reset_splits.get(&split.id()) {
+                self.state_cache.insert(split.id(), reset_split.clone());
+                reset_split.clone()
+            } else {
+                self.current_state(split).await?
+            };
+            target_state.push(state);
+        }
+
+        self.replace_stream_reader_with_target_state(source_desc, stream, target_state)
+            .await
+    }
+
+    /// Returns the latest state of the assigned split, which is the initial one if never read.
+    async fn current_state(&self, split: SplitImpl) -> StreamExecutorResult<SplitImpl> {
+        if let Some(state) = self.state_cache.get(&split.id()) {
+            return Ok(state.clone());
+        }
+        Ok(self
+            .split_state_store
+            .get_split_state(split.id())
+            .await?
+            .unwrap_or(split))
+    }
+
+    /// Whether all the parents of the split are read to the end, by this or other actors of the
+    /// source, whose states are shared in the state table.
+    async fn are_parents_finished(&self, split: &SplitImpl) -> StreamExecutorResult<bool> {
+        for parent_id in split.parent_ids() {
+            let parent_state = match self.state_cache.get(&parent_id) {
+                Some(state) => Some(state.clone()),
+                None => self.split_state_store.get_split_state(parent_id).await?,
+            };
+            if !parent_state.map_or(false, |state| state.is_finished()) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns the states of the splits to read, leaving out the ones whose parents are not read
+    /// to the end, which are recorded as pending.
+    async fn filter_ready_splits(
+        &mut self,
+        states: Vec<SplitImpl>,
+    ) -> StreamExecutorResult<Vec<SplitImpl>> {
+        self.pending_splits.clear();
+        let mut ready_states = Vec::with_capacity(states.len());
+        for state in states {
+            if self.are_parents_finished(&state).await? {
+                ready_states.push(state);
+            } else {
+                self.pending_splits.insert(state.id());
+            }
+        }
+        if !self.pending_splits.is_empty() {
+            tracing::info!(
+                actor_id = self.ctx.id,
+                "splits {:?} are pending on their parents",
+                self.pending_splits
+            );
+        }
+        Ok(ready_states)
+    }
+
+    /// Rebuilds the reader from the current states of the splits once any pending split is ready
+    /// to read.
+    async fn start_ready_splits(
+        &mut self,
+        source_desc: &SourceDescRef,
+        stream: &mut SourceReaderStream,
+    ) -> StreamExecutorResult<()> {
+        let mut any_ready = false;
+        for split in &self.stream_source_splits {
+            if self.pending_splits.contains(&split.id()) && self.are_parents_finished(split).await?
+            {
+                any_ready = true;
+                break;
+            }
+        }
+        if !any_ready {
+            return Ok(());
+        }
+
+        let mut target_state = Vec::with_capacity(self.stream_source_splits.len());
+        for split in self.stream_source_splits.clone() {
+            target_state.push(self.current_state(split).await?);
+        }
+        self.replace_stream_reader_with_target_state(source_desc, stream, target_state)
+            .await
+    }
+
+    /// Append the newly added columns to the output and rebuild the table reader to read them.
+    /// Dropped columns are only hidden in the catalog, so they are still read and emitted.
+    async fn apply_schema_change(
+        &mut self,
+        source_desc: &SourceDescRef,
+        stream: &mut SourceReaderStream,
+        columns: &[ProstColumnCatalog],
+    ) -> StreamExecutorResult<()> {
+        let SourceImpl::Table(table_source) = &source_desc.source else {
+            unreachable!("schema change is only supported on table sources")
+        };
+
+        let column_descs = columns
+            .iter()
+            .map(|c| ColumnDesc::from(c.column_desc.as_ref().unwrap()))
+            .collect_vec();
+        let added_columns = column_descs
+            .iter()
+            .filter(|c| !self.column_ids.contains(&c.column_id))
+            .cloned()
+            .collect_vec();
+        if added_columns.is_empty() {
+            return Ok(());
+        }
+
+        tracing::info!(
+            "actor {:?} apply schema change, add columns {:?}",
+            self.ctx.id,
+            added_columns
+        );
+
+        // The table source may be shared by several actors on this node, updating it repeatedly
+        // is harmless as the columns are the same.
+        table_source.update_column_descs(column_descs);
+        for column in added_columns {
+            self.schema.fields.push(Field::from(&column));
+            self.column_ids.push(column.column_id);
+        }
+
+        // Chunks not taken from the old reader will be dropped, and the DML statements writing
+        // them will fail.
+        let reader = self.build_stream_source_reader(source_desc, None).await?;
+        stream.replace_source_stream(reader);
+
+        Ok(())
+    }
+
+    /// Append the columns added to the connector source to the output, and rebuild the reader from
+    /// the current states of the splits to parse them. Returns the new source desc with the added
+    /// columns. Only the actors of the views re-planned with the change are changed, while others
+    /// reading from the source keep their columns.
+    async fn apply_source_columns_change(
+        &mut self,
+        stream: &mut SourceReaderStream,
+        columns: &[ProstColumnCatalog],
+    ) -> StreamExecutorResult<Option<SourceDescRef>> {
+        let added_columns = columns
+            .iter()
+            .map(|c| ColumnDesc::from(c.column_desc.as_ref().unwrap()))
+            .filter(|c| !self.column_ids.contains(&c.column_id))
+            .collect_vec();
+        if added_columns.is_empty() {
+            return Ok(None);
+        }
+
+        tracing::info!(
+            "actor {:?} apply schema change, add columns {:?}",
+            self.ctx.id,
+            added_columns
+        );
+
+        // The parser is created with the latest upstream schema, which has the added fields.
+        self.source_desc_builder.set_columns(columns.to_vec());
+        let source_desc = self
+            .source_desc_builder
+            .build()
+            .await
+            .map_err(StreamExecutorError::connector_error)?;
+        for column in added_columns {
+            self.schema.fields.push(Field::from(&column));
+            self.column_ids.push(column.column_id);
+        }
+
+        let mut target_state = Vec::with_capacity(self.stream_source_splits.len());
+        for split in self.stream_source_splits.clone() {
+            target_state.push(self.current_state(split).await?);
+        }
+        self.replace_stream_reader_with_target_state(&source_desc, stream, target_state)
+            .await?;
+
+        Ok(Some(source_desc))
+    }
+
     async fn replace_stream_reader_with_target_state(
         &mut self,
         source_desc: &SourceDescRef,
@@ -438,8 +859,12 @@ impl<S: StateStore> SourceExecutor<S> {
         );
 
         // Replace the source reader with a new one of the new state.
+        let ready_state = self.filter_ready_splits(target_state.clone()).await?;
         let reader = self
-            .build_stream_source_reader(source_desc, Some(target_state.clone()))
+            .build_stream_source_reader(
+                source_desc,
+                (!ready_state.is_empty()).then_some(ready_state),
+            )
             .await?;
         stream.replace_source_stream(reader);
 
@@ -492,6 +917,9 @@ mod tests {
     use risingwave_common::util::epoch::EpochPair;
     use risingwave_common::util::sort_util::{OrderPair, OrderType};
     use risingwave_connector::source::datagen::DatagenSplit;
+    use risingwave_connector::source::kinesis::split::{
+        KinesisOffset, KinesisSplit, KINESIS_SHARD_END_OFFSET,
+    };
     use risingwave_pb::catalog::{ColumnIndex as ProstColumnIndex, StreamSourceInfo};
     use risingwave_pb::data::data_type::TypeName;
     use risingwave_pb::data::DataType as ProstDataType;
@@ -568,6 +996,9 @@ mod tests {
             "SourceExecutor".to_string(),
             Arc::new(StreamingMetrics::new(prometheus::Registry::new())),
             u64::MAX,
+            vec![],
+            SourceRateLimit::default(),
+            false,
         )
         .unwrap();
         let mut executor = Box::new(executor).execute();
@@ -670,6 +1101,9 @@ mod tests {
             "SourceExecutor".to_string(),
             Arc::new(StreamingMetrics::unused()),
             u64::MAX,
+            vec![],
+            SourceRateLimit::default(),
+            false,
         )
         .unwrap();
         let mut executor = Box::new(executor).execute();
@@ -738,6 +1172,8 @@ mod tests {
         let stream_source_info = StreamSourceInfo {
             row_format: ProstRowFormatType::Json as i32,
             row_schema_location: "".to_string(),
+            use_schema_registry: false,
+            ..Default::default()
         };
         let source_manager = Arc::new(TableSourceManager::default());
         SourceDescBuilder::new(
@@ -797,6 +1233,9 @@ mod tests {
             "SourceExecutor".to_string(),
             Arc::new(StreamingMetrics::unused()),
             u64::MAX,
+            vec![],
+            SourceRateLimit::default(),
+            false,
         )
         .unwrap();
 
@@ -903,4 +1342,101 @@ mod tests {
         let barrier = Barrier::new_test_barrier(4).with_mutation(Mutation::Resume);
         barrier_tx.send(barrier).unwrap();
     }
+
+    #[tokio::test]
+    async fn test_pending_child_splits() {
+        let source_table_id = TableId::default();
+        let source_builder = mock_source_desc_builder(source_table_id);
+        let mut source_state_handler = SourceStateTableHandler::from_table_catalog(
+            &default_source_internal_table(0x2333),
+            MemoryStateStore::new(),
+        );
+        source_state_handler.init_epoch(EpochPair::new_test_epoch(1));
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int32),
+        ]);
+        let (_barrier_tx, barrier_rx) = unbounded_channel::<Barrier>();
+        let vnodes = Bitmap::from_bytes(Bytes::from_static(&[0b11111111]));
+
+        let mut source_exec = SourceExecutor::new(
+            ActorContext::create(0),
+            source_builder,
+            source_table_id,
+            vnodes,
+            source_state_handler,
+            vec![ColumnId::from(0), ColumnId::from(1)],
+            schema,
+            vec![0],
+            barrier_rx,
+            1,
+            1,
+            "SourceExecutor".to_string(),
+            Arc::new(StreamingMetrics::unused()),
+            u64::MAX,
+            vec![],
+            SourceRateLimit::default(),
+            false,
+        )
+        .unwrap();
+
+        // Shard 0 is split into 2 and 3, which are then merged into 4. Shard 3 is read by another
+        // actor.
+        let shard = |shard_id: &str, parents: &[&str]| {
+            SplitImpl::Kinesis(
+                KinesisSplit::new(
+                    shard_id.into(),
+                    KinesisOffset::Earliest,
+                    KinesisOffset::None,
+                )
+                .with_parent_shard_ids(parents.iter().map(|&p| p.into()).collect()),
+            )
+        };
+        let shard_0 = shard("shardId-0", &[]);
+        let shard_2 = shard("shardId-2", &["shardId-0"]);
+        let shard_3 = shard("shardId-3", &["shardId-0"]);
+        let shard_4 = shard("shardId-4", &["shardId-2", "shardId-3"]);
+        let split_ids = |splits: Vec<SplitImpl>| splits.iter().map(|s| s.id()).collect_vec();
+
+        let ready = (source_exec.filter_ready_splits(vec![shard_0.clone(), shard_2.clone()]))
+            .await
+            .unwrap();
+        assert_eq!(split_ids(ready), vec![shard_0.id()]);
+        assert_eq!(source_exec.pending_splits, HashSet::from([shard_2.id()]));
+
+        // The shard read to the end by this actor is found in the state cache.
+        let shard_0_end = shard_0.update(KINESIS_SHARD_END_OFFSET.to_string());
+        assert!(shard_0_end.is_finished());
+        source_exec
+            .state_cache
+            .insert(shard_0.id(), shard_0_end.clone());
+        let ready = (source_exec.filter_ready_splits(vec![shard_0_end, shard_2.clone()]))
+            .await
+            .unwrap();
+        assert_eq!(split_ids(ready), vec![shard_0.id(), shard_2.id()]);
+        assert!(source_exec.pending_splits.is_empty());
+
+        // The merged shard waits for both parents. The shard read by another actor is found in
+        // the state table.
+        let shard_2_end = shard_2.update(KINESIS_SHARD_END_OFFSET.to_string());
+        source_exec.state_cache.insert(shard_2.id(), shard_2_end);
+        let ready = (source_exec.filter_ready_splits(vec![shard_4.clone()]))
+            .await
+            .unwrap();
+        assert!(ready.is_empty());
+        assert_eq!(source_exec.pending_splits, HashSet::from([shard_4.id()]));
+
+        (source_exec.split_state_store)
+            .take_snapshot(vec![shard_3.update(
+                "49629139817504901062972448413535783695568426186596941842".to_string(),
+            )])
+            .await
+            .unwrap();
+        assert!(!source_exec.are_parents_finished(&shard_4).await.unwrap());
+        (source_exec.split_state_store)
+            .take_snapshot(vec![shard_3.update(KINESIS_SHARD_END_OFFSET.to_string())])
+            .await
+            .unwrap();
+        assert!(source_exec.are_parents_finished(&shard_4).await.unwrap());
+    }
 }
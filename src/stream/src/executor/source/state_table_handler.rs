@@ -34,15 +34,22 @@ use crate::executor::StreamExecutorResult;
 #[derive(Clone)]
 pub struct SourceStateTableHandler<S: StateStore> {
     pub state_store: StateTable<S>,
+    store: S,
 }
 
 impl<S: StateStore> SourceStateTableHandler<S> {
     pub fn from_table_catalog(table_catalog: &ProstTable, store: S) -> Self {
         Self {
-            state_store: StateTable::from_table_catalog(table_catalog, store, None),
+            state_store: StateTable::from_table_catalog(table_catalog, store.clone(), None),
+            store,
         }
     }
 
+    /// The state store of the table, by which the epochs are waited to be committed.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
     pub fn init_epoch(&mut self, epoch: EpochPair) {
         self.state_store.init_epoch(epoch);
     }
@@ -101,7 +108,16 @@ impl<S: StateStore> SourceStateTableHandler<S> {
         &mut self,
         stream_source_split: &SplitImpl,
     ) -> StreamExecutorResult<Option<SplitImpl>> {
-        Ok(match self.get(stream_source_split.id()).await? {
+        self.get_split_state(stream_source_split.id()).await
+    }
+
+    /// Returns the persisted state of the split, which may be written by another actor of the
+    /// source.
+    pub async fn get_split_state(
+        &self,
+        split_id: SplitId,
+    ) -> StreamExecutorResult<Option<SplitImpl>> {
+        Ok(match self.get(split_id).await? {
             None => None,
             Some(row) => match row.0.get(1).unwrap() {
                 Some(ScalarImpl::Utf8(s)) => Some(SplitImpl::restore_from_bytes(s.as_bytes())?),
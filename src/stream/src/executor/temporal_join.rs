@@ -0,0 +1,431 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Row, RowRef};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{DataType, ToOwnedDatum};
+use risingwave_expr::expr::BoxedExpression;
+use risingwave_hummock_sdk::HummockReadEpoch;
+use risingwave_pb::plan_common::JoinType;
+use risingwave_storage::table::batch_table::storage_table::StorageTable;
+use risingwave_storage::table::TableIter;
+use risingwave_storage::StateStore;
+
+use super::barrier_align::*;
+use super::error::{StreamExecutorError, StreamExecutorResult};
+use super::monitor::StreamingMetrics;
+use super::{
+    ActorContextRef, BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef,
+};
+use crate::cache::{EvictableHashMap, ExecutorCache, LruManagerRef};
+use crate::common::{InfallibleExpression, StreamChunkBuilder};
+use crate::executor::expect_first_barrier_from_aligned_stream;
+
+/// [`TemporalJoinExecutor`] joins an append-only left stream with the latest version of a table at
+/// processing time. Each left row is looked up in the storage of the right table by a prefix of its
+/// primary key, reading the epoch of the last barrier.
+///
+/// The right input is the upstream of the table without snapshot. Its changes never produce any
+/// output, but only invalidate the cached rows of the updated keys on the next barrier.
+pub struct TemporalJoinExecutor<S: StateStore> {
+    ctx: ActorContextRef,
+    left: Option<BoxedExecutor>,
+    right: Option<BoxedExecutor>,
+
+    /// The table to look up, whose output columns are the same as the right input.
+    right_table: StorageTable<S>,
+
+    /// Join keys of the left side, in the order of the primary key prefix of the right table.
+    left_key_indices: Vec<usize>,
+    /// Join keys of the right side, which are the primary key prefix of the right table.
+    right_key_indices: Vec<usize>,
+    null_safe: Vec<bool>,
+
+    join_type: JoinType,
+    condition: Option<BoxedExpression>,
+
+    /// The lookup results of the right table, keyed by the primary key prefix.
+    cache: ExecutorCache<Row, Vec<Row>>,
+
+    output_data_types: Vec<DataType>,
+    left_to_output: Vec<(usize, usize)>,
+    right_to_output: Vec<(usize, usize)>,
+
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    metrics: Arc<StreamingMetrics>,
+    /// The maximum size of the chunk produced by executor at a time.
+    chunk_size: usize,
+}
+
+impl<S: StateStore> TemporalJoinExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        ctx: ActorContextRef,
+        left: BoxedExecutor,
+        right: BoxedExecutor,
+        right_table: StorageTable<S>,
+        left_key_indices: Vec<usize>,
+        right_key_indices: Vec<usize>,
+        null_safe: Vec<bool>,
+        join_type: JoinType,
+        condition: Option<BoxedExpression>,
+        output_indices: Vec<usize>,
+        schema: Schema,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        lru_manager: Option<LruManagerRef>,
+        cache_size: usize,
+        metrics: Arc<StreamingMetrics>,
+        chunk_size: usize,
+    ) -> Self {
+        assert!(matches!(join_type, JoinType::Inner | JoinType::LeftOuter));
+        assert_eq!(left_key_indices.len(), right_key_indices.len());
+
+        let (left_to_output, right_to_output) = StreamChunkBuilder::get_i2o_mapping(
+            output_indices.iter().cloned(),
+            left.schema().len(),
+            right.schema().len(),
+        );
+
+        let cache = if let Some(lru_manager) = lru_manager {
+            ExecutorCache::Managed(lru_manager.create_cache())
+        } else {
+            ExecutorCache::Local(EvictableHashMap::new(cache_size))
+        };
+
+        Self {
+            ctx,
+            left: Some(left),
+            right: Some(right),
+            right_table,
+            left_key_indices,
+            right_key_indices,
+            null_safe,
+            join_type,
+            condition,
+            cache,
+            output_data_types: schema.data_types(),
+            left_to_output,
+            right_to_output,
+            schema,
+            pk_indices,
+            identity: format!("TemporalJoinExecutor {:X}", executor_id),
+            metrics,
+            chunk_size,
+        }
+    }
+
+    /// Looks up the right table by the key at the given epoch, or from the cache if present.
+    async fn lookup(&mut self, key: Row, epoch: u64) -> StreamExecutorResult<Vec<Row>> {
+        if let Some(rows) = self.cache.get(&key) {
+            return Ok(rows.clone());
+        }
+
+        let mut rows = vec![];
+        {
+            let iter = self
+                .right_table
+                .batch_iter_with_pk_bounds(HummockReadEpoch::NoWait(epoch), &key, .., None)
+                .await?;
+            pin_mut!(iter);
+            while let Some(row) = iter.next_row().await? {
+                rows.push(row);
+            }
+        }
+
+        self.cache.push(key, rows.clone());
+        Ok(rows)
+    }
+
+    fn check_condition(&self, left_row: &RowRef<'_>, right_row: &Row) -> bool {
+        let Some(condition) = &self.condition else {
+            return true;
+        };
+        let row = Row(left_row
+            .values()
+            .map(|datum| datum.to_owned_datum())
+            .chain(right_row.values().cloned())
+            .collect());
+        condition
+            .eval_row_infallible(&row, |err| self.ctx.on_compute_error(err, &self.identity))
+            .map(|s| *s.as_bool())
+            .unwrap_or(false)
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn into_stream(mut self) {
+        let left = self.left.take().unwrap();
+        let right = self.right.take().unwrap();
+        let aligned_stream = barrier_align(
+            left.execute(),
+            right.execute(),
+            self.ctx.id,
+            self.metrics.clone(),
+        );
+        pin_mut!(aligned_stream);
+
+        let barrier = expect_first_barrier_from_aligned_stream(&mut aligned_stream).await?;
+        // The left rows are looked up at the epoch of the last barrier, whose data is always
+        // complete in the storage.
+        let mut read_epoch = barrier.epoch.prev;
+        self.cache.update_epoch(barrier.epoch.curr);
+        yield Message::Barrier(barrier);
+
+        // The keys updated by the right side in the current epoch, whose cache entries become
+        // stale after the next barrier.
+        let mut updated_keys = HashSet::new();
+
+        #[for_await]
+        for msg in aligned_stream {
+            match msg? {
+                AlignedMessage::Left(chunk) => {
+                    let mut builder = StreamChunkBuilder::new(
+                        self.chunk_size,
+                        &self.output_data_types,
+                        self.left_to_output.clone(),
+                        self.right_to_output.clone(),
+                    )?;
+
+                    for (op, left_row) in chunk.rows() {
+                        let key = left_row.row_by_indices(&self.left_key_indices);
+                        let has_null = key
+                            .values()
+                            .zip_eq(&self.null_safe)
+                            .any(|(datum, &null_safe)| datum.is_none() && !null_safe);
+                        let right_rows = if has_null {
+                            vec![]
+                        } else {
+                            self.lookup(key, read_epoch).await?
+                        };
+
+                        let mut matched = false;
+                        for right_row in right_rows {
+                            if self.check_condition(&left_row, &right_row) {
+                                matched = true;
+                                if let Some(chunk) =
+                                    builder.append_row(op, &left_row, &right_row)?
+                                {
+                                    yield Message::Chunk(chunk);
+                                }
+                            }
+                        }
+                        if !matched && self.join_type == JoinType::LeftOuter {
+                            if let Some(chunk) = builder.append_row_update(op, &left_row)? {
+                                yield Message::Chunk(chunk);
+                            }
+                        }
+                    }
+
+                    if let Some(chunk) = builder.take()? && chunk.cardinality() > 0 {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                AlignedMessage::WatermarkLeft(watermark) => {
+                    for &(left_idx, output_idx) in &self.left_to_output {
+                        if left_idx == watermark.col_idx {
+                            yield Message::Watermark(watermark.clone().with_idx(output_idx));
+                        }
+                    }
+                }
+                AlignedMessage::WatermarkRight(_) => {}
+                AlignedMessage::Right(chunk) => {
+                    for (_, right_row) in chunk.rows() {
+                        updated_keys.insert(right_row.row_by_indices(&self.right_key_indices));
+                    }
+                }
+                AlignedMessage::Barrier(barrier) => {
+                    for key in updated_keys.drain() {
+                        self.cache.pop(&key);
+                    }
+                    if let Some(vnode_bitmap) = barrier.as_update_vnode_bitmap(self.ctx.id) {
+                        let _ = self.right_table.update_vnode_bitmap(vnode_bitmap);
+                        self.cache.clear();
+                    }
+                    self.cache.evict();
+                    self.cache.update_epoch(barrier.epoch.curr);
+                    read_epoch = barrier.epoch.prev;
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for TemporalJoinExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.into_stream().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, TableId};
+    use risingwave_common::util::epoch::EpochPair;
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_storage::memory::MemoryStateStore;
+    use risingwave_storage::table::streaming_table::state_table::StateTable;
+
+    use super::*;
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::ActorContext;
+
+    #[tokio::test]
+    async fn test_temporal_left_outer_join() {
+        let store = MemoryStateStore::new();
+        let table_id = TableId::new(0x42);
+        let column_descs = vec![
+            ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+            ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),
+        ];
+        let mut state_table = StateTable::new_without_distribution(
+            store.clone(),
+            table_id,
+            column_descs.clone(),
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+        let right_table = StorageTable::for_test(
+            store,
+            table_id,
+            column_descs,
+            vec![OrderType::Ascending],
+            vec![0],
+        );
+
+        // The table contains `(1, 10)` at epoch 1.
+        state_table.init_epoch(EpochPair::new_test_epoch(1));
+        state_table.insert(Row(vec![Some(1_i64.into()), Some(10_i64.into())]));
+        state_table
+            .commit_for_test(EpochPair::new_test_epoch(2))
+            .await
+            .unwrap();
+
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let (mut tx_l, source_l) = MockSource::channel(schema.clone(), vec![0]);
+        let (mut tx_r, source_r) = MockSource::channel(schema, vec![0]);
+        let output_schema = Schema::new(vec![Field::unnamed(DataType::Int64); 4]);
+        let mut temporal_join = Box::new(TemporalJoinExecutor::new(
+            ActorContext::create(123),
+            Box::new(source_l),
+            Box::new(source_r),
+            right_table,
+            vec![0],
+            vec![0],
+            vec![false],
+            JoinType::LeftOuter,
+            None,
+            vec![0, 1, 2, 3],
+            output_schema,
+            vec![0],
+            1,
+            None,
+            1024,
+            Arc::new(StreamingMetrics::unused()),
+            1024,
+        ))
+        .execute();
+
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        temporal_join.next().await.unwrap().unwrap();
+
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             + 1 4
+             + 2 5",
+        ));
+        let chunk = temporal_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 1 4 1 10
+                + 2 5 . ."
+            )
+        );
+
+        // The table is updated in epoch 2, which is not visible until the next barrier.
+        state_table.update(
+            Row(vec![Some(1_i64.into()), Some(10_i64.into())]),
+            Row(vec![Some(1_i64.into()), Some(11_i64.into())]),
+        );
+        state_table.insert(Row(vec![Some(2_i64.into()), Some(20_i64.into())]));
+        state_table
+            .commit_for_test(EpochPair::new_test_epoch(3))
+            .await
+            .unwrap();
+        tx_r.push_chunk(StreamChunk::from_pretty(
+            "   I I
+             U- 1 10
+             U+ 1 11
+             +  2 20",
+        ));
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             + 1 6",
+        ));
+        let chunk = temporal_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 1 6 1 10"
+            )
+        );
+
+        tx_l.push_barrier(3, false);
+        tx_r.push_barrier(3, false);
+        temporal_join.next().await.unwrap().unwrap();
+
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             + 1 7
+             + 2 8",
+        ));
+        let chunk = temporal_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 1 7 1 11
+                + 2 8 2 20"
+            )
+        );
+    }
+}
@@ -15,11 +15,11 @@
 use futures::StreamExt;
 use futures_async_stream::try_stream;
 use risingwave_common::catalog::Schema;
-use risingwave_common::types::Datum;
+use risingwave_common::types::{DataType, Datum};
 use tokio::sync::mpsc;
 
 use super::error::StreamExecutorError;
-use super::{Barrier, Executor, Message, PkIndices, StreamChunk, Watermark};
+use super::{Barrier, Executor, Message, Mutation, PkIndices, StreamChunk, Watermark};
 
 pub struct MockSource {
     schema: Schema,
@@ -49,9 +49,19 @@ impl MessageSender {
     }
 
     #[allow(dead_code)]
-    pub fn push_watermark(&mut self, col_idx: usize, val: Datum) {
+    pub fn push_barrier_with_mutation(&mut self, epoch: u64, mutation: Mutation) {
+        let barrier = Barrier::new_test_barrier(epoch).with_mutation(mutation);
+        self.0.send(Message::Barrier(barrier)).unwrap();
+    }
+
+    #[allow(dead_code)]
+    pub fn push_watermark(&mut self, col_idx: usize, data_type: DataType, val: Datum) {
         self.0
-            .send(Message::Watermark(Watermark { col_idx, val }))
+            .send(Message::Watermark(Watermark {
+                col_idx,
+                data_type,
+                val,
+            }))
             .unwrap();
     }
 }
@@ -151,6 +161,9 @@ macro_rules! row_nonnull {
 }
 
 pub mod agg_executor {
+    use std::collections::HashMap;
+
+    use itertools::Itertools;
     use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId};
     use risingwave_common::types::DataType;
     use risingwave_common::util::sort_util::OrderType;
@@ -159,7 +172,7 @@ pub mod agg_executor {
     use risingwave_storage::StateStore;
 
     use crate::common::StateTableColumnMapping;
-    use crate::executor::aggregation::{AggCall, AggStateStorage};
+    use crate::executor::aggregation::{is_deduplicated, AggCall, AggStateStorage};
     use crate::executor::{
         ActorContextRef, BoxedExecutor, Executor, GlobalSimpleAggExecutor, PkIndices,
     };
@@ -271,6 +284,50 @@ pub mod agg_executor {
         )
     }
 
+    /// Create distinct dedup tables for agg executor, keyed by the distinct column index.
+    /// Should infer the schema in the same way as `LogicalAgg::infer_distinct_dedup_tables`.
+    pub fn create_distinct_dedup_tables<S: StateStore>(
+        store: S,
+        first_table_id: u32,
+        agg_calls: &[AggCall],
+        group_key_indices: &[usize],
+        input_ref: &dyn Executor,
+    ) -> HashMap<usize, StateTable<S>> {
+        let input_fields = input_ref.schema().fields();
+
+        agg_calls
+            .iter()
+            .filter(|agg_call| is_deduplicated(agg_call))
+            .map(|agg_call| agg_call.args.val_indices()[0])
+            .unique()
+            .enumerate()
+            .map(|(i, distinct_col)| {
+                let key_indices = group_key_indices.iter().chain([&distinct_col]);
+                let n_key = group_key_indices.len() + 1;
+                let n_calls = agg_calls
+                    .iter()
+                    .filter(|agg_call| {
+                        is_deduplicated(agg_call) && agg_call.args.val_indices()[0] == distinct_col
+                    })
+                    .count();
+                let column_descs = key_indices
+                    .map(|idx| input_fields[*idx].data_type())
+                    .chain(std::iter::repeat(DataType::Int64).take(n_calls))
+                    .enumerate()
+                    .map(|(id, data_type)| ColumnDesc::unnamed(ColumnId::new(id as i32), data_type))
+                    .collect();
+                let table = StateTable::new_without_distribution(
+                    store.clone(),
+                    TableId::new(first_table_id + i as u32),
+                    column_descs,
+                    vec![OrderType::Ascending; n_key],
+                    (0..n_key).collect(),
+                );
+                (distinct_col, table)
+            })
+            .collect()
+    }
+
     pub fn new_boxed_simple_agg_executor<S: StateStore>(
         ctx: ActorContextRef,
         store: S,
@@ -294,12 +351,19 @@ pub mod agg_executor {
             })
             .collect();
         let result_table = create_result_table(
-            store,
+            store.clone(),
             TableId::new(agg_calls.len() as u32),
             &agg_calls,
             &[],
             input.as_ref(),
         );
+        let distinct_dedup_tables = create_distinct_dedup_tables(
+            store,
+            agg_calls.len() as u32 + 1,
+            &agg_calls,
+            &[],
+            input.as_ref(),
+        );
 
         Box::new(
             GlobalSimpleAggExecutor::new(
@@ -308,6 +372,7 @@ pub mod agg_executor {
                 agg_calls,
                 agg_state_tables,
                 result_table,
+                distinct_dedup_tables,
                 pk_indices,
                 executor_id,
                 1 << 10,
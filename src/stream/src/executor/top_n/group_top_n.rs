@@ -12,8 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::hash_map::Entry::Vacant;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -30,7 +30,7 @@ use risingwave_storage::StateStore;
 use super::top_n_cache::TopNCacheTrait;
 use super::utils::*;
 use super::TopNCache;
-use crate::cache::cache_may_stale;
+use crate::cache::{cache_may_stale, EvictableHashMap, ExecutorCache, LruManagerRef};
 use crate::error::StreamResult;
 use crate::executor::error::StreamExecutorResult;
 use crate::executor::managed_state::top_n::ManagedTopNState;
@@ -51,6 +51,8 @@ impl<S: StateStore> GroupTopNExecutor<S, false> {
         executor_id: u64,
         group_by: Vec<usize>,
         state_table: StateTable<S>,
+        lru_manager: Option<LruManagerRef>,
+        cache_size: usize,
     ) -> StreamResult<Self> {
         let info = input.info();
         let schema = input.schema().clone();
@@ -67,6 +69,8 @@ impl<S: StateStore> GroupTopNExecutor<S, false> {
                 executor_id,
                 group_by,
                 state_table,
+                lru_manager,
+                cache_size,
             )?,
         })
     }
@@ -84,6 +88,8 @@ impl<S: StateStore> GroupTopNExecutor<S, true> {
         executor_id: u64,
         group_by: Vec<usize>,
         state_table: StateTable<S>,
+        lru_manager: Option<LruManagerRef>,
+        cache_size: usize,
     ) -> StreamResult<Self> {
         let info = input.info();
         let schema = input.schema().clone();
@@ -101,11 +107,44 @@ impl<S: StateStore> GroupTopNExecutor<S, true> {
                 executor_id,
                 group_by,
                 state_table,
+                lru_manager,
+                cache_size,
             )?,
         })
     }
 }
 
+/// Caches of the top rows of each group. The number of cached groups is bounded, and the group
+/// evicted from the cache will be loaded from the state table again when it's accessed.
+pub struct GroupTopNCache<const WITH_TIES: bool> {
+    data: ExecutorCache<Vec<Datum>, TopNCache<WITH_TIES>>,
+}
+
+impl<const WITH_TIES: bool> GroupTopNCache<WITH_TIES> {
+    pub fn new(lru_manager: Option<LruManagerRef>, cache_size: usize) -> Self {
+        let data = if let Some(lru_manager) = lru_manager {
+            ExecutorCache::Managed(lru_manager.create_cache())
+        } else {
+            ExecutorCache::Local(EvictableHashMap::new(cache_size))
+        };
+        Self { data }
+    }
+}
+
+impl<const WITH_TIES: bool> Deref for GroupTopNCache<WITH_TIES> {
+    type Target = ExecutorCache<Vec<Datum>, TopNCache<WITH_TIES>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<const WITH_TIES: bool> DerefMut for GroupTopNCache<WITH_TIES> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
 pub struct InnerGroupTopNExecutorNew<S: StateStore, const WITH_TIES: bool> {
     info: ExecutorInfo,
 
@@ -131,7 +170,7 @@ pub struct InnerGroupTopNExecutorNew<S: StateStore, const WITH_TIES: bool> {
     group_by: Vec<usize>,
 
     /// group key -> cache for this group
-    caches: HashMap<Vec<Datum>, TopNCache<WITH_TIES>>,
+    caches: GroupTopNCache<WITH_TIES>,
 
     /// The number of fields of the ORDER BY clause, and will be used to split key into `CacheKey`.
     order_by_len: usize,
@@ -152,6 +191,8 @@ impl<S: StateStore, const WITH_TIES: bool> InnerGroupTopNExecutorNew<S, WITH_TIE
         executor_id: u64,
         group_by: Vec<usize>,
         state_table: StateTable<S>,
+        lru_manager: Option<LruManagerRef>,
+        cache_size: usize,
     ) -> StreamResult<Self> {
         // order_pairs is superset of pk
         assert!(order_pairs
@@ -195,7 +236,7 @@ impl<S: StateStore, const WITH_TIES: bool> InnerGroupTopNExecutorNew<S, WITH_TIE
             pk_indices,
             internal_key_indices,
             group_by,
-            caches: HashMap::new(),
+            caches: GroupTopNCache::new(lru_manager, cache_size),
             order_by_len,
             cache_key_serde,
         })
@@ -228,12 +269,12 @@ where
 
             // If 'self.caches' does not already have a cache for the current group, create a new
             // cache for it and insert it into `self.caches`
-            if let Vacant(entry) = self.caches.entry(group_key) {
+            if !self.caches.contains(&group_key) {
                 let mut topn_cache = TopNCache::new(self.offset, self.limit, self.order_by_len);
                 self.managed_state
                     .init_topn_cache(Some(&pk_prefix), &mut topn_cache, self.order_by_len)
                     .await?;
-                entry.insert(topn_cache);
+                self.caches.push(group_key, topn_cache);
             }
             let cache = self.caches.get_mut(&pk_prefix.0).unwrap();
 
@@ -264,7 +305,10 @@ where
     }
 
     async fn flush_data(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
-        self.managed_state.flush(epoch).await
+        self.managed_state.flush(epoch).await?;
+        self.caches.evict();
+        self.caches.update_epoch(epoch.curr);
+        Ok(())
     }
 
     fn schema(&self) -> &Schema {
@@ -404,6 +448,8 @@ mod tests {
                 1,
                 vec![1],
                 state_table,
+                None,
+                1024,
             )
             .unwrap(),
         );
@@ -501,6 +547,8 @@ mod tests {
                 1,
                 vec![1],
                 state_table,
+                None,
+                1024,
             )
             .unwrap(),
         );
@@ -590,6 +638,9 @@ mod tests {
                 1,
                 vec![1, 2],
                 state_table,
+                None,
+                // Keep one group cached; evicted groups are reloaded from the state table.
+                1,
             )
             .unwrap(),
         );
@@ -0,0 +1,401 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use risingwave_common::array::{Op, Row, RowDeserializer, StreamChunk};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::Schema;
+use risingwave_common::util::epoch::EpochPair;
+use risingwave_common::util::ordered::OrderedRowSerde;
+use risingwave_common::util::sort_util::OrderPair;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+use risingwave_storage::StateStore;
+
+use super::group_top_n::GroupTopNCache;
+use super::utils::*;
+use super::TopNCache;
+use crate::cache::{cache_may_stale, LruManagerRef};
+use crate::error::StreamResult;
+use crate::executor::error::StreamExecutorResult;
+use crate::executor::managed_state::top_n::ManagedTopNState;
+use crate::executor::{ActorContextRef, Executor, ExecutorInfo, PkIndices, PkIndicesRef};
+
+/// If the input contains only append, `AppendOnlyGroupTopNExecutor` does not need to keep all the
+/// rows of a group that have been seen. As long as a row is no longer being in the result set of
+/// its group, it can be deleted from the state table.
+pub type AppendOnlyGroupTopNExecutor<S> = TopNExecutorWrapper<InnerAppendOnlyGroupTopNExecutor<S>>;
+
+impl<S: StateStore> AppendOnlyGroupTopNExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input: Box<dyn Executor>,
+        ctx: ActorContextRef,
+        order_pairs: Vec<OrderPair>,
+        offset_and_limit: (usize, usize),
+        order_by_len: usize,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        group_by: Vec<usize>,
+        state_table: StateTable<S>,
+        lru_manager: Option<LruManagerRef>,
+        cache_size: usize,
+    ) -> StreamResult<Self> {
+        let info = input.info();
+        let schema = input.schema().clone();
+
+        Ok(TopNExecutorWrapper {
+            input,
+            ctx,
+            inner: InnerAppendOnlyGroupTopNExecutor::new(
+                info,
+                schema,
+                order_pairs,
+                offset_and_limit,
+                order_by_len,
+                pk_indices,
+                executor_id,
+                group_by,
+                state_table,
+                lru_manager,
+                cache_size,
+            )?,
+        })
+    }
+}
+
+pub struct InnerAppendOnlyGroupTopNExecutor<S: StateStore> {
+    info: ExecutorInfo,
+
+    /// Schema of the executor.
+    schema: Schema,
+
+    /// `LIMIT XXX`. None means no limit.
+    limit: usize,
+
+    /// `OFFSET XXX`. `0` means no offset.
+    offset: usize,
+
+    /// The primary key indices of the `AppendOnlyGroupTopNExecutor`
+    pk_indices: PkIndices,
+
+    /// The internal key indices of the `AppendOnlyGroupTopNExecutor`
+    internal_key_indices: PkIndices,
+
+    /// We are interested in which element is in the range of [offset, offset+limit).
+    managed_state: ManagedTopNState<S>,
+
+    /// which column we used to group the data.
+    group_by: Vec<usize>,
+
+    /// group key -> cache for this group
+    caches: GroupTopNCache<false>,
+
+    /// The number of fields of the ORDER BY clause, and will be used to split key into `CacheKey`.
+    order_by_len: usize,
+
+    /// Used for serializing pk into CacheKey.
+    cache_key_serde: (OrderedRowSerde, OrderedRowSerde),
+}
+
+impl<S: StateStore> InnerAppendOnlyGroupTopNExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input_info: ExecutorInfo,
+        schema: Schema,
+        order_pairs: Vec<OrderPair>,
+        offset_and_limit: (usize, usize),
+        order_by_len: usize,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        group_by: Vec<usize>,
+        state_table: StateTable<S>,
+        lru_manager: Option<LruManagerRef>,
+        cache_size: usize,
+    ) -> StreamResult<Self> {
+        // order_pairs is superset of pk
+        assert!(order_pairs
+            .iter()
+            .map(|x| x.column_idx)
+            .collect::<HashSet<_>>()
+            .is_superset(&pk_indices.iter().copied().collect::<HashSet<_>>()));
+        let (internal_key_indices, internal_key_data_types, internal_key_order_types) =
+            generate_executor_pk_indices_info(&order_pairs, &schema);
+
+        let managed_state = ManagedTopNState::<S>::new(
+            state_table,
+            &internal_key_data_types[group_by.len()..],
+            &internal_key_order_types[group_by.len()..],
+            order_by_len,
+        );
+        let (first_key_data_types, second_key_data_types) =
+            internal_key_data_types[group_by.len()..].split_at(order_by_len);
+        let (first_key_order_types, second_key_order_types) =
+            internal_key_order_types[group_by.len()..].split_at(order_by_len);
+        let first_key_serde = OrderedRowSerde::new(
+            first_key_data_types.to_vec(),
+            first_key_order_types.to_vec(),
+        );
+        let second_key_serde = OrderedRowSerde::new(
+            second_key_data_types.to_vec(),
+            second_key_order_types.to_vec(),
+        );
+
+        let cache_key_serde = (first_key_serde, second_key_serde);
+        Ok(Self {
+            info: ExecutorInfo {
+                schema: input_info.schema,
+                pk_indices: input_info.pk_indices,
+                identity: format!("AppendOnlyGroupTopNExecutor {:X}", executor_id),
+            },
+            schema,
+            offset: offset_and_limit.0,
+            limit: offset_and_limit.1,
+            managed_state,
+            pk_indices,
+            internal_key_indices,
+            group_by,
+            caches: GroupTopNCache::new(lru_manager, cache_size),
+            order_by_len,
+            cache_key_serde,
+        })
+    }
+}
+
+#[async_trait]
+impl<S: StateStore> TopNExecutorBase for InnerAppendOnlyGroupTopNExecutor<S> {
+    async fn apply_chunk(&mut self, chunk: StreamChunk) -> StreamExecutorResult<StreamChunk> {
+        let mut res_ops = Vec::with_capacity(self.limit);
+        let mut res_rows = Vec::with_capacity(self.limit);
+        let data_types = self.schema().data_types();
+        let row_deserializer = RowDeserializer::new(data_types);
+
+        for (op, row_ref) in chunk.rows() {
+            debug_assert_eq!(op, Op::Insert);
+            // The pk without group by
+            let pk_row = row_ref.row_by_indices(&self.internal_key_indices[self.group_by.len()..]);
+            let cache_key =
+                serialize_pk_to_cache_key(pk_row, self.order_by_len, &self.cache_key_serde);
+
+            let row = row_ref.to_owned_row();
+
+            let group_key = self
+                .group_by
+                .iter()
+                .map(|&col_id| row[col_id].clone())
+                .collect::<Vec<_>>();
+            let pk_prefix = Row::new(group_key.clone());
+
+            // If 'self.caches' does not already have a cache for the current group, create a new
+            // cache for it and insert it into `self.caches`
+            if !self.caches.contains(&group_key) {
+                let mut topn_cache = TopNCache::new(self.offset, self.limit, self.order_by_len);
+                self.managed_state
+                    .init_topn_cache(Some(&pk_prefix), &mut topn_cache, self.order_by_len)
+                    .await?;
+                self.caches.push(group_key, topn_cache);
+            }
+            let cache = self.caches.get_mut(&pk_prefix.0).unwrap();
+
+            cache.insert_append_only(
+                &mut self.managed_state,
+                cache_key,
+                row,
+                &row_deserializer,
+                &mut res_ops,
+                &mut res_rows,
+            )?;
+        }
+
+        generate_output(res_rows, res_ops, &self.schema)
+    }
+
+    async fn flush_data(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
+        self.managed_state.flush(epoch).await?;
+        self.caches.evict();
+        self.caches.update_epoch(epoch.curr);
+        Ok(())
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef<'_> {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        &self.info.identity
+    }
+
+    fn update_vnode_bitmap(&mut self, vnode_bitmap: Arc<Bitmap>) {
+        let previous_vnode_bitmap = self
+            .managed_state
+            .state_table
+            .update_vnode_bitmap(vnode_bitmap.clone());
+
+        if cache_may_stale(&previous_vnode_bitmap, &vnode_bitmap) {
+            self.caches.clear();
+        }
+    }
+
+    async fn init(&mut self, epoch: EpochPair) -> StreamExecutorResult<()> {
+        self.managed_state.state_table.init_epoch(epoch);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use futures::StreamExt;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::sort_util::OrderType;
+
+    use super::*;
+    use crate::executor::test_utils::top_n_executor::create_in_memory_state_table;
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::{ActorContext, Barrier, Message};
+
+    fn create_schema() -> Schema {
+        Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        }
+    }
+
+    fn create_order_pairs() -> Vec<OrderPair> {
+        vec![
+            OrderPair::new(1, OrderType::Ascending),
+            OrderPair::new(2, OrderType::Ascending),
+            OrderPair::new(0, OrderType::Ascending),
+        ]
+    }
+
+    fn create_source() -> Box<MockSource> {
+        let chunk0 = StreamChunk::from_pretty(
+            " I I  I
+            + 1 1 30
+            + 2 1 20
+            + 3 1 10
+            + 4 2 10",
+        );
+        let chunk1 = StreamChunk::from_pretty(
+            " I I  I
+            + 5 1 15
+            + 6 1 40
+            + 7 2  5",
+        );
+        let chunk2 = StreamChunk::from_pretty(
+            " I I  I
+            + 8 1 12",
+        );
+        Box::new(MockSource::with_messages(
+            create_schema(),
+            PkIndices::new(),
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(chunk0),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                Message::Chunk(chunk1),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+                Message::Chunk(chunk2),
+                Message::Barrier(Barrier::new_test_barrier(4)),
+            ],
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_append_only_group_top_n() {
+        let state_table = create_in_memory_state_table(
+            &[DataType::Int64, DataType::Int64, DataType::Int64],
+            &[
+                OrderType::Ascending,
+                OrderType::Ascending,
+                OrderType::Ascending,
+            ],
+            &[1, 2, 0],
+        );
+        let top_n_executor = Box::new(
+            AppendOnlyGroupTopNExecutor::new(
+                create_source() as Box<dyn Executor>,
+                ActorContext::create(0),
+                create_order_pairs(),
+                (0, 2),
+                1,
+                vec![1, 2, 0],
+                1,
+                vec![1],
+                state_table,
+                None,
+                // Keep one group cached; evicted groups are reloaded from the state table.
+                1,
+            )
+            .unwrap(),
+        );
+        let mut top_n_executor = top_n_executor.execute();
+
+        // consume the init barrier
+        top_n_executor.next().await.unwrap().unwrap();
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                " I I  I
+                + 1 1 30
+                + 2 1 20
+                - 1 1 30
+                + 3 1 10
+                + 4 2 10",
+            ),
+        );
+
+        assert_matches!(
+            top_n_executor.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                " I I  I
+                - 2 1 20
+                + 5 1 15
+                + 7 2  5",
+            ),
+        );
+
+        assert_matches!(
+            top_n_executor.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                " I I  I
+                - 5 1 15
+                + 8 1 12",
+            ),
+        );
+    }
+}
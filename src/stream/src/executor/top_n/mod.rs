@@ -20,10 +20,12 @@ use top_n_cache::TopNCacheTrait;
 
 // `TopN` variants
 mod group_top_n;
+mod group_top_n_appendonly;
 mod top_n_appendonly;
 mod top_n_plain;
 
 pub use group_top_n::GroupTopNExecutor;
+pub use group_top_n_appendonly::AppendOnlyGroupTopNExecutor;
 pub use top_n_appendonly::AppendOnlyTopNExecutor;
 pub use top_n_cache::{CacheKey, TopNCache};
 pub use top_n_plain::TopNExecutor;
@@ -168,57 +168,14 @@ impl<S: StateStore> TopNExecutorBase for InnerAppendOnlyTopNExecutor<S> {
                 serialize_pk_to_cache_key(pk_row, self.order_by_len, &self.cache_key_serde);
             let row = row_ref.to_owned_row();
 
-            if self.cache.is_middle_cache_full()
-                && &cache_key >= self.cache.middle.last_key_value().unwrap().0
-            {
-                continue;
-            }
-            self.managed_state.insert(row.clone());
-
-            // Then insert input row to corresponding cache range according to its order key
-            if !self.cache.is_low_cache_full() {
-                self.cache.low.insert(cache_key, (&row).into());
-                continue;
-            }
-
-            let elem_to_insert_into_middle =
-            if let Some(low_last) = self.cache.low.last_entry()
-                && &cache_key <= low_last.key() {
-                // Take the last element of `cache.low` and insert input row to it.
-                let low_last = low_last.remove_entry();
-                self.cache.low.insert(cache_key, (&row).into());
-                low_last
-            } else {
-                (cache_key, (&row).into())
-            };
-
-            if !self.cache.is_middle_cache_full() {
-                self.cache.middle.insert(
-                    elem_to_insert_into_middle.0,
-                    elem_to_insert_into_middle.1.clone(),
-                );
-                res_ops.push(Op::Insert);
-                res_rows.push(elem_to_insert_into_middle.1);
-                continue;
-            }
-
-            // The row must be in the range of [offset, offset+limit).
-            // the largest row in `cache.middle` needs to be removed.
-            let middle_last = self.cache.middle.pop_last().unwrap();
-            debug_assert!(elem_to_insert_into_middle.0 < middle_last.0);
-
-            res_ops.push(Op::Delete);
-            res_rows.push(middle_last.1.clone());
-            self.managed_state
-                .delete(row_deserializer.deserialize(middle_last.1.row.as_ref())?);
-
-            res_ops.push(Op::Insert);
-            res_rows.push(elem_to_insert_into_middle.1.clone());
-            self.cache
-                .middle
-                .insert(elem_to_insert_into_middle.0, elem_to_insert_into_middle.1);
-
-            // Unlike normal topN, append only topN does not use the high part of the cache.
+            self.cache.insert_append_only(
+                &mut self.managed_state,
+                cache_key,
+                row,
+                &row_deserializer,
+                &mut res_ops,
+                &mut res_rows,
+            )?;
         }
 
         generate_output(res_rows, res_ops, &self.schema)
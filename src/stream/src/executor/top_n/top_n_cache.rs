@@ -16,7 +16,7 @@ use std::cmp::Ordering;
 use std::collections::BTreeMap;
 
 use async_trait::async_trait;
-use risingwave_common::array::{Op, Row};
+use risingwave_common::array::{Op, Row, RowDeserializer};
 use risingwave_common::row::CompactedRow;
 use risingwave_storage::StateStore;
 
@@ -165,6 +165,75 @@ impl<const WITH_TIES: bool> TopNCache<WITH_TIES> {
     }
 }
 
+impl TopNCache<false> {
+    /// Insert input row of an append-only stream to the cache.
+    ///
+    /// Rows of an append-only stream are never deleted, so a row that falls out of the range
+    /// `[0, offset+limit)` will never be in the result again. Such rows are removed from both the
+    /// cache and `managed_state`, and the high part of the cache is not used.
+    ///
+    /// Changes in `self.middle` is recorded to `res_ops` and `res_rows`, which will be
+    /// used to generate messages to be sent to downstream operators.
+    pub fn insert_append_only<S: StateStore>(
+        &mut self,
+        managed_state: &mut ManagedTopNState<S>,
+        cache_key: CacheKey,
+        row: Row,
+        row_deserializer: &RowDeserializer,
+        res_ops: &mut Vec<Op>,
+        res_rows: &mut Vec<CompactedRow>,
+    ) -> StreamExecutorResult<()> {
+        if self.is_middle_cache_full() && &cache_key >= self.middle.last_key_value().unwrap().0 {
+            return Ok(());
+        }
+        managed_state.insert(row.clone());
+
+        // Then insert input row to corresponding cache range according to its order key
+        if !self.is_low_cache_full() {
+            self.low.insert(cache_key, (&row).into());
+            return Ok(());
+        }
+
+        let elem_to_insert_into_middle =
+            if let Some(low_last) = self.low.last_entry()
+                && &cache_key <= low_last.key() {
+                // Take the last element of `cache.low` and insert input row to it.
+                let low_last = low_last.remove_entry();
+                self.low.insert(cache_key, (&row).into());
+                low_last
+            } else {
+                (cache_key, (&row).into())
+            };
+
+        if !self.is_middle_cache_full() {
+            self.middle.insert(
+                elem_to_insert_into_middle.0,
+                elem_to_insert_into_middle.1.clone(),
+            );
+            res_ops.push(Op::Insert);
+            res_rows.push(elem_to_insert_into_middle.1);
+            return Ok(());
+        }
+
+        // The row must be in the range of [offset, offset+limit).
+        // the largest row in `cache.middle` needs to be removed.
+        let middle_last = self.middle.pop_last().unwrap();
+        debug_assert!(elem_to_insert_into_middle.0 < middle_last.0);
+
+        res_ops.push(Op::Delete);
+        res_rows.push(middle_last.1.clone());
+        managed_state.delete(row_deserializer.deserialize(middle_last.1.row.as_ref())?);
+
+        res_ops.push(Op::Insert);
+        res_rows.push(elem_to_insert_into_middle.1.clone());
+        self.middle
+            .insert(elem_to_insert_into_middle.0, elem_to_insert_into_middle.1);
+
+        // Unlike normal topN, append only topN does not use the high part of the cache.
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl TopNCacheTrait for TopNCache<false> {
     fn insert(
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
@@ -32,13 +33,14 @@ pub fn build_agg_call_from_prost(
     agg_call_proto: &risingwave_pb::expr::AggCall,
 ) -> StreamResult<AggCall> {
     let agg_kind = AggKind::try_from(agg_call_proto.get_type()?)?;
+    let is_binary = matches!(agg_kind, AggKind::StringAgg | AggKind::PercentileCont);
     let args = match &agg_call_proto.get_args()[..] {
         [] => AggArgs::None,
-        [arg] if agg_kind != AggKind::StringAgg => AggArgs::Unary(
+        [arg] if !is_binary => AggArgs::Unary(
             DataType::from(arg.get_type()?),
             arg.get_input()?.column_idx as usize,
         ),
-        [agg_arg, extra_arg] if agg_kind == AggKind::StringAgg => AggArgs::Binary(
+        [agg_arg, extra_arg] if is_binary => AggArgs::Binary(
             [
                 DataType::from(agg_arg.get_type()?),
                 DataType::from(extra_arg.get_type()?),
@@ -76,6 +78,7 @@ pub fn build_agg_call_from_prost(
         order_pairs,
         append_only,
         filter,
+        distinct: agg_call_proto.distinct,
     })
 }
 
@@ -125,3 +128,21 @@ pub fn build_agg_state_storages_from_proto<S: StateStore>(
         })
         .collect()
 }
+
+/// Parse from stream proto plan distinct dedup tables, generate state tables keyed by the distinct
+/// column index. The `vnodes` is generally `Some` for Hash Agg and `None` for Simple Agg.
+pub fn build_distinct_dedup_tables_from_proto<S: StateStore>(
+    dedup_tables: &HashMap<u32, risingwave_pb::catalog::Table>,
+    store: S,
+    vnodes: Option<Arc<Bitmap>>,
+) -> HashMap<usize, StateTable<S>> {
+    dedup_tables
+        .iter()
+        .map(|(distinct_col, table)| {
+            (
+                *distinct_col as usize,
+                StateTable::from_table_catalog(table, store.clone(), vnodes.clone()),
+            )
+        })
+        .collect()
+}
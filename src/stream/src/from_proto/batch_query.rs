@@ -13,9 +13,11 @@
 // limitations under the License.
 
 use itertools::Itertools;
+use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId, TableOption};
 use risingwave_common::util::sort_util::OrderType;
 use risingwave_pb::plan_common::{OrderType as ProstOrderType, StorageTableDesc};
+use risingwave_pb::stream_plan::BatchPlanNode;
 use risingwave_storage::table::batch_table::storage_table::StorageTable;
 use risingwave_storage::table::Distribution;
 use risingwave_storage::StateStore;
@@ -33,69 +35,7 @@ impl ExecutorBuilder for BatchQueryExecutorBuilder {
         _stream: &mut LocalStreamManagerCore,
     ) -> StreamResult<BoxedExecutor> {
         let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::BatchPlan)?;
-
-        let table_desc: &StorageTableDesc = node.get_table_desc()?;
-        let table_id = TableId {
-            table_id: table_desc.table_id,
-        };
-
-        let order_types = table_desc
-            .pk
-            .iter()
-            .map(|desc| OrderType::from_prost(&ProstOrderType::from_i32(desc.order_type).unwrap()))
-            .collect_vec();
-
-        let column_descs = table_desc
-            .columns
-            .iter()
-            .map(ColumnDesc::from)
-            .collect_vec();
-        let column_ids = node
-            .column_ids
-            .iter()
-            .copied()
-            .map(ColumnId::from)
-            .collect();
-
-        // Use indices based on full table instead of streaming executor output.
-        let pk_indices = table_desc.pk.iter().map(|k| k.index as usize).collect_vec();
-
-        let dist_key_indices = table_desc
-            .dist_key_indices
-            .iter()
-            .map(|&k| k as usize)
-            .collect_vec();
-        let distribution = match params.vnode_bitmap {
-            Some(vnodes) => Distribution {
-                dist_key_indices,
-                vnodes: vnodes.into(),
-            },
-            None => Distribution::fallback(),
-        };
-
-        let table_option = TableOption {
-            retention_seconds: if table_desc.retention_seconds > 0 {
-                Some(table_desc.retention_seconds)
-            } else {
-                None
-            },
-        };
-        let value_indices = table_desc
-            .get_value_indices()
-            .iter()
-            .map(|&k| k as usize)
-            .collect_vec();
-        let table = StorageTable::new_partial(
-            state_store,
-            table_id,
-            column_descs,
-            column_ids,
-            order_types,
-            pk_indices,
-            distribution,
-            table_option,
-            value_indices,
-        );
+        let table = build_storage_table(node, params.vnode_bitmap, state_store)?;
 
         let schema = table.schema().clone();
         let executor = BatchQueryExecutor::new(
@@ -111,3 +51,74 @@ impl ExecutorBuilder for BatchQueryExecutorBuilder {
         Ok(executor.boxed())
     }
 }
+
+/// Builds the [`StorageTable`] on the vnodes of the actor to read the snapshot of the upstream
+/// table, which is shared by the batch query and the backfill of the chain.
+pub(super) fn build_storage_table<S: StateStore>(
+    node: &BatchPlanNode,
+    vnode_bitmap: Option<Bitmap>,
+    state_store: S,
+) -> StreamResult<StorageTable<S>> {
+    let table_desc: &StorageTableDesc = node.get_table_desc()?;
+    let table_id = TableId {
+        table_id: table_desc.table_id,
+    };
+
+    let order_types = table_desc
+        .pk
+        .iter()
+        .map(|desc| OrderType::from_prost(&ProstOrderType::from_i32(desc.order_type).unwrap()))
+        .collect_vec();
+
+    let column_descs = table_desc
+        .columns
+        .iter()
+        .map(ColumnDesc::from)
+        .collect_vec();
+    let column_ids = node
+        .column_ids
+        .iter()
+        .copied()
+        .map(ColumnId::from)
+        .collect();
+
+    // Use indices based on full table instead of streaming executor output.
+    let pk_indices = table_desc.pk.iter().map(|k| k.index as usize).collect_vec();
+
+    let dist_key_indices = table_desc
+        .dist_key_indices
+        .iter()
+        .map(|&k| k as usize)
+        .collect_vec();
+    let distribution = match vnode_bitmap {
+        Some(vnodes) => Distribution {
+            dist_key_indices,
+            vnodes: vnodes.into(),
+        },
+        None => Distribution::fallback(),
+    };
+
+    let table_option = TableOption {
+        retention_seconds: if table_desc.retention_seconds > 0 {
+            Some(table_desc.retention_seconds)
+        } else {
+            None
+        },
+    };
+    let value_indices = table_desc
+        .get_value_indices()
+        .iter()
+        .map(|&k| k as usize)
+        .collect_vec();
+    Ok(StorageTable::new_partial(
+        state_store,
+        table_id,
+        column_descs,
+        column_ids,
+        order_types,
+        pk_indices,
+        distribution,
+        table_option,
+        value_indices,
+    ))
+}
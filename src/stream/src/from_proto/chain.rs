@@ -12,19 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use risingwave_pb::stream_plan::ChainType;
+
+use super::batch_query::build_storage_table;
 use super::*;
-use crate::executor::{ChainExecutor, RearrangedChainExecutor};
+use crate::executor::{BackfillExecutor, ChainExecutor, RearrangedChainExecutor};
 
 pub struct ChainExecutorBuilder;
 
 impl ExecutorBuilder for ChainExecutorBuilder {
     fn new_boxed_executor(
         params: ExecutorParams,
-        node: &StreamNode,
-        _store: impl StateStore,
+        stream_node: &StreamNode,
+        state_store: impl StateStore,
         stream: &mut LocalStreamManagerCore,
     ) -> StreamResult<BoxedExecutor> {
-        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::Chain)?;
+        let node = try_match_expand!(stream_node.get_node_body().unwrap(), NodeBody::Chain)?;
         let [mview, snapshot]: [_; 2] = params.input.try_into().unwrap();
 
         let upstream_indices: Vec<usize> = node
@@ -42,13 +45,55 @@ impl ExecutorBuilder for ChainExecutorBuilder {
         // its schema.
         let schema = snapshot.schema().clone();
 
-        if node.disable_rearrange {
-            let executor = ChainExecutor::new(snapshot, mview, upstream_indices, progress, schema);
-            Ok(executor.boxed())
-        } else {
-            let executor =
-                RearrangedChainExecutor::new(snapshot, mview, upstream_indices, progress, schema);
-            Ok(executor.boxed())
+        let chain_type = match node.chain_type() {
+            _ if node.upstream_only => ChainType::Chain,
+            ChainType::Unspecified if node.disable_rearrange => ChainType::Chain,
+            ChainType::Unspecified => ChainType::Rearrange,
+            chain_type => chain_type,
+        };
+
+        match chain_type {
+            ChainType::Chain => {
+                let executor = ChainExecutor::new(
+                    snapshot,
+                    mview,
+                    upstream_indices,
+                    progress,
+                    schema,
+                    node.upstream_only,
+                );
+                Ok(executor.boxed())
+            }
+            ChainType::Rearrange => {
+                let executor = RearrangedChainExecutor::new(
+                    snapshot,
+                    mview,
+                    upstream_indices,
+                    progress,
+                    schema,
+                    node.rate_limit,
+                );
+                Ok(executor.boxed())
+            }
+            ChainType::Backfill => {
+                // The backfill reads the snapshot by itself in the order of the primary key,
+                // instead of through the batch query executor.
+                let batch_plan = try_match_expand!(
+                    stream_node.input[1].get_node_body().unwrap(),
+                    NodeBody::BatchPlan
+                )?;
+                let table = build_storage_table(batch_plan, params.vnode_bitmap, state_store)?;
+                let executor = BackfillExecutor::new(
+                    table,
+                    mview,
+                    upstream_indices,
+                    progress,
+                    schema,
+                    node.rate_limit,
+                );
+                Ok(executor.boxed())
+            }
+            ChainType::Unspecified => unreachable!(),
         }
     }
 }
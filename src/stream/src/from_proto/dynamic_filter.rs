@@ -70,6 +70,7 @@ impl ExecutorBuilder for DynamicFilterExecutorBuilder {
             state_table_l,
             state_table_r,
             is_right_table_writer,
+            node.is_monotonic,
             params.executor_stats,
             params.env.config().developer.stream_chunk_size,
         )))
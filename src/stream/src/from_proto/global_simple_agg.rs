@@ -16,7 +16,10 @@
 
 use risingwave_storage::table::streaming_table::state_table::StateTable;
 
-use super::agg_common::{build_agg_call_from_prost, build_agg_state_storages_from_proto};
+use super::agg_common::{
+    build_agg_call_from_prost, build_agg_state_storages_from_proto,
+    build_distinct_dedup_tables_from_proto,
+};
 use super::*;
 use crate::executor::aggregation::AggCall;
 use crate::executor::GlobalSimpleAggExecutor;
@@ -40,7 +43,9 @@ impl ExecutorBuilder for GlobalSimpleAggExecutorBuilder {
         let storages =
             build_agg_state_storages_from_proto(node.get_agg_call_states(), store.clone(), None);
         let result_table =
-            StateTable::from_table_catalog(node.get_result_table().unwrap(), store, None);
+            StateTable::from_table_catalog(node.get_result_table().unwrap(), store.clone(), None);
+        let distinct_dedup_tables =
+            build_distinct_dedup_tables_from_proto(&node.distinct_dedup_tables, store, None);
 
         Ok(GlobalSimpleAggExecutor::new(
             params.actor_context,
@@ -48,6 +53,7 @@ impl ExecutorBuilder for GlobalSimpleAggExecutorBuilder {
             agg_calls,
             storages,
             result_table,
+            distinct_dedup_tables,
             params.pk_indices,
             params.executor_id,
             stream.config.developer.unsafe_stream_extreme_cache_size,
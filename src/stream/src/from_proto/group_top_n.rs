@@ -27,7 +27,7 @@ impl ExecutorBuilder for GroupTopNExecutorBuilder {
         mut params: ExecutorParams,
         node: &StreamNode,
         store: impl StateStore,
-        _stream: &mut LocalStreamManagerCore,
+        stream: &mut LocalStreamManagerCore,
     ) -> StreamResult<BoxedExecutor> {
         let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::GroupTopN)?;
         let group_by = node
@@ -51,6 +51,8 @@ impl ExecutorBuilder for GroupTopNExecutorBuilder {
                 params.executor_id,
                 group_by,
                 state_table,
+                stream.context.lru_manager.clone(),
+                stream.config.developer.unsafe_stream_group_top_n_cache_size,
             )?
             .boxed())
         } else {
@@ -64,6 +66,8 @@ impl ExecutorBuilder for GroupTopNExecutorBuilder {
                 params.executor_id,
                 group_by,
                 state_table,
+                stream.context.lru_manager.clone(),
+                stream.config.developer.unsafe_stream_group_top_n_cache_size,
             )?
             .boxed())
         }
@@ -14,13 +14,17 @@
 
 //! Global Streaming Hash Aggregators
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use risingwave_common::hash::{HashKey, HashKeyDispatcher};
 use risingwave_common::types::DataType;
 use risingwave_storage::table::streaming_table::state_table::StateTable;
 
-use super::agg_common::{build_agg_call_from_prost, build_agg_state_storages_from_proto};
+use super::agg_common::{
+    build_agg_call_from_prost, build_agg_state_storages_from_proto,
+    build_distinct_dedup_tables_from_proto,
+};
 use super::*;
 use crate::cache::LruManagerRef;
 use crate::executor::aggregation::{AggCall, AggStateStorage};
@@ -33,6 +37,7 @@ pub struct HashAggExecutorDispatcherArgs<S: StateStore> {
     agg_calls: Vec<AggCall>,
     storages: Vec<AggStateStorage<S>>,
     result_table: StateTable<S>,
+    distinct_dedup_tables: HashMap<usize, StateTable<S>>,
     group_key_indices: Vec<usize>,
     group_key_types: Vec<DataType>,
     pk_indices: PkIndices,
@@ -54,6 +59,7 @@ impl<S: StateStore> HashKeyDispatcher for HashAggExecutorDispatcherArgs<S> {
             self.agg_calls,
             self.storages,
             self.result_table,
+            self.distinct_dedup_tables,
             self.pk_indices,
             self.executor_id,
             self.group_key_indices,
@@ -106,8 +112,13 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
             store.clone(),
             vnodes.clone(),
         );
-        let result_table =
-            StateTable::from_table_catalog(node.get_result_table().unwrap(), store, vnodes);
+        let result_table = StateTable::from_table_catalog(
+            node.get_result_table().unwrap(),
+            store.clone(),
+            vnodes.clone(),
+        );
+        let distinct_dedup_tables =
+            build_distinct_dedup_tables_from_proto(&node.distinct_dedup_tables, store, vnodes);
 
         let args = HashAggExecutorDispatcherArgs {
             ctx: params.actor_context,
@@ -115,6 +126,7 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
             agg_calls,
             storages,
             result_table,
+            distinct_dedup_tables,
             group_key_indices,
             group_key_types,
             pk_indices: params.pk_indices,
@@ -0,0 +1,71 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_expr::expr::build_from_prost;
+use risingwave_pb::plan_common::JoinType as JoinTypeProto;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+
+use super::*;
+use crate::executor::IntervalJoinExecutor;
+
+pub struct IntervalJoinExecutorBuilder;
+
+impl ExecutorBuilder for IntervalJoinExecutorBuilder {
+    fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let schema = Schema::new(node.fields.iter().map(Field::from).collect());
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::IntervalJoin)?;
+        assert_eq!(node.get_join_type()?, JoinTypeProto::Inner);
+        let [source_l, source_r]: [_; 2] = params.input.try_into().unwrap();
+
+        let vnodes = params.vnode_bitmap.map(Arc::new);
+        let state_table_l =
+            StateTable::from_table_catalog(node.get_left_table()?, store.clone(), vnodes.clone());
+        let state_table_r = StateTable::from_table_catalog(node.get_right_table()?, store, vnodes);
+
+        let condition = match node.get_condition() {
+            Ok(cond_prost) => Some(build_from_prost(cond_prost)?),
+            Err(_) => None,
+        };
+
+        Ok(Box::new(IntervalJoinExecutor::new(
+            params.actor_context,
+            source_l,
+            source_r,
+            state_table_l,
+            state_table_r,
+            node.left_key.iter().map(|&k| k as usize).collect(),
+            node.right_key.iter().map(|&k| k as usize).collect(),
+            node.null_safe.clone(),
+            condition,
+            node.left_time_col as usize,
+            node.right_time_col as usize,
+            node.lower_bound_ms,
+            node.upper_bound_ms,
+            node.output_indices.iter().map(|&x| x as usize).collect(),
+            schema,
+            params.pk_indices,
+            params.executor_id,
+            params.executor_stats,
+            params.env.config().developer.stream_chunk_size,
+        )))
+    }
+}
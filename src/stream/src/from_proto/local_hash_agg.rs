@@ -0,0 +1,52 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::agg_common::build_agg_call_from_prost;
+use super::*;
+use crate::executor::aggregation::AggCall;
+use crate::executor::LocalHashAggExecutor;
+
+pub struct LocalHashAggExecutorBuilder;
+
+impl ExecutorBuilder for LocalHashAggExecutorBuilder {
+    fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &StreamNode,
+        _store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::LocalHashAgg)?;
+        let [input]: [_; 1] = params.input.try_into().unwrap();
+        let group_key_indices = node
+            .get_group_key()
+            .iter()
+            .map(|key| *key as usize)
+            .collect_vec();
+        let agg_calls: Vec<AggCall> = node
+            .get_agg_calls()
+            .iter()
+            .map(|agg_call| build_agg_call_from_prost(node.is_append_only, agg_call))
+            .try_collect()?;
+
+        Ok(LocalHashAggExecutor::new(
+            params.actor_context,
+            input,
+            agg_calls,
+            group_key_indices,
+            params.pk_indices,
+            params.executor_id,
+        )?
+        .boxed())
+    }
+}
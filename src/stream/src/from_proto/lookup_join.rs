@@ -0,0 +1,77 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_expr::expr::build_from_prost;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+
+use super::*;
+use crate::executor::LookupJoinExecutor;
+
+pub struct LookupJoinExecutorBuilder;
+
+impl ExecutorBuilder for LookupJoinExecutorBuilder {
+    fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let schema = Schema::new(node.fields.iter().map(Field::from).collect());
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::LookupJoin)?;
+        let [source_l, source_r]: [_; 2] = params.input.try_into().unwrap();
+
+        // The right table is read directly from the storage, with the same partition as the
+        // upstream of the right input.
+        let right_table = build_lookup_table(
+            node.get_table_desc()?,
+            &node.table_output_column_ids,
+            params.vnode_bitmap.clone(),
+            store.clone(),
+        );
+        let left_table = StateTable::from_table_catalog(
+            node.get_left_table()?,
+            store,
+            params.vnode_bitmap.map(Arc::new),
+        );
+
+        let condition = match node.get_condition() {
+            Ok(cond_prost) => Some(build_from_prost(cond_prost)?),
+            Err(_) => None,
+        };
+
+        Ok(Box::new(LookupJoinExecutor::new(
+            params.actor_context,
+            source_l,
+            source_r,
+            right_table,
+            left_table,
+            node.left_key.iter().map(|&k| k as usize).collect(),
+            node.right_key.iter().map(|&k| k as usize).collect(),
+            node.null_safe.clone(),
+            node.get_join_type()?,
+            condition,
+            node.output_indices.iter().map(|&x| x as usize).collect(),
+            schema,
+            params.pk_indices,
+            params.executor_id,
+            stream.context.lru_manager.clone(),
+            stream.config.developer.unsafe_stream_join_cache_size,
+            params.executor_stats,
+            params.env.config().developer.stream_chunk_size,
+        )))
+    }
+}
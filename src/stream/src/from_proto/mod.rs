@@ -22,19 +22,27 @@ mod expand;
 mod filter;
 mod global_simple_agg;
 mod group_top_n;
+mod group_top_n_appendonly;
 mod hash_agg;
 mod hash_join;
 mod hop_window;
+mod interval_join;
+mod local_hash_agg;
 mod local_simple_agg;
 mod lookup;
+mod lookup_join;
 mod lookup_union;
 mod merge;
 mod mview;
+mod now;
+mod over_window;
 mod project;
 mod project_set;
+mod session_window;
 mod sink;
 mod sort;
 mod source;
+mod temporal_join;
 mod top_n;
 mod top_n_appendonly;
 mod union;
@@ -53,19 +61,27 @@ use self::expand::*;
 use self::filter::*;
 use self::global_simple_agg::*;
 use self::group_top_n::GroupTopNExecutorBuilder;
+use self::group_top_n_appendonly::AppendOnlyGroupTopNExecutorBuilder;
 use self::hash_agg::*;
 use self::hash_join::*;
 use self::hop_window::*;
+use self::interval_join::*;
+use self::local_hash_agg::*;
 use self::local_simple_agg::*;
 use self::lookup::*;
+use self::lookup_join::*;
 use self::lookup_union::*;
 use self::merge::*;
 use self::mview::*;
+use self::now::*;
+use self::over_window::*;
 use self::project::*;
 use self::project_set::*;
+use self::session_window::*;
 use self::sink::*;
 use self::sort::*;
 use self::source::*;
+use self::temporal_join::*;
 use self::top_n::*;
 use self::top_n_appendonly::*;
 use self::union::*;
@@ -114,6 +130,7 @@ pub fn create_executor(
         NodeBody::TopN => TopNExecutorNewBuilder,
         NodeBody::AppendOnlyTopN => AppendOnlyTopNExecutorBuilder,
         NodeBody::LocalSimpleAgg => LocalSimpleAggExecutorBuilder,
+        NodeBody::LocalHashAgg => LocalHashAggExecutorBuilder,
         NodeBody::GlobalSimpleAgg => GlobalSimpleAggExecutorBuilder,
         NodeBody::HashAgg => HashAggExecutorBuilder,
         NodeBody::HashJoin => HashJoinExecutorBuilder,
@@ -131,6 +148,13 @@ pub fn create_executor(
         NodeBody::DynamicFilter => DynamicFilterExecutorBuilder,
         NodeBody::ProjectSet => ProjectSetExecutorBuilder,
         NodeBody::GroupTopN => GroupTopNExecutorBuilder,
+        NodeBody::AppendOnlyGroupTopN => AppendOnlyGroupTopNExecutorBuilder,
         NodeBody::Sort => SortExecutorBuilder,
+        NodeBody::OverWindow => OverWindowExecutorBuilder,
+        NodeBody::SessionWindow => SessionWindowExecutorBuilder,
+        NodeBody::TemporalJoin => TemporalJoinExecutorBuilder,
+        NodeBody::IntervalJoin => IntervalJoinExecutorBuilder,
+        NodeBody::Now => NowExecutorBuilder,
+        NodeBody::LookupJoin => LookupJoinExecutorBuilder,
     }
 }
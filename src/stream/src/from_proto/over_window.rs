@@ -0,0 +1,70 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_expr::window_function::WindowFuncCall;
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+
+use super::*;
+use crate::executor::OverWindowExecutor;
+
+pub struct OverWindowExecutorBuilder;
+
+impl ExecutorBuilder for OverWindowExecutorBuilder {
+    fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::OverWindow)?;
+        let [input]: [_; 1] = params.input.try_into().unwrap();
+        let calls: Vec<_> = node
+            .get_calls()
+            .iter()
+            .map(WindowFuncCall::from_protobuf)
+            .try_collect()?;
+        let partition_key_indices = node
+            .get_partition_by()
+            .iter()
+            .map(|idx| *idx as usize)
+            .collect();
+        let order_key_indices = node
+            .get_order_by()
+            .iter()
+            .map(|order| order.index as usize)
+            .collect();
+        let vnodes = params.vnode_bitmap.map(Arc::new);
+        let state_table = StateTable::from_table_catalog(node.get_state_table()?, store, vnodes);
+
+        Ok(OverWindowExecutor::new(
+            params.actor_context,
+            input,
+            calls,
+            partition_key_indices,
+            order_key_indices,
+            state_table,
+            params.pk_indices,
+            params.executor_id,
+            stream.context.lru_manager.clone(),
+            stream.config.developer.unsafe_stream_over_window_cache_size,
+            stream
+                .config
+                .developer
+                .unsafe_stream_over_window_cached_partition_rows,
+        )
+        .boxed())
+    }
+}
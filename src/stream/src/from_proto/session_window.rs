@@ -0,0 +1,53 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_storage::table::streaming_table::state_table::StateTable;
+
+use super::*;
+use crate::executor::SessionWindowExecutor;
+
+pub struct SessionWindowExecutorBuilder;
+
+impl ExecutorBuilder for SessionWindowExecutorBuilder {
+    fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::SessionWindow)?;
+        let [input]: [_; 1] = params.input.try_into().unwrap();
+        let partition_key_indices = node
+            .get_partition_by()
+            .iter()
+            .map(|idx| *idx as usize)
+            .collect();
+        let vnodes = params.vnode_bitmap.map(Arc::new);
+        let state_table = StateTable::from_table_catalog(node.get_state_table()?, store, vnodes);
+
+        Ok(SessionWindowExecutor::new(
+            params.actor_context,
+            input,
+            node.time_col as usize,
+            node.get_gap()?.into(),
+            partition_key_indices,
+            state_table,
+            params.pk_indices,
+            params.executor_id,
+        )
+        .boxed())
+    }
+}
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risingwave_common::catalog::{ColumnId, TableId};
+use std::sync::Arc;
+
+use risingwave_common::catalog::ColumnId;
 
 use super::*;
 use crate::executor::SinkExecutor;
@@ -29,7 +31,6 @@ impl ExecutorBuilder for SinkExecutorBuilder {
         let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::Sink)?;
         let [materialize_executor]: [_; 1] = params.input.try_into().unwrap();
 
-        let _sink_id = TableId::from(node.table_id);
         let _column_ids = node
             .get_column_ids()
             .iter()
@@ -37,11 +38,17 @@ impl ExecutorBuilder for SinkExecutorBuilder {
             .collect::<Vec<ColumnId>>();
 
         Ok(Box::new(SinkExecutor::new(
+            params.actor_context,
             materialize_executor,
             store,
             stream.streaming_metrics.clone(),
             node.properties.clone(),
+            node.sink_id,
+            params.env.meta_client(),
             params.executor_id,
+            params.pk_indices,
+            node.log_store_table.clone(),
+            params.vnode_bitmap.map(Arc::new),
         )))
     }
 }
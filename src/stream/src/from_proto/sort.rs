@@ -30,9 +30,8 @@ impl ExecutorBuilder for SortExecutorBuilder {
     ) -> StreamResult<BoxedExecutor> {
         let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::Sort)?;
         let [input]: [_; 1] = params.input.try_into().unwrap();
-        let vnodes = Arc::new(params.vnode_bitmap.expect("vnodes not set for sort"));
-        let state_table =
-            StateTable::from_table_catalog(node.get_state_table()?, store, Some(vnodes));
+        let vnodes = params.vnode_bitmap.map(Arc::new);
+        let state_table = StateTable::from_table_catalog(node.get_state_table()?, store, vnodes);
         Ok(Box::new(SortExecutor::new(
             params.actor_context,
             input,
@@ -14,6 +14,8 @@
 
 use risingwave_common::catalog::{ColumnId, Field, Schema, TableId};
 use risingwave_common::types::DataType;
+use risingwave_connector::source::SourceRateLimit;
+use risingwave_expr::expr::build_from_prost;
 use risingwave_source::SourceDescBuilder;
 use tokio::sync::mpsc::unbounded_channel;
 
@@ -38,6 +40,7 @@ impl ExecutorBuilder for SourceExecutorBuilder {
             .register_sender(params.actor_context.id, sender);
 
         let source_id = TableId::new(node.source_id);
+        let rate_limit = SourceRateLimit::from_properties(&node.properties)?;
         let source_builder = SourceDescBuilder::new(
             source_id,
             node.row_id_index.clone(),
@@ -63,6 +66,18 @@ impl ExecutorBuilder for SourceExecutorBuilder {
             })
             .collect();
         let schema = Schema::new(fields);
+        // The executor outputs all columns of the source, so the indices in the watermark
+        // descriptions can be used directly.
+        let watermark_descs = node
+            .watermark_descs
+            .iter()
+            .map(|desc| -> StreamResult<_> {
+                Ok((
+                    desc.watermark_idx as usize,
+                    build_from_prost(desc.get_expr()?)?,
+                ))
+            })
+            .try_collect()?;
 
         let vnodes = params
             .vnode_bitmap
@@ -86,6 +101,9 @@ impl ExecutorBuilder for SourceExecutorBuilder {
             params.op_info,
             params.executor_stats,
             stream.config.barrier_interval_ms as u64,
+            watermark_descs,
+            rate_limit,
+            node.dml_enabled,
         )?))
     }
 }
@@ -0,0 +1,136 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, Schema, TableId, TableOption};
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_expr::expr::build_from_prost;
+use risingwave_pb::plan_common::{OrderType as ProstOrderType, StorageTableDesc};
+use risingwave_storage::table::batch_table::storage_table::StorageTable;
+use risingwave_storage::table::Distribution;
+
+use super::*;
+use crate::executor::TemporalJoinExecutor;
+
+pub struct TemporalJoinExecutorBuilder;
+
+impl ExecutorBuilder for TemporalJoinExecutorBuilder {
+    fn new_boxed_executor(
+        params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        stream: &mut LocalStreamManagerCore,
+    ) -> StreamResult<BoxedExecutor> {
+        let schema = Schema::new(node.fields.iter().map(Field::from).collect());
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::TemporalJoin)?;
+        let [source_l, source_r]: [_; 2] = params.input.try_into().unwrap();
+
+        // The right table is read directly from the storage, with the same partition as the
+        // upstream of the right input.
+        let right_table = build_lookup_table(
+            node.get_table_desc()?,
+            &node.table_output_column_ids,
+            params.vnode_bitmap,
+            store,
+        );
+
+        let condition = match node.get_condition() {
+            Ok(cond_prost) => Some(build_from_prost(cond_prost)?),
+            Err(_) => None,
+        };
+
+        Ok(Box::new(TemporalJoinExecutor::new(
+            params.actor_context,
+            source_l,
+            source_r,
+            right_table,
+            node.left_key.iter().map(|&k| k as usize).collect(),
+            node.right_key.iter().map(|&k| k as usize).collect(),
+            node.null_safe.clone(),
+            node.get_join_type()?,
+            condition,
+            node.output_indices.iter().map(|&x| x as usize).collect(),
+            schema,
+            params.pk_indices,
+            params.executor_id,
+            stream.context.lru_manager.clone(),
+            stream.config.developer.unsafe_stream_join_cache_size,
+            params.executor_stats,
+            params.env.config().developer.stream_chunk_size,
+        )))
+    }
+}
+
+/// Builds the table looked up by a join, which is read directly from the storage with the partition
+/// of `vnode_bitmap`.
+pub(super) fn build_lookup_table<S: StateStore>(
+    table_desc: &StorageTableDesc,
+    table_output_column_ids: &[i32],
+    vnode_bitmap: Option<Bitmap>,
+    store: S,
+) -> StorageTable<S> {
+    let order_types = table_desc
+        .pk
+        .iter()
+        .map(|desc| OrderType::from_prost(&ProstOrderType::from_i32(desc.order_type).unwrap()))
+        .collect_vec();
+    let column_descs = table_desc
+        .columns
+        .iter()
+        .map(ColumnDesc::from)
+        .collect_vec();
+    let column_ids = table_output_column_ids
+        .iter()
+        .copied()
+        .map(ColumnId::from)
+        .collect();
+    let pk_indices = table_desc.pk.iter().map(|k| k.index as usize).collect_vec();
+    let dist_key_indices = table_desc
+        .dist_key_indices
+        .iter()
+        .map(|&k| k as usize)
+        .collect_vec();
+    let distribution = match vnode_bitmap {
+        Some(vnodes) => Distribution {
+            dist_key_indices,
+            vnodes: vnodes.into(),
+        },
+        None => Distribution::fallback(),
+    };
+    let table_option = TableOption {
+        retention_seconds: if table_desc.retention_seconds > 0 {
+            Some(table_desc.retention_seconds)
+        } else {
+            None
+        },
+    };
+    let value_indices = table_desc
+        .get_value_indices()
+        .iter()
+        .map(|&k| k as usize)
+        .collect_vec();
+    StorageTable::new_partial(
+        store,
+        TableId {
+            table_id: table_desc.table_id,
+        },
+        column_descs,
+        column_ids,
+        order_types,
+        pk_indices,
+        distribution,
+        table_option,
+        value_indices,
+    )
+}
@@ -13,15 +13,19 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use prometheus::HistogramTimer;
-use risingwave_pb::stream_service::barrier_complete_response::CreateMviewProgress as ProstCreateMviewProgress;
+use risingwave_pb::stream_service::barrier_complete_response::{
+    ActorBarrierLatency, CreateMviewProgress as ProstCreateMviewProgress,
+};
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot;
 use tokio::sync::oneshot::Receiver;
 
 use self::managed_state::ManagedBarrierState;
 use crate::error::StreamResult;
+use crate::executor::monitor::StreamingMetrics;
 use crate::executor::*;
 use crate::task::ActorId;
 
@@ -42,6 +46,8 @@ pub const ENABLE_BARRIER_AGGREGATION: bool = false;
 #[derive(Debug)]
 pub struct CollectResult {
     pub create_mview_progress: Vec<ProstCreateMviewProgress>,
+    /// The time for each actor on this node to collect the barrier.
+    pub actor_barrier_latency: Vec<ActorBarrierLatency>,
 }
 
 enum BarrierState {
@@ -94,8 +100,11 @@ impl LocalBarrierManager {
     }
 
     /// Create a [`LocalBarrierManager`] with managed mode.
-    pub fn new(state_store: StateStoreImpl) -> Self {
-        Self::with_state(BarrierState::Managed(ManagedBarrierState::new(state_store)))
+    pub fn new(state_store: StateStoreImpl, metrics: Arc<StreamingMetrics>) -> Self {
+        Self::with_state(BarrierState::Managed(ManagedBarrierState::new(
+            state_store,
+            metrics,
+        )))
     }
 
     /// Register sender for source actors, used to send barriers.
@@ -13,14 +13,20 @@
 // limitations under the License.
 
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Debug, Formatter};
 use std::iter::once;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use risingwave_pb::stream_service::barrier_complete_response::CreateMviewProgress;
+use risingwave_pb::stream_service::barrier_complete_response::{
+    ActorBarrierLatency, CreateMviewProgress,
+};
 use risingwave_storage::{dispatch_state_store, StateStore, StateStoreImpl};
 use tokio::sync::oneshot;
 
 use super::progress::ChainState;
 use super::CollectResult;
+use crate::executor::monitor::StreamingMetrics;
 use crate::executor::Barrier;
 use crate::task::ActorId;
 
@@ -49,9 +55,27 @@ pub(super) struct BarrierState {
     prev_epoch: u64,
     inner: ManagedBarrierStateInner,
     checkpoint: bool,
+
+    /// When the barrier is first seen on this node, either issued by the meta service or
+    /// collected from some actor.
+    start_time: Instant,
+
+    /// The time from `start_time` to the barrier being collected from each actor.
+    actor_collect_latency: HashMap<ActorId, Duration>,
+}
+
+impl BarrierState {
+    fn new(barrier: &Barrier, inner: ManagedBarrierStateInner) -> Self {
+        Self {
+            prev_epoch: barrier.epoch.prev,
+            inner,
+            checkpoint: barrier.checkpoint,
+            start_time: Instant::now(),
+            actor_collect_latency: HashMap::new(),
+        }
+    }
 }
 
-#[derive(Debug)]
 pub(super) struct ManagedBarrierState {
     /// Record barrier state for each epoch of concurrent checkpoints.
     ///
@@ -62,15 +86,27 @@ pub(super) struct ManagedBarrierState {
     pub(super) create_mview_progress: HashMap<u64, HashMap<ActorId, ChainState>>,
 
     state_store: StateStoreImpl,
+
+    metrics: Arc<StreamingMetrics>,
+}
+
+impl Debug for ManagedBarrierState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManagedBarrierState")
+            .field("epoch_barrier_state_map", &self.epoch_barrier_state_map)
+            .field("create_mview_progress", &self.create_mview_progress)
+            .finish_non_exhaustive()
+    }
 }
 
 impl ManagedBarrierState {
     /// Create a barrier manager state. This will be called only once.
-    pub(super) fn new(state_store: StateStoreImpl) -> Self {
+    pub(super) fn new(state_store: StateStoreImpl, metrics: Arc<StreamingMetrics>) -> Self {
         Self {
             epoch_barrier_state_map: BTreeMap::default(),
             create_mview_progress: Default::default(),
             state_store,
+            metrics,
         }
     }
 
@@ -114,10 +150,14 @@ impl ManagedBarrierState {
                     .into_iter()
                     .map(|(actor, state)| CreateMviewProgress {
                         chain_actor_id: actor,
-                        done: matches!(state, ChainState::Done),
+                        done: matches!(state, ChainState::Done(_)),
                         consumed_epoch: match state {
-                            ChainState::ConsumingUpstream(consumed_epoch) => consumed_epoch,
-                            ChainState::Done => epoch,
+                            ChainState::ConsumingUpstream(consumed_epoch, _) => consumed_epoch,
+                            ChainState::Done(_) => epoch,
+                        },
+                        consumed_rows: match state {
+                            ChainState::ConsumingUpstream(_, consumed_rows) => consumed_rows,
+                            ChainState::Done(consumed_rows) => consumed_rows,
                         },
                     })
                     .collect();
@@ -131,8 +171,17 @@ impl ManagedBarrierState {
                         collect_notifier, ..
                     } => {
                         // Notify about barrier finishing.
+                        let actor_barrier_latency = barrier_state
+                            .actor_collect_latency
+                            .into_iter()
+                            .map(|(actor_id, latency)| ActorBarrierLatency {
+                                actor_id,
+                                collect_latency_us: latency.as_micros() as u64,
+                            })
+                            .collect();
                         let result = CollectResult {
                             create_mview_progress,
+                            actor_barrier_latency,
                         };
                         if collect_notifier.send(result).is_err() {
                             warn!("failed to notify barrier collection with epoch {}", epoch)
@@ -148,6 +197,7 @@ impl ManagedBarrierState {
     pub(crate) fn clear_all_states(&mut self) {
         self.epoch_barrier_state_map.clear();
         self.create_mview_progress.clear();
+        self.metrics.actor_in_flight_barrier_nums.reset();
     }
 
     /// Collect a `barrier` from the actor with `actor_id`.
@@ -167,11 +217,14 @@ impl ManagedBarrierState {
                     ManagedBarrierStateInner::Stashed {
                         ref mut collected_actors,
                     },
+                start_time,
+                ref mut actor_collect_latency,
                 ..
             }) => {
                 let new = collected_actors.insert(actor_id);
                 assert!(new);
                 assert_eq!(prev_epoch, barrier.epoch.prev);
+                actor_collect_latency.insert(actor_id, start_time.elapsed());
             }
             Some(&mut BarrierState {
                 prev_epoch,
@@ -180,6 +233,8 @@ impl ManagedBarrierState {
                         ref mut remaining_actors,
                         ..
                     },
+                start_time,
+                ref mut actor_collect_latency,
                 ..
             }) => {
                 let exist = remaining_actors.remove(&actor_id);
@@ -189,19 +244,23 @@ impl ManagedBarrierState {
                     actor_id, barrier.epoch.curr
                 );
                 assert_eq!(prev_epoch, barrier.epoch.prev);
+                actor_collect_latency.insert(actor_id, start_time.elapsed());
+                self.metrics
+                    .actor_in_flight_barrier_nums
+                    .with_label_values(&[&actor_id.to_string()])
+                    .dec();
                 self.may_notify(barrier.epoch.curr);
             }
             None => {
-                self.epoch_barrier_state_map.insert(
-                    barrier.epoch.curr,
-                    BarrierState {
-                        prev_epoch: barrier.epoch.prev,
-                        inner: ManagedBarrierStateInner::Stashed {
-                            collected_actors: once(actor_id).collect(),
-                        },
-                        checkpoint: barrier.checkpoint,
+                let mut state = BarrierState::new(
+                    barrier,
+                    ManagedBarrierStateInner::Stashed {
+                        collected_actors: once(actor_id).collect(),
                     },
                 );
+                state.actor_collect_latency.insert(actor_id, Duration::ZERO);
+                self.epoch_barrier_state_map
+                    .insert(barrier.epoch.curr, state);
             }
         }
     }
@@ -214,49 +273,44 @@ impl ManagedBarrierState {
         actor_ids_to_collect: impl IntoIterator<Item = ActorId>,
         collect_notifier: oneshot::Sender<CollectResult>,
     ) {
-        let inner = match self.epoch_barrier_state_map.get_mut(&barrier.epoch.curr) {
-            Some(&mut BarrierState {
-                inner:
+        // The barrier may have been collected from some actors and stashed before.
+        let state = self
+            .epoch_barrier_state_map
+            .entry(barrier.epoch.curr)
+            .or_insert_with(|| {
+                BarrierState::new(
+                    barrier,
                     ManagedBarrierStateInner::Stashed {
-                        ref mut collected_actors,
+                        collected_actors: HashSet::new(),
                     },
-                ..
-            }) => {
+                )
+            });
+        let remaining_actors: HashSet<ActorId> = match &mut state.inner {
+            ManagedBarrierStateInner::Stashed { collected_actors } => {
                 let remaining_actors = actor_ids_to_collect
                     .into_iter()
                     .filter(|a| !collected_actors.remove(a))
                     .collect();
                 assert!(collected_actors.is_empty());
-                ManagedBarrierStateInner::Issued {
-                    remaining_actors,
-                    collect_notifier,
-                }
+                remaining_actors
             }
-            Some(&mut BarrierState {
-                inner: ManagedBarrierStateInner::Issued { .. },
-                ..
-            }) => {
+            ManagedBarrierStateInner::Issued { .. } => {
                 panic!(
                     "barrier epochs{:?} state has already been `Issued`",
                     barrier.epoch
                 );
             }
-            None => {
-                let remaining_actors = actor_ids_to_collect.into_iter().collect();
-                ManagedBarrierStateInner::Issued {
-                    remaining_actors,
-                    collect_notifier,
-                }
-            }
         };
-        self.epoch_barrier_state_map.insert(
-            barrier.epoch.curr,
-            BarrierState {
-                prev_epoch: barrier.epoch.prev,
-                inner,
-                checkpoint: barrier.checkpoint,
-            },
-        );
+        for actor_id in &remaining_actors {
+            self.metrics
+                .actor_in_flight_barrier_nums
+                .with_label_values(&[&actor_id.to_string()])
+                .inc();
+        }
+        state.inner = ManagedBarrierStateInner::Issued {
+            remaining_actors,
+            collect_notifier,
+        };
         self.may_notify(barrier.epoch.curr);
     }
 }
@@ -264,16 +318,21 @@ impl ManagedBarrierState {
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
+    use std::sync::Arc;
 
     use risingwave_storage::StateStoreImpl;
     use tokio::sync::oneshot;
 
+    use crate::executor::monitor::StreamingMetrics;
     use crate::executor::Barrier;
     use crate::task::barrier_manager::managed_state::ManagedBarrierState;
 
     #[tokio::test]
     async fn test_managed_state_add_actor() {
-        let mut managed_barrier_state = ManagedBarrierState::new(StateStoreImpl::for_test());
+        let mut managed_barrier_state = ManagedBarrierState::new(
+            StateStoreImpl::for_test(),
+            Arc::new(StreamingMetrics::unused()),
+        );
         let barrier1 = Barrier::new_test_barrier(1);
         let barrier2 = Barrier::new_test_barrier(2);
         let barrier3 = Barrier::new_test_barrier(3);
@@ -314,7 +373,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_managed_state_stop_actor() {
-        let mut managed_barrier_state = ManagedBarrierState::new(StateStoreImpl::for_test());
+        let mut managed_barrier_state = ManagedBarrierState::new(
+            StateStoreImpl::for_test(),
+            Arc::new(StreamingMetrics::unused()),
+        );
         let barrier1 = Barrier::new_test_barrier(1);
         let barrier2 = Barrier::new_test_barrier(2);
         let barrier3 = Barrier::new_test_barrier(3);
@@ -358,7 +420,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_managed_state_issued_after_collect() {
-        let mut managed_barrier_state = ManagedBarrierState::new(StateStoreImpl::for_test());
+        let mut managed_barrier_state = ManagedBarrierState::new(
+            StateStoreImpl::for_test(),
+            Arc::new(StreamingMetrics::unused()),
+        );
         let barrier1 = Barrier::new_test_barrier(1);
         let barrier2 = Barrier::new_test_barrier(2);
         let barrier3 = Barrier::new_test_barrier(3);
@@ -430,4 +495,39 @@ mod tests {
         managed_barrier_state.transform_to_issued(&barrier3, actor_ids_to_collect3, tx3);
         assert!(managed_barrier_state.epoch_barrier_state_map.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_managed_state_barrier_latency() {
+        let metrics = Arc::new(StreamingMetrics::unused());
+        let mut managed_barrier_state =
+            ManagedBarrierState::new(StateStoreImpl::for_test(), metrics.clone());
+        let barrier = Barrier::new_test_barrier(1);
+        let (tx, rx) = oneshot::channel();
+
+        // Actor 1 collects the barrier before it's issued.
+        managed_barrier_state.collect(1, &barrier);
+        managed_barrier_state.transform_to_issued(&barrier, HashSet::from([1, 2, 3]), tx);
+        let in_flight = |actor_id: &str| {
+            metrics
+                .actor_in_flight_barrier_nums
+                .with_label_values(&[actor_id])
+                .get()
+        };
+        assert_eq!(in_flight("1"), 0);
+        assert_eq!(in_flight("2"), 1);
+        assert_eq!(in_flight("3"), 1);
+
+        managed_barrier_state.collect(2, &barrier);
+        managed_barrier_state.collect(3, &barrier);
+        assert_eq!(in_flight("2"), 0);
+        assert_eq!(in_flight("3"), 0);
+
+        let result = rx.await.unwrap();
+        let actor_ids: HashSet<_> = result
+            .actor_barrier_latency
+            .iter()
+            .map(|l| l.actor_id)
+            .collect();
+        assert_eq!(actor_ids, HashSet::from([1, 2, 3]));
+    }
 }
@@ -18,11 +18,12 @@ use super::{BarrierState, LocalBarrierManager};
 use crate::task::{ActorId, SharedContext};
 
 type ConsumedEpoch = u64;
+type ConsumedRows = u64;
 
 #[derive(Debug, Clone, Copy)]
 pub(super) enum ChainState {
-    ConsumingUpstream(ConsumedEpoch),
-    Done,
+    ConsumingUpstream(ConsumedEpoch, ConsumedRows),
+    Done(ConsumedRows),
 }
 
 impl LocalBarrierManager {
@@ -87,27 +88,37 @@ impl CreateMviewProgress {
         );
     }
 
-    /// Update the progress to `ConsumingUpstream(consumed_epoch)`. The epoch must be monotonically
-    /// increasing.
+    /// Update the progress to `ConsumingUpstream(consumed_epoch, current_consumed_rows)`. The epoch
+    /// must be monotonically non-decreasing. While consuming the snapshot, `consumed_epoch` is the
+    /// epoch of the snapshot.
     /// `current_epoch` should be provided to locate the barrier under concurrent checkpoint.
-    pub fn update(&mut self, current_epoch: u64, consumed_epoch: ConsumedEpoch) {
+    pub fn update(
+        &mut self,
+        current_epoch: u64,
+        consumed_epoch: ConsumedEpoch,
+        current_consumed_rows: ConsumedRows,
+    ) {
         match self.state {
-            Some(ChainState::ConsumingUpstream(last)) => {
-                assert!(last < consumed_epoch);
+            Some(ChainState::ConsumingUpstream(last, last_consumed_rows)) => {
+                assert!(last <= consumed_epoch);
+                assert!(last_consumed_rows <= current_consumed_rows);
             }
-            Some(ChainState::Done) => unreachable!(),
+            Some(ChainState::Done(_)) => unreachable!(),
             None => {}
         }
-        self.update_inner(current_epoch, ChainState::ConsumingUpstream(consumed_epoch));
+        self.update_inner(
+            current_epoch,
+            ChainState::ConsumingUpstream(consumed_epoch, current_consumed_rows),
+        );
     }
 
     /// Finish the progress. If the progress is already finished, then perform no-op.
     /// `current_epoch` should be provided to locate the barrier under concurrent checkpoint.
-    pub fn finish(&mut self, current_epoch: u64) {
-        if let Some(ChainState::Done) = self.state {
+    pub fn finish(&mut self, current_epoch: u64, current_consumed_rows: ConsumedRows) {
+        if let Some(ChainState::Done(_)) = self.state {
             return;
         }
-        self.update_inner(current_epoch, ChainState::Done);
+        self.update_inner(current_epoch, ChainState::Done(current_consumed_rows));
     }
 }
 
@@ -21,7 +21,10 @@ use super::*;
 
 #[tokio::test]
 async fn test_managed_barrier_collection() -> StreamResult<()> {
-    let mut manager = LocalBarrierManager::new(StateStoreImpl::for_test());
+    let mut manager = LocalBarrierManager::new(
+        StateStoreImpl::for_test(),
+        Arc::new(StreamingMetrics::unused()),
+    );
     assert!(!manager.is_local_mode());
 
     let register_sender = |actor_id: u32| {
@@ -73,7 +76,10 @@ async fn test_managed_barrier_collection() -> StreamResult<()> {
 
 #[tokio::test]
 async fn test_managed_barrier_collection_before_send_request() -> StreamResult<()> {
-    let mut manager = LocalBarrierManager::new(StateStoreImpl::for_test());
+    let mut manager = LocalBarrierManager::new(
+        StateStoreImpl::for_test(),
+        Arc::new(StreamingMetrics::unused()),
+    );
     assert!(!manager.is_local_mode());
 
     let register_sender = |actor_id: u32| {
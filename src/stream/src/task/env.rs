@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use risingwave_common::config::StreamingConfig;
 use risingwave_common::util::addr::HostAddr;
+use risingwave_rpc_client::MetaClient;
 use risingwave_source::{TableSourceManager, TableSourceManagerRef};
 use risingwave_storage::StateStoreImpl;
 
@@ -39,6 +40,10 @@ pub struct StreamEnvironment {
 
     /// State store for table scanning.
     state_store: StateStoreImpl,
+
+    /// Client to the meta server, which coordinates the commits of the two-phase sinks. `None` in
+    /// tests.
+    meta_client: Option<MetaClient>,
 }
 
 impl StreamEnvironment {
@@ -48,6 +53,7 @@ impl StreamEnvironment {
         config: Arc<StreamingConfig>,
         worker_id: WorkerNodeId,
         state_store: StateStoreImpl,
+        meta_client: MetaClient,
     ) -> Self {
         StreamEnvironment {
             server_addr,
@@ -55,6 +61,7 @@ impl StreamEnvironment {
             config,
             worker_id,
             state_store,
+            meta_client: Some(meta_client),
         }
     }
 
@@ -70,6 +77,7 @@ impl StreamEnvironment {
             state_store: StateStoreImpl::shared_in_memory_store(Arc::new(
                 StateStoreMetrics::unused(),
             )),
+            meta_client: None,
         }
     }
 
@@ -96,4 +104,8 @@ impl StreamEnvironment {
     pub fn state_store(&self) -> StateStoreImpl {
         self.state_store.clone()
     }
+
+    pub fn meta_client(&self) -> Option<MetaClient> {
+        self.meta_client.clone()
+    }
 }
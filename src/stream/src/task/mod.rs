@@ -13,11 +13,11 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 
 use anyhow::anyhow;
 use parking_lot::{Mutex, MutexGuard, RwLock};
-use risingwave_common::config::StreamingConfig;
 use risingwave_common::util::addr::HostAddr;
 use risingwave_pb::common::ActorInfo;
 use risingwave_rpc_client::ComputeClientPool;
@@ -25,6 +25,7 @@ use tokio::sync::mpsc::{Receiver, Sender};
 
 use crate::cache::{LruManager, LruManagerRef};
 use crate::error::StreamResult;
+use crate::executor::monitor::StreamingMetrics;
 use crate::executor::Message;
 
 mod barrier_manager;
@@ -99,25 +100,19 @@ impl SharedContext {
     pub fn new(
         addr: HostAddr,
         state_store: StateStoreImpl,
-        config: &StreamingConfig,
-        enable_managed_cache: bool,
+        streaming_metrics: Arc<StreamingMetrics>,
+        watermark_epoch: Arc<AtomicU64>,
     ) -> Self {
-        let create_lru_manager = || {
-            let mgr = LruManager::new(
-                config.total_memory_available_bytes,
-                config.barrier_interval_ms,
-            );
-            // Run a background memory monitor
-            tokio::spawn(mgr.clone().run());
-            mgr
-        };
         Self {
             channel_map: Default::default(),
             actor_infos: Default::default(),
             addr,
             compute_client_pool: ComputeClientPool::default(),
-            lru_manager: enable_managed_cache.then(create_lru_manager),
-            barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::new(state_store))),
+            lru_manager: Some(LruManager::new(watermark_epoch)),
+            barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::new(
+                state_store,
+                streaming_metrics,
+            ))),
         }
     }
 
@@ -130,6 +125,7 @@ impl SharedContext {
             compute_client_pool: ComputeClientPool::default(),
             barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::new(
                 StateStoreImpl::for_test(),
+                Arc::new(StreamingMetrics::unused()),
             ))),
             lru_manager: None,
         }
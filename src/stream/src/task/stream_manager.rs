@@ -15,6 +15,7 @@
 use core::time::Duration;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 
 use anyhow::{anyhow, Context};
@@ -143,7 +144,7 @@ impl LocalStreamManager {
         streaming_metrics: Arc<StreamingMetrics>,
         config: StreamingConfig,
         async_stack_trace_config: Option<TraceConfig>,
-        enable_managed_cache: bool,
+        watermark_epoch: Arc<AtomicU64>,
     ) -> Self {
         Self::with_core(LocalStreamManagerCore::new(
             addr,
@@ -151,7 +152,7 @@ impl LocalStreamManager {
             streaming_metrics,
             config,
             async_stack_trace_config,
-            enable_managed_cache,
+            watermark_epoch,
         ))
     }
 
@@ -362,9 +363,14 @@ impl LocalStreamManagerCore {
         streaming_metrics: Arc<StreamingMetrics>,
         config: StreamingConfig,
         async_stack_trace_config: Option<TraceConfig>,
-        enable_managed_cache: bool,
+        watermark_epoch: Arc<AtomicU64>,
     ) -> Self {
-        let context = SharedContext::new(addr, state_store.clone(), &config, enable_managed_cache);
+        let context = SharedContext::new(
+            addr,
+            state_store.clone(),
+            streaming_metrics.clone(),
+            watermark_epoch,
+        );
         Self::new_inner(
             state_store,
             context,
@@ -470,6 +476,12 @@ impl LocalStreamManagerCore {
                     | NodeBody::Chain(_)
                     | NodeBody::DynamicFilter(_)
                     | NodeBody::GroupTopN(_)
+                    | NodeBody::AppendOnlyGroupTopN(_)
+                    | NodeBody::OverWindow(_)
+                    | NodeBody::SessionWindow(_)
+                    | NodeBody::TemporalJoin(_)
+                    | NodeBody::IntervalJoin(_)
+                    | NodeBody::LookupJoin(_)
             )
         }
         let is_stateful = is_stateful_executor(node);
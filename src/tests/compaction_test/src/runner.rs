@@ -137,6 +137,7 @@ async fn start_meta_node(listen_addr: String, config_path: String) {
         "--enable-compaction-deterministic",
         "--config-path",
         &config_path,
+        "--disable-telemetry",
     ]);
     risingwave_meta::start(opts).await
 }
@@ -160,6 +160,7 @@ async fn main() {
                 "etcd",
                 "--etcd-endpoints",
                 "192.168.10.1:2388",
+                "--disable-telemetry",
             ]);
             risingwave_meta::start(opts).await
         })
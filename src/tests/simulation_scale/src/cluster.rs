@@ -83,6 +83,7 @@ impl Cluster {
                     "0.0.0.0:5690",
                     "--backend",
                     "mem",
+                    "--disable-telemetry",
                 ]);
                 risingwave_meta::start(opts).await
             })
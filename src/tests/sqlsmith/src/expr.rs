@@ -279,7 +279,7 @@ impl<'a, R: Rng> SqlGenerator<'a, R> {
             A::Count => Some(Expr::Function(make_agg_func("count", exprs, distinct))),
             A::Avg => Some(Expr::Function(make_agg_func("avg", exprs, distinct))),
             A::StringAgg => Some(Expr::Function(make_agg_func("string_agg", exprs, distinct))),
-            A::FirstValue => None,
+            A::FirstValue | A::LastValue => None,
             A::ApproxCountDistinct => {
                 if distinct {
                     None
@@ -394,6 +394,7 @@ fn make_simple_func(func_name: &str, exprs: &[Expr]) -> Function {
         distinct: false,
         order_by: vec![],
         filter: None,
+        within_group: None,
     }
 }
 
@@ -415,6 +416,7 @@ fn make_agg_func(func_name: &str, exprs: &[Expr], _distinct: bool) -> Function {
         distinct: false,
         order_by: vec![],
         filter: None,
+        within_group: None,
     }
 }
 
@@ -61,6 +61,7 @@ impl<'a, R: Rng> SqlGenerator<'a, R> {
         let mut table = self.tables.choose(&mut self.rng).unwrap().clone();
         let table_factor = TableFactor::Table {
             name: ObjectName(vec![Ident::new(&table.name)]),
+            for_system_time_as_of_proctime: false,
             alias: Some(TableAlias {
                 name: alias.as_str().into(),
                 columns: vec![],
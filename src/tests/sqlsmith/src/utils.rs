@@ -59,6 +59,7 @@ impl<'a, R: Rng> SqlGenerator<'a, R> {
 pub(crate) fn create_table_factor_from_table(table: &Table) -> TableFactor {
     TableFactor::Table {
         name: ObjectName(vec![Ident::new(&table.name)]),
+        for_system_time_as_of_proctime: false,
         alias: None,
     }
 }
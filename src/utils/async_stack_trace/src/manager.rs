@@ -132,7 +132,11 @@ where
     K: std::hash::Hash + Eq + std::fmt::Debug,
 {
     /// Register with given key. Returns a sender that can be called `trace` on.
+    ///
+    /// The keys of finished tasks are pruned on registration, so that the manager doesn't grow
+    /// with the tasks registered with fresh keys if `get_all` is never called.
     pub fn register(&mut self, key: K) -> TraceReporter {
+        self.prune();
         let (tx, rx) = watch::channel(Default::default());
         self.rxs.try_insert(key, rx).unwrap();
         TraceReporter { tx }
@@ -144,7 +148,12 @@ where
     /// heavy work and never yields, one may check how long the captured time has elapsed to confirm
     /// this.
     pub fn get_all(&mut self) -> impl Iterator<Item = (&K, watch::Ref<'_, StackTraceReport>)> {
-        self.rxs.retain(|_, rx| rx.has_changed().is_ok());
+        self.prune();
         self.rxs.iter_mut().map(|(k, v)| (k, v.borrow_and_update()))
     }
+
+    /// Removes the tasks whose reporters have been dropped.
+    fn prune(&mut self) {
+        self.rxs.retain(|_, rx| rx.has_changed().is_ok());
+    }
 }
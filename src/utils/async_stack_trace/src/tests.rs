@@ -21,7 +21,7 @@ use tokio::sync::watch;
 
 use crate::context::with_context;
 use crate::manager::TraceConfig;
-use crate::{StackTrace, TraceReporter};
+use crate::{StackTrace, StackTraceManager, TraceReporter};
 
 async fn sleep(time: u64) {
     tokio::time::sleep(std::time::Duration::from_millis(time)).await;
@@ -147,3 +147,25 @@ async fn test_stack_trace_display() {
 
     collector.await.unwrap();
 }
+
+#[tokio::test]
+async fn test_manager_prune_on_register() {
+    let mut manager = StackTraceManager::default();
+
+    let reporter = manager.register(233);
+    reporter
+        .trace(
+            sleep(10),
+            "upload task 233",
+            TraceConfig {
+                report_detached: true,
+                verbose: false,
+                interval: Duration::from_millis(1),
+            },
+        )
+        .await;
+
+    // The finished task is pruned, so the key can be registered again.
+    let _reporter = manager.register(233);
+    assert_eq!(manager.get_all().count(), 1);
+}
@@ -12,8 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
-use std::str::FromStr;
 use std::sync::Arc;
 use std::vec::IntoIter;
 
@@ -21,9 +19,8 @@ use anyhow::anyhow;
 use bytes::Bytes;
 use futures::stream::FusedStream;
 use futures::{Stream, StreamExt, TryStreamExt};
-use itertools::zip_eq;
+use itertools::{zip_eq, Itertools};
 use postgres_types::{FromSql, Type};
-use regex::Regex;
 use tokio::io::{AsyncRead, AsyncWrite};
 
 use crate::error::{PsqlError, PsqlResult};
@@ -32,28 +29,18 @@ use crate::pg_message::{BeCommandCompleteMessage, BeMessage};
 use crate::pg_protocol::{cstr_to_str, Conn};
 use crate::pg_response::{PgResponse, RowSetResult};
 use crate::pg_server::{Session, SessionManager};
-use crate::types::Row;
+use crate::types::{Format, Row};
 
-#[derive(Default)]
 pub struct PgStatement {
     name: String,
     prepared_statement: PreparedStatement,
-    row_description: Vec<PgFieldDescriptor>,
-    is_query: bool,
 }
 
 impl PgStatement {
-    pub fn new(
-        name: String,
-        prepared_statement: PreparedStatement,
-        row_description: Vec<PgFieldDescriptor>,
-        is_query: bool,
-    ) -> Self {
+    pub fn new(name: String, prepared_statement: PreparedStatement) -> Self {
         PgStatement {
             name,
             prepared_statement,
-            row_description,
-            is_query,
         }
     }
 
@@ -62,40 +49,58 @@ impl PgStatement {
     }
 
     pub fn type_desc(&self) -> Vec<TypeOid> {
-        self.prepared_statement.type_description()
+        self.prepared_statement.param_types().to_vec()
     }
 
     pub fn row_desc(&self) -> Vec<PgFieldDescriptor> {
-        self.row_description.clone()
+        self.prepared_statement.row_description().to_vec()
     }
 
+    /// Binds the params to the statement to get a portal. The params are decoded into text format
+    /// according to their types, and will be bound as literals when the portal is executed.
     pub fn instance<VS>(
         &self,
         portal_name: String,
-        params: &[Bytes],
-        result_format: bool,
-        param_format: bool,
+        params: &[Option<Bytes>],
+        param_format_codes: &[i16],
+        result_format_codes: &[i16],
     ) -> PsqlResult<PgPortal<VS>>
     where
         VS: Stream<Item = RowSetResult> + Unpin + Send,
     {
-        let instance_query_string = self.prepared_statement.instance(params, param_format)?;
+        let param_types = self.prepared_statement.param_types();
+        if params.len() != param_types.len() {
+            return Err(PsqlError::Internal(anyhow!(
+                "bind message supplies {} parameters, but prepared statement \"{}\" requires {}",
+                params.len(),
+                self.name,
+                param_types.len()
+            )));
+        }
+        let param_formats = Format::batch_from_i16(param_format_codes, params.len())?;
+        let params = decode_params(param_types, params, &param_formats)?;
+
+        let mut row_description = self.row_desc();
+        let result_formats = Format::batch_from_i16(result_format_codes, row_description.len())?;
+        for (field, format) in row_description.iter_mut().zip_eq(&result_formats) {
+            field.set_format(*format);
+        }
 
         Ok(PgPortal {
             name: portal_name,
-            query_string: instance_query_string,
-            result_format,
-            is_query: self.is_query,
-            row_description: self.row_description.clone(),
+            prepared_statement: self.prepared_statement.clone(),
+            params,
+            result_formats,
+            row_description,
             result: None,
             row_cache: vec![].into_iter(),
         })
     }
 
-    /// We define the statement start with ("select","values","show","with","describe") is query
-    /// statement. Because these statement will return a result set.
+    /// A statement that returns rows, e.g. `SELECT`, `SHOW` and `DESCRIBE`, has a non-empty row
+    /// description.
     pub fn is_query(&self) -> bool {
-        self.is_query
+        !self.prepared_statement.row_description().is_empty()
     }
 }
 
@@ -104,9 +109,9 @@ where
     VS: Stream<Item = RowSetResult> + Unpin + Send,
 {
     name: String,
-    query_string: String,
-    result_format: bool,
-    is_query: bool,
+    prepared_statement: PreparedStatement,
+    params: Vec<Option<String>>,
+    result_formats: Vec<Format>,
     row_description: Vec<PgFieldDescriptor>,
     result: Option<PgResponse<VS>>,
     row_cache: IntoIter<Row>,
@@ -121,7 +126,7 @@ where
     }
 
     pub fn query_string(&self) -> String {
-        self.query_string.clone()
+        self.prepared_statement.sql().to_string()
     }
 
     pub fn row_desc(&self) -> Vec<PgFieldDescriptor> {
@@ -141,13 +146,16 @@ where
             result
         } else {
             let result = session
-                .run_statement(self.query_string.as_str(), self.result_format)
+                .run_prepared_statement(
+                    &self.prepared_statement,
+                    self.params.clone(),
+                    self.result_formats.clone(),
+                )
                 .await
                 .map_err(|err| PsqlError::ExecuteError(err))?;
             self.result = Some(result);
             self.result.as_mut().unwrap()
         };
-
         // Indicate all data from stream have been completely consumed.
         let mut query_end = false;
         let mut query_row_count = 0;
@@ -217,644 +225,227 @@ where
         Ok(())
     }
 
-    /// We define the statement start with ("select","values","show","with","describe") is query
-    /// statement. Because these statement will return a result set.
     pub fn is_query(&self) -> bool {
-        self.is_query
+        !self.row_description.is_empty()
     }
 }
 
-#[derive(Default)]
+/// A statement of the extended query protocol parsed by [`Session::parse`].
+#[derive(Clone, Debug)]
 pub struct PreparedStatement {
-    raw_statement: String,
-
-    /// Generic param information used for simplify replace_param().
-    ///
-    /// e.g.
-    /// raw_statement : "select * from table where a = $1 and b = $2::INT"
-    /// param_tokens : {{1,"$1"},{2,"$2::INT"}}
-    param_tokens: HashMap<usize, String>,
-
+    sql: String,
+    /// The types of all the params, including the ones inferred by the session.
     param_types: Vec<TypeOid>,
+    /// The description of the returned rows, which is empty if the statement returns no rows.
+    row_description: Vec<PgFieldDescriptor>,
 }
 
 impl PreparedStatement {
-    /// parse_statement is to parse the type information from raw_statement and
-    /// provided_param_types.
-    ///
-    /// raw_statement is the sql statement may with generic param. (e.g. "select * from table where
-    /// a = $1") provided_param_types is the type information provided by user.
-    ///
-    /// Why we need parse:
-    /// The point is user may not provided type information in provided_param_types explicitly.
-    /// - They may provide in the raw_statement implicitly (e.g. "select * from table where a =
-    ///   $1::INT") .
-    /// - Or they don't provide. In default, We will treat these unknow type as 'VARCHAR'.
-    /// So we need to integrate these param information to generate a complete type
-    /// information(PreparedStatement::param_types).
-    pub fn parse_statement(
-        raw_statement: String,
-        provided_param_types: Vec<TypeOid>,
-    ) -> PsqlResult<Self> {
-        // Match all generic param.
-        // e.g.
-        // raw_statement = "select * from table where a = $1 and b = $2::INT4"
-        // generic_params will be {"$1","$2::INT4"}
-        let parameter_pattern =
-            Regex::new(r"\$[0-9][0-9]*::[a-zA-Z]+[0-9]*|\$[0-9][0-8]*").unwrap();
-        let generic_params: Vec<String> = parameter_pattern
-            .find_iter(raw_statement.as_str())
-            .map(|m| m.as_str().to_string())
-            .collect();
-
-        if generic_params.is_empty() {
-            return Ok(PreparedStatement {
-                raw_statement,
-                param_types: provided_param_types,
-                param_tokens: HashMap::new(),
-            });
-        }
-
-        let mut param_tokens = HashMap::new();
-        let mut param_records: Vec<Option<TypeOid>> = vec![None; 1];
-
-        // Parse the implicit type information.
-        // e.g.
-        // generic_params = {"$1::VARCHAR","$2::INT4","$3"}
-        // param_record will be {Some(Type::VARCHAR),Some(Type::INT4),None}
-        // None means the type information isn't provided implicitly. Such as '$3' above.
-        for param in generic_params {
-            let token = param.clone();
-            let mut param = param.split("::");
-            let param_idx = param
-                .next()
-                .unwrap()
-                .trim_start_matches('$')
-                .parse::<usize>()
-                .unwrap();
-            let param_type = if let Some(str) = param.next() {
-                Some(TypeOid::from_str(str).map_err(|_| {
-                    PsqlError::ParseError(format!("Invalid type name {}", str).into())
-                })?)
-            } else {
-                None
-            };
-            if param_idx > param_records.len() {
-                param_records.resize(param_idx, None);
-            }
-            param_records[param_idx - 1] = param_type;
-            param_tokens.insert(param_idx, token);
-        }
-
-        // Integrate the param_records and provided_param_types.
-        if provided_param_types.len() > param_records.len() {
-            param_records.resize(provided_param_types.len(), None);
-        }
-        for (idx, param_record) in param_records.iter_mut().enumerate() {
-            if let Some(param_record) = param_record {
-                // Check consistency of param type.
-                if idx < provided_param_types.len() && provided_param_types[idx] != *param_record {
-                    return Err(PsqlError::ParseError(
-                        format!("Type mismatch for parameter ${}", idx).into(),
-                    ));
-                }
-                continue;
-            }
-            if idx < provided_param_types.len() {
-                *param_record = Some(provided_param_types[idx]);
-            } else {
-                // If the type information isn't provided implicitly or explicitly, we just assign
-                // it as VARCHAR.
-                *param_record = Some(TypeOid::Varchar);
-            }
-        }
-
-        let param_types = param_records.into_iter().map(|x| x.unwrap()).collect();
-
-        Ok(PreparedStatement {
-            raw_statement,
-            param_tokens,
+    pub fn new(
+        sql: String,
+        param_types: Vec<TypeOid>,
+        row_description: Vec<PgFieldDescriptor>,
+    ) -> Self {
+        Self {
+            sql,
             param_types,
-        })
-    }
-
-    /// parse_params is to parse raw_params:&[Bytes] into params:[String].
-    /// The param produced by this function will be used in the PreparedStatement.
-    ///
-    /// type_description is a list of type oids.
-    /// raw_params is a list of raw params.
-    /// param_format is format code : false for text, true for binary.
-    ///
-    /// # Example
-    ///
-    /// ```ignore
-    /// let raw_params = vec!["A".into(), "B".into(), "C".into()];
-    /// let type_description = vec![TypeOid::Varchar; 3];
-    /// let params = parse_params(&type_description, &raw_params,false);
-    /// assert_eq!(params, vec!["'A'", "'B'", "'C'"])
-    ///
-    /// let raw_params = vec!["1".into(), "2".into(), "3.1".into()];
-    /// let type_description = vec![TypeOid::INT,TypeOid::INT,TypeOid::FLOAT4];
-    /// let params = parse_params(&type_description, &raw_params,false);
-    /// assert_eq!(params, vec!["1::INT", "2::INT", "3.1::FLOAT4"])
-    /// ```
-    fn parse_params(
-        type_description: &[TypeOid],
-        raw_params: &[Bytes],
-        param_format: bool,
-    ) -> PsqlResult<Vec<String>> {
-        if type_description.len() != raw_params.len() {
-            return Err(PsqlError::Internal(anyhow!(
-                "The number of params doesn't match the number of types"
-            )));
-        }
-        assert_eq!(type_description.len(), raw_params.len());
-
-        let mut params = Vec::with_capacity(raw_params.len());
-
-        // BINARY FORMAT PARAMS
-        let place_hodler = Type::ANY;
-        for (type_oid, raw_param) in zip_eq(type_description.iter(), raw_params.iter()) {
-            let str = match type_oid {
-                TypeOid::Varchar => {
-                    format!("'{}'", cstr_to_str(raw_param).unwrap())
-                }
-                TypeOid::Boolean => {
-                    if param_format {
-                        bool::from_sql(&place_hodler, raw_param)
-                            .unwrap()
-                            .to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    }
-                }
-                TypeOid::BigInt => {
-                    let tmp = if param_format {
-                        i64::from_sql(&place_hodler, raw_param).unwrap().to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("{}::BIGINT", tmp)
-                }
-                TypeOid::SmallInt => {
-                    let tmp = if param_format {
-                        i16::from_sql(&place_hodler, raw_param).unwrap().to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("{}::SMALLINT", tmp)
-                }
-                TypeOid::Int => {
-                    let tmp = if param_format {
-                        i32::from_sql(&place_hodler, raw_param).unwrap().to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("{}::INT", tmp)
-                }
-                TypeOid::Float4 => {
-                    let tmp = if param_format {
-                        f32::from_sql(&place_hodler, raw_param).unwrap().to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("'{}'::FLOAT4", tmp)
-                }
-                TypeOid::Float8 => {
-                    let tmp = if param_format {
-                        f64::from_sql(&place_hodler, raw_param).unwrap().to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("'{}'::FLOAT8", tmp)
-                }
-                TypeOid::Date => {
-                    let tmp = if param_format {
-                        chrono::NaiveDate::from_sql(&place_hodler, raw_param)
-                            .unwrap()
-                            .to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("'{}'::DATE", tmp)
-                }
-                TypeOid::Time => {
-                    let tmp = if param_format {
-                        chrono::NaiveTime::from_sql(&place_hodler, raw_param)
-                            .unwrap()
-                            .to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("'{}'::TIME", tmp)
-                }
-                TypeOid::Timestamp => {
-                    let tmp = if param_format {
-                        chrono::NaiveDateTime::from_sql(&place_hodler, raw_param)
-                            .unwrap()
-                            .to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("'{}'::TIMESTAMP", tmp)
-                }
-                TypeOid::Decimal => {
-                    let tmp = if param_format {
-                        rust_decimal::Decimal::from_sql(&place_hodler, raw_param)
-                            .unwrap()
-                            .to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("'{}'::DECIMAL", tmp)
-                }
-                TypeOid::Timestamptz => {
-                    let tmp = if param_format {
-                        chrono::DateTime::<chrono::Utc>::from_sql(&place_hodler, raw_param)
-                            .unwrap()
-                            .to_string()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("'{}'::TIMESTAMPTZ", tmp)
-                }
-                TypeOid::Interval => {
-                    let tmp = if param_format {
-                        pg_interval::Interval::from_sql(&place_hodler, raw_param)
-                            .unwrap()
-                            .to_postgres()
-                    } else {
-                        cstr_to_str(raw_param).unwrap().to_string()
-                    };
-                    format!("'{}'::INTERVAL", tmp)
-                }
-            };
-            params.push(str)
+            row_description,
         }
-
-        Ok(params)
     }
 
-    /// `default_params` creates default params from type oids for
-    /// [`PreparedStatement::instance_default`].
-    fn default_params(type_description: &[TypeOid]) -> PsqlResult<Vec<String>> {
-        let mut params: _ = Vec::new();
-        for oid in type_description.iter() {
-            match oid {
-                TypeOid::Boolean => params.push("false".to_string()),
-                TypeOid::BigInt => params.push("0::BIGINT".to_string()),
-                TypeOid::SmallInt => params.push("0::SMALLINT".to_string()),
-                TypeOid::Int => params.push("0::INT".to_string()),
-                TypeOid::Float4 => params.push("0::FLOAT4".to_string()),
-                TypeOid::Float8 => params.push("0::FLOAT8".to_string()),
-                TypeOid::Varchar => params.push("'0'".to_string()),
-                TypeOid::Date => params.push("'2021-01-01'::DATE".to_string()),
-                TypeOid::Time => params.push("'00:00:00'::TIME".to_string()),
-                TypeOid::Timestamp => params.push("'2021-01-01 00:00:00'::TIMESTAMP".to_string()),
-                TypeOid::Decimal => params.push("'0'::DECIMAL".to_string()),
-                TypeOid::Timestamptz => {
-                    params.push("'2022-10-01 12:00:00+01:00'::timestamptz".to_string())
-                }
-                TypeOid::Interval => params.push("'2 months ago'::interval".to_string()),
-            };
-        }
-        Ok(params)
+    pub fn sql(&self) -> &str {
+        &self.sql
     }
 
-    fn replace_params(&self, params: &[String]) -> String {
-        let mut tmp = self.raw_statement.clone();
-
-        for (idx, generic_param) in &self.param_tokens {
-            let param = &params[*idx - 1];
-            tmp = tmp.replace(generic_param, param);
-        }
-
-        tmp
+    pub fn param_types(&self) -> &[TypeOid] {
+        &self.param_types
     }
 
-    pub fn type_description(&self) -> Vec<TypeOid> {
-        self.param_types.clone()
+    pub fn row_description(&self) -> &[PgFieldDescriptor] {
+        &self.row_description
     }
+}
+
+/// Decodes the raw params of a Bind message into text format, which is understood by the session
+/// regardless of the format the client uses. `None` stands for NULL.
+///
+/// # Example
+///
+/// ```ignore
+/// let raw_params = vec![Some("1".into()), None];
+/// let params = decode_params(&[TypeOid::Int, TypeOid::Varchar], &raw_params, &[Format::Text; 2]);
+/// assert_eq!(params, vec![Some("1".to_string()), None])
+/// ```
+fn decode_params(
+    param_types: &[TypeOid],
+    raw_params: &[Option<Bytes>],
+    param_formats: &[Format],
+) -> PsqlResult<Vec<Option<String>>> {
+    zip_eq(param_types, zip_eq(raw_params, param_formats))
+        .map(|(type_oid, (raw_param, format))| {
+            raw_param
+                .as_ref()
+                .map(|raw_param| decode_param(*type_oid, raw_param, *format))
+                .transpose()
+        })
+        .collect()
+}
 
-    /// `instance_default` used in parse phase.
-    /// At parse phase, user still do not provide params but we need to infer the sql result.(The
-    /// session can't support infer the sql with generic param now). Hence to get a sql without
-    /// generic param, we used `default_params()` to generate default params according `param_types`
-    /// and replace the generic param with them.
-    pub fn instance_default(&self) -> PsqlResult<String> {
-        let default_params = Self::default_params(&self.param_types)?;
-        Ok(self.replace_params(&default_params))
+fn decode_param(type_oid: TypeOid, raw_param: &Bytes, format: Format) -> PsqlResult<String> {
+    fn from_sql<'a, T: FromSql<'a>>(raw_param: &'a [u8]) -> PsqlResult<T> {
+        T::from_sql(&Type::ANY, raw_param).map_err(PsqlError::ParseError)
     }
 
-    pub fn instance(&self, raw_params: &[Bytes], param_format: bool) -> PsqlResult<String> {
-        let params = Self::parse_params(&self.param_types, raw_params, param_format)?;
-        Ok(self.replace_params(&params))
+    let to_str = |raw_param: &Bytes| {
+        cstr_to_str(raw_param)
+            .map(str::to_string)
+            .map_err(|err| PsqlError::ParseError(Box::new(err)))
+    };
+
+    if format == Format::Text {
+        return to_str(raw_param);
     }
+    let param = match type_oid {
+        TypeOid::Varchar => to_str(raw_param)?,
+        TypeOid::Boolean => from_sql::<bool>(raw_param)?.to_string(),
+        TypeOid::BigInt => from_sql::<i64>(raw_param)?.to_string(),
+        TypeOid::SmallInt => from_sql::<i16>(raw_param)?.to_string(),
+        TypeOid::Int => from_sql::<i32>(raw_param)?.to_string(),
+        TypeOid::Float4 => from_sql::<f32>(raw_param)?.to_string(),
+        TypeOid::Float8 => from_sql::<f64>(raw_param)?.to_string(),
+        TypeOid::Date => from_sql::<chrono::NaiveDate>(raw_param)?.to_string(),
+        TypeOid::Time => from_sql::<chrono::NaiveTime>(raw_param)?.to_string(),
+        TypeOid::Timestamp => from_sql::<chrono::NaiveDateTime>(raw_param)?.to_string(),
+        TypeOid::Decimal => from_sql::<rust_decimal::Decimal>(raw_param)?.to_string(),
+        TypeOid::Timestamptz => from_sql::<chrono::DateTime<chrono::Utc>>(raw_param)?.to_string(),
+        TypeOid::Interval => from_sql::<pg_interval::Interval>(raw_param)?.to_postgres(),
+        // The binary format of jsonb is a version number followed by the text form.
+        TypeOid::Jsonb => to_str(&raw_param.slice(1..))?,
+    };
+    Ok(param)
 }
 
 #[cfg(test)]
 mod tests {
+    use bytes::Bytes;
     use chrono::{DateTime, NaiveDateTime, Utc};
     use pg_interval::Interval;
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use postgres_types::private::BytesMut;
     use tokio_postgres::types::{ToSql, Type};
 
-    use crate::pg_extended::PreparedStatement;
+    use super::decode_params;
     use crate::pg_field_descriptor::TypeOid;
-
-    #[test]
-    fn test_prepared_statement_without_param() {
-        let raw_statement = "SELECT * FROM test_table".to_string();
-        let prepared_statement = PreparedStatement::parse_statement(raw_statement, vec![]).unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("SELECT * FROM test_table" == default_sql);
-        let sql = prepared_statement.instance(&[], false).unwrap();
-        assert!("SELECT * FROM test_table" == sql);
+    use crate::types::Format;
+
+    fn to_binary_params(values: &[&(dyn ToSql + Sync)]) -> Vec<Option<Bytes>> {
+        let place_holder = Type::ANY;
+        values
+            .iter()
+            .map(|value| {
+                let mut buf = BytesMut::new();
+                value.to_sql(&place_holder, &mut buf).unwrap();
+                Some(buf.freeze())
+            })
+            .collect()
     }
 
-    #[test]
-    fn test_prepared_statement_with_explicit_param() {
-        let raw_statement = "SELECT * FROM test_table WHERE id = $1".to_string();
-        let prepared_statement =
-            PreparedStatement::parse_statement(raw_statement, vec![TypeOid::Int]).unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("SELECT * FROM test_table WHERE id = 0::INT" == default_sql);
-        let sql = prepared_statement.instance(&["1".into()], false).unwrap();
-        assert!("SELECT * FROM test_table WHERE id = 1::INT" == sql);
-
-        let raw_statement = "INSERT INTO test (index,data) VALUES ($1,$2)".to_string();
-        let prepared_statement =
-            PreparedStatement::parse_statement(raw_statement, vec![TypeOid::Int, TypeOid::Varchar])
-                .unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("INSERT INTO test (index,data) VALUES (0::INT,'0')" == default_sql);
-        let sql = prepared_statement
-            .instance(&["1".into(), "DATA".into()], false)
-            .unwrap();
-        assert!("INSERT INTO test (index,data) VALUES (1::INT,'DATA')" == sql);
-
-        let raw_statement = "UPDATE COFFEES SET SALES = $1 WHERE COF_NAME LIKE $2".to_string();
-        let prepared_statement =
-            PreparedStatement::parse_statement(raw_statement, vec![TypeOid::Int, TypeOid::Varchar])
-                .unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("UPDATE COFFEES SET SALES = 0::INT WHERE COF_NAME LIKE '0'" == default_sql);
-        let sql = prepared_statement
-            .instance(&["1".into(), "DATA".into()], false)
-            .unwrap();
-        assert!("UPDATE COFFEES SET SALES = 1::INT WHERE COF_NAME LIKE 'DATA'" == sql);
-
-        let raw_statement = "SELECT * FROM test_table WHERE id = $1 AND name = $3".to_string();
-        let prepared_statement = PreparedStatement::parse_statement(
-            raw_statement,
-            vec![TypeOid::Int, TypeOid::Varchar, TypeOid::Varchar],
-        )
-        .unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("SELECT * FROM test_table WHERE id = 0::INT AND name = '0'" == default_sql);
-        let sql = prepared_statement
-            .instance(&["1".into(), "DATA".into(), "NAME".into()], false)
-            .unwrap();
-        assert!("SELECT * FROM test_table WHERE id = 1::INT AND name = 'NAME'" == sql);
+    fn text(params: &[&str]) -> Vec<Option<String>> {
+        params.iter().map(|p| Some(p.to_string())).collect()
     }
 
     #[test]
-    fn test_prepared_statement_with_implicit_param() {
-        let raw_statement = "SELECT * FROM test_table WHERE id = $1::INT".to_string();
-        let prepared_statement = PreparedStatement::parse_statement(raw_statement, vec![]).unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("SELECT * FROM test_table WHERE id = 0::INT" == default_sql);
-        let sql = prepared_statement.instance(&["1".into()], false).unwrap();
-        assert!("SELECT * FROM test_table WHERE id = 1::INT" == sql);
-
-        let raw_statement =
-            "INSERT INTO test (index,data) VALUES ($1::INT4,$2::VARCHAR)".to_string();
-        let prepared_statement = PreparedStatement::parse_statement(raw_statement, vec![]).unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("INSERT INTO test (index,data) VALUES (0::INT,'0')" == default_sql);
-        let sql = prepared_statement
-            .instance(&["1".into(), "DATA".into()], false)
-            .unwrap();
-        assert!("INSERT INTO test (index,data) VALUES (1::INT,'DATA')" == sql);
-
-        let raw_statement =
-            "UPDATE COFFEES SET SALES = $1::INT WHERE COF_NAME LIKE $2::VARCHAR".to_string();
-        let prepared_statement = PreparedStatement::parse_statement(raw_statement, vec![]).unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("UPDATE COFFEES SET SALES = 0::INT WHERE COF_NAME LIKE '0'" == default_sql);
-        let sql = prepared_statement
-            .instance(&["1".into(), "DATA".into()], false)
-            .unwrap();
-        assert!("UPDATE COFFEES SET SALES = 1::INT WHERE COF_NAME LIKE 'DATA'" == sql);
-    }
-
-    #[test]
-    fn test_prepared_statement_with_mix_param() {
-        let raw_statement =
-            "SELECT * FROM test_table WHERE id = $1 AND name = $2::VARCHAR".to_string();
-        let prepared_statement =
-            PreparedStatement::parse_statement(raw_statement, vec![TypeOid::Int]).unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("SELECT * FROM test_table WHERE id = 0::INT AND name = '0'" == default_sql);
-        let sql = prepared_statement
-            .instance(&["1".into(), "DATA".into()], false)
-            .unwrap();
-        assert!("SELECT * FROM test_table WHERE id = 1::INT AND name = 'DATA'" == sql);
-
-        let raw_statement = "INSERT INTO test (index,data) VALUES ($1,$2)".to_string();
-        let prepared_statement =
-            PreparedStatement::parse_statement(raw_statement, vec![TypeOid::Int]).unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("INSERT INTO test (index,data) VALUES (0::INT,'0')" == default_sql);
-        let sql = prepared_statement
-            .instance(&["1".into(), "DATA".into()], false)
-            .unwrap();
-        assert!("INSERT INTO test (index,data) VALUES (1::INT,'DATA')" == sql);
-
-        let raw_statement =
-            "UPDATE COFFEES SET SALES = $1 WHERE COF_NAME LIKE $2::VARCHAR".to_string();
-        let prepared_statement =
-            PreparedStatement::parse_statement(raw_statement, vec![TypeOid::Int]).unwrap();
-        let default_sql = prepared_statement.instance_default().unwrap();
-        assert!("UPDATE COFFEES SET SALES = 0::INT WHERE COF_NAME LIKE '0'" == default_sql);
-        let sql = prepared_statement
-            .instance(&["1".into(), "DATA".into()], false)
-            .unwrap();
-        assert!("UPDATE COFFEES SET SALES = 1::INT WHERE COF_NAME LIKE 'DATA'" == sql);
-    }
-    #[test]
-
-    fn test_parse_params_text() {
-        let raw_params = vec!["A".into(), "B".into(), "C".into()];
+    fn test_decode_params_text() {
+        let raw_params = vec![Some("A".into()), None, Some("C".into())];
         let type_description = vec![TypeOid::Varchar; 3];
-        let params =
-            PreparedStatement::parse_params(&type_description, &raw_params, false).unwrap();
-        assert_eq!(params, vec!["'A'", "'B'", "'C'"]);
-
-        let raw_params = vec!["false".into(), "true".into()];
-        let type_description = vec![TypeOid::Boolean; 2];
-        let params =
-            PreparedStatement::parse_params(&type_description, &raw_params, false).unwrap();
-        assert_eq!(params, vec!["false", "true"]);
-
-        let raw_params = vec!["1".into(), "2".into(), "3".into()];
-        let type_description = vec![TypeOid::SmallInt, TypeOid::Int, TypeOid::BigInt];
-        let params =
-            PreparedStatement::parse_params(&type_description, &raw_params, false).unwrap();
-        assert_eq!(params, vec!["1::SMALLINT", "2::INT", "3::BIGINT"]);
-
-        let raw_params = vec![
-            "1.0".into(),
-            "2.0".into(),
-            rust_decimal::Decimal::from_f32_retain(3.0_f32)
-                .unwrap()
-                .to_string()
-                .into(),
-        ];
-        let type_description = vec![TypeOid::Float4, TypeOid::Float8, TypeOid::Decimal];
-        let params =
-            PreparedStatement::parse_params(&type_description, &raw_params, false).unwrap();
+        let params = decode_params(&type_description, &raw_params, &[Format::Text; 3]).unwrap();
         assert_eq!(
             params,
-            vec!["'1.0'::FLOAT4", "'2.0'::FLOAT8", "'3'::DECIMAL"]
+            vec![Some("A".to_string()), None, Some("C".to_string())]
         );
 
+        let raw_params = vec![Some("1".into()), Some("2".into()), Some("3".into())];
+        let type_description = vec![TypeOid::SmallInt, TypeOid::Int, TypeOid::BigInt];
+        let params = decode_params(&type_description, &raw_params, &[Format::Text; 3]).unwrap();
+        assert_eq!(params, text(&["1", "2", "3"]));
+
         let raw_params = vec![
-            chrono::NaiveDate::from_ymd(2021, 1, 1).to_string().into(),
-            chrono::NaiveTime::from_hms(12, 0, 0).to_string().into(),
-            chrono::NaiveDateTime::from_timestamp(1610000000, 0)
-                .to_string()
-                .into(),
+            Some(chrono::NaiveDate::from_ymd(2021, 1, 1).to_string().into()),
+            Some(
+                chrono::NaiveDateTime::from_timestamp(1610000000, 0)
+                    .to_string()
+                    .into(),
+            ),
         ];
-        let type_description = vec![TypeOid::Date, TypeOid::Time, TypeOid::Timestamp];
-        let params =
-            PreparedStatement::parse_params(&type_description, &raw_params, false).unwrap();
-        assert_eq!(
-            params,
-            vec![
-                "'2021-01-01'::DATE",
-                "'12:00:00'::TIME",
-                "'2021-01-07 06:13:20'::TIMESTAMP"
-            ]
-        );
+        let type_description = vec![TypeOid::Date, TypeOid::Timestamp];
+        let params = decode_params(&type_description, &raw_params, &[Format::Text; 2]).unwrap();
+        assert_eq!(params, text(&["2021-01-01", "2021-01-07 06:13:20"]));
     }
 
     #[test]
-    fn test_parse_params_binary() {
-        let place_hodler = Type::ANY;
-
-        // Test VACHAR type.
-        let raw_params = vec!["A".into(), "B".into(), "C".into()];
-        let type_description = vec![TypeOid::Varchar; 3];
-        let params = PreparedStatement::parse_params(&type_description, &raw_params, true).unwrap();
-        assert_eq!(params, vec!["'A'", "'B'", "'C'"]);
-
-        // Test BOOLEAN type.
-        let mut raw_params = vec![BytesMut::new(); 2];
-        false.to_sql(&place_hodler, &mut raw_params[0]).unwrap();
-        true.to_sql(&place_hodler, &mut raw_params[1]).unwrap();
-        let raw_params = raw_params
-            .into_iter()
-            .map(|b| b.freeze())
-            .collect::<Vec<_>>();
-        let type_description = vec![TypeOid::Boolean; 2];
-        let params = PreparedStatement::parse_params(&type_description, &raw_params, true).unwrap();
-        assert_eq!(params, vec!["false", "true"]);
+    fn test_decode_params_binary() {
+        // Test VACHAR, BOOLEAN type.
+        let mut raw_params = vec![Some("A".into())];
+        raw_params.extend(to_binary_params(&[&false, &true]));
+        let type_description = vec![TypeOid::Varchar, TypeOid::Boolean, TypeOid::Boolean];
+        let params = decode_params(&type_description, &raw_params, &[Format::Binary; 3]).unwrap();
+        assert_eq!(params, text(&["A", "false", "true"]));
 
         // Test SMALLINT, INT, BIGINT type.
-        let mut raw_params = vec![BytesMut::new(); 3];
-        1_i16.to_sql(&place_hodler, &mut raw_params[0]).unwrap();
-        2_i32.to_sql(&place_hodler, &mut raw_params[1]).unwrap();
-        3_i64.to_sql(&place_hodler, &mut raw_params[2]).unwrap();
-        let raw_params = raw_params
-            .into_iter()
-            .map(|b| b.freeze())
-            .collect::<Vec<_>>();
+        let raw_params = to_binary_params(&[&1_i16, &2_i32, &3_i64]);
         let type_description = vec![TypeOid::SmallInt, TypeOid::Int, TypeOid::BigInt];
-        let params = PreparedStatement::parse_params(&type_description, &raw_params, true).unwrap();
-        assert_eq!(params, vec!["1::SMALLINT", "2::INT", "3::BIGINT"]);
+        let params = decode_params(&type_description, &raw_params, &[Format::Binary; 3]).unwrap();
+        assert_eq!(params, text(&["1", "2", "3"]));
 
         // Test FLOAT4, FLOAT8, DECIMAL type.
-        let mut raw_params = vec![BytesMut::new(); 3];
-        1.0_f32.to_sql(&place_hodler, &mut raw_params[0]).unwrap();
-        2.0_f64.to_sql(&place_hodler, &mut raw_params[1]).unwrap();
-        rust_decimal::Decimal::from_f32_retain(3.0_f32)
-            .unwrap()
-            .to_sql(&place_hodler, &mut raw_params[2])
-            .unwrap();
-        let raw_params = raw_params
-            .into_iter()
-            .map(|b| b.freeze())
-            .collect::<Vec<_>>();
+        let raw_params = to_binary_params(&[
+            &1.0_f32,
+            &f64::NEG_INFINITY,
+            &rust_decimal::Decimal::from_f32_retain(3.0_f32).unwrap(),
+        ]);
         let type_description = vec![TypeOid::Float4, TypeOid::Float8, TypeOid::Decimal];
-        let params = PreparedStatement::parse_params(&type_description, &raw_params, true).unwrap();
-        assert_eq!(params, vec!["'1'::FLOAT4", "'2'::FLOAT8", "'3'::DECIMAL"]);
-
-        let mut raw_params = vec![BytesMut::new(); 3];
-        f32::NAN.to_sql(&place_hodler, &mut raw_params[0]).unwrap();
-        f64::INFINITY
-            .to_sql(&place_hodler, &mut raw_params[1])
-            .unwrap();
-        f64::NEG_INFINITY
-            .to_sql(&place_hodler, &mut raw_params[2])
-            .unwrap();
-        let raw_params = raw_params
-            .into_iter()
-            .map(|b| b.freeze())
-            .collect::<Vec<_>>();
-        let type_description = vec![TypeOid::Float4, TypeOid::Float8, TypeOid::Float8];
-        let params = PreparedStatement::parse_params(&type_description, &raw_params, true).unwrap();
-        assert_eq!(
-            params,
-            vec!["'NaN'::FLOAT4", "'inf'::FLOAT8", "'-inf'::FLOAT8"]
-        );
+        let params = decode_params(&type_description, &raw_params, &[Format::Binary; 3]).unwrap();
+        assert_eq!(params, text(&["1", "-inf", "3"]));
 
         // Test DATE, TIME, TIMESTAMP type.
-        let mut raw_params = vec![BytesMut::new(); 3];
-        chrono::NaiveDate::from_ymd(2021, 1, 1)
-            .to_sql(&place_hodler, &mut raw_params[0])
-            .unwrap();
-        chrono::NaiveTime::from_hms(12, 0, 0)
-            .to_sql(&place_hodler, &mut raw_params[1])
-            .unwrap();
-        chrono::NaiveDateTime::from_timestamp(1610000000, 0)
-            .to_sql(&place_hodler, &mut raw_params[2])
-            .unwrap();
-        let raw_params = raw_params
-            .into_iter()
-            .map(|b| b.freeze())
-            .collect::<Vec<_>>();
+        let raw_params = to_binary_params(&[
+            &chrono::NaiveDate::from_ymd(2021, 1, 1),
+            &chrono::NaiveTime::from_hms(12, 0, 0),
+            &chrono::NaiveDateTime::from_timestamp(1610000000, 0),
+        ]);
         let type_description = vec![TypeOid::Date, TypeOid::Time, TypeOid::Timestamp];
-        let params = PreparedStatement::parse_params(&type_description, &raw_params, true).unwrap();
+        let params = decode_params(&type_description, &raw_params, &[Format::Binary; 3]).unwrap();
         assert_eq!(
             params,
-            vec![
-                "'2021-01-01'::DATE",
-                "'12:00:00'::TIME",
-                "'2021-01-07 06:13:20'::TIMESTAMP"
-            ]
+            text(&["2021-01-01", "12:00:00", "2021-01-07 06:13:20"])
         );
 
         // Test TIMESTAMPTZ, INTERVAL type.
-        let mut raw_params = vec![BytesMut::new(); 2];
-        DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(1200, 0), Utc)
-            .to_sql(&place_hodler, &mut raw_params[0])
-            .unwrap();
-        let interval = Interval::new(1, 1, 24000000);
-        ToSql::to_sql(&interval, &place_hodler, &mut raw_params[1]).unwrap();
-        let raw_params = raw_params
-            .into_iter()
-            .map(|b| b.freeze())
-            .collect::<Vec<_>>();
+        let raw_params = to_binary_params(&[
+            &DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(1200, 0), Utc),
+            &Interval::new(1, 1, 24000000),
+        ]);
         let type_description = vec![TypeOid::Timestamptz, TypeOid::Interval];
-        let params = PreparedStatement::parse_params(&type_description, &raw_params, true).unwrap();
+        let params = decode_params(&type_description, &raw_params, &[Format::Binary; 2]).unwrap();
         assert_eq!(
             params,
-            vec![
-                "'1970-01-01 00:20:00 UTC'::TIMESTAMPTZ",
-                "'1 mons 1 days 00:00:24'::INTERVAL"
-            ]
+            text(&["1970-01-01 00:20:00 UTC", "1 mons 1 days 00:00:24"])
         );
     }
+
+    #[test]
+    fn test_decode_params_mixed_format() {
+        let mut raw_params = vec![Some("1".into())];
+        raw_params.extend(to_binary_params(&[&2_i32]));
+        raw_params.push(None);
+        let type_description = vec![TypeOid::Int; 3];
+        let formats = Format::batch_from_i16(&[0, 1, 1], 3).unwrap();
+        let params = decode_params(&type_description, &raw_params, &formats).unwrap();
+        assert_eq!(
+            params,
+            vec![Some("1".to_string()), Some("2".to_string()), None]
+        );
+
+        assert!(Format::batch_from_i16(&[0, 1], 3).is_err());
+    }
 }
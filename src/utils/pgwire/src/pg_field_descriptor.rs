@@ -16,6 +16,8 @@ use std::str::FromStr;
 
 use thiserror::Error;
 
+use crate::types::Format;
+
 #[derive(Debug, Clone)]
 pub struct PgFieldDescriptor {
     name: String,
@@ -47,7 +49,7 @@ impl PgFieldDescriptor {
             | TypeOid::Time
             | TypeOid::Timestamptz => 8,
             TypeOid::SmallInt => 2,
-            TypeOid::Varchar | TypeOid::Decimal | TypeOid::Interval => -1,
+            TypeOid::Varchar | TypeOid::Decimal | TypeOid::Interval | TypeOid::Jsonb => -1,
         };
 
         Self {
@@ -88,6 +90,10 @@ impl PgFieldDescriptor {
     pub fn get_format_code(&self) -> i16 {
         self.format_code
     }
+
+    pub fn set_format(&mut self, format: Format) {
+        self.format_code = format.as_i16();
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -105,6 +111,7 @@ pub enum TypeOid {
     Timestamptz,
     Decimal,
     Interval,
+    Jsonb,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -116,7 +123,8 @@ impl TypeOid {
     // https://github.com/postgres/postgres/blob/master/src/include/catalog/pg_type.dat
     pub fn as_type(oid: i32) -> Result<TypeOid, TypeOidError> {
         match oid {
-            1043 => Ok(TypeOid::Varchar),
+            // `text` is treated as `varchar`.
+            25 | 1043 => Ok(TypeOid::Varchar),
             16 => Ok(TypeOid::Boolean),
             20 => Ok(TypeOid::BigInt),
             21 => Ok(TypeOid::SmallInt),
@@ -129,6 +137,7 @@ impl TypeOid {
             1184 => Ok(TypeOid::Timestamptz),
             1700 => Ok(TypeOid::Decimal),
             1186 => Ok(TypeOid::Interval),
+            3802 => Ok(TypeOid::Jsonb),
             v => Err(TypeOidError(v)),
         }
     }
@@ -154,6 +163,7 @@ impl TypeOid {
             TypeOid::Timestamptz => 1184,
             TypeOid::Decimal => 1700,
             TypeOid::Interval => 1186,
+            TypeOid::Jsonb => 3802,
         }
     }
 }
@@ -177,6 +187,7 @@ impl FromStr for TypeOid {
             "timestamptz" => Ok(TypeOid::Timestamptz),
             "decimal" | "numeric" => Ok(TypeOid::Decimal),
             "interval" => Ok(TypeOid::Interval),
+            "jsonb" => Ok(TypeOid::Jsonb),
             _ => Err(TypeOidError(0)),
         }
     }
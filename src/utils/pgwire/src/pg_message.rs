@@ -42,6 +42,9 @@ pub enum FeMessage {
     CancelQuery(FeCancelMessage),
     Terminate,
     Flush,
+    CopyData(FeCopyDataMessage),
+    CopyDone,
+    CopyFail(FeCopyFailMessage),
 }
 
 pub struct FeStartupMessage {
@@ -79,17 +82,15 @@ pub struct FeQueryMessage {
 
 #[derive(Debug)]
 pub struct FeBindMessage {
-    // param_format_code:
-    //  false: text
-    //  true: binary
-    pub param_format_code: bool,
-
-    // result_format_code:
-    //  false: text
-    //  true: binary
-    pub result_format_code: bool,
-
-    pub params: Vec<Bytes>,
+    // The format codes of the params and the result columns. Each of them can have no code (all in
+    // text), a single code applied to all, or one code for each. See `Format::batch_from_i16`.
+    //  0: text
+    //  1: binary
+    pub param_format_codes: Vec<i16>,
+    pub result_format_codes: Vec<i16>,
+
+    // The values of the params, where `None` is NULL.
+    pub params: Vec<Option<Bytes>>,
     pub portal_name: Bytes,
     pub statement_name: Bytes,
 }
@@ -107,9 +108,11 @@ pub struct FeParseMessage {
     pub type_ids: Vec<i32>,
 }
 
+/// `PasswordMessage`, `SASLInitialResponse` or `SASLResponse`, which share the same type byte and
+/// are told apart by the authentication method.
 #[derive(Debug)]
 pub struct FePasswordMessage {
-    pub password: Bytes,
+    pub body: Bytes,
 }
 
 #[derive(Debug)]
@@ -130,6 +133,25 @@ pub struct FeCancelMessage {
     pub target_secret_key: i32,
 }
 
+/// The data of `COPY ... FROM STDIN`, which may be any part of the data stream.
+#[derive(Debug)]
+pub struct FeCopyDataMessage {
+    pub data: Bytes,
+}
+
+/// Aborts `COPY ... FROM STDIN` with the error message of the client.
+#[derive(Debug)]
+pub struct FeCopyFailMessage {
+    pub message: Bytes,
+}
+
+impl FeCopyFailMessage {
+    pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
+        let message = read_null_terminated(&mut buf)?;
+        Ok(FeMessage::CopyFail(FeCopyFailMessage { message }))
+    }
+}
+
 impl FeCancelMessage {
     pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
         let target_process_id = buf.get_i32();
@@ -173,43 +195,27 @@ impl FeBindMessage {
         let statement_name = read_null_terminated(&mut buf)?;
         // Read FormatCode
         let len = buf.get_i16();
-
-        let param_format_code = if len == 0 || len == 1 {
-            if len == 0 {
-                false
-            } else {
-                buf.get_i16() == 1
-            }
-        } else {
-            let first_value = buf.get_i16();
-            for _ in 1..len {
-                assert!(buf.get_i16() == first_value,"Only support uniform param format (TEXT or BINARY), can't support mix format now.");
-            }
-            first_value == 1
-        };
+        let param_format_codes = (0..len).map(|_| buf.get_i16()).collect();
         // Read Params
         let len = buf.get_i16();
         let params = (0..len)
             .map(|_| {
                 let val_len = buf.get_i32();
-                buf.copy_to_bytes(val_len as usize)
+                // A length of -1 indicates a NULL param.
+                if val_len == -1 {
+                    None
+                } else {
+                    Some(buf.copy_to_bytes(val_len as usize))
+                }
             })
             .collect();
         // Read ResultFormatCode
         let len = buf.get_i16();
-
-        assert!(len==0||len==1,"Only support default result format(len==0) or uniform result format(len==1), can't support mix format now.");
-
-        let result_format_code = if len == 0 {
-            // default format:text
-            false
-        } else {
-            buf.get_i16() == 1
-        };
+        let result_format_codes = (0..len).map(|_| buf.get_i16()).collect();
 
         Ok(FeMessage::Bind(FeBindMessage {
-            param_format_code,
-            result_format_code,
+            param_format_codes,
+            result_format_codes,
             params,
             portal_name,
             statement_name,
@@ -246,10 +252,43 @@ impl FeParseMessage {
 }
 
 impl FePasswordMessage {
-    pub fn parse(mut buf: Bytes) -> Result<FeMessage> {
-        let password = read_null_terminated(&mut buf)?;
+    pub fn parse(buf: Bytes) -> Result<FeMessage> {
+        Ok(FeMessage::Password(FePasswordMessage { body: buf }))
+    }
+
+    /// Reads the password of a `PasswordMessage`.
+    pub fn password(&self) -> Result<Bytes> {
+        read_null_terminated(&mut self.body.clone())
+    }
+
+    /// Reads the mechanism and the initial response of a `SASLInitialResponse`.
+    pub fn sasl_initial_response(&self) -> Result<(Bytes, Bytes)> {
+        let mut buf = self.body.clone();
+        let mechanism = read_null_terminated(&mut buf)?;
+        if buf.remaining() < 4 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "malformed SASLInitialResponse",
+            ));
+        }
+        // -1 if there is no initial response.
+        let len = buf.get_i32();
+        let data = if len < 0 {
+            Bytes::new()
+        } else if buf.remaining() >= len as usize {
+            buf.split_to(len as usize)
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "malformed SASLInitialResponse",
+            ));
+        };
+        Ok((mechanism, data))
+    }
 
-        Ok(FeMessage::Password(FePasswordMessage { password }))
+    /// Reads the data of a `SASLResponse`.
+    pub fn sasl_response(&self) -> Bytes {
+        self.body.clone()
     }
 }
 
@@ -301,6 +340,9 @@ impl FeMessage {
             b'C' => FeCloseMessage::parse(sql_bytes),
             b'p' => FePasswordMessage::parse(sql_bytes),
             b'H' => Ok(FeMessage::Flush),
+            b'd' => Ok(FeMessage::CopyData(FeCopyDataMessage { data: sql_bytes })),
+            b'c' => Ok(FeMessage::CopyDone),
+            b'f' => FeCopyFailMessage::parse(sql_bytes),
             _ => Err(std::io::Error::new(
                 ErrorKind::InvalidInput,
                 format!("Unsupported tag of regular message: {}", val),
@@ -365,6 +407,9 @@ pub enum BeMessage<'a> {
     AuthenticationOk,
     AuthenticationCleartextPassword,
     AuthenticationMd5Password(&'a [u8; 4]),
+    AuthenticationSasl(&'a [&'a str]),
+    AuthenticationSaslContinue(&'a [u8]),
+    AuthenticationSaslFinal(&'a [u8]),
     CommandComplete(BeCommandCompleteMessage),
     NoticeResponse(&'a str),
     // Single byte - used in response to SSLRequest/GSSENCRequest.
@@ -382,6 +427,11 @@ pub enum BeMessage<'a> {
     RowDescription(&'a [PgFieldDescriptor]),
     ErrorResponse(BoxedError),
     CloseComplete,
+    // The number of columns, which are all in text format.
+    CopyInResponse(usize),
+    CopyOutResponse(usize),
+    CopyData(&'a [u8]),
+    CopyDone,
 
     // 0: process ID, 1: secret key
     BackendKeyData((i32, i32)),
@@ -438,6 +488,45 @@ impl<'a> BeMessage<'a> {
                 buf.put_slice(&salt[..]);
             }
 
+            // AuthenticationSASL
+            // +-----+-----------+-----------+----------------------+------+
+            // | 'R' | int32 len | int32(10) | str mechanism + '\0' | '\0' |
+            // +-----+-----------+-----------+----------------------+------+
+            //
+            // Each of the SASL mechanisms supported by the server is a null-terminated string.
+            BeMessage::AuthenticationSasl(mechanisms) => {
+                buf.put_u8(b'R');
+                let len = 8 + mechanisms.iter().map(|m| m.len() + 1).sum::<usize>() + 1;
+                buf.put_i32(len as i32);
+                buf.put_i32(10);
+                for mechanism in *mechanisms {
+                    write_cstr(buf, mechanism.as_bytes())?;
+                }
+                buf.put_u8(0);
+            }
+
+            // AuthenticationSASLContinue
+            // +-----+-----------+-----------+------------+
+            // | 'R' | int32 len | int32(11) | Byten data |
+            // +-----+-----------+-----------+------------+
+            BeMessage::AuthenticationSaslContinue(data) => {
+                buf.put_u8(b'R');
+                buf.put_i32(8 + data.len() as i32);
+                buf.put_i32(11);
+                buf.put_slice(data);
+            }
+
+            // AuthenticationSASLFinal
+            // +-----+-----------+-----------+------------+
+            // | 'R' | int32 len | int32(12) | Byten data |
+            // +-----+-----------+-----------+------------+
+            BeMessage::AuthenticationSaslFinal(data) => {
+                buf.put_u8(b'R');
+                buf.put_i32(8 + data.len() as i32);
+                buf.put_i32(12);
+                buf.put_slice(data);
+            }
+
             // ParameterStatus
             // +-----+-----------+----------+------+-----------+------+
             // | 'S' | int32 len | str name | '\0' | str value | '\0' |
@@ -646,6 +735,50 @@ impl<'a> BeMessage<'a> {
                 write_err_or_notice(buf, &ErrorOrNoticeMessage::internal_error(&msg));
             }
 
+            // CopyInResponse / CopyOutResponse
+            // +-----------+-----------+-----------+--------------+------------------+-----+
+            // | 'G' / 'H' | int32 len | int8(0)   | int16 colNum | int16 formatCode | ... |
+            // +-----------+-----------+-----------+--------------+------------------+-----+
+            //
+            // Only the text format is supported, so the overall format and the format codes of
+            // the columns are all 0.
+            BeMessage::CopyInResponse(columns) | BeMessage::CopyOutResponse(columns) => {
+                let tag = match message {
+                    BeMessage::CopyInResponse(_) => b'G',
+                    _ => b'H',
+                };
+                buf.put_u8(tag);
+                write_body(buf, |buf| {
+                    buf.put_i8(0);
+                    buf.put_i16(*columns as i16);
+                    for _ in 0..*columns {
+                        buf.put_i16(0);
+                    }
+                    Ok(())
+                })?;
+            }
+
+            // CopyData
+            // +-----+-----------+------------+
+            // | 'd' | int32 len | Byten data |
+            // +-----+-----------+------------+
+            BeMessage::CopyData(data) => {
+                buf.put_u8(b'd');
+                write_body(buf, |buf| {
+                    buf.put_slice(data);
+                    Ok(())
+                })?;
+            }
+
+            // CopyDone
+            // +-----+----------+
+            // | 'c' | int32(4) |
+            // +-----+----------+
+            BeMessage::CopyDone => {
+                buf.put_u8(b'c');
+                buf.put_i32(4);
+            }
+
             BeMessage::BackendKeyData((process_id, secret_key)) => {
                 buf.put_u8(b'K');
                 write_body(buf, |buf| {
@@ -20,25 +20,31 @@ use std::str::Utf8Error;
 use std::sync::Arc;
 use std::{str, vec};
 
+use anyhow::anyhow;
 use bytes::{Bytes, BytesMut};
 use futures::stream::StreamExt;
 use futures::Stream;
 use openssl::ssl::{SslAcceptor, SslContext, SslContextRef, SslMethod};
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
 use tokio_openssl::SslStream;
 use tracing::log::trace;
 use tracing::warn;
 
 use crate::error::{PsqlError, PsqlResult};
-use crate::pg_extended::{PgPortal, PgStatement, PreparedStatement};
-use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
+use crate::pg_extended::{PgPortal, PgStatement};
+use crate::pg_field_descriptor::TypeOid;
 use crate::pg_message::{
     BeCommandCompleteMessage, BeMessage, BeParameterStatusMessage, FeBindMessage, FeCancelMessage,
     FeCloseMessage, FeDescribeMessage, FeExecuteMessage, FeMessage, FeParseMessage,
     FePasswordMessage, FeStartupMessage,
 };
-use crate::pg_response::RowSetResult;
-use crate::pg_server::{Session, SessionManager, UserAuthenticator};
+use crate::pg_response::{CopyResponse, PgResponse, RowSetResult};
+use crate::pg_server::{CopyInData, Session, SessionManager, UserAuthenticator};
+use crate::scram::{ScramServer, SCRAM_SHA_256};
+
+/// The number of `CopyData` messages buffered for the session running `COPY ... FROM STDIN`.
+const COPY_IN_BUFFER_SIZE: usize = 16;
 
 /// The state machine for each psql connection.
 /// Read pg messages from tcp stream and write results back.
@@ -65,6 +71,11 @@ where
     // Used for ssl connection.
     // If None, not expected to build ssl connection (panic).
     tls_context: Option<SslContext>,
+    // Whether to reject the startup of an unencrypted connection.
+    tls_required: bool,
+
+    // The SCRAM exchange in progress, if the user is authenticated by SCRAM-SHA-256.
+    scram_server: Option<ScramServer>,
 }
 
 /// Configures TLS encryption for connections.
@@ -74,9 +85,19 @@ pub struct TlsConfig {
     pub cert: PathBuf,
     /// The path to the TLS key.
     pub key: PathBuf,
+    /// Whether to reject the connections without TLS, like `hostssl` in `pg_hba.conf`.
+    pub require: bool,
 }
 
 impl TlsConfig {
+    pub fn new(cert: impl Into<PathBuf>, key: impl Into<PathBuf>, require: bool) -> Self {
+        Self {
+            cert: cert.into(),
+            key: key.into(),
+            require,
+        }
+    }
+
     pub fn new_default() -> Self {
         let cert = PathBuf::new().join("tests/ssl/demo.crt");
         let key = PathBuf::new().join("tests/ssl/demo.key");
@@ -88,6 +109,7 @@ impl TlsConfig {
             // The path is mounted from project root.
             cert: path_to_cur_proj.join(cert),
             key: path_to_cur_proj.join(key),
+            require: false,
         }
     }
 }
@@ -146,12 +168,36 @@ where
             tls_context: tls_config
                 .as_ref()
                 .and_then(|e| build_ssl_ctx_from_config(e).ok()),
+            tls_required: tls_config.as_ref().map_or(false, |e| e.require),
+            scram_server: None,
         }
     }
 
     /// Processes one message. Returns true if the connection is terminated.
     pub async fn process(&mut self) -> bool {
-        self.do_process().await || self.is_terminate
+        let session = self.session.clone();
+        let killed = async move {
+            match session {
+                Some(session) => session.wait_killed().await,
+                None => futures::future::pending().await,
+            }
+        };
+        tokio::select! {
+            terminated = self.do_process() => terminated || self.is_terminate,
+            _ = killed => {
+                // The session is killed by `KILL`. Notify the client before closing the
+                // connection.
+                self.stream
+                    .write_no_flush(&BeMessage::ErrorResponse(Box::new(PsqlError::Internal(
+                        anyhow!("terminating connection due to administrator command"),
+                    ))))
+                    .unwrap();
+                self.stream.flush().await.unwrap_or_else(|e| {
+                    tracing::error!("flush error: {}", e);
+                });
+                true
+            }
+        }
     }
 
     async fn do_process(&mut self) -> bool {
@@ -216,6 +262,8 @@ where
             FeMessage::Sync => self.stream.write_no_flush(&BeMessage::ReadyForQuery)?,
             FeMessage::Close(m) => self.process_close_msg(m)?,
             FeMessage::Flush => self.stream.flush().await?,
+            // The rest of the data is discarded if the copy has failed.
+            FeMessage::CopyData(_) | FeMessage::CopyDone | FeMessage::CopyFail(_) => {}
         }
         self.stream.flush().await?;
         Ok(false)
@@ -244,6 +292,11 @@ where
     }
 
     fn process_startup_msg(&mut self, msg: FeStartupMessage) -> PsqlResult<()> {
+        if self.tls_required && !matches!(self.stream, Conn::Ssl(_)) {
+            return Err(PsqlError::SslError(
+                "SSL connection is required by the server".to_string(),
+            ));
+        }
         let db_name = msg
             .config
             .get("database")
@@ -280,6 +333,11 @@ where
                 self.stream
                     .write_no_flush(&BeMessage::AuthenticationMd5Password(salt))?;
             }
+            UserAuthenticator::ScramSha256(secret) => {
+                self.scram_server = Some(ScramServer::new(secret.clone()));
+                self.stream
+                    .write_no_flush(&BeMessage::AuthenticationSasl(&[SCRAM_SHA_256]))?;
+            }
         }
         self.session = Some(session);
         self.state = PgProtocolState::Regular;
@@ -287,12 +345,35 @@ where
     }
 
     fn process_password_msg(&mut self, msg: FePasswordMessage) -> PsqlResult<()> {
-        let authenticator = self.session.as_ref().unwrap().user_authenticator();
-        if !authenticator.authenticate(&msg.password) {
-            return Err(PsqlError::PasswordError(IoError::new(
-                ErrorKind::InvalidInput,
-                "Invalid password",
-            )));
+        if let Some(scram_server) = &mut self.scram_server {
+            // SASLInitialResponse starts the exchange, and SASLResponse finishes it.
+            if !scram_server.started() {
+                let (mechanism, client_first) = msg.sasl_initial_response()?;
+                if mechanism != SCRAM_SHA_256.as_bytes() {
+                    return Err(PsqlError::PasswordError(IoError::new(
+                        ErrorKind::Unsupported,
+                        "Unsupported SASL mechanism",
+                    )));
+                }
+                let server_first = scram_server.server_first(&client_first)?;
+                self.stream
+                    .write_no_flush(&BeMessage::AuthenticationSaslContinue(
+                        server_first.as_bytes(),
+                    ))?;
+                return Ok(());
+            }
+            let server_final = scram_server.server_final(&msg.sasl_response())?;
+            self.scram_server = None;
+            self.stream
+                .write_no_flush(&BeMessage::AuthenticationSaslFinal(server_final.as_bytes()))?;
+        } else {
+            let authenticator = self.session.as_ref().unwrap().user_authenticator();
+            if !authenticator.authenticate(&msg.password()?) {
+                return Err(PsqlError::PasswordError(IoError::new(
+                    ErrorKind::InvalidInput,
+                    "Invalid password",
+                )));
+            }
         }
         self.stream.write_no_flush(&BeMessage::AuthenticationOk)?;
         self.stream.write_parameter_status_msg_no_flush()?;
@@ -301,10 +382,12 @@ where
         Ok(())
     }
 
+    /// The cancel request is sent on a new connection instead of the one running the query. The
+    /// connection is closed without any response, as Postgres does.
     fn process_cancel_msg(&mut self, m: FeCancelMessage) -> PsqlResult<()> {
         let session_id = (m.target_process_id, m.target_secret_key);
         self.session_mgr.cancel_queries_in_session(session_id);
-        self.stream.write_no_flush(&BeMessage::EmptyQueryResponse)?;
+        self.is_terminate = true;
         Ok(())
     }
 
@@ -324,7 +407,39 @@ where
                 .write_no_flush(&BeMessage::NoticeResponse(&notice))?;
         }
 
-        if res.is_query() {
+        if let Some(CopyResponse::In { columns }) = res.get_copy_response() {
+            let res = self.process_copy_in(sql, columns).await?;
+            self.stream
+                .write_no_flush(&BeMessage::CommandComplete(BeCommandCompleteMessage {
+                    stmt_type: res.get_stmt_type(),
+                    rows_cnt: res
+                        .get_effected_rows_cnt()
+                        .expect("row count should be set"),
+                }))?;
+        } else if let Some(CopyResponse::Out { columns }) = res.get_copy_response() {
+            self.stream
+                .write_no_flush(&BeMessage::CopyOutResponse(columns))?;
+
+            let mut rows_cnt = 0;
+
+            while let Some(row_set) = res.values_stream().next().await {
+                let row_set = row_set.map_err(|err| PsqlError::QueryError(err))?;
+                for row in row_set {
+                    // Each row has a single value, which is a line of the data.
+                    if let Some(line) = &row.values()[0] {
+                        self.stream.write_no_flush(&BeMessage::CopyData(line))?;
+                    }
+                    rows_cnt += 1;
+                }
+            }
+
+            self.stream.write_no_flush(&BeMessage::CopyDone)?;
+            self.stream
+                .write_no_flush(&BeMessage::CommandComplete(BeCommandCompleteMessage {
+                    stmt_type: res.get_stmt_type(),
+                    rows_cnt,
+                }))?;
+        } else if res.is_query() {
             self.stream
                 .write_no_flush(&BeMessage::RowDescription(&res.get_row_desc()))?;
 
@@ -357,6 +472,52 @@ where
         Ok(())
     }
 
+    /// Receives the data of `COPY ... FROM STDIN` from the client, and runs the copy with them in
+    /// the session at the same time.
+    async fn process_copy_in(&mut self, sql: &str, columns: usize) -> PsqlResult<PgResponse<VS>> {
+        self.stream
+            .write_no_flush(&BeMessage::CopyInResponse(columns))?;
+        self.stream.flush().await?;
+
+        let session = self.session.clone().unwrap();
+        let (tx, rx) = mpsc::channel(COPY_IN_BUFFER_SIZE);
+        let stream = &mut self.stream;
+        let receive = async move {
+            // The messages are read until the end of the copy even if the session has failed, so
+            // that the rest of the data is not taken as other messages.
+            loop {
+                let data = match stream.read().await {
+                    Ok(FeMessage::CopyData(msg)) => msg.data,
+                    Ok(FeMessage::CopyDone) => return Ok(()),
+                    Ok(FeMessage::CopyFail(msg)) => {
+                        let message = format!(
+                            "COPY from stdin failed: {}",
+                            String::from_utf8_lossy(&msg.message)
+                        );
+                        let _ = tx.send(Err(message.into())).await;
+                        return Ok(());
+                    }
+                    // They are allowed but ignored during the copy.
+                    Ok(FeMessage::Flush | FeMessage::Sync) => continue,
+                    Ok(_) => {
+                        let message = "unexpected message during COPY from stdin";
+                        let _ = tx.send(Err(message.into())).await;
+                        return Err(PsqlError::QueryError(message.into()));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(e.to_string().into())).await;
+                        return Err(e.into());
+                    }
+                };
+                // The session may have stopped receiving the data on error.
+                let _ = tx.send(Ok(data)).await;
+            }
+        };
+        let (received, res) = tokio::join!(receive, session.run_copy_in(sql, CopyInData::new(rx)));
+        received?;
+        res.map_err(PsqlError::QueryError)
+    }
+
     fn process_terminate(&mut self) {
         self.is_terminate = true;
     }
@@ -364,44 +525,30 @@ where
     async fn process_parse_msg(&mut self, msg: FeParseMessage) -> PsqlResult<()> {
         let sql = cstr_to_str(&msg.sql_bytes).unwrap();
         tracing::trace!("(extended query)parse query: {}", sql);
-        // Create the types description.
+        // 1. Create the types description. An unspecified type (oid 0) is inferred by the
+        // session.
         let types = msg
             .type_ids
             .iter()
-            .map(|x| TypeOid::as_type(*x).map_err(|e| PsqlError::ParseError(Box::new(e))))
-            .collect::<PsqlResult<Vec<TypeOid>>>()?;
-
-        // Flag indicate whether statement is a query statement.
-        let is_query_sql = {
-            let lower_sql = sql.to_ascii_lowercase();
-            lower_sql.starts_with("select")
-                || lower_sql.starts_with("values")
-                || lower_sql.starts_with("show")
-                || lower_sql.starts_with("with")
-                || lower_sql.starts_with("describe")
-        };
-
-        let prepared_statement = PreparedStatement::parse_statement(sql.to_string(), types)?;
-
-        // 2. Create the row description.
-        let fields: Vec<PgFieldDescriptor> = if is_query_sql {
-            let sql = prepared_statement.instance_default()?;
-
-            let session = self.session.clone().unwrap();
-            session
-                .infer_return_type(&sql)
-                .await
-                .map_err(PsqlError::ParseError)?
-        } else {
-            vec![]
-        };
+            .map(|x| match *x {
+                0 => Ok(None),
+                x => TypeOid::as_type(x)
+                    .map(Some)
+                    .map_err(|e| PsqlError::ParseError(Box::new(e))),
+            })
+            .collect::<PsqlResult<Vec<_>>>()?;
+
+        // 2. Parse the statement, inferring the types of the params and the row description.
+        let session = self.session.clone().unwrap();
+        let prepared_statement = session
+            .parse(sql, types)
+            .await
+            .map_err(PsqlError::ParseError)?;
 
         // 3. Create the statement.
         let statement = PgStatement::new(
             cstr_to_str(&msg.statement_name).unwrap().to_string(),
             prepared_statement,
-            fields,
-            is_query_sql,
         );
 
         // 4. Insert the statement.
@@ -437,8 +584,8 @@ where
         let portal = statement.instance(
             portal_name.clone(),
             &msg.params,
-            msg.result_format_code,
-            msg.param_format_code,
+            &msg.param_format_codes,
+            &msg.result_format_codes,
         )?;
 
         // 3. Insert the Portal.
@@ -699,7 +846,7 @@ where
     }
 }
 
-fn build_ssl_ctx_from_config(tls_config: &TlsConfig) -> PsqlResult<SslContext> {
+pub(crate) fn build_ssl_ctx_from_config(tls_config: &TlsConfig) -> PsqlResult<SslContext> {
     let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
 
     let key_path = &tls_config.key;
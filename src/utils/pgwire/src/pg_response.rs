@@ -44,6 +44,8 @@ pub enum StatementType {
     CREATE_SCHEMA,
     CREATE_USER,
     CREATE_INDEX,
+    CREATE_FUNCTION,
+    CREATE_SECRET,
     DESCRIBE_TABLE,
     GRANT_PRIVILEGE,
     DROP_TABLE,
@@ -55,6 +57,8 @@ pub enum StatementType {
     DROP_SCHEMA,
     DROP_DATABASE,
     DROP_USER,
+    DROP_FUNCTION,
+    DROP_SECRET,
     REVOKE_PRIVILEGE,
     // Introduce ORDER_BY statement type cuz Calcite unvalidated AST has SqlKind.ORDER_BY. Note
     // that Statement Type is not designed to be one to one mapping with SqlKind.
@@ -64,8 +68,16 @@ pub enum StatementType {
     SHOW_COMMAND,
     START_TRANSACTION,
     UPDATE_USER,
+    ALTER_TABLE,
+    ALTER_SOURCE,
+    ALTER_MATERIALIZED_VIEW,
+    ALTER_SYSTEM,
     ABORT,
     FLUSH,
+    ANALYZE,
+    CANCEL_COMMAND,
+    KILL,
+    COMMENT,
     OTHER,
     // EMPTY is used when query statement is empty (e.g. ";").
     EMPTY,
@@ -91,6 +103,18 @@ where
     notice: Option<String>,
     values_stream: Option<VS>,
     row_desc: Vec<PgFieldDescriptor>,
+    copy: Option<CopyResponse>,
+}
+
+/// The `COPY` sub-protocol started by a response of `COPY`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CopyResponse {
+    /// `COPY ... FROM STDIN` of the number of columns. The data are then received from the client
+    /// and handed to [`crate::pg_server::Session::run_copy_in`].
+    In { columns: usize },
+    /// `COPY ... TO STDOUT` of the number of columns. Each row of the values stream has a single
+    /// value, which is a line of the data.
+    Out { columns: usize },
 }
 
 impl<VS> std::fmt::Debug for PgResponse<VS>
@@ -103,6 +127,7 @@ where
             .field("row_cnt", &self.row_cnt)
             .field("notice", &self.notice)
             .field("row_desc", &self.row_desc)
+            .field("copy", &self.copy)
             .finish()
     }
 }
@@ -151,6 +176,7 @@ where
             values_stream: None,
             row_desc: vec![],
             notice: None,
+            copy: None,
         }
     }
 
@@ -162,6 +188,19 @@ where
             values_stream: None,
             row_desc: vec![],
             notice: Some(notice),
+            copy: None,
+        }
+    }
+
+    /// The result of a statement that affects a number of rows without returning them.
+    pub fn with_row_cnt(stmt_type: StatementType, row_cnt: i32) -> Self {
+        Self {
+            stmt_type,
+            row_cnt: Some(row_cnt),
+            values_stream: None,
+            row_desc: vec![],
+            notice: None,
+            copy: None,
         }
     }
 
@@ -177,6 +216,32 @@ where
             values_stream: Some(values_stream),
             row_desc,
             notice: None,
+            copy: None,
+        }
+    }
+
+    /// Starts `COPY ... FROM STDIN` of the number of columns.
+    pub fn copy_in(columns: usize) -> Self {
+        Self {
+            stmt_type: StatementType::COPY,
+            row_cnt: Some(0),
+            values_stream: None,
+            row_desc: vec![],
+            notice: None,
+            copy: Some(CopyResponse::In { columns }),
+        }
+    }
+
+    /// Starts `COPY ... TO STDOUT` of the number of columns, whose lines of data are the single
+    /// values of the rows in `values_stream`.
+    pub fn copy_out(values_stream: VS, columns: usize) -> Self {
+        Self {
+            stmt_type: StatementType::COPY,
+            row_cnt: None,
+            values_stream: Some(values_stream),
+            row_desc: vec![],
+            notice: None,
+            copy: Some(CopyResponse::Out { columns }),
         }
     }
 
@@ -192,18 +257,30 @@ where
         self.row_cnt
     }
 
+    /// Whether the response is a row set. DML statements with `RETURNING` output rows too, while a
+    /// `SELECT` without the values stream, e.g. of `CREATE TABLE AS`, only reports the row count.
     pub fn is_query(&self) -> bool {
-        self.stmt_type.is_query()
+        self.values_stream.is_some()
+            && (self.stmt_type.is_query() || (self.stmt_type.is_dml() && !self.row_desc.is_empty()))
     }
 
     pub fn is_empty(&self) -> bool {
         self.stmt_type == StatementType::EMPTY
     }
 
+    pub fn get_copy_response(&self) -> Option<CopyResponse> {
+        self.copy
+    }
+
     pub fn get_row_desc(&self) -> Vec<PgFieldDescriptor> {
         self.row_desc.clone()
     }
 
+    /// Takes the values stream out of the response, e.g. to output the rows in another form.
+    pub fn take_values_stream(&mut self) -> Option<VS> {
+        self.values_stream.take()
+    }
+
     pub fn values_stream(&mut self) -> Pin<&mut VS> {
         Pin::new(
             self.values_stream
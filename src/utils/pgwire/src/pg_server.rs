@@ -16,12 +16,17 @@ use std::io;
 use std::result::Result;
 use std::sync::Arc;
 
+use bytes::Bytes;
 use futures::Stream;
 use tokio::net::TcpListener;
+use tokio::sync::mpsc;
 
-use crate::pg_field_descriptor::PgFieldDescriptor;
-use crate::pg_protocol::{PgProtocol, TlsConfig};
+use crate::pg_extended::PreparedStatement;
+use crate::pg_field_descriptor::TypeOid;
+use crate::pg_protocol::{build_ssl_ctx_from_config, PgProtocol, TlsConfig};
 use crate::pg_response::{PgResponse, RowSetResult};
+use crate::scram::ScramSecret;
+use crate::types::Format;
 
 pub type BoxedError = Box<dyn std::error::Error + Send + Sync>;
 pub type SessionId = (i32, i32);
@@ -56,13 +61,52 @@ where
         sql: &str,
         format: bool,
     ) -> Result<PgResponse<VS>, BoxedError>;
-    async fn infer_return_type(
+    /// Parses a statement of the extended query protocol. The types of params that are not given
+    /// (`None`) are inferred from the statement.
+    async fn parse(
         self: Arc<Self>,
         sql: &str,
-    ) -> Result<Vec<PgFieldDescriptor>, BoxedError>;
+        param_types: Vec<Option<TypeOid>>,
+    ) -> Result<PreparedStatement, BoxedError>;
+    /// Runs a statement returned by [`Session::parse`] with the params in text format, where
+    /// `None` stands for NULL.
+    async fn run_prepared_statement(
+        self: Arc<Self>,
+        prepared_statement: &PreparedStatement,
+        params: Vec<Option<String>>,
+        result_formats: Vec<Format>,
+    ) -> Result<PgResponse<VS>, BoxedError>;
+    /// Runs `COPY ... FROM STDIN` of `sql`, after its response of [`Session::run_statement`]
+    /// has started receiving the data from the client.
+    async fn run_copy_in(
+        self: Arc<Self>,
+        sql: &str,
+        data: CopyInData,
+    ) -> Result<PgResponse<VS>, BoxedError>;
     fn user_authenticator(&self) -> &UserAuthenticator;
 
     fn id(&self) -> SessionId;
+
+    /// Resolves once the session is killed, after which the connection will be closed.
+    async fn wait_killed(&self);
+}
+
+/// The data of `COPY ... FROM STDIN`, received from the `CopyData` messages of the client while
+/// [`Session::run_copy_in`] is running.
+pub struct CopyInData {
+    rx: mpsc::Receiver<Result<Bytes, BoxedError>>,
+}
+
+impl CopyInData {
+    pub fn new(rx: mpsc::Receiver<Result<Bytes, BoxedError>>) -> Self {
+        Self { rx }
+    }
+
+    /// Returns the next part of the data, or `None` when all of it has been received. An error is
+    /// returned if the client fails the copy, or the connection is broken.
+    pub async fn next(&mut self) -> Option<Result<Bytes, BoxedError>> {
+        self.rx.recv().await
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -76,6 +120,8 @@ pub enum UserAuthenticator {
         encrypted_password: Vec<u8>,
         salt: [u8; 4],
     },
+    // verifier of the password, checked by a SCRAM-SHA-256 exchange.
+    ScramSha256(ScramSecret),
 }
 
 impl UserAuthenticator {
@@ -86,6 +132,8 @@ impl UserAuthenticator {
             UserAuthenticator::Md5WithSalt {
                 encrypted_password, ..
             } => encrypted_password == password,
+            // The password is never sent, see `ScramServer`.
+            UserAuthenticator::ScramSha256(_) => false,
         }
     }
 }
@@ -99,6 +147,23 @@ pub async fn pg_serve<VS>(
 where
     VS: Stream<Item = RowSetResult> + Unpin + Send,
 {
+    let ssl_config = match ssl_config {
+        Some(config) => match build_ssl_ctx_from_config(&config) {
+            Ok(_) => Some(config),
+            Err(e) if config.require => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("failed to set up TLS: {}", e),
+                ))
+            }
+            Err(e) => {
+                tracing::warn!("TLS is disabled since it failed to set up: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
     let listener = TcpListener::bind(addr).await.unwrap();
     // accept connections and process them, spawning a new thread for each one
     tracing::info!("Server Listening at {}", addr);
@@ -136,10 +201,14 @@ mod tests {
     use tokio_postgres::types::*;
     use tokio_postgres::NoTls;
 
+    use crate::pg_extended::PreparedStatement;
     use crate::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
+    use crate::pg_protocol::TlsConfig;
     use crate::pg_response::{PgResponse, RowSetResult, StatementType};
-    use crate::pg_server::{pg_serve, Session, SessionId, SessionManager, UserAuthenticator};
-    use crate::types::Row;
+    use crate::pg_server::{
+        pg_serve, CopyInData, Session, SessionId, SessionManager, UserAuthenticator,
+    };
+    use crate::types::{Format, Row};
 
     struct MockSessionManager {}
 
@@ -163,58 +232,100 @@ mod tests {
 
     struct MockSession {}
 
-    #[async_trait::async_trait]
-    impl Session<BoxStream<'static, RowSetResult>> for MockSession {
-        async fn run_statement(
-            self: Arc<Self>,
+    impl MockSession {
+        /// Splits a statement and trims \' around the input param to construct result. Params are
+        /// replaced by their values.
+        /// Ex:
+        ///    SELECT 'a','b' -> result: a , b
+        ///    SELECT $1 with ('a') -> result: a
+        fn mock_result(
             sql: &str,
-            _format: bool,
-        ) -> Result<PgResponse<BoxStream<'static, RowSetResult>>, Box<dyn Error + Send + Sync>>
-        {
-            // split a statement and trim \' around the input param to construct result.
-            // Ex:
-            //    SELECT 'a','b' -> result: a , b
+            params: &[Option<String>],
+        ) -> PgResponse<BoxStream<'static, RowSetResult>> {
             let res: Vec<Option<Bytes>> = sql
                 .split(&[' ', ',', ';'])
                 .skip(1)
-                .map(|x| {
-                    Some(
+                .map(|x| match x.strip_prefix('$') {
+                    Some(index) => params[index.parse::<usize>().unwrap() - 1]
+                        .clone()
+                        .map(Into::into),
+                    None => Some(
                         x.trim_start_matches('\'')
                             .trim_end_matches('\'')
                             .to_string()
                             .into(),
-                    )
+                    ),
                 })
                 .collect();
             let len = res.len();
 
-            Ok(PgResponse::new_for_stream(
+            PgResponse::new_for_stream(
                 StatementType::SELECT,
                 Some(1),
                 futures::stream::iter(vec![Ok(vec![Row::new(res)])]).boxed(),
                 // NOTE: Extended mode don't need.
                 vec![PgFieldDescriptor::new("".to_string(), TypeOid::Varchar); len],
-            ))
+            )
         }
+    }
 
-        fn user_authenticator(&self) -> &UserAuthenticator {
-            &UserAuthenticator::None
+    #[async_trait::async_trait]
+    impl Session<BoxStream<'static, RowSetResult>> for MockSession {
+        async fn run_statement(
+            self: Arc<Self>,
+            sql: &str,
+            _format: bool,
+        ) -> Result<PgResponse<BoxStream<'static, RowSetResult>>, Box<dyn Error + Send + Sync>>
+        {
+            Ok(Self::mock_result(sql, &[]))
         }
 
-        async fn infer_return_type(
+        async fn run_copy_in(
+            self: Arc<Self>,
+            _sql: &str,
+            _data: CopyInData,
+        ) -> Result<PgResponse<BoxStream<'static, RowSetResult>>, super::BoxedError> {
+            Ok(PgResponse::empty_result(StatementType::COPY))
+        }
+
+        async fn parse(
             self: Arc<Self>,
             sql: &str,
-        ) -> Result<Vec<PgFieldDescriptor>, super::BoxedError> {
+            param_types: Vec<Option<TypeOid>>,
+        ) -> Result<PreparedStatement, super::BoxedError> {
             let count = sql.split(&[' ', ',', ';']).skip(1).count();
-            Ok(vec![
-                PgFieldDescriptor::new("".to_string(), TypeOid::Varchar,);
-                count
-            ])
+            // Params with unspecified types are inferred as `VARCHAR`.
+            let param_types = param_types
+                .into_iter()
+                .map(|t| t.unwrap_or(TypeOid::Varchar))
+                .collect();
+            Ok(PreparedStatement::new(
+                sql.to_string(),
+                param_types,
+                vec![PgFieldDescriptor::new("".to_string(), TypeOid::Varchar); count],
+            ))
+        }
+
+        async fn run_prepared_statement(
+            self: Arc<Self>,
+            prepared_statement: &PreparedStatement,
+            params: Vec<Option<String>>,
+            _result_formats: Vec<Format>,
+        ) -> Result<PgResponse<BoxStream<'static, RowSetResult>>, super::BoxedError> {
+            Ok(Self::mock_result(prepared_statement.sql(), &params))
+        }
+
+        fn user_authenticator(&self) -> &UserAuthenticator {
+            &UserAuthenticator::None
         }
 
         fn id(&self) -> SessionId {
             (0, 0)
         }
+
+        async fn wait_killed(&self) {
+            futures::future::pending().await
+        }
     }
 
     // test_psql_extended_mode_explicit_simple
@@ -360,4 +471,19 @@ mod tests {
             assert_eq!(value, "BB");
         }
     }
+
+    #[tokio::test]
+    async fn test_ssl_required() {
+        let session_mgr = Arc::new(MockSessionManager {});
+        let tls_config = TlsConfig::new("tests/ssl/demo.crt", "tests/ssl/demo.key", true);
+        tokio::spawn(
+            async move { pg_serve("127.0.0.1:10001", session_mgr, Some(tls_config)).await },
+        );
+        // wait for server to start
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+        // An unencrypted connection is rejected on startup.
+        let result = tokio_postgres::connect("host=localhost port=10001", NoTls).await;
+        assert!(result.is_err());
+    }
 }
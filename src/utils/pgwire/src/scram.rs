@@ -0,0 +1,312 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SCRAM-SHA-256 authentication, see RFC 5802 and RFC 7677. Channel binding is not supported.
+
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind};
+
+use openssl::base64::{decode_block, encode_block};
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+
+use crate::error::{PsqlError, PsqlResult};
+
+/// The name of the SASL mechanism.
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+
+const DEFAULT_ITERATIONS: u32 = 4096;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 18;
+const KEY_LEN: usize = 32;
+
+/// The verifier of a password, which is stored instead of the password. It is formatted the same
+/// as PostgreSQL: `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramSecret {
+    iterations: u32,
+    salt: Vec<u8>,
+    stored_key: Vec<u8>,
+    server_key: Vec<u8>,
+}
+
+impl ScramSecret {
+    /// Creates the verifier of a password with a random salt.
+    pub fn new(password: &str) -> Self {
+        let mut salt = vec![0; SALT_LEN];
+        openssl::rand::rand_bytes(&mut salt).unwrap();
+        Self::with_salt(password, salt, DEFAULT_ITERATIONS)
+    }
+
+    fn with_salt(password: &str, salt: Vec<u8>, iterations: u32) -> Self {
+        let mut salted_password = [0; KEY_LEN];
+        openssl::pkcs5::pbkdf2_hmac(
+            password.as_bytes(),
+            &salt,
+            iterations as usize,
+            MessageDigest::sha256(),
+            &mut salted_password,
+        )
+        .unwrap();
+        let client_key = hmac(&salted_password, b"Client Key");
+        Self {
+            iterations,
+            salt,
+            stored_key: openssl::sha::sha256(&client_key).to_vec(),
+            server_key: hmac(&salted_password, b"Server Key"),
+        }
+    }
+
+    /// Parses a verifier. Returns `None` if it is malformed.
+    pub fn parse(secret: &str) -> Option<Self> {
+        let secret = secret.strip_prefix(SCRAM_SHA_256)?.strip_prefix('$')?;
+        let (params, keys) = secret.split_once('$')?;
+        let (iterations, salt) = params.split_once(':')?;
+        let (stored_key, server_key) = keys.split_once(':')?;
+        let secret = Self {
+            iterations: iterations.parse().ok()?,
+            salt: decode_block(salt).ok()?,
+            stored_key: decode_block(stored_key).ok()?,
+            server_key: decode_block(server_key).ok()?,
+        };
+        (secret.iterations > 0
+            && secret.stored_key.len() == KEY_LEN
+            && secret.server_key.len() == KEY_LEN)
+            .then_some(secret)
+    }
+}
+
+impl fmt::Display for ScramSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}${}:{}${}:{}",
+            SCRAM_SHA_256,
+            self.iterations,
+            encode_block(&self.salt),
+            encode_block(&self.stored_key),
+            encode_block(&self.server_key)
+        )
+    }
+}
+
+/// The server side of a SCRAM-SHA-256 exchange:
+///
+/// 1. The client sends `client-first-message` in `SASLInitialResponse`, and the server replies
+///    `server-first-message` with the salt in `AuthenticationSASLContinue`.
+/// 2. The client sends `client-final-message` with the proof of the password in `SASLResponse`,
+///    and the server verifies the proof and replies `server-final-message` with its own proof in
+///    `AuthenticationSASLFinal`.
+pub struct ScramServer {
+    secret: ScramSecret,
+    state: ScramState,
+}
+
+enum ScramState {
+    Initial,
+    /// The first messages are part of the message signed by both sides.
+    FirstSent {
+        gs2_header: String,
+        client_first_bare: String,
+        server_first: String,
+        nonce: String,
+    },
+    Finished,
+}
+
+impl ScramServer {
+    pub fn new(secret: ScramSecret) -> Self {
+        Self {
+            secret,
+            state: ScramState::Initial,
+        }
+    }
+
+    /// Whether `client-first-message` has been handled.
+    pub fn started(&self) -> bool {
+        !matches!(self.state, ScramState::Initial)
+    }
+
+    /// Handles `client-first-message` and returns `server-first-message`.
+    pub fn server_first(&mut self, client_first: &[u8]) -> PsqlResult<String> {
+        let mut server_nonce = [0; NONCE_LEN];
+        openssl::rand::rand_bytes(&mut server_nonce).unwrap();
+        self.server_first_with_nonce(client_first, &encode_block(&server_nonce))
+    }
+
+    fn server_first_with_nonce(
+        &mut self,
+        client_first: &[u8],
+        server_nonce: &str,
+    ) -> PsqlResult<String> {
+        if !matches!(self.state, ScramState::Initial) {
+            return Err(malformed());
+        }
+        let client_first = std::str::from_utf8(client_first).map_err(|_| malformed())?;
+        // The GS2 header is `n,,` or `y,,` without channel binding. An authorization identity is
+        // not supported, since the user is given in the startup message.
+        match client_first.as_bytes() {
+            [b'n' | b'y', b',', b',', ..] => {}
+            [b'p', ..] => {
+                return Err(PsqlError::PasswordError(IoError::new(
+                    ErrorKind::Unsupported,
+                    "SCRAM channel binding is not supported",
+                )))
+            }
+            _ => return Err(malformed()),
+        }
+        let (gs2_header, client_first_bare) = client_first.split_at(3);
+        // The user name in the message is ignored, which is empty for libpq.
+        let client_nonce = client_first_bare
+            .split(',')
+            .find_map(|attr| attr.strip_prefix("r="))
+            .ok_or_else(malformed)?;
+
+        let nonce = format!("{}{}", client_nonce, server_nonce);
+        let server_first = format!(
+            "r={},s={},i={}",
+            nonce,
+            encode_block(&self.secret.salt),
+            self.secret.iterations
+        );
+        self.state = ScramState::FirstSent {
+            gs2_header: gs2_header.to_string(),
+            client_first_bare: client_first_bare.to_string(),
+            server_first: server_first.clone(),
+            nonce,
+        };
+        Ok(server_first)
+    }
+
+    /// Handles `client-final-message` and returns `server-final-message` if the proof of the
+    /// password is correct.
+    pub fn server_final(&mut self, client_final: &[u8]) -> PsqlResult<String> {
+        let ScramState::FirstSent {
+            gs2_header,
+            client_first_bare,
+            server_first,
+            nonce,
+        } = std::mem::replace(&mut self.state, ScramState::Finished) else {
+            return Err(malformed());
+        };
+        let client_final = std::str::from_utf8(client_final).map_err(|_| malformed())?;
+        // The proof is always the last attribute.
+        let (client_final_without_proof, proof) =
+            client_final.rsplit_once(",p=").ok_or_else(malformed)?;
+        let mut channel_binding = None;
+        let mut client_nonce = None;
+        for attr in client_final_without_proof.split(',') {
+            if let Some(value) = attr.strip_prefix("c=") {
+                channel_binding = Some(value);
+            } else if let Some(value) = attr.strip_prefix("r=") {
+                client_nonce = Some(value);
+            }
+        }
+        if channel_binding != Some(encode_block(gs2_header.as_bytes()).as_str())
+            || client_nonce != Some(nonce.as_str())
+        {
+            return Err(malformed());
+        }
+        let proof = decode_block(proof).map_err(|_| malformed())?;
+        if proof.len() != KEY_LEN {
+            return Err(malformed());
+        }
+
+        let auth_message = format!(
+            "{},{},{}",
+            client_first_bare, server_first, client_final_without_proof
+        );
+        let client_signature = hmac(&self.secret.stored_key, auth_message.as_bytes());
+        let client_key: Vec<u8> = proof
+            .iter()
+            .zip(&client_signature)
+            .map(|(p, s)| p ^ s)
+            .collect();
+        if !openssl::memcmp::eq(&openssl::sha::sha256(&client_key), &self.secret.stored_key) {
+            return Err(PsqlError::PasswordError(IoError::new(
+                ErrorKind::InvalidInput,
+                "Invalid password",
+            )));
+        }
+
+        let server_signature = hmac(&self.secret.server_key, auth_message.as_bytes());
+        Ok(format!("v={}", encode_block(&server_signature)))
+    }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let key = PKey::hmac(key).unwrap();
+    let mut signer = Signer::new(MessageDigest::sha256(), &key).unwrap();
+    signer.update(data).unwrap();
+    signer.sign_to_vec().unwrap()
+}
+
+fn malformed() -> PsqlError {
+    PsqlError::PasswordError(IoError::new(
+        ErrorKind::InvalidData,
+        "malformed SCRAM message",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example exchange in RFC 7677.
+    #[test]
+    fn test_scram_exchange() {
+        let secret = ScramSecret::with_salt(
+            "pencil",
+            decode_block("W22ZaJ0SNY7soEsUEjb6gQ==").unwrap(),
+            4096,
+        );
+        assert_eq!(
+            ScramSecret::parse(&secret.to_string()),
+            Some(secret.clone())
+        );
+
+        let mut server = ScramServer::new(secret);
+        let server_first = server
+            .server_first_with_nonce(
+                b"n,,n=user,r=rOprNGfwEbeRWgbNEkqO",
+                "%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0",
+            )
+            .unwrap();
+        assert_eq!(
+            server_first,
+            "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096"
+        );
+        let client_final = b"c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+        assert_eq!(
+            server.server_final(client_final).unwrap(),
+            "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4="
+        );
+        // The exchange is finished.
+        assert!(server.server_final(client_final).is_err());
+
+        // The proof of a wrong password is rejected.
+        let mut server = ScramServer::new(ScramSecret::new("pencil"));
+        let server_first = server
+            .server_first(b"n,,n=,r=rOprNGfwEbeRWgbNEkqO")
+            .unwrap();
+        let nonce = server_first.split(',').next().unwrap();
+        let proof = encode_block(&[0; KEY_LEN]);
+        assert!(server
+            .server_final(format!("c=biws,{},p={}", nonce, proof).as_bytes())
+            .is_err());
+
+        assert!(ScramSecret::parse("md5abc").is_none());
+    }
+}
@@ -14,8 +14,11 @@
 
 use std::ops::Index;
 
+use anyhow::anyhow;
 use bytes::Bytes;
 
+use crate::error::{PsqlError, PsqlResult};
+
 /// A row of data returned from the database by a query.
 #[derive(Debug, Clone)]
 // NOTE: Since we only support simple query protocol, the values are represented as strings.
@@ -50,3 +53,48 @@ impl Index<usize> for Row {
         &self.0[index]
     }
 }
+
+/// The format of a parameter or a result column in the extended query protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Binary,
+}
+
+impl Format {
+    pub fn from_i16(format_code: i16) -> PsqlResult<Self> {
+        match format_code {
+            0 => Ok(Format::Text),
+            1 => Ok(Format::Binary),
+            _ => Err(PsqlError::Internal(anyhow!(
+                "Unknown format code: {}",
+                format_code
+            ))),
+        }
+    }
+
+    pub fn as_i16(&self) -> i16 {
+        match self {
+            Format::Text => 0,
+            Format::Binary => 1,
+        }
+    }
+
+    /// Expands the format codes of a Bind message to the formats of `len` values. There can be no
+    /// code (all in text), a single code for all the values, or exactly one code for each value.
+    pub fn batch_from_i16(format_codes: &[i16], len: usize) -> PsqlResult<Vec<Self>> {
+        match format_codes {
+            [] => Ok(vec![Format::Text; len]),
+            [format_code] => Ok(vec![Self::from_i16(*format_code)?; len]),
+            _ if format_codes.len() == len => format_codes
+                .iter()
+                .map(|format_code| Self::from_i16(*format_code))
+                .collect(),
+            _ => Err(PsqlError::Internal(anyhow!(
+                "Expected {} format codes, got {}",
+                len,
+                format_codes.len()
+            ))),
+        }
+    }
+}